@@ -14,6 +14,7 @@ Example:
 """
 
 from grafeo.grafeo import (
+    CycleError,
     GrafeoDB,
     Node,
     Edge,
@@ -23,6 +24,7 @@ from grafeo.grafeo import (
 )
 
 __all__ = [
+    "CycleError",
     "GrafeoDB",
     "Node",
     "Edge",
@@ -2,36 +2,48 @@
 //!
 //! Access via `db.algorithms` - all the classic algorithms are here:
 //! traversals, shortest paths, centrality measures, community detection,
-//! spanning trees, and network flow.
+//! spanning trees, and network flow. Algorithms only see committed data,
+//! so a write from a transaction that is still open never shows up.
 
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use parking_lot::RwLock;
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyDict, PyList};
 
 use grafeo_adapters::plugins::algorithms;
 use grafeo_common::types::NodeId;
+use grafeo_core::graph::lpg::LpgStore;
 use grafeo_engine::database::GrafeoDB;
 
-use crate::error::PyGrafeoError;
+use crate::error::{PyGrafeoError, cycle_error, weight_error};
 
 /// Run graph algorithms at Rust speed from Python.
 ///
 /// Get this via `db.algorithms`. All algorithms run directly on the Rust
 /// graph store - no copying to Python data structures. Results come back
 /// as Python dicts and lists.
+///
+/// Every call sees the committed graph as it is when the call starts.
+/// Writes from transactions that are still open are left out; while one is
+/// open, the call runs on a private snapshot of the committed data instead
+/// of the shared store.
 #[pyclass(name = "Algorithms")]
 pub struct PyAlgorithms {
     db: Arc<RwLock<GrafeoDB>>,
 }
 
 impl PyAlgorithms {
-    /// Creates a new algorithms interface for the given database.
+    /// Creates a new algorithms interface sharing the given database.
     pub fn new(db: Arc<RwLock<GrafeoDB>>) -> Self {
         Self { db }
     }
+
+    /// Returns the committed graph for one algorithm call.
+    fn committed_store(&self) -> Arc<LpgStore> {
+        self.db.read().committed_store()
+    }
 }
 
 #[pymethods]
@@ -48,8 +60,7 @@ impl PyAlgorithms {
     /// Returns:
     ///     List of node IDs in BFS order
     fn bfs(&self, start: u64) -> PyResult<Vec<u64>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::bfs(store, NodeId::new(start));
         Ok(result.into_iter().map(|n| n.0).collect())
     }
@@ -62,8 +73,7 @@ impl PyAlgorithms {
     /// Returns:
     ///     List of lists, where result[i] contains nodes at distance i
     fn bfs_layers(&self, start: u64) -> PyResult<Vec<Vec<u64>>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let layers = algorithms::bfs_layers(store, NodeId::new(start));
         Ok(layers
             .into_iter()
@@ -79,8 +89,7 @@ impl PyAlgorithms {
     /// Returns:
     ///     List of node IDs in post-order (finished order)
     fn dfs(&self, start: u64) -> PyResult<Vec<u64>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::dfs(store, NodeId::new(start));
         Ok(result.into_iter().map(|n| n.0).collect())
     }
@@ -90,8 +99,7 @@ impl PyAlgorithms {
     /// Returns:
     ///     List of all node IDs in DFS post-order
     fn dfs_all(&self) -> PyResult<Vec<u64>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::dfs_all(store);
         Ok(result.into_iter().map(|n| n.0).collect())
     }
@@ -105,16 +113,14 @@ impl PyAlgorithms {
     /// Returns:
     ///     Dict mapping node ID to component ID
     fn connected_components(&self) -> PyResult<HashMap<u64, u64>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::connected_components(store);
         Ok(result.into_iter().map(|(n, c)| (n.0, c)).collect())
     }
 
     /// Count the number of connected components.
     fn connected_component_count(&self) -> PyResult<usize> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         Ok(algorithms::connected_component_count(store))
     }
 
@@ -123,8 +129,7 @@ impl PyAlgorithms {
     /// Returns:
     ///     List of lists, each inner list is a strongly connected component
     fn strongly_connected_components(&self) -> PyResult<Vec<Vec<u64>>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::strongly_connected_components(store);
 
         // Group nodes by component ID
@@ -139,17 +144,20 @@ impl PyAlgorithms {
     /// Topological sort of the graph.
     ///
     /// Returns:
-    ///     List of node IDs in topological order, or None if graph has cycle
-    fn topological_sort(&self) -> PyResult<Option<Vec<u64>>> {
-        let db = self.db.read();
-        let store = db.store();
-        Ok(algorithms::topological_sort(store).map(|v| v.into_iter().map(|n| n.0).collect()))
+    ///     List of node IDs in topological order
+    ///
+    /// Raises:
+    ///     CycleError: If the graph has a cycle; `args[1]` lists its node IDs
+    fn topological_sort(&self) -> PyResult<Vec<u64>> {
+        let store = &self.committed_store();
+        algorithms::topological_sort(store)
+            .map(|v| v.into_iter().map(|n| n.0).collect())
+            .map_err(cycle_error)
     }
 
     /// Check if the graph is a DAG.
     fn is_dag(&self) -> PyResult<bool> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         Ok(algorithms::is_dag(store))
     }
 
@@ -167,6 +175,9 @@ impl PyAlgorithms {
     /// Returns:
     ///     If target is None: Dict mapping node ID to distance
     ///     If target is provided: Tuple of (distance, path) or None if unreachable
+    ///
+    /// Raises:
+    ///     ValueError: If an edge has a negative weight
     #[pyo3(signature = (source, target=None, weight=None))]
     fn dijkstra(
         &self,
@@ -175,8 +186,7 @@ impl PyAlgorithms {
         weight: Option<&str>,
         py: Python<'_>,
     ) -> PyResult<Py<PyAny>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
 
         if let Some(target_id) = target {
             match algorithms::dijkstra_path(
@@ -184,7 +194,9 @@ impl PyAlgorithms {
                 NodeId::new(source),
                 NodeId::new(target_id),
                 weight,
-            ) {
+            )
+            .map_err(weight_error)?
+            {
                 Some((dist, path)) => {
                     let path_list: Vec<u64> = path.into_iter().map(|n| n.0).collect();
                     Ok((dist, path_list).into_pyobject(py)?.into_any().unbind())
@@ -192,7 +204,8 @@ impl PyAlgorithms {
                 None => Ok(py.None()),
             }
         } else {
-            let result = algorithms::dijkstra(store, NodeId::new(source), weight);
+            let result =
+                algorithms::dijkstra(store, NodeId::new(source), weight).map_err(weight_error)?;
             let distances: HashMap<u64, f64> = result
                 .distances
                 .into_iter()
@@ -212,6 +225,9 @@ impl PyAlgorithms {
     ///
     /// Returns:
     ///     Tuple of (distance, path) or None if unreachable
+    ///
+    /// Raises:
+    ///     ValueError: If an edge has a negative weight
     #[pyo3(signature = (source, target, heuristic=None, weight=None))]
     fn astar(
         &self,
@@ -221,8 +237,7 @@ impl PyAlgorithms {
         weight: Option<&str>,
         py: Python<'_>,
     ) -> PyResult<Py<PyAny>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
 
         // Build heuristic function
         let h_map: HashMap<u64, f64> = if let Some(h) = heuristic {
@@ -245,7 +260,9 @@ impl PyAlgorithms {
             NodeId::new(target),
             weight,
             heuristic_fn,
-        ) {
+        )
+        .map_err(weight_error)?
+        {
             Some((dist, path)) => {
                 let path_list: Vec<u64> = path.into_iter().map(|n| n.0).collect();
                 Ok((dist, path_list).into_pyobject(py)?.into_any().unbind())
@@ -261,7 +278,8 @@ impl PyAlgorithms {
     ///     weight: Optional edge property name for weights
     ///
     /// Returns:
-    ///     Dict with 'distances', 'predecessors', and 'has_negative_cycle' keys
+    ///     Dict with 'distances', 'predecessors', 'has_negative_cycle', and
+    ///     'negative_cycle' (node IDs on one such cycle, or None) keys
     #[pyo3(signature = (source, weight=None))]
     fn bellman_ford(
         &self,
@@ -269,8 +287,7 @@ impl PyAlgorithms {
         weight: Option<&str>,
         py: Python<'_>,
     ) -> PyResult<Py<PyAny>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
 
         let result = algorithms::bellman_ford(store, NodeId::new(source), weight);
 
@@ -289,6 +306,10 @@ impl PyAlgorithms {
         dict.set_item("distances", distances.into_pyobject(py)?)?;
         dict.set_item("predecessors", predecessors.into_pyobject(py)?)?;
         dict.set_item("has_negative_cycle", result.has_negative_cycle)?;
+        let negative_cycle: Option<Vec<u64>> = result
+            .negative_cycle
+            .map(|cycle| cycle.into_iter().map(|n| n.0).collect());
+        dict.set_item("negative_cycle", negative_cycle)?;
 
         Ok(dict.into_any().unbind())
     }
@@ -302,8 +323,7 @@ impl PyAlgorithms {
     ///     Dict mapping (source, target) tuples to distances
     #[pyo3(signature = (weight=None))]
     fn floyd_warshall(&self, weight: Option<&str>, py: Python<'_>) -> PyResult<Py<PyAny>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
 
         let result = algorithms::floyd_warshall(store, weight);
 
@@ -334,8 +354,7 @@ impl PyAlgorithms {
     ///     Dict mapping node ID to centrality score
     #[pyo3(signature = (normalized=false))]
     fn degree_centrality(&self, normalized: bool, py: Python<'_>) -> PyResult<Py<PyAny>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
 
         if normalized {
             let result = algorithms::degree_centrality_normalized(store);
@@ -373,8 +392,7 @@ impl PyAlgorithms {
         max_iterations: usize,
         tolerance: f64,
     ) -> PyResult<HashMap<u64, f64>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::pagerank(store, damping, max_iterations, tolerance);
         Ok(result.into_iter().map(|(n, s)| (n.0, s)).collect())
     }
@@ -388,8 +406,7 @@ impl PyAlgorithms {
     ///     Dict mapping node ID to betweenness score
     #[pyo3(signature = (normalized=true))]
     fn betweenness_centrality(&self, normalized: bool) -> PyResult<HashMap<u64, f64>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::betweenness_centrality(store, normalized);
         Ok(result.into_iter().map(|(n, s)| (n.0, s)).collect())
     }
@@ -403,8 +420,7 @@ impl PyAlgorithms {
     ///     Dict mapping node ID to closeness score
     #[pyo3(signature = (wf_improved=false))]
     fn closeness_centrality(&self, wf_improved: bool) -> PyResult<HashMap<u64, f64>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::closeness_centrality(store, wf_improved);
         Ok(result.into_iter().map(|(n, s)| (n.0, s)).collect())
     }
@@ -422,8 +438,7 @@ impl PyAlgorithms {
     ///     Dict mapping node ID to community ID
     #[pyo3(signature = (max_iterations=100))]
     fn label_propagation(&self, max_iterations: usize) -> PyResult<HashMap<u64, u64>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::label_propagation(store, max_iterations);
         Ok(result.into_iter().map(|(n, c)| (n.0, c)).collect())
     }
@@ -434,13 +449,28 @@ impl PyAlgorithms {
     ///     resolution: Resolution parameter (default: 1.0)
     ///
     /// Returns:
-    ///     Dict with 'communities', 'modularity', and 'num_communities' keys
+    ///     Dict with 'communities', 'modularity', 'num_communities', and
+    ///     'levels' keys. 'levels' lists a dict with the same first three keys
+    ///     for each aggregation level, finest first.
     #[pyo3(signature = (resolution=1.0))]
     fn louvain(&self, resolution: f64, py: Python<'_>) -> PyResult<Py<PyAny>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::louvain(store, resolution);
 
+        let levels = PyList::empty(py);
+        for level in result.levels {
+            let communities: HashMap<u64, u64> = level
+                .communities
+                .into_iter()
+                .map(|(n, c)| (n.0, c))
+                .collect();
+            let level_dict = PyDict::new(py);
+            level_dict.set_item("communities", communities.into_pyobject(py)?)?;
+            level_dict.set_item("modularity", level.modularity)?;
+            level_dict.set_item("num_communities", level.num_communities)?;
+            levels.append(level_dict)?;
+        }
+
         let communities: HashMap<u64, u64> = result
             .communities
             .into_iter()
@@ -451,6 +481,7 @@ impl PyAlgorithms {
         dict.set_item("communities", communities.into_pyobject(py)?)?;
         dict.set_item("modularity", result.modularity)?;
         dict.set_item("num_communities", result.num_communities)?;
+        dict.set_item("levels", levels)?;
 
         Ok(dict.into_any().unbind())
     }
@@ -468,8 +499,7 @@ impl PyAlgorithms {
     ///     Dict with 'edges' (list of (src, dst, weight)) and 'total_weight'
     #[pyo3(signature = (weight=None))]
     fn kruskal(&self, weight: Option<&str>, py: Python<'_>) -> PyResult<Py<PyAny>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::kruskal(store, weight);
 
         let edges: Vec<(u64, u64, f64)> = result
@@ -493,6 +523,9 @@ impl PyAlgorithms {
     ///
     /// Returns:
     ///     Dict with 'edges' (list of (src, dst, weight)) and 'total_weight'
+    ///
+    /// Raises:
+    ///     ValueError: If an edge has a negative weight
     #[pyo3(signature = (weight=None, start=None))]
     fn prim(
         &self,
@@ -500,10 +533,9 @@ impl PyAlgorithms {
         start: Option<u64>,
         py: Python<'_>,
     ) -> PyResult<Py<PyAny>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let start_node = start.map(NodeId::new);
-        let result = algorithms::prim(store, weight, start_node);
+        let result = algorithms::prim(store, weight, start_node).map_err(weight_error)?;
 
         let edges: Vec<(u64, u64, f64)> = result
             .edges
@@ -531,6 +563,9 @@ impl PyAlgorithms {
     ///
     /// Returns:
     ///     Dict with 'max_flow' and 'flow_edges' (list of (src, dst, flow))
+    ///
+    /// Raises:
+    ///     ValueError: If an edge has a negative capacity
     #[pyo3(signature = (source, sink, capacity=None))]
     fn max_flow(
         &self,
@@ -539,10 +574,11 @@ impl PyAlgorithms {
         capacity: Option<&str>,
         py: Python<'_>,
     ) -> PyResult<Py<PyAny>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
 
-        match algorithms::max_flow(store, NodeId::new(source), NodeId::new(sink), capacity) {
+        match algorithms::max_flow(store, NodeId::new(source), NodeId::new(sink), capacity)
+            .map_err(weight_error)?
+        {
             Some(result) => {
                 let flow_edges: Vec<(u64, u64, f64)> = result
                     .flow_edges
@@ -572,6 +608,9 @@ impl PyAlgorithms {
     ///
     /// Returns:
     ///     Dict with 'max_flow', 'total_cost', and 'flow_edges'
+    ///
+    /// Raises:
+    ///     ValueError: If an edge has a negative capacity
     #[pyo3(signature = (source, sink, capacity=None, cost=None))]
     fn min_cost_max_flow(
         &self,
@@ -581,8 +620,7 @@ impl PyAlgorithms {
         cost: Option<&str>,
         py: Python<'_>,
     ) -> PyResult<Py<PyAny>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
 
         match algorithms::min_cost_max_flow(
             store,
@@ -590,7 +628,9 @@ impl PyAlgorithms {
             NodeId::new(sink),
             capacity,
             cost,
-        ) {
+        )
+        .map_err(weight_error)?
+        {
             Some(result) => {
                 let flow_edges: Vec<(u64, u64, f64, f64)> = result
                     .flow_edges
@@ -620,8 +660,7 @@ impl PyAlgorithms {
     /// Returns:
     ///     List of node IDs that are articulation points
     fn articulation_points(&self) -> PyResult<Vec<u64>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::articulation_points(store);
         Ok(result.into_iter().map(|n| n.0).collect())
     }
@@ -631,8 +670,7 @@ impl PyAlgorithms {
     /// Returns:
     ///     List of (source, target) tuples representing bridges
     fn bridges(&self) -> PyResult<Vec<(u64, u64)>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::bridges(store);
         Ok(result.into_iter().map(|(s, t)| (s.0, t.0)).collect())
     }
@@ -647,8 +685,7 @@ impl PyAlgorithms {
     ///     If k is provided: List of node IDs in the k-core
     #[pyo3(signature = (k=None))]
     fn kcore(&self, k: Option<usize>, py: Python<'_>) -> PyResult<Py<PyAny>> {
-        let db = self.db.read();
-        let store = db.store();
+        let store = &self.committed_store();
         let result = algorithms::kcore_decomposition(store);
 
         if let Some(k_val) = k {
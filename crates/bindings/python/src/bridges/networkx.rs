@@ -12,11 +12,11 @@ use parking_lot::RwLock;
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyTuple};
 
-use grafeo_common::types::NodeId;
+use grafeo_common::types::{NodeId, Value};
 use grafeo_core::graph::Direction;
 use grafeo_engine::database::GrafeoDB;
 
-use crate::error::PyGrafeoError;
+use crate::error::{PyGrafeoError, weight_error};
 
 /// Work with your Grafeo graph using NetworkX conventions.
 ///
@@ -156,15 +156,22 @@ impl PyNetworkXAdapter {
 
     /// Convert to a NetworkX graph object.
     ///
+    /// Node properties become node attributes, along with a `labels` list;
+    /// edge properties become edge attributes, along with the edge `type`.
+    /// A plain `DiGraph`/`Graph` keeps only one edge per node pair, so pass
+    /// `multigraph=True` to keep parallel edges (keyed by edge ID).
+    ///
     /// Requires networkx to be installed.
-    fn to_networkx(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+    #[pyo3(signature = (multigraph=false))]
+    fn to_networkx(&self, py: Python<'_>, multigraph: bool) -> PyResult<Py<PyAny>> {
         let nx = py.import("networkx")?;
 
         // Create appropriate graph type
-        let graph = if self.directed {
-            nx.call_method0("DiGraph")?
-        } else {
-            nx.call_method0("Graph")?
+        let graph = match (self.directed, multigraph) {
+            (true, false) => nx.call_method0("DiGraph")?,
+            (false, false) => nx.call_method0("Graph")?,
+            (true, true) => nx.call_method0("MultiDiGraph")?,
+            (false, true) => nx.call_method0("MultiGraph")?,
         };
 
         let db = self.db.read();
@@ -203,6 +210,9 @@ impl PyNetworkXAdapter {
                         attrs.set_item(key.as_str(), crate::types::PyValue::to_py(value, py))?;
                     }
 
+                    if multigraph {
+                        attrs.set_item("key", edge_id.0)?;
+                    }
                     graph.call_method("add_edge", (node_id.0, neighbor.0), Some(&attrs))?;
                 }
             }
@@ -213,13 +223,25 @@ impl PyNetworkXAdapter {
 
     /// Create a Grafeo database from a NetworkX graph.
     ///
+    /// Node and edge attributes become properties. Labels come from the
+    /// `label_attribute` node attribute (a string or list of strings), which
+    /// defaults to the `labels` attribute written by `to_networkx()`; nodes
+    /// without it are labeled `Node`. The `type` edge attribute sets the edge
+    /// type, defaulting to `EDGE`. Parallel edges of a multigraph are all
+    /// imported.
+    ///
     /// Args:
     ///     G: NetworkX graph object
+    ///     label_attribute: Node attribute holding the labels
     ///
     /// Returns:
     ///     New PyNetworkXAdapter wrapping the imported graph
+    ///
+    /// Raises:
+    ///     ValueError: If an attribute value can't be stored as a property
     #[staticmethod]
-    fn from_networkx(g: &Bound<'_, PyAny>, _py: Python<'_>) -> PyResult<Self> {
+    #[pyo3(signature = (g, label_attribute="labels"))]
+    fn from_networkx(g: &Bound<'_, PyAny>, label_attribute: &str) -> PyResult<Self> {
         use grafeo_engine::config::Config;
 
         // Create new in-memory database
@@ -230,45 +252,34 @@ impl PyNetworkXAdapter {
         // Check if directed
         let is_directed: bool = g.call_method0("is_directed")?.extract()?;
 
-        // Import nodes with data
+        // Import nodes with data, keyed by the (hashable) NetworkX node
         let nodes_data = g.call_method1("nodes", (true,))?; // nodes(data=True)
-        let mut node_map: HashMap<i64, NodeId> = HashMap::new();
+        let node_map = PyDict::new(g.py());
 
         for item in nodes_data.try_iter()? {
             let item = item?;
             let tuple: &Bound<'_, PyTuple> = item.cast()?;
-            let py_id: i64 = tuple.get_item(0)?.extract()?;
+            let py_node = tuple.get_item(0)?;
             let node_data = tuple.get_item(1)?;
-
-            let db_guard = db.read();
-
-            // Try to get labels from node data
-            let labels: Vec<String> = if let Ok(labels_attr) = node_data.get_item("labels") {
-                labels_attr
-                    .extract()
-                    .unwrap_or_else(|_| vec!["Node".to_string()])
-            } else {
-                vec!["Node".to_string()]
+            let node_data: &Bound<'_, PyDict> = node_data.cast()?;
+
+            let labels = match node_data.get_item(label_attribute)? {
+                Some(attr) => extract_labels(&attr).ok_or_else(|| {
+                    PyGrafeoError::Type(format!(
+                        "node {py_node}: label attribute '{label_attribute}' must be a string or list of strings"
+                    ))
+                })?,
+                None => vec!["Node".to_string()],
             };
 
+            let db_guard = db.read();
             let label_refs: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
             let grafeo_id = db_guard.create_node(&label_refs);
-            node_map.insert(py_id, grafeo_id);
-
-            // Import all properties except "labels"
-            if let Ok(dict) = node_data.cast::<pyo3::types::PyDict>() {
-                for (key, value) in dict.iter() {
-                    let key_str: String = match key.extract() {
-                        Ok(k) => k,
-                        Err(_) => continue,
-                    };
-                    if key_str == "labels" {
-                        continue; // Skip labels, already handled
-                    }
-                    if let Ok(val) = crate::types::PyValue::from_py(&value) {
-                        db_guard.set_node_property(grafeo_id, &key_str, val);
-                    }
-                }
+            node_map.set_item(&py_node, grafeo_id.0)?;
+
+            // Import all properties except the labels
+            for (key, value) in property_items(node_data, label_attribute, &py_node)? {
+                db_guard.set_node_property(grafeo_id, &key, value);
             }
         }
 
@@ -277,37 +288,31 @@ impl PyNetworkXAdapter {
         for item in edges_data.try_iter()? {
             let item = item?;
             let tuple: &Bound<'_, PyTuple> = item.cast()?;
-            let src: i64 = tuple.get_item(0)?.extract()?;
-            let dst: i64 = tuple.get_item(1)?.extract()?;
+            let src = tuple.get_item(0)?;
+            let dst = tuple.get_item(1)?;
             let edge_data = tuple.get_item(2)?;
+            let edge_data: &Bound<'_, PyDict> = edge_data.cast()?;
 
-            if let (Some(&src_id), Some(&dst_id)) = (node_map.get(&src), node_map.get(&dst)) {
-                let db_guard = db.read();
-
-                // Get edge type
-                let edge_type: String = if let Ok(t) = edge_data.get_item("type") {
-                    t.extract().unwrap_or_else(|_| "EDGE".to_string())
-                } else {
-                    "EDGE".to_string()
-                };
-
-                let edge_id = db_guard.create_edge(src_id, dst_id, &edge_type);
-
-                // Import all properties except "type"
-                if let Ok(dict) = edge_data.cast::<pyo3::types::PyDict>() {
-                    for (key, value) in dict.iter() {
-                        let key_str: String = match key.extract() {
-                            Ok(k) => k,
-                            Err(_) => continue,
-                        };
-                        if key_str == "type" {
-                            continue; // Skip type, already handled
-                        }
-                        if let Ok(val) = crate::types::PyValue::from_py(&value) {
-                            db_guard.set_edge_property(edge_id, &key_str, val);
-                        }
-                    }
-                }
+            let (Some(src_id), Some(dst_id)) = (node_map.get_item(&src)?, node_map.get_item(&dst)?)
+            else {
+                continue;
+            };
+            let src_id = NodeId::new(src_id.extract()?);
+            let dst_id = NodeId::new(dst_id.extract()?);
+
+            // Get edge type
+            let edge_type: String = match edge_data.get_item("type")? {
+                Some(t) => t.extract().unwrap_or_else(|_| "EDGE".to_string()),
+                None => "EDGE".to_string(),
+            };
+
+            let db_guard = db.read();
+            let edge_id = db_guard.create_edge(src_id, dst_id, &edge_type);
+
+            // Import all properties except "type"
+            let context = PyTuple::new(g.py(), [&src, &dst])?;
+            for (key, value) in property_items(edge_data, "type", context.as_any())? {
+                db_guard.set_edge_property(edge_id, &key, value);
             }
         }
 
@@ -391,7 +396,9 @@ impl PyNetworkXAdapter {
                 NodeId::new(source),
                 NodeId::new(target_id),
                 weight,
-            ) {
+            )
+            .map_err(weight_error)?
+            {
                 Some((_, path)) => {
                     let path_list: Vec<u64> = path.into_iter().map(|n| n.0).collect();
                     Ok(path_list.into_pyobject(py)?.into_any().unbind())
@@ -400,7 +407,8 @@ impl PyNetworkXAdapter {
             }
         } else {
             // Return paths to all reachable nodes
-            let result = algorithms::dijkstra(store, NodeId::new(source), weight);
+            let result =
+                algorithms::dijkstra(store, NodeId::new(source), weight).map_err(weight_error)?;
             let dict = PyDict::new(py);
 
             for (target_node, _) in &result.distances {
@@ -434,12 +442,15 @@ impl PyNetworkXAdapter {
                 NodeId::new(source),
                 NodeId::new(target_id),
                 weight,
-            ) {
+            )
+            .map_err(weight_error)?
+            {
                 Some((dist, _)) => Ok(dist.into_pyobject(py)?.into_any().unbind()),
                 None => Err(PyGrafeoError::InvalidArgument("No path found".into()).into()),
             }
         } else {
-            let result = algorithms::dijkstra(store, NodeId::new(source), weight);
+            let result =
+                algorithms::dijkstra(store, NodeId::new(source), weight).map_err(weight_error)?;
             let distances: HashMap<u64, f64> = result
                 .distances
                 .into_iter()
@@ -458,3 +469,37 @@ impl PyNetworkXAdapter {
         )
     }
 }
+
+/// Reads labels from a string or a list of strings.
+fn extract_labels(attr: &Bound<'_, PyAny>) -> Option<Vec<String>> {
+    if let Ok(label) = attr.extract::<String>() {
+        return Some(vec![label]);
+    }
+    attr.extract::<Vec<String>>().ok()
+}
+
+/// Converts NetworkX attributes to properties, skipping `reserved`.
+///
+/// `element` names the node or edge in error messages.
+fn property_items(
+    attrs: &Bound<'_, PyDict>,
+    reserved: &str,
+    element: &Bound<'_, PyAny>,
+) -> PyResult<Vec<(String, Value)>> {
+    let mut properties = Vec::with_capacity(attrs.len());
+    for (key, value) in attrs.iter() {
+        let key: String = key.extract().map_err(|_| {
+            PyGrafeoError::Type(format!("{element}: attribute names must be strings"))
+        })?;
+        if key == reserved {
+            continue;
+        }
+        let value = crate::types::PyValue::from_py(&value).map_err(|e| {
+            PyGrafeoError::Type(format!(
+                "{element}: attribute '{key}' can't be stored as a property: {e}"
+            ))
+        })?;
+        properties.push((key, value));
+    }
+    Ok(properties)
+}
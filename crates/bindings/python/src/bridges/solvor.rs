@@ -15,7 +15,7 @@ use pyo3::types::PyDict;
 use grafeo_common::types::NodeId;
 use grafeo_engine::database::GrafeoDB;
 
-use crate::error::PyGrafeoError;
+use crate::error::{PyGrafeoError, cycle_error, weight_error};
 
 /// Solve classic OR problems on your graph.
 ///
@@ -81,6 +81,7 @@ impl PySolvORAdapter {
         let result = match method {
             "dijkstra" => {
                 algorithms::dijkstra_path(store, NodeId::new(source), NodeId::new(target), weight)
+                    .map_err(weight_error)?
             }
             "bellman_ford" => {
                 let bf_result = algorithms::bellman_ford(store, NodeId::new(source), weight);
@@ -108,6 +109,7 @@ impl PySolvORAdapter {
                     weight,
                     |_| 0.0,
                 )
+                .map_err(weight_error)?
             }
             _ => {
                 return Err(PyGrafeoError::InvalidArgument(format!(
@@ -192,7 +194,9 @@ impl PySolvORAdapter {
         let db = self.db.read();
         let store = db.store();
 
-        match algorithms::max_flow(store, NodeId::new(source), NodeId::new(sink), capacity) {
+        match algorithms::max_flow(store, NodeId::new(source), NodeId::new(sink), capacity)
+            .map_err(weight_error)?
+        {
             Some(result) => {
                 let flow_edges: Vec<(u64, u64, f64)> = result
                     .flow_edges
@@ -243,7 +247,9 @@ impl PySolvORAdapter {
             NodeId::new(sink),
             capacity,
             cost,
-        ) {
+        )
+        .map_err(weight_error)?
+        {
             Some(result) => {
                 let flow_edges: Vec<(u64, u64, f64, f64)> = result
                     .flow_edges
@@ -264,6 +270,85 @@ impl PySolvORAdapter {
         }
     }
 
+    /// Export a bipartite subgraph as solver input arrays.
+    ///
+    /// Every `edge_type` edge from a `source_label` node to a `target_label`
+    /// node becomes one arc. Nodes are numbered densely, source side first,
+    /// which is the layout OR-Tools' `linear_sum_assignment` and
+    /// `min_cost_flow` expect. Capacities and costs are floats; scale and
+    /// round them if the solver needs integers.
+    ///
+    /// ```python
+    /// from ortools.graph.python import min_cost_flow
+    ///
+    /// model = solvor.flow_model("Worker", "Task", "CAN_DO", cost="cost")
+    /// smcf = min_cost_flow.SimpleMinCostFlow()
+    /// smcf.add_arcs_with_capacity_and_unit_cost(
+    ///     model["tails"],
+    ///     model["heads"],
+    ///     [int(c) for c in model["capacities"]],
+    ///     [int(c) for c in model["unit_costs"]],
+    /// )
+    /// ```
+    ///
+    /// Args:
+    ///     source_label: Label of the source-side nodes
+    ///     target_label: Label of the target-side nodes
+    ///     edge_type: Type of the edges that become arcs
+    ///     capacity: Optional edge property name for capacities (default: 1.0)
+    ///     cost: Optional edge property name for unit costs
+    ///     default_cost: Cost of arcs whose cost property is missing (default: 0.0)
+    ///
+    /// Returns:
+    ///     Dict with 'nodes', 'num_sources', 'tails', 'heads', 'capacities',
+    ///     'unit_costs', and 'edges' keys. 'nodes' maps each index to its
+    ///     node ID and 'edges' gives the edge ID behind each arc.
+    #[pyo3(signature = (source_label, target_label, edge_type, capacity=None, cost=None, default_cost=0.0))]
+    #[allow(clippy::too_many_arguments)]
+    fn flow_model(
+        &self,
+        source_label: &str,
+        target_label: &str,
+        edge_type: &str,
+        capacity: Option<&str>,
+        cost: Option<&str>,
+        default_cost: f64,
+        py: Python<'_>,
+    ) -> PyResult<Py<PyAny>> {
+        use grafeo_adapters::plugins::algorithms;
+
+        let db = self.db.read();
+        let store = db.store();
+
+        let arcs = algorithms::flow_arcs(
+            store,
+            source_label,
+            target_label,
+            edge_type,
+            capacity,
+            cost,
+            default_cost,
+        );
+        let nodes: Vec<u64> = arcs
+            .source_nodes
+            .iter()
+            .chain(&arcs.target_nodes)
+            .map(|id| id.0)
+            .collect();
+        let edges: Vec<u64> = arcs.edges.iter().map(|id| id.0).collect();
+
+        let dict = PyDict::new(py);
+        dict.set_item("nodes", nodes)?;
+        dict.set_item("num_sources", arcs.source_nodes.len())?;
+        dict.set_item("tails", arcs.tails)?;
+        dict.set_item("heads", arcs.heads)?;
+        dict.set_item("capacities", arcs.capacities)?;
+        dict.set_item("unit_costs", arcs.unit_costs)?;
+        dict.set_item("edges", edges)?;
+
+        Ok(dict.into_any().unbind())
+    }
+
     // ==========================================================================
     // Minimum Spanning Tree (solvOR-style)
     // ==========================================================================
@@ -291,7 +376,7 @@ impl PySolvORAdapter {
 
         let result = match method {
             "kruskal" => algorithms::kruskal(store, weight),
-            "prim" => algorithms::prim(store, weight, None),
+            "prim" => algorithms::prim(store, weight, None).map_err(weight_error)?,
             _ => {
                 return Err(PyGrafeoError::InvalidArgument(format!(
                     "Unknown method: {}. Use 'kruskal' or 'prim'",
@@ -347,13 +432,18 @@ impl PySolvORAdapter {
     /// Topological sort of the graph.
     ///
     /// Returns:
-    ///     List of node IDs in topological order, or None if graph has cycle.
-    fn topological_sort(&self) -> PyResult<Option<Vec<u64>>> {
+    ///     List of node IDs in topological order.
+    ///
+    /// Raises:
+    ///     CycleError: If the graph has a cycle; `args[1]` lists its node IDs.
+    fn topological_sort(&self) -> PyResult<Vec<u64>> {
         use grafeo_adapters::plugins::algorithms;
 
         let db = self.db.read();
         let store = db.store();
-        Ok(algorithms::topological_sort(store).map(|v| v.into_iter().map(|n| n.0).collect()))
+        algorithms::topological_sort(store)
+            .map(|v| v.into_iter().map(|n| n.0).collect())
+            .map_err(cycle_error)
     }
 
     // ==========================================================================
@@ -24,7 +24,8 @@ use crate::types::PyValue;
 /// Holds results from async query execution.
 ///
 /// Works like [`PyQueryResult`] but without node/edge extraction (async context
-/// limitations). Iterate directly or call [`rows()`](Self::rows) to get all data.
+/// limitations). Iterate directly, use `async for`, or call
+/// [`rows()`](Self::rows) to get all data.
 #[pyclass(name = "AsyncQueryResult")]
 pub struct AsyncQueryResult {
     #[pyo3(get)]
@@ -63,6 +64,14 @@ impl AsyncQueryResult {
         }
     }
 
+    /// Iterate over rows with `async for`, awaiting each one.
+    fn __aiter__(slf: PyRef<'_, Self>) -> AsyncQueryResultIter {
+        AsyncQueryResultIter {
+            rows: slf.rows.clone(),
+            index: 0,
+        }
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "AsyncQueryResult(columns={:?}, rows={})",
@@ -73,12 +82,31 @@ impl AsyncQueryResult {
 }
 
 /// Iterates through async query result rows one at a time.
+///
+/// Supports both `for` and `async for`. With `async for`, each row is an
+/// awaitable that resolves on the event loop, so long results don't starve
+/// other tasks.
 #[pyclass]
 pub struct AsyncQueryResultIter {
     rows: Vec<Vec<Value>>,
     index: usize,
 }
 
+impl AsyncQueryResultIter {
+    /// Converts the next row to a Python list, advancing the iterator.
+    fn next_row(&mut self, py: Python<'_>) -> Option<Py<PyAny>> {
+        let row = self.rows.get(self.index)?;
+        self.index += 1;
+
+        let py_row = pyo3::types::PyList::empty(py);
+        for val in row {
+            let py_val = PyValue::to_py(val, py);
+            let _ = py_row.append(py_val);
+        }
+        Some(py_row.into())
+    }
+}
+
 #[pymethods]
 impl AsyncQueryResultIter {
     fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
@@ -86,18 +114,21 @@ impl AsyncQueryResultIter {
     }
 
     fn __next__(mut slf: PyRefMut<'_, Self>, py: Python<'_>) -> Option<Py<PyAny>> {
-        if slf.index >= slf.rows.len() {
-            return None;
-        }
-        let row = slf.rows[slf.index].clone();
-        slf.index += 1;
+        slf.next_row(py)
+    }
 
-        let py_row = pyo3::types::PyList::empty(py);
-        for val in &row {
-            let py_val = PyValue::to_py(val, py);
-            let _ = py_row.append(py_val);
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        py: Python<'py>,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        match slf.next_row(py) {
+            Some(row) => future_into_py(py, async move { Ok(row) }).map(Some),
+            None => Ok(None),
         }
-        Some(py_row.into())
     }
 }
 
@@ -177,12 +208,24 @@ impl PyGrafeoDB {
 
         Ok(PyQueryResult::new(
             result.columns,
+            result.column_types,
             result.rows,
             nodes,
             edges,
         ))
     }
 
+    /// Describes how a GQL query would run, without running it.
+    ///
+    /// Returns the physical operator tree as text, one operator per line,
+    /// with estimated rows and cost for each:
+    ///     print(db.explain("MATCH (p:Person) RETURN count(p)"))
+    #[cfg(feature = "gql")]
+    fn explain(&self, query: &str) -> PyResult<String> {
+        let db = self.inner.read();
+        Ok(db.explain(query).map_err(PyGrafeoError::from)?)
+    }
+
     /// Execute a query and return a query builder.
     fn query(&self, query: String) -> PyQueryBuilder {
         PyQueryBuilder::create(query)
@@ -218,6 +261,7 @@ impl PyGrafeoDB {
 
         Ok(PyQueryResult::new(
             result.columns,
+            result.column_types,
             result.rows,
             nodes,
             edges,
@@ -227,13 +271,15 @@ impl PyGrafeoDB {
     /// Execute a GQL query asynchronously.
     ///
     /// This method returns a Python awaitable that can be used with asyncio.
+    /// The query runs on a blocking thread pool, so the event loop stays
+    /// responsive and several async queries can run at the same time.
     ///
     /// Example:
     /// ```python
     /// async def main():
     ///     db = GrafeoDB()
     ///     result = await db.execute_async("MATCH (n:Person) RETURN n")
-    ///     for row in result:
+    ///     async for row in result:
     ///         print(row)
     ///
     /// asyncio.run(main())
@@ -316,6 +362,7 @@ impl PyGrafeoDB {
 
         Ok(PyQueryResult::new(
             result.columns,
+            result.column_types,
             result.rows,
             nodes,
             edges,
@@ -352,6 +399,7 @@ impl PyGrafeoDB {
 
         Ok(PyQueryResult::new(
             result.columns,
+            result.column_types,
             result.rows,
             nodes,
             edges,
@@ -361,17 +409,19 @@ impl PyGrafeoDB {
     /// Execute a SPARQL query against the RDF triple store.
     ///
     /// SPARQL is the W3C standard query language for RDF data.
+    /// ASK queries return a bool rather than a result set.
     ///
     /// Example:
     ///     result = db.execute_sparql("SELECT ?s ?p ?o WHERE { ?s ?p ?o }")
+    ///     found = db.execute_sparql("ASK { ?s ?p ?o }")
     #[cfg(feature = "sparql")]
     #[pyo3(signature = (query, params=None))]
     fn execute_sparql(
         &self,
         query: &str,
         params: Option<&Bound<'_, pyo3::types::PyDict>>,
-        _py: Python<'_>,
-    ) -> PyResult<PyQueryResult> {
+        py: Python<'_>,
+    ) -> PyResult<Py<PyAny>> {
         let _params = if let Some(p) = params {
             let mut map = HashMap::new();
             for (key, value) in p.iter() {
@@ -386,14 +436,7 @@ impl PyGrafeoDB {
 
         let db = self.inner.read();
         let result = db.execute_sparql(query).map_err(PyGrafeoError::from)?;
-
-        // SPARQL results don't have LPG nodes/edges, so pass empty vectors
-        Ok(PyQueryResult::new(
-            result.columns,
-            result.rows,
-            Vec::new(),
-            Vec::new(),
-        ))
+        sparql_result(py, result)
     }
 
     /// Create a node.
@@ -738,12 +781,17 @@ impl PyGrafeoDB {
     ///
     /// Returns:
     ///     dict with keys: labels (list of dicts), edge_types (list of dicts),
-    ///     property_keys (list of strings)
+    ///     property_keys (list of strings). Each label has name, count and
+    ///     properties; each edge type also has endpoints, a list of
+    ///     (source label, target label) tuples. A property is a dict with
+    ///     name, type, count, unique and required.
     ///
     /// Example:
     ///     schema = db.schema()
     ///     for label in schema['labels']:
     ///         print(f"{label['name']}: {label['count']} nodes")
+    ///         for prop in label['properties']:
+    ///             print(f"  {prop['name']}: {prop['type']}")
     fn schema(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
         let db = self.inner.read();
         let schema = db.schema();
@@ -757,6 +805,7 @@ impl PyGrafeoDB {
                     let label_dict = pyo3::types::PyDict::new(py);
                     label_dict.set_item("name", label.name)?;
                     label_dict.set_item("count", label.count)?;
+                    label_dict.set_item("properties", property_list(py, label.properties)?)?;
                     labels.append(label_dict)?;
                 }
                 dict.set_item("labels", labels)?;
@@ -766,6 +815,8 @@ impl PyGrafeoDB {
                     let et_dict = pyo3::types::PyDict::new(py);
                     et_dict.set_item("name", et.name)?;
                     et_dict.set_item("count", et.count)?;
+                    et_dict.set_item("endpoints", et.endpoints)?;
+                    et_dict.set_item("properties", property_list(py, et.properties)?)?;
                     edge_types.append(et_dict)?;
                 }
                 dict.set_item("edge_types", edge_types)?;
@@ -931,7 +982,8 @@ impl PyGrafeoDB {
 
     /// Get the algorithms interface.
     ///
-    /// Returns an Algorithms object providing access to all graph algorithms.
+    /// Returns an Algorithms object providing access to all graph algorithms,
+    /// which see only committed data.
     ///
     /// Example:
     ///     pr = db.algorithms.pagerank()
@@ -1131,6 +1183,7 @@ impl PyTransaction {
 
         Ok(PyQueryResult::new(
             result.columns,
+            result.column_types,
             result.rows,
             nodes,
             edges,
@@ -1183,6 +1236,7 @@ impl PyTransaction {
 
         Ok(PyQueryResult::new(
             result.columns,
+            result.column_types,
             result.rows,
             nodes,
             edges,
@@ -1235,6 +1289,7 @@ impl PyTransaction {
 
         Ok(PyQueryResult::new(
             result.columns,
+            result.column_types,
             result.rows,
             nodes,
             edges,
@@ -1245,7 +1300,8 @@ impl PyTransaction {
     ///
     /// SPARQL is the W3C standard query language for RDF data.
     /// All queries executed through this method see the same snapshot
-    /// and their changes are isolated until commit.
+    /// and their changes are isolated until commit. ASK queries return a
+    /// bool rather than a result set.
     ///
     /// Example:
     ///     with db.begin_transaction() as tx:
@@ -1258,8 +1314,8 @@ impl PyTransaction {
         &self,
         query: &str,
         params: Option<&Bound<'_, pyo3::types::PyDict>>,
-        _py: Python<'_>,
-    ) -> PyResult<PyQueryResult> {
+        py: Python<'_>,
+    ) -> PyResult<Py<PyAny>> {
         if self.committed || self.rolled_back {
             return Err(pyo3::exceptions::PyRuntimeError::new_err(
                 "Cannot execute on completed transaction",
@@ -1287,13 +1343,7 @@ impl PyTransaction {
             session.execute_sparql(query).map_err(PyGrafeoError::from)?
         };
 
-        // SPARQL results don't have LPG nodes/edges, so pass empty vectors
-        Ok(PyQueryResult::new(
-            result.columns,
-            result.rows,
-            Vec::new(),
-            Vec::new(),
-        ))
+        sparql_result(py, result)
     }
 
     /// Check if transaction is active.
@@ -1358,6 +1408,45 @@ impl PyDbStats {
     }
 }
 
+/// Converts a SPARQL result for Python: a bool for ASK, otherwise a result set.
+#[cfg(feature = "sparql")]
+fn sparql_result(py: Python<'_>, result: QueryResult) -> PyResult<Py<PyAny>> {
+    if let Some(answer) = result.as_bool() {
+        return Ok(pyo3::types::PyBool::new(py, answer)
+            .to_owned()
+            .into_any()
+            .unbind());
+    }
+
+    // SPARQL results don't have LPG nodes/edges, so pass empty vectors
+    let result = PyQueryResult::new(
+        result.columns,
+        result.column_types,
+        result.rows,
+        Vec::new(),
+        Vec::new(),
+    );
+    Ok(Py::new(py, result)?.into_any())
+}
+
+/// Converts the properties of a label or edge type for `schema()`.
+fn property_list<'py>(
+    py: Python<'py>,
+    properties: Vec<grafeo_engine::PropertyInfo>,
+) -> PyResult<Bound<'py, pyo3::types::PyList>> {
+    let list = pyo3::types::PyList::empty(py);
+    for property in properties {
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("name", property.name)?;
+        dict.set_item("type", property.data_type.to_string())?;
+        dict.set_item("count", property.count)?;
+        dict.set_item("unique", property.unique)?;
+        dict.set_item("required", property.required)?;
+        list.append(dict)?;
+    }
+    Ok(list)
+}
+
 /// Pulls nodes and edges out of query results so Python can work with them.
 fn extract_entities(result: &QueryResult, db: &GrafeoDB) -> (Vec<PyNode>, Vec<PyEdge>) {
     let mut nodes = Vec::new();
@@ -1,12 +1,33 @@
 //! Converts Rust errors to Python exceptions.
 //!
 //! Type errors and invalid arguments become `ValueError`, while database,
-//! query, and transaction errors become `RuntimeError`.
+//! query, and transaction errors become `RuntimeError`. A cycle found while
+//! sorting becomes `CycleError`, a `ValueError` that carries the cycle, and
+//! an edge weight an algorithm rejects becomes a `ValueError` too.
 
 use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
 use thiserror::Error;
 
+pyo3::create_exception!(
+    grafeo,
+    CycleError,
+    PyValueError,
+    "Raised when a topological sort finds a cycle. Like `graphlib.CycleError`, \
+     `args[1]` holds the node IDs on the cycle."
+);
+
+/// Builds the error for a cycle found while sorting, carrying its node IDs.
+pub fn cycle_error(cycle: Vec<grafeo_common::types::NodeId>) -> PyErr {
+    let ids: Vec<u64> = cycle.into_iter().map(|n| n.0).collect();
+    CycleError::new_err(("graph contains a cycle", ids))
+}
+
+/// Builds the error for an edge weight or capacity an algorithm rejects.
+pub fn weight_error(err: grafeo_common::utils::error::Error) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
 /// Grafeo errors that translate to Python exceptions.
 #[derive(Error, Debug)]
 pub enum PyGrafeoError {
@@ -30,7 +30,7 @@
 //! | Library | How to use | Best for |
 //! | ------- | ---------- | -------- |
 //! | NetworkX | `db.as_networkx().to_networkx()` | Graph visualization, analysis |
-//! | pandas | `result.to_list()` then `pd.DataFrame()` | Tabular operations |
+//! | pandas | `result.to_pandas()` | Tabular operations |
 //! | solvOR | `db.as_solvor()` | Operations research algorithms |
 
 #![warn(missing_docs)]
@@ -63,6 +63,7 @@ fn grafeo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyAlgorithms>()?;
     m.add_class::<PyNetworkXAdapter>()?;
     m.add_class::<PySolvORAdapter>()?;
+    m.add("CycleError", m.py().get_type::<error::CycleError>())?;
 
     // Add version info
     m.add("__version__", env!("CARGO_PKG_VERSION"))?;
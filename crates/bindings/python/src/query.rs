@@ -3,8 +3,9 @@
 use std::collections::HashMap;
 
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
 
-use grafeo_common::types::Value;
+use grafeo_common::types::{LogicalType, Value};
 
 use crate::graph::{PyEdge, PyNode};
 use crate::types::PyValue;
@@ -17,6 +18,7 @@ use crate::types::PyValue;
 #[pyclass(name = "QueryResult")]
 pub struct PyQueryResult {
     pub(crate) columns: Vec<String>,
+    pub(crate) column_types: Vec<LogicalType>,
     pub(crate) rows: Vec<Vec<Value>>,
     pub(crate) nodes: Vec<PyNode>,
     pub(crate) edges: Vec<PyEdge>,
@@ -111,6 +113,60 @@ impl PyQueryResult {
         list.unbind().into_any()
     }
 
+    /// Convert to a pandas DataFrame with one column per RETURN column.
+    ///
+    /// Column dtypes follow the values: `int64`, `float64`, and `bool` when
+    /// every value has that type, or the nullable `Int64`, `Float64`, and
+    /// `boolean` when some are null. Strings, lists, and mixed columns are
+    /// `object`. Nodes and edges become dicts with their id, labels or type,
+    /// and properties.
+    ///
+    /// Requires pandas to be installed.
+    fn to_pandas(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let pandas = py.import("pandas")?;
+
+        let nodes: HashMap<u64, &PyNode> = self.nodes.iter().map(|n| (n.id.0, n)).collect();
+        let edges: HashMap<u64, &PyEdge> = self.edges.iter().map(|e| (e.id.0, e)).collect();
+
+        // Columns are keyed by position so duplicate names survive
+        let data = PyDict::new(py);
+        for (col_idx, name) in self.columns.iter().enumerate() {
+            let column_type = self.column_types.get(col_idx);
+            let values = self
+                .rows
+                .iter()
+                .map(|row| row.get(col_idx).unwrap_or(&Value::Null));
+
+            let items = PyList::empty(py);
+            for value in values.clone() {
+                let item = match (column_type, value) {
+                    (Some(LogicalType::Node), Value::Int64(id)) => nodes
+                        .get(&(*id as u64))
+                        .map_or_else(|| PyValue::to_py(value, py), |node| node_dict(node, py)),
+                    (Some(LogicalType::Edge), Value::Int64(id)) => edges
+                        .get(&(*id as u64))
+                        .map_or_else(|| PyValue::to_py(value, py), |edge| edge_dict(edge, py)),
+                    _ => PyValue::to_py(value, py),
+                };
+                items.append(item)?;
+            }
+
+            let dtype = match column_type {
+                Some(LogicalType::Node | LogicalType::Edge) => "object",
+                _ => pandas_dtype(values),
+            };
+            let kwargs = PyDict::new(py);
+            kwargs.set_item("dtype", dtype)?;
+            kwargs.set_item("name", name)?;
+            let series = pandas.call_method("Series", (items,), Some(&kwargs))?;
+            data.set_item(col_idx, series)?;
+        }
+
+        let frame = pandas.call_method1("DataFrame", (data,))?;
+        frame.setattr("columns", self.columns.clone())?;
+        Ok(frame.unbind())
+    }
+
     /// Get single value (first column of first row).
     fn scalar(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
         if self.rows.is_empty() {
@@ -137,12 +193,14 @@ impl PyQueryResult {
     /// Creates a new query result (used internally).
     pub fn new(
         columns: Vec<String>,
+        column_types: Vec<LogicalType>,
         rows: Vec<Vec<Value>>,
         nodes: Vec<PyNode>,
         edges: Vec<PyEdge>,
     ) -> Self {
         Self {
             columns,
+            column_types,
             rows,
             nodes,
             edges,
@@ -154,6 +212,7 @@ impl PyQueryResult {
     pub fn empty() -> Self {
         Self {
             columns: Vec::new(),
+            column_types: Vec::new(),
             rows: Vec::new(),
             nodes: Vec::new(),
             edges: Vec::new(),
@@ -162,6 +221,72 @@ impl PyQueryResult {
     }
 }
 
+/// Picks the pandas dtype for a column of values.
+fn pandas_dtype<'a>(values: impl Iterator<Item = &'a Value>) -> &'static str {
+    let mut kind = None;
+    let mut has_null = false;
+    for value in values {
+        let value_kind = match value {
+            Value::Null => {
+                has_null = true;
+                continue;
+            }
+            Value::Int64(_) => "int",
+            Value::Float64(_) => "float",
+            Value::Bool(_) => "bool",
+            _ => return "object",
+        };
+        match kind {
+            None => kind = Some(value_kind),
+            Some(current) if current != value_kind => return "object",
+            Some(_) => {}
+        }
+    }
+    match (kind, has_null) {
+        (Some("int"), false) => "int64",
+        (Some("int"), true) => "Int64",
+        (Some("float"), false) => "float64",
+        (Some("float"), true) => "Float64",
+        (Some("bool"), false) => "bool",
+        (Some("bool"), true) => "boolean",
+        _ => "object",
+    }
+}
+
+/// Converts a node to a dict with its id, labels, and properties.
+fn node_dict(node: &PyNode, py: Python<'_>) -> Py<PyAny> {
+    let dict = PyDict::new(py);
+    let properties = PyDict::new(py);
+    for (key, value) in &node.properties {
+        properties
+            .set_item(key, PyValue::to_py(value, py))
+            .expect("dict.set_item only fails on memory exhaustion");
+    }
+    dict.set_item("id", node.id.0)
+        .and_then(|()| dict.set_item("labels", node.labels.clone()))
+        .and_then(|()| dict.set_item("properties", properties))
+        .expect("dict.set_item only fails on memory exhaustion");
+    dict.unbind().into_any()
+}
+
+/// Converts an edge to a dict with its id, type, endpoints, and properties.
+fn edge_dict(edge: &PyEdge, py: Python<'_>) -> Py<PyAny> {
+    let dict = PyDict::new(py);
+    let properties = PyDict::new(py);
+    for (key, value) in &edge.properties {
+        properties
+            .set_item(key, PyValue::to_py(value, py))
+            .expect("dict.set_item only fails on memory exhaustion");
+    }
+    dict.set_item("id", edge.id.0)
+        .and_then(|()| dict.set_item("type", edge.edge_type.clone()))
+        .and_then(|()| dict.set_item("source", edge.source_id.0))
+        .and_then(|()| dict.set_item("target", edge.target_id.0))
+        .and_then(|()| dict.set_item("properties", properties))
+        .expect("dict.set_item only fails on memory exhaustion");
+    dict.unbind().into_any()
+}
+
 /// Builds parameterized queries with a fluent API.
 ///
 /// Add parameters with `.param("name", value)` to safely inject values
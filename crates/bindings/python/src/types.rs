@@ -256,6 +256,20 @@ impl PyValue {
                     .map(|dt| dt.unbind().into_any())
                     .unwrap_or_else(|_| py.None())
             }
+            Value::Date(date) => {
+                let (year, month, day) = date.to_ymd();
+                py.import("datetime")
+                    .and_then(|m| m.getattr("date"))
+                    .and_then(|class| class.call1((year, month, day)))
+                    .map(|d| d.unbind().into_any())
+                    .unwrap_or_else(|_| py.None())
+            }
+            Value::Duration(dur) => py
+                .import("datetime")
+                .and_then(|m| m.getattr("timedelta"))
+                .and_then(|class| class.call1((0, 0, dur.as_micros())))
+                .map(|d| d.unbind().into_any())
+                .unwrap_or_else(|_| py.None()),
         }
     }
 }
@@ -8,7 +8,7 @@ use std::sync::OnceLock;
 
 use grafeo_common::types::{NodeId, Value};
 use grafeo_common::utils::error::Result;
-use grafeo_common::utils::hash::FxHashMap;
+use grafeo_common::utils::hash::{FxHashMap, FxHashSet};
 use grafeo_core::graph::Direction;
 use grafeo_core::graph::lpg::LpgStore;
 
@@ -86,6 +86,64 @@ pub fn degree_centrality(store: &LpgStore) -> DegreeCentralityResult {
     }
 }
 
+/// Computes weighted degree centrality for all nodes.
+///
+/// Like [`degree_centrality`], but each edge contributes the value of its
+/// `weight_property` instead of 1. Int64 and Float64 values are used as-is;
+/// edges where the property is missing or non-numeric contribute 0.0.
+///
+/// A self-loop counts as both an outgoing and an incoming edge of its node,
+/// so it contributes its weight twice under [`Direction::Both`], matching
+/// `total_degree` in the unweighted version.
+///
+/// # Arguments
+///
+/// * `store` - The graph store
+/// * `weight_property` - Edge property holding the weight
+/// * `direction` - Which edges to sum: incoming, outgoing, or both
+///
+/// # Returns
+///
+/// Summed edge weight for each node.
+///
+/// # Complexity
+///
+/// O(V + E)
+pub fn weighted_degree_centrality(
+    store: &LpgStore,
+    weight_property: &str,
+    direction: Direction,
+) -> FxHashMap<NodeId, f64> {
+    let nodes = store.node_ids();
+
+    // Initialize all nodes
+    let mut weighted: FxHashMap<NodeId, f64> = nodes.iter().map(|&n| (n, 0.0)).collect();
+
+    // Sum weights
+    for &node in &nodes {
+        for (neighbor, edge_id) in store.edges_from(node, Direction::Outgoing) {
+            let weight = store
+                .get_edge(edge_id)
+                .and_then(|edge| match edge.get_property(weight_property) {
+                    Some(Value::Int64(i)) => Some(*i as f64),
+                    Some(Value::Float64(f)) => Some(*f),
+                    _ => None,
+                })
+                .unwrap_or(0.0);
+
+            if matches!(direction, Direction::Outgoing | Direction::Both) {
+                *weighted.entry(node).or_insert(0.0) += weight;
+            }
+            // For incoming weights, credit the edge's target
+            if matches!(direction, Direction::Incoming | Direction::Both) {
+                *weighted.entry(neighbor).or_insert(0.0) += weight;
+            }
+        }
+    }
+
+    weighted
+}
+
 /// Computes normalized degree centrality.
 ///
 /// Normalizes by dividing by (n-1) where n is the node count.
@@ -137,6 +195,55 @@ pub fn pagerank(
     damping: f64,
     max_iterations: usize,
     tolerance: f64,
+) -> FxHashMap<NodeId, f64> {
+    pagerank_with_teleport(store, &[], damping, max_iterations, tolerance)
+}
+
+/// Computes personalized PageRank, restarting random walks at a seed set.
+///
+/// Works like [`pagerank`], but teleportation lands only on the seed nodes
+/// (uniformly among them), and the mass of dangling nodes is redistributed
+/// to the seeds as well. Scores therefore measure proximity to the seeds,
+/// which makes this the usual building block for recommendations.
+///
+/// Seeds that don't exist in the store are ignored. If no valid seed is
+/// left, this falls back to regular [`pagerank`].
+///
+/// # Arguments
+///
+/// * `store` - The graph store
+/// * `seeds` - Nodes that random walks restart from
+/// * `damping` - Damping factor (typically 0.85)
+/// * `max_iterations` - Maximum number of iterations
+/// * `tolerance` - Convergence tolerance (stop when change < tolerance)
+///
+/// # Returns
+///
+/// Personalized PageRank score for each node.
+///
+/// # Complexity
+///
+/// O(iterations × (V + E))
+pub fn personalized_pagerank(
+    store: &LpgStore,
+    seeds: &[NodeId],
+    damping: f64,
+    max_iterations: usize,
+    tolerance: f64,
+) -> FxHashMap<NodeId, f64> {
+    pagerank_with_teleport(store, seeds, damping, max_iterations, tolerance)
+}
+
+/// Power iteration shared by [`pagerank`] and [`personalized_pagerank`].
+///
+/// Teleportation and dangling mass go to `seeds`, or to every node when
+/// none of the seeds exist.
+fn pagerank_with_teleport(
+    store: &LpgStore,
+    seeds: &[NodeId],
+    damping: f64,
+    max_iterations: usize,
+    tolerance: f64,
 ) -> FxHashMap<NodeId, f64> {
     let nodes = store.node_ids();
     let n = nodes.len();
@@ -151,6 +258,21 @@ pub fn pagerank(
         node_to_idx.insert(node, idx);
     }
 
+    // Teleport distribution: uniform over the seeds, or over all nodes
+    let mut teleport = vec![0.0; n];
+    let seed_indices: FxHashSet<usize> = seeds
+        .iter()
+        .filter_map(|seed| node_to_idx.get(seed).copied())
+        .collect();
+    if seed_indices.is_empty() {
+        teleport.fill(1.0 / n as f64);
+    } else {
+        let share = 1.0 / seed_indices.len() as f64;
+        for &idx in &seed_indices {
+            teleport[idx] = share;
+        }
+    }
+
     // Build adjacency structure
     let mut out_edges: Vec<Vec<usize>> = vec![Vec::new(); n];
     let mut out_degree: Vec<usize> = vec![0; n];
@@ -164,9 +286,8 @@ pub fn pagerank(
         out_edges[idx] = edges;
     }
 
-    // Initialize PageRank scores
-    let initial_score = 1.0 / n as f64;
-    let mut scores = vec![initial_score; n];
+    // Initialize PageRank scores from the teleport distribution
+    let mut scores = teleport.clone();
     let mut new_scores = vec![0.0; n];
 
     // Identify dangling nodes (no outgoing edges)
@@ -174,14 +295,11 @@ pub fn pagerank(
 
     // Power iteration
     for _ in 0..max_iterations {
-        // Compute dangling contribution
+        // Teleportation and dangling mass both follow the teleport distribution
         let dangling_sum: f64 = dangling.iter().map(|&i| scores[i]).sum();
-        let dangling_contrib = damping * dangling_sum / n as f64;
-
-        // Reset new scores with teleportation and dangling contribution
-        let teleport = (1.0 - damping) / n as f64;
-        for score in &mut new_scores {
-            *score = teleport + dangling_contrib;
+        let restart = (1.0 - damping) + damping * dangling_sum;
+        for (score, &weight) in new_scores.iter_mut().zip(teleport.iter()) {
+            *score = restart * weight;
         }
 
         // Add contributions from incoming edges
@@ -252,78 +370,167 @@ pub fn betweenness_centrality(store: &LpgStore, normalized: bool) -> FxHashMap<N
 
     // Brandes' algorithm: run BFS from each source
     for &source in &nodes {
-        // BFS data structures
-        let mut stack: Vec<NodeId> = Vec::new();
-        let mut predecessors: FxHashMap<NodeId, Vec<NodeId>> = FxHashMap::default();
-        let mut sigma: FxHashMap<NodeId, f64> = FxHashMap::default(); // Number of shortest paths
-        let mut dist: FxHashMap<NodeId, i64> = FxHashMap::default();
+        accumulate_dependencies(store, &nodes, source, &mut centrality);
+    }
 
-        // Initialize
-        for &node in &nodes {
-            predecessors.insert(node, Vec::new());
-            sigma.insert(node, 0.0);
-            dist.insert(node, -1);
+    // Normalize if requested
+    if normalized && n > 2 {
+        let norm = 2.0 / ((n - 1) * (n - 2)) as f64;
+        for (_, v) in centrality.iter_mut() {
+            *v *= norm;
         }
-        sigma.insert(source, 1.0);
-        dist.insert(source, 0);
+    }
 
-        // BFS
-        let mut queue: VecDeque<NodeId> = VecDeque::new();
-        queue.push_back(source);
+    centrality
+}
 
-        while let Some(v) = queue.pop_front() {
-            stack.push(v);
-            let dist_v = *dist.get(&v).unwrap();
+/// Approximates betweenness centrality by running Brandes from a node sample.
+///
+/// Only `sample_size` source nodes, picked uniformly at random, contribute
+/// shortest-path dependencies; the totals are then scaled by
+/// `V / sample_size` so they estimate the exact (unnormalized) scores.
+/// The same `seed` always picks the same sample, so results are reproducible.
+///
+/// Falls back to exact [`betweenness_centrality`] when `sample_size` is at
+/// least the node count.
+///
+/// # Arguments
+///
+/// * `store` - The graph store
+/// * `sample_size` - Number of source nodes to sample
+/// * `seed` - Seed for the sampling RNG
+///
+/// # Returns
+///
+/// Estimated betweenness centrality score for each node.
+///
+/// # Complexity
+///
+/// O(sample_size × E) for unweighted graphs
+pub fn approximate_betweenness(
+    store: &LpgStore,
+    sample_size: usize,
+    seed: u64,
+) -> FxHashMap<NodeId, f64> {
+    let nodes = store.node_ids();
+    let n = nodes.len();
 
-            for (w, _) in store.edges_from(v, Direction::Outgoing) {
-                // First visit?
-                if *dist.get(&w).unwrap() < 0 {
-                    dist.insert(w, dist_v + 1);
-                    queue.push_back(w);
-                }
+    if sample_size >= n {
+        return betweenness_centrality(store, false);
+    }
 
-                // Shortest path to w via v?
-                if *dist.get(&w).unwrap() == dist_v + 1 {
-                    let sigma_v = *sigma.get(&v).unwrap();
-                    *sigma.entry(w).or_insert(0.0) += sigma_v;
-                    predecessors.entry(w).or_default().push(v);
-                }
-            }
-        }
+    let mut centrality: FxHashMap<NodeId, f64> = FxHashMap::default();
+    for &node in &nodes {
+        centrality.insert(node, 0.0);
+    }
 
-        // Accumulation
-        let mut delta: FxHashMap<NodeId, f64> = FxHashMap::default();
-        for &node in &nodes {
-            delta.insert(node, 0.0);
-        }
+    if sample_size == 0 || n <= 2 {
+        return centrality;
+    }
 
-        while let Some(w) = stack.pop() {
-            if w == source {
-                continue;
-            }
+    // Partial Fisher-Yates shuffle picks the sample without replacement
+    let mut candidates = nodes.clone();
+    let mut rng = SplitMix64(seed);
+    for i in 0..sample_size {
+        let j = i + (rng.next_u64() % (n - i) as u64) as usize;
+        candidates.swap(i, j);
+    }
+
+    for &source in &candidates[..sample_size] {
+        accumulate_dependencies(store, &nodes, source, &mut centrality);
+    }
 
-            let sigma_w = *sigma.get(&w).unwrap();
-            let delta_w = *delta.get(&w).unwrap();
+    let scale = n as f64 / sample_size as f64;
+    for (_, v) in centrality.iter_mut() {
+        *v *= scale;
+    }
+
+    centrality
+}
 
-            for v in predecessors.get(&w).unwrap_or(&Vec::new()) {
-                let sigma_v = *sigma.get(v).unwrap();
-                let coeff = (sigma_v / sigma_w) * (1.0 + delta_w);
-                *delta.entry(*v).or_insert(0.0) += coeff;
+/// Runs one single-source pass of Brandes' algorithm.
+///
+/// Adds the dependency of `source` on every other node to `centrality`.
+fn accumulate_dependencies(
+    store: &LpgStore,
+    nodes: &[NodeId],
+    source: NodeId,
+    centrality: &mut FxHashMap<NodeId, f64>,
+) {
+    // BFS data structures
+    let mut stack: Vec<NodeId> = Vec::new();
+    let mut predecessors: FxHashMap<NodeId, Vec<NodeId>> = FxHashMap::default();
+    let mut sigma: FxHashMap<NodeId, f64> = FxHashMap::default(); // Number of shortest paths
+    let mut dist: FxHashMap<NodeId, i64> = FxHashMap::default();
+
+    // Initialize
+    for &node in nodes {
+        predecessors.insert(node, Vec::new());
+        sigma.insert(node, 0.0);
+        dist.insert(node, -1);
+    }
+    sigma.insert(source, 1.0);
+    dist.insert(source, 0);
+
+    // BFS
+    let mut queue: VecDeque<NodeId> = VecDeque::new();
+    queue.push_back(source);
+
+    while let Some(v) = queue.pop_front() {
+        stack.push(v);
+        let dist_v = *dist.get(&v).unwrap();
+
+        for (w, _) in store.edges_from(v, Direction::Outgoing) {
+            // First visit?
+            if *dist.get(&w).unwrap() < 0 {
+                dist.insert(w, dist_v + 1);
+                queue.push_back(w);
             }
 
-            *centrality.entry(w).or_insert(0.0) += delta_w;
+            // Shortest path to w via v?
+            if *dist.get(&w).unwrap() == dist_v + 1 {
+                let sigma_v = *sigma.get(&v).unwrap();
+                *sigma.entry(w).or_insert(0.0) += sigma_v;
+                predecessors.entry(w).or_default().push(v);
+            }
         }
     }
 
-    // Normalize if requested
-    if normalized && n > 2 {
-        let norm = 2.0 / ((n - 1) * (n - 2)) as f64;
-        for (_, v) in centrality.iter_mut() {
-            *v *= norm;
+    // Accumulation
+    let mut delta: FxHashMap<NodeId, f64> = FxHashMap::default();
+    for &node in nodes {
+        delta.insert(node, 0.0);
+    }
+
+    while let Some(w) = stack.pop() {
+        if w == source {
+            continue;
         }
+
+        let sigma_w = *sigma.get(&w).unwrap();
+        let delta_w = *delta.get(&w).unwrap();
+
+        for v in predecessors.get(&w).unwrap_or(&Vec::new()) {
+            let sigma_v = *sigma.get(v).unwrap();
+            let coeff = (sigma_v / sigma_w) * (1.0 + delta_w);
+            *delta.entry(*v).or_insert(0.0) += coeff;
+        }
+
+        *centrality.entry(w).or_insert(0.0) += delta_w;
     }
+}
 
-    centrality
+/// Minimal SplitMix64 generator for reproducible sampling.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
 }
 
 // ============================================================================
@@ -461,7 +668,7 @@ impl GraphAlgorithm for PageRankAlgorithm {
 
         let scores = pagerank(store, damping, max_iter, tolerance);
 
-        let mut builder = NodeValueResultBuilder::with_capacity("pagerank", scores.len());
+        let mut builder = NodeValueResultBuilder::with_capacity("score", scores.len());
         for (node, score) in scores {
             builder.push(node, Value::Float64(score));
         }
@@ -703,6 +910,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_weighted_degree_centrality() {
+        // a -2.5-> b, a -1-> c, c -4-> b, plus an unweighted a -> c
+        let store = LpgStore::new();
+        let a = store.create_node(&["Node"]);
+        let b = store.create_node(&["Node"]);
+        let c = store.create_node(&["Node"]);
+        store.create_edge_with_props(a, b, "EDGE", [("weight", Value::Float64(2.5))]);
+        store.create_edge_with_props(a, c, "EDGE", [("weight", Value::Int64(1))]);
+        store.create_edge_with_props(c, b, "EDGE", [("weight", Value::Int64(4))]);
+        store.create_edge(a, c, "EDGE");
+
+        let out = weighted_degree_centrality(&store, "weight", Direction::Outgoing);
+        assert_eq!(out[&a], 3.5);
+        assert_eq!(out[&b], 0.0);
+        assert_eq!(out[&c], 4.0);
+
+        let incoming = weighted_degree_centrality(&store, "weight", Direction::Incoming);
+        assert_eq!(incoming[&a], 0.0);
+        assert_eq!(incoming[&b], 6.5);
+        assert_eq!(incoming[&c], 1.0);
+
+        let both = weighted_degree_centrality(&store, "weight", Direction::Both);
+        assert_eq!(both[&a], 3.5);
+        assert_eq!(both[&b], 6.5);
+        assert_eq!(both[&c], 5.0);
+    }
+
+    #[test]
+    fn test_weighted_degree_centrality_self_loop() {
+        let store = LpgStore::new();
+        let a = store.create_node(&["Node"]);
+        let b = store.create_node(&["Node"]);
+        store.create_edge_with_props(a, a, "EDGE", [("weight", Value::Float64(3.0))]);
+        store.create_edge_with_props(a, b, "EDGE", [("weight", Value::Float64(1.0))]);
+
+        // Same convention as the unweighted total degree: the loop counts twice
+        let unweighted = degree_centrality(&store);
+        assert_eq!(unweighted.total_degree[&a], 3);
+
+        let out = weighted_degree_centrality(&store, "weight", Direction::Outgoing);
+        let incoming = weighted_degree_centrality(&store, "weight", Direction::Incoming);
+        let both = weighted_degree_centrality(&store, "weight", Direction::Both);
+        assert_eq!(out[&a], 4.0);
+        assert_eq!(incoming[&a], 3.0);
+        assert_eq!(both[&a], 7.0);
+    }
+
     #[test]
     fn test_pagerank_basic() {
         let store = create_pagerank_graph();
@@ -743,6 +998,61 @@ mod tests {
         assert!(scores.is_empty());
     }
 
+    #[test]
+    fn test_personalized_pagerank_favors_seed_neighborhood() {
+        // Two chains joined by a bridge: 0 <-> 1 <-> 2 <-> 3 <-> 4
+        let store = LpgStore::new();
+        let nodes: Vec<NodeId> = (0..5).map(|_| store.create_node(&["Node"])).collect();
+        for pair in nodes.windows(2) {
+            store.create_edge(pair[0], pair[1], "EDGE");
+            store.create_edge(pair[1], pair[0], "EDGE");
+        }
+
+        let uniform = pagerank(&store, 0.85, 100, 1e-9);
+        let personalized = personalized_pagerank(&store, &[nodes[0]], 0.85, 100, 1e-9);
+
+        assert_eq!(personalized.len(), 5);
+        let total: f64 = personalized.values().sum();
+        assert!((total - 1.0).abs() < 0.01);
+
+        // The seed and its neighbor gain, the far end loses
+        assert!(personalized[&nodes[0]] > uniform[&nodes[0]]);
+        assert!(personalized[&nodes[1]] > uniform[&nodes[1]]);
+        assert!(personalized[&nodes[4]] < uniform[&nodes[4]]);
+        assert!(personalized[&nodes[1]] > personalized[&nodes[3]]);
+    }
+
+    #[test]
+    fn test_personalized_pagerank_dangling_seed() {
+        // The seed has no outgoing edges: a -> b, c -> seed
+        let store = LpgStore::new();
+        let a = store.create_node(&["Node"]);
+        let b = store.create_node(&["Node"]);
+        let c = store.create_node(&["Node"]);
+        let seed = store.create_node(&["Node"]);
+        store.create_edge(a, b, "EDGE");
+        store.create_edge(c, seed, "EDGE");
+
+        let scores = personalized_pagerank(&store, &[seed], 0.85, 100, 1e-9);
+
+        // Dangling mass returns to the seed instead of spreading out
+        assert!((scores[&seed] - 1.0).abs() < 1e-6);
+        assert!(scores[&a].abs() < 1e-6);
+        assert!(scores[&b].abs() < 1e-6);
+        assert!(scores[&c].abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_personalized_pagerank_without_valid_seeds() {
+        let store = create_pagerank_graph();
+        let uniform = pagerank(&store, 0.85, 100, 1e-6);
+        let fallback = personalized_pagerank(&store, &[NodeId::new(99)], 0.85, 100, 1e-6);
+
+        for (node, score) in &uniform {
+            assert!((fallback[node] - score).abs() < 1e-12);
+        }
+    }
+
     #[test]
     fn test_betweenness_centrality() {
         let store = create_test_graph();
@@ -767,6 +1077,100 @@ mod tests {
         }
     }
 
+    /// Two stars whose hubs are joined by a short path, all edges bidirectional.
+    fn create_barbell_stars() -> LpgStore {
+        let store = LpgStore::new();
+        let nodes: Vec<NodeId> = (0..24).map(|_| store.create_node(&["Node"])).collect();
+        let link = |a: usize, b: usize| {
+            store.create_edge(nodes[a], nodes[b], "EDGE");
+            store.create_edge(nodes[b], nodes[a], "EDGE");
+        };
+
+        // Hubs 0 and 1, bridged by 0 - 22 - 23 - 1
+        for leaf in 2..12 {
+            link(0, leaf);
+        }
+        for leaf in 12..22 {
+            link(1, leaf);
+        }
+        link(0, 22);
+        link(22, 23);
+        link(23, 1);
+        // A few extra edges among leaves
+        link(2, 3);
+        link(12, 13);
+        link(5, 6);
+
+        store
+    }
+
+    /// Spearman rank correlation between two score maps over the same nodes.
+    fn spearman(a: &FxHashMap<NodeId, f64>, b: &FxHashMap<NodeId, f64>) -> f64 {
+        let ranks = |scores: &FxHashMap<NodeId, f64>| {
+            let mut sorted: Vec<(NodeId, f64)> = scores.iter().map(|(&k, &v)| (k, v)).collect();
+            sorted.sort_by(|x, y| x.1.total_cmp(&y.1).then(x.0.cmp(&y.0)));
+            // Average ranks over ties
+            let mut ranks: FxHashMap<NodeId, f64> = FxHashMap::default();
+            let mut i = 0;
+            while i < sorted.len() {
+                let mut j = i;
+                while j + 1 < sorted.len() && (sorted[j + 1].1 - sorted[i].1).abs() < 1e-9 {
+                    j += 1;
+                }
+                let rank = (i + j) as f64 / 2.0;
+                for entry in &sorted[i..=j] {
+                    ranks.insert(entry.0, rank);
+                }
+                i = j + 1;
+            }
+            ranks
+        };
+
+        let (ra, rb) = (ranks(a), ranks(b));
+        let n = ra.len() as f64;
+        let mean = (n - 1.0) / 2.0;
+        let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+        for (node, &x) in &ra {
+            let y = rb[node];
+            cov += (x - mean) * (y - mean);
+            var_a += (x - mean).powi(2);
+            var_b += (y - mean).powi(2);
+        }
+        cov / (var_a * var_b).sqrt()
+    }
+
+    #[test]
+    fn test_approximate_betweenness_ranking() {
+        let store = create_barbell_stars();
+        let exact = betweenness_centrality(&store, false);
+        let approx = approximate_betweenness(&store, 12, 42);
+
+        assert_eq!(approx.len(), exact.len());
+        assert!(spearman(&exact, &approx) > 0.8);
+
+        // The hubs and bridge path still dominate the estimate
+        let mut ranked: Vec<(NodeId, f64)> = approx.into_iter().collect();
+        ranked.sort_by(|x, y| y.1.total_cmp(&x.1));
+        let top: FxHashSet<u64> = ranked[..4].iter().map(|(n, _)| n.0).collect();
+        assert_eq!(top, [0, 1, 22, 23].into_iter().collect());
+    }
+
+    #[test]
+    fn test_approximate_betweenness_reproducible() {
+        let store = create_barbell_stars();
+        let first = approximate_betweenness(&store, 6, 7);
+        let second = approximate_betweenness(&store, 6, 7);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_approximate_betweenness_full_sample_is_exact() {
+        let store = create_test_graph();
+        let exact = betweenness_centrality(&store, false);
+        assert_eq!(approximate_betweenness(&store, 5, 1), exact);
+        assert_eq!(approximate_betweenness(&store, 100, 1), exact);
+    }
+
     #[test]
     fn test_closeness_centrality() {
         let store = create_test_graph();
@@ -1,8 +1,11 @@
-//! Community detection algorithms: Louvain, Label Propagation.
+//! Community detection algorithms: Louvain, Label Propagation, and
+//! modularity scoring.
 //!
 //! These algorithms identify clusters or communities of nodes that are
 //! more densely connected to each other than to the rest of the graph.
 
+use std::collections::HashMap;
+use std::hash::BuildHasher;
 use std::sync::OnceLock;
 
 use grafeo_common::types::{NodeId, Value};
@@ -12,7 +15,7 @@ use grafeo_core::graph::Direction;
 use grafeo_core::graph::lpg::LpgStore;
 
 use super::super::{AlgorithmResult, ParameterDef, ParameterType, Parameters};
-use super::traits::{ComponentResultBuilder, GraphAlgorithm};
+use super::traits::{ComponentResultBuilder, EdgeWeight, GraphAlgorithm};
 
 // ============================================================================
 // Label Propagation
@@ -126,6 +129,17 @@ pub fn label_propagation(store: &LpgStore, max_iterations: usize) -> FxHashMap<N
 // Louvain Algorithm
 // ============================================================================
 
+/// Partition found at one aggregation level of the Louvain algorithm.
+#[derive(Debug, Clone)]
+pub struct LouvainLevel {
+    /// Community assignment for each node.
+    pub communities: FxHashMap<NodeId, u64>,
+    /// Modularity of this partition.
+    pub modularity: f64,
+    /// Number of communities at this level.
+    pub num_communities: usize,
+}
+
 /// Result of Louvain algorithm.
 #[derive(Debug, Clone)]
 pub struct LouvainResult {
@@ -135,6 +149,12 @@ pub struct LouvainResult {
     pub modularity: f64,
     /// Number of communities detected.
     pub num_communities: usize,
+    /// Partition after each aggregation level, finest first.
+    ///
+    /// Each level merges communities of the one before, and modularity never
+    /// decreases from one level to the next. The last level is the final
+    /// partition. Empty for an empty graph.
+    pub levels: Vec<LouvainLevel>,
 }
 
 /// Detects communities using the Louvain algorithm.
@@ -144,14 +164,20 @@ pub struct LouvainResult {
 /// 1. Local optimization: Move nodes to neighboring communities if it increases modularity
 /// 2. Aggregation: Build a new graph where communities become super-nodes
 ///
+/// Each repetition is one level of the result, so coarser partitions can be
+/// picked from [`LouvainResult::levels`]. Edges are treated as undirected
+/// with weight 1.0.
+///
 /// # Arguments
 ///
 /// * `store` - The graph store
-/// * `resolution` - Resolution parameter (higher = smaller communities, default 1.0)
+/// * `resolution` - Resolution parameter (higher = smaller communities, default 1.0).
+///   It scales the expected-edges term of modularity,
+///   `Q = Σ_c [in_c / 2m - resolution · (tot_c / 2m)²]`.
 ///
 /// # Returns
 ///
-/// Community assignments and modularity score.
+/// Community assignments and modularity score, per level and final.
 ///
 /// # Complexity
 ///
@@ -165,26 +191,100 @@ pub fn louvain(store: &LpgStore, resolution: f64) -> LouvainResult {
             communities: FxHashMap::default(),
             modularity: 0.0,
             num_communities: 0,
+            levels: Vec::new(),
         };
     }
 
+    let (mut weights, total_weight) = undirected_weights(store, &nodes, EdgeWeight::new(None));
+
+    // Handle isolated nodes
+    if total_weight == 0.0 {
+        let communities: FxHashMap<NodeId, u64> = nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, &node)| (node, idx as u64))
+            .collect();
+        let level = LouvainLevel {
+            communities: communities.clone(),
+            modularity: 0.0,
+            num_communities: n,
+        };
+        return LouvainResult {
+            communities,
+            modularity: 0.0,
+            num_communities: n,
+            levels: vec![level],
+        };
+    }
+
+    // membership[i] = super-node of original node i in the current graph
+    let mut membership: Vec<usize> = (0..n).collect();
+    let mut levels: Vec<LouvainLevel> = Vec::new();
+
+    loop {
+        // Phase 1: Local optimization
+        let (community, num_communities) = louvain_local_moves(&weights, total_weight, resolution);
+
+        // A level that merges nothing only repeats the previous one
+        if num_communities == weights.len() && !levels.is_empty() {
+            break;
+        }
+
+        for super_node in &mut membership {
+            *super_node = community[*super_node];
+        }
+        levels.push(LouvainLevel {
+            communities: nodes
+                .iter()
+                .zip(&membership)
+                .map(|(&node, &c)| (node, c as u64))
+                .collect(),
+            modularity: compute_modularity(&weights, &community, total_weight, resolution),
+            num_communities,
+        });
+
+        if num_communities == weights.len() {
+            break;
+        }
+
+        // Phase 2: Aggregation
+        weights = aggregate_communities(&weights, &community, num_communities);
+    }
+
+    let last = levels.last().expect("at least one level").clone();
+    LouvainResult {
+        communities: last.communities,
+        modularity: last.modularity,
+        num_communities: last.num_communities,
+        levels,
+    }
+}
+
+/// Builds the weighted adjacency of `nodes`, treating edges as undirected.
+///
+/// `weights[i][j]` is the summed weight of edges between `nodes[i]` and
+/// `nodes[j]` in either direction, and a self-loop counts twice towards
+/// `weights[i][i]`, so each row sums to the node's degree. Also returns the
+/// total edge weight `m`.
+fn undirected_weights(
+    store: &LpgStore,
+    nodes: &[NodeId],
+    edge_weights: EdgeWeight<'_>,
+) -> (Vec<FxHashMap<usize, f64>>, f64) {
     // Build node index mapping
     let mut node_to_idx: FxHashMap<NodeId, usize> = FxHashMap::default();
     for (idx, &node) in nodes.iter().enumerate() {
         node_to_idx.insert(node, idx);
     }
 
-    // Build adjacency with weights (for undirected graph)
-    // weights[i][j] = weight of edge between nodes i and j
-    let mut weights: Vec<FxHashMap<usize, f64>> = vec![FxHashMap::default(); n];
+    let mut weights: Vec<FxHashMap<usize, f64>> = vec![FxHashMap::default(); nodes.len()];
     let mut total_weight = 0.0;
 
-    for &node in &nodes {
-        let i = *node_to_idx.get(&node).unwrap();
-        for (neighbor, _edge_id) in store.edges_from(node, Direction::Outgoing) {
+    for (i, &node) in nodes.iter().enumerate() {
+        for (neighbor, edge_id) in store.edges_from(node, Direction::Outgoing) {
             if let Some(&j) = node_to_idx.get(&neighbor) {
                 // For undirected: add weight to both directions
-                let w = 1.0; // Could extract from edge property
+                let w = edge_weights.value(store, edge_id);
                 *weights[i].entry(j).or_insert(0.0) += w;
                 *weights[j].entry(i).or_insert(0.0) += w;
                 total_weight += w;
@@ -192,119 +292,95 @@ pub fn louvain(store: &LpgStore, resolution: f64) -> LouvainResult {
         }
     }
 
-    // Handle isolated nodes
-    if total_weight == 0.0 {
-        let communities: FxHashMap<NodeId, u64> = nodes
-            .iter()
-            .enumerate()
-            .map(|(idx, &node)| (node, idx as u64))
-            .collect();
-        return LouvainResult {
-            communities,
-            modularity: 0.0,
-            num_communities: n,
-        };
-    }
+    (weights, total_weight)
+}
+
+/// Repeatedly moves each node to the neighboring community with the best
+/// modularity gain until no move improves it.
+///
+/// Returns each node's community, numbered from 0 in order of first
+/// appearance, and the number of communities.
+fn louvain_local_moves(
+    weights: &[FxHashMap<usize, f64>],
+    total_weight: f64,
+    resolution: f64,
+) -> (Vec<usize>, usize) {
+    let n = weights.len();
+    let m2 = 2.0 * total_weight;
 
     // Compute node degrees (sum of incident edge weights)
-    let degrees: Vec<f64> = (0..n).map(|i| weights[i].values().sum()).collect();
+    let degrees: Vec<f64> = weights.iter().map(|w| w.values().sum()).collect();
 
     // Initialize: each node in its own community
     let mut community: Vec<usize> = (0..n).collect();
+    let mut community_total: Vec<f64> = degrees.clone();
 
-    // Community internal weights and total weights
-    let mut community_internal: FxHashMap<usize, f64> = FxHashMap::default();
-    let mut community_total: FxHashMap<usize, f64> = FxHashMap::default();
-
-    for i in 0..n {
-        community_total.insert(i, degrees[i]);
-        community_internal.insert(i, weights[i].get(&i).copied().unwrap_or(0.0));
-    }
-
-    // Phase 1: Local optimization
     let mut improved = true;
     while improved {
         improved = false;
 
         for i in 0..n {
             let current_comm = community[i];
+            let ki = degrees[i];
 
             // Compute links to each neighboring community
             let mut comm_links: FxHashMap<usize, f64> = FxHashMap::default();
             for (&j, &w) in &weights[i] {
-                let c = community[j];
-                *comm_links.entry(c).or_insert(0.0) += w;
-            }
-
-            // Try moving to each neighboring community
-            let mut best_delta = 0.0;
-            let mut best_comm = current_comm;
-
-            // Remove node from current community for delta calculation
-            let ki = degrees[i];
-            let ki_in = comm_links.get(&current_comm).copied().unwrap_or(0.0);
-
-            for (&target_comm, &k_i_to_comm) in &comm_links {
-                if target_comm == current_comm {
-                    continue;
+                if j != i {
+                    *comm_links.entry(community[j]).or_insert(0.0) += w;
                 }
+            }
 
-                let sigma_tot = *community_total.get(&target_comm).unwrap_or(&0.0);
-
-                // Modularity delta for moving to target_comm
-                let delta = resolution
-                    * (k_i_to_comm
-                        - ki_in
-                        - ki * (sigma_tot - community_total.get(&current_comm).unwrap_or(&0.0)
-                            + ki)
-                            / (2.0 * total_weight));
+            // Take the node out, then put it back where it gains the most:
+            // joining c gains k_i,c - resolution * tot_c * k_i / 2m
+            community_total[current_comm] -= ki;
+            let gain =
+                |comm: usize, links: f64| links - resolution * community_total[comm] * ki / m2;
 
-                if delta > best_delta {
-                    best_delta = delta;
+            let mut best_comm = current_comm;
+            let mut best_gain = gain(
+                current_comm,
+                comm_links.get(&current_comm).copied().unwrap_or(0.0),
+            );
+            for (&target_comm, &links) in &comm_links {
+                let target_gain = gain(target_comm, links);
+                if target_gain > best_gain {
+                    best_gain = target_gain;
                     best_comm = target_comm;
                 }
             }
 
+            community_total[best_comm] += ki;
             if best_comm != current_comm {
-                // Move node to best community
-                // Update community statistics
-                *community_total.entry(current_comm).or_insert(0.0) -= ki;
-                *community_internal.entry(current_comm).or_insert(0.0) -=
-                    2.0 * ki_in + weights[i].get(&i).copied().unwrap_or(0.0);
-
                 community[i] = best_comm;
-
-                *community_total.entry(best_comm).or_insert(0.0) += ki;
-                let k_i_best = comm_links.get(&best_comm).copied().unwrap_or(0.0);
-                *community_internal.entry(best_comm).or_insert(0.0) +=
-                    2.0 * k_i_best + weights[i].get(&i).copied().unwrap_or(0.0);
-
                 improved = true;
             }
         }
     }
 
     // Normalize community IDs
-    let unique_comms: FxHashSet<usize> = community.iter().copied().collect();
-    let mut comm_map: FxHashMap<usize, u64> = FxHashMap::default();
-    for (idx, c) in unique_comms.iter().enumerate() {
-        comm_map.insert(*c, idx as u64);
+    let mut comm_map: FxHashMap<usize, usize> = FxHashMap::default();
+    for c in &mut community {
+        let next = comm_map.len();
+        *c = *comm_map.entry(*c).or_insert(next);
     }
+    (community, comm_map.len())
+}
 
-    let communities: FxHashMap<NodeId, u64> = nodes
-        .iter()
-        .enumerate()
-        .map(|(i, &node)| (node, *comm_map.get(&community[i]).unwrap()))
-        .collect();
-
-    // Compute final modularity
-    let modularity = compute_modularity(&weights, &community, total_weight, resolution);
-
-    LouvainResult {
-        communities,
-        modularity,
-        num_communities: unique_comms.len(),
+/// Builds the graph whose nodes are the given communities, summing the
+/// weights between them; weights inside a community become self-loops.
+fn aggregate_communities(
+    weights: &[FxHashMap<usize, f64>],
+    community: &[usize],
+    num_communities: usize,
+) -> Vec<FxHashMap<usize, f64>> {
+    let mut aggregated: Vec<FxHashMap<usize, f64>> = vec![FxHashMap::default(); num_communities];
+    for (i, neighbors) in weights.iter().enumerate() {
+        for (&j, &w) in neighbors {
+            *aggregated[community[i]].entry(community[j]).or_insert(0.0) += w;
+        }
     }
+    aggregated
 }
 
 /// Computes the modularity of a community assignment.
@@ -314,26 +390,78 @@ fn compute_modularity(
     total_weight: f64,
     resolution: f64,
 ) -> f64 {
-    let n = community.len();
     let m2 = 2.0 * total_weight;
 
     if m2 == 0.0 {
         return 0.0;
     }
 
-    let degrees: Vec<f64> = (0..n).map(|i| weights[i].values().sum()).collect();
-
-    let mut modularity = 0.0;
-
-    for i in 0..n {
-        for (&j, &a_ij) in &weights[i] {
+    // Weight inside each community and total degree of its members
+    let mut internal: FxHashMap<usize, f64> = FxHashMap::default();
+    let mut totals: FxHashMap<usize, f64> = FxHashMap::default();
+    for (i, neighbors) in weights.iter().enumerate() {
+        for (&j, &w) in neighbors {
             if community[i] == community[j] {
-                modularity += a_ij - resolution * degrees[i] * degrees[j] / m2;
+                *internal.entry(community[i]).or_insert(0.0) += w;
             }
+            *totals.entry(community[i]).or_insert(0.0) += w;
         }
     }
 
-    modularity / m2
+    totals
+        .iter()
+        .map(|(c, &tot)| {
+            internal.get(c).copied().unwrap_or(0.0) / m2 - resolution * (tot / m2) * (tot / m2)
+        })
+        .sum()
+}
+
+/// Computes the modularity of a community assignment.
+///
+/// Modularity compares the weight of edges inside communities with what a
+/// random graph with the same degrees would have there:
+/// `Q = Σ_c [in_c / 2m - (tot_c / 2m)²]`. It ranges from -1/2 to 1, and
+/// higher means denser communities. Edges are treated as undirected, as in
+/// [`louvain`], so scores are comparable to [`LouvainResult::modularity`]
+/// at resolution 1.0.
+///
+/// # Arguments
+///
+/// * `store` - The graph store
+/// * `partition` - Community of each node; nodes not listed are each a
+///   community of their own
+/// * `weight_property` - Optional property name for edge weights (defaults to 1.0)
+///
+/// # Returns
+///
+/// The modularity score, or 0.0 for a graph without edges.
+///
+/// # Complexity
+///
+/// O(V + E)
+pub fn modularity<S: BuildHasher>(
+    store: &LpgStore,
+    partition: &HashMap<NodeId, usize, S>,
+    weight_property: Option<&str>,
+) -> f64 {
+    let nodes = store.node_ids();
+    let (weights, total_weight) =
+        undirected_weights(store, &nodes, EdgeWeight::new(weight_property));
+
+    // Unlisted nodes get labels past every listed one
+    let mut next_label = partition.values().max().map_or(0, |&max| max + 1);
+    let community: Vec<usize> = nodes
+        .iter()
+        .map(|node| {
+            partition.get(node).copied().unwrap_or_else(|| {
+                let label = next_label;
+                next_label += 1;
+                label
+            })
+        })
+        .collect();
+
+    compute_modularity(&weights, &community, total_weight, 1.0)
 }
 
 /// Returns the number of communities detected.
@@ -600,6 +728,200 @@ mod tests {
         assert!(!result_high.communities.is_empty());
     }
 
+    /// `count` cliques of `size` nodes, each linked to the next in a ring
+    /// when `ring` is set.
+    fn create_cliques(count: usize, size: usize, ring: bool) -> (LpgStore, Vec<NodeId>) {
+        let store = LpgStore::new();
+        let nodes: Vec<NodeId> = (0..count * size)
+            .map(|_| store.create_node(&["Node"]))
+            .collect();
+        for c in 0..count {
+            for i in 0..size {
+                for j in (i + 1)..size {
+                    store.create_edge(nodes[c * size + i], nodes[c * size + j], "EDGE");
+                }
+            }
+            if ring {
+                let next = (c + 1) % count;
+                store.create_edge(nodes[c * size], nodes[next * size + 1], "EDGE");
+            }
+        }
+        (store, nodes)
+    }
+
+    #[test]
+    fn test_louvain_levels() {
+        // Modularity favours merging neighbouring cliques in a long ring,
+        // so this takes more than one level
+        let (store, nodes) = create_cliques(16, 4, true);
+        let result = louvain(&store, 1.0);
+
+        assert!(result.levels.len() >= 2, "{} levels", result.levels.len());
+        for pair in result.levels.windows(2) {
+            let (finer, coarser) = (&pair[0], &pair[1]);
+            assert!(coarser.modularity >= finer.modularity);
+            assert!(coarser.num_communities < finer.num_communities);
+            // Nodes together at one level stay together at the next
+            for &a in &nodes {
+                for &b in &nodes {
+                    if finer.communities[&a] == finer.communities[&b] {
+                        assert_eq!(coarser.communities[&a], coarser.communities[&b]);
+                    }
+                }
+            }
+        }
+        assert_eq!(result.levels[0].num_communities, 16);
+
+        let last = result.levels.last().unwrap();
+        assert_eq!(result.communities, last.communities);
+        assert_eq!(result.modularity, last.modularity);
+        assert_eq!(result.num_communities, last.num_communities);
+    }
+
+    #[test]
+    fn test_louvain_single_level_disconnected() {
+        // Separate cliques: the first pass finds them and nothing can merge
+        let (store, nodes) = create_cliques(2, 4, false);
+        let result = louvain(&store, 1.0);
+
+        assert_eq!(result.levels.len(), 1);
+        assert_eq!(result.num_communities, 2);
+        assert!((result.modularity - 0.5).abs() < 1e-12);
+        for i in 0..4 {
+            assert_eq!(result.communities[&nodes[i]], result.communities[&nodes[0]]);
+            assert_eq!(
+                result.communities[&nodes[4 + i]],
+                result.communities[&nodes[4]]
+            );
+        }
+        assert_ne!(result.communities[&nodes[0]], result.communities[&nodes[4]]);
+
+        // Isolated nodes are a single level of singletons
+        let store = LpgStore::new();
+        store.create_node(&["Node"]);
+        store.create_node(&["Node"]);
+        let result = louvain(&store, 1.0);
+        assert_eq!(result.levels.len(), 1);
+        assert_eq!(result.levels[0].num_communities, 2);
+    }
+
+    #[test]
+    fn test_louvain_resolution_changes_community_count() {
+        let store = create_two_cliques_graph();
+
+        let coarse = louvain(&store, 0.1).num_communities;
+        let default = louvain(&store, 1.0).num_communities;
+        let fine = louvain(&store, 5.0).num_communities;
+
+        assert_eq!(default, 2);
+        assert!(coarse < default, "{coarse} communities at resolution 0.1");
+        assert!(fine > default, "{fine} communities at resolution 5.0");
+    }
+
+    /// Triangles 0-1-2 and 3-4-5 joined by a 2-3 bridge of weight `bridge`.
+    fn create_bridged_triangles(bridge: i64) -> (LpgStore, Vec<NodeId>) {
+        let store = LpgStore::new();
+        let n: Vec<NodeId> = (0..6).map(|_| store.create_node(&["Node"])).collect();
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)] {
+            store.create_edge_with_props(n[a], n[b], "EDGE", [("weight", Value::Int64(1))]);
+        }
+        store.create_edge_with_props(n[3], n[2], "EDGE", [("weight", Value::Int64(bridge))]);
+        (store, n)
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-12, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_modularity_known_partitions() {
+        // m = 7; degrees 2, 2, 3, 3, 2, 2
+        let (store, n) = create_bridged_triangles(1);
+        let n = &n;
+        let partition = |groups: &[&[usize]]| -> HashMap<NodeId, usize> {
+            groups
+                .iter()
+                .enumerate()
+                .flat_map(|(c, group)| group.iter().map(move |&i| (n[i], c)))
+                .collect()
+        };
+
+        // Each triangle: in = 6, tot = 7, so 2 * (6/14 - 1/4)
+        let triangles = partition(&[&[0, 1, 2], &[3, 4, 5]]);
+        assert_close(modularity(&store, &triangles, None), 5.0 / 14.0);
+
+        // One community covers everything: 14/14 - 1
+        let whole = partition(&[&[0, 1, 2, 3, 4, 5]]);
+        assert_close(modularity(&store, &whole, None), 0.0);
+
+        // Singletons: -(4 + 4 + 9 + 9 + 4 + 4) / 196
+        let singletons = partition(&[&[0], &[1], &[2], &[3], &[4], &[5]]);
+        assert_close(modularity(&store, &singletons, None), -17.0 / 98.0);
+
+        // Node 5 unlisted is its own community:
+        // (6/14 - 49/196) + (2/14 - 25/196) + (0 - 4/196)
+        let partial = partition(&[&[0, 1, 2], &[3, 4]]);
+        assert_close(modularity(&store, &partial, None), 17.0 / 98.0);
+
+        // Louvain scores the same partition the same way
+        let result = louvain(&store, 1.0);
+        let found: HashMap<NodeId, usize> = result
+            .communities
+            .iter()
+            .map(|(&node, &c)| (node, c as usize))
+            .collect();
+        assert_close(modularity(&store, &found, None), result.modularity);
+    }
+
+    #[test]
+    fn test_modularity_weighted() {
+        // Bridge weight 5: m = 11; degrees 2, 2, 7, 7, 2, 2
+        let (store, n) = create_bridged_triangles(5);
+        let triangles: HashMap<NodeId, usize> = n
+            .iter()
+            .enumerate()
+            .map(|(i, &node)| (node, i / 3))
+            .collect();
+
+        // 2 * (6/22 - (11/22)²)
+        assert_close(modularity(&store, &triangles, Some("weight")), 1.0 / 22.0);
+        // Unweighted, the bridge counts 1 like any other edge
+        assert_close(modularity(&store, &triangles, None), 5.0 / 14.0);
+    }
+
+    #[test]
+    fn test_modularity_edge_cases() {
+        let store = LpgStore::new();
+        assert_eq!(modularity(&store, &HashMap::new(), None), 0.0);
+
+        // Nodes but no edges
+        let a = store.create_node(&["Node"]);
+        let b = store.create_node(&["Node"]);
+        let partition: HashMap<NodeId, usize> = [(a, 0), (b, 0)].into_iter().collect();
+        assert_eq!(modularity(&store, &partition, None), 0.0);
+
+        // A single community always scores 0 on a graph with edges
+        store.create_edge(a, b, "EDGE");
+        assert_close(modularity(&store, &partition, None), 0.0);
+        // Edge direction doesn't matter
+        let (forward, n) = create_bridged_triangles(1);
+        let reversed = LpgStore::new();
+        let r: Vec<NodeId> = (0..6).map(|_| reversed.create_node(&["Node"])).collect();
+        for (x, y) in [(1, 0), (2, 1), (0, 2), (4, 3), (5, 4), (3, 5), (2, 3)] {
+            reversed.create_edge(r[x], r[y], "EDGE");
+        }
+        let halves = |ids: &[NodeId]| -> HashMap<NodeId, usize> {
+            ids.iter()
+                .enumerate()
+                .map(|(i, &node)| (node, i / 3))
+                .collect()
+        };
+        assert_close(
+            modularity(&forward, &halves(&n), None),
+            modularity(&reversed, &halves(&r), None),
+        );
+    }
+
     #[test]
     fn test_community_count() {
         let mut communities: FxHashMap<NodeId, u64> = FxHashMap::default();
@@ -93,39 +93,7 @@ impl UnionFind {
 ///
 /// A map from node ID to component ID.
 pub fn connected_components(store: &LpgStore) -> FxHashMap<NodeId, u64> {
-    let node_ids = store.node_ids();
-    let n = node_ids.len();
-
-    if n == 0 {
-        return FxHashMap::default();
-    }
-
-    // Map NodeId -> index
-    let mut node_to_idx: FxHashMap<NodeId, usize> = FxHashMap::default();
-    for (idx, &node) in node_ids.iter().enumerate() {
-        node_to_idx.insert(node, idx);
-    }
-
-    let mut uf = UnionFind::new(n);
-
-    // Process all edges (treating graph as undirected)
-    for &node in &node_ids {
-        let idx = node_to_idx[&node];
-
-        // Outgoing edges
-        for (neighbor, _) in store.edges_from(node, Direction::Outgoing) {
-            if let Some(&neighbor_idx) = node_to_idx.get(&neighbor) {
-                uf.union(idx, neighbor_idx);
-            }
-        }
-
-        // Incoming edges (for weakly connected)
-        for (neighbor, _) in store.edges_from(node, Direction::Incoming) {
-            if let Some(&neighbor_idx) = node_to_idx.get(&neighbor) {
-                uf.union(idx, neighbor_idx);
-            }
-        }
-    }
+    let (node_ids, mut uf) = union_edge_endpoints(store);
 
     // Build result: map each node to its component
     let mut root_to_component: FxHashMap<usize, u64> = FxHashMap::default();
@@ -145,6 +113,63 @@ pub fn connected_components(store: &LpgStore) -> FxHashMap<NodeId, u64> {
     result
 }
 
+/// Finds weakly connected components in a directed graph.
+///
+/// Edge direction is ignored: two nodes share a component if any path
+/// connects them when every edge is treated as undirected.
+///
+/// # Returns
+///
+/// The components, each sorted by node ID, ordered by their smallest node ID.
+pub fn weakly_connected_components(store: &LpgStore) -> Vec<Vec<NodeId>> {
+    let (node_ids, mut uf) = union_edge_endpoints(store);
+
+    let mut root_to_component: FxHashMap<usize, usize> = FxHashMap::default();
+    let mut components: Vec<Vec<NodeId>> = Vec::new();
+    for (idx, &node) in node_ids.iter().enumerate() {
+        let root = uf.find(idx);
+        let component = *root_to_component.entry(root).or_insert_with(|| {
+            components.push(Vec::new());
+            components.len() - 1
+        });
+        components[component].push(node);
+    }
+
+    for component in &mut components {
+        component.sort_unstable();
+    }
+    components.sort_unstable_by_key(|component| component[0]);
+    components
+}
+
+/// Unions the endpoints of every edge, ignoring direction.
+///
+/// Both outgoing and incoming adjacency are consulted so that every edge
+/// touching a node is seen from that node.
+///
+/// Returns the node IDs and the Union-Find indexed by their position.
+fn union_edge_endpoints(store: &LpgStore) -> (Vec<NodeId>, UnionFind) {
+    let node_ids = store.node_ids();
+
+    // Map NodeId -> index
+    let mut node_to_idx: FxHashMap<NodeId, usize> = FxHashMap::default();
+    for (idx, &node) in node_ids.iter().enumerate() {
+        node_to_idx.insert(node, idx);
+    }
+
+    let mut uf = UnionFind::new(node_ids.len());
+
+    for (idx, &node) in node_ids.iter().enumerate() {
+        for (neighbor, _) in store.edges_from(node, Direction::Both) {
+            if let Some(&neighbor_idx) = node_to_idx.get(&neighbor) {
+                uf.union(idx, neighbor_idx);
+            }
+        }
+    }
+
+    (node_ids, uf)
+}
+
 /// Returns the number of connected components.
 pub fn connected_component_count(store: &LpgStore) -> usize {
     let components = connected_components(store);
@@ -298,12 +323,15 @@ pub fn strongly_connected_component_count(store: &LpgStore) -> usize {
 ///
 /// # Returns
 ///
-/// `Some(order)` if the graph is a DAG, `None` if there's a cycle.
-pub fn topological_sort(store: &LpgStore) -> Option<Vec<NodeId>> {
+/// `Ok(order)` if the graph is a DAG. Otherwise `Err(cycle)` with the nodes
+/// of one directed cycle in edge order: each node has an edge to the next,
+/// and the last to the first. A self-loop is a cycle of one node. If there
+/// are several cycles, any one of them is returned.
+pub fn topological_sort(store: &LpgStore) -> std::result::Result<Vec<NodeId>, Vec<NodeId>> {
     let node_ids = store.node_ids();
 
     if node_ids.is_empty() {
-        return Some(Vec::new());
+        return Ok(Vec::new());
     }
 
     // Compute in-degrees
@@ -342,15 +370,47 @@ pub fn topological_sort(store: &LpgStore) -> Option<Vec<NodeId>> {
 
     // Check for cycle
     if result.len() == node_ids.len() {
-        Some(result)
+        Ok(result)
     } else {
-        None // Cycle detected
+        Err(find_cycle(store, &in_degree))
+    }
+}
+
+/// Finds a cycle among the nodes Kahn's algorithm couldn't order.
+///
+/// Each of them still has an incoming edge from another of them, so walking
+/// such edges backwards must eventually revisit a node, closing a cycle.
+fn find_cycle(store: &LpgStore, in_degree: &FxHashMap<NodeId, usize>) -> Vec<NodeId> {
+    let mut predecessor: FxHashMap<NodeId, NodeId> = FxHashMap::default();
+    for (&node, _) in in_degree.iter().filter(|&(_, deg)| *deg > 0) {
+        for (neighbor, _) in store.edges_from(node, Direction::Outgoing) {
+            if in_degree.get(&neighbor).is_some_and(|&deg| deg > 0) {
+                predecessor.entry(neighbor).or_insert(node);
+            }
+        }
+    }
+
+    // Walk back until a node repeats; the walk from there on is the cycle
+    let Some(&start) = predecessor.keys().min() else {
+        return Vec::new();
+    };
+    let mut position: FxHashMap<NodeId, usize> = FxHashMap::default();
+    let mut walk = Vec::new();
+    let mut current = start;
+    while !position.contains_key(&current) {
+        position.insert(current, walk.len());
+        walk.push(current);
+        current = predecessor[&current];
     }
+
+    let mut cycle = walk.split_off(position[&current]);
+    cycle.reverse();
+    cycle
 }
 
 /// Checks if the graph is a DAG (Directed Acyclic Graph).
 pub fn is_dag(store: &LpgStore) -> bool {
-    topological_sort(store).is_some()
+    topological_sort(store).is_ok()
 }
 
 // ============================================================================
@@ -431,7 +491,7 @@ impl GraphAlgorithm for TopologicalSortAlgorithm {
 
     fn execute(&self, store: &LpgStore, _params: &Parameters) -> Result<AlgorithmResult> {
         match topological_sort(store) {
-            Some(order) => {
+            Ok(order) => {
                 let mut result =
                     AlgorithmResult::new(vec!["node_id".to_string(), "order".to_string()]);
                 for (idx, node) in order.iter().enumerate() {
@@ -439,10 +499,17 @@ impl GraphAlgorithm for TopologicalSortAlgorithm {
                 }
                 Ok(result)
             }
-            None => {
+            Err(cycle) => {
                 // Return empty result with error indication for cycles
+                let nodes: Vec<String> = cycle
+                    .iter()
+                    .chain(cycle.first())
+                    .map(|node| node.0.to_string())
+                    .collect();
                 let mut result = AlgorithmResult::new(vec!["error".to_string()]);
-                result.add_row(vec![Value::String("Graph contains a cycle".into())]);
+                result.add_row(vec![Value::String(
+                    format!("Graph contains a cycle: {}", nodes.join(" -> ")).into(),
+                )]);
                 Ok(result)
             }
         }
@@ -536,6 +603,47 @@ mod tests {
         assert_eq!(count, 2);
     }
 
+    #[test]
+    fn test_weakly_connected_components_chain() {
+        let store = LpgStore::new();
+
+        // Directed chain: 0 -> 1 -> 2
+        let n0 = store.create_node(&["Node"]);
+        let n1 = store.create_node(&["Node"]);
+        let n2 = store.create_node(&["Node"]);
+        store.create_edge(n0, n1, "EDGE");
+        store.create_edge(n1, n2, "EDGE");
+
+        let weak = weakly_connected_components(&store);
+        assert_eq!(weak, vec![vec![n0, n1, n2]]);
+
+        // No node can reach back, so each is its own strong component
+        assert_eq!(strongly_connected_component_count(&store), 3);
+    }
+
+    #[test]
+    fn test_weakly_connected_components_disconnected() {
+        let store = LpgStore::new();
+
+        // 0 <- 1, 2 -> 3, and an isolated node 4
+        let n0 = store.create_node(&["Node"]);
+        let n1 = store.create_node(&["Node"]);
+        let n2 = store.create_node(&["Node"]);
+        let n3 = store.create_node(&["Node"]);
+        let n4 = store.create_node(&["Node"]);
+        store.create_edge(n1, n0, "EDGE");
+        store.create_edge(n2, n3, "EDGE");
+
+        let weak = weakly_connected_components(&store);
+        assert_eq!(weak, vec![vec![n0, n1], vec![n2, n3], vec![n4]]);
+    }
+
+    #[test]
+    fn test_weakly_connected_components_empty() {
+        let store = LpgStore::new();
+        assert!(weakly_connected_components(&store).is_empty());
+    }
+
     #[test]
     fn test_scc_dag() {
         let store = create_dag();
@@ -556,7 +664,7 @@ mod tests {
     fn test_topological_sort_dag() {
         let store = create_dag();
         let order = topological_sort(&store);
-        assert!(order.is_some());
+        assert!(order.is_ok());
 
         let order = order.unwrap();
         assert_eq!(order.len(), 5);
@@ -572,11 +680,54 @@ mod tests {
         }
     }
 
+    /// Rotates a cycle to start at its smallest node, for comparison.
+    fn canonical_cycle(mut cycle: Vec<NodeId>) -> Vec<NodeId> {
+        let smallest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap_or(0);
+        cycle.rotate_left(smallest);
+        cycle
+    }
+
     #[test]
     fn test_topological_sort_cycle() {
         let store = create_cyclic_graph();
-        let order = topological_sort(&store);
-        assert!(order.is_none()); // Cycle detected
+        let cycle = topological_sort(&store).unwrap_err();
+        let n: Vec<NodeId> = (0..3).map(NodeId::new).collect();
+        assert_eq!(canonical_cycle(cycle), vec![n[0], n[1], n[2]]);
+    }
+
+    #[test]
+    fn test_topological_sort_cycle_among_dag() {
+        // DAG 0 -> 1 -> 2, a 3-cycle 3 -> 4 -> 5 -> 3 hanging off 2, and
+        // 5 -> 6 downstream of it
+        let store = LpgStore::new();
+        let n: Vec<NodeId> = (0..7).map(|_| store.create_node(&["Node"])).collect();
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 3), (5, 6)] {
+            store.create_edge(n[a], n[b], "EDGE");
+        }
+        let cycle = topological_sort(&store).unwrap_err();
+        assert_eq!(canonical_cycle(cycle), vec![n[3], n[4], n[5]]);
+
+        // With a second, independent cycle either one may be reported
+        store.create_edge(n[6], n[6], "EDGE");
+        let cycle = canonical_cycle(topological_sort(&store).unwrap_err());
+        assert!(
+            cycle == vec![n[3], n[4], n[5]] || cycle == vec![n[6]],
+            "{cycle:?}"
+        );
+
+        let result = TopologicalSortAlgorithm
+            .execute(&store, &Parameters::new())
+            .unwrap();
+        assert_eq!(result.columns, vec!["error".to_string()]);
+    }
+
+    #[test]
+    fn test_topological_sort_self_loop() {
+        let store = create_dag();
+        store.create_edge(NodeId::new(4), NodeId::new(4), "EDGE");
+
+        assert_eq!(topological_sort(&store), Err(vec![NodeId::new(4)]));
+        assert!(!is_dag(&store));
     }
 
     #[test]
@@ -1,4 +1,5 @@
-//! Network flow algorithms: Max Flow (Edmonds-Karp), Min Cost Max Flow.
+//! Network flow algorithms: Max Flow (Edmonds-Karp, Dinic), Min Cut,
+//! Min Cost Max Flow.
 //!
 //! These algorithms find optimal flow through a network with capacity
 //! constraints on edges.
@@ -8,48 +9,12 @@ use std::sync::OnceLock;
 
 use grafeo_common::types::{EdgeId, NodeId, Value};
 use grafeo_common::utils::error::{Error, Result};
-use grafeo_common::utils::hash::FxHashMap;
+use grafeo_common::utils::hash::{FxHashMap, FxHashSet};
 use grafeo_core::graph::Direction;
 use grafeo_core::graph::lpg::LpgStore;
 
 use super::super::{AlgorithmResult, ParameterDef, ParameterType, Parameters};
-use super::traits::GraphAlgorithm;
-
-// ============================================================================
-// Property Extraction
-// ============================================================================
-
-/// Extracts capacity from an edge property.
-fn extract_capacity(store: &LpgStore, edge_id: EdgeId, capacity_prop: Option<&str>) -> f64 {
-    if let Some(prop_name) = capacity_prop {
-        if let Some(edge) = store.get_edge(edge_id) {
-            if let Some(value) = edge.get_property(prop_name) {
-                return match value {
-                    Value::Int64(i) => *i as f64,
-                    Value::Float64(f) => *f,
-                    _ => 1.0,
-                };
-            }
-        }
-    }
-    1.0
-}
-
-/// Extracts cost from an edge property.
-fn extract_cost(store: &LpgStore, edge_id: EdgeId, cost_prop: Option<&str>) -> f64 {
-    if let Some(prop_name) = cost_prop {
-        if let Some(edge) = store.get_edge(edge_id) {
-            if let Some(value) = edge.get_property(prop_name) {
-                return match value {
-                    Value::Int64(i) => *i as f64,
-                    Value::Float64(f) => *f,
-                    _ => 0.0,
-                };
-            }
-        }
-    }
-    0.0
-}
+use super::traits::{EdgeWeight, GraphAlgorithm, MissingWeight, NegativeWeight};
 
 // ============================================================================
 // Max Flow Result
@@ -82,7 +47,12 @@ pub struct MaxFlowResult {
 ///
 /// # Returns
 ///
-/// Maximum flow value and flow assignment on edges.
+/// Maximum flow value and flow assignment on edges, or `None` if `source`
+/// or `sink` doesn't exist.
+///
+/// # Errors
+///
+/// Returns an error if any edge has a negative capacity.
 ///
 /// # Complexity
 ///
@@ -92,7 +62,27 @@ pub fn max_flow(
     source: NodeId,
     sink: NodeId,
     capacity_property: Option<&str>,
+) -> Result<Option<MaxFlowResult>> {
+    reject_negative_capacities(store, capacity_property)?;
+    Ok(edmonds_karp(store, source, sink, capacity_property))
+}
+
+/// Fails on the first negative capacity, which no flow can respect.
+fn reject_negative_capacities(store: &LpgStore, capacity_property: Option<&str>) -> Result<()> {
+    EdgeWeight::new(capacity_property)
+        .negative(NegativeWeight::Reject)
+        .check(store)
+}
+
+/// Edmonds-Karp behind [`max_flow`], without the capacity check.
+fn edmonds_karp(
+    store: &LpgStore,
+    source: NodeId,
+    sink: NodeId,
+    capacity_property: Option<&str>,
 ) -> Option<MaxFlowResult> {
+    let capacities = EdgeWeight::new(capacity_property);
+
     // Verify source and sink exist
     if store.get_node(source).is_none() || store.get_node(sink).is_none() {
         return None;
@@ -128,7 +118,7 @@ pub fn max_flow(
         let i = *node_to_idx.get(&node).unwrap();
         for (neighbor, edge_id) in store.edges_from(node, Direction::Outgoing) {
             if let Some(&j) = node_to_idx.get(&neighbor) {
-                let cap = extract_capacity(store, edge_id, capacity_property);
+                let cap = capacities.value(store, edge_id);
                 *capacity[i].entry(j).or_insert(0.0) += cap;
                 edge_map.insert((i, j), edge_id);
             }
@@ -214,6 +204,309 @@ pub fn max_flow(
     })
 }
 
+// ============================================================================
+// Dinic's Algorithm (Max Flow)
+// ============================================================================
+
+/// Computes maximum flow using Dinic's algorithm.
+///
+/// Each phase builds a level graph by BFS from the source, then pushes a
+/// blocking flow along level-increasing arcs with a depth-first search that
+/// never retries an arc it has exhausted. The source-sink distance in the
+/// residual graph grows every phase, so there are at most V phases.
+///
+/// Returns the same flow value as [`max_flow`], usually much faster: each
+/// Edmonds-Karp augmentation costs a full BFS, while a Dinic phase saturates
+/// every shortest augmenting path at once. Prefer this on dense graphs or
+/// with many augmenting paths; on unit capacities it runs in O(E√V).
+///
+/// The residual graph is built from the store once and the store is not
+/// modified. Parallel edges between the same pair of nodes are merged, their
+/// capacities summed, so `flow_edges` has one entry per connected pair, as
+/// in [`max_flow`]. Edges in opposite directions stay separate.
+///
+/// # Arguments
+///
+/// * `store` - The graph store
+/// * `source` - Source node ID
+/// * `sink` - Sink node ID
+/// * `capacity_property` - Optional property name for edge capacities (defaults to 1.0)
+///
+/// # Returns
+///
+/// Maximum flow value and flow assignment on edges, or `None` if `source`
+/// or `sink` doesn't exist.
+///
+/// # Errors
+///
+/// Returns an error if any edge has a negative capacity.
+///
+/// # Complexity
+///
+/// O(V² × E)
+pub fn max_flow_dinic(
+    store: &LpgStore,
+    source: NodeId,
+    sink: NodeId,
+    capacity_property: Option<&str>,
+) -> Result<Option<MaxFlowResult>> {
+    reject_negative_capacities(store, capacity_property)?;
+    Ok(dinic(store, source, sink, capacity_property))
+}
+
+/// Dinic's algorithm behind [`max_flow_dinic`], without the capacity check.
+fn dinic(
+    store: &LpgStore,
+    source: NodeId,
+    sink: NodeId,
+    capacity_property: Option<&str>,
+) -> Option<MaxFlowResult> {
+    let capacities = EdgeWeight::new(capacity_property);
+
+    // Verify source and sink exist
+    if store.get_node(source).is_none() || store.get_node(sink).is_none() {
+        return None;
+    }
+
+    if source == sink {
+        return Some(MaxFlowResult {
+            max_flow: 0.0,
+            flow_edges: Vec::new(),
+        });
+    }
+
+    let nodes = store.node_ids();
+    let n = nodes.len();
+
+    // Build node index mapping
+    let mut node_to_idx: FxHashMap<NodeId, usize> = FxHashMap::default();
+    for (idx, &node) in nodes.iter().enumerate() {
+        node_to_idx.insert(node, idx);
+    }
+
+    let source_idx = *node_to_idx.get(&source)?;
+    let sink_idx = *node_to_idx.get(&sink)?;
+
+    // Sum parallel edges, in a stable order
+    let mut pair_capacity: FxHashMap<(usize, usize), f64> = FxHashMap::default();
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    for (i, &node) in nodes.iter().enumerate() {
+        for (neighbor, edge_id) in store.edges_from(node, Direction::Outgoing) {
+            if let Some(&j) = node_to_idx.get(&neighbor) {
+                let cap = pair_capacity.entry((i, j)).or_insert_with(|| {
+                    pairs.push((i, j));
+                    0.0
+                });
+                *cap += capacities.value(store, edge_id);
+            }
+        }
+    }
+
+    // Residual arcs: arc 2k follows pair k, arc 2k + 1 is its reverse
+    let mut head: Vec<usize> = Vec::with_capacity(2 * pairs.len());
+    let mut residual: Vec<f64> = Vec::with_capacity(2 * pairs.len());
+    let mut arcs_from: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for &(i, j) in &pairs {
+        arcs_from[i].push(head.len());
+        head.push(j);
+        residual.push(pair_capacity[&(i, j)]);
+        arcs_from[j].push(head.len());
+        head.push(i);
+        residual.push(0.0);
+    }
+
+    let mut total_flow = 0.0;
+    let mut level: Vec<usize> = vec![usize::MAX; n];
+    let mut next_arc: Vec<usize> = vec![0; n];
+
+    loop {
+        // BFS to build the level graph
+        level.fill(usize::MAX);
+        level[source_idx] = 0;
+        let mut queue: VecDeque<usize> = VecDeque::from([source_idx]);
+        while let Some(u) = queue.pop_front() {
+            for &arc in &arcs_from[u] {
+                let v = head[arc];
+                if level[v] == usize::MAX && residual[arc] > 1e-9 {
+                    level[v] = level[u] + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        // Sink unreachable: the flow is maximum
+        if level[sink_idx] == usize::MAX {
+            break;
+        }
+
+        // Blocking flow by iterative DFS; `path` holds the arcs taken
+        next_arc.fill(0);
+        let mut path: Vec<usize> = Vec::new();
+        let mut u = source_idx;
+        loop {
+            if u == sink_idx {
+                let path_flow = path
+                    .iter()
+                    .map(|&arc| residual[arc])
+                    .fold(f64::INFINITY, f64::min);
+                for &arc in &path {
+                    residual[arc] -= path_flow;
+                    residual[arc ^ 1] += path_flow;
+                }
+                total_flow += path_flow;
+
+                // Resume from the tail of the first saturated arc
+                let saturated = path
+                    .iter()
+                    .position(|&arc| residual[arc] <= 1e-9)
+                    .unwrap_or(0);
+                path.truncate(saturated);
+                u = path.last().map_or(source_idx, |&arc| head[arc]);
+                continue;
+            }
+
+            // Advance along the next usable arc
+            let mut advanced = false;
+            while next_arc[u] < arcs_from[u].len() {
+                let arc = arcs_from[u][next_arc[u]];
+                let v = head[arc];
+                if residual[arc] > 1e-9 && level[v] == level[u] + 1 {
+                    path.push(arc);
+                    u = v;
+                    advanced = true;
+                    break;
+                }
+                next_arc[u] += 1;
+            }
+
+            // Dead end: retreat and skip the arc that led here
+            if !advanced {
+                let Some(arc) = path.pop() else {
+                    break;
+                };
+                u = head[arc ^ 1];
+                next_arc[u] += 1;
+            }
+        }
+    }
+
+    // Extract flow on original edges
+    let flow_edges: Vec<(NodeId, NodeId, f64)> = pairs
+        .iter()
+        .enumerate()
+        .filter_map(|(k, &(i, j))| {
+            let flow = pair_capacity[&(i, j)] - residual[2 * k];
+            (flow > 1e-9).then(|| (nodes[i], nodes[j], flow))
+        })
+        .collect();
+
+    Some(MaxFlowResult {
+        max_flow: total_flow,
+        flow_edges,
+    })
+}
+
+// ============================================================================
+// Minimum Cut
+// ============================================================================
+
+/// Finds a minimum `source`-`sink` cut.
+///
+/// Runs [`max_flow`], then collects every node still reachable from `source`
+/// in the residual graph. Those nodes form the source side of the cut, and
+/// the edges leaving them are the cut edges. By the max-flow min-cut
+/// theorem, the capacities of the cut edges sum to the maximum flow, and
+/// each cut edge is saturated.
+///
+/// Parallel edges appear once per pair of nodes, and edges without positive
+/// capacity are not listed.
+///
+/// # Arguments
+///
+/// * `store` - The graph store
+/// * `source` - Source node ID
+/// * `sink` - Sink node ID
+/// * `capacity_property` - Optional property name for edge capacities (defaults to 1.0)
+///
+/// # Returns
+///
+/// The source-side node set and the cut edges sorted by node IDs, or `None`
+/// if `source` or `sink` doesn't exist or they are the same node.
+///
+/// # Errors
+///
+/// Returns an error if any edge has a negative capacity.
+///
+/// # Complexity
+///
+/// That of [`max_flow`], plus O(V + E)
+pub fn min_cut(
+    store: &LpgStore,
+    source: NodeId,
+    sink: NodeId,
+    capacity_property: Option<&str>,
+) -> Result<Option<(FxHashSet<NodeId>, Vec<(NodeId, NodeId)>)>> {
+    reject_negative_capacities(store, capacity_property)?;
+    Ok(cut_after_flow(store, source, sink, capacity_property))
+}
+
+/// [`min_cut`] without the capacity check.
+fn cut_after_flow(
+    store: &LpgStore,
+    source: NodeId,
+    sink: NodeId,
+    capacity_property: Option<&str>,
+) -> Option<(FxHashSet<NodeId>, Vec<(NodeId, NodeId)>)> {
+    if source == sink {
+        return None;
+    }
+    let flow = edmonds_karp(store, source, sink, capacity_property)?;
+    let capacities = EdgeWeight::new(capacity_property);
+
+    // Capacity per pair, then residual = capacity minus net flow either way
+    let mut capacity: FxHashMap<(NodeId, NodeId), f64> = FxHashMap::default();
+    for node in store.node_ids() {
+        for (neighbor, edge_id) in store.edges_from(node, Direction::Outgoing) {
+            *capacity.entry((node, neighbor)).or_insert(0.0) += capacities.value(store, edge_id);
+        }
+    }
+    let mut residual = capacity.clone();
+    for &(from, to, amount) in &flow.flow_edges {
+        *residual.entry((from, to)).or_insert(0.0) -= amount;
+        *residual.entry((to, from)).or_insert(0.0) += amount;
+    }
+
+    let mut residual_arcs: FxHashMap<NodeId, Vec<NodeId>> = FxHashMap::default();
+    for (&(from, to), &cap) in &residual {
+        if cap > 1e-9 {
+            residual_arcs.entry(from).or_default().push(to);
+        }
+    }
+
+    // BFS from the source over arcs with residual capacity left
+    let mut source_side: FxHashSet<NodeId> = FxHashSet::default();
+    source_side.insert(source);
+    let mut queue: VecDeque<NodeId> = VecDeque::from([source]);
+    while let Some(u) = queue.pop_front() {
+        for &v in residual_arcs.get(&u).into_iter().flatten() {
+            if source_side.insert(v) {
+                queue.push_back(v);
+            }
+        }
+    }
+
+    let mut cut_edges: Vec<(NodeId, NodeId)> = capacity
+        .into_iter()
+        .filter(|&((from, to), cap)| {
+            cap > 1e-9 && source_side.contains(&from) && !source_side.contains(&to)
+        })
+        .map(|(pair, _)| pair)
+        .collect();
+    cut_edges.sort_unstable();
+
+    Some((source_side, cut_edges))
+}
+
 // ============================================================================
 // Min Cost Max Flow Result
 // ============================================================================
@@ -248,7 +541,12 @@ pub struct MinCostFlowResult {
 ///
 /// # Returns
 ///
-/// Maximum flow value, total cost, and flow assignment on edges.
+/// Maximum flow value, total cost, and flow assignment on edges, or `None`
+/// if `source` or `sink` doesn't exist.
+///
+/// # Errors
+///
+/// Returns an error if any edge has a negative capacity.
 ///
 /// # Complexity
 ///
@@ -259,7 +557,29 @@ pub fn min_cost_max_flow(
     sink: NodeId,
     capacity_property: Option<&str>,
     cost_property: Option<&str>,
+) -> Result<Option<MinCostFlowResult>> {
+    reject_negative_capacities(store, capacity_property)?;
+    Ok(successive_shortest_paths(
+        store,
+        source,
+        sink,
+        capacity_property,
+        cost_property,
+    ))
+}
+
+/// Successive shortest paths behind [`min_cost_max_flow`], without the
+/// capacity check.
+fn successive_shortest_paths(
+    store: &LpgStore,
+    source: NodeId,
+    sink: NodeId,
+    capacity_property: Option<&str>,
+    cost_property: Option<&str>,
 ) -> Option<MinCostFlowResult> {
+    let capacities = EdgeWeight::new(capacity_property);
+    let costs = EdgeWeight::new(cost_property).missing(MissingWeight::Default(0.0));
+
     // Verify source and sink exist
     if store.get_node(source).is_none() || store.get_node(sink).is_none() {
         return None;
@@ -295,8 +615,8 @@ pub fn min_cost_max_flow(
         let i = *node_to_idx.get(&node).unwrap();
         for (neighbor, edge_id) in store.edges_from(node, Direction::Outgoing) {
             if let Some(&j) = node_to_idx.get(&neighbor) {
-                let cap = extract_capacity(store, edge_id, capacity_property);
-                let edge_cost = extract_cost(store, edge_id, cost_property);
+                let cap = capacities.value(store, edge_id);
+                let edge_cost = costs.value(store, edge_id);
                 *capacity[i].entry(j).or_insert(0.0) += cap;
                 *cost[i].entry(j).or_insert(0.0) = edge_cost;
             }
@@ -397,6 +717,97 @@ pub fn min_cost_max_flow(
     })
 }
 
+// ============================================================================
+// Flow Network Export
+// ============================================================================
+
+/// Arcs of a flow network between two node sets, in solver-ready form.
+///
+/// Nodes are numbered densely: source-side nodes get indices
+/// `0..source_nodes.len()` and target-side nodes follow, which is the
+/// layout linear assignment and min-cost-flow solvers expect. Arc `k` runs
+/// from `tails[k]` to `heads[k]`.
+#[derive(Debug, Clone, Default)]
+pub struct FlowArcs {
+    /// Source-side node IDs, in index order.
+    pub source_nodes: Vec<NodeId>,
+    /// Target-side node IDs, in index order after the source side.
+    pub target_nodes: Vec<NodeId>,
+    /// Tail (start) index of each arc.
+    pub tails: Vec<usize>,
+    /// Head (end) index of each arc.
+    pub heads: Vec<usize>,
+    /// Capacity of each arc.
+    pub capacities: Vec<f64>,
+    /// Cost per unit of flow on each arc.
+    pub unit_costs: Vec<f64>,
+    /// Edge each arc was built from.
+    pub edges: Vec<EdgeId>,
+}
+
+/// Extracts the arcs of a bipartite flow network.
+///
+/// Every outgoing `edge_type` edge from a `source_label` node to a
+/// `target_label` node becomes one arc, with capacity and cost read the same
+/// way as in [`min_cost_max_flow`]. Parallel edges stay separate arcs. A node
+/// carrying both labels counts only on the source side.
+///
+/// # Arguments
+///
+/// * `store` - The graph store
+/// * `source_label` - Label of the source-side nodes
+/// * `target_label` - Label of the target-side nodes
+/// * `edge_type` - Type of the edges that become arcs
+/// * `capacity_property` - Optional property name for arc capacities (defaults to 1.0)
+/// * `cost_property` - Optional property name for arc costs
+/// * `default_cost` - Cost of arcs without a numeric cost property
+pub fn flow_arcs(
+    store: &LpgStore,
+    source_label: &str,
+    target_label: &str,
+    edge_type: &str,
+    capacity_property: Option<&str>,
+    cost_property: Option<&str>,
+    default_cost: f64,
+) -> FlowArcs {
+    let capacities = EdgeWeight::new(capacity_property);
+    let costs = EdgeWeight::new(cost_property).missing(MissingWeight::Default(default_cost));
+
+    // Both lists come back sorted by NodeId
+    let source_nodes = store.nodes_by_label(source_label);
+    let target_nodes: Vec<NodeId> = store
+        .nodes_by_label(target_label)
+        .into_iter()
+        .filter(|node| source_nodes.binary_search(node).is_err())
+        .collect();
+    let mut target_idx: FxHashMap<NodeId, usize> = FxHashMap::default();
+    for (offset, &node) in target_nodes.iter().enumerate() {
+        target_idx.insert(node, source_nodes.len() + offset);
+    }
+
+    let mut arcs = FlowArcs::default();
+    for (tail, &node) in source_nodes.iter().enumerate() {
+        let mut out: Vec<(NodeId, EdgeId)> = store
+            .edges_from(node, Direction::Outgoing)
+            .filter(|(_, edge_id)| store.edge_type(*edge_id).as_deref() == Some(edge_type))
+            .collect();
+        out.sort_unstable_by_key(|(_, edge_id)| *edge_id);
+        for (neighbor, edge_id) in out {
+            if let Some(&head) = target_idx.get(&neighbor) {
+                arcs.tails.push(tail);
+                arcs.heads.push(head);
+                arcs.capacities.push(capacities.value(store, edge_id));
+                arcs.unit_costs.push(costs.value(store, edge_id));
+                arcs.edges.push(edge_id);
+            }
+        }
+    }
+
+    arcs.source_nodes = source_nodes;
+    arcs.target_nodes = target_nodes;
+    arcs
+}
+
 // ============================================================================
 // Algorithm Wrappers for Plugin Registry
 // ============================================================================
@@ -433,6 +844,8 @@ fn max_flow_params() -> &'static [ParameterDef] {
 }
 
 /// Max Flow algorithm wrapper.
+///
+/// Fails on negative capacities.
 pub struct MaxFlowAlgorithm;
 
 impl GraphAlgorithm for MaxFlowAlgorithm {
@@ -460,7 +873,7 @@ impl GraphAlgorithm for MaxFlowAlgorithm {
         let sink = NodeId::new(sink_id as u64);
         let capacity_prop = params.get_string("capacity");
 
-        let result = max_flow(store, source, sink, capacity_prop)
+        let result = max_flow(store, source, sink, capacity_prop)?
             .ok_or_else(|| Error::InvalidValue("Invalid source or sink node".to_string()))?;
 
         let mut output = AlgorithmResult::new(vec![
@@ -532,6 +945,8 @@ fn min_cost_flow_params() -> &'static [ParameterDef] {
 }
 
 /// Min Cost Max Flow algorithm wrapper.
+///
+/// Fails on negative capacities; negative costs are allowed.
 pub struct MinCostFlowAlgorithm;
 
 impl GraphAlgorithm for MinCostFlowAlgorithm {
@@ -560,7 +975,7 @@ impl GraphAlgorithm for MinCostFlowAlgorithm {
         let capacity_prop = params.get_string("capacity");
         let cost_prop = params.get_string("cost");
 
-        let result = min_cost_max_flow(store, source, sink, capacity_prop, cost_prop)
+        let result = min_cost_max_flow(store, source, sink, capacity_prop, cost_prop)?
             .ok_or_else(|| Error::InvalidValue("Invalid source or sink node".to_string()))?;
 
         let mut output = AlgorithmResult::new(vec![
@@ -664,7 +1079,7 @@ mod tests {
     #[test]
     fn test_max_flow_basic() {
         let store = create_simple_flow_graph();
-        let result = max_flow(&store, NodeId::new(0), NodeId::new(3), Some("capacity"));
+        let result = max_flow(&store, NodeId::new(0), NodeId::new(3), Some("capacity")).unwrap();
 
         assert!(result.is_some());
         let result = result.unwrap();
@@ -680,7 +1095,7 @@ mod tests {
     #[test]
     fn test_max_flow_same_source_sink() {
         let store = create_simple_flow_graph();
-        let result = max_flow(&store, NodeId::new(0), NodeId::new(0), Some("capacity"));
+        let result = max_flow(&store, NodeId::new(0), NodeId::new(0), Some("capacity")).unwrap();
 
         assert!(result.is_some());
         assert_eq!(result.unwrap().max_flow, 0.0);
@@ -692,7 +1107,7 @@ mod tests {
         let n0 = store.create_node(&["Node"]);
         let _n1 = store.create_node(&["Node"]); // Disconnected
 
-        let result = max_flow(&store, n0, NodeId::new(1), None);
+        let result = max_flow(&store, n0, NodeId::new(1), None).unwrap();
         assert!(result.is_some());
         assert_eq!(result.unwrap().max_flow, 0.0);
     }
@@ -702,10 +1117,224 @@ mod tests {
         let store = LpgStore::new();
         store.create_node(&["Node"]);
 
-        let result = max_flow(&store, NodeId::new(999), NodeId::new(0), None);
+        let result = max_flow(&store, NodeId::new(999), NodeId::new(0), None).unwrap();
         assert!(result.is_none());
     }
 
+    /// A pseudo-random network with integer capacities, including parallel
+    /// and antiparallel edges.
+    fn create_random_flow_graph(nodes: u64, edges: usize, seed: u64) -> LpgStore {
+        let store = LpgStore::new();
+        let ids: Vec<NodeId> = (0..nodes).map(|_| store.create_node(&["Node"])).collect();
+        let mut state = seed;
+        for _ in 0..edges {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let from = ids[((state >> 33) % nodes) as usize];
+            let to = ids[((state >> 13) % nodes) as usize];
+            let capacity = ((state >> 45) % 10) as i64;
+            store.create_edge_with_props(from, to, "EDGE", [("capacity", Value::Int64(capacity))]);
+        }
+        store
+    }
+
+    /// Checks capacity limits and conservation of a flow assignment.
+    fn assert_valid_flow(store: &LpgStore, source: NodeId, sink: NodeId, result: &MaxFlowResult) {
+        let mut net: FxHashMap<NodeId, f64> = FxHashMap::default();
+        for &(from, to, flow) in &result.flow_edges {
+            let capacity: f64 = store
+                .edges_from(from, Direction::Outgoing)
+                .filter(|&(neighbor, _)| neighbor == to)
+                .map(|(_, edge)| EdgeWeight::new(Some("capacity")).value(store, edge))
+                .sum();
+            assert!(flow <= capacity + 1e-9, "{from:?} -> {to:?} over capacity");
+            *net.entry(from).or_insert(0.0) -= flow;
+            *net.entry(to).or_insert(0.0) += flow;
+        }
+        for (node, balance) in net {
+            let expected = if node == sink {
+                result.max_flow
+            } else if node == source {
+                -result.max_flow
+            } else {
+                0.0
+            };
+            assert!((balance - expected).abs() < 1e-9, "{node:?} unbalanced");
+        }
+    }
+
+    #[test]
+    fn test_dinic_matches_edmonds_karp() {
+        let mut instances = vec![(create_simple_flow_graph(), 4)];
+        for seed in 1..=6 {
+            instances.push((create_random_flow_graph(12, 60, seed), 12));
+        }
+        instances.push((create_random_flow_graph(40, 600, 7), 40));
+
+        for (store, nodes) in &instances {
+            for (source, sink) in [(0, nodes - 1), (1, 2), (nodes / 2, 0)] {
+                let (source, sink) = (NodeId::new(source), NodeId::new(sink));
+                let dinic = max_flow_dinic(store, source, sink, Some("capacity"))
+                    .unwrap()
+                    .unwrap();
+                let edmonds_karp = max_flow(store, source, sink, Some("capacity"))
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(
+                    dinic.max_flow, edmonds_karp.max_flow,
+                    "{source:?} -> {sink:?}"
+                );
+                assert_valid_flow(store, source, sink, &dinic);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dinic_parallel_and_antiparallel_edges() {
+        // Two parallel 0 -> 1 edges sum to 5; 1 -> 0 doesn't add to them
+        let store = LpgStore::new();
+        let n0 = store.create_node(&["Node"]);
+        let n1 = store.create_node(&["Node"]);
+        let n2 = store.create_node(&["Node"]);
+        store.create_edge_with_props(n0, n1, "EDGE", [("capacity", Value::Int64(2))]);
+        store.create_edge_with_props(n0, n1, "EDGE", [("capacity", Value::Int64(3))]);
+        store.create_edge_with_props(n1, n0, "EDGE", [("capacity", Value::Int64(4))]);
+        store.create_edge_with_props(n1, n2, "EDGE", [("capacity", Value::Int64(9))]);
+
+        let result = max_flow_dinic(&store, n0, n2, Some("capacity"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.max_flow, 5.0);
+        let mut flow_edges = result.flow_edges.clone();
+        flow_edges.sort_by_key(|&(from, to, _)| (from, to));
+        assert_eq!(flow_edges, vec![(n0, n1, 5.0), (n1, n2, 5.0)]);
+        assert_valid_flow(&store, n0, n2, &result);
+    }
+
+    #[test]
+    fn test_dinic_edge_cases() {
+        let store = create_simple_flow_graph();
+        let (n0, n3) = (NodeId::new(0), NodeId::new(3));
+
+        let result = max_flow_dinic(&store, n0, n0, Some("capacity"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.max_flow, 0.0);
+        assert!(result.flow_edges.is_empty());
+
+        // Against edge direction nothing flows
+        assert_eq!(
+            max_flow_dinic(&store, n3, n0, Some("capacity"))
+                .unwrap()
+                .unwrap()
+                .max_flow,
+            0.0
+        );
+        assert!(
+            max_flow_dinic(&store, n0, NodeId::new(999), None)
+                .unwrap()
+                .is_none()
+        );
+
+        // 0 -> 1 -> 3 carries 3, 0 -> 2 -> 3 carries 3, 0 -> 1 -> 2 -> 3 carries 1
+        assert_eq!(
+            max_flow_dinic(&store, n0, n3, Some("capacity"))
+                .unwrap()
+                .unwrap()
+                .max_flow,
+            7.0
+        );
+        // The store is left untouched
+        assert_eq!(store.edge_count(), 5);
+    }
+
+    #[test]
+    fn test_min_cut_matches_max_flow() {
+        let mut instances = vec![(create_simple_flow_graph(), 4)];
+        for seed in 1..=6 {
+            instances.push((create_random_flow_graph(12, 60, seed), 12));
+        }
+
+        for (store, nodes) in &instances {
+            for (source, sink) in [(0, nodes - 1), (1, 2), (nodes / 2, 0)] {
+                let (source, sink) = (NodeId::new(source), NodeId::new(sink));
+                let flow = max_flow(store, source, sink, Some("capacity"))
+                    .unwrap()
+                    .unwrap();
+                let (source_side, cut_edges) = min_cut(store, source, sink, Some("capacity"))
+                    .unwrap()
+                    .unwrap();
+
+                assert!(source_side.contains(&source));
+                assert!(!source_side.contains(&sink));
+
+                let capacity_between = |from: NodeId, to: NodeId| -> f64 {
+                    store
+                        .edges_from(from, Direction::Outgoing)
+                        .filter(|&(neighbor, _)| neighbor == to)
+                        .map(|(_, edge)| EdgeWeight::new(Some("capacity")).value(store, edge))
+                        .sum()
+                };
+                let mut cut_capacity = 0.0;
+                for &(from, to) in &cut_edges {
+                    assert!(source_side.contains(&from) && !source_side.contains(&to));
+                    let capacity = capacity_between(from, to);
+                    cut_capacity += capacity;
+
+                    // Every cut edge is saturated
+                    let carried: f64 = flow
+                        .flow_edges
+                        .iter()
+                        .filter(|&&(f, t, _)| (f, t) == (from, to))
+                        .map(|&(_, _, amount)| amount)
+                        .sum();
+                    assert!((carried - capacity).abs() < 1e-9, "{from:?} -> {to:?}");
+                }
+                assert!(
+                    (cut_capacity - flow.max_flow).abs() < 1e-9,
+                    "cut {cut_capacity} != flow {}",
+                    flow.max_flow
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_min_cut_simple() {
+        // 0 -> 1 -> 3 and 0 -> 2 -> 3 carry 3 each, 1 -> 2 -> 3 one more:
+        // the sink's incoming edges (3 + 4) are the bottleneck
+        let store = create_simple_flow_graph();
+        let n: Vec<NodeId> = (0..4).map(NodeId::new).collect();
+
+        let (source_side, cut_edges) = min_cut(&store, n[0], n[3], Some("capacity"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            source_side,
+            [n[0], n[1], n[2]].into_iter().collect::<FxHashSet<_>>()
+        );
+        assert_eq!(cut_edges, vec![(n[1], n[3]), (n[2], n[3])]);
+
+        // Nothing reaches 0 from 3: the source side is just the source
+        let (source_side, cut_edges) = min_cut(&store, n[3], n[0], Some("capacity"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(source_side.len(), 1);
+        assert!(cut_edges.is_empty());
+
+        assert!(
+            min_cut(&store, n[0], n[0], Some("capacity"))
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            min_cut(&store, n[0], NodeId::new(999), None)
+                .unwrap()
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_min_cost_flow_basic() {
         let store = create_cost_flow_graph();
@@ -715,7 +1344,8 @@ mod tests {
             NodeId::new(2),
             Some("capacity"),
             Some("cost"),
-        );
+        )
+        .unwrap();
 
         assert!(result.is_some());
         let result = result.unwrap();
@@ -736,7 +1366,8 @@ mod tests {
             NodeId::new(2),
             Some("capacity"),
             Some("cost"),
-        );
+        )
+        .unwrap();
 
         assert!(result.is_some());
         let result = result.unwrap();
@@ -754,7 +1385,7 @@ mod tests {
         let n0 = store.create_node(&["Node"]);
         let _n1 = store.create_node(&["Node"]);
 
-        let result = min_cost_max_flow(&store, n0, NodeId::new(1), None, None);
+        let result = min_cost_max_flow(&store, n0, NodeId::new(1), None, None).unwrap();
         assert!(result.is_some());
         assert_eq!(result.unwrap().max_flow, 0.0);
     }
@@ -771,10 +1402,68 @@ mod tests {
         store.create_edge(n0, n2, "EDGE");
         store.create_edge(n1, n2, "EDGE");
 
-        let result = max_flow(&store, n0, n2, None);
+        let result = max_flow(&store, n0, n2, None).unwrap();
         assert!(result.is_some());
 
         // Two paths: 0->2 (cap 1) and 0->1->2 (cap 1)
         assert!(result.unwrap().max_flow >= 1.0);
     }
+
+    #[test]
+    fn test_flow_arcs_bipartite() {
+        let store = LpgStore::new();
+        let w0 = store.create_node(&["Worker"]);
+        let w1 = store.create_node(&["Worker"]);
+        let t0 = store.create_node(&["Task"]);
+        let t1 = store.create_node(&["Task"]);
+
+        store.create_edge_with_props(w0, t0, "CAN_DO", [("cost", Value::Int64(4))]);
+        store.create_edge_with_props(w0, t1, "CAN_DO", [("cost", Value::Float64(2.5))]);
+        store.create_edge(w1, t1, "CAN_DO");
+        // Wrong type and wrong direction are both ignored
+        store.create_edge(w1, t0, "KNOWS");
+        store.create_edge(t0, w1, "CAN_DO");
+
+        let arcs = flow_arcs(&store, "Worker", "Task", "CAN_DO", None, Some("cost"), 9.0);
+
+        assert_eq!(arcs.source_nodes, vec![w0, w1]);
+        assert_eq!(arcs.target_nodes, vec![t0, t1]);
+        assert_eq!(arcs.tails, vec![0, 0, 1]);
+        assert_eq!(arcs.heads, vec![2, 3, 3]);
+        assert_eq!(arcs.capacities, vec![1.0, 1.0, 1.0]);
+        assert_eq!(arcs.unit_costs, vec![4.0, 2.5, 9.0]);
+        assert_eq!(arcs.edges.len(), 3);
+    }
+
+    #[test]
+    fn test_negative_capacity_rejected() {
+        let store = LpgStore::new();
+        let n0 = store.create_node(&["Node"]);
+        let n1 = store.create_node(&["Node"]);
+        store.create_edge_with_props(n0, n1, "EDGE", [("capacity", Value::Int64(-4))]);
+
+        let mut params = Parameters::new();
+        params.set_int("source", n0.0 as i64);
+        params.set_int("sink", n1.0 as i64);
+        params.set_string("capacity", "capacity");
+
+        for algorithm in [
+            &MaxFlowAlgorithm as &dyn GraphAlgorithm,
+            &MinCostFlowAlgorithm,
+        ] {
+            let err = algorithm.execute(&store, &params).unwrap_err();
+            assert!(
+                err.to_string().contains("negative 'capacity' weight -4"),
+                "{err}"
+            );
+        }
+
+        // The functions check capacities themselves too
+        let capacity = Some("capacity");
+        assert!(max_flow(&store, n0, n1, capacity).is_err());
+        assert!(max_flow_dinic(&store, n0, n1, capacity).is_err());
+        assert!(min_cut(&store, n0, n1, capacity).is_err());
+        assert!(min_cost_max_flow(&store, n0, n1, capacity, None).is_err());
+        assert!(max_flow(&store, n0, n1, None).is_ok());
+    }
 }
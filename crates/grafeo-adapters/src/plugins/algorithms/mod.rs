@@ -5,12 +5,12 @@
 //!
 //! | Category | Algorithms |
 //! | -------- | ---------- |
-//! | Traversal | BFS, DFS with visitor pattern |
+//! | Traversal | BFS (sequential and parallel), DFS with visitor pattern |
 //! | Components | Connected, strongly connected, topological sort |
-//! | Shortest paths | Dijkstra, A*, Bellman-Ford, Floyd-Warshall |
-//! | Centrality | PageRank, betweenness, closeness, degree |
+//! | Shortest paths | Dijkstra (one- or two-sided), A*, Bellman-Ford, Floyd-Warshall, Yen (k-shortest) |
+//! | Centrality | PageRank (global and personalized), betweenness (exact or sampled), closeness, degree (weighted or not) |
 //! | Community | Louvain, label propagation |
-//! | Structure | K-core, bridges, articulation points |
+//! | Structure | K-core, bridges, articulation points, triangles, clustering coefficient |
 //!
 //! ## Usage
 //!
@@ -44,43 +44,57 @@ mod traversal;
 
 // Core traits
 pub use traits::{
-    Control, DistanceMap, GraphAlgorithm, MinScored, ParallelGraphAlgorithm, TraversalEvent,
+    Control, DistanceMap, EdgeWeight, GraphAlgorithm, MinScored, MissingWeight, NegativeWeight,
+    ParallelGraphAlgorithm, TraversalEvent,
 };
 
 // Traversal algorithms
-pub use traversal::{bfs, bfs_layers, bfs_with_visitor, dfs, dfs_all, dfs_with_visitor};
+pub use traversal::{
+    bfs, bfs_distances, bfs_layers, bfs_parallel, bfs_with_visitor, dfs, dfs_all, dfs_with_visitor,
+};
 
 // Component algorithms
 pub use components::{
     UnionFind, connected_component_count, connected_components, is_dag,
     strongly_connected_component_count, strongly_connected_components, topological_sort,
+    weakly_connected_components,
 };
 
 // Shortest path algorithms
 pub use shortest_path::{
-    BellmanFordResult, DijkstraResult, FloydWarshallResult, astar, bellman_ford, dijkstra,
-    dijkstra_path, floyd_warshall,
+    BellmanFordResult, DijkstraResult, FloydWarshallResult, all_pairs_dijkstra,
+    all_pairs_dijkstra_parallel, astar, bellman_ford, bidirectional_dijkstra, dijkstra,
+    dijkstra_path, floyd_warshall, johnson, k_shortest_paths,
 };
 
 // Centrality algorithms
 pub use centrality::{
-    DegreeCentralityResult, betweenness_centrality, closeness_centrality, degree_centrality,
-    degree_centrality_normalized, pagerank,
+    DegreeCentralityResult, approximate_betweenness, betweenness_centrality, closeness_centrality,
+    degree_centrality, degree_centrality_normalized, pagerank, personalized_pagerank,
+    weighted_degree_centrality,
 };
 
 // Community detection algorithms
-pub use community::{LouvainResult, community_count, label_propagation, louvain};
+pub use community::{
+    LouvainLevel, LouvainResult, community_count, label_propagation, louvain, modularity,
+};
 
 // Minimum Spanning Tree algorithms
 pub use mst::{MstResult, kruskal, prim};
 
 // Network Flow algorithms
-pub use flow::{MaxFlowResult, MinCostFlowResult, max_flow, min_cost_max_flow};
+pub use flow::{
+    FlowArcs, MaxFlowResult, MinCostFlowResult, flow_arcs, max_flow, max_flow_dinic,
+    min_cost_max_flow, min_cut,
+};
 
 // Structure analysis algorithms
-pub use structure::{KCoreResult, articulation_points, bridges, k_core, kcore_decomposition};
+pub use structure::{
+    KCoreResult, articulation_points, bridges, clustering_coefficient, k_core, kcore_decomposition,
+    triangle_count,
+};
 
-// Algorithm wrappers (for future registry integration)
+// Algorithm wrappers for the plugin registry
 pub use centrality::{
     BetweennessCentralityAlgorithm, ClosenessCentralityAlgorithm, DegreeCentralityAlgorithm,
     PageRankAlgorithm,
@@ -91,6 +105,8 @@ pub use components::{
 };
 pub use flow::{MaxFlowAlgorithm, MinCostFlowAlgorithm};
 pub use mst::{KruskalAlgorithm, PrimAlgorithm};
-pub use shortest_path::{BellmanFordAlgorithm, DijkstraAlgorithm, FloydWarshallAlgorithm};
+pub use shortest_path::{
+    AllPairsDijkstraAlgorithm, BellmanFordAlgorithm, DijkstraAlgorithm, FloydWarshallAlgorithm,
+};
 pub use structure::{ArticulationPointsAlgorithm, BridgesAlgorithm, KCoreAlgorithm};
 pub use traversal::{BfsAlgorithm, DfsAlgorithm};
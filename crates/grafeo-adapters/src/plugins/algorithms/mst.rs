@@ -14,27 +14,7 @@ use grafeo_core::graph::lpg::LpgStore;
 
 use super::super::{AlgorithmResult, ParameterDef, ParameterType, Parameters};
 use super::components::UnionFind;
-use super::traits::{GraphAlgorithm, MinScored};
-
-// ============================================================================
-// Edge Weight Extraction
-// ============================================================================
-
-/// Extracts edge weight from a property value.
-fn extract_weight(store: &LpgStore, edge_id: EdgeId, weight_prop: Option<&str>) -> f64 {
-    if let Some(prop_name) = weight_prop {
-        if let Some(edge) = store.get_edge(edge_id) {
-            if let Some(value) = edge.get_property(prop_name) {
-                return match value {
-                    Value::Int64(i) => *i as f64,
-                    Value::Float64(f) => *f,
-                    _ => 1.0,
-                };
-            }
-        }
-    }
-    1.0
-}
+use super::traits::{EdgeWeight, GraphAlgorithm, MinScored, NegativeWeight};
 
 // ============================================================================
 // MST Result
@@ -86,6 +66,8 @@ impl MstResult {
 ///
 /// O(E log E) for sorting edges
 pub fn kruskal(store: &LpgStore, weight_property: Option<&str>) -> MstResult {
+    let weights = EdgeWeight::new(weight_property);
+
     let nodes = store.node_ids();
     let n = nodes.len();
 
@@ -115,7 +97,7 @@ pub fn kruskal(store: &LpgStore, weight_property: Option<&str>) -> MstResult {
                 let key = if i < j { (i, j) } else { (j, i) };
                 if !seen_edges.contains(&key) {
                     seen_edges.insert(key);
-                    let weight = extract_weight(store, edge_id, weight_property);
+                    let weight = weights.value(store, edge_id);
                     edges.push((weight, node, neighbor, edge_id));
                 }
             }
@@ -172,10 +154,28 @@ pub fn kruskal(store: &LpgStore, weight_property: Option<&str>) -> MstResult {
 ///
 /// The MST edges and total weight.
 ///
+/// # Errors
+///
+/// Returns an error if any edge has a negative weight.
+///
 /// # Complexity
 ///
 /// O(E log V) using a binary heap
-pub fn prim(store: &LpgStore, weight_property: Option<&str>, start: Option<NodeId>) -> MstResult {
+pub fn prim(
+    store: &LpgStore,
+    weight_property: Option<&str>,
+    start: Option<NodeId>,
+) -> Result<MstResult> {
+    EdgeWeight::new(weight_property)
+        .negative(NegativeWeight::Reject)
+        .check(store)?;
+    Ok(grow_tree(store, weight_property, start))
+}
+
+/// Prim's algorithm behind [`prim`], without the weight check.
+fn grow_tree(store: &LpgStore, weight_property: Option<&str>, start: Option<NodeId>) -> MstResult {
+    let weights = EdgeWeight::new(weight_property);
+
     let nodes = store.node_ids();
     let n = nodes.len();
 
@@ -209,7 +209,7 @@ pub fn prim(store: &LpgStore, weight_property: Option<&str>, start: Option<NodeI
 
     // Add edges from start node
     for (neighbor, edge_id) in store.edges_from(start_node, Direction::Outgoing) {
-        let weight = extract_weight(store, edge_id, weight_property);
+        let weight = weights.value(store, edge_id);
         heap.push(MinScored::new(weight, (start_node, neighbor, edge_id)));
     }
 
@@ -217,7 +217,7 @@ pub fn prim(store: &LpgStore, weight_property: Option<&str>, start: Option<NodeI
     for &other in &nodes {
         for (neighbor, edge_id) in store.edges_from(other, Direction::Outgoing) {
             if neighbor == start_node {
-                let weight = extract_weight(store, edge_id, weight_property);
+                let weight = weights.value(store, edge_id);
                 heap.push(MinScored::new(weight, (other, start_node, edge_id)));
             }
         }
@@ -237,7 +237,7 @@ pub fn prim(store: &LpgStore, weight_property: Option<&str>, start: Option<NodeI
         // Add edges from new node
         for (neighbor, new_edge_id) in store.edges_from(dst, Direction::Outgoing) {
             if !*in_tree.get(&neighbor).unwrap_or(&false) {
-                let new_weight = extract_weight(store, new_edge_id, weight_property);
+                let new_weight = weights.value(store, new_edge_id);
                 heap.push(MinScored::new(new_weight, (dst, neighbor, new_edge_id)));
             }
         }
@@ -247,7 +247,7 @@ pub fn prim(store: &LpgStore, weight_property: Option<&str>, start: Option<NodeI
             if !*in_tree.get(&other).unwrap_or(&false) {
                 for (neighbor, new_edge_id) in store.edges_from(other, Direction::Outgoing) {
                     if neighbor == dst {
-                        let new_weight = extract_weight(store, new_edge_id, weight_property);
+                        let new_weight = weights.value(store, new_edge_id);
                         heap.push(MinScored::new(new_weight, (other, dst, new_edge_id)));
                     }
                 }
@@ -351,6 +351,8 @@ fn prim_params() -> &'static [ParameterDef] {
 }
 
 /// Prim's MST algorithm wrapper.
+///
+/// Fails on negative edge weights.
 pub struct PrimAlgorithm;
 
 impl GraphAlgorithm for PrimAlgorithm {
@@ -370,7 +372,7 @@ impl GraphAlgorithm for PrimAlgorithm {
         let weight_prop = params.get_string("weight");
         let start = params.get_int("start").map(|id| NodeId::new(id as u64));
 
-        let result = prim(store, weight_prop, start);
+        let result = prim(store, weight_prop, start)?;
 
         let mut output = AlgorithmResult::new(vec![
             "source".to_string(),
@@ -487,7 +489,7 @@ mod tests {
     #[test]
     fn test_prim_triangle() {
         let store = create_weighted_triangle();
-        let result = prim(&store, Some("weight"), None);
+        let result = prim(&store, Some("weight"), None).unwrap();
 
         // MST should have 2 edges for 3 nodes
         assert_eq!(result.edges.len(), 2);
@@ -499,7 +501,7 @@ mod tests {
     #[test]
     fn test_prim_chain() {
         let store = create_simple_chain();
-        let result = prim(&store, None, None);
+        let result = prim(&store, None, None).unwrap();
 
         // MST should have 3 edges for 4 nodes
         assert_eq!(result.edges.len(), 3);
@@ -508,7 +510,7 @@ mod tests {
     #[test]
     fn test_prim_with_start() {
         let store = create_simple_chain();
-        let result = prim(&store, None, Some(NodeId::new(2)));
+        let result = prim(&store, None, Some(NodeId::new(2))).unwrap();
 
         // Should still find valid MST starting from node 2
         assert_eq!(result.edges.len(), 3);
@@ -517,7 +519,7 @@ mod tests {
     #[test]
     fn test_prim_empty() {
         let store = LpgStore::new();
-        let result = prim(&store, None, None);
+        let result = prim(&store, None, None).unwrap();
 
         assert!(result.edges.is_empty());
     }
@@ -527,7 +529,7 @@ mod tests {
         let store = create_weighted_triangle();
 
         let kruskal_result = kruskal(&store, Some("weight"));
-        let prim_result = prim(&store, Some("weight"), None);
+        let prim_result = prim(&store, Some("weight"), None).unwrap();
 
         // Both should have the same total weight
         assert!((kruskal_result.total_weight - prim_result.total_weight).abs() < 0.001);
@@ -540,4 +542,34 @@ mod tests {
 
         assert!(result.is_spanning_tree(4));
     }
+
+    #[test]
+    fn test_weight_policies() {
+        // Triangle with one negative edge
+        let store = LpgStore::new();
+        let n0 = store.create_node(&["Node"]);
+        let n1 = store.create_node(&["Node"]);
+        let n2 = store.create_node(&["Node"]);
+        store.create_edge_with_props(n0, n1, "EDGE", [("weight", Value::Float64(-2.0))]);
+        store.create_edge_with_props(n1, n2, "EDGE", [("weight", Value::Float64(3.0))]);
+        store.create_edge(n0, n2, "EDGE");
+
+        let mut params = Parameters::new();
+        params.set_string("weight", "weight");
+
+        // Prim assumes non-negative weights
+        let err = PrimAlgorithm.execute(&store, &params).unwrap_err();
+        assert!(
+            err.to_string().contains("negative 'weight' weight -2"),
+            "{err}"
+        );
+        assert!(prim(&store, Some("weight"), None).is_err());
+        assert!(prim(&store, None, None).is_ok());
+
+        // Kruskal only compares weights; the missing weight counts as 1.0
+        assert!(KruskalAlgorithm.execute(&store, &params).is_ok());
+        let result = kruskal(&store, Some("weight"));
+        assert_eq!(result.edge_count(), 2);
+        assert_eq!(result.total_weight, -1.0);
+    }
 }
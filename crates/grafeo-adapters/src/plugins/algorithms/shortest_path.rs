@@ -1,4 +1,5 @@
-//! Shortest path algorithms: Dijkstra, A*, Bellman-Ford, Floyd-Warshall.
+//! Shortest path algorithms: Dijkstra (one- and two-sided), A*, Bellman-Ford,
+//! Floyd-Warshall, Yen's k-shortest.
 //!
 //! These algorithms find optimal paths in weighted graphs, supporting
 //! both single-source and all-pairs variants.
@@ -6,40 +7,18 @@
 use std::collections::BinaryHeap;
 use std::sync::OnceLock;
 
-use grafeo_common::types::{NodeId, Value};
+use rayon::prelude::*;
+
+use grafeo_common::types::{EdgeId, NodeId, Value};
 use grafeo_common::utils::error::{Error, Result};
-use grafeo_common::utils::hash::FxHashMap;
+use grafeo_common::utils::hash::{FxHashMap, FxHashSet};
 use grafeo_core::graph::Direction;
 use grafeo_core::graph::lpg::LpgStore;
 
 use super::super::{AlgorithmResult, ParameterDef, ParameterType, Parameters};
-use super::traits::{GraphAlgorithm, MinScored};
-
-// ============================================================================
-// Edge Weight Extraction
-// ============================================================================
-
-/// Extracts edge weight from a property value.
-///
-/// Supports Int64 and Float64 values, defaulting to 1.0 if no weight property.
-fn extract_weight(
-    store: &LpgStore,
-    edge_id: grafeo_common::types::EdgeId,
-    weight_prop: Option<&str>,
-) -> f64 {
-    if let Some(prop_name) = weight_prop {
-        if let Some(edge) = store.get_edge(edge_id) {
-            if let Some(value) = edge.get_property(prop_name) {
-                return match value {
-                    Value::Int64(i) => *i as f64,
-                    Value::Float64(f) => *f,
-                    _ => 1.0,
-                };
-            }
-        }
-    }
-    1.0
-}
+use super::traits::{
+    EdgeWeight, GraphAlgorithm, MinScored, NegativeWeight, ParallelGraphAlgorithm,
+};
 
 // ============================================================================
 // Dijkstra's Algorithm
@@ -94,10 +73,46 @@ impl DijkstraResult {
 ///
 /// Distances and predecessors for all reachable nodes.
 ///
+/// # Errors
+///
+/// Returns an error if any edge has a negative weight.
+///
 /// # Complexity
 ///
 /// O((V + E) log V) using a binary heap.
-pub fn dijkstra(store: &LpgStore, source: NodeId, weight_property: Option<&str>) -> DijkstraResult {
+pub fn dijkstra(
+    store: &LpgStore,
+    source: NodeId,
+    weight_property: Option<&str>,
+) -> Result<DijkstraResult> {
+    reject_negative_weights(store, weight_property)?;
+    let weights = EdgeWeight::new(weight_property);
+    Ok(dijkstra_with(store, source, |edge_id, _, _| {
+        weights.value(store, edge_id)
+    }))
+}
+
+/// Fails on the first negative weight, which Dijkstra-based searches can't
+/// handle.
+fn reject_negative_weights(store: &LpgStore, weight_property: Option<&str>) -> Result<()> {
+    EdgeWeight::new(weight_property)
+        .negative(NegativeWeight::Reject)
+        .check(store)
+        .map_err(|e| match e {
+            Error::InvalidValue(msg) => Error::InvalidValue(format!(
+                "{msg}; use floyd_warshall or bellman_ford for negative weights"
+            )),
+            e => e,
+        })
+}
+
+/// Dijkstra with weights from `weight(edge, from, to)`, which must be
+/// non-negative.
+fn dijkstra_with(
+    store: &LpgStore,
+    source: NodeId,
+    weight: impl Fn(EdgeId, NodeId, NodeId) -> f64,
+) -> DijkstraResult {
     let mut distances: FxHashMap<NodeId, f64> = FxHashMap::default();
     let mut predecessors: FxHashMap<NodeId, NodeId> = FxHashMap::default();
     let mut heap: BinaryHeap<MinScored<f64, NodeId>> = BinaryHeap::new();
@@ -123,8 +138,7 @@ pub fn dijkstra(store: &LpgStore, source: NodeId, weight_property: Option<&str>)
 
         // Explore neighbors
         for (neighbor, edge_id) in store.edges_from(node, Direction::Outgoing) {
-            let weight = extract_weight(store, edge_id, weight_property);
-            let new_dist = dist + weight;
+            let new_dist = dist + weight(edge_id, node, neighbor);
 
             let is_better = distances
                 .get(&neighbor)
@@ -147,14 +161,57 @@ pub fn dijkstra(store: &LpgStore, source: NodeId, weight_property: Option<&str>)
 /// Runs Dijkstra's algorithm to find shortest path to a specific target.
 ///
 /// Early terminates when target is reached.
+///
+/// # Errors
+///
+/// Returns an error if any edge has a negative weight.
 pub fn dijkstra_path(
     store: &LpgStore,
     source: NodeId,
     target: NodeId,
     weight_property: Option<&str>,
+) -> Result<Option<(f64, Vec<NodeId>)>> {
+    reject_negative_weights(store, weight_property)?;
+    Ok(single_pair_dijkstra(store, source, target, weight_property))
+}
+
+/// [`dijkstra_path`] without the weight check.
+fn single_pair_dijkstra(
+    store: &LpgStore,
+    source: NodeId,
+    target: NodeId,
+    weight_property: Option<&str>,
 ) -> Option<(f64, Vec<NodeId>)> {
+    let no_edges = FxHashSet::default();
+    let no_nodes = FxHashSet::default();
+    dijkstra_path_excluding(store, source, target, weight_property, &no_edges, &no_nodes)
+        .map(|path| (path.cost, path.nodes))
+}
+
+/// A weighted path together with the edges it traverses.
+#[derive(Debug, Clone)]
+struct WeightedPath {
+    cost: f64,
+    nodes: Vec<NodeId>,
+    edges: Vec<EdgeId>,
+}
+
+/// Single-pair Dijkstra that ignores the given edges and nodes.
+///
+/// The exclusions act as an overlay over the store, so callers can
+/// "remove" parts of the graph without mutating it.
+fn dijkstra_path_excluding(
+    store: &LpgStore,
+    source: NodeId,
+    target: NodeId,
+    weight_property: Option<&str>,
+    excluded_edges: &FxHashSet<EdgeId>,
+    excluded_nodes: &FxHashSet<NodeId>,
+) -> Option<WeightedPath> {
+    let weights = EdgeWeight::new(weight_property);
+
     let mut distances: FxHashMap<NodeId, f64> = FxHashMap::default();
-    let mut predecessors: FxHashMap<NodeId, NodeId> = FxHashMap::default();
+    let mut predecessors: FxHashMap<NodeId, (NodeId, EdgeId)> = FxHashMap::default();
     let mut heap: BinaryHeap<MinScored<f64, NodeId>> = BinaryHeap::new();
 
     // Check if source and target exist
@@ -169,15 +226,23 @@ pub fn dijkstra_path(
         // Early termination if we've reached target
         if node == target {
             // Reconstruct path
-            let mut path = Vec::new();
+            let mut nodes = Vec::new();
+            let mut edges = Vec::new();
             let mut current = target;
             while current != source {
-                path.push(current);
-                current = *predecessors.get(&current)?;
+                let &(pred, edge) = predecessors.get(&current)?;
+                nodes.push(current);
+                edges.push(edge);
+                current = pred;
             }
-            path.push(source);
-            path.reverse();
-            return Some((dist, path));
+            nodes.push(source);
+            nodes.reverse();
+            edges.reverse();
+            return Some(WeightedPath {
+                cost: dist,
+                nodes,
+                edges,
+            });
         }
 
         // Skip if we've found a better path
@@ -189,7 +254,11 @@ pub fn dijkstra_path(
 
         // Explore neighbors
         for (neighbor, edge_id) in store.edges_from(node, Direction::Outgoing) {
-            let weight = extract_weight(store, edge_id, weight_property);
+            if excluded_edges.contains(&edge_id) || excluded_nodes.contains(&neighbor) {
+                continue;
+            }
+
+            let weight = weights.value(store, edge_id);
             let new_dist = dist + weight;
 
             let is_better = distances
@@ -198,7 +267,7 @@ pub fn dijkstra_path(
 
             if is_better {
                 distances.insert(neighbor, new_dist);
-                predecessors.insert(neighbor, node);
+                predecessors.insert(neighbor, (node, edge_id));
                 heap.push(MinScored::new(new_dist, neighbor));
             }
         }
@@ -207,24 +276,341 @@ pub fn dijkstra_path(
     None // Target not reachable
 }
 
+// ============================================================================
+// Bidirectional Dijkstra
+// ============================================================================
+
+/// One side of a bidirectional search.
+struct Frontier {
+    /// Edges this side follows: outgoing from the source, incoming to the target.
+    direction: Direction,
+    distances: FxHashMap<NodeId, f64>,
+    /// Neighbour each reached node was reached from, towards this side's start.
+    parents: FxHashMap<NodeId, NodeId>,
+    heap: BinaryHeap<MinScored<f64, NodeId>>,
+}
+
+impl Frontier {
+    fn new(start: NodeId, direction: Direction) -> Self {
+        let mut distances = FxHashMap::default();
+        distances.insert(start, 0.0);
+        let mut heap = BinaryHeap::new();
+        heap.push(MinScored::new(0.0, start));
+        Self {
+            direction,
+            distances,
+            parents: FxHashMap::default(),
+            heap,
+        }
+    }
+
+    /// Smallest distance still queued, a lower bound on any unsettled node.
+    fn top(&self) -> f64 {
+        self.heap.peek().map_or(f64::INFINITY, |entry| entry.0)
+    }
+
+    /// Pops the closest unsettled node, skipping entries made stale by a
+    /// shorter path found later.
+    fn pop(&mut self) -> Option<(f64, NodeId)> {
+        while let Some(MinScored(dist, node)) = self.heap.pop() {
+            if self.distances.get(&node).is_some_and(|&best| dist <= best) {
+                return Some((dist, node));
+            }
+        }
+        None
+    }
+
+    /// Walks parents from `node` back to this side's start, `node` first.
+    fn walk(&self, node: NodeId) -> Vec<NodeId> {
+        let mut path = vec![node];
+        let mut current = node;
+        while let Some(&parent) = self.parents.get(&current) {
+            path.push(parent);
+            current = parent;
+        }
+        path
+    }
+}
+
+/// Finds a shortest path by searching from both ends at once.
+///
+/// A forward search from `source` over outgoing edges alternates with a
+/// backward search from `target` over incoming edges. Each time one side
+/// reaches a node the other side has reached, the path through it is a
+/// candidate. The search stops once the smallest queued distances of the
+/// two sides sum to at least the best candidate: any path through a node
+/// neither side has settled is at least that long. On large graphs the two
+/// searches together settle far fewer nodes than one search from `source`.
+///
+/// Returns the same distance as [`dijkstra_path`], and the same path unless
+/// several shortest paths tie. Edge weights must be non-negative. Falls back
+/// to [`dijkstra_path`] if the store doesn't index incoming edges.
+///
+/// # Returns
+///
+/// The shortest path distance and path, or `None` if unreachable.
+///
+/// # Errors
+///
+/// Returns an error if any edge has a negative weight.
+pub fn bidirectional_dijkstra(
+    store: &LpgStore,
+    source: NodeId,
+    target: NodeId,
+    weight_property: Option<&str>,
+) -> Result<Option<(f64, Vec<NodeId>)>> {
+    reject_negative_weights(store, weight_property)?;
+    if !store.has_backward_adjacency() {
+        return Ok(single_pair_dijkstra(store, source, target, weight_property));
+    }
+    Ok(bidirectional_search(store, source, target, weight_property).0)
+}
+
+/// Bidirectional Dijkstra, also returning how many nodes were expanded.
+fn bidirectional_search(
+    store: &LpgStore,
+    source: NodeId,
+    target: NodeId,
+    weight_property: Option<&str>,
+) -> (Option<(f64, Vec<NodeId>)>, usize) {
+    let weights = EdgeWeight::new(weight_property);
+
+    if store.get_node(source).is_none() || store.get_node(target).is_none() {
+        return (None, 0);
+    }
+    if source == target {
+        return (Some((0.0, vec![source])), 0);
+    }
+
+    let mut sides = [
+        Frontier::new(source, Direction::Outgoing),
+        Frontier::new(target, Direction::Incoming),
+    ];
+    let mut best = f64::INFINITY;
+    let mut meeting = None;
+    let mut expanded = 0;
+    let mut side = 0;
+
+    while sides[0].top() + sides[1].top() < best {
+        let (this, other) = match &mut sides {
+            [forward, backward] if side == 0 => (forward, backward),
+            [forward, backward] => (backward, forward),
+        };
+        side = 1 - side;
+
+        let Some((dist, node)) = this.pop() else {
+            break;
+        };
+        expanded += 1;
+
+        for (neighbor, edge_id) in store.edges_from(node, this.direction) {
+            let new_dist = dist + weights.value(store, edge_id);
+            let is_better = this
+                .distances
+                .get(&neighbor)
+                .map_or(true, |&current| new_dist < current);
+            if !is_better {
+                continue;
+            }
+
+            this.distances.insert(neighbor, new_dist);
+            this.parents.insert(neighbor, node);
+            this.heap.push(MinScored::new(new_dist, neighbor));
+
+            if let Some(&remaining) = other.distances.get(&neighbor) {
+                if new_dist + remaining < best {
+                    best = new_dist + remaining;
+                    meeting = Some(neighbor);
+                }
+            }
+        }
+    }
+
+    let path = meeting.map(|meeting| {
+        let mut path = sides[0].walk(meeting);
+        path.reverse();
+        path.extend(sides[1].walk(meeting).into_iter().skip(1));
+        (best, path)
+    });
+    (path, expanded)
+}
+
+// ============================================================================
+// K Shortest Paths (Yen's Algorithm)
+// ============================================================================
+
+/// Finds up to `k` loopless shortest paths from source to target.
+///
+/// Uses Yen's algorithm: each new path deviates from a previously found one
+/// at some spur node, with the spur path computed by Dijkstra over an overlay
+/// that hides the root path's nodes and the edges already taken from it.
+/// The store itself is never modified.
+///
+/// # Arguments
+///
+/// * `store` - The graph store
+/// * `source` - Starting node ID
+/// * `target` - Target node ID
+/// * `k` - Maximum number of paths to return
+/// * `weight_property` - Optional property name for edge weights (defaults to 1.0)
+///
+/// # Returns
+///
+/// Up to `k` `(distance, path)` pairs sorted by distance. Paths of equal
+/// distance are ordered by their node sequence. Fewer than `k` paths are
+/// returned when no more distinct loopless paths exist.
+///
+/// # Errors
+///
+/// Returns an error if any edge has a negative weight.
+///
+/// # Complexity
+///
+/// O(k · V · (V + E) log V): up to V spur searches per accepted path.
+pub fn k_shortest_paths(
+    store: &LpgStore,
+    source: NodeId,
+    target: NodeId,
+    k: usize,
+    weight_property: Option<&str>,
+) -> Result<Vec<(f64, Vec<NodeId>)>> {
+    reject_negative_weights(store, weight_property)?;
+    Ok(yen_search(store, source, target, k, weight_property))
+}
+
+/// Yen's algorithm behind [`k_shortest_paths`], without the weight check.
+fn yen_search(
+    store: &LpgStore,
+    source: NodeId,
+    target: NodeId,
+    k: usize,
+    weight_property: Option<&str>,
+) -> Vec<(f64, Vec<NodeId>)> {
+    let weights = EdgeWeight::new(weight_property);
+
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let no_edges = FxHashSet::default();
+    let no_nodes = FxHashSet::default();
+    let Some(first) =
+        dijkstra_path_excluding(store, source, target, weight_property, &no_edges, &no_nodes)
+    else {
+        return Vec::new();
+    };
+
+    let mut accepted: Vec<WeightedPath> = vec![first];
+    let mut candidates: Vec<WeightedPath> = Vec::new();
+
+    while accepted.len() < k {
+        let last = &accepted[accepted.len() - 1];
+
+        for i in 0..last.edges.len() {
+            let spur_node = last.nodes[i];
+            let root_nodes = &last.nodes[..=i];
+            let root_edges = &last.edges[..i];
+
+            // Hide the next edge of every accepted path sharing this root
+            let mut excluded_edges: FxHashSet<EdgeId> = FxHashSet::default();
+            for path in &accepted {
+                if path.edges.len() > i && path.edges[..i] == *root_edges {
+                    excluded_edges.insert(path.edges[i]);
+                }
+            }
+
+            // Hide the root path (except the spur node) to keep paths loopless
+            let excluded_nodes: FxHashSet<NodeId> = root_nodes[..i].iter().copied().collect();
+
+            let Some(spur) = dijkstra_path_excluding(
+                store,
+                spur_node,
+                target,
+                weight_property,
+                &excluded_edges,
+                &excluded_nodes,
+            ) else {
+                continue;
+            };
+
+            let root_cost: f64 = root_edges
+                .iter()
+                .map(|&edge| weights.value(store, edge))
+                .sum();
+
+            let mut nodes = root_nodes[..i].to_vec();
+            nodes.extend_from_slice(&spur.nodes);
+            let mut edges = root_edges.to_vec();
+            edges.extend_from_slice(&spur.edges);
+
+            let is_new = accepted
+                .iter()
+                .chain(candidates.iter())
+                .all(|path| path.edges != edges);
+            if is_new {
+                candidates.push(WeightedPath {
+                    cost: root_cost + spur.cost,
+                    nodes,
+                    edges,
+                });
+            }
+        }
+
+        // Promote the cheapest candidate; ties break on node sequence
+        let Some(best) = candidates
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                a.cost
+                    .total_cmp(&b.cost)
+                    .then_with(|| a.nodes.cmp(&b.nodes))
+            })
+            .map(|(idx, _)| idx)
+        else {
+            break;
+        };
+        accepted.push(candidates.swap_remove(best));
+    }
+
+    accepted
+        .into_iter()
+        .map(|path| (path.cost, path.nodes))
+        .collect()
+}
+
 // ============================================================================
 // A* Algorithm
 // ============================================================================
 
 /// Runs A* algorithm with a heuristic function.
 ///
+/// The heuristic estimates the remaining cost from a node to `target`, for
+/// example the straight-line distance between node coordinates. The search
+/// expands nodes in order of cost so far plus that estimate, so a closer
+/// estimate means fewer nodes expanded. A heuristic of `0.0` everywhere
+/// reduces A* to Dijkstra's algorithm.
+///
+/// The result is a shortest path only if the heuristic is admissible (never
+/// overestimates the remaining cost) and consistent (drops by at most the
+/// edge weight along any edge). Any other heuristic can still find a path,
+/// but loses the optimality guarantee. Edge weights must be non-negative.
+///
 /// # Arguments
 ///
 /// * `store` - The graph store
 /// * `source` - Starting node ID
 /// * `target` - Target node ID
 /// * `weight_property` - Optional property name for edge weights
-/// * `heuristic` - Function estimating cost from node to target (must be admissible)
+/// * `heuristic` - Function estimating cost from node to target
 ///
 /// # Returns
 ///
 /// The shortest path distance and path, or `None` if unreachable.
 ///
+/// # Errors
+///
+/// Returns an error if any edge has a negative weight.
+///
 /// # Complexity
 ///
 /// O(E) in the best case with a good heuristic, O((V + E) log V) in the worst case.
@@ -234,13 +620,37 @@ pub fn astar<H>(
     target: NodeId,
     weight_property: Option<&str>,
     heuristic: H,
+) -> Result<Option<(f64, Vec<NodeId>)>>
+where
+    H: Fn(NodeId) -> f64,
+{
+    reject_negative_weights(store, weight_property)?;
+    Ok(astar_search(
+        store,
+        source,
+        target,
+        weight_property,
+        heuristic,
+    ))
+}
+
+/// A* search behind [`astar`], without the weight check.
+fn astar_search<H>(
+    store: &LpgStore,
+    source: NodeId,
+    target: NodeId,
+    weight_property: Option<&str>,
+    heuristic: H,
 ) -> Option<(f64, Vec<NodeId>)>
 where
     H: Fn(NodeId) -> f64,
 {
+    let weights = EdgeWeight::new(weight_property);
+
     let mut g_score: FxHashMap<NodeId, f64> = FxHashMap::default();
     let mut predecessors: FxHashMap<NodeId, NodeId> = FxHashMap::default();
-    let mut heap: BinaryHeap<MinScored<f64, NodeId>> = BinaryHeap::new();
+    // Scored by f = g + h, carrying the g the entry was pushed with
+    let mut heap: BinaryHeap<MinScored<f64, (f64, NodeId)>> = BinaryHeap::new();
 
     // Check if source and target exist
     if store.get_node(source).is_none() || store.get_node(target).is_none() {
@@ -248,10 +658,16 @@ where
     }
 
     g_score.insert(source, 0.0);
-    let f_score = heuristic(source);
-    heap.push(MinScored::new(f_score, source));
+    heap.push(MinScored::new(heuristic(source), (0.0, source)));
+
+    while let Some(MinScored(_, (entry_g, node))) = heap.pop() {
+        let current_g = *g_score.get(&node).unwrap_or(&f64::INFINITY);
+
+        // A cheaper path to this node was found after this entry was pushed
+        if entry_g > current_g {
+            continue;
+        }
 
-    while let Some(MinScored(_, node)) = heap.pop() {
         if node == target {
             // Reconstruct path
             let mut path = Vec::new();
@@ -262,14 +678,12 @@ where
             }
             path.push(source);
             path.reverse();
-            return Some((*g_score.get(&target)?, path));
+            return Some((current_g, path));
         }
 
-        let current_g = *g_score.get(&node).unwrap_or(&f64::INFINITY);
-
         // Explore neighbors
         for (neighbor, edge_id) in store.edges_from(node, Direction::Outgoing) {
-            let weight = extract_weight(store, edge_id, weight_property);
+            let weight = weights.value(store, edge_id);
             let tentative_g = current_g + weight;
 
             let is_better = g_score
@@ -280,7 +694,7 @@ where
                 predecessors.insert(neighbor, node);
                 g_score.insert(neighbor, tentative_g);
                 let f = tentative_g + heuristic(neighbor);
-                heap.push(MinScored::new(f, neighbor));
+                heap.push(MinScored::new(f, (tentative_g, neighbor)));
             }
         }
     }
@@ -299,14 +713,23 @@ pub struct BellmanFordResult {
     pub distances: FxHashMap<NodeId, f64>,
     /// Predecessor map for path reconstruction.
     pub predecessors: FxHashMap<NodeId, NodeId>,
-    /// Whether a negative cycle was detected.
+    /// Whether a negative cycle reachable from the source was detected.
+    ///
+    /// If so, distances to nodes reachable from the cycle are not shortest
+    /// distances: walking the cycle again makes them arbitrarily small.
     pub has_negative_cycle: bool,
+    /// Nodes on one negative cycle, in edge order: each node has an edge to
+    /// the next, and the last to the first. `None` without a negative cycle.
+    pub negative_cycle: Option<Vec<NodeId>>,
     /// The source node used for path reconstruction.
     source: NodeId,
 }
 
 impl BellmanFordResult {
     /// Reconstructs the path from source to target.
+    ///
+    /// Returns `None` if `target` is unreachable, or if its predecessors lead
+    /// into a negative cycle rather than back to the source.
     pub fn path_to(&self, target: NodeId) -> Option<Vec<NodeId>> {
         if !self.distances.contains_key(&target) {
             return None;
@@ -317,6 +740,9 @@ impl BellmanFordResult {
 
         while current != self.source {
             let pred = self.predecessors.get(&current)?;
+            if path.len() > self.distances.len() {
+                return None; // Looping around a negative cycle
+            }
             path.push(*pred);
             current = *pred;
         }
@@ -329,7 +755,9 @@ impl BellmanFordResult {
 /// Runs Bellman-Ford algorithm from a source node.
 ///
 /// Unlike Dijkstra, this algorithm handles negative edge weights
-/// and detects negative cycles.
+/// and detects negative cycles. Only cycles reachable from `source` are
+/// reported; a negative cycle elsewhere in the graph doesn't affect any
+/// distance from `source`.
 ///
 /// # Arguments
 ///
@@ -339,7 +767,7 @@ impl BellmanFordResult {
 ///
 /// # Returns
 ///
-/// Distances, predecessors, and negative cycle detection flag.
+/// Distances, predecessors, and the nodes of one negative cycle if any.
 ///
 /// # Complexity
 ///
@@ -349,19 +777,46 @@ pub fn bellman_ford(
     source: NodeId,
     weight_property: Option<&str>,
 ) -> BellmanFordResult {
-    let mut distances: FxHashMap<NodeId, f64> = FxHashMap::default();
-    let mut predecessors: FxHashMap<NodeId, NodeId> = FxHashMap::default();
-
     // Check if source exists
     if store.get_node(source).is_none() {
         return BellmanFordResult {
-            distances,
-            predecessors,
+            distances: FxHashMap::default(),
+            predecessors: FxHashMap::default(),
             has_negative_cycle: false,
+            negative_cycle: None,
             source,
         };
     }
 
+    let (distances, predecessors, negative_cycle) =
+        bellman_ford_from(store, &[source], EdgeWeight::new(weight_property));
+
+    BellmanFordResult {
+        distances,
+        predecessors,
+        has_negative_cycle: negative_cycle.is_some(),
+        negative_cycle,
+        source,
+    }
+}
+
+/// Bellman-Ford relaxation with every node of `sources` at distance zero.
+///
+/// Several sources behave like one virtual source with a zero-weight edge to
+/// each of them. Returns distances, predecessors, and one negative cycle
+/// reachable from the sources, if any.
+fn bellman_ford_from(
+    store: &LpgStore,
+    sources: &[NodeId],
+    weights: EdgeWeight<'_>,
+) -> (
+    FxHashMap<NodeId, f64>,
+    FxHashMap<NodeId, NodeId>,
+    Option<Vec<NodeId>>,
+) {
+    let mut distances: FxHashMap<NodeId, f64> = FxHashMap::default();
+    let mut predecessors: FxHashMap<NodeId, NodeId> = FxHashMap::default();
+
     // Collect all nodes and edges
     let nodes: Vec<NodeId> = store.node_ids();
     let edges: Vec<(NodeId, NodeId, grafeo_common::types::EdgeId)> = nodes
@@ -376,14 +831,16 @@ pub fn bellman_ford(
     let n = nodes.len();
 
     // Initialize distances
-    distances.insert(source, 0.0);
+    for &source in sources {
+        distances.insert(source, 0.0);
+    }
 
     // Relax edges V-1 times
     for _ in 0..n.saturating_sub(1) {
         let mut changed = false;
         for &(u, v, edge_id) in &edges {
             if let Some(&dist_u) = distances.get(&u) {
-                let weight = extract_weight(store, edge_id, weight_property);
+                let weight = weights.value(store, edge_id);
                 let new_dist = dist_u + weight;
 
                 let is_better = distances
@@ -402,26 +859,48 @@ pub fn bellman_ford(
         }
     }
 
-    // Check for negative cycles
-    let mut has_negative_cycle = false;
+    // A V-th round can only still improve a distance through a negative
+    // cycle. Only reached nodes have distances, so unreachable cycles never
+    // show up here.
+    let mut negative_cycle = None;
     for &(u, v, edge_id) in &edges {
         if let Some(&dist_u) = distances.get(&u) {
-            let weight = extract_weight(store, edge_id, weight_property);
+            let weight = weights.value(store, edge_id);
             if let Some(&dist_v) = distances.get(&v) {
                 if dist_u + weight < dist_v {
-                    has_negative_cycle = true;
+                    predecessors.insert(v, u);
+                    negative_cycle = trace_cycle(&predecessors, v, n);
                     break;
                 }
             }
         }
     }
 
-    BellmanFordResult {
-        distances,
-        predecessors,
-        has_negative_cycle,
-        source,
+    (distances, predecessors, negative_cycle)
+}
+
+/// Recovers the cycle behind a node still relaxable after V-1 rounds.
+///
+/// Following `n` predecessors from such a node is guaranteed to land on the
+/// cycle; from there, predecessors lead around it back to the same node.
+fn trace_cycle(
+    predecessors: &FxHashMap<NodeId, NodeId>,
+    relaxed: NodeId,
+    n: usize,
+) -> Option<Vec<NodeId>> {
+    let mut start = relaxed;
+    for _ in 0..n {
+        start = *predecessors.get(&start)?;
     }
+
+    let mut cycle = vec![start];
+    let mut current = *predecessors.get(&start)?;
+    while current != start {
+        cycle.push(current);
+        current = *predecessors.get(&current)?;
+    }
+    cycle.reverse();
+    Some(cycle)
 }
 
 // ============================================================================
@@ -505,6 +984,8 @@ impl FloydWarshallResult {
 ///
 /// O(V³)
 pub fn floyd_warshall(store: &LpgStore, weight_property: Option<&str>) -> FloydWarshallResult {
+    let weights = EdgeWeight::new(weight_property);
+
     let nodes: Vec<NodeId> = store.node_ids();
     let n = nodes.len();
 
@@ -527,7 +1008,7 @@ pub fn floyd_warshall(store: &LpgStore, weight_property: Option<&str>) -> FloydW
     for (idx, &node) in nodes.iter().enumerate() {
         for (neighbor, edge_id) in store.edges_from(node, Direction::Outgoing) {
             if let Some(&neighbor_idx) = node_to_index.get(&neighbor) {
-                let weight = extract_weight(store, edge_id, weight_property);
+                let weight = weights.value(store, edge_id);
                 if weight < distances[idx][neighbor_idx] {
                     distances[idx][neighbor_idx] = weight;
                     next[idx][neighbor_idx] = Some(neighbor_idx);
@@ -557,6 +1038,174 @@ pub fn floyd_warshall(store: &LpgStore, weight_property: Option<&str>) -> FloydW
     }
 }
 
+// ============================================================================
+// All-Pairs Dijkstra
+// ============================================================================
+
+/// Runs Dijkstra from every node for all-pairs shortest paths.
+///
+/// Produces the same [`FloydWarshallResult`] as [`floyd_warshall`], with
+/// unreachable pairs at infinity, but is much cheaper on sparse graphs.
+/// Dijkstra needs non-negative weights; use [`floyd_warshall`] or
+/// [`bellman_ford`] when some are negative.
+///
+/// # Errors
+///
+/// Returns an error if any edge has a negative weight.
+///
+/// # Complexity
+///
+/// O(V (V + E) log V)
+pub fn all_pairs_dijkstra(
+    store: &LpgStore,
+    weight_property: Option<&str>,
+) -> Result<FloydWarshallResult> {
+    all_pairs_dijkstra_impl(store, weight_property, false)
+}
+
+/// Parallel [`all_pairs_dijkstra`]: sources are processed concurrently on
+/// the current rayon thread pool.
+///
+/// # Errors
+///
+/// Returns an error if any edge has a negative weight.
+pub fn all_pairs_dijkstra_parallel(
+    store: &LpgStore,
+    weight_property: Option<&str>,
+) -> Result<FloydWarshallResult> {
+    all_pairs_dijkstra_impl(store, weight_property, true)
+}
+
+fn all_pairs_dijkstra_impl(
+    store: &LpgStore,
+    weight_property: Option<&str>,
+    parallel: bool,
+) -> Result<FloydWarshallResult> {
+    reject_negative_weights(store, weight_property)?;
+
+    let weights = EdgeWeight::new(weight_property);
+    let single_source =
+        |source| dijkstra_with(store, source, |edge_id, _, _| weights.value(store, edge_id));
+    Ok(all_pairs_from(store, parallel, single_source))
+}
+
+/// Assembles an all-pairs result from one single-source run per node.
+fn all_pairs_from(
+    store: &LpgStore,
+    parallel: bool,
+    single_source: impl Fn(NodeId) -> DijkstraResult + Sync,
+) -> FloydWarshallResult {
+    let nodes: Vec<NodeId> = store.node_ids();
+    let node_to_index: FxHashMap<NodeId, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(idx, &node)| (node, idx))
+        .collect();
+
+    let row = |&source: &NodeId| matrix_row(&node_to_index, source, &single_source(source));
+    let rows: Vec<(Vec<f64>, Vec<Option<usize>>)> = if parallel {
+        nodes.par_iter().map(row).collect()
+    } else {
+        nodes.iter().map(row).collect()
+    };
+    let (distances, next) = rows.into_iter().unzip();
+
+    FloydWarshallResult {
+        distances,
+        next,
+        node_to_index,
+        index_to_node: nodes,
+    }
+}
+
+/// Distance and next-hop matrix rows for a single source.
+fn matrix_row(
+    node_to_index: &FxHashMap<NodeId, usize>,
+    source: NodeId,
+    result: &DijkstraResult,
+) -> (Vec<f64>, Vec<Option<usize>>) {
+    let n = node_to_index.len();
+
+    let mut distances = vec![f64::INFINITY; n];
+    for (node, &dist) in &result.distances {
+        distances[node_to_index[node]] = dist;
+    }
+
+    // The first hop towards a target is the first hop towards its
+    // predecessor; walk back until a known hop or the source, then memoize
+    let mut next: Vec<Option<usize>> = vec![None; n];
+    let mut chain = Vec::new();
+    for &target in result.predecessors.keys() {
+        let mut current = target;
+        let hop = loop {
+            let idx = node_to_index[&current];
+            if let Some(hop) = next[idx] {
+                break hop;
+            }
+            chain.push(idx);
+            let pred = result.predecessors[&current];
+            if pred == source {
+                break idx;
+            }
+            current = pred;
+        };
+        for idx in chain.drain(..) {
+            next[idx] = Some(hop);
+        }
+    }
+
+    (distances, next)
+}
+
+// ============================================================================
+// Johnson's Algorithm
+// ============================================================================
+
+/// Runs Johnson's algorithm for all-pairs shortest paths.
+///
+/// Handles negative edge weights like [`floyd_warshall`], without its O(V³)
+/// cost on sparse graphs. Bellman-Ford from a virtual source yields a
+/// potential `h` per node; reweighting each edge `u -> v` to
+/// `w + h(u) - h(v)` makes every weight non-negative while preserving
+/// shortest paths, so Dijkstra can run from every node. Unreachable pairs
+/// are at infinity.
+///
+/// # Errors
+///
+/// Returns an error naming the cycle if the graph has a negative cycle.
+///
+/// # Complexity
+///
+/// O(V × E + V (V + E) log V)
+pub fn johnson(store: &LpgStore, weight_property: Option<&str>) -> Result<FloydWarshallResult> {
+    let weights = EdgeWeight::new(weight_property);
+
+    let (potentials, _, negative_cycle) = bellman_ford_from(store, &store.node_ids(), weights);
+    if let Some(cycle) = negative_cycle {
+        let nodes: Vec<String> = cycle
+            .iter()
+            .chain(cycle.first())
+            .map(|node| node.0.to_string())
+            .collect();
+        return Err(Error::InvalidValue(format!(
+            "Graph contains a negative cycle: {}",
+            nodes.join(" -> ")
+        )));
+    }
+
+    let single_source = |source: NodeId| {
+        // Rounding can leave a reweighted shortest-path edge just below zero
+        let mut result = dijkstra_with(store, source, |edge_id, from, to| {
+            (weights.value(store, edge_id) + potentials[&from] - potentials[&to]).max(0.0)
+        });
+        for (node, dist) in &mut result.distances {
+            *dist += potentials[node] - potentials[&source];
+        }
+        result
+    };
+    Ok(all_pairs_from(store, false, single_source))
+}
+
 // ============================================================================
 // Algorithm Wrappers for Plugin Registry
 // ============================================================================
@@ -593,6 +1242,8 @@ fn dijkstra_params() -> &'static [ParameterDef] {
 }
 
 /// Dijkstra algorithm wrapper for the plugin registry.
+///
+/// Fails on negative edge weights, which Dijkstra cannot handle.
 pub struct DijkstraAlgorithm;
 
 impl GraphAlgorithm for DijkstraAlgorithm {
@@ -619,7 +1270,7 @@ impl GraphAlgorithm for DijkstraAlgorithm {
         if let Some(target_id) = params.get_int("target") {
             // Single-pair shortest path
             let target = NodeId::new(target_id as u64);
-            match dijkstra_path(store, source, target, weight_prop.as_deref()) {
+            match dijkstra_path(store, source, target, weight_prop.as_deref())? {
                 Some((distance, path)) => {
                     let mut result = AlgorithmResult::new(vec![
                         "source".to_string(),
@@ -661,7 +1312,7 @@ impl GraphAlgorithm for DijkstraAlgorithm {
             }
         } else {
             // Single-source shortest paths
-            let dijkstra_result = dijkstra(store, source, weight_prop.as_deref());
+            let dijkstra_result = dijkstra(store, source, weight_prop.as_deref())?;
 
             let mut result =
                 AlgorithmResult::new(vec!["node_id".to_string(), "distance".to_string()]);
@@ -779,27 +1430,79 @@ impl GraphAlgorithm for FloydWarshallAlgorithm {
 
         let fw_result = floyd_warshall(store, weight_prop.as_deref());
 
-        let mut result = AlgorithmResult::new(vec![
-            "source".to_string(),
-            "target".to_string(),
-            "distance".to_string(),
-        ]);
+        Ok(all_pairs_rows(&fw_result))
+    }
+}
 
-        // Output all pairs with finite distances
-        for (i, &from_node) in fw_result.index_to_node.iter().enumerate() {
-            for (j, &to_node) in fw_result.index_to_node.iter().enumerate() {
-                let dist = fw_result.distances[i][j];
-                if dist < f64::INFINITY {
-                    result.add_row(vec![
-                        Value::Int64(from_node.0 as i64),
-                        Value::Int64(to_node.0 as i64),
-                        Value::Float64(dist),
-                    ]);
-                }
+/// Outputs all pairs with finite distances as `source, target, distance` rows.
+fn all_pairs_rows(all_pairs: &FloydWarshallResult) -> AlgorithmResult {
+    let mut result = AlgorithmResult::new(vec![
+        "source".to_string(),
+        "target".to_string(),
+        "distance".to_string(),
+    ]);
+
+    for (i, &from_node) in all_pairs.index_to_node.iter().enumerate() {
+        for (j, &to_node) in all_pairs.index_to_node.iter().enumerate() {
+            let dist = all_pairs.distances[i][j];
+            if dist < f64::INFINITY {
+                result.add_row(vec![
+                    Value::Int64(from_node.0 as i64),
+                    Value::Int64(to_node.0 as i64),
+                    Value::Float64(dist),
+                ]);
             }
         }
+    }
 
-        Ok(result)
+    result
+}
+
+/// All-pairs Dijkstra wrapper for the plugin registry.
+///
+/// Produces the same rows as [`FloydWarshallAlgorithm`]; prefer it on sparse
+/// graphs. Fails if any edge weight is negative.
+pub struct AllPairsDijkstraAlgorithm;
+
+impl GraphAlgorithm for AllPairsDijkstraAlgorithm {
+    fn name(&self) -> &str {
+        "all_pairs_dijkstra"
+    }
+
+    fn description(&self) -> &str {
+        "All-pairs shortest paths by repeated Dijkstra, for sparse graphs"
+    }
+
+    fn parameters(&self) -> &[ParameterDef] {
+        floyd_warshall_params()
+    }
+
+    fn execute(&self, store: &LpgStore, params: &Parameters) -> Result<AlgorithmResult> {
+        let weight_prop = params.get_string("weight");
+
+        let all_pairs = all_pairs_dijkstra(store, weight_prop.as_deref())?;
+
+        Ok(all_pairs_rows(&all_pairs))
+    }
+}
+
+impl ParallelGraphAlgorithm for AllPairsDijkstraAlgorithm {
+    fn execute_parallel(
+        &self,
+        store: &LpgStore,
+        params: &Parameters,
+        num_threads: usize,
+    ) -> Result<AlgorithmResult> {
+        let weight_prop = params.get_string("weight");
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .map_err(|e| Error::Internal(format!("Failed to build thread pool: {e}")))?;
+        let all_pairs =
+            pool.install(|| all_pairs_dijkstra_parallel(store, weight_prop.as_deref()))?;
+
+        Ok(all_pairs_rows(&all_pairs))
     }
 }
 
@@ -842,7 +1545,7 @@ mod tests {
     #[test]
     fn test_dijkstra_basic() {
         let store = create_weighted_graph();
-        let result = dijkstra(&store, NodeId::new(0), Some("weight"));
+        let result = dijkstra(&store, NodeId::new(0), Some("weight")).unwrap();
 
         assert!(result.distances.contains_key(&NodeId::new(0)));
         assert_eq!(result.distance_to(NodeId::new(0)), Some(0.0));
@@ -853,7 +1556,7 @@ mod tests {
     #[test]
     fn test_dijkstra_path() {
         let store = create_weighted_graph();
-        let result = dijkstra(&store, NodeId::new(0), Some("weight"));
+        let result = dijkstra(&store, NodeId::new(0), Some("weight")).unwrap();
 
         let path = result.path_to(NodeId::new(0), NodeId::new(2));
         assert!(path.is_some());
@@ -866,7 +1569,7 @@ mod tests {
     #[test]
     fn test_dijkstra_single_pair() {
         let store = create_weighted_graph();
-        let result = dijkstra_path(&store, NodeId::new(0), NodeId::new(4), Some("weight"));
+        let result = dijkstra_path(&store, NodeId::new(0), NodeId::new(4), Some("weight")).unwrap();
 
         assert!(result.is_some());
         let (distance, path) = result.unwrap();
@@ -881,10 +1584,91 @@ mod tests {
         let n0 = store.create_node(&["Node"]);
         let _n1 = store.create_node(&["Node"]); // Disconnected
 
-        let result = dijkstra_path(&store, n0, NodeId::new(1), None);
+        let result = dijkstra_path(&store, n0, NodeId::new(1), None).unwrap();
         assert!(result.is_none());
     }
 
+    fn create_diamond_graph() -> LpgStore {
+        let store = LpgStore::new();
+
+        // Two equal-cost routes and one longer direct edge:
+        //   0 --1--> 1 --1--> 3
+        //   0 --1--> 2 --1--> 3
+        //   0 -------5------> 3
+        let n0 = store.create_node(&["Node"]);
+        let n1 = store.create_node(&["Node"]);
+        let n2 = store.create_node(&["Node"]);
+        let n3 = store.create_node(&["Node"]);
+
+        store.create_edge_with_props(n0, n1, "EDGE", [("weight", Value::Float64(1.0))]);
+        store.create_edge_with_props(n1, n3, "EDGE", [("weight", Value::Float64(1.0))]);
+        store.create_edge_with_props(n0, n2, "EDGE", [("weight", Value::Float64(1.0))]);
+        store.create_edge_with_props(n2, n3, "EDGE", [("weight", Value::Float64(1.0))]);
+        store.create_edge_with_props(n0, n3, "EDGE", [("weight", Value::Float64(5.0))]);
+
+        store
+    }
+
+    #[test]
+    fn test_k_shortest_paths_ties_and_longer() {
+        let store = create_diamond_graph();
+        let paths =
+            k_shortest_paths(&store, NodeId::new(0), NodeId::new(3), 3, Some("weight")).unwrap();
+
+        let ids = |path: &[NodeId]| path.iter().map(|n| n.0).collect::<Vec<_>>();
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths[0].0, 2.0);
+        assert_eq!(ids(&paths[0].1), vec![0, 1, 3]);
+        assert_eq!(paths[1].0, 2.0);
+        assert_eq!(ids(&paths[1].1), vec![0, 2, 3]);
+        assert_eq!(paths[2].0, 5.0);
+        assert_eq!(ids(&paths[2].1), vec![0, 3]);
+    }
+
+    #[test]
+    fn test_k_shortest_paths_fewer_than_k() {
+        let store = create_diamond_graph();
+        let paths =
+            k_shortest_paths(&store, NodeId::new(0), NodeId::new(3), 10, Some("weight")).unwrap();
+        assert_eq!(paths.len(), 3);
+
+        let paths =
+            k_shortest_paths(&store, NodeId::new(0), NodeId::new(3), 1, Some("weight")).unwrap();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].0, 2.0);
+
+        assert!(
+            k_shortest_paths(&store, NodeId::new(0), NodeId::new(3), 0, None)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_k_shortest_paths_unreachable() {
+        let store = create_diamond_graph();
+        // Edges only point towards node 3
+        let paths =
+            k_shortest_paths(&store, NodeId::new(3), NodeId::new(0), 3, Some("weight")).unwrap();
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    fn test_k_shortest_paths_loopless() {
+        let store = create_weighted_graph();
+        let paths =
+            k_shortest_paths(&store, NodeId::new(0), NodeId::new(4), 5, Some("weight")).unwrap();
+
+        // 0 -> 1 -> 2 -> 4 (6) and 0 -> 3 -> 1 -> 2 -> 4 (9)
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].0, 6.0);
+        assert_eq!(paths[1].0, 9.0);
+        for (_, path) in &paths {
+            let unique: FxHashSet<NodeId> = path.iter().copied().collect();
+            assert_eq!(unique.len(), path.len());
+        }
+    }
+
     #[test]
     fn test_bellman_ford_basic() {
         let store = create_weighted_graph();
@@ -895,6 +1679,99 @@ mod tests {
         assert_eq!(*result.distances.get(&NodeId::new(0)).unwrap(), 0.0);
     }
 
+    /// Asserts that `cycle` lists the nodes of a cycle in edge order.
+    fn assert_cycle(store: &LpgStore, cycle: &[NodeId]) {
+        for (i, &node) in cycle.iter().enumerate() {
+            let next = cycle[(i + 1) % cycle.len()];
+            assert!(
+                store
+                    .edges_from(node, Direction::Outgoing)
+                    .any(|(neighbor, _)| neighbor == next),
+                "no edge {node:?} -> {next:?} in {cycle:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_bellman_ford_reachable_negative_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 1 with the cycle summing to -1, then 3 -> 4
+        let store = LpgStore::new();
+        let n: Vec<NodeId> = (0..5).map(|_| store.create_node(&["Node"])).collect();
+        for (from, to, weight) in [(0, 1, 5), (1, 2, 2), (2, 3, -4), (3, 1, 1), (3, 4, 1)] {
+            store.create_edge_with_props(
+                n[from],
+                n[to],
+                "EDGE",
+                [("weight", Value::Int64(weight))],
+            );
+        }
+
+        let result = bellman_ford(&store, n[0], Some("weight"));
+        assert!(result.has_negative_cycle);
+        let mut cycle = result.negative_cycle.clone().unwrap();
+        assert_cycle(&store, &cycle);
+        cycle.sort();
+        assert_eq!(cycle, vec![n[1], n[2], n[3]]);
+
+        // Paths into the cycle's reach have no shortest form
+        assert_eq!(result.path_to(n[4]), None);
+        assert_eq!(result.path_to(n[0]), Some(vec![n[0]]));
+
+        // A cycle through the source itself
+        store.create_edge_with_props(n[1], n[0], "EDGE", [("weight", Value::Int64(-10))]);
+        let result = bellman_ford(&store, n[0], Some("weight"));
+        let cycle = result.negative_cycle.unwrap();
+        assert_cycle(&store, &cycle);
+    }
+
+    #[test]
+    fn test_bellman_ford_unreachable_negative_cycle() {
+        // 0 -> 1, and a separate 2 -> 3 -> 2 cycle summing to -2
+        let store = LpgStore::new();
+        let n: Vec<NodeId> = (0..4).map(|_| store.create_node(&["Node"])).collect();
+        for (from, to, weight) in [(0, 1, 1), (2, 3, -3), (3, 2, 1)] {
+            store.create_edge_with_props(
+                n[from],
+                n[to],
+                "EDGE",
+                [("weight", Value::Int64(weight))],
+            );
+        }
+
+        let result = bellman_ford(&store, n[0], Some("weight"));
+        assert!(!result.has_negative_cycle);
+        assert_eq!(result.negative_cycle, None);
+        assert_eq!(result.distances.get(&n[1]), Some(&1.0));
+        assert_eq!(result.path_to(n[2]), None);
+
+        // From inside, it is found
+        let result = bellman_ford(&store, n[2], Some("weight"));
+        assert!(result.has_negative_cycle);
+        assert_cycle(&store, &result.negative_cycle.unwrap());
+    }
+
+    #[test]
+    fn test_bellman_ford_negative_dag() {
+        // Negative edges without a cycle: 0 -> 2 -> 1 beats 0 -> 1
+        let store = LpgStore::new();
+        let n: Vec<NodeId> = (0..4).map(|_| store.create_node(&["Node"])).collect();
+        for (from, to, weight) in [(0, 1, 4), (0, 2, 1), (2, 1, -3), (1, 3, -1)] {
+            store.create_edge_with_props(
+                n[from],
+                n[to],
+                "EDGE",
+                [("weight", Value::Int64(weight))],
+            );
+        }
+
+        let result = bellman_ford(&store, n[0], Some("weight"));
+        assert!(!result.has_negative_cycle);
+        assert_eq!(result.negative_cycle, None);
+        assert_eq!(result.distances[&n[1]], -2.0);
+        assert_eq!(result.distances[&n[3]], -3.0);
+        assert_eq!(result.path_to(n[3]), Some(vec![n[0], n[2], n[1], n[3]]));
+    }
+
     #[test]
     fn test_floyd_warshall_basic() {
         let store = create_weighted_graph();
@@ -921,6 +1798,178 @@ mod tests {
         assert_eq!(*path.last().unwrap(), NodeId::new(2));
     }
 
+    #[test]
+    fn test_all_pairs_dijkstra_matches_floyd_warshall() {
+        let graphs = [
+            (create_weighted_graph(), 5),
+            (create_power_weighted_graph(12, 30), 12),
+            (create_power_weighted_graph(20, 31), 20),
+        ];
+        for (store, nodes) in &graphs {
+            let expected = floyd_warshall(store, Some("weight"));
+            let result = all_pairs_dijkstra(store, Some("weight")).unwrap();
+            assert_eq!(result.nodes(), expected.nodes());
+            for source in 0..*nodes {
+                for target in 0..*nodes {
+                    let (source, target) = (NodeId::new(source), NodeId::new(target));
+                    assert_eq!(
+                        result.distance(source, target),
+                        expected.distance(source, target),
+                        "{source:?} -> {target:?}"
+                    );
+                    assert_eq!(
+                        result.path(source, target),
+                        expected.path(source, target),
+                        "{source:?} -> {target:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_pairs_dijkstra_parallel() {
+        let store = create_power_weighted_graph(60, 150);
+        let sequential = all_pairs_dijkstra(&store, Some("weight")).unwrap();
+        let parallel = all_pairs_dijkstra_parallel(&store, Some("weight")).unwrap();
+        assert_eq!(parallel.distances, sequential.distances);
+        assert_eq!(parallel.next, sequential.next);
+
+        let mut params = Parameters::new();
+        params.set_string("weight", "weight");
+        let rows = FloydWarshallAlgorithm
+            .execute(&store, &params)
+            .unwrap()
+            .row_count();
+        assert_eq!(
+            AllPairsDijkstraAlgorithm
+                .execute(&store, &params)
+                .unwrap()
+                .row_count(),
+            rows
+        );
+        assert_eq!(
+            AllPairsDijkstraAlgorithm
+                .execute_parallel(&store, &params, 4)
+                .unwrap()
+                .row_count(),
+            rows
+        );
+    }
+
+    #[test]
+    fn test_all_pairs_dijkstra_unreachable_and_negative() {
+        let store = LpgStore::new();
+        let n0 = store.create_node(&["Node"]);
+        let n1 = store.create_node(&["Node"]);
+        let n2 = store.create_node(&["Node"]);
+        let e01 = store.create_edge_with_props(n0, n1, "EDGE", [("weight", Value::Int64(2))]);
+
+        let result = all_pairs_dijkstra(&store, Some("weight")).unwrap();
+        assert_eq!(result.distance(n0, n1), Some(2.0));
+        assert_eq!(result.distance(n1, n0), None);
+        assert_eq!(result.distance(n0, n2), None);
+        assert_eq!(result.distance(n2, n2), Some(0.0));
+        assert_eq!(result.path(n0, n1), Some(vec![n0, n1]));
+        assert_eq!(result.path(n2, n0), None);
+
+        store.set_edge_property(e01, "weight", Value::Int64(-2));
+        let err = all_pairs_dijkstra(&store, Some("weight")).unwrap_err();
+        assert!(err.to_string().contains("floyd_warshall"), "{err}");
+        assert!(!floyd_warshall(&store, Some("weight")).has_negative_cycle());
+    }
+
+    /// A graph with negative edges but no negative cycle:
+    ///
+    /// ```text
+    /// 0 --3--> 1 --(-2)--> 2 --2--> 3
+    /// 0 ------(4)--------> 2
+    /// 3 --1--> 1,  4 --(-5)--> 0
+    /// ```
+    fn create_negative_weight_graph() -> LpgStore {
+        let store = LpgStore::new();
+        let n: Vec<NodeId> = (0..5).map(|_| store.create_node(&["Node"])).collect();
+        for (from, to, weight) in [
+            (0, 1, 3),
+            (1, 2, -2),
+            (2, 3, 2),
+            (0, 2, 4),
+            (3, 1, 1),
+            (4, 0, -5),
+        ] {
+            store.create_edge_with_props(
+                n[from],
+                n[to],
+                "EDGE",
+                [("weight", Value::Int64(weight))],
+            );
+        }
+        store
+    }
+
+    #[test]
+    fn test_johnson_matches_floyd_warshall() {
+        let graphs = [
+            (create_negative_weight_graph(), 5),
+            (create_weighted_graph(), 5),
+            (create_power_weighted_graph(20, 31), 20),
+        ];
+        for (store, nodes) in &graphs {
+            let expected = floyd_warshall(store, Some("weight"));
+            let result = johnson(store, Some("weight")).unwrap();
+            for source in 0..*nodes {
+                for target in 0..*nodes {
+                    let (source, target) = (NodeId::new(source), NodeId::new(target));
+                    assert_eq!(
+                        result.distance(source, target),
+                        expected.distance(source, target),
+                        "{source:?} -> {target:?}"
+                    );
+                    assert_eq!(
+                        result.path(source, target),
+                        expected.path(source, target),
+                        "{source:?} -> {target:?}"
+                    );
+                }
+            }
+        }
+
+        let result = johnson(&graphs[0].0, Some("weight")).unwrap();
+        let (n0, n1, n3, n4) = (
+            NodeId::new(0),
+            NodeId::new(1),
+            NodeId::new(3),
+            NodeId::new(4),
+        );
+        assert_eq!(result.distance(n4, n3), Some(-2.0));
+        assert_eq!(
+            result.path(n4, n3),
+            Some(vec![n4, n0, n1, NodeId::new(2), n3])
+        );
+        assert_eq!(result.distance(n1, n0), None);
+    }
+
+    #[test]
+    fn test_johnson_negative_cycle() {
+        let store = create_negative_weight_graph();
+        // A second 3 -> 1 edge makes 1 -> 2 -> 3 -> 1 cost -2 + 2 - 3 = -3
+        store.create_edge_with_props(
+            NodeId::new(3),
+            NodeId::new(1),
+            "EDGE",
+            [("weight", Value::Int64(-3))],
+        );
+
+        let err = johnson(&store, Some("weight")).unwrap_err();
+        assert!(
+            err.to_string().contains("negative cycle: 1 -> 2 -> 3 -> 1")
+                || err.to_string().contains("negative cycle: 2 -> 3 -> 1 -> 2")
+                || err.to_string().contains("negative cycle: 3 -> 1 -> 2 -> 3"),
+            "{err}"
+        );
+        assert!(floyd_warshall(&store, Some("weight")).has_negative_cycle());
+    }
+
     #[test]
     fn test_astar_basic() {
         let store = create_weighted_graph();
@@ -934,7 +1983,8 @@ mod tests {
             NodeId::new(4),
             Some("weight"),
             heuristic,
-        );
+        )
+        .unwrap();
         assert!(result.is_some());
 
         let (distance, path) = result.unwrap();
@@ -943,10 +1993,186 @@ mod tests {
         assert_eq!(*path.last().unwrap(), NodeId::new(4));
     }
 
+    #[test]
+    fn test_astar_zero_heuristic_matches_dijkstra() {
+        let store = create_weighted_graph();
+
+        for source in 0..5 {
+            for target in 0..5 {
+                let (source, target) = (NodeId::new(source), NodeId::new(target));
+                assert_eq!(
+                    astar(&store, source, target, Some("weight"), |_| 0.0).unwrap(),
+                    dijkstra_path(&store, source, target, Some("weight")).unwrap(),
+                    "{source:?} -> {target:?}"
+                );
+            }
+        }
+    }
+
+    /// A `size` x `size` grid with unit edges both ways between neighbours,
+    /// each node carrying its `x` and `y` coordinates.
+    fn create_grid(size: i64) -> LpgStore {
+        let store = LpgStore::new();
+        let mut ids = Vec::new();
+        for y in 0..size {
+            for x in 0..size {
+                ids.push(store.create_node_with_props(
+                    &["Cell"],
+                    [("x", Value::Int64(x)), ("y", Value::Int64(y))],
+                ));
+            }
+        }
+        let at = |x: i64, y: i64| ids[(y * size + x) as usize];
+        for y in 0..size {
+            for x in 0..size {
+                if x + 1 < size {
+                    store.create_edge(at(x, y), at(x + 1, y), "NEXT");
+                    store.create_edge(at(x + 1, y), at(x, y), "NEXT");
+                }
+                if y + 1 < size {
+                    store.create_edge(at(x, y), at(x, y + 1), "NEXT");
+                    store.create_edge(at(x, y + 1), at(x, y), "NEXT");
+                }
+            }
+        }
+        store
+    }
+
+    #[test]
+    fn test_astar_good_heuristic_expands_fewer_nodes() {
+        let store = create_grid(10);
+        let coordinates = |node: NodeId| {
+            let node = store.get_node(node).unwrap();
+            let coordinate = |key| match node.get_property(key) {
+                Some(Value::Int64(v)) => *v as f64,
+                other => panic!("Expected coordinate, got {other:?}"),
+            };
+            (coordinate("x"), coordinate("y"))
+        };
+        let (source, target) = (NodeId::new(0), NodeId::new(9));
+        let (tx, ty) = coordinates(target);
+
+        // Every node pushed onto the frontier is scored once
+        let scored = std::cell::Cell::new(0);
+        let blind = astar(&store, source, target, None, |_| {
+            scored.set(scored.get() + 1);
+            0.0
+        })
+        .unwrap();
+        let blind_scored = scored.replace(0);
+
+        // Manhattan distance is admissible on a unit grid
+        let guided = astar(&store, source, target, None, |node| {
+            scored.set(scored.get() + 1);
+            let (x, y) = coordinates(node);
+            (tx - x).abs() + (ty - y).abs()
+        })
+        .unwrap();
+        let guided_scored = scored.get();
+
+        assert_eq!(blind.as_ref().map(|(d, _)| *d), Some(9.0));
+        assert_eq!(guided.as_ref().map(|(d, _)| *d), Some(9.0));
+        assert!(
+            guided_scored < blind_scored,
+            "guided scored {guided_scored}, blind scored {blind_scored}"
+        );
+    }
+
+    /// A pseudo-random graph whose edge weights are distinct powers of two,
+    /// so every path has a distinct, exactly representable cost and every
+    /// shortest path is unique.
+    fn create_power_weighted_graph(nodes: u64, edges: u32) -> LpgStore {
+        let store = LpgStore::new();
+        let ids: Vec<NodeId> = (0..nodes).map(|_| store.create_node(&["Node"])).collect();
+        let mut state = 42_u64;
+        for i in 0..edges {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let from = ids[((state >> 33) % nodes) as usize];
+            let to = ids[((state >> 13) % nodes) as usize];
+            store.create_edge_with_props(
+                from,
+                to,
+                "EDGE",
+                [("weight", Value::Float64(f64::from(2_u32.pow(i % 31))))],
+            );
+        }
+        store
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra_matches_dijkstra() {
+        let graphs = [
+            (create_weighted_graph(), 5),
+            (create_power_weighted_graph(12, 30), 12),
+            (create_power_weighted_graph(20, 31), 20),
+        ];
+        for (store, nodes) in &graphs {
+            for source in 0..*nodes {
+                for target in 0..*nodes {
+                    let (source, target) = (NodeId::new(source), NodeId::new(target));
+                    assert_eq!(
+                        bidirectional_dijkstra(store, source, target, Some("weight")).unwrap(),
+                        dijkstra_path(store, source, target, Some("weight")).unwrap(),
+                        "{source:?} -> {target:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra_expands_fewer_nodes() {
+        let store = create_grid(15);
+        let (source, target) = (NodeId::new(0), NodeId::new(15 * 15 - 1));
+
+        let (path, expanded) = bidirectional_search(&store, source, target, None);
+        let (distance, path) = path.unwrap();
+        assert_eq!(distance, 28.0);
+        assert_eq!(path.len(), 29);
+        assert_eq!(
+            dijkstra_path(&store, source, target, None)
+                .unwrap()
+                .map(|(d, _)| d),
+            Some(28.0)
+        );
+
+        // One-sided Dijkstra settles every node closer than the target first
+        let closer = dijkstra(&store, source, None)
+            .unwrap()
+            .distances
+            .values()
+            .filter(|&&d| d < distance)
+            .count();
+        assert!(
+            expanded < closer,
+            "bidirectional expanded {expanded}, one-sided at least {closer}"
+        );
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra_unreachable() {
+        let store = LpgStore::new();
+        let n0 = store.create_node(&["Node"]);
+        let n1 = store.create_node(&["Node"]);
+        store.create_edge(n1, n0, "EDGE");
+
+        assert_eq!(bidirectional_dijkstra(&store, n0, n1, None).unwrap(), None);
+        assert_eq!(
+            bidirectional_dijkstra(&store, n0, n0, None).unwrap(),
+            Some((0.0, vec![n0]))
+        );
+        assert_eq!(
+            bidirectional_dijkstra(&store, n0, NodeId::new(99), None).unwrap(),
+            None
+        );
+    }
+
     #[test]
     fn test_dijkstra_nonexistent_source() {
         let store = LpgStore::new();
-        let result = dijkstra(&store, NodeId::new(999), None);
+        let result = dijkstra(&store, NodeId::new(999), None).unwrap();
         assert!(result.distances.is_empty());
     }
 
@@ -960,8 +2186,52 @@ mod tests {
         store.create_edge(n1, n2, "EDGE");
 
         // Without weight property, should default to 1.0 per edge
-        let result = dijkstra(&store, n0, None);
+        let result = dijkstra(&store, n0, None).unwrap();
         assert_eq!(result.distance_to(n1), Some(1.0));
         assert_eq!(result.distance_to(n2), Some(2.0));
     }
+
+    #[test]
+    fn test_weight_policies() {
+        // 0 --2--> 1 --(-1)--> 2, and 2 --> 3 without a weight
+        let store = LpgStore::new();
+        let n0 = store.create_node(&["Node"]);
+        let n1 = store.create_node(&["Node"]);
+        let n2 = store.create_node(&["Node"]);
+        let n3 = store.create_node(&["Node"]);
+        store.create_edge_with_props(n0, n1, "EDGE", [("weight", Value::Int64(2))]);
+        store.create_edge_with_props(n1, n2, "EDGE", [("weight", Value::Int64(-1))]);
+        store.create_edge(n2, n3, "EDGE");
+
+        let mut params = Parameters::new();
+        params.set_int("source", n0.0 as i64);
+        params.set_string("weight", "weight");
+
+        // Dijkstra assumes non-negative weights
+        let err = DijkstraAlgorithm.execute(&store, &params).unwrap_err();
+        assert!(
+            err.to_string().contains("negative 'weight' weight -1"),
+            "{err}"
+        );
+        // So do the functions themselves, not just the registry wrapper
+        let weight = Some("weight");
+        assert!(dijkstra(&store, n0, weight).is_err());
+        assert!(dijkstra_path(&store, n0, n3, weight).is_err());
+        assert!(bidirectional_dijkstra(&store, n0, n3, weight).is_err());
+        assert!(k_shortest_paths(&store, n0, n3, 2, weight).is_err());
+        assert!(astar(&store, n0, n3, weight, |_| 0.0).is_err());
+        assert!(dijkstra(&store, n0, None).is_ok());
+
+        // Bellman-Ford accepts them; the missing weight counts as 1.0
+        assert_eq!(
+            BellmanFordAlgorithm
+                .execute(&store, &params)
+                .unwrap()
+                .row_count(),
+            4
+        );
+        let result = bellman_ford(&store, n0, Some("weight"));
+        assert_eq!(result.distances[&n2], 1.0);
+        assert_eq!(result.distances[&n3], 2.0);
+    }
 }
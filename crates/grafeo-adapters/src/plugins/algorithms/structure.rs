@@ -1,4 +1,5 @@
-//! Structure analysis algorithms: Articulation Points, Bridges, K-Core decomposition.
+//! Structure analysis algorithms: Articulation Points, Bridges, K-Core decomposition,
+//! triangle counting and clustering coefficients.
 //!
 //! These algorithms identify critical structural elements in graphs.
 
@@ -13,6 +14,41 @@ use grafeo_core::graph::lpg::LpgStore;
 use super::super::{AlgorithmResult, ParameterDef, ParameterType, Parameters};
 use super::traits::GraphAlgorithm;
 
+// ============================================================================
+// Shared Helpers
+// ============================================================================
+
+/// Builds a sorted, deduplicated undirected adjacency list without self-loops.
+///
+/// Sorted neighbor lists allow merge-based intersection.
+fn sorted_undirected_adjacency(store: &LpgStore) -> (Vec<NodeId>, Vec<Vec<usize>>) {
+    let nodes = store.node_ids();
+
+    let mut node_to_idx: FxHashMap<NodeId, usize> = FxHashMap::default();
+    for (idx, &node) in nodes.iter().enumerate() {
+        node_to_idx.insert(node, idx);
+    }
+
+    let mut adj: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    for (i, &node) in nodes.iter().enumerate() {
+        for (neighbor, _) in store.edges_from(node, Direction::Outgoing) {
+            if let Some(&j) = node_to_idx.get(&neighbor) {
+                if i != j {
+                    adj[i].push(j);
+                    adj[j].push(i);
+                }
+            }
+        }
+    }
+
+    for neighbors in &mut adj {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+
+    (nodes, adj)
+}
+
 // ============================================================================
 // Articulation Points (Cut Vertices)
 // ============================================================================
@@ -20,7 +56,8 @@ use super::traits::GraphAlgorithm;
 /// Finds articulation points (cut vertices) in the graph.
 ///
 /// An articulation point is a vertex whose removal disconnects the graph.
-/// Uses Tarjan's algorithm with low-link values.
+/// Uses Tarjan's algorithm with low-link values, driven by an explicit
+/// stack so that very deep graphs don't overflow the call stack.
 ///
 /// # Arguments
 ///
@@ -34,33 +71,13 @@ use super::traits::GraphAlgorithm;
 ///
 /// O(V + E)
 pub fn articulation_points(store: &LpgStore) -> FxHashSet<NodeId> {
-    let nodes = store.node_ids();
-    let n = nodes.len();
+    let (idx_to_node, adj) = sorted_undirected_adjacency(store);
+    let n = idx_to_node.len();
 
     if n == 0 {
         return FxHashSet::default();
     }
 
-    // Build node index mapping
-    let mut node_to_idx: FxHashMap<NodeId, usize> = FxHashMap::default();
-    let mut idx_to_node: Vec<NodeId> = Vec::with_capacity(n);
-    for (idx, &node) in nodes.iter().enumerate() {
-        node_to_idx.insert(node, idx);
-        idx_to_node.push(node);
-    }
-
-    // Build undirected adjacency list
-    let mut adj: Vec<FxHashSet<usize>> = vec![FxHashSet::default(); n];
-    for &node in &nodes {
-        let i = *node_to_idx.get(&node).unwrap();
-        for (neighbor, _) in store.edges_from(node, Direction::Outgoing) {
-            if let Some(&j) = node_to_idx.get(&neighbor) {
-                adj[i].insert(j);
-                adj[j].insert(i); // Undirected
-            }
-        }
-    }
-
     let mut visited = vec![false; n];
     let mut disc = vec![0usize; n]; // Discovery time
     let mut low = vec![0usize; n]; // Low-link value
@@ -74,7 +91,7 @@ pub fn articulation_points(store: &LpgStore) -> FxHashSet<NodeId> {
             continue;
         }
 
-        // Iterative DFS using explicit stack
+        // Iterative DFS using an explicit stack, so long paths can't overflow
         let mut stack: Vec<(usize, usize)> = vec![(start, 0)]; // (node, neighbor_idx)
         let mut children_count: FxHashMap<usize, usize> = FxHashMap::default();
 
@@ -87,10 +104,7 @@ pub fn articulation_points(store: &LpgStore) -> FxHashSet<NodeId> {
                 children_count.insert(u, 0);
             }
 
-            let neighbors: Vec<usize> = adj[u].iter().copied().collect();
-
-            if idx < neighbors.len() {
-                let v = neighbors[idx];
+            if let Some(&v) = adj[u].get(idx) {
                 stack.last_mut().unwrap().1 += 1;
 
                 if !visited[v] {
@@ -134,7 +148,8 @@ pub fn articulation_points(store: &LpgStore) -> FxHashSet<NodeId> {
 /// Finds bridges (cut edges) in the graph.
 ///
 /// A bridge is an edge whose removal disconnects the graph.
-/// Uses Tarjan's algorithm with low-link values.
+/// Uses Tarjan's algorithm with low-link values, driven by an explicit
+/// stack so that very deep graphs don't overflow the call stack.
 ///
 /// # Arguments
 ///
@@ -148,33 +163,13 @@ pub fn articulation_points(store: &LpgStore) -> FxHashSet<NodeId> {
 ///
 /// O(V + E)
 pub fn bridges(store: &LpgStore) -> Vec<(NodeId, NodeId)> {
-    let nodes = store.node_ids();
-    let n = nodes.len();
+    let (idx_to_node, adj) = sorted_undirected_adjacency(store);
+    let n = idx_to_node.len();
 
     if n == 0 {
         return Vec::new();
     }
 
-    // Build node index mapping
-    let mut node_to_idx: FxHashMap<NodeId, usize> = FxHashMap::default();
-    let mut idx_to_node: Vec<NodeId> = Vec::with_capacity(n);
-    for (idx, &node) in nodes.iter().enumerate() {
-        node_to_idx.insert(node, idx);
-        idx_to_node.push(node);
-    }
-
-    // Build undirected adjacency list
-    let mut adj: Vec<FxHashSet<usize>> = vec![FxHashSet::default(); n];
-    for &node in &nodes {
-        let i = *node_to_idx.get(&node).unwrap();
-        for (neighbor, _) in store.edges_from(node, Direction::Outgoing) {
-            if let Some(&j) = node_to_idx.get(&neighbor) {
-                adj[i].insert(j);
-                adj[j].insert(i);
-            }
-        }
-    }
-
     let mut visited = vec![false; n];
     let mut disc = vec![0usize; n];
     let mut low = vec![0usize; n];
@@ -187,6 +182,7 @@ pub fn bridges(store: &LpgStore) -> Vec<(NodeId, NodeId)> {
             continue;
         }
 
+        // Iterative DFS using an explicit stack, so long paths can't overflow
         let mut stack: Vec<(usize, usize)> = vec![(start, 0)];
 
         while let Some(&(u, idx)) = stack.last() {
@@ -197,10 +193,7 @@ pub fn bridges(store: &LpgStore) -> Vec<(NodeId, NodeId)> {
                 time += 1;
             }
 
-            let neighbors: Vec<usize> = adj[u].iter().copied().collect();
-
-            if idx < neighbors.len() {
-                let v = neighbors[idx];
+            if let Some(&v) = adj[u].get(idx) {
                 stack.last_mut().unwrap().1 += 1;
 
                 if !visited[v] {
@@ -377,6 +370,97 @@ pub fn k_core(store: &LpgStore, k: usize) -> Vec<NodeId> {
     result.k_core(k)
 }
 
+// ============================================================================
+// Triangles and Clustering Coefficient
+// ============================================================================
+
+/// Counts the common elements of two sorted slices.
+fn sorted_intersection_count(a: &[usize], b: &[usize]) -> usize {
+    let (mut i, mut j, mut count) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                count += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    count
+}
+
+/// Counts the triangles in the graph.
+///
+/// # Arguments
+///
+/// * `store` - The graph store (treated as undirected, parallel edges and
+///   self-loops ignored)
+///
+/// # Returns
+///
+/// The total number of triangles, each counted once.
+///
+/// # Complexity
+///
+/// O(Σ d(v)²) in the worst case, using merge-based neighbor intersection.
+pub fn triangle_count(store: &LpgStore) -> usize {
+    let (_, adj) = sorted_undirected_adjacency(store);
+
+    // Count each triangle u < v < w exactly once
+    let mut total = 0;
+    for (u, neighbors) in adj.iter().enumerate() {
+        let higher_u = &neighbors[neighbors.partition_point(|&x| x <= u)..];
+        for &v in higher_u {
+            let higher_v = &adj[v][adj[v].partition_point(|&x| x <= v)..];
+            total += sorted_intersection_count(higher_u, higher_v);
+        }
+    }
+    total
+}
+
+/// Computes the local clustering coefficient of every node.
+///
+/// The coefficient is the fraction of a node's neighbor pairs that are
+/// themselves connected. Nodes with fewer than two neighbors get 0.0.
+///
+/// # Arguments
+///
+/// * `store` - The graph store (treated as undirected, parallel edges and
+///   self-loops ignored)
+///
+/// # Returns
+///
+/// Clustering coefficient in [0, 1] for each node.
+///
+/// # Complexity
+///
+/// O(Σ d(v)²) in the worst case, using merge-based neighbor intersection.
+pub fn clustering_coefficient(store: &LpgStore) -> FxHashMap<NodeId, f64> {
+    let (nodes, adj) = sorted_undirected_adjacency(store);
+
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(u, &node)| {
+            let degree = adj[u].len();
+            if degree < 2 {
+                return (node, 0.0);
+            }
+
+            // Each edge between two neighbors is seen from both ends
+            let links: usize = adj[u]
+                .iter()
+                .map(|&v| sorted_intersection_count(&adj[u], &adj[v]))
+                .sum::<usize>()
+                / 2;
+            let possible = degree * (degree - 1) / 2;
+            (node, links as f64 / possible as f64)
+        })
+        .collect()
+}
+
 // ============================================================================
 // Algorithm Wrappers for Plugin Registry
 // ============================================================================
@@ -659,6 +743,28 @@ mod tests {
         assert!(br.is_empty());
     }
 
+    #[test]
+    fn test_bridges_long_path_no_stack_overflow() {
+        // Deep enough that a recursive DFS would blow the default test stack
+        const N: usize = 200_000;
+        let store = LpgStore::new();
+        let nodes: Vec<NodeId> = (0..N).map(|_| store.create_node(&["Node"])).collect();
+        for pair in nodes.windows(2) {
+            store.create_edge(pair[0], pair[1], "EDGE");
+        }
+
+        let found: FxHashSet<(NodeId, NodeId)> = bridges(&store).into_iter().collect();
+        assert_eq!(found.len(), N - 1);
+        for pair in nodes.windows(2) {
+            assert!(found.contains(&(pair[0], pair[1])));
+        }
+
+        let ap = articulation_points(&store);
+        assert_eq!(ap.len(), N - 2);
+        assert!(!ap.contains(&nodes[0]));
+        assert!(!ap.contains(&nodes[N - 1]));
+    }
+
     #[test]
     fn test_kcore_path() {
         let store = create_simple_path();
@@ -742,4 +848,78 @@ mod tests {
         let total_in_shells: usize = (0..=result.max_core).map(|k| result.k_shell(k).len()).sum();
         assert_eq!(total_in_shells, 4);
     }
+
+    fn create_undirected(n: usize, edges: &[(usize, usize)]) -> (LpgStore, Vec<NodeId>) {
+        let store = LpgStore::new();
+        let nodes: Vec<NodeId> = (0..n).map(|_| store.create_node(&["Node"])).collect();
+        for &(a, b) in edges {
+            store.create_edge(nodes[a], nodes[b], "EDGE");
+            store.create_edge(nodes[b], nodes[a], "EDGE");
+        }
+        (store, nodes)
+    }
+
+    #[test]
+    fn test_triangle_count_triangle() {
+        let (store, nodes) = create_undirected(3, &[(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(triangle_count(&store), 1);
+
+        let coefficients = clustering_coefficient(&store);
+        for node in &nodes {
+            assert_eq!(coefficients[node], 1.0);
+        }
+    }
+
+    #[test]
+    fn test_triangle_count_star() {
+        // Center 0 with leaves 1..=4, plus an isolated node 5
+        let (store, nodes) = create_undirected(6, &[(0, 1), (0, 2), (0, 3), (0, 4)]);
+        assert_eq!(triangle_count(&store), 0);
+
+        let coefficients = clustering_coefficient(&store);
+        assert_eq!(coefficients.len(), 6);
+        for node in &nodes {
+            assert_eq!(coefficients[node], 0.0);
+        }
+    }
+
+    #[test]
+    fn test_triangle_count_k4() {
+        let (store, nodes) =
+            create_undirected(4, &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+        assert_eq!(triangle_count(&store), 4);
+
+        let coefficients = clustering_coefficient(&store);
+        for node in &nodes {
+            assert_eq!(coefficients[node], 1.0);
+        }
+    }
+
+    #[test]
+    fn test_clustering_coefficient_partial() {
+        // Triangle 0-1-2 with a pendant node 3 on node 0
+        let (store, nodes) = create_undirected(4, &[(0, 1), (1, 2), (2, 0), (0, 3)]);
+        assert_eq!(triangle_count(&store), 1);
+
+        let coefficients = clustering_coefficient(&store);
+        assert!((coefficients[&nodes[0]] - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(coefficients[&nodes[1]], 1.0);
+        assert_eq!(coefficients[&nodes[3]], 0.0);
+    }
+
+    #[test]
+    fn test_triangle_count_ignores_direction_and_self_loops() {
+        // One-way edges still form a triangle; the self-loop adds nothing
+        let store = LpgStore::new();
+        let a = store.create_node(&["Node"]);
+        let b = store.create_node(&["Node"]);
+        let c = store.create_node(&["Node"]);
+        store.create_edge(a, b, "EDGE");
+        store.create_edge(b, c, "EDGE");
+        store.create_edge(a, c, "EDGE");
+        store.create_edge(a, a, "EDGE");
+
+        assert_eq!(triangle_count(&store), 1);
+        assert_eq!(clustering_coefficient(&store)[&a], 1.0);
+    }
 }
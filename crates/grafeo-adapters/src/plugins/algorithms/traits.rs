@@ -4,7 +4,8 @@
 //! high-performance graph algorithms, inspired by rustworkx patterns.
 
 use grafeo_common::types::{EdgeId, NodeId, Value};
-use grafeo_common::utils::error::Result;
+use grafeo_common::utils::error::{Error, Result};
+use grafeo_common::utils::hash::FxHashMap;
 use grafeo_core::graph::lpg::LpgStore;
 use std::cmp::Ordering;
 use std::collections::HashMap;
@@ -186,14 +187,158 @@ impl<K: PartialOrd, T> Ord for MinScored<K, T> {
     }
 }
 
+// ============================================================================
+// Edge Weights
+// ============================================================================
+
+/// How [`EdgeWeight`] treats an edge without a numeric weight.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MissingWeight {
+    /// Use this weight instead.
+    Default(f64),
+    /// Fail with an error.
+    Error,
+}
+
+/// How [`EdgeWeight`] treats a negative weight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NegativeWeight {
+    /// Accept it, for algorithms that handle negative weights (Bellman-Ford).
+    Allow,
+    /// Fail with an error, for algorithms that assume non-negative weights
+    /// (Dijkstra, Prim).
+    Reject,
+}
+
+/// Reads numeric edge weights from a property.
+///
+/// `Int64` and `Float64` values are read as `f64`. An edge without the
+/// property, or with a non-numeric value, is missing a weight. Without a
+/// property the graph is unweighted: every edge weighs the missing default,
+/// or 1.0 under [`MissingWeight::Error`].
+///
+/// By default missing weights are 1.0 and negative weights are allowed.
+///
+/// # Example
+///
+/// ```ignore
+/// let weights = EdgeWeight::new(Some("cost")).negative(NegativeWeight::Reject);
+/// weights.check(&store)?; // fail before running
+/// let w = weights.value(&store, edge_id);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct EdgeWeight<'a> {
+    property: Option<&'a str>,
+    missing: MissingWeight,
+    negative: NegativeWeight,
+}
+
+impl<'a> EdgeWeight<'a> {
+    /// Creates a weight reader for `property` with the default policies.
+    #[must_use]
+    pub fn new(property: Option<&'a str>) -> Self {
+        Self {
+            property,
+            missing: MissingWeight::Default(1.0),
+            negative: NegativeWeight::Allow,
+        }
+    }
+
+    /// Sets how edges without a numeric weight are treated.
+    #[must_use]
+    pub fn missing(mut self, policy: MissingWeight) -> Self {
+        self.missing = policy;
+        self
+    }
+
+    /// Sets how negative weights are treated.
+    #[must_use]
+    pub fn negative(mut self, policy: NegativeWeight) -> Self {
+        self.negative = policy;
+        self
+    }
+
+    /// Returns the weight of an edge, applying both policies.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidValue`] if the weight is missing under
+    /// [`MissingWeight::Error`], or negative under [`NegativeWeight::Reject`].
+    pub fn get(&self, store: &LpgStore, edge_id: EdgeId) -> Result<f64> {
+        let Some(property) = self.property else {
+            return Ok(self.default());
+        };
+        let weight = match (self.read(store, edge_id, property), self.missing) {
+            (Some(weight), _) => weight,
+            (None, MissingWeight::Default(default)) => default,
+            (None, MissingWeight::Error) => {
+                return Err(Error::InvalidValue(format!(
+                    "edge {} has no numeric '{property}' weight",
+                    edge_id.0
+                )));
+            }
+        };
+        if weight < 0.0 && self.negative == NegativeWeight::Reject {
+            return Err(Error::InvalidValue(format!(
+                "edge {} has negative '{property}' weight {weight}",
+                edge_id.0
+            )));
+        }
+        Ok(weight)
+    }
+
+    /// Returns the weight of an edge without failing.
+    ///
+    /// Missing weights take the default (1.0 under [`MissingWeight::Error`])
+    /// and negative weights pass through. Algorithms read weights this way
+    /// after [`check`](Self::check) has enforced the policies.
+    #[must_use]
+    pub fn value(&self, store: &LpgStore, edge_id: EdgeId) -> f64 {
+        self.property
+            .and_then(|property| self.read(store, edge_id, property))
+            .unwrap_or_else(|| self.default())
+    }
+
+    /// Checks every edge in the store against both policies.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error [`get`](Self::get) reports for the first offending
+    /// edge.
+    pub fn check(&self, store: &LpgStore) -> Result<()> {
+        if self.property.is_none() {
+            return Ok(());
+        }
+        store
+            .all_edges()
+            .try_for_each(|edge| self.get(store, edge.id).map(|_| ()))
+    }
+
+    fn default(&self) -> f64 {
+        match self.missing {
+            MissingWeight::Default(default) => default,
+            MissingWeight::Error => 1.0,
+        }
+    }
+
+    fn read(&self, store: &LpgStore, edge_id: EdgeId, property: &str) -> Option<f64> {
+        match store.get_edge(edge_id)?.get_property(property)? {
+            Value::Int64(i) => Some(*i as f64),
+            Value::Float64(f) => Some(*f),
+            _ => None,
+        }
+    }
+}
+
 // ============================================================================
 // Graph Algorithm Traits
 // ============================================================================
 
 /// A graph algorithm that can be executed on an LPG store.
 ///
-/// This trait extends the base `Algorithm` trait with graph-specific
-/// functionality, providing direct access to the graph store.
+/// Implementations are registered with
+/// [`PluginRegistry::register_algorithm`](crate::plugins::PluginRegistry::register_algorithm),
+/// which makes them callable from queries by name.
 pub trait GraphAlgorithm: Send + Sync {
     /// Returns the name of the algorithm.
     fn name(&self) -> &str;
@@ -261,6 +406,16 @@ impl<K> DistanceMap<K> for HashMap<NodeId, K> {
     }
 }
 
+impl<K> DistanceMap<K> for FxHashMap<NodeId, K> {
+    fn get(&self, node: NodeId) -> Option<&K> {
+        FxHashMap::get(self, &node)
+    }
+
+    fn insert(&mut self, node: NodeId, dist: K) {
+        FxHashMap::insert(self, node, dist);
+    }
+}
+
 // ============================================================================
 // Result Builders
 // ============================================================================
@@ -356,3 +511,89 @@ impl Default for ComponentResultBuilder {
         Self::new()
     }
 }
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Edges weighing 2, -3, 1.5, "heavy" and nothing.
+    fn create_weighted_edges() -> (LpgStore, Vec<EdgeId>) {
+        let store = LpgStore::new();
+        let a = store.create_node(&["Node"]);
+        let b = store.create_node(&["Node"]);
+        let edges = vec![
+            store.create_edge_with_props(a, b, "EDGE", [("w", Value::Int64(2))]),
+            store.create_edge_with_props(a, b, "EDGE", [("w", Value::Int64(-3))]),
+            store.create_edge_with_props(a, b, "EDGE", [("w", Value::Float64(1.5))]),
+            store.create_edge_with_props(a, b, "EDGE", [("w", Value::String("heavy".into()))]),
+            store.create_edge(a, b, "EDGE"),
+        ];
+        (store, edges)
+    }
+
+    #[test]
+    fn test_edge_weight_defaults() {
+        let (store, edges) = create_weighted_edges();
+        let weights = EdgeWeight::new(Some("w"));
+
+        let read: Vec<f64> = edges
+            .iter()
+            .map(|&e| weights.get(&store, e).unwrap())
+            .collect();
+        assert_eq!(read, vec![2.0, -3.0, 1.5, 1.0, 1.0]);
+        assert!(weights.check(&store).is_ok());
+
+        // Without a property every edge weighs the default
+        let unweighted = EdgeWeight::new(None).missing(MissingWeight::Default(0.0));
+        assert_eq!(unweighted.get(&store, edges[0]).unwrap(), 0.0);
+        assert_eq!(unweighted.value(&store, edges[1]), 0.0);
+    }
+
+    #[test]
+    fn test_edge_weight_missing_policy() {
+        let (store, edges) = create_weighted_edges();
+        let weights = EdgeWeight::new(Some("w")).missing(MissingWeight::Error);
+
+        assert_eq!(weights.get(&store, edges[2]).unwrap(), 1.5);
+        for &edge in &edges[3..] {
+            let err = weights.get(&store, edge).unwrap_err();
+            assert!(err.to_string().contains("no numeric 'w' weight"), "{err}");
+            assert_eq!(weights.value(&store, edge), 1.0);
+        }
+        assert!(weights.check(&store).is_err());
+
+        // An unweighted graph has nothing to be missing
+        let unweighted = EdgeWeight::new(None).missing(MissingWeight::Error);
+        assert_eq!(unweighted.get(&store, edges[4]).unwrap(), 1.0);
+        assert!(unweighted.check(&store).is_ok());
+
+        let defaulted = EdgeWeight::new(Some("w")).missing(MissingWeight::Default(7.0));
+        assert_eq!(defaulted.get(&store, edges[3]).unwrap(), 7.0);
+        assert_eq!(defaulted.value(&store, edges[4]), 7.0);
+    }
+
+    #[test]
+    fn test_edge_weight_negative_policy() {
+        let (store, edges) = create_weighted_edges();
+        let weights = EdgeWeight::new(Some("w")).negative(NegativeWeight::Reject);
+
+        let err = weights.get(&store, edges[1]).unwrap_err();
+        assert!(err.to_string().contains("negative 'w' weight -3"), "{err}");
+        assert_eq!(weights.value(&store, edges[1]), -3.0);
+        assert_eq!(weights.get(&store, edges[0]).unwrap(), 2.0);
+        assert!(weights.check(&store).is_err());
+
+        // A negative missing default is rejected too
+        let negative_default = EdgeWeight::new(Some("w"))
+            .missing(MissingWeight::Default(-1.0))
+            .negative(NegativeWeight::Reject);
+        assert!(negative_default.get(&store, edges[4]).is_err());
+
+        let allowed = EdgeWeight::new(Some("w")).negative(NegativeWeight::Allow);
+        assert_eq!(allowed.get(&store, edges[1]).unwrap(), -3.0);
+    }
+}
@@ -5,15 +5,19 @@
 
 use std::collections::VecDeque;
 use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use grafeo_common::types::{NodeId, Value};
-use grafeo_common::utils::error::Result;
+use grafeo_common::utils::error::{Error, Result};
 use grafeo_common::utils::hash::{FxHashMap, FxHashSet};
 use grafeo_core::graph::Direction;
 use grafeo_core::graph::lpg::LpgStore;
+use rayon::prelude::*;
 
 use super::super::{AlgorithmResult, ParameterDef, ParameterType, Parameters};
-use super::traits::{Control, GraphAlgorithm, NodeValueResultBuilder, TraversalEvent};
+use super::traits::{
+    Control, GraphAlgorithm, NodeValueResultBuilder, ParallelGraphAlgorithm, TraversalEvent,
+};
 
 // ============================================================================
 // BFS Implementation
@@ -170,6 +174,92 @@ pub fn bfs_layers(store: &LpgStore, start: NodeId) -> Vec<Vec<NodeId>> {
     layers
 }
 
+/// BFS distances - the hop count from the start to every reachable node.
+///
+/// Unreachable nodes are absent from the map. Returns an empty map if the
+/// start node doesn't exist.
+pub fn bfs_distances(store: &LpgStore, start: NodeId) -> FxHashMap<NodeId, usize> {
+    let mut distances: FxHashMap<NodeId, usize> = FxHashMap::default();
+    for (distance, layer) in bfs_layers(store, start).into_iter().enumerate() {
+        for node in layer {
+            distances.insert(node, distance);
+        }
+    }
+    distances
+}
+
+// ============================================================================
+// Parallel BFS
+// ============================================================================
+
+/// A fixed-size set of node IDs that threads can claim concurrently.
+struct AtomicBitSet {
+    words: Vec<AtomicU64>,
+}
+
+impl AtomicBitSet {
+    /// Creates a set able to hold IDs `0..len`.
+    fn new(len: usize) -> Self {
+        Self {
+            words: (0..len.div_ceil(64)).map(|_| AtomicU64::new(0)).collect(),
+        }
+    }
+
+    /// Marks the node as visited, returning `true` if this call was first.
+    fn insert(&self, node: NodeId) -> bool {
+        let index = node.as_u64() as usize;
+        let Some(word) = self.words.get(index / 64) else {
+            return false;
+        };
+        let mask = 1u64 << (index % 64);
+        word.fetch_or(mask, Ordering::Relaxed) & mask == 0
+    }
+}
+
+/// Parallel breadth-first search, returning hop distances from the start.
+///
+/// Each frontier level is expanded in parallel with rayon. Threads claim
+/// neighbors through an atomic bitset, so every node joins exactly one
+/// frontier and gets the same distance as with [`bfs_distances`], whatever
+/// order the threads visit nodes in. Unreachable nodes are absent from the map.
+pub fn bfs_parallel(store: &LpgStore, start: NodeId) -> FxHashMap<NodeId, usize> {
+    let mut distances: FxHashMap<NodeId, usize> = FxHashMap::default();
+    if store.get_node(start).is_none() {
+        return distances;
+    }
+
+    let max_id = store
+        .node_ids()
+        .iter()
+        .map(|id| id.as_u64() as usize)
+        .max()
+        .unwrap_or(0);
+    let visited = AtomicBitSet::new(max_id + 1);
+    visited.insert(start);
+
+    let mut frontier = vec![start];
+    let mut distance = 0;
+    while !frontier.is_empty() {
+        for &node in &frontier {
+            distances.insert(node, distance);
+        }
+
+        frontier = frontier
+            .par_iter()
+            .flat_map_iter(|&node| {
+                store
+                    .edges_from(node, Direction::Outgoing)
+                    .filter(|(neighbor, _)| visited.insert(*neighbor))
+                    .map(|(neighbor, _)| neighbor)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        distance += 1;
+    }
+
+    distances
+}
+
 // ============================================================================
 // DFS Implementation
 // ============================================================================
@@ -187,7 +277,9 @@ enum NodeColor {
 
 /// Performs depth-first search from a starting node.
 ///
-/// Returns nodes in the order they were finished (post-order).
+/// Returns nodes in the order they were finished (post-order). Like
+/// [`dfs_with_visitor`], it doesn't recurse, so path depth is only limited
+/// by memory.
 ///
 /// # Arguments
 ///
@@ -210,7 +302,12 @@ pub fn dfs(store: &LpgStore, start: NodeId) -> Vec<NodeId> {
 
 /// Performs depth-first search with a visitor callback.
 ///
-/// Uses an explicit stack to avoid stack overflow on deep graphs.
+/// Uses an explicit stack to avoid stack overflow on deep graphs. Events
+/// arrive in the same order as from the textbook recursive DFS: a node's
+/// `Discover` comes after the `TreeEdge` leading to it, and its `Finish`
+/// after every event of its subtree. Returning [`Control::Prune`] from a
+/// `TreeEdge` skips that edge; from a `Discover`, it finishes the node
+/// without exploring its edges.
 ///
 /// # Arguments
 ///
@@ -415,6 +512,43 @@ impl GraphAlgorithm for BfsAlgorithm {
     }
 }
 
+impl ParallelGraphAlgorithm for BfsAlgorithm {
+    fn execute_parallel(
+        &self,
+        store: &LpgStore,
+        params: &Parameters,
+        num_threads: usize,
+    ) -> Result<AlgorithmResult> {
+        let start_id = params
+            .get_int("start")
+            .ok_or_else(|| Error::InvalidValue("start parameter required".to_string()))?;
+        let start = NodeId::new(start_id as u64);
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .map_err(|e| Error::Internal(format!("Failed to build thread pool: {e}")))?;
+        let distances = pool.install(|| bfs_parallel(store, start));
+
+        // Match the sequential output: by distance, then by node
+        let mut rows: Vec<(usize, NodeId)> = distances
+            .into_iter()
+            .map(|(node, distance)| (distance, node))
+            .collect();
+        rows.sort_unstable();
+
+        let mut result = AlgorithmResult::new(vec!["node_id".to_string(), "distance".to_string()]);
+        for (distance, node) in rows {
+            result.add_row(vec![
+                Value::Int64(node.0 as i64),
+                Value::Int64(distance as i64),
+            ]);
+        }
+
+        Ok(result)
+    }
+}
+
 /// Static parameter definitions for DFS algorithm.
 static DFS_PARAMS: OnceLock<Vec<ParameterDef>> = OnceLock::new();
 
@@ -465,6 +599,7 @@ impl GraphAlgorithm for DfsAlgorithm {
 
 #[cfg(test)]
 mod tests {
+    use super::super::traits::DistanceMap;
     use super::*;
 
     fn create_test_graph() -> LpgStore {
@@ -549,4 +684,235 @@ mod tests {
 
         assert_eq!(found, Some(true));
     }
+
+    /// Builds a pseudo-random graph with a disconnected tail of isolated pairs.
+    fn create_generated_graph(connected: usize, fanout: usize) -> LpgStore {
+        let store = LpgStore::new();
+        let nodes: Vec<NodeId> = (0..connected)
+            .map(|_| store.create_node(&["Node"]))
+            .collect();
+
+        // Linear congruential generator keeps the graph deterministic
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) as usize
+        };
+        for &source in &nodes {
+            for _ in 0..fanout {
+                let target = nodes[next() % connected];
+                store.create_edge(source, target, "EDGE");
+            }
+        }
+
+        // Components the start can never reach
+        for _ in 0..10 {
+            let a = store.create_node(&["Island"]);
+            let b = store.create_node(&["Island"]);
+            store.create_edge(a, b, "EDGE");
+        }
+
+        store
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let store = create_test_graph();
+        let distances = bfs_distances(&store, NodeId::new(0));
+
+        assert_eq!(distances.len(), 5);
+        assert_eq!(distances[&NodeId::new(0)], 0);
+        assert_eq!(distances[&NodeId::new(1)], 1);
+        assert_eq!(distances[&NodeId::new(3)], 1);
+        assert_eq!(distances[&NodeId::new(2)], 2);
+        assert_eq!(distances[&NodeId::new(4)], 2);
+    }
+
+    #[test]
+    fn test_bfs_parallel_matches_sequential() {
+        let store = create_generated_graph(5_000, 3);
+        let start = NodeId::new(0);
+
+        let sequential = bfs_distances(&store, start);
+        let parallel = bfs_parallel(&store, start);
+
+        assert!(sequential.len() > 1);
+        assert_eq!(parallel, sequential);
+
+        // Repeated runs give the same distances despite scheduling differences
+        assert_eq!(bfs_parallel(&store, start), sequential);
+    }
+
+    #[test]
+    fn test_bfs_parallel_skips_unreachable_nodes() {
+        let store = create_generated_graph(100, 2);
+        let distances = bfs_parallel(&store, NodeId::new(0));
+
+        for node in store.node_ids() {
+            let is_island = store
+                .get_node(node)
+                .is_some_and(|n| n.labels.iter().any(|l| l.as_ref() == "Island"));
+            if is_island {
+                assert!(!DistanceMap::contains(&distances, node));
+            }
+        }
+    }
+
+    #[test]
+    fn test_bfs_parallel_nonexistent_start() {
+        let store = LpgStore::new();
+        assert!(bfs_parallel(&store, NodeId::new(999)).is_empty());
+    }
+
+    #[test]
+    fn test_bfs_execute_parallel_matches_execute() {
+        let store = create_generated_graph(500, 2);
+        let mut params = Parameters::new();
+        params.set_int("start", 0);
+
+        let sequential = BfsAlgorithm.execute(&store, &params).unwrap();
+        let parallel = BfsAlgorithm.execute_parallel(&store, &params, 4).unwrap();
+
+        let sorted_rows = |result: &AlgorithmResult| {
+            let mut rows: Vec<(i64, i64)> = result
+                .rows
+                .iter()
+                .map(|row| match (&row[0], &row[1]) {
+                    (Value::Int64(node), Value::Int64(distance)) => (*node, *distance),
+                    other => panic!("Unexpected row {other:?}"),
+                })
+                .collect();
+            rows.sort_unstable();
+            rows
+        };
+        assert_eq!(sorted_rows(&parallel), sorted_rows(&sequential));
+    }
+
+    /// Recursive DFS emitting the same events as [`dfs_with_visitor`].
+    fn dfs_recursive<B>(
+        store: &LpgStore,
+        node: NodeId,
+        color: &mut FxHashMap<NodeId, NodeColor>,
+        visitor: &mut impl FnMut(TraversalEvent) -> Control<B>,
+    ) -> Option<B> {
+        color.insert(node, NodeColor::Gray);
+        match visitor(TraversalEvent::Discover(node)) {
+            Control::Break(b) => return Some(b),
+            Control::Prune => {}
+            Control::Continue => {
+                for (neighbor, edge) in store.edges_from(node, Direction::Outgoing) {
+                    let (source, target) = (node, neighbor);
+                    let control = match color.get(&neighbor).copied() {
+                        None => match visitor(TraversalEvent::TreeEdge {
+                            source,
+                            target,
+                            edge,
+                        }) {
+                            Control::Continue => {
+                                match dfs_recursive(store, neighbor, color, visitor) {
+                                    Some(b) => Control::Break(b),
+                                    None => Control::Continue,
+                                }
+                            }
+                            control => control,
+                        },
+                        Some(NodeColor::Gray) => visitor(TraversalEvent::BackEdge {
+                            source,
+                            target,
+                            edge,
+                        }),
+                        Some(_) => visitor(TraversalEvent::NonTreeEdge {
+                            source,
+                            target,
+                            edge,
+                        }),
+                    };
+                    if let Control::Break(b) = control {
+                        return Some(b);
+                    }
+                }
+            }
+        }
+        color.insert(node, NodeColor::Black);
+        match visitor(TraversalEvent::Finish(node)) {
+            Control::Break(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// Collects the events of both DFS versions, pruning and stopping alike.
+    fn both_dfs_events(
+        store: &LpgStore,
+        start: NodeId,
+        control: impl Fn(usize, TraversalEvent) -> Control<()>,
+    ) -> (Vec<TraversalEvent>, Vec<TraversalEvent>) {
+        let mut iterative = Vec::new();
+        dfs_with_visitor(store, start, |event| {
+            iterative.push(event);
+            control(iterative.len() - 1, event)
+        });
+        let mut recursive = Vec::new();
+        dfs_recursive(store, start, &mut FxHashMap::default(), &mut |event| {
+            recursive.push(event);
+            control(recursive.len() - 1, event)
+        });
+        (iterative, recursive)
+    }
+
+    #[test]
+    fn test_dfs_events_match_recursive() {
+        for store in [create_test_graph(), create_generated_graph(40, 3)] {
+            let (iterative, recursive) =
+                both_dfs_events(&store, NodeId::new(0), |_, _| Control::Continue);
+            assert_eq!(iterative, recursive);
+            let discovered = iterative
+                .iter()
+                .filter(|e| matches!(e, TraversalEvent::Discover(_)))
+                .count();
+            assert_eq!(
+                discovered,
+                iterative
+                    .iter()
+                    .filter(|e| matches!(e, TraversalEvent::Finish(_)))
+                    .count()
+            );
+
+            // Pruning: skip tree edges into multiples of 3, don't expand 5
+            let (iterative, recursive) =
+                both_dfs_events(&store, NodeId::new(0), |_, event| match event {
+                    TraversalEvent::TreeEdge { target, .. } if target.0 % 3 == 0 => Control::Prune,
+                    TraversalEvent::Discover(node) if node.0 == 5 => Control::Prune,
+                    _ => Control::Continue,
+                });
+            assert_eq!(iterative, recursive);
+
+            // Breaking stops both at the same event
+            let (iterative, recursive) = both_dfs_events(&store, NodeId::new(0), |index, _| {
+                if index == 20 {
+                    Control::Break(())
+                } else {
+                    Control::Continue
+                }
+            });
+            assert_eq!(iterative, recursive);
+        }
+    }
+
+    #[test]
+    fn test_dfs_deep_path() {
+        // Far deeper than a recursive DFS could go on a thread's stack
+        const DEPTH: usize = 500_000;
+        let store = LpgStore::new();
+        let nodes: Vec<NodeId> = (0..DEPTH).map(|_| store.create_node(&["Node"])).collect();
+        for pair in nodes.windows(2) {
+            store.create_edge(pair[0], pair[1], "EDGE");
+        }
+
+        let finished = dfs(&store, nodes[0]);
+        assert_eq!(finished.len(), DEPTH);
+        assert_eq!(finished[0], nodes[DEPTH - 1]);
+        assert_eq!(finished[DEPTH - 1], nodes[0]);
+    }
 }
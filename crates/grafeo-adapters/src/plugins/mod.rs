@@ -11,4 +11,4 @@ mod registry;
 mod traits;
 
 pub use registry::PluginRegistry;
-pub use traits::{Algorithm, AlgorithmResult, ParameterDef, ParameterType, Parameters, Plugin};
+pub use traits::{AlgorithmResult, ParameterDef, ParameterType, Parameters, Plugin};
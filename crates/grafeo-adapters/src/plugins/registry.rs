@@ -1,7 +1,17 @@
 //! Plugin registry.
 
-use super::{Algorithm, Plugin};
-use grafeo_common::utils::error::Result;
+use super::algorithms::{
+    AllPairsDijkstraAlgorithm, ArticulationPointsAlgorithm, BellmanFordAlgorithm,
+    BetweennessCentralityAlgorithm, BfsAlgorithm, BridgesAlgorithm, ClosenessCentralityAlgorithm,
+    ConnectedComponentsAlgorithm, DegreeCentralityAlgorithm, DfsAlgorithm, DijkstraAlgorithm,
+    FloydWarshallAlgorithm, GraphAlgorithm, KCoreAlgorithm, KruskalAlgorithm,
+    LabelPropagationAlgorithm, LouvainAlgorithm, MaxFlowAlgorithm, MinCostFlowAlgorithm,
+    PageRankAlgorithm, PrimAlgorithm, StronglyConnectedComponentsAlgorithm,
+    TopologicalSortAlgorithm,
+};
+use super::{AlgorithmResult, Parameters, Plugin};
+use grafeo_common::utils::error::{Error, Result};
+use grafeo_core::graph::lpg::LpgStore;
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -10,8 +20,8 @@ use std::sync::Arc;
 pub struct PluginRegistry {
     /// Loaded plugins.
     plugins: RwLock<HashMap<String, Arc<dyn Plugin>>>,
-    /// Registered algorithms.
-    algorithms: RwLock<HashMap<String, Arc<dyn Algorithm>>>,
+    /// Registered algorithms, by name.
+    algorithms: RwLock<HashMap<String, Arc<dyn GraphAlgorithm>>>,
 }
 
 impl PluginRegistry {
@@ -23,6 +33,41 @@ impl PluginRegistry {
         }
     }
 
+    /// Creates a registry with every built-in graph algorithm registered.
+    ///
+    /// These are the algorithms queries can `CALL` out of the box.
+    pub fn with_builtin_algorithms() -> Self {
+        let registry = Self::new();
+        let builtins: [Arc<dyn GraphAlgorithm>; 22] = [
+            Arc::new(BfsAlgorithm),
+            Arc::new(DfsAlgorithm),
+            Arc::new(ConnectedComponentsAlgorithm),
+            Arc::new(StronglyConnectedComponentsAlgorithm),
+            Arc::new(TopologicalSortAlgorithm),
+            Arc::new(DijkstraAlgorithm),
+            Arc::new(BellmanFordAlgorithm),
+            Arc::new(FloydWarshallAlgorithm),
+            Arc::new(AllPairsDijkstraAlgorithm),
+            Arc::new(PageRankAlgorithm),
+            Arc::new(BetweennessCentralityAlgorithm),
+            Arc::new(ClosenessCentralityAlgorithm),
+            Arc::new(DegreeCentralityAlgorithm),
+            Arc::new(LabelPropagationAlgorithm),
+            Arc::new(LouvainAlgorithm),
+            Arc::new(KruskalAlgorithm),
+            Arc::new(PrimAlgorithm),
+            Arc::new(MaxFlowAlgorithm),
+            Arc::new(MinCostFlowAlgorithm),
+            Arc::new(ArticulationPointsAlgorithm),
+            Arc::new(BridgesAlgorithm),
+            Arc::new(KCoreAlgorithm),
+        ];
+        for algorithm in builtins {
+            registry.register_algorithm(algorithm);
+        }
+        registry
+    }
+
     /// Registers a plugin.
     pub fn register_plugin(&self, plugin: Arc<dyn Plugin>) -> Result<()> {
         plugin.on_load()?;
@@ -45,18 +90,41 @@ impl PluginRegistry {
         self.plugins.read().get(name).cloned()
     }
 
-    /// Registers an algorithm.
-    pub fn register_algorithm(&self, algorithm: Arc<dyn Algorithm>) {
+    /// Registers an algorithm, replacing any registered under the same name.
+    pub fn register_algorithm(&self, algorithm: Arc<dyn GraphAlgorithm>) {
         self.algorithms
             .write()
             .insert(algorithm.name().to_string(), algorithm);
     }
 
     /// Gets an algorithm by name.
-    pub fn get_algorithm(&self, name: &str) -> Option<Arc<dyn Algorithm>> {
+    pub fn get_algorithm(&self, name: &str) -> Option<Arc<dyn GraphAlgorithm>> {
         self.algorithms.read().get(name).cloned()
     }
 
+    /// Runs the algorithm registered as `name` on `store`.
+    ///
+    /// `params` are [validated](Parameters::validate) against the
+    /// algorithm's parameter definitions first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidValue`] if no algorithm is registered as
+    /// `name` or the parameters don't validate, and otherwise whatever the
+    /// algorithm returns.
+    pub fn run(
+        &self,
+        name: &str,
+        store: &LpgStore,
+        params: &Parameters,
+    ) -> Result<AlgorithmResult> {
+        let algorithm = self
+            .get_algorithm(name)
+            .ok_or_else(|| Error::InvalidValue(format!("unknown algorithm '{name}'")))?;
+        let params = params.validate(algorithm.parameters())?;
+        algorithm.execute(store, &params)
+    }
+
     /// Lists all registered plugins.
     pub fn list_plugins(&self) -> Vec<String> {
         self.plugins.read().keys().cloned().collect()
@@ -77,6 +145,8 @@ impl Default for PluginRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::plugins::ParameterType;
+    use grafeo_common::types::{NodeId, Value};
 
     struct TestPlugin;
 
@@ -111,4 +181,108 @@ mod tests {
         registry.unregister_plugin("test").unwrap();
         assert!(registry.get_plugin("test").is_none());
     }
+
+    #[test]
+    fn test_builtin_algorithms() {
+        let registry = PluginRegistry::with_builtin_algorithms();
+
+        let names = registry.list_algorithms();
+        assert_eq!(names.len(), 22);
+        for name in ["pagerank", "connected_components", "dijkstra", "louvain"] {
+            assert!(names.iter().any(|n| n == name), "missing {name}");
+        }
+        assert!(registry.get_algorithm("no_such_algorithm").is_none());
+    }
+
+    /// `0 -> 1 -> 2` with unit weights.
+    fn create_path() -> LpgStore {
+        let store = LpgStore::new();
+        let n0 = store.create_node(&["Node"]);
+        let n1 = store.create_node(&["Node"]);
+        let n2 = store.create_node(&["Node"]);
+        store.create_edge_with_props(n0, n1, "EDGE", [("weight", Value::Float64(1.0))]);
+        store.create_edge_with_props(n1, n2, "EDGE", [("weight", Value::Float64(1.0))]);
+        store
+    }
+
+    #[test]
+    fn test_run_by_name() {
+        let registry = PluginRegistry::new();
+        registry.register_algorithm(Arc::new(PageRankAlgorithm));
+        registry.register_algorithm(Arc::new(DijkstraAlgorithm));
+        let store = create_path();
+
+        let ranks = registry
+            .run("pagerank", &store, &Parameters::new())
+            .unwrap();
+        assert_eq!(ranks.columns, vec!["node_id", "score"]);
+        assert_eq!(ranks.row_count(), 3);
+
+        let mut params = Parameters::new();
+        params.set_int("source", NodeId::new(0).0 as i64);
+        params.set_int("target", NodeId::new(2).0 as i64);
+        params.set_string("weight", "weight");
+        let path = registry.run("dijkstra", &store, &params).unwrap();
+        assert_eq!(path.rows[0][2], Value::Float64(2.0));
+
+        // Integer damping widens to a float
+        let mut params = Parameters::new();
+        params.set_int("damping", 1);
+        assert!(registry.run("pagerank", &store, &params).is_ok());
+    }
+
+    #[test]
+    fn test_run_rejects_bad_calls() {
+        let registry = PluginRegistry::new();
+        registry.register_algorithm(Arc::new(PageRankAlgorithm));
+        registry.register_algorithm(Arc::new(DijkstraAlgorithm));
+        let store = create_path();
+
+        let error = registry
+            .run("louvain", &store, &Parameters::new())
+            .unwrap_err();
+        assert!(error.to_string().contains("unknown algorithm 'louvain'"));
+
+        let error = registry
+            .run("dijkstra", &store, &Parameters::new())
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("missing required parameter 'source'")
+        );
+
+        let mut params = Parameters::new();
+        params.set_string("damping", "high");
+        let error = registry.run("pagerank", &store, &params).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("parameter 'damping' expects Float")
+        );
+
+        let mut params = Parameters::new();
+        params.set_float("dampening", 0.5);
+        let error = registry.run("pagerank", &store, &params).unwrap_err();
+        assert!(error.to_string().contains("unknown parameter 'dampening'"));
+    }
+
+    #[test]
+    fn test_arguments_validated_against_parameters() {
+        let registry = PluginRegistry::with_builtin_algorithms();
+        let pagerank = registry.get_algorithm("pagerank").unwrap();
+        let defs = pagerank.parameters();
+        assert_eq!(defs[0].param_type, ParameterType::Float);
+
+        // Integers widen to floats, nulls fall back to defaults
+        let params =
+            Parameters::from_arguments(defs, &[Value::Int64(1), Value::Null, Value::Float64(0.5)])
+                .unwrap();
+        assert_eq!(params.get_float("damping"), Some(1.0));
+        assert_eq!(params.get_int("max_iterations"), Some(100));
+        assert_eq!(params.get_float("tolerance"), Some(0.5));
+
+        assert!(Parameters::from_arguments(defs, &[Value::String("high".into())]).is_err());
+        assert!(Parameters::from_arguments(defs, &vec![Value::Int64(1); 4]).is_err());
+    }
 }
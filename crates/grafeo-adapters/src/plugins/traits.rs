@@ -1,6 +1,7 @@
 //! Plugin traits.
 
-use grafeo_common::utils::error::Result;
+use grafeo_common::types::Value;
+use grafeo_common::utils::error::{Error, Result};
 use std::collections::HashMap;
 
 /// A Grafeo plugin.
@@ -22,21 +23,6 @@ pub trait Plugin: Send + Sync {
     }
 }
 
-/// A graph algorithm that can be invoked from queries.
-pub trait Algorithm: Send + Sync {
-    /// Returns the name of the algorithm.
-    fn name(&self) -> &str;
-
-    /// Returns a description of the algorithm.
-    fn description(&self) -> &str;
-
-    /// Returns the parameter definitions.
-    fn parameters(&self) -> &[ParameterDef];
-
-    /// Executes the algorithm.
-    fn execute(&self, params: &Parameters) -> Result<AlgorithmResult>;
-}
-
 /// Definition of an algorithm parameter.
 #[derive(Debug, Clone)]
 pub struct ParameterDef {
@@ -68,6 +54,7 @@ pub enum ParameterType {
 }
 
 /// Parameters passed to an algorithm.
+#[derive(Debug, Clone)]
 pub struct Parameters {
     /// Parameter values.
     values: HashMap<String, ParameterValue>,
@@ -81,6 +68,101 @@ impl Parameters {
         }
     }
 
+    /// Binds positional arguments to parameter definitions.
+    ///
+    /// Arguments are matched to `defs` in order, and null arguments count as
+    /// not given. The result is then [validated](Self::validate), so it has
+    /// the defaults filled in.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidValue`] if there are more arguments than
+    /// parameters, or if validation fails.
+    pub fn from_arguments(defs: &[ParameterDef], arguments: &[Value]) -> Result<Self> {
+        if arguments.len() > defs.len() {
+            return Err(Error::InvalidValue(format!(
+                "expected at most {} arguments, got {}",
+                defs.len(),
+                arguments.len()
+            )));
+        }
+
+        let mut params = Self::new();
+        for (def, argument) in defs.iter().zip(arguments) {
+            let value = match argument {
+                Value::Null => continue,
+                Value::Int64(v) => ParameterValue::Integer(*v),
+                Value::Float64(v) => ParameterValue::Float(*v),
+                Value::String(v) => ParameterValue::String(v.to_string()),
+                Value::Bool(v) => ParameterValue::Boolean(*v),
+                other => {
+                    return Err(Error::InvalidValue(format!(
+                        "parameter '{}' expects {:?}, got {other:?}",
+                        def.name, def.param_type
+                    )));
+                }
+            };
+            params.values.insert(def.name.clone(), value);
+        }
+        params.validate(defs)
+    }
+
+    /// Checks these parameters against an algorithm's definitions.
+    ///
+    /// Returns a copy with the defaults of missing optional parameters
+    /// filled in. Integers are accepted for float parameters, and node IDs
+    /// are integers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidValue`] for a parameter not in `defs`, a
+    /// value of the wrong type, or a missing required parameter.
+    pub fn validate(&self, defs: &[ParameterDef]) -> Result<Self> {
+        if let Some(name) = self
+            .values
+            .keys()
+            .find(|name| !defs.iter().any(|def| &def.name == *name))
+        {
+            return Err(Error::InvalidValue(format!("unknown parameter '{name}'")));
+        }
+
+        let mut params = Self::new();
+        for def in defs {
+            let value = match (self.values.get(&def.name), &def.default) {
+                (Some(value), _) => value.clone(),
+                (None, _) if def.required => {
+                    return Err(Error::InvalidValue(format!(
+                        "missing required parameter '{}'",
+                        def.name
+                    )));
+                }
+                (None, Some(default)) => ParameterValue::parse(def.param_type, default)
+                    .ok_or_else(|| {
+                        Error::InvalidValue(format!(
+                            "invalid default '{default}' for parameter '{}'",
+                            def.name
+                        ))
+                    })?,
+                (None, None) => continue,
+            };
+
+            let value = match (def.param_type, value) {
+                (ParameterType::Float, ParameterValue::Integer(v)) => {
+                    ParameterValue::Float(v as f64)
+                }
+                (expected, value) if value.matches(expected) => value,
+                (expected, value) => {
+                    return Err(Error::InvalidValue(format!(
+                        "parameter '{}' expects {expected:?}, got {value:?}",
+                        def.name
+                    )));
+                }
+            };
+            params.values.insert(def.name.clone(), value);
+        }
+        Ok(params)
+    }
+
     /// Sets an integer parameter.
     pub fn set_int(&mut self, name: impl Into<String>, value: i64) {
         self.values
@@ -153,7 +235,33 @@ enum ParameterValue {
     Boolean(bool),
 }
 
+impl ParameterValue {
+    /// Parses a parameter's textual default.
+    fn parse(param_type: ParameterType, text: &str) -> Option<Self> {
+        match param_type {
+            ParameterType::Integer | ParameterType::NodeId => text.parse().ok().map(Self::Integer),
+            ParameterType::Float => text.parse().ok().map(Self::Float),
+            ParameterType::String => Some(Self::String(text.to_string())),
+            ParameterType::Boolean => text.parse().ok().map(Self::Boolean),
+        }
+    }
+
+    /// Returns `true` if this value can be passed as `param_type`.
+    fn matches(&self, param_type: ParameterType) -> bool {
+        matches!(
+            (param_type, self),
+            (
+                ParameterType::Integer | ParameterType::NodeId,
+                Self::Integer(_)
+            ) | (ParameterType::Float, Self::Float(_))
+                | (ParameterType::String, Self::String(_))
+                | (ParameterType::Boolean, Self::Boolean(_))
+        )
+    }
+}
+
 /// Result of an algorithm execution.
+#[derive(Debug)]
 pub struct AlgorithmResult {
     /// Result columns.
     pub columns: Vec<String>,
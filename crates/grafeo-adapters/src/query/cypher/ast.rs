@@ -45,6 +45,8 @@ pub enum Clause {
     Return(ReturnClause),
     /// UNWIND clause.
     Unwind(UnwindClause),
+    /// CALL clause.
+    Call(CallClause),
     /// ORDER BY clause.
     OrderBy(OrderByClause),
     /// SKIP clause.
@@ -215,6 +217,19 @@ pub struct ProjectionItem {
     pub span: Option<SourceSpan>,
 }
 
+/// A CALL clause invoking a procedure, e.g. `CALL pagerank(0.85) YIELD node, score`.
+#[derive(Debug, Clone)]
+pub struct CallClause {
+    /// Procedure name.
+    pub procedure: String,
+    /// Positional arguments.
+    pub arguments: Vec<Expression>,
+    /// Yielded result columns.
+    pub yield_items: Vec<String>,
+    /// Source span.
+    pub span: Option<SourceSpan>,
+}
+
 /// An UNWIND clause.
 #[derive(Debug, Clone)]
 pub struct UnwindClause {
@@ -76,4 +76,11 @@ mod tests {
         let result = parse("MERGE (n:Person {name: 'Alice'}) RETURN n");
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_parse_call_yield() {
+        let result = parse("CALL pagerank(0.85) YIELD node, score RETURN node");
+        assert!(result.is_ok());
+        assert!(parse("CALL pagerank() RETURN 1").is_err());
+    }
 }
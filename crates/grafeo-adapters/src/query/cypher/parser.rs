@@ -66,6 +66,9 @@ impl<'a> Parser<'a> {
                 TokenKind::Unwind => {
                     clauses.push(Clause::Unwind(self.parse_unwind_clause()?));
                 }
+                TokenKind::Call => {
+                    clauses.push(Clause::Call(self.parse_call_clause()?));
+                }
                 TokenKind::Create => {
                     clauses.push(Clause::Create(self.parse_create_clause()?));
                 }
@@ -191,6 +194,36 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_call_clause(&mut self) -> Result<CallClause> {
+        self.expect(TokenKind::Call)?;
+        let procedure = self.expect_identifier()?;
+
+        self.expect(TokenKind::LParen)?;
+        let mut arguments = Vec::new();
+        if self.current.kind != TokenKind::RParen {
+            arguments.push(self.parse_expression()?);
+            while self.current.kind == TokenKind::Comma {
+                self.advance();
+                arguments.push(self.parse_expression()?);
+            }
+        }
+        self.expect(TokenKind::RParen)?;
+
+        self.expect(TokenKind::Yield)?;
+        let mut yield_items = vec![self.expect_identifier()?];
+        while self.current.kind == TokenKind::Comma {
+            self.advance();
+            yield_items.push(self.expect_identifier()?);
+        }
+
+        Ok(CallClause {
+            procedure,
+            arguments,
+            yield_items,
+            span: None,
+        })
+    }
+
     fn parse_create_clause(&mut self) -> Result<CreateClause> {
         self.expect(TokenKind::Create)?;
         let patterns = self.parse_pattern_list()?;
@@ -16,6 +16,8 @@ pub enum Statement {
 /// A query statement.
 #[derive(Debug, Clone)]
 pub struct QueryStatement {
+    /// Leading CALL clause, whose yielded rows start the query.
+    pub call_clause: Option<CallClause>,
     /// MATCH clauses (regular and optional).
     pub match_clauses: Vec<MatchClause>,
     /// Optional WHERE clause.
@@ -126,6 +128,19 @@ pub struct WithClause {
     pub span: Option<SourceSpan>,
 }
 
+/// A CALL clause invoking a procedure, e.g. `CALL pagerank(0.85) YIELD node, score`.
+#[derive(Debug, Clone)]
+pub struct CallClause {
+    /// Procedure name.
+    pub procedure: String,
+    /// Positional arguments.
+    pub arguments: Vec<Expression>,
+    /// Yielded result columns.
+    pub yield_items: Vec<String>,
+    /// Source span.
+    pub span: Option<SourceSpan>,
+}
+
 /// An UNWIND clause for expanding lists into rows.
 #[derive(Debug, Clone)]
 pub struct UnwindClause {
@@ -337,6 +352,10 @@ pub enum SchemaStatement {
     CreateNodeType(CreateNodeTypeStatement),
     /// CREATE EDGE TYPE.
     CreateEdgeType(CreateEdgeTypeStatement),
+    /// CREATE INDEX.
+    CreateIndex(CreateIndexStatement),
+    /// CREATE CONSTRAINT.
+    CreateConstraint(CreateConstraintStatement),
 }
 
 /// A CREATE NODE TYPE statement.
@@ -361,6 +380,41 @@ pub struct CreateEdgeTypeStatement {
     pub span: Option<SourceSpan>,
 }
 
+/// A CREATE INDEX statement, e.g. `CREATE INDEX ON :Person(lastName, firstName)`.
+#[derive(Debug, Clone)]
+pub struct CreateIndexStatement {
+    /// Label the index applies to.
+    pub label: String,
+    /// Indexed property keys, in key order.
+    pub properties: Vec<String>,
+    /// Source span.
+    pub span: Option<SourceSpan>,
+}
+
+/// A CREATE CONSTRAINT statement, e.g.
+/// `CREATE CONSTRAINT ON :User(email) IS UNIQUE` or
+/// `CREATE CONSTRAINT ON :Person(name) IS NOT NULL`.
+#[derive(Debug, Clone)]
+pub struct CreateConstraintStatement {
+    /// Label the constraint applies to.
+    pub label: String,
+    /// Constrained property key.
+    pub property: String,
+    /// What the constraint requires.
+    pub kind: ConstraintKind,
+    /// Source span.
+    pub span: Option<SourceSpan>,
+}
+
+/// What a constraint requires of a property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintKind {
+    /// No two nodes with the label share a value (`IS UNIQUE`).
+    Unique,
+    /// Every node with the label has a value (`IS NOT NULL`).
+    NotNull,
+}
+
 /// A property definition in a schema.
 #[derive(Debug, Clone)]
 pub struct PropertyDefinition {
@@ -429,6 +483,13 @@ pub enum Expression {
         /// The inner query pattern to check for existence.
         query: Box<QueryStatement>,
     },
+    /// IN subquery expression - checks if a value is among the inner query's results.
+    InSubquery {
+        /// The value to look for.
+        expr: Box<Expression>,
+        /// The inner query, which must return a single column.
+        query: Box<QueryStatement>,
+    },
 }
 
 /// A literal value.
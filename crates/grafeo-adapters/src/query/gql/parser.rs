@@ -127,9 +127,11 @@ impl<'a> Parser<'a> {
     /// Parses the input into a statement.
     pub fn parse(&mut self) -> Result<Statement> {
         match self.current.kind {
-            TokenKind::Match | TokenKind::Optional | TokenKind::Unwind | TokenKind::Merge => {
-                self.parse_query().map(Statement::Query)
-            }
+            TokenKind::Match
+            | TokenKind::Optional
+            | TokenKind::Unwind
+            | TokenKind::Merge
+            | TokenKind::Call => self.parse_query().map(Statement::Query),
             TokenKind::Insert => self
                 .parse_insert()
                 .map(|s| Statement::DataModification(DataModificationStatement::Insert(s))),
@@ -144,11 +146,11 @@ impl<'a> Parser<'a> {
                     self.parse_create_as_insert()
                         .map(|s| Statement::DataModification(DataModificationStatement::Insert(s)))
                 } else {
-                    // GQL schema: CREATE NODE TYPE / CREATE EDGE TYPE
+                    // GQL schema: CREATE NODE TYPE / EDGE TYPE / INDEX / CONSTRAINT
                     self.parse_create_schema().map(Statement::Schema)
                 }
             }
-            _ => Err(self.error("Expected MATCH, INSERT, DELETE, MERGE, UNWIND, or CREATE")),
+            _ => Err(self.error("Expected MATCH, INSERT, DELETE, MERGE, UNWIND, CALL, or CREATE")),
         }
     }
 
@@ -162,6 +164,13 @@ impl<'a> Parser<'a> {
         let mut create_clauses = Vec::new();
         let mut delete_clauses = Vec::new();
 
+        // A CALL clause can only start the query
+        let call_clause = if self.current.kind == TokenKind::Call {
+            Some(self.parse_call_clause()?)
+        } else {
+            None
+        };
+
         // Parse initial clauses (MATCH, OPTIONAL MATCH, UNWIND, MERGE)
         loop {
             match self.current.kind {
@@ -197,8 +206,8 @@ impl<'a> Parser<'a> {
             remove_clauses.push(self.parse_remove_clause()?);
         }
 
-        // Parse CREATE clauses (Cypher-style: MATCH ... CREATE ...)
-        while self.current.kind == TokenKind::Create {
+        // Parse CREATE/INSERT clauses (MATCH ... CREATE ... or MATCH ... INSERT ...)
+        while matches!(self.current.kind, TokenKind::Create | TokenKind::Insert) {
             create_clauses.push(self.parse_create_clause_in_query()?);
         }
 
@@ -259,6 +268,7 @@ impl<'a> Parser<'a> {
         };
 
         Ok(QueryStatement {
+            call_clause,
             match_clauses,
             where_clause,
             set_clauses,
@@ -396,6 +406,49 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_call_clause(&mut self) -> Result<CallClause> {
+        let span_start = self.current.span.start;
+        self.expect(TokenKind::Call)?;
+
+        if !self.is_identifier() {
+            return Err(self.error("Expected procedure name after CALL"));
+        }
+        let procedure = self.get_identifier_name();
+        self.advance();
+
+        self.expect(TokenKind::LParen)?;
+        let mut arguments = Vec::new();
+        if self.current.kind != TokenKind::RParen {
+            arguments.push(self.parse_expression()?);
+            while self.current.kind == TokenKind::Comma {
+                self.advance();
+                arguments.push(self.parse_expression()?);
+            }
+        }
+        self.expect(TokenKind::RParen)?;
+
+        self.expect(TokenKind::Yield)?;
+        let mut yield_items = Vec::new();
+        loop {
+            if !self.is_identifier() {
+                return Err(self.error("Expected column name in YIELD"));
+            }
+            yield_items.push(self.get_identifier_name());
+            self.advance();
+            if self.current.kind != TokenKind::Comma {
+                break;
+            }
+            self.advance();
+        }
+
+        Ok(CallClause {
+            procedure,
+            arguments,
+            yield_items,
+            span: Some(SourceSpan::new(span_start, self.current.span.end, 1, 1)),
+        })
+    }
+
     fn parse_unwind_clause(&mut self) -> Result<UnwindClause> {
         let span_start = self.current.span.start;
         self.expect(TokenKind::Unwind)?;
@@ -1109,6 +1162,27 @@ impl<'a> Parser<'a> {
                     right: Box::new(right),
                 });
             }
+            TokenKind::In => {
+                self.advance(); // consume IN
+                // IN (MATCH ... RETURN x) is a subquery, anything else a list
+                if self.current.kind == TokenKind::LParen
+                    && matches!(self.peek_kind(), TokenKind::Match | TokenKind::Optional)
+                {
+                    self.advance(); // consume (
+                    let query = self.parse_query()?;
+                    self.expect(TokenKind::RParen)?;
+                    return Ok(Expression::InSubquery {
+                        expr: Box::new(left),
+                        query: Box::new(query),
+                    });
+                }
+                let right = self.parse_additive_expression()?;
+                return Ok(Expression::Binary {
+                    left: Box::new(left),
+                    op: BinaryOp::In,
+                    right: Box::new(right),
+                });
+            }
             _ => {}
         }
 
@@ -1417,6 +1491,7 @@ impl<'a> Parser<'a> {
 
         // EXISTS doesn't need RETURN - create empty return clause
         Ok(QueryStatement {
+            call_clause: None,
             match_clauses,
             where_clause,
             set_clauses: vec![],
@@ -1503,9 +1578,9 @@ impl<'a> Parser<'a> {
         })
     }
 
-    /// Parses CREATE clause within a query (e.g., MATCH ... CREATE ...).
+    /// Parses a CREATE or INSERT clause within a query (e.g., MATCH ... CREATE ...).
     fn parse_create_clause_in_query(&mut self) -> Result<InsertStatement> {
-        self.expect(TokenKind::Create)?;
+        self.advance();
 
         let mut patterns = Vec::new();
         patterns.push(self.parse_pattern()?);
@@ -1637,8 +1712,94 @@ impl<'a> Parser<'a> {
                     span: None,
                 }))
             }
-            _ => Err(self.error("Expected NODE or EDGE")),
+            TokenKind::Identifier if self.current.text.eq_ignore_ascii_case("INDEX") => {
+                self.advance();
+                self.parse_create_index().map(SchemaStatement::CreateIndex)
+            }
+            TokenKind::Identifier if self.current.text.eq_ignore_ascii_case("CONSTRAINT") => {
+                self.advance();
+                self.parse_create_constraint()
+                    .map(SchemaStatement::CreateConstraint)
+            }
+            _ => Err(self.error("Expected NODE, EDGE, INDEX, or CONSTRAINT")),
+        }
+    }
+
+    /// Parses the remainder of `CREATE CONSTRAINT ON :Label(prop) IS UNIQUE`
+    /// or `... IS NOT NULL`.
+    fn parse_create_constraint(&mut self) -> Result<CreateConstraintStatement> {
+        self.expect(TokenKind::On)?;
+        self.expect(TokenKind::Colon)?;
+
+        if !self.is_identifier() {
+            return Err(self.error("Expected label name"));
+        }
+        let label = self.get_identifier_name();
+        self.advance();
+
+        self.expect(TokenKind::LParen)?;
+        if !self.is_identifier() {
+            return Err(self.error("Expected property name"));
+        }
+        let property = self.get_identifier_name();
+        self.advance();
+        self.expect(TokenKind::RParen)?;
+
+        self.expect(TokenKind::Is)?;
+        let kind = if self.current.kind == TokenKind::Identifier
+            && self.current.text.eq_ignore_ascii_case("UNIQUE")
+        {
+            self.advance();
+            ConstraintKind::Unique
+        } else if self.current.kind == TokenKind::Not {
+            self.advance();
+            self.expect(TokenKind::Null)?;
+            ConstraintKind::NotNull
+        } else {
+            return Err(self.error("Expected UNIQUE or NOT NULL"));
+        };
+
+        Ok(CreateConstraintStatement {
+            label,
+            property,
+            kind,
+            span: None,
+        })
+    }
+
+    /// Parses the remainder of `CREATE INDEX ON :Label(prop1, prop2, ...)`.
+    fn parse_create_index(&mut self) -> Result<CreateIndexStatement> {
+        self.expect(TokenKind::On)?;
+        self.expect(TokenKind::Colon)?;
+
+        if !self.is_identifier() {
+            return Err(self.error("Expected label name"));
+        }
+        let label = self.get_identifier_name();
+        self.advance();
+
+        self.expect(TokenKind::LParen)?;
+        let mut properties = Vec::new();
+        loop {
+            if !self.is_identifier() {
+                return Err(self.error("Expected property name"));
+            }
+            properties.push(self.get_identifier_name());
+            self.advance();
+
+            if self.current.kind == TokenKind::Comma {
+                self.advance();
+            } else {
+                break;
+            }
         }
+        self.expect(TokenKind::RParen)?;
+
+        Ok(CreateIndexStatement {
+            label,
+            properties,
+            span: None,
+        })
     }
 
     fn parse_property_definitions(&mut self) -> Result<Vec<PropertyDefinition>> {
@@ -2033,6 +2194,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_call_yield() {
+        let mut parser = Parser::new("CALL pagerank(0.9, 20) YIELD node, score RETURN node");
+        let result = parser.parse();
+        assert!(result.is_ok(), "Parse error: {:?}", result.err());
+
+        if let Statement::Query(query) = result.unwrap() {
+            let call = query.call_clause.expect("Expected CALL clause");
+            assert_eq!(call.procedure, "pagerank");
+            assert_eq!(call.arguments.len(), 2);
+            assert_eq!(call.yield_items, vec!["node", "score"]);
+        } else {
+            panic!("Expected Query statement");
+        }
+
+        assert!(Parser::new("CALL pagerank() RETURN 1").parse().is_err());
+    }
+
     #[test]
     fn test_parse_merge() {
         let mut parser = Parser::new("MERGE (n:Person {name: 'Alice'}) RETURN n");
@@ -2120,4 +2299,100 @@ mod tests {
             panic!("Expected Query statement");
         }
     }
+
+    #[test]
+    fn test_parse_in_subquery() {
+        let mut parser = Parser::new(
+            "MATCH (n:Person) WHERE n.city IN (MATCH (c:City) WHERE c.size > 10 RETURN c.name) RETURN n",
+        );
+        let result = parser.parse();
+        assert!(result.is_ok(), "Parse error: {:?}", result.err());
+
+        if let Statement::Query(query) = result.unwrap() {
+            let where_clause = query.where_clause.expect("Expected WHERE clause");
+            if let Expression::InSubquery { expr, query } = where_clause.expression {
+                assert!(matches!(*expr, Expression::PropertyAccess { .. }));
+                assert_eq!(query.match_clauses.len(), 1);
+                assert!(query.where_clause.is_some());
+                assert_eq!(query.return_clause.items.len(), 1);
+            } else {
+                panic!("Expected IN subquery");
+            }
+        } else {
+            panic!("Expected Query statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_in_list() {
+        let mut parser = Parser::new("MATCH (n:Person) WHERE n.age IN [30, 40] RETURN n");
+        let result = parser.parse();
+        assert!(result.is_ok(), "Parse error: {:?}", result.err());
+
+        if let Statement::Query(query) = result.unwrap() {
+            let where_clause = query.where_clause.expect("Expected WHERE clause");
+            assert!(matches!(
+                where_clause.expression,
+                Expression::Binary {
+                    op: BinaryOp::In,
+                    ..
+                }
+            ));
+        } else {
+            panic!("Expected Query statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_create_composite_index() {
+        let mut parser = Parser::new("CREATE INDEX ON :Person(lastName, firstName)");
+        let result = parser.parse();
+        assert!(result.is_ok(), "Parse error: {:?}", result.err());
+
+        if let Statement::Schema(SchemaStatement::CreateIndex(index)) = result.unwrap() {
+            assert_eq!(index.label, "Person");
+            assert_eq!(index.properties, vec!["lastName", "firstName"]);
+        } else {
+            panic!("Expected CREATE INDEX statement");
+        }
+    }
+
+    #[test]
+    fn test_parse_create_index_requires_properties() {
+        let mut parser = Parser::new("CREATE INDEX ON :Person()");
+        assert!(parser.parse().is_err());
+    }
+
+    #[test]
+    fn test_parse_create_unique_constraint() {
+        let mut parser = Parser::new("CREATE CONSTRAINT ON :User(email) IS UNIQUE");
+        let result = parser.parse();
+        assert!(result.is_ok(), "Parse error: {:?}", result.err());
+
+        if let Statement::Schema(SchemaStatement::CreateConstraint(constraint)) = result.unwrap() {
+            assert_eq!(constraint.label, "User");
+            assert_eq!(constraint.property, "email");
+            assert_eq!(constraint.kind, ConstraintKind::Unique);
+        } else {
+            panic!("Expected CREATE CONSTRAINT statement");
+        }
+
+        let mut parser = Parser::new("CREATE CONSTRAINT ON :User(email) IS SPECIAL");
+        assert!(parser.parse().is_err());
+    }
+
+    #[test]
+    fn test_parse_create_not_null_constraint() {
+        let mut parser = Parser::new("CREATE CONSTRAINT ON :Person(name) IS NOT NULL");
+        let result = parser.parse();
+        assert!(result.is_ok(), "Parse error: {:?}", result.err());
+
+        if let Statement::Schema(SchemaStatement::CreateConstraint(constraint)) = result.unwrap() {
+            assert_eq!(constraint.label, "Person");
+            assert_eq!(constraint.property, "name");
+            assert_eq!(constraint.kind, ConstraintKind::NotNull);
+        } else {
+            panic!("Expected CREATE CONSTRAINT statement");
+        }
+    }
 }
@@ -70,6 +70,8 @@ pub enum Step {
     Or(Vec<Vec<Step>>),
     /// .not(traversal)
     Not(Vec<Step>),
+    /// .is(predicate or value)
+    Is(Predicate),
     /// .dedup(keys...)
     Dedup(Vec<String>),
     /// .limit(n)
@@ -131,6 +133,14 @@ pub enum Step {
     /// .choose(predicate, true_branch, false_branch)
     Choose(ChooseClause),
 
+    // === Looping Steps ===
+    /// .repeat(traversal)
+    Repeat(Vec<Step>),
+    /// .times(n) - modifies a preceding repeat()
+    Times(usize),
+    /// .until(traversal) - modifies a preceding repeat()
+    Until(Vec<Step>),
+
     // === Side Effect Steps ===
     /// .as(label)
     As(String),
@@ -213,8 +223,8 @@ pub enum Predicate {
 /// Where clause variants.
 #[derive(Debug, Clone)]
 pub enum WhereClause {
-    /// .where(P.eq(label))
-    Predicate(String, Predicate),
+    /// .where(P.eq(label)) or .where(start, P.eq(label))
+    Predicate(Option<String>, Predicate),
     /// .where(traversal)
     Traversal(Vec<Step>),
 }
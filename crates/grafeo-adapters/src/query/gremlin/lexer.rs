@@ -47,6 +47,7 @@ pub enum TokenKind {
     And,
     Or,
     Not,
+    Is,
     Dedup,
     Limit,
     Skip,
@@ -79,6 +80,11 @@ pub enum TokenKind {
     Union,
     Choose,
 
+    // Looping
+    Repeat,
+    Times,
+    Until,
+
     // Side effect steps
     As,
     SideEffect,
@@ -337,6 +343,7 @@ impl<'a> Lexer<'a> {
             "and" => TokenKind::And,
             "or" => TokenKind::Or,
             "not" => TokenKind::Not,
+            "is" => TokenKind::Is,
             "dedup" => TokenKind::Dedup,
             "limit" => TokenKind::Limit,
             "skip" => TokenKind::Skip,
@@ -366,6 +373,9 @@ impl<'a> Lexer<'a> {
             "optional" => TokenKind::Optional,
             "union" => TokenKind::Union,
             "choose" => TokenKind::Choose,
+            "repeat" => TokenKind::Repeat,
+            "times" => TokenKind::Times,
+            "until" => TokenKind::Until,
             "as" | "as_" => TokenKind::As,
             "sideEffect" => TokenKind::SideEffect,
             "aggregate" => TokenKind::Aggregate,
@@ -207,6 +207,36 @@ impl<'a> Parser<'a> {
                 self.expect(TokenKind::RParen)?;
                 Ok(Step::Range(start, end))
             }
+            TokenKind::Where => {
+                self.expect(TokenKind::LParen)?;
+                let clause = self.parse_where_clause()?;
+                self.expect(TokenKind::RParen)?;
+                Ok(Step::Where(clause))
+            }
+            TokenKind::And => {
+                self.expect(TokenKind::LParen)?;
+                let traversals = self.parse_traversal_list()?;
+                self.expect(TokenKind::RParen)?;
+                Ok(Step::And(traversals))
+            }
+            TokenKind::Or => {
+                self.expect(TokenKind::LParen)?;
+                let traversals = self.parse_traversal_list()?;
+                self.expect(TokenKind::RParen)?;
+                Ok(Step::Or(traversals))
+            }
+            TokenKind::Not => {
+                self.expect(TokenKind::LParen)?;
+                let traversal = self.parse_anonymous_traversal()?;
+                self.expect(TokenKind::RParen)?;
+                Ok(Step::Not(traversal))
+            }
+            TokenKind::Is => {
+                self.expect(TokenKind::LParen)?;
+                let pred = self.parse_predicate_or_value()?;
+                self.expect(TokenKind::RParen)?;
+                Ok(Step::Is(pred))
+            }
 
             // Map steps
             TokenKind::Values => {
@@ -328,6 +358,26 @@ impl<'a> Parser<'a> {
                 Ok(Step::Order(Vec::new()))
             }
 
+            // Looping steps
+            TokenKind::Repeat => {
+                self.expect(TokenKind::LParen)?;
+                let body = self.parse_anonymous_traversal()?;
+                self.expect(TokenKind::RParen)?;
+                Ok(Step::Repeat(body))
+            }
+            TokenKind::Times => {
+                self.expect(TokenKind::LParen)?;
+                let n = self.parse_integer()? as usize;
+                self.expect(TokenKind::RParen)?;
+                Ok(Step::Times(n))
+            }
+            TokenKind::Until => {
+                self.expect(TokenKind::LParen)?;
+                let condition = self.parse_anonymous_traversal()?;
+                self.expect(TokenKind::RParen)?;
+                Ok(Step::Until(condition))
+            }
+
             // Side effect steps
             TokenKind::As => {
                 self.expect(TokenKind::LParen)?;
@@ -801,6 +851,74 @@ impl<'a> Parser<'a> {
         Ok(steps)
     }
 
+    /// Parses the arguments of `where()`.
+    ///
+    /// Accepts a predicate (`where(P.gt('a'))`), a start label plus predicate
+    /// (`where('a', gt('b'))`), or an anonymous traversal.
+    fn parse_where_clause(&mut self) -> Result<WhereClause> {
+        if let Some(pred) = self.try_parse_predicate()? {
+            return Ok(WhereClause::Predicate(None, pred));
+        }
+
+        if matches!(self.current_kind(), Some(TokenKind::String(_))) {
+            let start = self.parse_string()?;
+            self.expect(TokenKind::Comma)?;
+            let pred = self
+                .try_parse_predicate()?
+                .ok_or_else(|| self.error("Expected predicate in where()"))?;
+            return Ok(WhereClause::Predicate(Some(start), pred));
+        }
+
+        Ok(WhereClause::Traversal(self.parse_anonymous_traversal()?))
+    }
+
+    /// Parses a predicate in either `P.gt(x)` or bare `gt(x)` form.
+    fn try_parse_predicate(&mut self) -> Result<Option<Predicate>> {
+        if self.check(TokenKind::P) {
+            return self.parse_predicate().map(Some);
+        }
+        self.try_parse_direct_predicate()
+    }
+
+    /// Parses a predicate, treating a plain value as `P.eq(value)`.
+    fn parse_predicate_or_value(&mut self) -> Result<Predicate> {
+        match self.try_parse_predicate()? {
+            Some(pred) => Ok(pred),
+            None => Ok(Predicate::Eq(self.parse_value()?)),
+        }
+    }
+
+    /// Parses a comma-separated list of anonymous traversals.
+    fn parse_traversal_list(&mut self) -> Result<Vec<Vec<Step>>> {
+        let mut traversals = Vec::new();
+        while !self.check(TokenKind::RParen) {
+            traversals.push(self.parse_anonymous_traversal()?);
+            if !self.check(TokenKind::Comma) {
+                break;
+            }
+            self.advance(); // consume ','
+        }
+        Ok(traversals)
+    }
+
+    /// Parses an anonymous traversal such as `__.out('knows')` or `values('age').is(lt(40))`.
+    fn parse_anonymous_traversal(&mut self) -> Result<Vec<Step>> {
+        // The `__` prefix lexes as two underscores followed by a dot
+        if self.check(TokenKind::Underscore) {
+            while self.check(TokenKind::Underscore) {
+                self.advance();
+            }
+            self.expect(TokenKind::Dot)?;
+        }
+
+        let mut steps = vec![self.parse_step()?];
+        while self.check(TokenKind::Dot) {
+            self.advance(); // consume '.'
+            steps.push(self.parse_step()?);
+        }
+        Ok(steps)
+    }
+
     fn parse_string_list(&mut self) -> Result<Vec<String>> {
         let mut result = Vec::new();
         while self.check_string() {
@@ -1021,4 +1139,58 @@ mod tests {
             panic!("Expected Has step with within predicate");
         }
     }
+
+    #[test]
+    fn test_parse_where_with_anonymous_traversal() {
+        let mut parser = Parser::new("g.V().where(values('age').is(lt(40)))");
+        let stmt = parser.parse().unwrap();
+        if let Step::Where(WhereClause::Traversal(steps)) = &stmt.steps[0] {
+            assert_eq!(steps.len(), 2);
+            assert!(matches!(&steps[0], Step::Values(keys) if keys == &["age"]));
+            assert!(matches!(
+                &steps[1],
+                Step::Is(Predicate::Lt(Value::Int64(40)))
+            ));
+        } else {
+            panic!(
+                "Expected Where step with traversal, got: {:?}",
+                stmt.steps[0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_where_with_label_predicate() {
+        let mut parser = Parser::new("g.V().as('a').out().where(P.neq('a'))");
+        let stmt = parser.parse().unwrap();
+        assert!(matches!(
+            &stmt.steps[2],
+            Step::Where(WhereClause::Predicate(None, Predicate::Neq(_)))
+        ));
+    }
+
+    #[test]
+    fn test_parse_and_or_traversals() {
+        let mut parser =
+            Parser::new("g.V().and(has('age'), __.out('knows')).or(has('a'), has('b'))");
+        let stmt = parser.parse().unwrap();
+        assert!(matches!(&stmt.steps[0], Step::And(ts) if ts.len() == 2));
+        assert!(matches!(&stmt.steps[1], Step::Or(ts) if ts.len() == 2));
+    }
+
+    #[test]
+    fn test_parse_repeat_times() {
+        let mut parser = Parser::new("g.V().repeat(out('knows')).times(3)");
+        let stmt = parser.parse().unwrap();
+        assert!(matches!(&stmt.steps[0], Step::Repeat(body) if body.len() == 1));
+        assert!(matches!(&stmt.steps[1], Step::Times(3)));
+    }
+
+    #[test]
+    fn test_parse_repeat_until() {
+        let mut parser = Parser::new("g.V().repeat(__.out()).until(has('name', 'Z'))");
+        let stmt = parser.parse().unwrap();
+        assert!(matches!(&stmt.steps[0], Step::Repeat(_)));
+        assert!(matches!(&stmt.steps[1], Step::Until(cond) if cond.len() == 1));
+    }
 }
@@ -0,0 +1,135 @@
+//! Memory-mapped storage backend.
+//!
+//! Maps a file read-only so the OS pages it in on demand. How well that
+//! works depends on readahead: a full scan wants the kernel to read far
+//! ahead, a burst of index lookups wants it not to bother. The executor
+//! says which one is coming through [`AccessAdvisor::advise_access`],
+//! which becomes `madvise` on Unix and does nothing elsewhere.
+
+use grafeo_common::utils::error::Result;
+use memmap2::Mmap;
+use std::fs::File;
+use std::path::Path;
+
+/// How a plan is about to read storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessPattern {
+    /// No strong pattern - use the OS default.
+    Normal,
+    /// Long sequential passes, as in full scans.
+    Sequential,
+    /// Scattered point reads, as in index lookups.
+    Random,
+}
+
+/// Something that can pass access-pattern hints on to the OS.
+pub trait AccessAdvisor: Send + Sync {
+    /// Hints how storage is about to be read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OS rejects the hint.
+    fn advise_access(&self, pattern: AccessPattern) -> Result<()>;
+}
+
+/// Read-only memory-mapped file.
+pub struct MmapBackend {
+    /// The mapping; `None` for an empty file, which can't be mapped.
+    map: Option<Mmap>,
+}
+
+impl MmapBackend {
+    /// Maps the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be opened or mapped.
+    #[allow(unsafe_code)]
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path)?;
+        let map = if file.metadata()?.len() == 0 {
+            None
+        } else {
+            // SAFETY: the mapping is read-only; callers must not truncate
+            // the file while it is mapped
+            Some(unsafe { Mmap::map(&file)? })
+        };
+        Ok(Self { map })
+    }
+
+    /// Returns the mapped bytes.
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        self.map.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the length of the mapping in bytes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bytes().len()
+    }
+
+    /// Returns true if the mapped file is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_none()
+    }
+}
+
+impl AccessAdvisor for MmapBackend {
+    #[cfg(unix)]
+    fn advise_access(&self, pattern: AccessPattern) -> Result<()> {
+        use memmap2::Advice;
+
+        let Some(map) = &self.map else {
+            return Ok(());
+        };
+        let advice = match pattern {
+            AccessPattern::Normal => Advice::Normal,
+            AccessPattern::Sequential => Advice::Sequential,
+            AccessPattern::Random => Advice::Random,
+        };
+        map.advise(advice)?;
+        Ok(())
+    }
+
+    /// Platforms without `madvise` get no hint.
+    #[cfg(not(unix))]
+    fn advise_access(&self, _pattern: AccessPattern) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_mmap_backend_advise() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&[7u8; 8192]).unwrap();
+        file.flush().unwrap();
+
+        let backend = MmapBackend::open(file.path()).unwrap();
+        assert_eq!(backend.len(), 8192);
+        assert_eq!(backend.bytes()[100], 7);
+
+        for pattern in [
+            AccessPattern::Sequential,
+            AccessPattern::Random,
+            AccessPattern::Normal,
+        ] {
+            backend.advise_access(pattern).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_mmap_backend_empty_file() {
+        let file = NamedTempFile::new().unwrap();
+        let backend = MmapBackend::open(file.path()).unwrap();
+        assert!(backend.is_empty());
+        backend.advise_access(AccessPattern::Sequential).unwrap();
+    }
+}
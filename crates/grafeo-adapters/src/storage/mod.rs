@@ -3,13 +3,16 @@
 //! | Backend | Speed | Durability | Use when |
 //! | ------- | ----- | ---------- | -------- |
 //! | [`memory`] | Fastest | None (data lost on restart) | Testing, prototyping |
+//! | [`mmap`] | Fast, OS-paged | Read-only | Data larger than RAM, analytical scans |
 //! | [`wal`] | Fast | Survives crashes | Production workloads |
 //!
 //! The WAL (Write-Ahead Log) writes changes to disk before applying them,
 //! so you can recover after crashes without losing committed transactions.
 
 pub mod memory;
+pub mod mmap;
 pub mod wal;
 
 pub use memory::MemoryBackend;
+pub use mmap::{AccessAdvisor, AccessPattern, MmapBackend};
 pub use wal::WalManager;
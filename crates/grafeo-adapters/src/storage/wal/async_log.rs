@@ -8,7 +8,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 use tokio::fs::{self, File, OpenOptions};
 use tokio::io::{AsyncWriteExt, BufWriter};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Notify};
 use tokio::task::JoinHandle;
 
 /// State for a single async log file.
@@ -29,6 +29,11 @@ struct AsyncLogFile {
 ///
 /// This manager provides the same durability guarantees as the sync version
 /// but uses tokio's async I/O for better throughput in async contexts.
+///
+/// In [`Sync`](DurabilityMode::Sync) mode, commits group up: a committer
+/// that finds no fsync in progress becomes the leader and syncs every record
+/// written so far, while the others wait for it. Each committer still returns
+/// only once its own record is on disk.
 pub struct AsyncWalManager {
     /// Directory for WAL files.
     dir: PathBuf,
@@ -38,6 +43,14 @@ pub struct AsyncWalManager {
     active_log: Mutex<Option<AsyncLogFile>>,
     /// Total number of records written across all log files.
     total_record_count: AtomicU64,
+    /// Number of records known to be on disk (a prefix of all written).
+    synced_record_count: AtomicU64,
+    /// Held by the group commit leader while it syncs.
+    sync_leader: Mutex<()>,
+    /// Wakes committers waiting on the leader's sync.
+    sync_notify: Notify,
+    /// Number of fsyncs issued.
+    sync_count: AtomicU64,
     /// Records since last sync (for batch mode).
     records_since_sync: AtomicU64,
     /// Time of last sync (for batch mode).
@@ -92,6 +105,10 @@ impl AsyncWalManager {
             config,
             active_log: Mutex::new(None),
             total_record_count: AtomicU64::new(0),
+            synced_record_count: AtomicU64::new(0),
+            sync_leader: Mutex::new(()),
+            sync_notify: Notify::new(),
+            sync_count: AtomicU64::new(0),
             records_since_sync: AtomicU64::new(0),
             last_sync: Mutex::new(Instant::now()),
             current_sequence: AtomicU64::new(max_sequence),
@@ -108,6 +125,8 @@ impl AsyncWalManager {
 
     /// Logs a record to the WAL asynchronously.
     ///
+    /// In `Sync` mode, a commit record resolves only after it is fsynced.
+    ///
     /// # Errors
     ///
     /// Returns an error if the record cannot be written.
@@ -120,8 +139,7 @@ impl AsyncWalManager {
             .ok_or_else(|| Error::Internal("WAL writer not available".to_string()))?;
 
         // Serialize the record
-        let data = bincode::serde::encode_to_vec(record, bincode::config::standard())
-            .map_err(|e| Error::Serialization(e.to_string()))?;
+        let data = record.encode()?;
 
         // Write length prefix
         let len = data.len() as u32;
@@ -138,22 +156,20 @@ impl AsyncWalManager {
         let record_size = 4 + data.len() as u64 + 4; // length + data + checksum
         log_file.size += record_size;
 
-        self.total_record_count.fetch_add(1, Ordering::Relaxed);
+        // Only advanced under the log lock, so it orders records in the file
+        let record_number = self.total_record_count.fetch_add(1, Ordering::SeqCst) + 1;
         self.records_since_sync.fetch_add(1, Ordering::Relaxed);
 
         // Check if we need to rotate
         let needs_rotation = log_file.size >= self.config.max_log_size;
 
+        // Commits in sync mode wait for a (possibly shared) fsync below
+        let mut wait_durable = false;
+
         // Handle durability mode
         match &self.config.durability {
             DurabilityMode::Sync => {
-                // Sync on every commit record
-                if matches!(record, WalRecord::TxCommit { .. }) {
-                    log_file.writer.flush().await?;
-                    log_file.writer.get_ref().sync_all().await?;
-                    self.records_since_sync.store(0, Ordering::Relaxed);
-                    *self.last_sync.lock().await = Instant::now();
-                }
+                wait_durable = record.is_commit();
             }
             DurabilityMode::Batch {
                 max_delay_ms,
@@ -163,10 +179,7 @@ impl AsyncWalManager {
                 let elapsed = self.last_sync.lock().await.elapsed();
 
                 if records >= *max_records || elapsed >= Duration::from_millis(*max_delay_ms) {
-                    log_file.writer.flush().await?;
-                    log_file.writer.get_ref().sync_all().await?;
-                    self.records_since_sync.store(0, Ordering::Relaxed);
-                    *self.last_sync.lock().await = Instant::now();
+                    self.sync_log_file(log_file).await?;
                 }
             }
             DurabilityMode::NoSync => {
@@ -182,6 +195,10 @@ impl AsyncWalManager {
             self.rotate().await?;
         }
 
+        if wait_durable {
+            self.wait_durable(record_number).await?;
+        }
+
         Ok(())
     }
 
@@ -232,9 +249,13 @@ impl AsyncWalManager {
 
         // Replace active log
         let mut guard = self.active_log.lock().await;
-        if let Some(old_log) = guard.take() {
-            // Ensure old log is flushed
-            drop(old_log);
+        if let Some(mut old_log) = guard.take() {
+            // Records in the old log must not be lost to the switch
+            if matches!(self.config.durability, DurabilityMode::NoSync) {
+                old_log.writer.flush().await?;
+            } else {
+                self.sync_log_file(&mut old_log).await?;
+            }
         }
         *guard = Some(new_log);
 
@@ -262,11 +283,8 @@ impl AsyncWalManager {
     pub async fn sync(&self) -> Result<()> {
         let mut guard = self.active_log.lock().await;
         if let Some(log_file) = guard.as_mut() {
-            log_file.writer.flush().await?;
-            log_file.writer.get_ref().sync_all().await?;
+            self.sync_log_file(log_file).await?;
         }
-        self.records_since_sync.store(0, Ordering::Relaxed);
-        *self.last_sync.lock().await = Instant::now();
         Ok(())
     }
 
@@ -335,6 +353,15 @@ impl AsyncWalManager {
         self.total_record_count.load(Ordering::Relaxed)
     }
 
+    /// Returns the number of fsyncs issued so far.
+    ///
+    /// Concurrent commits in `Sync` mode share fsyncs, so under load this
+    /// grows much slower than the number of commits.
+    #[must_use]
+    pub fn sync_count(&self) -> u64 {
+        self.sync_count.load(Ordering::Relaxed)
+    }
+
     /// Returns the WAL directory path.
     #[must_use]
     pub fn dir(&self) -> &Path {
@@ -376,6 +403,58 @@ impl AsyncWalManager {
 
     // === Private methods ===
 
+    /// Flushes and fsyncs the given log file.
+    ///
+    /// Must be called with the active log lock held, so that every record
+    /// counted in `total_record_count` is in this file or an already synced
+    /// one.
+    async fn sync_log_file(&self, log_file: &mut AsyncLogFile) -> Result<()> {
+        log_file.writer.flush().await?;
+        log_file.writer.get_ref().sync_all().await?;
+        self.sync_count.fetch_add(1, Ordering::Relaxed);
+        self.synced_record_count.fetch_max(
+            self.total_record_count.load(Ordering::SeqCst),
+            Ordering::SeqCst,
+        );
+        self.records_since_sync.store(0, Ordering::Relaxed);
+        *self.last_sync.lock().await = Instant::now();
+        Ok(())
+    }
+
+    /// Waits until the first `record_number` records are on disk.
+    ///
+    /// If no other committer is syncing, this one leads: it lets the other
+    /// committers append their records, then fsyncs them all at once.
+    async fn wait_durable(&self, record_number: u64) -> Result<()> {
+        loop {
+            // Register before checking, so a sync finishing in between
+            // still wakes us
+            let notified = self.sync_notify.notified();
+            if self.synced_record_count.load(Ordering::SeqCst) >= record_number {
+                return Ok(());
+            }
+
+            let Ok(_leader) = self.sync_leader.try_lock() else {
+                notified.await;
+                continue;
+            };
+
+            // Give concurrent committers a chance to join this batch
+            tokio::task::yield_now().await;
+
+            let result = {
+                let mut guard = self.active_log.lock().await;
+                match guard.as_mut() {
+                    Some(log_file) => self.sync_log_file(log_file).await,
+                    None => Err(Error::Internal("WAL writer not available".to_string())),
+                }
+            };
+            // Waiters retry (and may lead the next sync) even if this one failed
+            self.sync_notify.notify_waiters();
+            result?;
+        }
+    }
+
     async fn ensure_active_log(&self) -> Result<()> {
         let mut guard = self.active_log.lock().await;
         if guard.is_none() {
@@ -511,11 +590,7 @@ mod tests {
         let wal = AsyncWalManager::with_config(dir.path().join("sync"), config)
             .await
             .unwrap();
-        wal.log(&WalRecord::TxCommit {
-            tx_id: TxId::new(1),
-        })
-        .await
-        .unwrap();
+        wal.log(&WalRecord::commit(TxId::new(1))).await.unwrap();
 
         // Test NoSync mode
         let config = WalConfig {
@@ -567,11 +642,7 @@ mod tests {
         .await
         .unwrap();
 
-        wal.log(&WalRecord::TxCommit {
-            tx_id: TxId::new(1),
-        })
-        .await
-        .unwrap();
+        wal.log(&WalRecord::commit(TxId::new(1))).await.unwrap();
 
         // Create checkpoint
         wal.checkpoint(TxId::new(1), EpochId::new(10))
@@ -617,4 +688,61 @@ mod tests {
         // Stop background sync
         wal.stop_background_sync().await;
     }
+
+    #[tokio::test]
+    async fn test_group_commit_shares_fsyncs() {
+        const COMMITS: u64 = 64;
+
+        let dir = tempdir().unwrap();
+        let config = WalConfig {
+            durability: DurabilityMode::Sync,
+            ..Default::default()
+        };
+        let wal = std::sync::Arc::new(
+            AsyncWalManager::with_config(dir.path(), config)
+                .await
+                .unwrap(),
+        );
+
+        let handles: Vec<_> = (0..COMMITS)
+            .map(|i| {
+                let wal = std::sync::Arc::clone(&wal);
+                tokio::spawn(async move {
+                    wal.log(&WalRecord::CreateNode {
+                        id: NodeId::new(i),
+                        labels: vec![],
+                    })
+                    .await
+                    .unwrap();
+                    wal.log(&WalRecord::commit(TxId::new(i))).await.unwrap();
+                    // The commit only returned once its record was synced
+                    assert!(wal.synced_record_count.load(Ordering::SeqCst) >= 2);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(
+            wal.synced_record_count.load(Ordering::SeqCst),
+            wal.record_count()
+        );
+        assert!(
+            wal.sync_count() <= COMMITS / 4,
+            "{} fsyncs for {COMMITS} commits",
+            wal.sync_count()
+        );
+
+        let records = crate::storage::wal::WalRecovery::new(dir.path())
+            .recover()
+            .unwrap();
+        let commits = records.iter().filter(|r| r.is_commit()).count();
+        let nodes = records
+            .iter()
+            .filter(|r| matches!(r, WalRecord::CreateNode { .. }))
+            .count();
+        assert_eq!(commits as u64, COMMITS);
+        assert_eq!(nodes as u64, COMMITS);
+    }
 }
@@ -1,6 +1,6 @@
 //! WAL log file management.
 
-use super::WalRecord;
+use super::{PageId, WalRecord};
 use grafeo_common::types::{EpochId, TxId};
 use grafeo_common::utils::error::{Error, Result};
 use parking_lot::Mutex;
@@ -25,11 +25,71 @@ pub struct CheckpointMetadata {
     pub timestamp_ms: u64,
     /// Transaction ID at checkpoint.
     pub tx_id: TxId,
+    /// Pages this checkpoint rewrote, i.e. those dirtied since the last one.
+    pub dirty_pages: Vec<PageId>,
 }
 
 /// Name of the checkpoint metadata file.
 const CHECKPOINT_METADATA_FILE: &str = "checkpoint.meta";
 
+/// Starts a checkpoint metadata file, followed by its format version.
+///
+/// Files without it predate the header and hold the version 0 layout.
+const CHECKPOINT_METADATA_MAGIC: &[u8; 4] = b"GCKP";
+
+/// Format version of the checkpoint metadata written now.
+const CHECKPOINT_METADATA_VERSION: u8 = 1;
+
+/// Checkpoint metadata as written before it listed dirty pages.
+#[derive(Deserialize)]
+struct CheckpointMetadataV0 {
+    epoch: EpochId,
+    log_sequence: u64,
+    timestamp_ms: u64,
+    tx_id: TxId,
+}
+
+impl CheckpointMetadata {
+    /// Encodes the metadata with the current format header.
+    fn encode(&self) -> Result<Vec<u8>> {
+        let mut data = CHECKPOINT_METADATA_MAGIC.to_vec();
+        data.push(CHECKPOINT_METADATA_VERSION);
+        bincode::serde::encode_into_std_write(self, &mut data, bincode::config::standard())
+            .map_err(|e| Error::Serialization(e.to_string()))?;
+        Ok(data)
+    }
+
+    /// Decodes metadata written by any version so far.
+    pub(crate) fn decode(data: &[u8]) -> Result<Self> {
+        let config = bincode::config::standard();
+        let Some(rest) = data.strip_prefix(CHECKPOINT_METADATA_MAGIC) else {
+            let (v0, _): (CheckpointMetadataV0, _) =
+                bincode::serde::decode_from_slice(data, config)
+                    .map_err(|e| Error::Serialization(e.to_string()))?;
+            return Ok(Self {
+                epoch: v0.epoch,
+                log_sequence: v0.log_sequence,
+                timestamp_ms: v0.timestamp_ms,
+                tx_id: v0.tx_id,
+                dirty_pages: Vec::new(),
+            });
+        };
+        match rest.split_first() {
+            Some((&CHECKPOINT_METADATA_VERSION, payload)) => {
+                bincode::serde::decode_from_slice(payload, config)
+                    .map(|(metadata, _)| metadata)
+                    .map_err(|e| Error::Serialization(e.to_string()))
+            }
+            Some((version, _)) => Err(Error::Serialization(format!(
+                "unsupported checkpoint metadata version {version}"
+            ))),
+            None => Err(Error::Serialization(
+                "checkpoint metadata has no version".to_string(),
+            )),
+        }
+    }
+}
+
 /// Durability mode for the WAL.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DurabilityMode {
@@ -179,8 +239,7 @@ impl WalManager {
             .ok_or_else(|| Error::Internal("WAL writer not available".to_string()))?;
 
         // Serialize the record
-        let data = bincode::serde::encode_to_vec(record, bincode::config::standard())
-            .map_err(|e| Error::Serialization(e.to_string()))?;
+        let data = record.encode()?;
 
         // Write length prefix
         let len = data.len() as u32;
@@ -207,7 +266,7 @@ impl WalManager {
         match &self.config.durability {
             DurabilityMode::Sync => {
                 // Sync on every commit record
-                if matches!(record, WalRecord::TxCommit { .. }) {
+                if record.is_commit() {
                     log_file.writer.flush()?;
                     log_file.writer.get_ref().sync_all()?;
                     self.records_since_sync.store(0, Ordering::Relaxed);
@@ -253,6 +312,23 @@ impl WalManager {
     ///
     /// Returns an error if the checkpoint cannot be written.
     pub fn checkpoint(&self, current_tx: TxId, epoch: EpochId) -> Result<()> {
+        self.checkpoint_with_pages(current_tx, epoch, Vec::new())
+    }
+
+    /// Writes a checkpoint that persisted `dirty_pages`.
+    ///
+    /// Call this after the pages themselves are on disk: the checkpoint
+    /// record tells recovery that everything before it is covered by pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint fails.
+    pub fn checkpoint_with_pages(
+        &self,
+        current_tx: TxId,
+        epoch: EpochId,
+        dirty_pages: Vec<PageId>,
+    ) -> Result<()> {
         // Write checkpoint record to WAL
         self.log(&WalRecord::Checkpoint { tx_id: current_tx })?;
 
@@ -274,6 +350,7 @@ impl WalManager {
             log_sequence,
             timestamp_ms,
             tx_id: current_tx,
+            dirty_pages,
         };
 
         // Write checkpoint metadata atomically
@@ -295,9 +372,7 @@ impl WalManager {
         let metadata_path = self.dir.join(CHECKPOINT_METADATA_FILE);
         let temp_path = self.dir.join(format!("{}.tmp", CHECKPOINT_METADATA_FILE));
 
-        // Serialize metadata
-        let data = bincode::serde::encode_to_vec(metadata, bincode::config::standard())
-            .map_err(|e| Error::Serialization(e.to_string()))?;
+        let data = metadata.encode()?;
 
         // Write to temp file
         let mut file = File::create(&temp_path)?;
@@ -326,11 +401,7 @@ impl WalManager {
         let mut data = Vec::new();
         reader.read_to_end(&mut data)?;
 
-        let (metadata, _): (CheckpointMetadata, _) =
-            bincode::serde::decode_from_slice(&data, bincode::config::standard())
-                .map_err(|e| Error::Serialization(e.to_string()))?;
-
-        Ok(Some(metadata))
+        CheckpointMetadata::decode(&data).map(Some)
     }
 
     /// Rotates to a new log file.
@@ -625,10 +696,7 @@ mod tests {
             ..Default::default()
         };
         let wal = WalManager::with_config(dir.path().join("sync"), config).unwrap();
-        wal.log(&WalRecord::TxCommit {
-            tx_id: TxId::new(1),
-        })
-        .unwrap();
+        wal.log(&WalRecord::commit(TxId::new(1))).unwrap();
 
         // Test NoSync mode
         let config = WalConfig {
@@ -673,14 +741,60 @@ mod tests {
         })
         .unwrap();
 
-        wal.log(&WalRecord::TxCommit {
-            tx_id: TxId::new(1),
-        })
-        .unwrap();
+        wal.log(&WalRecord::commit(TxId::new(1))).unwrap();
 
         // Create checkpoint
         wal.checkpoint(TxId::new(1), EpochId::new(10)).unwrap();
 
         assert_eq!(wal.checkpoint_epoch(), Some(EpochId::new(10)));
     }
+
+    #[test]
+    fn test_checkpoint_metadata_versions() {
+        #[derive(Serialize)]
+        struct V0 {
+            epoch: EpochId,
+            log_sequence: u64,
+            timestamp_ms: u64,
+            tx_id: TxId,
+        }
+
+        let dir = tempdir().unwrap();
+        let wal = WalManager::open(dir.path()).unwrap();
+        let metadata_path = dir.path().join(CHECKPOINT_METADATA_FILE);
+
+        // Metadata written before the header is still read
+        let v0 = V0 {
+            epoch: EpochId::new(3),
+            log_sequence: 2,
+            timestamp_ms: 1_700_000_000_000,
+            tx_id: TxId::new(9),
+        };
+        let data = bincode::serde::encode_to_vec(&v0, bincode::config::standard()).unwrap();
+        fs::write(&metadata_path, data).unwrap();
+        let metadata = wal.read_checkpoint_metadata().unwrap().unwrap();
+        assert_eq!(metadata.epoch, EpochId::new(3));
+        assert_eq!(metadata.log_sequence, 2);
+        assert_eq!(metadata.tx_id, TxId::new(9));
+        assert!(metadata.dirty_pages.is_empty());
+
+        // Current metadata round trips
+        wal.checkpoint_with_pages(TxId::new(10), EpochId::new(4), vec![PageId::Nodes(1)])
+            .unwrap();
+        let data = fs::read(&metadata_path).unwrap();
+        assert!(data.starts_with(CHECKPOINT_METADATA_MAGIC));
+        let metadata = wal.read_checkpoint_metadata().unwrap().unwrap();
+        assert_eq!(metadata.epoch, EpochId::new(4));
+        assert_eq!(metadata.dirty_pages, vec![PageId::Nodes(1)]);
+
+        // A version from the future is refused rather than misread
+        let mut future = data;
+        future[CHECKPOINT_METADATA_MAGIC.len()] = CHECKPOINT_METADATA_VERSION + 1;
+        fs::write(&metadata_path, future).unwrap();
+        let err = wal.read_checkpoint_metadata().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unsupported checkpoint metadata version")
+        );
+    }
 }
@@ -6,18 +6,24 @@
 //!
 //! | Durability mode | What it does | When to use |
 //! | --------------- | ------------ | ----------- |
-//! | [`Sync`](DurabilityMode::Sync) | fsync after every commit | Can't lose any data |
+//! | [`Sync`](DurabilityMode::Sync) | fsync per commit (concurrent async commits share one) | Can't lose any data |
 //! | [`Batch`](DurabilityMode::Batch) | Periodic fsync | Balance of safety and speed |
 //! | [`NoSync`](DurabilityMode::NoSync) | Let OS decide | Testing, when speed matters most |
 //!
 //! Choose [`WalManager`] for sync code, [`AsyncWalManager`] for async.
+//!
+//! Checkpoints persist the graph as [`PageStore`] pages and only rewrite
+//! the pages dirtied since the previous checkpoint, so recovery is the
+//! pages plus the WAL tail after the last checkpoint record.
 
 mod async_log;
 mod log;
+mod pages;
 mod record;
 mod recovery;
 
 pub use async_log::AsyncWalManager;
 pub use log::{CheckpointMetadata, DurabilityMode, WalConfig, WalManager};
-pub use record::WalRecord;
-pub use recovery::WalRecovery;
+pub use pages::{EdgeImage, NodeImage, PAGE_SIZE, PageData, PageId, PageStore, SchemaImage};
+pub use record::{MAX_RECORD_SIZE, WalRecord};
+pub use recovery::{RecoveryTarget, WalRecovery};
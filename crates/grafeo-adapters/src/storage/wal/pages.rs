@@ -0,0 +1,262 @@
+//! Checkpoint pages - the graph state a checkpoint persists.
+//!
+//! Entities are grouped into fixed-size pages by ID: node `n` lives in
+//! [`PageId::Nodes`]`(n / PAGE_SIZE)`, edges likewise. A checkpoint rewrites
+//! only the pages touched since the previous one, so its cost tracks the
+//! amount of change rather than the size of the graph. Settings that apply
+//! to the graph as a whole, like undirected edge types, share one
+//! [`PageId::Schema`] page.
+
+use grafeo_common::types::{EdgeId, NodeId, Value};
+use grafeo_common::utils::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Number of entity IDs covered by one page.
+pub const PAGE_SIZE: u64 = 1024;
+
+/// Identifies a page of nodes or edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum PageId {
+    /// Nodes with IDs in `[n * PAGE_SIZE, (n + 1) * PAGE_SIZE)`.
+    Nodes(u64),
+    /// Edges with IDs in `[n * PAGE_SIZE, (n + 1) * PAGE_SIZE)`.
+    Edges(u64),
+    /// Graph-wide schema settings.
+    Schema,
+}
+
+impl PageId {
+    /// Returns the page holding a node.
+    #[must_use]
+    pub fn for_node(id: NodeId) -> Self {
+        Self::Nodes(id.as_u64() / PAGE_SIZE)
+    }
+
+    /// Returns the page holding an edge.
+    #[must_use]
+    pub fn for_edge(id: EdgeId) -> Self {
+        Self::Edges(id.as_u64() / PAGE_SIZE)
+    }
+
+    /// Returns the range of raw entity IDs this page covers.
+    ///
+    /// The schema page covers no entities, so its range is empty.
+    #[must_use]
+    pub fn id_range(&self) -> std::ops::Range<u64> {
+        let page = match self {
+            Self::Nodes(page) | Self::Edges(page) => *page,
+            Self::Schema => return 0..0,
+        };
+        page * PAGE_SIZE..(page + 1) * PAGE_SIZE
+    }
+
+    fn file_name(&self) -> String {
+        match self {
+            Self::Nodes(page) => format!("nodes_{page:08}.page"),
+            Self::Edges(page) => format!("edges_{page:08}.page"),
+            Self::Schema => "schema.page".to_string(),
+        }
+    }
+}
+
+/// A node as stored in a page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeImage {
+    /// Node ID.
+    pub id: NodeId,
+    /// Labels on the node.
+    pub labels: Vec<String>,
+    /// Properties on the node.
+    pub properties: Vec<(String, Value)>,
+}
+
+/// An edge as stored in a page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EdgeImage {
+    /// Edge ID.
+    pub id: EdgeId,
+    /// Source node ID.
+    pub src: NodeId,
+    /// Destination node ID.
+    pub dst: NodeId,
+    /// Edge type.
+    pub edge_type: String,
+    /// Properties on the edge.
+    pub properties: Vec<(String, Value)>,
+}
+
+/// Graph-wide settings as stored in the schema page.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaImage {
+    /// Edge types marked undirected.
+    pub undirected_edge_types: Vec<String>,
+}
+
+/// The contents of one page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PageData {
+    /// A page of nodes.
+    Nodes(Vec<NodeImage>),
+    /// A page of edges.
+    Edges(Vec<EdgeImage>),
+    /// The schema page.
+    Schema(SchemaImage),
+}
+
+impl PageData {
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Nodes(nodes) => nodes.is_empty(),
+            Self::Edges(edges) => edges.is_empty(),
+            Self::Schema(schema) => schema.undirected_edge_types.is_empty(),
+        }
+    }
+}
+
+/// Directory of checkpoint pages, one file per page.
+pub struct PageStore {
+    /// Directory holding the page files.
+    dir: PathBuf,
+    /// Pages written since this store was opened.
+    pages_written: AtomicU64,
+}
+
+impl PageStore {
+    /// Opens the page directory.
+    ///
+    /// The directory is created by the first page write, so its existence
+    /// means a checkpoint has written pages.
+    #[must_use]
+    pub fn open(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            pages_written: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns whether any page has ever been written here.
+    #[must_use]
+    pub fn exists(&self) -> bool {
+        self.dir.exists()
+    }
+
+    /// Writes a page, replacing its previous contents.
+    ///
+    /// The page is written to a temp file and renamed into place, so a
+    /// crash leaves either the old or the new page. An empty page removes
+    /// the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the page can't be written.
+    pub fn write_page(&self, page: PageId, data: &PageData) -> Result<()> {
+        let path = self.dir.join(page.file_name());
+        self.pages_written.fetch_add(1, Ordering::Relaxed);
+
+        if data.is_empty() {
+            match fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => return Ok(()),
+            }
+        }
+
+        let bytes = bincode::serde::encode_to_vec(data, bincode::config::standard())
+            .map_err(|e| Error::Serialization(e.to_string()))?;
+        fs::create_dir_all(&self.dir)?;
+        let temp_path = path.with_extension("tmp");
+        let mut file = File::create(&temp_path)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+
+    /// Reads every page in the directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a page can't be read or decoded.
+    pub fn read_all(&self) -> Result<Vec<PageData>> {
+        if !self.exists() {
+            return Ok(Vec::new());
+        }
+        let mut paths: Vec<PathBuf> = fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "page"))
+            .collect();
+        paths.sort();
+
+        let mut pages = Vec::with_capacity(paths.len());
+        for path in paths {
+            let bytes = fs::read(&path)?;
+            let (data, _): (PageData, _) =
+                bincode::serde::decode_from_slice(&bytes, bincode::config::standard())
+                    .map_err(|e| Error::Serialization(e.to_string()))?;
+            pages.push(data);
+        }
+        Ok(pages)
+    }
+
+    /// Returns how many pages have been written since the store was opened.
+    #[must_use]
+    pub fn pages_written(&self) -> u64 {
+        self.pages_written.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_page_round_trip() {
+        let dir = tempdir().unwrap();
+        let store = PageStore::open(dir.path().join("pages"));
+        assert!(!store.exists());
+
+        let node = NodeImage {
+            id: NodeId::new(PAGE_SIZE + 3),
+            labels: vec!["Person".to_string()],
+            properties: vec![("name".to_string(), Value::from("Alix"))],
+        };
+        let page = PageId::for_node(node.id);
+        assert_eq!(page, PageId::Nodes(1));
+        assert!(page.id_range().contains(&node.id.as_u64()));
+
+        store
+            .write_page(page, &PageData::Nodes(vec![node.clone()]))
+            .unwrap();
+        assert_eq!(store.read_all().unwrap(), vec![PageData::Nodes(vec![node])]);
+
+        // Emptying a page removes it
+        store
+            .write_page(page, &PageData::Nodes(Vec::new()))
+            .unwrap();
+        assert!(store.read_all().unwrap().is_empty());
+        assert_eq!(store.pages_written(), 2);
+    }
+
+    #[test]
+    fn test_schema_page_round_trip() {
+        let dir = tempdir().unwrap();
+        let store = PageStore::open(dir.path().join("pages"));
+        assert!(PageId::Schema.id_range().is_empty());
+
+        let schema = PageData::Schema(SchemaImage {
+            undirected_edge_types: vec!["FRIEND".to_string()],
+        });
+        store.write_page(PageId::Schema, &schema).unwrap();
+        assert_eq!(store.read_all().unwrap(), vec![schema]);
+
+        store
+            .write_page(PageId::Schema, &PageData::Schema(SchemaImage::default()))
+            .unwrap();
+        assert!(store.read_all().unwrap().is_empty());
+    }
+}
@@ -1,8 +1,15 @@
 //! WAL record types.
 
-use grafeo_common::types::{EdgeId, NodeId, TxId, Value};
+use grafeo_common::types::{EdgeId, NodeId, Timestamp, TxId, Value};
+use grafeo_common::utils::error::{Error, Result, StorageError};
 use serde::{Deserialize, Serialize};
 
+/// Largest encoded record the log accepts, in bytes.
+///
+/// Recovery reads a longer length prefix as corruption rather than as a
+/// record cut short by a crash.
+pub const MAX_RECORD_SIZE: usize = 64 * 1024 * 1024;
+
 /// A record in the Write-Ahead Log.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum WalRecord {
@@ -74,7 +81,10 @@ pub enum WalRecord {
         label: String,
     },
 
-    /// Transaction commit.
+    /// Transaction commit, as logged before commits carried a time.
+    ///
+    /// Still read from older logs; new commits are logged as
+    /// [`TxCommitAt`](Self::TxCommitAt).
     TxCommit {
         /// Transaction ID.
         tx_id: TxId,
@@ -91,4 +101,120 @@ pub enum WalRecord {
         /// Transaction ID at checkpoint.
         tx_id: TxId,
     },
+
+    /// Transaction commit with the time it committed.
+    ///
+    /// Records are encoded by variant position, so this comes last to keep
+    /// the encoding of every earlier variant unchanged.
+    TxCommitAt {
+        /// Transaction ID.
+        tx_id: TxId,
+        /// When the transaction committed.
+        timestamp: Timestamp,
+    },
+
+    /// Mark an edge type as undirected.
+    SetEdgeTypeUndirected {
+        /// Edge type.
+        edge_type: String,
+    },
+}
+
+impl WalRecord {
+    /// Creates a commit record for `tx_id`, stamped with the current time.
+    #[must_use]
+    pub fn commit(tx_id: TxId) -> Self {
+        Self::TxCommitAt {
+            tx_id,
+            timestamp: Timestamp::now(),
+        }
+    }
+
+    /// Returns whether this record commits a transaction.
+    #[must_use]
+    pub fn is_commit(&self) -> bool {
+        matches!(self, Self::TxCommit { .. } | Self::TxCommitAt { .. })
+    }
+
+    /// Encodes the record as it is framed in the log.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record can't be encoded or its encoding is
+    /// larger than [`MAX_RECORD_SIZE`].
+    pub(crate) fn encode(&self) -> Result<Vec<u8>> {
+        let data = bincode::serde::encode_to_vec(self, bincode::config::standard())
+            .map_err(|e| Error::Serialization(e.to_string()))?;
+        if data.len() > MAX_RECORD_SIZE {
+            return Err(Error::Storage(StorageError::InvalidWalEntry(format!(
+                "record of {} bytes exceeds the {MAX_RECORD_SIZE} byte limit",
+                data.len()
+            ))));
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(record: &WalRecord) -> Vec<u8> {
+        bincode::serde::encode_to_vec(record, bincode::config::standard()).unwrap()
+    }
+
+    fn decode(bytes: &[u8]) -> WalRecord {
+        bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn test_legacy_commit_layout_still_decodes() {
+        // A commit as older versions wrote it: variant 8, then the tx id
+        let legacy = [8u8, 7];
+        assert!(matches!(
+            decode(&legacy),
+            WalRecord::TxCommit { tx_id } if tx_id == TxId::new(7)
+        ));
+        assert_eq!(
+            encode(&WalRecord::TxCommit {
+                tx_id: TxId::new(7)
+            }),
+            legacy
+        );
+
+        // Records logged before and after it keep their layout too
+        let checkpoint = encode(&WalRecord::Checkpoint {
+            tx_id: TxId::new(7),
+        });
+        assert_eq!(checkpoint, [10u8, 7]);
+
+        let timed = WalRecord::TxCommitAt {
+            tx_id: TxId::new(7),
+            timestamp: Timestamp::from_millis(1_000),
+        };
+        let bytes = encode(&timed);
+        assert_eq!(bytes[0], 11);
+        assert!(matches!(
+            decode(&bytes),
+            WalRecord::TxCommitAt { tx_id, timestamp }
+                if tx_id == TxId::new(7) && timestamp == Timestamp::from_millis(1_000)
+        ));
+        assert!(decode(&bytes).is_commit());
+        assert!(decode(&legacy).is_commit());
+    }
+
+    #[test]
+    fn test_oversized_record_is_rejected() {
+        let record = |len: usize| WalRecord::SetNodeProperty {
+            id: NodeId::new(1),
+            key: "blob".to_string(),
+            value: Value::from("x".repeat(len)),
+        };
+
+        assert!(record(1024).encode().is_ok());
+        let err = record(MAX_RECORD_SIZE).encode().unwrap_err();
+        assert!(err.to_string().contains("exceeds"), "{err}");
+    }
 }
@@ -1,18 +1,70 @@
 //! WAL recovery.
 
-use super::{CheckpointMetadata, WalManager, WalRecord};
+use super::{CheckpointMetadata, MAX_RECORD_SIZE, WalManager, WalRecord};
+use grafeo_common::types::{Timestamp, TxId};
 use grafeo_common::utils::error::{Error, Result, StorageError};
-use std::fs::File;
+use std::fs::{File, OpenOptions};
 use std::io::{BufReader, Read};
 use std::path::Path;
 
 /// Name of the checkpoint metadata file.
 const CHECKPOINT_METADATA_FILE: &str = "checkpoint.meta";
 
+/// Where point-in-time recovery stops replaying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryTarget {
+    /// Replay transactions that committed at or before this time.
+    Time(Timestamp),
+    /// Replay transactions up to and including this one.
+    Transaction(TxId),
+}
+
+impl RecoveryTarget {
+    /// Returns whether a commit lies past this target.
+    ///
+    /// Commits logged without a time are only compared by transaction, so
+    /// a time target replays them.
+    fn is_exceeded_by(self, tx_id: TxId, timestamp: Option<Timestamp>) -> bool {
+        match self {
+            Self::Time(target) => timestamp.is_some_and(|timestamp| timestamp > target),
+            Self::Transaction(target) => tx_id > target,
+        }
+    }
+}
+
+/// Outcome of reading one framed record.
+enum ReadOutcome {
+    /// A valid record and the size of its frame in bytes.
+    Record(WalRecord, u64),
+    /// Clean end of file.
+    Eof,
+    /// The file ended partway through a record.
+    Truncated,
+    /// A length prefix no writer could have produced.
+    BadLength(u32),
+    /// A complete frame whose checksum or encoding is bad.
+    Corrupt {
+        /// Size of the bad frame in bytes.
+        frame_len: u64,
+        /// What was wrong with it.
+        reason: String,
+    },
+}
+
 /// Handles WAL recovery after a crash.
+///
+/// Each record is framed by a length prefix and a CRC32 checksum. A crash
+/// mid-write can leave a torn record at the very end of the log; by default
+/// that is an error, but [`truncate_torn_tail`](Self::truncate_torn_tail)
+/// cuts it off instead so the WAL can be appended to again. A bad record
+/// anywhere else is real corruption and always an error.
 pub struct WalRecovery {
     /// Directory containing WAL files.
     dir: std::path::PathBuf,
+    /// Whether to truncate a torn final record instead of failing.
+    truncate_torn_tail: bool,
+    /// Where to stop replaying, if not at the end of the log.
+    target: Option<RecoveryTarget>,
 }
 
 impl WalRecovery {
@@ -20,6 +72,8 @@ impl WalRecovery {
     pub fn new(dir: impl AsRef<Path>) -> Self {
         Self {
             dir: dir.as_ref().to_path_buf(),
+            truncate_torn_tail: false,
+            target: None,
         }
     }
 
@@ -28,9 +82,31 @@ impl WalRecovery {
     pub fn from_wal(wal: &WalManager) -> Self {
         Self {
             dir: wal.dir().to_path_buf(),
+            truncate_torn_tail: false,
+            target: None,
         }
     }
 
+    /// Sets whether a torn record at the end of the log is truncated away
+    /// (`true`) or reported as an error (`false`, the default).
+    #[must_use]
+    pub fn truncate_torn_tail(mut self, truncate: bool) -> Self {
+        self.truncate_torn_tail = truncate;
+        self
+    }
+
+    /// Stops replay at `target`, recovering the state as of that point.
+    ///
+    /// Replay ends at the first commit past the target; that transaction
+    /// and everything after it are left out. Replay still starts at the
+    /// last checkpoint, so a target older than the checkpoint yields the
+    /// checkpointed state plus nothing more.
+    #[must_use]
+    pub fn until(mut self, target: RecoveryTarget) -> Self {
+        self.target = Some(target);
+        self
+    }
+
     /// Reads checkpoint metadata if it exists.
     ///
     /// Returns `None` if no checkpoint metadata is found.
@@ -46,11 +122,7 @@ impl WalRecovery {
         let mut data = Vec::new();
         reader.read_to_end(&mut data)?;
 
-        let (metadata, _): (CheckpointMetadata, _) =
-            bincode::serde::decode_from_slice(&data, bincode::config::standard())
-                .map_err(|e| Error::Serialization(e.to_string()))?;
-
-        Ok(Some(metadata))
+        CheckpointMetadata::decode(&data).map(Some)
     }
 
     /// Returns the checkpoint metadata, if any.
@@ -111,9 +183,9 @@ impl WalRecovery {
         }
 
         // Read log files in sequence, skipping those before checkpoint
-        for log_file in log_files {
+        'files: for (index, log_file) in log_files.iter().enumerate() {
             // Extract sequence number from filename
-            let sequence = Self::sequence_from_path(&log_file).unwrap_or(0);
+            let sequence = Self::sequence_from_path(log_file).unwrap_or(0);
 
             // Skip files that are completely before the checkpoint
             // We include the checkpoint sequence file because it may contain
@@ -128,43 +200,48 @@ impl WalRecovery {
                 continue;
             }
 
-            let file = match File::open(&log_file) {
-                Ok(f) => f,
-                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
-                Err(e) => return Err(e.into()),
+            // A torn record can only be the tail if nothing follows it
+            let is_tail_file = later_files_empty(&log_files[index + 1..]);
+            let records = match self.read_file(log_file, is_tail_file) {
+                Ok(records) => records,
+                Err(Error::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
             };
-            let mut reader = BufReader::new(file);
-
-            // Read all records from this file
-            loop {
-                match self.read_record(&mut reader) {
-                    Ok(Some(record)) => {
-                        match &record {
-                            WalRecord::TxCommit { .. } => {
-                                // Commit current transaction
-                                committed_records.append(&mut current_tx_records);
-                                committed_records.push(record);
-                            }
-                            WalRecord::TxAbort { .. } => {
-                                // Discard current transaction
-                                current_tx_records.clear();
-                            }
-                            WalRecord::Checkpoint { .. } => {
-                                // Checkpoint - clear uncommitted, keep committed
-                                current_tx_records.clear();
-                                committed_records.push(record);
-                            }
-                            _ => {
-                                current_tx_records.push(record);
-                            }
-                        }
+
+            for record in records {
+                match &record {
+                    WalRecord::TxCommit { tx_id }
+                        if self
+                            .target
+                            .is_some_and(|target| target.is_exceeded_by(*tx_id, None)) =>
+                    {
+                        // Past the recovery target: this transaction and
+                        // everything after it are left out
+                        break 'files;
+                    }
+                    WalRecord::TxCommitAt { tx_id, timestamp }
+                        if self.target.is_some_and(|target| {
+                            target.is_exceeded_by(*tx_id, Some(*timestamp))
+                        }) =>
+                    {
+                        break 'files;
+                    }
+                    WalRecord::TxCommit { .. } | WalRecord::TxCommitAt { .. } => {
+                        // Commit current transaction
+                        committed_records.append(&mut current_tx_records);
+                        committed_records.push(record);
+                    }
+                    WalRecord::TxAbort { .. } => {
+                        // Discard current transaction
+                        current_tx_records.clear();
                     }
-                    Ok(None) => break, // EOF
-                    Err(e) => {
-                        // Log corruption - stop reading this file but continue
-                        // with remaining files (best-effort recovery)
-                        tracing::warn!("WAL corruption detected in {:?}: {}", log_file, e);
-                        break;
+                    WalRecord::Checkpoint { .. } => {
+                        // Checkpoint - clear uncommitted, keep committed
+                        current_tx_records.clear();
+                        committed_records.push(record);
+                    }
+                    _ => {
+                        current_tx_records.push(record);
                     }
                 }
             }
@@ -189,30 +266,20 @@ impl WalRecovery {
     ///
     /// Returns an error if recovery fails.
     pub fn recover_file(&self, path: impl AsRef<Path>) -> Result<Vec<WalRecord>> {
-        let file = File::open(path.as_ref())?;
-        let mut reader = BufReader::new(file);
-
         let mut current_tx_records = Vec::new();
         let mut committed_records = Vec::new();
 
-        loop {
-            match self.read_record(&mut reader) {
-                Ok(Some(record)) => match &record {
-                    WalRecord::TxCommit { .. } => {
-                        committed_records.append(&mut current_tx_records);
-                        committed_records.push(record);
-                    }
-                    WalRecord::TxAbort { .. } => {
-                        current_tx_records.clear();
-                    }
-                    _ => {
-                        current_tx_records.push(record);
-                    }
-                },
-                Ok(None) => break,
-                Err(e) => {
-                    tracing::warn!("WAL corruption detected: {}", e);
-                    break;
+        for record in self.read_file(path.as_ref(), true)? {
+            match &record {
+                WalRecord::TxCommit { .. } | WalRecord::TxCommitAt { .. } => {
+                    committed_records.append(&mut current_tx_records);
+                    committed_records.push(record);
+                }
+                WalRecord::TxAbort { .. } => {
+                    current_tx_records.clear();
+                }
+                _ => {
+                    current_tx_records.push(record);
                 }
             }
         }
@@ -220,6 +287,65 @@ impl WalRecovery {
         Ok(committed_records)
     }
 
+    /// Reads every record of one log file.
+    ///
+    /// A record cut short by the end of a tail file, or a bad record whose
+    /// frame runs exactly to that end, is a torn write: it's truncated or
+    /// reported depending on `truncate_torn_tail`. An implausible length
+    /// prefix, or a bad frame with more data after it, is corruption.
+    fn read_file(&self, path: &Path, is_tail_file: bool) -> Result<Vec<WalRecord>> {
+        let file = File::open(path)?;
+        let file_len = file.metadata()?.len();
+        let mut reader = BufReader::new(file);
+
+        let mut records = Vec::new();
+        let mut offset = 0u64;
+        loop {
+            let reason = match self.read_record(&mut reader)? {
+                ReadOutcome::Record(record, frame_len) => {
+                    records.push(record);
+                    offset += frame_len;
+                    continue;
+                }
+                ReadOutcome::Eof => return Ok(records),
+                ReadOutcome::Truncated => "incomplete record".to_string(),
+                ReadOutcome::BadLength(len) => {
+                    return Err(corruption(
+                        path,
+                        offset,
+                        &format!("implausible record length {len}"),
+                    ));
+                }
+                ReadOutcome::Corrupt { frame_len, reason } => {
+                    if offset + frame_len < file_len {
+                        return Err(corruption(path, offset, &reason));
+                    }
+                    reason
+                }
+            };
+
+            if !is_tail_file {
+                return Err(corruption(path, offset, &reason));
+            }
+            if !self.truncate_torn_tail {
+                return Err(Error::Storage(StorageError::Corruption(format!(
+                    "torn record at the end of {} (offset {offset}): {reason}",
+                    path.display()
+                ))));
+            }
+
+            tracing::warn!(
+                "Truncating torn WAL record at the end of {:?} (offset {}, {} bytes dropped): {}",
+                path,
+                offset,
+                file_len - offset,
+                reason
+            );
+            OpenOptions::new().write(true).open(path)?.set_len(offset)?;
+            return Ok(records);
+        }
+    }
+
     fn get_log_files(&self) -> Result<Vec<std::path::PathBuf>> {
         let mut files = Vec::new();
 
@@ -242,39 +368,81 @@ impl WalRecovery {
         Ok(files)
     }
 
-    fn read_record(&self, reader: &mut BufReader<File>) -> Result<Option<WalRecord>> {
+    fn read_record(&self, reader: &mut BufReader<File>) -> Result<ReadOutcome> {
         // Read length prefix
         let mut len_buf = [0u8; 4];
-        match reader.read_exact(&mut len_buf) {
-            Ok(()) => {}
-            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
-            Err(e) => return Err(e.into()),
+        match read_fully(reader, &mut len_buf)? {
+            0 => return Ok(ReadOutcome::Eof),
+            4 => {}
+            _ => return Ok(ReadOutcome::Truncated),
         }
         let len = u32::from_le_bytes(len_buf) as usize;
+        // Every record encodes to at least its variant tag, and writers
+        // refuse anything larger than the limit
+        if len == 0 || len > MAX_RECORD_SIZE {
+            return Ok(ReadOutcome::BadLength(len as u32));
+        }
+        let frame_len = 4 + len as u64 + 4;
 
-        // Read data
-        let mut data = vec![0u8; len];
-        reader.read_exact(&mut data)?;
+        // Read data, growing the buffer as bytes arrive so a garbage length
+        // can't trigger a huge allocation
+        let mut data = Vec::new();
+        if (&mut *reader).take(len as u64).read_to_end(&mut data)? < len {
+            return Ok(ReadOutcome::Truncated);
+        }
 
         // Read and verify checksum
         let mut checksum_buf = [0u8; 4];
-        reader.read_exact(&mut checksum_buf)?;
+        if read_fully(reader, &mut checksum_buf)? < 4 {
+            return Ok(ReadOutcome::Truncated);
+        }
         let stored_checksum = u32::from_le_bytes(checksum_buf);
         let computed_checksum = crc32fast::hash(&data);
 
         if stored_checksum != computed_checksum {
-            return Err(Error::Storage(StorageError::Corruption(
-                "WAL checksum mismatch".to_string(),
-            )));
+            return Ok(ReadOutcome::Corrupt {
+                frame_len,
+                reason: "checksum mismatch".to_string(),
+            });
         }
 
         // Deserialize
-        let (record, _): (WalRecord, _) =
-            bincode::serde::decode_from_slice(&data, bincode::config::standard())
-                .map_err(|e| Error::Serialization(e.to_string()))?;
+        match bincode::serde::decode_from_slice(&data, bincode::config::standard()) {
+            Ok((record, _)) => Ok(ReadOutcome::Record(record, frame_len)),
+            Err(e) => Ok(ReadOutcome::Corrupt {
+                frame_len,
+                reason: e.to_string(),
+            }),
+        }
+    }
+}
 
-        Ok(Some(record))
+/// Reads until `buf` is full or the file ends, returning the bytes read.
+fn read_fully(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
     }
+    Ok(filled)
+}
+
+/// Returns whether all of `files` are empty (or gone).
+fn later_files_empty(files: &[std::path::PathBuf]) -> bool {
+    files
+        .iter()
+        .all(|f| std::fs::metadata(f).map_or(true, |m| m.len() == 0))
+}
+
+fn corruption(path: &Path, offset: u64, reason: &str) -> Error {
+    Error::Storage(StorageError::Corruption(format!(
+        "WAL record at offset {offset} in {} is corrupt: {reason}",
+        path.display()
+    )))
 }
 
 #[cfg(test)]
@@ -297,10 +465,7 @@ mod tests {
             })
             .unwrap();
 
-            wal.log(&WalRecord::TxCommit {
-                tx_id: TxId::new(1),
-            })
-            .unwrap();
+            wal.log(&WalRecord::commit(TxId::new(1))).unwrap();
 
             wal.sync().unwrap();
         }
@@ -358,10 +523,7 @@ mod tests {
                 })
                 .unwrap();
             }
-            wal.log(&WalRecord::TxCommit {
-                tx_id: TxId::new(1),
-            })
-            .unwrap();
+            wal.log(&WalRecord::commit(TxId::new(1))).unwrap();
 
             // Second transaction
             for i in 5..10 {
@@ -371,10 +533,7 @@ mod tests {
                 })
                 .unwrap();
             }
-            wal.log(&WalRecord::TxCommit {
-                tx_id: TxId::new(2),
-            })
-            .unwrap();
+            wal.log(&WalRecord::commit(TxId::new(2))).unwrap();
 
             wal.sync().unwrap();
         }
@@ -403,10 +562,7 @@ mod tests {
                 labels: vec!["Test".to_string()],
             })
             .unwrap();
-            wal.log(&WalRecord::TxCommit {
-                tx_id: TxId::new(1),
-            })
-            .unwrap();
+            wal.log(&WalRecord::commit(TxId::new(1))).unwrap();
 
             // Create checkpoint
             wal.checkpoint(TxId::new(1), EpochId::new(10)).unwrap();
@@ -417,10 +573,7 @@ mod tests {
                 labels: vec!["Test".to_string()],
             })
             .unwrap();
-            wal.log(&WalRecord::TxCommit {
-                tx_id: TxId::new(2),
-            })
-            .unwrap();
+            wal.log(&WalRecord::commit(TxId::new(2))).unwrap();
 
             wal.sync().unwrap();
         }
@@ -458,10 +611,7 @@ mod tests {
                 })
                 .unwrap();
             }
-            wal.log(&WalRecord::TxCommit {
-                tx_id: TxId::new(1),
-            })
-            .unwrap();
+            wal.log(&WalRecord::commit(TxId::new(1))).unwrap();
 
             // Create checkpoint
             wal.checkpoint(TxId::new(1), EpochId::new(100)).unwrap();
@@ -474,10 +624,7 @@ mod tests {
                 })
                 .unwrap();
             }
-            wal.log(&WalRecord::TxCommit {
-                tx_id: TxId::new(2),
-            })
-            .unwrap();
+            wal.log(&WalRecord::commit(TxId::new(2))).unwrap();
 
             wal.sync().unwrap();
         }
@@ -490,4 +637,253 @@ mod tests {
         // The number depends on how many log files were skipped
         assert!(!records.is_empty(), "Should recover some records");
     }
+
+    /// Writes one committed transaction creating nodes `ids` and returns
+    /// the path of the single log file.
+    fn write_committed(dir: &Path, ids: std::ops::Range<u64>, tx: u64) -> std::path::PathBuf {
+        let wal = WalManager::open(dir).unwrap();
+        for i in ids {
+            wal.log(&WalRecord::CreateNode {
+                id: NodeId::new(i),
+                labels: vec!["Person".to_string()],
+            })
+            .unwrap();
+        }
+        wal.log(&WalRecord::commit(TxId::new(tx))).unwrap();
+        wal.sync().unwrap();
+
+        let files = WalRecovery::new(dir).get_log_files().unwrap();
+        assert_eq!(files.len(), 1);
+        files.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn test_recovery_clean_log_strict() {
+        let dir = tempdir().unwrap();
+        let path = write_committed(dir.path(), 0..3, 1);
+        let len = std::fs::metadata(&path).unwrap().len();
+
+        let records = WalRecovery::new(dir.path()).recover().unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), len);
+    }
+
+    #[test]
+    fn test_recovery_torn_tail() {
+        let dir = tempdir().unwrap();
+        let path = write_committed(dir.path(), 0..3, 1);
+        let clean_len = std::fs::metadata(&path).unwrap().len();
+
+        // A crash mid-write: a length prefix promising more than was written
+        {
+            use std::io::Write;
+            let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+            file.write_all(&100u32.to_le_bytes()).unwrap();
+            file.write_all(&[7u8; 10]).unwrap();
+        }
+
+        let err = WalRecovery::new(dir.path()).recover().unwrap_err();
+        assert!(err.to_string().contains("torn record"), "{err}");
+
+        let records = WalRecovery::new(dir.path())
+            .truncate_torn_tail(true)
+            .recover()
+            .unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), clean_len);
+
+        // The truncated log can be appended to and replayed strictly again
+        write_committed(dir.path(), 3..5, 2);
+        let records = WalRecovery::new(dir.path()).recover().unwrap();
+        assert_eq!(records.len(), 7);
+    }
+
+    #[test]
+    fn test_recovery_mid_log_corruption_errors() {
+        let dir = tempdir().unwrap();
+        let path = write_committed(dir.path(), 0..3, 1);
+
+        // Flip a byte inside the first record's payload
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[5] ^= 0xFF;
+        std::fs::write(&path, &bytes).unwrap();
+
+        for truncate in [false, true] {
+            let err = WalRecovery::new(dir.path())
+                .truncate_torn_tail(truncate)
+                .recover()
+                .unwrap_err();
+            assert!(err.to_string().contains("is corrupt"), "{err}");
+        }
+        // Nothing was cut off
+        assert_eq!(std::fs::read(&path).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_recovery_bad_length_prefix_errors() {
+        let dir = tempdir().unwrap();
+        let path = write_committed(dir.path(), 0..3, 1);
+        let clean = std::fs::read(&path).unwrap();
+
+        let assert_corrupt = |bytes: &[u8]| {
+            std::fs::write(&path, bytes).unwrap();
+            for truncate in [false, true] {
+                let err = WalRecovery::new(dir.path())
+                    .truncate_torn_tail(truncate)
+                    .recover()
+                    .unwrap_err();
+                assert!(err.to_string().contains("is corrupt"), "{err}");
+                assert!(err.to_string().contains("implausible"), "{err}");
+            }
+            // Nothing was cut off
+            assert_eq!(std::fs::read(&path).unwrap(), bytes);
+        };
+
+        // A garbage length in the first record's prefix
+        let mut bytes = clean.clone();
+        bytes[..4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_corrupt(&bytes);
+
+        // The same garbage after the last record is not a torn write either
+        let mut bytes = clean.clone();
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(&[7u8; 10]);
+        assert_corrupt(&bytes);
+
+        // Nor is a zero length, which no record encodes to
+        let mut bytes = clean;
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(&[0u8; 4]);
+        assert_corrupt(&bytes);
+    }
+
+    /// Logs a transaction creating nodes `ids` that commits at `millis`.
+    fn log_tx_at(wal: &WalManager, ids: std::ops::Range<u64>, tx: u64, millis: i64) {
+        for i in ids {
+            wal.log(&WalRecord::CreateNode {
+                id: NodeId::new(i),
+                labels: vec![],
+            })
+            .unwrap();
+        }
+        wal.log(&WalRecord::TxCommitAt {
+            tx_id: TxId::new(tx),
+            timestamp: Timestamp::from_millis(millis),
+        })
+        .unwrap();
+    }
+
+    fn created_nodes(records: &[WalRecord]) -> Vec<u64> {
+        records
+            .iter()
+            .filter_map(|r| match r {
+                WalRecord::CreateNode { id, .. } => Some(id.as_u64()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_point_in_time_recovery() {
+        let dir = tempdir().unwrap();
+        {
+            let wal = WalManager::open(dir.path()).unwrap();
+            log_tx_at(&wal, 0..3, 1, 1_000);
+            log_tx_at(&wal, 3..6, 2, 2_000);
+            log_tx_at(&wal, 6..9, 3, 3_000);
+            wal.sync().unwrap();
+        }
+
+        let recover_until = |target| {
+            let records = WalRecovery::new(dir.path())
+                .until(target)
+                .recover()
+                .unwrap();
+            created_nodes(&records)
+        };
+
+        let at = |millis| RecoveryTarget::Time(Timestamp::from_millis(millis));
+        assert_eq!(recover_until(at(2_000)), (0..6).collect::<Vec<_>>());
+        assert_eq!(recover_until(at(2_500)), (0..6).collect::<Vec<_>>());
+        assert!(recover_until(at(999)).is_empty());
+        assert_eq!(
+            recover_until(RecoveryTarget::Transaction(TxId::new(1))),
+            (0..3).collect::<Vec<_>>()
+        );
+
+        let all = WalRecovery::new(dir.path()).recover().unwrap();
+        assert_eq!(created_nodes(&all), (0..9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_recovery_reads_commits_without_a_time() {
+        let dir = tempdir().unwrap();
+        {
+            // Transactions 1 and 2 were logged by a version without commit
+            // times, transaction 3 after upgrading
+            let wal = WalManager::open(dir.path()).unwrap();
+            for (ids, tx) in [(0..3, 1), (3..6, 2)] {
+                for i in ids {
+                    wal.log(&WalRecord::CreateNode {
+                        id: NodeId::new(i),
+                        labels: vec![],
+                    })
+                    .unwrap();
+                }
+                wal.log(&WalRecord::TxCommit {
+                    tx_id: TxId::new(tx),
+                })
+                .unwrap();
+            }
+            log_tx_at(&wal, 6..9, 3, 3_000);
+            wal.sync().unwrap();
+        }
+
+        let recover_until = |target| {
+            let records = WalRecovery::new(dir.path())
+                .until(target)
+                .recover()
+                .unwrap();
+            created_nodes(&records)
+        };
+
+        let all = WalRecovery::new(dir.path()).recover().unwrap();
+        assert_eq!(created_nodes(&all), (0..9).collect::<Vec<_>>());
+        assert_eq!(
+            recover_until(RecoveryTarget::Transaction(TxId::new(1))),
+            (0..3).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            recover_until(RecoveryTarget::Time(Timestamp::from_millis(2_000))),
+            (0..6).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_point_in_time_recovery_before_checkpoint() {
+        use grafeo_common::types::EpochId;
+
+        let dir = tempdir().unwrap();
+        {
+            let wal = WalManager::open(dir.path()).unwrap();
+            log_tx_at(&wal, 0..3, 1, 1_000);
+            wal.rotate().unwrap();
+            log_tx_at(&wal, 3..6, 2, 2_000);
+            wal.checkpoint(TxId::new(2), EpochId::new(1)).unwrap();
+            log_tx_at(&wal, 6..9, 3, 3_000);
+            wal.sync().unwrap();
+        }
+
+        let recover_until = |millis| {
+            let records = WalRecovery::new(dir.path())
+                .until(RecoveryTarget::Time(Timestamp::from_millis(millis)))
+                .recover()
+                .unwrap();
+            created_nodes(&records)
+        };
+
+        // Replay starts at the checkpoint's log file, not at the beginning
+        assert_eq!(recover_until(2_500), (3..6).collect::<Vec<_>>());
+        assert!(recover_until(1_500).is_empty());
+    }
 }
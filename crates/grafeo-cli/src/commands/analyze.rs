@@ -0,0 +1,67 @@
+//! Statistics rebuild command.
+
+use std::path::Path;
+
+use anyhow::Result;
+use grafeo_engine::GrafeoDB;
+use serde::Serialize;
+
+use crate::OutputFormat;
+use crate::output::{self, Format};
+
+/// Statistics rebuild result output.
+#[derive(Serialize)]
+struct AnalyzeOutput {
+    nodes_before: u64,
+    nodes_after: u64,
+    edges_before: u64,
+    edges_after: u64,
+    label_count: usize,
+    edge_type_count: usize,
+    property_count: usize,
+    duration_ms: u64,
+}
+
+/// Run the analyze command.
+pub fn run(path: &Path, format: OutputFormat, quiet: bool) -> Result<()> {
+    let db = GrafeoDB::open(path)?;
+
+    output::status("Rebuilding statistics...", quiet);
+    let stats = db.analyze();
+
+    let output = AnalyzeOutput {
+        nodes_before: stats.nodes_before,
+        nodes_after: stats.nodes_after,
+        edges_before: stats.edges_before,
+        edges_after: stats.edges_after,
+        label_count: stats.label_count,
+        edge_type_count: stats.edge_type_count,
+        property_count: stats.property_count,
+        duration_ms: stats.duration_ms,
+    };
+
+    let fmt: Format = format.into();
+    match fmt {
+        Format::Json => {
+            if !quiet {
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            }
+        }
+        Format::Table => {
+            let items = vec![
+                ("Nodes Before", output.nodes_before.to_string()),
+                ("Nodes After", output.nodes_after.to_string()),
+                ("Edges Before", output.edges_before.to_string()),
+                ("Edges After", output.edges_after.to_string()),
+                ("Labels", output.label_count.to_string()),
+                ("Edge Types", output.edge_type_count.to_string()),
+                ("Property Keys", output.property_count.to_string()),
+                ("Duration", format!("{} ms", output.duration_ms)),
+            ];
+            output::print_key_value_table(&items, fmt, quiet);
+            output::success("Statistics rebuilt", quiet);
+        }
+    }
+
+    Ok(())
+}
@@ -1,10 +1,10 @@
 //! Data export/import commands.
 
-use anyhow::{Context, Result};
-use grafeo_engine::GrafeoDB;
+use anyhow::{Context, Result, bail};
+use grafeo_engine::{DumpFormat, GrafeoDB};
 
 use crate::output;
-use crate::{DataCommands, OutputFormat};
+use crate::{DataCommands, OutputFormat, dump, graphml};
 
 /// Run data commands.
 pub fn run(cmd: DataCommands, _format: OutputFormat, quiet: bool) -> Result<()> {
@@ -12,15 +12,18 @@ pub fn run(cmd: DataCommands, _format: OutputFormat, quiet: bool) -> Result<()>
         DataCommands::Dump {
             path,
             output: out,
-            format: dump_format,
+            export_format,
         } => {
-            let format_name = dump_format.as_deref().unwrap_or("parquet");
+            let dump_format: DumpFormat = match export_format {
+                Some(name) => name.parse().map_err(anyhow::Error::msg)?,
+                None => DumpFormat::default(),
+            };
             output::status(
                 &format!(
                     "Exporting {} to {} (format: {})...",
                     path.display(),
                     out.display(),
-                    format_name
+                    dump_format
                 ),
                 quiet,
             );
@@ -28,19 +31,21 @@ pub fn run(cmd: DataCommands, _format: OutputFormat, quiet: bool) -> Result<()>
             let db = GrafeoDB::open(&path)
                 .with_context(|| format!("Failed to open database at {}", path.display()))?;
 
-            // Get counts for progress reporting
-            let info = db.info();
-
-            // TODO: Implement actual export when format handlers are available
-            // For now, use the native backup format
-            db.save(&out)
-                .with_context(|| format!("Failed to export to {}", out.display()))?;
+            let metadata = match dump_format {
+                DumpFormat::Parquet => dump::write(&db, &out)
+                    .with_context(|| format!("Failed to export to {}", out.display()))?,
+                DumpFormat::GraphMl => graphml::write(&db, &out)
+                    .with_context(|| format!("Failed to export to {}", out.display()))?,
+                DumpFormat::Turtle | DumpFormat::Json => {
+                    bail!("Export format '{dump_format}' is not supported yet")
+                }
+            };
 
             output::success(
                 &format!(
                     "Exported {} nodes and {} edges to {}",
-                    info.node_count,
-                    info.edge_count,
+                    metadata.node_count,
+                    metadata.edge_count,
                     out.display()
                 ),
                 quiet,
@@ -52,10 +57,17 @@ pub fn run(cmd: DataCommands, _format: OutputFormat, quiet: bool) -> Result<()>
                 quiet,
             );
 
-            // TODO: Implement format detection and import
-            // For now, use native backup format
-            let db = GrafeoDB::open(&input)
-                .with_context(|| format!("Failed to open dump at {}", input.display()))?;
+            // Parquet dumps carry a metadata file; anything else is read as
+            // a native database
+            let db = match dump::read_metadata(&input)? {
+                Some(metadata) if metadata.format == DumpFormat::Parquet => dump::read(&input)
+                    .with_context(|| format!("Failed to read dump at {}", input.display()))?,
+                Some(metadata) => {
+                    bail!("Import format '{}' is not supported yet", metadata.format)
+                }
+                None => GrafeoDB::open(&input)
+                    .with_context(|| format!("Failed to open dump at {}", input.display()))?,
+            };
             db.save(&path)
                 .with_context(|| format!("Failed to save to {}", path.display()))?;
 
@@ -1,10 +1,13 @@
 //! CLI command implementations.
 
+pub mod analyze;
 pub mod backup;
 pub mod compact;
 pub mod data;
 pub mod index;
 pub mod info;
+#[cfg(feature = "gql")]
+pub mod query;
 pub mod schema;
 pub mod stats;
 pub mod validate;
@@ -0,0 +1,173 @@
+//! Ad-hoc read query command.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use comfy_table::Cell;
+use grafeo_common::types::{EdgeId, LogicalType, NodeId, PropertyKey, Value};
+use grafeo_engine::GrafeoDB;
+use serde::Serialize;
+
+use crate::OutputFormat;
+use crate::output::{self, Format};
+
+/// Properties shown per node or edge in table mode before the rest are elided.
+const TABLE_PROPERTY_LIMIT: usize = 3;
+
+/// Query results for JSON output.
+#[derive(Serialize)]
+struct QueryOutput {
+    columns: Vec<String>,
+    rows: Vec<Vec<serde_json::Value>>,
+}
+
+/// Run the query command.
+///
+/// The database is opened read-only, so running a query never writes to
+/// it, and the query runs against a read-only snapshot, so anything that
+/// would modify the graph is rejected before it executes. Results are
+/// printed even with `--quiet`, which only drops the row count.
+pub fn run(path: &Path, query: &str, format: OutputFormat, quiet: bool) -> Result<()> {
+    if !path.exists() {
+        bail!("No database at {}", path.display());
+    }
+    let db = GrafeoDB::open_read_only(path)
+        .with_context(|| format!("Failed to open database at {}", path.display()))?;
+    let session = db.session();
+    let result = session.begin_read_only().execute(query)?;
+
+    let column_type = |i: usize| result.column_types.get(i).unwrap_or(&LogicalType::Any);
+
+    let fmt: Format = format.into();
+    match fmt {
+        Format::Json => {
+            let output = QueryOutput {
+                columns: result.columns.clone(),
+                rows: result
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .enumerate()
+                            .map(|(i, value)| cell_json(&db, value, column_type(i)))
+                            .collect()
+                    })
+                    .collect(),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        Format::Table => {
+            let mut table = output::create_table();
+            let headers: Vec<&str> = result.columns.iter().map(String::as_str).collect();
+            output::add_header(&mut table, &headers);
+            for row in &result.rows {
+                table.add_row(
+                    row.iter()
+                        .enumerate()
+                        .map(|(i, value)| Cell::new(cell_text(&db, value, column_type(i))))
+                        .collect::<Vec<_>>(),
+                );
+            }
+            println!("{table}");
+        }
+    }
+
+    let rows = result.rows.len();
+    output::status(
+        &format!("{rows} row{}", if rows == 1 { "" } else { "s" }),
+        quiet,
+    );
+
+    Ok(())
+}
+
+/// Renders a result cell for table mode.
+///
+/// Nodes print as `(:Label {key: value})` and edges as `[:TYPE {key: value}]`,
+/// with at most [`TABLE_PROPERTY_LIMIT`] properties in key order.
+fn cell_text(db: &GrafeoDB, value: &Value, column_type: &LogicalType) -> String {
+    match (column_type, value) {
+        (LogicalType::Node, Value::Int64(id)) => match db.get_node(NodeId(*id as u64)) {
+            Some(node) => {
+                let labels: String = node.labels.iter().flat_map(|l| [":", l]).collect();
+                format!("({labels}{})", property_text(&node.properties))
+            }
+            None => value.to_string(),
+        },
+        (LogicalType::Edge, Value::Int64(id)) => match db.get_edge(EdgeId(*id as u64)) {
+            Some(edge) => format!("[:{}{}]", edge.edge_type, property_text(&edge.properties)),
+            None => value.to_string(),
+        },
+        _ => value.to_string(),
+    }
+}
+
+/// Formats properties as ` {key: value, ...}`, or nothing if there are none.
+fn property_text(properties: &BTreeMap<PropertyKey, Value>) -> String {
+    if properties.is_empty() {
+        return String::new();
+    }
+    let mut shown: Vec<String> = properties
+        .iter()
+        .take(TABLE_PROPERTY_LIMIT)
+        .map(|(key, value)| format!("{key}: {value}"))
+        .collect();
+    if properties.len() > TABLE_PROPERTY_LIMIT {
+        shown.push("…".to_string());
+    }
+    format!(" {{{}}}", shown.join(", "))
+}
+
+/// Renders a result cell for JSON output, expanding nodes and edges into
+/// objects with all their properties.
+fn cell_json(db: &GrafeoDB, value: &Value, column_type: &LogicalType) -> serde_json::Value {
+    match (column_type, value) {
+        (LogicalType::Node, Value::Int64(id)) => match db.get_node(NodeId(*id as u64)) {
+            Some(node) => serde_json::json!({
+                "id": id,
+                "labels": node.labels.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                "properties": properties_json(&node.properties),
+            }),
+            None => value_json(value),
+        },
+        (LogicalType::Edge, Value::Int64(id)) => match db.get_edge(EdgeId(*id as u64)) {
+            Some(edge) => serde_json::json!({
+                "id": id,
+                "type": edge.edge_type.to_string(),
+                "source": edge.src.0,
+                "target": edge.dst.0,
+                "properties": properties_json(&edge.properties),
+            }),
+            None => value_json(value),
+        },
+        _ => value_json(value),
+    }
+}
+
+fn properties_json(properties: &BTreeMap<PropertyKey, Value>) -> serde_json::Value {
+    serde_json::Value::Object(
+        properties
+            .iter()
+            .map(|(key, value)| (key.to_string(), value_json(value)))
+            .collect(),
+    )
+}
+
+/// Converts a value to plain JSON; types JSON lacks print as strings.
+fn value_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Bool(b) => serde_json::Value::Bool(*b),
+        Value::Int64(i) => serde_json::Value::from(*i),
+        // NaN and infinities have no JSON form
+        Value::Float64(f) => serde_json::Number::from_f64(*f)
+            .map_or(serde_json::Value::Null, serde_json::Value::Number),
+        Value::String(s) => serde_json::Value::String(s.to_string()),
+        Value::List(items) => serde_json::Value::Array(items.iter().map(value_json).collect()),
+        Value::Map(entries) => properties_json(entries),
+        Value::Bytes(_) | Value::Timestamp(_) | Value::Date(_) | Value::Duration(_) => {
+            serde_json::Value::String(value.to_string())
+        }
+    }
+}
@@ -4,7 +4,7 @@ use std::path::Path;
 
 use anyhow::Result;
 use comfy_table::Cell;
-use grafeo_engine::{GrafeoDB, SchemaInfo};
+use grafeo_engine::{GrafeoDB, PropertyInfo, SchemaInfo};
 use serde::Serialize;
 
 use crate::OutputFormat;
@@ -24,6 +24,7 @@ struct LpgSchemaOutput {
 struct LabelOutput {
     name: String,
     count: usize,
+    properties: Vec<PropertyOutput>,
 }
 
 /// Edge type information.
@@ -31,6 +32,49 @@ struct LabelOutput {
 struct EdgeTypeOutput {
     name: String,
     count: usize,
+    endpoints: Vec<(String, String)>,
+    properties: Vec<PropertyOutput>,
+}
+
+/// Property key information.
+#[derive(Serialize)]
+struct PropertyOutput {
+    name: String,
+    #[serde(rename = "type")]
+    data_type: String,
+    count: usize,
+    unique: bool,
+    required: bool,
+}
+
+impl From<&PropertyInfo> for PropertyOutput {
+    fn from(property: &PropertyInfo) -> Self {
+        Self {
+            name: property.name.clone(),
+            data_type: property.data_type.to_string(),
+            count: property.count,
+            unique: property.unique,
+            required: property.required,
+        }
+    }
+}
+
+/// Formats properties as `name: TYPE` entries, one per line.
+fn property_cell(properties: &[PropertyOutput]) -> String {
+    properties
+        .iter()
+        .map(|p| {
+            let mut entry = format!("{}: {}", p.name, p.data_type);
+            if p.unique {
+                entry.push_str(" UNIQUE");
+            }
+            if p.required {
+                entry.push_str(" NOT NULL");
+            }
+            entry
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 /// Schema output for RDF mode.
@@ -67,6 +111,7 @@ pub fn run(path: &Path, format: OutputFormat, quiet: bool) -> Result<()> {
                     .map(|l| LabelOutput {
                         name: l.name.clone(),
                         count: l.count,
+                        properties: l.properties.iter().map(PropertyOutput::from).collect(),
                     })
                     .collect(),
                 edge_types: lpg
@@ -75,6 +120,8 @@ pub fn run(path: &Path, format: OutputFormat, quiet: bool) -> Result<()> {
                     .map(|e| EdgeTypeOutput {
                         name: e.name.clone(),
                         count: e.count,
+                        endpoints: e.endpoints.clone(),
+                        properties: e.properties.iter().map(PropertyOutput::from).collect(),
                     })
                     .collect(),
                 property_keys: lpg.property_keys,
@@ -92,19 +139,34 @@ pub fn run(path: &Path, format: OutputFormat, quiet: bool) -> Result<()> {
 
                         // Labels table
                         let mut table = output::create_table();
-                        output::add_header(&mut table, &["Label", "Count"]);
+                        output::add_header(&mut table, &["Label", "Count", "Properties"]);
                         for label in &output.labels {
-                            table.add_row(vec![Cell::new(&label.name), Cell::new(label.count)]);
+                            table.add_row(vec![
+                                Cell::new(&label.name),
+                                Cell::new(label.count),
+                                Cell::new(property_cell(&label.properties)),
+                            ]);
                         }
                         println!("{table}\n");
 
                         // Edge types table
                         let mut table = output::create_table();
-                        output::add_header(&mut table, &["Edge Type", "Count"]);
+                        output::add_header(
+                            &mut table,
+                            &["Edge Type", "Count", "Endpoints", "Properties"],
+                        );
                         for edge_type in &output.edge_types {
+                            let endpoints = edge_type
+                                .endpoints
+                                .iter()
+                                .map(|(source, target)| format!("(:{source})->(:{target})"))
+                                .collect::<Vec<_>>()
+                                .join("\n");
                             table.add_row(vec![
                                 Cell::new(&edge_type.name),
                                 Cell::new(edge_type.count),
+                                Cell::new(endpoints),
+                                Cell::new(property_cell(&edge_type.properties)),
                             ]);
                         }
                         println!("{table}\n");
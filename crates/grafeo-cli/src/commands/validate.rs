@@ -1,5 +1,6 @@
 //! Database validation command.
 
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use anyhow::Result;
@@ -16,6 +17,8 @@ struct ValidationOutput {
     valid: bool,
     error_count: usize,
     warning_count: usize,
+    /// Number of errors per error code.
+    counts: BTreeMap<String, usize>,
     errors: Vec<ErrorOutput>,
     warnings: Vec<WarningOutput>,
 }
@@ -37,14 +40,23 @@ struct WarningOutput {
 }
 
 /// Run the validate command.
-pub fn run(path: &Path, format: OutputFormat, quiet: bool) -> Result<()> {
+///
+/// The table lists how many problems of each kind were found; `verbose`
+/// adds a row per problem with the offending ids. JSON always has both.
+pub fn run(path: &Path, format: OutputFormat, verbose: bool, quiet: bool) -> Result<()> {
     let db = GrafeoDB::open(path)?;
     let result = db.validate();
 
+    let mut counts = BTreeMap::new();
+    for error in &result.errors {
+        *counts.entry(error.code.clone()).or_insert(0) += 1;
+    }
+
     let output = ValidationOutput {
         valid: result.errors.is_empty(),
         error_count: result.errors.len(),
         warning_count: result.warnings.len(),
+        counts,
         errors: result
             .errors
             .iter()
@@ -85,7 +97,16 @@ pub fn run(path: &Path, format: OutputFormat, quiet: bool) -> Result<()> {
                     output.error_count, output.warning_count
                 );
 
-                if !output.errors.is_empty() {
+                if !output.counts.is_empty() {
+                    let mut table = output::create_table();
+                    output::add_header(&mut table, &["Code", "Count"]);
+                    for (code, count) in &output.counts {
+                        table.add_row(vec![Cell::new(code).fg(Color::Red), Cell::new(count)]);
+                    }
+                    println!("{table}\n");
+                }
+
+                if verbose && !output.errors.is_empty() {
                     let mut table = output::create_table();
                     output::add_header(&mut table, &["Code", "Message", "Context"]);
                     for error in &output.errors {
@@ -96,6 +117,8 @@ pub fn run(path: &Path, format: OutputFormat, quiet: bool) -> Result<()> {
                         ]);
                     }
                     println!("Errors:\n{table}\n");
+                } else if !output.errors.is_empty() {
+                    println!("Run with --verbose to list the offending ids.\n");
                 }
 
                 if !output.warnings.is_empty() {
@@ -1,7 +1,8 @@
 //! WAL management commands.
 
 use anyhow::Result;
-use grafeo_engine::GrafeoDB;
+use grafeo_common::types::{Timestamp, TxId};
+use grafeo_engine::{GrafeoDB, RecoveryTarget};
 use serde::Serialize;
 
 use crate::output::{self, Format};
@@ -19,6 +20,14 @@ struct WalStatusOutput {
     current_epoch: u64,
 }
 
+/// WAL replay output.
+#[derive(Serialize)]
+struct WalReplayOutput {
+    target: String,
+    node_count: usize,
+    edge_count: usize,
+}
+
 /// Format bytes as human-readable string.
 fn format_bytes(bytes: usize) -> String {
     const KB: usize = 1024;
@@ -90,6 +99,44 @@ pub fn run(cmd: WalCommands, format: OutputFormat, quiet: bool) -> Result<()> {
 
             output::success("WAL checkpoint completed", quiet);
         }
+        WalCommands::Replay {
+            path,
+            until,
+            until_tx,
+        } => {
+            let target = match (until, until_tx) {
+                (_, Some(tx)) => RecoveryTarget::Transaction(TxId::new(tx)),
+                (Some(ms), None) => RecoveryTarget::Time(Timestamp::from_millis(ms)),
+                (None, None) => anyhow::bail!("either --until or --until-tx is required"),
+            };
+            let db = GrafeoDB::open_until(&path, target)?;
+
+            let output = WalReplayOutput {
+                target: match target {
+                    RecoveryTarget::Time(ts) => format!("time {} ms", ts.as_millis()),
+                    RecoveryTarget::Transaction(tx) => format!("transaction {}", tx.as_u64()),
+                },
+                node_count: db.node_count(),
+                edge_count: db.edge_count(),
+            };
+
+            let fmt: Format = format.into();
+            match fmt {
+                Format::Json => {
+                    if !quiet {
+                        println!("{}", serde_json::to_string_pretty(&output)?);
+                    }
+                }
+                Format::Table => {
+                    let items = vec![
+                        ("Target", output.target),
+                        ("Nodes", output.node_count.to_string()),
+                        ("Edges", output.edge_count.to_string()),
+                    ];
+                    output::print_key_value_table(&items, fmt, quiet);
+                }
+            }
+        }
     }
 
     Ok(())
@@ -0,0 +1,423 @@
+//! Parquet dumps for `grafeo data dump` and `grafeo data load`.
+//!
+//! A dump is a directory with a `metadata.json` file and one Parquet file per
+//! label set (for nodes) and per edge type (for edges). Every file has a
+//! column per property key next to a few reserved columns:
+//!
+//! | Column | Files | Contents |
+//! | ------ | ----- | -------- |
+//! | `:id` | nodes, edges | Original node or edge ID |
+//! | `:labels` | nodes | List of the node's labels, in the node's order |
+//! | `:type` | edges | Edge type |
+//! | `:src`, `:dst` | edges | Source and destination node IDs |
+//!
+//! Property columns use a native Parquet type when every value in the file
+//! has the same scalar type. Anything else - lists, maps, timestamps, mixed
+//! types, or explicit nulls - is stored with [`Value::serialize`] so it
+//! reads back exactly. A missing property is a Parquet null either way.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result, bail};
+use arrow_array::builder::{ListBuilder, StringBuilder};
+use arrow_array::{
+    Array, ArrayRef, BinaryArray, BooleanArray, Float64Array, Int64Array, ListArray, RecordBatch,
+    StringArray, UInt64Array,
+};
+use arrow_schema::{DataType, Field, Schema};
+use grafeo_common::types::{EdgeId, NodeId, PropertyKey, Timestamp, Value};
+use grafeo_engine::{DatabaseMode, DumpFormat, DumpMetadata, GrafeoDB};
+use parquet::arrow::ArrowWriter;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+/// Name of the metadata file in a dump directory.
+pub const METADATA_FILE: &str = "metadata.json";
+
+const ID_COLUMN: &str = ":id";
+const LABELS_COLUMN: &str = ":labels";
+const TYPE_COLUMN: &str = ":type";
+const SRC_COLUMN: &str = ":src";
+const DST_COLUMN: &str = ":dst";
+
+/// Field metadata key marking columns that hold serialized values.
+const ENCODING_KEY: &str = "grafeo:encoding";
+const VALUE_ENCODING: &str = "value";
+
+/// How a property column is stored.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Bool,
+    Int64,
+    Float64,
+    String,
+    Bytes,
+    /// Serialized [`Value`]s, for everything without a native column type.
+    Value,
+}
+
+impl ColumnKind {
+    /// Picks the native kind for a value, if it has one.
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Bool(_) => Self::Bool,
+            Value::Int64(_) => Self::Int64,
+            Value::Float64(_) => Self::Float64,
+            Value::String(_) => Self::String,
+            Value::Bytes(_) => Self::Bytes,
+            _ => Self::Value,
+        }
+    }
+
+    /// Picks the narrowest kind that stores every present value.
+    fn infer<'a>(values: impl Iterator<Item = Option<&'a Value>>) -> Self {
+        let mut kind = None;
+        for value in values.flatten() {
+            let value_kind = Self::of(value);
+            match kind {
+                None => kind = Some(value_kind),
+                Some(current) if current != value_kind => return Self::Value,
+                Some(_) => {}
+            }
+        }
+        kind.unwrap_or(Self::Value)
+    }
+
+    fn field(self, name: &str) -> Field {
+        let data_type = match self {
+            Self::Bool => DataType::Boolean,
+            Self::Int64 => DataType::Int64,
+            Self::Float64 => DataType::Float64,
+            Self::String => DataType::Utf8,
+            Self::Bytes | Self::Value => DataType::Binary,
+        };
+        let field = Field::new(name, data_type, true);
+        if self == Self::Value {
+            let metadata = [(ENCODING_KEY.to_string(), VALUE_ENCODING.to_string())];
+            field.with_metadata(metadata.into_iter().collect())
+        } else {
+            field
+        }
+    }
+
+    fn build(self, values: &[Option<&Value>]) -> ArrayRef {
+        match self {
+            Self::Bool => Arc::new(
+                values
+                    .iter()
+                    .map(|v| v.and_then(Value::as_bool))
+                    .collect::<BooleanArray>(),
+            ),
+            Self::Int64 => Arc::new(
+                values
+                    .iter()
+                    .map(|v| v.and_then(Value::as_int64))
+                    .collect::<Int64Array>(),
+            ),
+            Self::Float64 => Arc::new(
+                values
+                    .iter()
+                    .map(|v| v.and_then(Value::as_float64))
+                    .collect::<Float64Array>(),
+            ),
+            Self::String => Arc::new(
+                values
+                    .iter()
+                    .map(|v| v.and_then(Value::as_str))
+                    .collect::<StringArray>(),
+            ),
+            Self::Bytes => Arc::new(
+                values
+                    .iter()
+                    .map(|v| v.and_then(Value::as_bytes))
+                    .collect::<BinaryArray>(),
+            ),
+            Self::Value => {
+                let encoded: Vec<Option<Vec<u8>>> =
+                    values.iter().map(|v| v.map(Value::serialize)).collect();
+                Arc::new(
+                    encoded
+                        .iter()
+                        .map(Option::as_deref)
+                        .collect::<BinaryArray>(),
+                )
+            }
+        }
+    }
+}
+
+/// A group of rows written to one Parquet file.
+struct Table<'a> {
+    /// Reserved columns, in order.
+    columns: Vec<(Field, ArrayRef)>,
+    /// Properties of each row.
+    properties: Vec<&'a BTreeMap<PropertyKey, Value>>,
+}
+
+impl Table<'_> {
+    fn write(self, path: &Path) -> Result<()> {
+        let keys: BTreeSet<&PropertyKey> = self
+            .properties
+            .iter()
+            .flat_map(|properties| properties.keys())
+            .collect();
+
+        let mut fields = Vec::new();
+        let mut arrays = Vec::new();
+        for (field, array) in self.columns {
+            fields.push(field);
+            arrays.push(array);
+        }
+        for key in keys {
+            if key.as_str().starts_with(':') {
+                bail!(
+                    "property '{}' clashes with a reserved dump column",
+                    key.as_str()
+                );
+            }
+            let values: Vec<Option<&Value>> = self
+                .properties
+                .iter()
+                .map(|properties| properties.get(key))
+                .collect();
+            let kind = ColumnKind::infer(values.iter().copied());
+            fields.push(kind.field(key.as_str()));
+            arrays.push(kind.build(&values));
+        }
+
+        let schema = Arc::new(Schema::new(fields));
+        let batch = RecordBatch::try_new(Arc::clone(&schema), arrays)?;
+        let file =
+            File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let mut writer = ArrowWriter::try_new(file, schema, None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(())
+    }
+}
+
+/// Turns a label or edge type into something safe for a file name.
+fn file_name(kind: &str, index: usize, name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{kind}_{index:04}_{name}.parquet")
+}
+
+/// Writes every node and edge of a database as a Parquet dump in `dir`.
+pub fn write(db: &GrafeoDB, dir: &Path) -> Result<DumpMetadata> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    // Group nodes by their (sorted) label set; each row keeps its own order
+    let nodes: Vec<_> = db.iter_nodes().collect();
+    let mut node_groups: BTreeMap<Vec<&str>, Vec<usize>> = BTreeMap::new();
+    for (i, node) in nodes.iter().enumerate() {
+        let mut labels: Vec<&str> = node.labels.iter().map(AsRef::as_ref).collect();
+        labels.sort_unstable();
+        node_groups.entry(labels).or_default().push(i);
+    }
+    for (index, (labels, rows)) in node_groups.iter().enumerate() {
+        let ids: UInt64Array = rows.iter().map(|&i| nodes[i].id.as_u64()).collect();
+        let mut label_lists = ListBuilder::new(StringBuilder::new());
+        for &i in rows {
+            for label in &nodes[i].labels {
+                label_lists.values().append_value(label);
+            }
+            label_lists.append(true);
+        }
+        let label_lists = label_lists.finish();
+        let labels_field = Field::new(LABELS_COLUMN, label_lists.data_type().clone(), false);
+
+        let table = Table {
+            columns: vec![
+                (
+                    Field::new(ID_COLUMN, DataType::UInt64, false),
+                    Arc::new(ids),
+                ),
+                (labels_field, Arc::new(label_lists)),
+            ],
+            properties: rows.iter().map(|&i| &nodes[i].properties).collect(),
+        };
+        table.write(&dir.join(file_name("nodes", index, &labels.join("_"))))?;
+    }
+
+    // Group edges by type
+    let edges: Vec<_> = db.iter_edges().collect();
+    let mut edge_groups: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, edge) in edges.iter().enumerate() {
+        edge_groups.entry(&edge.edge_type).or_default().push(i);
+    }
+    for (index, (edge_type, rows)) in edge_groups.iter().enumerate() {
+        let ids: UInt64Array = rows.iter().map(|&i| edges[i].id.as_u64()).collect();
+        let types: StringArray = rows.iter().map(|_| Some(*edge_type)).collect();
+        let srcs: UInt64Array = rows.iter().map(|&i| edges[i].src.as_u64()).collect();
+        let dsts: UInt64Array = rows.iter().map(|&i| edges[i].dst.as_u64()).collect();
+
+        let table = Table {
+            columns: vec![
+                (
+                    Field::new(ID_COLUMN, DataType::UInt64, false),
+                    Arc::new(ids),
+                ),
+                (
+                    Field::new(TYPE_COLUMN, DataType::Utf8, false),
+                    Arc::new(types),
+                ),
+                (
+                    Field::new(SRC_COLUMN, DataType::UInt64, false),
+                    Arc::new(srcs),
+                ),
+                (
+                    Field::new(DST_COLUMN, DataType::UInt64, false),
+                    Arc::new(dsts),
+                ),
+            ],
+            properties: rows.iter().map(|&i| &edges[i].properties).collect(),
+        };
+        table.write(&dir.join(file_name("edges", index, edge_type)))?;
+    }
+
+    let metadata = DumpMetadata {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        mode: DatabaseMode::Lpg,
+        format: DumpFormat::Parquet,
+        node_count: nodes.len(),
+        edge_count: edges.len(),
+        created_at: Timestamp::now().to_string(),
+        extra: Default::default(),
+    };
+    let file = File::create(dir.join(METADATA_FILE))?;
+    serde_json::to_writer_pretty(file, &metadata)?;
+    Ok(metadata)
+}
+
+/// Reads a Parquet dump from `dir` into a new in-memory database.
+///
+/// Nodes and edges keep the IDs they had when the dump was written.
+pub fn read(dir: &Path) -> Result<GrafeoDB> {
+    let db = GrafeoDB::new_in_memory();
+    let store = db.store();
+
+    let mut files: Vec<_> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<_>>()?;
+    files.retain(|path| path.extension().is_some_and(|ext| ext == "parquet"));
+    files.sort();
+
+    // Nodes first, so every edge finds its endpoints
+    let (node_files, edge_files): (Vec<_>, Vec<_>) = files.into_iter().partition(|path| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("nodes_"))
+    });
+
+    for path in node_files {
+        for batch in read_batches(&path)? {
+            let ids = column::<UInt64Array>(&batch, ID_COLUMN)?;
+            let labels = column::<ListArray>(&batch, LABELS_COLUMN)?;
+            for row in 0..batch.num_rows() {
+                let id = NodeId::new(ids.value(row));
+                let row_labels = labels.value(row);
+                let row_labels = row_labels
+                    .as_any()
+                    .downcast_ref::<StringArray>()
+                    .context("node labels must be strings")?;
+                let row_labels: Vec<&str> = row_labels.iter().flatten().collect();
+                store.create_node_with_id(id, &row_labels);
+                for (key, value) in row_properties(&batch, row)? {
+                    store.set_node_property(id, &key, value);
+                }
+            }
+        }
+    }
+
+    for path in edge_files {
+        for batch in read_batches(&path)? {
+            let ids = column::<UInt64Array>(&batch, ID_COLUMN)?;
+            let types = column::<StringArray>(&batch, TYPE_COLUMN)?;
+            let srcs = column::<UInt64Array>(&batch, SRC_COLUMN)?;
+            let dsts = column::<UInt64Array>(&batch, DST_COLUMN)?;
+            for row in 0..batch.num_rows() {
+                let id = EdgeId::new(ids.value(row));
+                store.create_edge_with_id(
+                    id,
+                    NodeId::new(srcs.value(row)),
+                    NodeId::new(dsts.value(row)),
+                    types.value(row),
+                );
+                for (key, value) in row_properties(&batch, row)? {
+                    store.set_edge_property(id, &key, value);
+                }
+            }
+        }
+    }
+
+    Ok(db)
+}
+
+/// Reads the metadata file of a dump, if `dir` holds one.
+pub fn read_metadata(dir: &Path) -> Result<Option<DumpMetadata>> {
+    let path = dir.join(METADATA_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let file = File::open(&path)?;
+    let metadata = serde_json::from_reader(file)
+        .with_context(|| format!("Invalid dump metadata in {}", path.display()))?;
+    Ok(Some(metadata))
+}
+
+fn read_batches(path: &Path) -> Result<Vec<RecordBatch>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)?.build()?;
+    let batches = reader.collect::<std::result::Result<_, _>>()?;
+    Ok(batches)
+}
+
+fn column<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> Result<&'a T> {
+    batch
+        .column_by_name(name)
+        .and_then(|array| array.as_any().downcast_ref::<T>())
+        .with_context(|| format!("dump file is missing a valid '{name}' column"))
+}
+
+/// Decodes the properties stored in one row, skipping missing ones.
+fn row_properties(batch: &RecordBatch, row: usize) -> Result<Vec<(String, Value)>> {
+    let schema = batch.schema();
+    let mut properties = Vec::new();
+    for (field, array) in schema.fields().iter().zip(batch.columns()) {
+        if field.name().starts_with(':') || array.is_null(row) {
+            continue;
+        }
+        let serialized = field
+            .metadata()
+            .get(ENCODING_KEY)
+            .is_some_and(|encoding| encoding == VALUE_ENCODING);
+        let any = array.as_any();
+        let value = match field.data_type() {
+            DataType::Boolean => downcast::<BooleanArray>(any)?.value(row).into(),
+            DataType::Int64 => downcast::<Int64Array>(any)?.value(row).into(),
+            DataType::Float64 => downcast::<Float64Array>(any)?.value(row).into(),
+            DataType::Utf8 => downcast::<StringArray>(any)?.value(row).into(),
+            DataType::Binary if serialized => {
+                let bytes = downcast::<BinaryArray>(any)?.value(row);
+                Value::deserialize(bytes)
+                    .with_context(|| format!("corrupt value in column '{}'", field.name()))?
+            }
+            DataType::Binary => downcast::<BinaryArray>(any)?.value(row).into(),
+            other => bail!("unsupported column type {other} for '{}'", field.name()),
+        };
+        properties.push((field.name().clone(), value));
+    }
+    Ok(properties)
+}
+
+fn downcast<T: 'static>(array: &dyn std::any::Any) -> Result<&T> {
+    array
+        .downcast_ref::<T>()
+        .context("column type does not match its schema")
+}
@@ -0,0 +1,256 @@
+//! GraphML export for `grafeo data dump --export-format graphml`.
+//!
+//! Writes a single `.graphml` file that Gephi, yEd, and NetworkX can open.
+//! Every property key in the schema gets a `<key>` declaration for each
+//! domain (node or edge) it appears in, even when only some elements have
+//! it. Labels and edge types are written as the reserved `:labels` and
+//! `:type` attributes, matching the column names of Parquet dumps.
+//!
+//! Attribute types map as follows; a key whose values have more than one
+//! type, or a type GraphML lacks, is declared as `string`:
+//!
+//! | Value | `attr.type` |
+//! | ----- | ----------- |
+//! | `Bool` | `boolean` |
+//! | `Int64` | `long` |
+//! | `Float64` | `double` |
+//! | anything else | `string` |
+//!
+//! `Int64` maps to `long` rather than `int`, which is 32 bits in GraphML.
+//! Null properties are left out, since GraphML has no null.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use grafeo_common::types::{PropertyKey, Timestamp, Value};
+use grafeo_engine::{DatabaseMode, DumpFormat, DumpMetadata, GrafeoDB, SchemaInfo};
+
+const LABELS_ATTR: &str = ":labels";
+const TYPE_ATTR: &str = ":type";
+
+/// GraphML type of a declared key.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AttrType {
+    Boolean,
+    Long,
+    Double,
+    String,
+}
+
+impl AttrType {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Bool(_) => Self::Boolean,
+            Value::Int64(_) => Self::Long,
+            Value::Float64(_) => Self::Double,
+            _ => Self::String,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Boolean => "boolean",
+            Self::Long => "long",
+            Self::Double => "double",
+            Self::String => "string",
+        }
+    }
+}
+
+/// Keys declared for one domain.
+struct Keys {
+    /// Key ID prefix, `n` for nodes and `e` for edges.
+    prefix: &'static str,
+    /// Attribute names and types, in declaration order.
+    declared: Vec<(String, AttrType)>,
+    /// Position of each attribute name in `declared`.
+    index: HashMap<String, usize>,
+}
+
+impl Keys {
+    /// Declares every property in `names` that appears on some element.
+    fn collect<'a>(
+        prefix: &'static str,
+        names: &[String],
+        elements: impl Iterator<Item = &'a BTreeMap<PropertyKey, Value>>,
+    ) -> Self {
+        let mut types: BTreeMap<&str, AttrType> = BTreeMap::new();
+        for properties in elements {
+            for (key, value) in properties {
+                if value.is_null() {
+                    continue;
+                }
+                let value_type = AttrType::of(value);
+                types
+                    .entry(key.as_str())
+                    .and_modify(|ty| {
+                        if *ty != value_type {
+                            *ty = AttrType::String;
+                        }
+                    })
+                    .or_insert(value_type);
+            }
+        }
+
+        // Schema keys first, then anything the schema missed
+        let known: BTreeSet<&str> = names.iter().map(String::as_str).collect();
+        let declared: Vec<(String, AttrType)> = names
+            .iter()
+            .map(String::as_str)
+            .chain(types.keys().copied().filter(|key| !known.contains(key)))
+            .filter_map(|name| types.get(name).map(|ty| (name.to_string(), *ty)))
+            .collect();
+        let index = declared
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| (name.clone(), i))
+            .collect();
+        Self {
+            prefix,
+            declared,
+            index,
+        }
+    }
+
+    fn declare(&self, out: &mut impl Write, domain: &str) -> Result<()> {
+        for (i, (name, ty)) in self.declared.iter().enumerate() {
+            writeln!(
+                out,
+                "  <key id=\"{}{i}\" for=\"{domain}\" attr.name=\"{}\" attr.type=\"{}\"/>",
+                self.prefix,
+                escape(name),
+                ty.name()
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_data(
+        &self,
+        out: &mut impl Write,
+        properties: &BTreeMap<PropertyKey, Value>,
+    ) -> Result<()> {
+        for (key, value) in properties {
+            if value.is_null() {
+                continue;
+            }
+            if let Some(&i) = self.index.get(key.as_str()) {
+                writeln!(
+                    out,
+                    "      <data key=\"{}{i}\">{}</data>",
+                    self.prefix,
+                    escape(&format_value(value, self.declared[i].1))
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats a value as GraphML text for a key of type `ty`.
+fn format_value(value: &Value, ty: AttrType) -> String {
+    match (value, ty) {
+        (Value::Float64(f), AttrType::Double) if f.is_infinite() => {
+            if *f > 0.0 { "INF" } else { "-INF" }.to_string()
+        }
+        (Value::String(s), _) => s.to_string(),
+        _ => value.to_string(),
+    }
+}
+
+/// Escapes text for use in XML content and attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes every node and edge of a database as a GraphML file at `path`.
+pub fn write(db: &GrafeoDB, path: &Path) -> Result<DumpMetadata> {
+    let property_keys = match db.schema() {
+        SchemaInfo::Lpg(schema) => schema.property_keys,
+        SchemaInfo::Rdf(_) => Vec::new(),
+    };
+    let nodes: Vec<_> = db.iter_nodes().collect();
+    let edges: Vec<_> = db.iter_edges().collect();
+    let node_keys = Keys::collect("n", &property_keys, nodes.iter().map(|n| &n.properties));
+    let edge_keys = Keys::collect("e", &property_keys, edges.iter().map(|e| &e.properties));
+
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut out = BufWriter::new(file);
+    writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(
+        out,
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" \
+         xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+         xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns \
+         http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">"
+    )?;
+    writeln!(
+        out,
+        "  <key id=\"labels\" for=\"node\" attr.name=\"{LABELS_ATTR}\" attr.type=\"string\"/>"
+    )?;
+    writeln!(
+        out,
+        "  <key id=\"type\" for=\"edge\" attr.name=\"{TYPE_ATTR}\" attr.type=\"string\"/>"
+    )?;
+    node_keys.declare(&mut out, "node")?;
+    edge_keys.declare(&mut out, "edge")?;
+
+    writeln!(out, "  <graph id=\"G\" edgedefault=\"directed\">")?;
+    for node in &nodes {
+        writeln!(out, "    <node id=\"n{}\">", node.id.as_u64())?;
+        if !node.labels.is_empty() {
+            let labels: Vec<&str> = node.labels.iter().map(AsRef::as_ref).collect();
+            writeln!(
+                out,
+                "      <data key=\"labels\">{}</data>",
+                escape(&format!(":{}", labels.join(":")))
+            )?;
+        }
+        node_keys.write_data(&mut out, &node.properties)?;
+        writeln!(out, "    </node>")?;
+    }
+    for edge in &edges {
+        writeln!(
+            out,
+            "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">",
+            edge.id.as_u64(),
+            edge.src.as_u64(),
+            edge.dst.as_u64()
+        )?;
+        writeln!(
+            out,
+            "      <data key=\"type\">{}</data>",
+            escape(&edge.edge_type)
+        )?;
+        edge_keys.write_data(&mut out, &edge.properties)?;
+        writeln!(out, "    </edge>")?;
+    }
+    writeln!(out, "  </graph>")?;
+    writeln!(out, "</graphml>")?;
+    out.flush()?;
+
+    Ok(DumpMetadata {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        mode: DatabaseMode::Lpg,
+        format: DumpFormat::GraphMl,
+        node_count: nodes.len(),
+        edge_count: edges.len(),
+        created_at: Timestamp::now().to_string(),
+        extra: Default::default(),
+    })
+}
@@ -1,9 +1,12 @@
 //! Grafeo CLI - Admin tool for Grafeo graph databases.
 //!
 //! A focused admin CLI for operators and DevOps. The query API is for building
-//! applications; the CLI is for inspection, backup, and maintenance.
+//! applications; the CLI is for inspection, backup, and maintenance, plus
+//! quick read-only queries.
 
 mod commands;
+mod dump;
+mod graphml;
 mod output;
 
 use clap::{Parser, Subcommand, ValueEnum};
@@ -28,7 +31,7 @@ struct Cli {
     #[arg(long, short, global = true)]
     quiet: bool,
 
-    /// Enable verbose debug logging
+    /// Enable verbose output and debug logging
     #[arg(long, short, global = true)]
     verbose: bool,
 }
@@ -58,6 +61,12 @@ enum Commands {
         path: PathBuf,
     },
 
+    /// Rebuild optimizer statistics (counts and histograms)
+    Analyze {
+        /// Path to the database
+        path: PathBuf,
+    },
+
     /// Display schema information (labels, edge types, property keys)
     Schema {
         /// Path to the database
@@ -70,6 +79,16 @@ enum Commands {
         path: PathBuf,
     },
 
+    /// Run a read-only GQL query and print the results
+    #[cfg(feature = "gql")]
+    Query {
+        /// Path to the database
+        path: PathBuf,
+
+        /// The query, e.g. "MATCH (p:Person) RETURN p.name"
+        query: String,
+    },
+
     /// Manage indexes
     #[command(subcommand)]
     Index(IndexCommands),
@@ -152,9 +171,12 @@ enum DataCommands {
         #[arg(short, long)]
         output: PathBuf,
 
-        /// Export format (parquet, turtle, json)
-        #[arg(long)]
-        format: Option<String>,
+        /// Export format (parquet, graphml, turtle, json)
+        ///
+        /// Named apart from the global `--format`, which picks how results
+        /// are printed.
+        #[arg(long = "export-format", value_name = "FORMAT")]
+        export_format: Option<String>,
     },
 
     /// Import data from a dump
@@ -181,6 +203,24 @@ enum WalCommands {
         /// Path to the database
         path: PathBuf,
     },
+
+    /// Replay the WAL up to a point in time, without modifying the database
+    Replay {
+        /// Path to the database
+        path: PathBuf,
+
+        /// Stop after commits made at or before this time (milliseconds since the Unix epoch)
+        #[arg(
+            long,
+            required_unless_present = "until_tx",
+            conflicts_with = "until_tx"
+        )]
+        until: Option<i64>,
+
+        /// Stop after this transaction commits
+        #[arg(long)]
+        until_tx: Option<u64>,
+    },
 }
 
 fn main() {
@@ -200,8 +240,15 @@ fn main() {
     let result = match cli.command {
         Commands::Info { path } => commands::info::run(&path, cli.format, cli.quiet),
         Commands::Stats { path } => commands::stats::run(&path, cli.format, cli.quiet),
+        Commands::Analyze { path } => commands::analyze::run(&path, cli.format, cli.quiet),
         Commands::Schema { path } => commands::schema::run(&path, cli.format, cli.quiet),
-        Commands::Validate { path } => commands::validate::run(&path, cli.format, cli.quiet),
+        Commands::Validate { path } => {
+            commands::validate::run(&path, cli.format, cli.verbose, cli.quiet)
+        }
+        #[cfg(feature = "gql")]
+        Commands::Query { path, query } => {
+            commands::query::run(&path, &query, cli.format, cli.quiet)
+        }
         Commands::Index(cmd) => commands::index::run(cmd, cli.format, cli.quiet),
         Commands::Backup(cmd) => commands::backup::run(cmd, cli.format, cli.quiet),
         Commands::Data(cmd) => commands::data::run(cmd, cli.format, cli.quiet),
@@ -0,0 +1,129 @@
+//! Round-trip tests for `grafeo data dump` and `grafeo data load`.
+
+use std::path::Path;
+use std::process::Command;
+
+use grafeo_common::types::{Timestamp, Value};
+use grafeo_engine::GrafeoDB;
+
+fn grafeo(args: &[&str]) {
+    let status = Command::new(env!("CARGO_BIN_EXE_grafeo"))
+        .args(args)
+        .arg("--quiet")
+        .status()
+        .expect("failed to run grafeo");
+    assert!(status.success(), "grafeo {args:?} failed");
+}
+
+fn sorted_rows(db: &GrafeoDB, query: &str) -> Vec<String> {
+    let result = db.execute(query).unwrap();
+    let mut rows: Vec<String> = result.rows.iter().map(|row| format!("{row:?}")).collect();
+    rows.sort();
+    rows
+}
+
+fn create_source(path: &Path) {
+    let db = GrafeoDB::open(path).unwrap();
+    let alice = db.create_node_with_props(
+        &["Person"],
+        [
+            ("name", Value::from("Alice")),
+            ("age", Value::Int64(30)),
+            ("score", Value::Float64(4.5)),
+            ("active", Value::Bool(true)),
+            (
+                "tags",
+                Value::List(vec![Value::from("admin"), Value::Int64(7)].into()),
+            ),
+        ],
+    );
+    let bob = db.create_node_with_props(
+        &["Person", "Employee"],
+        [
+            ("name", Value::from("Bob")),
+            ("age", Value::Null),
+            (
+                "joined",
+                Value::Timestamp(Timestamp::from_secs(1_700_000_000)),
+            ),
+        ],
+    );
+    // Mixed types in one column, and a node without properties
+    let acme = db.create_node_with_props(&["Company"], [("founded", Value::Int64(1999))]);
+    db.create_node_with_props(&["Company"], [("founded", Value::from("unknown"))]);
+    db.create_node(&[]);
+
+    db.create_edge_with_props(alice, bob, "KNOWS", [("since", Value::Int64(2020))]);
+    db.create_edge(bob, acme, "WORKS_AT");
+    db.close().unwrap();
+}
+
+#[test]
+fn test_parquet_dump_and_load_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source");
+    let dump = dir.path().join("dump");
+    let target = dir.path().join("target");
+    create_source(&source);
+
+    let (source, dump, target) = (
+        source.to_str().unwrap(),
+        dump.to_str().unwrap(),
+        target.to_str().unwrap(),
+    );
+    grafeo(&[
+        "data",
+        "dump",
+        source,
+        "-o",
+        dump,
+        "--export-format",
+        "parquet",
+    ]);
+    assert!(Path::new(dump).join("metadata.json").is_file());
+    grafeo(&["data", "load", dump, target]);
+
+    let before = GrafeoDB::open(source).unwrap();
+    let after = GrafeoDB::open(target).unwrap();
+
+    // Same IDs, labels in the same order, and property values, types and
+    // nulls included
+    let mut nodes_before: Vec<_> = before
+        .iter_nodes()
+        .map(|n| (n.id, n.labels, n.properties))
+        .collect();
+    let mut nodes_after: Vec<_> = after
+        .iter_nodes()
+        .map(|n| (n.id, n.labels, n.properties))
+        .collect();
+    nodes_before.sort_by_key(|(id, _, _)| *id);
+    nodes_after.sort_by_key(|(id, _, _)| *id);
+    assert_eq!(nodes_before.len(), 5);
+    assert_eq!(nodes_after, nodes_before);
+
+    let mut edges_before: Vec<_> = before
+        .iter_edges()
+        .map(|e| (e.id, e.src, e.dst, e.edge_type, e.properties))
+        .collect();
+    let mut edges_after: Vec<_> = after
+        .iter_edges()
+        .map(|e| (e.id, e.src, e.dst, e.edge_type, e.properties))
+        .collect();
+    edges_before.sort_by_key(|(id, ..)| *id);
+    edges_after.sort_by_key(|(id, ..)| *id);
+    assert_eq!(edges_before.len(), 2);
+    assert_eq!(edges_after, edges_before);
+
+    for query in [
+        "MATCH (p:Person) RETURN p.name, p.age, p.score, p.active, p.tags, p.joined",
+        "MATCH (c:Company) RETURN c.founded",
+        "MATCH (a)-[k:KNOWS]->(b) RETURN a.name, k.since, b.name",
+        "MATCH (p:Person)-[:WORKS_AT]->(c) RETURN p.name, c.founded",
+    ] {
+        assert_eq!(
+            sorted_rows(&after, query),
+            sorted_rows(&before, query),
+            "{query}"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! Tests for `grafeo data dump --export-format graphml`.
+
+use std::process::Command;
+
+use grafeo_common::types::Value;
+use grafeo_engine::GrafeoDB;
+
+#[test]
+fn test_graphml_export() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source");
+    let out = dir.path().join("graph.graphml");
+
+    let db = GrafeoDB::open(&source).unwrap();
+    let alice = db.create_node_with_props(
+        &["Person"],
+        [
+            ("name", Value::from("Alice & <Co>")),
+            ("age", Value::Int64(30)),
+            ("score", Value::Float64(4.5)),
+        ],
+    );
+    // `active` only appears on one node, `age` is null here
+    let bob = db.create_node_with_props(
+        &["Person", "Employee"],
+        [
+            ("name", Value::from("Bob")),
+            ("active", Value::Bool(true)),
+            ("age", Value::Null),
+        ],
+    );
+    let acme = db.create_node(&["Company"]);
+    db.create_edge_with_props(alice, bob, "KNOWS", [("since", Value::Int64(2020))]);
+    db.create_edge(bob, acme, "WORKS_AT");
+    db.close().unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_grafeo"))
+        .args(["data", "dump"])
+        .arg(&source)
+        .arg("-o")
+        .arg(&out)
+        .args(["--export-format", "graphml", "--quiet"])
+        .status()
+        .expect("failed to run grafeo");
+    assert!(status.success());
+
+    let text = std::fs::read_to_string(&out).unwrap();
+    let doc = roxmltree::Document::parse(&text).expect("GraphML must be valid XML");
+    let count = |tag: &str| doc.descendants().filter(|n| n.has_tag_name(tag)).count();
+    assert_eq!(count("node"), 3);
+    assert_eq!(count("edge"), 2);
+
+    // Every property gets a typed key, including ones only some nodes have
+    let key_type = |domain: &str, name: &str| {
+        doc.descendants()
+            .find(|n| {
+                n.has_tag_name("key")
+                    && n.attribute("for") == Some(domain)
+                    && n.attribute("attr.name") == Some(name)
+            })
+            .and_then(|n| n.attribute("attr.type"))
+    };
+    assert_eq!(key_type("node", "name"), Some("string"));
+    assert_eq!(key_type("node", "age"), Some("long"));
+    assert_eq!(key_type("node", "score"), Some("double"));
+    assert_eq!(key_type("node", "active"), Some("boolean"));
+    assert_eq!(key_type("edge", "since"), Some("long"));
+    assert_eq!(key_type("edge", "name"), None);
+
+    let data: Vec<&str> = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("data"))
+        .filter_map(|n| n.text())
+        .collect();
+    assert!(data.contains(&"Alice & <Co>"));
+    assert!(data.contains(&":Employee:Person") || data.contains(&":Person:Employee"));
+    assert!(data.contains(&"WORKS_AT"));
+}
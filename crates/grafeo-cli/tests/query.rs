@@ -0,0 +1,116 @@
+//! Tests for `grafeo query`.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use grafeo_common::types::Value;
+use grafeo_engine::GrafeoDB;
+
+fn query(path: &Path, gql: &str, format: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_grafeo"))
+        .arg("query")
+        .arg(path)
+        .arg(gql)
+        .args(["--format", format, "--quiet"])
+        .output()
+        .expect("failed to run grafeo")
+}
+
+fn create_source(path: &Path) {
+    let db = GrafeoDB::open(path).unwrap();
+    let alice = db.create_node_with_props(
+        &["Person"],
+        [("name", Value::from("Alice")), ("age", Value::Int64(30))],
+    );
+    let bob = db.create_node_with_props(&["Person"], [("name", Value::from("Bob"))]);
+    db.create_edge_with_props(alice, bob, "KNOWS", [("since", Value::Int64(2020))]);
+    db.close().unwrap();
+}
+
+#[test]
+fn test_query_renders_table() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("db");
+    create_source(&path);
+
+    let output = query(
+        &path,
+        "MATCH (a:Person)-[k:KNOWS]->(b:Person) RETURN a, k, b.name",
+        "table",
+    );
+    assert!(output.status.success(), "{output:?}");
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.contains("b.name"), "{text}");
+    assert!(
+        text.contains(r#"(:Person {age: 30, name: "Alice"})"#),
+        "{text}"
+    );
+    assert!(text.contains("[:KNOWS {since: 2020}]"), "{text}");
+    assert!(text.contains(r#""Bob""#), "{text}");
+}
+
+#[test]
+fn test_query_renders_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("db");
+    create_source(&path);
+
+    let output = query(
+        &path,
+        "MATCH (p:Person) RETURN p.name, p ORDER BY p.name",
+        "json",
+    );
+    assert!(output.status.success(), "{output:?}");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["columns"], serde_json::json!(["p.name", "p"]));
+    let rows = json["rows"].as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0][0], "Alice");
+    assert_eq!(rows[0][1]["labels"], serde_json::json!(["Person"]));
+    assert_eq!(rows[0][1]["properties"]["age"], 30);
+}
+
+#[test]
+fn test_query_refuses_mutations() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("db");
+    create_source(&path);
+
+    let output = query(&path, "INSERT (:Person {name: 'Mallory'})", "table");
+    assert!(!output.status.success());
+
+    let db = GrafeoDB::open(&path).unwrap();
+    assert_eq!(db.node_count(), 2);
+}
+
+#[test]
+fn test_query_leaves_database_files_untouched() {
+    // Every file under `dir` with its contents
+    fn snapshot(dir: &Path) -> std::collections::BTreeMap<std::path::PathBuf, Vec<u8>> {
+        let mut files = std::collections::BTreeMap::new();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else {
+                    files.insert(path.clone(), std::fs::read(&path).unwrap());
+                }
+            }
+        }
+        files
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("db");
+    create_source(&path);
+    let before = snapshot(&path);
+
+    let output = query(&path, "MATCH (p:Person) RETURN p.name", "json");
+    assert!(output.status.success(), "{output:?}");
+    let output = query(&path, "INSERT (:Person {name: 'Mallory'})", "table");
+    assert!(!output.status.success());
+
+    assert_eq!(snapshot(&path), before);
+}
@@ -0,0 +1,48 @@
+//! Tests for `grafeo validate`.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use grafeo_engine::GrafeoDB;
+
+fn validate(path: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_grafeo"))
+        .arg("validate")
+        .arg(path)
+        .args(args)
+        .output()
+        .expect("failed to run grafeo")
+}
+
+#[test]
+fn test_validate_accepts_consistent_database() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("db");
+    let db = GrafeoDB::open(&path).unwrap();
+    let alice = db.create_node(&["Person"]);
+    let bob = db.create_node(&["Person"]);
+    db.create_edge(alice, bob, "KNOWS");
+    db.close().unwrap();
+
+    let output = validate(&path, &["--quiet"]);
+    assert!(output.status.success(), "{output:?}");
+}
+
+#[test]
+fn test_validate_flags_dangling_edge() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("db");
+    let db = GrafeoDB::open(&path).unwrap();
+    let alice = db.create_node(&["Person"]);
+    let bob = db.create_node(&["Person"]);
+    let knows = db.create_edge(alice, bob, "KNOWS");
+    // Removes the node but not its edges
+    db.delete_node(bob);
+    db.close().unwrap();
+
+    let output = validate(&path, &["--verbose"]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.contains("DANGLING_DST"), "{text}");
+    assert!(text.contains(&format!("edge:{}", knows.0)), "{text}");
+}
@@ -77,6 +77,9 @@ pub mod priorities {
     /// Spill staging buffers - evict first (already written to disk).
     pub const SPILL_STAGING: u8 = 10;
 
+    /// Spillable operator state - moving it to disk slows the query down.
+    pub const SPILLABLE_OPERATOR: u8 = 20;
+
     /// Cached query results - relatively cheap to recompute.
     pub const QUERY_CACHE: u8 = 30;
 
@@ -165,24 +165,14 @@ impl BufferManager {
     /// Attempts to allocate memory for the given region.
     ///
     /// Returns `None` if allocation would exceed the hard limit after
-    /// eviction attempts.
+    /// eviction and spilling attempts.
     pub fn try_allocate(
         self: &Arc<Self>,
         size: usize,
         region: MemoryRegion,
     ) -> Option<MemoryGrant> {
-        // Check if we can allocate
-        let current = self.allocated.load(Ordering::Relaxed);
-
-        if current + size > self.hard_limit {
-            // Try eviction first
-            self.run_eviction_cycle(true);
-
-            // Check again
-            let current = self.allocated.load(Ordering::Relaxed);
-            if current + size > self.hard_limit {
-                return None;
-            }
+        if !self.make_room(size) {
+            return None;
         }
 
         // Perform allocation
@@ -300,6 +290,56 @@ impl BufferManager {
             let aggressive = level >= PressureLevel::High;
             self.run_eviction_cycle(aggressive);
         }
+        if self.pressure_level().should_spill() {
+            self.run_spill_cycle();
+        }
+    }
+
+    /// Makes room for `size` more bytes under the hard limit, evicting and
+    /// then spilling as needed. Returns whether the bytes now fit.
+    fn make_room(&self, size: usize) -> bool {
+        let fits = || self.allocated.load(Ordering::Relaxed) + size <= self.hard_limit;
+        if fits() {
+            return true;
+        }
+
+        self.run_eviction_cycle(true);
+        if fits() {
+            return true;
+        }
+
+        self.run_spill_cycle();
+        fits()
+    }
+
+    /// Asks spillable consumers to move data to disk until usage drops to
+    /// the soft limit.
+    ///
+    /// Consumers are asked in eviction priority order (lowest first). One
+    /// that fails or frees too little doesn't stop the cycle; the next
+    /// consumer is asked for the rest.
+    fn run_spill_cycle(&self) -> usize {
+        let current = self.allocated.load(Ordering::Relaxed);
+        if current <= self.soft_limit {
+            return 0;
+        }
+        let to_free = current - self.soft_limit;
+
+        let consumers = self.consumers.read();
+        let mut sorted: Vec<_> = consumers.iter().filter(|c| c.can_spill()).collect();
+        sorted.sort_by_key(|c| c.eviction_priority());
+
+        let mut total_freed = 0;
+        for consumer in sorted {
+            if total_freed >= to_free {
+                break;
+            }
+            if let Ok(freed) = consumer.spill(to_free - total_freed) {
+                total_freed += freed;
+            }
+        }
+
+        total_freed
     }
 
     fn run_eviction_cycle(&self, aggressive: bool) -> usize {
@@ -355,16 +395,8 @@ impl GrantReleaser for BufferManager {
     }
 
     fn try_allocate_raw(&self, size: usize, region: MemoryRegion) -> bool {
-        let current = self.allocated.load(Ordering::Relaxed);
-
-        if current + size > self.hard_limit {
-            // Try eviction
-            self.run_eviction_cycle(true);
-
-            let current = self.allocated.load(Ordering::Relaxed);
-            if current + size > self.hard_limit {
-                return false;
-            }
+        if !self.make_room(size) {
+            return false;
         }
 
         self.allocated.fetch_add(size, Ordering::Relaxed);
@@ -382,7 +414,7 @@ impl Drop for BufferManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::memory::buffer::consumer::priorities;
+    use crate::memory::buffer::consumer::{SpillError, priorities};
     use std::sync::atomic::AtomicUsize;
 
     struct TestConsumer {
@@ -587,4 +619,81 @@ mod tests {
         let _g = manager.try_allocate(300, MemoryRegion::ExecutionBuffers);
         assert_eq!(manager.available(), 700);
     }
+
+    /// A consumer that can only free memory by spilling, logging each call.
+    struct SpillingConsumer {
+        name: String,
+        priority: u8,
+        usage: AtomicUsize,
+        fail: bool,
+        log: Arc<parking_lot::Mutex<Vec<String>>>,
+    }
+
+    impl MemoryConsumer for SpillingConsumer {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn memory_usage(&self) -> usize {
+            self.usage.load(Ordering::Relaxed)
+        }
+
+        fn eviction_priority(&self) -> u8 {
+            self.priority
+        }
+
+        fn region(&self) -> MemoryRegion {
+            MemoryRegion::ExecutionBuffers
+        }
+
+        fn evict(&self, _target_bytes: usize) -> usize {
+            0
+        }
+
+        fn can_spill(&self) -> bool {
+            true
+        }
+
+        fn spill(&self, target_bytes: usize) -> Result<usize, SpillError> {
+            self.log.lock().push(self.name.clone());
+            if self.fail {
+                return Err(SpillError::InsufficientDiskSpace);
+            }
+            let freed = target_bytes.min(self.usage.load(Ordering::Relaxed));
+            self.usage.fetch_sub(freed, Ordering::Relaxed);
+            Ok(freed)
+        }
+    }
+
+    #[test]
+    fn test_high_pressure_spills_lowest_priority_first() {
+        for low_fails in [false, true] {
+            let manager = BufferManager::with_budget(1000);
+            let log = Arc::new(parking_lot::Mutex::new(Vec::new()));
+            for (name, priority, fail) in [
+                ("index", priorities::INDEX_BUFFERS, false),
+                ("staging", priorities::SPILL_STAGING, low_fails),
+            ] {
+                manager.register_consumer(Arc::new(SpillingConsumer {
+                    name: name.to_string(),
+                    priority,
+                    usage: AtomicUsize::new(500),
+                    fail,
+                    log: Arc::clone(&log),
+                }));
+            }
+
+            // 900 of 1000 bytes crosses the High threshold (850)
+            let grant = manager.try_allocate(900, MemoryRegion::ExecutionBuffers);
+            assert!(grant.is_some());
+
+            if low_fails {
+                // The failed consumer doesn't stop the cycle
+                assert_eq!(*log.lock(), vec!["staging", "index"]);
+            } else {
+                // Freeing 200 bytes from the low-priority consumer was enough
+                assert_eq!(*log.lock(), vec!["staging"]);
+            }
+        }
+    }
 }
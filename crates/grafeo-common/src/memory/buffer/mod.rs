@@ -40,11 +40,9 @@
 //!     // Memory is automatically released when grant is dropped
 //! }
 //!
-//! // Check pressure level
-//! let level = manager.pressure_level();
-//! if level.should_spill() {
-//!     // Trigger spilling for spillable operators
-//! }
+//! // Spillable operators register as consumers; once pressure reaches High,
+//! // the manager calls their `spill()`, lowest eviction priority first
+//! manager.register_consumer(consumer);
 //! ```
 
 mod consumer;
@@ -18,6 +18,8 @@ pub struct VersionInfo {
     pub deleted_epoch: Option<EpochId>,
     /// The transaction that created this version.
     pub created_by: TxId,
+    /// The transaction that deleted this version, if it was deleted by one.
+    pub deleted_by: Option<TxId>,
 }
 
 impl VersionInfo {
@@ -28,6 +30,7 @@ impl VersionInfo {
             created_epoch,
             deleted_epoch: None,
             created_by,
+            deleted_by: None,
         }
     }
 
@@ -69,6 +72,39 @@ impl VersionInfo {
         // Otherwise, use epoch-based visibility
         self.is_visible_at(viewing_epoch)
     }
+
+    /// Checks if this version is visible to a transaction, ignoring the
+    /// changes of other transactions that haven't committed yet.
+    ///
+    /// `open` tells whether a transaction is still open. A version created
+    /// by an open transaction is hidden from everyone else, and one deleted
+    /// by an open transaction stays visible to everyone else. Reads as
+    /// [`TxId::SYSTEM`] see only committed state, even for versions it
+    /// created itself.
+    #[must_use]
+    pub fn is_visible_to_committed(
+        &self,
+        viewing_epoch: EpochId,
+        viewing_tx: TxId,
+        open: impl Fn(TxId) -> bool,
+    ) -> bool {
+        if self.created_by == viewing_tx && viewing_tx != TxId::SYSTEM {
+            return self.deleted_epoch.is_none();
+        }
+        if self.created_by != TxId::SYSTEM && open(self.created_by) {
+            return false;
+        }
+        if !self.created_epoch.is_visible_at(viewing_epoch) {
+            return false;
+        }
+
+        match (self.deleted_epoch, self.deleted_by) {
+            (None, _) => true,
+            (Some(_), Some(deleter)) if deleter == viewing_tx => false,
+            (Some(_), Some(deleter)) if open(deleter) => true,
+            (Some(deleted), _) => deleted.as_u64() > viewing_epoch.as_u64(),
+        }
+    }
 }
 
 /// A single version of data.
@@ -150,13 +186,38 @@ impl<T> VersionChain<T> {
             .map(|v| &v.data)
     }
 
+    /// Finds the version visible to a transaction, leaving out the changes
+    /// of other transactions that are still open.
+    ///
+    /// See [`VersionInfo::is_visible_to_committed`].
+    #[must_use]
+    pub fn visible_to_committed(
+        &self,
+        epoch: EpochId,
+        tx: TxId,
+        open: impl Fn(TxId) -> bool,
+    ) -> Option<&T> {
+        self.versions
+            .iter()
+            .find(|v| v.info.is_visible_to_committed(epoch, tx, &open))
+            .map(|v| &v.data)
+    }
+
     /// Marks the current visible version as deleted.
     ///
     /// Returns `true` if a version was marked, `false` if no visible version exists.
     pub fn mark_deleted(&mut self, delete_epoch: EpochId) -> bool {
+        self.mark_deleted_by(delete_epoch, TxId::SYSTEM)
+    }
+
+    /// Marks the current visible version as deleted by a transaction.
+    ///
+    /// Returns `true` if a version was marked, `false` if no visible version exists.
+    pub fn mark_deleted_by(&mut self, delete_epoch: EpochId, tx: TxId) -> bool {
         for version in &mut self.versions {
             if version.info.deleted_epoch.is_none() {
                 version.info.mark_deleted(delete_epoch);
+                version.info.deleted_by = (tx != TxId::SYSTEM).then_some(tx);
                 return true;
             }
         }
@@ -176,6 +237,41 @@ impl<T> VersionChain<T> {
         self.versions.retain(|v| v.info.created_by != tx);
     }
 
+    /// Moves all versions created by the given transaction to `epoch`.
+    ///
+    /// Used on commit, so the versions become visible at the commit epoch
+    /// rather than at the epoch the transaction started in.
+    pub fn stamp_versions_by(&mut self, tx: TxId, epoch: EpochId) {
+        for version in &mut self.versions {
+            if version.info.created_by == tx {
+                version.info.created_epoch = epoch;
+            }
+        }
+    }
+
+    /// Clears a deletion made at `delete_epoch`.
+    ///
+    /// Used for rollback to revive entities the aborted transaction deleted.
+    pub fn unmark_deleted(&mut self, delete_epoch: EpochId) {
+        for version in &mut self.versions {
+            if version.info.deleted_epoch == Some(delete_epoch) {
+                version.info.deleted_epoch = None;
+                version.info.deleted_by = None;
+            }
+        }
+    }
+
+    /// Moves a deletion made at `from` to the epoch `to`.
+    ///
+    /// Used on commit, like [`stamp_versions_by`](Self::stamp_versions_by).
+    pub fn restamp_deletion(&mut self, from: EpochId, to: EpochId) {
+        for version in &mut self.versions {
+            if version.info.deleted_epoch == Some(from) {
+                version.info.deleted_epoch = Some(to);
+            }
+        }
+    }
+
     /// Checks if there's a concurrent modification conflict.
     ///
     /// A conflict exists if another transaction modified this entity
@@ -310,6 +406,86 @@ mod tests {
         assert!(v.is_visible_to(EpochId::new(5), TxId::new(2)));
     }
 
+    #[test]
+    fn test_open_transaction_changes_are_hidden() {
+        let open = |tx: TxId| tx == TxId::new(2);
+
+        // Created by an open transaction: only the creator sees it
+        let created = VersionInfo::new(EpochId::new(1), TxId::new(2));
+        assert!(created.is_visible_to_committed(EpochId::new(5), TxId::new(2), open));
+        assert!(!created.is_visible_to_committed(EpochId::new(5), TxId::new(3), open));
+        assert!(!created.is_visible_to_committed(EpochId::new(5), TxId::SYSTEM, open));
+
+        // Deleted by an open transaction: only the deleter misses it
+        let mut chain = VersionChain::with_initial("v1", EpochId::new(1), TxId::new(1));
+        chain.mark_deleted_by(EpochId::new(1), TxId::new(2));
+        assert_eq!(
+            chain.visible_to_committed(EpochId::new(5), TxId::new(2), open),
+            None
+        );
+        assert_eq!(
+            chain.visible_to_committed(EpochId::new(5), TxId::new(3), open),
+            Some(&"v1")
+        );
+
+        // Once the deleter has committed, the deletion epoch decides
+        chain.restamp_deletion(EpochId::new(1), EpochId::new(4));
+        let closed = |_: TxId| false;
+        assert_eq!(
+            chain.visible_to_committed(EpochId::new(3), TxId::new(3), closed),
+            Some(&"v1")
+        );
+        assert_eq!(
+            chain.visible_to_committed(EpochId::new(4), TxId::new(3), closed),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stamp_versions_by() {
+        let mut chain = VersionChain::with_initial("v1", EpochId::new(1), TxId::new(1));
+        chain.add_version("v2", EpochId::new(1), TxId::new(2));
+
+        // Committing tx 2 at epoch 3 hides v2 from readers pinned before it
+        chain.stamp_versions_by(TxId::new(2), EpochId::new(3));
+        assert_eq!(chain.visible_at(EpochId::new(2)), Some(&"v1"));
+        assert_eq!(chain.visible_at(EpochId::new(3)), Some(&"v2"));
+    }
+
+    #[test]
+    fn test_unmark_and_restamp_deletion() {
+        let mut chain = VersionChain::with_initial("v1", EpochId::new(1), TxId::new(1));
+        chain.mark_deleted(EpochId::new(2));
+
+        chain.restamp_deletion(EpochId::new(2), EpochId::new(4));
+        assert_eq!(chain.visible_at(EpochId::new(3)), Some(&"v1"));
+        assert_eq!(chain.visible_at(EpochId::new(4)), None);
+
+        chain.unmark_deleted(EpochId::new(4));
+        assert_eq!(chain.visible_at(EpochId::new(10)), Some(&"v1"));
+    }
+
+    #[test]
+    fn test_open_delete_hidden_from_system_reads() {
+        let mut chain = VersionChain::with_initial("v1", EpochId::new(1), TxId::SYSTEM);
+        chain.mark_deleted_by(EpochId::new(2), TxId::new(5));
+        let open = |tx: TxId| tx == TxId::new(5);
+
+        // The system created v1, but the delete isn't committed yet
+        assert_eq!(
+            chain.visible_to_committed(EpochId::new(2), TxId::SYSTEM, open),
+            Some(&"v1")
+        );
+        assert_eq!(
+            chain.visible_to_committed(EpochId::new(2), TxId::new(5), open),
+            None
+        );
+        assert_eq!(
+            chain.visible_to_committed(EpochId::new(2), TxId::SYSTEM, |_| false),
+            None
+        );
+    }
+
     #[test]
     fn test_version_chain_basic() {
         let mut chain = VersionChain::with_initial("v1", EpochId::new(1), TxId::new(1));
@@ -6,14 +6,16 @@
 //! - **IDs**: [`NodeId`], [`EdgeId`] - handles to graph elements
 //! - **Values**: [`Value`] - the dynamic type for properties
 //! - **Keys**: [`PropertyKey`] - interned property names
-//! - **Time**: [`Timestamp`] - for temporal properties
+//! - **Time**: [`Timestamp`], [`Date`], [`Duration`] - for temporal properties
 
 mod id;
 mod logical_type;
+mod temporal;
 mod timestamp;
 mod value;
 
 pub use id::{EdgeId, EdgeTypeId, EpochId, IndexId, LabelId, NodeId, PropertyKeyId, TxId};
 pub use logical_type::LogicalType;
+pub use temporal::{Date, Duration};
 pub use timestamp::Timestamp;
 pub use value::{PropertyKey, Value};
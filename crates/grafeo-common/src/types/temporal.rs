@@ -0,0 +1,406 @@
+//! Calendar dates and durations.
+//!
+//! [`Date`] is a day on the proleptic Gregorian calendar, [`Duration`] a
+//! fixed span of time. Together with [`Timestamp`](super::Timestamp), which
+//! doubles as the date-time type, they cover what queries need to filter and
+//! do arithmetic on time.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+pub(crate) const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+
+/// A calendar date, stored as days since 1970-01-01.
+///
+/// Parse one with [`parse()`](Self::parse) (`YYYY-MM-DD`) or build it with
+/// [`from_ymd()`](Self::from_ymd). Compared with a timestamp, a date stands
+/// for midnight UTC at the start of that day.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default)]
+#[repr(transparent)]
+pub struct Date(i32);
+
+impl Date {
+    /// 1970-01-01.
+    pub const EPOCH: Self = Self(0);
+
+    /// Creates a date from days since 1970-01-01.
+    #[inline]
+    #[must_use]
+    pub const fn from_days(days: i32) -> Self {
+        Self(days)
+    }
+
+    /// Creates a date from a year, month (1-12) and day (1-31).
+    ///
+    /// Returns `None` if the day doesn't exist, like February 30th.
+    #[must_use]
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        let days = days_from_civil(i64::from(year), month, day);
+        i32::try_from(days).ok().map(Self)
+    }
+
+    /// Parses an ISO 8601 date, `YYYY-MM-DD`.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        let (year, rest) = s.split_once('-')?;
+        let (month, day) = rest.split_once('-')?;
+        if year.len() < 4 || month.len() != 2 || day.len() != 2 {
+            return None;
+        }
+        Self::from_ymd(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+    }
+
+    /// Returns the number of days since 1970-01-01.
+    #[inline]
+    #[must_use]
+    pub const fn as_days(&self) -> i32 {
+        self.0
+    }
+
+    /// Returns the year, month and day.
+    #[must_use]
+    pub fn to_ymd(&self) -> (i32, u32, u32) {
+        let (year, month, day) = civil_from_days(i64::from(self.0));
+        (year as i32, month, day)
+    }
+
+    /// Returns midnight UTC at the start of this day.
+    #[must_use]
+    pub const fn to_timestamp(&self) -> super::Timestamp {
+        super::Timestamp::from_micros(self.0 as i64 * MICROS_PER_DAY)
+    }
+
+    /// Returns the day a timestamp falls on, in UTC.
+    #[must_use]
+    pub const fn from_timestamp(timestamp: super::Timestamp) -> Self {
+        Self(timestamp.as_micros().div_euclid(MICROS_PER_DAY) as i32)
+    }
+}
+
+impl fmt::Debug for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Date({self})")
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.to_ymd();
+        write!(f, "{year:04}-{month:02}-{day:02}")
+    }
+}
+
+/// A fixed span of time, stored in microseconds.
+///
+/// Years and months aren't supported because their length depends on
+/// where they start; use days instead. Parse one with
+/// [`parse()`](Self::parse) from ISO 8601 like `P1DT2H30M`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default)]
+#[repr(transparent)]
+pub struct Duration(i64);
+
+impl Duration {
+    /// A zero-length duration.
+    pub const ZERO: Self = Self(0);
+
+    /// Creates a duration from microseconds.
+    #[inline]
+    #[must_use]
+    pub const fn from_micros(micros: i64) -> Self {
+        Self(micros)
+    }
+
+    /// Creates a duration from seconds.
+    #[inline]
+    #[must_use]
+    pub const fn from_secs(secs: i64) -> Self {
+        Self(secs * MICROS_PER_SECOND)
+    }
+
+    /// Creates a duration from days.
+    #[inline]
+    #[must_use]
+    pub const fn from_days(days: i64) -> Self {
+        Self(days * MICROS_PER_DAY)
+    }
+
+    /// Returns the duration in microseconds.
+    #[inline]
+    #[must_use]
+    pub const fn as_micros(&self) -> i64 {
+        self.0
+    }
+
+    /// Returns the number of whole days, if the duration is exactly that.
+    #[must_use]
+    pub const fn as_whole_days(&self) -> Option<i64> {
+        if self.0 % MICROS_PER_DAY == 0 {
+            Some(self.0 / MICROS_PER_DAY)
+        } else {
+            None
+        }
+    }
+
+    /// Parses an ISO 8601 duration such as `P2D`, `PT1H30M` or `-P1W`.
+    ///
+    /// Weeks, days, hours, minutes and (fractional) seconds are accepted;
+    /// years and months are not.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let s = s.strip_prefix('P')?;
+        let (date_part, time_part) = match s.split_once('T') {
+            Some((date, time)) if !time.is_empty() => (date, Some(time)),
+            Some(_) => return None,
+            None => (s, None),
+        };
+        if date_part.is_empty() && time_part.is_none() {
+            return None;
+        }
+
+        let mut micros = 0i64;
+        for (number, unit) in duration_fields(date_part)? {
+            let scale = match unit {
+                'W' => 7 * MICROS_PER_DAY,
+                'D' => MICROS_PER_DAY,
+                _ => return None,
+            };
+            micros = micros.checked_add(number.parse::<i64>().ok()?.checked_mul(scale)?)?;
+        }
+        for (number, unit) in duration_fields(time_part.unwrap_or(""))? {
+            let part = match unit {
+                'H' => number.parse::<i64>().ok()?.checked_mul(MICROS_PER_HOUR)?,
+                'M' => number.parse::<i64>().ok()?.checked_mul(MICROS_PER_MINUTE)?,
+                'S' => parse_seconds(number)?,
+                _ => return None,
+            };
+            micros = micros.checked_add(part)?;
+        }
+        Some(Self(if negative { -micros } else { micros }))
+    }
+}
+
+impl fmt::Debug for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Duration({self})")
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 < 0 {
+            write!(f, "-")?;
+        }
+        let mut rest = self.0.unsigned_abs();
+        let days = rest / MICROS_PER_DAY as u64;
+        rest %= MICROS_PER_DAY as u64;
+        let hours = rest / MICROS_PER_HOUR as u64;
+        rest %= MICROS_PER_HOUR as u64;
+        let minutes = rest / MICROS_PER_MINUTE as u64;
+        rest %= MICROS_PER_MINUTE as u64;
+        let seconds = rest / MICROS_PER_SECOND as u64;
+        let micros = rest % MICROS_PER_SECOND as u64;
+
+        write!(f, "P")?;
+        if days > 0 {
+            write!(f, "{days}D")?;
+        }
+        if hours == 0 && minutes == 0 && seconds == 0 && micros == 0 {
+            return if days == 0 { write!(f, "T0S") } else { Ok(()) };
+        }
+        write!(f, "T")?;
+        if hours > 0 {
+            write!(f, "{hours}H")?;
+        }
+        if minutes > 0 {
+            write!(f, "{minutes}M")?;
+        }
+        match (seconds, micros) {
+            (0, 0) => Ok(()),
+            (seconds, 0) => write!(f, "{seconds}S"),
+            (seconds, micros) => {
+                let fraction = format!("{micros:06}");
+                write!(f, "{seconds}.{}S", fraction.trim_end_matches('0'))
+            }
+        }
+    }
+}
+
+/// Splits `1D2H` into `[("1", 'D'), ("2", 'H')]`.
+fn duration_fields(s: &str) -> Option<Vec<(&str, char)>> {
+    let mut fields = Vec::new();
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        if c.is_ascii_alphabetic() {
+            if i == start {
+                return None;
+            }
+            fields.push((&s[start..i], c));
+            start = i + 1;
+        }
+    }
+    (start == s.len()).then_some(fields)
+}
+
+/// Parses `12` or `12.5` seconds into microseconds.
+fn parse_seconds(s: &str) -> Option<i64> {
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    if fraction.len() > 6 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        format!("{fraction:0<6}").parse::<i64>().ok()?
+    };
+    whole
+        .parse::<i64>()
+        .ok()?
+        .checked_mul(MICROS_PER_SECOND)?
+        .checked_add(fraction)
+}
+
+/// Parses `HH:MM[:SS[.ffffff]]` into microseconds since midnight.
+pub(crate) fn parse_time_of_day(s: &str) -> Option<i64> {
+    let mut parts = s.splitn(3, ':');
+    let hours: i64 = parse_two_digits(parts.next()?)?;
+    let minutes: i64 = parse_two_digits(parts.next()?)?;
+    let seconds = match parts.next() {
+        Some(seconds) => {
+            let whole = seconds.split('.').next()?;
+            if whole.len() != 2 {
+                return None;
+            }
+            parse_seconds(seconds)?
+        }
+        None => 0,
+    };
+    if hours > 23 || minutes > 59 || seconds >= 60 * MICROS_PER_SECOND {
+        return None;
+    }
+    Some(hours * MICROS_PER_HOUR + minutes * MICROS_PER_MINUTE + seconds)
+}
+
+fn parse_two_digits(s: &str) -> Option<i64> {
+    if s.len() == 2 && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+///
+/// Howard Hinnant's `days_from_civil`, which works in 400-year eras.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = i64::from((month + 9) % 12);
+    let day_of_year = (153 * month_from_march + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The proleptic Gregorian date `days` after 1970-01-01.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_round_trip() {
+        for (text, days) in [
+            ("1970-01-01", 0),
+            ("2000-02-29", 11_016),
+            ("2020-01-01", 18_262),
+            ("1969-12-31", -1),
+            ("1600-03-01", -135_080),
+        ] {
+            let date = Date::parse(text).unwrap();
+            assert_eq!(date.as_days(), days, "{text}");
+            assert_eq!(date.to_string(), text);
+        }
+
+        assert!(Date::parse("2021-02-29").is_none());
+        assert!(Date::parse("2020-13-01").is_none());
+        assert!(Date::parse("2020-1-01").is_none());
+        assert!(Date::parse("yesterday").is_none());
+    }
+
+    #[test]
+    fn test_date_timestamp_conversion() {
+        let date = Date::from_ymd(2020, 1, 2).unwrap();
+        let midnight = date.to_timestamp();
+        assert_eq!(Date::from_timestamp(midnight), date);
+        assert_eq!(
+            Date::from_timestamp(midnight.add_micros(MICROS_PER_DAY - 1)),
+            date
+        );
+        assert_eq!(
+            Date::from_timestamp(midnight.sub_micros(1)),
+            Date::from_ymd(2020, 1, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_duration_round_trip() {
+        for (text, micros) in [
+            ("PT0S", 0),
+            ("P1D", MICROS_PER_DAY),
+            ("PT1H30M", 90 * MICROS_PER_MINUTE),
+            ("P2DT3.25S", 2 * MICROS_PER_DAY + 3_250_000),
+            ("-PT1S", -MICROS_PER_SECOND),
+        ] {
+            let duration = Duration::parse(text).unwrap();
+            assert_eq!(duration.as_micros(), micros, "{text}");
+            assert_eq!(duration.to_string(), text);
+        }
+
+        assert_eq!(Duration::parse("P1W"), Some(Duration::from_days(7)));
+        assert!(Duration::parse("P1M").is_none());
+        assert!(Duration::parse("P1Y").is_none());
+        assert!(Duration::parse("PT").is_none());
+        assert!(Duration::parse("P").is_none());
+        assert!(Duration::parse("1D").is_none());
+    }
+}
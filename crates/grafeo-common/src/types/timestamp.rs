@@ -6,6 +6,9 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use super::Date;
+use super::temporal::{MICROS_PER_DAY, civil_from_days, parse_time_of_day};
+
 /// A point in time, stored as microseconds since Unix epoch.
 ///
 /// Microsecond precision, covering roughly 290,000 years in each direction
@@ -98,6 +101,26 @@ impl Timestamp {
         Self(self.0.saturating_sub(micros))
     }
 
+    /// Parses an ISO 8601 date-time such as `2020-01-01T12:30:00Z`.
+    ///
+    /// The time may have fractional seconds and may be separated from the
+    /// date by a space instead of `T`. A `Z` or `+HH:MM`/`-HH:MM` offset is
+    /// converted to UTC; a date-time without one is taken to be UTC
+    /// already. A bare date is midnight UTC.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        let (date, time) = match s.split_once(['T', ' ']) {
+            Some((date, time)) => (date, Some(time)),
+            None => (s, None),
+        };
+        let mut micros = Date::parse(date)?.to_timestamp().as_micros();
+        if let Some(time) = time {
+            let (time, offset) = split_utc_offset(time)?;
+            micros += parse_time_of_day(time)? - offset;
+        }
+        Some(Self(micros))
+    }
+
     /// Returns the duration between this timestamp and another.
     ///
     /// Returns a positive value if `other` is before `self`, negative otherwise.
@@ -115,29 +138,23 @@ impl fmt::Debug for Timestamp {
 
 impl fmt::Display for Timestamp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Simple ISO 8601-ish format
-        let secs = self.0 / 1_000_000;
-        let micros = (self.0 % 1_000_000).unsigned_abs();
-
-        // Calculate date/time components (simplified, doesn't handle all edge cases)
-        const SECS_PER_DAY: i64 = 86400;
-        const DAYS_PER_YEAR: i64 = 365;
-
-        let days = secs / SECS_PER_DAY;
-        let time_secs = (secs % SECS_PER_DAY + SECS_PER_DAY) % SECS_PER_DAY;
-
-        let hours = time_secs / 3600;
-        let minutes = (time_secs % 3600) / 60;
-        let seconds = time_secs % 60;
-
-        // Very rough year calculation (ignores leap years for display)
-        let year = 1970 + days / DAYS_PER_YEAR;
-        let day_of_year = days % DAYS_PER_YEAR;
+        // ISO 8601 in UTC, e.g. 2020-01-01T12:30:00.000000Z
+        let days = self.0.div_euclid(MICROS_PER_DAY);
+        let time = self.0.rem_euclid(MICROS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
 
+        let secs = time / 1_000_000;
+        let micros = time % 1_000_000;
         write!(
             f,
-            "{:04}-{:03}T{:02}:{:02}:{:02}.{:06}Z",
-            year, day_of_year, hours, minutes, seconds, micros
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}Z",
+            year,
+            month,
+            day,
+            secs / 3600,
+            (secs % 3600) / 60,
+            secs % 60,
+            micros
         )
     }
 }
@@ -165,6 +182,21 @@ impl TryFrom<SystemTime> for Timestamp {
     }
 }
 
+/// Splits a trailing `Z` or `+HH:MM`/`-HH:MM` off a time of day, returning
+/// the offset in microseconds east of UTC.
+fn split_utc_offset(time: &str) -> Option<(&str, i64)> {
+    if let Some(time) = time.strip_suffix('Z') {
+        return Some((time, 0));
+    }
+    let Some(sign_at) = time.rfind(['+', '-']) else {
+        return Some((time, 0));
+    };
+    let (time, offset) = time.split_at(sign_at);
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let offset = parse_time_of_day(&offset[1..])?;
+    Some((time, sign * offset))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,6 +260,31 @@ mod tests {
         assert!(diff.as_micros() < 2);
     }
 
+    #[test]
+    fn test_timestamp_parse_and_display() {
+        let ts = Timestamp::parse("2020-01-01T12:30:00Z").unwrap();
+        assert_eq!(ts.as_secs(), 1_577_881_800);
+        assert_eq!(ts.to_string(), "2020-01-01T12:30:00.000000Z");
+
+        assert_eq!(Timestamp::parse("2020-01-01 12:30:00"), Some(ts));
+        assert_eq!(Timestamp::parse("2020-01-01T14:30+02:00"), Some(ts));
+        assert_eq!(
+            Timestamp::parse("2020-01-01T12:30:00.5Z"),
+            Some(ts.add_micros(500_000))
+        );
+        assert_eq!(
+            Timestamp::parse("2020-01-01"),
+            Some(Timestamp::from_secs(1_577_836_800))
+        );
+        assert_eq!(
+            Timestamp::from_micros(-1).to_string(),
+            "1969-12-31T23:59:59.999999Z"
+        );
+
+        assert!(Timestamp::parse("2020-01-01T25:00Z").is_none());
+        assert!(Timestamp::parse("2020-01-01T12").is_none());
+    }
+
     #[test]
     fn test_timestamp_epoch() {
         assert_eq!(Timestamp::EPOCH.as_micros(), 0);
@@ -9,7 +9,7 @@ use std::collections::BTreeMap;
 use std::fmt;
 use std::sync::Arc;
 
-use super::Timestamp;
+use super::{Date, Duration, Timestamp};
 
 /// An interned property name - cheap to clone and compare.
 ///
@@ -66,7 +66,11 @@ impl AsRef<str> for PropertyKey {
 ///
 /// Nodes and edges can have properties of various types - this enum holds
 /// them all. Follows the GQL type system, so you can store nulls, booleans,
-/// numbers, strings, timestamps, lists, and maps.
+/// numbers, strings, dates, timestamps, durations, lists, and maps.
+///
+/// [`Timestamp`] is the date-time type. Where a [`Date`] meets a timestamp,
+/// in comparisons or arithmetic, the date stands for midnight UTC at the
+/// start of that day; see [`temporal_cmp()`](Self::temporal_cmp).
 ///
 /// # Examples
 ///
@@ -98,7 +102,13 @@ pub enum Value {
     /// UTF-8 string (uses Arc for cheap cloning)
     String(Arc<str>),
 
-    /// Binary data
+    /// Binary data.
+    ///
+    /// Byte strings order lexicographically by unsigned byte, so a prefix
+    /// sorts before anything that extends it: `[] < [0] < [0, 0] < [1]`.
+    /// Human-readable formats like JSON carry them as standard base64;
+    /// binary formats store the raw bytes.
+    #[serde(with = "bytes_serde")]
     Bytes(Arc<[u8]>),
 
     /// Timestamp with timezone
@@ -109,6 +119,13 @@ pub enum Value {
 
     /// Key-value map (uses BTreeMap for deterministic ordering)
     Map(Arc<BTreeMap<PropertyKey, Value>>),
+
+    // New variants go last so values already written to disk still decode
+    /// Calendar date
+    Date(Date),
+
+    /// Fixed span of time
+    Duration(Duration),
 }
 
 impl Value {
@@ -179,6 +196,26 @@ impl Value {
         }
     }
 
+    /// Returns the date value if this is a Date, otherwise None.
+    #[inline]
+    #[must_use]
+    pub const fn as_date(&self) -> Option<Date> {
+        match self {
+            Value::Date(d) => Some(*d),
+            _ => None,
+        }
+    }
+
+    /// Returns the duration value if this is a Duration, otherwise None.
+    #[inline]
+    #[must_use]
+    pub const fn as_duration(&self) -> Option<Duration> {
+        match self {
+            Value::Duration(d) => Some(*d),
+            _ => None,
+        }
+    }
+
     /// Returns the list value if this is a List, otherwise None.
     #[inline]
     #[must_use]
@@ -212,6 +249,68 @@ impl Value {
             Value::Timestamp(_) => "TIMESTAMP",
             Value::List(_) => "LIST",
             Value::Map(_) => "MAP",
+            Value::Date(_) => "DATE",
+            Value::Duration(_) => "DURATION",
+        }
+    }
+
+    /// Orders two temporal values, or returns `None` if they aren't both
+    /// temporal or don't compare.
+    ///
+    /// Dates, timestamps and durations compare with their own kind. A date
+    /// also compares with a timestamp as midnight UTC at the start of that
+    /// day, so it equals only the timestamp at exactly that midnight and
+    /// is less than any later time on the same day. Durations never compare
+    /// with points in time.
+    #[must_use]
+    pub fn temporal_cmp(&self, other: &Value) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (Value::Date(a), Value::Date(b)) => Some(a.cmp(b)),
+            (Value::Timestamp(a), Value::Timestamp(b)) => Some(a.cmp(b)),
+            (Value::Duration(a), Value::Duration(b)) => Some(a.cmp(b)),
+            (Value::Date(a), Value::Timestamp(b)) => Some(a.to_timestamp().cmp(b)),
+            (Value::Timestamp(a), Value::Date(b)) => Some(a.cmp(&b.to_timestamp())),
+            _ => None,
+        }
+    }
+
+    /// Adds (or with `negate`, subtracts) two temporal values.
+    ///
+    /// - timestamp ± duration is a timestamp
+    /// - date ± duration is a date if the duration is whole days, otherwise
+    ///   the timestamp it lands on (the date taken as midnight UTC)
+    /// - duration ± duration is a duration
+    /// - subtracting two dates or timestamps (in any mix) gives the
+    ///   duration between them
+    ///
+    /// Returns `None` for anything else, or if the result overflows.
+    #[must_use]
+    pub fn temporal_add(&self, other: &Value, negate: bool) -> Option<Value> {
+        let sign = if negate { -1 } else { 1 };
+        match (self, other) {
+            (Value::Timestamp(t), Value::Duration(d)) => Some(Value::Timestamp(
+                Timestamp::from_micros(t.as_micros().checked_add(sign * d.as_micros())?),
+            )),
+            (Value::Date(date), Value::Duration(d)) => match d.as_whole_days() {
+                Some(days) => {
+                    let days = i64::from(date.as_days()).checked_add(sign * days)?;
+                    Some(Value::Date(Date::from_days(i32::try_from(days).ok()?)))
+                }
+                None => Value::Timestamp(date.to_timestamp()).temporal_add(other, negate),
+            },
+            (Value::Duration(a), Value::Duration(b)) => Some(Value::Duration(
+                Duration::from_micros(a.as_micros().checked_add(sign * b.as_micros())?),
+            )),
+            // Only a duration can be added to a duration; the rest of the
+            // addition cases are covered above
+            (Value::Duration(d), Value::Timestamp(_) | Value::Date(_)) if !negate => {
+                other.temporal_add(&Value::Duration(*d), false)
+            }
+            _ if negate => {
+                let (a, b) = (instant_micros(self)?, instant_micros(other)?);
+                Some(Value::Duration(Duration::from_micros(a.checked_sub(b)?)))
+            }
+            _ => None,
         }
     }
 
@@ -233,6 +332,15 @@ impl Value {
     }
 }
 
+/// Microseconds since the Unix epoch of a date (at midnight UTC) or timestamp.
+fn instant_micros(value: &Value) -> Option<i64> {
+    match value {
+        Value::Date(d) => Some(d.to_timestamp().as_micros()),
+        Value::Timestamp(t) => Some(t.as_micros()),
+        _ => None,
+    }
+}
+
 impl fmt::Debug for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -245,6 +353,8 @@ impl fmt::Debug for Value {
             Value::Timestamp(t) => write!(f, "Timestamp({t:?})"),
             Value::List(l) => write!(f, "List({l:?})"),
             Value::Map(m) => write!(f, "Map({m:?})"),
+            Value::Date(d) => write!(f, "{d:?}"),
+            Value::Duration(d) => write!(f, "{d:?}"),
         }
     }
 }
@@ -259,6 +369,8 @@ impl fmt::Display for Value {
             Value::String(s) => write!(f, "{s:?}"),
             Value::Bytes(b) => write!(f, "<bytes: {} bytes>", b.len()),
             Value::Timestamp(t) => write!(f, "{t}"),
+            Value::Date(d) => write!(f, "{d}"),
+            Value::Duration(d) => write!(f, "{d}"),
             Value::List(l) => {
                 write!(f, "[")?;
                 for (i, v) in l.iter().enumerate() {
@@ -350,6 +462,18 @@ impl From<Timestamp> for Value {
     }
 }
 
+impl From<Date> for Value {
+    fn from(d: Date) -> Self {
+        Value::Date(d)
+    }
+}
+
+impl From<Duration> for Value {
+    fn from(d: Duration) -> Self {
+        Value::Duration(d)
+    }
+}
+
 impl<T: Into<Value>> From<Vec<T>> for Value {
     fn from(v: Vec<T>) -> Self {
         Value::List(v.into_iter().map(Into::into).collect())
@@ -365,6 +489,35 @@ impl<T: Into<Value>> From<Option<T>> for Value {
     }
 }
 
+/// Serde for [`Value::Bytes`]: base64 text in human-readable formats, the
+/// plain byte sequence everywhere else.
+mod bytes_serde {
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::sync::Arc;
+
+    pub fn serialize<S: Serializer>(bytes: &Arc<[u8]>, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&STANDARD.encode(bytes))
+        } else {
+            bytes.serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Arc<[u8]>, D::Error> {
+        if deserializer.is_human_readable() {
+            let text = String::deserialize(deserializer)?;
+            STANDARD
+                .decode(text)
+                .map(Arc::from)
+                .map_err(serde::de::Error::custom)
+        } else {
+            Arc::<[u8]>::deserialize(deserializer)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -413,6 +566,9 @@ mod tests {
             Value::String("hello world".into()),
             Value::Bytes(vec![0, 1, 2, 255].into()),
             Value::List(vec![Value::Int64(1), Value::Int64(2)].into()),
+            Value::Timestamp(Timestamp::from_secs(1_577_836_800)),
+            Value::Date(Date::from_ymd(2020, 1, 1).unwrap()),
+            Value::Duration(Duration::from_secs(-90)),
         ];
 
         for v in values {
@@ -422,6 +578,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bytes_json_is_base64() {
+        let large: Vec<u8> = (0..=255u8).cycle().take(1 << 20).collect();
+        for bytes in [Vec::new(), vec![0, 1, 2, 255], large] {
+            let value = Value::Bytes(bytes.into());
+            let json = serde_json::to_string(&value).unwrap();
+            let decoded: Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, value);
+        }
+
+        let json = serde_json::to_string(&Value::Bytes(vec![0, 1, 2, 255].into())).unwrap();
+        assert_eq!(json, r#"{"Bytes":"AAEC/w=="}"#);
+        assert_eq!(
+            serde_json::to_string(&Value::Bytes(Vec::new().into())).unwrap(),
+            r#"{"Bytes":""}"#
+        );
+    }
+
     #[test]
     fn test_property_key() {
         let key = PropertyKey::new("name");
@@ -444,5 +618,69 @@ mod tests {
         assert_eq!(Value::Bytes(vec![].into()).type_name(), "BYTES");
         assert_eq!(Value::List(vec![].into()).type_name(), "LIST");
         assert_eq!(Value::Map(BTreeMap::new().into()).type_name(), "MAP");
+        assert_eq!(Value::Date(Date::EPOCH).type_name(), "DATE");
+        assert_eq!(Value::Duration(Duration::ZERO).type_name(), "DURATION");
+    }
+
+    #[test]
+    fn test_temporal_comparison() {
+        use std::cmp::Ordering;
+
+        let date = Value::Date(Date::parse("2020-01-01").unwrap());
+        let midnight = Value::Timestamp(Timestamp::parse("2020-01-01T00:00:00Z").unwrap());
+        let noon = Value::Timestamp(Timestamp::parse("2020-01-01T12:00:00Z").unwrap());
+
+        assert_eq!(date.temporal_cmp(&midnight), Some(Ordering::Equal));
+        assert_eq!(date.temporal_cmp(&noon), Some(Ordering::Less));
+        assert_eq!(noon.temporal_cmp(&date), Some(Ordering::Greater));
+        assert_eq!(
+            date.temporal_cmp(&Value::Date(Date::parse("2019-12-31").unwrap())),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(date.temporal_cmp(&Value::Duration(Duration::ZERO)), None);
+        assert_eq!(date.temporal_cmp(&Value::Int64(0)), None);
+    }
+
+    #[test]
+    fn test_temporal_arithmetic() {
+        let date = Value::Date(Date::parse("2020-02-28").unwrap());
+        let noon = Value::Timestamp(Timestamp::parse("2020-02-28T12:00:00Z").unwrap());
+        let day = Value::Duration(Duration::from_days(1));
+        let hour = Value::Duration(Duration::from_secs(3600));
+
+        assert_eq!(
+            date.temporal_add(&day, false),
+            Some(Value::Date(Date::parse("2020-02-29").unwrap()))
+        );
+        assert_eq!(
+            date.temporal_add(&hour, true),
+            Some(Value::Timestamp(
+                Timestamp::parse("2020-02-27T23:00:00Z").unwrap()
+            ))
+        );
+        assert_eq!(
+            noon.temporal_add(&day, true),
+            Some(Value::Timestamp(
+                Timestamp::parse("2020-02-27T12:00:00Z").unwrap()
+            ))
+        );
+        assert_eq!(
+            hour.temporal_add(&noon, false),
+            Some(Value::Timestamp(
+                Timestamp::parse("2020-02-28T13:00:00Z").unwrap()
+            ))
+        );
+        assert_eq!(
+            noon.temporal_add(&date, true),
+            Some(Value::Duration(Duration::from_secs(12 * 3600)))
+        );
+        assert_eq!(
+            day.temporal_add(&hour, false),
+            Some(Value::Duration(Duration::from_secs(25 * 3600)))
+        );
+
+        assert_eq!(hour.temporal_add(&noon, true), None);
+        assert_eq!(date.temporal_add(&date, false), None);
+        assert_eq!(date.temporal_add(&Value::Int64(1), false), None);
     }
 }
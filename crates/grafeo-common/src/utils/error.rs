@@ -49,6 +49,17 @@ pub enum Error {
     /// I/O error.
     Io(std::io::Error),
 
+    /// The query ran past its time limit and was cancelled.
+    Timeout,
+
+    /// The query needed more memory than the budget allows, even after
+    /// spilling what it could.
+    OutOfMemory,
+
+    /// A write would break a schema constraint, such as two nodes sharing a
+    /// value of a unique property.
+    ConstraintViolation(String),
+
     /// Internal error (should not happen in normal operation).
     Internal(String),
 }
@@ -69,11 +80,31 @@ impl fmt::Display for Error {
             Error::Query(e) => write!(f, "Query error: {e}"),
             Error::Serialization(msg) => write!(f, "Serialization error: {msg}"),
             Error::Io(e) => write!(f, "I/O error: {e}"),
+            Error::Timeout => write!(f, "Query timed out"),
+            Error::OutOfMemory => write!(f, "Query exceeded the memory budget"),
+            Error::ConstraintViolation(msg) => write!(f, "Constraint violation: {msg}"),
             Error::Internal(msg) => write!(f, "Internal error: {msg}"),
         }
     }
 }
 
+impl Error {
+    /// Returns `true` if the operation failed because of a concurrent
+    /// transaction and may succeed when retried.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::Transaction(
+                TransactionError::Conflict
+                    | TransactionError::WriteConflict(_)
+                    | TransactionError::SerializationFailure(_)
+                    | TransactionError::Deadlock
+            )
+        )
+    }
+}
+
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
@@ -100,6 +131,9 @@ pub enum TransactionError {
 
     /// Write-write conflict with another transaction.
     WriteConflict(String),
+    /// Another transaction changed an entity this one wrote since it was
+    /// read; retrying the transaction may succeed.
+    SerializationFailure(String),
 
     /// Deadlock detected.
     Deadlock,
@@ -120,6 +154,9 @@ impl fmt::Display for TransactionError {
             TransactionError::Aborted => write!(f, "Transaction aborted"),
             TransactionError::Conflict => write!(f, "Transaction conflict"),
             TransactionError::WriteConflict(msg) => write!(f, "Write conflict: {msg}"),
+            TransactionError::SerializationFailure(msg) => {
+                write!(f, "Serialization failure: {msg}")
+            }
             TransactionError::Deadlock => write!(f, "Deadlock detected"),
             TransactionError::Timeout => write!(f, "Transaction timeout"),
             TransactionError::ReadOnly => write!(f, "Cannot write in read-only transaction"),
@@ -355,6 +392,19 @@ mod tests {
         assert!(msg.contains("Did you mean 'Person'?"));
     }
 
+    #[test]
+    fn test_serialization_failure_is_retryable() {
+        let err: Error = TransactionError::SerializationFailure("node 1".to_string()).into();
+        assert!(err.is_retryable());
+        assert_eq!(
+            err.to_string(),
+            "Transaction error: Serialization failure: node 1"
+        );
+
+        let err: Error = TransactionError::InvalidState("closed".to_string()).into();
+        assert!(!err.is_retryable());
+    }
+
     #[test]
     fn test_transaction_error() {
         let err: Error = TransactionError::Conflict.into();
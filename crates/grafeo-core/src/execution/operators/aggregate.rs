@@ -63,6 +63,10 @@ pub enum AggregateFunction {
     /// Last value in the group.
     Last,
     /// Collect values into a list.
+    ///
+    /// Values keep their input order within each group, nulls are skipped
+    /// (as in Cypher's `collect`), and DISTINCT keeps the first occurrence of
+    /// each value. A group with no non-null values collects an empty list.
     Collect,
     /// Sample standard deviation (STDEV).
     StdDev,
@@ -534,43 +538,47 @@ impl AggregateState {
                     Value::Float64((*m2 / *count as f64).sqrt())
                 }
             }
-            // Discrete percentile: return actual value at percentile position
             AggregateState::PercentileDisc { values, percentile } => {
-                if values.is_empty() {
-                    Value::Null
-                } else {
-                    let mut sorted = values.clone();
-                    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-                    // Index calculation per SQL standard: floor(p * (n - 1))
-                    let index = (percentile * (sorted.len() - 1) as f64).floor() as usize;
-                    Value::Float64(sorted[index])
-                }
+                percentile_disc(values, *percentile)
             }
-            // Continuous percentile: interpolate between values
             AggregateState::PercentileCont { values, percentile } => {
-                if values.is_empty() {
-                    Value::Null
-                } else {
-                    let mut sorted = values.clone();
-                    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-                    // Linear interpolation per SQL standard
-                    let rank = percentile * (sorted.len() - 1) as f64;
-                    let lower_idx = rank.floor() as usize;
-                    let upper_idx = rank.ceil() as usize;
-                    if lower_idx == upper_idx {
-                        Value::Float64(sorted[lower_idx])
-                    } else {
-                        let fraction = rank - lower_idx as f64;
-                        let result =
-                            sorted[lower_idx] + fraction * (sorted[upper_idx] - sorted[lower_idx]);
-                        Value::Float64(result)
-                    }
-                }
+                percentile_cont(values, *percentile)
             }
         }
     }
 }
 
+fn sorted_values(values: &[f64]) -> Vec<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    sorted
+}
+
+/// Discrete percentile: the value at position `floor(p * (n - 1))` of the
+/// sorted input, or null for no input.
+pub(crate) fn percentile_disc(values: &[f64], percentile: f64) -> Value {
+    if values.is_empty() {
+        return Value::Null;
+    }
+    let sorted = sorted_values(values);
+    let index = (percentile * (sorted.len() - 1) as f64).floor() as usize;
+    Value::Float64(sorted[index])
+}
+
+/// Continuous percentile: linearly interpolates between the two sorted
+/// values around rank `p * (n - 1)`, or null for no input.
+pub(crate) fn percentile_cont(values: &[f64], percentile: f64) -> Value {
+    if values.is_empty() {
+        return Value::Null;
+    }
+    let sorted = sorted_values(values);
+    let rank = percentile * (sorted.len() - 1) as f64;
+    let lower_idx = rank.floor() as usize;
+    let upper_idx = rank.ceil() as usize;
+    let fraction = rank - lower_idx as f64;
+    Value::Float64(sorted[lower_idx] + fraction * (sorted[upper_idx] - sorted[lower_idx]))
+}
+
 /// Convert a value to f64 for numeric aggregations.
 /// Supports RDF values stored as strings by attempting numeric parsing.
 fn value_to_f64(value: &Value) -> Option<f64> {
@@ -598,6 +606,7 @@ fn compare_values(a: &Value, b: &Value) -> Option<std::cmp::Ordering> {
             }
         }
         (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::Bytes(a), Value::Bytes(b)) => Some(a.cmp(b)),
         (Value::Int64(a), Value::Float64(b)) => (*a as f64).partial_cmp(b),
         (Value::Float64(a), Value::Int64(b)) => a.partial_cmp(&(*b as f64)),
         // String-to-numeric comparisons for RDF
@@ -605,7 +614,7 @@ fn compare_values(a: &Value, b: &Value) -> Option<std::cmp::Ordering> {
         (Value::String(s), Value::Float64(f)) => s.parse::<f64>().ok()?.partial_cmp(f),
         (Value::Int64(i), Value::String(s)) => (*i as f64).partial_cmp(&s.parse::<f64>().ok()?),
         (Value::Float64(f), Value::String(s)) => f.partial_cmp(&s.parse::<f64>().ok()?),
-        _ => None,
+        _ => a.temporal_cmp(b),
     }
 }
 
@@ -1457,4 +1466,79 @@ mod tests {
         let stdev = result.column(0).unwrap().get_float64(0).unwrap();
         assert!((stdev - 0.0).abs() < 0.01);
     }
+
+    fn create_collect_chunk() -> DataChunk {
+        // [(group, value)] with nulls and a repeated value in group 1
+        let mut builder = DataChunkBuilder::new(&[LogicalType::Int64, LogicalType::Any]);
+
+        let data = [
+            (1i64, Value::from("b")),
+            (2, Value::from("x")),
+            (1, Value::Null),
+            (1, Value::from("a")),
+            (3, Value::Null),
+            (1, Value::from("b")),
+        ];
+        for (group, value) in data {
+            builder.column_mut(0).unwrap().push_int64(group);
+            builder.column_mut(1).unwrap().push_value(value);
+            builder.advance_row();
+        }
+
+        builder.finish()
+    }
+
+    fn collect_groups(aggregate: AggregateExpr) -> Vec<(i64, Value)> {
+        let mock = MockOperator::new(vec![create_collect_chunk()]);
+        let mut agg = HashAggregateOperator::new(
+            Box::new(mock),
+            vec![0],
+            vec![aggregate],
+            vec![LogicalType::Int64, LogicalType::Any],
+        );
+
+        let mut results = Vec::new();
+        while let Some(chunk) = agg.next().unwrap() {
+            for row in chunk.selected_indices() {
+                let group = chunk.column(0).unwrap().get_int64(row).unwrap();
+                let list = chunk.column(1).unwrap().get_value(row).unwrap();
+                results.push((group, list));
+            }
+        }
+        results.sort_by_key(|(g, _)| *g);
+        results
+    }
+
+    fn str_list(items: &[&str]) -> Value {
+        Value::List(items.iter().map(|s| Value::from(*s)).collect())
+    }
+
+    #[test]
+    fn test_collect_preserves_order_and_skips_nulls() {
+        let results = collect_groups(AggregateExpr::collect(1));
+
+        assert_eq!(
+            results,
+            vec![
+                (1, str_list(&["b", "a", "b"])),
+                (2, str_list(&["x"])),
+                (3, str_list(&[])),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_distinct() {
+        let results = collect_groups(AggregateExpr::collect(1).with_distinct());
+
+        // First occurrence wins, so order is still input order
+        assert_eq!(
+            results,
+            vec![
+                (1, str_list(&["b", "a"])),
+                (2, str_list(&["x"])),
+                (3, str_list(&[])),
+            ]
+        );
+    }
 }
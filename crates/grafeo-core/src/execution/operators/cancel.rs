@@ -0,0 +1,164 @@
+//! Cooperative cancellation for running queries.
+//!
+//! A [`CancellationToken`] is shared by every operator of a plan. Each one is
+//! wrapped in a [`CancellableOperator`], which checks the token before pulling
+//! the next chunk, so even operators that drain their whole input in one call
+//! (sort, aggregate, hash join build) stop within a chunk of the token firing.
+//! Operators clean up in `Drop`, so abandoning the tree after the error frees
+//! whatever they held.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use super::{Operator, OperatorError, OperatorResult};
+
+/// Signals a running plan to stop, either on request or once a deadline
+/// passes.
+///
+/// The clock for a timeout starts when the plan first pulls a chunk, not
+/// when the token is made, so planning and any wait before the plan runs
+/// don't count against it. Clones share state: cancelling one cancels them
+/// all, and they share one deadline.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    timeout: Option<Duration>,
+    /// Set by the first [`start`](Self::start); `None` inside if the
+    /// deadline is too far off to represent.
+    deadline: Arc<OnceLock<Option<Instant>>>,
+}
+
+impl CancellationToken {
+    /// Creates a token that only fires when [`cancel`](Self::cancel) is called.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a token that also fires once `timeout` has passed since
+    /// [`start`](Self::start).
+    #[must_use]
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..Self::default()
+        }
+    }
+
+    /// Starts the timeout clock, if it isn't running already.
+    pub fn start(&self) {
+        if let Some(timeout) = self.timeout {
+            self.deadline
+                .get_or_init(|| Instant::now().checked_add(timeout));
+        }
+    }
+
+    /// Cancels every operator sharing this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the token was cancelled or its deadline has passed.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .deadline
+                .get()
+                .copied()
+                .flatten()
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// Wraps an operator and fails with [`OperatorError::Cancelled`] once its
+/// token fires. The first pull starts the token's clock.
+pub struct CancellableOperator {
+    inner: Box<dyn Operator>,
+    token: CancellationToken,
+}
+
+impl CancellableOperator {
+    /// Wraps `inner`, checking `token` before each pull.
+    pub fn new(inner: Box<dyn Operator>, token: CancellationToken) -> Self {
+        Self { inner, token }
+    }
+}
+
+impl Operator for CancellableOperator {
+    fn next(&mut self) -> OperatorResult {
+        self.token.start();
+        if self.token.is_cancelled() {
+            return Err(OperatorError::Cancelled);
+        }
+        self.inner.next()
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::DataChunk;
+
+    /// Produces empty chunks forever.
+    struct EndlessOperator;
+
+    impl Operator for EndlessOperator {
+        fn next(&mut self) -> OperatorResult {
+            Ok(Some(DataChunk::empty()))
+        }
+
+        fn reset(&mut self) {}
+
+        fn name(&self) -> &'static str {
+            "Endless"
+        }
+    }
+
+    #[test]
+    fn test_cancel_stops_operator() {
+        let token = CancellationToken::new();
+        let mut op = CancellableOperator::new(Box::new(EndlessOperator), token.clone());
+        assert_eq!(op.name(), "Endless");
+        assert!(op.next().unwrap().is_some());
+
+        token.cancel();
+        assert!(matches!(op.next(), Err(OperatorError::Cancelled)));
+    }
+
+    #[test]
+    fn test_deadline_stops_operator() {
+        let mut op = CancellableOperator::new(
+            Box::new(EndlessOperator),
+            CancellationToken::with_timeout(Duration::ZERO),
+        );
+        assert!(matches!(op.next(), Err(OperatorError::Cancelled)));
+
+        let token = CancellationToken::with_timeout(Duration::from_secs(30));
+        assert!(!token.is_cancelled());
+    }
+
+    #[test]
+    fn test_deadline_starts_on_first_pull() {
+        let token = CancellationToken::with_timeout(Duration::from_millis(20));
+        let mut op = CancellableOperator::new(Box::new(EndlessOperator), token.clone());
+
+        // Time before the plan runs doesn't count
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(!token.is_cancelled());
+        assert!(op.next().unwrap().is_some());
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(token.is_cancelled());
+        assert!(matches!(op.next(), Err(OperatorError::Cancelled)));
+    }
+}
@@ -124,10 +124,16 @@ impl ExpandOperator {
         let epoch = self.viewing_epoch;
         let tx = self.tx_id.unwrap_or(TxId::SYSTEM);
 
-        // Get edges from this node
-        let edges: Vec<(NodeId, EdgeId)> = self
-            .store
-            .edges_from(source_id, self.direction)
+        // Get edges from this node, only the visible ones with a tx context
+        let candidates: Vec<(NodeId, EdgeId)> = match epoch {
+            Some(epoch) => self
+                .store
+                .edges_from_versioned(source_id, self.direction, epoch, tx),
+            None => self.store.edges_from(source_id, self.direction).collect(),
+        };
+
+        let edges: Vec<(NodeId, EdgeId)> = candidates
+            .into_iter()
             .filter(|(target_id, edge_id)| {
                 // Filter by edge type if specified
                 let type_matches = if let Some(ref filter_type) = self.edge_type {
@@ -144,15 +150,11 @@ impl ExpandOperator {
                     return false;
                 }
 
-                // Filter by visibility if we have tx context
+                // The target must be visible too
                 if let Some(epoch) = epoch {
-                    // Check if edge and target node are visible
-                    let edge_visible = self.store.get_edge_versioned(*edge_id, epoch, tx).is_some();
-                    let target_visible = self
-                        .store
+                    self.store
                         .get_node_versioned(*target_id, epoch, tx)
-                        .is_some();
-                    edge_visible && target_visible
+                        .is_some()
                 } else {
                     true
                 }
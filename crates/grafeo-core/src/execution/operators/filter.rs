@@ -1,18 +1,29 @@
 //! Filter operator for applying predicates.
 
-use super::{Operator, OperatorResult};
+use super::{Operator, OperatorError, OperatorResult};
 use crate::execution::{DataChunk, SelectionVector};
 use crate::graph::Direction;
-use crate::graph::lpg::LpgStore;
-use grafeo_common::types::{PropertyKey, Value};
+use crate::graph::lpg::{Edge, LpgStore, Node};
+use grafeo_common::types::{
+    Date, Duration, EdgeId, EpochId, NodeId, PropertyKey, Timestamp, TxId, Value,
+};
+use parking_lot::Mutex;
 use regex::Regex;
 use std::collections::{BTreeMap, HashMap};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
 
 /// A predicate for filtering rows.
 pub trait Predicate: Send + Sync {
     /// Evaluates the predicate for a row.
     fn evaluate(&self, chunk: &DataChunk, row: usize) -> bool;
+
+    /// Takes the first error raised while evaluating, if any.
+    ///
+    /// Most predicates can't fail, so the default never reports one.
+    fn take_error(&self) -> Option<OperatorError> {
+        None
+    }
 }
 
 /// A comparison operator.
@@ -110,6 +121,12 @@ pub struct ExpressionPredicate {
     variable_columns: HashMap<String, usize>,
     /// The graph store for property lookups.
     store: Arc<LpgStore>,
+    /// Epoch to read nodes and edges at; the latest state if unset.
+    viewing_epoch: Option<EpochId>,
+    /// Transaction reading the nodes and edges.
+    tx_id: Option<TxId>,
+    /// First evaluation error, reported through [`Predicate::take_error`].
+    error: Mutex<Option<String>>,
 }
 
 /// A filter expression that can be evaluated.
@@ -149,6 +166,8 @@ pub enum FilterExpression {
         /// Arguments.
         args: Vec<FilterExpression>,
     },
+    /// `rand()`, drawing from the query's random source.
+    Random(Arc<RandomSource>),
     /// List literal.
     List(Vec<FilterExpression>),
     /// Map literal (e.g., {name: 'Alice', age: 30}).
@@ -170,6 +189,11 @@ pub enum FilterExpression {
         end: Option<Box<FilterExpression>>,
     },
     /// CASE expression.
+    ///
+    /// Branches are tried in order and only the first match is evaluated;
+    /// with no match and no ELSE the result is null. Results aren't coerced:
+    /// each row gets the value of the branch it took, so branches of
+    /// different types give a column of mixed types.
     Case {
         /// Test expression (for simple CASE).
         operand: Option<Box<FilterExpression>>,
@@ -281,9 +305,48 @@ impl ExpressionPredicate {
             expression,
             variable_columns,
             store,
+            viewing_epoch: None,
+            tx_id: None,
+            error: Mutex::new(None),
         }
     }
 
+    /// Reads nodes and edges as a transaction sees them at `epoch`.
+    #[must_use]
+    pub fn with_tx_context(mut self, epoch: EpochId, tx_id: Option<TxId>) -> Self {
+        self.viewing_epoch = Some(epoch);
+        self.tx_id = tx_id;
+        self
+    }
+
+    /// Looks up a node, as the transaction context sees it if one is set.
+    fn node(&self, id: NodeId) -> Option<Node> {
+        match self.viewing_epoch {
+            Some(epoch) => {
+                self.store
+                    .get_node_versioned(id, epoch, self.tx_id.unwrap_or(TxId::SYSTEM))
+            }
+            None => self.store.get_node(id),
+        }
+    }
+
+    /// Looks up an edge, as the transaction context sees it if one is set.
+    fn edge(&self, id: EdgeId) -> Option<Edge> {
+        match self.viewing_epoch {
+            Some(epoch) => {
+                self.store
+                    .get_edge_versioned(id, epoch, self.tx_id.unwrap_or(TxId::SYSTEM))
+            }
+            None => self.store.get_edge(id),
+        }
+    }
+
+    /// Records an evaluation error, keeping the first one, and yields no value.
+    fn fail(&self, message: &str) -> Option<Value> {
+        self.error.lock().get_or_insert_with(|| message.to_string());
+        None
+    }
+
     /// Evaluates the expression for a specific row in a chunk, returning the result value.
     /// This is useful for evaluating expressions in contexts like RETURN clauses.
     pub fn eval_at(&self, chunk: &DataChunk, row: usize) -> Option<Value> {
@@ -307,17 +370,25 @@ impl ExpressionPredicate {
                 let col = chunk.column(col_idx)?;
                 // Try as node first
                 if let Some(node_id) = col.get_node_id(row) {
-                    if let Some(node) = self.store.get_node(node_id) {
+                    if let Some(node) = self.node(node_id) {
                         return node.get_property(property).cloned();
                     }
                 }
                 // Try as edge if node lookup failed
                 if let Some(edge_id) = col.get_edge_id(row) {
-                    if let Some(edge) = self.store.get_edge(edge_id) {
+                    if let Some(edge) = self.edge(edge_id) {
                         return edge.get_property(property).cloned();
                     }
                 }
-                None
+                // Otherwise `map.key` on a map value; a missing key is null
+                match col.get_value(row)? {
+                    Value::Map(m) => Some(
+                        m.get(&PropertyKey::new(property.as_str()))
+                            .cloned()
+                            .unwrap_or(Value::Null),
+                    ),
+                    _ => None,
+                }
             }
             FilterExpression::Binary { left, op, right } => {
                 // For IN operator, right side is a list that we evaluate specially
@@ -336,6 +407,7 @@ impl ExpressionPredicate {
             FilterExpression::FunctionCall { name, args } => {
                 self.eval_function(name, args, chunk, row)
             }
+            FilterExpression::Random(source) => Some(Value::Float64(source.next_f64())),
             FilterExpression::List(items) => {
                 let values: Vec<Value> = items
                     .iter()
@@ -356,88 +428,56 @@ impl ExpressionPredicate {
             FilterExpression::IndexAccess { base, index } => {
                 let base_val = self.eval_expr(base, chunk, row)?;
                 let index_val = self.eval_expr(index, chunk, row)?;
+                // Out-of-range indexes and missing keys give null
                 match (&base_val, &index_val) {
-                    (Value::List(items), Value::Int64(i)) => {
-                        let idx = if *i < 0 {
-                            // Negative indexing from end
-                            let len = items.len() as i64;
-                            (len + i) as usize
-                        } else {
-                            *i as usize
-                        };
-                        items.get(idx).cloned()
-                    }
+                    (Value::List(items), Value::Int64(i)) => Some(
+                        list_index(*i, items.len()).map_or(Value::Null, |idx| items[idx].clone()),
+                    ),
                     (Value::String(s), Value::Int64(i)) => {
-                        let idx = if *i < 0 {
-                            let len = s.len() as i64;
-                            (len + i) as usize
-                        } else {
-                            *i as usize
-                        };
-                        s.chars()
-                            .nth(idx)
-                            .map(|c| Value::String(c.to_string().into()))
+                        let idx = list_index(*i, s.chars().count());
+                        Some(idx.map_or(Value::Null, |idx| {
+                            s.chars()
+                                .nth(idx)
+                                .map_or(Value::Null, |c| Value::String(c.to_string().into()))
+                        }))
                     }
                     (Value::Map(m), Value::String(key)) => {
                         let prop_key = PropertyKey::new(key.as_ref());
-                        m.get(&prop_key).cloned()
+                        Some(m.get(&prop_key).cloned().unwrap_or(Value::Null))
                     }
+                    (Value::Null, _) | (_, Value::Null) => Some(Value::Null),
                     _ => None,
                 }
             }
             FilterExpression::SliceAccess { base, start, end } => {
                 let base_val = self.eval_expr(base, chunk, row)?;
-                let start_idx = start
+                let bound = |expr: &Option<Box<FilterExpression>>| match expr
                     .as_ref()
-                    .and_then(|s| self.eval_expr(s, chunk, row))
-                    .and_then(|v| {
-                        if let Value::Int64(i) = v {
-                            Some(i as usize)
-                        } else {
-                            None
-                        }
-                    })
-                    .unwrap_or(0);
+                    .map(|e| self.eval_expr(e, chunk, row))
+                {
+                    None => Some(None),
+                    Some(Some(Value::Int64(i))) => Some(Some(i)),
+                    Some(_) => None,
+                };
+                let (start, end) = match (bound(start), bound(end)) {
+                    (Some(start), Some(end)) => (start, end),
+                    // A null or non-integer bound gives null
+                    _ => return Some(Value::Null),
+                };
 
                 match &base_val {
                     Value::List(items) => {
-                        let end_idx = end
-                            .as_ref()
-                            .and_then(|e| self.eval_expr(e, chunk, row))
-                            .and_then(|v| {
-                                if let Value::Int64(i) = v {
-                                    Some(i as usize)
-                                } else {
-                                    None
-                                }
-                            })
-                            .unwrap_or(items.len());
-                        let sliced: Vec<Value> = items
-                            .get(start_idx..end_idx.min(items.len()))
-                            .unwrap_or(&[])
-                            .to_vec();
-                        Some(Value::List(sliced.into()))
+                        let range = slice_range(start, end, items.len());
+                        Some(Value::List(items[range].to_vec().into()))
                     }
                     Value::String(s) => {
                         let chars: Vec<char> = s.chars().collect();
-                        let end_idx = end
-                            .as_ref()
-                            .and_then(|e| self.eval_expr(e, chunk, row))
-                            .and_then(|v| {
-                                if let Value::Int64(i) = v {
-                                    Some(i as usize)
-                                } else {
-                                    None
-                                }
-                            })
-                            .unwrap_or(chars.len());
-                        let sliced: String = chars
-                            .get(start_idx..end_idx.min(chars.len()))
-                            .unwrap_or(&[])
-                            .iter()
-                            .collect();
-                        Some(Value::String(sliced.into()))
+                        let range = slice_range(start, end, chars.len());
+                        Some(Value::String(
+                            chars[range].iter().collect::<String>().into(),
+                        ))
                     }
+                    Value::Null => Some(Value::Null),
                     _ => None,
                 }
             }
@@ -468,7 +508,7 @@ impl ExpressionPredicate {
                 let col_idx = *self.variable_columns.get(variable)?;
                 let col = chunk.column(col_idx)?;
                 let node_id = col.get_node_id(row)?;
-                let node = self.store.get_node(node_id)?;
+                let node = self.node(node_id)?;
                 let labels: Vec<Value> = node
                     .labels
                     .iter()
@@ -480,7 +520,7 @@ impl ExpressionPredicate {
                 let col_idx = *self.variable_columns.get(variable)?;
                 let col = chunk.column(col_idx)?;
                 let edge_id = col.get_edge_id(row)?;
-                let edge = self.store.get_edge(edge_id)?;
+                let edge = self.edge(edge_id)?;
                 Some(Value::String(edge.edge_type.clone()))
             }
             FilterExpression::ListComprehension {
@@ -624,10 +664,23 @@ impl ExpressionPredicate {
                 .compare_values(left, right)
                 .map(|c| Value::Bool(c >= 0)),
             // Arithmetic operators
-            BinaryFilterOp::Add => self.eval_arithmetic(left, right, |a, b| a + b, |a, b| a + b),
-            BinaryFilterOp::Sub => self.eval_arithmetic(left, right, |a, b| a - b, |a, b| a - b),
-            BinaryFilterOp::Mul => self.eval_arithmetic(left, right, |a, b| a * b, |a, b| a * b),
-            BinaryFilterOp::Div => self.eval_arithmetic(left, right, |a, b| a / b, |a, b| a / b),
+            BinaryFilterOp::Add => left
+                .temporal_add(right, false)
+                .or_else(|| self.eval_arithmetic(left, right, i64::checked_add, |a, b| a + b)),
+            BinaryFilterOp::Sub => left
+                .temporal_add(right, true)
+                .or_else(|| self.eval_arithmetic(left, right, i64::checked_sub, |a, b| a - b)),
+            BinaryFilterOp::Mul => {
+                self.eval_arithmetic(left, right, i64::checked_mul, |a, b| a * b)
+            }
+            BinaryFilterOp::Div => {
+                // Division by zero is null, for floats as well as integers
+                let zero = matches!(right, Value::Int64(0)) || right.as_float64() == Some(0.0);
+                if zero {
+                    return Some(Value::Null);
+                }
+                self.eval_arithmetic(left, right, i64::checked_div, |a, b| a / b)
+            }
             BinaryFilterOp::Mod => self.eval_modulo(left, right),
             // String operators
             BinaryFilterOp::StartsWith => {
@@ -681,6 +734,8 @@ impl ExpressionPredicate {
         }
     }
 
+    /// Applies an arithmetic operator. Int with int stays int; anything with a
+    /// float is a float. Integer overflow gives null.
     fn eval_arithmetic<F1, F2>(
         &self,
         left: &Value,
@@ -689,14 +744,17 @@ impl ExpressionPredicate {
         float_op: F2,
     ) -> Option<Value>
     where
-        F1: Fn(i64, i64) -> i64,
+        F1: Fn(i64, i64) -> Option<i64>,
         F2: Fn(f64, f64) -> f64,
     {
         match (left, right) {
-            (Value::Int64(a), Value::Int64(b)) => Some(Value::Int64(int_op(*a, *b))),
+            (Value::Int64(a), Value::Int64(b)) => {
+                Some(int_op(*a, *b).map_or(Value::Null, Value::Int64))
+            }
             (Value::Float64(a), Value::Float64(b)) => Some(Value::Float64(float_op(*a, *b))),
             (Value::Int64(a), Value::Float64(b)) => Some(Value::Float64(float_op(*a as f64, *b))),
             (Value::Float64(a), Value::Int64(b)) => Some(Value::Float64(float_op(*a, *b as f64))),
+            (Value::Null, _) | (_, Value::Null) => Some(Value::Null),
             _ => None,
         }
     }
@@ -762,7 +820,7 @@ impl ExpressionPredicate {
                     let col_idx = *self.variable_columns.get(var)?;
                     let col = chunk.column(col_idx)?;
                     let node_id = col.get_node_id(row)?;
-                    let node = self.store.get_node(node_id)?;
+                    let node = self.node(node_id)?;
                     let labels: Vec<Value> = node
                         .labels
                         .iter()
@@ -780,7 +838,7 @@ impl ExpressionPredicate {
                     let col_idx = *self.variable_columns.get(var)?;
                     let col = chunk.column(col_idx)?;
                     let edge_id = col.get_edge_id(row)?;
-                    let edge = self.store.get_edge(edge_id)?;
+                    let edge = self.edge(edge_id)?;
                     return Some(Value::String(edge.edge_type.clone()));
                 }
                 None
@@ -792,7 +850,9 @@ impl ExpressionPredicate {
                 let val = self.eval_expr(&args[0], chunk, row)?;
                 match val {
                     Value::List(items) => Some(Value::Int64(items.len() as i64)),
-                    Value::String(s) => Some(Value::Int64(s.len() as i64)),
+                    Value::String(s) => Some(Value::Int64(s.chars().count() as i64)),
+                    Value::Map(m) => Some(Value::Int64(m.len() as i64)),
+                    Value::Null => Some(Value::Null),
                     _ => None,
                 }
             }
@@ -880,7 +940,7 @@ impl ExpressionPredicate {
                     _ => return None,
                 };
                 // Check if the node has this label
-                let node = self.store.get_node(node_id)?;
+                let node = self.node(node_id)?;
                 let has_label = node.labels.iter().any(|l| l.as_ref() == label.as_ref());
                 Some(Value::Bool(has_label))
             }
@@ -891,7 +951,8 @@ impl ExpressionPredicate {
                 }
                 let val = self.eval_expr(&args[0], chunk, row)?;
                 match val {
-                    Value::List(items) => items.first().cloned(),
+                    Value::List(items) => Some(items.first().cloned().unwrap_or(Value::Null)),
+                    Value::Null => Some(Value::Null),
                     _ => None,
                 }
             }
@@ -919,7 +980,8 @@ impl ExpressionPredicate {
                 }
                 let val = self.eval_expr(&args[0], chunk, row)?;
                 match val {
-                    Value::List(items) => items.last().cloned(),
+                    Value::List(items) => Some(items.last().cloned().unwrap_or(Value::Null)),
+                    Value::Null => Some(Value::Null),
                     _ => None,
                 }
             }
@@ -941,10 +1003,150 @@ impl ExpressionPredicate {
                     _ => None,
                 }
             }
+            name @ ("tolower" | "lower" | "toupper" | "upper" | "trim" | "ltrim" | "rtrim"
+            | "substring" | "split" | "replace") => {
+                let values = args
+                    .iter()
+                    .map(|arg| self.eval_expr(arg, chunk, row))
+                    .collect::<Option<Vec<_>>>()?;
+                // Null in, null out
+                if values.iter().any(|v| matches!(v, Value::Null)) {
+                    return Some(Value::Null);
+                }
+                self.eval_string_function(name, &values)
+            }
+            "rand" if args.is_empty() => Some(Value::Float64(RandomSource::global().next_f64())),
+            name @ ("abs" | "ceil" | "floor" | "round" | "sqrt" | "sign" | "pow" | "power") => {
+                let values = args
+                    .iter()
+                    .map(|arg| self.eval_expr(arg, chunk, row))
+                    .collect::<Option<Vec<_>>>()?;
+                if values.iter().any(|v| matches!(v, Value::Null)) {
+                    return Some(Value::Null);
+                }
+                eval_math_function(name, &values)
+            }
+            "keys" => {
+                // keys(map) or keys(node) - property names in key order
+                if args.len() != 1 {
+                    return None;
+                }
+                let properties = self.eval_properties(&args[0], chunk, row)?;
+                let keys: Vec<Value> = properties
+                    .keys()
+                    .map(|k| Value::String(k.as_str().into()))
+                    .collect();
+                Some(Value::List(keys.into()))
+            }
+            "values" => {
+                // values(map) or values(node) - property values in key order
+                if args.len() != 1 {
+                    return None;
+                }
+                let properties = self.eval_properties(&args[0], chunk, row)?;
+                Some(Value::List(
+                    properties.values().cloned().collect::<Vec<_>>().into(),
+                ))
+            }
+            "date" => {
+                // date() is today; date(string) parses; date(datetime) truncates
+                match args {
+                    [] => Some(Value::Date(Date::from_timestamp(Timestamp::now()))),
+                    [arg] => match self.eval_expr(arg, chunk, row)? {
+                        Value::String(s) => Date::parse(&s).map(Value::Date),
+                        Value::Timestamp(ts) => Some(Value::Date(Date::from_timestamp(ts))),
+                        Value::Date(d) => Some(Value::Date(d)),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            "datetime" | "timestamp" => match args {
+                [] => Some(Value::Timestamp(Timestamp::now())),
+                [arg] => match self.eval_expr(arg, chunk, row)? {
+                    Value::String(s) => Timestamp::parse(&s).map(Value::Timestamp),
+                    Value::Date(d) => Some(Value::Timestamp(d.to_timestamp())),
+                    Value::Timestamp(ts) => Some(Value::Timestamp(ts)),
+                    _ => None,
+                },
+                _ => None,
+            },
+            "duration" => {
+                if args.len() != 1 {
+                    return None;
+                }
+                match self.eval_expr(&args[0], chunk, row)? {
+                    Value::String(s) => Duration::parse(&s).map(Value::Duration),
+                    Value::Duration(d) => Some(Value::Duration(d)),
+                    _ => None,
+                }
+            }
             _ => None, // Unknown function
         }
     }
 
+    fn eval_string_function(&self, name: &str, args: &[Value]) -> Option<Value> {
+        let string = |s: String| Some(Value::String(s.into()));
+        match (name, args) {
+            ("tolower" | "lower", [Value::String(s)]) => string(s.to_lowercase()),
+            ("toupper" | "upper", [Value::String(s)]) => string(s.to_uppercase()),
+            ("trim", [Value::String(s)]) => string(s.trim().to_string()),
+            ("ltrim", [Value::String(s)]) => string(s.trim_start().to_string()),
+            ("rtrim", [Value::String(s)]) => string(s.trim_end().to_string()),
+            // substring(s, start[, length]) counts characters from zero
+            ("substring", [Value::String(s), Value::Int64(start), rest @ ..]) => {
+                let length = match rest {
+                    [] => usize::MAX,
+                    [Value::Int64(length)] => (*length).max(0) as usize,
+                    _ => return None,
+                };
+                string(
+                    s.chars()
+                        .skip((*start).max(0) as usize)
+                        .take(length)
+                        .collect(),
+                )
+            }
+            ("split", [Value::String(s), Value::String(delimiter)]) => {
+                if delimiter.is_empty() {
+                    return self.fail("split() delimiter must not be empty");
+                }
+                let parts: Vec<Value> = s
+                    .split(delimiter.as_ref())
+                    .map(|part| Value::String(part.into()))
+                    .collect();
+                Some(Value::List(parts.into()))
+            }
+            ("replace", [Value::String(s), Value::String(from), Value::String(to)]) => {
+                string(s.replace(from.as_ref(), to))
+            }
+            _ => None,
+        }
+    }
+
+    /// Evaluates an argument as a set of properties: a map value, or the
+    /// properties of a bound node or edge.
+    fn eval_properties(
+        &self,
+        expr: &FilterExpression,
+        chunk: &DataChunk,
+        row: usize,
+    ) -> Option<BTreeMap<PropertyKey, Value>> {
+        if let FilterExpression::Variable(var) = expr {
+            let col = chunk.column(*self.variable_columns.get(var)?)?;
+            if let Some(node_id) = col.get_node_id(row) {
+                return self.node(node_id).map(|n| n.properties);
+            }
+            if let Some(edge_id) = col.get_edge_id(row) {
+                return self.edge(edge_id).map(|e| e.properties);
+            }
+        }
+        match self.eval_expr(expr, chunk, row)? {
+            Value::Map(m) => Some(m.as_ref().clone()),
+            _ => None,
+        }
+    }
+
     fn eval_case(
         &self,
         operand: Option<&FilterExpression>,
@@ -953,20 +1155,30 @@ impl ExpressionPredicate {
         chunk: &DataChunk,
         row: usize,
     ) -> Option<Value> {
+        // A WHEN that is null or can't be evaluated doesn't match
         if let Some(test_expr) = operand {
-            // Simple CASE: CASE expr WHEN val1 THEN res1 ...
-            let test_val = self.eval_expr(test_expr, chunk, row)?;
-            for (when_expr, then_expr) in when_clauses {
-                let when_val = self.eval_expr(when_expr, chunk, row)?;
-                if self.values_equal(&test_val, &when_val) {
-                    return self.eval_expr(then_expr, chunk, row);
+            // Simple CASE: CASE expr WHEN val1 THEN res1 ...; null equals nothing
+            let test_val = self
+                .eval_expr(test_expr, chunk, row)
+                .filter(|v| !matches!(v, Value::Null));
+            if let Some(test_val) = test_val {
+                for (when_expr, then_expr) in when_clauses {
+                    let matched = self
+                        .eval_expr(when_expr, chunk, row)
+                        .is_some_and(|when_val| {
+                            !matches!(when_val, Value::Null)
+                                && self.values_equal(&test_val, &when_val)
+                        });
+                    if matched {
+                        return self.eval_expr(then_expr, chunk, row);
+                    }
                 }
             }
         } else {
             // Searched CASE: CASE WHEN cond1 THEN res1 ...
             for (when_expr, then_expr) in when_clauses {
-                let when_val = self.eval_expr(when_expr, chunk, row)?;
-                if when_val.as_bool() == Some(true) {
+                let when_val = self.eval_expr(when_expr, chunk, row);
+                if when_val.and_then(|v| v.as_bool()) == Some(true) {
                     return self.eval_expr(then_expr, chunk, row);
                 }
             }
@@ -1006,10 +1218,11 @@ impl ExpressionPredicate {
             (Value::Int64(a), Value::Int64(b)) => a == b,
             (Value::Float64(a), Value::Float64(b)) => (a - b).abs() < f64::EPSILON,
             (Value::String(a), Value::String(b)) => a == b,
+            (Value::Bytes(a), Value::Bytes(b)) => a == b,
             (Value::Int64(a), Value::Float64(b)) | (Value::Float64(b), Value::Int64(a)) => {
                 (*a as f64 - b).abs() < f64::EPSILON
             }
-            _ => false,
+            _ => left.temporal_cmp(right) == Some(std::cmp::Ordering::Equal),
         }
     }
 
@@ -1026,6 +1239,7 @@ impl ExpressionPredicate {
                 }
             }
             (Value::String(a), Value::String(b)) => Some(a.cmp(b) as i32),
+            (Value::Bytes(a), Value::Bytes(b)) => Some(a.cmp(b) as i32),
             (Value::Int64(a), Value::Float64(b)) => {
                 let af = *a as f64;
                 if af < *b {
@@ -1046,7 +1260,7 @@ impl ExpressionPredicate {
                     Some(0)
                 }
             }
-            _ => None,
+            _ => left.temporal_cmp(right).map(|c| c as i32),
         }
     }
 }
@@ -1058,6 +1272,10 @@ impl Predicate for ExpressionPredicate {
             _ => false,
         }
     }
+
+    fn take_error(&self) -> Option<OperatorError> {
+        self.error.lock().take().map(OperatorError::Execution)
+    }
 }
 
 /// A filter operator that applies a predicate to filter rows.
@@ -1087,6 +1305,9 @@ impl Operator for FilterOperator {
         let count = chunk.total_row_count();
         let selection =
             SelectionVector::from_predicate(count, |row| self.predicate.evaluate(&chunk, row));
+        if let Some(err) = self.predicate.take_error() {
+            return Err(err);
+        }
 
         // If nothing passes, skip to next chunk
         if selection.is_empty() {
@@ -1106,6 +1327,128 @@ impl Operator for FilterOperator {
     }
 }
 
+/// Seedable source of random numbers for `rand()`.
+///
+/// A planner hands one source to every `rand()` in a query, so a fixed seed
+/// gives the same sequence on every run.
+pub struct RandomSource {
+    /// SplitMix64 state.
+    state: Mutex<u64>,
+}
+
+impl RandomSource {
+    /// Creates a source with a fixed seed.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Mutex::new(seed),
+        }
+    }
+
+    /// Creates a source seeded from the clock.
+    #[must_use]
+    pub fn from_entropy() -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        Self::new(nanos ^ COUNTER.fetch_add(1, Ordering::Relaxed).rotate_left(32))
+    }
+
+    /// The source used when no planner supplied one.
+    fn global() -> &'static Self {
+        static GLOBAL: OnceLock<RandomSource> = OnceLock::new();
+        GLOBAL.get_or_init(Self::from_entropy)
+    }
+
+    /// Returns the next value, uniform in `[0, 1)`.
+    pub fn next_f64(&self) -> f64 {
+        let mut state = self.state.lock();
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        // The top 53 bits fill an f64 mantissa exactly
+        ((z ^ (z >> 31)) >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl std::fmt::Debug for RandomSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RandomSource").finish_non_exhaustive()
+    }
+}
+
+/// Evaluates a math function over non-null arguments.
+///
+/// Functions that keep the value's magnitude keep its type: `abs`, `ceil`,
+/// `floor` and `round` of an integer are integers. `sqrt` and `pow` always
+/// return floats, and `sign` always returns an integer. Results with no real
+/// value - the square root of a negative number, a NaN - are null rather
+/// than an error, as is integer overflow.
+fn eval_math_function(name: &str, args: &[Value]) -> Option<Value> {
+    let float = |f: f64| {
+        Some(if f.is_nan() {
+            Value::Null
+        } else {
+            Value::Float64(f)
+        })
+    };
+    match (name, args) {
+        ("abs", [Value::Int64(i)]) => Some(i.checked_abs().map_or(Value::Null, Value::Int64)),
+        ("abs", [Value::Float64(f)]) => float(f.abs()),
+        ("ceil" | "floor" | "round", [Value::Int64(i)]) => Some(Value::Int64(*i)),
+        ("ceil", [Value::Float64(f)]) => float(f.ceil()),
+        ("floor", [Value::Float64(f)]) => float(f.floor()),
+        // Halves round away from zero
+        ("round", [Value::Float64(f)]) => float(f.round()),
+        ("sqrt", [x]) => {
+            let x = x.as_float64().or_else(|| x.as_int64().map(|i| i as f64))?;
+            if x < 0.0 {
+                Some(Value::Null)
+            } else {
+                float(x.sqrt())
+            }
+        }
+        ("sign", [Value::Int64(i)]) => Some(Value::Int64(i.signum())),
+        ("sign", [Value::Float64(f)]) if f.is_nan() => Some(Value::Null),
+        ("sign", [Value::Float64(f)]) => Some(Value::Int64(if *f > 0.0 {
+            1
+        } else if *f < 0.0 {
+            -1
+        } else {
+            0
+        })),
+        ("pow" | "power", [base, exp]) => {
+            let to_f64 = |v: &Value| v.as_float64().or_else(|| v.as_int64().map(|i| i as f64));
+            float(to_f64(base)?.powf(to_f64(exp)?))
+        }
+        _ => None,
+    }
+}
+
+/// Resolves a list index, counting from the end when negative.
+fn list_index(index: i64, len: usize) -> Option<usize> {
+    let len = len as i64;
+    let index = if index < 0 { len + index } else { index };
+    (0..len).contains(&index).then_some(index as usize)
+}
+
+/// Resolves `[start..end]` slice bounds against a length.
+///
+/// Missing bounds mean the start or end of the list, negative ones count
+/// from the end, and everything is clamped into range, so a slice never
+/// fails - at worst it's empty.
+fn slice_range(start: Option<i64>, end: Option<i64>, len: usize) -> std::ops::Range<usize> {
+    let resolve = |bound: i64| {
+        let bound = if bound < 0 { len as i64 + bound } else { bound };
+        bound.clamp(0, len as i64) as usize
+    };
+    let start = start.map_or(0, resolve);
+    let end = end.map_or(len, resolve);
+    start..end.max(start)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1384,4 +1727,477 @@ mod tests {
             panic!("Expected List value");
         }
     }
+
+    /// Evaluates an expression that doesn't depend on the row.
+    fn eval_constant(expr: FilterExpression) -> Option<Value> {
+        let chunk = DataChunkBuilder::new(&[LogicalType::Int64]).finish();
+        ExpressionPredicate::new(expr, HashMap::new(), Arc::new(LpgStore::new())).eval(&chunk, 0)
+    }
+
+    fn int_list(items: &[i64]) -> FilterExpression {
+        FilterExpression::List(
+            items
+                .iter()
+                .map(|&i| FilterExpression::Literal(Value::Int64(i)))
+                .collect(),
+        )
+    }
+
+    fn call(name: &str, arg: FilterExpression) -> FilterExpression {
+        FilterExpression::FunctionCall {
+            name: name.to_string(),
+            args: vec![arg],
+        }
+    }
+
+    #[test]
+    fn test_index_access_out_of_bounds_is_null() {
+        let index = |i: i64| {
+            eval_constant(FilterExpression::IndexAccess {
+                base: Box::new(int_list(&[1, 2, 3])),
+                index: Box::new(FilterExpression::Literal(Value::Int64(i))),
+            })
+        };
+        assert_eq!(index(0), Some(Value::Int64(1)));
+        assert_eq!(index(-3), Some(Value::Int64(1)));
+        assert_eq!(index(3), Some(Value::Null));
+        assert_eq!(index(-4), Some(Value::Null));
+
+        let map = FilterExpression::Map(vec![(
+            "a".to_string(),
+            FilterExpression::Literal(Value::Int64(1)),
+        )]);
+        let key = |k: &str| {
+            eval_constant(FilterExpression::IndexAccess {
+                base: Box::new(map.clone()),
+                index: Box::new(FilterExpression::Literal(Value::String(k.into()))),
+            })
+        };
+        assert_eq!(key("a"), Some(Value::Int64(1)));
+        assert_eq!(key("b"), Some(Value::Null));
+
+        // Strings index by character, not byte
+        let result = eval_constant(FilterExpression::IndexAccess {
+            base: Box::new(FilterExpression::Literal(Value::String("héllo".into()))),
+            index: Box::new(FilterExpression::Literal(Value::Int64(-4))),
+        });
+        assert_eq!(result, Some(Value::String("é".into())));
+    }
+
+    #[test]
+    fn test_slice_bounds_clamping() {
+        let slice = |start: Option<i64>, end: Option<i64>| {
+            let bound =
+                |b: Option<i64>| b.map(|i| Box::new(FilterExpression::Literal(Value::Int64(i))));
+            eval_constant(FilterExpression::SliceAccess {
+                base: Box::new(int_list(&[1, 2, 3, 4, 5])),
+                start: bound(start),
+                end: bound(end),
+            })
+        };
+        let list = |items: &[i64]| {
+            Some(Value::List(
+                items
+                    .iter()
+                    .map(|&i| Value::Int64(i))
+                    .collect::<Vec<_>>()
+                    .into(),
+            ))
+        };
+
+        assert_eq!(slice(Some(1), Some(3)), list(&[2, 3]));
+        assert_eq!(slice(Some(0), Some(10)), list(&[1, 2, 3, 4, 5]));
+        assert_eq!(slice(Some(-2), None), list(&[4, 5]));
+        assert_eq!(slice(None, Some(-1)), list(&[1, 2, 3, 4]));
+        assert_eq!(slice(Some(-10), Some(2)), list(&[1, 2]));
+        assert_eq!(slice(Some(3), Some(1)), list(&[]));
+        assert_eq!(slice(Some(7), None), list(&[]));
+    }
+
+    #[test]
+    fn test_collection_functions() {
+        assert_eq!(
+            eval_constant(call("size", int_list(&[1, 2, 3]))),
+            Some(Value::Int64(3))
+        );
+        assert_eq!(
+            eval_constant(call(
+                "size",
+                FilterExpression::Literal(Value::String("héllo".into()))
+            )),
+            Some(Value::Int64(5))
+        );
+
+        let map = FilterExpression::Map(vec![
+            (
+                "name".to_string(),
+                FilterExpression::Literal(Value::String("Alix".into())),
+            ),
+            (
+                "age".to_string(),
+                FilterExpression::Literal(Value::Int64(30)),
+            ),
+        ]);
+        assert_eq!(
+            eval_constant(call("size", map.clone())),
+            Some(Value::Int64(2))
+        );
+        assert_eq!(
+            eval_constant(call("keys", map.clone())),
+            Some(Value::List(
+                vec![Value::String("age".into()), Value::String("name".into())].into()
+            ))
+        );
+        assert_eq!(
+            eval_constant(call("values", map)),
+            Some(Value::List(
+                vec![Value::Int64(30), Value::String("Alix".into())].into()
+            ))
+        );
+
+        assert_eq!(
+            eval_constant(call("head", int_list(&[1, 2, 3]))),
+            Some(Value::Int64(1))
+        );
+        assert_eq!(
+            eval_constant(call("last", int_list(&[1, 2, 3]))),
+            Some(Value::Int64(3))
+        );
+        assert_eq!(
+            eval_constant(call("tail", int_list(&[1, 2, 3]))),
+            Some(Value::List(vec![Value::Int64(2), Value::Int64(3)].into()))
+        );
+        assert_eq!(
+            eval_constant(call("head", int_list(&[]))),
+            Some(Value::Null)
+        );
+        assert_eq!(
+            eval_constant(call("last", int_list(&[]))),
+            Some(Value::Null)
+        );
+        assert_eq!(
+            eval_constant(call("tail", int_list(&[]))),
+            Some(Value::List(vec![].into()))
+        );
+    }
+
+    #[test]
+    fn test_keys_and_property_access_on_bound_values() {
+        let store = Arc::new(LpgStore::new());
+        let node = store.create_node_with_props(
+            &["Person"],
+            [("name", Value::from("Gus")), ("age", Value::Int64(40))],
+        );
+
+        let mut builder = DataChunkBuilder::new(&[LogicalType::Node, LogicalType::Any]);
+        builder.column_mut(0).unwrap().push_node_id(node);
+        let map: BTreeMap<PropertyKey, Value> =
+            [(PropertyKey::new("city"), Value::from("Leiden"))].into();
+        builder
+            .column_mut(1)
+            .unwrap()
+            .push_value(Value::Map(Arc::new(map)));
+        builder.advance_row();
+        let chunk = builder.finish();
+
+        let mut columns = HashMap::new();
+        columns.insert("n".to_string(), 0);
+        columns.insert("m".to_string(), 1);
+        let eval = |expr| {
+            ExpressionPredicate::new(expr, columns.clone(), Arc::clone(&store)).eval(&chunk, 0)
+        };
+
+        assert_eq!(
+            eval(call("keys", FilterExpression::Variable("n".to_string()))),
+            Some(Value::List(
+                vec![Value::String("age".into()), Value::String("name".into())].into()
+            ))
+        );
+        assert_eq!(
+            eval(FilterExpression::Property {
+                variable: "m".to_string(),
+                property: "city".to_string(),
+            }),
+            Some(Value::from("Leiden"))
+        );
+        assert_eq!(
+            eval(FilterExpression::Property {
+                variable: "m".to_string(),
+                property: "zip".to_string(),
+            }),
+            Some(Value::Null)
+        );
+    }
+
+    fn call_with(name: &str, args: &[Value]) -> Option<Value> {
+        eval_constant(FilterExpression::FunctionCall {
+            name: name.to_string(),
+            args: args
+                .iter()
+                .cloned()
+                .map(FilterExpression::Literal)
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn test_string_functions() {
+        let s = |s: &str| Value::String(s.into());
+
+        assert_eq!(call_with("toLower", &[s("HeLLo")]), Some(s("hello")));
+        assert_eq!(call_with("toUpper", &[s("HeLLo")]), Some(s("HELLO")));
+        assert_eq!(call_with("trim", &[s("  hi  ")]), Some(s("hi")));
+        assert_eq!(call_with("ltrim", &[s("  hi  ")]), Some(s("hi  ")));
+        assert_eq!(call_with("rtrim", &[s("  hi  ")]), Some(s("  hi")));
+        assert_eq!(
+            call_with("substring", &[s("héllo"), Value::Int64(1), Value::Int64(3)]),
+            Some(s("éll"))
+        );
+        assert_eq!(
+            call_with("substring", &[s("hello"), Value::Int64(2)]),
+            Some(s("llo"))
+        );
+        assert_eq!(
+            call_with("substring", &[s("hello"), Value::Int64(9)]),
+            Some(s(""))
+        );
+        assert_eq!(
+            call_with("split", &[s("a,b,,c"), s(",")]),
+            Some(Value::List(vec![s("a"), s("b"), s(""), s("c")].into()))
+        );
+        assert_eq!(
+            call_with("replace", &[s("banana"), s("an"), s("o")]),
+            Some(s("booa"))
+        );
+    }
+
+    #[test]
+    fn test_string_functions_propagate_null() {
+        let s = |s: &str| Value::String(s.into());
+
+        for name in ["toLower", "toUpper", "trim", "ltrim", "rtrim"] {
+            assert_eq!(call_with(name, &[Value::Null]), Some(Value::Null));
+        }
+        assert_eq!(
+            call_with("substring", &[Value::Null, Value::Int64(0)]),
+            Some(Value::Null)
+        );
+        assert_eq!(
+            call_with("substring", &[s("abc"), Value::Null]),
+            Some(Value::Null)
+        );
+        assert_eq!(
+            call_with("split", &[Value::Null, s(",")]),
+            Some(Value::Null)
+        );
+        assert_eq!(
+            call_with("split", &[s("a,b"), Value::Null]),
+            Some(Value::Null)
+        );
+        assert_eq!(
+            call_with("replace", &[s("abc"), Value::Null, s("x")]),
+            Some(Value::Null)
+        );
+    }
+
+    #[test]
+    fn test_split_empty_delimiter_errors() {
+        let expr = FilterExpression::Binary {
+            left: Box::new(FilterExpression::FunctionCall {
+                name: "split".to_string(),
+                args: vec![
+                    FilterExpression::Literal(Value::String("a,b".into())),
+                    FilterExpression::Literal(Value::String("".into())),
+                ],
+            }),
+            op: BinaryFilterOp::Eq,
+            right: Box::new(FilterExpression::Literal(Value::Null)),
+        };
+        let predicate = ExpressionPredicate::new(expr, HashMap::new(), Arc::new(LpgStore::new()));
+
+        let mut builder = DataChunkBuilder::new(&[LogicalType::Int64]);
+        builder.column_mut(0).unwrap().push_int64(1);
+        builder.advance_row();
+        let scan = MockScanOperator {
+            chunks: vec![builder.finish()],
+            position: 0,
+        };
+
+        let mut filter = FilterOperator::new(Box::new(scan), Box::new(predicate));
+        let err = filter.next().unwrap_err();
+        assert!(err.to_string().contains("delimiter must not be empty"));
+    }
+
+    #[test]
+    fn test_math_functions() {
+        let int = Value::Int64;
+        let float = Value::Float64;
+
+        assert_eq!(call_with("abs", &[int(-3)]), Some(int(3)));
+        assert_eq!(call_with("abs", &[float(-2.5)]), Some(float(2.5)));
+        // Rounding an integer keeps it an integer
+        for name in ["ceil", "floor", "round"] {
+            assert_eq!(call_with(name, &[int(7)]), Some(int(7)));
+        }
+        assert_eq!(call_with("ceil", &[float(1.2)]), Some(float(2.0)));
+        assert_eq!(call_with("floor", &[float(-1.2)]), Some(float(-2.0)));
+        assert_eq!(call_with("round", &[float(2.5)]), Some(float(3.0)));
+        assert_eq!(call_with("round", &[float(-2.5)]), Some(float(-3.0)));
+        // sqrt and pow always give floats, sign always an integer
+        assert_eq!(call_with("sqrt", &[int(16)]), Some(float(4.0)));
+        assert_eq!(call_with("pow", &[int(2), int(10)]), Some(float(1024.0)));
+        assert_eq!(
+            call_with("pow", &[float(4.0), float(0.5)]),
+            Some(float(2.0))
+        );
+        assert_eq!(call_with("sign", &[int(-9)]), Some(int(-1)));
+        assert_eq!(call_with("sign", &[float(0.0)]), Some(int(0)));
+        assert_eq!(call_with("sign", &[float(0.1)]), Some(int(1)));
+
+        for name in ["abs", "ceil", "floor", "round", "sqrt", "sign"] {
+            assert_eq!(call_with(name, &[Value::Null]), Some(Value::Null));
+        }
+        assert_eq!(call_with("pow", &[int(2), Value::Null]), Some(Value::Null));
+    }
+
+    #[test]
+    fn test_math_edge_cases_are_null() {
+        let int = Value::Int64;
+        let float = Value::Float64;
+        let arith = |left: Value, op: BinaryFilterOp, right: Value| {
+            eval_constant(FilterExpression::Binary {
+                left: Box::new(FilterExpression::Literal(left)),
+                op,
+                right: Box::new(FilterExpression::Literal(right)),
+            })
+        };
+
+        assert_eq!(call_with("sqrt", &[int(-4)]), Some(Value::Null));
+        assert_eq!(call_with("abs", &[int(i64::MIN)]), Some(Value::Null));
+        assert_eq!(
+            arith(int(1), BinaryFilterOp::Div, int(0)),
+            Some(Value::Null)
+        );
+        assert_eq!(
+            arith(float(1.0), BinaryFilterOp::Div, float(0.0)),
+            Some(Value::Null)
+        );
+        assert_eq!(
+            arith(int(i64::MAX), BinaryFilterOp::Add, int(1)),
+            Some(Value::Null)
+        );
+        // Int with int stays int; a float operand makes it a float
+        assert_eq!(arith(int(7), BinaryFilterOp::Div, int(2)), Some(int(3)));
+        assert_eq!(
+            arith(int(7), BinaryFilterOp::Div, float(2.0)),
+            Some(float(3.5))
+        );
+    }
+
+    #[test]
+    fn test_random_source_is_seedable() {
+        let draw = |source: &RandomSource| (0..5).map(|_| source.next_f64()).collect::<Vec<_>>();
+        let first = draw(&RandomSource::new(42));
+        assert_eq!(first, draw(&RandomSource::new(42)));
+        assert_ne!(first, draw(&RandomSource::new(43)));
+        assert!(first.iter().all(|x| (0.0..1.0).contains(x)));
+
+        let source = Arc::new(RandomSource::new(42));
+        let value = eval_constant(FilterExpression::Random(Arc::clone(&source)));
+        assert_eq!(value, Some(Value::Float64(first[0])));
+        assert_eq!(source.next_f64(), first[1]);
+    }
+
+    fn lit(value: Value) -> FilterExpression {
+        FilterExpression::Literal(value)
+    }
+
+    /// `split('a', '')`, which records an error whenever it's evaluated.
+    fn failing_expr() -> FilterExpression {
+        FilterExpression::FunctionCall {
+            name: "split".to_string(),
+            args: vec![lit(Value::from("a")), lit(Value::from(""))],
+        }
+    }
+
+    #[test]
+    fn test_case_searched_form() {
+        let case = |x: i64| {
+            eval_constant(FilterExpression::Case {
+                operand: None,
+                when_clauses: vec![
+                    (
+                        FilterExpression::Binary {
+                            left: Box::new(lit(Value::Int64(x))),
+                            op: BinaryFilterOp::Lt,
+                            right: Box::new(lit(Value::Int64(0))),
+                        },
+                        lit(Value::from("negative")),
+                    ),
+                    (
+                        FilterExpression::Binary {
+                            left: Box::new(lit(Value::Int64(x))),
+                            op: BinaryFilterOp::Lt,
+                            right: Box::new(lit(Value::Int64(10))),
+                        },
+                        lit(Value::from("small")),
+                    ),
+                ],
+                else_clause: Some(Box::new(lit(Value::from("large")))),
+            })
+        };
+        assert_eq!(case(-1), Some(Value::from("negative")));
+        // Both later conditions hold; the first one wins
+        assert_eq!(case(5), Some(Value::from("small")));
+        assert_eq!(case(50), Some(Value::from("large")));
+    }
+
+    #[test]
+    fn test_case_simple_form() {
+        let case = |operand: Value| {
+            eval_constant(FilterExpression::Case {
+                operand: Some(Box::new(lit(operand))),
+                when_clauses: vec![
+                    (lit(Value::Int64(1)), lit(Value::from("one"))),
+                    (lit(Value::Null), lit(Value::from("null"))),
+                    (lit(Value::Int64(2)), lit(Value::from("two"))),
+                ],
+                else_clause: None,
+            })
+        };
+        assert_eq!(case(Value::Int64(2)), Some(Value::from("two")));
+        // Equality follows the evaluator's numeric comparison
+        assert_eq!(case(Value::Float64(1.0)), Some(Value::from("one")));
+        // No match and no ELSE gives null, and null never matches WHEN null
+        assert_eq!(case(Value::Int64(3)), Some(Value::Null));
+        assert_eq!(case(Value::Null), Some(Value::Null));
+    }
+
+    #[test]
+    fn test_case_short_circuits() {
+        let predicate = ExpressionPredicate::new(
+            FilterExpression::Case {
+                operand: None,
+                when_clauses: vec![
+                    (lit(Value::Bool(true)), lit(Value::Int64(1))),
+                    (failing_expr(), failing_expr()),
+                ],
+                else_clause: Some(Box::new(failing_expr())),
+            },
+            HashMap::new(),
+            Arc::new(LpgStore::new()),
+        );
+        let chunk = DataChunkBuilder::new(&[LogicalType::Int64]).finish();
+
+        assert_eq!(predicate.eval(&chunk, 0), Some(Value::Int64(1)));
+        assert!(predicate.take_error().is_none());
+
+        // Mixed branch types come through as-is
+        let mixed = eval_constant(FilterExpression::Case {
+            operand: None,
+            when_clauses: vec![(lit(Value::Bool(false)), lit(Value::Int64(1)))],
+            else_clause: Some(Box::new(lit(Value::from("other")))),
+        });
+        assert_eq!(mixed, Some(Value::from("other")));
+    }
 }
@@ -0,0 +1,332 @@
+//! Index-nested-loop join probing a property index on the inner side.
+//!
+//! Instead of scanning the inner relation and building a hash table from it,
+//! each outer row looks its key up in the store's index on the inner label's
+//! property. When the inner side is large and already indexed that is
+//! strictly less work: one probe per outer row, and the inner side is never
+//! read.
+
+use std::sync::Arc;
+
+use grafeo_common::types::{EpochId, LogicalType, TxId, Value};
+
+use super::{Operator, OperatorError, OperatorResult};
+use crate::execution::chunk::DataChunkBuilder;
+use crate::graph::lpg::LpgStore;
+
+/// Inner join of an outer input against the nodes of a label, found through
+/// the store's index on one of the label's properties.
+///
+/// Output rows are the outer columns followed by the matched node, one row
+/// per match, so a key shared by several nodes yields a row for each. Keys
+/// compare like a hash join's do, so the results are the ones a
+/// [`HashJoinOperator`] over a scan of the label would give. Null keys never
+/// match.
+///
+/// [`HashJoinOperator`]: super::HashJoinOperator
+pub struct IndexNestedLoopJoinOperator {
+    /// Outer input.
+    outer: Box<dyn Operator>,
+    /// Store holding the index.
+    store: Arc<LpgStore>,
+    /// Label of the inner nodes.
+    label: String,
+    /// Indexed property the probe key is compared with.
+    property: String,
+    /// Column of the outer input holding the probe key.
+    probe_column: usize,
+    /// Output schema (outer columns + the node column).
+    output_schema: Vec<LogicalType>,
+    /// Epoch for MVCC visibility checks.
+    viewing_epoch: Option<EpochId>,
+    /// Transaction whose own changes are visible.
+    tx_id: Option<TxId>,
+}
+
+impl IndexNestedLoopJoinOperator {
+    /// Creates an index-nested-loop join.
+    ///
+    /// # Arguments
+    /// * `outer` - Outer input, read once.
+    /// * `store` - Store with an index on `label`'s `property`.
+    /// * `label` - Label of the inner nodes.
+    /// * `property` - Indexed property the probe key is compared with.
+    /// * `probe_column` - Column of `outer` whose value is looked up.
+    /// * `output_schema` - Schema of the output (outer columns + node column).
+    pub fn new(
+        outer: Box<dyn Operator>,
+        store: Arc<LpgStore>,
+        label: impl Into<String>,
+        property: impl Into<String>,
+        probe_column: usize,
+        output_schema: Vec<LogicalType>,
+    ) -> Self {
+        Self {
+            outer,
+            store,
+            label: label.into(),
+            property: property.into(),
+            probe_column,
+            output_schema,
+            viewing_epoch: None,
+            tx_id: None,
+        }
+    }
+
+    /// Sets the transaction context, so probes only match nodes and values
+    /// the transaction sees.
+    #[must_use]
+    pub fn with_tx_context(mut self, epoch: EpochId, tx_id: Option<TxId>) -> Self {
+        self.viewing_epoch = Some(epoch);
+        self.tx_id = tx_id;
+        self
+    }
+}
+
+impl Operator for IndexNestedLoopJoinOperator {
+    fn next(&mut self) -> OperatorResult {
+        while let Some(chunk) = self.outer.next()? {
+            let mut builder = DataChunkBuilder::with_capacity(&self.output_schema, chunk.len());
+            let node_column = chunk.column_count();
+
+            for row in chunk.selected_indices() {
+                let Some(key) = chunk
+                    .column(self.probe_column)
+                    .and_then(|c| c.get_value(row))
+                else {
+                    continue;
+                };
+                let matches = self
+                    .store
+                    .find_nodes_by_property_index(
+                        &self.label,
+                        &self.property,
+                        &key,
+                        self.viewing_epoch,
+                        self.tx_id,
+                    )
+                    .ok_or_else(|| {
+                        OperatorError::Execution(format!(
+                            "no index on :{}({})",
+                            self.label, self.property
+                        ))
+                    })?;
+
+                for node in matches {
+                    for col_idx in 0..node_column {
+                        if let (Some(src_col), Some(dst_col)) =
+                            (chunk.column(col_idx), builder.column_mut(col_idx))
+                        {
+                            dst_col.push_value(src_col.get_value(row).unwrap_or(Value::Null));
+                        }
+                    }
+                    if let Some(dst_col) = builder.column_mut(node_column) {
+                        dst_col.push_node_id(node);
+                    }
+                    builder.advance_row();
+                }
+            }
+
+            if builder.row_count() > 0 {
+                return Ok(Some(builder.finish()));
+            }
+        }
+        Ok(None)
+    }
+
+    fn reset(&mut self) {
+        self.outer.reset();
+    }
+
+    fn name(&self) -> &'static str {
+        "IndexNestedLoopJoin"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::DataChunk;
+    use crate::execution::operators::{HashJoinOperator, JoinType};
+
+    struct MockOperator {
+        chunks: Vec<DataChunk>,
+        position: usize,
+    }
+
+    impl Operator for MockOperator {
+        fn next(&mut self) -> OperatorResult {
+            if self.position < self.chunks.len() {
+                let chunk = std::mem::replace(&mut self.chunks[self.position], DataChunk::empty());
+                self.position += 1;
+                Ok(Some(chunk))
+            } else {
+                Ok(None)
+            }
+        }
+
+        fn reset(&mut self) {
+            self.position = 0;
+        }
+
+        fn name(&self) -> &'static str {
+            "Mock"
+        }
+    }
+
+    /// Outer `(key, tag)` rows, two per chunk.
+    fn outer(rows: &[(Value, &str)]) -> Box<dyn Operator> {
+        let chunks = rows
+            .chunks(2)
+            .map(|rows| {
+                let mut builder = DataChunkBuilder::new(&[LogicalType::Any, LogicalType::String]);
+                for (key, tag) in rows {
+                    builder.column_mut(0).unwrap().push_value(key.clone());
+                    builder.column_mut(1).unwrap().push_string(*tag);
+                    builder.advance_row();
+                }
+                builder.finish()
+            })
+            .collect();
+        Box::new(MockOperator {
+            chunks,
+            position: 0,
+        })
+    }
+
+    /// Inner `(node, key)` rows for the `Person` nodes of `store`, as a hash
+    /// join's build side would see them.
+    fn inner(store: &LpgStore) -> Box<dyn Operator> {
+        let mut builder = DataChunkBuilder::new(&[LogicalType::Node, LogicalType::Any]);
+        for node in store.nodes_by_label("Person") {
+            let key = store
+                .get_node(node)
+                .and_then(|n| n.get_property("key").cloned())
+                .unwrap_or(Value::Null);
+            builder.column_mut(0).unwrap().push_node_id(node);
+            builder.column_mut(1).unwrap().push_value(key);
+            builder.advance_row();
+        }
+        Box::new(MockOperator {
+            chunks: vec![builder.finish()],
+            position: 0,
+        })
+    }
+
+    /// `(tag, node)` pairs, sorted so join orders don't matter.
+    fn collect(op: &mut dyn Operator, tag_column: usize, node_column: usize) -> Vec<(String, u64)> {
+        let mut rows = Vec::new();
+        while let Some(chunk) = op.next().unwrap() {
+            for row in chunk.selected_indices() {
+                let tag = chunk.column(tag_column).unwrap().get_string(row).unwrap();
+                let node = chunk.column(node_column).unwrap().get_node_id(row).unwrap();
+                rows.push((tag.to_string(), node.as_u64()));
+            }
+        }
+        rows.sort();
+        rows
+    }
+
+    fn index_join(store: &Arc<LpgStore>, probes: &[(Value, &str)]) -> IndexNestedLoopJoinOperator {
+        IndexNestedLoopJoinOperator::new(
+            outer(probes),
+            Arc::clone(store),
+            "Person",
+            "key",
+            0,
+            vec![LogicalType::Any, LogicalType::String, LogicalType::Node],
+        )
+    }
+
+    #[test]
+    fn test_index_join_matches_hash_join_with_duplicate_keys() {
+        let store = Arc::new(LpgStore::new());
+        let mut nodes = Vec::new();
+        for key in [
+            Value::Int64(1),
+            Value::String("bob".into()),
+            Value::Int64(3),
+            Value::Int64(3),
+        ] {
+            nodes.push(store.create_node_with_props(&["Person"], [("key", key)]));
+        }
+        // Not a Person, so never matched
+        store.create_node_with_props(&["Robot"], [("key", Value::Int64(1))]);
+        assert!(store.create_property_index("Person", "key"));
+        // Written after the index exists
+        nodes.push(store.create_node_with_props(&["Person"], [("key", Value::Int64(1))]));
+
+        let probes = [
+            (Value::Int64(3), "a"),
+            (Value::String("bob".into()), "b"),
+            (Value::Int64(2), "c"),
+            (Value::Int64(1), "d"),
+            (Value::Null, "e"),
+        ];
+
+        let mut hash_join = HashJoinOperator::new(
+            outer(&probes),
+            inner(&store),
+            vec![0],
+            vec![1],
+            JoinType::Inner,
+            vec![
+                LogicalType::Any,
+                LogicalType::String,
+                LogicalType::Node,
+                LogicalType::Any,
+            ],
+        );
+
+        let expected = collect(&mut hash_join, 1, 2);
+        let id = |i: usize| nodes[i].as_u64();
+        assert_eq!(
+            expected,
+            vec![
+                ("a".to_string(), id(2)),
+                ("a".to_string(), id(3)),
+                ("b".to_string(), id(1)),
+                ("d".to_string(), id(0)),
+                ("d".to_string(), id(4)),
+            ]
+        );
+        assert_eq!(collect(&mut index_join(&store, &probes), 1, 2), expected);
+    }
+
+    #[test]
+    fn test_index_join_only_matches_visible_values() {
+        let store = Arc::new(LpgStore::new());
+        let alice = store.create_node_with_props(&["Person"], [("key", Value::Int64(1))]);
+        store.create_property_index("Person", "key");
+        let epoch = store.current_epoch();
+
+        // An open transaction moves alice to key 2 and adds a node with key 1
+        let tx = TxId::new(5);
+        store.set_node_property_versioned(alice, "key", Value::Int64(2), tx);
+        let bob = store.create_node_versioned(&["Person"], epoch, tx);
+        store.set_node_property_versioned(bob, "key", Value::Int64(1), tx);
+
+        let probes = [(Value::Int64(1), "one"), (Value::Int64(2), "two")];
+        let mut outside = index_join(&store, &probes).with_tx_context(epoch, None);
+        assert_eq!(
+            collect(&mut outside, 1, 2),
+            vec![("one".to_string(), alice.as_u64())]
+        );
+
+        let mut inside = index_join(&store, &probes).with_tx_context(epoch, Some(tx));
+        assert_eq!(
+            collect(&mut inside, 1, 2),
+            vec![
+                ("one".to_string(), bob.as_u64()),
+                ("two".to_string(), alice.as_u64()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_index_join_without_index_fails() {
+        let store = Arc::new(LpgStore::new());
+        let mut join = index_join(&store, &[(Value::Int64(1), "a")]);
+        assert!(join.next().is_err());
+    }
+}
@@ -3,13 +3,25 @@
 //! This module provides:
 //! - `HashJoinOperator`: Efficient hash-based join for equality conditions
 //! - `NestedLoopJoinOperator`: General-purpose join for any condition
+//! - `SpillableHashJoinOperator`: Hash join that partitions to disk under memory pressure
 
 use std::collections::HashMap;
-
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use grafeo_common::memory::buffer::{
+    BufferManager, MemoryConsumer, MemoryGrant, MemoryRegion, SpillError, priorities,
+};
 use grafeo_common::types::{LogicalType, Value};
+use parking_lot::Mutex;
 
 use super::{Operator, OperatorError, OperatorResult};
 use crate::execution::chunk::DataChunkBuilder;
+use crate::execution::spill::{
+    SpillFile, SpillFileReader, SpillManager, deserialize_row, serialize_row,
+};
 use crate::execution::{DataChunk, ValueVector};
 
 /// The type of join to perform.
@@ -32,7 +44,10 @@ pub enum JoinType {
 }
 
 /// A hash key that can be hashed and compared for join operations.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+///
+/// Keys are also ordered, variant first, so tuples of them can key ordered
+/// indexes.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum HashKey {
     /// Null key.
     Null,
@@ -63,6 +78,9 @@ impl HashKey {
                 HashKey::String(format!("{b:?}"))
             }
             Value::Timestamp(t) => HashKey::Int64(t.as_micros()),
+            // A date joins with the timestamp at its midnight
+            Value::Date(d) => HashKey::Int64(d.to_timestamp().as_micros()),
+            Value::Duration(d) => HashKey::Int64(d.as_micros()),
             Value::List(items) => {
                 HashKey::Composite(items.iter().map(HashKey::from_value).collect())
             }
@@ -286,21 +304,20 @@ impl HashJoinOperator {
                 }
             }
             _ => {
-                // Emit nulls for build side (left outer join case)
-                if !self.build_chunks.is_empty() {
-                    let build_col_count = self.build_chunks[0].column_count();
-                    for col_idx in 0..build_col_count {
-                        let dst_col =
-                            builder
-                                .column_mut(probe_col_count + col_idx)
-                                .ok_or_else(|| {
-                                    OperatorError::ColumnNotFound(format!(
-                                        "output column {}",
-                                        probe_col_count + col_idx
-                                    ))
-                                })?;
-                        dst_col.push_value(Value::Null);
-                    }
+                // Emit nulls for build side (left outer join case). The output
+                // schema is used so this also works when the build side is empty.
+                let build_col_count = self.output_schema.len().saturating_sub(probe_col_count);
+                for col_idx in 0..build_col_count {
+                    let dst_col =
+                        builder
+                            .column_mut(probe_col_count + col_idx)
+                            .ok_or_else(|| {
+                                OperatorError::ColumnNotFound(format!(
+                                    "output column {}",
+                                    probe_col_count + col_idx
+                                ))
+                            })?;
+                    dst_col.push_value(Value::Null);
                 }
             }
         }
@@ -611,21 +628,21 @@ pub trait JoinCondition: Send + Sync {
     ) -> bool;
 }
 
-/// A simple equality condition for nested loop joins.
+/// An equality condition for nested loop joins, over one or more column pairs.
 pub struct EqualityCondition {
-    /// Column index on the left side.
-    left_column: usize,
-    /// Column index on the right side.
-    right_column: usize,
+    /// `(left column, right column)` pairs that must all be equal.
+    columns: Vec<(usize, usize)>,
 }
 
 impl EqualityCondition {
     /// Creates a new equality condition.
     pub fn new(left_column: usize, right_column: usize) -> Self {
-        Self {
-            left_column,
-            right_column,
-        }
+        Self::on_columns(vec![(left_column, right_column)])
+    }
+
+    /// Creates a condition requiring every `(left, right)` column pair to match.
+    pub fn on_columns(columns: Vec<(usize, usize)>) -> Self {
+        Self { columns }
     }
 }
 
@@ -637,17 +654,19 @@ impl JoinCondition for EqualityCondition {
         right_chunk: &DataChunk,
         right_row: usize,
     ) -> bool {
-        let left_val = left_chunk
-            .column(self.left_column)
-            .and_then(|c| c.get_value(left_row));
-        let right_val = right_chunk
-            .column(self.right_column)
-            .and_then(|c| c.get_value(right_row));
-
-        match (left_val, right_val) {
-            (Some(l), Some(r)) => l == r,
-            _ => false,
-        }
+        self.columns.iter().all(|&(left_column, right_column)| {
+            let left_val = left_chunk
+                .column(left_column)
+                .and_then(|c| c.get_value(left_row));
+            let right_val = right_chunk
+                .column(right_column)
+                .and_then(|c| c.get_value(right_row));
+
+            match (left_val, right_val) {
+                (Some(l), Some(r)) => l == r,
+                _ => false,
+            }
+        })
     }
 }
 
@@ -901,6 +920,674 @@ impl Operator for NestedLoopJoinOperator {
     }
 }
 
+/// Default number of hash partitions for [`SpillableHashJoinOperator`].
+pub const DEFAULT_JOIN_PARTITIONS: usize = 32;
+
+/// Source of unique consumer names, so concurrent joins can all register.
+static NEXT_JOIN_CONSUMER: AtomicU64 = AtomicU64::new(0);
+
+/// Builds the join key of a materialized row the same way
+/// [`HashJoinOperator`] builds it from a chunk row.
+fn row_key(row: &[Value], key_columns: &[usize]) -> HashKey {
+    let key = |col: usize| row.get(col).map_or(HashKey::Null, HashKey::from_value);
+    if key_columns.len() == 1 {
+        key(key_columns[0])
+    } else {
+        HashKey::Composite(key_columns.iter().map(|&col| key(col)).collect())
+    }
+}
+
+/// Picks the partition for a join key.
+///
+/// Partitioning hashes the [`HashKey`] rather than the raw values (as
+/// [`PartitionedState`](crate::execution::spill::PartitionedState) does), so
+/// every pair of rows the in-memory join would match lands in the same
+/// partition.
+fn partition_of(key: &HashKey, num_partitions: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    (hasher.finish() % num_partitions as u64) as usize
+}
+
+/// Copies one row of a chunk into owned values.
+fn chunk_row(chunk: &DataChunk, row: usize) -> Vec<Value> {
+    (0..chunk.column_count())
+        .map(|col| {
+            chunk
+                .column(col)
+                .and_then(|c| c.get_value(row))
+                .unwrap_or(Value::Null)
+        })
+        .collect()
+}
+
+/// Estimates the memory a materialized row holds.
+fn row_bytes(row: &[Value]) -> usize {
+    let heap = serialize_row(row, &mut std::io::sink()).unwrap_or(0);
+    std::mem::size_of_val(row) + heap
+}
+
+fn spill_error(e: std::io::Error) -> OperatorError {
+    OperatorError::Execution(format!("hash join spill failed: {e}"))
+}
+
+/// One hash partition of a join input.
+///
+/// Rows stay in memory until the partition spills; after that every new row
+/// goes straight to its file, so a partition is either fully in memory or
+/// fully on disk.
+#[derive(Default)]
+struct JoinPartition {
+    rows: Vec<Vec<Value>>,
+    /// Estimated memory held by `rows`.
+    bytes: usize,
+    file: Option<SpillFile>,
+    spilled_rows: usize,
+}
+
+impl JoinPartition {
+    fn is_spilled(&self) -> bool {
+        self.file.is_some()
+    }
+
+    /// Appends a row to the partition's spill file, creating it if needed.
+    fn write_row(&mut self, spill: &JoinSpill, row: &[Value]) -> std::io::Result<()> {
+        let manager = &spill.manager;
+        let file = match self.file.take() {
+            Some(file) => file,
+            None => manager.create_file(&spill.prefix)?,
+        };
+        let file = self.file.insert(file);
+        let mut buf = Vec::new();
+        serialize_row(row, &mut buf)?;
+        file.write_all(&buf)?;
+        manager.register_spilled_bytes(buf.len() as u64);
+        self.spilled_rows += 1;
+        Ok(())
+    }
+
+    /// Moves the in-memory rows to disk, returning the bytes freed.
+    fn spill(&mut self, spill: &JoinSpill) -> std::io::Result<usize> {
+        for row in std::mem::take(&mut self.rows) {
+            self.write_row(spill, &row)?;
+        }
+        if self.file.is_none() {
+            // Even an empty partition routes later rows to disk
+            self.file = Some(spill.manager.create_file(&spill.prefix)?);
+        }
+        Ok(std::mem::take(&mut self.bytes))
+    }
+
+    /// Takes every row of the partition, reading spilled rows back.
+    fn take_rows(&mut self, manager: &SpillManager) -> std::io::Result<Vec<Vec<Value>>> {
+        let mut rows = Vec::with_capacity(self.spilled_rows + self.rows.len());
+        if let Some(mut file) = self.file.take() {
+            file.finish_write()?;
+            let mut reader = file.reader()?;
+            for _ in 0..self.spilled_rows {
+                rows.push(deserialize_row(&mut SpillReader(&mut reader), 0)?);
+            }
+            manager.unregister_spilled_bytes(file.bytes_written());
+            file.delete()?;
+        }
+        rows.append(&mut self.rows);
+        self.spilled_rows = 0;
+        self.bytes = 0;
+        Ok(rows)
+    }
+
+    /// Drops all rows and deletes the spill file.
+    fn discard(&mut self, manager: &SpillManager) {
+        if let Some(file) = self.file.take() {
+            manager.unregister_spilled_bytes(file.bytes_written());
+            let _ = file.delete();
+        }
+        self.rows.clear();
+        self.spilled_rows = 0;
+        self.bytes = 0;
+    }
+}
+
+/// Adapter to read from SpillFileReader through std::io::Read.
+struct SpillReader<'a>(&'a mut SpillFileReader);
+
+impl std::io::Read for SpillReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read_exact(buf)?;
+        Ok(buf.len())
+    }
+}
+
+/// Build side partitions, mutable by both the join and the buffer manager.
+struct JoinBuild {
+    partitions: Vec<JoinPartition>,
+    /// Memory granted for the in-memory build rows.
+    grant: Option<MemoryGrant>,
+    /// Set once probing starts; from then on nothing can spill.
+    sealed: bool,
+}
+
+impl JoinBuild {
+    fn new(num_partitions: usize) -> Self {
+        Self {
+            partitions: (0..num_partitions)
+                .map(|_| JoinPartition::default())
+                .collect(),
+            grant: None,
+            sealed: false,
+        }
+    }
+
+    /// Gives back `bytes` of the grant after rows moved to disk.
+    fn release(&mut self, bytes: usize) {
+        if let Some(grant) = &mut self.grant {
+            let size = grant.size().saturating_sub(bytes);
+            grant.resize(size);
+        }
+    }
+
+    /// Spills the largest in-memory partitions until `target` bytes are freed.
+    fn spill_largest(&mut self, spill: &JoinSpill, target: usize) -> std::io::Result<usize> {
+        let mut freed = 0;
+        while freed < target {
+            let Some(largest) = self
+                .partitions
+                .iter_mut()
+                .filter(|p| p.bytes > 0)
+                .max_by_key(|p| p.bytes)
+            else {
+                break;
+            };
+            freed += largest.spill(spill)?;
+        }
+        self.release(freed);
+        Ok(freed)
+    }
+}
+
+/// Where a join writes its spill files.
+struct JoinSpill {
+    manager: Arc<SpillManager>,
+    /// File name prefix, unique to the join so that joins in other queries
+    /// or processes can share the spill directory.
+    prefix: String,
+}
+
+/// The build state a [`SpillableHashJoinOperator`] registers with the
+/// buffer manager.
+struct JoinBuildState {
+    name: String,
+    spill: JoinSpill,
+    build: Mutex<JoinBuild>,
+}
+
+impl JoinBuildState {
+    /// Adds a build row to partition `partition`, spilling that partition if
+    /// the buffer manager has no room for it.
+    fn insert(
+        &self,
+        buffer_manager: &Arc<BufferManager>,
+        partition: usize,
+        row: Vec<Value>,
+    ) -> std::io::Result<()> {
+        let mut build = self.build.lock();
+        if build.partitions[partition].is_spilled() {
+            return build.partitions[partition].write_row(&self.spill, &row);
+        }
+        drop(build);
+
+        // Allocate without holding the lock: under pressure the buffer
+        // manager calls back into `evict` on this same state.
+        let bytes = row_bytes(&row);
+        let grant = buffer_manager.try_allocate(bytes, MemoryRegion::ExecutionBuffers);
+
+        let mut build = self.build.lock();
+        let build = &mut *build;
+        let target = &mut build.partitions[partition];
+        match grant {
+            // Eviction may have spilled the partition while we allocated
+            Some(grant) if !target.is_spilled() => {
+                target.rows.push(row);
+                target.bytes += bytes;
+                match &mut build.grant {
+                    Some(existing) => existing.merge(grant),
+                    None => build.grant = Some(grant),
+                }
+                Ok(())
+            }
+            _ => {
+                let freed = target.spill(&self.spill)?;
+                target.write_row(&self.spill, &row)?;
+                build.release(freed);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl MemoryConsumer for JoinBuildState {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.build
+            .lock()
+            .grant
+            .as_ref()
+            .map_or(0, MemoryGrant::size)
+    }
+
+    fn eviction_priority(&self) -> u8 {
+        priorities::SPILLABLE_OPERATOR
+    }
+
+    fn region(&self) -> MemoryRegion {
+        MemoryRegion::ExecutionBuffers
+    }
+
+    fn evict(&self, target_bytes: usize) -> usize {
+        self.spill(target_bytes).unwrap_or(0)
+    }
+
+    fn can_spill(&self) -> bool {
+        true
+    }
+
+    fn spill(&self, target_bytes: usize) -> Result<usize, SpillError> {
+        let mut build = self.build.lock();
+        if build.sealed {
+            return Ok(0);
+        }
+        build
+            .spill_largest(&self.spill, target_bytes)
+            .map_err(|e| SpillError::IoError(e.to_string()))
+    }
+}
+
+/// An in-memory build partition, indexed by join key.
+struct PartitionTable {
+    rows: Vec<Vec<Value>>,
+    index: HashMap<HashKey, Vec<usize>>,
+    matched: Vec<bool>,
+}
+
+impl PartitionTable {
+    fn new(rows: Vec<Vec<Value>>, build_keys: &[usize], join_type: JoinType) -> Self {
+        let mut index: HashMap<HashKey, Vec<usize>> = HashMap::new();
+        for (i, row) in rows.iter().enumerate() {
+            let key = row_key(row, build_keys);
+            // Skip null keys for inner/semi/anti joins
+            if matches!(key, HashKey::Null)
+                && !matches!(join_type, JoinType::Left | JoinType::Right | JoinType::Full)
+            {
+                continue;
+            }
+            index.entry(key).or_default().push(i);
+        }
+        let matched = vec![false; rows.len()];
+        Self {
+            rows,
+            index,
+            matched,
+        }
+    }
+}
+
+/// Where a [`SpillableHashJoinOperator`] is in its work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpillJoinPhase {
+    Build,
+    Probe,
+    /// Joining the spilled partition pair with this index.
+    Spilled(usize),
+    Done,
+}
+
+/// Hash join that partitions both inputs to disk when the build side
+/// outgrows its memory budget (a grace hash join).
+///
+/// Build rows are hash-partitioned on their join key and kept in memory as
+/// long as the [`BufferManager`] grants room for them. When a grant fails, or
+/// the buffer manager asks the join to spill under pressure (the build state
+/// is registered as a [`MemoryConsumer`]), whole partitions move to disk.
+/// Probe rows whose partition stayed in memory are joined right away; the
+/// rest are written to the matching probe partition, and each spilled pair
+/// is then joined on its own.
+///
+/// Keys are compared exactly like [`HashJoinOperator`] compares them, so
+/// both joins produce the same rows, whatever the partition count. Output
+/// order differs.
+pub struct SpillableHashJoinOperator {
+    probe_side: Box<dyn Operator>,
+    build_side: Box<dyn Operator>,
+    probe_keys: Vec<usize>,
+    build_keys: Vec<usize>,
+    join_type: JoinType,
+    output_schema: Vec<LogicalType>,
+    buffer_manager: Arc<BufferManager>,
+    state: Arc<JoinBuildState>,
+    num_partitions: usize,
+    phase: SpillJoinPhase,
+    /// Build partitions, moved out of `state` once the build is sealed.
+    build_partitions: Vec<JoinPartition>,
+    /// Hash tables for the build partitions that stayed in memory.
+    tables: Vec<Option<PartitionTable>>,
+    /// Probe rows waiting for their spilled build partition.
+    probe_partitions: Vec<JoinPartition>,
+    /// Output rows not yet returned.
+    pending: Vec<Vec<Value>>,
+    spilled_partitions: usize,
+}
+
+impl SpillableHashJoinOperator {
+    /// Creates a new spillable hash join.
+    ///
+    /// Spill files go through `spill_manager`; memory for the build side is
+    /// requested from `buffer_manager`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        probe_side: Box<dyn Operator>,
+        build_side: Box<dyn Operator>,
+        probe_keys: Vec<usize>,
+        build_keys: Vec<usize>,
+        join_type: JoinType,
+        output_schema: Vec<LogicalType>,
+        spill_manager: Arc<SpillManager>,
+        buffer_manager: Arc<BufferManager>,
+    ) -> Self {
+        let id = NEXT_JOIN_CONSUMER.fetch_add(1, Ordering::Relaxed);
+        let state = Arc::new(JoinBuildState {
+            name: format!("spillable_hash_join_{id}"),
+            spill: JoinSpill {
+                manager: spill_manager,
+                prefix: format!("join_{}_{id}", std::process::id()),
+            },
+            build: Mutex::new(JoinBuild::new(DEFAULT_JOIN_PARTITIONS)),
+        });
+        buffer_manager.register_consumer(Arc::clone(&state) as Arc<dyn MemoryConsumer>);
+
+        Self {
+            probe_side,
+            build_side,
+            probe_keys,
+            build_keys,
+            join_type,
+            output_schema,
+            buffer_manager,
+            state,
+            num_partitions: DEFAULT_JOIN_PARTITIONS,
+            phase: SpillJoinPhase::Build,
+            build_partitions: Vec::new(),
+            tables: Vec::new(),
+            probe_partitions: Vec::new(),
+            pending: Vec::new(),
+            spilled_partitions: 0,
+        }
+    }
+
+    /// Sets the number of hash partitions (at least 1).
+    pub fn with_partitions(mut self, num_partitions: usize) -> Self {
+        self.num_partitions = num_partitions.max(1);
+        *self.state.build.lock() = JoinBuild::new(self.num_partitions);
+        self
+    }
+
+    /// Returns how many build partitions had to go to disk.
+    ///
+    /// Only known once the build side has been consumed.
+    pub fn spilled_partitions(&self) -> usize {
+        self.spilled_partitions
+    }
+
+    fn build(&mut self) -> Result<(), OperatorError> {
+        while let Some(chunk) = self.build_side.next()? {
+            for row in chunk.selected_indices() {
+                let values = chunk_row(&chunk, row);
+                let partition =
+                    partition_of(&row_key(&values, &self.build_keys), self.num_partitions);
+                self.state
+                    .insert(&self.buffer_manager, partition, values)
+                    .map_err(spill_error)?;
+            }
+        }
+
+        let mut build = self.state.build.lock();
+        build.sealed = true;
+        self.build_partitions = std::mem::take(&mut build.partitions);
+        drop(build);
+
+        self.spilled_partitions = self
+            .build_partitions
+            .iter()
+            .filter(|p| p.is_spilled())
+            .count();
+        self.tables = self
+            .build_partitions
+            .iter_mut()
+            .map(|p| {
+                (!p.is_spilled()).then(|| {
+                    PartitionTable::new(
+                        std::mem::take(&mut p.rows),
+                        &self.build_keys,
+                        self.join_type,
+                    )
+                })
+            })
+            .collect();
+        self.probe_partitions = (0..self.num_partitions)
+            .map(|_| JoinPartition::default())
+            .collect();
+        Ok(())
+    }
+
+    /// Joins a probe row against its in-memory partition, or parks it on
+    /// disk until its spilled partition is joined.
+    fn probe(&mut self, row: Vec<Value>) -> Result<(), OperatorError> {
+        let key = row_key(&row, &self.probe_keys);
+        let partition = partition_of(&key, self.num_partitions);
+        match &mut self.tables[partition] {
+            Some(table) => {
+                Self::probe_row(
+                    table,
+                    row,
+                    &key,
+                    self.join_type,
+                    self.output_schema.len(),
+                    &mut self.pending,
+                );
+                Ok(())
+            }
+            None => self.probe_partitions[partition]
+                .write_row(&self.state.spill, &row)
+                .map_err(spill_error),
+        }
+    }
+
+    fn probe_row(
+        table: &mut PartitionTable,
+        row: Vec<Value>,
+        key: &HashKey,
+        join_type: JoinType,
+        output_width: usize,
+        out: &mut Vec<Vec<Value>>,
+    ) {
+        match join_type {
+            JoinType::Semi => {
+                if table.index.contains_key(key) {
+                    out.push(row);
+                }
+            }
+            JoinType::Anti => {
+                if !table.index.contains_key(key) {
+                    out.push(row);
+                }
+            }
+            _ => match table.index.get(key) {
+                Some(matches) if !matches.is_empty() => {
+                    for &i in matches {
+                        if matches!(join_type, JoinType::Right | JoinType::Full) {
+                            table.matched[i] = true;
+                        }
+                        let mut output = row.clone();
+                        output.extend(table.rows[i].iter().cloned());
+                        out.push(output);
+                    }
+                }
+                _ => {
+                    if matches!(join_type, JoinType::Left | JoinType::Full) {
+                        let mut output = row;
+                        output.resize(output_width, Value::Null);
+                        out.push(output);
+                    }
+                }
+            },
+        }
+    }
+
+    /// Queues the build rows of `table` that found no match (right/full
+    /// outer join).
+    fn emit_unmatched(&mut self, table: PartitionTable) {
+        if !matches!(self.join_type, JoinType::Right | JoinType::Full) {
+            return;
+        }
+        for (row, matched) in table.rows.into_iter().zip(table.matched) {
+            if !matched {
+                let probe_width = self.output_schema.len().saturating_sub(row.len());
+                let mut output = vec![Value::Null; probe_width];
+                output.extend(row);
+                self.pending.push(output);
+            }
+        }
+    }
+
+    /// Joins spilled partition `partition` with its parked probe rows.
+    fn join_spilled(&mut self, partition: usize) -> Result<(), OperatorError> {
+        let manager = Arc::clone(&self.state.spill.manager);
+        let build_rows = self.build_partitions[partition]
+            .take_rows(&manager)
+            .map_err(spill_error)?;
+        let probe_rows = self.probe_partitions[partition]
+            .take_rows(&manager)
+            .map_err(spill_error)?;
+
+        let mut table = PartitionTable::new(build_rows, &self.build_keys, self.join_type);
+        for row in probe_rows {
+            let key = row_key(&row, &self.probe_keys);
+            Self::probe_row(
+                &mut table,
+                row,
+                &key,
+                self.join_type,
+                self.output_schema.len(),
+                &mut self.pending,
+            );
+        }
+        self.emit_unmatched(table);
+        Ok(())
+    }
+
+    /// Builds an output chunk from up to 2048 pending rows.
+    fn emit_chunk(&mut self) -> DataChunk {
+        let count = self.pending.len().min(2048);
+        let mut builder = DataChunkBuilder::with_capacity(&self.output_schema, count);
+        for row in self.pending.drain(..count) {
+            for (col_idx, value) in row.into_iter().enumerate() {
+                if let Some(dst_col) = builder.column_mut(col_idx) {
+                    dst_col.push_value(value);
+                }
+            }
+            builder.advance_row();
+        }
+        builder.finish()
+    }
+
+    /// Drops all partitions and spill files and returns the memory grant.
+    fn clear(&mut self) {
+        let manager = Arc::clone(&self.state.spill.manager);
+        let mut build = self.state.build.lock();
+        for partition in build
+            .partitions
+            .iter_mut()
+            .chain(&mut self.build_partitions)
+            .chain(&mut self.probe_partitions)
+        {
+            partition.discard(&manager);
+        }
+        *build = JoinBuild::new(self.num_partitions);
+        drop(build);
+
+        self.build_partitions.clear();
+        self.probe_partitions.clear();
+        self.tables.clear();
+        self.pending.clear();
+        self.spilled_partitions = 0;
+    }
+}
+
+impl Operator for SpillableHashJoinOperator {
+    fn next(&mut self) -> OperatorResult {
+        loop {
+            if self.pending.len() >= 2048
+                || (self.phase == SpillJoinPhase::Done && !self.pending.is_empty())
+            {
+                return Ok(Some(self.emit_chunk()));
+            }
+
+            match self.phase {
+                SpillJoinPhase::Build => {
+                    self.build()?;
+                    self.phase = SpillJoinPhase::Probe;
+                }
+                SpillJoinPhase::Probe => match self.probe_side.next()? {
+                    Some(chunk) => {
+                        for row in chunk.selected_indices() {
+                            self.probe(chunk_row(&chunk, row))?;
+                        }
+                    }
+                    None => {
+                        for table in std::mem::take(&mut self.tables).into_iter().flatten() {
+                            self.emit_unmatched(table);
+                        }
+                        // The in-memory partitions are done with
+                        self.state.build.lock().grant = None;
+                        self.phase = SpillJoinPhase::Spilled(0);
+                    }
+                },
+                SpillJoinPhase::Spilled(partition) => {
+                    if partition == self.num_partitions {
+                        self.phase = SpillJoinPhase::Done;
+                    } else {
+                        if self.build_partitions[partition].is_spilled() {
+                            self.join_spilled(partition)?;
+                        }
+                        self.phase = SpillJoinPhase::Spilled(partition + 1);
+                    }
+                }
+                SpillJoinPhase::Done => return Ok(None),
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.probe_side.reset();
+        self.build_side.reset();
+        self.clear();
+        self.phase = SpillJoinPhase::Build;
+    }
+
+    fn name(&self) -> &'static str {
+        "SpillableHashJoin"
+    }
+}
+
+impl Drop for SpillableHashJoinOperator {
+    fn drop(&mut self) {
+        self.clear();
+        self.buffer_manager.unregister_consumer(&self.state.name);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1017,6 +1704,37 @@ mod tests {
         assert_eq!(results[2], (3, Some(3)));
     }
 
+    #[test]
+    fn test_hash_join_left_outer_empty_build() {
+        // Left: [1, 2], Right: empty
+        // Every left row is emitted with a null right side
+
+        let left = MockOperator::new(vec![create_int_chunk(&[1, 2])]);
+        let right = MockOperator::new(vec![]);
+
+        let output_schema = vec![LogicalType::Int64, LogicalType::Int64];
+        let mut join = HashJoinOperator::new(
+            Box::new(left),
+            Box::new(right),
+            vec![0],
+            vec![0],
+            JoinType::Left,
+            output_schema,
+        );
+
+        let mut results = Vec::new();
+        while let Some(chunk) = join.next().unwrap() {
+            for row in chunk.selected_indices() {
+                let left_val = chunk.column(0).unwrap().get_int64(row).unwrap();
+                let right_val = chunk.column(1).unwrap().get_int64(row);
+                results.push((left_val, right_val));
+            }
+        }
+
+        results.sort_by_key(|(l, _)| *l);
+        assert_eq!(results, vec![(1, None), (2, None)]);
+    }
+
     #[test]
     fn test_nested_loop_cross_join() {
         // Left: [1, 2]
@@ -1110,4 +1828,115 @@ mod tests {
         results.sort();
         assert_eq!(results, vec![1, 3]);
     }
+
+    fn create_keyed_chunks(rows: &[(Option<i64>, i64)]) -> Vec<DataChunk> {
+        rows.chunks(100)
+            .map(|rows| {
+                let mut builder = DataChunkBuilder::new(&[LogicalType::Int64, LogicalType::Int64]);
+                for &(key, payload) in rows {
+                    builder
+                        .column_mut(0)
+                        .unwrap()
+                        .push_value(key.map_or(Value::Null, Value::Int64));
+                    builder.column_mut(1).unwrap().push_int64(payload);
+                    builder.advance_row();
+                }
+                builder.finish()
+            })
+            .collect()
+    }
+
+    fn collect_sorted_rows(op: &mut dyn Operator) -> Vec<String> {
+        let mut rows = Vec::new();
+        while let Some(chunk) = op.next().unwrap() {
+            for row in chunk.selected_indices() {
+                let values: Vec<Value> = (0..chunk.column_count())
+                    .map(|col| {
+                        chunk
+                            .column(col)
+                            .unwrap()
+                            .get_value(row)
+                            .unwrap_or(Value::Null)
+                    })
+                    .collect();
+                rows.push(format!("{values:?}"));
+            }
+        }
+        rows.sort();
+        rows
+    }
+
+    #[test]
+    fn test_spillable_hash_join_matches_in_memory_join() {
+        // Overlapping key ranges with duplicates and some null keys
+        let probe: Vec<(Option<i64>, i64)> = (0..300)
+            .map(|i| ((i % 7 != 0).then_some(i % 120), i))
+            .collect();
+        let build: Vec<(Option<i64>, i64)> = (0..400)
+            .map(|i| ((i % 11 != 0).then_some(i % 90 + 30), -i))
+            .collect();
+
+        let spill_dir = tempfile::TempDir::new().unwrap();
+        let spill_manager = Arc::new(SpillManager::new(spill_dir.path()).unwrap());
+
+        for join_type in [
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::Semi,
+            JoinType::Anti,
+        ] {
+            let width = if matches!(join_type, JoinType::Semi | JoinType::Anti) {
+                2
+            } else {
+                4
+            };
+            let output_schema = vec![LogicalType::Int64; width];
+
+            let mut in_memory = HashJoinOperator::new(
+                Box::new(MockOperator::new(create_keyed_chunks(&probe))),
+                Box::new(MockOperator::new(create_keyed_chunks(&build))),
+                vec![0],
+                vec![0],
+                join_type,
+                output_schema.clone(),
+            );
+            let expected = collect_sorted_rows(&mut in_memory);
+
+            for num_partitions in [2, 5, 16] {
+                // Room for a couple dozen build rows at most
+                let buffer_manager = BufferManager::with_budget(2048);
+                let mut join = SpillableHashJoinOperator::new(
+                    Box::new(MockOperator::new(create_keyed_chunks(&probe))),
+                    Box::new(MockOperator::new(create_keyed_chunks(&build))),
+                    vec![0],
+                    vec![0],
+                    join_type,
+                    output_schema.clone(),
+                    Arc::clone(&spill_manager),
+                    Arc::clone(&buffer_manager),
+                )
+                .with_partitions(num_partitions);
+
+                let actual = collect_sorted_rows(&mut join);
+                assert!(
+                    join.spilled_partitions() >= 2,
+                    "{join_type:?} with {num_partitions} partitions spilled {}",
+                    join.spilled_partitions()
+                );
+                assert_eq!(
+                    actual, expected,
+                    "{join_type:?} with {num_partitions} partitions"
+                );
+
+                drop(join);
+                let stats = buffer_manager.stats();
+                assert_eq!(stats.total_allocated, 0);
+                assert_eq!(stats.consumer_count, 0);
+            }
+        }
+
+        assert_eq!(spill_manager.spilled_bytes(), 0);
+    }
 }
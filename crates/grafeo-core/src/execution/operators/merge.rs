@@ -5,12 +5,24 @@
 //! 2. If found, return existing element (optionally apply ON MATCH SET)
 //! 3. If not found, create the element (optionally apply ON CREATE SET)
 
-use super::{Operator, OperatorResult};
+use super::{
+    ConstraintChecker, ExpressionPredicate, FilterExpression, Operator, OperatorError,
+    OperatorResult, Predicate,
+};
+use crate::execution::DataChunk;
 use crate::execution::chunk::DataChunkBuilder;
 use crate::graph::lpg::LpgStore;
-use grafeo_common::types::{LogicalType, NodeId, PropertyKey, Value};
+use grafeo_common::types::{EpochId, LogicalType, NodeId, PropertyKey, TxId, Value};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
+/// Claims a MERGE key for the running transaction.
+///
+/// The engine records the claim as a write, so two transactions merging the
+/// same key conflict at commit instead of both creating a node. It is called
+/// once, when the operator runs.
+pub type MergeKeyClaim = Box<dyn Fn() -> Result<(), OperatorError> + Send + Sync>;
+
 /// Merge operator for MERGE clause.
 ///
 /// Tries to match a node with the given labels and properties.
@@ -24,12 +36,21 @@ pub struct MergeOperator {
     labels: Vec<String>,
     /// Properties that must match (also used for creation).
     match_properties: Vec<(String, Value)>,
-    /// Properties to set on CREATE.
-    on_create_properties: Vec<(String, Value)>,
-    /// Properties to set on MATCH.
-    on_match_properties: Vec<(String, Value)>,
+    /// Properties to set on CREATE. The merge variable is bound to a map of
+    /// the key properties, as the node doesn't exist yet.
+    on_create_properties: Vec<(String, FilterExpression)>,
+    /// Properties to set on MATCH, evaluated against the matched node.
+    on_match_properties: Vec<(String, FilterExpression)>,
     /// Whether we've already executed.
     executed: bool,
+    /// Epoch for MVCC versioning.
+    viewing_epoch: Option<EpochId>,
+    /// Transaction ID for MVCC versioning.
+    tx_id: Option<TxId>,
+    /// Constraints the merged node must meet.
+    constraints: Option<Arc<dyn ConstraintChecker>>,
+    /// Claim on the merge key, made before the key is looked up.
+    key_claim: Option<MergeKeyClaim>,
 }
 
 impl MergeOperator {
@@ -39,8 +60,8 @@ impl MergeOperator {
         variable: String,
         labels: Vec<String>,
         match_properties: Vec<(String, Value)>,
-        on_create_properties: Vec<(String, Value)>,
-        on_match_properties: Vec<(String, Value)>,
+        on_create_properties: Vec<(String, FilterExpression)>,
+        on_match_properties: Vec<(String, FilterExpression)>,
     ) -> Self {
         Self {
             store,
@@ -50,9 +71,41 @@ impl MergeOperator {
             on_create_properties,
             on_match_properties,
             executed: false,
+            viewing_epoch: None,
+            tx_id: None,
+            constraints: None,
+            key_claim: None,
         }
     }
 
+    /// Sets the transaction context for MVCC versioning.
+    pub fn with_tx_context(mut self, epoch: EpochId, tx_id: Option<TxId>) -> Self {
+        self.viewing_epoch = Some(epoch);
+        self.tx_id = tx_id;
+        self
+    }
+
+    /// Checks the created node, or the ON MATCH properties, against
+    /// `constraints`.
+    pub fn with_constraints(mut self, constraints: Option<Arc<dyn ConstraintChecker>>) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Claims the merge key with `claim` when the operator runs.
+    pub fn with_key_claim(mut self, claim: MergeKeyClaim) -> Self {
+        self.key_claim = Some(claim);
+        self
+    }
+
+    /// Returns the epoch and transaction to read and write with.
+    fn tx_context(&self) -> (EpochId, TxId) {
+        let epoch = self
+            .viewing_epoch
+            .unwrap_or_else(|| self.store.current_epoch());
+        (epoch, self.tx_id.unwrap_or(TxId::SYSTEM))
+    }
+
     /// Returns the variable name for the merged node.
     #[must_use]
     pub fn variable(&self) -> &str {
@@ -68,9 +121,11 @@ impl MergeOperator {
             self.store.node_ids()
         };
 
+        let (epoch, tx) = self.tx_context();
+
         // Filter by all labels and properties
         for node_id in candidates {
-            if let Some(node) = self.store.get_node(node_id) {
+            if let Some(node) = self.store.get_node_versioned(node_id, epoch, tx) {
                 // Check all labels
                 let has_all_labels = self.labels.iter().all(|label| node.has_label(label));
                 if !has_all_labels {
@@ -94,8 +149,64 @@ impl MergeOperator {
         None
     }
 
+    /// Evaluates SET assignments against a one-row chunk whose only column
+    /// holds the merge variable.
+    fn evaluate(
+        &self,
+        assignments: &[(String, FilterExpression)],
+        chunk: &DataChunk,
+    ) -> Result<Vec<(String, Value)>, OperatorError> {
+        let (epoch, _) = self.tx_context();
+        let variable_columns = HashMap::from([(self.variable.clone(), 0)]);
+        assignments
+            .iter()
+            .map(|(key, expr)| {
+                let evaluator = ExpressionPredicate::new(
+                    expr.clone(),
+                    variable_columns.clone(),
+                    Arc::clone(&self.store),
+                )
+                .with_tx_context(epoch, self.tx_id);
+                let value = evaluator.eval_at(chunk, 0).unwrap_or(Value::Null);
+                match evaluator.take_error() {
+                    Some(err) => Err(err),
+                    None => Ok((key.clone(), value)),
+                }
+            })
+            .collect()
+    }
+
+    /// Evaluates the ON CREATE properties, reading the merge variable's
+    /// properties from the key.
+    fn on_create_values(&self) -> Result<Vec<(String, Value)>, OperatorError> {
+        let key: BTreeMap<PropertyKey, Value> = self
+            .match_properties
+            .iter()
+            .map(|(k, v)| (PropertyKey::new(k.as_str()), v.clone()))
+            .collect();
+        let mut builder = DataChunkBuilder::new(&[LogicalType::Any]);
+        builder
+            .column_mut(0)
+            .unwrap()
+            .push_value(Value::Map(Arc::new(key)));
+        builder.advance_row();
+        self.evaluate(&self.on_create_properties, &builder.finish())
+    }
+
+    /// Evaluates the ON MATCH properties against the matched node.
+    fn on_match_values(&self, node_id: NodeId) -> Result<Vec<(String, Value)>, OperatorError> {
+        let mut builder = DataChunkBuilder::new(&[LogicalType::Node]);
+        builder.column_mut(0).unwrap().push_node_id(node_id);
+        builder.advance_row();
+        self.evaluate(&self.on_match_properties, &builder.finish())
+    }
+
     /// Creates a new node with the specified labels and properties.
-    fn create_node(&self) -> NodeId {
+    ///
+    /// The properties are checked before any is set; if one breaks a
+    /// constraint, the node is deleted again. A null ON CREATE value leaves
+    /// the property unset.
+    fn create_node(&self) -> Result<NodeId, OperatorError> {
         // Combine match properties with on_create properties
         let mut all_props: Vec<(PropertyKey, Value)> = self
             .match_properties
@@ -104,25 +215,61 @@ impl MergeOperator {
             .collect();
 
         // Add on_create properties (may override match properties)
-        for (k, v) in &self.on_create_properties {
+        for (k, v) in self.on_create_values()? {
             // Check if property already exists, if so update it
             if let Some(existing) = all_props.iter_mut().find(|(key, _)| key.as_str() == k) {
-                existing.1 = v.clone();
+                existing.1 = v;
             } else {
-                all_props.push((PropertyKey::new(k.as_str()), v.clone()));
+                all_props.push((PropertyKey::new(k.as_str()), v));
             }
         }
+        all_props.retain(|(_, v)| !v.is_null());
 
+        let (epoch, tx) = self.tx_context();
         let labels: Vec<&str> = self.labels.iter().map(String::as_str).collect();
-        self.store.create_node_with_props(&labels, all_props)
+        let Some(constraints) = &self.constraints else {
+            return Ok(self
+                .store
+                .create_node_with_props_versioned(&labels, all_props, epoch, tx));
+        };
+
+        let node_id = self.store.create_node_versioned(&labels, epoch, tx);
+        let properties: Vec<(&str, Value)> = all_props
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.clone()))
+            .collect();
+        if let Err(err) = constraints.check_new_node(&self.store, node_id, &properties, tx) {
+            self.store.delete_node_versioned(node_id, epoch, tx);
+            return Err(err);
+        }
+        for (key, value) in all_props {
+            self.store.set_node_property(node_id, key.as_str(), value);
+        }
+        Ok(node_id)
     }
 
-    /// Applies ON MATCH properties to an existing node.
-    fn apply_on_match(&self, node_id: NodeId) {
-        for (key, value) in &self.on_match_properties {
-            self.store
-                .set_node_property(node_id, key.as_str(), value.clone());
+    /// Applies ON MATCH properties to an existing node, checking them all
+    /// before setting any. A null value removes the property.
+    fn apply_on_match(&self, node_id: NodeId) -> Result<(), OperatorError> {
+        if self.on_match_properties.is_empty() {
+            return Ok(());
+        }
+        let values = self.on_match_values(node_id)?;
+        let (_, tx) = self.tx_context();
+        if let Some(constraints) = &self.constraints {
+            for (key, value) in &values {
+                constraints.check_node_property(&self.store, node_id, key, value, tx)?;
+            }
+        }
+        for (key, value) in values {
+            if value.is_null() {
+                self.store.remove_node_property_versioned(node_id, &key, tx);
+            } else {
+                self.store
+                    .set_node_property_versioned(node_id, &key, value, tx);
+            }
         }
+        Ok(())
     }
 }
 
@@ -133,14 +280,18 @@ impl Operator for MergeOperator {
         }
         self.executed = true;
 
+        if let Some(claim) = &self.key_claim {
+            claim()?;
+        }
+
         // Try to find matching node
         let (node_id, was_created) = if let Some(existing_id) = self.find_matching_node() {
             // Node exists - apply ON MATCH properties
-            self.apply_on_match(existing_id);
+            self.apply_on_match(existing_id)?;
             (existing_id, false)
         } else {
             // Node doesn't exist - create it
-            let new_id = self.create_node();
+            let new_id = self.create_node()?;
             (new_id, true)
         };
 
@@ -167,6 +318,7 @@ impl Operator for MergeOperator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::execution::operators::BinaryFilterOp;
 
     #[test]
     fn test_merge_creates_new_node() {
@@ -235,8 +387,11 @@ mod tests {
             "n".to_string(),
             vec!["Person".to_string()],
             vec![("name".to_string(), Value::String("Charlie".into()))],
-            vec![("created".to_string(), Value::Bool(true))], // on_create
-            vec![],                                           // no on_match
+            vec![(
+                "created".to_string(),
+                FilterExpression::Literal(Value::Bool(true)),
+            )], // on_create
+            vec![], // no on_match
         );
 
         let _ = merge.next().unwrap();
@@ -270,8 +425,11 @@ mod tests {
             "n".to_string(),
             vec!["Person".to_string()],
             vec![("name".to_string(), Value::String("Diana".into()))],
-            vec![],                                           // no on_create
-            vec![("updated".to_string(), Value::Bool(true))], // on_match
+            vec![], // no on_create
+            vec![(
+                "updated".to_string(),
+                FilterExpression::Literal(Value::Bool(true)),
+            )], // on_match
         );
 
         let _ = merge.next().unwrap();
@@ -283,4 +441,119 @@ mod tests {
             Some(&Value::Bool(true))
         );
     }
+
+    #[test]
+    fn test_merge_evaluates_set_expressions() {
+        let store = Arc::new(LpgStore::new());
+        let gus = store.create_node_with_props(
+            &["Person"],
+            vec![
+                (PropertyKey::new("name"), Value::String("Gus".into())),
+                (PropertyKey::new("visits"), Value::Int64(1)),
+            ],
+        );
+        let property = |property: &str| FilterExpression::Property {
+            variable: "n".to_string(),
+            property: property.to_string(),
+        };
+        let upper_name = FilterExpression::FunctionCall {
+            name: "toUpper".to_string(),
+            args: vec![property("name")],
+        };
+        let merge = |name: &str| {
+            MergeOperator::new(
+                Arc::clone(&store),
+                "n".to_string(),
+                vec!["Person".to_string()],
+                vec![("name".to_string(), Value::String(name.into()))],
+                vec![("shout".to_string(), upper_name.clone())],
+                vec![(
+                    "visits".to_string(),
+                    FilterExpression::Binary {
+                        left: Box::new(property("visits")),
+                        op: BinaryFilterOp::Add,
+                        right: Box::new(FilterExpression::Literal(Value::Int64(1))),
+                    },
+                )],
+            )
+        };
+
+        // ON MATCH reads the matched node
+        merge("Gus").next().unwrap();
+        let node = store.get_node(gus).unwrap();
+        assert_eq!(node.get_property("visits"), Some(&Value::Int64(2)));
+        assert_eq!(node.get_property("shout"), None);
+
+        // ON CREATE reads the key properties of the node being created
+        let chunk = merge("Vincent").next().unwrap().unwrap();
+        let vincent = chunk.column(0).unwrap().get_node_id(0).unwrap();
+        let node = store.get_node(vincent).unwrap();
+        assert_eq!(
+            node.get_property("shout"),
+            Some(&Value::String("VINCENT".into()))
+        );
+        assert_eq!(node.get_property("visits"), None);
+    }
+
+    #[test]
+    fn test_merge_claims_key_when_run() {
+        let store = Arc::new(LpgStore::new());
+        let claims = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = Arc::clone(&claims);
+        let mut merge = MergeOperator::new(
+            Arc::clone(&store),
+            "n".to_string(),
+            vec!["Person".to_string()],
+            vec![("name".to_string(), Value::String("Jules".into()))],
+            vec![],
+            vec![],
+        )
+        .with_key_claim(Box::new(move || {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }));
+        assert_eq!(claims.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        merge.next().unwrap();
+        assert_eq!(claims.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // A failed claim stops the merge before anything is written
+        let mut merge = MergeOperator::new(
+            Arc::clone(&store),
+            "n".to_string(),
+            vec!["Person".to_string()],
+            vec![("name".to_string(), Value::String("Mia".into()))],
+            vec![],
+            vec![],
+        )
+        .with_key_claim(Box::new(|| {
+            Err(OperatorError::Execution("transaction is not active".into()))
+        }));
+        assert!(merge.next().is_err());
+        assert_eq!(store.nodes_by_label("Person").len(), 1);
+    }
+
+    #[test]
+    fn test_merge_creates_node_in_transaction() {
+        let store = Arc::new(LpgStore::new());
+        let tx = TxId::new(7);
+
+        let mut merge = MergeOperator::new(
+            Arc::clone(&store),
+            "n".to_string(),
+            vec!["Person".to_string()],
+            vec![("name".to_string(), Value::String("Eve".into()))],
+            vec![],
+            vec![],
+        )
+        .with_tx_context(store.current_epoch(), Some(tx));
+
+        let chunk = merge.next().unwrap().unwrap();
+        let node_id = chunk.column(0).unwrap().get_node_id(0).unwrap();
+        assert!(store.get_node(node_id).is_some());
+
+        // The node belongs to the transaction and goes away on rollback
+        store.discard_uncommitted_versions(tx);
+        assert!(store.get_node(node_id).is_none());
+    }
 }
@@ -0,0 +1,516 @@
+//! Sort-merge join for inputs already sorted on the join key.
+//!
+//! When both inputs arrive in ascending key order - say, each from a
+//! [`SortOperator`](super::SortOperator) on the key - the join can walk them
+//! in step instead of building a hash table. Only the run of right rows
+//! sharing the current key is buffered; every left row with that key is
+//! paired with each of them, so duplicate keys on both sides give the full
+//! cartesian product within the key group.
+//!
+//! Keys are walked in the numeric order the sort produces, where `2` and
+//! `2.0` tie, but they only match when their [`HashKey`]s do - the same
+//! equality the hash join uses - so the choice of join never changes the
+//! result.
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+use grafeo_common::types::{LogicalType, Value};
+
+use super::{HashKey, Operator, OperatorError, OperatorResult};
+use crate::execution::DataChunk;
+use crate::execution::chunk::DataChunkBuilder;
+
+/// Inner equi-join over two inputs sorted ascending on their key columns.
+///
+/// Output rows are the left columns followed by the right columns, in key
+/// order. Rows with a null key never match, and neither do keys of
+/// different types: an integer never matches a float, as in the hash join.
+/// Inputs that aren't actually sorted give missing matches, not an error.
+pub struct MergeJoinOperator {
+    /// Left input.
+    left: Cursor,
+    /// Right input.
+    right: Cursor,
+    /// Key columns on the left side.
+    left_keys: Vec<usize>,
+    /// Key columns on the right side.
+    right_keys: Vec<usize>,
+    /// Output schema (left columns + right columns).
+    output_schema: Vec<LogicalType>,
+    /// Key of the buffered right-side group.
+    group_key: Option<Vec<Value>>,
+    /// Right rows whose key sorts equal to `group_key`, with their join keys.
+    group: Vec<(HashKey, Vec<Value>)>,
+    /// Joined rows not yet emitted.
+    pending: VecDeque<Vec<Value>>,
+}
+
+impl MergeJoinOperator {
+    /// Creates a merge join.
+    ///
+    /// # Arguments
+    /// * `left` - Left input, sorted ascending on `left_keys`.
+    /// * `right` - Right input, sorted ascending on `right_keys`.
+    /// * `left_keys` - Key columns on the left side.
+    /// * `right_keys` - Key columns on the right side, pairwise with `left_keys`.
+    /// * `output_schema` - Schema of the output (left columns + right columns).
+    pub fn new(
+        left: Box<dyn Operator>,
+        right: Box<dyn Operator>,
+        left_keys: Vec<usize>,
+        right_keys: Vec<usize>,
+        output_schema: Vec<LogicalType>,
+    ) -> Self {
+        Self {
+            left: Cursor::new(left),
+            right: Cursor::new(right),
+            left_keys,
+            right_keys,
+            output_schema,
+            group_key: None,
+            group: Vec::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Joins rows until `target` output rows are pending or the inputs run out.
+    fn fill(&mut self, target: usize) -> Result<(), OperatorError> {
+        while self.pending.len() < target {
+            let Some(left_key) = self.left.key(&self.left_keys)? else {
+                return Ok(());
+            };
+            if left_key.iter().any(Value::is_null) {
+                self.left.advance();
+                continue;
+            }
+
+            // Rows matching the buffered group pair with all of it
+            if let Some(group_key) = &self.group_key {
+                match compare_keys(&left_key, group_key) {
+                    Some(Ordering::Equal) => {
+                        let left_row = self.left.row_values();
+                        let join_key = join_key(&left_key);
+                        for (_, right_row) in self.group.iter().filter(|(k, _)| *k == join_key) {
+                            let mut row = left_row.clone();
+                            row.extend(right_row.iter().cloned());
+                            self.pending.push_back(row);
+                        }
+                        self.left.advance();
+                        continue;
+                    }
+                    Some(Ordering::Greater) => {
+                        self.group_key = None;
+                        self.group.clear();
+                    }
+                    // Out of order or incomparable: no match for this row
+                    _ => {
+                        self.left.advance();
+                        continue;
+                    }
+                }
+            }
+
+            // Skip right rows below the left key, then buffer the equal ones
+            loop {
+                let Some(right_key) = self.right.key(&self.right_keys)? else {
+                    break;
+                };
+                if right_key.iter().any(Value::is_null) {
+                    self.right.advance();
+                    continue;
+                }
+                match compare_keys(&right_key, &left_key) {
+                    Some(Ordering::Less) | None => self.right.advance(),
+                    Some(Ordering::Equal) => {
+                        self.group
+                            .push((join_key(&right_key), self.right.row_values()));
+                        self.right.advance();
+                    }
+                    Some(Ordering::Greater) => break,
+                }
+            }
+
+            if self.group.is_empty() {
+                if self.right.is_exhausted() {
+                    // Nothing left on the right for any later left row
+                    return Ok(());
+                }
+                self.left.advance();
+            } else {
+                self.group_key = Some(left_key);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Operator for MergeJoinOperator {
+    fn next(&mut self) -> OperatorResult {
+        let mut builder = DataChunkBuilder::with_capacity(&self.output_schema, 2048);
+        self.fill(2048)?;
+
+        while !builder.is_full() {
+            let Some(row) = self.pending.pop_front() else {
+                break;
+            };
+            for (col_idx, value) in row.into_iter().enumerate() {
+                if let Some(col) = builder.column_mut(col_idx) {
+                    col.push_value(value);
+                }
+            }
+            builder.advance_row();
+        }
+
+        if builder.row_count() > 0 {
+            Ok(Some(builder.finish()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.left.reset();
+        self.right.reset();
+        self.group_key = None;
+        self.group.clear();
+        self.pending.clear();
+    }
+
+    fn name(&self) -> &'static str {
+        "MergeJoin"
+    }
+}
+
+/// Row-at-a-time position in one input.
+struct Cursor {
+    input: Box<dyn Operator>,
+    /// Current chunk, if any rows of it are left.
+    chunk: Option<DataChunk>,
+    /// Selected row indices of the current chunk.
+    rows: Vec<usize>,
+    /// Position in `rows`.
+    position: usize,
+    /// Whether the input has no more chunks.
+    exhausted: bool,
+}
+
+impl Cursor {
+    fn new(input: Box<dyn Operator>) -> Self {
+        Self {
+            input,
+            chunk: None,
+            rows: Vec::new(),
+            position: 0,
+            exhausted: false,
+        }
+    }
+
+    /// Makes sure a current row is loaded; false once the input is done.
+    fn load(&mut self) -> Result<bool, OperatorError> {
+        while self.position >= self.rows.len() {
+            if self.exhausted {
+                return Ok(false);
+            }
+            match self.input.next()? {
+                Some(chunk) => {
+                    self.rows = chunk.selected_indices().collect();
+                    self.chunk = Some(chunk);
+                    self.position = 0;
+                }
+                None => {
+                    self.chunk = None;
+                    self.rows.clear();
+                    self.position = 0;
+                    self.exhausted = true;
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns the key of the current row, or `None` once the input is done.
+    fn key(&mut self, columns: &[usize]) -> Result<Option<Vec<Value>>, OperatorError> {
+        if !self.load()? {
+            return Ok(None);
+        }
+        let (chunk, row) = self.current();
+        Ok(Some(
+            columns
+                .iter()
+                .map(|&col| {
+                    chunk
+                        .column(col)
+                        .and_then(|c| c.get_value(row))
+                        .unwrap_or(Value::Null)
+                })
+                .collect(),
+        ))
+    }
+
+    /// Returns every column of the current row. Call only after `key`.
+    fn row_values(&self) -> Vec<Value> {
+        let (chunk, row) = self.current();
+        (0..chunk.column_count())
+            .map(|col| {
+                chunk
+                    .column(col)
+                    .and_then(|c| c.get_value(row))
+                    .unwrap_or(Value::Null)
+            })
+            .collect()
+    }
+
+    fn current(&self) -> (&DataChunk, usize) {
+        let chunk = self
+            .chunk
+            .as_ref()
+            .expect("cursor has a current row: load() returned true");
+        (chunk, self.rows[self.position])
+    }
+
+    fn advance(&mut self) {
+        self.position += 1;
+    }
+
+    fn is_exhausted(&self) -> bool {
+        // Once `key` has returned `None` the input is known to be done
+        self.exhausted && self.position >= self.rows.len()
+    }
+
+    fn reset(&mut self) {
+        self.input.reset();
+        self.chunk = None;
+        self.rows.clear();
+        self.position = 0;
+        self.exhausted = false;
+    }
+}
+
+/// The key two rows must share to join.
+fn join_key(key: &[Value]) -> HashKey {
+    HashKey::Composite(key.iter().map(HashKey::from_value).collect())
+}
+
+/// Compares two keys column by column; `None` if some column pair can't be
+/// compared (different types).
+fn compare_keys(a: &[Value], b: &[Value]) -> Option<Ordering> {
+    for (a, b) in a.iter().zip(b) {
+        let ordering = match (a, b) {
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Int64(a), Value::Int64(b)) => a.cmp(b),
+            (Value::Float64(a), Value::Float64(b)) => a.partial_cmp(b)?,
+            (Value::Int64(a), Value::Float64(b)) => (*a as f64).partial_cmp(b)?,
+            (Value::Float64(a), Value::Int64(b)) => a.partial_cmp(&(*b as f64))?,
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::Bytes(a), Value::Bytes(b)) => a.cmp(b),
+            _ => a.temporal_cmp(b)?,
+        };
+        if ordering != Ordering::Equal {
+            return Some(ordering);
+        }
+    }
+    Some(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockOperator {
+        chunks: Vec<DataChunk>,
+        position: usize,
+    }
+
+    impl Operator for MockOperator {
+        fn next(&mut self) -> OperatorResult {
+            if self.position < self.chunks.len() {
+                let chunk = std::mem::replace(&mut self.chunks[self.position], DataChunk::empty());
+                self.position += 1;
+                Ok(Some(chunk))
+            } else {
+                Ok(None)
+            }
+        }
+
+        fn reset(&mut self) {
+            self.position = 0;
+        }
+
+        fn name(&self) -> &'static str {
+            "Mock"
+        }
+    }
+
+    /// Sorted `(key, tag)` rows, split into chunks of `chunk_size` rows so
+    /// key groups straddle chunk boundaries.
+    fn input(rows: &[(Option<i64>, &str)], chunk_size: usize) -> Box<dyn Operator> {
+        let chunks = rows
+            .chunks(chunk_size)
+            .map(|rows| {
+                let mut builder = DataChunkBuilder::new(&[LogicalType::Int64, LogicalType::String]);
+                for (key, tag) in rows {
+                    let key = key.map_or(Value::Null, Value::Int64);
+                    builder.column_mut(0).unwrap().push_value(key);
+                    builder.column_mut(1).unwrap().push_string(*tag);
+                    builder.advance_row();
+                }
+                builder.finish()
+            })
+            .collect();
+        Box::new(MockOperator {
+            chunks,
+            position: 0,
+        })
+    }
+
+    /// Runs the join and returns `(key, left tag, right tag)` per output row.
+    fn merge(left: Box<dyn Operator>, right: Box<dyn Operator>) -> Vec<(i64, String, String)> {
+        let schema = vec![
+            LogicalType::Int64,
+            LogicalType::String,
+            LogicalType::Int64,
+            LogicalType::String,
+        ];
+        let mut join = MergeJoinOperator::new(left, right, vec![0], vec![0], schema);
+
+        let mut rows = Vec::new();
+        while let Some(chunk) = join.next().unwrap() {
+            for row in chunk.selected_indices() {
+                let value = |col: usize| chunk.column(col).unwrap().get_value(row).unwrap();
+                let (Value::Int64(key), Value::String(l), Value::Int64(rkey), Value::String(r)) =
+                    (value(0), value(1), value(2), value(3))
+                else {
+                    panic!("unexpected row");
+                };
+                assert_eq!(key, rkey);
+                rows.push((key, l.to_string(), r.to_string()));
+            }
+        }
+        rows
+    }
+
+    fn row(key: i64, left: &str, right: &str) -> (i64, String, String) {
+        (key, left.to_string(), right.to_string())
+    }
+
+    #[test]
+    fn test_merge_join_unique_keys() {
+        let left = input(&[(Some(1), "a"), (Some(3), "b"), (Some(5), "c")], 2);
+        let right = input(
+            &[
+                (Some(2), "x"),
+                (Some(3), "y"),
+                (Some(5), "z"),
+                (Some(6), "w"),
+            ],
+            1,
+        );
+
+        assert_eq!(merge(left, right), vec![row(3, "b", "y"), row(5, "c", "z")]);
+    }
+
+    #[test]
+    fn test_merge_join_duplicates_on_one_side() {
+        let left = input(
+            &[
+                (Some(1), "a"),
+                (Some(1), "b"),
+                (Some(1), "c"),
+                (Some(2), "d"),
+            ],
+            2,
+        );
+        let right = input(&[(Some(1), "x"), (Some(2), "y")], 1);
+
+        assert_eq!(
+            merge(left, right),
+            vec![
+                row(1, "a", "x"),
+                row(1, "b", "x"),
+                row(1, "c", "x"),
+                row(2, "d", "y"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_join_duplicates_on_both_sides() {
+        let left = input(
+            &[
+                (Some(1), "a"),
+                (Some(2), "b"),
+                (Some(2), "c"),
+                (Some(4), "d"),
+            ],
+            3,
+        );
+        let right = input(
+            &[
+                (Some(2), "x"),
+                (Some(2), "y"),
+                (Some(2), "z"),
+                (Some(4), "w"),
+            ],
+            2,
+        );
+
+        // Each left row with key 2 pairs with every right row with key 2
+        assert_eq!(
+            merge(left, right),
+            vec![
+                row(2, "b", "x"),
+                row(2, "b", "y"),
+                row(2, "b", "z"),
+                row(2, "c", "x"),
+                row(2, "c", "y"),
+                row(2, "c", "z"),
+                row(4, "d", "w"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_join_mixed_numeric_keys_match_by_type() {
+        let input = |keys: &[Value]| -> Box<dyn Operator> {
+            let mut builder = DataChunkBuilder::new(&[LogicalType::Any]);
+            for key in keys {
+                builder.column_mut(0).unwrap().push_value(key.clone());
+                builder.advance_row();
+            }
+            Box::new(MockOperator {
+                chunks: vec![builder.finish()],
+                position: 0,
+            })
+        };
+        // Sorted numerically, so 2 and 2.0 tie in either order
+        let left = input(&[Value::Int64(1), Value::Float64(2.0), Value::Int64(2)]);
+        let right = input(&[Value::Int64(2), Value::Float64(2.0), Value::Float64(3.0)]);
+        let schema = vec![LogicalType::Any, LogicalType::Any];
+        let mut join = MergeJoinOperator::new(left, right, vec![0], vec![0], schema);
+
+        let mut pairs = Vec::new();
+        while let Some(chunk) = join.next().unwrap() {
+            for row in chunk.selected_indices() {
+                let value = |col: usize| chunk.column(col).unwrap().get_value(row).unwrap();
+                pairs.push((value(0), value(1)));
+            }
+        }
+        // Each key only pairs with the key of its own type
+        assert_eq!(
+            pairs,
+            vec![
+                (Value::Float64(2.0), Value::Float64(2.0)),
+                (Value::Int64(2), Value::Int64(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_join_null_keys_never_match() {
+        let left = input(&[(None, "a"), (Some(1), "b")], 1);
+        let right = input(&[(None, "x"), (Some(1), "y")], 1);
+
+        assert_eq!(merge(left, right), vec![row(1, "b", "y")]);
+    }
+}
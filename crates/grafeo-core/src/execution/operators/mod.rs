@@ -13,20 +13,31 @@
 //! - [`FilterOperator`] - Apply predicates
 //! - [`ProjectOperator`] - Select/transform columns
 //! - [`HashJoinOperator`] - Efficient equi-joins
+//! - [`MergeJoinOperator`] - Equi-joins over inputs sorted on the key
+//! - [`IndexNestedLoopJoinOperator`] - Equi-joins probing a hash index
 //! - [`HashAggregateOperator`] - Group by with aggregation
 //! - [`SortOperator`] - Order results
+//! - [`TopNOperator`] - Order results and keep the first N
 //! - [`LimitOperator`] - SKIP and LIMIT
 //!
+//! [`ProfiledOperator`] wraps any of these to count rows and time for
+//! `EXPLAIN ANALYZE`, and [`CancellableOperator`] to stop them once a
+//! [`CancellationToken`] fires.
+//!
 //! The [`push`] submodule has push-based variants for pipeline execution.
 
 mod aggregate;
+mod cancel;
 mod distinct;
 mod expand;
 mod filter;
+mod index_join;
 mod join;
 mod limit;
 mod merge;
+mod merge_join;
 mod mutation;
+mod profile;
 mod project;
 pub mod push;
 mod scan;
@@ -40,29 +51,36 @@ mod variable_length_expand;
 pub use aggregate::{
     AggregateExpr, AggregateFunction, HashAggregateOperator, SimpleAggregateOperator,
 };
+pub use cancel::{CancellableOperator, CancellationToken};
 pub use distinct::DistinctOperator;
 pub use expand::ExpandOperator;
 pub use filter::{
-    BinaryFilterOp, ExpressionPredicate, FilterExpression, FilterOperator, Predicate, UnaryFilterOp,
+    BinaryFilterOp, ExpressionPredicate, FilterExpression, FilterOperator, Predicate, RandomSource,
+    UnaryFilterOp,
 };
+pub use index_join::IndexNestedLoopJoinOperator;
 pub use join::{
-    EqualityCondition, HashJoinOperator, HashKey, JoinCondition, JoinType, NestedLoopJoinOperator,
+    DEFAULT_JOIN_PARTITIONS, EqualityCondition, HashJoinOperator, HashKey, JoinCondition, JoinType,
+    NestedLoopJoinOperator, SpillableHashJoinOperator,
 };
 pub use limit::{LimitOperator, LimitSkipOperator, SkipOperator};
-pub use merge::MergeOperator;
+pub use merge::{MergeKeyClaim, MergeOperator};
+pub use merge_join::MergeJoinOperator;
 pub use mutation::{
-    AddLabelOperator, CreateEdgeOperator, CreateNodeOperator, DeleteEdgeOperator,
-    DeleteNodeOperator, PropertySource, RemoveLabelOperator, SetPropertyOperator,
+    AddLabelOperator, ConstraintChecker, CreateEdgeOperator, CreateNodeOperator,
+    DeleteEdgeOperator, DeleteNodeOperator, PropertySource, RemoveLabelOperator,
+    SetPropertyOperator,
 };
+pub use profile::{OperatorProfile, ProfiledOperator};
 pub use project::{ProjectExpr, ProjectOperator};
 pub use push::{
     AggregatePushOperator, DistinctMaterializingOperator, DistinctPushOperator, FilterPushOperator,
     LimitPushOperator, ProjectPushOperator, SkipLimitPushOperator, SkipPushOperator,
     SortPushOperator, SpillableAggregatePushOperator, SpillableSortPushOperator,
 };
-pub use scan::ScanOperator;
+pub use scan::{IndexLookup, ScanOperator, build_node_zone_map};
 pub use shortest_path::ShortestPathOperator;
-pub use sort::{NullOrder, SortDirection, SortKey, SortOperator};
+pub use sort::{NullOrder, SortDirection, SortKey, SortOperator, TopNOperator};
 pub use union::UnionOperator;
 pub use unwind::UnwindOperator;
 pub use variable_length_expand::VariableLengthExpandOperator;
@@ -91,6 +109,22 @@ pub enum OperatorError {
     /// Execution error.
     #[error("execution error: {0}")]
     Execution(String),
+    /// An entity is being written by another open transaction.
+    #[error("write conflict: {0}")]
+    WriteConflict(String),
+    /// The query was cancelled or ran past its deadline.
+    #[error("query cancelled")]
+    Cancelled,
+    /// The buffer manager refused a memory grant the operator could not do
+    /// without.
+    #[error("out of memory: could not reserve {requested} bytes")]
+    OutOfMemory {
+        /// Size of the refused grant.
+        requested: usize,
+    },
+    /// A write would break a schema constraint.
+    #[error("constraint violation: {0}")]
+    ConstraintViolation(String),
 }
 
 /// The core trait for pull-based operators.
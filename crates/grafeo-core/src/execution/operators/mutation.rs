@@ -8,12 +8,92 @@
 
 use std::sync::Arc;
 
-use grafeo_common::types::{EdgeId, EpochId, LogicalType, NodeId, TxId, Value};
+use grafeo_common::types::{EdgeId, EpochId, LogicalType, NodeId, PropertyKey, TxId, Value};
 
 use super::{Operator, OperatorError, OperatorResult};
+use crate::execution::DataChunk;
 use crate::execution::chunk::DataChunkBuilder;
 use crate::graph::lpg::LpgStore;
 
+/// Checks node writes against schema constraints.
+///
+/// Mutation operators that are given a checker call it before each node
+/// property or label write, and fail with the error it returns; without one,
+/// every write is accepted. Checks only read: a write that passes is
+/// recorded by the store as it lands. The engine's catalog implements this
+/// for the constraints it holds.
+pub trait ConstraintChecker: Send + Sync {
+    /// Checks that `node`, just created with its labels but no properties
+    /// yet, may take all of `properties`.
+    ///
+    /// This covers what a property-by-property check can't see, such as a
+    /// required property that is missing altogether.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OperatorError::ConstraintViolation`] if the node would
+    /// break a constraint.
+    fn check_new_node(
+        &self,
+        store: &LpgStore,
+        node: NodeId,
+        properties: &[(&str, Value)],
+        tx: TxId,
+    ) -> Result<(), OperatorError>;
+
+    /// Checks that `node`, as `tx` sees it, may take `value` for `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OperatorError::ConstraintViolation`] if the write would
+    /// break a constraint.
+    fn check_node_property(
+        &self,
+        store: &LpgStore,
+        node: NodeId,
+        key: &str,
+        value: &Value,
+        tx: TxId,
+    ) -> Result<(), OperatorError>;
+
+    /// Checks that `node`, as `tx` sees it, may take `label` with the
+    /// properties it already has.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OperatorError::ConstraintViolation`] if the node would
+    /// break a constraint of the label.
+    fn check_new_label(
+        &self,
+        store: &LpgStore,
+        node: NodeId,
+        label: &str,
+        tx: TxId,
+    ) -> Result<(), OperatorError>;
+}
+
+/// Claims a node for `tx`, failing if another open transaction wrote it.
+fn lock_node(store: &LpgStore, id: NodeId, tx: TxId) -> Result<(), OperatorError> {
+    if store.try_lock_node(id, tx) {
+        Ok(())
+    } else {
+        Err(OperatorError::WriteConflict(format!(
+            "node {id} is being modified by another transaction"
+        )))
+    }
+}
+
+/// Claims an edge for `tx`, failing if another open transaction wrote it.
+fn lock_edge(store: &LpgStore, id: EdgeId, tx: TxId) -> Result<(), OperatorError> {
+    if store.try_lock_edge(id, tx) {
+        Ok(())
+    } else {
+        Err(OperatorError::WriteConflict(format!(
+            "edge {id} is being modified by another transaction"
+        )))
+    }
+}
+
 /// Operator that creates new nodes.
 ///
 /// For each input row, creates a new node with the specified labels
@@ -37,6 +117,8 @@ pub struct CreateNodeOperator {
     viewing_epoch: Option<EpochId>,
     /// Transaction ID for MVCC versioning.
     tx_id: Option<TxId>,
+    /// Constraints each property write is checked against.
+    constraints: Option<Arc<dyn ConstraintChecker>>,
 }
 
 /// Source for a property value.
@@ -76,6 +158,7 @@ impl CreateNodeOperator {
             executed: false,
             viewing_epoch: None,
             tx_id: None,
+            constraints: None,
         }
     }
 
@@ -85,6 +168,36 @@ impl CreateNodeOperator {
         self.tx_id = tx_id;
         self
     }
+
+    /// Checks every property of the created nodes against `constraints`.
+    pub fn with_constraints(mut self, constraints: Option<Arc<dyn ConstraintChecker>>) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Sets the properties of a node this operator created.
+    ///
+    /// Every property is checked before any is set. If one breaks a
+    /// constraint, the node is deleted again, so the failed write leaves
+    /// nothing behind even outside a transaction.
+    fn set_properties(
+        &self,
+        node: NodeId,
+        properties: Vec<(&str, Value)>,
+        epoch: EpochId,
+        tx: TxId,
+    ) -> Result<(), OperatorError> {
+        if let Some(constraints) = &self.constraints {
+            if let Err(err) = constraints.check_new_node(&self.store, node, &properties, tx) {
+                self.store.delete_node_versioned(node, epoch, tx);
+                return Err(err);
+            }
+        }
+        for (key, value) in properties {
+            self.store.set_node_property(node, key, value);
+        }
+        Ok(())
+    }
 }
 
 impl Operator for CreateNodeOperator {
@@ -107,16 +220,21 @@ impl Operator for CreateNodeOperator {
                     let node_id = self.store.create_node_versioned(&label_refs, epoch, tx);
 
                     // Set properties
-                    for (prop_name, source) in &self.properties {
-                        let value = match source {
-                            PropertySource::Column(col_idx) => chunk
-                                .column(*col_idx)
-                                .and_then(|c| c.get_value(row))
-                                .unwrap_or(Value::Null),
-                            PropertySource::Constant(v) => v.clone(),
-                        };
-                        self.store.set_node_property(node_id, prop_name, value);
-                    }
+                    let properties = self
+                        .properties
+                        .iter()
+                        .map(|(prop_name, source)| {
+                            let value = match source {
+                                PropertySource::Column(col_idx) => chunk
+                                    .column(*col_idx)
+                                    .and_then(|c| c.get_value(row))
+                                    .unwrap_or(Value::Null),
+                                PropertySource::Constant(v) => v.clone(),
+                            };
+                            (prop_name.as_str(), value)
+                        })
+                        .collect();
+                    self.set_properties(node_id, properties, epoch, tx)?;
 
                     // Copy input columns to output
                     for col_idx in 0..chunk.column_count() {
@@ -156,12 +274,15 @@ impl Operator for CreateNodeOperator {
             let node_id = self.store.create_node_versioned(&label_refs, epoch, tx);
 
             // Set properties from constants only
-            for (prop_name, source) in &self.properties {
-                if let PropertySource::Constant(value) = source {
-                    self.store
-                        .set_node_property(node_id, prop_name, value.clone());
-                }
-            }
+            let properties = self
+                .properties
+                .iter()
+                .filter_map(|(prop_name, source)| match source {
+                    PropertySource::Constant(value) => Some((prop_name.as_str(), value.clone())),
+                    PropertySource::Column(_) => None,
+                })
+                .collect();
+            self.set_properties(node_id, properties, epoch, tx)?;
 
             // Build output chunk with just the node ID
             let mut builder = DataChunkBuilder::with_capacity(&self.output_schema, 1);
@@ -353,6 +474,9 @@ impl Operator for CreateEdgeOperator {
 }
 
 /// Operator that deletes nodes.
+///
+/// Without `detach`, deleting a node that still has edges is an error, so
+/// no edge is left dangling.
 pub struct DeleteNodeOperator {
     /// The graph store to modify.
     store: Arc<LpgStore>,
@@ -366,8 +490,7 @@ pub struct DeleteNodeOperator {
     detach: bool,
     /// Epoch for MVCC versioning.
     viewing_epoch: Option<EpochId>,
-    /// Transaction ID for MVCC versioning (reserved for future use).
-    #[allow(dead_code)]
+    /// Transaction ID for MVCC versioning.
     tx_id: Option<TxId>,
 }
 
@@ -405,6 +528,7 @@ impl Operator for DeleteNodeOperator {
         let epoch = self
             .viewing_epoch
             .unwrap_or_else(|| self.store.current_epoch());
+        let tx = self.tx_id.unwrap_or(TxId::SYSTEM);
 
         if let Some(chunk) = self.input.next()? {
             let mut deleted_count = 0;
@@ -427,14 +551,19 @@ impl Operator for DeleteNodeOperator {
                     }
                 };
 
+                lock_node(&self.store, node_id, tx)?;
+
                 if self.detach {
                     // Delete all connected edges first
-                    // Note: Edge deletion will use epoch internally
-                    self.store.delete_node_edges(node_id);
+                    self.store.delete_node_edges_versioned(node_id, epoch, tx);
+                } else if self.store.has_connected_edges(node_id, epoch, tx) {
+                    return Err(OperatorError::Execution(format!(
+                        "cannot delete node {node_id} because it still has edges; use DETACH DELETE"
+                    )));
                 }
 
                 // Delete the node with MVCC versioning
-                if self.store.delete_node_at_epoch(node_id, epoch) {
+                if self.store.delete_node_versioned(node_id, epoch, tx) {
                     deleted_count += 1;
                 }
             }
@@ -472,8 +601,7 @@ pub struct DeleteEdgeOperator {
     output_schema: Vec<LogicalType>,
     /// Epoch for MVCC versioning.
     viewing_epoch: Option<EpochId>,
-    /// Transaction ID for MVCC versioning (reserved for future use).
-    #[allow(dead_code)]
+    /// Transaction ID for MVCC versioning.
     tx_id: Option<TxId>,
 }
 
@@ -509,6 +637,7 @@ impl Operator for DeleteEdgeOperator {
         let epoch = self
             .viewing_epoch
             .unwrap_or_else(|| self.store.current_epoch());
+        let tx = self.tx_id.unwrap_or(TxId::SYSTEM);
 
         if let Some(chunk) = self.input.next()? {
             let mut deleted_count = 0;
@@ -531,8 +660,10 @@ impl Operator for DeleteEdgeOperator {
                     }
                 };
 
+                lock_edge(&self.store, edge_id, tx)?;
+
                 // Delete the edge with MVCC versioning
-                if self.store.delete_edge_at_epoch(edge_id, epoch) {
+                if self.store.delete_edge_versioned(edge_id, epoch, tx) {
                     deleted_count += 1;
                 }
             }
@@ -559,6 +690,9 @@ impl Operator for DeleteEdgeOperator {
 }
 
 /// Operator that adds labels to nodes.
+///
+/// Input rows are passed through, so later clauses can still use the node.
+/// Adding a label the node already has does nothing.
 pub struct AddLabelOperator {
     /// The graph store.
     store: Arc<LpgStore>,
@@ -570,6 +704,10 @@ pub struct AddLabelOperator {
     labels: Vec<String>,
     /// Output schema.
     output_schema: Vec<LogicalType>,
+    /// Transaction ID, so rollback can revert the change.
+    tx_id: Option<TxId>,
+    /// Constraints the labelled nodes must meet.
+    constraints: Option<Arc<dyn ConstraintChecker>>,
 }
 
 impl AddLabelOperator {
@@ -587,14 +725,30 @@ impl AddLabelOperator {
             node_column,
             labels,
             output_schema,
+            tx_id: None,
+            constraints: None,
         }
     }
+
+    /// Sets the transaction the labels are changed in.
+    pub fn with_tx_id(mut self, tx_id: Option<TxId>) -> Self {
+        self.tx_id = tx_id;
+        self
+    }
+
+    /// Checks each node against the constraints of the labels it gains.
+    pub fn with_constraints(mut self, constraints: Option<Arc<dyn ConstraintChecker>>) -> Self {
+        self.constraints = constraints;
+        self
+    }
 }
 
 impl Operator for AddLabelOperator {
     fn next(&mut self) -> OperatorResult {
         if let Some(chunk) = self.input.next()? {
-            let mut updated_count = 0;
+            let mut builder =
+                DataChunkBuilder::with_capacity(&self.output_schema, chunk.row_count());
+            let tx = self.tx_id.unwrap_or(TxId::SYSTEM);
 
             for row in chunk.selected_indices() {
                 let node_val = chunk
@@ -614,20 +768,29 @@ impl Operator for AddLabelOperator {
                     }
                 };
 
-                // Add all labels
+                lock_node(&self.store, node_id, tx)?;
+
+                // Check every label before adding any
+                if let Some(constraints) = &self.constraints {
+                    for label in &self.labels {
+                        constraints.check_new_label(&self.store, node_id, label, tx)?;
+                    }
+                }
                 for label in &self.labels {
-                    if self.store.add_label(node_id, label) {
-                        updated_count += 1;
+                    self.store.add_label_versioned(node_id, label, tx);
+                }
+
+                // Copy input columns to output
+                for col_idx in 0..chunk.column_count() {
+                    if let (Some(src), Some(dst)) =
+                        (chunk.column(col_idx), builder.column_mut(col_idx))
+                    {
+                        dst.push_value(src.get_value(row).unwrap_or(Value::Null));
                     }
                 }
-            }
 
-            // Return a chunk with the update count
-            let mut builder = DataChunkBuilder::with_capacity(&self.output_schema, 1);
-            if let Some(dst) = builder.column_mut(0) {
-                dst.push_value(Value::Int64(updated_count));
+                builder.advance_row();
             }
-            builder.advance_row();
 
             return Ok(Some(builder.finish()));
         }
@@ -644,6 +807,9 @@ impl Operator for AddLabelOperator {
 }
 
 /// Operator that removes labels from nodes.
+///
+/// Input rows are passed through, so later clauses can still use the node.
+/// Removing a label the node doesn't have does nothing.
 pub struct RemoveLabelOperator {
     /// The graph store.
     store: Arc<LpgStore>,
@@ -655,6 +821,8 @@ pub struct RemoveLabelOperator {
     labels: Vec<String>,
     /// Output schema.
     output_schema: Vec<LogicalType>,
+    /// Transaction ID, so rollback can revert the change.
+    tx_id: Option<TxId>,
 }
 
 impl RemoveLabelOperator {
@@ -672,14 +840,23 @@ impl RemoveLabelOperator {
             node_column,
             labels,
             output_schema,
+            tx_id: None,
         }
     }
+
+    /// Sets the transaction the labels are changed in.
+    pub fn with_tx_id(mut self, tx_id: Option<TxId>) -> Self {
+        self.tx_id = tx_id;
+        self
+    }
 }
 
 impl Operator for RemoveLabelOperator {
     fn next(&mut self) -> OperatorResult {
         if let Some(chunk) = self.input.next()? {
-            let mut updated_count = 0;
+            let mut builder =
+                DataChunkBuilder::with_capacity(&self.output_schema, chunk.row_count());
+            let tx = self.tx_id.unwrap_or(TxId::SYSTEM);
 
             for row in chunk.selected_indices() {
                 let node_val = chunk
@@ -699,20 +876,24 @@ impl Operator for RemoveLabelOperator {
                     }
                 };
 
+                lock_node(&self.store, node_id, tx)?;
+
                 // Remove all labels
                 for label in &self.labels {
-                    if self.store.remove_label(node_id, label) {
-                        updated_count += 1;
+                    self.store.remove_label_versioned(node_id, label, tx);
+                }
+
+                // Copy input columns to output
+                for col_idx in 0..chunk.column_count() {
+                    if let (Some(src), Some(dst)) =
+                        (chunk.column(col_idx), builder.column_mut(col_idx))
+                    {
+                        dst.push_value(src.get_value(row).unwrap_or(Value::Null));
                     }
                 }
-            }
 
-            // Return a chunk with the update count
-            let mut builder = DataChunkBuilder::with_capacity(&self.output_schema, 1);
-            if let Some(dst) = builder.column_mut(0) {
-                dst.push_value(Value::Int64(updated_count));
+                builder.advance_row();
             }
-            builder.advance_row();
 
             return Ok(Some(builder.finish()));
         }
@@ -731,7 +912,8 @@ impl Operator for RemoveLabelOperator {
 /// Operator that sets properties on nodes or edges.
 ///
 /// This operator reads node/edge IDs from a column and sets the
-/// specified properties on each entity.
+/// specified properties on each entity. Setting a property to null removes
+/// it.
 pub struct SetPropertyOperator {
     /// The graph store.
     store: Arc<LpgStore>,
@@ -743,8 +925,16 @@ pub struct SetPropertyOperator {
     is_edge: bool,
     /// Properties to set (name -> source).
     properties: Vec<(String, PropertySource)>,
+    /// Sources of maps whose entries are all set as properties.
+    property_maps: Vec<PropertySource>,
+    /// Whether properties not being set are removed first.
+    replace: bool,
     /// Output schema.
     output_schema: Vec<LogicalType>,
+    /// Transaction ID, so rollback can revert the change.
+    tx_id: Option<TxId>,
+    /// Constraints each node property write is checked against.
+    constraints: Option<Arc<dyn ConstraintChecker>>,
 }
 
 impl SetPropertyOperator {
@@ -762,7 +952,11 @@ impl SetPropertyOperator {
             entity_column: node_column,
             is_edge: false,
             properties,
+            property_maps: Vec::new(),
+            replace: false,
             output_schema,
+            tx_id: None,
+            constraints: None,
         }
     }
 
@@ -780,9 +974,82 @@ impl SetPropertyOperator {
             entity_column: edge_column,
             is_edge: true,
             properties,
+            property_maps: Vec::new(),
+            replace: false,
             output_schema,
+            tx_id: None,
+            constraints: None,
         }
     }
+
+    /// Sets the transaction the properties are written in.
+    pub fn with_tx_id(mut self, tx_id: Option<TxId>) -> Self {
+        self.tx_id = tx_id;
+        self
+    }
+
+    /// Also sets every entry of the maps these sources produce, as for
+    /// Cypher `SET n += $props`. A source that isn't a map is an error.
+    pub fn with_property_maps(mut self, property_maps: Vec<PropertySource>) -> Self {
+        self.property_maps = property_maps;
+        self
+    }
+
+    /// Removes all other properties first, as for Cypher `SET n = {...}`.
+    pub fn with_replace(mut self, replace: bool) -> Self {
+        self.replace = replace;
+        self
+    }
+
+    /// Checks node property writes against `constraints`; edge writes are
+    /// never checked.
+    pub fn with_constraints(mut self, constraints: Option<Arc<dyn ConstraintChecker>>) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Reads a property value for `row`.
+    fn value_of(source: &PropertySource, chunk: &DataChunk, row: usize) -> Value {
+        match source {
+            PropertySource::Column(col_idx) => chunk
+                .column(*col_idx)
+                .and_then(|c| c.get_value(row))
+                .unwrap_or(Value::Null),
+            PropertySource::Constant(v) => v.clone(),
+        }
+    }
+
+    /// Sets one property on the entity, removing it if `value` is null.
+    fn write(
+        &self,
+        entity_id: u64,
+        key: &str,
+        value: Value,
+        tx: TxId,
+    ) -> Result<(), OperatorError> {
+        if let (false, Some(constraints)) = (self.is_edge, &self.constraints) {
+            constraints.check_node_property(&self.store, NodeId(entity_id), key, &value, tx)?;
+        }
+        match (self.is_edge, value) {
+            (true, Value::Null) => {
+                self.store
+                    .remove_edge_property_versioned(EdgeId(entity_id), key, tx);
+            }
+            (true, value) => {
+                self.store
+                    .set_edge_property_versioned(EdgeId(entity_id), key, value, tx);
+            }
+            (false, Value::Null) => {
+                self.store
+                    .remove_node_property_versioned(NodeId(entity_id), key, tx);
+            }
+            (false, value) => {
+                self.store
+                    .set_node_property_versioned(NodeId(entity_id), key, value, tx);
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Operator for SetPropertyOperator {
@@ -790,6 +1057,7 @@ impl Operator for SetPropertyOperator {
         if let Some(chunk) = self.input.next()? {
             let mut builder =
                 DataChunkBuilder::with_capacity(&self.output_schema, chunk.row_count());
+            let tx = self.tx_id.unwrap_or(TxId::SYSTEM);
 
             for row in chunk.selected_indices() {
                 let entity_val = chunk
@@ -812,25 +1080,57 @@ impl Operator for SetPropertyOperator {
                     }
                 };
 
-                // Set all properties
+                if self.is_edge {
+                    lock_edge(&self.store, EdgeId(entity_id), tx)?;
+                } else {
+                    lock_node(&self.store, NodeId(entity_id), tx)?;
+                }
+
+                // Expand property maps into individual assignments
+                let mut assignments: Vec<(PropertyKey, Value)> = Vec::new();
+                for source in &self.property_maps {
+                    match Self::value_of(source, &chunk, row) {
+                        Value::Map(map) => assignments
+                            .extend(map.iter().map(|(key, value)| (key.clone(), value.clone()))),
+                        Value::Null => {}
+                        other => {
+                            return Err(OperatorError::TypeMismatch {
+                                expected: "Map".to_string(),
+                                found: format!("{other:?}"),
+                            });
+                        }
+                    }
+                }
                 for (prop_name, source) in &self.properties {
-                    let value = match source {
-                        PropertySource::Column(col_idx) => chunk
-                            .column(*col_idx)
-                            .and_then(|c| c.get_value(row))
-                            .unwrap_or(Value::Null),
-                        PropertySource::Constant(v) => v.clone(),
-                    };
+                    assignments.push((
+                        PropertyKey::from(prop_name.as_str()),
+                        Self::value_of(source, &chunk, row),
+                    ));
+                }
 
-                    if self.is_edge {
+                if self.replace {
+                    let existing: Vec<PropertyKey> = if self.is_edge {
                         self.store
-                            .set_edge_property(EdgeId(entity_id), prop_name, value);
+                            .get_edge(EdgeId(entity_id))
+                            .map(|edge| edge.properties.into_keys().collect())
                     } else {
                         self.store
-                            .set_node_property(NodeId(entity_id), prop_name, value);
+                            .get_node(NodeId(entity_id))
+                            .map(|node| node.properties.into_keys().collect())
+                    }
+                    .unwrap_or_default();
+                    for key in existing {
+                        if !assignments.iter().any(|(name, _)| *name == key) {
+                            self.write(entity_id, key.as_str(), Value::Null, tx)?;
+                        }
                     }
                 }
 
+                // Set all properties
+                for (key, value) in assignments {
+                    self.write(entity_id, key.as_str(), value, tx)?;
+                }
+
                 // Copy input columns to output
                 for col_idx in 0..chunk.column_count() {
                     if let (Some(src), Some(dst)) =
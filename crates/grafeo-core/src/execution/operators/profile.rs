@@ -0,0 +1,176 @@
+//! Profiling wrapper for `EXPLAIN ANALYZE`.
+//!
+//! [`ProfiledOperator`] sits between an operator and its consumer and counts
+//! what passes through, without touching the chunks themselves. The counts
+//! land in a shared [`OperatorProfile`] so they can be read after the plan
+//! has run (and the operator tree has been dropped).
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use super::{Operator, OperatorResult};
+
+/// Counters collected for one operator while it runs.
+#[derive(Debug, Default)]
+pub struct OperatorProfile {
+    rows: AtomicU64,
+    chunks: AtomicU64,
+    nanos: AtomicU64,
+}
+
+impl OperatorProfile {
+    /// Creates an empty profile.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of rows the operator emitted.
+    ///
+    /// Only chunks a consumer actually pulled count, so an input cut short
+    /// by a `LIMIT` reports the rows it produced before it was abandoned.
+    #[must_use]
+    pub fn rows(&self) -> u64 {
+        self.rows.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of non-empty results from `next`.
+    #[must_use]
+    pub fn chunks(&self) -> u64 {
+        self.chunks.load(Ordering::Relaxed)
+    }
+
+    /// Returns the wall-clock time spent in `next`.
+    ///
+    /// This includes time spent pulling from the operator's inputs.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::Relaxed))
+    }
+}
+
+/// Wraps an operator and records its output into an [`OperatorProfile`].
+///
+/// Results are passed through unchanged, errors included.
+pub struct ProfiledOperator {
+    inner: Box<dyn Operator>,
+    profile: Arc<OperatorProfile>,
+}
+
+impl ProfiledOperator {
+    /// Wraps `inner`, recording into `profile`.
+    pub fn new(inner: Box<dyn Operator>, profile: Arc<OperatorProfile>) -> Self {
+        Self { inner, profile }
+    }
+
+    /// Returns the profile this operator records into.
+    #[must_use]
+    pub fn profile(&self) -> &Arc<OperatorProfile> {
+        &self.profile
+    }
+}
+
+impl Operator for ProfiledOperator {
+    fn next(&mut self) -> OperatorResult {
+        let start = Instant::now();
+        let result = self.inner.next();
+        let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.profile.nanos.fetch_add(nanos, Ordering::Relaxed);
+
+        if let Ok(Some(chunk)) = &result {
+            self.profile
+                .rows
+                .fetch_add(chunk.row_count() as u64, Ordering::Relaxed);
+            self.profile.chunks.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::DataChunk;
+    use crate::execution::chunk::DataChunkBuilder;
+    use crate::execution::operators::LimitOperator;
+    use grafeo_common::types::LogicalType;
+
+    struct MockOperator {
+        chunks: Vec<DataChunk>,
+        position: usize,
+    }
+
+    impl Operator for MockOperator {
+        fn next(&mut self) -> OperatorResult {
+            if self.position < self.chunks.len() {
+                let chunk = std::mem::replace(&mut self.chunks[self.position], DataChunk::empty());
+                self.position += 1;
+                Ok(Some(chunk))
+            } else {
+                Ok(None)
+            }
+        }
+
+        fn reset(&mut self) {
+            self.position = 0;
+        }
+
+        fn name(&self) -> &'static str {
+            "Mock"
+        }
+    }
+
+    fn create_int_chunk(values: &[i64]) -> DataChunk {
+        let mut builder = DataChunkBuilder::new(&[LogicalType::Int64]);
+        for &v in values {
+            builder.column_mut(0).unwrap().push_int64(v);
+            builder.advance_row();
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn test_profiled_operator_counts_pulled_rows() {
+        let input = MockOperator {
+            chunks: vec![
+                create_int_chunk(&[1, 2, 3]),
+                create_int_chunk(&[4, 5, 6]),
+                create_int_chunk(&[7, 8, 9]),
+            ],
+            position: 0,
+        };
+        let input_profile = Arc::new(OperatorProfile::new());
+        let limit_profile = Arc::new(OperatorProfile::new());
+        let limit = LimitOperator::new(
+            Box::new(ProfiledOperator::new(
+                Box::new(input),
+                Arc::clone(&input_profile),
+            )),
+            4,
+            vec![LogicalType::Int64],
+        );
+        let mut op = ProfiledOperator::new(Box::new(limit), Arc::clone(&limit_profile));
+        assert_eq!(op.name(), "Limit");
+
+        let mut total = 0;
+        while let Some(chunk) = op.next().unwrap() {
+            total += chunk.row_count();
+        }
+
+        assert_eq!(total, 4);
+        assert_eq!(limit_profile.rows(), 4);
+        // The limit stopped pulling after the second chunk
+        assert_eq!(input_profile.rows(), 6);
+        assert_eq!(input_profile.chunks(), 2);
+        assert!(limit_profile.elapsed() >= input_profile.elapsed());
+    }
+}
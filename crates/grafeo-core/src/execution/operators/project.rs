@@ -1,10 +1,10 @@
 //! Project operator for selecting and transforming columns.
 
-use super::filter::{ExpressionPredicate, FilterExpression};
+use super::filter::{ExpressionPredicate, FilterExpression, Predicate};
 use super::{Operator, OperatorError, OperatorResult};
 use crate::execution::DataChunk;
 use crate::graph::lpg::LpgStore;
-use grafeo_common::types::{LogicalType, Value};
+use grafeo_common::types::{EpochId, LogicalType, TxId, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -45,6 +45,10 @@ pub struct ProjectOperator {
     output_types: Vec<LogicalType>,
     /// Optional store for property access.
     store: Option<Arc<LpgStore>>,
+    /// Epoch to read properties at; the latest state if unset.
+    viewing_epoch: Option<EpochId>,
+    /// Transaction reading the properties.
+    tx_id: Option<TxId>,
 }
 
 impl ProjectOperator {
@@ -60,6 +64,8 @@ impl ProjectOperator {
             projections,
             output_types,
             store: None,
+            viewing_epoch: None,
+            tx_id: None,
         }
     }
 
@@ -76,9 +82,19 @@ impl ProjectOperator {
             projections,
             output_types,
             store: Some(store),
+            viewing_epoch: None,
+            tx_id: None,
         }
     }
 
+    /// Reads properties as a transaction sees them at `epoch`.
+    #[must_use]
+    pub fn with_tx_context(mut self, epoch: EpochId, tx_id: Option<TxId>) -> Self {
+        self.viewing_epoch = Some(epoch);
+        self.tx_id = tx_id;
+        self
+    }
+
     /// Creates a project operator that selects specific columns.
     pub fn select_columns(
         child: Box<dyn Operator>,
@@ -98,8 +114,23 @@ impl Operator for ProjectOperator {
             None => return Ok(None),
         };
 
-        // Create output chunk
-        let mut output = DataChunk::with_capacity(&self.output_types, input.row_count());
+        // Create output chunk. Node and edge columns copied as `Any` or
+        // `Node` keep their own type, so results can still tell IDs from
+        // plain integers and edges from nodes.
+        let output_types: Vec<LogicalType> = self
+            .projections
+            .iter()
+            .zip(&self.output_types)
+            .map(|(proj, output_type)| match (proj, output_type) {
+                (ProjectExpr::Column(col_idx), LogicalType::Any | LogicalType::Node) => input
+                    .column(*col_idx)
+                    .map(|col| col.data_type())
+                    .filter(|t| matches!(t, LogicalType::Node | LogicalType::Edge))
+                    .map_or_else(|| output_type.clone(), Clone::clone),
+                _ => output_type.clone(),
+            })
+            .collect();
+        let mut output = DataChunk::with_capacity(&output_types, input.row_count());
 
         // Evaluate each projection
         for (i, proj) in self.projections.iter().enumerate() {
@@ -138,19 +169,25 @@ impl Operator for ProjectOperator {
                         OperatorError::Execution("Store required for property access".to_string())
                     })?;
 
+                    let tx = self.tx_id.unwrap_or(TxId::SYSTEM);
+
                     // Extract property for each row
                     for row in input.selected_indices() {
                         // Try to get node ID first, then edge ID
                         let value = if let Some(node_id) = input_col.get_node_id(row) {
-                            store
-                                .get_node(node_id)
-                                .and_then(|node| node.get_property(property).cloned())
-                                .unwrap_or(Value::Null)
+                            match self.viewing_epoch {
+                                Some(epoch) => store.get_node_versioned(node_id, epoch, tx),
+                                None => store.get_node(node_id),
+                            }
+                            .and_then(|node| node.get_property(property).cloned())
+                            .unwrap_or(Value::Null)
                         } else if let Some(edge_id) = input_col.get_edge_id(row) {
-                            store
-                                .get_edge(edge_id)
-                                .and_then(|edge| edge.get_property(property).cloned())
-                                .unwrap_or(Value::Null)
+                            match self.viewing_epoch {
+                                Some(epoch) => store.get_edge_versioned(edge_id, epoch, tx),
+                                None => store.get_edge(edge_id),
+                            }
+                            .and_then(|edge| edge.get_property(property).cloned())
+                            .unwrap_or(Value::Null)
                         } else {
                             Value::Null
                         };
@@ -194,16 +231,22 @@ impl Operator for ProjectOperator {
                     })?;
 
                     // Use the ExpressionPredicate for expression evaluation
-                    let evaluator = ExpressionPredicate::new(
+                    let mut evaluator = ExpressionPredicate::new(
                         expr.clone(),
                         variable_columns.clone(),
                         Arc::clone(store),
                     );
+                    if let Some(epoch) = self.viewing_epoch {
+                        evaluator = evaluator.with_tx_context(epoch, self.tx_id);
+                    }
 
                     for row in input.selected_indices() {
                         let value = evaluator.eval_at(&input, row).unwrap_or(Value::Null);
                         output_col.push_value(value);
                     }
+                    if let Some(err) = evaluator.take_error() {
+                        return Err(err);
+                    }
                 }
             }
         }
@@ -323,4 +366,61 @@ mod tests {
         assert_eq!(result.column(1).unwrap().get_string(0), Some("constant"));
         assert_eq!(result.column(1).unwrap().get_string(1), Some("constant"));
     }
+
+    #[test]
+    fn test_project_keeps_node_column_type() {
+        let mut builder = DataChunkBuilder::new(&[LogicalType::Node, LogicalType::Int64]);
+        builder
+            .column_mut(0)
+            .unwrap()
+            .push_node_id(grafeo_common::types::NodeId::new(7));
+        builder.column_mut(1).unwrap().push_int64(7);
+        builder.advance_row();
+
+        let mock_scan = MockScanOperator {
+            chunks: vec![builder.finish()],
+            position: 0,
+        };
+
+        // The planner projects variables as `Any`
+        let mut project = ProjectOperator::select_columns(
+            Box::new(mock_scan),
+            vec![0, 1],
+            vec![LogicalType::Any, LogicalType::Any],
+        );
+
+        let result = project.next().unwrap().unwrap();
+        assert_eq!(result.column(0).unwrap().data_type(), &LogicalType::Node);
+        assert_eq!(result.column(1).unwrap().data_type(), &LogicalType::Any);
+        assert_eq!(
+            result.column(0).unwrap().get_value(0),
+            Some(Value::Int64(7))
+        );
+    }
+
+    #[test]
+    fn test_project_keeps_edge_column_declared_as_node() {
+        let mut builder = DataChunkBuilder::new(&[LogicalType::Edge]);
+        builder
+            .column_mut(0)
+            .unwrap()
+            .push_edge_id(grafeo_common::types::EdgeId::new(4));
+        builder.advance_row();
+
+        let mock_scan = MockScanOperator {
+            chunks: vec![builder.finish()],
+            position: 0,
+        };
+
+        // Pass-through columns ahead of a sort are declared `Node`
+        let mut project =
+            ProjectOperator::select_columns(Box::new(mock_scan), vec![0], vec![LogicalType::Node]);
+
+        let result = project.next().unwrap().unwrap();
+        assert_eq!(result.column(0).unwrap().data_type(), &LogicalType::Edge);
+        assert_eq!(
+            result.column(0).unwrap().get_edge_id(0),
+            Some(grafeo_common::types::EdgeId::new(4))
+        );
+    }
 }
@@ -2,6 +2,7 @@
 
 use crate::execution::chunk::DataChunk;
 use crate::execution::operators::OperatorError;
+use crate::execution::operators::aggregate::{percentile_cont, percentile_disc};
 use crate::execution::pipeline::{ChunkSizeHint, PushOperator, Sink};
 use crate::execution::spill::{PartitionedState, SpillManager};
 use crate::execution::vector::ValueVector;
@@ -25,6 +26,21 @@ pub enum AggregateFunction {
     Avg,
     /// First value in group.
     First,
+    /// Sample standard deviation; null for fewer than two values.
+    StdDev,
+    /// Population standard deviation; 0 for a single value.
+    StdDevPop,
+    /// Discrete percentile, taken from the buffered group values.
+    PercentileDisc,
+    /// Continuous (interpolated) percentile, from the buffered group values.
+    PercentileCont,
+}
+
+impl AggregateFunction {
+    /// Whether the function needs every value of the group, not a summary.
+    fn buffers_values(self) -> bool {
+        matches!(self, Self::PercentileDisc | Self::PercentileCont)
+    }
 }
 
 /// Aggregate expression.
@@ -36,6 +52,8 @@ pub struct AggregateExpr {
     pub column: Option<usize>,
     /// Whether DISTINCT applies.
     pub distinct: bool,
+    /// Percentile for `PercentileDisc`/`PercentileCont` (0.0 to 1.0).
+    pub percentile: Option<f64>,
 }
 
 impl AggregateExpr {
@@ -45,6 +63,7 @@ impl AggregateExpr {
             function: AggregateFunction::Count,
             column: None,
             distinct: false,
+            percentile: None,
         }
     }
 
@@ -54,6 +73,7 @@ impl AggregateExpr {
             function: AggregateFunction::Count,
             column: Some(column),
             distinct: false,
+            percentile: None,
         }
     }
 
@@ -63,6 +83,7 @@ impl AggregateExpr {
             function: AggregateFunction::Sum,
             column: Some(column),
             distinct: false,
+            percentile: None,
         }
     }
 
@@ -72,6 +93,7 @@ impl AggregateExpr {
             function: AggregateFunction::Min,
             column: Some(column),
             distinct: false,
+            percentile: None,
         }
     }
 
@@ -81,6 +103,7 @@ impl AggregateExpr {
             function: AggregateFunction::Max,
             column: Some(column),
             distinct: false,
+            percentile: None,
         }
     }
 
@@ -90,6 +113,57 @@ impl AggregateExpr {
             function: AggregateFunction::Avg,
             column: Some(column),
             distinct: false,
+            percentile: None,
+        }
+    }
+
+    /// Create a STDEV(column) expression.
+    pub fn stdev(column: usize) -> Self {
+        Self {
+            function: AggregateFunction::StdDev,
+            column: Some(column),
+            distinct: false,
+            percentile: None,
+        }
+    }
+
+    /// Create a STDEVP(column) expression.
+    pub fn stdev_pop(column: usize) -> Self {
+        Self {
+            function: AggregateFunction::StdDevPop,
+            column: Some(column),
+            distinct: false,
+            percentile: None,
+        }
+    }
+
+    /// Create a PERCENTILE_DISC(column, percentile) expression.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `percentile` is not between 0.0 and 1.0.
+    pub fn percentile_disc(column: usize, percentile: f64) -> Self {
+        assert!((0.0..=1.0).contains(&percentile), "percentile out of range");
+        Self {
+            function: AggregateFunction::PercentileDisc,
+            column: Some(column),
+            distinct: false,
+            percentile: Some(percentile),
+        }
+    }
+
+    /// Create a PERCENTILE_CONT(column, percentile) expression.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `percentile` is not between 0.0 and 1.0.
+    pub fn percentile_cont(column: usize, percentile: f64) -> Self {
+        assert!((0.0..=1.0).contains(&percentile), "percentile out of range");
+        Self {
+            function: AggregateFunction::PercentileCont,
+            column: Some(column),
+            distinct: false,
+            percentile: Some(percentile),
         }
     }
 }
@@ -102,16 +176,19 @@ struct Accumulator {
     min: Option<Value>,
     max: Option<Value>,
     first: Option<Value>,
+    /// Running mean and sum of squared deviations (Welford's algorithm).
+    numeric_count: i64,
+    mean: f64,
+    m2: f64,
+    /// Every numeric value, kept only for percentiles. Spills with the group.
+    values: Option<Vec<f64>>,
 }
 
 impl Accumulator {
-    fn new() -> Self {
+    fn new(expr: &AggregateExpr) -> Self {
         Self {
-            count: 0,
-            sum: 0.0,
-            min: None,
-            max: None,
-            first: None,
+            values: expr.function.buffers_values().then(Vec::new),
+            ..Self::default()
         }
     }
 
@@ -123,9 +200,16 @@ impl Accumulator {
 
         self.count += 1;
 
-        // Sum (for numeric types)
+        // Sum and spread (for numeric types)
         if let Some(n) = value_to_f64(value) {
             self.sum += n;
+            self.numeric_count += 1;
+            let delta = n - self.mean;
+            self.mean += delta / self.numeric_count as f64;
+            self.m2 += delta * (n - self.mean);
+            if let Some(values) = &mut self.values {
+                values.push(n);
+            }
         }
 
         // Min
@@ -144,8 +228,8 @@ impl Accumulator {
         }
     }
 
-    fn finalize(&self, func: AggregateFunction) -> Value {
-        match func {
+    fn finalize(&self, expr: &AggregateExpr) -> Value {
+        match expr.function {
             AggregateFunction::Count => Value::Int64(self.count),
             AggregateFunction::Sum => {
                 if self.count == 0 {
@@ -164,6 +248,28 @@ impl Accumulator {
                 }
             }
             AggregateFunction::First => self.first.clone().unwrap_or(Value::Null),
+            AggregateFunction::StdDev => {
+                if self.numeric_count < 2 {
+                    Value::Null
+                } else {
+                    Value::Float64((self.m2 / (self.numeric_count - 1) as f64).sqrt())
+                }
+            }
+            AggregateFunction::StdDevPop => {
+                if self.numeric_count == 0 {
+                    Value::Null
+                } else {
+                    Value::Float64((self.m2 / self.numeric_count as f64).sqrt())
+                }
+            }
+            AggregateFunction::PercentileDisc => percentile_disc(
+                self.values.as_deref().unwrap_or_default(),
+                expr.percentile.unwrap_or(0.5),
+            ),
+            AggregateFunction::PercentileCont => percentile_cont(
+                self.values.as_deref().unwrap_or_default(),
+                expr.percentile.unwrap_or(0.5),
+            ),
         }
     }
 }
@@ -304,7 +410,7 @@ impl AggregatePushOperator {
     /// Create a new aggregate operator.
     pub fn new(group_by: Vec<usize>, aggregates: Vec<AggregateExpr>) -> Self {
         let global_state = if group_by.is_empty() {
-            Some(aggregates.iter().map(|_| Accumulator::new()).collect())
+            Some(aggregates.iter().map(Accumulator::new).collect())
         } else {
             None
         };
@@ -364,7 +470,7 @@ impl PushOperator for AggregatePushOperator {
 
                     GroupState {
                         key_values,
-                        accumulators: self.aggregates.iter().map(|_| Accumulator::new()).collect(),
+                        accumulators: self.aggregates.iter().map(Accumulator::new).collect(),
                     }
                 });
 
@@ -395,7 +501,7 @@ impl PushOperator for AggregatePushOperator {
             // Global aggregation - single row output
             if let Some(ref accumulators) = self.global_state {
                 for (i, (acc, expr)) in accumulators.iter().zip(&self.aggregates).enumerate() {
-                    columns[i].push(acc.finalize(expr.function));
+                    columns[i].push(acc.finalize(expr));
                 }
             }
         } else {
@@ -409,7 +515,7 @@ impl PushOperator for AggregatePushOperator {
                 // Output aggregate results
                 for (i, (acc, expr)) in state.accumulators.iter().zip(&self.aggregates).enumerate()
                 {
-                    columns[self.group_by.len() + i].push(acc.finalize(expr.function));
+                    columns[self.group_by.len() + i].push(acc.finalize(expr));
                 }
             }
         }
@@ -470,6 +576,20 @@ fn serialize_group_state(state: &GroupState, w: &mut dyn Write) -> std::io::Resu
         if let Some(ref v) = acc.first {
             serialize_value(v, w)?;
         }
+
+        // Spread
+        w.write_all(&acc.numeric_count.to_le_bytes())?;
+        w.write_all(&acc.mean.to_bits().to_le_bytes())?;
+        w.write_all(&acc.m2.to_bits().to_le_bytes())?;
+
+        // Buffered values
+        w.write_all(&[acc.values.is_some() as u8])?;
+        if let Some(ref values) = acc.values {
+            w.write_all(&(values.len() as u64).to_le_bytes())?;
+            for v in values {
+                w.write_all(&v.to_bits().to_le_bytes())?;
+            }
+        }
     }
 
     Ok(())
@@ -527,12 +647,39 @@ fn deserialize_group_state(r: &mut dyn Read) -> std::io::Result<GroupState> {
             None
         };
 
+        // Spread
+        r.read_exact(&mut count_buf)?;
+        let numeric_count = i64::from_le_bytes(count_buf);
+        r.read_exact(&mut count_buf)?;
+        let mean = f64::from_bits(u64::from_le_bytes(count_buf));
+        r.read_exact(&mut count_buf)?;
+        let m2 = f64::from_bits(u64::from_le_bytes(count_buf));
+
+        // Buffered values
+        r.read_exact(&mut flag_buf)?;
+        let values = if flag_buf[0] != 0 {
+            r.read_exact(&mut len_buf)?;
+            let len = u64::from_le_bytes(len_buf) as usize;
+            let mut values = Vec::with_capacity(len);
+            for _ in 0..len {
+                r.read_exact(&mut count_buf)?;
+                values.push(f64::from_bits(u64::from_le_bytes(count_buf)));
+            }
+            Some(values)
+        } else {
+            None
+        };
+
         accumulators.push(Accumulator {
             count,
             sum,
             min,
             max,
             first,
+            numeric_count,
+            mean,
+            m2,
+            values,
         });
     }
 
@@ -569,7 +716,7 @@ impl SpillableAggregatePushOperator {
     /// Create a new spillable aggregate operator.
     pub fn new(group_by: Vec<usize>, aggregates: Vec<AggregateExpr>) -> Self {
         let global_state = if group_by.is_empty() {
-            Some(aggregates.iter().map(|_| Accumulator::new()).collect())
+            Some(aggregates.iter().map(Accumulator::new).collect())
         } else {
             None
         };
@@ -594,7 +741,7 @@ impl SpillableAggregatePushOperator {
         threshold: usize,
     ) -> Self {
         let global_state = if group_by.is_empty() {
-            Some(aggregates.iter().map(|_| Accumulator::new()).collect())
+            Some(aggregates.iter().map(Accumulator::new).collect())
         } else {
             None
         };
@@ -710,7 +857,7 @@ impl PushOperator for SpillableAggregatePushOperator {
                     let state = partitioned
                         .get_or_insert_with(key_values.clone(), || GroupState {
                             key_values: key_values.clone(),
-                            accumulators: aggregates.iter().map(|_| Accumulator::new()).collect(),
+                            accumulators: aggregates.iter().map(Accumulator::new).collect(),
                         })
                         .map_err(|e| OperatorError::Execution(e.to_string()))?;
 
@@ -744,7 +891,7 @@ impl PushOperator for SpillableAggregatePushOperator {
 
                     GroupState {
                         key_values,
-                        accumulators: self.aggregates.iter().map(|_| Accumulator::new()).collect(),
+                        accumulators: self.aggregates.iter().map(Accumulator::new).collect(),
                     }
                 });
 
@@ -777,7 +924,7 @@ impl PushOperator for SpillableAggregatePushOperator {
             // Global aggregation - single row output
             if let Some(ref accumulators) = self.global_state {
                 for (i, (acc, expr)) in accumulators.iter().zip(&self.aggregates).enumerate() {
-                    columns[i].push(acc.finalize(expr.function));
+                    columns[i].push(acc.finalize(expr));
                 }
             }
         } else if self.using_partitioned {
@@ -797,7 +944,7 @@ impl PushOperator for SpillableAggregatePushOperator {
                     for (i, (acc, expr)) in
                         state.accumulators.iter().zip(&self.aggregates).enumerate()
                     {
-                        columns[self.group_by.len() + i].push(acc.finalize(expr.function));
+                        columns[self.group_by.len() + i].push(acc.finalize(expr));
                     }
                 }
             }
@@ -812,7 +959,7 @@ impl PushOperator for SpillableAggregatePushOperator {
                 // Output aggregate results
                 for (i, (acc, expr)) in state.accumulators.iter().zip(&self.aggregates).enumerate()
                 {
-                    columns[self.group_by.len() + i].push(acc.finalize(expr.function));
+                    columns[self.group_by.len() + i].push(acc.finalize(expr));
                 }
             }
         }
@@ -1036,4 +1183,106 @@ mod tests {
             }
         }
     }
+
+    /// stdev, stdevp and both percentiles of `[2, 4, 4, 4, 5, 5, 7, 9]`:
+    /// mean 5, squared deviations summing to 32.
+    fn spread_aggregates() -> Vec<AggregateExpr> {
+        vec![
+            AggregateExpr::stdev(1),
+            AggregateExpr::stdev_pop(1),
+            AggregateExpr::percentile_disc(1, 0.5),
+            AggregateExpr::percentile_cont(1, 0.5),
+            AggregateExpr::percentile_cont(1, 0.9),
+        ]
+    }
+
+    fn float_at(chunk: &DataChunk, col: usize, row: usize) -> Option<f64> {
+        match chunk.column(col).unwrap().get_value(row) {
+            Some(Value::Float64(f)) => Some(f),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_stdev_and_percentiles() {
+        let mut agg = AggregatePushOperator::new(vec![0], spread_aggregates());
+        let mut sink = CollectorSink::new();
+
+        agg.push(
+            create_two_column_chunk(&[1; 8], &[2, 4, 4, 4, 5, 5, 7, 9]),
+            &mut sink,
+        )
+        .unwrap();
+        agg.finalize(&mut sink).unwrap();
+
+        let chunks = sink.into_chunks();
+        let chunk = &chunks[0];
+        // sqrt(32 / 7) and sqrt(32 / 8)
+        assert!((float_at(chunk, 1, 0).unwrap() - (32.0f64 / 7.0).sqrt()).abs() < 1e-12);
+        assert_eq!(float_at(chunk, 2, 0), Some(2.0));
+        // Rank 3.5: the discrete percentile takes index 3, the continuous one
+        // interpolates between 4 and 5
+        assert_eq!(float_at(chunk, 3, 0), Some(4.0));
+        assert_eq!(float_at(chunk, 4, 0), Some(4.5));
+        // Rank 6.3: 7 + 0.3 * (9 - 7)
+        assert!((float_at(chunk, 5, 0).unwrap() - 7.6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_spread_aggregates_survive_spilling() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let manager = Arc::new(SpillManager::new(temp_dir.path()).unwrap());
+        let mut agg =
+            SpillableAggregatePushOperator::with_spilling(vec![0], spread_aggregates(), manager, 1);
+        let mut sink = CollectorSink::new();
+
+        // Interleave the groups so both get spilled and reloaded mid-stream
+        for (group, value) in [(1, 2), (2, 10), (1, 4), (1, 4), (2, 10), (1, 4)] {
+            agg.push(create_two_column_chunk(&[group], &[value]), &mut sink)
+                .unwrap();
+        }
+        agg.push(
+            create_two_column_chunk(&[1, 1, 1, 1], &[5, 5, 7, 9]),
+            &mut sink,
+        )
+        .unwrap();
+        agg.finalize(&mut sink).unwrap();
+
+        let chunks = sink.into_chunks();
+        let chunk = &chunks[0];
+        assert_eq!(chunk.len(), 2);
+        for row in 0..2 {
+            match chunk.column(0).unwrap().get_value(row) {
+                Some(Value::Int64(1)) => {
+                    assert_eq!(float_at(chunk, 2, row), Some(2.0));
+                    assert_eq!(float_at(chunk, 4, row), Some(4.5));
+                }
+                Some(Value::Int64(2)) => {
+                    // No spread in [10, 10]: sample stdev 0, population stdev 0
+                    assert_eq!(float_at(chunk, 1, row), Some(0.0));
+                    assert_eq!(float_at(chunk, 2, row), Some(0.0));
+                    assert_eq!(float_at(chunk, 3, row), Some(10.0));
+                }
+                other => panic!("unexpected group {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_stdev_single_value() {
+        let mut agg = AggregatePushOperator::global(spread_aggregates());
+        let mut sink = CollectorSink::new();
+
+        agg.push(create_two_column_chunk(&[1], &[42]), &mut sink)
+            .unwrap();
+        agg.finalize(&mut sink).unwrap();
+
+        let chunks = sink.into_chunks();
+        // Sample stdev needs two values; population stdev of one value is 0
+        assert_eq!(chunks[0].column(0).unwrap().get_value(0), Some(Value::Null));
+        assert_eq!(float_at(&chunks[0], 1, 0), Some(0.0));
+        assert_eq!(float_at(&chunks[0], 3, 0), Some(42.0));
+    }
 }
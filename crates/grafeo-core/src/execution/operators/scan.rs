@@ -3,9 +3,82 @@
 use super::{Operator, OperatorResult};
 use crate::execution::DataChunk;
 use crate::graph::lpg::LpgStore;
-use grafeo_common::types::{EpochId, LogicalType, NodeId, TxId};
+use crate::index::zone_map::{ZoneMapBuilder, ZoneMapIndex, ZonePredicate};
+use grafeo_common::types::{EpochId, LogicalType, NodeId, TxId, Value};
+use grafeo_common::utils::hash::FxHashMap;
 use std::sync::Arc;
 
+/// Builds a zone map of a node property over fixed node ID ranges.
+///
+/// Zone `k` covers node IDs `k * chunk_size .. (k + 1) * chunk_size`, matching
+/// what [`ScanOperator::with_zone_map`] expects. Nodes without the property
+/// count as nulls. The map is a snapshot: rebuild it after writes, since a
+/// stale map could rule out a zone that now holds a match.
+///
+/// # Panics
+///
+/// Panics if `chunk_size` is zero.
+pub fn build_node_zone_map(store: &LpgStore, property: &str, chunk_size: u64) -> ZoneMapIndex {
+    assert!(chunk_size > 0, "zone chunk size must be positive");
+
+    let mut builders: FxHashMap<u64, ZoneMapBuilder> = FxHashMap::default();
+    for id in store.node_ids() {
+        let value = store
+            .get_node(id)
+            .and_then(|node| node.get_property(property).cloned())
+            .unwrap_or(Value::Null);
+        builders
+            .entry(id.as_u64() / chunk_size)
+            .or_default()
+            .add(&value);
+    }
+
+    let mut index = ZoneMapIndex::new(property);
+    for (zone, builder) in builders {
+        index.insert(zone, builder.build());
+    }
+    index
+}
+
+/// Zone map and pushed-down predicate used to skip whole zones of a scan.
+struct ZoneFilter {
+    zone_map: Arc<ZoneMapIndex>,
+    predicate: ZonePredicate,
+    chunk_size: u64,
+}
+
+/// An index lookup that finds a scan's nodes in place of its label.
+///
+/// The lookup returns exactly the label's nodes the predicate holds for, as
+/// the scan's transaction sees them; the index never decides on its own.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexLookup {
+    /// Nodes whose value of `property` equals `value`, from the store's
+    /// hash index on it.
+    Equal {
+        /// The indexed property.
+        property: String,
+        /// The value to find.
+        value: Value,
+    },
+    /// Nodes whose leading `properties` equal `values`, from the store's
+    /// composite index on all of `properties`.
+    Composite {
+        /// The indexed properties, in index order.
+        properties: Vec<String>,
+        /// Values for the first `values.len()` properties.
+        values: Vec<Value>,
+    },
+    /// Nodes whose string value of `property` starts with `prefix`, from the
+    /// store's text index on it.
+    Prefix {
+        /// The indexed property.
+        property: String,
+        /// The prefix to find.
+        prefix: String,
+    },
+}
+
 /// A scan operator that reads nodes from storage.
 pub struct ScanOperator {
     /// The store to scan from.
@@ -24,6 +97,12 @@ pub struct ScanOperator {
     tx_id: Option<TxId>,
     /// Epoch for version visibility.
     viewing_epoch: Option<EpochId>,
+    /// Optional zone map used to skip zones the predicate can't match.
+    zone_filter: Option<ZoneFilter>,
+    /// Number of zones skipped by the zone filter.
+    skipped_chunks: usize,
+    /// Index lookup that replaces the label scan.
+    index: Option<IndexLookup>,
 }
 
 impl ScanOperator {
@@ -38,6 +117,9 @@ impl ScanOperator {
             chunk_capacity: 2048,
             tx_id: None,
             viewing_epoch: None,
+            zone_filter: None,
+            skipped_chunks: 0,
+            index: None,
         }
     }
 
@@ -52,6 +134,9 @@ impl ScanOperator {
             chunk_capacity: 2048,
             tx_id: None,
             viewing_epoch: None,
+            zone_filter: None,
+            skipped_chunks: 0,
+            index: None,
         }
     }
 
@@ -70,32 +155,121 @@ impl ScanOperator {
         self
     }
 
+    /// Skips zones of nodes whose property statistics rule out `predicate`.
+    ///
+    /// `zone_map` must cover node ID ranges of `chunk_size`, as built by
+    /// [`build_node_zone_map`]. Zones without an entry are always scanned, and
+    /// Bloom filter false positives only cost extra rows. The predicate is not
+    /// applied row by row, so keep the filter above the scan.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn with_zone_map(
+        mut self,
+        zone_map: Arc<ZoneMapIndex>,
+        predicate: ZonePredicate,
+        chunk_size: u64,
+    ) -> Self {
+        assert!(chunk_size > 0, "zone chunk size must be positive");
+        self.zone_filter = Some(ZoneFilter {
+            zone_map,
+            predicate,
+            chunk_size,
+        });
+        self
+    }
+
+    /// Finds the label's nodes through `lookup` instead of scanning them all.
+    ///
+    /// Only applies to a scan with a label. If the store has no index to
+    /// serve the lookup, the scan falls back to reading the label.
+    pub fn with_index(mut self, lookup: IndexLookup) -> Self {
+        self.index = Some(lookup);
+        self
+    }
+
+    /// Returns how many zones the zone map let this scan skip.
+    #[must_use]
+    pub fn skipped_chunks(&self) -> usize {
+        self.skipped_chunks
+    }
+
     fn load_batch(&mut self) {
         if !self.batch.is_empty() || self.exhausted {
             return;
         }
 
-        // Get nodes, using versioned method if tx context is set
-        let all_ids = match &self.label {
-            Some(label) => self.store.nodes_by_label(label),
-            None => self.store.node_ids(),
+        // Get nodes from the index if there is one, otherwise using
+        // versioned methods if tx context is set
+        self.batch = match (self.lookup_index(), self.viewing_epoch, &self.label) {
+            (Some(nodes), _, _) => nodes,
+            (None, Some(epoch), Some(label)) => {
+                let tx = self.tx_id.unwrap_or(TxId::SYSTEM);
+                self.store.nodes_by_label_versioned(label, epoch, tx)
+            }
+            (None, Some(epoch), None) => {
+                let tx = self.tx_id.unwrap_or(TxId::SYSTEM);
+                self.store
+                    .node_ids()
+                    .into_iter()
+                    .filter(|id| self.store.is_node_visible(*id, epoch, tx))
+                    .collect()
+            }
+            (None, None, Some(label)) => self.store.nodes_by_label(label),
+            (None, None, None) => self.store.node_ids(),
         };
 
-        // Filter by visibility if we have tx context
-        self.batch = if let Some(epoch) = self.viewing_epoch {
-            let tx = self.tx_id.unwrap_or(TxId::SYSTEM);
-            all_ids
-                .into_iter()
-                .filter(|id| self.store.get_node_versioned(*id, epoch, tx).is_some())
-                .collect()
-        } else {
-            all_ids
-        };
+        if let Some(filter) = &self.zone_filter {
+            let mut zones: FxHashMap<u64, bool> = FxHashMap::default();
+            self.batch.retain(|id| {
+                *zones
+                    .entry(id.as_u64() / filter.chunk_size)
+                    .or_insert_with_key(|&zone| {
+                        filter.zone_map.might_match(zone, &filter.predicate)
+                    })
+            });
+            self.skipped_chunks = zones.values().filter(|&&keep| !keep).count();
+        }
 
         if self.batch.is_empty() {
             self.exhausted = true;
         }
     }
+
+    /// Runs the index lookup, or returns `None` if there is none or the
+    /// store can't serve it.
+    fn lookup_index(&self) -> Option<Vec<NodeId>> {
+        let (Some(lookup), Some(label)) = (&self.index, &self.label) else {
+            return None;
+        };
+        match lookup {
+            IndexLookup::Equal { property, value } => self.store.find_nodes_by_property_index(
+                label,
+                property,
+                value,
+                self.viewing_epoch,
+                self.tx_id,
+            ),
+            IndexLookup::Composite { properties, values } => {
+                let properties: Vec<&str> = properties.iter().map(String::as_str).collect();
+                self.store.find_nodes_by_composite_index(
+                    label,
+                    &properties,
+                    values,
+                    self.viewing_epoch,
+                    self.tx_id,
+                )
+            }
+            IndexLookup::Prefix { property, prefix } => self.store.find_nodes_by_text_prefix(
+                label,
+                property,
+                prefix,
+                self.viewing_epoch,
+                self.tx_id,
+            ),
+        }
+    }
 }
 
 impl Operator for ScanOperator {
@@ -133,6 +307,7 @@ impl Operator for ScanOperator {
         self.position = 0;
         self.batch.clear();
         self.exhausted = false;
+        self.skipped_chunks = 0;
     }
 
     fn name(&self) -> &'static str {
@@ -212,10 +387,11 @@ mod tests {
         store.create_node_versioned(&["Person"], epoch1, tx1);
         store.create_node_versioned(&["Person"], epoch1, tx1);
 
-        // Create a node at epoch 5
+        // Create a node in a transaction committed at epoch 5
         let epoch5 = EpochId::new(5);
         let tx2 = TxId::new(2);
-        store.create_node_versioned(&["Person"], epoch5, tx2);
+        store.create_node_versioned(&["Person"], EpochId::new(2), tx2);
+        store.commit_versions(tx2, epoch5);
 
         // Scan at epoch 3 should see only the first 2 nodes (created at epoch 1)
         let mut scan = ScanOperator::with_label(Arc::clone(&store), "Person")
@@ -231,4 +407,132 @@ mod tests {
         let chunk_all = scan_all.next().unwrap().unwrap();
         assert_eq!(chunk_all.row_count(), 3, "Should see 3 nodes at epoch 5");
     }
+
+    fn create_aged_people(count: i64) -> Arc<LpgStore> {
+        let store = Arc::new(LpgStore::new());
+        for age in 0..count {
+            let id = store.create_node(&["Person"]);
+            store.set_node_property(id, "age", Value::Int64(age));
+        }
+        store
+    }
+
+    fn scan_all(scan: &mut ScanOperator) -> usize {
+        let mut rows = 0;
+        while let Some(chunk) = scan.next().unwrap() {
+            rows += chunk.row_count();
+        }
+        rows
+    }
+
+    #[test]
+    fn test_scan_skips_zones_outside_range() {
+        // Ages 0..40 over four zones of ten nodes each
+        let store = create_aged_people(40);
+        let zone_map = Arc::new(build_node_zone_map(&store, "age", 10));
+        assert_eq!(zone_map.len(), 4);
+
+        // age >= 25 rules out the zones holding 0..9 and 10..19
+        let predicate = ZonePredicate::Range {
+            lower: Some(Value::Int64(25)),
+            upper: None,
+            lower_inclusive: true,
+            upper_inclusive: false,
+        };
+        let mut scan = ScanOperator::with_label(Arc::clone(&store), "Person")
+            .with_chunk_capacity(10)
+            .with_zone_map(zone_map, predicate, 10);
+
+        assert_eq!(scan_all(&mut scan), 20);
+        assert_eq!(scan.skipped_chunks(), 2);
+    }
+
+    #[test]
+    fn test_scan_zone_equality_never_drops_matches() {
+        let store = create_aged_people(40);
+        let zone_map = Arc::new(build_node_zone_map(&store, "age", 10));
+
+        for age in 0..40 {
+            let mut scan = ScanOperator::with_label(Arc::clone(&store), "Person").with_zone_map(
+                Arc::clone(&zone_map),
+                ZonePredicate::Equal(Value::Int64(age)),
+                10,
+            );
+            let chunk = scan.next().unwrap().unwrap();
+            let col = chunk.column(0).unwrap();
+            let found = (0..chunk.row_count()).any(|i| {
+                let id = col.get_node_id(i).unwrap();
+                store.get_node(id).unwrap().get_property("age") == Some(&Value::Int64(age))
+            });
+            assert!(found, "zone filter dropped the node with age {age}");
+            assert!(scan.skipped_chunks() <= 3);
+        }
+
+        // A value outside every zone's range skips everything
+        let mut scan = ScanOperator::with_label(Arc::clone(&store), "Person").with_zone_map(
+            zone_map,
+            ZonePredicate::Equal(Value::Int64(100)),
+            10,
+        );
+        assert!(scan.next().unwrap().is_none());
+        assert_eq!(scan.skipped_chunks(), 4);
+    }
+
+    #[test]
+    fn test_scan_zone_without_entry_is_scanned() {
+        let store = create_aged_people(20);
+        let zone_map = Arc::new(build_node_zone_map(&store, "age", 10));
+
+        // Nodes created after the map was built land in a zone without an entry
+        for age in 100..105 {
+            let id = store.create_node(&["Person"]);
+            store.set_node_property(id, "age", Value::Int64(age));
+        }
+
+        let predicate = ZonePredicate::Equal(Value::Int64(102));
+        let mut scan = ScanOperator::with_label(Arc::clone(&store), "Person")
+            .with_zone_map(zone_map, predicate, 10);
+
+        assert_eq!(scan_all(&mut scan), 5);
+        assert_eq!(scan.skipped_chunks(), 2);
+
+        scan.reset();
+        assert_eq!(scan.skipped_chunks(), 0);
+    }
+
+    #[test]
+    fn test_scan_with_index_lookup() {
+        let store = create_aged_people(20);
+        for id in store.nodes_by_label("Person") {
+            let age = store
+                .get_node(id)
+                .and_then(|n| n.get_property("age").cloned());
+            if let Some(Value::Int64(age)) = age {
+                store.set_node_property(id, "band", Value::Int64(age / 10));
+            }
+        }
+        let lookup = IndexLookup::Composite {
+            properties: vec!["band".to_string(), "age".to_string()],
+            values: vec![Value::Int64(1)],
+        };
+
+        // Without the index the lookup falls back to the label
+        let mut scan =
+            ScanOperator::with_label(Arc::clone(&store), "Person").with_index(lookup.clone());
+        assert_eq!(scan_all(&mut scan), 20);
+
+        store.create_composite_index("Person", &["band", "age"]);
+        let mut scan = ScanOperator::with_label(Arc::clone(&store), "Person").with_index(lookup);
+        assert_eq!(scan_all(&mut scan), 10);
+
+        store.create_property_index("Person", "age");
+        let lookup = IndexLookup::Equal {
+            property: "age".to_string(),
+            value: Value::Int64(7),
+        };
+        let mut scan = ScanOperator::with_label(Arc::clone(&store), "Person")
+            .with_index(lookup)
+            .with_tx_context(store.current_epoch(), None);
+        assert_eq!(scan_all(&mut scan), 1);
+    }
 }
@@ -2,14 +2,15 @@
 //!
 //! This module provides:
 //! - `SortOperator`: Orders results by one or more columns
+//! - `TopNOperator`: Keeps only the first N rows of that order
 
 use std::cmp::Ordering;
 
 use grafeo_common::types::{LogicalType, Value};
 
 use super::{Operator, OperatorError, OperatorResult};
-use crate::execution::DataChunk;
 use crate::execution::chunk::DataChunkBuilder;
+use crate::execution::{DataChunk, ExecutionMemoryContext};
 
 /// Sort direction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -78,6 +79,10 @@ struct SortRow {
 /// Sort operator.
 ///
 /// Materializes all input and sorts by the specified keys.
+///
+/// Sorting cannot spill, so with a memory context attached the operator
+/// reserves room for every chunk it holds and fails with
+/// [`OperatorError::OutOfMemory`] once a reservation is refused.
 pub struct SortOperator {
     /// Child operator.
     child: Box<dyn Operator>,
@@ -93,6 +98,8 @@ pub struct SortOperator {
     sort_complete: bool,
     /// Current position in output.
     output_position: usize,
+    /// Grants covering the materialized chunks, if memory is budgeted.
+    memory: Option<ExecutionMemoryContext>,
 }
 
 impl SortOperator {
@@ -110,13 +117,39 @@ impl SortOperator {
             sorted_rows: Vec::new(),
             sort_complete: false,
             output_position: 0,
+            memory: None,
+        }
+    }
+
+    /// Reserves memory from `memory` for each materialized chunk.
+    #[must_use]
+    pub fn with_memory_context(mut self, memory: ExecutionMemoryContext) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Reserves room for `chunk`, dropping everything held so far if the
+    /// buffer manager refuses.
+    fn reserve(&mut self, chunk: &DataChunk) -> Result<(), OperatorError> {
+        let Some(memory) = &mut self.memory else {
+            return Ok(());
+        };
+        let requested = chunk.column_count() * chunk.row_count() * std::mem::size_of::<Value>();
+        if memory.allocate_tracked(requested) {
+            return Ok(());
         }
+        memory.release_all();
+        self.chunks.clear();
+        self.sorted_rows.clear();
+        Err(OperatorError::OutOfMemory { requested })
     }
 
     /// Materializes and sorts the input.
     fn sort(&mut self) -> Result<(), OperatorError> {
         // Materialize all input
         while let Some(chunk) = self.child.next()? {
+            self.reserve(&chunk)?;
+            keep_id_types(&mut self.output_schema, &chunk);
             let chunk_idx = self.chunks.len();
             for row_idx in chunk.selected_indices() {
                 self.sorted_rows.push(SortRow {
@@ -189,13 +222,28 @@ fn compare_values(a: &Value, b: &Value) -> Ordering {
         (Value::Int64(a), Value::Int64(b)) => a.cmp(b),
         (Value::Float64(a), Value::Float64(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
         (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Bytes(a), Value::Bytes(b)) => a.cmp(b),
         (Value::Int64(a), Value::Float64(b)) => {
             (*a as f64).partial_cmp(b).unwrap_or(Ordering::Equal)
         }
         (Value::Float64(a), Value::Int64(b)) => {
             a.partial_cmp(&(*b as f64)).unwrap_or(Ordering::Equal)
         }
-        _ => Ordering::Equal,
+        _ => a.temporal_cmp(b).unwrap_or(Ordering::Equal),
+    }
+}
+
+/// Narrows `Any` columns of `schema` to node or edge IDs where `chunk` holds
+/// them, so sorted results can still tell IDs from plain integers.
+fn keep_id_types(schema: &mut [LogicalType], chunk: &DataChunk) {
+    for (col_idx, column_type) in schema.iter_mut().enumerate() {
+        if *column_type == LogicalType::Any {
+            if let Some(col) = chunk.column(col_idx) {
+                if matches!(col.data_type(), LogicalType::Node | LogicalType::Edge) {
+                    *column_type = col.data_type().clone();
+                }
+            }
+        }
     }
 }
 
@@ -245,6 +293,9 @@ impl Operator for SortOperator {
         self.sorted_rows.clear();
         self.sort_complete = false;
         self.output_position = 0;
+        if let Some(memory) = &mut self.memory {
+            memory.release_all();
+        }
     }
 
     fn name(&self) -> &'static str {
@@ -252,6 +303,212 @@ impl Operator for SortOperator {
     }
 }
 
+/// A row kept by [`TopNOperator`], with its sort key values pulled out.
+struct TopNRow {
+    /// Values of the sort key columns, in key order.
+    keys: Vec<Option<Value>>,
+    /// Values of all columns.
+    values: Vec<Value>,
+    /// Arrival order, so ties keep the earlier row like a stable sort.
+    sequence: u64,
+}
+
+/// Top-N operator, a sort fused with the limit above it.
+///
+/// Keeps the best `limit` rows seen so far in a bounded max-heap whose root
+/// is the worst kept row, taking O(n log N) time and O(N) memory instead of
+/// materializing and sorting the whole input. The output is exactly what
+/// [`SortOperator`] followed by a limit produces, ties included.
+pub struct TopNOperator {
+    /// Child operator.
+    child: Box<dyn Operator>,
+    /// Sort keys.
+    sort_keys: Vec<SortKey>,
+    /// Maximum number of rows to return.
+    limit: usize,
+    /// Output schema.
+    output_schema: Vec<LogicalType>,
+    /// Kept rows: a heap while consuming input, then sorted.
+    rows: Vec<TopNRow>,
+    /// Sequence number of the next input row.
+    next_sequence: u64,
+    /// Whether sorting is complete.
+    sort_complete: bool,
+    /// Current position in output.
+    output_position: usize,
+}
+
+impl TopNOperator {
+    /// Creates a new top-N operator.
+    pub fn new(
+        child: Box<dyn Operator>,
+        sort_keys: Vec<SortKey>,
+        limit: usize,
+        output_schema: Vec<LogicalType>,
+    ) -> Self {
+        Self {
+            child,
+            sort_keys,
+            limit,
+            output_schema,
+            rows: Vec::new(),
+            next_sequence: 0,
+            sort_complete: false,
+            output_position: 0,
+        }
+    }
+
+    /// Consumes the input, keeping the best rows, and sorts them.
+    fn sort(&mut self) -> Result<(), OperatorError> {
+        if self.limit == 0 {
+            self.sort_complete = true;
+            return Ok(());
+        }
+
+        while let Some(chunk) = self.child.next()? {
+            keep_id_types(&mut self.output_schema, &chunk);
+            for row_idx in chunk.selected_indices() {
+                let keys: Vec<Option<Value>> = self
+                    .sort_keys
+                    .iter()
+                    .map(|key| chunk.column(key.column).and_then(|c| c.get_value(row_idx)))
+                    .collect();
+                let sequence = self.next_sequence;
+                self.next_sequence += 1;
+
+                // A tie with the worst kept row loses, since that row came first
+                let full = self.rows.len() == self.limit;
+                if full
+                    && compare_key_values(&self.sort_keys, &keys, &self.rows[0].keys)
+                        != Ordering::Less
+                {
+                    continue;
+                }
+
+                let values = (0..chunk.column_count())
+                    .map(|col_idx| {
+                        chunk
+                            .column(col_idx)
+                            .and_then(|c| c.get_value(row_idx))
+                            .unwrap_or(Value::Null)
+                    })
+                    .collect();
+                let row = TopNRow {
+                    keys,
+                    values,
+                    sequence,
+                };
+                if full {
+                    self.rows[0] = row;
+                    sift_down(&mut self.rows, &self.sort_keys);
+                } else {
+                    self.rows.push(row);
+                    sift_up(&mut self.rows, &self.sort_keys);
+                }
+            }
+        }
+
+        let sort_keys = &self.sort_keys;
+        self.rows
+            .sort_by(|a, b| compare_top_n_rows(sort_keys, a, b));
+        self.sort_complete = true;
+        Ok(())
+    }
+}
+
+/// Compares extracted sort key values, honoring direction and null order.
+fn compare_key_values(sort_keys: &[SortKey], a: &[Option<Value>], b: &[Option<Value>]) -> Ordering {
+    for ((key, val_a), val_b) in sort_keys.iter().zip(a).zip(b) {
+        let cmp = compare_values_with_nulls(val_a, val_b, key.null_order);
+        let cmp = match key.direction {
+            SortDirection::Ascending => cmp,
+            SortDirection::Descending => cmp.reverse(),
+        };
+        if cmp != Ordering::Equal {
+            return cmp;
+        }
+    }
+    Ordering::Equal
+}
+
+/// Orders top-N rows by their keys, then by arrival.
+fn compare_top_n_rows(sort_keys: &[SortKey], a: &TopNRow, b: &TopNRow) -> Ordering {
+    compare_key_values(sort_keys, &a.keys, &b.keys).then(a.sequence.cmp(&b.sequence))
+}
+
+/// Restores the max-heap after pushing a row onto the end.
+fn sift_up(heap: &mut [TopNRow], sort_keys: &[SortKey]) {
+    let mut index = heap.len() - 1;
+    while index > 0 {
+        let parent = (index - 1) / 2;
+        if compare_top_n_rows(sort_keys, &heap[index], &heap[parent]) != Ordering::Greater {
+            break;
+        }
+        heap.swap(index, parent);
+        index = parent;
+    }
+}
+
+/// Restores the max-heap after replacing its root.
+fn sift_down(heap: &mut [TopNRow], sort_keys: &[SortKey]) {
+    let mut index = 0;
+    loop {
+        let mut largest = index;
+        for child in [2 * index + 1, 2 * index + 2] {
+            if child < heap.len()
+                && compare_top_n_rows(sort_keys, &heap[child], &heap[largest]) == Ordering::Greater
+            {
+                largest = child;
+            }
+        }
+        if largest == index {
+            return;
+        }
+        heap.swap(index, largest);
+        index = largest;
+    }
+}
+
+impl Operator for TopNOperator {
+    fn next(&mut self) -> OperatorResult {
+        if !self.sort_complete {
+            self.sort()?;
+        }
+
+        if self.output_position >= self.rows.len() {
+            return Ok(None);
+        }
+
+        let mut builder = DataChunkBuilder::with_capacity(&self.output_schema, 2048);
+
+        while self.output_position < self.rows.len() && !builder.is_full() {
+            let row = &self.rows[self.output_position];
+            for (col_idx, value) in row.values.iter().enumerate() {
+                if let Some(dst_col) = builder.column_mut(col_idx) {
+                    dst_col.push_value(value.clone());
+                }
+            }
+
+            builder.advance_row();
+            self.output_position += 1;
+        }
+
+        Ok(Some(builder.finish()))
+    }
+
+    fn reset(&mut self) {
+        self.child.reset();
+        self.rows.clear();
+        self.next_sequence = 0;
+        self.sort_complete = false;
+        self.output_position = 0;
+    }
+
+    fn name(&self) -> &'static str {
+        "TopN"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -340,6 +597,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sort_out_of_memory_releases_grants() {
+        use grafeo_common::memory::buffer::BufferManager;
+        use std::sync::Arc;
+
+        // Room for the first chunk but not the second
+        let chunk_bytes = 2 * 4 * std::mem::size_of::<Value>();
+        let manager = BufferManager::with_budget(chunk_bytes * 3 / 2);
+        let mock = MockOperator::new(vec![create_unsorted_chunk(), create_unsorted_chunk()]);
+
+        let mut sort = SortOperator::new(
+            Box::new(mock),
+            vec![SortKey::ascending(0)],
+            vec![LogicalType::Int64, LogicalType::String],
+        )
+        .with_memory_context(ExecutionMemoryContext::new(Arc::clone(&manager)));
+
+        assert!(matches!(
+            sort.next(),
+            Err(OperatorError::OutOfMemory { requested }) if requested == chunk_bytes
+        ));
+        assert_eq!(manager.allocated(), 0);
+    }
+
     #[test]
     fn test_sort_descending() {
         let mock = MockOperator::new(vec![create_unsorted_chunk()]);
@@ -394,4 +675,142 @@ mod tests {
             ]
         );
     }
+
+    /// Ten rows over two chunks: (score, name, id), with tied and null scores.
+    fn create_tied_chunks() -> Vec<DataChunk> {
+        let data = [
+            (Some(3i64), "c"),
+            (None, "a"),
+            (Some(1), "b"),
+            (Some(3), "a"),
+            (Some(2), "d"),
+            (Some(1), "b"),
+            (None, "e"),
+            (Some(3), "b"),
+            (Some(2), "a"),
+            (Some(1), "c"),
+        ];
+        let schema = [LogicalType::Int64, LogicalType::String, LogicalType::Int64];
+        data.chunks(4)
+            .enumerate()
+            .map(|(chunk_idx, rows)| {
+                let mut builder = DataChunkBuilder::new(&schema);
+                for (row_idx, (score, name)) in rows.iter().enumerate() {
+                    let score = score.map_or(Value::Null, Value::Int64);
+                    builder.column_mut(0).unwrap().push_value(score);
+                    builder.column_mut(1).unwrap().push_string(*name);
+                    builder
+                        .column_mut(2)
+                        .unwrap()
+                        .push_int64((chunk_idx * 4 + row_idx) as i64);
+                    builder.advance_row();
+                }
+                builder.finish()
+            })
+            .collect()
+    }
+
+    fn collect_rows(op: &mut dyn Operator) -> Vec<Vec<Value>> {
+        let mut rows = Vec::new();
+        while let Some(chunk) = op.next().unwrap() {
+            for row in chunk.selected_indices() {
+                rows.push(
+                    (0..chunk.column_count())
+                        .map(|col| chunk.column(col).unwrap().get_value(row).unwrap())
+                        .collect(),
+                );
+            }
+        }
+        rows
+    }
+
+    #[test]
+    fn test_top_n_matches_sort_then_limit() {
+        use crate::execution::operators::LimitOperator;
+
+        let schema = vec![LogicalType::Int64, LogicalType::String, LogicalType::Int64];
+        let key_sets = [
+            vec![SortKey::ascending(0)],
+            vec![SortKey::descending(0)],
+            vec![SortKey::descending(0).with_null_order(NullOrder::NullsFirst)],
+            vec![SortKey::ascending(1), SortKey::descending(0)],
+            vec![
+                SortKey::ascending(0).with_null_order(NullOrder::NullsFirst),
+                SortKey::descending(1),
+            ],
+        ];
+
+        for keys in &key_sets {
+            for n in [0, 1, 2, 3, 4, 7, 9, 10, 11, 100] {
+                let sort = SortOperator::new(
+                    Box::new(MockOperator::new(create_tied_chunks())),
+                    keys.clone(),
+                    schema.clone(),
+                );
+                let mut expected = LimitOperator::new(Box::new(sort), n, schema.clone());
+                let mut top_n = TopNOperator::new(
+                    Box::new(MockOperator::new(create_tied_chunks())),
+                    keys.clone(),
+                    n,
+                    schema.clone(),
+                );
+
+                let rows = collect_rows(&mut top_n);
+                assert_eq!(rows.len(), n.min(10), "keys {keys:?}, n {n}");
+                assert_eq!(rows, collect_rows(&mut expected), "keys {keys:?}, n {n}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_top_n_zero_yields_nothing() {
+        let mut top_n = TopNOperator::new(
+            Box::new(MockOperator::new(create_tied_chunks())),
+            vec![SortKey::ascending(0)],
+            0,
+            vec![LogicalType::Int64, LogicalType::String, LogicalType::Int64],
+        );
+        assert!(top_n.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_compare_bytes_ordering() {
+        let bytes = |b: &[u8]| Value::Bytes(b.into());
+        let mut values = vec![bytes(&[1]), bytes(&[0, 0]), bytes(&[]), bytes(&[0])];
+        values.sort_by(compare_values);
+        assert_eq!(
+            values,
+            vec![bytes(&[]), bytes(&[0]), bytes(&[0, 0]), bytes(&[1])]
+        );
+    }
+
+    #[test]
+    fn test_sort_and_top_n_keep_id_column_types() {
+        let ids = || {
+            let mut builder = DataChunkBuilder::new(&[LogicalType::Node, LogicalType::Int64]);
+            for id in [3u64, 1, 2] {
+                builder
+                    .column_mut(0)
+                    .unwrap()
+                    .push_node_id(grafeo_common::types::NodeId::new(id));
+                builder.column_mut(1).unwrap().push_int64(id as i64);
+                builder.advance_row();
+            }
+            MockOperator::new(vec![builder.finish()])
+        };
+        // The planner types variables as `Any`
+        let schema = vec![LogicalType::Any, LogicalType::Any];
+
+        let mut sort =
+            SortOperator::new(Box::new(ids()), vec![SortKey::ascending(1)], schema.clone());
+        let chunk = sort.next().unwrap().unwrap();
+        assert_eq!(chunk.column(0).unwrap().data_type(), &LogicalType::Node);
+        assert_eq!(chunk.column(1).unwrap().data_type(), &LogicalType::Any);
+
+        let mut top_n = TopNOperator::new(Box::new(ids()), vec![SortKey::ascending(1)], 2, schema);
+        let chunk = top_n.next().unwrap().unwrap();
+        assert_eq!(chunk.row_count(), 2);
+        assert_eq!(chunk.column(0).unwrap().data_type(), &LogicalType::Node);
+        assert_eq!(chunk.column(1).unwrap().data_type(), &LogicalType::Any);
+    }
 }
@@ -1,6 +1,6 @@
 //! Unwind operator for expanding lists into individual rows.
 
-use super::{Operator, OperatorResult};
+use super::{Operator, OperatorError, OperatorResult};
 use crate::execution::chunk::{DataChunk, DataChunkBuilder};
 use grafeo_common::types::{LogicalType, Value};
 
@@ -85,11 +85,12 @@ impl UnwindOperator {
 
             let chunk = self.current_chunk.as_ref().unwrap();
 
-            // Find the next row with a list value
+            // Find the next row with a non-empty list. Null unwinds to no
+            // rows, like an empty list; anything else is a type error.
             while self.current_row < chunk.row_count() {
                 if let Some(col) = chunk.column(self.list_col_idx) {
-                    if let Some(value) = col.get_value(self.current_row) {
-                        if let Value::List(list_arc) = value {
+                    match col.get_value(self.current_row) {
+                        Some(Value::List(list_arc)) => {
                             // Found a list - store it and return first element
                             let list: Vec<Value> = list_arc.iter().cloned().collect();
                             if !list.is_empty() {
@@ -97,6 +98,13 @@ impl UnwindOperator {
                                 return Ok(Some(self.emit_row()?));
                             }
                         }
+                        Some(Value::Null) | None => {}
+                        Some(other) => {
+                            return Err(OperatorError::TypeMismatch {
+                                expected: "List".to_string(),
+                                found: format!("{other:?}"),
+                            });
+                        }
                     }
                 }
                 self.current_row += 1;
@@ -108,7 +116,7 @@ impl UnwindOperator {
     }
 
     /// Emits a single row with the current list element.
-    fn emit_row(&mut self) -> Result<DataChunk, super::OperatorError> {
+    fn emit_row(&mut self) -> Result<DataChunk, OperatorError> {
         let chunk = self.current_chunk.as_ref().unwrap();
         let list = self.current_list.as_ref().unwrap();
         let element = list[self.current_list_idx].clone();
@@ -117,17 +125,17 @@ impl UnwindOperator {
         let mut builder = DataChunkBuilder::new(&self.output_schema);
 
         // Copy existing columns (except the list column which we're replacing)
+        let mut out_idx = 0;
         for col_idx in 0..chunk.column_count() {
             if col_idx == self.list_col_idx {
                 continue; // Skip the list column
             }
             if let Some(col) = chunk.column(col_idx) {
-                if let Some(value) = col.get_value(self.current_row) {
-                    if let Some(out_col) = builder.column_mut(col_idx) {
-                        out_col.push_value(value);
-                    }
+                if let Some(out_col) = builder.column_mut(out_idx) {
+                    out_col.push_value(col.get_value(self.current_row).unwrap_or(Value::Null));
                 }
             }
+            out_idx += 1;
         }
 
         // Add the unwound element as the last column
@@ -231,4 +239,79 @@ mod tests {
 
         assert_eq!(results.len(), 3);
     }
+
+    fn single_value_chunk(value: Value) -> DataChunk {
+        let mut builder = DataChunkBuilder::new(&[LogicalType::Any]);
+        builder.column_mut(0).unwrap().push_value(value);
+        builder.advance_row();
+        builder.finish()
+    }
+
+    #[test]
+    fn test_unwind_empty_list() {
+        let mock = MockOperator {
+            chunks: vec![single_value_chunk(Value::List(Arc::new([])))],
+            position: 0,
+        };
+        let mut unwind =
+            UnwindOperator::new(Box::new(mock), 0, "x".to_string(), vec![LogicalType::Any]);
+
+        assert!(unwind.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_unwind_non_list_is_type_error() {
+        let mock = MockOperator {
+            chunks: vec![single_value_chunk(Value::Int64(5))],
+            position: 0,
+        };
+        let mut unwind =
+            UnwindOperator::new(Box::new(mock), 0, "x".to_string(), vec![LogicalType::Any]);
+
+        assert!(matches!(
+            unwind.next(),
+            Err(OperatorError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unwind_keeps_other_columns() {
+        // Columns: [list, name] -> output [name, x]
+        let mut builder = DataChunkBuilder::new(&[LogicalType::Any, LogicalType::String]);
+        builder
+            .column_mut(0)
+            .unwrap()
+            .push_value(Value::List(Arc::new([Value::Int64(1), Value::Int64(2)])));
+        builder
+            .column_mut(1)
+            .unwrap()
+            .push_value(Value::String("a".into()));
+        builder.advance_row();
+
+        let mock = MockOperator {
+            chunks: vec![builder.finish()],
+            position: 0,
+        };
+        let mut unwind = UnwindOperator::new(
+            Box::new(mock),
+            0,
+            "x".to_string(),
+            vec![LogicalType::String, LogicalType::Any],
+        );
+
+        let mut rows = Vec::new();
+        while let Some(chunk) = unwind.next().unwrap() {
+            rows.push((
+                chunk.column(0).unwrap().get_value(0).unwrap(),
+                chunk.column(1).unwrap().get_value(0).unwrap(),
+            ));
+        }
+        assert_eq!(
+            rows,
+            vec![
+                (Value::String("a".into()), Value::Int64(1)),
+                (Value::String("a".into()), Value::Int64(2)),
+            ]
+        );
+    }
 }
@@ -65,7 +65,7 @@ enum ColumnValue {
 struct OutputRow {
     /// Index into input_rows for the source row.
     input_idx: usize,
-    /// The final edge in the path.
+    /// The final edge in the path, or `EdgeId::INVALID` for the zero-length path.
     edge_id: EdgeId,
     /// The target node.
     target_id: NodeId,
@@ -175,8 +175,16 @@ impl VariableLengthExpandOperator {
         let epoch = self.viewing_epoch;
         let tx = self.tx_id.unwrap_or(TxId::SYSTEM);
 
-        self.store
-            .edges_from(node_id, self.direction)
+        // Visible edges only when there is a tx context
+        let candidates: Vec<(NodeId, EdgeId)> = match epoch {
+            Some(epoch) => self
+                .store
+                .edges_from_versioned(node_id, self.direction, epoch, tx),
+            None => self.store.edges_from(node_id, self.direction).collect(),
+        };
+
+        candidates
+            .into_iter()
             .filter(|(target_id, edge_id)| {
                 // Filter by edge type if specified
                 let type_matches = if let Some(ref filter_type) = self.edge_type {
@@ -193,14 +201,11 @@ impl VariableLengthExpandOperator {
                     return false;
                 }
 
-                // Filter by visibility if we have tx context
+                // The target must be visible too
                 if let Some(epoch) = epoch {
-                    let edge_visible = self.store.get_edge_versioned(*edge_id, epoch, tx).is_some();
-                    let target_visible = self
-                        .store
+                    self.store
                         .get_node_versioned(*target_id, epoch, tx)
-                        .is_some();
-                    edge_visible && target_visible
+                        .is_some()
                 } else {
                     true
                 }
@@ -212,6 +217,16 @@ impl VariableLengthExpandOperator {
     fn process_input_row(&self, input_idx: usize, source_node: NodeId) -> Vec<OutputRow> {
         let mut results = Vec::new();
 
+        // With min_hops 0 the source is its own target via the empty path
+        if self.min_hops == 0 {
+            results.push(OutputRow {
+                input_idx,
+                edge_id: EdgeId::INVALID,
+                target_id: source_node,
+                path_length: 0,
+            });
+        }
+
         // BFS from source node
         let mut frontier: VecDeque<(NodeId, u32, EdgeId)> = VecDeque::new();
 
@@ -328,7 +343,11 @@ impl Operator for VariableLengthExpandOperator {
 
             // Add edge column
             if let Some(col) = chunk.column_mut(num_input_cols) {
-                col.push_edge_id(out_row.edge_id);
+                if out_row.edge_id == EdgeId::INVALID {
+                    col.push_value(grafeo_common::types::Value::Null);
+                } else {
+                    col.push_edge_id(out_row.edge_id);
+                }
             }
 
             // Add target node column
@@ -468,4 +487,44 @@ mod tests {
         );
         assert!(a_targets.contains(&c), "a should reach c");
     }
+
+    #[test]
+    fn test_variable_length_expand_zero_hops() {
+        let store = Arc::new(LpgStore::new());
+
+        // Create chain: a -> b
+        let a = store.create_node(&["Node"]);
+        let b = store.create_node(&["Node"]);
+        store.create_edge(a, b, "NEXT");
+
+        let scan = Box::new(ScanOperator::with_label(Arc::clone(&store), "Node"));
+
+        let mut expand = VariableLengthExpandOperator::new(
+            Arc::clone(&store),
+            scan,
+            0,
+            Direction::Outgoing,
+            Some("NEXT".to_string()),
+            0,
+            1,
+        )
+        .with_path_length_output();
+
+        let mut results = Vec::new();
+        while let Ok(Some(chunk)) = expand.next() {
+            for i in 0..chunk.row_count() {
+                let src = chunk.column(0).unwrap().get_node_id(i).unwrap();
+                let edge_is_null = chunk.column(1).unwrap().is_null(i);
+                let dst = chunk.column(2).unwrap().get_node_id(i).unwrap();
+                let len = chunk.column(3).unwrap().get_int64(i).unwrap();
+                results.push((src, dst, len, edge_is_null));
+            }
+        }
+
+        // Every node reaches itself over the empty path, which has no edge
+        assert!(results.contains(&(a, a, 0, true)));
+        assert!(results.contains(&(b, b, 0, true)));
+        assert!(results.contains(&(a, b, 1, false)));
+        assert_eq!(results.len(), 3);
+    }
 }
@@ -102,6 +102,13 @@ impl SpillManager {
         self.total_spilled_bytes.load(Ordering::Relaxed)
     }
 
+    /// Returns how many spill files this manager has created, including
+    /// ones since deleted.
+    #[must_use]
+    pub fn files_created(&self) -> u64 {
+        self.next_file_id.load(Ordering::Relaxed)
+    }
+
     /// Returns the number of active spill files.
     #[must_use]
     pub fn active_file_count(&self) -> usize {
@@ -179,6 +186,7 @@ mod tests {
         let file3 = manager.create_file("agg").unwrap();
 
         assert_eq!(manager.active_file_count(), 3);
+        assert_eq!(manager.files_created(), 3);
 
         // File names should be unique
         assert_ne!(file1.path(), file2.path());
@@ -200,6 +208,7 @@ mod tests {
         // Cleanup should remove all files
         manager.cleanup().unwrap();
         assert_eq!(manager.active_file_count(), 0);
+        assert_eq!(manager.files_created(), 2);
     }
 
     #[test]
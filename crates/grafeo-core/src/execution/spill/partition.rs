@@ -509,6 +509,14 @@ fn hash_key(key: &[Value]) -> u64 {
                 8u8.hash(&mut hasher);
                 m.len().hash(&mut hasher);
             }
+            Value::Date(d) => {
+                9u8.hash(&mut hasher);
+                d.hash(&mut hasher);
+            }
+            Value::Duration(d) => {
+                10u8.hash(&mut hasher);
+                d.hash(&mut hasher);
+            }
         }
     }
 
@@ -21,6 +21,8 @@ const TAG_BYTES: u8 = 5;
 const TAG_TIMESTAMP: u8 = 6;
 const TAG_LIST: u8 = 7;
 const TAG_MAP: u8 = 8;
+const TAG_DATE: u8 = 9;
+const TAG_DURATION: u8 = 10;
 
 /// Serializes a Value to bytes.
 ///
@@ -93,6 +95,16 @@ pub fn serialize_value<W: Write + ?Sized>(value: &Value, w: &mut W) -> std::io::
             }
             Ok(total)
         }
+        Value::Date(d) => {
+            w.write_all(&[TAG_DATE])?;
+            w.write_all(&d.as_days().to_le_bytes())?;
+            Ok(5)
+        }
+        Value::Duration(d) => {
+            w.write_all(&[TAG_DURATION])?;
+            w.write_all(&d.as_micros().to_le_bytes())?;
+            Ok(9)
+        }
     }
 }
 
@@ -148,6 +160,20 @@ pub fn deserialize_value<R: Read + ?Sized>(r: &mut R) -> std::io::Result<Value>
                 grafeo_common::types::Timestamp::from_micros(micros),
             ))
         }
+        TAG_DATE => {
+            let mut buf = [0u8; 4];
+            r.read_exact(&mut buf)?;
+            Ok(Value::Date(grafeo_common::types::Date::from_days(
+                i32::from_le_bytes(buf),
+            )))
+        }
+        TAG_DURATION => {
+            let mut buf = [0u8; 8];
+            r.read_exact(&mut buf)?;
+            Ok(Value::Duration(
+                grafeo_common::types::Duration::from_micros(i64::from_le_bytes(buf)),
+            ))
+        }
         TAG_LIST => {
             let mut len_buf = [0u8; 8];
             r.read_exact(&mut len_buf)?;
@@ -314,6 +340,21 @@ mod tests {
         assert_eq!(result.as_bytes(), Some(&[][..]));
     }
 
+    #[test]
+    fn test_serialize_large_bytes() {
+        let data: Vec<u8> = (0..=255u8).cycle().take(4 << 20).collect();
+        let value = Value::Bytes(Arc::from(data.clone()));
+
+        // Tag, length prefix, then the raw bytes
+        let mut buf = Vec::new();
+        let written = serialize_value(&value, &mut buf).unwrap();
+        assert_eq!(written, 1 + 8 + data.len());
+        assert_eq!(buf.len(), written);
+
+        let decoded = deserialize_value(&mut Cursor::new(&buf)).unwrap();
+        assert_eq!(decoded.as_bytes(), Some(&data[..]));
+    }
+
     #[test]
     fn test_serialize_timestamp() {
         let ts = grafeo_common::types::Timestamp::from_micros(1234567890);
@@ -321,6 +362,18 @@ mod tests {
         assert_eq!(result.as_timestamp(), Some(ts));
     }
 
+    #[test]
+    fn test_serialize_temporal() {
+        let date = grafeo_common::types::Date::from_ymd(2024, 2, 29).unwrap();
+        assert_eq!(roundtrip_value(Value::Date(date)).as_date(), Some(date));
+
+        let dur = grafeo_common::types::Duration::from_micros(-90_000_000);
+        assert_eq!(
+            roundtrip_value(Value::Duration(dur)).as_duration(),
+            Some(dur)
+        );
+    }
+
     #[test]
     fn test_serialize_list() {
         let list = Value::List(Arc::from(vec![
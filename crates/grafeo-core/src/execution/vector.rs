@@ -121,9 +121,11 @@ impl ValueVector {
             self.validity = Some(vec![true; self.len]);
         }
         if let Some(validity) = &mut self.validity {
-            if index < validity.len() {
-                validity[index] = false;
+            // Values pushed after the bitmap was created are valid by default
+            if index >= validity.len() {
+                validity.resize(index + 1, true);
             }
+            validity[index] = false;
         }
     }
 
@@ -508,6 +510,19 @@ mod tests {
         assert_eq!(vec.get_int64(2), Some(3));
     }
 
+    #[test]
+    fn test_push_multiple_nulls() {
+        let mut vec = ValueVector::with_type(LogicalType::Node);
+
+        vec.push_value(Value::Null);
+        vec.push_value(Value::Int64(7));
+        vec.push_value(Value::Null);
+
+        assert_eq!(vec.get_value(0), Some(Value::Null));
+        assert_eq!(vec.get_value(1), Some(Value::Int64(7)));
+        assert_eq!(vec.get_value(2), Some(Value::Null));
+    }
+
     #[test]
     fn test_get_value() {
         let mut vec = ValueVector::with_type(LogicalType::Int64);
@@ -20,4 +20,4 @@ mod store;
 pub use edge::{Edge, EdgeRecord};
 pub use node::{Node, NodeRecord};
 pub use property::{CompareOp, PropertyStorage};
-pub use store::LpgStore;
+pub use store::{IntegrityReport, LpgStore, LpgStoreConfig, Savepoint};
@@ -11,17 +11,29 @@
 
 use super::property::CompareOp;
 use super::{Edge, EdgeRecord, Node, NodeRecord, PropertyStorage};
+use crate::execution::operators::HashKey;
 use crate::graph::Direction;
 use crate::index::adjacency::ChunkedAdjacency;
 use crate::index::zone_map::ZoneMapEntry;
-use crate::statistics::{EdgeTypeStatistics, LabelStatistics, Statistics};
+use crate::index::{BTreeIndex, MultiHashIndex, TextTrieIndex};
+use crate::statistics::{EdgeTypeStatistics, LabelStatistics, Statistics, StatisticsCollector};
 use grafeo_common::mvcc::VersionChain;
 use grafeo_common::types::{EdgeId, EpochId, NodeId, PropertyKey, TxId, Value};
-use grafeo_common::utils::hash::{FxHashMap, FxHashSet};
+use grafeo_common::utils::hash::{FxBuildHasher, FxHashMap, FxHashSet};
+use indexmap::IndexSet;
 use parking_lot::RwLock;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
+/// Label IDs of a node, in the order the node was given them.
+type NodeLabels = IndexSet<u32, FxBuildHasher>;
+
+/// Number of histogram buckets built per property by [`LpgStore::compute_statistics`].
+const HISTOGRAM_BUCKETS: usize = 32;
+
+/// Number of most common values kept per property.
+const MOST_COMMON_VALUES: usize = 8;
+
 /// Configuration for the LPG store.
 ///
 /// The defaults work well for most cases. Tune `backward_edges` if you only
@@ -36,6 +48,9 @@ pub struct LpgStoreConfig {
     pub initial_node_capacity: usize,
     /// Initial capacity for edges (avoids early reallocations).
     pub initial_edge_capacity: usize,
+    /// Keep label, edge type, and property counts current as the graph
+    /// changes, instead of only on [`LpgStore::compute_statistics`].
+    pub incremental_statistics: bool,
 }
 
 impl Default for LpgStoreConfig {
@@ -44,6 +59,7 @@ impl Default for LpgStoreConfig {
             backward_edges: true,
             initial_node_capacity: 1024,
             initial_edge_capacity: 4096,
+            incremental_statistics: false,
         }
     }
 }
@@ -77,7 +93,6 @@ impl Default for LpgStoreConfig {
 /// ```
 pub struct LpgStore {
     /// Configuration.
-    #[allow(dead_code)]
     config: LpgStoreConfig,
 
     /// Node records indexed by NodeId, with version chains for MVCC.
@@ -104,6 +119,10 @@ pub struct LpgStore {
     /// Edge type ID to name mapping.
     id_to_edge_type: RwLock<Vec<Arc<str>>>,
 
+    /// Edge types whose edges are undirected: stored once, traversed from
+    /// both endpoints.
+    undirected_edge_types: RwLock<FxHashSet<u32>>,
+
     /// Forward adjacency lists (outgoing edges).
     forward_adj: ChunkedAdjacency,
 
@@ -114,9 +133,23 @@ pub struct LpgStore {
     /// Label index: label_id -> set of node IDs.
     label_index: RwLock<Vec<FxHashMap<NodeId, ()>>>,
 
-    /// Node labels: node_id -> set of label IDs.
+    /// Node labels: node_id -> label IDs, in the order they were added.
     /// Reverse mapping to efficiently get labels for a node.
-    node_labels: RwLock<FxHashMap<NodeId, FxHashSet<u32>>>,
+    node_labels: RwLock<FxHashMap<NodeId, NodeLabels>>,
+
+    /// Value indexes over node properties: (label_id, property) -> index.
+    /// Entries are only ever added, so lookups check every candidate
+    /// against the store.
+    property_indexes: RwLock<FxHashMap<(u32, PropertyKey), Arc<PropertyIndex>>>,
+
+    /// Ordered indexes over several properties of a label's nodes:
+    /// (label_id, properties) -> index. Like `property_indexes`, entries are
+    /// only ever added.
+    composite_indexes: RwLock<FxHashMap<(u32, Vec<PropertyKey>), Arc<CompositeIndex>>>,
+
+    /// Prefix indexes over string properties: (label_id, property) -> trie.
+    /// Like `property_indexes`, entries are only ever added.
+    text_indexes: RwLock<FxHashMap<(u32, PropertyKey), Arc<RwLock<TextTrieIndex>>>>,
 
     /// Next node ID.
     next_node_id: AtomicU64,
@@ -129,6 +162,161 @@ pub struct LpgStore {
 
     /// Statistics for cost-based optimization.
     statistics: RwLock<Statistics>,
+
+    /// Changes made by open transactions outside their version chains.
+    undo_log: RwLock<FxHashMap<TxId, Vec<UndoEntry>>>,
+
+    /// Nodes written by open transactions, with the writing transaction.
+    node_locks: RwLock<FxHashMap<NodeId, TxId>>,
+
+    /// Edges written by open transactions, with the writing transaction.
+    edge_locks: RwLock<FxHashMap<EdgeId, TxId>>,
+
+    /// Earlier states of node properties and labels, for readers that must
+    /// not see later or uncommitted changes.
+    node_history: RwLock<FxHashMap<NodeId, Vec<PriorState>>>,
+
+    /// Earlier states of edge properties, like `node_history`.
+    edge_history: RwLock<FxHashMap<EdgeId, Vec<PriorState>>>,
+
+    /// Start epoch of the oldest open reader, or `u64::MAX` if none is open.
+    /// Changes made outside transactions only enter the history while a
+    /// reader is open.
+    oldest_reader: AtomicU64,
+}
+
+/// A value index over one property of one label's nodes.
+type PropertyIndex = MultiHashIndex<HashKey, NodeId>;
+
+/// An ordered index over several properties of one label's nodes, keyed by
+/// the tuple of their values, so any leading run of them can be looked up.
+type CompositeIndex = BTreeIndex<(Vec<HashKey>, NodeId), ()>;
+
+/// Returns the key the property indexes file `value` under.
+///
+/// Integral floats share their integer's key, so `30` and `30.0`, which
+/// compare equal, are found by the same lookup.
+fn index_key(value: &Value) -> HashKey {
+    match value {
+        Value::Float64(f) if f.fract() == 0.0 && f.abs() < i64::MAX as f64 => {
+            HashKey::Int64(*f as i64)
+        }
+        value => HashKey::from_value(value),
+    }
+}
+
+/// A point in a transaction's changes that it can roll back to, taken by
+/// [`LpgStore::savepoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Savepoint {
+    /// Length of the transaction's undo log at the savepoint.
+    undo_len: usize,
+}
+
+/// Disagreements between the parts of an [`LpgStore`], found by
+/// [`LpgStore::check_integrity`]. Each list is sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// Edges whose source node doesn't exist, with that node.
+    pub dangling_sources: Vec<(EdgeId, NodeId)>,
+    /// Edges whose destination node doesn't exist, with that node.
+    pub dangling_targets: Vec<(EdgeId, NodeId)>,
+    /// Label index entries for nodes that don't exist or lack the label.
+    pub orphaned_label_entries: Vec<(Arc<str>, NodeId)>,
+    /// Edges missing from the adjacency list of one of their endpoints.
+    pub unindexed_edges: Vec<EdgeId>,
+    /// Adjacency entries, as `(node, edge)`, for edges that don't exist or
+    /// connect other nodes.
+    pub stale_adjacency: Vec<(NodeId, EdgeId)>,
+}
+
+impl IntegrityReport {
+    /// Returns `true` if no problems were found.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.dangling_sources.is_empty()
+            && self.dangling_targets.is_empty()
+            && self.orphaned_label_entries.is_empty()
+            && self.unindexed_edges.is_empty()
+            && self.stale_adjacency.is_empty()
+    }
+}
+
+/// A change made inside a transaction that commit or rollback has to visit.
+///
+/// Properties and labels are not versioned, so the store remembers their
+/// previous state for everything a transaction touches.
+#[derive(Clone)]
+enum UndoEntry {
+    /// A node was created.
+    NodeCreated { id: NodeId },
+    /// An edge was created.
+    EdgeCreated { id: EdgeId },
+    /// A node property was set; `old` is the value it replaced.
+    NodeProperty {
+        id: NodeId,
+        key: PropertyKey,
+        old: Option<Value>,
+    },
+    /// An edge property was set; `old` is the value it replaced.
+    EdgeProperty {
+        id: EdgeId,
+        key: PropertyKey,
+        old: Option<Value>,
+    },
+    /// A label was added to a node.
+    LabelAdded { id: NodeId, label: String },
+    /// A label was removed from a node.
+    LabelRemoved { id: NodeId, label: String },
+    /// A node was deleted, taking its labels and properties with it.
+    NodeDeleted {
+        id: NodeId,
+        epoch: EpochId,
+        labels: Vec<Arc<str>>,
+        properties: Vec<(PropertyKey, Value)>,
+    },
+    /// An edge was deleted, taking its properties with it.
+    EdgeDeleted {
+        id: EdgeId,
+        epoch: EpochId,
+        src: NodeId,
+        dst: NodeId,
+        properties: Vec<(PropertyKey, Value)>,
+    },
+}
+
+/// The state a property or label change replaced.
+///
+/// Properties and labels are changed in place; readers that must not see a
+/// change yet (snapshots taken before it, or anyone but the writer while it
+/// is uncommitted) put the replaced state back when reading.
+#[derive(Clone)]
+struct PriorState {
+    /// The transaction that made the change.
+    writer: TxId,
+    /// The epoch the change became visible at, or `None` while its
+    /// transaction is open.
+    epoch: Option<EpochId>,
+    /// What the change replaced.
+    change: PriorChange,
+}
+
+#[derive(Clone)]
+enum PriorChange {
+    /// A property was set or removed; `old` is the value it replaced.
+    Property {
+        key: PropertyKey,
+        old: Option<Value>,
+    },
+    /// A label was added (`had` is false) or removed (`had` is true).
+    Label { label_id: u32, had: bool },
+}
+
+impl PriorState {
+    /// Returns true if a reader at `epoch` on behalf of `tx_id` sees the change.
+    fn visible_to(&self, epoch: EpochId, tx_id: TxId) -> bool {
+        self.writer == tx_id || self.epoch.is_some_and(|e| e.is_visible_at(epoch))
+    }
 }
 
 impl LpgStore {
@@ -156,14 +344,24 @@ impl LpgStore {
             id_to_label: RwLock::new(Vec::new()),
             edge_type_to_id: RwLock::new(FxHashMap::default()),
             id_to_edge_type: RwLock::new(Vec::new()),
+            undirected_edge_types: RwLock::new(FxHashSet::default()),
             forward_adj: ChunkedAdjacency::new(),
             backward_adj,
             label_index: RwLock::new(Vec::new()),
             node_labels: RwLock::new(FxHashMap::default()),
+            property_indexes: RwLock::new(FxHashMap::default()),
+            composite_indexes: RwLock::new(FxHashMap::default()),
+            text_indexes: RwLock::new(FxHashMap::default()),
             next_node_id: AtomicU64::new(0),
             next_edge_id: AtomicU64::new(0),
             current_epoch: AtomicU64::new(0),
             statistics: RwLock::new(Statistics::new()),
+            undo_log: RwLock::new(FxHashMap::default()),
+            node_locks: RwLock::new(FxHashMap::default()),
+            edge_locks: RwLock::new(FxHashMap::default()),
+            node_history: RwLock::new(FxHashMap::default()),
+            edge_history: RwLock::new(FxHashMap::default()),
+            oldest_reader: AtomicU64::new(u64::MAX),
             config,
         }
     }
@@ -197,7 +395,7 @@ impl LpgStore {
         record.set_label_count(labels.len() as u16);
 
         // Store labels in node_labels map and label_index
-        let mut node_label_set = FxHashSet::default();
+        let mut node_label_set = NodeLabels::default();
         for label in labels {
             let label_id = self.get_or_create_label_id(*label);
             node_label_set.insert(label_id);
@@ -216,6 +414,8 @@ impl LpgStore {
         // Create version chain with initial version
         let chain = VersionChain::with_initial(record, epoch, tx_id);
         self.nodes.write().insert(id, chain);
+        self.update_statistics(|stats| stats.record_node_added(labels));
+        self.record_undo(tx_id, UndoEntry::NodeCreated { id });
         id
     }
 
@@ -244,8 +444,12 @@ impl LpgStore {
         let id = self.create_node_versioned(labels, epoch, tx_id);
 
         for (key, value) in properties {
-            self.node_properties.set(id, key.into(), value.into());
+            let key = key.into();
+            let value = value.into();
+            self.index_node_value(id, &key, &value);
+            self.node_properties.set(id, key, value);
         }
+        self.count_node_values(id, true);
 
         // Update props_count in record
         let count = self.node_properties.get_all(id).len() as u16;
@@ -277,7 +481,7 @@ impl LpgStore {
 
         let mut node = Node::new(id);
 
-        // Get labels from node_labels map
+        // Labels come in the order the node was given them
         let id_to_label = self.id_to_label.read();
         let node_labels = self.node_labels.read();
         if let Some(label_ids) = node_labels.get(&id) {
@@ -295,33 +499,78 @@ impl LpgStore {
     }
 
     /// Gets a node visible to a specific transaction.
+    ///
+    /// Changes made by other open transactions, or committed after `epoch`,
+    /// are left out: the node, its labels, and its properties are seen as
+    /// they were before them.
     #[must_use]
     pub fn get_node_versioned(&self, id: NodeId, epoch: EpochId, tx_id: TxId) -> Option<Node> {
-        let nodes = self.nodes.read();
-        let chain = nodes.get(&id)?;
-        let record = chain.visible_to(epoch, tx_id)?;
-
-        if record.is_deleted() {
+        if !self.is_node_visible(id, epoch, tx_id) {
             return None;
         }
+        let mut label_ids = self
+            .node_labels
+            .read()
+            .get(&id)
+            .cloned()
+            .unwrap_or_default();
+        let mut properties = self.node_properties.get_all(id);
+        self.rewind(
+            &self.node_history,
+            id,
+            epoch,
+            tx_id,
+            &mut label_ids,
+            &mut properties,
+        );
 
         let mut node = Node::new(id);
-
-        // Get labels from node_labels map
         let id_to_label = self.id_to_label.read();
-        let node_labels = self.node_labels.read();
-        if let Some(label_ids) = node_labels.get(&id) {
-            for &label_id in label_ids {
-                if let Some(label) = id_to_label.get(label_id as usize) {
-                    node.labels.push(label.clone());
-                }
-            }
-        }
+        node.labels = label_ids
+            .into_iter()
+            .filter_map(|label_id| id_to_label.get(label_id as usize).cloned())
+            .collect();
+        node.properties = properties.into_iter().collect();
+        Some(node)
+    }
 
-        // Get properties
-        node.properties = self.node_properties.get_all(id).into_iter().collect();
+    /// Returns true if a node exists for a transaction; see
+    /// [`get_node_versioned`](Self::get_node_versioned).
+    #[must_use]
+    pub fn is_node_visible(&self, id: NodeId, epoch: EpochId, tx_id: TxId) -> bool {
+        self.nodes.read().get(&id).is_some_and(|chain| {
+            chain
+                .visible_to_committed(epoch, tx_id, |writer| self.is_open(writer))
+                .is_some_and(|record| !record.is_deleted())
+        })
+    }
 
-        Some(node)
+    /// Returns the label IDs a node has for a transaction, or `None` if the
+    /// node isn't visible to it.
+    fn node_label_ids_versioned(
+        &self,
+        id: NodeId,
+        epoch: EpochId,
+        tx_id: TxId,
+    ) -> Option<NodeLabels> {
+        if !self.is_node_visible(id, epoch, tx_id) {
+            return None;
+        }
+        let mut label_ids = self
+            .node_labels
+            .read()
+            .get(&id)
+            .cloned()
+            .unwrap_or_default();
+        self.rewind(
+            &self.node_history,
+            id,
+            epoch,
+            tx_id,
+            &mut label_ids,
+            &mut FxHashMap::default(),
+        );
+        Some(label_ids)
     }
 
     /// Deletes a node and all its edges (using latest epoch).
@@ -331,6 +580,11 @@ impl LpgStore {
 
     /// Deletes a node at a specific epoch.
     pub fn delete_node_at_epoch(&self, id: NodeId, epoch: EpochId) -> bool {
+        self.delete_node_by(id, epoch, TxId::SYSTEM)
+    }
+
+    /// Deletes a node at a specific epoch on behalf of a transaction.
+    fn delete_node_by(&self, id: NodeId, epoch: EpochId, tx_id: TxId) -> bool {
         let mut nodes = self.nodes.write();
         if let Some(chain) = nodes.get_mut(&id) {
             // Check if visible at this epoch (not already deleted)
@@ -344,16 +598,15 @@ impl LpgStore {
             }
 
             // Mark the version chain as deleted at this epoch
-            chain.mark_deleted(epoch);
+            chain.mark_deleted_by(epoch, tx_id);
 
             // Remove from label index using node_labels map
             let mut index = self.label_index.write();
             let mut node_labels = self.node_labels.write();
-            if let Some(label_ids) = node_labels.remove(&id) {
-                for label_id in label_ids {
-                    if let Some(set) = index.get_mut(label_id as usize) {
-                        set.remove(&id);
-                    }
+            let label_ids = node_labels.remove(&id).unwrap_or_default();
+            for label_id in &label_ids {
+                if let Some(set) = index.get_mut(*label_id as usize) {
+                    set.remove(&id);
                 }
             }
 
@@ -361,8 +614,26 @@ impl LpgStore {
             drop(nodes); // Release lock before removing properties
             drop(index);
             drop(node_labels);
+            self.count_node_removed(id, &label_ids);
+            let properties = self.node_properties.get_all(id);
             self.node_properties.remove_all(id);
 
+            // Readers that still see the node need what it carried
+            for label_id in label_ids {
+                let change = PriorChange::Label {
+                    label_id,
+                    had: true,
+                };
+                self.remember(&self.node_history, id, tx_id, change);
+            }
+            for (key, value) in properties {
+                let change = PriorChange::Property {
+                    key,
+                    old: Some(value),
+                };
+                self.remember(&self.node_history, id, tx_id, change);
+            }
+
             // Note: Caller should use delete_node_edges() first if detach is needed
 
             true
@@ -376,6 +647,28 @@ impl LpgStore {
     /// Call this before `delete_node()` if you want to remove a node that
     /// has edges. Grafeo doesn't auto-delete edges - you have to be explicit.
     pub fn delete_node_edges(&self, node_id: NodeId) {
+        for edge_id in self.connected_edges(node_id) {
+            self.delete_edge(edge_id);
+        }
+    }
+
+    /// Deletes all edges connected to a node within a transaction context.
+    pub fn delete_node_edges_versioned(&self, node_id: NodeId, epoch: EpochId, tx_id: TxId) {
+        for edge_id in self.connected_edges(node_id) {
+            self.delete_edge_versioned(edge_id, epoch, tx_id);
+        }
+    }
+
+    /// Returns true if a node has an edge visible to a transaction.
+    #[must_use]
+    pub fn has_connected_edges(&self, node_id: NodeId, epoch: EpochId, tx_id: TxId) -> bool {
+        self.connected_edges(node_id)
+            .into_iter()
+            .any(|edge_id| self.get_edge_versioned(edge_id, epoch, tx_id).is_some())
+    }
+
+    /// Returns the IDs of all edges starting or ending at a node.
+    fn connected_edges(&self, node_id: NodeId) -> Vec<EdgeId> {
         // Get outgoing edges
         let outgoing: Vec<EdgeId> = self
             .forward_adj
@@ -409,35 +702,129 @@ impl LpgStore {
                 .collect()
         };
 
-        // Delete all edges
-        for edge_id in outgoing.into_iter().chain(incoming) {
-            self.delete_edge(edge_id);
+        outgoing.into_iter().chain(incoming).collect()
+    }
+
+    /// Deletes a node within a transaction context.
+    ///
+    /// The node's labels and properties are remembered, so rolling back
+    /// the transaction restores them.
+    pub fn delete_node_versioned(&self, id: NodeId, epoch: EpochId, tx_id: TxId) -> bool {
+        let Some(node) = self.get_node_at_epoch(id, epoch) else {
+            return false;
+        };
+        if !self.delete_node_by(id, epoch, tx_id) {
+            return false;
         }
+        self.record_undo(
+            tx_id,
+            UndoEntry::NodeDeleted {
+                id,
+                epoch,
+                labels: node.labels,
+                properties: node.properties.into_iter().collect(),
+            },
+        );
+        true
     }
 
     /// Sets a property on a node.
     pub fn set_node_property(&self, id: NodeId, key: &str, value: Value) {
-        self.node_properties.set(id, key.into(), value);
+        self.set_node_property_versioned(id, key, value, TxId::SYSTEM);
+    }
 
-        // Update props_count in record
-        let count = self.node_properties.get_all(id).len() as u16;
-        if let Some(chain) = self.nodes.write().get_mut(&id) {
-            if let Some(record) = chain.latest_mut() {
-                record.props_count = count;
-            }
-        }
+    /// Sets a property on a node within a transaction context.
+    pub fn set_node_property_versioned(&self, id: NodeId, key: &str, value: Value, tx_id: TxId) {
+        let key = PropertyKey::from(key);
+        let old = self.write_node_property(id, &key, Some(value));
+        self.record_node_property(id, key, old, tx_id);
     }
 
     /// Sets a property on an edge.
     pub fn set_edge_property(&self, id: EdgeId, key: &str, value: Value) {
-        self.edge_properties.set(id, key.into(), value);
+        self.set_edge_property_versioned(id, key, value, TxId::SYSTEM);
+    }
+
+    /// Sets a property on an edge within a transaction context.
+    pub fn set_edge_property_versioned(&self, id: EdgeId, key: &str, value: Value, tx_id: TxId) {
+        let key = PropertyKey::from(key);
+        let old = self.write_edge_property(id, &key, Some(value));
+        self.record_edge_property(id, key, old, tx_id);
     }
 
     /// Removes a property from a node.
     ///
     /// Returns the previous value if it existed, or None if the property didn't exist.
     pub fn remove_node_property(&self, id: NodeId, key: &str) -> Option<Value> {
-        let result = self.node_properties.remove(id, &key.into());
+        self.remove_node_property_versioned(id, key, TxId::SYSTEM)
+    }
+
+    /// Removes a property from an edge.
+    ///
+    /// Returns the previous value if it existed, or None if the property didn't exist.
+    pub fn remove_edge_property(&self, id: EdgeId, key: &str) -> Option<Value> {
+        self.remove_edge_property_versioned(id, key, TxId::SYSTEM)
+    }
+
+    /// Removes a property from a node within a transaction context.
+    pub fn remove_node_property_versioned(
+        &self,
+        id: NodeId,
+        key: &str,
+        tx_id: TxId,
+    ) -> Option<Value> {
+        let key = PropertyKey::from(key);
+        let old = self.write_node_property(id, &key, None);
+        if old.is_some() {
+            self.record_node_property(id, key, old.clone(), tx_id);
+        }
+        old
+    }
+
+    /// Removes a property from an edge within a transaction context.
+    pub fn remove_edge_property_versioned(
+        &self,
+        id: EdgeId,
+        key: &str,
+        tx_id: TxId,
+    ) -> Option<Value> {
+        let key = PropertyKey::from(key);
+        let old = self.write_edge_property(id, &key, None);
+        if old.is_some() {
+            self.record_edge_property(id, key, old.clone(), tx_id);
+        }
+        old
+    }
+
+    /// Sets (`Some`) or removes (`None`) a node property in place, returning
+    /// the value it replaced.
+    fn write_node_property(
+        &self,
+        id: NodeId,
+        key: &PropertyKey,
+        value: Option<Value>,
+    ) -> Option<Value> {
+        let old = match value {
+            Some(value) => {
+                let old = self.node_properties.get(id, key);
+                self.update_statistics(|stats| {
+                    if let Some(old) = &old {
+                        stats.record_value_removed(key.as_str(), old);
+                    }
+                    stats.record_value_added(key.as_str(), &value);
+                });
+                self.index_node_value(id, key, &value);
+                self.node_properties.set(id, key.clone(), value);
+                old
+            }
+            None => {
+                let old = self.node_properties.remove(id, key);
+                if let Some(ref old) = old {
+                    self.update_statistics(|stats| stats.record_value_removed(key.as_str(), old));
+                }
+                old
+            }
+        };
 
         // Update props_count in record
         let count = self.node_properties.get_all(id).len() as u16;
@@ -447,14 +834,47 @@ impl LpgStore {
             }
         }
 
-        result
+        old
     }
 
-    /// Removes a property from an edge.
-    ///
-    /// Returns the previous value if it existed, or None if the property didn't exist.
-    pub fn remove_edge_property(&self, id: EdgeId, key: &str) -> Option<Value> {
-        self.edge_properties.remove(id, &key.into())
+    /// Sets (`Some`) or removes (`None`) an edge property in place,
+    /// returning the value it replaced.
+    fn write_edge_property(
+        &self,
+        id: EdgeId,
+        key: &PropertyKey,
+        value: Option<Value>,
+    ) -> Option<Value> {
+        // Edge property statistics only come from a full recompute
+        self.mark_statistics_stale();
+        match value {
+            Some(value) => {
+                let old = self.edge_properties.get(id, key);
+                self.edge_properties.set(id, key.clone(), value);
+                old
+            }
+            None => self.edge_properties.remove(id, key),
+        }
+    }
+
+    /// Remembers the value a node property change replaced.
+    fn record_node_property(&self, id: NodeId, key: PropertyKey, old: Option<Value>, tx_id: TxId) {
+        let change = PriorChange::Property {
+            key: key.clone(),
+            old: old.clone(),
+        };
+        self.remember(&self.node_history, id, tx_id, change);
+        self.record_undo(tx_id, UndoEntry::NodeProperty { id, key, old });
+    }
+
+    /// Remembers the value an edge property change replaced.
+    fn record_edge_property(&self, id: EdgeId, key: PropertyKey, old: Option<Value>, tx_id: TxId) {
+        let change = PriorChange::Property {
+            key: key.clone(),
+            old: old.clone(),
+        };
+        self.remember(&self.edge_history, id, tx_id, change);
+        self.record_undo(tx_id, UndoEntry::EdgeProperty { id, key, old });
     }
 
     /// Adds a label to a node.
@@ -462,16 +882,60 @@ impl LpgStore {
     /// Returns true if the label was added, false if the node doesn't exist
     /// or already has the label.
     pub fn add_label(&self, node_id: NodeId, label: &str) -> bool {
+        self.add_label_versioned(node_id, label, TxId::SYSTEM)
+    }
+
+    /// Adds a label to a node within a transaction context.
+    pub fn add_label_versioned(&self, node_id: NodeId, label: &str, tx_id: TxId) -> bool {
+        let Some(label_id) = self.attach_label(node_id, label) else {
+            return false;
+        };
+        let change = PriorChange::Label {
+            label_id,
+            had: false,
+        };
+        self.remember(&self.node_history, node_id, tx_id, change);
+        let label = label.to_string();
+        self.record_undo(tx_id, UndoEntry::LabelAdded { id: node_id, label });
+        true
+    }
+
+    /// Removes a label from a node within a transaction context.
+    pub fn remove_label_versioned(&self, node_id: NodeId, label: &str, tx_id: TxId) -> bool {
+        let Some(label_id) = self.detach_label(node_id, label) else {
+            return false;
+        };
+        let change = PriorChange::Label {
+            label_id,
+            had: true,
+        };
+        self.remember(&self.node_history, node_id, tx_id, change);
+        let label = label.to_string();
+        self.record_undo(tx_id, UndoEntry::LabelRemoved { id: node_id, label });
+        true
+    }
+
+    /// Removes a label from a node.
+    ///
+    /// Returns true if the label was removed, false if the node doesn't exist
+    /// or doesn't have the label.
+    pub fn remove_label(&self, node_id: NodeId, label: &str) -> bool {
+        self.remove_label_versioned(node_id, label, TxId::SYSTEM)
+    }
+
+    /// Adds a label to a node in place, returning its ID if the node didn't
+    /// have it yet.
+    fn attach_label(&self, node_id: NodeId, label: &str) -> Option<u32> {
         let epoch = self.current_epoch();
 
         // Check if node exists
         let nodes = self.nodes.read();
-        if let Some(chain) = nodes.get(&node_id) {
-            if chain.visible_at(epoch).map_or(true, |r| r.is_deleted()) {
-                return false;
-            }
-        } else {
-            return false;
+        if nodes
+            .get(&node_id)?
+            .visible_at(epoch)
+            .map_or(true, |r| r.is_deleted())
+        {
+            return None;
         }
         drop(nodes);
 
@@ -482,13 +946,11 @@ impl LpgStore {
         let mut node_labels = self.node_labels.write();
         let label_set = node_labels
             .entry(node_id)
-            .or_insert_with(FxHashSet::default);
+            .or_insert_with(NodeLabels::default);
 
-        if label_set.contains(&label_id) {
-            return false; // Already has this label
+        if !label_set.insert(label_id) {
+            return None; // Already has this label
         }
-
-        label_set.insert(label_id);
         drop(node_labels);
 
         // Add to label_index
@@ -497,71 +959,63 @@ impl LpgStore {
             index.resize(label_id as usize + 1, FxHashMap::default());
         }
         index[label_id as usize].insert(node_id, ());
+        drop(index);
 
-        // Update label count in node record
-        if let Some(chain) = self.nodes.write().get_mut(&node_id) {
-            if let Some(record) = chain.latest_mut() {
-                let count = self.node_labels.read().get(&node_id).map_or(0, |s| s.len());
-                record.set_label_count(count as u16);
-            }
-        }
-
-        true
+        self.index_node_label(node_id, label_id);
+        self.update_label_count(node_id);
+        self.update_statistics(|stats| stats.record_label_added(label));
+        Some(label_id)
     }
 
-    /// Removes a label from a node.
-    ///
-    /// Returns true if the label was removed, false if the node doesn't exist
-    /// or doesn't have the label.
-    pub fn remove_label(&self, node_id: NodeId, label: &str) -> bool {
+    /// Removes a label from a node in place, returning its ID if the node
+    /// had it.
+    fn detach_label(&self, node_id: NodeId, label: &str) -> Option<u32> {
         let epoch = self.current_epoch();
 
         // Check if node exists
         let nodes = self.nodes.read();
-        if let Some(chain) = nodes.get(&node_id) {
-            if chain.visible_at(epoch).map_or(true, |r| r.is_deleted()) {
-                return false;
-            }
-        } else {
-            return false;
+        if nodes
+            .get(&node_id)?
+            .visible_at(epoch)
+            .map_or(true, |r| r.is_deleted())
+        {
+            return None;
         }
         drop(nodes);
 
         // Get label ID
-        let label_id = {
-            let label_ids = self.label_to_id.read();
-            match label_ids.get(label) {
-                Some(&id) => id,
-                None => return false, // Label doesn't exist
-            }
-        };
+        let label_id = *self.label_to_id.read().get(label)?;
 
         // Remove from node_labels map
-        let mut node_labels = self.node_labels.write();
-        if let Some(label_set) = node_labels.get_mut(&node_id) {
-            if !label_set.remove(&label_id) {
-                return false; // Node doesn't have this label
-            }
-        } else {
-            return false;
+        if !self
+            .node_labels
+            .write()
+            .get_mut(&node_id)?
+            .shift_remove(&label_id)
+        {
+            return None; // Node doesn't have this label
         }
-        drop(node_labels);
 
         // Remove from label_index
         let mut index = self.label_index.write();
         if (label_id as usize) < index.len() {
             index[label_id as usize].remove(&node_id);
         }
+        drop(index);
+
+        self.update_label_count(node_id);
+        self.update_statistics(|stats| stats.record_label_removed(label));
+        Some(label_id)
+    }
 
-        // Update label count in node record
+    /// Updates the label count in a node's record.
+    fn update_label_count(&self, node_id: NodeId) {
+        let count = self.node_labels.read().get(&node_id).map_or(0, |s| s.len());
         if let Some(chain) = self.nodes.write().get_mut(&node_id) {
             if let Some(record) = chain.latest_mut() {
-                let count = self.node_labels.read().get(&node_id).map_or(0, |s| s.len());
                 record.set_label_count(count as u16);
             }
         }
-
-        true
     }
 
     /// Returns the number of nodes (non-deleted at current epoch).
@@ -627,6 +1081,8 @@ impl LpgStore {
             backward.add_edge(dst, src, id);
         }
 
+        self.update_statistics(|stats| stats.record_edge_added(edge_type));
+        self.record_undo(tx_id, UndoEntry::EdgeCreated { id });
         id
     }
 
@@ -678,25 +1134,39 @@ impl LpgStore {
     }
 
     /// Gets an edge visible to a specific transaction.
+    ///
+    /// Like [`get_node_versioned`](Self::get_node_versioned), changes the
+    /// transaction must not see are left out.
     #[must_use]
     pub fn get_edge_versioned(&self, id: EdgeId, epoch: EpochId, tx_id: TxId) -> Option<Edge> {
-        let edges = self.edges.read();
-        let chain = edges.get(&id)?;
-        let record = chain.visible_to(epoch, tx_id)?;
-
-        if record.is_deleted() {
-            return None;
-        }
+        let (src, dst, type_id) = {
+            let edges = self.edges.read();
+            let record = edges
+                .get(&id)?
+                .visible_to_committed(epoch, tx_id, |writer| self.is_open(writer))?;
+            if record.is_deleted() {
+                return None;
+            }
+            (record.src, record.dst, record.type_id)
+        };
 
         let edge_type = {
             let id_to_type = self.id_to_edge_type.read();
-            id_to_type.get(record.type_id as usize)?.clone()
+            id_to_type.get(type_id as usize)?.clone()
         };
 
-        let mut edge = Edge::new(id, record.src, record.dst, edge_type);
+        let mut edge = Edge::new(id, src, dst, edge_type);
 
-        // Get properties
-        edge.properties = self.edge_properties.get_all(id).into_iter().collect();
+        let mut properties = self.edge_properties.get_all(id);
+        self.rewind(
+            &self.edge_history,
+            id,
+            epoch,
+            tx_id,
+            &mut NodeLabels::default(),
+            &mut properties,
+        );
+        edge.properties = properties.into_iter().collect();
 
         Some(edge)
     }
@@ -708,23 +1178,28 @@ impl LpgStore {
 
     /// Deletes an edge at a specific epoch.
     pub fn delete_edge_at_epoch(&self, id: EdgeId, epoch: EpochId) -> bool {
+        self.delete_edge_by(id, epoch, TxId::SYSTEM)
+    }
+
+    /// Deletes an edge at a specific epoch on behalf of a transaction.
+    fn delete_edge_by(&self, id: EdgeId, epoch: EpochId, tx_id: TxId) -> bool {
         let mut edges = self.edges.write();
         if let Some(chain) = edges.get_mut(&id) {
             // Get the visible record to check if deleted and get src/dst
-            let (src, dst) = {
+            let (src, dst, type_id) = {
                 match chain.visible_at(epoch) {
                     Some(record) => {
                         if record.is_deleted() {
                             return false;
                         }
-                        (record.src, record.dst)
+                        (record.src, record.dst, record.type_id)
                     }
                     None => return false, // Not visible at this epoch (already deleted)
                 }
             };
 
             // Mark the version chain as deleted
-            chain.mark_deleted(epoch);
+            chain.mark_deleted_by(epoch, tx_id);
 
             drop(edges); // Release lock
 
@@ -734,15 +1209,47 @@ impl LpgStore {
                 backward.mark_deleted(dst, id);
             }
 
-            // Remove properties
+            // Remove properties, keeping them for readers that still see the edge
+            for (key, value) in self.edge_properties.get_all(id) {
+                let change = PriorChange::Property {
+                    key,
+                    old: Some(value),
+                };
+                self.remember(&self.edge_history, id, tx_id, change);
+            }
             self.edge_properties.remove_all(id);
 
+            self.count_edge_removed(type_id);
             true
         } else {
             false
         }
     }
 
+    /// Deletes an edge within a transaction context.
+    ///
+    /// The edge's properties are remembered, so rolling back the
+    /// transaction restores them.
+    pub fn delete_edge_versioned(&self, id: EdgeId, epoch: EpochId, tx_id: TxId) -> bool {
+        let Some(edge) = self.get_edge_at_epoch(id, epoch) else {
+            return false;
+        };
+        if !self.delete_edge_by(id, epoch, tx_id) {
+            return false;
+        }
+        self.record_undo(
+            tx_id,
+            UndoEntry::EdgeDeleted {
+                id,
+                epoch,
+                src: edge.src,
+                dst: edge.dst,
+                properties: edge.properties.into_iter().collect(),
+            },
+        );
+        true
+    }
+
     /// Returns the number of edges (non-deleted at current epoch).
     #[must_use]
     pub fn edge_count(&self) -> usize {
@@ -758,38 +1265,485 @@ impl LpgStore {
     /// Discards all uncommitted versions created by a transaction.
     ///
     /// This is called during transaction rollback to clean up uncommitted changes.
-    /// The method removes version chain entries created by the specified transaction.
+    /// The changes are found through the transaction's undo log, which is
+    /// dropped last so the transaction's writes stay hidden from other
+    /// readers until they are gone.
     pub fn discard_uncommitted_versions(&self, tx_id: TxId) {
-        // Remove uncommitted node versions
-        {
-            let mut nodes = self.nodes.write();
-            for chain in nodes.values_mut() {
-                chain.remove_versions_by(tx_id);
-            }
-            // Remove completely empty chains (no versions left)
-            nodes.retain(|_, chain| !chain.is_empty());
-        }
-
-        // Remove uncommitted edge versions
-        {
-            let mut edges = self.edges.write();
-            for chain in edges.values_mut() {
-                chain.remove_versions_by(tx_id);
-            }
-            // Remove completely empty chains (no versions left)
-            edges.retain(|_, chain| !chain.is_empty());
-        }
+        let undo = self
+            .undo_log
+            .read()
+            .get(&tx_id)
+            .cloned()
+            .unwrap_or_default();
+        self.revert(tx_id, undo);
+        self.release_locks(tx_id);
+        self.undo_log.write().remove(&tx_id);
     }
 
-    /// Returns the number of distinct labels in the store.
+    /// Marks the current state of a transaction's changes.
+    ///
+    /// Passing the result to [`rollback_to_savepoint`](Self::rollback_to_savepoint)
+    /// undoes whatever the transaction changes afterwards.
     #[must_use]
-    pub fn label_count(&self) -> usize {
-        self.id_to_label.read().len()
+    pub fn savepoint(&self, tx_id: TxId) -> Savepoint {
+        Savepoint {
+            undo_len: self.undo_log.read().get(&tx_id).map_or(0, Vec::len),
+        }
     }
 
-    /// Returns the number of distinct property keys in the store.
+    /// Undoes the changes a transaction made after `savepoint`, keeping the
+    /// ones made before it.
     ///
-    /// This counts unique property keys across both nodes and edges.
+    /// Nodes and edges the transaction created since the savepoint are
+    /// discarded. Its write claims are kept until it commits or rolls back.
+    pub fn rollback_to_savepoint(&self, tx_id: TxId, savepoint: Savepoint) {
+        let undo = self
+            .undo_log
+            .read()
+            .get(&tx_id)
+            .map(|log| log[savepoint.undo_len.min(log.len())..].to_vec())
+            .unwrap_or_default();
+        self.revert(tx_id, undo);
+        if let Some(log) = self.undo_log.write().get_mut(&tx_id) {
+            log.truncate(savepoint.undo_len);
+        }
+    }
+
+    /// Reverts a transaction's changes, newest first, and discards the
+    /// nodes and edges it created.
+    fn revert(&self, tx_id: TxId, undo: Vec<UndoEntry>) {
+        let mut created_nodes = Vec::new();
+        let mut created_edges = Vec::new();
+        for entry in undo.into_iter().rev() {
+            match entry {
+                UndoEntry::NodeCreated { id } => created_nodes.push(id),
+                UndoEntry::EdgeCreated { id } => created_edges.push(id),
+                entry => self.apply_undo(tx_id, entry),
+            }
+        }
+        self.remove_versions_created_by(tx_id, &created_nodes, &created_edges);
+    }
+
+    /// Removes the versions a transaction created for the given nodes and
+    /// edges, dropping entities left with no versions from the indexes.
+    fn remove_versions_created_by(&self, tx_id: TxId, nodes: &[NodeId], edges: &[EdgeId]) {
+        // Remove uncommitted node versions
+        let mut removed_nodes = Vec::new();
+        {
+            let mut chains = self.nodes.write();
+            for id in nodes {
+                if let Some(chain) = chains.get_mut(id) {
+                    chain.remove_versions_by(tx_id);
+                    if chain.is_empty() {
+                        chains.remove(id);
+                        removed_nodes.push(*id);
+                    }
+                }
+            }
+        }
+
+        // Remove uncommitted edge versions
+        let mut removed_edges = Vec::new();
+        {
+            let mut chains = self.edges.write();
+            for id in edges {
+                if let Some(chain) = chains.get_mut(id) {
+                    let endpoints = chain.latest().map(|r| (r.src, r.dst, r.type_id));
+                    chain.remove_versions_by(tx_id);
+                    if chain.is_empty() {
+                        chains.remove(id);
+                        removed_edges
+                            .extend(endpoints.map(|(src, dst, type_id)| (*id, src, dst, type_id)));
+                    }
+                }
+            }
+        }
+
+        // Drop what the discarded entities left in the indexes
+        for id in removed_nodes {
+            let label_ids = self.node_labels.write().remove(&id).unwrap_or_default();
+            {
+                let mut index = self.label_index.write();
+                for label_id in &label_ids {
+                    if let Some(set) = index.get_mut(*label_id as usize) {
+                        set.remove(&id);
+                    }
+                }
+            }
+            self.count_node_removed(id, &label_ids);
+            self.node_properties.remove_all(id);
+            self.node_history.write().remove(&id);
+        }
+        for (id, src, dst, type_id) in removed_edges {
+            self.forward_adj.mark_deleted(src, id);
+            if let Some(ref backward) = self.backward_adj {
+                backward.mark_deleted(dst, id);
+            }
+            self.edge_properties.remove_all(id);
+            self.edge_history.write().remove(&id);
+            self.count_edge_removed(type_id);
+        }
+    }
+
+    /// Claims a node for writing by a transaction.
+    ///
+    /// Returns `false` if another open transaction has already written to
+    /// the node. The system transaction never holds a claim, but it still
+    /// respects claims made by others.
+    pub fn try_lock_node(&self, id: NodeId, tx_id: TxId) -> bool {
+        Self::try_lock(&self.node_locks, id, tx_id)
+    }
+
+    /// Claims an edge for writing by a transaction.
+    ///
+    /// See [`try_lock_node`](Self::try_lock_node).
+    pub fn try_lock_edge(&self, id: EdgeId, tx_id: TxId) -> bool {
+        Self::try_lock(&self.edge_locks, id, tx_id)
+    }
+
+    /// Returns the nodes claimed by a transaction, in ID order.
+    #[must_use]
+    pub fn nodes_written_by(&self, tx_id: TxId) -> Vec<NodeId> {
+        Self::locked_by(&self.node_locks, tx_id)
+    }
+
+    /// Returns the edges claimed by a transaction, in ID order.
+    #[must_use]
+    pub fn edges_written_by(&self, tx_id: TxId) -> Vec<EdgeId> {
+        Self::locked_by(&self.edge_locks, tx_id)
+    }
+
+    fn try_lock<K: Copy + Eq + std::hash::Hash>(
+        locks: &RwLock<FxHashMap<K, TxId>>,
+        key: K,
+        tx_id: TxId,
+    ) -> bool {
+        if tx_id == TxId::SYSTEM {
+            return !locks.read().contains_key(&key);
+        }
+        let mut locks = locks.write();
+        *locks.entry(key).or_insert(tx_id) == tx_id
+    }
+
+    fn locked_by<K: Copy + Ord>(locks: &RwLock<FxHashMap<K, TxId>>, tx_id: TxId) -> Vec<K> {
+        let mut keys: Vec<K> = locks
+            .read()
+            .iter()
+            .filter(|(_, owner)| **owner == tx_id)
+            .map(|(key, _)| *key)
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// Releases every claim held by a transaction.
+    fn release_locks(&self, tx_id: TxId) {
+        self.node_locks.write().retain(|_, owner| *owner != tx_id);
+        self.edge_locks.write().retain(|_, owner| *owner != tx_id);
+    }
+
+    /// Remembers how to revert a change made by a user transaction.
+    fn record_undo(&self, tx_id: TxId, entry: UndoEntry) {
+        if tx_id != TxId::SYSTEM {
+            self.undo_log.write().entry(tx_id).or_default().push(entry);
+        }
+    }
+
+    /// Reverts a single change recorded in the undo log, and forgets the
+    /// state the change left in the history.
+    fn apply_undo(&self, tx_id: TxId, entry: UndoEntry) {
+        match entry {
+            UndoEntry::NodeCreated { .. } | UndoEntry::EdgeCreated { .. } => {}
+            UndoEntry::NodeProperty { id, key, old } => {
+                self.write_node_property(id, &key, old);
+                self.forget(&self.node_history, id, tx_id, 1);
+            }
+            UndoEntry::EdgeProperty { id, key, old } => {
+                self.write_edge_property(id, &key, old);
+                self.forget(&self.edge_history, id, tx_id, 1);
+            }
+            UndoEntry::LabelAdded { id, label } => {
+                self.detach_label(id, &label);
+                self.forget(&self.node_history, id, tx_id, 1);
+            }
+            UndoEntry::LabelRemoved { id, label } => {
+                self.attach_label(id, &label);
+                self.forget(&self.node_history, id, tx_id, 1);
+            }
+            UndoEntry::NodeDeleted {
+                id,
+                epoch,
+                labels,
+                properties,
+            } => {
+                if let Some(chain) = self.nodes.write().get_mut(&id) {
+                    chain.unmark_deleted(epoch);
+                }
+                self.forget(
+                    &self.node_history,
+                    id,
+                    tx_id,
+                    labels.len() + properties.len(),
+                );
+                // Labels and properties are counted as they are restored
+                self.update_statistics(|stats| stats.record_node_added(&[]));
+                for label in labels {
+                    self.attach_label(id, &label);
+                }
+                for (key, value) in properties {
+                    self.write_node_property(id, &key, Some(value));
+                }
+            }
+            UndoEntry::EdgeDeleted {
+                id,
+                epoch,
+                src,
+                dst,
+                properties,
+            } => {
+                let type_id = self.edges.write().get_mut(&id).and_then(|chain| {
+                    chain.unmark_deleted(epoch);
+                    chain.latest().map(|record| record.type_id)
+                });
+                if let Some(type_id) = type_id {
+                    self.count_edge_added(type_id);
+                }
+                self.forward_adj.unmark_deleted(src, id);
+                if let Some(ref backward) = self.backward_adj {
+                    backward.unmark_deleted(dst, id);
+                }
+                self.forget(&self.edge_history, id, tx_id, properties.len());
+                for (key, value) in properties {
+                    self.edge_properties.set(id, key, value);
+                }
+            }
+        }
+    }
+
+    /// Moves all versions created by a transaction to its commit epoch.
+    ///
+    /// Versions are written at the transaction's start epoch; restamping them
+    /// (and the transaction's deletions and property and label changes) on
+    /// commit keeps them out of snapshots pinned before the commit. Only the
+    /// entities in the transaction's undo log are visited. The transaction's
+    /// write claims are released, and the store's epoch is advanced to
+    /// `commit_epoch` if it lags behind.
+    pub fn commit_versions(&self, tx_id: TxId, commit_epoch: EpochId) {
+        let undo = self
+            .undo_log
+            .read()
+            .get(&tx_id)
+            .cloned()
+            .unwrap_or_default();
+        {
+            let mut nodes = self.nodes.write();
+            for entry in &undo {
+                match entry {
+                    UndoEntry::NodeCreated { id } => {
+                        if let Some(chain) = nodes.get_mut(id) {
+                            chain.stamp_versions_by(tx_id, commit_epoch);
+                        }
+                    }
+                    UndoEntry::NodeDeleted { id, epoch, .. } => {
+                        if let Some(chain) = nodes.get_mut(id) {
+                            chain.restamp_deletion(*epoch, commit_epoch);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        {
+            let mut edges = self.edges.write();
+            for entry in &undo {
+                match entry {
+                    UndoEntry::EdgeCreated { id } => {
+                        if let Some(chain) = edges.get_mut(id) {
+                            chain.stamp_versions_by(tx_id, commit_epoch);
+                        }
+                    }
+                    UndoEntry::EdgeDeleted { id, epoch, .. } => {
+                        if let Some(chain) = edges.get_mut(id) {
+                            chain.restamp_deletion(*epoch, commit_epoch);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        {
+            let mut node_history = self.node_history.write();
+            let mut edge_history = self.edge_history.write();
+            for entry in &undo {
+                let changes = match entry {
+                    UndoEntry::NodeProperty { id, .. }
+                    | UndoEntry::LabelAdded { id, .. }
+                    | UndoEntry::LabelRemoved { id, .. }
+                    | UndoEntry::NodeDeleted { id, .. } => node_history.get_mut(id),
+                    UndoEntry::EdgeProperty { id, .. } | UndoEntry::EdgeDeleted { id, .. } => {
+                        edge_history.get_mut(id)
+                    }
+                    UndoEntry::NodeCreated { .. } | UndoEntry::EdgeCreated { .. } => None,
+                };
+                for prior in changes.into_iter().flatten() {
+                    if prior.writer == tx_id && prior.epoch.is_none() {
+                        prior.epoch = Some(commit_epoch);
+                    }
+                }
+            }
+        }
+        self.undo_log.write().remove(&tx_id);
+        self.release_locks(tx_id);
+        self.advance_epoch_to(commit_epoch);
+    }
+
+    /// Returns true if a transaction has uncommitted changes in the store.
+    fn is_open(&self, tx_id: TxId) -> bool {
+        tx_id != TxId::SYSTEM && self.undo_log.read().contains_key(&tx_id)
+    }
+
+    /// Remembers the state a property or label change replaced.
+    ///
+    /// Changes made outside a transaction are only kept while a reader is
+    /// open that may need the older state.
+    fn remember<K: Copy + Eq + std::hash::Hash>(
+        &self,
+        history: &RwLock<FxHashMap<K, Vec<PriorState>>>,
+        id: K,
+        tx_id: TxId,
+        change: PriorChange,
+    ) {
+        let epoch = if tx_id == TxId::SYSTEM {
+            if self.oldest_reader.load(Ordering::Acquire) == u64::MAX {
+                return;
+            }
+            Some(self.current_epoch())
+        } else {
+            None
+        };
+        history.write().entry(id).or_default().push(PriorState {
+            writer: tx_id,
+            epoch,
+            change,
+        });
+    }
+
+    /// Forgets the last `count` uncommitted changes a transaction made to
+    /// an entity, once they have been reverted.
+    fn forget<K: Copy + Eq + std::hash::Hash>(
+        &self,
+        history: &RwLock<FxHashMap<K, Vec<PriorState>>>,
+        id: K,
+        tx_id: TxId,
+        mut count: usize,
+    ) {
+        let mut history = history.write();
+        let Some(changes) = history.get_mut(&id) else {
+            return;
+        };
+        while count > 0 {
+            let Some(index) = changes
+                .iter()
+                .rposition(|prior| prior.writer == tx_id && prior.epoch.is_none())
+            else {
+                break;
+            };
+            changes.remove(index);
+            count -= 1;
+        }
+        if changes.is_empty() {
+            history.remove(&id);
+        }
+    }
+
+    /// Puts back the labels and properties an entity had before the changes
+    /// a reader at `epoch` on behalf of `tx_id` must not see.
+    fn rewind<K: Copy + Eq + std::hash::Hash>(
+        &self,
+        history: &RwLock<FxHashMap<K, Vec<PriorState>>>,
+        id: K,
+        epoch: EpochId,
+        tx_id: TxId,
+        label_ids: &mut NodeLabels,
+        properties: &mut FxHashMap<PropertyKey, Value>,
+    ) {
+        let history = history.read();
+        let Some(changes) = history.get(&id) else {
+            return;
+        };
+        // Newest first, so the oldest hidden change decides the state
+        for prior in changes.iter().rev() {
+            if prior.visible_to(epoch, tx_id) {
+                continue;
+            }
+            match &prior.change {
+                PriorChange::Property {
+                    key,
+                    old: Some(old),
+                } => {
+                    properties.insert(key.clone(), old.clone());
+                }
+                PriorChange::Property { key, old: None } => {
+                    properties.remove(key);
+                }
+                PriorChange::Label {
+                    label_id,
+                    had: true,
+                } => {
+                    label_ids.insert(*label_id);
+                }
+                PriorChange::Label {
+                    label_id,
+                    had: false,
+                } => {
+                    label_ids.shift_remove(label_id);
+                }
+            }
+        }
+    }
+
+    /// Tells the store the start epoch of its oldest open transaction or
+    /// snapshot, or `None` if none is open.
+    ///
+    /// Earlier property and label states that every open reader can see past
+    /// are dropped, and changes made outside transactions are only kept for
+    /// readers while one is open.
+    pub fn set_oldest_reader(&self, epoch: Option<EpochId>) {
+        self.oldest_reader
+            .store(epoch.map_or(u64::MAX, |e| e.as_u64()), Ordering::Release);
+        Self::prune_history(&self.node_history, epoch);
+        Self::prune_history(&self.edge_history, epoch);
+    }
+
+    fn prune_history<K: Copy + Eq + std::hash::Hash>(
+        history: &RwLock<FxHashMap<K, Vec<PriorState>>>,
+        oldest_reader: Option<EpochId>,
+    ) {
+        history.write().retain(|_, changes| {
+            changes.retain(|prior| match (prior.epoch, oldest_reader) {
+                (None, _) => true,
+                (Some(_), None) => false,
+                (Some(epoch), Some(oldest)) => epoch.as_u64() > oldest.as_u64(),
+            });
+            !changes.is_empty()
+        });
+    }
+
+    /// Advances the current epoch to `epoch`, never moving it backwards.
+    pub fn advance_epoch_to(&self, epoch: EpochId) {
+        self.current_epoch
+            .fetch_max(epoch.as_u64(), Ordering::AcqRel);
+    }
+
+    /// Returns the number of distinct labels in the store.
+    #[must_use]
+    pub fn label_count(&self) -> usize {
+        self.id_to_label.read().len()
+    }
+
+    /// Returns the number of distinct property keys in the store.
+    ///
+    /// This counts unique property keys across both nodes and edges.
     #[must_use]
     pub fn property_key_count(&self) -> usize {
         let node_keys = self.node_properties.column_count();
@@ -835,7 +1789,10 @@ impl LpgStore {
             Direction::Outgoing => Box::new(std::iter::empty()),
         };
 
-        forward.chain(backward)
+        let undirected = self.undirected_edges_against(node, direction, None);
+        forward
+            .chain(backward)
+            .chain(undirected.into_iter().map(|(target, _)| target))
     }
 
     /// Returns edges from a node with their targets.
@@ -864,7 +1821,154 @@ impl LpgStore {
             Direction::Outgoing => Box::new(std::iter::empty()),
         };
 
-        forward.chain(backward)
+        let undirected = self.undirected_edges_against(node, direction, None);
+        forward.chain(backward).chain(undirected)
+    }
+
+    /// Returns the edges from a node that a transaction can see, with their
+    /// targets.
+    ///
+    /// Like [`edges_from`](Self::edges_from), but drops edges that were not
+    /// yet committed at `epoch` (other than `tx_id`'s own) or were deleted.
+    #[must_use]
+    pub fn edges_from_versioned(
+        &self,
+        node: NodeId,
+        direction: Direction,
+        epoch: EpochId,
+        tx_id: TxId,
+    ) -> Vec<(NodeId, EdgeId)> {
+        let mut found: Vec<(NodeId, EdgeId)> = match direction {
+            Direction::Outgoing => self.forward_adj.edges_from(node),
+            Direction::Incoming => self
+                .backward_adj
+                .as_ref()
+                .map_or_else(Vec::new, |adj| adj.edges_from(node)),
+            Direction::Both => {
+                let mut both = self.forward_adj.edges_from(node);
+                if let Some(ref adj) = self.backward_adj {
+                    both.extend(adj.edges_from(node));
+                }
+                both
+            }
+        };
+        {
+            let edges = self.edges.read();
+            found.retain(|(_, id)| {
+                edges
+                    .get(id)
+                    .and_then(|chain| {
+                        chain.visible_to_committed(epoch, tx_id, |writer| self.is_open(writer))
+                    })
+                    .is_some_and(|record| !record.is_deleted())
+            });
+        }
+        found.extend(self.undirected_edges_against(node, direction, Some((epoch, tx_id))));
+        found
+    }
+
+    /// Returns the number of edges at a node in the given direction.
+    ///
+    /// An undirected edge counts once, whichever direction is asked for.
+    #[must_use]
+    pub fn degree(&self, node: NodeId, direction: Direction) -> usize {
+        self.edges_from(node, direction).count()
+    }
+
+    /// Undirected edges at `node` that a one-way lookup in `direction` would
+    /// miss because they are indexed the other way round.
+    ///
+    /// `Both` already reads both adjacency lists, so it needs no extras, and
+    /// self-loops are left out since the matching list already has them.
+    /// With `at` set, only edges that transaction sees count; otherwise the
+    /// version committed at the current epoch does, as in
+    /// [`edge_type`](Self::edge_type). Without backward adjacency, edges
+    /// ending at `node` are found by scanning all edges.
+    fn undirected_edges_against(
+        &self,
+        node: NodeId,
+        direction: Direction,
+        at: Option<(EpochId, TxId)>,
+    ) -> Vec<(NodeId, EdgeId)> {
+        let undirected = self.undirected_edge_types.read();
+        if undirected.is_empty() || direction == Direction::Both {
+            return Vec::new();
+        }
+
+        let current = self.current_epoch();
+        let edges = self.edges.read();
+        let undirected_record = |chain: &VersionChain<EdgeRecord>| {
+            let record = match at {
+                Some((epoch, tx_id)) => {
+                    chain.visible_to_committed(epoch, tx_id, |writer| self.is_open(writer))
+                }
+                None => chain.visible_at(current),
+            }?;
+            (!record.is_deleted() && undirected.contains(&record.type_id))
+                .then_some((record.src, record.dst))
+        };
+
+        let adj = match direction {
+            Direction::Outgoing => self.backward_adj.as_ref(),
+            _ => Some(&self.forward_adj),
+        };
+        match adj {
+            Some(adj) => adj
+                .edges_from(node)
+                .into_iter()
+                .filter(|&(target, id)| {
+                    target != node && edges.get(&id).and_then(undirected_record).is_some()
+                })
+                .collect(),
+            None => edges
+                .iter()
+                .filter_map(|(&id, chain)| {
+                    let (src, dst) = undirected_record(chain)?;
+                    (dst == node && src != node).then_some((src, id))
+                })
+                .collect(),
+        }
+    }
+
+    /// Marks an edge type as undirected.
+    ///
+    /// Its edges are still stored once, from source to destination, but
+    /// traversals see them from both endpoints in every direction. Without
+    /// backward adjacency, reaching one from its destination means a scan
+    /// over all edges.
+    pub fn set_edge_type_undirected(&self, edge_type: &str) {
+        let type_id = self.get_or_create_edge_type_id(edge_type);
+        self.undirected_edge_types.write().insert(type_id);
+    }
+
+    /// Returns `true` if the edge type was marked undirected.
+    #[must_use]
+    pub fn is_edge_type_undirected(&self, edge_type: &str) -> bool {
+        self.edge_type_to_id
+            .read()
+            .get(edge_type)
+            .is_some_and(|id| self.undirected_edge_types.read().contains(id))
+    }
+
+    /// Returns the names of the edge types marked undirected.
+    #[must_use]
+    pub fn undirected_edge_types(&self) -> Vec<Arc<str>> {
+        let id_to_type = self.id_to_edge_type.read();
+        let mut names: Vec<Arc<str>> = self
+            .undirected_edge_types
+            .read()
+            .iter()
+            .filter_map(|&id| id_to_type.get(id as usize).cloned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Returns `true` if incoming edges are indexed, so that
+    /// [`Direction::Incoming`] lookups see them.
+    #[must_use]
+    pub fn has_backward_adjacency(&self) -> bool {
+        self.backward_adj.is_some()
     }
 
     /// Gets the type of an edge by ID.
@@ -896,6 +2000,99 @@ impl LpgStore {
         Vec::new()
     }
 
+    /// Returns the nodes that have a label for a transaction, in ID order.
+    ///
+    /// Like [`get_node_versioned`](Self::get_node_versioned), this leaves out
+    /// changes the transaction must not see: a node that gained the label
+    /// later is skipped, and one that lost it later is included.
+    #[must_use]
+    pub fn nodes_by_label_versioned(
+        &self,
+        label: &str,
+        epoch: EpochId,
+        tx_id: TxId,
+    ) -> Vec<NodeId> {
+        let Some(label_id) = self.label_to_id.read().get(label).copied() else {
+            return Vec::new();
+        };
+        let mut candidates: FxHashSet<NodeId> = self
+            .label_index
+            .read()
+            .get(label_id as usize)
+            .map(|set| set.keys().copied().collect())
+            .unwrap_or_default();
+        // Nodes the label was taken from since
+        candidates.extend(
+            self.node_history
+                .read()
+                .iter()
+                .filter(|(_, changes)| {
+                    changes.iter().any(|prior| {
+                        matches!(prior.change, PriorChange::Label { label_id: l, had: true } if l == label_id)
+                    })
+                })
+                .map(|(id, _)| *id),
+        );
+
+        let mut ids: Vec<NodeId> = candidates
+            .into_iter()
+            .filter(|id| {
+                self.node_label_ids_versioned(*id, epoch, tx_id)
+                    .is_some_and(|label_ids| label_ids.contains(&label_id))
+            })
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Returns the distinct `(source label, target label)` pairs that the
+    /// visible edges of `edge_type` connect, sorted by name.
+    ///
+    /// Only edges of that type are looked at, and their endpoints' label
+    /// sets are read in place rather than copied out per node.
+    #[must_use]
+    pub fn edge_type_endpoints(&self, edge_type: &str) -> Vec<(Arc<str>, Arc<str>)> {
+        let Some(type_id) = self.edge_type_to_id.read().get(edge_type).copied() else {
+            return Vec::new();
+        };
+        let epoch = self.current_epoch();
+        let mut pairs: FxHashSet<(u32, u32)> = FxHashSet::default();
+        {
+            let edges = self.edges.read();
+            let node_labels = self.node_labels.read();
+            for chain in edges.values() {
+                let Some(record) = chain.visible_at(epoch) else {
+                    continue;
+                };
+                if record.is_deleted() || record.type_id != type_id {
+                    continue;
+                }
+                if let (Some(sources), Some(targets)) =
+                    (node_labels.get(&record.src), node_labels.get(&record.dst))
+                {
+                    for &source in sources {
+                        for &target in targets {
+                            pairs.insert((source, target));
+                        }
+                    }
+                }
+            }
+        }
+
+        let id_to_label = self.id_to_label.read();
+        let mut named: Vec<(Arc<str>, Arc<str>)> = pairs
+            .into_iter()
+            .filter_map(|(source, target)| {
+                Some((
+                    Arc::clone(id_to_label.get(source as usize)?),
+                    Arc::clone(id_to_label.get(target as usize)?),
+                ))
+            })
+            .collect();
+        named.sort_unstable();
+        named
+    }
+
     // === Admin API: Iteration ===
 
     /// Returns an iterator over all nodes in the database.
@@ -1059,8 +2256,9 @@ impl LpgStore {
 
     /// Recomputes statistics from current data.
     ///
-    /// Scans all labels and edge types to build cardinality estimates for the
-    /// query optimizer. Call this periodically or after bulk data loads.
+    /// Scans all labels, edge types, and their properties to build cardinality
+    /// estimates and histograms for the query optimizer. Call this
+    /// periodically or after bulk data loads.
     pub fn compute_statistics(&self) {
         let mut stats = Statistics::new();
 
@@ -1068,11 +2266,41 @@ impl LpgStore {
         stats.total_nodes = self.node_count() as u64;
         stats.total_edges = self.edge_count() as u64;
 
-        // Compute per-label statistics
-        let id_to_label = self.id_to_label.read();
-        let label_index = self.label_index.read();
-
-        for (label_id, label_name) in id_to_label.iter().enumerate() {
+        // Stream every node property value through a collector, once for the
+        // whole graph and once per label of the node
+        let mut property_collectors: FxHashMap<PropertyKey, StatisticsCollector> =
+            FxHashMap::default();
+        let mut label_collectors: FxHashMap<(u32, PropertyKey), StatisticsCollector> =
+            FxHashMap::default();
+        for id in self.node_ids() {
+            let label_ids: Vec<u32> = self
+                .node_labels
+                .read()
+                .get(&id)
+                .map(|set| set.iter().copied().collect())
+                .unwrap_or_default();
+            for (key, value) in self.node_properties.get_all(id) {
+                for &label_id in &label_ids {
+                    label_collectors
+                        .entry((label_id, key.clone()))
+                        .or_default()
+                        .add(value.clone());
+                }
+                property_collectors.entry(key).or_default().add(value);
+            }
+        }
+        for (key, collector) in property_collectors {
+            stats.update_property(
+                key.as_str(),
+                collector.build(HISTOGRAM_BUCKETS, MOST_COMMON_VALUES),
+            );
+        }
+
+        // Compute per-label statistics
+        let id_to_label = self.id_to_label.read();
+        let label_index = self.label_index.read();
+
+        for (label_id, label_name) in id_to_label.iter().enumerate() {
             let node_count = label_index
                 .get(label_id)
                 .map(|set| set.len() as u64)
@@ -1086,8 +2314,17 @@ impl LpgStore {
                     0.0
                 };
 
-                let label_stats =
+                let mut label_stats =
                     LabelStatistics::new(node_count).with_degrees(avg_out_degree, avg_out_degree);
+                let label_id = label_id as u32;
+                for ((_, key), collector) in
+                    label_collectors.extract_if(|(id, _), _| *id == label_id)
+                {
+                    label_stats = label_stats.with_property(
+                        key.as_str(),
+                        collector.build(HISTOGRAM_BUCKETS, MOST_COMMON_VALUES),
+                    );
+                }
 
                 stats.update_label(label_name.as_ref(), label_stats);
             }
@@ -1099,10 +2336,18 @@ impl LpgStore {
         let epoch = self.current_epoch();
 
         let mut edge_type_counts: FxHashMap<u32, u64> = FxHashMap::default();
-        for chain in edges.values() {
+        let mut edge_type_collectors: FxHashMap<(u32, PropertyKey), StatisticsCollector> =
+            FxHashMap::default();
+        for (id, chain) in edges.iter() {
             if let Some(record) = chain.visible_at(epoch) {
                 if !record.is_deleted() {
                     *edge_type_counts.entry(record.type_id).or_default() += 1;
+                    for (key, value) in self.edge_properties.get_all(*id) {
+                        edge_type_collectors
+                            .entry((record.type_id, key))
+                            .or_default()
+                            .add(value);
+                    }
                 }
             }
         }
@@ -1115,7 +2360,15 @@ impl LpgStore {
                     0.0
                 };
 
-                let edge_stats = EdgeTypeStatistics::new(count, avg_degree, avg_degree);
+                let mut edge_stats = EdgeTypeStatistics::new(count, avg_degree, avg_degree);
+                for ((_, key), collector) in
+                    edge_type_collectors.extract_if(|(id, _), _| *id == type_id)
+                {
+                    edge_stats = edge_stats.with_property(
+                        key.as_str(),
+                        collector.build(HISTOGRAM_BUCKETS, MOST_COMMON_VALUES),
+                    );
+                }
                 stats.update_edge_type(type_name.as_ref(), edge_stats);
             }
         }
@@ -1137,6 +2390,176 @@ impl LpgStore {
             .estimate_avg_degree(edge_type, outgoing)
     }
 
+    /// Applies an incremental change to the statistics if enabled, and
+    /// otherwise just flags them stale.
+    fn update_statistics(&self, update: impl FnOnce(&mut Statistics)) {
+        if self.config.incremental_statistics {
+            update(&mut self.statistics.write());
+        } else {
+            self.mark_statistics_stale();
+        }
+    }
+
+    /// Flags the statistics stale, taking the write lock only the first time.
+    fn mark_statistics_stale(&self) {
+        if !self.statistics.read().is_stale() {
+            self.statistics.write().mark_stale();
+        }
+    }
+
+    /// Counts (or discounts) every property value currently on a node.
+    fn count_node_values(&self, id: NodeId, added: bool) {
+        if !self.config.incremental_statistics {
+            self.mark_statistics_stale();
+            return;
+        }
+        let values = self.node_properties.get_all(id);
+        let mut stats = self.statistics.write();
+        for (key, value) in &values {
+            if added {
+                stats.record_value_added(key.as_str(), value);
+            } else {
+                stats.record_value_removed(key.as_str(), value);
+            }
+        }
+    }
+
+    /// Discounts a node that is going away, along with its labels and values.
+    fn count_node_removed(&self, id: NodeId, label_ids: &NodeLabels) {
+        if !self.config.incremental_statistics {
+            self.mark_statistics_stale();
+            return;
+        }
+        let labels: Vec<Arc<str>> = {
+            let id_to_label = self.id_to_label.read();
+            label_ids
+                .iter()
+                .filter_map(|label_id| id_to_label.get(*label_id as usize).cloned())
+                .collect()
+        };
+        let labels: Vec<&str> = labels.iter().map(AsRef::as_ref).collect();
+        self.update_statistics(|stats| stats.record_node_removed(&labels));
+        self.count_node_values(id, false);
+    }
+
+    fn count_edge_added(&self, type_id: u32) {
+        match self.counted_edge_type(type_id) {
+            Some(edge_type) => self.update_statistics(|stats| stats.record_edge_added(&edge_type)),
+            None => self.mark_statistics_stale(),
+        }
+    }
+
+    fn count_edge_removed(&self, type_id: u32) {
+        match self.counted_edge_type(type_id) {
+            Some(edge_type) => {
+                self.update_statistics(|stats| stats.record_edge_removed(&edge_type));
+            }
+            None => self.mark_statistics_stale(),
+        }
+    }
+
+    /// Resolves an edge type name, but only when statistics need it.
+    fn counted_edge_type(&self, type_id: u32) -> Option<Arc<str>> {
+        if !self.config.incremental_statistics {
+            return None;
+        }
+        self.id_to_edge_type.read().get(type_id as usize).cloned()
+    }
+
+    // === Integrity ===
+
+    /// Cross-checks edges against the nodes they connect, the adjacency
+    /// lists against the edges, and the label index against node labels,
+    /// as of the current epoch.
+    ///
+    /// Writes of open transactions are already in the adjacency lists and
+    /// label index but not yet visible, so check a store nobody is writing
+    /// to.
+    #[must_use]
+    pub fn check_integrity(&self) -> IntegrityReport {
+        let epoch = self.current_epoch();
+        let mut report = IntegrityReport::default();
+
+        let live_nodes: FxHashSet<NodeId> = self.node_ids().into_iter().collect();
+        let live_edges: FxHashMap<EdgeId, (NodeId, NodeId)> = self
+            .edges
+            .read()
+            .iter()
+            .filter_map(|(id, chain)| {
+                chain
+                    .visible_at(epoch)
+                    .filter(|record| !record.is_deleted())
+                    .map(|record| (*id, (record.src, record.dst)))
+            })
+            .collect();
+
+        for (&id, &(src, dst)) in &live_edges {
+            if !live_nodes.contains(&src) {
+                report.dangling_sources.push((id, src));
+            }
+            if !live_nodes.contains(&dst) {
+                report.dangling_targets.push((id, dst));
+            }
+        }
+
+        // Each edge is listed forward from its source and, when backward
+        // adjacency is kept, backward from its destination
+        let mut forward_listed = FxHashSet::default();
+        for (src, dst, id) in self.forward_adj.all_edges() {
+            if live_edges.get(&id) == Some(&(src, dst)) {
+                forward_listed.insert(id);
+            } else {
+                report.stale_adjacency.push((src, id));
+            }
+        }
+        let backward_listed = self.backward_adj.as_ref().map(|backward| {
+            let mut listed = FxHashSet::default();
+            for (dst, src, id) in backward.all_edges() {
+                if live_edges.get(&id) == Some(&(src, dst)) {
+                    listed.insert(id);
+                } else {
+                    report.stale_adjacency.push((dst, id));
+                }
+            }
+            listed
+        });
+        for id in live_edges.keys() {
+            let backward_missing = backward_listed
+                .as_ref()
+                .is_some_and(|listed| !listed.contains(id));
+            if !forward_listed.contains(id) || backward_missing {
+                report.unindexed_edges.push(*id);
+            }
+        }
+
+        // Same lock order as node deletion: label index, then node labels
+        let id_to_label = self.id_to_label.read().clone();
+        let label_index = self.label_index.read();
+        let node_labels = self.node_labels.read();
+        for (label_id, nodes) in label_index.iter().enumerate() {
+            for node in nodes.keys() {
+                let labelled = live_nodes.contains(node)
+                    && node_labels
+                        .get(node)
+                        .is_some_and(|labels| labels.contains(&(label_id as u32)));
+                if !labelled {
+                    if let Some(label) = id_to_label.get(label_id) {
+                        report
+                            .orphaned_label_entries
+                            .push((Arc::clone(label), *node));
+                    }
+                }
+            }
+        }
+
+        report.dangling_sources.sort_unstable();
+        report.dangling_targets.sort_unstable();
+        report.orphaned_label_entries.sort_unstable();
+        report.unindexed_edges.sort_unstable();
+        report.stale_adjacency.sort_unstable();
+        report
+    }
+
     // === Internal Helpers ===
 
     fn get_or_create_label_id(&self, label: &str) -> u32 {
@@ -1188,6 +2611,459 @@ impl LpgStore {
         id
     }
 
+    // === Property Indexes ===
+
+    /// Indexes `label` nodes by their value of `property`.
+    ///
+    /// The nodes that have the property now are indexed right away, and
+    /// every later write keeps the index current, whichever path it takes.
+    /// Returns false if the index already exists.
+    pub fn create_property_index(&self, label: &str, property: &str) -> bool {
+        let label_id = self.get_or_create_label_id(label);
+        let key = PropertyKey::from(property);
+        let index = Arc::new(PropertyIndex::new());
+        {
+            let mut indexes = self.property_indexes.write();
+            if indexes.contains_key(&(label_id, key.clone())) {
+                return false;
+            }
+            // Registered before the backfill so no concurrent write is missed
+            indexes.insert((label_id, key.clone()), Arc::clone(&index));
+        }
+
+        for id in self.nodes_by_label(label) {
+            if let Some(value) = self.node_properties.get(id, &key) {
+                if !value.is_null() {
+                    index.insert(index_key(&value), id);
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns true if `label` nodes are indexed by `property`.
+    #[must_use]
+    pub fn has_property_index(&self, label: &str, property: &str) -> bool {
+        let Some(label_id) = self.label_to_id.read().get(label).copied() else {
+            return false;
+        };
+        self.property_indexes
+            .read()
+            .contains_key(&(label_id, PropertyKey::from(property)))
+    }
+
+    /// Drops the index on `label`'s `property`, returning true if it existed.
+    pub fn drop_property_index(&self, label: &str, property: &str) -> bool {
+        let Some(label_id) = self.label_to_id.read().get(label).copied() else {
+            return false;
+        };
+        self.property_indexes
+            .write()
+            .remove(&(label_id, PropertyKey::from(property)))
+            .is_some()
+    }
+
+    /// Finds the `label` nodes whose `property` equals `value` through the
+    /// index on that pair, or `None` if there is no such index.
+    ///
+    /// Every candidate is checked against the store, so the result is what
+    /// a scan of the label would find: with an epoch, as seen by `tx_id` at
+    /// that epoch, and otherwise in the latest state. Nulls never match.
+    #[must_use]
+    pub fn find_nodes_by_property_index(
+        &self,
+        label: &str,
+        property: &str,
+        value: &Value,
+        epoch: Option<EpochId>,
+        tx_id: Option<TxId>,
+    ) -> Option<Vec<NodeId>> {
+        let label_id = self.label_to_id.read().get(label).copied()?;
+        let index = self
+            .property_indexes
+            .read()
+            .get(&(label_id, PropertyKey::from(property)))
+            .map(Arc::clone)?;
+        if value.is_null() {
+            return Some(Vec::new());
+        }
+
+        let wanted = index_key(value);
+        let mut nodes: Vec<NodeId> = index
+            .get(&wanted)
+            .into_iter()
+            .filter(|&id| {
+                let node = match epoch {
+                    Some(epoch) => {
+                        self.get_node_versioned(id, epoch, tx_id.unwrap_or(TxId::SYSTEM))
+                    }
+                    None => self.get_node(id),
+                };
+                node.is_some_and(|node| {
+                    node.has_label(label)
+                        && node
+                            .get_property(property)
+                            .is_some_and(|v| index_key(v) == wanted)
+                })
+            })
+            .collect();
+        nodes.sort_unstable();
+        Some(nodes)
+    }
+
+    /// Indexes `label` nodes by the tuple of their `properties` values.
+    ///
+    /// Lookups bind a leading run of the properties, so the index serves a
+    /// predicate on the first alone as well as on all of them. Nodes without
+    /// a value for the first property are left out, and a missing later
+    /// value is indexed as null. As with
+    /// [`create_property_index`](Self::create_property_index), existing
+    /// nodes are indexed right away and later writes keep the index current.
+    /// Returns false if the index already exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `properties` is empty.
+    pub fn create_composite_index(&self, label: &str, properties: &[&str]) -> bool {
+        assert!(
+            !properties.is_empty(),
+            "an index needs at least one property"
+        );
+        let label_id = self.get_or_create_label_id(label);
+        let keys: Vec<PropertyKey> = properties.iter().map(|&p| PropertyKey::from(p)).collect();
+        let index = Arc::new(CompositeIndex::new());
+        {
+            let mut indexes = self.composite_indexes.write();
+            if indexes.contains_key(&(label_id, keys.clone())) {
+                return false;
+            }
+            // Registered before the backfill so no concurrent write is missed
+            indexes.insert((label_id, keys.clone()), Arc::clone(&index));
+        }
+
+        for id in self.nodes_by_label(label) {
+            self.index_node_tuple(&index, id, &keys, None);
+        }
+        true
+    }
+
+    /// Returns true if `label` nodes are indexed by exactly `properties`, in
+    /// this order.
+    #[must_use]
+    pub fn has_composite_index(&self, label: &str, properties: &[&str]) -> bool {
+        let Some(label_id) = self.label_to_id.read().get(label).copied() else {
+            return false;
+        };
+        let keys: Vec<PropertyKey> = properties.iter().map(|&p| PropertyKey::from(p)).collect();
+        self.composite_indexes
+            .read()
+            .contains_key(&(label_id, keys))
+    }
+
+    /// Finds the `label` nodes whose leading `properties` equal `values`
+    /// through the composite index on `properties`, or `None` if there is no
+    /// such index.
+    ///
+    /// `values` binds the first `values.len()` properties. As with
+    /// [`find_nodes_by_property_index`](Self::find_nodes_by_property_index),
+    /// every candidate is checked against the store at the given epoch, and
+    /// a null never matches.
+    #[must_use]
+    pub fn find_nodes_by_composite_index(
+        &self,
+        label: &str,
+        properties: &[&str],
+        values: &[Value],
+        epoch: Option<EpochId>,
+        tx_id: Option<TxId>,
+    ) -> Option<Vec<NodeId>> {
+        let label_id = self.label_to_id.read().get(label).copied()?;
+        let keys: Vec<PropertyKey> = properties.iter().map(|&p| PropertyKey::from(p)).collect();
+        let index = self
+            .composite_indexes
+            .read()
+            .get(&(label_id, keys))
+            .map(Arc::clone)?;
+        if values.is_empty() || values.len() > properties.len() || values.iter().any(Value::is_null)
+        {
+            return Some(Vec::new());
+        }
+
+        let wanted: Vec<HashKey> = values.iter().map(index_key).collect();
+        let start = (wanted.clone(), NodeId::new(0));
+        let mut nodes: Vec<NodeId> = index
+            .range_while(start.., |(tuple, _)| tuple.starts_with(&wanted))
+            .into_iter()
+            .map(|((_, id), ())| id)
+            .filter(|&id| {
+                let node = match epoch {
+                    Some(epoch) => {
+                        self.get_node_versioned(id, epoch, tx_id.unwrap_or(TxId::SYSTEM))
+                    }
+                    None => self.get_node(id),
+                };
+                node.is_some_and(|node| {
+                    node.has_label(label)
+                        && properties.iter().zip(&wanted).all(|(property, key)| {
+                            node.get_property(property)
+                                .is_some_and(|v| index_key(v) == *key)
+                        })
+                })
+            })
+            .collect();
+        nodes.sort_unstable();
+        nodes.dedup();
+        Some(nodes)
+    }
+
+    /// Indexes the string values of `label` nodes' `property` by prefix.
+    ///
+    /// A case-insensitive index folds both values and lookup prefixes to
+    /// lowercase. As with [`create_property_index`](Self::create_property_index),
+    /// existing nodes are indexed right away and later writes keep the index
+    /// current. Returns false if the pair already has a text index.
+    pub fn create_text_index(&self, label: &str, property: &str, case_sensitive: bool) -> bool {
+        let label_id = self.get_or_create_label_id(label);
+        let key = PropertyKey::from(property);
+        let index = Arc::new(RwLock::new(TextTrieIndex::new(case_sensitive)));
+        {
+            let mut indexes = self.text_indexes.write();
+            if indexes.contains_key(&(label_id, key.clone())) {
+                return false;
+            }
+            // Registered before the backfill so no concurrent write is missed
+            indexes.insert((label_id, key.clone()), Arc::clone(&index));
+        }
+
+        for id in self.nodes_by_label(label) {
+            if let Some(Value::String(text)) = self.node_properties.get(id, &key) {
+                index.write().insert(&text, id);
+            }
+        }
+        true
+    }
+
+    /// Returns true if `label` nodes' `property` has a text index.
+    #[must_use]
+    pub fn has_text_index(&self, label: &str, property: &str) -> bool {
+        let Some(label_id) = self.label_to_id.read().get(label).copied() else {
+            return false;
+        };
+        self.text_indexes
+            .read()
+            .contains_key(&(label_id, PropertyKey::from(property)))
+    }
+
+    /// Finds the `label` nodes whose `property` starts with `prefix` through
+    /// the text index on that pair, or `None` if there is no such index.
+    ///
+    /// Case is ignored if the index ignores it. As with
+    /// [`find_nodes_by_property_index`](Self::find_nodes_by_property_index),
+    /// every candidate is checked against the store at the given epoch. The
+    /// empty prefix matches every string value.
+    #[must_use]
+    pub fn find_nodes_by_text_prefix(
+        &self,
+        label: &str,
+        property: &str,
+        prefix: &str,
+        epoch: Option<EpochId>,
+        tx_id: Option<TxId>,
+    ) -> Option<Vec<NodeId>> {
+        let label_id = self.label_to_id.read().get(label).copied()?;
+        let index = self
+            .text_indexes
+            .read()
+            .get(&(label_id, PropertyKey::from(property)))
+            .map(Arc::clone)?;
+
+        let index = index.read();
+        let fold = |text: &str| {
+            if index.is_case_sensitive() {
+                text.to_string()
+            } else {
+                text.to_lowercase()
+            }
+        };
+        let wanted = fold(prefix);
+        let nodes = index
+            .starts_with(prefix)
+            .into_iter()
+            .filter(|&id| {
+                let node = match epoch {
+                    Some(epoch) => {
+                        self.get_node_versioned(id, epoch, tx_id.unwrap_or(TxId::SYSTEM))
+                    }
+                    None => self.get_node(id),
+                };
+                node.is_some_and(|node| {
+                    node.has_label(label)
+                        && matches!(
+                            node.get_property(property),
+                            Some(Value::String(text)) if fold(text).starts_with(&wanted)
+                        )
+                })
+            })
+            .collect();
+        Some(nodes)
+    }
+
+    /// Adds `id` under `value` to the indexes on `key` of its labels.
+    fn index_node_value(&self, id: NodeId, key: &PropertyKey, value: &Value) {
+        let indexes = self.property_indexes.read();
+        let composites = self.composite_indexes.read();
+        let texts = self.text_indexes.read();
+        if (indexes.is_empty() && composites.is_empty() && texts.is_empty()) || value.is_null() {
+            return;
+        }
+        let Some(label_ids) = self.node_labels.read().get(&id).cloned() else {
+            return;
+        };
+        for label_id in label_ids {
+            if let Some(index) = indexes.get(&(label_id, key.clone())) {
+                index.insert(index_key(value), id);
+            }
+            for ((indexed_label, keys), index) in composites.iter() {
+                if *indexed_label == label_id && keys.contains(key) {
+                    self.index_node_tuple(index, id, keys, Some((key, value)));
+                }
+            }
+            if let (Some(index), Value::String(text)) = (texts.get(&(label_id, key.clone())), value)
+            {
+                index.write().insert(text, id);
+            }
+        }
+    }
+
+    /// Adds `id` to the indexes on `label_id` for the properties it has.
+    fn index_node_label(&self, id: NodeId, label_id: u32) {
+        let indexes = self.property_indexes.read();
+        for ((indexed_label, key), index) in indexes.iter() {
+            if *indexed_label != label_id {
+                continue;
+            }
+            if let Some(value) = self.node_properties.get(id, key) {
+                if !value.is_null() {
+                    index.insert(index_key(&value), id);
+                }
+            }
+        }
+        for ((indexed_label, keys), index) in self.composite_indexes.read().iter() {
+            if *indexed_label == label_id {
+                self.index_node_tuple(index, id, keys, None);
+            }
+        }
+        for ((indexed_label, key), index) in self.text_indexes.read().iter() {
+            if *indexed_label != label_id {
+                continue;
+            }
+            if let Some(Value::String(text)) = self.node_properties.get(id, key) {
+                index.write().insert(&text, id);
+            }
+        }
+    }
+
+    /// Adds `id` to a composite index under its values of `keys`, taking
+    /// `pending` in place of the stored value of its key.
+    fn index_node_tuple(
+        &self,
+        index: &CompositeIndex,
+        id: NodeId,
+        keys: &[PropertyKey],
+        pending: Option<(&PropertyKey, &Value)>,
+    ) {
+        let tuple: Vec<HashKey> = keys
+            .iter()
+            .map(|key| match pending {
+                Some((pending_key, value)) if pending_key == key => value.clone(),
+                _ => self.node_properties.get(id, key).unwrap_or(Value::Null),
+            })
+            .map(|value| index_key(&value))
+            .collect();
+        if tuple.first().is_some_and(|first| *first != HashKey::Null) {
+            index.insert((tuple, id), ());
+        }
+    }
+
+    // === Snapshots ===
+
+    /// Copies the committed state of the store into a new, private store.
+    ///
+    /// `open` lists the transactions that haven't finished yet. Their writes
+    /// are rolled back in the copy, so it holds what a reader starting now
+    /// would see. Node and edge IDs are preserved, and later writes to this
+    /// store never reach the copy, which makes it a stable view for
+    /// long-running analytics. Callers that need an exact cut should keep
+    /// writers out while the copy is taken.
+    #[must_use]
+    pub fn snapshot(&self, open: &[TxId]) -> LpgStore {
+        let epoch = self.current_epoch();
+        let copy = LpgStore::with_config(LpgStoreConfig {
+            incremental_statistics: false,
+            ..self.config.clone()
+        });
+        copy.set_epoch(epoch);
+        copy.next_node_id
+            .store(self.next_node_id.load(Ordering::SeqCst), Ordering::SeqCst);
+        copy.next_edge_id
+            .store(self.next_edge_id.load(Ordering::SeqCst), Ordering::SeqCst);
+
+        // Records refer to labels and edge types by ID, so the tables are
+        // copied as they are
+        *copy.label_to_id.write() = self.label_to_id.read().clone();
+        *copy.id_to_label.write() = self.id_to_label.read().clone();
+        *copy.edge_type_to_id.write() = self.edge_type_to_id.read().clone();
+        *copy.id_to_edge_type.write() = self.id_to_edge_type.read().clone();
+        *copy.undirected_edge_types.write() = self.undirected_edge_types.read().clone();
+        *copy.label_index.write() = self.label_index.read().clone();
+        *copy.node_labels.write() = self.node_labels.read().clone();
+
+        let nodes = self.nodes.read().clone();
+        for &id in nodes.keys() {
+            for (key, value) in self.node_properties.get_all(id) {
+                copy.node_properties.set(id, key, value);
+            }
+        }
+        *copy.nodes.write() = nodes;
+
+        let edges = self.edges.read().clone();
+        for (&id, chain) in &edges {
+            let Some(record) = chain.latest() else {
+                continue;
+            };
+            copy.forward_adj.add_edge(record.src, record.dst, id);
+            if let Some(ref backward) = copy.backward_adj {
+                backward.add_edge(record.dst, record.src, id);
+            }
+            if chain.visible_at(epoch).is_none_or(EdgeRecord::is_deleted) {
+                copy.forward_adj.mark_deleted(record.src, id);
+                if let Some(ref backward) = copy.backward_adj {
+                    backward.mark_deleted(record.dst, id);
+                }
+            }
+            for (key, value) in self.edge_properties.get_all(id) {
+                copy.edge_properties.set(id, key, value);
+            }
+        }
+        *copy.edges.write() = edges;
+
+        // Roll back open transactions the same way an abort would
+        {
+            let undo_log = self.undo_log.read();
+            let mut copy_log = copy.undo_log.write();
+            for tx_id in open {
+                if let Some(entries) = undo_log.get(tx_id) {
+                    copy_log.insert(*tx_id, entries.clone());
+                }
+            }
+        }
+        for &tx_id in open {
+            copy.discard_uncommitted_versions(tx_id);
+        }
+
+        copy
+    }
+
     // === Recovery Support ===
 
     /// Creates a node with a specific ID during recovery.
@@ -1200,7 +3076,7 @@ impl LpgStore {
         record.set_label_count(labels.len() as u16);
 
         // Store labels in node_labels map and label_index
-        let mut node_label_set = FxHashSet::default();
+        let mut node_label_set = NodeLabels::default();
         for label in labels {
             let label_id = self.get_or_create_label_id(*label);
             node_label_set.insert(label_id);
@@ -1219,6 +3095,7 @@ impl LpgStore {
         // Create version chain with initial version (using SYSTEM tx for recovery)
         let chain = VersionChain::with_initial(record, epoch, TxId::SYSTEM);
         self.nodes.write().insert(id, chain);
+        self.update_statistics(|stats| stats.record_node_added(labels));
 
         // Update next_node_id if necessary to avoid future collisions
         let id_val = id.as_u64();
@@ -1249,6 +3126,7 @@ impl LpgStore {
         if let Some(ref backward) = self.backward_adj {
             backward.add_edge(dst, src, id);
         }
+        self.update_statistics(|stats| stats.record_edge_added(edge_type));
 
         // Update next_edge_id if necessary
         let id_val = id.as_u64();
@@ -1326,6 +3204,275 @@ mod tests {
         assert!(!store.delete_node(id));
     }
 
+    #[test]
+    fn test_discard_reverts_transactional_changes() {
+        let store = LpgStore::new();
+        let tx = TxId::new(2);
+        let epoch = store.current_epoch();
+
+        let alice = store.create_node_with_props(&["Person"], [("age", Value::Int64(30))]);
+        let bob = store.create_node(&["Person"]);
+        let edge = store.create_edge(alice, bob, "KNOWS");
+        store.set_edge_property(edge, "since", Value::Int64(2020));
+
+        store.set_node_property_versioned(alice, "age", Value::Int64(31), tx);
+        store.add_label_versioned(alice, "Employee", tx);
+        store.delete_node_edges_versioned(bob, epoch, tx);
+        assert!(store.delete_node_versioned(bob, epoch, tx));
+        let carol = store.create_node_versioned(&["Person"], epoch, tx);
+        assert_eq!(store.node_count(), 2);
+
+        store.discard_uncommitted_versions(tx);
+
+        assert_eq!(store.node_count(), 2);
+        assert!(store.get_node(carol).is_none());
+        assert_eq!(store.nodes_by_label("Person"), vec![alice, bob]);
+        assert!(store.nodes_by_label("Employee").is_empty());
+        assert_eq!(
+            store.get_node(alice).unwrap().get_property("age"),
+            Some(&Value::Int64(30))
+        );
+        assert_eq!(
+            store.get_edge(edge).unwrap().get_property("since"),
+            Some(&Value::Int64(2020))
+        );
+        assert_eq!(store.neighbors(alice, Direction::Outgoing).count(), 1);
+    }
+
+    #[test]
+    fn test_rollback_to_savepoint_keeps_earlier_changes() {
+        let store = LpgStore::new();
+        let tx = TxId::new(2);
+        let epoch = store.current_epoch();
+
+        let alice = store.create_node_with_props(&["Person"], [("age", Value::Int64(30))]);
+        store.set_node_property_versioned(alice, "age", Value::Int64(31), tx);
+        let bob = store.create_node_versioned(&["Person"], epoch, tx);
+
+        let savepoint = store.savepoint(tx);
+        store.set_node_property_versioned(alice, "age", Value::Int64(32), tx);
+        store.add_label_versioned(bob, "Employee", tx);
+        let carol = store.create_node_versioned(&["Person"], epoch, tx);
+        let edge = store.create_edge_versioned(bob, carol, "KNOWS", epoch, tx);
+
+        store.rollback_to_savepoint(tx, savepoint);
+
+        assert!(store.get_node(carol).is_none());
+        assert!(store.get_edge(edge).is_none());
+        assert_eq!(store.neighbors(bob, Direction::Outgoing).count(), 0);
+        assert_eq!(store.nodes_by_label("Person"), vec![alice, bob]);
+        assert!(store.nodes_by_label("Employee").is_empty());
+        assert_eq!(
+            store.get_node(alice).unwrap().get_property("age"),
+            Some(&Value::Int64(31))
+        );
+
+        // The changes before the savepoint are still the transaction's own
+        store.discard_uncommitted_versions(tx);
+        assert!(store.get_node(bob).is_none());
+        assert_eq!(
+            store.get_node(alice).unwrap().get_property("age"),
+            Some(&Value::Int64(30))
+        );
+    }
+
+    #[test]
+    fn test_snapshot_excludes_open_and_later_writes() {
+        let store = LpgStore::new();
+        let tx = TxId::new(2);
+        let epoch = store.current_epoch();
+
+        let alice = store.create_node_with_props(&["Person"], [("age", Value::Int64(30))]);
+        let bob = store.create_node(&["Person"]);
+        let edge = store.create_edge(alice, bob, "KNOWS");
+
+        // Uncommitted changes from an open transaction
+        store.set_node_property_versioned(alice, "age", Value::Int64(31), tx);
+        store.delete_node_edges_versioned(bob, epoch, tx);
+        let carol = store.create_node_versioned(&["Person"], epoch, tx);
+
+        let snapshot = store.snapshot(&[tx]);
+
+        // Writes after the snapshot don't reach it
+        let dave = store.create_node(&["Person"]);
+        store.create_edge(alice, dave, "KNOWS");
+        store.set_node_property(alice, "age", Value::Int64(40));
+
+        assert_eq!(snapshot.node_ids(), vec![alice, bob]);
+        assert!(snapshot.get_node(carol).is_none());
+        assert_eq!(
+            snapshot.get_node(alice).unwrap().get_property("age"),
+            Some(&Value::Int64(30))
+        );
+        assert_eq!(snapshot.get_edge(edge).unwrap().dst, bob);
+        assert_eq!(
+            snapshot
+                .neighbors(alice, Direction::Outgoing)
+                .collect::<Vec<_>>(),
+            vec![bob]
+        );
+        assert_eq!(snapshot.neighbors(bob, Direction::Incoming).count(), 1);
+
+        // The source store is untouched by taking the snapshot
+        assert!(store.get_node(carol).is_some());
+        assert_eq!(store.neighbors(alice, Direction::Outgoing).count(), 1);
+    }
+
+    #[test]
+    fn test_versioned_reads_leave_out_later_and_open_changes() {
+        let store = LpgStore::new();
+        let alice = store.create_node_with_props(&["Person"], [("age", Value::Int64(30))]);
+        let before = store.current_epoch();
+        store.set_oldest_reader(Some(before));
+        store.new_epoch();
+
+        // An open transaction's changes are only seen by the transaction
+        let tx = TxId::new(2);
+        store.set_node_property_versioned(alice, "age", Value::Int64(31), tx);
+        store.add_label_versioned(alice, "Employee", tx);
+        let bob = store.create_node_versioned(&["Person"], before, tx);
+        let now = store.current_epoch();
+        let other = TxId::new(3);
+        let alice_for = |tx| store.get_node_versioned(alice, now, tx).unwrap();
+        assert_eq!(
+            alice_for(other).get_property("age"),
+            Some(&Value::Int64(30))
+        );
+        assert_eq!(alice_for(tx).get_property("age"), Some(&Value::Int64(31)));
+        assert!(!alice_for(other).has_label("Employee"));
+        assert!(store.get_node_versioned(bob, now, other).is_none());
+        assert_eq!(
+            store.nodes_by_label_versioned("Employee", now, other),
+            vec![]
+        );
+        assert_eq!(
+            store.nodes_by_label_versioned("Employee", now, tx),
+            vec![alice]
+        );
+
+        // Committed later, still hidden from a reader at the earlier epoch
+        let commit = store.new_epoch();
+        store.commit_versions(tx, commit);
+        store.remove_label(alice, "Person");
+        let reader = TxId::new(4);
+        let old = store.get_node_versioned(alice, before, reader).unwrap();
+        assert_eq!(old.get_property("age"), Some(&Value::Int64(30)));
+        assert!(old.has_label("Person") && !old.has_label("Employee"));
+        assert!(store.get_node_versioned(bob, before, reader).is_none());
+        assert_eq!(
+            store.nodes_by_label_versioned("Person", before, reader),
+            vec![alice]
+        );
+        let new = store.get_node_versioned(alice, commit, reader).unwrap();
+        assert_eq!(new.get_property("age"), Some(&Value::Int64(31)));
+        assert!(store.get_node_versioned(bob, commit, reader).is_some());
+
+        // Once no reader needs it, the history is dropped
+        store.set_oldest_reader(None);
+        assert!(store.node_history.read().is_empty());
+    }
+
+    #[test]
+    fn test_writes_mark_statistics_stale() {
+        let store = LpgStore::new();
+        let alice = store.create_node(&["Person"]);
+        let bob = store.create_node(&["Person"]);
+        let edge = store.create_edge(alice, bob, "KNOWS");
+        store.compute_statistics();
+        assert!(!store.statistics().is_stale());
+
+        store.set_edge_property(edge, "since", Value::Int64(2020));
+        assert!(store.statistics().is_stale());
+        store.compute_statistics();
+        let stats = store.statistics();
+        let since = &stats.get_edge_type("KNOWS").unwrap().properties["since"];
+        assert_eq!(since.data_type, grafeo_common::types::LogicalType::Int64);
+
+        store.add_label(bob, "Admin");
+        assert!(store.statistics().is_stale());
+    }
+
+    #[test]
+    fn test_edge_type_endpoints() {
+        let store = LpgStore::new();
+        let alice = store.create_node(&["Person"]);
+        let bob = store.create_node(&["Person", "Admin"]);
+        let acme = store.create_node(&["Company"]);
+        store.create_edge(alice, bob, "KNOWS");
+        store.create_edge(alice, acme, "WORKS_AT");
+        let gone = store.create_edge(acme, alice, "KNOWS");
+        store.delete_edge(gone);
+
+        let pairs = |edge_type: &str| -> Vec<(String, String)> {
+            store
+                .edge_type_endpoints(edge_type)
+                .into_iter()
+                .map(|(source, target)| (source.to_string(), target.to_string()))
+                .collect()
+        };
+        assert_eq!(
+            pairs("KNOWS"),
+            [
+                ("Person".to_string(), "Admin".to_string()),
+                ("Person".to_string(), "Person".to_string())
+            ]
+        );
+        assert_eq!(
+            pairs("WORKS_AT"),
+            [("Person".to_string(), "Company".to_string())]
+        );
+        assert!(pairs("MISSING").is_empty());
+    }
+
+    #[test]
+    fn test_incremental_statistics() {
+        let store = LpgStore::with_config(LpgStoreConfig {
+            incremental_statistics: true,
+            ..Default::default()
+        });
+        let label_count = |label: &str| {
+            store
+                .statistics()
+                .get_label(label)
+                .map_or(0, |stats| stats.node_count)
+        };
+
+        let alice = store.create_node_with_props(&["Person"], [("age", Value::Int64(30))]);
+        let bob = store.create_node(&["Person"]);
+        let carol = store.create_node(&["Person", "Admin"]);
+        let edge = store.create_edge(alice, bob, "KNOWS");
+        assert_eq!(label_count("Person"), 3);
+        assert_eq!(label_count("Admin"), 1);
+        assert_eq!(store.statistics().total_edges, 1);
+
+        store.delete_edge(edge);
+        store.delete_node(bob);
+        store.remove_label(carol, "Admin");
+        store.set_node_property(carol, "age", Value::Null);
+
+        let stats = store.statistics();
+        assert!(stats.is_stale());
+        assert_eq!(stats.total_nodes, 2);
+        assert_eq!(label_count("Person"), 2);
+        assert_eq!(label_count("Admin"), 0);
+        assert_eq!(stats.get_edge_type("KNOWS").unwrap().edge_count, 0);
+        let age = stats.get_property("age").unwrap();
+        assert_eq!((age.total_count, age.null_count), (2, 1));
+
+        // Rolled back changes are discounted again
+        let tx = TxId::new(2);
+        store.create_node_versioned(&["Person"], store.current_epoch(), tx);
+        assert!(store.delete_node_versioned(alice, store.current_epoch(), tx));
+        store.discard_uncommitted_versions(tx);
+        assert_eq!(label_count("Person"), 2);
+        assert_eq!(store.statistics().total_nodes, 2);
+
+        // A full recompute agrees and refreshes everything
+        store.compute_statistics();
+        assert!(!store.statistics().is_stale());
+        assert_eq!(label_count("Person"), 2);
+    }
+
     #[test]
     fn test_create_edge() {
         let store = LpgStore::new();
@@ -1363,6 +3510,109 @@ mod tests {
         assert!(incoming.contains(&a));
     }
 
+    #[test]
+    fn test_undirected_edge_type() {
+        let store = LpgStore::new();
+        store.set_edge_type_undirected("FRIEND");
+        assert!(store.is_edge_type_undirected("FRIEND"));
+        assert!(!store.is_edge_type_undirected("KNOWS"));
+
+        let a = store.create_node(&["Person"]);
+        let b = store.create_node(&["Person"]);
+        let friend = store.create_edge(a, b, "FRIEND");
+        store.create_edge(a, b, "KNOWS");
+
+        // Stored once, reachable from both ends in every direction
+        assert_eq!(store.edge_count(), 2);
+        for (node, other) in [(a, b), (b, a)] {
+            for direction in [Direction::Outgoing, Direction::Incoming, Direction::Both] {
+                let friends: Vec<_> = store
+                    .edges_from(node, direction)
+                    .filter(|&(_, id)| id == friend)
+                    .collect();
+                assert_eq!(friends, vec![(other, friend)], "{direction:?}");
+            }
+        }
+        let neighbors: Vec<_> = store.neighbors(b, Direction::Outgoing).collect();
+        assert_eq!(neighbors, vec![a]);
+    }
+
+    #[test]
+    fn test_undirected_edges_respect_visibility() {
+        let store = LpgStore::new();
+        store.set_edge_type_undirected("FRIEND");
+        let a = store.create_node(&["Person"]);
+        let b = store.create_node(&["Person"]);
+        let committed = store.create_edge(a, b, "FRIEND");
+
+        // An open transaction adds and removes FRIEND edges
+        let writer = TxId::new(5);
+        let epoch = store.current_epoch();
+        let pending = store.create_edge_versioned(a, b, "FRIEND", epoch, writer);
+
+        for direction in [Direction::Outgoing, Direction::Incoming] {
+            let others: Vec<_> = store.edges_from_versioned(b, direction, epoch, TxId::SYSTEM);
+            assert!(others.contains(&(a, committed)), "{direction:?}");
+            assert!(!others.contains(&(a, pending)), "{direction:?}");
+
+            let own: Vec<_> = store.edges_from_versioned(b, direction, epoch, writer);
+            assert!(own.contains(&(a, pending)), "{direction:?}");
+        }
+
+        store.discard_uncommitted_versions(writer);
+        assert_eq!(
+            store.edges_from_versioned(b, Direction::Outgoing, epoch, writer),
+            vec![(a, committed)]
+        );
+    }
+
+    #[test]
+    fn test_undirected_edges_without_backward_adjacency() {
+        let store = LpgStore::with_config(LpgStoreConfig {
+            backward_edges: false,
+            ..LpgStoreConfig::default()
+        });
+        store.set_edge_type_undirected("FRIEND");
+        let a = store.create_node(&["Person"]);
+        let b = store.create_node(&["Person"]);
+        let friend = store.create_edge(a, b, "FRIEND");
+        store.create_edge(a, b, "KNOWS");
+
+        // Found by scanning the edges, since nothing indexes them by target
+        let from_b: Vec<_> = store.edges_from(b, Direction::Outgoing).collect();
+        assert_eq!(from_b, vec![(a, friend)]);
+        assert_eq!(
+            store.edges_from_versioned(b, Direction::Outgoing, store.current_epoch(), TxId::SYSTEM),
+            vec![(a, friend)]
+        );
+    }
+
+    #[test]
+    fn test_undirected_edge_degree() {
+        let store = LpgStore::new();
+        store.set_edge_type_undirected("FRIEND");
+
+        let a = store.create_node(&["Person"]);
+        let b = store.create_node(&["Person"]);
+        let c = store.create_node(&["Person"]);
+        store.create_edge(a, b, "FRIEND");
+        store.create_edge(c, a, "FRIEND");
+        store.create_edge(a, c, "KNOWS");
+        store.create_edge(a, a, "FRIEND");
+
+        // Two undirected edges plus the self-loop, each counted once
+        assert_eq!(store.degree(a, Direction::Outgoing), 4);
+        assert_eq!(store.degree(a, Direction::Incoming), 3);
+        assert_eq!(store.degree(b, Direction::Outgoing), 1);
+        assert_eq!(store.degree(b, Direction::Both), 1);
+        assert_eq!(store.degree(c, Direction::Both), 2);
+
+        let deleted = store.edges_from(b, Direction::Both).next().unwrap().1;
+        store.delete_edge(deleted);
+        assert_eq!(store.degree(a, Direction::Outgoing), 3);
+        assert_eq!(store.degree(b, Direction::Incoming), 0);
+    }
+
     #[test]
     fn test_nodes_by_label() {
         let store = LpgStore::new();
@@ -1394,4 +3644,196 @@ mod tests {
         assert_eq!(store.edge_count(), 0);
         assert!(store.get_edge(edge_id).is_none());
     }
+
+    #[test]
+    fn test_check_integrity() {
+        let store = LpgStore::new();
+        let a = store.create_node(&["Person"]);
+        let b = store.create_node(&["Person"]);
+        let c = store.create_node(&["Person"]);
+        let ab = store.create_edge(a, b, "KNOWS");
+        let bc = store.create_edge(b, c, "KNOWS");
+        store.delete_edge(bc);
+        assert!(store.check_integrity().is_clean());
+
+        // Deleting a node without its edges leaves them dangling
+        store.delete_node(b);
+        let report = store.check_integrity();
+        assert_eq!(report.dangling_targets, vec![(ab, b)]);
+        assert!(report.dangling_sources.is_empty());
+        assert!(report.stale_adjacency.is_empty());
+
+        // Corrupt the indexes directly
+        let ghost = EdgeId::new(99);
+        store.forward_adj.add_edge(a, c, ghost);
+        store.label_index.write()[0].insert(NodeId::new(42), ());
+        store.backward_adj.as_ref().unwrap().mark_deleted(b, ab);
+
+        let report = store.check_integrity();
+        assert_eq!(report.stale_adjacency, vec![(a, ghost)]);
+        assert_eq!(
+            report.orphaned_label_entries,
+            vec![(Arc::from("Person"), NodeId::new(42))]
+        );
+        assert_eq!(report.unindexed_edges, vec![ab]);
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_property_index_tracks_writes_and_visibility() {
+        let store = LpgStore::new();
+        let alice = store.create_node_with_props(&["Person"], [("city", Value::from("Oslo"))]);
+        let bob = store.create_node_with_props(&["Person"], [("city", Value::from("Oslo"))]);
+        let oslo = Value::from("Oslo");
+        let find = |value: &Value, epoch, tx| {
+            store
+                .find_nodes_by_property_index("Person", "city", value, epoch, tx)
+                .unwrap()
+        };
+
+        assert!(
+            store
+                .find_nodes_by_property_index("Person", "city", &oslo, None, None)
+                .is_none()
+        );
+        assert!(store.create_property_index("Person", "city"));
+        assert!(!store.create_property_index("Person", "city"));
+
+        // Existing nodes share the key, and later writes are indexed
+        assert_eq!(find(&oslo, None, None), vec![alice, bob]);
+        let carol = store.create_node(&["Person"]);
+        store.set_node_property(carol, "city", oslo.clone());
+        let dave = store.create_node_with_props(&["Robot"], [("city", Value::from("Oslo"))]);
+        assert_eq!(find(&oslo, None, None), vec![alice, bob, carol]);
+        store.add_label(dave, "Person");
+        assert_eq!(find(&oslo, None, None), vec![alice, bob, carol, dave]);
+
+        // Stale entries are left behind but never returned
+        store.set_node_property(bob, "city", Value::from("Rome"));
+        store.remove_label(dave, "Person");
+        store.delete_node(carol);
+        assert_eq!(find(&oslo, None, None), vec![alice]);
+        assert_eq!(find(&Value::from("Rome"), None, None), vec![bob]);
+
+        // An open transaction's change is only seen by that transaction
+        let epoch = store.current_epoch();
+        let tx = TxId::new(7);
+        store.set_node_property_versioned(alice, "city", Value::from("Rome"), tx);
+        assert_eq!(find(&oslo, Some(epoch), None), vec![alice]);
+        assert_eq!(
+            find(&Value::from("Rome"), Some(epoch), Some(tx)),
+            vec![alice, bob]
+        );
+        assert!(find(&Value::Null, None, None).is_empty());
+    }
+
+    #[test]
+    fn test_composite_index_serves_leading_keys() {
+        let store = LpgStore::new();
+        let person = |last: &str, first: &str| {
+            store.create_node_with_props(
+                &["Person"],
+                [("last", Value::from(last)), ("first", Value::from(first))],
+            )
+        };
+        let ada = person("Lovelace", "Ada");
+        let byron = person("Lovelace", "Byron");
+        let alan = person("Turing", "Alan");
+        let keys = ["last", "first"];
+        let find = |values: &[Value], epoch, tx| {
+            store
+                .find_nodes_by_composite_index("Person", &keys, values, epoch, tx)
+                .unwrap()
+        };
+
+        assert!(!store.has_composite_index("Person", &keys));
+        assert!(store.create_composite_index("Person", &keys));
+        assert!(!store.create_composite_index("Person", &keys));
+        assert!(store.has_composite_index("Person", &keys));
+        assert!(!store.has_composite_index("Person", &["first", "last"]));
+
+        // The leading key alone, or both keys
+        let lovelace = Value::from("Lovelace");
+        assert_eq!(
+            find(std::slice::from_ref(&lovelace), None, None),
+            vec![ada, byron]
+        );
+        assert_eq!(
+            find(&[lovelace.clone(), Value::from("Ada")], None, None),
+            vec![ada]
+        );
+        assert!(find(&[Value::from("Ada")], None, None).is_empty());
+
+        // Later writes are indexed, and changed values are not returned
+        let mary = store.create_node(&["Person"]);
+        store.set_node_property(mary, "last", lovelace.clone());
+        store.set_node_property(byron, "last", Value::from("Byron"));
+        assert_eq!(
+            find(std::slice::from_ref(&lovelace), None, None),
+            vec![ada, mary]
+        );
+        store.set_node_property(alan, "first", Value::from("Alan M."));
+        assert_eq!(
+            find(&[Value::from("Turing"), Value::from("Alan M.")], None, None),
+            vec![alan]
+        );
+
+        // Numbers are found whichever type they were written as
+        store.set_node_property(mary, "first", Value::Float64(1.0));
+        assert_eq!(
+            find(&[lovelace.clone(), Value::Int64(1)], None, None),
+            vec![mary]
+        );
+
+        // An open transaction's change is only seen by that transaction
+        let epoch = store.current_epoch();
+        let tx = TxId::new(7);
+        store.set_node_property_versioned(ada, "last", Value::from("King"), tx);
+        assert_eq!(
+            find(std::slice::from_ref(&lovelace), Some(epoch), None),
+            vec![ada, mary]
+        );
+        assert_eq!(find(&[lovelace], Some(epoch), Some(tx)), vec![mary]);
+        assert!(find(&[Value::Null], None, None).is_empty());
+    }
+
+    #[test]
+    fn test_text_index_finds_prefixes() {
+        let store = LpgStore::new();
+        let alix = store.create_node_with_props(&["Person"], [("name", Value::from("Alix"))]);
+        let alan = store.create_node_with_props(&["Person"], [("name", Value::from("alan"))]);
+        let gus = store.create_node_with_props(&["Person"], [("name", Value::from("Gus"))]);
+        let find = |prefix: &str, epoch, tx| {
+            store
+                .find_nodes_by_text_prefix("Person", "name", prefix, epoch, tx)
+                .unwrap()
+        };
+
+        assert!(
+            store
+                .find_nodes_by_text_prefix("Person", "name", "Al", None, None)
+                .is_none()
+        );
+        assert!(store.create_text_index("Person", "name", false));
+        assert!(!store.create_text_index("Person", "name", true));
+        assert!(store.has_text_index("Person", "name"));
+
+        // Case is ignored, and the empty prefix matches every string
+        assert_eq!(find("AL", None, None), vec![alix, alan]);
+        assert_eq!(find("", None, None), vec![alix, alan, gus]);
+
+        // Later writes are indexed, and changed values are not returned
+        let mia = store.create_node(&["Person"]);
+        store.set_node_property(mia, "name", Value::from("Alma"));
+        store.set_node_property(alan, "name", Value::from("Vincent"));
+        store.set_node_property(gus, "name", Value::Int64(7));
+        assert_eq!(find("al", None, None), vec![alix, mia]);
+
+        // An open transaction's change is only seen by that transaction
+        let epoch = store.current_epoch();
+        let tx = TxId::new(7);
+        store.set_node_property_versioned(alix, "name", Value::from("Jules"), tx);
+        assert_eq!(find("al", Some(epoch), None), vec![alix, mia]);
+        assert_eq!(find("al", Some(epoch), Some(tx)), vec![mia]);
+    }
 }
@@ -209,6 +209,10 @@ impl AdjacencyList {
         self.deleted.insert(edge_id);
     }
 
+    fn unmark_deleted(&mut self, edge_id: EdgeId) -> bool {
+        self.deleted.remove(&edge_id)
+    }
+
     fn compact(&mut self, chunk_capacity: usize) {
         if self.delta_inserts.is_empty() {
             return;
@@ -422,6 +426,16 @@ impl ChunkedAdjacency {
         }
     }
 
+    /// Restores an edge previously marked as deleted.
+    pub fn unmark_deleted(&self, src: NodeId, edge_id: EdgeId) {
+        let mut lists = self.lists.write();
+        if let Some(list) = lists.get_mut(&src) {
+            if list.unmark_deleted(edge_id) {
+                self.deleted_count.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+    }
+
     /// Returns all neighbors of a node.
     ///
     /// Note: This allocates a Vec to collect neighbors while the internal lock
@@ -450,6 +464,16 @@ impl ChunkedAdjacency {
             .unwrap_or_default()
     }
 
+    /// Returns every `(src, dst, edge_id)` entry not marked as deleted.
+    #[must_use]
+    pub fn all_edges(&self) -> Vec<(NodeId, NodeId, EdgeId)> {
+        let lists = self.lists.read();
+        lists
+            .iter()
+            .flat_map(|(&src, list)| list.iter().map(move |(dst, edge_id)| (src, dst, edge_id)))
+            .collect()
+    }
+
     /// Returns the out-degree of a node.
     pub fn out_degree(&self, src: NodeId) -> usize {
         let lists = self.lists.read();
@@ -642,6 +666,19 @@ mod tests {
         assert!(neighbors.contains(&NodeId::new(2)));
     }
 
+    #[test]
+    fn test_unmark_deleted() {
+        let adj = ChunkedAdjacency::new();
+
+        adj.add_edge(NodeId::new(0), NodeId::new(1), EdgeId::new(0));
+        adj.mark_deleted(NodeId::new(0), EdgeId::new(0));
+        assert_eq!(adj.active_edge_count(), 0);
+
+        adj.unmark_deleted(NodeId::new(0), EdgeId::new(0));
+        assert_eq!(adj.neighbors(NodeId::new(0)), vec![NodeId::new(1)]);
+        assert_eq!(adj.active_edge_count(), 1);
+    }
+
     #[test]
     fn test_edges_from() {
         let adj = ChunkedAdjacency::new();
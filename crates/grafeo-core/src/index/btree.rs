@@ -75,6 +75,24 @@ impl<K: Ord + Clone, V: Copy> BTreeIndex<K, V> {
             .collect()
     }
 
+    /// Returns the entries from the start of `range` up to, but not
+    /// including, the first key `keep` rejects.
+    ///
+    /// With a range starting at a prefix and a `keep` that tests for it,
+    /// this reads just the keys sharing the prefix.
+    pub fn range_while<R: RangeBounds<K>>(
+        &self,
+        range: R,
+        mut keep: impl FnMut(&K) -> bool,
+    ) -> Vec<(K, V)> {
+        self.map
+            .read()
+            .range(range)
+            .take_while(|(k, _)| keep(k))
+            .map(|(k, v)| (k.clone(), *v))
+            .collect()
+    }
+
     /// Returns the minimum key-value pair.
     pub fn min(&self) -> Option<(K, V)> {
         self.map
@@ -181,6 +199,25 @@ mod tests {
         assert!(range.contains(&(30, NodeId::new(300))));
     }
 
+    #[test]
+    fn test_btree_range_while_reads_a_prefix() {
+        let index: BTreeIndex<Vec<i64>, NodeId> = BTreeIndex::new();
+
+        index.insert(vec![1, 9], NodeId::new(100));
+        index.insert(vec![2], NodeId::new(200));
+        index.insert(vec![2, 1], NodeId::new(201));
+        index.insert(vec![2, 7], NodeId::new(207));
+        index.insert(vec![3, 0], NodeId::new(300));
+
+        let prefix = [2];
+        let found = index.range_while(prefix.to_vec().., |key| key.starts_with(&prefix));
+        let ids: Vec<_> = found.into_iter().map(|(_, id)| id).collect();
+        assert_eq!(
+            ids,
+            vec![NodeId::new(200), NodeId::new(201), NodeId::new(207)]
+        );
+    }
+
     #[test]
     fn test_btree_min_max() {
         let index: Int64Index = BTreeIndex::new();
@@ -4,7 +4,7 @@
 //! a user by their unique username or finding a node by a primary key.
 
 use grafeo_common::types::NodeId;
-use grafeo_common::utils::hash::FxHashMap;
+use grafeo_common::utils::hash::{FxHashMap, FxHashSet};
 use parking_lot::RwLock;
 use std::hash::Hash;
 
@@ -96,6 +96,95 @@ impl<K: Hash + Eq, V: Copy> Default for HashIndex<K, V> {
     }
 }
 
+/// A thread-safe hash index that keeps every value inserted under a key.
+///
+/// Where [`HashIndex`] holds one value per key, this holds a set, so it
+/// fits non-unique keys like the nodes sharing a property value.
+///
+/// # Example
+///
+/// ```
+/// use grafeo_core::index::MultiHashIndex;
+/// use grafeo_common::types::NodeId;
+///
+/// let index: MultiHashIndex<String, NodeId> = MultiHashIndex::new();
+/// index.insert("berlin".to_string(), NodeId::new(1));
+/// index.insert("berlin".to_string(), NodeId::new(2));
+///
+/// assert_eq!(index.get(&"berlin".to_string()).len(), 2);
+/// ```
+pub struct MultiHashIndex<K: Hash + Eq, V: Copy + Hash + Eq> {
+    /// The values under each key.
+    map: RwLock<FxHashMap<K, FxHashSet<V>>>,
+}
+
+impl<K: Hash + Eq, V: Copy + Hash + Eq> MultiHashIndex<K, V> {
+    /// Creates a new empty index.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            map: RwLock::new(FxHashMap::default()),
+        }
+    }
+
+    /// Adds `value` under `key`.
+    ///
+    /// Returns false if the pair was already present.
+    pub fn insert(&self, key: K, value: V) -> bool {
+        self.map.write().entry(key).or_default().insert(value)
+    }
+
+    /// Gets every value under a key, in no particular order.
+    pub fn get(&self, key: &K) -> Vec<V> {
+        self.map
+            .read()
+            .get(key)
+            .map(|values| values.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Removes `value` from under `key`.
+    ///
+    /// Returns true if the pair was present.
+    pub fn remove(&self, key: &K, value: &V) -> bool {
+        let mut map = self.map.write();
+        let Some(values) = map.get_mut(key) else {
+            return false;
+        };
+        let removed = values.remove(value);
+        if values.is_empty() {
+            map.remove(key);
+        }
+        removed
+    }
+
+    /// Checks if any value is stored under a key.
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.read().contains_key(key)
+    }
+
+    /// Returns the number of distinct keys in the index.
+    pub fn len(&self) -> usize {
+        self.map.read().len()
+    }
+
+    /// Returns true if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.map.read().is_empty()
+    }
+
+    /// Clears all entries from the index.
+    pub fn clear(&self) {
+        self.map.write().clear();
+    }
+}
+
+impl<K: Hash + Eq, V: Copy + Hash + Eq> Default for MultiHashIndex<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// A hash index from string keys to NodeIds.
 pub type StringKeyIndex = HashIndex<String, NodeId>;
 
@@ -178,4 +267,25 @@ mod tests {
         assert_eq!(index.get(&"alice".to_string()), Some(NodeId::new(1)));
         assert_eq!(index.get(&"bob".to_string()), Some(NodeId::new(2)));
     }
+
+    #[test]
+    fn test_multi_hash_index_keeps_every_value() {
+        let index: MultiHashIndex<u64, NodeId> = MultiHashIndex::new();
+
+        assert!(index.insert(1, NodeId::new(100)));
+        assert!(index.insert(1, NodeId::new(101)));
+        assert!(!index.insert(1, NodeId::new(100)));
+        index.insert(2, NodeId::new(200));
+
+        let mut values = index.get(&1);
+        values.sort();
+        assert_eq!(values, vec![NodeId::new(100), NodeId::new(101)]);
+        assert_eq!(index.len(), 2);
+
+        assert!(index.remove(&1, &NodeId::new(100)));
+        assert_eq!(index.get(&1), vec![NodeId::new(101)]);
+        assert!(index.remove(&1, &NodeId::new(101)));
+        assert!(!index.contains(&1));
+        assert!(index.get(&3).is_empty());
+    }
 }
@@ -7,7 +7,7 @@
 //! | [`adjacency`] | Traversing neighbors | O(degree) |
 //! | [`hash`] | Point lookups by exact value | O(1) average |
 //! | [`btree`] | Range queries like `age > 30` | O(log n) |
-//! | [`trie`] | Multi-way joins | Worst-case optimal |
+//! | [`trie`] | Multi-way joins, `STARTS WITH` on strings | Worst-case optimal / O(prefix) |
 //! | [`zone_map`] | Skipping chunks during scans | O(1) per chunk |
 //!
 //! Most queries use `adjacency` for traversals and `hash` or `btree` for filtering.
@@ -20,5 +20,6 @@ pub mod zone_map;
 
 pub use adjacency::ChunkedAdjacency;
 pub use btree::BTreeIndex;
-pub use hash::HashIndex;
-pub use zone_map::{BloomFilter, ZoneMapBuilder, ZoneMapEntry, ZoneMapIndex};
+pub use hash::{HashIndex, MultiHashIndex};
+pub use trie::TextTrieIndex;
+pub use zone_map::{BloomFilter, ZoneMapBuilder, ZoneMapEntry, ZoneMapIndex, ZonePredicate};
@@ -6,6 +6,12 @@
 //!
 //! Built lazily on-demand. You won't interact with this directly unless you're
 //! implementing custom join algorithms.
+//!
+//! The same structure keyed on characters gives [`TextTrieIndex`], a string
+//! property index that answers `STARTS WITH` by walking the prefix and
+//! collecting the subtree below it.
+
+use std::collections::BTreeMap;
 
 use grafeo_common::types::{EdgeId, NodeId};
 use grafeo_common::utils::hash::FxHashMap;
@@ -289,6 +295,138 @@ impl<'a> LeapfrogJoin<'a> {
     }
 }
 
+// ============================================================================
+// Text Trie
+// ============================================================================
+
+/// A character-level trie node holding the nodes whose value ends here.
+#[derive(Debug, Clone, Default)]
+struct TextTrieNode {
+    children: BTreeMap<char, TextTrieNode>,
+    values: SmallVec<[NodeId; 4]>,
+}
+
+impl TextTrieNode {
+    fn collect(&self, out: &mut Vec<NodeId>) {
+        out.extend_from_slice(&self.values);
+        for child in self.children.values() {
+            child.collect(out);
+        }
+    }
+}
+
+/// A string property index answering prefix (`STARTS WITH`) lookups.
+///
+/// Maps string values to the nodes holding them. A case-insensitive index
+/// lowercases both stored values and lookup prefixes.
+///
+/// # Example
+///
+/// ```
+/// use grafeo_core::index::TextTrieIndex;
+/// use grafeo_common::types::NodeId;
+///
+/// let mut index = TextTrieIndex::new(true);
+/// index.insert("Alice", NodeId::new(1));
+/// index.insert("Bob", NodeId::new(2));
+///
+/// assert_eq!(index.starts_with("Al"), vec![NodeId::new(1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextTrieIndex {
+    root: TextTrieNode,
+    case_sensitive: bool,
+    size: usize,
+}
+
+impl TextTrieIndex {
+    /// Creates an empty index.
+    #[must_use]
+    pub fn new(case_sensitive: bool) -> Self {
+        Self {
+            root: TextTrieNode::default(),
+            case_sensitive,
+            size: 0,
+        }
+    }
+
+    /// Returns whether lookups distinguish upper and lower case.
+    #[must_use]
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Indexes `node` under `text`. Inserting the same pair twice is a no-op.
+    pub fn insert(&mut self, text: &str, node: NodeId) {
+        let folded = self.fold(text);
+        let mut current = &mut self.root;
+        for ch in folded.chars() {
+            current = current.children.entry(ch).or_default();
+        }
+        if !current.values.contains(&node) {
+            current.values.push(node);
+            self.size += 1;
+        }
+    }
+
+    /// Removes `node` from under `text`. Returns whether it was present.
+    pub fn remove(&mut self, text: &str, node: NodeId) -> bool {
+        let folded = self.fold(text);
+        let mut current = &mut self.root;
+        for ch in folded.chars() {
+            match current.children.get_mut(&ch) {
+                Some(child) => current = child,
+                None => return false,
+            }
+        }
+        let Some(pos) = current.values.iter().position(|&v| v == node) else {
+            return false;
+        };
+        current.values.swap_remove(pos);
+        self.size -= 1;
+        true
+    }
+
+    /// Returns the nodes whose value starts with `prefix`, sorted and unique.
+    ///
+    /// The empty prefix matches every indexed value.
+    #[must_use]
+    pub fn starts_with(&self, prefix: &str) -> Vec<NodeId> {
+        let mut current = &self.root;
+        for ch in self.fold(prefix).chars() {
+            match current.children.get(&ch) {
+                Some(child) => current = child,
+                None => return Vec::new(),
+            }
+        }
+        let mut nodes = Vec::new();
+        current.collect(&mut nodes);
+        nodes.sort_unstable();
+        nodes.dedup();
+        nodes
+    }
+
+    /// Returns the number of indexed (value, node) pairs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns true if nothing is indexed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    fn fold<'a>(&self, text: &'a str) -> std::borrow::Cow<'a, str> {
+        if self.case_sensitive {
+            std::borrow::Cow::Borrowed(text)
+        } else {
+            std::borrow::Cow::Owned(text.to_lowercase())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -382,4 +520,68 @@ mod tests {
         assert!(results.contains(&NodeId::new(3)));
         assert!(results.contains(&NodeId::new(5)));
     }
+
+    fn name_index(case_sensitive: bool) -> TextTrieIndex {
+        let mut index = TextTrieIndex::new(case_sensitive);
+        index.insert("Alice", NodeId::new(1));
+        index.insert("Alfred", NodeId::new(2));
+        index.insert("alan", NodeId::new(3));
+        index.insert("Bob", NodeId::new(4));
+        index
+    }
+
+    #[test]
+    fn test_text_trie_prefix_hits() {
+        let index = name_index(true);
+        assert_eq!(index.len(), 4);
+
+        assert_eq!(
+            index.starts_with("Al"),
+            vec![NodeId::new(1), NodeId::new(2)]
+        );
+        assert_eq!(index.starts_with("Alice"), vec![NodeId::new(1)]);
+        assert!(index.starts_with("Alicia").is_empty());
+        assert!(index.starts_with("Z").is_empty());
+    }
+
+    #[test]
+    fn test_text_trie_case_insensitive() {
+        let index = name_index(false);
+        assert!(!index.is_case_sensitive());
+
+        assert_eq!(
+            index.starts_with("AL"),
+            vec![NodeId::new(1), NodeId::new(2), NodeId::new(3)]
+        );
+        assert_eq!(index.starts_with("bo"), vec![NodeId::new(4)]);
+    }
+
+    #[test]
+    fn test_text_trie_empty_prefix_matches_everything() {
+        let mut index = name_index(true);
+        index.insert("", NodeId::new(5));
+
+        assert_eq!(index.starts_with("").len(), 5);
+        assert!(TextTrieIndex::new(true).starts_with("").is_empty());
+    }
+
+    #[test]
+    fn test_text_trie_insert_remove() {
+        let mut index = name_index(true);
+
+        // Duplicate pairs are ignored, shared values list every node
+        index.insert("Bob", NodeId::new(4));
+        index.insert("Bob", NodeId::new(6));
+        assert_eq!(index.len(), 5);
+        assert_eq!(
+            index.starts_with("Bob"),
+            vec![NodeId::new(4), NodeId::new(6)]
+        );
+
+        assert!(index.remove("Bob", NodeId::new(4)));
+        assert!(!index.remove("Bob", NodeId::new(4)));
+        assert!(!index.remove("Bobby", NodeId::new(6)));
+        assert_eq!(index.starts_with("B"), vec![NodeId::new(6)]);
+        assert_eq!(index.len(), 4);
+    }
 }
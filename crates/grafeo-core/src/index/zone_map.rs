@@ -11,6 +11,27 @@ use grafeo_common::types::Value;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
+/// A predicate that zone maps can evaluate against chunk statistics.
+///
+/// Evaluation is conservative: a chunk is only ruled out when its statistics
+/// prove no value can match.
+#[derive(Debug, Clone)]
+pub enum ZonePredicate {
+    /// `property = value`.
+    Equal(Value),
+    /// `property` between two bounds; a missing bound is unbounded.
+    Range {
+        /// Lower bound.
+        lower: Option<Value>,
+        /// Upper bound.
+        upper: Option<Value>,
+        /// Whether the lower bound itself matches.
+        lower_inclusive: bool,
+        /// Whether the upper bound itself matches.
+        upper_inclusive: bool,
+    },
+}
+
 /// Statistics for a single chunk of property data.
 ///
 /// The query optimizer uses these to skip chunks that can't match a predicate.
@@ -158,6 +179,26 @@ impl ZoneMapEntry {
         true
     }
 
+    /// Checks if this chunk might contain values matching a predicate.
+    ///
+    /// Equality consults the Bloom filter when one was built.
+    pub fn might_match(&self, predicate: &ZonePredicate) -> bool {
+        match predicate {
+            ZonePredicate::Equal(value) => self.might_contain_equal(value),
+            ZonePredicate::Range {
+                lower,
+                upper,
+                lower_inclusive,
+                upper_inclusive,
+            } => self.might_contain_range(
+                lower.as_ref(),
+                upper.as_ref(),
+                *lower_inclusive,
+                *upper_inclusive,
+            ),
+        }
+    }
+
     /// Checks if this chunk might contain non-null values.
     pub fn might_contain_non_null(&self) -> bool {
         self.row_count > self.null_count
@@ -362,6 +403,15 @@ impl ZoneMapIndex {
         self.entries.is_empty()
     }
 
+    /// Checks if a chunk might contain values matching a predicate.
+    ///
+    /// Chunks without a zone map are assumed to match.
+    pub fn might_match(&self, chunk_id: u64, predicate: &ZonePredicate) -> bool {
+        self.entries
+            .get(&chunk_id)
+            .is_none_or(|e| e.might_match(predicate))
+    }
+
     /// Filters chunk IDs that might match an equality predicate.
     pub fn filter_equal<'a>(
         &'a self,
@@ -580,9 +630,10 @@ fn compare_values(a: &Value, b: &Value) -> Option<Ordering> {
         (Value::Float64(a), Value::Float64(b)) => a.partial_cmp(b),
         (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
         (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::Bytes(a), Value::Bytes(b)) => Some(a.cmp(b)),
         (Value::Int64(a), Value::Float64(b)) => (*a as f64).partial_cmp(b),
         (Value::Float64(a), Value::Int64(b)) => a.partial_cmp(&(*b as f64)),
-        _ => None,
+        _ => a.temporal_cmp(b),
     }
 }
 
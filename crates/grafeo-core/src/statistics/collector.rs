@@ -5,7 +5,7 @@
 //! The [`Statistics`] struct holds everything the optimizer needs.
 
 use super::histogram::Histogram;
-use grafeo_common::types::Value;
+use grafeo_common::types::{LogicalType, Value};
 use std::collections::HashMap;
 
 /// A property key identifier.
@@ -27,6 +27,8 @@ pub struct Statistics {
     pub total_nodes: u64,
     /// Total edge count.
     pub total_edges: u64,
+    /// Whether counts changed since the histograms were built.
+    stale: bool,
 }
 
 impl Statistics {
@@ -65,6 +67,91 @@ impl Statistics {
         self.properties.get(property)
     }
 
+    /// Returns `true` if the data changed since these statistics were computed.
+    ///
+    /// Incremental maintenance keeps counts and min/max current, but
+    /// histograms and most common values only refresh on a full recompute.
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+
+    /// Flags the statistics as out of date without changing any counts.
+    ///
+    /// Stores that don't maintain statistics incrementally call this on
+    /// every write, so readers know to recompute before trusting them.
+    pub fn mark_stale(&mut self) {
+        self.stale = true;
+    }
+
+    /// Counts a new node with the given labels.
+    pub fn record_node_added(&mut self, labels: &[&str]) {
+        self.total_nodes += 1;
+        for label in labels {
+            self.record_label_added(label);
+        }
+    }
+
+    /// Discounts a removed node with the given labels.
+    pub fn record_node_removed(&mut self, labels: &[&str]) {
+        self.total_nodes = self.total_nodes.saturating_sub(1);
+        for label in labels {
+            self.record_label_removed(label);
+        }
+    }
+
+    /// Counts a label added to a node.
+    pub fn record_label_added(&mut self, label: &str) {
+        self.labels
+            .entry(label.to_string())
+            .or_insert_with(|| LabelStatistics::new(0))
+            .node_count += 1;
+        self.stale = true;
+    }
+
+    /// Discounts a label removed from a node.
+    pub fn record_label_removed(&mut self, label: &str) {
+        if let Some(stats) = self.labels.get_mut(label) {
+            stats.node_count = stats.node_count.saturating_sub(1);
+        }
+        self.stale = true;
+    }
+
+    /// Counts a new edge of the given type.
+    pub fn record_edge_added(&mut self, edge_type: &str) {
+        self.total_edges += 1;
+        self.edge_types
+            .entry(edge_type.to_string())
+            .or_insert_with(|| EdgeTypeStatistics::new(0, 0.0, 0.0))
+            .edge_count += 1;
+        self.stale = true;
+    }
+
+    /// Discounts a removed edge of the given type.
+    pub fn record_edge_removed(&mut self, edge_type: &str) {
+        self.total_edges = self.total_edges.saturating_sub(1);
+        if let Some(stats) = self.edge_types.get_mut(edge_type) {
+            stats.edge_count = stats.edge_count.saturating_sub(1);
+        }
+        self.stale = true;
+    }
+
+    /// Counts a property value that was written.
+    pub fn record_value_added(&mut self, property: &str, value: &Value) {
+        self.properties
+            .entry(property.to_string())
+            .or_insert_with(|| ColumnStatistics::new(0, 0, 0))
+            .record_insert(value);
+        self.stale = true;
+    }
+
+    /// Discounts a property value that was overwritten or removed.
+    pub fn record_value_removed(&mut self, property: &str, value: &Value) {
+        if let Some(stats) = self.properties.get_mut(property) {
+            stats.record_remove(value);
+        }
+        self.stale = true;
+    }
+
     /// Estimates the cardinality of a label scan.
     pub fn estimate_label_cardinality(&self, label: &str) -> f64 {
         self.labels
@@ -207,6 +294,9 @@ pub struct ColumnStatistics {
     pub histogram: Option<Histogram>,
     /// Most common values with their frequencies.
     pub most_common: Vec<(Value, f64)>,
+    /// Common type of the non-null values: `Null` while there are none,
+    /// `Any` once they don't share one.
+    pub data_type: LogicalType,
 }
 
 impl ColumnStatistics {
@@ -221,6 +311,7 @@ impl ColumnStatistics {
             avg_value: None,
             histogram: None,
             most_common: Vec::new(),
+            data_type: LogicalType::Null,
         }
     }
 
@@ -249,6 +340,49 @@ impl ColumnStatistics {
         self
     }
 
+    /// Accounts for one more value without rebuilding the histogram.
+    ///
+    /// The distinct count only grows for values outside the known min/max
+    /// range, so it errs low until the next full recompute.
+    pub fn record_insert(&mut self, value: &Value) {
+        self.total_count += 1;
+        if matches!(value, Value::Null) {
+            self.null_count += 1;
+            return;
+        }
+        self.data_type = widen(&self.data_type, &value_type(value));
+
+        let below = self
+            .min_value
+            .as_ref()
+            .is_none_or(|min| compare_values(value, min) == Some(std::cmp::Ordering::Less));
+        let above = self
+            .max_value
+            .as_ref()
+            .is_none_or(|max| compare_values(value, max) == Some(std::cmp::Ordering::Greater));
+        if below {
+            self.min_value = Some(value.clone());
+        }
+        if above {
+            self.max_value = Some(value.clone());
+        }
+        if below || above {
+            self.distinct_count += 1;
+        }
+    }
+
+    /// Accounts for one value fewer without rebuilding the histogram.
+    ///
+    /// Min and max stay as they are: they remain valid, if loose, bounds.
+    pub fn record_remove(&mut self, value: &Value) {
+        self.total_count = self.total_count.saturating_sub(1);
+        if matches!(value, Value::Null) {
+            self.null_count = self.null_count.saturating_sub(1);
+        }
+        let non_null = self.total_count - self.null_count.min(self.total_count);
+        self.distinct_count = self.distinct_count.min(non_null);
+    }
+
     /// Returns the null fraction.
     pub fn null_fraction(&self) -> f64 {
         if self.total_count == 0 {
@@ -350,7 +484,6 @@ fn estimate_linear_range(min: &Value, max: &Value, lower: &Value, upper: &Value)
 ///
 /// Call [`add()`](Self::add) for each value, then [`build()`](Self::build)
 /// to get the final [`ColumnStatistics`] with histogram and most common values.
-#[allow(dead_code)]
 pub struct StatisticsCollector {
     /// Values collected for histogram building.
     values: Vec<Value>,
@@ -366,9 +499,10 @@ pub struct StatisticsCollector {
     null_count: u64,
     /// Value frequency counter.
     frequencies: HashMap<String, u64>,
+    /// Common type of the non-null values so far.
+    data_type: LogicalType,
 }
 
-#[allow(dead_code)]
 impl StatisticsCollector {
     /// Creates a new statistics collector.
     pub fn new() -> Self {
@@ -380,6 +514,7 @@ impl StatisticsCollector {
             sum: 0.0,
             null_count: 0,
             frequencies: HashMap::new(),
+            data_type: LogicalType::Null,
         }
     }
 
@@ -397,8 +532,9 @@ impl StatisticsCollector {
         // Track frequencies
         *self.frequencies.entry(key).or_insert(0) += 1;
 
-        // Track min/max
+        // Track min/max and the common type
         self.update_min_max(&value);
+        self.data_type = widen(&self.data_type, &value_type(&value));
 
         // Track sum for numeric
         if let Some(v) = value_to_f64(&value) {
@@ -453,7 +589,7 @@ impl StatisticsCollector {
         // Find most common values
         let total_non_null = self.values.len() as f64;
         let mut freq_vec: Vec<_> = self.frequencies.into_iter().collect();
-        freq_vec.sort_by(|a, b| b.1.cmp(&a.1));
+        freq_vec.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
 
         let most_common: Vec<(Value, f64)> = freq_vec
             .into_iter()
@@ -477,6 +613,7 @@ impl StatisticsCollector {
             .collect();
 
         let mut stats = ColumnStatistics::new(distinct_count, total_count, self.null_count);
+        stats.data_type = self.data_type;
 
         if let Some(min) = self.min {
             if let Some(max) = self.max {
@@ -507,7 +644,6 @@ impl Default for StatisticsCollector {
 }
 
 /// Converts a value to f64.
-#[allow(dead_code)]
 fn value_to_f64(value: &Value) -> Option<f64> {
     match value {
         Value::Int64(i) => Some(*i as f64),
@@ -517,7 +653,6 @@ fn value_to_f64(value: &Value) -> Option<f64> {
 }
 
 /// Compares two values.
-#[allow(dead_code)]
 fn compare_values(a: &Value, b: &Value) -> Option<std::cmp::Ordering> {
     match (a, b) {
         (Value::Int64(a), Value::Int64(b)) => Some(a.cmp(b)),
@@ -530,6 +665,47 @@ fn compare_values(a: &Value, b: &Value) -> Option<std::cmp::Ordering> {
     }
 }
 
+/// The type of a stored value; list and map elements are widened to their
+/// common type.
+fn value_type(value: &Value) -> LogicalType {
+    let elements = |values: &mut dyn Iterator<Item = &Value>| {
+        let common = values.fold(LogicalType::Null, |common, value| {
+            widen(&common, &value_type(value))
+        });
+        // Nothing to infer from an empty collection
+        if common == LogicalType::Null {
+            LogicalType::Any
+        } else {
+            common
+        }
+    };
+    match value {
+        Value::Null => LogicalType::Null,
+        Value::Bool(_) => LogicalType::Bool,
+        Value::Int64(_) => LogicalType::Int64,
+        Value::Float64(_) => LogicalType::Float64,
+        Value::String(_) => LogicalType::String,
+        Value::Bytes(_) => LogicalType::Bytes,
+        Value::Timestamp(_) => LogicalType::Timestamp,
+        Value::Date(_) => LogicalType::Date,
+        Value::Duration(_) => LogicalType::Duration,
+        Value::List(items) => LogicalType::List(Box::new(elements(&mut items.iter()))),
+        Value::Map(entries) => LogicalType::Map {
+            key: Box::new(LogicalType::String),
+            value: Box::new(elements(&mut entries.values())),
+        },
+    }
+}
+
+/// Widens `current` to also cover `next`; types with nothing in common
+/// widen to `Any`, which then stays put.
+fn widen(current: &LogicalType, next: &LogicalType) -> LogicalType {
+    if *current == LogicalType::Any {
+        return LogicalType::Any;
+    }
+    current.common_type(next).unwrap_or(LogicalType::Any)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -551,6 +727,21 @@ mod tests {
         assert_eq!(stats.max_value, Some(Value::Int64(9)));
     }
 
+    #[test]
+    fn test_statistics_track_common_type() {
+        let mut collector = StatisticsCollector::new();
+        collector.add(Value::Null);
+        collector.add(Value::Int64(1));
+        collector.add(Value::Float64(2.5));
+        let mut stats = collector.build(10, 5);
+        assert_eq!(stats.data_type, LogicalType::Float64);
+
+        stats.record_insert(&Value::String("x".into()));
+        assert_eq!(stats.data_type, LogicalType::Any);
+        stats.record_insert(&Value::Int64(3));
+        assert_eq!(stats.data_type, LogicalType::Any);
+    }
+
     #[test]
     fn test_statistics_with_nulls() {
         let mut collector = StatisticsCollector::new();
@@ -600,4 +791,44 @@ mod tests {
 
         assert_eq!(db_stats.estimate_avg_degree("KNOWS", true), 5.0);
     }
+
+    #[test]
+    fn test_incremental_counts() {
+        let mut stats = Statistics::new();
+        assert!(!stats.is_stale());
+
+        stats.record_node_added(&["Person"]);
+        stats.record_node_added(&["Person", "Admin"]);
+        stats.record_edge_added("KNOWS");
+        stats.record_node_removed(&["Person"]);
+
+        assert!(stats.is_stale());
+        assert_eq!(stats.total_nodes, 1);
+        assert_eq!(stats.get_label("Person").unwrap().node_count, 1);
+        assert_eq!(stats.get_label("Admin").unwrap().node_count, 1);
+        assert_eq!(stats.get_edge_type("KNOWS").unwrap().edge_count, 1);
+    }
+
+    #[test]
+    fn test_column_statistics_incremental() {
+        let mut stats = ColumnStatistics::new(0, 0, 0);
+
+        stats.record_insert(&Value::Int64(5));
+        stats.record_insert(&Value::Int64(1));
+        stats.record_insert(&Value::Int64(3));
+        stats.record_insert(&Value::Null);
+
+        assert_eq!(stats.total_count, 4);
+        assert_eq!(stats.null_count, 1);
+        assert_eq!(stats.min_value, Some(Value::Int64(1)));
+        assert_eq!(stats.max_value, Some(Value::Int64(5)));
+
+        stats.record_remove(&Value::Null);
+        stats.record_remove(&Value::Int64(5));
+        stats.record_remove(&Value::Int64(1));
+
+        assert_eq!(stats.total_count, 1);
+        assert_eq!(stats.null_count, 0);
+        assert_eq!(stats.distinct_count, 1);
+    }
 }
@@ -16,7 +16,8 @@ mod histogram;
 mod rdf;
 
 pub use collector::{
-    ColumnStatistics, EdgeTypeStatistics, LabelStatistics, PropertyKey, Statistics, TableStatistics,
+    ColumnStatistics, EdgeTypeStatistics, LabelStatistics, PropertyKey, Statistics,
+    StatisticsCollector, TableStatistics,
 };
 pub use histogram::{Histogram, HistogramBucket};
 pub use rdf::{
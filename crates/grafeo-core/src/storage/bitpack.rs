@@ -388,6 +388,106 @@ impl DeltaBitPacked {
     }
 }
 
+/// Frame-of-reference encoding: subtract the minimum, bit-pack the rest.
+///
+/// Suits unsorted values clustered in a narrow range, like ages stored next
+/// to large IDs or timestamps within one day: [1_000_042, 1_000_017, ...]
+/// becomes residuals [25, 0, ...] packed in just the bits the range needs.
+/// The base is the true minimum, so residuals are never negative.
+#[derive(Debug, Clone)]
+pub struct FrameOfReference {
+    /// Minimum value, subtracted from every value.
+    base: u64,
+    /// Bit-packed residuals (`value - base`).
+    residuals: BitPackedInts,
+}
+
+impl FrameOfReference {
+    /// Encodes values relative to their minimum.
+    #[must_use]
+    pub fn encode(values: &[u64]) -> Self {
+        let base = values.iter().copied().min().unwrap_or(0);
+        let residuals: Vec<u64> = values.iter().map(|&v| v - base).collect();
+
+        Self {
+            base,
+            residuals: BitPackedInts::pack(&residuals),
+        }
+    }
+
+    /// Decodes back to the original values.
+    #[must_use]
+    pub fn decode(&self) -> Vec<u64> {
+        self.residuals
+            .unpack()
+            .into_iter()
+            .map(|r| self.base + r)
+            .collect()
+    }
+
+    /// Returns the number of values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.residuals.len()
+    }
+
+    /// Returns whether the encoding is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.residuals.is_empty()
+    }
+
+    /// Returns the base (minimum) value.
+    #[must_use]
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// Returns the bits used per residual.
+    #[must_use]
+    pub fn bits_per_residual(&self) -> u8 {
+        self.residuals.bits_per_value()
+    }
+
+    /// Returns the compression ratio.
+    #[must_use]
+    pub fn compression_ratio(&self) -> f64 {
+        let count = self.len();
+        if count == 0 {
+            return 1.0;
+        }
+
+        let original_size = count * 8;
+        let packed_size = 8 + self.residuals.data().len() * 8; // base + packed residuals
+
+        original_size as f64 / packed_size as f64
+    }
+
+    /// Serializes to bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let residual_bytes = self.residuals.to_bytes();
+        let mut buf = Vec::with_capacity(8 + residual_bytes.len());
+        buf.extend_from_slice(&self.base.to_le_bytes());
+        buf.extend_from_slice(&residual_bytes);
+        buf
+    }
+
+    /// Deserializes from bytes.
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        if bytes.len() < 8 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "FrameOfReference too short",
+            ));
+        }
+
+        let base = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let residuals = BitPackedInts::from_bytes(&bytes[8..])?;
+
+        Ok(Self { base, residuals })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -529,4 +629,36 @@ mod tests {
         assert_eq!(BitPackedInts::bits_needed(256), 9);
         assert_eq!(BitPackedInts::bits_needed(u64::MAX), 64);
     }
+
+    #[test]
+    fn test_frame_of_reference_round_trip() {
+        let values = vec![1_000_042u64, 1_000_017, 1_000_063, 1_000_000, 1_000_031];
+        let encoded = FrameOfReference::encode(&values);
+
+        assert_eq!(encoded.base(), 1_000_000);
+        assert_eq!(encoded.bits_per_residual(), 6);
+        assert_eq!(encoded.len(), 5);
+        assert_eq!(encoded.decode(), values);
+
+        let restored = FrameOfReference::from_bytes(&encoded.to_bytes()).unwrap();
+        assert_eq!(restored.decode(), values);
+    }
+
+    #[test]
+    fn test_frame_of_reference_edge_cases() {
+        let empty = FrameOfReference::encode(&[]);
+        assert!(empty.is_empty());
+        assert!(empty.decode().is_empty());
+
+        // Constant values leave all-zero residuals
+        let constant = FrameOfReference::encode(&[u64::MAX; 10]);
+        assert_eq!(constant.decode(), vec![u64::MAX; 10]);
+
+        // Full range still round-trips
+        let extremes = vec![u64::MAX, 0, 7, u64::MAX - 1];
+        let encoded = FrameOfReference::encode(&extremes);
+        assert_eq!(encoded.decode(), extremes);
+
+        assert!(FrameOfReference::from_bytes(&[1, 2, 3]).is_err());
+    }
 }
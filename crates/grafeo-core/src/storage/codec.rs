@@ -10,13 +10,14 @@
 //! | Delta | Sorted integers | 2-10x |
 //! | DeltaBitPacked | Sequential IDs, timestamps | 5-20x |
 //! | BitPacked | Small integers (ages, counts) | 2-16x |
+//! | FrameOfReference | Unsorted integers in a narrow range | 2-30x |
 //! | Dictionary | Repeated strings (labels) | 2-50x |
 //! | BitVector | Booleans | 8x |
-//! | RunLength | Highly repetitive data | 2-100x |
+//! | RunLength | Highly repetitive data, sorted low-cardinality columns | 2-100x |
 
-use std::io;
+use std::io::{self, Read};
 
-use super::bitpack::{BitPackedInts, DeltaBitPacked};
+use super::bitpack::{BitPackedInts, DeltaBitPacked, FrameOfReference};
 use super::bitvec::BitVector;
 use super::runlength::{RunLengthAnalyzer, RunLengthEncoding};
 
@@ -41,6 +42,12 @@ pub enum CompressionCodec {
         bits: u8,
     },
 
+    /// Minimum as base + bit packing of residuals, for clustered integers.
+    FrameOfReference {
+        /// Number of bits per residual.
+        bits: u8,
+    },
+
     /// Dictionary encoding for strings.
     Dictionary,
 
@@ -60,6 +67,7 @@ impl CompressionCodec {
             Self::Delta => "Delta",
             Self::BitPacked { .. } => "BitPacked",
             Self::DeltaBitPacked { .. } => "DeltaBitPacked",
+            Self::FrameOfReference { .. } => "FrameOfReference",
             Self::Dictionary => "Dictionary",
             Self::BitVector => "BitVector",
             Self::RunLength => "RunLength",
@@ -112,6 +120,13 @@ pub enum CompressionMetadata {
         /// Number of values.
         count: usize,
     },
+    /// Frame-of-reference metadata.
+    FrameOfReference {
+        /// Minimum value, added back to every residual.
+        base: u64,
+        /// Number of values.
+        count: usize,
+    },
     /// Dictionary metadata.
     Dictionary {
         /// Dictionary identifier (for shared dictionaries).
@@ -152,6 +167,14 @@ impl CompressedData {
     }
 }
 
+/// Average run length from which strings prefer run-length over dictionary
+/// encoding: a run costs two words, so shorter runs gain little over codes.
+const STRING_RLE_MIN_AVG_RUN: f64 = 4.0;
+
+/// Average run length from which booleans prefer run-length over a bit
+/// vector: a 16-byte run only beats one bit per value past 128 values.
+const BOOLEAN_RLE_MIN_AVG_RUN: f64 = 128.0;
+
 /// Analyzes your data and picks the best compression codec.
 ///
 /// Don't want to think about compression? Call [`select_for_integers()`](Self::select_for_integers)
@@ -166,6 +189,7 @@ impl CodecSelector {
     /// compression ratio:
     /// - RunLength: Best for highly repetitive data (avg run length > 2)
     /// - DeltaBitPacked: Best for sorted/sequential integers
+    /// - FrameOfReference: Best for unsorted integers clustered in a narrow range
     /// - BitPacked: Best for small integers with limited range
     #[must_use]
     pub fn select_for_integers(values: &[u64]) -> CompressionCodec {
@@ -208,9 +232,21 @@ impl CodecSelector {
         }
 
         // Not sorted - try simple bit-packing
+        let min_value = values.iter().copied().min().unwrap_or(0);
         let max_value = values.iter().copied().max().unwrap_or(0);
         let bits_needed = BitPackedInts::bits_needed(max_value);
 
+        // Frame of reference pays a 64-bit base to pack only the range
+        let range_bits = BitPackedInts::bits_needed(max_value - min_value);
+        let saved_bits = (bits_needed - range_bits) as usize * values.len();
+        if saved_bits > 64 {
+            let for_ratio = 64.0 / range_bits as f64;
+            if rle_ratio > for_ratio && rle_ratio > 1.0 {
+                return CompressionCodec::RunLength;
+            }
+            return CompressionCodec::FrameOfReference { bits: range_bits };
+        }
+
         // Estimate BitPacked ratio
         let bitpack_ratio = if bits_needed > 0 {
             64.0 / bits_needed as f64
@@ -231,12 +267,19 @@ impl CodecSelector {
     }
 
     /// Selects the best codec for a slice of strings.
+    ///
+    /// Long runs of the same string (e.g. a sorted category column) pick
+    /// RunLength; otherwise low cardinality picks Dictionary.
     #[must_use]
     pub fn select_for_strings(values: &[&str]) -> CompressionCodec {
         if values.is_empty() || values.len() < 4 {
             return CompressionCodec::None;
         }
 
+        if RunLengthAnalyzer::average_run_length(values) >= STRING_RLE_MIN_AVG_RUN {
+            return CompressionCodec::RunLength;
+        }
+
         // Count unique values
         let unique: std::collections::HashSet<_> = values.iter().collect();
         let cardinality_ratio = unique.len() as f64 / values.len() as f64;
@@ -250,10 +293,16 @@ impl CodecSelector {
     }
 
     /// Selects the best codec for boolean values.
+    ///
+    /// BitVector unless runs are long enough for RunLength to beat one bit
+    /// per value.
     #[must_use]
-    pub fn select_for_booleans(_values: &[bool]) -> CompressionCodec {
-        // BitVector is always the best choice for booleans
-        CompressionCodec::BitVector
+    pub fn select_for_booleans(values: &[bool]) -> CompressionCodec {
+        if RunLengthAnalyzer::average_run_length(values) > BOOLEAN_RLE_MIN_AVG_RUN {
+            CompressionCodec::RunLength
+        } else {
+            CompressionCodec::BitVector
+        }
     }
 }
 
@@ -304,6 +353,18 @@ impl TypeSpecificCompressor {
                     },
                 }
             }
+            CompressionCodec::FrameOfReference { bits } => {
+                let encoded = FrameOfReference::encode(values);
+                CompressedData {
+                    codec: CompressionCodec::FrameOfReference { bits },
+                    uncompressed_size: values.len() * 8,
+                    data: encoded.to_bytes(),
+                    metadata: CompressionMetadata::FrameOfReference {
+                        base: encoded.base(),
+                        count: values.len(),
+                    },
+                }
+            }
             CompressionCodec::RunLength => {
                 let encoded = RunLengthEncoding::encode(values);
                 CompressedData {
@@ -331,6 +392,19 @@ impl TypeSpecificCompressor {
 
     /// Compresses boolean values.
     pub fn compress_booleans(values: &[bool]) -> CompressedData {
+        if CodecSelector::select_for_booleans(values) == CompressionCodec::RunLength {
+            let bits: Vec<u64> = values.iter().map(|&b| u64::from(b)).collect();
+            let encoded = RunLengthEncoding::encode(&bits);
+            return CompressedData {
+                codec: CompressionCodec::RunLength,
+                uncompressed_size: values.len(),
+                data: encoded.to_bytes(),
+                metadata: CompressionMetadata::RunLength {
+                    run_count: encoded.run_count(),
+                },
+            };
+        }
+
         let bitvec = BitVector::from_bools(values);
         CompressedData {
             codec: CompressionCodec::BitVector,
@@ -342,6 +416,67 @@ impl TypeSpecificCompressor {
         }
     }
 
+    /// Compresses strings using the optimal codec.
+    ///
+    /// Dictionary and RunLength both store the distinct strings once and
+    /// encode each value as its dictionary code; RunLength then stores the
+    /// codes as (code, run length) pairs.
+    pub fn compress_strings(values: &[&str]) -> CompressedData {
+        let codec = CodecSelector::select_for_strings(values);
+        let uncompressed_size = values.iter().map(|v| v.len()).sum();
+
+        match codec {
+            CompressionCodec::None => {
+                let mut data = Vec::new();
+                write_strings(&mut data, values);
+                CompressedData {
+                    codec,
+                    uncompressed_size,
+                    data,
+                    metadata: CompressionMetadata::None,
+                }
+            }
+            CompressionCodec::Dictionary | CompressionCodec::RunLength => {
+                let mut dictionary: Vec<&str> = Vec::new();
+                let mut positions: std::collections::HashMap<&str, u64> =
+                    std::collections::HashMap::new();
+                let codes: Vec<u64> = values
+                    .iter()
+                    .map(|&v| {
+                        *positions.entry(v).or_insert_with(|| {
+                            dictionary.push(v);
+                            dictionary.len() as u64 - 1
+                        })
+                    })
+                    .collect();
+
+                let mut data = Vec::new();
+                write_strings(&mut data, &dictionary);
+                let metadata = if codec == CompressionCodec::RunLength {
+                    let encoded = RunLengthEncoding::encode(&codes);
+                    data.extend_from_slice(&encoded.to_bytes());
+                    CompressionMetadata::RunLength {
+                        run_count: encoded.run_count(),
+                    }
+                } else {
+                    data.extend_from_slice(&(codes.len() as u64).to_le_bytes());
+                    for code in codes {
+                        data.extend_from_slice(&(code as u32).to_le_bytes());
+                    }
+                    CompressionMetadata::Dictionary { dict_id: 0 }
+                };
+
+                CompressedData {
+                    codec,
+                    uncompressed_size,
+                    data,
+                    metadata,
+                }
+            }
+            _ => unreachable!("Unexpected codec for strings"),
+        }
+    }
+
     /// Decompresses u64 values.
     pub fn decompress_integers(data: &CompressedData) -> io::Result<Vec<u64>> {
         match data.codec {
@@ -360,6 +495,10 @@ impl TypeSpecificCompressor {
                 let packed = BitPackedInts::from_bytes(&data.data)?;
                 Ok(packed.unpack())
             }
+            CompressionCodec::FrameOfReference { .. } => {
+                let encoded = FrameOfReference::from_bytes(&data.data)?;
+                Ok(encoded.decode())
+            }
             CompressionCodec::RunLength => {
                 let encoded = RunLengthEncoding::from_bytes(&data.data)?;
                 Ok(encoded.decode())
@@ -378,12 +517,86 @@ impl TypeSpecificCompressor {
                 let bitvec = BitVector::from_bytes(&data.data)?;
                 Ok(bitvec.to_bools())
             }
+            CompressionCodec::RunLength => {
+                let encoded = RunLengthEncoding::from_bytes(&data.data)?;
+                Ok(encoded.iter().map(|bit| bit != 0).collect())
+            }
             _ => Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 "Invalid codec for boolean decompression",
             )),
         }
     }
+
+    /// Decompresses strings.
+    pub fn decompress_strings(data: &CompressedData) -> io::Result<Vec<String>> {
+        let mut cursor = io::Cursor::new(data.data.as_slice());
+        match data.codec {
+            CompressionCodec::None => read_strings(&mut cursor),
+            CompressionCodec::Dictionary | CompressionCodec::RunLength => {
+                let dictionary = read_strings(&mut cursor)?;
+                let rest = &data.data[cursor.position() as usize..];
+                let codes: Vec<u64> = if data.codec == CompressionCodec::RunLength {
+                    RunLengthEncoding::from_bytes(rest)?.decode()
+                } else {
+                    let mut cursor = io::Cursor::new(rest);
+                    let count = read_u64(&mut cursor)? as usize;
+                    let mut buf = [0u8; 4];
+                    let mut codes = Vec::with_capacity(count);
+                    for _ in 0..count {
+                        cursor.read_exact(&mut buf)?;
+                        codes.push(u64::from(u32::from_le_bytes(buf)));
+                    }
+                    codes
+                };
+                codes
+                    .into_iter()
+                    .map(|code| {
+                        dictionary.get(code as usize).cloned().ok_or_else(|| {
+                            io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                "Dictionary code out of range",
+                            )
+                        })
+                    })
+                    .collect()
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Invalid codec for string decompression",
+            )),
+        }
+    }
+}
+
+/// Writes a count followed by length-prefixed UTF-8 strings.
+fn write_strings(out: &mut Vec<u8>, values: &[&str]) {
+    out.extend_from_slice(&(values.len() as u64).to_le_bytes());
+    for value in values {
+        out.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        out.extend_from_slice(value.as_bytes());
+    }
+}
+
+/// Reads strings written by [`write_strings`].
+fn read_strings(cursor: &mut io::Cursor<&[u8]>) -> io::Result<Vec<String>> {
+    let count = read_u64(cursor)? as usize;
+    let mut values = Vec::with_capacity(count.min(1 << 16));
+    for _ in 0..count {
+        let len = read_u64(cursor)? as usize;
+        let mut bytes = vec![0u8; len];
+        cursor.read_exact(&mut bytes)?;
+        let value =
+            String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        values.push(value);
+    }
+    Ok(values)
+}
+
+fn read_u64(cursor: &mut io::Cursor<&[u8]>) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    cursor.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
 }
 
 #[cfg(test)]
@@ -476,6 +689,10 @@ mod tests {
             CompressionCodec::DeltaBitPacked { bits: 4 }.name(),
             "DeltaBitPacked"
         );
+        assert_eq!(
+            CompressionCodec::FrameOfReference { bits: 4 }.name(),
+            "FrameOfReference"
+        );
         assert_eq!(CompressionCodec::Dictionary.name(), "Dictionary");
         assert_eq!(CompressionCodec::BitVector.name(), "BitVector");
         assert_eq!(CompressionCodec::RunLength.name(), "RunLength");
@@ -541,4 +758,110 @@ mod tests {
         let codec = CodecSelector::select_for_integers(&constant);
         assert_eq!(codec, CompressionCodec::RunLength);
     }
+
+    #[test]
+    fn test_codec_selection_strings_prefers_runlength_for_long_runs() {
+        // Sorted low-cardinality column: dictionary would also qualify
+        let mut sorted = vec!["active"; 50];
+        sorted.extend(vec!["inactive"; 50]);
+        assert_eq!(
+            CodecSelector::select_for_strings(&sorted),
+            CompressionCodec::RunLength
+        );
+
+        // Same values interleaved have short runs and stay dictionary-encoded
+        let interleaved: Vec<&str> = (0..100)
+            .map(|i| if i % 2 == 0 { "active" } else { "inactive" })
+            .collect();
+        assert_eq!(
+            CodecSelector::select_for_strings(&interleaved),
+            CompressionCodec::Dictionary
+        );
+    }
+
+    #[test]
+    fn test_compress_decompress_strings() {
+        let mut runs = vec!["Person"; 40];
+        runs.extend(vec!["Company"; 30]);
+        runs.extend(vec!["Person"; 30]);
+        let interleaved = vec!["a", "b", "a", "c", "b", "a", "a", "b"];
+        let unique = vec!["alpha", "beta", "gamma", "", "δέλτα"];
+
+        for (values, codec) in [
+            (runs, CompressionCodec::RunLength),
+            (interleaved, CompressionCodec::Dictionary),
+            (unique, CompressionCodec::None),
+        ] {
+            let compressed = TypeSpecificCompressor::compress_strings(&values);
+            assert_eq!(compressed.codec, codec);
+
+            let decompressed = TypeSpecificCompressor::decompress_strings(&compressed).unwrap();
+            assert_eq!(decompressed, values);
+        }
+    }
+
+    #[test]
+    fn test_compress_strings_runlength_metadata() {
+        let mut values = vec!["x"; 100];
+        values.extend(vec!["y"; 100]);
+        values.extend(vec!["x"; 100]);
+
+        let compressed = TypeSpecificCompressor::compress_strings(&values);
+        assert!(matches!(
+            compressed.metadata,
+            CompressionMetadata::RunLength { run_count: 3 }
+        ));
+        assert!(compressed.compression_ratio() > 1.0);
+    }
+
+    #[test]
+    fn test_compress_decompress_boolean_runs() {
+        let mut values = vec![false; 500];
+        values.extend(vec![true; 500]);
+
+        assert_eq!(
+            CodecSelector::select_for_booleans(&values),
+            CompressionCodec::RunLength
+        );
+        let compressed = TypeSpecificCompressor::compress_booleans(&values);
+        assert_eq!(compressed.codec, CompressionCodec::RunLength);
+
+        let decompressed = TypeSpecificCompressor::decompress_booleans(&compressed).unwrap();
+        assert_eq!(decompressed, values);
+    }
+
+    #[test]
+    fn test_frame_of_reference_for_clustered_unsorted_integers() {
+        // Unsorted readings clustered around one million
+        let values: Vec<u64> = (0..200u64).map(|i| 1_000_000 + (i * 37) % 100).collect();
+
+        let codec = CodecSelector::select_for_integers(&values);
+        assert_eq!(codec, CompressionCodec::FrameOfReference { bits: 7 });
+
+        let compressed = TypeSpecificCompressor::compress_integers(&values);
+        assert!(matches!(
+            compressed.metadata,
+            CompressionMetadata::FrameOfReference {
+                base: 1_000_000,
+                count: 200
+            }
+        ));
+        let decompressed = TypeSpecificCompressor::decompress_integers(&compressed).unwrap();
+        assert_eq!(decompressed, values);
+
+        // Packing the raw values needs 20 bits each instead of 7
+        let plain = BitPackedInts::pack(&values).to_bytes();
+        assert!(compressed.data.len() < plain.len());
+        assert!(compressed.compression_ratio() > 64.0 / 20.0);
+    }
+
+    #[test]
+    fn test_frame_of_reference_not_chosen_when_range_starts_near_zero() {
+        // Small values gain nothing from a base
+        let values: Vec<u64> = (0..100u64).map(|i| (i * 7) % 50).collect();
+        assert!(matches!(
+            CodecSelector::select_for_integers(&values),
+            CompressionCodec::BitPacked { .. }
+        ));
+    }
 }
@@ -8,8 +8,10 @@
 //! | --------- | ---------- | --------------- |
 //! | Sorted integers (IDs, timestamps) | [`DeltaBitPacked`] | 5-20x smaller |
 //! | Small integers (ages, counts) | [`BitPackedInts`] | 2-16x smaller |
+//! | Clustered unsorted integers (timestamps in a window) | [`FrameOfReference`] | 2-30x smaller |
 //! | Repeated strings (labels, categories) | [`DictionaryEncoding`] | 2-50x smaller |
 //! | Booleans (flags, markers) | [`BitVector`] | 8x smaller |
+//! | Long runs (sorted categories, constant flags) | [`RunLengthEncoding`] | 10-100x smaller |
 //!
 //! Use [`CodecSelector`] to automatically pick the best codec for your data,
 //! or choose manually when you know your data characteristics.
@@ -37,7 +39,7 @@ pub mod dictionary;
 pub mod runlength;
 
 // Re-export commonly used types
-pub use bitpack::{BitPackedInts, DeltaBitPacked};
+pub use bitpack::{BitPackedInts, DeltaBitPacked, FrameOfReference};
 pub use bitvec::BitVector;
 pub use codec::{
     CodecSelector, CompressedData, CompressionCodec, CompressionMetadata, TypeSpecificCompressor,
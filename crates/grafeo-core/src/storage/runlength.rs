@@ -398,19 +398,15 @@ impl RunLengthAnalyzer {
     }
 
     /// Returns the average run length in the data.
+    ///
+    /// Works for any comparable values, e.g. strings or booleans.
     #[must_use]
-    pub fn average_run_length(values: &[u64]) -> f64 {
+    pub fn average_run_length<T: PartialEq>(values: &[T]) -> f64 {
         if values.is_empty() {
             return 0.0;
         }
 
-        let mut run_count = 1usize;
-        for i in 1..values.len() {
-            if values[i] != values[i - 1] {
-                run_count += 1;
-            }
-        }
-
+        let run_count = 1 + values.windows(2).filter(|w| w[0] != w[1]).count();
         values.len() as f64 / run_count as f64
     }
 }
@@ -6,6 +6,7 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use grafeo_common::types::LogicalType;
 use serde::{Deserialize, Serialize};
 
 /// Database mode - either LPG (Labeled Property Graph) or RDF (Triple Store).
@@ -85,6 +86,9 @@ pub struct LabelInfo {
     pub name: String,
     /// Number of nodes with this label.
     pub count: usize,
+    /// Property keys set on nodes with this label, sorted by name.
+    #[serde(default)]
+    pub properties: Vec<PropertyInfo>,
 }
 
 /// Information about an edge type.
@@ -94,6 +98,29 @@ pub struct EdgeTypeInfo {
     pub name: String,
     /// Number of edges with this type.
     pub count: usize,
+    /// Distinct `(source label, target label)` pairs seen on these edges.
+    #[serde(default)]
+    pub endpoints: Vec<(String, String)>,
+    /// Property keys set on edges with this type, sorted by name.
+    #[serde(default)]
+    pub properties: Vec<PropertyInfo>,
+}
+
+/// Information about a property key of a label or edge type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropertyInfo {
+    /// The property key.
+    pub name: String,
+    /// Common type of the non-null values, `Any` if they don't share one.
+    pub data_type: LogicalType,
+    /// Number of nodes or edges that have the property.
+    pub count: usize,
+    /// Whether a unique constraint covers the property.
+    #[serde(default)]
+    pub unique: bool,
+    /// Whether a NOT NULL constraint covers the property.
+    #[serde(default)]
+    pub required: bool,
 }
 
 /// Schema information for RDF databases.
@@ -213,6 +240,8 @@ pub enum DumpFormat {
     Turtle,
     /// JSON Lines format.
     Json,
+    /// GraphML, for graph visualization tools like Gephi and yEd.
+    GraphMl,
 }
 
 impl Default for DumpFormat {
@@ -227,6 +256,7 @@ impl std::fmt::Display for DumpFormat {
             DumpFormat::Parquet => write!(f, "parquet"),
             DumpFormat::Turtle => write!(f, "turtle"),
             DumpFormat::Json => write!(f, "json"),
+            DumpFormat::GraphMl => write!(f, "graphml"),
         }
     }
 }
@@ -239,6 +269,7 @@ impl std::str::FromStr for DumpFormat {
             "parquet" => Ok(DumpFormat::Parquet),
             "turtle" | "ttl" => Ok(DumpFormat::Turtle),
             "json" | "jsonl" => Ok(DumpFormat::Json),
+            "graphml" => Ok(DumpFormat::GraphMl),
             _ => Err(format!("Unknown dump format: {}", s)),
         }
     }
@@ -257,6 +288,30 @@ pub struct CompactionStats {
     pub duration_ms: u64,
 }
 
+/// Result of rebuilding the optimizer statistics with `analyze()`.
+///
+/// Row counts are the ones the optimizer worked with before and after the
+/// rebuild, so a large difference means its estimates had drifted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyzeStats {
+    /// Node count in the statistics before the rebuild.
+    pub nodes_before: u64,
+    /// Node count in the statistics after the rebuild.
+    pub nodes_after: u64,
+    /// Edge count in the statistics before the rebuild.
+    pub edges_before: u64,
+    /// Edge count in the statistics after the rebuild.
+    pub edges_after: u64,
+    /// Number of labels with statistics.
+    pub label_count: usize,
+    /// Number of edge types with statistics.
+    pub edge_type_count: usize,
+    /// Number of property keys with statistics.
+    pub property_count: usize,
+    /// Duration in milliseconds.
+    pub duration_ms: u64,
+}
+
 /// Metadata for dump files.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DumpMetadata {
@@ -10,14 +10,24 @@
 //! | Property keys | Maps "name" → PropertyKeyId |
 //! | Edge types | Maps "KNOWS" → EdgeTypeId |
 //! | Indexes | Which properties are indexed for fast lookups |
+//! | Constraints | Which properties must be unique or present |
+//!
+//! Unique constraints are enforced by the mutation operators, which check
+//! each node property write against the catalog through
+//! [`ConstraintChecker`] before the write lands. A unique constraint probes
+//! the store's hash index on its property, which the write itself updates,
+//! so a value is only indexed once every check on it has passed.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
 
 use parking_lot::RwLock;
 
-use grafeo_common::types::{EdgeTypeId, IndexId, LabelId, PropertyKeyId};
+use grafeo_common::types::{EdgeTypeId, IndexId, LabelId, NodeId, PropertyKeyId, TxId, Value};
+use grafeo_core::execution::operators::{ConstraintChecker, HashKey, OperatorError};
+use grafeo_core::graph::lpg::LpgStore;
+use grafeo_core::index::HashIndex;
 
 /// The database's schema dictionary - maps names to compact internal IDs.
 ///
@@ -154,6 +164,21 @@ impl Catalog {
         self.edge_types.all_names()
     }
 
+    /// Marks an edge type as undirected, creating the type if needed.
+    pub fn set_edge_type_undirected(&self, name: &str) -> EdgeTypeId {
+        let id = self.edge_types.get_or_create(name);
+        self.edge_types.undirected.write().insert(id);
+        id
+    }
+
+    /// Returns `true` if the edge type was marked undirected.
+    #[must_use]
+    pub fn is_edge_type_undirected(&self, name: &str) -> bool {
+        self.edge_types
+            .get_id(name)
+            .is_some_and(|id| self.edge_types.undirected.read().contains(&id))
+    }
+
     // === Index Operations ===
 
     /// Creates a new index on a label and property key.
@@ -163,7 +188,81 @@ impl Catalog {
         property_key: PropertyKeyId,
         index_type: IndexType,
     ) -> IndexId {
-        self.indexes.create(label, property_key, index_type)
+        self.indexes.create(label, vec![property_key], index_type)
+    }
+
+    /// Creates a hash index on `label`'s `property`, indexing the values the
+    /// nodes in `store` already have.
+    ///
+    /// The store keeps the index current from then on, and joins on the
+    /// property probe it instead of scanning the label. Returns the existing
+    /// index if the pair already has a hash index.
+    pub fn create_hash_index(&self, store: &LpgStore, label: &str, property: &str) -> IndexId {
+        let label_id = self.get_or_create_label(label);
+        let property_key = self.get_or_create_property_key(property);
+        store.create_property_index(label, property);
+        let existing = self
+            .indexes_for_label_property(label_id, property_key)
+            .into_iter()
+            .find(|&id| {
+                self.get_index(id).is_some_and(|definition| {
+                    definition.index_type == IndexType::Hash && !definition.is_composite()
+                })
+            });
+        existing.unwrap_or_else(|| self.create_index(label_id, property_key, IndexType::Hash))
+    }
+
+    /// Creates a trie index on `label`'s `property`, indexing the string
+    /// values the nodes in `store` already have.
+    ///
+    /// The store keeps the index current from then on, and `STARTS WITH`
+    /// filters on the property look their prefix up in it. Returns the
+    /// existing index if the pair already has a trie index.
+    pub fn create_text_index(
+        &self,
+        store: &LpgStore,
+        label: &str,
+        property: &str,
+        case_sensitive: bool,
+    ) -> IndexId {
+        let label_id = self.get_or_create_label(label);
+        let property_key = self.get_or_create_property_key(property);
+        store.create_text_index(label, property, case_sensitive);
+        self.find_text_index(label_id, property_key)
+            .unwrap_or_else(|| {
+                self.create_index(label_id, property_key, IndexType::Trie { case_sensitive })
+            })
+    }
+
+    /// Creates a BTree index over several properties of `label`, indexing
+    /// the values the nodes in `store` already have.
+    ///
+    /// Key order matters: the index sorts by the first property, then the
+    /// second, and so on, so it can serve equality lookups on any leading
+    /// run of `properties`. The store keeps the index current from then on.
+    /// Returns the existing index if one covers exactly these properties.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `properties` is empty.
+    pub fn create_composite_index(
+        &self,
+        store: &LpgStore,
+        label: &str,
+        properties: &[&str],
+    ) -> IndexId {
+        assert!(
+            !properties.is_empty(),
+            "an index needs at least one property key"
+        );
+        let label_id = self.get_or_create_label(label);
+        let keys: Vec<PropertyKeyId> = properties
+            .iter()
+            .map(|property| self.get_or_create_property_key(property))
+            .collect();
+        store.create_composite_index(label, properties);
+        self.find_index(label_id, &keys)
+            .unwrap_or_else(|| self.indexes.create(label_id, keys, IndexType::BTree))
     }
 
     /// Drops an index by ID.
@@ -184,6 +283,9 @@ impl Catalog {
     }
 
     /// Finds indexes for a given label and property key.
+    ///
+    /// Composite indexes are listed under their leading key only, since
+    /// that is the only column they can serve on its own.
     #[must_use]
     pub fn indexes_for_label_property(
         &self,
@@ -193,6 +295,35 @@ impl Catalog {
         self.indexes.for_label_property(label, property_key)
     }
 
+    /// Finds an index on exactly these property keys, in this order.
+    #[must_use]
+    pub fn find_index(&self, label: LabelId, property_keys: &[PropertyKeyId]) -> Option<IndexId> {
+        self.indexes.find_exact(label, property_keys)
+    }
+
+    /// Picks the index that best serves equality predicates on a label.
+    ///
+    /// An index matches when its leading keys are all among
+    /// `equality_keys`; the match with the longest bound prefix wins. A
+    /// composite index is never chosen when its first key is unbound, so a
+    /// predicate on only the second column of `(a, b)` finds nothing. Hash
+    /// indexes only match when every key is bound, and full-text indexes
+    /// never serve equality lookups.
+    #[must_use]
+    pub fn find_prefix_index(
+        &self,
+        label: LabelId,
+        equality_keys: &[PropertyKeyId],
+    ) -> Option<IndexPrefixMatch> {
+        self.indexes.find_prefix(label, equality_keys)
+    }
+
+    /// Finds a trie index that can serve `STARTS WITH` on a label's property.
+    #[must_use]
+    pub fn find_text_index(&self, label: LabelId, property_key: PropertyKeyId) -> Option<IndexId> {
+        self.indexes.find_text(label, property_key)
+    }
+
     /// Returns the number of indexes.
     #[must_use]
     pub fn index_count(&self) -> usize {
@@ -250,6 +381,261 @@ impl Catalog {
             .as_ref()
             .is_some_and(|s| s.is_property_unique(label, property_key))
     }
+
+    /// Makes `property` unique among `label` nodes, indexing the values the
+    /// nodes in `store` already have.
+    ///
+    /// A hash index is created for the pair too, unless one exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CatalogError::DuplicateValue`] naming the value if two nodes
+    /// already share one, in which case no constraint is added, or an error
+    /// if schema is not enabled or the constraint already exists.
+    pub fn create_unique_constraint(
+        &self,
+        store: &LpgStore,
+        label: &str,
+        property: &str,
+    ) -> Result<(), CatalogError> {
+        let Some(schema) = &self.schema else {
+            return Err(CatalogError::SchemaNotEnabled);
+        };
+        let label_id = self.get_or_create_label(label);
+        let property_key = self.get_or_create_property_key(property);
+        if schema.is_property_unique(label_id, property_key) {
+            return Err(CatalogError::ConstraintAlreadyExists);
+        }
+
+        let index = HashIndex::new();
+        for node in store.nodes_with_label(label) {
+            let Some(value) = node.properties.get(&property.into()) else {
+                continue;
+            };
+            if value.is_null() {
+                continue;
+            }
+            if index.insert(HashKey::from_value(value), node.id).is_some() {
+                return Err(CatalogError::DuplicateValue {
+                    label: label.to_string(),
+                    property: property.to_string(),
+                    value: value.to_string(),
+                });
+            }
+        }
+
+        schema.add_unique_constraint(label_id, property_key)?;
+        self.create_hash_index(store, label, property);
+        Ok(())
+    }
+
+    /// Requires every `label` node to have a non-null `property`, checking
+    /// the nodes in `store` first.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CatalogError::MissingValue`] naming a node without the
+    /// property, in which case no constraint is added, or an error if
+    /// schema is not enabled or the constraint already exists.
+    pub fn create_required_constraint(
+        &self,
+        store: &LpgStore,
+        label: &str,
+        property: &str,
+    ) -> Result<(), CatalogError> {
+        let Some(schema) = &self.schema else {
+            return Err(CatalogError::SchemaNotEnabled);
+        };
+        let label_id = self.get_or_create_label(label);
+        let property_key = self.get_or_create_property_key(property);
+        if schema.is_property_required(label_id, property_key) {
+            return Err(CatalogError::ConstraintAlreadyExists);
+        }
+
+        let key = property.into();
+        if let Some(node) = store
+            .nodes_with_label(label)
+            .find(|node| node.properties.get(&key).is_none_or(Value::is_null))
+        {
+            return Err(CatalogError::MissingValue {
+                label: label.to_string(),
+                property: property.to_string(),
+                node: node.id,
+            });
+        }
+        schema.add_required_property(label_id, property_key)
+    }
+}
+
+impl Catalog {
+    /// Returns a node other than `node` that `tx` sees holding `value` for
+    /// `key` under `label`.
+    ///
+    /// Probes the store's index on the pair, which every unique constraint
+    /// created with a store has, and scans the label otherwise.
+    fn unique_holder(
+        store: &LpgStore,
+        node: NodeId,
+        label: &str,
+        key: &str,
+        value: &Value,
+        tx: TxId,
+    ) -> Option<NodeId> {
+        let epoch = store.current_epoch();
+        let holders = store
+            .find_nodes_by_property_index(label, key, value, Some(epoch), Some(tx))
+            .unwrap_or_else(|| {
+                let wanted = HashKey::from_value(value);
+                store
+                    .nodes_by_label_versioned(label, epoch, tx)
+                    .into_iter()
+                    .filter(|&id| {
+                        store
+                            .get_node_versioned(id, epoch, tx)
+                            .is_some_and(|other| {
+                                other
+                                    .get_property(key)
+                                    .is_some_and(|v| HashKey::from_value(v) == wanted)
+                            })
+                    })
+                    .collect()
+            });
+        holders.into_iter().find(|&holder| holder != node)
+    }
+}
+
+impl ConstraintChecker for Catalog {
+    /// Checks that `properties` gives every property `node`'s labels
+    /// require a value, then checks each property.
+    fn check_new_node(
+        &self,
+        store: &LpgStore,
+        node: NodeId,
+        properties: &[(&str, Value)],
+        tx: TxId,
+    ) -> Result<(), OperatorError> {
+        let Some(schema) = &self.schema else {
+            return Ok(());
+        };
+        let Some(target) = store.get_node_versioned(node, store.current_epoch(), tx) else {
+            return Ok(());
+        };
+
+        for label in &target.labels {
+            let Some(label_id) = self.get_label_id(label) else {
+                continue;
+            };
+            for property_key in schema.required_properties_of(label_id) {
+                let Some(key) = self.get_property_key_name(property_key) else {
+                    continue;
+                };
+                let present = properties
+                    .iter()
+                    .any(|(name, value)| *name == &*key && !value.is_null());
+                if !present {
+                    return Err(OperatorError::ConstraintViolation(format!(
+                        "node {node} must have a value for :{label}({key})"
+                    )));
+                }
+            }
+        }
+
+        for (key, value) in properties {
+            self.check_node_property(store, node, key, value, tx)?;
+        }
+        Ok(())
+    }
+
+    /// Checks `value` against the constraints of `node`'s labels: a null
+    /// fails if the property is required, and any other value if another
+    /// node `tx` sees with the label already holds it.
+    fn check_node_property(
+        &self,
+        store: &LpgStore,
+        node: NodeId,
+        key: &str,
+        value: &Value,
+        tx: TxId,
+    ) -> Result<(), OperatorError> {
+        let Some(schema) = &self.schema else {
+            return Ok(());
+        };
+        let Some(property_key) = self.get_property_key_id(key) else {
+            return Ok(());
+        };
+        let Some(target) = store.get_node_versioned(node, store.current_epoch(), tx) else {
+            return Ok(());
+        };
+
+        for label in &target.labels {
+            let Some(label_id) = self.get_label_id(label) else {
+                continue;
+            };
+            if value.is_null() {
+                if schema.is_property_required(label_id, property_key) {
+                    return Err(OperatorError::ConstraintViolation(format!(
+                        "node {node} must have a value for :{label}({key})"
+                    )));
+                }
+                continue;
+            }
+            if !schema.is_property_unique(label_id, property_key) {
+                continue;
+            }
+            if let Some(holder) = Self::unique_holder(store, node, label, key, value, tx) {
+                return Err(OperatorError::ConstraintViolation(format!(
+                    "node {holder} already has :{label}({key}) = {value}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the properties `node` already has against the constraints of
+    /// `label`: each it requires must be set, and each it makes unique must
+    /// be free.
+    fn check_new_label(
+        &self,
+        store: &LpgStore,
+        node: NodeId,
+        label: &str,
+        tx: TxId,
+    ) -> Result<(), OperatorError> {
+        let (Some(schema), Some(label_id)) = (&self.schema, self.get_label_id(label)) else {
+            return Ok(());
+        };
+        let Some(target) = store.get_node_versioned(node, store.current_epoch(), tx) else {
+            return Ok(());
+        };
+        if target.has_label(label) {
+            return Ok(());
+        }
+
+        for property_key in schema.required_properties_of(label_id) {
+            let Some(key) = self.get_property_key_name(property_key) else {
+                continue;
+            };
+            if target.get_property(&key).is_none_or(Value::is_null) {
+                return Err(OperatorError::ConstraintViolation(format!(
+                    "node {node} must have a value for :{label}({key})"
+                )));
+            }
+        }
+        for (key, value) in &target.properties {
+            let Some(property_key) = self.get_property_key_id(key.as_str()) else {
+                continue;
+            };
+            if value.is_null() || !schema.is_property_unique(label_id, property_key) {
+                continue;
+            }
+            if let Some(holder) = Self::unique_holder(store, node, label, key.as_str(), value, tx) {
+                return Err(OperatorError::ConstraintViolation(format!(
+                    "node {holder} already has :{label}({key}) = {value}"
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for Catalog {
@@ -385,6 +771,8 @@ struct EdgeTypeCatalog {
     name_to_id: RwLock<HashMap<Arc<str>, EdgeTypeId>>,
     id_to_name: RwLock<Vec<Arc<str>>>,
     next_id: AtomicU32,
+    /// Edge types whose edges match from either endpoint.
+    undirected: RwLock<HashSet<EdgeTypeId>>,
 }
 
 impl EdgeTypeCatalog {
@@ -393,6 +781,7 @@ impl EdgeTypeCatalog {
             name_to_id: RwLock::new(HashMap::new()),
             id_to_name: RwLock::new(Vec::new()),
             next_id: AtomicU32::new(0),
+            undirected: RwLock::new(HashSet::new()),
         }
     }
 
@@ -449,6 +838,11 @@ pub enum IndexType {
     BTree,
     /// Full-text index for text search.
     FullText,
+    /// Character trie over string values for `STARTS WITH` lookups.
+    Trie {
+        /// Whether lookups distinguish upper and lower case.
+        case_sensitive: bool,
+    },
 }
 
 /// Index definition.
@@ -458,12 +852,33 @@ pub struct IndexDefinition {
     pub id: IndexId,
     /// The label this index applies to.
     pub label: LabelId,
-    /// The property key being indexed.
+    /// The property key being indexed (the leading key for composite indexes).
     pub property_key: PropertyKeyId,
+    /// All indexed property keys in key order; starts with `property_key`.
+    pub property_keys: Vec<PropertyKeyId>,
     /// The type of index.
     pub index_type: IndexType,
 }
 
+impl IndexDefinition {
+    /// Returns true if this index covers more than one property.
+    #[must_use]
+    pub fn is_composite(&self) -> bool {
+        self.property_keys.len() > 1
+    }
+}
+
+/// An index chosen to serve equality predicates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexPrefixMatch {
+    /// The chosen index.
+    pub index_id: IndexId,
+    /// How many leading index keys are bound by the predicates.
+    pub prefix_len: usize,
+    /// Whether every key of the index is bound (a full point lookup).
+    pub full_key: bool,
+}
+
 /// Manages index definitions.
 struct IndexCatalog {
     indexes: RwLock<HashMap<IndexId, IndexDefinition>>,
@@ -485,14 +900,16 @@ impl IndexCatalog {
     fn create(
         &self,
         label: LabelId,
-        property_key: PropertyKeyId,
+        property_keys: Vec<PropertyKeyId>,
         index_type: IndexType,
     ) -> IndexId {
         let id = IndexId::new(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let property_key = property_keys[0];
         let definition = IndexDefinition {
             id,
             label,
             property_key,
+            property_keys,
             index_type,
         };
 
@@ -552,6 +969,61 @@ impl IndexCatalog {
             .unwrap_or_default()
     }
 
+    fn find_exact(&self, label: LabelId, property_keys: &[PropertyKeyId]) -> Option<IndexId> {
+        let indexes = self.indexes.read();
+        self.for_label(label).into_iter().find(|id| {
+            indexes
+                .get(id)
+                .is_some_and(|d| d.property_keys == property_keys)
+        })
+    }
+
+    fn find_prefix(
+        &self,
+        label: LabelId,
+        equality_keys: &[PropertyKeyId],
+    ) -> Option<IndexPrefixMatch> {
+        let indexes = self.indexes.read();
+        let mut best: Option<IndexPrefixMatch> = None;
+
+        for id in self.for_label(label) {
+            let Some(definition) = indexes.get(&id) else {
+                continue;
+            };
+            let prefix_len = definition
+                .property_keys
+                .iter()
+                .take_while(|key| equality_keys.contains(key))
+                .count();
+            let full_key = prefix_len == definition.property_keys.len();
+            let usable = match definition.index_type {
+                IndexType::BTree => prefix_len > 0,
+                IndexType::Hash => full_key,
+                IndexType::FullText | IndexType::Trie { .. } => false,
+            };
+            if usable && best.is_none_or(|b| prefix_len > b.prefix_len) {
+                best = Some(IndexPrefixMatch {
+                    index_id: id,
+                    prefix_len,
+                    full_key,
+                });
+            }
+        }
+
+        best
+    }
+
+    fn find_text(&self, label: LabelId, property_key: PropertyKeyId) -> Option<IndexId> {
+        let indexes = self.indexes.read();
+        self.for_label_property(label, property_key)
+            .into_iter()
+            .find(|id| {
+                indexes.get(id).is_some_and(|d| {
+                    matches!(d.index_type, IndexType::Trie { .. }) && !d.is_composite()
+                })
+            })
+    }
+
     fn count(&self) -> usize {
         self.indexes.read().len()
     }
@@ -609,6 +1081,15 @@ impl SchemaCatalog {
             .contains_key(&(label, property_key))
     }
 
+    fn required_properties_of(&self, label: LabelId) -> Vec<PropertyKeyId> {
+        self.required_properties
+            .read()
+            .keys()
+            .filter(|(required_label, _)| *required_label == label)
+            .map(|&(_, property_key)| property_key)
+            .collect()
+    }
+
     fn is_property_unique(&self, label: LabelId, property_key: PropertyKeyId) -> bool {
         self.unique_constraints
             .read()
@@ -633,6 +1114,24 @@ pub enum CatalogError {
     EdgeTypeNotFound(String),
     /// The index does not exist.
     IndexNotFound(IndexId),
+    /// Existing data breaks the constraint being added.
+    DuplicateValue {
+        /// Label of the constraint.
+        label: String,
+        /// Property of the constraint.
+        property: String,
+        /// The value more than one node has.
+        value: String,
+    },
+    /// A node lacks the property a constraint being added requires.
+    MissingValue {
+        /// Label of the constraint.
+        label: String,
+        /// Property of the constraint.
+        property: String,
+        /// A node without the property.
+        node: NodeId,
+    },
 }
 
 impl std::fmt::Display for CatalogError {
@@ -644,6 +1143,22 @@ impl std::fmt::Display for CatalogError {
             Self::PropertyKeyNotFound(name) => write!(f, "Property key not found: {name}"),
             Self::EdgeTypeNotFound(name) => write!(f, "Edge type not found: {name}"),
             Self::IndexNotFound(id) => write!(f, "Index not found: {id}"),
+            Self::DuplicateValue {
+                label,
+                property,
+                value,
+            } => write!(
+                f,
+                "Cannot make :{label}({property}) unique: {value} appears more than once"
+            ),
+            Self::MissingValue {
+                label,
+                property,
+                node,
+            } => write!(
+                f,
+                "Cannot make :{label}({property}) required: node {node} has no value"
+            ),
         }
     }
 }
@@ -717,6 +1232,11 @@ mod tests {
             Some("KNOWS")
         );
         assert_eq!(catalog.edge_type_count(), 2);
+
+        assert!(!catalog.is_edge_type_undirected("KNOWS"));
+        assert_eq!(catalog.set_edge_type_undirected("KNOWS"), knows_id);
+        assert!(catalog.is_edge_type_undirected("KNOWS"));
+        assert!(!catalog.is_edge_type_undirected("WORKS_AT"));
     }
 
     #[test]
@@ -929,6 +1449,167 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_catalog_composite_index_round_trip() {
+        let catalog = Catalog::new();
+
+        let person = catalog.get_or_create_label("Person");
+        let last = catalog.get_or_create_property_key("lastName");
+        let first = catalog.get_or_create_property_key("firstName");
+
+        let store = LpgStore::new();
+        let idx = catalog.create_composite_index(&store, "Person", &["lastName", "firstName"]);
+        assert!(store.has_composite_index("Person", &["lastName", "firstName"]));
+        assert_eq!(
+            catalog.create_composite_index(&store, "Person", &["lastName", "firstName"]),
+            idx
+        );
+
+        let def = catalog.get_index(idx).unwrap();
+        assert_eq!(def.index_type, IndexType::BTree);
+        assert_eq!(def.label, person);
+        assert_eq!(def.property_key, last);
+        assert_eq!(def.property_keys, vec![last, first]);
+        assert!(def.is_composite());
+
+        // Listed under the leading key only
+        assert_eq!(catalog.indexes_for_label_property(person, last), vec![idx]);
+        assert!(catalog.indexes_for_label_property(person, first).is_empty());
+
+        // Exact lookup respects key order
+        assert_eq!(catalog.find_index(person, &[last, first]), Some(idx));
+        assert_eq!(catalog.find_index(person, &[first, last]), None);
+        assert_eq!(catalog.find_index(person, &[last]), None);
+
+        assert!(catalog.drop_index(idx));
+        assert!(catalog.get_index(idx).is_none());
+        assert!(catalog.indexes_for_label_property(person, last).is_empty());
+        assert_eq!(catalog.find_index(person, &[last, first]), None);
+    }
+
+    #[test]
+    fn test_catalog_single_index_is_not_composite() {
+        let catalog = Catalog::new();
+        let person = catalog.get_or_create_label("Person");
+        let name = catalog.get_or_create_property_key("name");
+
+        let idx = catalog.create_index(person, name, IndexType::Hash);
+        let def = catalog.get_index(idx).unwrap();
+        assert_eq!(def.property_keys, vec![name]);
+        assert!(!def.is_composite());
+    }
+
+    #[test]
+    fn test_catalog_prefix_index_selection() {
+        let catalog = Catalog::new();
+
+        let person = catalog.get_or_create_label("Person");
+        let last = catalog.get_or_create_property_key("lastName");
+        let first = catalog.get_or_create_property_key("firstName");
+        let age = catalog.get_or_create_property_key("age");
+
+        let idx =
+            catalog.create_composite_index(&LpgStore::new(), "Person", &["lastName", "firstName"]);
+
+        // Leading column alone is a prefix lookup
+        let m = catalog.find_prefix_index(person, &[last]).unwrap();
+        assert_eq!(m.index_id, idx);
+        assert_eq!(m.prefix_len, 1);
+        assert!(!m.full_key);
+
+        // Both columns, in any predicate order, are a point lookup
+        let m = catalog.find_prefix_index(person, &[first, last]).unwrap();
+        assert_eq!(m.prefix_len, 2);
+        assert!(m.full_key);
+
+        // Unrelated extra predicates don't matter
+        let m = catalog.find_prefix_index(person, &[age, last]).unwrap();
+        assert_eq!(m.prefix_len, 1);
+
+        // Second column alone cannot use the index
+        assert!(catalog.find_prefix_index(person, &[first]).is_none());
+        assert!(catalog.find_prefix_index(person, &[]).is_none());
+    }
+
+    #[test]
+    fn test_catalog_prefix_index_prefers_longest_prefix() {
+        let catalog = Catalog::new();
+
+        let person = catalog.get_or_create_label("Person");
+        let last = catalog.get_or_create_property_key("lastName");
+        let first = catalog.get_or_create_property_key("firstName");
+
+        let single = catalog.create_index(person, last, IndexType::BTree);
+        let composite =
+            catalog.create_composite_index(&LpgStore::new(), "Person", &["lastName", "firstName"]);
+
+        let m = catalog.find_prefix_index(person, &[last, first]).unwrap();
+        assert_eq!(m.index_id, composite);
+
+        let m = catalog.find_prefix_index(person, &[last]).unwrap();
+        assert_eq!(m.index_id, single);
+        assert!(m.full_key);
+    }
+
+    #[test]
+    fn test_catalog_prefix_index_hash_needs_full_key() {
+        let catalog = Catalog::new();
+
+        let person = catalog.get_or_create_label("Person");
+        let last = catalog.get_or_create_property_key("lastName");
+        let first = catalog.get_or_create_property_key("firstName");
+
+        let idx = catalog
+            .indexes
+            .create(person, vec![last, first], IndexType::Hash);
+
+        assert!(catalog.find_prefix_index(person, &[last]).is_none());
+        assert_eq!(
+            catalog
+                .find_prefix_index(person, &[last, first])
+                .unwrap()
+                .index_id,
+            idx
+        );
+    }
+
+    #[test]
+    fn test_catalog_trie_index() {
+        let catalog = Catalog::new();
+
+        let person = catalog.get_or_create_label("Person");
+        let name = catalog.get_or_create_property_key("name");
+        let city = catalog.get_or_create_property_key("city");
+
+        assert!(catalog.find_text_index(person, name).is_none());
+
+        let btree = catalog.create_index(person, name, IndexType::BTree);
+        let trie = catalog.create_index(
+            person,
+            name,
+            IndexType::Trie {
+                case_sensitive: false,
+            },
+        );
+
+        assert_eq!(catalog.find_text_index(person, name), Some(trie));
+        assert!(catalog.find_text_index(person, city).is_none());
+        assert_eq!(
+            catalog.get_index(trie).unwrap().index_type,
+            IndexType::Trie {
+                case_sensitive: false
+            }
+        );
+
+        // Trie indexes never serve equality lookups
+        assert_eq!(
+            catalog.find_prefix_index(person, &[name]).unwrap().index_id,
+            btree
+        );
+        catalog.drop_index(btree);
+        assert!(catalog.find_prefix_index(person, &[name]).is_none());
+    }
+
     #[test]
     fn test_catalog_schema_required_property_duplicate() {
         let catalog = Catalog::with_schema();
@@ -1134,6 +1815,7 @@ mod tests {
             id: IndexId::new(1),
             label: LabelId::new(2),
             property_key: PropertyKeyId::new(3),
+            property_keys: vec![PropertyKeyId::new(3)],
             index_type: IndexType::Hash,
         };
 
@@ -1,9 +1,13 @@
 //! Database configuration.
 
 use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::query::planner::DEFAULT_NESTED_LOOP_THRESHOLD;
 
 /// Database configuration.
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Config {
     /// Path to the database directory (None for in-memory only).
     pub path: Option<PathBuf>,
@@ -26,11 +30,33 @@ pub struct Config {
     /// Whether to maintain backward edges.
     pub backward_edges: bool,
 
+    /// Whether to keep label, edge type, and property statistics current
+    /// as the graph changes, instead of recomputing them when the
+    /// optimizer or schema finds them stale.
+    pub incremental_statistics: bool,
+
     /// Whether to enable query logging.
     pub query_logging: bool,
 
     /// Adaptive execution configuration.
     pub adaptive: AdaptiveConfig,
+
+    /// Whether to hint registered storage with each plan's access pattern
+    /// (sequential for scans, random for index lookups).
+    pub access_hints: bool,
+
+    /// Seed for `rand()` in queries; each session starts from it, so the
+    /// same queries give the same numbers. `None` seeds from the clock.
+    pub random_seed: Option<u64>,
+
+    /// Estimated row count below which a join input is small enough for a
+    /// nested-loop join instead of a hash join.
+    pub nested_loop_join_threshold: f64,
+
+    /// Longest a query may run before it is cancelled with
+    /// [`Error::Timeout`](grafeo_common::utils::error::Error::Timeout).
+    /// `None` lets queries run to completion.
+    pub query_timeout: Option<Duration>,
 }
 
 /// Configuration for adaptive query execution.
@@ -110,8 +136,13 @@ impl Default for Config {
             wal_enabled: true,
             wal_flush_interval_ms: 100,
             backward_edges: true,
+            incremental_statistics: false,
             query_logging: false,
             adaptive: AdaptiveConfig::default(),
+            access_hints: true,
+            random_seed: None,
+            nested_loop_join_threshold: DEFAULT_NESTED_LOOP_THRESHOLD,
+            query_timeout: None,
         }
     }
 }
@@ -158,6 +189,13 @@ impl Config {
         self
     }
 
+    /// Keeps statistics current on every write.
+    #[must_use]
+    pub fn with_incremental_statistics(mut self) -> Self {
+        self.incremental_statistics = true;
+        self
+    }
+
     /// Enables query logging.
     #[must_use]
     pub fn with_query_logging(mut self) -> Self {
@@ -174,6 +212,13 @@ impl Config {
         self
     }
 
+    /// Cancels queries that run longer than `timeout`.
+    #[must_use]
+    pub fn with_query_timeout(mut self, timeout: Duration) -> Self {
+        self.query_timeout = Some(timeout);
+        self
+    }
+
     /// Sets the spill directory for out-of-core processing.
     #[must_use]
     pub fn with_spill_path(mut self, path: impl Into<PathBuf>) -> Self {
@@ -194,6 +239,27 @@ impl Config {
         self.adaptive.enabled = false;
         self
     }
+
+    /// Disables access-pattern hints to storage.
+    #[must_use]
+    pub fn without_access_hints(mut self) -> Self {
+        self.access_hints = false;
+        self
+    }
+
+    /// Seeds `rand()` for reproducible query results.
+    #[must_use]
+    pub fn with_random_seed(mut self, seed: u64) -> Self {
+        self.random_seed = Some(seed);
+        self
+    }
+
+    /// Sets the estimated row count below which joins use a nested loop.
+    #[must_use]
+    pub fn with_nested_loop_join_threshold(mut self, rows: f64) -> Self {
+        self.nested_loop_join_threshold = rows;
+        self
+    }
 }
 
 /// Helper function to get CPU count (fallback implementation).
@@ -2,21 +2,31 @@
 //!
 //! Start here with [`GrafeoDB`] - it's your handle to everything.
 
+use std::collections::BTreeSet;
 use std::path::Path;
 use std::sync::Arc;
 
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 
-use grafeo_adapters::storage::wal::{WalConfig, WalManager, WalRecord, WalRecovery};
+use grafeo_adapters::storage::AccessAdvisor;
+use grafeo_adapters::storage::wal::{
+    EdgeImage, NodeImage, PageData, PageId, PageStore, RecoveryTarget, SchemaImage, WalConfig,
+    WalManager, WalRecord, WalRecovery,
+};
 use grafeo_common::memory::buffer::{BufferManager, BufferManagerConfig};
+use grafeo_common::types::{EpochId, TxId};
 use grafeo_common::utils::error::Result;
-use grafeo_core::graph::lpg::LpgStore;
+use grafeo_core::execution::operators::RandomSource;
+use grafeo_core::graph::lpg::{LpgStore, LpgStoreConfig};
 #[cfg(feature = "rdf")]
 use grafeo_core::graph::rdf::RdfStore;
 
+use crate::catalog::Catalog;
 use crate::config::Config;
+use crate::query::QueryCache;
 use crate::session::Session;
 use crate::transaction::TransactionManager;
+use grafeo_adapters::plugins::PluginRegistry;
 
 /// Your handle to a Grafeo database.
 ///
@@ -54,8 +64,20 @@ pub struct GrafeoDB {
     buffer_manager: Arc<BufferManager>,
     /// Write-ahead log manager (if durability is enabled).
     wal: Option<Arc<WalManager>>,
+    /// Checkpoint pages (present whenever the WAL is).
+    pages: Option<PageStore>,
+    /// Pages changed since the last checkpoint.
+    dirty_pages: Mutex<BTreeSet<PageId>>,
     /// Whether the database is open.
     is_open: RwLock<bool>,
+    /// Cache of translated query plans, shared by all sessions.
+    query_cache: Arc<QueryCache>,
+    /// Schema catalog (indexes), shared by all sessions.
+    catalog: Arc<Catalog>,
+    /// Procedures `CALL` can run, shared by all sessions.
+    plugins: Arc<PluginRegistry>,
+    /// Storage that sessions hint with each plan's access pattern.
+    access_advisor: RwLock<Option<Arc<dyn AccessAdvisor>>>,
 }
 
 impl GrafeoDB {
@@ -125,7 +147,10 @@ impl GrafeoDB {
     /// # Ok::<(), grafeo_common::utils::error::Error>(())
     /// ```
     pub fn with_config(config: Config) -> Result<Self> {
-        let store = Arc::new(LpgStore::new());
+        let store = Arc::new(LpgStore::with_config(LpgStoreConfig {
+            incremental_statistics: config.incremental_statistics,
+            ..Default::default()
+        }));
         #[cfg(feature = "rdf")]
         let rdf_store = Arc::new(RdfStore::new());
         let tx_manager = Arc::new(TransactionManager::new());
@@ -144,31 +169,46 @@ impl GrafeoDB {
         let buffer_manager = BufferManager::new(buffer_config);
 
         // Initialize WAL if persistence is enabled
-        let wal = if config.wal_enabled {
+        let mut dirty_pages = BTreeSet::new();
+        let (wal, pages) = if config.wal_enabled {
             if let Some(ref db_path) = config.path {
                 // Create database directory if it doesn't exist
                 std::fs::create_dir_all(db_path)?;
 
                 let wal_path = db_path.join("wal");
-
-                // Check if WAL exists and recover if needed
-                if wal_path.exists() {
-                    let recovery = WalRecovery::new(&wal_path);
-                    let records = recovery.recover()?;
-                    Self::apply_wal_records(&store, &records)?;
+                let pages = PageStore::open(db_path.join("pages"));
+
+                // A crash mid-write leaves a torn last record; drop it so
+                // the WAL can be appended to again
+                let had_pages = Self::recover(&store, db_path, true)?;
+
+                // A database from before pages existed has nothing paged
+                // yet, so its first checkpoint has to write everything
+                if !had_pages {
+                    dirty_pages.extend(store.node_ids().into_iter().map(PageId::for_node));
+                    dirty_pages.extend(store.all_edges().map(|e| PageId::for_edge(e.id)));
+                    if !store.undirected_edge_types().is_empty() {
+                        dirty_pages.insert(PageId::Schema);
+                    }
                 }
 
                 // Open/create WAL manager
                 let wal_config = WalConfig::default();
                 let wal_manager = WalManager::with_config(&wal_path, wal_config)?;
-                Some(Arc::new(wal_manager))
+                (Some(Arc::new(wal_manager)), Some(pages))
             } else {
-                None
+                (None, None)
             }
         } else {
-            None
+            (None, None)
         };
 
+        // Edge type flags recovered into the store belong in the catalog too
+        let catalog = Arc::new(Catalog::with_schema());
+        for edge_type in store.undirected_edge_types() {
+            catalog.set_edge_type_undirected(&edge_type);
+        }
+
         Ok(Self {
             config,
             store,
@@ -177,17 +217,97 @@ impl GrafeoDB {
             tx_manager,
             buffer_manager,
             wal,
+            pages,
+            dirty_pages: Mutex::new(dirty_pages),
             is_open: RwLock::new(true),
+            query_cache: Arc::new(QueryCache::default()),
+            catalog,
+            plugins: Arc::new(PluginRegistry::with_builtin_algorithms()),
+            access_advisor: RwLock::new(None),
         })
     }
 
+    /// Recovers the database at `db_path` into `store`, returning whether it
+    /// had checkpoint pages.
+    ///
+    /// The last checkpoint's state lives in pages; the WAL holds what
+    /// happened after it. A torn last WAL record is truncated away if
+    /// `truncate_torn_tail` is set, and is an error otherwise.
+    fn recover(store: &LpgStore, db_path: &Path, truncate_torn_tail: bool) -> Result<bool> {
+        let pages = PageStore::open(db_path.join("pages"));
+        let had_pages = pages.exists();
+        if had_pages {
+            Self::load_pages(store, &pages)?;
+        }
+
+        let wal_path = db_path.join("wal");
+        if wal_path.exists() {
+            let records = WalRecovery::new(&wal_path)
+                .truncate_torn_tail(truncate_torn_tail)
+                .recover()?;
+            let tail = if had_pages {
+                let start = records
+                    .iter()
+                    .rposition(|r| matches!(r, WalRecord::Checkpoint { .. }))
+                    .map_or(0, |i| i + 1);
+                &records[start..]
+            } else {
+                &records[..]
+            };
+            Self::apply_wal_records(store, tail)?;
+        }
+        Ok(had_pages)
+    }
+
+    /// Loads every checkpoint page into the store.
+    fn load_pages(store: &LpgStore, pages: &PageStore) -> Result<()> {
+        let pages = pages.read_all()?;
+
+        // Nodes first, so every edge finds its endpoints
+        for page in &pages {
+            if let PageData::Nodes(nodes) = page {
+                for node in nodes {
+                    let label_refs: Vec<&str> = node.labels.iter().map(String::as_str).collect();
+                    store.create_node_with_id(node.id, &label_refs);
+                    for (key, value) in &node.properties {
+                        store.set_node_property(node.id, key, value.clone());
+                    }
+                }
+            }
+        }
+        for page in &pages {
+            if let PageData::Schema(schema) = page {
+                for edge_type in &schema.undirected_edge_types {
+                    store.set_edge_type_undirected(edge_type);
+                }
+            }
+        }
+        for page in &pages {
+            if let PageData::Edges(edges) = page {
+                for edge in edges {
+                    store.create_edge_with_id(edge.id, edge.src, edge.dst, &edge.edge_type);
+                    for (key, value) in &edge.properties {
+                        store.set_edge_property(edge.id, key, value.clone());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Applies WAL records to restore the database state.
+    ///
+    /// Creates of entities that already exist are skipped: after a crash
+    /// between writing pages and the checkpoint record, the replayed tail
+    /// overlaps what the pages already hold.
     fn apply_wal_records(store: &LpgStore, records: &[WalRecord]) -> Result<()> {
         for record in records {
             match record {
                 WalRecord::CreateNode { id, labels } => {
-                    let label_refs: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
-                    store.create_node_with_id(*id, &label_refs);
+                    if store.get_node(*id).is_none() {
+                        let label_refs: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
+                        store.create_node_with_id(*id, &label_refs);
+                    }
                 }
                 WalRecord::DeleteNode { id } => {
                     store.delete_node(*id);
@@ -198,7 +318,9 @@ impl GrafeoDB {
                     dst,
                     edge_type,
                 } => {
-                    store.create_edge_with_id(*id, *src, *dst, edge_type);
+                    if store.get_edge(*id).is_none() {
+                        store.create_edge_with_id(*id, *src, *dst, edge_type);
+                    }
                 }
                 WalRecord::DeleteEdge { id } => {
                     store.delete_edge(*id);
@@ -215,7 +337,11 @@ impl GrafeoDB {
                 WalRecord::RemoveNodeLabel { id, label } => {
                     store.remove_label(*id, label);
                 }
+                WalRecord::SetEdgeTypeUndirected { edge_type } => {
+                    store.set_edge_type_undirected(edge_type);
+                }
                 WalRecord::TxCommit { .. }
+                | WalRecord::TxCommitAt { .. }
                 | WalRecord::TxAbort { .. }
                 | WalRecord::Checkpoint { .. } => {
                     // Transaction control records don't need replay action
@@ -254,6 +380,14 @@ impl GrafeoDB {
                 Arc::clone(&self.tx_manager),
                 self.config.adaptive.clone(),
             )
+            .with_plan_cache(Arc::clone(&self.query_cache))
+            .with_catalog(Arc::clone(&self.catalog))
+            .with_plugins(Arc::clone(&self.plugins))
+            .with_access_advisor(self.session_access_advisor())
+            .with_random_source(self.session_random_source())
+            .with_nested_loop_threshold(self.config.nested_loop_join_threshold)
+            .with_query_timeout(self.config.query_timeout)
+            .with_buffer_manager(Some(Arc::clone(&self.buffer_manager)))
         }
         #[cfg(not(feature = "rdf"))]
         {
@@ -262,9 +396,65 @@ impl GrafeoDB {
                 Arc::clone(&self.tx_manager),
                 self.config.adaptive.clone(),
             )
+            .with_plan_cache(Arc::clone(&self.query_cache))
+            .with_catalog(Arc::clone(&self.catalog))
+            .with_plugins(Arc::clone(&self.plugins))
+            .with_access_advisor(self.session_access_advisor())
+            .with_random_source(self.session_random_source())
+            .with_nested_loop_threshold(self.config.nested_loop_join_threshold)
+            .with_query_timeout(self.config.query_timeout)
+            .with_buffer_manager(Some(Arc::clone(&self.buffer_manager)))
         }
     }
 
+    /// Registers storage to hint with each query's access pattern.
+    ///
+    /// Before running a plan, sessions call
+    /// [`advise_access`](AccessAdvisor::advise_access) with
+    /// [`Sequential`](grafeo_adapters::storage::AccessPattern::Sequential)
+    /// when it is dominated by scans and
+    /// [`Random`](grafeo_adapters::storage::AccessPattern::Random) when it
+    /// is dominated by index lookups. Typically an
+    /// [`MmapBackend`](grafeo_adapters::storage::MmapBackend). Ignored when
+    /// [`Config::access_hints`] is off; only sessions opened afterwards
+    /// see it.
+    pub fn set_access_advisor(&self, advisor: Arc<dyn AccessAdvisor>) {
+        *self.access_advisor.write() = Some(advisor);
+    }
+
+    fn session_random_source(&self) -> Arc<RandomSource> {
+        Arc::new(match self.config.random_seed {
+            Some(seed) => RandomSource::new(seed),
+            None => RandomSource::from_entropy(),
+        })
+    }
+
+    fn session_access_advisor(&self) -> Option<Arc<dyn AccessAdvisor>> {
+        if self.config.access_hints {
+            self.access_advisor.read().clone()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the schema catalog shared by all sessions.
+    ///
+    /// Indexes created with `CREATE INDEX` are registered here.
+    #[must_use]
+    pub fn catalog(&self) -> &Arc<Catalog> {
+        &self.catalog
+    }
+
+    /// Returns the plugin registry shared by all sessions.
+    ///
+    /// Graph algorithms registered here can be run from queries with
+    /// `CALL name(args) YIELD ...`; the built-in algorithms are registered
+    /// from the start.
+    #[must_use]
+    pub fn plugins(&self) -> &Arc<PluginRegistry> {
+        &self.plugins
+    }
+
     /// Returns the adaptive execution configuration.
     #[must_use]
     pub fn adaptive_config(&self) -> &crate::config::AdaptiveConfig {
@@ -299,6 +489,32 @@ impl GrafeoDB {
         session.execute_with_params(query, params)
     }
 
+    /// Describes how a GQL query would run, without running it.
+    ///
+    /// See [`Session::explain`] for the output format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails to parse, validate, or plan.
+    #[cfg(feature = "gql")]
+    pub fn explain(&self, query: &str) -> Result<String> {
+        let session = self.session();
+        session.explain(query)
+    }
+
+    /// Runs a GQL query and describes how it actually ran.
+    ///
+    /// See [`Session::explain_analyze`] for the output format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails to parse, plan, or execute.
+    #[cfg(feature = "gql")]
+    pub fn explain_analyze(&self, query: &str) -> Result<String> {
+        let session = self.session();
+        session.explain_analyze(query)
+    }
+
     /// Executes a Cypher query and returns the result.
     ///
     /// # Errors
@@ -324,7 +540,8 @@ impl GrafeoDB {
         use crate::query::processor::{QueryLanguage, QueryProcessor};
 
         // Create processor
-        let processor = QueryProcessor::for_lpg(Arc::clone(&self.store));
+        let processor = QueryProcessor::for_lpg(Arc::clone(&self.store))
+            .with_buffer_manager(Some(Arc::clone(&self.buffer_manager)));
         processor.process(query, QueryLanguage::Cypher, Some(&params))
     }
 
@@ -410,12 +627,13 @@ impl GrafeoDB {
         let optimized_plan = optimizer.optimize(logical_plan)?;
 
         // Convert to physical plan using RDF planner
-        let planner = RdfPlanner::new(Arc::clone(&self.rdf_store));
+        let planner = RdfPlanner::new(Arc::clone(&self.rdf_store))
+            .with_query_timeout(self.config.query_timeout)
+            .with_buffer_manager(Some(Arc::clone(&self.buffer_manager)));
         let mut physical_plan = planner.plan(&optimized_plan)?;
 
         // Execute the plan
-        let executor = Executor::with_columns(physical_plan.columns.clone());
-        executor.execute(physical_plan.operator.as_mut())
+        Executor::execute_plan(&optimized_plan, &mut physical_plan)
     }
 
     /// Returns the RDF store.
@@ -451,6 +669,22 @@ impl GrafeoDB {
         &self.store
     }
 
+    /// Returns a store holding only committed data, for algorithms that
+    /// read the graph directly.
+    ///
+    /// With no transaction open this is the database's own store, shared
+    /// rather than copied. Otherwise it is a [snapshot](LpgStore::snapshot)
+    /// with the open transactions' writes left out.
+    #[must_use]
+    pub fn committed_store(&self) -> Arc<LpgStore> {
+        let open = self.tx_manager.active_transactions();
+        if open.is_empty() {
+            Arc::clone(&self.store)
+        } else {
+            Arc::new(self.store.snapshot(&open))
+        }
+    }
+
     /// Returns the buffer manager for memory-aware operations.
     #[must_use]
     pub fn buffer_manager(&self) -> &Arc<BufferManager> {
@@ -483,12 +717,11 @@ impl GrafeoDB {
             });
 
             // Log a TxCommit to mark all pending records as committed
-            wal.log(&WalRecord::TxCommit {
-                tx_id: checkpoint_tx,
-            })?;
+            wal.log(&WalRecord::commit(checkpoint_tx))?;
 
             // Then checkpoint
-            wal.checkpoint(checkpoint_tx, epoch)?;
+            let dirty_pages = self.flush_dirty_pages()?;
+            wal.checkpoint_with_pages(checkpoint_tx, epoch, dirty_pages)?;
             wal.sync()?;
         }
 
@@ -507,9 +740,107 @@ impl GrafeoDB {
         if let Some(ref wal) = self.wal {
             wal.log(record)?;
         }
+        match record {
+            WalRecord::CreateNode { id, .. }
+            | WalRecord::DeleteNode { id }
+            | WalRecord::SetNodeProperty { id, .. }
+            | WalRecord::AddNodeLabel { id, .. }
+            | WalRecord::RemoveNodeLabel { id, .. } => self.mark_dirty(PageId::for_node(*id)),
+            WalRecord::CreateEdge { id, .. }
+            | WalRecord::DeleteEdge { id }
+            | WalRecord::SetEdgeProperty { id, .. } => self.mark_dirty(PageId::for_edge(*id)),
+            WalRecord::SetEdgeTypeUndirected { .. } => self.mark_dirty(PageId::Schema),
+            WalRecord::TxCommit { .. }
+            | WalRecord::TxCommitAt { .. }
+            | WalRecord::TxAbort { .. }
+            | WalRecord::Checkpoint { .. } => {}
+        }
         Ok(())
     }
 
+    /// Records that a page must be rewritten at the next checkpoint.
+    fn mark_dirty(&self, page: PageId) {
+        if self.pages.is_some() {
+            self.dirty_pages.lock().insert(page);
+        }
+    }
+
+    /// Writes every dirty page and returns the pages written.
+    fn flush_dirty_pages(&self) -> Result<Vec<PageId>> {
+        let Some(ref pages) = self.pages else {
+            return Ok(Vec::new());
+        };
+
+        // Pages hold what has committed so far; open transactions' writes
+        // stay out of them
+        let epoch = self.tx_manager.current_epoch();
+        self.store.advance_epoch_to(epoch);
+
+        let dirty: Vec<PageId> = std::mem::take(&mut *self.dirty_pages.lock())
+            .into_iter()
+            .collect();
+        for (i, &page) in dirty.iter().enumerate() {
+            if let Err(e) = pages.write_page(page, &self.page_data(page, epoch)) {
+                // Keep what wasn't written for the next attempt
+                self.dirty_pages.lock().extend(&dirty[i..]);
+                return Err(e);
+            }
+        }
+        Ok(dirty)
+    }
+
+    /// Snapshots the contents of a page as committed at `epoch`.
+    fn page_data(&self, page: PageId, epoch: EpochId) -> PageData {
+        use grafeo_common::types::{EdgeId, NodeId};
+
+        match page {
+            PageId::Nodes(_) => PageData::Nodes(
+                page.id_range()
+                    .filter_map(|id| {
+                        self.store
+                            .get_node_versioned(NodeId::new(id), epoch, TxId::SYSTEM)
+                    })
+                    .map(|node| NodeImage {
+                        id: node.id,
+                        labels: node.labels.iter().map(|l| l.to_string()).collect(),
+                        properties: node
+                            .properties
+                            .into_iter()
+                            .map(|(k, v)| (k.to_string(), v))
+                            .collect(),
+                    })
+                    .collect(),
+            ),
+            PageId::Edges(_) => PageData::Edges(
+                page.id_range()
+                    .filter_map(|id| {
+                        self.store
+                            .get_edge_versioned(EdgeId::new(id), epoch, TxId::SYSTEM)
+                    })
+                    .map(|edge| EdgeImage {
+                        id: edge.id,
+                        src: edge.src,
+                        dst: edge.dst,
+                        edge_type: edge.edge_type.to_string(),
+                        properties: edge
+                            .properties
+                            .into_iter()
+                            .map(|(k, v)| (k.to_string(), v))
+                            .collect(),
+                    })
+                    .collect(),
+            ),
+            PageId::Schema => PageData::Schema(SchemaImage {
+                undirected_edge_types: self
+                    .store
+                    .undirected_edge_types()
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect(),
+            }),
+        }
+    }
+
     /// Returns the number of nodes in the database.
     #[must_use]
     pub fn node_count(&self) -> usize {
@@ -754,6 +1085,40 @@ impl GrafeoDB {
 
     // === Edge Operations ===
 
+    /// Marks an edge type as undirected.
+    ///
+    /// Each edge of the type is still stored once, but `->`, `<-` and
+    /// undirected patterns all match it from either endpoint, and it counts
+    /// once toward each endpoint's degree. The flag is recorded in the
+    /// catalog and, with persistence on, in the WAL and checkpoints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grafeo_engine::GrafeoDB;
+    ///
+    /// let db = GrafeoDB::new_in_memory();
+    /// db.set_edge_type_undirected("FRIEND");
+    /// let alice = db.create_node(&["Person"]);
+    /// let bob = db.create_node(&["Person"]);
+    /// db.create_edge(alice, bob, "FRIEND");
+    ///
+    /// let result = db
+    ///     .execute("MATCH (:Person)-[:FRIEND]->(p:Person) RETURN p")
+    ///     .unwrap();
+    /// assert_eq!(result.row_count(), 2);
+    /// ```
+    pub fn set_edge_type_undirected(&self, edge_type: &str) {
+        self.store.set_edge_type_undirected(edge_type);
+        self.catalog.set_edge_type_undirected(edge_type);
+
+        if let Err(e) = self.log_wal(&WalRecord::SetEdgeTypeUndirected {
+            edge_type: edge_type.to_string(),
+        }) {
+            tracing::warn!("Failed to log SetEdgeTypeUndirected to WAL: {}", e);
+        }
+    }
+
     /// Creates an edge (relationship) between two nodes.
     ///
     /// Edges connect nodes and have a type that describes the relationship.
@@ -896,6 +1261,7 @@ impl GrafeoDB {
     /// Returns true if the property existed and was removed, false otherwise.
     pub fn remove_node_property(&self, id: grafeo_common::types::NodeId, key: &str) -> bool {
         // Note: RemoveProperty WAL records not yet implemented, but operation works in memory
+        self.mark_dirty(PageId::for_node(id));
         self.store.remove_node_property(id, key).is_some()
     }
 
@@ -904,6 +1270,7 @@ impl GrafeoDB {
     /// Returns true if the property existed and was removed, false otherwise.
     pub fn remove_edge_property(&self, id: grafeo_common::types::EdgeId, key: &str) -> bool {
         // Note: RemoveProperty WAL records not yet implemented, but operation works in memory
+        self.mark_dirty(PageId::for_edge(id));
         self.store.remove_edge_property(id, key).is_some()
     }
 
@@ -968,6 +1335,13 @@ impl GrafeoDB {
         }
     }
 
+    /// Rescans the store and rebuilds the optimizer statistics.
+    ///
+    /// See [`Session::analyze`] for details.
+    pub fn analyze(&self) -> crate::admin::AnalyzeStats {
+        self.session().analyze()
+    }
+
     /// Calculates total disk usage for the database directory.
     fn calculate_disk_usage(path: &Path) -> Result<usize> {
         let mut total = 0usize;
@@ -987,37 +1361,11 @@ impl GrafeoDB {
 
     /// Returns schema information (labels, edge types, property keys).
     ///
-    /// For LPG mode, returns label and edge type information.
-    /// For RDF mode, returns predicate and named graph information.
+    /// See [`Session::schema`] for what is reported. RDF data is described
+    /// by `rdf_schema` instead.
     #[must_use]
     pub fn schema(&self) -> crate::admin::SchemaInfo {
-        let labels = self
-            .store
-            .all_labels()
-            .into_iter()
-            .map(|name| crate::admin::LabelInfo {
-                name: name.clone(),
-                count: self.store.nodes_with_label(&name).count(),
-            })
-            .collect();
-
-        let edge_types = self
-            .store
-            .all_edge_types()
-            .into_iter()
-            .map(|name| crate::admin::EdgeTypeInfo {
-                name: name.clone(),
-                count: self.store.edges_with_type(&name).count(),
-            })
-            .collect();
-
-        let property_keys = self.store.all_property_keys();
-
-        crate::admin::SchemaInfo::Lpg(crate::admin::LpgSchemaInfo {
-            labels,
-            edge_types,
-            property_keys,
-        })
+        self.session().schema()
     }
 
     /// Returns RDF schema information.
@@ -1053,35 +1401,63 @@ impl GrafeoDB {
     ///
     /// Checks for:
     /// - Dangling edge references (edges pointing to non-existent nodes)
-    /// - Internal index consistency
+    /// - Label index entries for nodes that no longer carry the label
+    /// - Disagreements between the adjacency lists and the edge store
     ///
     /// Returns a list of errors and warnings. Empty errors = valid.
     #[must_use]
     pub fn validate(&self) -> crate::admin::ValidationResult {
-        let mut result = crate::admin::ValidationResult::default();
+        use crate::admin::ValidationError;
 
-        // Check for dangling edge references
-        for edge in self.store.all_edges() {
-            if self.store.get_node(edge.src).is_none() {
-                result.errors.push(crate::admin::ValidationError {
-                    code: "DANGLING_SRC".to_string(),
-                    message: format!(
-                        "Edge {} references non-existent source node {}",
-                        edge.id.0, edge.src.0
-                    ),
-                    context: Some(format!("edge:{}", edge.id.0)),
-                });
-            }
-            if self.store.get_node(edge.dst).is_none() {
-                result.errors.push(crate::admin::ValidationError {
-                    code: "DANGLING_DST".to_string(),
-                    message: format!(
-                        "Edge {} references non-existent destination node {}",
-                        edge.id.0, edge.dst.0
-                    ),
-                    context: Some(format!("edge:{}", edge.id.0)),
-                });
-            }
+        let mut result = crate::admin::ValidationResult::default();
+        let report = self.store.check_integrity();
+
+        for (edge, src) in report.dangling_sources {
+            result.errors.push(ValidationError {
+                code: "DANGLING_SRC".to_string(),
+                message: format!(
+                    "Edge {} references non-existent source node {}",
+                    edge.0, src.0
+                ),
+                context: Some(format!("edge:{}", edge.0)),
+            });
+        }
+        for (edge, dst) in report.dangling_targets {
+            result.errors.push(ValidationError {
+                code: "DANGLING_DST".to_string(),
+                message: format!(
+                    "Edge {} references non-existent destination node {}",
+                    edge.0, dst.0
+                ),
+                context: Some(format!("edge:{}", edge.0)),
+            });
+        }
+        for (label, node) in report.orphaned_label_entries {
+            result.errors.push(ValidationError {
+                code: "ORPHANED_LABEL_ENTRY".to_string(),
+                message: format!(
+                    "Label index for :{label} lists node {} which does not have that label",
+                    node.0
+                ),
+                context: Some(format!("node:{}", node.0)),
+            });
+        }
+        for edge in report.unindexed_edges {
+            result.errors.push(ValidationError {
+                code: "UNINDEXED_EDGE".to_string(),
+                message: format!("Edge {} is missing from the adjacency lists", edge.0),
+                context: Some(format!("edge:{}", edge.0)),
+            });
+        }
+        for (node, edge) in report.stale_adjacency {
+            result.errors.push(ValidationError {
+                code: "STALE_ADJACENCY".to_string(),
+                message: format!(
+                    "Adjacency list of node {} references missing edge {}",
+                    node.0, edge.0
+                ),
+                context: Some(format!("edge:{}", edge.0)),
+            });
         }
 
         // Add warnings for potential issues
@@ -1124,7 +1500,8 @@ impl GrafeoDB {
 
     /// Forces a WAL checkpoint.
     ///
-    /// Flushes all pending WAL records to the main storage.
+    /// Writes the pages changed since the last checkpoint, then marks the
+    /// WAL so recovery starts from them.
     ///
     /// # Errors
     ///
@@ -1136,7 +1513,8 @@ impl GrafeoDB {
                 .tx_manager
                 .last_assigned_tx_id()
                 .unwrap_or_else(|| self.tx_manager.begin());
-            wal.checkpoint(tx_id, epoch)?;
+            let dirty_pages = self.flush_dirty_pages()?;
+            wal.checkpoint_with_pages(tx_id, epoch, dirty_pages)?;
             wal.sync()?;
         }
         Ok(())
@@ -1187,6 +1565,10 @@ impl GrafeoDB {
             }
         }
 
+        for edge_type in self.store.undirected_edge_types() {
+            target.set_edge_type_undirected(&edge_type);
+        }
+
         // Copy all edges using WAL-enabled methods
         for edge in self.store.all_edges() {
             target
@@ -1245,6 +1627,10 @@ impl GrafeoDB {
             }
         }
 
+        for edge_type in self.store.undirected_edge_types() {
+            target.set_edge_type_undirected(&edge_type);
+        }
+
         // Copy all edges
         for edge in self.store.all_edges() {
             target
@@ -1260,6 +1646,56 @@ impl GrafeoDB {
         Ok(target)
     }
 
+    /// Recovers the database at `path` as it was at `target`.
+    ///
+    /// The write-ahead log is replayed up to the target into a new in-memory
+    /// database. Nothing on disk is touched, so this is safe to run against
+    /// a database another process has open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write-ahead log can't be read.
+    pub fn open_until(path: impl AsRef<Path>, target: RecoveryTarget) -> Result<Self> {
+        // A missing database is an error, not an empty recovery
+        std::fs::metadata(path.as_ref())?;
+
+        let db = Self::new_in_memory();
+        let wal_path = path.as_ref().join("wal");
+        if wal_path.exists() {
+            let records = WalRecovery::new(&wal_path).until(target).recover()?;
+            Self::apply_wal_records(&db.store, &records)?;
+            for edge_type in db.store.undirected_edge_types() {
+                db.catalog.set_edge_type_undirected(&edge_type);
+            }
+        }
+        Ok(db)
+    }
+
+    /// Opens the database at `path` for reading only.
+    ///
+    /// The checkpoint pages and write-ahead log are read into a new
+    /// in-memory database, and nothing on disk is written: closing it logs
+    /// no commit and takes no checkpoint. Changes made through it are never
+    /// written back. A torn record at the end of the log is an error, since
+    /// repairing it would write; opening the database with
+    /// [`open()`](Self::open) repairs it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no database at `path` or it can't be
+    /// read.
+    pub fn open_read_only(path: impl AsRef<Path>) -> Result<Self> {
+        // A missing database is an error, not an empty one
+        std::fs::metadata(path.as_ref())?;
+
+        let db = Self::new_in_memory();
+        Self::recover(&db.store, path.as_ref(), false)?;
+        for edge_type in db.store.undirected_edge_types() {
+            db.catalog.set_edge_type_undirected(&edge_type);
+        }
+        Ok(db)
+    }
+
     /// Opens a database file and loads it entirely into memory.
     ///
     /// The returned database has no connection to the original file.
@@ -1341,6 +1777,9 @@ pub struct QueryResult {
     pub column_types: Vec<grafeo_common::types::LogicalType>,
     /// The actual result rows.
     pub rows: Vec<Vec<grafeo_common::types::Value>>,
+    /// True if this is the answer to a boolean query, like SPARQL `ASK`,
+    /// held as the single value in `rows`.
+    is_boolean: bool,
 }
 
 impl QueryResult {
@@ -1352,6 +1791,7 @@ impl QueryResult {
             columns,
             column_types: vec![grafeo_common::types::LogicalType::Any; len],
             rows: Vec::new(),
+            is_boolean: false,
         }
     }
 
@@ -1365,9 +1805,39 @@ impl QueryResult {
             columns,
             column_types,
             rows: Vec::new(),
+            is_boolean: false,
+        }
+    }
+
+    /// Creates the result of a boolean query, like SPARQL `ASK`.
+    ///
+    /// The answer is a single `Bool` value in an `ask` column.
+    #[must_use]
+    pub fn boolean(answer: bool) -> Self {
+        Self {
+            columns: vec!["ask".to_string()],
+            column_types: vec![grafeo_common::types::LogicalType::Bool],
+            rows: vec![vec![grafeo_common::types::Value::Bool(answer)]],
+            is_boolean: true,
         }
     }
 
+    /// Returns true if this is the answer to a boolean query, like SPARQL
+    /// `ASK`.
+    #[must_use]
+    pub fn is_boolean(&self) -> bool {
+        self.is_boolean
+    }
+
+    /// Returns the answer if this is the result of a boolean query.
+    #[must_use]
+    pub fn as_bool(&self) -> Option<bool> {
+        if !self.is_boolean {
+            return None;
+        }
+        self.rows.first()?.first()?.as_bool()
+    }
+
     /// Returns the number of rows.
     #[must_use]
     pub fn row_count(&self) -> usize {
@@ -1482,6 +1952,21 @@ mod tests {
         assert!(db.config().query_logging);
     }
 
+    #[test]
+    fn test_incremental_statistics_config() {
+        let db = GrafeoDB::with_config(Config::in_memory().with_incremental_statistics()).unwrap();
+        db.create_node(&["Person"]);
+        db.create_node(&["Person"]);
+
+        let stats = db.store().statistics();
+        assert_eq!(stats.get_label("Person").unwrap().node_count, 2);
+
+        // Off by default: writes only mark the statistics stale
+        let db = GrafeoDB::new_in_memory();
+        db.create_node(&["Person"]);
+        assert!(db.store().statistics().get_label("Person").is_none());
+    }
+
     #[test]
     fn test_database_session() {
         let db = GrafeoDB::new_in_memory();
@@ -1529,6 +2014,256 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_undirected_edge_type_survives_reopen() {
+        use grafeo_common::types::Timestamp;
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("undirected_db");
+
+        {
+            let db = GrafeoDB::open(&db_path).unwrap();
+            db.set_edge_type_undirected("FRIEND");
+            let alice = db.create_node(&["Person"]);
+            let bob = db.create_node(&["Person"]);
+            db.set_node_property(bob, "name", "Bob".into());
+            db.create_edge(alice, bob, "FRIEND");
+            db.close().unwrap();
+        }
+
+        let friends_of_bob = |db: &GrafeoDB| {
+            db.execute("MATCH (:Person {name: 'Bob'})-[:FRIEND]->(a:Person) RETURN a")
+                .unwrap()
+                .row_count()
+        };
+
+        // Restored from the checkpoint pages
+        let db = GrafeoDB::open(&db_path).unwrap();
+        assert!(db.store.is_edge_type_undirected("FRIEND"));
+        assert!(db.catalog.is_edge_type_undirected("FRIEND"));
+        assert_eq!(friends_of_bob(&db), 1);
+        db.close().unwrap();
+
+        // And from the WAL alone
+        let replayed =
+            GrafeoDB::open_until(&db_path, RecoveryTarget::Time(Timestamp::now())).unwrap();
+        assert!(replayed.store.is_edge_type_undirected("FRIEND"));
+        assert!(replayed.catalog.is_edge_type_undirected("FRIEND"));
+        assert_eq!(friends_of_bob(&replayed), 1);
+    }
+
+    #[test]
+    fn test_open_read_only_leaves_files_untouched() {
+        use tempfile::tempdir;
+
+        // Every file under `dir` with its contents
+        fn snapshot(dir: &Path) -> std::collections::BTreeMap<std::path::PathBuf, Vec<u8>> {
+            let mut files = std::collections::BTreeMap::new();
+            let mut pending = vec![dir.to_path_buf()];
+            while let Some(dir) = pending.pop() {
+                for entry in std::fs::read_dir(dir).unwrap() {
+                    let path = entry.unwrap().path();
+                    if path.is_dir() {
+                        pending.push(path);
+                    } else {
+                        files.insert(path.clone(), std::fs::read(&path).unwrap());
+                    }
+                }
+            }
+            files
+        }
+
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("read_only_db");
+
+        {
+            let db = GrafeoDB::open(&db_path).unwrap();
+            let alice = db.create_node(&["Person"]);
+            let bob = db.create_node(&["Person"]);
+            db.create_edge(alice, bob, "KNOWS");
+            db.close().unwrap();
+        }
+        {
+            // Leave changes in the WAL past the checkpoint, as a crash would
+            let db = GrafeoDB::open(&db_path).unwrap();
+            db.create_node(&["Person"]);
+            let wal = db.wal().unwrap();
+            wal.log(&WalRecord::commit(grafeo_common::types::TxId::new(100)))
+                .unwrap();
+            wal.sync().unwrap();
+            std::mem::forget(db);
+        }
+        let before = snapshot(&db_path);
+
+        let db = GrafeoDB::open_read_only(&db_path).unwrap();
+        assert_eq!(db.node_count(), 3);
+        assert_eq!(db.edge_count(), 1);
+        let result = db.execute("MATCH (p:Person) RETURN p").unwrap();
+        assert_eq!(result.row_count(), 3);
+        db.close().unwrap();
+        drop(db);
+        assert_eq!(snapshot(&db_path), before);
+
+        assert!(GrafeoDB::open_read_only(dir.path().join("missing")).is_err());
+        assert!(!dir.path().join("missing").exists());
+    }
+
+    #[test]
+    fn test_open_until_recovers_earlier_state() {
+        use grafeo_common::types::Timestamp;
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("pitr_db");
+
+        {
+            let db = GrafeoDB::open(&db_path).unwrap();
+            let alice = db.create_node(&["Person"]);
+            let bob = db.create_node(&["Person"]);
+            db.create_edge(alice, bob, "KNOWS");
+            db.close().unwrap();
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let between = Timestamp::now();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+
+        {
+            let db = GrafeoDB::open(&db_path).unwrap();
+            db.create_node(&["Person"]);
+            db.close().unwrap();
+        }
+
+        let earlier = GrafeoDB::open_until(&db_path, RecoveryTarget::Time(between)).unwrap();
+        assert_eq!(earlier.node_count(), 2);
+        assert_eq!(earlier.edge_count(), 1);
+
+        let latest =
+            GrafeoDB::open_until(&db_path, RecoveryTarget::Time(Timestamp::now())).unwrap();
+        assert_eq!(latest.node_count(), 3);
+    }
+
+    #[test]
+    fn test_incremental_checkpoint_flushes_only_dirty_pages() {
+        use grafeo_adapters::storage::wal::PAGE_SIZE;
+        use grafeo_common::types::{NodeId, Value};
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("paged_db");
+        let crashed_path = dir.path().join("crashed_db");
+
+        let node_count = 3 * PAGE_SIZE;
+        let (nodes, edges) = {
+            let db = GrafeoDB::open(&db_path).unwrap();
+            let ids: Vec<NodeId> = (0..node_count)
+                .map(|i| {
+                    let id = db.create_node(&["Item"]);
+                    db.set_node_property(id, "rank", Value::Int64(i as i64));
+                    id
+                })
+                .collect();
+
+            db.wal_checkpoint().unwrap();
+            let pages = db.pages.as_ref().unwrap();
+            assert_eq!(pages.pages_written(), 3);
+
+            // Touch one node page and one edge page
+            db.set_node_property(ids[7], "rank", Value::Int64(-1));
+            db.create_edge(ids[1], ids[PAGE_SIZE as usize], "NEXT");
+            db.wal_checkpoint().unwrap();
+            assert_eq!(pages.pages_written(), 5);
+            let checkpoint = db
+                .wal()
+                .unwrap()
+                .read_checkpoint_metadata()
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                checkpoint.dirty_pages,
+                vec![PageId::Nodes(0), PageId::Edges(0)]
+            );
+
+            // An open transaction's writes to a page that is flushed stay
+            // out of the checkpoint
+            db.set_node_property(ids[5], "rank", Value::Int64(-3));
+            let mut session = db.session();
+            session.begin_tx().unwrap();
+            session
+                .execute("MATCH (n:Item) WHERE n.rank = 3 SET n.rank = 100")
+                .unwrap();
+            session
+                .execute("MATCH (n:Item) WHERE n.rank = 4 DELETE n")
+                .unwrap();
+            db.wal_checkpoint().unwrap();
+
+            // A committed tail after the checkpoint
+            db.set_node_property(ids[2000], "rank", Value::Int64(-2));
+            db.delete_node(ids[2999]);
+            db.log_wal(&WalRecord::commit(db.tx_manager.begin()))
+                .unwrap();
+            db.wal().unwrap().sync().unwrap();
+
+            // Crash: take the files as they are, with the transaction open
+            copy_dir(&db_path, &crashed_path);
+            (committed_nodes(&db, node_count), committed_edges(&db))
+        };
+
+        let db = GrafeoDB::open(&crashed_path).unwrap();
+        assert_eq!(
+            format!("{:?}", committed_nodes(&db, node_count)),
+            format!("{nodes:?}")
+        );
+        assert_eq!(format!("{:?}", committed_edges(&db)), format!("{edges:?}"));
+        assert_eq!(db.node_count(), node_count as usize - 1);
+        assert_eq!(db.edge_count(), 1);
+        let rank = |i: usize| db.get_node(NodeId::new(i as u64)).unwrap().properties;
+        assert_eq!(rank(3).get(&"rank".into()), Some(&Value::Int64(3)));
+        assert_eq!(rank(4).get(&"rank".into()), Some(&Value::Int64(4)));
+        assert_eq!(rank(5).get(&"rank".into()), Some(&Value::Int64(-3)));
+    }
+
+    /// Copies a database directory, as a crash would leave it on disk.
+    fn copy_dir(from: &Path, to: &Path) {
+        std::fs::create_dir_all(to).unwrap();
+        for entry in std::fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            let target = to.join(entry.file_name());
+            if entry.file_type().unwrap().is_dir() {
+                copy_dir(&entry.path(), &target);
+            } else {
+                std::fs::copy(entry.path(), target).unwrap();
+            }
+        }
+    }
+
+    /// Every committed node among the first `count` ids, in id order.
+    fn committed_nodes(db: &GrafeoDB, count: u64) -> Vec<grafeo_core::graph::lpg::Node> {
+        let epoch = db.tx_manager.current_epoch();
+        (0..count)
+            .filter_map(|id| {
+                db.store.get_node_versioned(
+                    grafeo_common::types::NodeId::new(id),
+                    epoch,
+                    TxId::SYSTEM,
+                )
+            })
+            .collect()
+    }
+
+    /// Every committed edge, in id order.
+    fn committed_edges(db: &GrafeoDB) -> Vec<grafeo_core::graph::lpg::Edge> {
+        let epoch = db.tx_manager.current_epoch();
+        let mut edges: Vec<_> = db
+            .store
+            .all_edges()
+            .filter_map(|e| db.store.get_edge_versioned(e.id, epoch, TxId::SYSTEM))
+            .collect();
+        edges.sort_by_key(|e| e.id);
+        edges
+    }
+
     #[test]
     fn test_wal_logging() {
         use tempfile::tempdir;
@@ -1549,4 +2284,46 @@ mod tests {
 
         db.close().unwrap();
     }
+
+    #[test]
+    fn test_analyze_empty_database() {
+        let db = GrafeoDB::new_in_memory();
+
+        let stats = db.analyze();
+        assert_eq!((stats.nodes_after, stats.edges_after), (0, 0));
+        assert_eq!(stats.label_count, 0);
+        assert_eq!(stats.property_count, 0);
+    }
+
+    #[test]
+    fn test_analyze_rebuilds_cardinality_estimates() {
+        use crate::query::CardinalityEstimator;
+        use crate::query::plan::{LogicalOperator, NodeScanOp};
+        use grafeo_common::types::Value;
+
+        let db = GrafeoDB::new_in_memory();
+        for age in 0..50 {
+            db.create_node_with_props(&["Person"], [("age", Value::Int64(age))]);
+        }
+        let scan = LogicalOperator::NodeScan(NodeScanOp {
+            variable: "p".to_string(),
+            label: Some("Person".to_string()),
+            input: None,
+        });
+        let default_estimate = CardinalityEstimator::new().estimate(&scan);
+
+        let stats = db.analyze();
+        assert_eq!(stats.nodes_before, 0);
+        assert_eq!(stats.nodes_after, 50);
+        assert_eq!(stats.label_count, 1);
+
+        let statistics = db.store().statistics();
+        let estimate = CardinalityEstimator::from_statistics(&statistics).estimate(&scan);
+        assert_ne!(estimate, default_estimate);
+        assert_eq!(estimate, 50.0);
+
+        let age = statistics.get_property("age").unwrap();
+        assert_eq!(age.distinct_count, 50);
+        assert!(age.histogram.is_some());
+    }
 }
@@ -0,0 +1,812 @@
+//! Bulk loading of nodes and edges from CSV files and property maps.
+//!
+//! The first row of a file is the header; every other row becomes one node
+//! or edge with a property per column. Column types are inferred from the
+//! whole file:
+//!
+//! | Cells in the column | Property type |
+//! | ------------------- | ------------- |
+//! | All integers | `Int64` |
+//! | Integers and decimals | `Float64` |
+//! | All `true`/`false` | `Bool` |
+//! | Anything else, or a mix of the above | `String` |
+//!
+//! An empty unquoted cell is a null; `""` is an empty string. Rows are
+//! inserted through the mutation operators a [`DataChunk`] at a time, so a
+//! load costs one pass over the file rather than one query per row.
+//!
+//! Node IDs in the id column are remembered by the session, and edge files
+//! refer to nodes by those IDs.
+//!
+//! Rows that are already in memory skip the CSV step: [`insert_nodes`] and
+//! [`insert_edges`] take one property map per row and feed the same
+//! operators, without going through a query.
+//!
+//! RDF triples are loaded from Turtle files by the `turtle` submodule.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use grafeo_common::types::{EdgeId, EpochId, LogicalType, NodeId, PropertyKey, TxId, Value};
+use grafeo_common::utils::error::{Error, Result};
+use grafeo_core::execution::DataChunk;
+use grafeo_core::execution::chunk::{DEFAULT_CHUNK_SIZE, DataChunkBuilder};
+use grafeo_core::execution::operators::{
+    ConstraintChecker, CreateEdgeOperator, CreateNodeOperator, Operator, OperatorResult,
+    PropertySource,
+};
+use grafeo_core::graph::lpg::LpgStore;
+
+use crate::query::Executor;
+
+#[cfg(all(feature = "sparql", feature = "rdf"))]
+pub mod turtle;
+
+/// Summary of a CSV load.
+#[derive(Debug, Clone)]
+pub struct CsvLoadStats {
+    /// Number of nodes or edges created.
+    pub rows_loaded: usize,
+    /// Inferred type of every property column, in file order.
+    pub column_types: Vec<(String, LogicalType)>,
+    /// Duration in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Inferred type of a CSV column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    Int,
+    Float,
+    Bool,
+    String,
+}
+
+impl ColumnType {
+    /// Picks the narrowest type a single cell parses as.
+    fn of(cell: &str) -> Self {
+        if cell.parse::<i64>().is_ok() {
+            Self::Int
+        } else if cell.parse::<f64>().is_ok() {
+            Self::Float
+        } else if cell.eq_ignore_ascii_case("true") || cell.eq_ignore_ascii_case("false") {
+            Self::Bool
+        } else {
+            Self::String
+        }
+    }
+
+    /// Picks a type that holds values of both types.
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Int, Self::Float) | (Self::Float, Self::Int) => Self::Float,
+            _ => Self::String,
+        }
+    }
+
+    /// Infers the type of a column from its non-null cells.
+    ///
+    /// A column with only nulls is a string column.
+    fn infer<'a>(cells: impl Iterator<Item = Option<&'a str>>) -> Self {
+        cells
+            .flatten()
+            .map(Self::of)
+            .reduce(Self::merge)
+            .unwrap_or(Self::String)
+    }
+
+    fn logical_type(self) -> LogicalType {
+        match self {
+            Self::Int => LogicalType::Int64,
+            Self::Float => LogicalType::Float64,
+            Self::Bool => LogicalType::Bool,
+            Self::String => LogicalType::String,
+        }
+    }
+
+    /// Converts a cell, which is known to fit this type.
+    fn value(self, cell: Option<&str>) -> Value {
+        let Some(cell) = cell else {
+            return Value::Null;
+        };
+        match self {
+            Self::Int => cell.parse::<i64>().map_or(Value::Null, Value::Int64),
+            Self::Float => cell.parse::<f64>().map_or(Value::Null, Value::Float64),
+            Self::Bool => Value::Bool(cell.eq_ignore_ascii_case("true")),
+            Self::String => Value::from(cell),
+        }
+    }
+}
+
+/// A parsed CSV file.
+struct CsvFile {
+    header: Vec<String>,
+    /// Cells of each data row; `None` is an empty unquoted cell.
+    rows: Vec<Vec<Option<String>>>,
+}
+
+impl CsvFile {
+    fn read(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let mut records = parse(&text)?.into_iter();
+        let (_, header) = records
+            .next()
+            .ok_or_else(|| invalid(path, "file has no header row"))?;
+        let header: Vec<String> = header.into_iter().map(Option::unwrap_or_default).collect();
+
+        let mut rows = Vec::new();
+        for (line, record) in records {
+            if record.len() != header.len() {
+                return Err(invalid(
+                    path,
+                    &format!(
+                        "line {line} has {} fields, expected {}",
+                        record.len(),
+                        header.len()
+                    ),
+                ));
+            }
+            rows.push(record);
+        }
+        Ok(Self { header, rows })
+    }
+
+    fn column_index(&self, path: &Path, name: &str) -> Result<usize> {
+        self.header
+            .iter()
+            .position(|column| column == name)
+            .ok_or_else(|| invalid(path, &format!("no column named '{name}'")))
+    }
+
+    fn cells(&self, column: usize) -> impl Iterator<Item = Option<&str>> {
+        self.rows.iter().map(move |row| row[column].as_deref())
+    }
+}
+
+fn invalid(path: &Path, message: &str) -> Error {
+    Error::InvalidValue(format!("{}: {message}", path.display()))
+}
+
+/// Splits CSV text into records, each tagged with its starting line number.
+///
+/// Fields may be quoted to hold commas, line breaks, or doubled quotes.
+/// Blank lines are skipped.
+fn parse(text: &str) -> Result<Vec<(usize, Vec<Option<String>>)>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() && !quoted => {
+                quoted = true;
+                in_quotes = true;
+            }
+            ',' => record.push(take_field(&mut field, &mut quoted)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(take_field(&mut field, &mut quoted));
+                if !(record.len() == 1 && record[0].is_none()) {
+                    records.push((record_line, std::mem::take(&mut record)));
+                }
+                record.clear();
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(Error::InvalidValue(format!(
+            "unterminated quoted field starting on line {record_line}"
+        )));
+    }
+    if !field.is_empty() || quoted || !record.is_empty() {
+        record.push(take_field(&mut field, &mut quoted));
+        records.push((record_line, record));
+    }
+    Ok(records)
+}
+
+fn take_field(field: &mut String, quoted: &mut bool) -> Option<String> {
+    let value = if field.is_empty() && !*quoted {
+        None
+    } else {
+        Some(std::mem::take(field))
+    };
+    *quoted = false;
+    value
+}
+
+/// Feeds prebuilt chunks to a mutation operator.
+struct ChunkInput {
+    chunks: std::vec::IntoIter<DataChunk>,
+}
+
+impl Operator for ChunkInput {
+    fn next(&mut self) -> OperatorResult {
+        Ok(self.chunks.next())
+    }
+
+    fn reset(&mut self) {
+        // Chunks are handed off as they are read, so there is nothing to rewind
+    }
+
+    fn name(&self) -> &'static str {
+        "CsvInput"
+    }
+}
+
+/// Builds chunks of `DEFAULT_CHUNK_SIZE` rows from per-row values.
+fn build_chunks(types: &[LogicalType], rows: impl Iterator<Item = Vec<Value>>) -> Vec<DataChunk> {
+    let mut chunks = Vec::new();
+    let mut builder = DataChunkBuilder::with_capacity(types, DEFAULT_CHUNK_SIZE);
+    for row in rows {
+        for (column, value) in row.into_iter().enumerate() {
+            if let Some(vector) = builder.column_mut(column) {
+                vector.push_value(value);
+            }
+        }
+        builder.advance_row();
+        if builder.is_full() {
+            let full = std::mem::replace(
+                &mut builder,
+                DataChunkBuilder::with_capacity(types, DEFAULT_CHUNK_SIZE),
+            );
+            chunks.push(full.finish());
+        }
+    }
+    if builder.row_count() > 0 {
+        chunks.push(builder.finish());
+    }
+    chunks
+}
+
+/// Where loaded rows are written.
+pub(crate) struct LoadTarget<'a> {
+    pub store: &'a Arc<LpgStore>,
+    pub epoch: EpochId,
+    pub tx_id: Option<TxId>,
+    /// Constraints every node property is checked against.
+    pub constraints: Option<Arc<dyn ConstraintChecker>>,
+}
+
+/// Loads one node per row of `path`, remembering each row's ID in `ids`.
+///
+/// Every column, the id column included, becomes a property.
+pub(crate) fn load_nodes(
+    target: &LoadTarget<'_>,
+    ids: &mut HashMap<String, NodeId>,
+    path: &Path,
+    label: &str,
+    id_column: &str,
+) -> Result<CsvLoadStats> {
+    let start = std::time::Instant::now();
+    let file = CsvFile::read(path)?;
+    let id_index = file.column_index(path, id_column)?;
+
+    // Check the IDs before inserting, so a bad file loads nothing
+    let mut row_ids = Vec::with_capacity(file.rows.len());
+    let mut seen = HashSet::with_capacity(file.rows.len());
+    for (row, id) in file.cells(id_index).enumerate() {
+        let id = id.ok_or_else(|| invalid(path, &format!("row {} has no id", row + 1)))?;
+        if ids.contains_key(id) || !seen.insert(id) {
+            return Err(invalid(path, &format!("duplicate node id '{id}'")));
+        }
+        row_ids.push(id);
+    }
+
+    let types: Vec<ColumnType> = (0..file.header.len())
+        .map(|column| ColumnType::infer(file.cells(column)))
+        .collect();
+    let mut schema: Vec<LogicalType> = types.iter().map(|t| t.logical_type()).collect();
+    let chunks = build_chunks(
+        &schema,
+        file.rows.iter().map(|row| {
+            row.iter()
+                .zip(&types)
+                .map(|(cell, ty)| ty.value(cell.as_deref()))
+                .collect()
+        }),
+    );
+
+    let properties = file
+        .header
+        .iter()
+        .enumerate()
+        .map(|(column, name)| (name.clone(), PropertySource::Column(column)))
+        .collect();
+    let output_column = schema.len();
+    schema.push(LogicalType::Node);
+    let mut operator = CreateNodeOperator::new(
+        Arc::clone(target.store),
+        Some(Box::new(ChunkInput {
+            chunks: chunks.into_iter(),
+        })),
+        vec![label.to_string()],
+        properties,
+        schema,
+        output_column,
+    )
+    .with_tx_context(target.epoch, target.tx_id)
+    .with_constraints(target.constraints.clone());
+    let result = Executor::new().execute(&mut operator)?;
+
+    for (id, row) in row_ids.into_iter().zip(&result.rows) {
+        if let Some(Value::Int64(node)) = row.get(output_column) {
+            ids.insert(id.to_string(), NodeId::new(*node as u64));
+        }
+    }
+
+    Ok(CsvLoadStats {
+        rows_loaded: result.rows.len(),
+        column_types: file
+            .header
+            .into_iter()
+            .zip(types.iter().map(|t| t.logical_type()))
+            .collect(),
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+/// Loads one edge per row of `path`, resolving endpoints through `ids`.
+///
+/// The source and target columns pick the endpoints; every other column
+/// becomes a property.
+pub(crate) fn load_edges(
+    target: &LoadTarget<'_>,
+    ids: &HashMap<String, NodeId>,
+    path: &Path,
+    edge_type: &str,
+    source_column: &str,
+    target_column: &str,
+) -> Result<CsvLoadStats> {
+    let start = std::time::Instant::now();
+    let file = CsvFile::read(path)?;
+    let src_index = file.column_index(path, source_column)?;
+    let dst_index = file.column_index(path, target_column)?;
+
+    // Resolve every endpoint before inserting, so a bad file loads nothing
+    let resolve = |row: usize, cell: Option<&str>| {
+        let id = cell.ok_or_else(|| invalid(path, &format!("row {} has no endpoint", row + 1)))?;
+        ids.get(id)
+            .copied()
+            .ok_or_else(|| invalid(path, &format!("unknown node id '{id}'")))
+    };
+    let endpoints = file
+        .cells(src_index)
+        .zip(file.cells(dst_index))
+        .enumerate()
+        .map(|(row, (src, dst))| Ok((resolve(row, src)?, resolve(row, dst)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let property_columns: Vec<usize> = (0..file.header.len())
+        .filter(|&column| column != src_index && column != dst_index)
+        .collect();
+    let types: Vec<ColumnType> = property_columns
+        .iter()
+        .map(|&column| ColumnType::infer(file.cells(column)))
+        .collect();
+
+    let mut schema = vec![LogicalType::Node, LogicalType::Node];
+    schema.extend(types.iter().map(|t| t.logical_type()));
+    let chunks = build_chunks(
+        &schema,
+        file.rows.iter().zip(&endpoints).map(|(row, (src, dst))| {
+            let mut values = vec![
+                Value::Int64(src.as_u64() as i64),
+                Value::Int64(dst.as_u64() as i64),
+            ];
+            values.extend(
+                property_columns
+                    .iter()
+                    .zip(&types)
+                    .map(|(&column, ty)| ty.value(row[column].as_deref())),
+            );
+            values
+        }),
+    );
+
+    let properties = property_columns
+        .iter()
+        .enumerate()
+        .map(|(i, &column)| (file.header[column].clone(), PropertySource::Column(i + 2)))
+        .collect();
+    let mut operator = CreateEdgeOperator::new(
+        Arc::clone(target.store),
+        Box::new(ChunkInput {
+            chunks: chunks.into_iter(),
+        }),
+        0,
+        1,
+        edge_type.to_string(),
+        properties,
+        schema,
+        None,
+    )
+    .with_tx_context(target.epoch, target.tx_id);
+    let result = Executor::new().execute(&mut operator)?;
+
+    Ok(CsvLoadStats {
+        rows_loaded: result.rows.len(),
+        column_types: property_columns
+            .iter()
+            .zip(&types)
+            .map(|(&column, ty)| (file.header[column].clone(), ty.logical_type()))
+            .collect(),
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+/// Inserts one `label` node per property map, returning the IDs in order.
+pub(crate) fn insert_nodes(
+    target: &LoadTarget<'_>,
+    label: &str,
+    rows: impl IntoIterator<Item = HashMap<PropertyKey, Value>>,
+) -> Result<Vec<NodeId>> {
+    let mut ids = Vec::new();
+    for_each_batch(
+        rows,
+        |properties| properties,
+        |keys, rows| {
+            let mut schema = vec![LogicalType::Any; keys.len()];
+            let chunks = build_chunks(
+                &schema,
+                rows.into_iter().map(|mut properties| {
+                    keys.iter()
+                        .map(|key| properties.remove(key).unwrap_or(Value::Null))
+                        .collect()
+                }),
+            );
+
+            let output_column = schema.len();
+            schema.push(LogicalType::Node);
+            let mut operator = CreateNodeOperator::new(
+                Arc::clone(target.store),
+                Some(Box::new(ChunkInput {
+                    chunks: chunks.into_iter(),
+                })),
+                vec![label.to_string()],
+                property_sources(keys, 0),
+                schema,
+                output_column,
+            )
+            .with_tx_context(target.epoch, target.tx_id)
+            .with_constraints(target.constraints.clone());
+            let result = Executor::new().execute(&mut operator)?;
+            ids.extend(
+                result
+                    .rows
+                    .iter()
+                    .filter_map(|row| match row.get(output_column) {
+                        Some(Value::Int64(id)) => Some(NodeId::new(*id as u64)),
+                        _ => None,
+                    }),
+            );
+            Ok(())
+        },
+    )?;
+    Ok(ids)
+}
+
+/// Inserts one `edge_type` edge per `(source, target, properties)` row,
+/// returning the IDs in order.
+///
+/// Fails with [`Error::NodeNotFound`] on the first batch with an endpoint
+/// the target can't see; earlier batches stay inserted.
+pub(crate) fn insert_edges(
+    target: &LoadTarget<'_>,
+    edge_type: &str,
+    rows: impl IntoIterator<Item = (NodeId, NodeId, HashMap<PropertyKey, Value>)>,
+) -> Result<Vec<EdgeId>> {
+    let visible = |id: NodeId| {
+        match target.tx_id {
+            Some(tx) => target.store.get_node_versioned(id, target.epoch, tx),
+            None => target.store.get_node_at_epoch(id, target.epoch),
+        }
+        .is_some()
+    };
+
+    let mut ids = Vec::new();
+    for_each_batch(
+        rows,
+        |(_, _, properties)| properties,
+        |keys, rows| {
+            if let Some(&missing) = rows
+                .iter()
+                .flat_map(|(src, dst, _)| [src, dst])
+                .find(|&&id| !visible(id))
+            {
+                return Err(Error::NodeNotFound(missing));
+            }
+
+            let mut schema = vec![LogicalType::Node, LogicalType::Node];
+            schema.extend(keys.iter().map(|_| LogicalType::Any));
+            let chunks = build_chunks(
+                &schema,
+                rows.into_iter().map(|(src, dst, mut properties)| {
+                    let mut values = vec![
+                        Value::Int64(src.as_u64() as i64),
+                        Value::Int64(dst.as_u64() as i64),
+                    ];
+                    values.extend(
+                        keys.iter()
+                            .map(|key| properties.remove(key).unwrap_or(Value::Null)),
+                    );
+                    values
+                }),
+            );
+
+            let output_column = schema.len();
+            schema.push(LogicalType::Edge);
+            let mut operator = CreateEdgeOperator::new(
+                Arc::clone(target.store),
+                Box::new(ChunkInput {
+                    chunks: chunks.into_iter(),
+                }),
+                0,
+                1,
+                edge_type.to_string(),
+                property_sources(keys, 2),
+                schema,
+                Some(output_column),
+            )
+            .with_tx_context(target.epoch, target.tx_id);
+            let result = Executor::new().execute(&mut operator)?;
+            ids.extend(
+                result
+                    .rows
+                    .iter()
+                    .filter_map(|row| match row.get(output_column) {
+                        Some(Value::Int64(id)) => Some(EdgeId::new(*id as u64)),
+                        _ => None,
+                    }),
+            );
+            Ok(())
+        },
+    )?;
+    Ok(ids)
+}
+
+/// Splits `rows` into batches of up to `DEFAULT_CHUNK_SIZE` consecutive rows
+/// whose property maps have the same keys, and hands each to `insert`.
+///
+/// Keeping the keys uniform within a batch means a row never gets a null
+/// for a property only its neighbours have.
+fn for_each_batch<T>(
+    rows: impl IntoIterator<Item = T>,
+    properties: impl Fn(&T) -> &HashMap<PropertyKey, Value>,
+    mut insert: impl FnMut(&[PropertyKey], Vec<T>) -> Result<()>,
+) -> Result<()> {
+    let mut keys: Vec<PropertyKey> = Vec::new();
+    let mut batch: Vec<T> = Vec::with_capacity(DEFAULT_CHUNK_SIZE);
+    for row in rows {
+        let map = properties(&row);
+        let same_keys = map.len() == keys.len() && keys.iter().all(|key| map.contains_key(key));
+        if !batch.is_empty() && (!same_keys || batch.len() == DEFAULT_CHUNK_SIZE) {
+            insert(&keys, std::mem::take(&mut batch))?;
+        }
+        if !same_keys {
+            keys = map.keys().cloned().collect();
+        }
+        batch.push(row);
+    }
+    if !batch.is_empty() {
+        insert(&keys, batch)?;
+    }
+    Ok(())
+}
+
+/// Maps each key to the chunk column it's stored in, starting at `first`.
+fn property_sources(keys: &[PropertyKey], first: usize) -> Vec<(String, PropertySource)> {
+    keys.iter()
+        .enumerate()
+        .map(|(i, key)| (key.as_str().to_string(), PropertySource::Column(first + i)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(text: &str) -> Vec<Vec<Option<String>>> {
+        parse(text)
+            .unwrap()
+            .into_iter()
+            .map(|(_, record)| record)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_quoted_fields() {
+        let records = cells("a,b,c\n\"x, y\",\"say \"\"hi\"\"\",\"two\nlines\"\r\n");
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[1],
+            vec![
+                Some("x, y".to_string()),
+                Some("say \"hi\"".to_string()),
+                Some("two\nlines".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_cells() {
+        let records = cells("a,b,c\n,\"\",z");
+        assert_eq!(
+            records[1],
+            vec![None, Some(String::new()), Some("z".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_parse_unterminated_quote() {
+        assert!(parse("a\n\"open").is_err());
+    }
+
+    #[test]
+    fn test_infer_column_types() {
+        let infer = |cells: &[Option<&str>]| ColumnType::infer(cells.iter().copied());
+        assert_eq!(infer(&[Some("1"), None, Some("-7")]), ColumnType::Int);
+        assert_eq!(infer(&[Some("1"), Some("2.5")]), ColumnType::Float);
+        assert_eq!(infer(&[Some("true"), Some("FALSE")]), ColumnType::Bool);
+        assert_eq!(infer(&[Some("1"), Some("true")]), ColumnType::String);
+        assert_eq!(infer(&[Some("1.5"), Some("n/a")]), ColumnType::String);
+        assert_eq!(infer(&[None, None]), ColumnType::String);
+    }
+
+    fn write_csv(dir: &tempfile::TempDir, name: &str, text: &str) -> std::path::PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, text).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_load_nodes_and_edges() {
+        let dir = tempfile::tempdir().unwrap();
+        let people = write_csv(
+            &dir,
+            "people.csv",
+            "id,name,age,score,active\n\
+             1,\"Smith, Alice\",30,4.5,true\n\
+             2,Bob,,3,false\n\
+             3,Carol,41,n/a,true\n",
+        );
+        let knows = write_csv(&dir, "knows.csv", "from,to,since\n1,2,2020\n2,3,2021\n");
+
+        let db = crate::GrafeoDB::new_in_memory();
+        let session = db.session();
+        let stats = session.load_csv_nodes(&people, "Person", "id").unwrap();
+        assert_eq!(stats.rows_loaded, 3);
+        assert_eq!(
+            stats.column_types,
+            vec![
+                ("id".to_string(), LogicalType::Int64),
+                ("name".to_string(), LogicalType::String),
+                ("age".to_string(), LogicalType::Int64),
+                ("score".to_string(), LogicalType::String),
+                ("active".to_string(), LogicalType::Bool),
+            ]
+        );
+
+        let stats = session
+            .load_csv_edges(&knows, "KNOWS", "from", "to")
+            .unwrap();
+        assert_eq!(stats.rows_loaded, 2);
+        assert_eq!(
+            stats.column_types,
+            vec![("since".to_string(), LogicalType::Int64)]
+        );
+        assert_eq!(db.node_count(), 3);
+        assert_eq!(db.edge_count(), 2);
+
+        let result = session
+            .execute("MATCH (a:Person)-[k:KNOWS]->(b:Person) WHERE a.id = 1 RETURN a.name, a.age, k.since, b.name, b.age")
+            .unwrap();
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(
+            result.rows[0],
+            vec![
+                Value::from("Smith, Alice"),
+                Value::Int64(30),
+                Value::Int64(2020),
+                Value::from("Bob"),
+                Value::Null,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_spans_multiple_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let text: String = std::iter::once("id".to_string())
+            .chain((0..DEFAULT_CHUNK_SIZE * 2 + 5).map(|i| i.to_string()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let path = write_csv(&dir, "many.csv", &text);
+
+        let db = crate::GrafeoDB::new_in_memory();
+        let stats = db.session().load_csv_nodes(&path, "N", "id").unwrap();
+        assert_eq!(stats.rows_loaded, DEFAULT_CHUNK_SIZE * 2 + 5);
+        assert_eq!(db.node_count(), DEFAULT_CHUNK_SIZE * 2 + 5);
+    }
+
+    #[test]
+    fn test_load_rejects_bad_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let duplicate = write_csv(&dir, "dup.csv", "id\n1\n1\n");
+        let nodes = write_csv(&dir, "nodes.csv", "id\n1\n");
+        let dangling = write_csv(&dir, "edges.csv", "src,dst\n1,9\n");
+
+        let db = crate::GrafeoDB::new_in_memory();
+        let session = db.session();
+        assert!(session.load_csv_nodes(&duplicate, "N", "id").is_err());
+        assert!(session.load_csv_nodes(&nodes, "N", "missing").is_err());
+        assert_eq!(db.node_count(), 0);
+
+        session.load_csv_nodes(&nodes, "N", "id").unwrap();
+        assert!(
+            session
+                .load_csv_edges(&dangling, "E", "src", "dst")
+                .is_err()
+        );
+        assert_eq!(db.edge_count(), 0);
+    }
+
+    #[test]
+    fn test_insert_rows_with_differing_keys() {
+        let db = crate::GrafeoDB::new_in_memory();
+        let session = db.session();
+        let rows = vec![
+            HashMap::from([("name".into(), Value::from("Alix"))]),
+            HashMap::from([
+                ("name".into(), Value::from("Gus")),
+                ("age".into(), Value::Int64(40)),
+            ]),
+            HashMap::new(),
+        ];
+        let ids = session.insert_nodes("Person", rows).unwrap();
+        assert_eq!(ids.len(), 3);
+
+        // A row only gets the properties it was given
+        let store = db.store();
+        assert_eq!(store.get_node(ids[0]).unwrap().properties.len(), 1);
+        assert_eq!(store.get_node(ids[1]).unwrap().properties.len(), 2);
+        assert!(store.get_node(ids[2]).unwrap().properties.is_empty());
+
+        let edges = session
+            .insert_edges("KNOWS", [(ids[0], ids[1], HashMap::new())])
+            .unwrap();
+        assert_eq!(store.get_edge(edges[0]).unwrap().dst, ids[1]);
+
+        let missing = NodeId::new(999);
+        assert!(matches!(
+            session.insert_edges("KNOWS", [(ids[0], missing, HashMap::new())]),
+            Err(Error::NodeNotFound(id)) if id == missing
+        ));
+        assert_eq!(db.edge_count(), 1);
+    }
+}
@@ -0,0 +1,868 @@
+//! Loading RDF triples from [Turtle](https://www.w3.org/TR/turtle/) files.
+//!
+//! Supported syntax: `@prefix` and `@base` (and their SPARQL-style `PREFIX`
+//! and `BASE` forms), IRIs relative to the base, prefixed names, `a`,
+//! predicate and object lists, blank node labels and `[ ... ]` property
+//! lists, collections, numeric and boolean shorthands, and `#` comments.
+//!
+//! Typed literals are converted the way SPARQL literals are, so a loaded
+//! `"42"^^xsd:int` is the same term that `INSERT DATA` stores for it and a
+//! query matches with `42`. Language tags are kept.
+//!
+//! Blank nodes are skolemized: labels are rewritten to ids derived from a
+//! hash of the document. Loading the same file again yields the same nodes,
+//! while the same label in two different files stays two different nodes.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use grafeo_common::types::{TxId, Value};
+use grafeo_common::utils::error::{Error, Result};
+use grafeo_common::utils::hash::stable_hash;
+use grafeo_core::graph::rdf::{Literal, RdfStore, Term, Triple};
+
+use crate::query::planner_rdf::literal_term;
+use crate::query::sparql_translator::typed_literal_to_value;
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+
+/// Summary of a Turtle load.
+#[derive(Debug, Clone)]
+pub struct TurtleLoadStats {
+    /// Number of triples in the file, including any already in the store.
+    pub triples_loaded: usize,
+    /// Duration in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Inserts the triples of the Turtle file at `path` into `store`.
+///
+/// With a `tx_id`, inserts are buffered in that transaction. The whole file
+/// is parsed first, so a malformed file inserts nothing.
+pub(crate) fn load(store: &RdfStore, tx_id: Option<TxId>, path: &Path) -> Result<TurtleLoadStats> {
+    let start = std::time::Instant::now();
+    let text = std::fs::read_to_string(path)?;
+    let triples = parse(&text)
+        .map_err(|message| Error::InvalidValue(format!("{}: {message}", path.display())))?;
+
+    let triples_loaded = triples.len();
+    for triple in triples {
+        match tx_id {
+            Some(tx_id) => store.insert_in_tx(tx_id, triple),
+            None => {
+                store.insert(triple);
+            }
+        }
+    }
+
+    Ok(TurtleLoadStats {
+        triples_loaded,
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+/// A parse error message, prefixed with its line number.
+type ParseResult<T> = std::result::Result<T, String>;
+
+/// Parses a Turtle document into triples, in document order.
+fn parse(text: &str) -> ParseResult<Vec<Triple>> {
+    let mut parser = Parser {
+        text,
+        pos: 0,
+        line: 1,
+        base: None,
+        prefixes: HashMap::new(),
+        blank_prefix: format!("b{:016x}", stable_hash(text.as_bytes())),
+        anon_count: 0,
+        triples: Vec::new(),
+    };
+    parser.document()?;
+    Ok(parser.triples)
+}
+
+/// Recursive descent parser over the document text.
+struct Parser<'a> {
+    text: &'a str,
+    /// Byte offset of the next character.
+    pos: usize,
+    line: usize,
+    base: Option<String>,
+    prefixes: HashMap<String, String>,
+    /// Start of every blank node id, unique to the document.
+    blank_prefix: String,
+    anon_count: usize,
+    triples: Vec<Triple>,
+}
+
+impl Parser<'_> {
+    fn document(&mut self) -> ParseResult<()> {
+        loop {
+            self.skip_ws();
+            if self.peek().is_none() {
+                return Ok(());
+            }
+            self.statement()?;
+        }
+    }
+
+    fn statement(&mut self) -> ParseResult<()> {
+        if self.eat('@') {
+            match self.word().as_str() {
+                "prefix" => self.prefix_decl()?,
+                "base" => self.base_decl()?,
+                other => return Err(self.error(format_args!("unknown directive '@{other}'"))),
+            }
+            return self.expect('.');
+        }
+        // SPARQL-style directives have no trailing '.'
+        if self.eat_keyword("PREFIX") {
+            return self.prefix_decl();
+        }
+        if self.eat_keyword("BASE") {
+            return self.base_decl();
+        }
+
+        self.skip_ws();
+        if self.peek() == Some('[') {
+            // `[ :p :o ] .` is a complete statement on its own
+            let subject = self.blank_node_property_list()?;
+            self.skip_ws();
+            if self.peek() != Some('.') {
+                self.predicate_object_list(&subject)?;
+            }
+        } else {
+            let subject = self.subject()?;
+            self.predicate_object_list(&subject)?;
+        }
+        self.expect('.')
+    }
+
+    fn prefix_decl(&mut self) -> ParseResult<()> {
+        self.skip_ws();
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c != ':' && is_name_char(c)) {
+            self.bump();
+        }
+        let prefix = self.text[start..self.pos].to_string();
+        self.expect(':')?;
+        self.skip_ws();
+        let namespace = self.iriref()?;
+        self.prefixes.insert(prefix, namespace);
+        Ok(())
+    }
+
+    fn base_decl(&mut self) -> ParseResult<()> {
+        self.skip_ws();
+        self.base = Some(self.iriref()?);
+        Ok(())
+    }
+
+    fn subject(&mut self) -> ParseResult<Term> {
+        match self.peek() {
+            Some('<') => Ok(Term::iri(self.iriref()?)),
+            Some('_') if self.peek_nth(1) == Some(':') => self.blank_label(),
+            Some('(') => self.collection(),
+            Some('"' | '\'') => Err(self.error("a literal can't be a subject")),
+            _ => {
+                let name = self.name_token()?;
+                Ok(Term::iri(self.expand(&name)?))
+            }
+        }
+    }
+
+    fn predicate_object_list(&mut self, subject: &Term) -> ParseResult<()> {
+        loop {
+            let predicate = self.verb()?;
+            self.object_list(subject, &predicate)?;
+
+            self.skip_ws();
+            if !self.eat(';') {
+                return Ok(());
+            }
+            // Repeated and trailing ';' are allowed
+            loop {
+                self.skip_ws();
+                if !self.eat(';') {
+                    break;
+                }
+            }
+            if matches!(self.peek(), Some('.' | ']') | None) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn verb(&mut self) -> ParseResult<Term> {
+        self.skip_ws();
+        if self.peek() == Some('<') {
+            return Ok(Term::iri(self.iriref()?));
+        }
+        let name = self.name_token()?;
+        if name == "a" {
+            return Ok(Term::iri(RDF_TYPE));
+        }
+        Ok(Term::iri(self.expand(&name)?))
+    }
+
+    fn object_list(&mut self, subject: &Term, predicate: &Term) -> ParseResult<()> {
+        loop {
+            let object = self.object()?;
+            self.triples
+                .push(Triple::new(subject.clone(), predicate.clone(), object));
+            self.skip_ws();
+            if !self.eat(',') {
+                return Ok(());
+            }
+        }
+    }
+
+    fn object(&mut self) -> ParseResult<Term> {
+        self.skip_ws();
+        match self.peek() {
+            Some('<') => Ok(Term::iri(self.iriref()?)),
+            Some('_') if self.peek_nth(1) == Some(':') => self.blank_label(),
+            Some('[') => self.blank_node_property_list(),
+            Some('(') => self.collection(),
+            Some('"' | '\'') => self.literal(),
+            Some(c) if c.is_ascii_digit() => self.number(),
+            Some('+' | '-' | '.')
+                if self
+                    .peek_nth(1)
+                    .is_some_and(|c| c.is_ascii_digit() || c == '.') =>
+            {
+                self.number()
+            }
+            _ => {
+                let name = self.name_token()?;
+                match name.as_str() {
+                    "true" | "false" => Ok(Term::Literal(Literal::boolean(name == "true"))),
+                    _ => Ok(Term::iri(self.expand(&name)?)),
+                }
+            }
+        }
+    }
+
+    fn blank_label(&mut self) -> ParseResult<Term> {
+        self.pos += 2; // `_:`
+        let label = self.name_token()?;
+        Ok(Term::blank(format!("{}_{label}", self.blank_prefix)))
+    }
+
+    /// A fresh blank node for `[]` and collections.
+    fn anon(&mut self) -> Term {
+        self.anon_count += 1;
+        Term::blank(format!("{}.{}", self.blank_prefix, self.anon_count))
+    }
+
+    fn blank_node_property_list(&mut self) -> ParseResult<Term> {
+        self.bump(); // `[`
+        let node = self.anon();
+        self.skip_ws();
+        if !self.eat(']') {
+            self.predicate_object_list(&node)?;
+            self.expect(']')?;
+        }
+        Ok(node)
+    }
+
+    /// Parses `( ... )` into an `rdf:first`/`rdf:rest` list.
+    fn collection(&mut self) -> ParseResult<Term> {
+        self.bump(); // `(`
+        let mut items = Vec::new();
+        loop {
+            self.skip_ws();
+            if self.eat(')') {
+                break;
+            }
+            if self.peek().is_none() {
+                return Err(self.error("unterminated collection"));
+            }
+            items.push(self.object()?);
+        }
+
+        let mut head = Term::iri(RDF_NIL);
+        for item in items.into_iter().rev() {
+            let node = self.anon();
+            self.triples
+                .push(Triple::new(node.clone(), Term::iri(RDF_FIRST), item));
+            self.triples
+                .push(Triple::new(node.clone(), Term::iri(RDF_REST), head));
+            head = node;
+        }
+        Ok(head)
+    }
+
+    fn literal(&mut self) -> ParseResult<Term> {
+        let value = self.string()?;
+        if self.eat('@') {
+            let language = self.word();
+            if language.is_empty() {
+                return Err(self.error("expected a language tag after '@'"));
+            }
+            return Ok(Term::lang_literal(value, language));
+        }
+        if self.rest().starts_with("^^") {
+            self.pos += 2;
+            let datatype = if self.peek() == Some('<') {
+                self.iriref()?
+            } else {
+                let name = self.name_token()?;
+                self.expand(&name)?
+            };
+            return Ok(typed_literal(value, &datatype));
+        }
+        Ok(Term::literal(value))
+    }
+
+    /// Parses a quoted string, short or `"""long"""`, resolving escapes.
+    fn string(&mut self) -> ParseResult<String> {
+        let quote = self.bump().unwrap_or('"');
+        let long = self.peek() == Some(quote) && self.peek_nth(1) == Some(quote);
+        if long {
+            self.pos += 2;
+        } else if self.eat(quote) {
+            return Ok(String::new());
+        }
+
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                None => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => {
+                    if !long {
+                        return Ok(value);
+                    }
+                    if self.peek() == Some(quote) && self.peek_nth(1) == Some(quote) {
+                        // Quotes right before the closing ones belong to the string
+                        while self.peek_nth(2) == Some(quote) {
+                            value.push(quote);
+                            self.bump();
+                        }
+                        self.pos += 2;
+                        return Ok(value);
+                    }
+                    value.push(c);
+                }
+                Some('\n' | '\r') if !long => return Err(self.error("line break in string")),
+                Some('\\') => value.push(self.escape()?),
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    /// Resolves the escape after a `\`.
+    fn escape(&mut self) -> ParseResult<char> {
+        match self.bump() {
+            Some('t') => Ok('\t'),
+            Some('b') => Ok('\u{8}'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('f') => Ok('\u{c}'),
+            Some(c @ ('"' | '\'' | '\\')) => Ok(c),
+            Some('u') => self.unicode_escape(4),
+            Some('U') => self.unicode_escape(8),
+            _ => Err(self.error("invalid escape sequence")),
+        }
+    }
+
+    fn unicode_escape(&mut self, digits: usize) -> ParseResult<char> {
+        let hex = self
+            .rest()
+            .get(..digits)
+            .ok_or_else(|| self.error("truncated unicode escape"))?;
+        let c = u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error(format_args!("invalid unicode escape '{hex}'")))?;
+        self.pos += digits;
+        Ok(c)
+    }
+
+    /// Parses an integer, decimal or double shorthand.
+    fn number(&mut self) -> ParseResult<Term> {
+        let start = self.pos;
+        if matches!(self.peek(), Some('+' | '-')) {
+            self.bump();
+        }
+        let mut digits = self.digits();
+        let mut datatype = Literal::XSD_INTEGER;
+        // A '.' not followed by a digit ends the statement instead
+        if self.peek() == Some('.') && self.peek_nth(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+            digits += self.digits();
+            datatype = Literal::XSD_DECIMAL;
+        }
+        if digits > 0 && matches!(self.peek(), Some('e' | 'E')) {
+            self.bump();
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.bump();
+            }
+            if self.digits() == 0 {
+                return Err(self.error("missing exponent digits"));
+            }
+            datatype = Literal::XSD_DOUBLE;
+        }
+        if digits == 0 {
+            return Err(self.error("invalid number"));
+        }
+        Ok(typed_literal(
+            self.text[start..self.pos].to_string(),
+            datatype,
+        ))
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+        self.pos - start
+    }
+
+    /// Parses `<...>` and resolves it against the base.
+    fn iriref(&mut self) -> ParseResult<String> {
+        if !self.eat('<') {
+            return Err(self.error("expected '<'"));
+        }
+        let mut iri = String::new();
+        loop {
+            match self.bump() {
+                Some('>') => break,
+                Some('\\') => match self.bump() {
+                    Some('u') => iri.push(self.unicode_escape(4)?),
+                    Some('U') => iri.push(self.unicode_escape(8)?),
+                    _ => return Err(self.error("invalid escape in IRI")),
+                },
+                Some(c) if c.is_whitespace() => return Err(self.error("whitespace in IRI")),
+                Some(c) => iri.push(c),
+                None => return Err(self.error("unterminated IRI")),
+            }
+        }
+        Ok(self.resolve(iri))
+    }
+
+    /// Resolves a relative IRI against the base, if one is set.
+    fn resolve(&self, iri: String) -> String {
+        let Some(base) = &self.base else {
+            return iri;
+        };
+        if has_scheme(&iri) {
+            return iri;
+        }
+        let base = base.split('#').next().unwrap_or(base);
+        if iri.is_empty() || iri.starts_with('#') {
+            return format!("{base}{iri}");
+        }
+        let scheme_end = base.find(':').map_or(0, |i| i + 1);
+        if iri.starts_with("//") {
+            return format!("{}{iri}", &base[..scheme_end]);
+        }
+        if iri.starts_with('/') {
+            let authority_end = base[scheme_end..]
+                .strip_prefix("//")
+                .and_then(|rest| rest.find('/'))
+                .map_or(base.len(), |i| scheme_end + 2 + i);
+            return format!("{}{iri}", &base[..authority_end]);
+        }
+        let path = base.split('?').next().unwrap_or(base);
+        let dir_end = path.rfind('/').map_or(path.len(), |i| i + 1);
+        format!("{}{iri}", &path[..dir_end])
+    }
+
+    /// Reads a prefixed name, blank node label, or keyword.
+    ///
+    /// A trailing '.' ends the statement rather than the name.
+    fn name_token(&mut self) -> ParseResult<String> {
+        let mut name = String::new();
+        let mut trailing_dots = 0;
+        while let Some(c) = self.peek() {
+            if c == '\\' {
+                self.bump();
+                match self.bump() {
+                    Some(escaped) if !escaped.is_alphanumeric() => name.push(escaped),
+                    _ => return Err(self.error("invalid escape in name")),
+                }
+                trailing_dots = 0;
+            } else if is_name_char(c) {
+                self.bump();
+                name.push(c);
+                trailing_dots = if c == '.' { trailing_dots + 1 } else { 0 };
+            } else {
+                break;
+            }
+        }
+        name.truncate(name.len() - trailing_dots);
+        self.pos -= trailing_dots;
+
+        if name.is_empty() {
+            return Err(match self.peek() {
+                Some(c) => self.error(format_args!("unexpected '{c}'")),
+                None => self.error("unexpected end of input"),
+            });
+        }
+        Ok(name)
+    }
+
+    /// Expands a prefixed name to a full IRI.
+    fn expand(&self, name: &str) -> ParseResult<String> {
+        let (prefix, local) = name
+            .split_once(':')
+            .ok_or_else(|| self.error(format_args!("expected an IRI, found '{name}'")))?;
+        let namespace = self
+            .prefixes
+            .get(prefix)
+            .ok_or_else(|| self.error(format_args!("undeclared prefix '{prefix}:'")))?;
+        Ok(format!("{namespace}{local}"))
+    }
+
+    /// Reads a directive keyword or language tag.
+    fn word(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            self.bump();
+        }
+        self.text[start..self.pos].to_string()
+    }
+
+    /// Consumes a case-insensitive keyword followed by whitespace.
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let rest = self.rest();
+        let matches = rest
+            .get(..keyword.len())
+            .is_some_and(|word| word.eq_ignore_ascii_case(keyword))
+            && rest[keyword.len()..].starts_with(char::is_whitespace);
+        if matches {
+            self.pos += keyword.len();
+        }
+        matches
+    }
+
+    fn expect(&mut self, c: char) -> ParseResult<()> {
+        self.skip_ws();
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(match self.peek() {
+                Some(found) => self.error(format_args!("expected '{c}', found '{found}'")),
+                None => self.error(format_args!("expected '{c}', found end of input")),
+            })
+        }
+    }
+
+    /// Skips whitespace and comments.
+    fn skip_ws(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '#' {
+                while self.bump().is_some_and(|c| c != '\n') {}
+            } else if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn rest(&self) -> &str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self, message: impl fmt::Display) -> String {
+        format!("line {}: {message}", self.line)
+    }
+}
+
+/// Builds a typed literal the way SPARQL does: datatypes SPARQL reads as
+/// numbers or booleans get their canonical form, any other is kept as is.
+fn typed_literal(lexical: String, datatype: &str) -> Term {
+    match typed_literal_to_value(&lexical, Some(datatype)) {
+        value @ (Value::Int64(_) | Value::Float64(_) | Value::Bool(_)) => literal_term(&value),
+        _ => Term::typed_literal(lexical, datatype),
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':' | '%')
+}
+
+/// Whether an IRI starts with a scheme and so is absolute.
+fn has_scheme(iri: &str) -> bool {
+    iri.find(':').is_some_and(|end| {
+        let scheme = &iri[..end];
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EX: &str = "http://example.org/";
+
+    fn ex(local: &str) -> Term {
+        Term::iri(format!("{EX}{local}"))
+    }
+
+    #[test]
+    fn test_parse_statements() {
+        let triples = parse(
+            r#"
+            # Comments may appear anywhere
+            @prefix ex: <http://example.org/> .
+            PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+            @base <http://example.org/base/doc> .
+
+            ex:alice a ex:Person ;          # '#' inside IRIs and strings isn't one
+                ex:name "Alice", "Alicia"@es ;
+                ex:age 30 ;
+                ex:height 1.70 ;
+                ex:score "4.5e0"^^xsd:double ;
+                ex:active true ;
+                ex:homepage <page#top> ;
+                ex:note """two
+            lines with "quotes\"""" ;
+                ex:born "1990-01-02"^^xsd:date ;
+                ex:tag "a\tb\u00e9 #1" ;
+            .
+            <other> ex:knows ex:alice.
+            "#,
+        )
+        .unwrap();
+
+        let alice = ex("alice");
+        let expected = vec![
+            Triple::new(alice.clone(), Term::iri(RDF_TYPE), ex("Person")),
+            Triple::new(alice.clone(), ex("name"), Term::literal("Alice")),
+            Triple::new(
+                alice.clone(),
+                ex("name"),
+                Term::lang_literal("Alicia", "es"),
+            ),
+            Triple::new(
+                alice.clone(),
+                ex("age"),
+                Term::Literal(Literal::integer(30)),
+            ),
+            Triple::new(
+                alice.clone(),
+                ex("height"),
+                Term::Literal(Literal::double(1.7)),
+            ),
+            Triple::new(
+                alice.clone(),
+                ex("score"),
+                Term::Literal(Literal::double(4.5)),
+            ),
+            Triple::new(
+                alice.clone(),
+                ex("active"),
+                Term::Literal(Literal::boolean(true)),
+            ),
+            Triple::new(alice.clone(), ex("homepage"), ex("base/page#top")),
+            Triple::new(
+                alice.clone(),
+                ex("note"),
+                Term::literal("two\n            lines with \"quotes\""),
+            ),
+            Triple::new(
+                alice.clone(),
+                ex("born"),
+                Term::typed_literal("1990-01-02", "http://www.w3.org/2001/XMLSchema#date"),
+            ),
+            Triple::new(alice.clone(), ex("tag"), Term::literal("a\tb\u{e9} #1")),
+            Triple::new(ex("base/other"), ex("knows"), alice),
+        ];
+        assert_eq!(triples, expected);
+    }
+
+    #[test]
+    fn test_parse_blank_nodes_and_collections() {
+        let text = r#"
+            @prefix ex: <http://example.org/> .
+            _:x ex:knows _:y .
+            _:y ex:knows _:x .
+            ex:alice ex:address [ ex:city "Paris" ; ex:zip "75001" ] .
+            [] ex:says ( 1 ex:two ) .
+            ex:empty ex:list () .
+        "#;
+        let triples = parse(text).unwrap();
+        assert_eq!(triples.len(), 11);
+        let find = |subject: Option<&Term>, predicate: Term| {
+            triples
+                .iter()
+                .find(|t| subject.is_none_or(|s| t.subject() == s) && t.predicate() == &predicate)
+                .map(|t| t.object().clone())
+                .unwrap()
+        };
+
+        // Labels name the same node throughout the document
+        let (x, y) = (triples[0].subject(), triples[0].object());
+        assert!(x.is_blank_node() && y.is_blank_node() && x != y);
+        assert_eq!(triples[1].subject(), y);
+        assert_eq!(triples[1].object(), x);
+
+        // The property list's node carries the nested triples
+        let address = find(Some(&ex("alice")), ex("address"));
+        assert!(address.is_blank_node());
+        assert_eq!(find(Some(&address), ex("city")), Term::literal("Paris"));
+        assert_eq!(find(Some(&address), ex("zip")), Term::literal("75001"));
+
+        // ( 1 ex:two ) becomes a two-cell rdf:first/rdf:rest list
+        let cell = find(None, ex("says"));
+        let first = Term::iri(RDF_FIRST);
+        let rest = Term::iri(RDF_REST);
+        assert_eq!(
+            find(Some(&cell), first.clone()),
+            Term::Literal(Literal::integer(1))
+        );
+        let cell = find(Some(&cell), rest.clone());
+        assert_eq!(find(Some(&cell), first), ex("two"));
+        assert_eq!(find(Some(&cell), rest), Term::iri(RDF_NIL));
+        assert_eq!(find(Some(&ex("empty")), ex("list")), Term::iri(RDF_NIL));
+
+        // Skolemized ids are stable per document and distinct across documents
+        assert_eq!(parse(text).unwrap(), triples);
+        let other = parse(&format!("{text}# changed\n")).unwrap();
+        assert_ne!(other[0].subject(), x);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |text: &str| parse(text).unwrap_err();
+        assert_eq!(
+            error("@prefix ex: <http://example.org/> .\n\nex:a ex:b nope:c ."),
+            "line 3: undeclared prefix 'nope:'"
+        );
+        assert_eq!(
+            error("<http://a> <http://b> <http://c>"),
+            "line 1: expected '.', found end of input"
+        );
+        assert_eq!(
+            error("<http://a> <http://b> \"open ."),
+            "line 1: unterminated string"
+        );
+        assert_eq!(
+            error("\"lit\" <http://b> <http://c> ."),
+            "line 1: a literal can't be a subject"
+        );
+        assert_eq!(
+            error("@foo <http://a> ."),
+            "line 1: unknown directive '@foo'"
+        );
+        assert_eq!(
+            error("<http://a> <http://b> <http://c> ;; ,"),
+            "line 1: unexpected ','"
+        );
+    }
+
+    #[test]
+    fn test_load_turtle_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("people.ttl");
+        std::fs::write(
+            &path,
+            r#"
+            @prefix ex: <http://example.org/> .
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+            ex:alice ex:name "Alice" ; ex:age "30"^^xsd:int ; ex:knows ex:bob .
+            ex:bob ex:name "Bob" ; ex:age 25 ; ex:active true .
+            "#,
+        )
+        .unwrap();
+
+        let db = crate::GrafeoDB::new_in_memory();
+        let stats = db.session().load_turtle(&path).unwrap();
+        assert_eq!(stats.triples_loaded, 6);
+
+        // The same data through SPARQL stores identical triples
+        let reference = crate::GrafeoDB::new_in_memory();
+        reference
+            .execute_sparql(
+                r#"
+                PREFIX ex: <http://example.org/>
+                INSERT DATA {
+                    ex:alice ex:name "Alice" .
+                    ex:alice ex:age 30 .
+                    ex:alice ex:knows ex:bob .
+                    ex:bob ex:name "Bob" .
+                    ex:bob ex:age 25 .
+                    ex:bob ex:active true .
+                }
+                "#,
+            )
+            .unwrap();
+        let sorted = |db: &crate::GrafeoDB| {
+            let mut triples: Vec<String> = db
+                .rdf_store()
+                .triples()
+                .iter()
+                .map(|t| format!("{} {} {}", t.subject(), t.predicate(), t.object()))
+                .collect();
+            triples.sort();
+            triples
+        };
+        assert_eq!(sorted(&db), sorted(&reference));
+
+        // And queries see the typed literals
+        let result = db
+            .execute_sparql(
+                r#"
+                PREFIX ex: <http://example.org/>
+                SELECT ?name WHERE { ?p ex:age 30 . ?p ex:name ?name }
+                "#,
+            )
+            .unwrap();
+        assert_eq!(result.rows, vec![vec![Value::from("Alice")]]);
+    }
+
+    #[test]
+    fn test_load_turtle_rejects_bad_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad.ttl");
+        std::fs::write(
+            &path,
+            "<http://a> <http://b> <http://c> .\n<http://a> <http://b> .",
+        )
+        .unwrap();
+
+        let db = crate::GrafeoDB::new_in_memory();
+        let err = db.session().load_turtle(&path).unwrap_err();
+        assert!(err.to_string().contains("bad.ttl: line 2"), "{err}");
+        assert!(db.rdf_store().is_empty());
+    }
+}
@@ -14,22 +14,28 @@
 //! - [`transaction`] - MVCC transaction management (snapshot isolation)
 //! - [`query`] - The full query pipeline: parsing, planning, optimization, execution
 //! - [`catalog`] - Schema metadata: labels, property keys, indexes
+//! - [`import`] - Bulk loading from CSV and Turtle files
 //! - [`admin`] - Admin API types for inspection, backup, and maintenance
 
 pub mod admin;
 pub mod catalog;
 pub mod config;
 pub mod database;
+pub mod import;
 pub mod query;
 pub mod session;
 pub mod transaction;
 
 pub use admin::{
-    CompactionStats, DatabaseInfo, DatabaseMode, DatabaseStats, DumpFormat, DumpMetadata,
-    IndexInfo, LpgSchemaInfo, RdfSchemaInfo, SchemaInfo, ValidationError, ValidationResult,
-    ValidationWarning, WalStatus,
+    AnalyzeStats, CompactionStats, DatabaseInfo, DatabaseMode, DatabaseStats, DumpFormat,
+    DumpMetadata, EdgeTypeInfo, IndexInfo, LabelInfo, LpgSchemaInfo, PropertyInfo, RdfSchemaInfo,
+    SchemaInfo, ValidationError, ValidationResult, ValidationWarning, WalStatus,
 };
-pub use catalog::{Catalog, CatalogError, IndexDefinition, IndexType};
+pub use catalog::{Catalog, CatalogError, IndexDefinition, IndexPrefixMatch, IndexType};
 pub use config::Config;
 pub use database::GrafeoDB;
-pub use session::Session;
+pub use grafeo_adapters::storage::wal::RecoveryTarget;
+pub use import::CsvLoadStats;
+#[cfg(all(feature = "sparql", feature = "rdf"))]
+pub use import::turtle::TurtleLoadStats;
+pub use session::{ReadSnapshot, Session, Transaction};
@@ -8,12 +8,12 @@
 //! Better to catch these errors early than waste time executing a broken query.
 
 use crate::query::plan::{
-    ExpandOp, FilterOp, LogicalExpression, LogicalOperator, LogicalPlan, NodeScanOp, ReturnItem,
-    ReturnOp, TripleScanOp,
+    ExpandOp, FilterOp, LeftJoinOp, LogicalExpression, LogicalOperator, LogicalPlan, NodeScanOp,
+    ReturnItem, ReturnOp, TripleScanOp, split_conjuncts,
 };
 use grafeo_common::types::LogicalType;
 use grafeo_common::utils::error::{Error, QueryError, QueryErrorKind, Result};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Creates a semantic binding error.
 fn binding_error(message: impl Into<String>) -> Error {
@@ -31,6 +31,8 @@ pub struct VariableInfo {
     pub is_node: bool,
     /// Whether this variable is an edge.
     pub is_edge: bool,
+    /// Whether this variable may be null (bound only by an OPTIONAL MATCH).
+    pub nullable: bool,
 }
 
 /// Context containing all bound variables and their information.
@@ -144,6 +146,7 @@ impl Binder {
                                 data_type,
                                 is_node: false,
                                 is_edge: false,
+                                nullable: false,
                             },
                         );
                     }
@@ -171,6 +174,7 @@ impl Binder {
                         data_type: LogicalType::Node,
                         is_node: true,
                         is_edge: false,
+                        nullable: false,
                     },
                 );
                 // Validate property expressions
@@ -190,6 +194,7 @@ impl Binder {
                         data_type: LogicalType::Edge,
                         is_node: false,
                         is_edge: true,
+                        nullable: false,
                     },
                 );
                 Ok(())
@@ -221,6 +226,7 @@ impl Binder {
                             data_type: LogicalType::Edge,
                             is_node: false,
                             is_edge: true,
+                            nullable: false,
                         },
                     );
                 }
@@ -282,6 +288,7 @@ impl Binder {
                         data_type: LogicalType::Any, // Unwound elements can be any type
                         is_node: false,
                         is_edge: false,
+                        nullable: false,
                     },
                 );
                 Ok(())
@@ -289,20 +296,52 @@ impl Binder {
 
             // RDF/SPARQL operators
             LogicalOperator::TripleScan(scan) => self.bind_triple_scan(scan),
-            LogicalOperator::Union(union) => {
-                for input in &union.inputs {
-                    self.bind_operator(input)?;
+            LogicalOperator::TriplePath(path) => {
+                use crate::query::plan::TripleComponent;
+
+                for endpoint in [&path.subject, &path.object] {
+                    if let TripleComponent::Variable(name) = endpoint {
+                        if !self.context.contains(name) {
+                            self.context.add_variable(
+                                name.clone(),
+                                VariableInfo {
+                                    name: name.clone(),
+                                    data_type: LogicalType::Any, // RDF term
+                                    is_node: false,
+                                    is_edge: false,
+                                    nullable: false,
+                                },
+                            );
+                        }
+                    }
                 }
                 Ok(())
             }
-            LogicalOperator::LeftJoin(lj) => {
-                self.bind_operator(&lj.left)?;
-                self.bind_operator(&lj.right)?;
-                if let Some(ref cond) = lj.condition {
-                    self.validate_expression(cond)?;
+            LogicalOperator::Values(values) => {
+                // UNDEF cells leave a variable unbound
+                for name in &values.variables {
+                    if !self.context.contains(name) {
+                        self.context.add_variable(
+                            name.clone(),
+                            VariableInfo {
+                                name: name.clone(),
+                                data_type: LogicalType::Any,
+                                is_node: false,
+                                is_edge: false,
+                                nullable: true,
+                            },
+                        );
+                    }
                 }
                 Ok(())
             }
+            LogicalOperator::Union(union) => {
+                for input in &union.inputs {
+                    self.bind_operator(input)?;
+                }
+                Ok(())
+            }
+            LogicalOperator::LeftJoin(lj) => self.bind_left_join(lj),
             LogicalOperator::AntiJoin(aj) => {
                 self.bind_operator(&aj.left)?;
                 self.bind_operator(&aj.right)?;
@@ -318,6 +357,7 @@ impl Binder {
                         data_type: LogicalType::Any,
                         is_node: false,
                         is_edge: false,
+                        nullable: false,
                     },
                 );
                 Ok(())
@@ -325,28 +365,43 @@ impl Binder {
             LogicalOperator::Merge(merge) => {
                 // First bind the input
                 self.bind_operator(&merge.input)?;
-                // Validate the match property expressions
-                for (_, expr) in &merge.match_properties {
-                    self.validate_expression(expr)?;
-                }
-                // Validate the ON CREATE property expressions
-                for (_, expr) in &merge.on_create {
-                    self.validate_expression(expr)?;
+                // MERGE needs key properties to decide between match and create
+                if merge.match_properties.is_empty() {
+                    return Err(binding_error(format!(
+                        "MERGE on '{}' requires at least one key property",
+                        merge.variable
+                    )));
                 }
-                // Validate the ON MATCH property expressions
-                for (_, expr) in &merge.on_match {
-                    self.validate_expression(expr)?;
+                // Keys are looked up before anything runs, so they must be
+                // known values
+                for (name, expr) in &merge.match_properties {
+                    if !matches!(
+                        expr,
+                        LogicalExpression::Literal(_) | LogicalExpression::Parameter(_)
+                    ) {
+                        return Err(binding_error(format!(
+                            "MERGE key property '{name}' must be a literal or parameter"
+                        )));
+                    }
                 }
                 // MERGE introduces a new variable
-                self.context.add_variable(
-                    merge.variable.clone(),
-                    VariableInfo {
-                        name: merge.variable.clone(),
-                        data_type: LogicalType::Node,
-                        is_node: true,
-                        is_edge: false,
-                    },
-                );
+                let info = VariableInfo {
+                    name: merge.variable.clone(),
+                    data_type: LogicalType::Node,
+                    is_node: true,
+                    is_edge: false,
+                    nullable: false,
+                };
+                // ON CREATE and ON MATCH are evaluated for the merged node
+                // alone, so it is the only variable they can read
+                let mut scope = Binder::new();
+                scope
+                    .context
+                    .add_variable(merge.variable.clone(), info.clone());
+                for (_, expr) in merge.on_create.iter().chain(&merge.on_match) {
+                    scope.validate_expression(expr)?;
+                }
+                self.context.add_variable(merge.variable.clone(), info);
                 Ok(())
             }
             LogicalOperator::AddLabel(add_label) => {
@@ -395,6 +450,7 @@ impl Binder {
                         data_type: LogicalType::Any, // Path is a complex type
                         is_node: false,
                         is_edge: false,
+                        nullable: false,
                     },
                 );
                 // Also add the path length variable for length(p) calls
@@ -406,6 +462,7 @@ impl Binder {
                         data_type: LogicalType::Int64,
                         is_node: false,
                         is_edge: false,
+                        nullable: false,
                     },
                 );
                 Ok(())
@@ -427,7 +484,41 @@ impl Binder {
                 self.bind_operator(&modify.where_clause)?;
                 Ok(())
             }
-            LogicalOperator::ClearGraph(_)
+            LogicalOperator::Construct(construct) => {
+                self.bind_operator(&construct.input)?;
+                Ok(())
+            }
+            LogicalOperator::Ask(ask) => {
+                self.bind_operator(&ask.input)?;
+                Ok(())
+            }
+            LogicalOperator::CallProcedure(call) => {
+                for argument in &call.arguments {
+                    self.validate_expression(argument)?;
+                }
+                // A procedure's `node_id` column is yielded as the node `node`
+                for column in &call.yields {
+                    let is_node = column == "node";
+                    self.context.add_variable(
+                        column.clone(),
+                        VariableInfo {
+                            name: column.clone(),
+                            data_type: if is_node {
+                                LogicalType::Node
+                            } else {
+                                LogicalType::Any
+                            },
+                            is_node,
+                            is_edge: false,
+                            nullable: false,
+                        },
+                    );
+                }
+                Ok(())
+            }
+            LogicalOperator::CreateIndex(_)
+            | LogicalOperator::CreateConstraint(_)
+            | LogicalOperator::ClearGraph(_)
             | LogicalOperator::CreateGraph(_)
             | LogicalOperator::DropGraph(_)
             | LogicalOperator::LoadGraph(_)
@@ -456,6 +547,7 @@ impl Binder {
                         data_type: LogicalType::Any, // RDF term
                         is_node: false,
                         is_edge: false,
+                        nullable: false,
                     },
                 );
             }
@@ -470,6 +562,7 @@ impl Binder {
                         data_type: LogicalType::Any, // IRI
                         is_node: false,
                         is_edge: false,
+                        nullable: false,
                     },
                 );
             }
@@ -484,6 +577,7 @@ impl Binder {
                         data_type: LogicalType::Any, // RDF term
                         is_node: false,
                         is_edge: false,
+                        nullable: false,
                     },
                 );
             }
@@ -498,6 +592,7 @@ impl Binder {
                         data_type: LogicalType::Any, // IRI
                         is_node: false,
                         is_edge: false,
+                        nullable: false,
                     },
                 );
             }
@@ -521,6 +616,7 @@ impl Binder {
                 data_type: LogicalType::Node,
                 is_node: true,
                 is_edge: false,
+                nullable: false,
             },
         );
 
@@ -559,6 +655,7 @@ impl Binder {
                     data_type: LogicalType::Edge,
                     is_node: false,
                     is_edge: true,
+                    nullable: false,
                 },
             );
         }
@@ -571,6 +668,7 @@ impl Binder {
                 data_type: LogicalType::Node,
                 is_node: true,
                 is_edge: false,
+                nullable: false,
             },
         );
 
@@ -584,6 +682,7 @@ impl Binder {
                     data_type: LogicalType::Int64,
                     is_node: false,
                     is_edge: false,
+                    nullable: false,
                 },
             );
         }
@@ -722,14 +821,101 @@ impl Binder {
                 self.validate_expression(map_expr)?;
                 Ok(())
             }
-            LogicalExpression::ExistsSubquery(subquery)
-            | LogicalExpression::CountSubquery(subquery) => {
+            LogicalExpression::ExistsSubquery(subquery) => {
+                self.bind_subquery(subquery)?;
+                Ok(())
+            }
+            LogicalExpression::CountSubquery(subquery) => {
                 // Subqueries have their own binding context
                 // For now, just validate the structure exists
                 let _ = subquery; // Would need recursive binding
                 Ok(())
             }
+            LogicalExpression::InSubquery { expr, subquery } => {
+                self.validate_expression(expr)?;
+
+                let (value, input, columns) = match subquery.as_ref() {
+                    LogicalOperator::Return(ret) => (
+                        ret.items.first().map(|item| &item.expression),
+                        ret.input.as_ref(),
+                        ret.items.len(),
+                    ),
+                    LogicalOperator::Project(project) => (
+                        project.projections.first().map(|p| &p.expression),
+                        project.input.as_ref(),
+                        project.projections.len(),
+                    ),
+                    _ => {
+                        return Err(binding_error(
+                            "IN subquery must end with a RETURN of a single column",
+                        ));
+                    }
+                };
+                let (Some(value), 1) = (value, columns) else {
+                    return Err(binding_error(format!(
+                        "IN subquery must return exactly one column, found {columns}"
+                    )));
+                };
+                let inner = self.bind_subquery(input)?;
+
+                // A value of outer variables alone is compared on the outer row
+                self.correlated_scope(&inner).validate_expression(value)?;
+                let mut vars = HashSet::new();
+                value.collect_variables(&mut vars);
+                let own = vars.iter().filter(|v| inner.context.contains(v)).count();
+                if own != 0 && own != vars.len() {
+                    return Err(binding_error(
+                        "IN subquery can't return a value mixing its own and outer variables",
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Binds a subquery in a scope of its own.
+    ///
+    /// Only the conditions of the subquery's WHERE may read variables of the
+    /// enclosing scope that the subquery doesn't bind. The planner lifts those
+    /// into the join with the enclosing query, so each must be one that
+    /// [`LogicalExpression::correlation`] splits.
+    fn bind_subquery(&self, subquery: &LogicalOperator) -> Result<Binder> {
+        let mut inner = Binder::new();
+        let LogicalOperator::Filter(filter) = subquery else {
+            inner.bind_operator(subquery)?;
+            return Ok(inner);
+        };
+        inner.bind_operator(&filter.input)?;
+
+        let scope = self.correlated_scope(&inner);
+        let binds = |v: &str| inner.context.contains(v);
+        let mut conjuncts = Vec::new();
+        split_conjuncts(&filter.predicate, &mut conjuncts);
+        for conjunct in conjuncts {
+            scope.validate_expression(conjunct)?;
+            let mut vars = HashSet::new();
+            conjunct.collect_variables(&mut vars);
+            if !vars.iter().all(|v| binds(v)) && conjunct.correlation(binds).is_none() {
+                return Err(binding_error(
+                    "Subquery conditions on outer variables must compare them to subquery values with '='",
+                ));
+            }
         }
+        Ok(inner)
+    }
+
+    /// Returns a binder that sees the variables of both this scope and a
+    /// subquery's.
+    fn correlated_scope(&self, inner: &Binder) -> Binder {
+        let mut scope = Binder {
+            context: self.context.clone(),
+        };
+        for name in inner.context.variable_names() {
+            if let Some(info) = inner.context.get(name) {
+                scope.context.add_variable(name.clone(), info.clone());
+            }
+        }
+        scope
     }
 
     /// Infers the type of an expression for use in WITH clause aliasing.
@@ -791,6 +977,30 @@ impl Binder {
         Ok(())
     }
 
+    /// Binds a left outer join (OPTIONAL MATCH).
+    ///
+    /// Variables first introduced on the right side may have no match, so
+    /// they are marked nullable. Variables shared with the left side keep
+    /// whatever nullability the left side gave them.
+    fn bind_left_join(&mut self, lj: &LeftJoinOp) -> Result<()> {
+        self.bind_operator(&lj.left)?;
+        let left_vars = self.context.variables.clone();
+
+        self.bind_operator(&lj.right)?;
+        if let Some(ref cond) = lj.condition {
+            self.validate_expression(cond)?;
+        }
+
+        for (name, info) in &mut self.context.variables {
+            match left_vars.get(name) {
+                Some(left_info) => info.nullable = left_info.nullable,
+                None => info.nullable = true,
+            }
+        }
+
+        Ok(())
+    }
+
     /// Binds an aggregate operator.
     fn bind_aggregate(&mut self, agg: &crate::query::plan::AggregateOp) -> Result<()> {
         // Bind the input first
@@ -799,6 +1009,11 @@ impl Binder {
         // Validate group by expressions
         for expr in &agg.group_by {
             self.validate_expression(expr)?;
+            // Non-variable group keys become output columns (e.g. `n.city`)
+            let column = crate::query::planner::expression_to_string(expr);
+            if !self.context.contains(&column) {
+                self.add_aggregate_column(column);
+            }
         }
 
         // Validate aggregate expressions
@@ -808,20 +1023,26 @@ impl Binder {
             }
             // Add the alias as a new variable if present
             if let Some(ref alias) = agg_expr.alias {
-                self.context.add_variable(
-                    alias.clone(),
-                    VariableInfo {
-                        name: alias.clone(),
-                        data_type: LogicalType::Any,
-                        is_node: false,
-                        is_edge: false,
-                    },
-                );
+                self.add_aggregate_column(alias.clone());
             }
         }
 
         Ok(())
     }
+
+    /// Registers an aggregate output column so operators above can reference it.
+    fn add_aggregate_column(&mut self, name: String) {
+        self.context.add_variable(
+            name.clone(),
+            VariableInfo {
+                name,
+                data_type: LogicalType::Any,
+                is_node: false,
+                is_edge: false,
+                nullable: false,
+            },
+        );
+    }
 }
 
 impl Default for Binder {
@@ -833,7 +1054,7 @@ impl Default for Binder {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::query::plan::{BinaryOp, FilterOp};
+    use crate::query::plan::{BinaryOp, FilterOp, MergeOp};
 
     #[test]
     fn test_bind_simple_scan() {
@@ -986,4 +1207,271 @@ mod tests {
         assert!(ctx.get("b").unwrap().is_node);
         assert!(ctx.get("e").unwrap().is_edge);
     }
+
+    #[test]
+    fn test_bind_left_join_marks_right_variables_nullable() {
+        use crate::query::plan::{ExpandDirection, ExpandOp};
+
+        // MATCH (a) OPTIONAL MATCH (a)-[e]->(b) RETURN a, b
+        let plan = LogicalPlan::new(LogicalOperator::Return(ReturnOp {
+            items: vec![
+                ReturnItem {
+                    expression: LogicalExpression::Variable("a".to_string()),
+                    alias: None,
+                },
+                ReturnItem {
+                    expression: LogicalExpression::Variable("b".to_string()),
+                    alias: None,
+                },
+            ],
+            distinct: false,
+            input: Box::new(LogicalOperator::LeftJoin(LeftJoinOp {
+                left: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                    variable: "a".to_string(),
+                    label: None,
+                    input: None,
+                })),
+                right: Box::new(LogicalOperator::Expand(ExpandOp {
+                    from_variable: "a".to_string(),
+                    to_variable: "b".to_string(),
+                    edge_variable: Some("e".to_string()),
+                    direction: ExpandDirection::Outgoing,
+                    edge_type: None,
+                    min_hops: 1,
+                    max_hops: Some(1),
+                    input: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                        variable: "a".to_string(),
+                        label: None,
+                        input: None,
+                    })),
+                    path_alias: None,
+                })),
+                condition: None,
+            })),
+        }));
+
+        let mut binder = Binder::new();
+        let ctx = binder.bind(&plan).unwrap();
+
+        assert!(!ctx.get("a").unwrap().nullable);
+        assert!(ctx.get("b").unwrap().nullable);
+        assert!(ctx.get("e").unwrap().nullable);
+        assert!(ctx.get("b").unwrap().is_node);
+    }
+
+    /// Builds `MATCH (n:Person) WHERE n.name IN (<subquery>) RETURN n`.
+    fn in_subquery_plan(subquery: LogicalOperator) -> LogicalPlan {
+        LogicalPlan::new(LogicalOperator::Return(ReturnOp {
+            items: vec![ReturnItem {
+                expression: LogicalExpression::Variable("n".to_string()),
+                alias: None,
+            }],
+            distinct: false,
+            input: Box::new(LogicalOperator::Filter(FilterOp {
+                predicate: LogicalExpression::InSubquery {
+                    expr: Box::new(LogicalExpression::Property {
+                        variable: "n".to_string(),
+                        property: "name".to_string(),
+                    }),
+                    subquery: Box::new(subquery),
+                },
+                input: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                    variable: "n".to_string(),
+                    label: Some("Person".to_string()),
+                    input: None,
+                })),
+            })),
+        }))
+    }
+
+    /// Builds `MATCH (m) RETURN <items>` for use as a subquery.
+    fn subquery_returning(items: Vec<LogicalExpression>) -> LogicalOperator {
+        LogicalOperator::Return(ReturnOp {
+            items: items
+                .into_iter()
+                .map(|expression| ReturnItem {
+                    expression,
+                    alias: None,
+                })
+                .collect(),
+            distinct: false,
+            input: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                variable: "m".to_string(),
+                label: None,
+                input: None,
+            })),
+        })
+    }
+
+    #[test]
+    fn test_bind_in_subquery_resolves_outer_variables() {
+        // The subquery refers to the outer `n` without binding it itself
+        let plan = in_subquery_plan(subquery_returning(vec![LogicalExpression::Property {
+            variable: "n".to_string(),
+            property: "alias".to_string(),
+        }]));
+
+        let mut binder = Binder::new();
+        let ctx = binder.bind(&plan).unwrap();
+
+        // Subquery variables don't leak into the outer scope
+        assert!(ctx.contains("n"));
+        assert!(!ctx.contains("m"));
+    }
+
+    #[test]
+    fn test_bind_in_subquery_undefined_variable() {
+        let plan = in_subquery_plan(subquery_returning(vec![LogicalExpression::Property {
+            variable: "x".to_string(),
+            property: "name".to_string(),
+        }]));
+
+        let mut binder = Binder::new();
+        let err = binder.bind(&plan).unwrap_err();
+
+        assert!(err.to_string().contains("Undefined variable"));
+    }
+
+    #[test]
+    fn test_bind_in_subquery_rejects_mixed_value() {
+        // `n.age + m.age` is neither a subquery value nor one of the outer row
+        let plan = in_subquery_plan(subquery_returning(vec![LogicalExpression::Binary {
+            left: Box::new(LogicalExpression::Property {
+                variable: "n".to_string(),
+                property: "age".to_string(),
+            }),
+            op: BinaryOp::Add,
+            right: Box::new(LogicalExpression::Property {
+                variable: "m".to_string(),
+                property: "age".to_string(),
+            }),
+        }]));
+
+        let mut binder = Binder::new();
+        let err = binder.bind(&plan).unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("mixing its own and outer variables")
+        );
+    }
+
+    #[test]
+    fn test_bind_in_subquery_requires_single_column() {
+        let plan = in_subquery_plan(subquery_returning(vec![
+            LogicalExpression::Property {
+                variable: "m".to_string(),
+                property: "name".to_string(),
+            },
+            LogicalExpression::Property {
+                variable: "m".to_string(),
+                property: "age".to_string(),
+            },
+        ]));
+
+        let mut binder = Binder::new();
+        let err = binder.bind(&plan).unwrap_err();
+
+        assert!(err.to_string().contains("exactly one column"));
+    }
+
+    /// Builds `MATCH (n:Person) WHERE EXISTS { MATCH (m) WHERE m.name <op> n.nick } RETURN n`.
+    fn exists_plan(op: BinaryOp) -> LogicalPlan {
+        LogicalPlan::new(LogicalOperator::Return(ReturnOp {
+            items: vec![ReturnItem {
+                expression: LogicalExpression::Variable("n".to_string()),
+                alias: None,
+            }],
+            distinct: false,
+            input: Box::new(LogicalOperator::Filter(FilterOp {
+                predicate: LogicalExpression::ExistsSubquery(Box::new(LogicalOperator::Filter(
+                    FilterOp {
+                        predicate: LogicalExpression::Binary {
+                            left: Box::new(LogicalExpression::Property {
+                                variable: "m".to_string(),
+                                property: "name".to_string(),
+                            }),
+                            op,
+                            right: Box::new(LogicalExpression::Property {
+                                variable: "n".to_string(),
+                                property: "nick".to_string(),
+                            }),
+                        },
+                        input: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                            variable: "m".to_string(),
+                            label: None,
+                            input: None,
+                        })),
+                    },
+                ))),
+                input: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                    variable: "n".to_string(),
+                    label: Some("Person".to_string()),
+                    input: None,
+                })),
+            })),
+        }))
+    }
+
+    #[test]
+    fn test_bind_exists_correlated_equality() {
+        let mut binder = Binder::new();
+        let ctx = binder.bind(&exists_plan(BinaryOp::Eq)).unwrap();
+
+        assert!(ctx.contains("n"));
+        assert!(!ctx.contains("m"));
+    }
+
+    #[test]
+    fn test_bind_exists_rejects_unjoinable_correlation() {
+        let mut binder = Binder::new();
+        let err = binder.bind(&exists_plan(BinaryOp::Gt)).unwrap_err();
+
+        assert!(err.to_string().contains("with '='"));
+    }
+
+    /// Builds `MERGE (c:Counter {k: <key>}) ON MATCH SET c.n = <on_match>`.
+    fn merge_plan(key: LogicalExpression, on_match: LogicalExpression) -> LogicalPlan {
+        LogicalPlan::new(LogicalOperator::Merge(MergeOp {
+            variable: "c".to_string(),
+            labels: vec!["Counter".to_string()],
+            match_properties: vec![("k".to_string(), key)],
+            on_create: Vec::new(),
+            on_match: vec![("n".to_string(), on_match)],
+            input: Box::new(LogicalOperator::Empty),
+        }))
+    }
+
+    #[test]
+    fn test_bind_merge_set_reads_merged_node() {
+        let plan = merge_plan(
+            LogicalExpression::Parameter("k".to_string()),
+            LogicalExpression::Property {
+                variable: "c".to_string(),
+                property: "n".to_string(),
+            },
+        );
+
+        let mut binder = Binder::new();
+        let ctx = binder.bind(&plan).unwrap();
+        assert!(ctx.contains("c"));
+    }
+
+    #[test]
+    fn test_bind_merge_rejects_computed_key() {
+        let plan = merge_plan(
+            LogicalExpression::FunctionCall {
+                name: "toUpper".to_string(),
+                args: vec![LogicalExpression::Literal(
+                    grafeo_common::types::Value::from("b"),
+                )],
+                distinct: false,
+            },
+            LogicalExpression::Literal(grafeo_common::types::Value::Int64(1)),
+        );
+
+        let mut binder = Binder::new();
+        let err = binder.bind(&plan).unwrap_err();
+        assert!(err.to_string().contains("must be a literal or parameter"));
+    }
 }
@@ -30,8 +30,11 @@ use std::hash::Hash;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
 
+use grafeo_common::utils::error::Result;
+
+use crate::database::QueryResult;
 use crate::query::plan::LogicalPlan;
-use crate::query::processor::QueryLanguage;
+use crate::query::processor::{QueryLanguage, QueryParams, QueryProcessor};
 
 /// Cache key combining query text and language.
 #[derive(Clone, Eq, PartialEq, Hash)]
@@ -44,10 +47,15 @@ pub struct CacheKey {
 
 impl CacheKey {
     /// Creates a new cache key.
+    ///
+    /// The query text is normalized so that whitespace and keyword-case
+    /// variants of the same query share a key. Parameter values are never
+    /// part of the key: a query using `$name` placeholders maps to a single
+    /// entry whatever values it is later run with.
     #[must_use]
     pub fn new(query: impl Into<String>, language: QueryLanguage) -> Self {
         Self {
-            query: normalize_query(&query.into()),
+            query: normalize_query(&query.into(), language),
             language,
         }
     }
@@ -65,12 +73,133 @@ impl CacheKey {
     }
 }
 
+/// Keywords whose case is folded for GQL and Cypher cache keys.
+const FOLDED_KEYWORDS: &[&str] = &[
+    "ALL",
+    "AND",
+    "AS",
+    "ASC",
+    "ASCENDING",
+    "BY",
+    "CALL",
+    "CASE",
+    "CONTAINS",
+    "CREATE",
+    "DELETE",
+    "DESC",
+    "DESCENDING",
+    "DETACH",
+    "DISTINCT",
+    "ELSE",
+    "END",
+    "ENDS",
+    "EXISTS",
+    "FALSE",
+    "FILTER",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INSERT",
+    "IS",
+    "LIMIT",
+    "MATCH",
+    "MERGE",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "OPTIONAL",
+    "OR",
+    "ORDER",
+    "REMOVE",
+    "RETURN",
+    "SET",
+    "SKIP",
+    "STARTS",
+    "THEN",
+    "TRUE",
+    "UNION",
+    "UNWIND",
+    "WHEN",
+    "WHERE",
+    "WITH",
+    "XOR",
+    "YIELD",
+];
+
+/// Returns whether the language treats keywords case-insensitively.
+fn folds_keyword_case(language: QueryLanguage) -> bool {
+    match language {
+        #[cfg(feature = "gql")]
+        QueryLanguage::Gql => true,
+        #[cfg(feature = "cypher")]
+        QueryLanguage::Cypher => true,
+        #[allow(unreachable_patterns)]
+        _ => false,
+    }
+}
+
 /// Normalizes a query string for caching.
 ///
-/// Removes extra whitespace and normalizes case for keywords.
-fn normalize_query(query: &str) -> String {
-    // Simple normalization: collapse whitespace
-    query.split_whitespace().collect::<Vec<_>>().join(" ")
+/// Splits the query into tokens and rejoins them with single spaces, so
+/// whitespace differences (including none at all around punctuation) don't
+/// matter. Quoted strings and identifiers are kept verbatim. For languages
+/// with case-insensitive keywords, keywords are uppercased unless they are
+/// used as a property, label, alias, or map key.
+fn normalize_query(query: &str, language: QueryLanguage) -> String {
+    let fold_keywords = folds_keyword_case(language);
+    let chars: Vec<char> = query.chars().collect();
+    let mut tokens: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if matches!(c, '\'' | '"' | '`') {
+            // Copy up to the matching unescaped quote
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            tokens.push(chars[start..i].iter().collect());
+        } else if c.is_alphanumeric() || c == '_' || c == '$' {
+            i += 1;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+
+            let is_name = matches!(tokens.last().map(String::as_str), Some("." | ":" | "AS"))
+                || chars[i..].iter().find(|c| !c.is_whitespace()) == Some(&':');
+            let upper = word.to_ascii_uppercase();
+            if fold_keywords && !is_name && FOLDED_KEYWORDS.contains(&upper.as_str()) {
+                tokens.push(upper);
+            } else {
+                tokens.push(word);
+            }
+        } else if "<>=!-+*/%|&^~".contains(c) {
+            // Multi-character operators such as `->` or `<=` stay together
+            i += 1;
+            while i < chars.len() && "<>=!-+*/%|&^~".contains(chars[i]) {
+                i += 1;
+            }
+            tokens.push(chars[start..i].iter().collect());
+        } else {
+            i += 1;
+            tokens.push(c.to_string());
+        }
+    }
+
+    tokens.join(" ")
 }
 
 /// Entry in the cache with metadata.
@@ -112,6 +241,8 @@ struct LruCache<K, V> {
     capacity: usize,
     /// Order of access (for LRU eviction).
     access_order: Vec<K>,
+    /// Number of entries evicted to make room.
+    evictions: u64,
 }
 
 impl<K: Clone + Eq + Hash, V: Clone> LruCache<K, V> {
@@ -120,6 +251,7 @@ impl<K: Clone + Eq + Hash, V: Clone> LruCache<K, V> {
             entries: HashMap::with_capacity(capacity),
             capacity,
             access_order: Vec::with_capacity(capacity),
+            evictions: 0,
         }
     }
 
@@ -156,6 +288,7 @@ impl<K: Clone + Eq + Hash, V: Clone> LruCache<K, V> {
         if let Some(key) = self.access_order.first().cloned() {
             self.access_order.remove(0);
             self.entries.remove(&key);
+            self.evictions += 1;
         }
     }
 
@@ -248,6 +381,24 @@ impl QueryCache {
         result
     }
 
+    /// Gets a parsed plan from the cache, translating and caching it on a miss.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `translate` if the plan wasn't cached.
+    pub fn get_or_insert_parsed(
+        &self,
+        key: CacheKey,
+        translate: impl FnOnce() -> Result<LogicalPlan>,
+    ) -> Result<LogicalPlan> {
+        if let Some(plan) = self.get_parsed(&key) {
+            return Ok(plan);
+        }
+        let plan = translate()?;
+        self.put_parsed(key, plan.clone());
+        Ok(plan)
+    }
+
     /// Puts a parsed plan into the cache.
     pub fn put_parsed(&self, key: CacheKey, plan: LogicalPlan) {
         if !self.enabled {
@@ -294,22 +445,28 @@ impl QueryCache {
     /// Returns cache statistics.
     #[must_use]
     pub fn stats(&self) -> CacheStats {
+        let parsed = self.parsed_cache.lock();
+        let optimized = self.optimized_cache.lock();
         CacheStats {
-            parsed_size: self.parsed_cache.lock().len(),
-            optimized_size: self.optimized_cache.lock().len(),
+            parsed_size: parsed.len(),
+            optimized_size: optimized.len(),
             parsed_hits: self.parsed_hits.load(Ordering::Relaxed),
             parsed_misses: self.parsed_misses.load(Ordering::Relaxed),
             optimized_hits: self.optimized_hits.load(Ordering::Relaxed),
             optimized_misses: self.optimized_misses.load(Ordering::Relaxed),
+            parsed_evictions: parsed.evictions,
+            optimized_evictions: optimized.evictions,
         }
     }
 
-    /// Resets hit/miss counters.
+    /// Resets hit/miss/eviction counters.
     pub fn reset_stats(&self) {
         self.parsed_hits.store(0, Ordering::Relaxed);
         self.parsed_misses.store(0, Ordering::Relaxed);
         self.optimized_hits.store(0, Ordering::Relaxed);
         self.optimized_misses.store(0, Ordering::Relaxed);
+        self.parsed_cache.lock().evictions = 0;
+        self.optimized_cache.lock().evictions = 0;
     }
 }
 
@@ -335,6 +492,10 @@ pub struct CacheStats {
     pub optimized_hits: u64,
     /// Number of optimized cache misses.
     pub optimized_misses: u64,
+    /// Number of parsed plans evicted to make room.
+    pub parsed_evictions: u64,
+    /// Number of optimized plans evicted to make room.
+    pub optimized_evictions: u64,
 }
 
 impl CacheStats {
@@ -426,6 +587,34 @@ impl<P> CachingQueryProcessor<P> {
     }
 }
 
+impl CachingQueryProcessor<QueryProcessor> {
+    /// Processes a query, reusing the translated plan of earlier calls.
+    ///
+    /// The cached plan keeps its `$name` placeholders and parameters are
+    /// bound on every call, so a single entry serves all parameter values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any stage of the pipeline fails.
+    pub fn process(
+        &self,
+        query: &str,
+        language: QueryLanguage,
+        params: Option<&QueryParams>,
+    ) -> Result<QueryResult> {
+        if !language.is_lpg() {
+            return self.processor.process(query, language, params);
+        }
+
+        let key = CacheKey::new(query, language);
+        let plan = self
+            .cache
+            .get_or_insert_parsed(key, || self.processor.translate_lpg(query, language))?;
+
+        self.processor.execute_lpg_plan(plan, params)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -453,6 +642,67 @@ mod tests {
         assert_eq!(key1.query(), key2.query());
     }
 
+    #[test]
+    fn test_cache_key_normalizes_spacing_around_punctuation() {
+        let key1 = CacheKey::new(
+            "MATCH (n:Person)-[:KNOWS]->(m) WHERE n.age>30 RETURN m",
+            test_language(),
+        );
+        let key2 = CacheKey::new(
+            "MATCH ( n : Person ) -[ :KNOWS ]-> ( m )\n  WHERE n.age > 30\n  RETURN m",
+            test_language(),
+        );
+        assert!(key1 == key2);
+
+        // Literals still distinguish queries
+        let key3 = CacheKey::new(
+            "MATCH (n:Person)-[:KNOWS]->(m) WHERE n.age>40 RETURN m",
+            test_language(),
+        );
+        assert!(key1 != key3);
+    }
+
+    #[test]
+    fn test_cache_key_keeps_string_contents() {
+        let key1 = CacheKey::new("MATCH (n) WHERE n.name = 'a  b' RETURN n", test_language());
+        let key2 = CacheKey::new("MATCH (n) WHERE n.name = 'a b' RETURN n", test_language());
+        assert!(key1 != key2);
+    }
+
+    #[cfg(feature = "gql")]
+    #[test]
+    fn test_cache_key_folds_keyword_case() {
+        let key1 = CacheKey::new(
+            "match (n:Person) where n.age > $min return n",
+            QueryLanguage::Gql,
+        );
+        let key2 = CacheKey::new(
+            "MATCH (n:Person) WHERE n.age > $min RETURN n",
+            QueryLanguage::Gql,
+        );
+        assert!(key1 == key2);
+
+        // Properties, labels, and aliases keep their case even if they look like keywords
+        let key3 = CacheKey::new(
+            "MATCH (n:order) RETURN n.limit AS match",
+            QueryLanguage::Gql,
+        );
+        let key4 = CacheKey::new(
+            "MATCH (n:ORDER) RETURN n.LIMIT AS MATCH",
+            QueryLanguage::Gql,
+        );
+        assert!(key3 != key4);
+        assert_eq!(
+            key3.query(),
+            "MATCH ( n : order ) RETURN n . limit AS match"
+        );
+
+        // Identifiers are case-sensitive
+        let key5 = CacheKey::new("MATCH (N) RETURN N", QueryLanguage::Gql);
+        let key6 = CacheKey::new("MATCH (n) RETURN n", QueryLanguage::Gql);
+        assert!(key5 != key6);
+    }
+
     #[test]
     fn test_cache_basic_operations() {
         let cache = QueryCache::new(10);
@@ -497,6 +747,22 @@ mod tests {
         let key2 = CacheKey::new("QUERY 2", test_language());
         assert!(cache.get_parsed(&key1).is_some());
         assert!(cache.get_parsed(&key2).is_some());
+
+        let stats = cache.stats();
+        assert_eq!(stats.parsed_evictions, 1);
+        assert_eq!(stats.optimized_evictions, 0);
+        assert_eq!(stats.parsed_size, 2);
+
+        // Touching entry 1 makes entry 2 the least recently used
+        cache.get_parsed(&key1);
+        let key3 = CacheKey::new("QUERY 3", test_language());
+        cache.put_parsed(key3.clone(), LogicalPlan::new(LogicalOperator::Empty));
+        assert!(cache.get_parsed(&key2).is_none());
+        assert!(cache.get_parsed(&key1).is_some());
+        assert_eq!(cache.stats().parsed_evictions, 2);
+
+        cache.reset_stats();
+        assert_eq!(cache.stats().parsed_evictions, 0);
     }
 
     #[test]
@@ -566,4 +832,38 @@ mod tests {
         assert_eq!(stats.optimized_misses, 2);
         assert!((stats.optimized_hit_rate() - 0.5).abs() < 0.01);
     }
+
+    #[cfg(feature = "gql")]
+    #[test]
+    fn test_caching_processor_reuses_plan_across_params() {
+        use grafeo_common::types::Value;
+        use grafeo_core::graph::lpg::LpgStore;
+        use std::sync::Arc;
+
+        let store = Arc::new(LpgStore::new());
+        for age in [25, 35, 45] {
+            store.create_node_with_props(&["Person"], [("age", Value::Int64(age))]);
+        }
+        let processor = CachingQueryProcessor::with_default_cache(QueryProcessor::for_lpg(store));
+        let query = "MATCH (n:Person) WHERE n.age > $min_age RETURN n";
+
+        let mut params = QueryParams::new();
+        params.insert("min_age".to_string(), Value::Int64(30));
+        let result = processor
+            .process(query, QueryLanguage::Gql, Some(&params))
+            .unwrap();
+        assert_eq!(result.row_count(), 2);
+
+        params.insert("min_age".to_string(), Value::Int64(40));
+        let result = processor
+            .process(query, QueryLanguage::Gql, Some(&params))
+            .unwrap();
+        assert_eq!(result.row_count(), 1);
+
+        // Translated once, bound twice
+        let stats = processor.stats();
+        assert_eq!(stats.parsed_misses, 1);
+        assert_eq!(stats.parsed_hits, 1);
+        assert_eq!(stats.parsed_size, 1);
+    }
 }
@@ -4,15 +4,16 @@
 //! that can be optimized and executed.
 
 use crate::query::plan::{
-    AggregateExpr, AggregateFunction, AggregateOp, BinaryOp, CreateEdgeOp, CreateNodeOp,
-    DeleteNodeOp, DistinctOp, ExpandDirection, ExpandOp, FilterOp, LeftJoinOp, LimitOp,
-    LogicalExpression, LogicalOperator, LogicalPlan, MergeOp, NodeScanOp, ProjectOp, Projection,
-    RemoveLabelOp, ReturnItem, ReturnOp, SetPropertyOp, ShortestPathOp, SkipOp, SortKey, SortOp,
-    SortOrder, UnaryOp, UnwindOp,
+    AddLabelOp, AggregateExpr, AggregateFunction, AggregateOp, BinaryOp, CallProcedureOp,
+    CreateEdgeOp, CreateNodeOp, DeleteNodeOp, DistinctOp, ExpandDirection, ExpandOp, FilterOp,
+    LeftJoinOp, LimitOp, LogicalExpression, LogicalOperator, LogicalPlan, MergeOp, NodeScanOp,
+    ProjectOp, Projection, RemoveLabelOp, ReturnItem, ReturnOp, SetPropertyOp, ShortestPathOp,
+    SkipOp, SortKey, SortOp, SortOrder, UnaryOp, UnwindOp,
 };
 use grafeo_adapters::query::cypher::{self, ast};
 use grafeo_common::types::Value;
-use grafeo_common::utils::error::{Error, Result};
+use grafeo_common::utils::error::{Error, QueryError, QueryErrorKind, Result};
+use std::cell::Cell;
 
 /// Translates a Cypher query string to a logical plan.
 pub fn translate(query: &str) -> Result<LogicalPlan> {
@@ -24,13 +25,21 @@ pub fn translate(query: &str) -> Result<LogicalPlan> {
 /// Cypher AST to logical plan translator.
 struct CypherTranslator {
     /// Variable counter for generating unique variable names.
-    #[allow(dead_code)]
-    var_counter: u32,
+    var_counter: Cell<u32>,
 }
 
 impl CypherTranslator {
     fn new() -> Self {
-        Self { var_counter: 0 }
+        Self {
+            var_counter: Cell::new(0),
+        }
+    }
+
+    /// Generates a fresh name for an anonymous node or edge.
+    fn anon_variable(&self) -> String {
+        let id = self.var_counter.get();
+        self.var_counter.set(id + 1);
+        format!("_anon_{id}")
     }
 
     fn translate_statement(&self, stmt: &ast::Statement) -> Result<LogicalPlan> {
@@ -69,6 +78,7 @@ impl CypherTranslator {
             ast::Clause::With(with_clause) => self.translate_with(with_clause, input),
             ast::Clause::Return(return_clause) => self.translate_return(return_clause, input),
             ast::Clause::Unwind(unwind_clause) => self.translate_unwind(unwind_clause, input),
+            ast::Clause::Call(call_clause) => self.translate_call(call_clause, input),
             ast::Clause::OrderBy(order_by) => self.translate_order_by(order_by, input),
             ast::Clause::Skip(expr) => self.translate_skip(expr, input),
             ast::Clause::Limit(expr) => self.translate_limit(expr, input),
@@ -507,6 +517,29 @@ impl CypherTranslator {
         }))
     }
 
+    fn translate_call(
+        &self,
+        call_clause: &ast::CallClause,
+        input: Option<LogicalOperator>,
+    ) -> Result<LogicalOperator> {
+        if input.is_some() {
+            return Err(Error::Query(QueryError::new(
+                QueryErrorKind::Semantic,
+                "CALL must start the query",
+            )));
+        }
+
+        Ok(LogicalOperator::CallProcedure(CallProcedureOp {
+            name: call_clause.procedure.clone(),
+            arguments: call_clause
+                .arguments
+                .iter()
+                .map(|argument| self.translate_expression(argument))
+                .collect::<Result<_>>()?,
+            yields: call_clause.yield_items.clone(),
+        }))
+    }
+
     fn translate_merge_statement(&self, merge: &ast::MergeClause) -> Result<LogicalPlan> {
         let op = self.translate_merge(merge, None)?;
         Ok(LogicalPlan { root: op })
@@ -749,15 +782,9 @@ impl CypherTranslator {
                         AggregateFunction::PercentileDisc | AggregateFunction::PercentileCont
                     ) && args.len() >= 2
                     {
-                        // Second argument is the percentile value
-                        if let ast::Expression::Literal(ast::Literal::Float(p)) = &args[1] {
-                            Some((*p).clamp(0.0, 1.0))
-                        } else if let ast::Expression::Literal(ast::Literal::Integer(p)) = &args[1]
-                        {
-                            Some((*p as f64).clamp(0.0, 1.0))
-                        } else {
-                            Some(0.5) // Default to median
-                        }
+                        Some(AggregateExpr::percentile_argument(
+                            &self.translate_expression(&args[1])?,
+                        )?)
                     } else {
                         None
                     };
@@ -852,68 +879,41 @@ impl CypherTranslator {
         input: Option<LogicalOperator>,
     ) -> Result<LogicalOperator> {
         match pattern {
-            ast::Pattern::Node(node) => {
-                let variable = node.variable.clone().unwrap_or_else(|| "_anon".to_string());
-                let labels = node.labels.clone();
-                let properties: Vec<(String, LogicalExpression)> = node
-                    .properties
-                    .iter()
-                    .map(|(k, v)| Ok((k.clone(), self.translate_expression(v)?)))
-                    .collect::<Result<_>>()?;
-
-                Ok(LogicalOperator::CreateNode(CreateNodeOp {
-                    variable,
-                    labels,
-                    properties,
-                    input: input.map(Box::new),
-                }))
-            }
+            ast::Pattern::Node(node) => Ok(self.translate_create_node(node, input)?.1),
             ast::Pattern::Path(path) => {
-                let mut current =
-                    self.translate_create_pattern(&ast::Pattern::Node(path.start.clone()), input)?;
+                let (mut source_var, mut current) =
+                    self.translate_create_node(&path.start, input)?;
 
                 for rel in &path.chain {
-                    let from_variable = self.get_last_node_variable(&Some(current.clone()))?;
-                    let to_variable = rel
-                        .target
-                        .variable
-                        .clone()
-                        .unwrap_or_else(|| "_anon".to_string());
+                    let (target_var, target_plan) =
+                        self.translate_create_node(&rel.target, Some(current))?;
                     let edge_type = rel
                         .types
                         .first()
                         .cloned()
                         .unwrap_or_else(|| "RELATED".to_string());
-
-                    let target_labels = rel.target.labels.clone();
-                    let target_props: Vec<(String, LogicalExpression)> = rel
-                        .target
-                        .properties
-                        .iter()
-                        .map(|(k, v)| Ok((k.clone(), self.translate_expression(v)?)))
-                        .collect::<Result<_>>()?;
-
-                    current = LogicalOperator::CreateNode(CreateNodeOp {
-                        variable: to_variable.clone(),
-                        labels: target_labels,
-                        properties: target_props,
-                        input: Some(Box::new(current)),
-                    });
-
                     let edge_props: Vec<(String, LogicalExpression)> = rel
                         .properties
                         .iter()
                         .map(|(k, v)| Ok((k.clone(), self.translate_expression(v)?)))
                         .collect::<Result<_>>()?;
 
+                    let (from_variable, to_variable) = match rel.direction {
+                        ast::Direction::Incoming => (target_var.clone(), source_var),
+                        ast::Direction::Outgoing | ast::Direction::Undirected => {
+                            (source_var, target_var.clone())
+                        }
+                    };
+
                     current = LogicalOperator::CreateEdge(CreateEdgeOp {
                         variable: rel.variable.clone(),
                         from_variable,
                         to_variable,
                         edge_type,
                         properties: edge_props,
-                        input: Box::new(current),
+                        input: Box::new(target_plan),
                     });
+                    source_var = target_var;
                 }
 
                 Ok(current)
@@ -924,6 +924,38 @@ impl CypherTranslator {
         }
     }
 
+    /// Resolves a CREATE node to its variable. A variable bound by an earlier
+    /// clause or pattern is reused; otherwise a `CreateNode` is added.
+    fn translate_create_node(
+        &self,
+        node: &ast::NodePattern,
+        input: Option<LogicalOperator>,
+    ) -> Result<(String, LogicalOperator)> {
+        if let (Some(variable), Some(input)) = (&node.variable, &input) {
+            if input.binds_variable(variable) {
+                return Ok((variable.clone(), input.clone()));
+            }
+        }
+
+        let variable = node
+            .variable
+            .clone()
+            .unwrap_or_else(|| self.anon_variable());
+        let properties: Vec<(String, LogicalExpression)> = node
+            .properties
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), self.translate_expression(v)?)))
+            .collect::<Result<_>>()?;
+
+        let plan = LogicalOperator::CreateNode(CreateNodeOp {
+            variable: variable.clone(),
+            labels: node.labels.clone(),
+            properties,
+            input: input.map(Box::new),
+        });
+        Ok((variable, plan))
+    }
+
     fn translate_create_statement(&self, create: &ast::CreateClause) -> Result<LogicalPlan> {
         let mut plan: Option<LogicalOperator> = None;
 
@@ -1015,8 +1047,13 @@ impl CypherTranslator {
                         input: Box::new(plan),
                     });
                 }
-                ast::SetItem::Labels { .. } => {
-                    return Err(Error::Internal("SET labels not yet supported".into()));
+                ast::SetItem::Labels { variable, labels } => {
+                    // SET n:Label adds labels to the node
+                    plan = LogicalOperator::AddLabel(AddLabelOp {
+                        variable: variable.clone(),
+                        labels: labels.clone(),
+                        input: Box::new(plan),
+                    });
                 }
             }
         }
@@ -1307,25 +1344,6 @@ impl CypherTranslator {
             _ => Err(Error::Internal("Cannot get variable from operator".into())),
         }
     }
-
-    fn get_last_node_variable(&self, plan: &Option<LogicalOperator>) -> Result<String> {
-        match plan {
-            Some(LogicalOperator::CreateNode(node)) => Ok(node.variable.clone()),
-            Some(LogicalOperator::NodeScan(scan)) => Ok(scan.variable.clone()),
-            Some(LogicalOperator::CreateEdge(edge)) => Ok(edge.to_variable.clone()),
-            Some(other) => self.get_last_node_variable(&self.extract_input(other)),
-            None => Err(Error::Internal("No previous node variable".into())),
-        }
-    }
-
-    fn extract_input(&self, plan: &LogicalOperator) -> Option<LogicalOperator> {
-        match plan {
-            LogicalOperator::CreateNode(n) => n.input.as_ref().map(|b| b.as_ref().clone()),
-            LogicalOperator::CreateEdge(e) => Some(e.input.as_ref().clone()),
-            LogicalOperator::Filter(f) => Some(f.input.as_ref().clone()),
-            _ => None,
-        }
-    }
 }
 
 /// Checks if an AST expression contains an aggregate function call.
@@ -1529,6 +1547,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_translate_create_edge_between_anonymous_nodes() {
+        let plan = translate("CREATE (:Person)<-[:KNOWS]-(:Person)").unwrap();
+
+        let LogicalOperator::CreateEdge(edge) = &plan.root else {
+            panic!("Expected CreateEdge, got {:?}", plan.root);
+        };
+        let LogicalOperator::CreateNode(target) = edge.input.as_ref() else {
+            panic!("Expected CreateNode, got {:?}", edge.input);
+        };
+        let Some(LogicalOperator::CreateNode(source)) = target.input.as_deref() else {
+            panic!("Expected CreateNode, got {:?}", target.input);
+        };
+        assert_ne!(source.variable, target.variable);
+        // Incoming: the edge runs from the pattern's target to its source
+        assert_eq!(edge.from_variable, target.variable);
+        assert_eq!(edge.to_variable, source.variable);
+    }
+
+    #[test]
+    fn test_translate_match_create_reuses_bound_nodes() {
+        let plan = translate("MATCH (a:Person), (b:Person) CREATE (a)-[:KNOWS]->(b)").unwrap();
+
+        let LogicalOperator::CreateEdge(edge) = &plan.root else {
+            panic!("Expected CreateEdge, got {:?}", plan.root);
+        };
+        assert_eq!(
+            (edge.from_variable.as_str(), edge.to_variable.as_str()),
+            ("a", "b")
+        );
+        assert!(
+            !matches!(edge.input.as_ref(), LogicalOperator::CreateNode(_)),
+            "bound endpoints are not recreated"
+        );
+    }
+
     #[test]
     fn test_translate_delete_node() {
         let plan = translate("MATCH (n:Person) DELETE n").unwrap();
@@ -1564,6 +1618,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_translate_set_merge_properties() {
+        let plan = translate("MATCH (n:Person) SET n += {age: 31, city: 'Oslo'} RETURN n").unwrap();
+
+        let LogicalOperator::Return(ret) = &plan.root else {
+            panic!("Expected Return, got {:?}", plan.root);
+        };
+        let LogicalOperator::SetProperty(set) = ret.input.as_ref() else {
+            panic!("Expected SetProperty");
+        };
+        assert_eq!(set.variable, "n");
+        assert!(!set.replace);
+        assert_eq!(set.properties.len(), 1);
+        assert_eq!(set.properties[0].0, "*");
+        let LogicalExpression::Map(entries) = &set.properties[0].1 else {
+            panic!("Expected map expression");
+        };
+        let keys: Vec<&str> = entries.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, vec!["age", "city"]);
+    }
+
+    #[test]
+    fn test_translate_set_replace_properties() {
+        let plan = translate("MATCH (n:Person) SET n = {name: 'Bob'} RETURN n").unwrap();
+
+        let LogicalOperator::Return(ret) = &plan.root else {
+            panic!("Expected Return, got {:?}", plan.root);
+        };
+        let LogicalOperator::SetProperty(set) = ret.input.as_ref() else {
+            panic!("Expected SetProperty");
+        };
+        assert_eq!(set.variable, "n");
+        assert!(set.replace);
+        assert_eq!(set.properties[0].0, "*");
+        assert!(matches!(set.properties[0].1, LogicalExpression::Map(_)));
+    }
+
     #[test]
     fn test_translate_set_multiple_properties() {
         let plan = translate("MATCH (n:Person) SET n.name = 'Alice', n.age = 30 RETURN n").unwrap();
@@ -1626,6 +1717,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_translate_remove_chains_label_and_property() {
+        let plan = translate("MATCH (n:Person) REMOVE n:Admin, n.age RETURN n").unwrap();
+
+        let LogicalOperator::Return(ret) = &plan.root else {
+            panic!("Expected Return, got {:?}", plan.root);
+        };
+        let LogicalOperator::SetProperty(set) = ret.input.as_ref() else {
+            panic!("Expected SetProperty");
+        };
+        assert_eq!(set.variable, "n");
+        assert_eq!(set.properties[0].0, "age");
+        assert!(matches!(
+            set.properties[0].1,
+            LogicalExpression::Literal(Value::Null)
+        ));
+        let LogicalOperator::RemoveLabel(remove) = set.input.as_ref() else {
+            panic!("Expected RemoveLabel");
+        };
+        assert_eq!(remove.labels, vec!["Admin".to_string()]);
+        assert!(matches!(
+            remove.input.as_ref(),
+            LogicalOperator::NodeScan(_)
+        ));
+    }
+
     // === WITH, UNWIND, ORDER BY, SKIP, LIMIT Tests ===
 
     #[test]
@@ -1682,6 +1799,47 @@ mod tests {
         assert_eq!(unwind.variable, "x");
     }
 
+    #[test]
+    fn test_translate_call_procedure() {
+        let plan = translate("CALL pagerank(0.9) YIELD node, score RETURN node").unwrap();
+
+        fn find_call(op: &LogicalOperator) -> Option<&CallProcedureOp> {
+            match op {
+                LogicalOperator::CallProcedure(call) => Some(call),
+                LogicalOperator::Return(r) => find_call(&r.input),
+                _ => None,
+            }
+        }
+
+        let call = find_call(&plan.root).expect("Expected CallProcedure");
+        assert_eq!(call.name, "pagerank");
+        assert_eq!(call.arguments.len(), 1);
+        assert_eq!(call.yields, vec!["node", "score"]);
+
+        assert!(translate("MATCH (n) CALL pagerank() YIELD score RETURN score").is_err());
+    }
+
+    #[test]
+    fn test_translate_unwind_after_match() {
+        let plan = translate("MATCH (n:Person) UNWIND n.tags AS tag RETURN n, tag").unwrap();
+
+        let LogicalOperator::Return(ret) = &plan.root else {
+            panic!("Expected Return, got {:?}", plan.root);
+        };
+        let LogicalOperator::Unwind(unwind) = ret.input.as_ref() else {
+            panic!("Expected Unwind, got {:?}", ret.input);
+        };
+        assert_eq!(unwind.variable, "tag");
+        assert!(matches!(
+            &unwind.expression,
+            LogicalExpression::Property { variable, property } if variable == "n" && property == "tags"
+        ));
+        assert!(matches!(
+            unwind.input.as_ref(),
+            LogicalOperator::NodeScan(_)
+        ));
+    }
+
     #[test]
     fn test_translate_order_by() {
         let plan = translate("MATCH (n:Person) RETURN n ORDER BY n.name").unwrap();
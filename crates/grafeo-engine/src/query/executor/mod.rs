@@ -4,8 +4,10 @@
 
 use crate::config::AdaptiveConfig;
 use crate::database::QueryResult;
+use crate::query::plan::LogicalPlan;
+use crate::query::planner::PhysicalPlan;
 use grafeo_common::types::{LogicalType, Value};
-use grafeo_common::utils::error::{Error, Result};
+use grafeo_common::utils::error::{Error, Result, TransactionError};
 use grafeo_core::execution::operators::{Operator, OperatorError};
 use grafeo_core::execution::{
     AdaptiveContext, AdaptiveSummary, CardinalityTrackingWrapper, DataChunk, SharedAdaptiveContext,
@@ -48,6 +50,25 @@ impl Executor {
         }
     }
 
+    /// Executes `physical_plan`, the physical form of `plan`.
+    ///
+    /// An `ASK` query is reduced to its answer: whether any row came back.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if operator execution fails.
+    pub fn execute_plan(
+        plan: &LogicalPlan,
+        physical_plan: &mut PhysicalPlan,
+    ) -> Result<QueryResult> {
+        let executor = Self::with_columns(physical_plan.columns.clone());
+        let result = executor.execute(physical_plan.operator.as_mut())?;
+        if plan.is_ask() {
+            return Ok(QueryResult::boolean(!result.is_empty()));
+        }
+        Ok(result)
+    }
+
     /// Executes a physical operator and collects all results.
     ///
     /// # Errors
@@ -130,19 +151,10 @@ impl Executor {
     /// Uses `selected_indices()` to correctly handle chunks with selection vectors
     /// (e.g., after filtering operations).
     fn collect_chunk(&self, chunk: &DataChunk, result: &mut QueryResult) -> Result<usize> {
-        let col_count = chunk.column_count();
         let mut collected = 0;
 
         for row_idx in chunk.selected_indices() {
-            let mut row = Vec::with_capacity(col_count);
-            for col_idx in 0..col_count {
-                let value = chunk
-                    .column(col_idx)
-                    .and_then(|col| col.get_value(row_idx))
-                    .unwrap_or(Value::Null);
-                row.push(value);
-            }
-            result.rows.push(row);
+            result.rows.push(chunk_row(chunk, row_idx));
             collected += 1;
         }
 
@@ -159,22 +171,13 @@ impl Executor {
         result: &mut QueryResult,
         limit: usize,
     ) -> Result<usize> {
-        let col_count = chunk.column_count();
         let mut collected = 0;
 
         for row_idx in chunk.selected_indices() {
             if collected >= limit {
                 break;
             }
-            let mut row = Vec::with_capacity(col_count);
-            for col_idx in 0..col_count {
-                let value = chunk
-                    .column(col_idx)
-                    .and_then(|col| col.get_value(row_idx))
-                    .unwrap_or(Value::Null);
-                row.push(value);
-            }
-            result.rows.push(row);
+            result.rows.push(chunk_row(chunk, row_idx));
             collected += 1;
         }
 
@@ -280,6 +283,93 @@ impl Default for Executor {
     }
 }
 
+/// A lazily evaluated query result.
+///
+/// Rows are pulled from the operator tree one chunk at a time: the operator
+/// only advances once every row of the current chunk has been handed out,
+/// so dropping the stream early stops the scan. An operator error is yielded
+/// once, after which the stream ends.
+pub struct ResultStream {
+    /// The root physical operator.
+    operator: Box<dyn Operator>,
+    /// Column names for the result.
+    columns: Vec<String>,
+    /// The chunk being drained, with its selected row indices.
+    current: Option<(DataChunk, Vec<usize>)>,
+    /// Position within the selected rows of the current chunk.
+    position: usize,
+    /// Set once the operator is exhausted or has failed.
+    finished: bool,
+}
+
+impl ResultStream {
+    /// Creates a stream over the rows produced by `operator`.
+    #[must_use]
+    pub fn new(operator: Box<dyn Operator>, columns: Vec<String>) -> Self {
+        Self {
+            operator,
+            columns,
+            current: None,
+            position: 0,
+            finished: false,
+        }
+    }
+
+    /// Returns the column names of the result.
+    #[must_use]
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+}
+
+impl Iterator for ResultStream {
+    type Item = Result<Vec<Value>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((chunk, selected)) = &self.current {
+                if let Some(&row_idx) = selected.get(self.position) {
+                    self.position += 1;
+                    return Some(Ok(chunk_row(chunk, row_idx)));
+                }
+                self.current = None;
+            }
+
+            if self.finished {
+                return None;
+            }
+
+            match self.operator.next() {
+                Ok(Some(chunk)) => {
+                    let selected: Vec<usize> = chunk.selected_indices().collect();
+                    self.current = Some((chunk, selected));
+                    self.position = 0;
+                }
+                Ok(None) => {
+                    self.finished = true;
+                    return None;
+                }
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(convert_operator_error(err)));
+                }
+            }
+        }
+    }
+}
+
+/// Extracts one row of a DataChunk as values.
+fn chunk_row(chunk: &DataChunk, row_idx: usize) -> Vec<Value> {
+    (0..chunk.column_count())
+        .map(|col_idx| {
+            chunk
+                .column(col_idx)
+                .and_then(|col| col.get_value(row_idx))
+                .unwrap_or(Value::Null)
+        })
+        .collect()
+}
+
 /// Converts an operator error to a common error.
 fn convert_operator_error(err: OperatorError) -> Error {
     match err {
@@ -288,6 +378,12 @@ fn convert_operator_error(err: OperatorError) -> Error {
             Error::InvalidValue(format!("Column not found: {name}"))
         }
         OperatorError::Execution(msg) => Error::Internal(msg),
+        OperatorError::WriteConflict(msg) => {
+            Error::Transaction(TransactionError::SerializationFailure(msg))
+        }
+        OperatorError::Cancelled => Error::Timeout,
+        OperatorError::OutOfMemory { .. } => Error::OutOfMemory,
+        OperatorError::ConstraintViolation(msg) => Error::ConstraintViolation(msg),
     }
 }
 
@@ -359,6 +455,53 @@ mod tests {
         }
     }
 
+    /// Counts how often the wrapped operator is advanced.
+    struct CountingOperator {
+        inner: MockIntOperator,
+        pulls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl Operator for CountingOperator {
+        fn next(&mut self) -> grafeo_core::execution::operators::OperatorResult {
+            self.pulls
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            self.inner.next()
+        }
+
+        fn reset(&mut self) {
+            self.inner.reset();
+        }
+
+        fn name(&self) -> &'static str {
+            "Counting"
+        }
+    }
+
+    /// Produces one chunk, then fails.
+    struct FailingOperator {
+        inner: MockIntOperator,
+        emitted: bool,
+    }
+
+    impl Operator for FailingOperator {
+        fn next(&mut self) -> grafeo_core::execution::operators::OperatorResult {
+            if self.emitted {
+                return Err(OperatorError::Execution("boom".to_string()));
+            }
+            self.emitted = true;
+            self.inner.next()
+        }
+
+        fn reset(&mut self) {
+            self.inner.reset();
+            self.emitted = false;
+        }
+
+        fn name(&self) -> &'static str {
+            "Failing"
+        }
+    }
+
     #[test]
     fn test_executor_empty() {
         let executor = Executor::with_columns(vec!["a".to_string()]);
@@ -389,4 +532,42 @@ mod tests {
         let result = executor.execute_with_limit(&mut op, 5).unwrap();
         assert_eq!(result.row_count(), 5);
     }
+
+    #[test]
+    fn test_result_stream_pulls_lazily() {
+        let pulls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let op = CountingOperator {
+            inner: MockIntOperator::new((0..1_000_000).collect(), 100),
+            pulls: pulls.clone(),
+        };
+
+        let stream = ResultStream::new(Box::new(op), vec!["value".to_string()]);
+        let rows: Vec<Vec<Value>> = stream.take(150).collect::<Result<_>>().unwrap();
+
+        assert_eq!(rows.len(), 150);
+        assert_eq!(rows[149][0], Value::Int64(149));
+        // Only the two chunks covering the first 150 rows were produced
+        assert_eq!(pulls.load(std::sync::atomic::Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_result_stream_error_mid_stream() {
+        let op = FailingOperator {
+            inner: MockIntOperator::new(vec![1, 2], 10),
+            emitted: false,
+        };
+        let mut stream = ResultStream::new(Box::new(op), vec!["value".to_string()]);
+
+        assert_eq!(stream.next().unwrap().unwrap(), vec![Value::Int64(1)]);
+        assert_eq!(stream.next().unwrap().unwrap(), vec![Value::Int64(2)]);
+        assert!(stream.next().unwrap().is_err());
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn test_result_stream_empty() {
+        let mut stream = ResultStream::new(Box::new(EmptyOperator), vec!["a".to_string()]);
+        assert_eq!(stream.columns(), ["a".to_string()]);
+        assert!(stream.next().is_none());
+    }
 }
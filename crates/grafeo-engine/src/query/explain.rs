@@ -0,0 +1,463 @@
+//! Renders the physical plan of a query as text, for `EXPLAIN`.
+//!
+//! Physical operators only know their own name, so the tree is rebuilt from
+//! the optimized logical plan, with each node named after the operator the
+//! [`Planner`] builds for it. Each line of output is one operator, indented
+//! under its parent:
+//!
+//! ```text
+//! HashAggregate groups=1 aggregates=[count] (rows=10, cost=25.40)
+//!   Expand a-[:KNOWS]->b (rows=30, cost=12.10)
+//!     Scan a:Person index=label (rows=10, cost=1.10)
+//! ```
+//!
+//! `rows` is the [`CardinalityEstimator`] estimate for the operator's output
+//! and `cost` is the [`CostModel`] total for the whole subtree below it.
+//! Scans say how they find their nodes: `index=none` walks every node,
+//! `index=label` uses the label index, and anything else names the catalog
+//! index the scan looks up for a filter on top of it. An index the store
+//! doesn't hold, or one the filter can't look up, isn't named.
+//!
+//! [`explain_analyze`] runs the plan as well and adds what actually happened
+//! next to each estimate: `actual_rows` counts the rows the operator emitted
+//! and `time` is the wall-clock time spent in its `next` calls, inputs
+//! included. Operators the planner builds internally, like the second scan
+//! of a `NestedLoopJoin`, have no actuals of their own.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use grafeo_common::types::IndexId;
+use grafeo_common::utils::error::Result;
+
+use super::executor::Executor;
+use super::optimizer::{CardinalityEstimator, CostModel};
+use super::plan::{
+    AggregateFunction, ExpandDirection, ExpandOp, JoinOp, LogicalExpression, LogicalOperator,
+    LogicalPlan, NodeScanOp,
+};
+use super::planner::Planner;
+use crate::catalog::IndexType;
+
+/// Renders the physical operator tree the planner builds for `plan`.
+///
+/// # Errors
+///
+/// Returns the planner's error if `plan` can't be turned into physical
+/// operators, exactly as executing it would.
+pub fn explain(
+    planner: &Planner,
+    plan: &LogicalPlan,
+    estimator: &CardinalityEstimator,
+    cost_model: &CostModel,
+) -> Result<String> {
+    // Planning first makes EXPLAIN fail on the same queries execution does
+    planner.plan(plan)?;
+
+    Ok(render(planner, plan, estimator, cost_model, false))
+}
+
+/// Runs `plan` and renders its operator tree with actual row counts and
+/// timings next to the estimates.
+///
+/// `planner` must have been built [`with_profiling`](Planner::with_profiling);
+/// otherwise no actuals are shown. The query really runs, so writes in it
+/// are applied.
+///
+/// # Errors
+///
+/// Returns an error if planning or execution fails.
+pub fn explain_analyze(
+    planner: &Planner,
+    plan: &LogicalPlan,
+    estimator: &CardinalityEstimator,
+    cost_model: &CostModel,
+) -> Result<String> {
+    let mut physical = planner.plan(plan)?;
+    Executor::with_columns(physical.columns.clone()).execute(physical.operator.as_mut())?;
+
+    Ok(render(planner, plan, estimator, cost_model, true))
+}
+
+fn render(
+    planner: &Planner,
+    plan: &LogicalPlan,
+    estimator: &CardinalityEstimator,
+    cost_model: &CostModel,
+    analyze: bool,
+) -> String {
+    let explainer = Explainer {
+        planner,
+        estimator,
+        cost_model,
+        analyze,
+    };
+    let mut out = String::new();
+    explainer.node(&plan.root, None).render(&mut out, 0);
+    out
+}
+
+/// One line of `EXPLAIN` output and the operators feeding it.
+struct ExplainNode {
+    operator: &'static str,
+    detail: String,
+    rows: f64,
+    /// Cost of this operator plus everything below it.
+    cost: f64,
+    /// Rows emitted and time spent, for `EXPLAIN ANALYZE`.
+    actual: Option<(u64, Duration)>,
+    children: Vec<ExplainNode>,
+}
+
+impl ExplainNode {
+    fn render(&self, out: &mut String, depth: usize) {
+        let _ = write!(out, "{:indent$}{}", "", self.operator, indent = depth * 2);
+        if !self.detail.is_empty() {
+            out.push(' ');
+            out.push_str(&self.detail);
+        }
+        let _ = write!(out, " (rows={:.0}", self.rows);
+        if let Some((rows, elapsed)) = self.actual {
+            let millis = elapsed.as_secs_f64() * 1000.0;
+            let _ = write!(out, ", actual_rows={rows}, time={millis:.3}ms");
+        }
+        let _ = writeln!(out, ", cost={:.2})", self.cost);
+        for child in &self.children {
+            child.render(out, depth + 1);
+        }
+    }
+}
+
+struct Explainer<'a> {
+    planner: &'a Planner,
+    estimator: &'a CardinalityEstimator,
+    cost_model: &'a CostModel,
+    analyze: bool,
+}
+
+impl Explainer<'_> {
+    /// Builds the node for `op`; `scan_index` overrides how a scan finds
+    /// its nodes when a filter above it is answered from an index.
+    fn node(&self, op: &LogicalOperator, scan_index: Option<String>) -> ExplainNode {
+        let (operator, detail, children) = match op {
+            LogicalOperator::NodeScan(scan) => match &scan.input {
+                // The planner joins a scan with an input against a fresh scan
+                Some(input) => {
+                    let leaf = LogicalOperator::NodeScan(NodeScanOp {
+                        variable: scan.variable.clone(),
+                        label: scan.label.clone(),
+                        input: None,
+                    });
+                    (
+                        "NestedLoopJoin",
+                        String::new(),
+                        vec![self.node(input, None), self.node(&leaf, scan_index)],
+                    )
+                }
+                None => ("Scan", scan_detail(scan, scan_index), Vec::new()),
+            },
+            LogicalOperator::Filter(filter) => {
+                let index = self
+                    .planner
+                    .index_lookup(filter)
+                    .map(|access| self.describe_index(access.index_id, access.prefix));
+                (
+                    "Filter",
+                    String::new(),
+                    vec![self.node(&filter.input, index)],
+                )
+            }
+            LogicalOperator::Expand(expand) => {
+                let variable_length = expand.min_hops != 1 || expand.max_hops != Some(1);
+                let operator = if variable_length {
+                    "VariableLengthExpand"
+                } else {
+                    "Expand"
+                };
+                (
+                    operator,
+                    expand_detail(expand),
+                    vec![self.node(&expand.input, None)],
+                )
+            }
+            LogicalOperator::Project(project) => (
+                "Project",
+                format!("columns={}", project.projections.len()),
+                vec![self.node(&project.input, None)],
+            ),
+            LogicalOperator::Return(ret) => (
+                "Project",
+                format!("columns={}", ret.items.len()),
+                vec![self.node(&ret.input, None)],
+            ),
+            LogicalOperator::Join(join) => match self.planner.index_probe(join) {
+                Some(probe) => (
+                    "IndexNestedLoopJoin",
+                    format!(
+                        "type=inner {} on :{}({})",
+                        self.describe_index(probe.index_id, None),
+                        probe.label,
+                        probe.property
+                    ),
+                    vec![self.node(probe.outer, None)],
+                ),
+                None => self.join_parts(join),
+            },
+            LogicalOperator::LeftJoin(join) => (
+                "HashJoin",
+                "type=left".to_string(),
+                vec![self.node(&join.left, None), self.node(&join.right, None)],
+            ),
+            LogicalOperator::AntiJoin(join) => (
+                "HashJoin",
+                "type=anti".to_string(),
+                vec![self.node(&join.left, None), self.node(&join.right, None)],
+            ),
+            LogicalOperator::Aggregate(agg) => {
+                let functions: Vec<&str> = agg
+                    .aggregates
+                    .iter()
+                    .map(|a| function_name(a.function))
+                    .collect();
+                let functions = functions.join(", ");
+                let (operator, detail) = if agg.group_by.is_empty() {
+                    ("SimpleAggregate", format!("aggregates=[{functions}]"))
+                } else {
+                    (
+                        "HashAggregate",
+                        format!("groups={} aggregates=[{functions}]", agg.group_by.len()),
+                    )
+                };
+                (operator, detail, vec![self.node(&agg.input, None)])
+            }
+            // The planner fuses a limit over a sort into one operator
+            LogicalOperator::Limit(limit) => match limit.input.as_ref() {
+                LogicalOperator::Sort(sort) => (
+                    "TopN",
+                    format!("keys={} count={}", sort.keys.len(), limit.count),
+                    vec![self.node(&sort.input, None)],
+                ),
+                _ => (
+                    "Limit",
+                    format!("count={}", limit.count),
+                    vec![self.node(&limit.input, None)],
+                ),
+            },
+            LogicalOperator::Skip(skip) => (
+                "Skip",
+                format!("count={}", skip.count),
+                vec![self.node(&skip.input, None)],
+            ),
+            LogicalOperator::Sort(sort) => (
+                "Sort",
+                format!("keys={}", sort.keys.len()),
+                vec![self.node(&sort.input, None)],
+            ),
+            LogicalOperator::Distinct(distinct) => (
+                "Distinct",
+                String::new(),
+                vec![self.node(&distinct.input, None)],
+            ),
+            LogicalOperator::Union(union) => (
+                "Union",
+                String::new(),
+                union.inputs.iter().map(|i| self.node(i, None)).collect(),
+            ),
+            LogicalOperator::Unwind(unwind) => (
+                "Unwind",
+                format!("as {}", unwind.variable),
+                vec![self.node(&unwind.input, None)],
+            ),
+            LogicalOperator::Merge(merge) => (
+                "Merge",
+                node_pattern(&merge.variable, &merge.labels),
+                vec![self.node(&merge.input, None)],
+            ),
+            LogicalOperator::ShortestPath(path) => (
+                "ShortestPath",
+                format!("{} -> {}", path.source_var, path.target_var),
+                vec![self.node(&path.input, None)],
+            ),
+            LogicalOperator::CreateNode(create) => (
+                "CreateNode",
+                node_pattern(&create.variable, &create.labels),
+                create
+                    .input
+                    .iter()
+                    .map(|input| self.node(input, None))
+                    .collect(),
+            ),
+            LogicalOperator::CreateEdge(create) => (
+                "CreateEdge",
+                format!(
+                    "{}-[:{}]->{}",
+                    create.from_variable, create.edge_type, create.to_variable
+                ),
+                vec![self.node(&create.input, None)],
+            ),
+            LogicalOperator::DeleteNode(delete) => (
+                "DeleteNode",
+                delete.variable.clone(),
+                vec![self.node(&delete.input, None)],
+            ),
+            LogicalOperator::DeleteEdge(delete) => (
+                "DeleteEdge",
+                delete.variable.clone(),
+                vec![self.node(&delete.input, None)],
+            ),
+            LogicalOperator::SetProperty(set) => (
+                "SetProperty",
+                set.variable.clone(),
+                vec![self.node(&set.input, None)],
+            ),
+            LogicalOperator::AddLabel(add) => (
+                "AddLabel",
+                node_pattern(&add.variable, &add.labels),
+                vec![self.node(&add.input, None)],
+            ),
+            LogicalOperator::RemoveLabel(remove) => (
+                "RemoveLabel",
+                node_pattern(&remove.variable, &remove.labels),
+                vec![self.node(&remove.input, None)],
+            ),
+            LogicalOperator::CreateIndex(create) => (
+                "CreateIndex",
+                format!(":{}({})", create.label, create.properties.join(", ")),
+                Vec::new(),
+            ),
+            LogicalOperator::CreateConstraint(create) => (
+                "CreateConstraint",
+                format!(":{}({}) {:?}", create.label, create.property, create.kind),
+                Vec::new(),
+            ),
+            LogicalOperator::CallProcedure(call) => (
+                "CallProcedure",
+                format!("{}() yield=[{}]", call.name, call.yields.join(", ")),
+                Vec::new(),
+            ),
+            // Empty inputs of projections, unwinds and merges plan as one row
+            LogicalOperator::Empty => ("SingleRow", String::new(), Vec::new()),
+            // Anything else fails in `Planner::plan` before we get here
+            _ => ("Unknown", String::new(), Vec::new()),
+        };
+
+        let rows = self.estimator.estimate(op);
+        let cost = self.cost_model.estimate(op, rows).total()
+            + children.iter().map(|child| child.cost).sum::<f64>();
+        let actual = self
+            .analyze
+            .then(|| self.planner.profile(op))
+            .flatten()
+            .map(|profile| (profile.rows(), profile.elapsed()));
+        ExplainNode {
+            operator,
+            detail,
+            rows,
+            cost,
+            actual,
+            children,
+        }
+    }
+
+    /// Operator, detail and children for a join the planner doesn't answer
+    /// from an index.
+    fn join_parts(&self, join: &JoinOp) -> (&'static str, String, Vec<ExplainNode>) {
+        // The planner can only key on conditions between variables
+        let key_count = join
+            .conditions
+            .iter()
+            .filter(|c| {
+                matches!(
+                    (&c.left, &c.right),
+                    (
+                        LogicalExpression::Variable(_),
+                        LogicalExpression::Variable(_)
+                    )
+                )
+            })
+            .count();
+        let strategy = self.planner.join_strategy(join, key_count);
+        let mut children = vec![self.node(&join.left, None), self.node(&join.right, None)];
+        if strategy.swap_inputs() {
+            children.reverse();
+        }
+        (
+            strategy.operator_name(),
+            format!("type={}", format!("{:?}", join.join_type).to_lowercase()),
+            children,
+        )
+    }
+
+    /// Describes a catalog index for a scan, e.g. `index=hash#2 keys=1/2`.
+    fn describe_index(&self, id: IndexId, prefix: Option<(usize, bool)>) -> String {
+        let Some(definition) = self.planner.catalog().and_then(|c| c.get_index(id)) else {
+            return format!("index=#{}", id.as_u32());
+        };
+        let kind = match definition.index_type {
+            IndexType::Hash => "hash",
+            IndexType::BTree => "btree",
+            IndexType::FullText => "fulltext",
+            IndexType::Trie { .. } => "trie",
+        };
+        let mut described = format!("index={kind}#{}", id.as_u32());
+        if let Some((prefix_len, false)) = prefix {
+            let _ = write!(
+                described,
+                " keys={prefix_len}/{}",
+                definition.property_keys.len()
+            );
+        }
+        described
+    }
+}
+
+fn scan_detail(scan: &NodeScanOp, index: Option<String>) -> String {
+    match (&scan.label, index) {
+        (Some(label), Some(index)) => format!("{}:{label} {index}", scan.variable),
+        (Some(label), None) => format!("{}:{label} index=label", scan.variable),
+        (None, _) => format!("{} index=none", scan.variable),
+    }
+}
+
+fn expand_detail(expand: &ExpandOp) -> String {
+    let mut edge = expand.edge_variable.clone().unwrap_or_default();
+    if let Some(edge_type) = &expand.edge_type {
+        let _ = write!(edge, ":{edge_type}");
+    }
+    if expand.min_hops != 1 || expand.max_hops != Some(1) {
+        let max = expand.max_hops.map(|m| m.to_string()).unwrap_or_default();
+        let _ = write!(edge, "*{}..{max}", expand.min_hops);
+    }
+    let (left, right) = match expand.direction {
+        ExpandDirection::Outgoing => ("-", "->"),
+        ExpandDirection::Incoming => ("<-", "-"),
+        ExpandDirection::Both => ("-", "-"),
+    };
+    format!(
+        "{}{left}[{edge}]{right}{}",
+        expand.from_variable, expand.to_variable
+    )
+}
+
+fn function_name(function: AggregateFunction) -> &'static str {
+    match function {
+        AggregateFunction::Count | AggregateFunction::CountNonNull => "count",
+        AggregateFunction::Sum => "sum",
+        AggregateFunction::Avg => "avg",
+        AggregateFunction::Min => "min",
+        AggregateFunction::Max => "max",
+        AggregateFunction::Collect => "collect",
+        AggregateFunction::StdDev => "stdev",
+        AggregateFunction::StdDevPop => "stdevp",
+        AggregateFunction::PercentileDisc => "percentile_disc",
+        AggregateFunction::PercentileCont => "percentile_cont",
+    }
+}
+
+fn node_pattern(variable: &str, labels: &[String]) -> String {
+    let mut pattern = variable.to_string();
+    for label in labels {
+        let _ = write!(pattern, ":{label}");
+    }
+    pattern
+}
@@ -3,12 +3,14 @@
 //! Translates GQL AST to the common logical plan representation.
 
 use crate::query::plan::{
-    AddLabelOp, AggregateExpr, AggregateFunction, AggregateOp, BinaryOp, CreateEdgeOp,
-    CreateNodeOp, DeleteNodeOp, DistinctOp, ExpandDirection, ExpandOp, FilterOp, JoinOp, JoinType,
-    LeftJoinOp, LimitOp, LogicalExpression, LogicalOperator, LogicalPlan, MergeOp, NodeScanOp,
-    ProjectOp, Projection, RemoveLabelOp, ReturnItem, ReturnOp, SetPropertyOp, ShortestPathOp,
-    SkipOp, SortKey, SortOp, SortOrder, UnaryOp, UnwindOp,
+    AddLabelOp, AggregateExpr, AggregateFunction, AggregateOp, BinaryOp, CallProcedureOp,
+    ConstraintKind, CreateConstraintOp, CreateEdgeOp, CreateIndexOp, CreateNodeOp, DeleteNodeOp,
+    DistinctOp, ExpandDirection, ExpandOp, FilterOp, JoinOp, JoinType, LeftJoinOp, LimitOp,
+    LogicalExpression, LogicalOperator, LogicalPlan, MergeOp, NodeScanOp, ProjectOp, Projection,
+    RemoveLabelOp, ReturnItem, ReturnOp, SetPropertyOp, ShortestPathOp, SkipOp, SortKey, SortOp,
+    SortOrder, UnaryOp, UnwindOp,
 };
+use crate::query::planner::expression_to_string;
 use grafeo_adapters::query::gql::{self, ast};
 use grafeo_common::types::Value;
 use grafeo_common::utils::error::{Error, Result};
@@ -36,6 +38,22 @@ impl GqlTranslator {
         match stmt {
             ast::Statement::Query(query) => self.translate_query(query),
             ast::Statement::DataModification(dm) => self.translate_data_modification(dm),
+            ast::Statement::Schema(ast::SchemaStatement::CreateIndex(index)) => Ok(
+                LogicalPlan::new(LogicalOperator::CreateIndex(CreateIndexOp {
+                    label: index.label.clone(),
+                    properties: index.properties.clone(),
+                })),
+            ),
+            ast::Statement::Schema(ast::SchemaStatement::CreateConstraint(constraint)) => Ok(
+                LogicalPlan::new(LogicalOperator::CreateConstraint(CreateConstraintOp {
+                    label: constraint.label.clone(),
+                    property: constraint.property.clone(),
+                    kind: match constraint.kind {
+                        ast::ConstraintKind::Unique => ConstraintKind::Unique,
+                        ast::ConstraintKind::NotNull => ConstraintKind::NotNull,
+                    },
+                })),
+            ),
             ast::Statement::Schema(_) => Err(Error::Internal(
                 "Schema statements not yet supported".to_string(),
             )),
@@ -43,8 +61,19 @@ impl GqlTranslator {
     }
 
     fn translate_query(&self, query: &ast::QueryStatement) -> Result<LogicalPlan> {
-        // Start with the pattern scan (MATCH clauses)
-        let mut plan = LogicalOperator::Empty;
+        // A CALL clause's rows start the query; MATCH clauses join onto them
+        let mut plan = match &query.call_clause {
+            Some(call) => LogicalOperator::CallProcedure(CallProcedureOp {
+                name: call.procedure.clone(),
+                arguments: call
+                    .arguments
+                    .iter()
+                    .map(|argument| self.translate_expression(argument))
+                    .collect::<Result<_>>()?,
+                yields: call.yield_items.clone(),
+            }),
+            None => LogicalOperator::Empty,
+        };
 
         for match_clause in &query.match_clauses {
             let match_plan = self.translate_match(match_clause)?;
@@ -242,44 +271,41 @@ impl GqlTranslator {
             }
         }
 
-        // Apply SKIP
-        if let Some(skip_expr) = &query.return_clause.skip {
-            if let ast::Expression::Literal(ast::Literal::Integer(n)) = skip_expr {
-                plan = LogicalOperator::Skip(SkipOp {
-                    count: *n as usize,
-                    input: Box::new(plan),
-                });
-            }
-        }
-
-        // Apply LIMIT
-        if let Some(limit_expr) = &query.return_clause.limit {
-            if let ast::Expression::Literal(ast::Literal::Integer(n)) = limit_expr {
-                plan = LogicalOperator::Limit(LimitOp {
-                    count: *n as usize,
-                    input: Box::new(plan),
-                });
-            }
-        }
-
-        // Check if RETURN contains aggregate functions
+        // Check if RETURN (or HAVING) contains aggregate functions
         let has_aggregates = query
             .return_clause
             .items
             .iter()
-            .any(|item| contains_aggregate(&item.expression));
+            .any(|item| contains_aggregate(&item.expression))
+            || query
+                .having_clause
+                .as_ref()
+                .is_some_and(|having| contains_aggregate(&having.expression));
 
         if has_aggregates {
             // Extract aggregate and group-by expressions
             let (aggregates, group_by) =
                 self.extract_aggregates_and_groups(&query.return_clause.items)?;
 
-            // Translate HAVING clause if present
-            let having = if let Some(having_clause) = &query.having_clause {
-                Some(self.translate_expression(&having_clause.expression)?)
-            } else {
-                None
+            // Columns produced by the aggregate, in output order
+            let output_columns: Vec<String> = group_by
+                .iter()
+                .map(expression_to_string)
+                .chain(aggregates.iter().map(AggregateExpr::output_column))
+                .collect();
+
+            // Translate HAVING against the aggregate's output columns. Aggregates that
+            // only appear in HAVING are computed as extra hidden columns.
+            let mut aggregates = aggregates;
+            let having = match &query.having_clause {
+                Some(having_clause) => Some(self.translate_having_expression(
+                    &having_clause.expression,
+                    &group_by,
+                    &mut aggregates,
+                )?),
+                None => None,
             };
+            let has_hidden_columns = aggregates.len() + group_by.len() > output_columns.len();
 
             // Insert Aggregate operator - this is the final operator for aggregate queries
             // The aggregate operator produces the output columns directly
@@ -287,9 +313,32 @@ impl GqlTranslator {
                 group_by,
                 aggregates,
                 input: Box::new(plan),
-                having,
+                having: None, // HAVING handled as separate Filter below
             });
 
+            // Apply HAVING directly above the aggregate
+            if let Some(predicate) = having {
+                plan = LogicalOperator::Filter(FilterOp {
+                    predicate,
+                    input: Box::new(plan),
+                });
+
+                // Drop the hidden HAVING-only aggregates from the result
+                if has_hidden_columns {
+                    plan = LogicalOperator::Return(ReturnOp {
+                        items: output_columns
+                            .into_iter()
+                            .map(|column| ReturnItem {
+                                expression: LogicalExpression::Variable(column.clone()),
+                                alias: Some(column),
+                            })
+                            .collect(),
+                        distinct: false,
+                        input: Box::new(plan),
+                    });
+                }
+            }
+
             // Apply ORDER BY for aggregate queries
             // Note: ORDER BY sort keys reference aggregate output columns (aliases)
             if let Some(order_by) = &query.return_clause.order_by {
@@ -315,6 +364,7 @@ impl GqlTranslator {
 
             // Note: For aggregate queries, we don't add a Return operator
             // because Aggregate already produces the final output
+            plan = Self::apply_skip_limit(&query.return_clause, plan);
         } else {
             // Apply ORDER BY
             if let Some(order_by) = &query.return_clause.order_by {
@@ -338,6 +388,13 @@ impl GqlTranslator {
                 });
             }
 
+            // Without DISTINCT, rows can be cut before they are projected,
+            // which keeps SKIP and LIMIT right above the sort
+            let distinct = query.return_clause.distinct;
+            if !distinct {
+                plan = Self::apply_skip_limit(&query.return_clause, plan);
+            }
+
             // Apply RETURN
             let return_items = query
                 .return_clause
@@ -353,14 +410,40 @@ impl GqlTranslator {
 
             plan = LogicalOperator::Return(ReturnOp {
                 items: return_items,
-                distinct: query.return_clause.distinct,
+                distinct,
                 input: Box::new(plan),
             });
+            if distinct {
+                plan = Self::apply_skip_limit(&query.return_clause, plan);
+            }
         }
 
         Ok(LogicalPlan::new(plan))
     }
 
+    /// Applies the RETURN clause's SKIP and LIMIT over `plan`.
+    ///
+    /// They count result rows, so `plan` must already be sorted, aggregated
+    /// and deduplicated.
+    fn apply_skip_limit(
+        return_clause: &ast::ReturnClause,
+        mut plan: LogicalOperator,
+    ) -> LogicalOperator {
+        if let Some(ast::Expression::Literal(ast::Literal::Integer(n))) = &return_clause.skip {
+            plan = LogicalOperator::Skip(SkipOp {
+                count: *n as usize,
+                input: Box::new(plan),
+            });
+        }
+        if let Some(ast::Expression::Literal(ast::Literal::Integer(n))) = &return_clause.limit {
+            plan = LogicalOperator::Limit(LimitOp {
+                count: *n as usize,
+                input: Box::new(plan),
+            });
+        }
+        plan
+    }
+
     /// Builds return items for an aggregate query.
     #[allow(dead_code)]
     fn build_aggregate_return_items(&self, items: &[ast::ReturnItem]) -> Result<Vec<ReturnItem>> {
@@ -514,114 +597,97 @@ impl GqlTranslator {
     fn translate_create_patterns(
         &self,
         patterns: &[ast::Pattern],
-        mut plan: LogicalOperator,
+        plan: LogicalOperator,
     ) -> Result<LogicalOperator> {
+        let mut plan = Some(plan);
         for pattern in patterns {
-            match pattern {
-                ast::Pattern::Node(node) => {
-                    let variable = node
-                        .variable
-                        .clone()
-                        .unwrap_or_else(|| format!("_anon_{}", rand_id()));
-                    let properties: Vec<(String, LogicalExpression)> = node
+            plan = Some(self.translate_create_pattern(pattern, plan)?);
+        }
+        plan.ok_or_else(|| Error::Internal("Empty CREATE pattern".to_string()))
+    }
+
+    /// Translates one CREATE/INSERT pattern. Node variables already bound by
+    /// the input (a prior MATCH or an earlier node in the same statement) are
+    /// reused; every other node is created before the edges that touch it.
+    fn translate_create_pattern(
+        &self,
+        pattern: &ast::Pattern,
+        input: Option<LogicalOperator>,
+    ) -> Result<LogicalOperator> {
+        match pattern {
+            ast::Pattern::Node(node) => {
+                let (_, plan) = self.translate_create_endpoint(node, input)?;
+                plan.ok_or_else(|| Error::Internal("Empty CREATE pattern".to_string()))
+            }
+            ast::Pattern::Path(path) => {
+                let (mut source_var, mut plan) =
+                    self.translate_create_endpoint(&path.source, input)?;
+
+                for edge in &path.edges {
+                    let (target_var, target_plan) =
+                        self.translate_create_endpoint(&edge.target, plan)?;
+
+                    let edge_props: Vec<(String, LogicalExpression)> = edge
                         .properties
                         .iter()
                         .map(|(k, v)| Ok((k.clone(), self.translate_expression(v)?)))
                         .collect::<Result<_>>()?;
 
-                    plan = LogicalOperator::CreateNode(CreateNodeOp {
-                        variable,
-                        labels: node.labels.clone(),
-                        properties,
-                        input: Some(Box::new(plan)),
-                    });
-                }
-                ast::Pattern::Path(path) => {
-                    // First create the source node if it has labels (new node)
-                    let source_var = path
-                        .source
-                        .variable
-                        .clone()
-                        .unwrap_or_else(|| format!("_anon_{}", rand_id()));
-
-                    // If source has labels, it's a new node to create
-                    if !path.source.labels.is_empty() {
-                        let source_props: Vec<(String, LogicalExpression)> = path
-                            .source
-                            .properties
-                            .iter()
-                            .map(|(k, v)| Ok((k.clone(), self.translate_expression(v)?)))
-                            .collect::<Result<_>>()?;
-
-                        plan = LogicalOperator::CreateNode(CreateNodeOp {
-                            variable: source_var.clone(),
-                            labels: path.source.labels.clone(),
-                            properties: source_props,
-                            input: Some(Box::new(plan)),
-                        });
-                    }
-
-                    // Create edges and target nodes
-                    for edge in &path.edges {
-                        let target_var = edge
-                            .target
-                            .variable
-                            .clone()
-                            .unwrap_or_else(|| format!("_anon_{}", rand_id()));
-
-                        // If target has labels, create it
-                        if !edge.target.labels.is_empty() {
-                            let target_props: Vec<(String, LogicalExpression)> = edge
-                                .target
-                                .properties
-                                .iter()
-                                .map(|(k, v)| Ok((k.clone(), self.translate_expression(v)?)))
-                                .collect::<Result<_>>()?;
-
-                            plan = LogicalOperator::CreateNode(CreateNodeOp {
-                                variable: target_var.clone(),
-                                labels: edge.target.labels.clone(),
-                                properties: target_props,
-                                input: Some(Box::new(plan)),
-                            });
+                    let (from_variable, to_variable) = match edge.direction {
+                        ast::EdgeDirection::Incoming => (target_var.clone(), source_var),
+                        ast::EdgeDirection::Outgoing | ast::EdgeDirection::Undirected => {
+                            (source_var, target_var.clone())
                         }
+                    };
 
-                        // Create the edge
-                        let edge_type = edge.types.first().cloned().unwrap_or_default();
-                        let edge_var = edge.variable.clone();
-                        let edge_props: Vec<(String, LogicalExpression)> = edge
-                            .properties
-                            .iter()
-                            .map(|(k, v)| Ok((k.clone(), self.translate_expression(v)?)))
-                            .collect::<Result<_>>()?;
-
-                        // Determine direction
-                        let (from_var, to_var) = match edge.direction {
-                            ast::EdgeDirection::Outgoing => (source_var.clone(), target_var),
-                            ast::EdgeDirection::Incoming => {
-                                let tv = edge
-                                    .target
-                                    .variable
-                                    .clone()
-                                    .unwrap_or_else(|| format!("_anon_{}", rand_id()));
-                                (tv, source_var.clone())
-                            }
-                            ast::EdgeDirection::Undirected => (source_var.clone(), target_var),
-                        };
-
-                        plan = LogicalOperator::CreateEdge(CreateEdgeOp {
-                            variable: edge_var,
-                            from_variable: from_var,
-                            to_variable: to_var,
-                            edge_type,
-                            properties: edge_props,
-                            input: Box::new(plan),
-                        });
-                    }
+                    let input = target_plan
+                        .ok_or_else(|| Error::Internal("Edge has no endpoints".to_string()))?;
+                    plan = Some(LogicalOperator::CreateEdge(CreateEdgeOp {
+                        variable: edge.variable.clone(),
+                        from_variable,
+                        to_variable,
+                        edge_type: edge.types.first().cloned().unwrap_or_default(),
+                        properties: edge_props,
+                        input: Box::new(input),
+                    }));
+                    source_var = target_var;
                 }
+
+                plan.ok_or_else(|| Error::Internal("Empty CREATE pattern".to_string()))
             }
         }
-        Ok(plan)
+    }
+
+    /// Resolves a node in a CREATE/INSERT pattern to its variable, adding a
+    /// `CreateNode` unless the variable is already bound by the input.
+    fn translate_create_endpoint(
+        &self,
+        node: &ast::NodePattern,
+        input: Option<LogicalOperator>,
+    ) -> Result<(String, Option<LogicalOperator>)> {
+        if let Some(variable) = &node.variable {
+            if input.as_ref().is_some_and(|i| i.binds_variable(variable)) {
+                return Ok((variable.clone(), input));
+            }
+        }
+
+        let variable = node
+            .variable
+            .clone()
+            .unwrap_or_else(|| format!("_anon_{}", rand_id()));
+        let properties: Vec<(String, LogicalExpression)> = node
+            .properties
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), self.translate_expression(v)?)))
+            .collect::<Result<_>>()?;
+
+        let plan = LogicalOperator::CreateNode(CreateNodeOp {
+            variable: variable.clone(),
+            labels: node.labels.clone(),
+            properties,
+            input: input.map(Box::new),
+        });
+        Ok((variable, Some(plan)))
     }
 
     fn translate_node_pattern(
@@ -750,10 +816,14 @@ impl GqlTranslator {
 
             let edge_var_for_filter = edge_var.clone();
 
+            // No quantifier means exactly one hop; `*` and `*n..` leave max unbounded
+            let (min_hops, max_hops) = match edge.min_hops {
+                Some(min) => (min, edge.max_hops),
+                None => (1, Some(1)),
+            };
+
             // Only set path_alias on the last edge of a variable-length path
-            let is_variable_length = edge.min_hops.unwrap_or(1) != 1
-                || edge.max_hops.is_none()
-                || edge.max_hops.map_or(false, |m| m != 1);
+            let is_variable_length = min_hops != 1 || max_hops != Some(1);
             let expand_path_alias = if is_variable_length && idx == edge_count - 1 {
                 path_alias.map(String::from)
             } else {
@@ -766,8 +836,8 @@ impl GqlTranslator {
                 edge_variable: edge_var,
                 direction,
                 edge_type,
-                min_hops: edge.min_hops.unwrap_or(1),
-                max_hops: edge.max_hops.or(Some(1)),
+                min_hops,
+                max_hops,
                 input: Box::new(plan),
                 path_alias: expand_path_alias,
             });
@@ -891,51 +961,34 @@ impl GqlTranslator {
     }
 
     fn translate_insert(&self, insert: &ast::InsertStatement) -> Result<LogicalPlan> {
-        // For now, just translate insert patterns as creates
-        // A full implementation would handle multiple patterns
-
         if insert.patterns.is_empty() {
             return Err(Error::Internal("Empty INSERT statement".to_string()));
         }
 
-        let pattern = &insert.patterns[0];
-
-        match pattern {
-            ast::Pattern::Node(node) => {
-                let variable = node
-                    .variable
-                    .clone()
-                    .unwrap_or_else(|| format!("_anon_{}", rand_id()));
-
-                let properties = node
-                    .properties
-                    .iter()
-                    .map(|(k, v)| Ok((k.clone(), self.translate_expression(v)?)))
-                    .collect::<Result<Vec<_>>>()?;
-
-                let create = LogicalOperator::CreateNode(crate::query::plan::CreateNodeOp {
-                    variable: variable.clone(),
-                    labels: node.labels.clone(),
-                    properties,
-                    input: None,
-                });
-
-                // Return the created node
-                let ret = LogicalOperator::Return(ReturnOp {
-                    items: vec![ReturnItem {
-                        expression: LogicalExpression::Variable(variable),
-                        alias: None,
-                    }],
-                    distinct: false,
-                    input: Box::new(create),
-                });
+        let mut plan = None;
+        for pattern in &insert.patterns {
+            plan = Some(self.translate_create_pattern(pattern, plan)?);
+        }
+        let plan = plan.ok_or_else(|| Error::Internal("Empty INSERT statement".to_string()))?;
 
-                Ok(LogicalPlan::new(ret))
-            }
-            ast::Pattern::Path(_) => {
-                Err(Error::Internal("Path INSERT not yet supported".to_string()))
-            }
+        // A single inserted node is returned, matching the node-only form
+        if let [ast::Pattern::Node(node)] = insert.patterns.as_slice() {
+            let variable = match &plan {
+                LogicalOperator::CreateNode(create) => create.variable.clone(),
+                _ => node.variable.clone().unwrap_or_default(),
+            };
+            let ret = LogicalOperator::Return(ReturnOp {
+                items: vec![ReturnItem {
+                    expression: LogicalExpression::Variable(variable),
+                    alias: None,
+                }],
+                distinct: false,
+                input: Box::new(plan),
+            });
+            return Ok(LogicalPlan::new(ret));
         }
+
+        Ok(LogicalPlan::new(plan))
     }
 
     fn translate_expression(&self, expr: &ast::Expression) -> Result<LogicalExpression> {
@@ -1041,6 +1094,15 @@ impl GqlTranslator {
                 let inner_plan = self.translate_subquery_to_operator(query)?;
                 Ok(LogicalExpression::ExistsSubquery(Box::new(inner_plan)))
             }
+            ast::Expression::InSubquery { expr, query } => {
+                let expr = self.translate_expression(expr)?;
+                // Keep the RETURN so the planner knows which column to compare
+                let inner_plan = self.translate_query(query)?;
+                Ok(LogicalExpression::InSubquery {
+                    expr: Box::new(expr),
+                    subquery: Box::new(inner_plan.root),
+                })
+            }
         }
     }
 
@@ -1141,6 +1203,56 @@ impl GqlTranslator {
         Ok((aggregates, group_by))
     }
 
+    /// Translates a HAVING expression so it reads the aggregate's output columns.
+    ///
+    /// Aggregate calls resolve to the matching RETURN aggregate (or a new hidden one
+    /// appended to `aggregates`), and group keys resolve to their group-by column.
+    fn translate_having_expression(
+        &self,
+        expr: &ast::Expression,
+        group_by: &[LogicalExpression],
+        aggregates: &mut Vec<AggregateExpr>,
+    ) -> Result<LogicalExpression> {
+        if let Some(mut agg_expr) = self.try_extract_aggregate(expr, &None)? {
+            let existing = aggregates.iter().find(|existing| {
+                existing.function == agg_expr.function
+                    && existing.distinct == agg_expr.distinct
+                    && existing.expression == agg_expr.expression
+            });
+            let column = match existing {
+                Some(existing) => existing.output_column(),
+                None => {
+                    let column = format!("__having_{}", aggregates.len());
+                    agg_expr.alias = Some(column.clone());
+                    aggregates.push(agg_expr);
+                    column
+                }
+            };
+            return Ok(LogicalExpression::Variable(column));
+        }
+
+        match expr {
+            ast::Expression::Binary { left, op, right } => Ok(LogicalExpression::Binary {
+                left: Box::new(self.translate_having_expression(left, group_by, aggregates)?),
+                op: self.translate_binary_op(*op),
+                right: Box::new(self.translate_having_expression(right, group_by, aggregates)?),
+            }),
+            ast::Expression::Unary { op, operand } => Ok(LogicalExpression::Unary {
+                op: self.translate_unary_op(*op),
+                operand: Box::new(self.translate_having_expression(operand, group_by, aggregates)?),
+            }),
+            _ => {
+                let translated = self.translate_expression(expr)?;
+                match group_by.iter().find(|g| **g == translated) {
+                    Some(group_key) => {
+                        Ok(LogicalExpression::Variable(expression_to_string(group_key)))
+                    }
+                    None => Ok(translated),
+                }
+            }
+        }
+    }
+
     /// Tries to extract an aggregate expression from an AST expression.
     fn try_extract_aggregate(
         &self,
@@ -1177,16 +1289,9 @@ impl GqlTranslator {
                             AggregateFunction::PercentileDisc | AggregateFunction::PercentileCont
                         ) && args.len() >= 2
                         {
-                            // Second argument is the percentile value
-                            if let ast::Expression::Literal(ast::Literal::Float(p)) = &args[1] {
-                                Some((*p).clamp(0.0, 1.0))
-                            } else if let ast::Expression::Literal(ast::Literal::Integer(p)) =
-                                &args[1]
-                            {
-                                Some((*p as f64).clamp(0.0, 1.0))
-                            } else {
-                                Some(0.5) // Default to median
-                            }
+                            Some(AggregateExpr::percentile_argument(
+                                &self.translate_expression(&args[1])?,
+                            )?)
                         } else {
                             None
                         };
@@ -1490,6 +1595,61 @@ mod tests {
         assert_eq!(expand.direction, ExpandDirection::Both);
     }
 
+    fn find_expand(op: &LogicalOperator) -> Option<&ExpandOp> {
+        match op {
+            LogicalOperator::Expand(e) => Some(e),
+            LogicalOperator::Return(r) => find_expand(&r.input),
+            LogicalOperator::Filter(f) => find_expand(&f.input),
+            _ => None,
+        }
+    }
+
+    fn expand_hops(query: &str) -> (u32, Option<u32>) {
+        let plan = translate(query).unwrap();
+        let expand = find_expand(&plan.root).expect("Expected Expand");
+        (expand.min_hops, expand.max_hops)
+    }
+
+    #[test]
+    fn test_translate_single_hop_without_quantifier() {
+        assert_eq!(
+            expand_hops("MATCH (a:Person)-[:KNOWS]->(b:Person) RETURN a, b"),
+            (1, Some(1))
+        );
+    }
+
+    #[test]
+    fn test_translate_unbounded_quantifier() {
+        assert_eq!(
+            expand_hops("MATCH (a:Person)-[:KNOWS*]->(b:Person) RETURN a, b"),
+            (1, None)
+        );
+    }
+
+    #[test]
+    fn test_translate_exact_quantifier() {
+        assert_eq!(
+            expand_hops("MATCH (a:Person)-[:KNOWS*2]->(b:Person) RETURN a, b"),
+            (2, Some(2))
+        );
+    }
+
+    #[test]
+    fn test_translate_open_ended_quantifier() {
+        assert_eq!(
+            expand_hops("MATCH (a:Person)-[:KNOWS*1..]->(b:Person) RETURN a, b"),
+            (1, None)
+        );
+    }
+
+    #[test]
+    fn test_translate_bounded_quantifier() {
+        assert_eq!(
+            expand_hops("MATCH (a:Person)-[:KNOWS*2..5]->(b:Person) RETURN a, b"),
+            (2, Some(5))
+        );
+    }
+
     // === Aggregation Tests ===
 
     #[test]
@@ -1508,6 +1668,104 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_translate_in_subquery() {
+        let query = "MATCH (n:Person) WHERE n.city IN (MATCH (c:City) RETURN c.name) RETURN n";
+        let plan = translate(query).unwrap();
+
+        let LogicalOperator::Return(ret) = &plan.root else {
+            panic!("Expected Return operator");
+        };
+        let LogicalOperator::Filter(filter) = ret.input.as_ref() else {
+            panic!("Expected Filter operator");
+        };
+        let LogicalExpression::InSubquery { expr, subquery } = &filter.predicate else {
+            panic!("Expected IN subquery, got {:?}", filter.predicate);
+        };
+        assert!(matches!(
+            expr.as_ref(),
+            LogicalExpression::Property { variable, property } if variable == "n" && property == "city"
+        ));
+
+        // The subquery keeps its RETURN so the compared column is known
+        let LogicalOperator::Return(inner) = subquery.as_ref() else {
+            panic!("Expected subquery to end in Return");
+        };
+        assert_eq!(inner.items.len(), 1);
+    }
+
+    #[test]
+    fn test_translate_having_on_alias() {
+        let query = "MATCH (n:Person) RETURN n.city, COUNT(n) AS cnt HAVING cnt > 5";
+        let plan = translate(query).unwrap();
+
+        // HAVING becomes a Filter directly above the Aggregate
+        let LogicalOperator::Filter(filter) = &plan.root else {
+            panic!("Expected Filter operator, got {:?}", plan.root);
+        };
+        let LogicalExpression::Binary { left, op, .. } = &filter.predicate else {
+            panic!("Expected binary predicate");
+        };
+        assert_eq!(*op, BinaryOp::Gt);
+        assert!(matches!(left.as_ref(), LogicalExpression::Variable(v) if v == "cnt"));
+
+        let LogicalOperator::Aggregate(agg) = filter.input.as_ref() else {
+            panic!("Expected Aggregate below HAVING filter");
+        };
+        assert!(agg.having.is_none());
+        assert_eq!(agg.aggregates.len(), 1);
+    }
+
+    #[test]
+    fn test_translate_having_reuses_return_aggregate() {
+        let query = "MATCH (n:Person) RETURN n.city, COUNT(n) AS cnt HAVING COUNT(n) > 5";
+        let plan = translate(query).unwrap();
+
+        let LogicalOperator::Filter(filter) = &plan.root else {
+            panic!("Expected Filter operator");
+        };
+        let LogicalExpression::Binary { left, .. } = &filter.predicate else {
+            panic!("Expected binary predicate");
+        };
+        assert!(matches!(left.as_ref(), LogicalExpression::Variable(v) if v == "cnt"));
+    }
+
+    #[test]
+    fn test_translate_having_hidden_aggregate() {
+        let query = "MATCH (n:Person) RETURN n.city HAVING COUNT(n) > 5 AND n.city <> 'X'";
+        let plan = translate(query).unwrap();
+
+        // A Return strips the HAVING-only aggregate from the output
+        let LogicalOperator::Return(ret) = &plan.root else {
+            panic!("Expected Return operator");
+        };
+        assert_eq!(ret.items.len(), 1);
+        assert_eq!(ret.items[0].alias.as_deref(), Some("n.city"));
+
+        let LogicalOperator::Filter(filter) = ret.input.as_ref() else {
+            panic!("Expected Filter operator");
+        };
+        let LogicalExpression::Binary { left, right, .. } = &filter.predicate else {
+            panic!("Expected binary predicate");
+        };
+        // Group keys read the aggregate's group column rather than the node
+        assert!(matches!(
+            left.as_ref(),
+            LogicalExpression::Binary { left, .. }
+                if matches!(left.as_ref(), LogicalExpression::Variable(v) if v.starts_with("__having_"))
+        ));
+        assert!(matches!(
+            right.as_ref(),
+            LogicalExpression::Binary { left, .. }
+                if matches!(left.as_ref(), LogicalExpression::Variable(v) if v == "n.city")
+        ));
+
+        let LogicalOperator::Aggregate(agg) = filter.input.as_ref() else {
+            panic!("Expected Aggregate operator");
+        };
+        assert_eq!(agg.aggregates.len(), 1);
+    }
+
     #[test]
     fn test_translate_sum_aggregate() {
         let query = "MATCH (n:Person) RETURN SUM(n.age)";
@@ -1621,6 +1879,55 @@ mod tests {
         assert!(find_create(&plan.root));
     }
 
+    #[test]
+    fn test_translate_insert_path_creates_endpoints_and_edge() {
+        let plan = translate("INSERT (a:Person)-[:KNOWS {since: 2020}]->(b:Person)").unwrap();
+
+        let LogicalOperator::CreateEdge(edge) = &plan.root else {
+            panic!("Expected CreateEdge, got {:?}", plan.root);
+        };
+        assert_eq!(edge.edge_type, "KNOWS");
+        assert_eq!(
+            (edge.from_variable.as_str(), edge.to_variable.as_str()),
+            ("a", "b")
+        );
+        assert_eq!(edge.properties.len(), 1);
+
+        let LogicalOperator::CreateNode(b) = edge.input.as_ref() else {
+            panic!("Expected CreateNode for b, got {:?}", edge.input);
+        };
+        assert_eq!(b.variable, "b");
+        let Some(LogicalOperator::CreateNode(a)) = b.input.as_deref() else {
+            panic!("Expected CreateNode for a, got {:?}", b.input);
+        };
+        assert_eq!(a.variable, "a");
+    }
+
+    #[test]
+    fn test_translate_match_insert_reuses_bound_nodes() {
+        let plan = translate(
+            "MATCH (a:Person {name: 'Alix'}), (b:Person {name: 'Gus'}) \
+             INSERT (a)-[:KNOWS]->(b)",
+        )
+        .unwrap();
+
+        let root = match &plan.root {
+            LogicalOperator::Return(ret) => ret.input.as_ref(),
+            other => other,
+        };
+        let LogicalOperator::CreateEdge(edge) = root else {
+            panic!("Expected CreateEdge, got {root:?}");
+        };
+        assert_eq!(
+            (edge.from_variable.as_str(), edge.to_variable.as_str()),
+            ("a", "b")
+        );
+        assert!(
+            !matches!(edge.input.as_ref(), LogicalOperator::CreateNode(_)),
+            "bound endpoints are not recreated"
+        );
+    }
+
     #[test]
     fn test_translate_delete() {
         let query = "DELETE n";
@@ -1661,6 +1968,46 @@ mod tests {
         }
     }
 
+    fn find_merge(op: &LogicalOperator) -> Option<&MergeOp> {
+        match op {
+            LogicalOperator::Merge(m) => Some(m),
+            LogicalOperator::Return(r) => find_merge(&r.input),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_translate_merge() {
+        let plan = translate("MERGE (n:Person {email: $e}) RETURN n").unwrap();
+        let merge = find_merge(&plan.root).expect("Expected Merge");
+
+        assert_eq!(merge.variable, "n");
+        assert_eq!(merge.labels, vec!["Person".to_string()]);
+        assert_eq!(merge.match_properties.len(), 1);
+        assert_eq!(merge.match_properties[0].0, "email");
+        assert!(matches!(
+            &merge.match_properties[0].1,
+            LogicalExpression::Parameter(name) if name == "e"
+        ));
+        assert!(merge.on_create.is_empty());
+        assert!(merge.on_match.is_empty());
+        assert!(matches!(merge.input.as_ref(), LogicalOperator::Empty));
+    }
+
+    #[test]
+    fn test_translate_merge_on_create_on_match() {
+        let plan = translate(
+            "MERGE (n:Person {email: 'a@b.c'}) ON CREATE SET n.created = 1 ON MATCH SET n.seen = 2 RETURN n",
+        )
+        .unwrap();
+        let merge = find_merge(&plan.root).expect("Expected Merge");
+
+        assert_eq!(merge.on_create.len(), 1);
+        assert_eq!(merge.on_create[0].0, "created");
+        assert_eq!(merge.on_match.len(), 1);
+        assert_eq!(merge.on_match[0].0, "seen");
+    }
+
     // === Expression Translation Tests ===
 
     #[test]
@@ -1871,4 +2218,38 @@ mod tests {
             UnaryOp::IsNotNull
         );
     }
+
+    #[test]
+    fn test_translate_call_procedure() {
+        let plan =
+            translate("CALL pagerank() YIELD node, score RETURN node ORDER BY score DESC LIMIT 10")
+                .unwrap();
+
+        fn find_call(op: &LogicalOperator) -> Option<&CallProcedureOp> {
+            match op {
+                LogicalOperator::CallProcedure(call) => Some(call),
+                LogicalOperator::Return(r) => find_call(&r.input),
+                LogicalOperator::Limit(l) => find_call(&l.input),
+                LogicalOperator::Sort(s) => find_call(&s.input),
+                _ => None,
+            }
+        }
+
+        let call = find_call(&plan.root).expect("Expected CallProcedure");
+        assert_eq!(call.name, "pagerank");
+        assert!(call.arguments.is_empty());
+        assert_eq!(call.yields, vec!["node", "score"]);
+    }
+
+    #[test]
+    fn test_translate_create_composite_index() {
+        let plan = translate("CREATE INDEX ON :Person(lastName, firstName)").unwrap();
+
+        if let LogicalOperator::CreateIndex(index) = &plan.root {
+            assert_eq!(index.label, "Person");
+            assert_eq!(index.properties, vec!["lastName", "firstName"]);
+        } else {
+            panic!("Expected CreateIndex, got {:?}", plan.root);
+        }
+    }
 }
@@ -7,8 +7,10 @@
 //! GraphQL's hierarchical selection model maps to LPG traversals:
 //! - Root fields → NodeScan (field name is the type/label)
 //! - Field arguments → Filter predicates
-//! - Nested selections → Expand (field name is relationship type)
-//! - Scalar fields → Return projections
+//! - Nested selections → Expand (field name is relationship type, optionally
+//!   remapped through an edge type mapping)
+//! - Scalar fields → Return projections (absent properties project to null)
+//! - `id` arguments and fields → node IDs
 
 use crate::query::plan::{
     BinaryOp, CreateNodeOp, DeleteNodeOp, ExpandDirection, ExpandOp, FilterOp, LimitOp,
@@ -26,8 +28,26 @@ use std::sync::atomic::{AtomicU32, Ordering};
 ///
 /// Returns an error if the query cannot be parsed or translated.
 pub fn translate(query: &str) -> Result<LogicalPlan> {
+    translate_with_edge_types(query, &HashMap::new())
+}
+
+/// Translates a GraphQL query string to a logical plan, resolving relationship
+/// fields to edge types through `edge_types`.
+///
+/// Fields without an entry in the mapping use the field name as the edge type.
+///
+/// # Errors
+///
+/// Returns an error if the query cannot be parsed or translated.
+pub fn translate_with_edge_types(
+    query: &str,
+    edge_types: &HashMap<String, String>,
+) -> Result<LogicalPlan> {
     let doc = graphql::parse(query)?;
-    let translator = GraphQLTranslator::new();
+    let translator = GraphQLTranslator {
+        edge_types: edge_types.clone(),
+        ..GraphQLTranslator::new()
+    };
     translator.translate_document(&doc)
 }
 
@@ -57,6 +77,8 @@ struct GraphQLTranslator {
     var_counter: AtomicU32,
     /// Fragment definitions for resolution.
     fragments: HashMap<String, ast::FragmentDefinition>,
+    /// Relationship field name to edge type mapping.
+    edge_types: HashMap<String, String>,
 }
 
 impl GraphQLTranslator {
@@ -64,6 +86,7 @@ impl GraphQLTranslator {
         Self {
             var_counter: AtomicU32::new(0),
             fragments: HashMap::new(),
+            edge_types: HashMap::new(),
         }
     }
 
@@ -95,6 +118,7 @@ impl GraphQLTranslator {
         let translator = GraphQLTranslator {
             var_counter: AtomicU32::new(0),
             fragments,
+            edge_types: self.edge_types.clone(),
         };
 
         translator.translate_operation(operation)
@@ -539,10 +563,7 @@ impl GraphQLTranslator {
                         // Scalar field - add to return items
                         let alias = field.alias.clone().unwrap_or(field.name.clone());
                         return_items.push(ReturnItem {
-                            expression: LogicalExpression::Property {
-                                variable: current_var.to_string(),
-                                property: field.name.clone(),
-                            },
+                            expression: self.scalar_field_expression(field, current_var),
                             alias: Some(alias),
                         });
                     }
@@ -591,13 +612,13 @@ impl GraphQLTranslator {
     ) -> Result<(LogicalOperator, Vec<ReturnItem>)> {
         let to_var = self.next_var();
 
-        // The field name is the edge type
+        // The field name (or its mapping) is the edge type
         let mut plan = LogicalOperator::Expand(ExpandOp {
             from_variable: from_var.to_string(),
             to_variable: to_var.clone(),
             edge_variable: None,
             direction: ExpandDirection::Outgoing,
-            edge_type: Some(field.name.clone()),
+            edge_type: Some(self.resolve_edge_type(&field.name)),
             min_hops: 1,
             max_hops: Some(1),
             input: Box::new(input),
@@ -638,13 +659,13 @@ impl GraphQLTranslator {
     ) -> Result<(LogicalOperator, String)> {
         let to_var = self.next_var();
 
-        // The field name is the edge type
+        // The field name (or its mapping) is the edge type
         let mut plan = LogicalOperator::Expand(ExpandOp {
             from_variable: from_var.to_string(),
             to_variable: to_var.clone(),
             edge_variable: None,
             direction: ExpandDirection::Outgoing,
-            edge_type: Some(field.name.clone()),
+            edge_type: Some(self.resolve_edge_type(&field.name)),
             min_hops: 1,
             max_hops: Some(1),
             input: Box::new(input),
@@ -700,9 +721,13 @@ impl GraphQLTranslator {
                 }
             } else {
                 // Direct argument (legacy behavior): name: "Alice" → name = "Alice"
-                let prop = LogicalExpression::Property {
-                    variable: var.to_string(),
-                    property: arg.name.clone(),
+                let prop = if arg.name == "id" {
+                    LogicalExpression::Id(var.to_string())
+                } else {
+                    LogicalExpression::Property {
+                        variable: var.to_string(),
+                        property: arg.name.clone(),
+                    }
                 };
                 let value = LogicalExpression::Literal(arg.value.to_value());
                 predicates.push(LogicalExpression::Binary {
@@ -785,10 +810,7 @@ impl GraphQLTranslator {
                     // Scalar field
                     let alias = field.alias.clone().unwrap_or(field.name.clone());
                     return_items.push(ReturnItem {
-                        expression: LogicalExpression::Property {
-                            variable: current_var.to_string(),
-                            property: field.name.clone(),
-                        },
+                        expression: self.scalar_field_expression(field, current_var),
                         alias: Some(alias),
                     });
                 }
@@ -811,10 +833,7 @@ impl GraphQLTranslator {
                 if field.selection_set.is_none() {
                     let alias = field.alias.clone().unwrap_or(field.name.clone());
                     return_items.push(ReturnItem {
-                        expression: LogicalExpression::Property {
-                            variable: current_var.to_string(),
-                            property: field.name.clone(),
-                        },
+                        expression: self.scalar_field_expression(field, current_var),
                         alias: Some(alias),
                     });
                 }
@@ -824,6 +843,27 @@ impl GraphQLTranslator {
         Ok((input, return_items))
     }
 
+    /// Returns the expression for a scalar field: `id` is the element ID, any other
+    /// field is a property lookup that yields null when the property is absent.
+    fn scalar_field_expression(&self, field: &ast::Field, var: &str) -> LogicalExpression {
+        if field.name == "id" {
+            LogicalExpression::Id(var.to_string())
+        } else {
+            LogicalExpression::Property {
+                variable: var.to_string(),
+                property: field.name.clone(),
+            }
+        }
+    }
+
+    /// Resolves a relationship field name to its edge type.
+    fn resolve_edge_type(&self, field_name: &str) -> String {
+        self.edge_types
+            .get(field_name)
+            .cloned()
+            .unwrap_or_else(|| field_name.to_string())
+    }
+
     fn get_first_field<'a>(&self, selection_set: &'a ast::SelectionSet) -> Result<&'a ast::Field> {
         for selection in &selection_set.selections {
             if let ast::Selection::Field(field) = selection {
@@ -934,6 +974,85 @@ mod tests {
         }
     }
 
+    // ==================== Nesting Tests ====================
+
+    fn collect_expands(op: &LogicalOperator) -> Vec<&ExpandOp> {
+        match op {
+            LogicalOperator::Expand(e) => {
+                let mut expands = collect_expands(&e.input);
+                expands.push(e);
+                expands
+            }
+            LogicalOperator::Return(r) => collect_expands(&r.input),
+            LogicalOperator::Filter(f) => collect_expands(&f.input),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_id_argument_filters_on_node_id() {
+        let plan = translate("{ person(id: 1) { name } }").unwrap();
+
+        let LogicalOperator::Return(ret) = &plan.root else {
+            panic!("Expected Return operator");
+        };
+        let LogicalOperator::Filter(filter) = ret.input.as_ref() else {
+            panic!("Expected Filter operator");
+        };
+        let LogicalExpression::Binary { left, op, .. } = &filter.predicate else {
+            panic!("Expected Binary predicate");
+        };
+        assert_eq!(*op, BinaryOp::Eq);
+        assert!(matches!(left.as_ref(), LogicalExpression::Id(_)));
+    }
+
+    #[test]
+    fn test_one_level_nesting_with_edge_type_mapping() {
+        let edge_types = HashMap::from([("friends".to_string(), "KNOWS".to_string())]);
+        let plan =
+            translate_with_edge_types("{ person(id: 1) { name friends { name } } }", &edge_types)
+                .unwrap();
+
+        let expands = collect_expands(&plan.root);
+        assert_eq!(expands.len(), 1);
+        assert_eq!(expands[0].edge_type.as_deref(), Some("KNOWS"));
+
+        let LogicalOperator::Return(ret) = &plan.root else {
+            panic!("Expected Return operator");
+        };
+        let aliases: Vec<_> = ret.items.iter().map(|i| i.alias.as_deref()).collect();
+        assert_eq!(aliases, vec![Some("name"), Some("friends_name")]);
+        assert!(matches!(
+            &ret.items[1].expression,
+            LogicalExpression::Property { variable, .. } if *variable == expands[0].to_variable
+        ));
+    }
+
+    #[test]
+    fn test_two_level_nesting() {
+        let plan = translate("{ person { name friends { name employer { name } } } }").unwrap();
+
+        let expands = collect_expands(&plan.root);
+        assert_eq!(expands.len(), 2);
+        assert_eq!(expands[0].edge_type.as_deref(), Some("friends"));
+        assert_eq!(expands[1].edge_type.as_deref(), Some("employer"));
+        // The second hop starts where the first one ended
+        assert_eq!(expands[1].from_variable, expands[0].to_variable);
+
+        let LogicalOperator::Return(ret) = &plan.root else {
+            panic!("Expected Return operator");
+        };
+        let aliases: Vec<_> = ret.items.iter().map(|i| i.alias.as_deref()).collect();
+        assert_eq!(
+            aliases,
+            vec![
+                Some("name"),
+                Some("friends_name"),
+                Some("friends_employer_name")
+            ]
+        );
+    }
+
     // ==================== Pagination Tests ====================
 
     #[test]
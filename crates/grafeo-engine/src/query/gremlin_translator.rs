@@ -483,70 +483,16 @@ impl GremlinTranslator {
             }
 
             // Filter steps
-            ast::Step::Has(has_step) => {
-                let predicate = self.translate_has_step(has_step, current_var)?;
-                let plan = LogicalOperator::Filter(FilterOp {
-                    predicate,
-                    input: Box::new(input),
-                });
-                Ok((plan, None))
-            }
-            ast::Step::HasLabel(labels) => {
-                // Labels(var) returns a list of labels, so we need to check if the
-                // target label is IN that list, not if the list equals the label
-                let predicate = if labels.len() == 1 {
-                    LogicalExpression::Binary {
-                        left: Box::new(LogicalExpression::Literal(Value::String(
-                            labels[0].clone().into(),
-                        ))),
-                        op: BinaryOp::In,
-                        right: Box::new(LogicalExpression::Labels(current_var.to_string())),
-                    }
-                } else {
-                    // For multiple labels, check if ANY of them are in the node's labels
-                    let mut conditions: Vec<LogicalExpression> = labels
-                        .iter()
-                        .map(|l| LogicalExpression::Binary {
-                            left: Box::new(LogicalExpression::Literal(Value::String(
-                                l.clone().into(),
-                            ))),
-                            op: BinaryOp::In,
-                            right: Box::new(LogicalExpression::Labels(current_var.to_string())),
-                        })
-                        .collect();
-                    // OR all conditions together
-                    let mut result = conditions.pop().unwrap();
-                    for cond in conditions {
-                        result = LogicalExpression::Binary {
-                            left: Box::new(cond),
-                            op: BinaryOp::Or,
-                            right: Box::new(result),
-                        };
-                    }
-                    result
-                };
-                let plan = LogicalOperator::Filter(FilterOp {
-                    predicate,
-                    input: Box::new(input),
-                });
-                Ok((plan, None))
-            }
-            ast::Step::HasId(ids) => {
-                let predicate = self.build_id_filter(current_var, ids);
-                let plan = LogicalOperator::Filter(FilterOp {
-                    predicate,
-                    input: Box::new(input),
-                });
-                Ok((plan, None))
-            }
-            ast::Step::HasNot(key) => {
-                let predicate = LogicalExpression::Unary {
-                    op: UnaryOp::IsNull,
-                    operand: Box::new(LogicalExpression::Property {
-                        variable: current_var.to_string(),
-                        property: key.clone(),
-                    }),
-                };
+            ast::Step::Has(_)
+            | ast::Step::HasLabel(_)
+            | ast::Step::HasId(_)
+            | ast::Step::HasNot(_)
+            | ast::Step::Where(_)
+            | ast::Step::And(_)
+            | ast::Step::Or(_)
+            | ast::Step::Not(_)
+            | ast::Step::Is(_) => {
+                let predicate = self.translate_filter_step(step, current_var)?;
                 let plan = LogicalOperator::Filter(FilterOp {
                     predicate,
                     input: Box::new(input),
@@ -818,6 +764,51 @@ impl GremlinTranslator {
                 Ok((input, None))
             }
 
+            // Looping steps
+            ast::Step::Repeat(body) => {
+                let [nav @ (ast::Step::Out(_) | ast::Step::In(_) | ast::Step::Both(_))] =
+                    body.as_slice()
+                else {
+                    return Err(Error::Internal(
+                        "repeat() body must be a single out(), in() or both() step".to_string(),
+                    ));
+                };
+                // Unbounded until a times() or until() modifier narrows it
+                match self.translate_step(nav, input, current_var)? {
+                    (LogicalOperator::Expand(mut expand), target_var) => {
+                        expand.max_hops = None;
+                        Ok((LogicalOperator::Expand(expand), target_var))
+                    }
+                    (plan, target_var) => Ok((plan, target_var)),
+                }
+            }
+            ast::Step::Times(n) => match input {
+                LogicalOperator::Expand(mut expand) => {
+                    let hops = u32::try_from(*n)
+                        .map_err(|_| Error::Internal(format!("times({n}) is out of range")))?;
+                    expand.min_hops = hops;
+                    expand.max_hops = Some(hops);
+                    Ok((LogicalOperator::Expand(expand), None))
+                }
+                _ => Err(Error::Internal(
+                    "times() must directly follow repeat()".to_string(),
+                )),
+            },
+            ast::Step::Until(condition) => match input {
+                LogicalOperator::Expand(expand) => {
+                    // Keep only the paths whose endpoint satisfies the condition
+                    let predicate = self.translate_traversal_condition(condition, current_var)?;
+                    let plan = LogicalOperator::Filter(FilterOp {
+                        predicate,
+                        input: Box::new(LogicalOperator::Expand(expand)),
+                    });
+                    Ok((plan, None))
+                }
+                _ => Err(Error::Internal(
+                    "until() must directly follow repeat()".to_string(),
+                )),
+            },
+
             ast::Step::By(by_modifier) => {
                 // 'by' modifies a preceding order() step
                 // If the input is a Sort operation, we replace its keys with the by modifier
@@ -891,6 +882,174 @@ impl GremlinTranslator {
         }
     }
 
+    /// Translates a filter step into a predicate on `var`.
+    fn translate_filter_step(&self, step: &ast::Step, var: &str) -> Result<LogicalExpression> {
+        match step {
+            ast::Step::Has(has_step) => self.translate_has_step(has_step, var),
+            ast::Step::HasLabel(labels) => {
+                // Labels(var) returns a list of labels, so we need to check if the
+                // target label is IN that list, not if the list equals the label
+                let conditions = labels
+                    .iter()
+                    .map(|l| LogicalExpression::Binary {
+                        left: Box::new(LogicalExpression::Literal(Value::String(l.clone().into()))),
+                        op: BinaryOp::In,
+                        right: Box::new(LogicalExpression::Labels(var.to_string())),
+                    })
+                    .collect();
+                // For multiple labels, check if ANY of them are in the node's labels
+                Ok(Self::combine_conditions(conditions, BinaryOp::Or))
+            }
+            ast::Step::HasId(ids) => Ok(self.build_id_filter(var, ids)),
+            ast::Step::HasNot(key) => Ok(LogicalExpression::Unary {
+                op: UnaryOp::IsNull,
+                operand: Box::new(LogicalExpression::Property {
+                    variable: var.to_string(),
+                    property: key.clone(),
+                }),
+            }),
+            ast::Step::Where(ast::WhereClause::Predicate(start, pred)) => {
+                // where() predicates compare against step labels, not literals
+                let left = LogicalExpression::Variable(start.as_deref().unwrap_or(var).to_string());
+                let predicate = Self::translate_predicate(pred, left)?;
+                Ok(Self::labels_to_variables(predicate))
+            }
+            ast::Step::Where(ast::WhereClause::Traversal(steps)) => {
+                self.translate_traversal_condition(steps, var)
+            }
+            ast::Step::And(traversals) => {
+                let conditions = traversals
+                    .iter()
+                    .map(|steps| self.translate_traversal_condition(steps, var))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Self::combine_conditions(conditions, BinaryOp::And))
+            }
+            ast::Step::Or(traversals) => {
+                let conditions = traversals
+                    .iter()
+                    .map(|steps| self.translate_traversal_condition(steps, var))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Self::combine_conditions(conditions, BinaryOp::Or))
+            }
+            ast::Step::Not(steps) => Ok(LogicalExpression::Unary {
+                op: UnaryOp::Not,
+                operand: Box::new(self.translate_traversal_condition(steps, var)?),
+            }),
+            ast::Step::Is(pred) => {
+                Self::translate_predicate(pred, LogicalExpression::Variable(var.to_string()))
+            }
+            _ => Err(Error::Internal(format!("{step:?} is not a filter step"))),
+        }
+    }
+
+    /// Translates an anonymous traversal used as a filter (e.g. inside `where()`,
+    /// `and()` or `or()`) into a condition on `var`.
+    ///
+    /// Filter steps are conjoined, `values(key)` narrows a following `is()` to that
+    /// property, and a lone navigation step becomes an existence check.
+    fn translate_traversal_condition(
+        &self,
+        steps: &[ast::Step],
+        var: &str,
+    ) -> Result<LogicalExpression> {
+        if let [step @ (ast::Step::Out(_) | ast::Step::In(_) | ast::Step::Both(_))] = steps {
+            let scan = LogicalOperator::NodeScan(NodeScanOp {
+                variable: var.to_string(),
+                label: None,
+                input: None,
+            });
+            let (pattern, _) = self.translate_step(step, scan, var)?;
+            return Ok(LogicalExpression::ExistsSubquery(Box::new(pattern)));
+        }
+
+        let mut conditions = Vec::new();
+        // Property selected by values() that has not been tested by is() yet
+        let mut pending_value: Option<LogicalExpression> = None;
+
+        for step in steps {
+            match step {
+                ast::Step::Values(keys) if keys.len() == 1 => {
+                    if let Some(value) = pending_value.take() {
+                        conditions.push(Self::is_not_null(value));
+                    }
+                    pending_value = Some(LogicalExpression::Property {
+                        variable: var.to_string(),
+                        property: keys[0].clone(),
+                    });
+                }
+                ast::Step::Is(pred) => {
+                    let target = pending_value
+                        .take()
+                        .unwrap_or_else(|| LogicalExpression::Variable(var.to_string()));
+                    conditions.push(Self::translate_predicate(pred, target)?);
+                }
+                ast::Step::Has(_)
+                | ast::Step::HasLabel(_)
+                | ast::Step::HasId(_)
+                | ast::Step::HasNot(_)
+                | ast::Step::Where(_)
+                | ast::Step::And(_)
+                | ast::Step::Or(_)
+                | ast::Step::Not(_) => {
+                    conditions.push(self.translate_filter_step(step, var)?);
+                }
+                _ => {
+                    return Err(Error::Internal(format!(
+                        "Unsupported step in filter traversal: {step:?}"
+                    )));
+                }
+            }
+        }
+
+        if let Some(value) = pending_value {
+            conditions.push(Self::is_not_null(value));
+        }
+
+        Ok(Self::combine_conditions(conditions, BinaryOp::And))
+    }
+
+    /// Folds conditions into a left-deep chain of `op`; an empty list is `true`.
+    fn combine_conditions(conditions: Vec<LogicalExpression>, op: BinaryOp) -> LogicalExpression {
+        conditions
+            .into_iter()
+            .reduce(|left, right| LogicalExpression::Binary {
+                left: Box::new(left),
+                op,
+                right: Box::new(right),
+            })
+            .unwrap_or(LogicalExpression::Literal(Value::Bool(true)))
+    }
+
+    fn is_not_null(expr: LogicalExpression) -> LogicalExpression {
+        LogicalExpression::Unary {
+            op: UnaryOp::IsNotNull,
+            operand: Box::new(expr),
+        }
+    }
+
+    /// Rewrites string literals into variable references, since predicates in
+    /// `where(P.eq('a'))` name step labels.
+    fn labels_to_variables(expr: LogicalExpression) -> LogicalExpression {
+        match expr {
+            LogicalExpression::Literal(Value::String(label)) => {
+                LogicalExpression::Variable(label.to_string())
+            }
+            LogicalExpression::Binary { left, op, right } => LogicalExpression::Binary {
+                left: Box::new(Self::labels_to_variables(*left)),
+                op,
+                right: Box::new(Self::labels_to_variables(*right)),
+            },
+            LogicalExpression::Unary { op, operand } => LogicalExpression::Unary {
+                op,
+                operand: Box::new(Self::labels_to_variables(*operand)),
+            },
+            LogicalExpression::List(items) => {
+                LogicalExpression::List(items.into_iter().map(Self::labels_to_variables).collect())
+            }
+            other => other,
+        }
+    }
+
     fn translate_has_step(&self, has: &ast::HasStep, var: &str) -> Result<LogicalExpression> {
         match has {
             ast::HasStep::Key(key) => {
@@ -1584,6 +1743,165 @@ mod tests {
         assert!(find_sort(&plan.root).is_some());
     }
 
+    // === Filter Combinator Tests ===
+
+    fn top_filter(plan: &LogicalPlan) -> &FilterOp {
+        match &plan.root {
+            LogicalOperator::Return(r) => match r.input.as_ref() {
+                LogicalOperator::Filter(f) => f,
+                other => panic!("Expected Filter, got {other:?}"),
+            },
+            LogicalOperator::Filter(f) => f,
+            other => panic!("Expected Filter, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_translate_where_traversal() {
+        let plan = translate("g.V().has('age', gt(20)).where(values('age').is(lt(40)))").unwrap();
+        let filter = top_filter(&plan);
+
+        match &filter.predicate {
+            LogicalExpression::Binary { left, op, right } => {
+                assert_eq!(*op, BinaryOp::Lt);
+                assert!(matches!(
+                    left.as_ref(),
+                    LogicalExpression::Property { property, .. } if property == "age"
+                ));
+                assert!(matches!(
+                    right.as_ref(),
+                    LogicalExpression::Literal(Value::Int64(40))
+                ));
+            }
+            other => panic!("Expected Binary predicate, got {other:?}"),
+        }
+        // The has() filter is still below the where() filter
+        assert!(matches!(filter.input.as_ref(), LogicalOperator::Filter(_)));
+    }
+
+    #[test]
+    fn test_translate_where_label_predicate() {
+        let plan = translate("g.V().as('a').out().where(P.neq('a'))").unwrap();
+        let filter = top_filter(&plan);
+
+        match &filter.predicate {
+            LogicalExpression::Binary { op, right, .. } => {
+                assert_eq!(*op, BinaryOp::Ne);
+                assert!(matches!(right.as_ref(), LogicalExpression::Variable(v) if v == "a"));
+            }
+            other => panic!("Expected Binary predicate, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_translate_and_traversals() {
+        let plan = translate("g.V().and(has('age', gt(20)), has('name'))").unwrap();
+        let filter = top_filter(&plan);
+
+        match &filter.predicate {
+            LogicalExpression::Binary { left, op, right } => {
+                assert_eq!(*op, BinaryOp::And);
+                assert!(matches!(
+                    left.as_ref(),
+                    LogicalExpression::Binary {
+                        op: BinaryOp::Gt,
+                        ..
+                    }
+                ));
+                assert!(matches!(
+                    right.as_ref(),
+                    LogicalExpression::Unary {
+                        op: UnaryOp::IsNotNull,
+                        ..
+                    }
+                ));
+            }
+            other => panic!("Expected AND predicate, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_translate_or_traversals() {
+        let plan = translate("g.V().or(has('name', 'Alice'), has('name', 'Bob'), __.out('knows'))")
+            .unwrap();
+        let filter = top_filter(&plan);
+
+        // Left-deep: ((a OR b) OR exists)
+        match &filter.predicate {
+            LogicalExpression::Binary { left, op, right } => {
+                assert_eq!(*op, BinaryOp::Or);
+                assert!(matches!(
+                    left.as_ref(),
+                    LogicalExpression::Binary {
+                        op: BinaryOp::Or,
+                        ..
+                    }
+                ));
+                assert!(matches!(
+                    right.as_ref(),
+                    LogicalExpression::ExistsSubquery(_)
+                ));
+            }
+            other => panic!("Expected OR predicate, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_translate_filter_traversal_rejects_map_steps() {
+        assert!(translate("g.V().where(count())").is_err());
+    }
+
+    // === Repeat Tests ===
+
+    fn find_expand(op: &LogicalOperator) -> Option<&ExpandOp> {
+        match op {
+            LogicalOperator::Expand(e) => Some(e),
+            LogicalOperator::Filter(f) => find_expand(&f.input),
+            LogicalOperator::Return(r) => find_expand(&r.input),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_translate_repeat_times() {
+        let plan = translate("g.V().repeat(out()).times(2)").unwrap();
+        let expand = find_expand(&plan.root).expect("Expected Expand");
+
+        assert_eq!(expand.direction, ExpandDirection::Outgoing);
+        assert_eq!(expand.min_hops, 2);
+        assert_eq!(expand.max_hops, Some(2));
+    }
+
+    #[test]
+    fn test_translate_repeat_until() {
+        let plan = translate("g.V().repeat(out('knows')).until(has('name', 'Z'))").unwrap();
+        let filter = top_filter(&plan);
+
+        match &filter.predicate {
+            LogicalExpression::Binary { left, op, .. } => {
+                assert_eq!(*op, BinaryOp::Eq);
+                assert!(matches!(
+                    left.as_ref(),
+                    LogicalExpression::Property { property, .. } if property == "name"
+                ));
+            }
+            other => panic!("Expected Binary predicate, got {other:?}"),
+        }
+
+        let LogicalOperator::Expand(expand) = filter.input.as_ref() else {
+            panic!("Expected Expand below the until() filter");
+        };
+        assert_eq!(expand.edge_type.as_deref(), Some("knows"));
+        assert_eq!(expand.min_hops, 1);
+        assert_eq!(expand.max_hops, None);
+    }
+
+    #[test]
+    fn test_translate_repeat_rejects_multi_step_body() {
+        let result = translate("g.V().repeat(out().out()).times(2)");
+        assert!(matches!(result, Err(Error::Internal(_))));
+    }
+
     // === Predicate Tests ===
 
     #[test]
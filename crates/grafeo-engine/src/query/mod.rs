@@ -15,6 +15,7 @@
 pub mod binder;
 pub mod cache;
 pub mod executor;
+pub mod explain;
 pub mod optimizer;
 pub mod plan;
 pub mod planner;
@@ -43,12 +44,13 @@ pub mod graphql_rdf_translator;
 
 // Core exports
 pub use cache::{CacheKey, CacheStats, CachingQueryProcessor, QueryCache};
-pub use executor::Executor;
+pub use executor::{Executor, ResultStream};
+pub use explain::{explain, explain_analyze};
 pub use optimizer::{CardinalityEstimator, Optimizer};
 pub use plan::{LogicalExpression, LogicalOperator, LogicalPlan};
 pub use planner::{
-    PhysicalPlan, Planner, convert_aggregate_function, convert_binary_op,
-    convert_filter_expression, convert_unary_op,
+    DEFAULT_NESTED_LOOP_THRESHOLD, PhysicalPlan, Planner, convert_aggregate_function,
+    convert_binary_op, convert_filter_expression, convert_unary_op,
 };
 pub use processor::{QueryLanguage, QueryParams, QueryProcessor};
 
@@ -18,6 +18,8 @@ use crate::query::plan::{
     AggregateOp, BinaryOp, DistinctOp, ExpandOp, FilterOp, JoinOp, JoinType, LimitOp,
     LogicalExpression, LogicalOperator, NodeScanOp, ProjectOp, SkipOp, SortOp, UnaryOp,
 };
+use grafeo_common::types::Value;
+use grafeo_core::statistics::{ColumnStatistics, Histogram, LabelStatistics, Statistics};
 use std::collections::HashMap;
 
 /// A bucket in an equi-depth histogram.
@@ -309,6 +311,20 @@ impl TableStats {
         self.columns.insert(name.to_string(), stats);
         self
     }
+
+    /// Builds table statistics from the statistics collected for a label.
+    #[must_use]
+    pub fn from_label_statistics(stats: &LabelStatistics) -> Self {
+        let columns = stats
+            .properties
+            .iter()
+            .map(|(name, column)| (name.clone(), ColumnStats::from_statistics(column)))
+            .collect();
+        Self {
+            row_count: stats.node_count,
+            columns,
+        }
+    }
 }
 
 /// Statistics for a column.
@@ -390,6 +406,49 @@ impl ColumnStats {
             histogram: Some(histogram),
         }
     }
+
+    /// Builds column statistics from collected property statistics.
+    ///
+    /// Only numeric bounds and histograms carry over; other value types
+    /// keep just the distinct and null counts.
+    #[must_use]
+    pub fn from_statistics(stats: &ColumnStatistics) -> Self {
+        Self {
+            distinct_count: stats.distinct_count,
+            null_count: stats.null_count,
+            min_value: stats.min_value.as_ref().and_then(numeric_value),
+            max_value: stats.max_value.as_ref().and_then(numeric_value),
+            histogram: stats.histogram.as_ref().and_then(numeric_histogram),
+        }
+    }
+}
+
+/// Returns the value as `f64` if it is numeric.
+fn numeric_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int64(n) => Some(*n as f64),
+        Value::Float64(f) => Some(*f),
+        _ => None,
+    }
+}
+
+/// Converts a value histogram into an equi-depth histogram over `f64`.
+///
+/// Returns `None` if any bucket has non-numeric bounds.
+fn numeric_histogram(histogram: &Histogram) -> Option<EquiDepthHistogram> {
+    let buckets = histogram
+        .buckets()
+        .iter()
+        .map(|bucket| {
+            Some(HistogramBucket::new(
+                numeric_value(&bucket.lower)?,
+                numeric_value(&bucket.upper)?,
+                bucket.row_count,
+                bucket.distinct_count,
+            ))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(EquiDepthHistogram::new(buckets))
 }
 
 /// Cardinality estimator.
@@ -416,6 +475,16 @@ impl CardinalityEstimator {
         }
     }
 
+    /// Creates a cardinality estimator seeded with collected graph statistics.
+    #[must_use]
+    pub fn from_statistics(stats: &Statistics) -> Self {
+        let mut estimator = Self::new();
+        for (label, label_stats) in &stats.labels {
+            estimator.add_table_stats(label, TableStats::from_label_statistics(label_stats));
+        }
+        estimator
+    }
+
     /// Adds statistics for a table/label.
     pub fn add_table_stats(&mut self, name: &str, stats: TableStats) {
         self.table_stats.insert(name.to_string(), stats);
@@ -460,7 +529,9 @@ impl CardinalityEstimator {
     /// Estimates filter cardinality.
     fn estimate_filter(&self, filter: &FilterOp) -> f64 {
         let input_cardinality = self.estimate(&filter.input);
-        let selectivity = self.estimate_selectivity(&filter.predicate);
+        let mut labels = HashMap::new();
+        collect_variable_labels(&filter.input, &mut labels);
+        let selectivity = self.estimate_selectivity(&filter.predicate, &labels);
         (input_cardinality * selectivity).max(1.0)
     }
 
@@ -592,17 +663,24 @@ impl CardinalityEstimator {
     }
 
     /// Estimates the selectivity of a predicate (0.0 to 1.0).
-    fn estimate_selectivity(&self, expr: &LogicalExpression) -> f64 {
+    ///
+    /// `labels` maps variables to the label they were scanned with, so that
+    /// property predicates can be matched against that label's column stats.
+    fn estimate_selectivity(
+        &self,
+        expr: &LogicalExpression,
+        labels: &HashMap<String, String>,
+    ) -> f64 {
         match expr {
             LogicalExpression::Binary { left, op, right } => {
-                self.estimate_binary_selectivity(left, *op, right)
+                self.estimate_binary_selectivity(left, *op, right, labels)
             }
             LogicalExpression::Unary { op, operand } => {
                 self.estimate_unary_selectivity(*op, operand)
             }
             LogicalExpression::Literal(value) => {
                 // Boolean literal
-                if let grafeo_common::types::Value::Bool(b) = value {
+                if let Value::Bool(b) = value {
                     if *b { 1.0 } else { 0.0 }
                 } else {
                     self.default_selectivity
@@ -618,11 +696,12 @@ impl CardinalityEstimator {
         left: &LogicalExpression,
         op: BinaryOp,
         right: &LogicalExpression,
+        labels: &HashMap<String, String>,
     ) -> f64 {
         match op {
             // Equality - try histogram-based estimation
             BinaryOp::Eq => {
-                if let Some(selectivity) = self.try_equality_selectivity(left, right) {
+                if let Some(selectivity) = self.try_equality_selectivity(left, right, labels) {
                     return selectivity;
                 }
                 0.01
@@ -631,21 +710,26 @@ impl CardinalityEstimator {
             BinaryOp::Ne => 0.99,
             // Range predicates - use histogram if available
             BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
-                if let Some(selectivity) = self.try_range_selectivity(left, op, right) {
+                if let Some(selectivity) = self.try_range_selectivity(left, op, right, labels) {
                     return selectivity;
                 }
                 0.33
             }
             // Logical operators - recursively estimate sub-expressions
             BinaryOp::And => {
-                let left_sel = self.estimate_selectivity(left);
-                let right_sel = self.estimate_selectivity(right);
+                // Two bounds on the same column (`a > 10 AND a < 20`) form a
+                // single range; multiplying them would double-count.
+                if let Some(selectivity) = self.try_between_selectivity(left, right, labels) {
+                    return selectivity;
+                }
+                let left_sel = self.estimate_selectivity(left, labels);
+                let right_sel = self.estimate_selectivity(right, labels);
                 // AND reduces selectivity (multiply assuming independence)
                 left_sel * right_sel
             }
             BinaryOp::Or => {
-                let left_sel = self.estimate_selectivity(left);
-                let right_sel = self.estimate_selectivity(right);
+                let left_sel = self.estimate_selectivity(left, labels);
+                let right_sel = self.estimate_selectivity(right, labels);
                 // OR: P(A ∪ B) = P(A) + P(B) - P(A ∩ B)
                 // Assuming independence: P(A ∩ B) = P(A) * P(B)
                 (left_sel + right_sel - left_sel * right_sel).min(1.0)
@@ -662,29 +746,26 @@ impl CardinalityEstimator {
         }
     }
 
-    /// Tries to estimate equality selectivity using histograms.
+    /// Tries to estimate equality selectivity from column statistics.
+    ///
+    /// Works for any literal or parameter compared with a property; numeric
+    /// literals additionally consult the histogram.
     fn try_equality_selectivity(
         &self,
         left: &LogicalExpression,
         right: &LogicalExpression,
+        labels: &HashMap<String, String>,
     ) -> Option<f64> {
-        // Extract property access and literal value
-        let (label, column, value) = self.extract_column_and_value(left, right)?;
-
-        // Get column stats with histogram
-        let stats = self.get_column_stats(&label, &column)?;
-
-        // Try histogram-based estimation
-        if let Some(ref histogram) = stats.histogram {
-            return Some(histogram.equality_selectivity(value));
-        }
-
-        // Fall back to distinct count estimation
-        if stats.distinct_count > 0 {
-            return Some(1.0 / stats.distinct_count as f64);
-        }
-
-        None
+        let ((label, column), other) = match resolve_property(left, labels) {
+            Some(resolved) => (resolved, right),
+            None => (resolve_property(right, labels)?, left),
+        };
+        let value = match other {
+            LogicalExpression::Literal(value) => numeric_value(value),
+            LogicalExpression::Parameter(_) => None,
+            _ => return None,
+        };
+        self.estimate_equality_with_stats(&label, &column, value)
     }
 
     /// Tries to estimate range selectivity using histograms.
@@ -693,59 +774,88 @@ impl CardinalityEstimator {
         left: &LogicalExpression,
         op: BinaryOp,
         right: &LogicalExpression,
+        labels: &HashMap<String, String>,
     ) -> Option<f64> {
-        // Extract property access and literal value
-        let (label, column, value) = self.extract_column_and_value(left, right)?;
-
-        // Get column stats
-        let stats = self.get_column_stats(&label, &column)?;
-
-        // Determine the range based on operator
-        let (lower, upper) = match op {
-            BinaryOp::Lt => (None, Some(value)),
-            BinaryOp::Le => (None, Some(value + f64::EPSILON)),
-            BinaryOp::Gt => (Some(value + f64::EPSILON), None),
-            BinaryOp::Ge => (Some(value), None),
-            _ => return None,
-        };
+        let (label, column, op, value) = self.extract_column_and_value(left, op, right, labels)?;
+        let (lower, upper) = range_bounds(op, value)?;
+        self.estimate_range_with_stats(&label, &column, lower, upper)
+    }
 
-        // Try histogram-based estimation first
-        if let Some(ref histogram) = stats.histogram {
-            return Some(histogram.range_selectivity(lower, upper));
+    /// Tries to estimate a conjunction of two range predicates on the same
+    /// column as a single bounded range.
+    fn try_between_selectivity(
+        &self,
+        left: &LogicalExpression,
+        right: &LogicalExpression,
+        labels: &HashMap<String, String>,
+    ) -> Option<f64> {
+        let (left_label, left_column, left_lower, left_upper) = self.extract_range(left, labels)?;
+        let (right_label, right_column, right_lower, right_upper) =
+            self.extract_range(right, labels)?;
+        if left_label != right_label || left_column != right_column {
+            return None;
         }
 
-        // Fall back to min/max range estimation
-        if let (Some(min), Some(max)) = (stats.min_value, stats.max_value) {
-            let range = max - min;
-            if range <= 0.0 {
-                return Some(1.0);
+        let lower = match (left_lower, right_lower) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        let upper = match (left_upper, right_upper) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if let (Some(l), Some(u)) = (lower, upper) {
+            if l >= u {
+                return Some(0.0);
             }
-
-            let effective_lower = lower.unwrap_or(min).max(min);
-            let effective_upper = upper.unwrap_or(max).min(max);
-            let overlap = (effective_upper - effective_lower).max(0.0);
-            return Some((overlap / range).min(1.0).max(0.0));
         }
 
-        None
+        self.estimate_range_with_stats(&left_label, &left_column, lower, upper)
+    }
+
+    /// Extracts `(label, column, lower, upper)` from a range comparison.
+    fn extract_range(
+        &self,
+        expr: &LogicalExpression,
+        labels: &HashMap<String, String>,
+    ) -> Option<(String, String, Option<f64>, Option<f64>)> {
+        let LogicalExpression::Binary { left, op, right } = expr else {
+            return None;
+        };
+        let (label, column, op, value) = self.extract_column_and_value(left, *op, right, labels)?;
+        let (lower, upper) = range_bounds(op, value)?;
+        Some((label, column, lower, upper))
     }
 
     /// Extracts column information and literal value from a comparison.
     ///
-    /// Returns (label, column_name, numeric_value) if the expression is
-    /// a comparison between a property access and a numeric literal.
+    /// Returns (label, column_name, operator, numeric_value) if the expression
+    /// compares a property of a label-bound variable with a numeric literal.
+    /// The operator is flipped when the literal is on the left, so that it
+    /// always reads as `property op value`.
     fn extract_column_and_value(
         &self,
         left: &LogicalExpression,
+        op: BinaryOp,
         right: &LogicalExpression,
-    ) -> Option<(String, String, f64)> {
+        labels: &HashMap<String, String>,
+    ) -> Option<(String, String, BinaryOp, f64)> {
         // Try left as property, right as literal
-        if let Some(result) = self.try_extract_property_literal(left, right) {
-            return Some(result);
+        if let Some((label, column, value)) = self.try_extract_property_literal(left, right, labels)
+        {
+            return Some((label, column, op, value));
         }
 
         // Try right as property, left as literal
-        self.try_extract_property_literal(right, left)
+        let (label, column, value) = self.try_extract_property_literal(right, left, labels)?;
+        let flipped = match op {
+            BinaryOp::Lt => BinaryOp::Gt,
+            BinaryOp::Le => BinaryOp::Ge,
+            BinaryOp::Gt => BinaryOp::Lt,
+            BinaryOp::Ge => BinaryOp::Le,
+            other => other,
+        };
+        Some((label, column, flipped, value))
     }
 
     /// Tries to extract property and literal from a specific ordering.
@@ -753,35 +863,14 @@ impl CardinalityEstimator {
         &self,
         property_expr: &LogicalExpression,
         literal_expr: &LogicalExpression,
+        labels: &HashMap<String, String>,
     ) -> Option<(String, String, f64)> {
-        // Extract property access
-        let (variable, property) = match property_expr {
-            LogicalExpression::Property { variable, property } => {
-                (variable.clone(), property.clone())
-            }
-            _ => return None,
-        };
-
-        // Extract numeric literal
-        let value = match literal_expr {
-            LogicalExpression::Literal(grafeo_common::types::Value::Int64(n)) => *n as f64,
-            LogicalExpression::Literal(grafeo_common::types::Value::Float64(f)) => *f,
-            _ => return None,
+        let LogicalExpression::Literal(literal) = literal_expr else {
+            return None;
         };
-
-        // Try to find a label for this variable from table stats
-        // Use the variable name as a heuristic label lookup
-        // In practice, the optimizer would track which labels variables are bound to
-        for label in self.table_stats.keys() {
-            if let Some(stats) = self.table_stats.get(label) {
-                if stats.columns.contains_key(&property) {
-                    return Some((label.clone(), property, value));
-                }
-            }
-        }
-
-        // If no stats found but we have the property, return with variable as label
-        Some((variable, property, value))
+        let value = numeric_value(literal)?;
+        let (label, column) = resolve_property(property_expr, labels)?;
+        Some((label, column, value))
     }
 
     /// Estimates unary expression selectivity.
@@ -800,40 +889,110 @@ impl CardinalityEstimator {
     }
 
     /// Estimates equality selectivity using column statistics.
-    #[allow(dead_code)]
-    fn estimate_equality_with_stats(&self, label: &str, column: &str) -> f64 {
-        if let Some(stats) = self.get_column_stats(label, column) {
-            if stats.distinct_count > 0 {
-                return 1.0 / stats.distinct_count as f64;
+    ///
+    /// `value` is the compared value when it is a known number.
+    fn estimate_equality_with_stats(
+        &self,
+        label: &str,
+        column: &str,
+        value: Option<f64>,
+    ) -> Option<f64> {
+        let stats = self.get_column_stats(label, column)?;
+
+        // Try histogram-based estimation
+        if let (Some(histogram), Some(value)) = (&stats.histogram, value) {
+            if histogram.total_rows() > 0 {
+                return Some(histogram.equality_selectivity(value));
             }
         }
-        0.01 // Default for equality
+
+        // Fall back to distinct count estimation
+        if stats.distinct_count > 0 {
+            return Some(1.0 / stats.distinct_count as f64);
+        }
+
+        None
     }
 
     /// Estimates range selectivity using column statistics.
-    #[allow(dead_code)]
+    ///
+    /// Prefers the histogram and falls back to linear interpolation between
+    /// the column's min and max.
     fn estimate_range_with_stats(
         &self,
         label: &str,
         column: &str,
         lower: Option<f64>,
         upper: Option<f64>,
-    ) -> f64 {
-        if let Some(stats) = self.get_column_stats(label, column) {
-            if let (Some(min), Some(max)) = (stats.min_value, stats.max_value) {
-                let range = max - min;
-                if range <= 0.0 {
-                    return 1.0;
-                }
+    ) -> Option<f64> {
+        let stats = self.get_column_stats(label, column)?;
+
+        if let Some(ref histogram) = stats.histogram {
+            if histogram.total_rows() > 0 {
+                return Some(histogram.range_selectivity(lower, upper));
+            }
+        }
+
+        let (min, max) = (stats.min_value?, stats.max_value?);
+        let range = max - min;
+        if range <= 0.0 {
+            let inside = lower.is_none_or(|l| l <= min) && upper.is_none_or(|u| u > max);
+            return Some(if inside { 1.0 } else { 0.0 });
+        }
 
-                let effective_lower = lower.unwrap_or(min).max(min);
-                let effective_upper = upper.unwrap_or(max).min(max);
+        let effective_lower = lower.unwrap_or(min).max(min);
+        let effective_upper = upper.unwrap_or(max).min(max);
+        let overlap = (effective_upper - effective_lower).max(0.0);
+        Some((overlap / range).clamp(0.0, 1.0))
+    }
+}
+
+/// Converts a range comparison into `(lower, upper)` bounds.
+fn range_bounds(op: BinaryOp, value: f64) -> Option<(Option<f64>, Option<f64>)> {
+    match op {
+        BinaryOp::Lt => Some((None, Some(value))),
+        BinaryOp::Le => Some((None, Some(value + f64::EPSILON))),
+        BinaryOp::Gt => Some((Some(value + f64::EPSILON), None)),
+        BinaryOp::Ge => Some((Some(value), None)),
+        _ => None,
+    }
+}
+
+/// Resolves a property access to `(label, column)` using the scanned labels.
+fn resolve_property(
+    expr: &LogicalExpression,
+    labels: &HashMap<String, String>,
+) -> Option<(String, String)> {
+    let LogicalExpression::Property { variable, property } = expr else {
+        return None;
+    };
+    let label = labels.get(variable)?;
+    Some((label.clone(), property.clone()))
+}
 
-                let overlap = (effective_upper - effective_lower).max(0.0);
-                return (overlap / range).min(1.0).max(0.0);
+/// Collects the label each variable was scanned with in a plan subtree.
+fn collect_variable_labels(op: &LogicalOperator, labels: &mut HashMap<String, String>) {
+    match op {
+        LogicalOperator::NodeScan(scan) => {
+            if let Some(label) = &scan.label {
+                labels.insert(scan.variable.clone(), label.clone());
             }
+            if let Some(input) = &scan.input {
+                collect_variable_labels(input, labels);
+            }
+        }
+        LogicalOperator::Expand(expand) => collect_variable_labels(&expand.input, labels),
+        LogicalOperator::Filter(filter) => collect_variable_labels(&filter.input, labels),
+        LogicalOperator::Project(project) => collect_variable_labels(&project.input, labels),
+        LogicalOperator::Join(join) => {
+            collect_variable_labels(&join.left, labels);
+            collect_variable_labels(&join.right, labels);
         }
-        0.33 // Default for range
+        LogicalOperator::Limit(limit) => collect_variable_labels(&limit.input, labels),
+        LogicalOperator::Skip(skip) => collect_variable_labels(&skip.input, labels),
+        LogicalOperator::Sort(sort) => collect_variable_labels(&sort.input, labels),
+        LogicalOperator::Distinct(distinct) => collect_variable_labels(&distinct.input, labels),
+        _ => {}
     }
 }
 
@@ -850,7 +1009,6 @@ mod tests {
         DistinctOp, ExpandDirection, ExpandOp, FilterOp, JoinCondition, NodeScanOp, ProjectOp,
         Projection, ReturnItem, ReturnOp, SkipOp, SortKey, SortOp, SortOrder,
     };
-    use grafeo_common::types::Value;
 
     #[test]
     fn test_node_scan_with_stats() {
@@ -1853,4 +2011,285 @@ mod tests {
         // Max is the upper bound of the last bucket
         assert!(histogram.max_value().is_some());
     }
+
+    fn range_filter(
+        variable: &str,
+        label: &str,
+        property: &str,
+        predicate: impl Fn(LogicalExpression) -> LogicalExpression,
+    ) -> LogicalOperator {
+        LogicalOperator::Filter(FilterOp {
+            predicate: predicate(LogicalExpression::Property {
+                variable: variable.to_string(),
+                property: property.to_string(),
+            }),
+            input: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                variable: variable.to_string(),
+                label: Some(label.to_string()),
+                input: None,
+            })),
+        })
+    }
+
+    fn compare(
+        left: LogicalExpression,
+        op: BinaryOp,
+        right: LogicalExpression,
+    ) -> LogicalExpression {
+        LogicalExpression::Binary {
+            left: Box::new(left),
+            op,
+            right: Box::new(right),
+        }
+    }
+
+    fn int(n: i64) -> LogicalExpression {
+        LogicalExpression::Literal(Value::Int64(n))
+    }
+
+    #[test]
+    fn test_tight_range_much_lower_than_loose_range() {
+        let mut estimator = CardinalityEstimator::new();
+        let values: Vec<f64> = (0..10_000).map(|i| i as f64).collect();
+        estimator.add_table_stats(
+            "Order",
+            TableStats::new(10_000).with_column("amount", ColumnStats::from_values(values, 20)),
+        );
+
+        let tight = estimator.estimate(&range_filter("o", "Order", "amount", |p| {
+            compare(p, BinaryOp::Gt, int(9_900))
+        }));
+        let loose = estimator.estimate(&range_filter("o", "Order", "amount", |p| {
+            compare(p, BinaryOp::Gt, int(100))
+        }));
+
+        assert!(tight < 200.0, "tight estimate was {tight}");
+        assert!(loose > 9_000.0, "loose estimate was {loose}");
+        assert!(loose / tight > 40.0);
+    }
+
+    #[test]
+    fn test_range_uses_min_max_without_histogram() {
+        let mut estimator = CardinalityEstimator::new();
+        estimator.add_table_stats(
+            "Order",
+            TableStats::new(1000)
+                .with_column("amount", ColumnStats::new(1000).with_range(0.0, 1000.0)),
+        );
+
+        let cardinality = estimator.estimate(&range_filter("o", "Order", "amount", |p| {
+            compare(p, BinaryOp::Lt, int(50))
+        }));
+
+        // 50 / 1000 of the range = 5%
+        assert!((cardinality - 50.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_range_literal_on_left_is_flipped() {
+        let mut estimator = CardinalityEstimator::new();
+        estimator.add_table_stats(
+            "Order",
+            TableStats::new(1000)
+                .with_column("amount", ColumnStats::new(1000).with_range(0.0, 1000.0)),
+        );
+
+        // 900 < o.amount is o.amount > 900
+        let cardinality = estimator.estimate(&range_filter("o", "Order", "amount", |p| {
+            compare(int(900), BinaryOp::Lt, p)
+        }));
+
+        assert!(cardinality < 150.0, "estimate was {cardinality}");
+    }
+
+    #[test]
+    fn test_conjunctive_range_on_same_column() {
+        let mut estimator = CardinalityEstimator::new();
+        estimator.add_table_stats(
+            "Order",
+            TableStats::new(1000)
+                .with_column("amount", ColumnStats::new(1000).with_range(0.0, 1000.0)),
+        );
+
+        // 400 <= amount < 600 is 20% of the range, not 60% * 40% = 24%
+        let cardinality = estimator.estimate(&range_filter("o", "Order", "amount", |p| {
+            compare(
+                compare(p.clone(), BinaryOp::Ge, int(400)),
+                BinaryOp::And,
+                compare(p, BinaryOp::Lt, int(600)),
+            )
+        }));
+        assert!(
+            (cardinality - 200.0).abs() < 1.0,
+            "estimate was {cardinality}"
+        );
+
+        // Disjoint bounds match nothing (clamped to one row)
+        let empty = estimator.estimate(&range_filter("o", "Order", "amount", |p| {
+            compare(
+                compare(p.clone(), BinaryOp::Gt, int(600)),
+                BinaryOp::And,
+                compare(p, BinaryOp::Lt, int(400)),
+            )
+        }));
+        assert!((empty - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_range_stats_are_looked_up_on_the_scanned_label() {
+        let mut estimator = CardinalityEstimator::new();
+        estimator.add_table_stats(
+            "Person",
+            TableStats::new(1000).with_column("age", ColumnStats::new(100).with_range(0.0, 100.0)),
+        );
+        // A different label with a much wider "age" column
+        estimator.add_table_stats(
+            "Building",
+            TableStats::new(1000).with_column("age", ColumnStats::new(500).with_range(0.0, 1000.0)),
+        );
+
+        let person = estimator.estimate(&range_filter("n", "Person", "age", |p| {
+            compare(p, BinaryOp::Gt, int(50))
+        }));
+        let building = estimator.estimate(&range_filter("n", "Building", "age", |p| {
+            compare(p, BinaryOp::Gt, int(50))
+        }));
+
+        assert!((person - 500.0).abs() < 1.0, "estimate was {person}");
+        assert!((building - 950.0).abs() < 1.0, "estimate was {building}");
+    }
+
+    #[test]
+    fn test_range_without_stats_falls_back_to_default() {
+        let mut estimator = CardinalityEstimator::new();
+        estimator.add_table_stats(
+            "Person",
+            TableStats::new(1000).with_column("age", ColumnStats::new(100).with_range(0.0, 100.0)),
+        );
+
+        // Property without column stats
+        let unknown_column = estimator.estimate(&range_filter("n", "Person", "height", |p| {
+            compare(p, BinaryOp::Gt, int(50))
+        }));
+        assert!((unknown_column - 330.0).abs() < 1.0);
+
+        // Variable not bound by any scan below the filter
+        let unbound = estimator.estimate(&range_filter("n", "Person", "age", |_| {
+            compare(
+                LogicalExpression::Property {
+                    variable: "m".to_string(),
+                    property: "age".to_string(),
+                },
+                BinaryOp::Gt,
+                int(99),
+            )
+        }));
+        assert!((unbound - 330.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_estimator_from_collected_statistics() {
+        let values: Vec<Value> = (0..1000).map(Value::Int64).collect();
+        let column = ColumnStatistics::new(1000, 1000, 0)
+            .with_min_max(Value::Int64(0), Value::Int64(999))
+            .with_histogram(Histogram::build(&values, 10));
+        let mut stats = Statistics::new();
+        stats.update_label(
+            "Order",
+            LabelStatistics::new(1000).with_property("amount", column),
+        );
+
+        let estimator = CardinalityEstimator::from_statistics(&stats);
+
+        let scan = estimator.estimate(&LogicalOperator::NodeScan(NodeScanOp {
+            variable: "o".to_string(),
+            label: Some("Order".to_string()),
+            input: None,
+        }));
+        assert!((scan - 1000.0).abs() < f64::EPSILON);
+
+        let tight = estimator.estimate(&range_filter("o", "Order", "amount", |p| {
+            compare(p, BinaryOp::Ge, int(990))
+        }));
+        assert!(tight < 30.0, "estimate was {tight}");
+    }
+
+    #[test]
+    fn test_column_stats_from_non_numeric_statistics() {
+        let column =
+            ColumnStatistics::new(3, 10, 2).with_min_max(Value::from("a"), Value::from("c"));
+        let stats = ColumnStats::from_statistics(&column);
+
+        assert_eq!(stats.distinct_count, 3);
+        assert_eq!(stats.null_count, 2);
+        assert!(stats.min_value.is_none());
+        assert!(stats.histogram.is_none());
+    }
+
+    #[test]
+    fn test_equality_uses_distinct_count_of_scanned_label() {
+        let mut estimator = CardinalityEstimator::new();
+        estimator.add_table_stats(
+            "User",
+            TableStats::new(100_000)
+                .with_column("email", ColumnStats::new(100_000))
+                .with_column("status", ColumnStats::new(3)),
+        );
+
+        let key = estimator.estimate(&range_filter("u", "User", "email", |p| {
+            compare(
+                p,
+                BinaryOp::Eq,
+                LogicalExpression::Literal(Value::from("alix@example.com")),
+            )
+        }));
+        let enumeration = estimator.estimate(&range_filter("u", "User", "status", |p| {
+            compare(
+                LogicalExpression::Literal(Value::from("active")),
+                BinaryOp::Eq,
+                p,
+            )
+        }));
+
+        assert!(key < 2.0, "estimate was {key}");
+        assert!(
+            (enumeration - 100_000.0 / 3.0).abs() < 1.0,
+            "estimate was {enumeration}"
+        );
+    }
+
+    #[test]
+    fn test_equality_on_single_valued_column_keeps_all_rows() {
+        let mut estimator = CardinalityEstimator::new();
+        estimator.add_table_stats(
+            "User",
+            TableStats::new(500).with_column("tenant", ColumnStats::new(1)),
+        );
+
+        let cardinality = estimator.estimate(&range_filter("u", "User", "tenant", |p| {
+            compare(
+                p,
+                BinaryOp::Eq,
+                LogicalExpression::Parameter("tenant".to_string()),
+            )
+        }));
+
+        assert!((cardinality - 500.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_equality_without_column_stats_uses_default() {
+        let mut estimator = CardinalityEstimator::new();
+        estimator.add_table_stats("User", TableStats::new(1000));
+
+        let cardinality = estimator.estimate(&range_filter("u", "User", "name", |p| {
+            compare(
+                p,
+                BinaryOp::Eq,
+                LogicalExpression::Literal(Value::from("Gus")),
+            )
+        }));
+
+        assert!((cardinality - 10.0).abs() < f64::EPSILON);
+    }
 }
@@ -216,6 +216,26 @@ impl CostModel {
         }
     }
 
+    /// Estimates a hash join that builds a table from `build_rows` and probes
+    /// it with `probe_rows`.
+    ///
+    /// The build side is held in memory, so building on the smaller input is
+    /// cheaper.
+    #[must_use]
+    pub fn hash_join_cost(&self, build_rows: f64, probe_rows: f64) -> Cost {
+        let build_cost = build_rows * (self.hash_lookup_cost + self.cpu_tuple_cost);
+        let probe_cost = probe_rows * self.hash_lookup_cost;
+        Cost::cpu(build_cost + probe_cost).with_memory(build_rows * self.avg_tuple_size)
+    }
+
+    /// Estimates a nested-loop join, which compares every pair of rows and
+    /// materializes the right side.
+    #[must_use]
+    pub fn nested_loop_join_cost(&self, left_rows: f64, right_rows: f64) -> Cost {
+        Cost::cpu(left_rows * right_rows * self.cpu_tuple_cost)
+            .with_memory(right_rows * self.avg_tuple_size)
+    }
+
     /// Estimates the cost of an aggregation.
     fn aggregate_cost(&self, agg: &AggregateOp, cardinality: f64) -> Cost {
         // Hash aggregation cost
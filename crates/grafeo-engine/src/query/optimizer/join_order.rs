@@ -124,6 +124,9 @@ impl JoinGraph {
     }
 
     /// Gets the join conditions between two node sets.
+    ///
+    /// Conditions are oriented so that `left` refers to the `left` node set
+    /// and `right` to the `right` node set.
     pub fn get_conditions(&self, left: &BitSet, right: &BitSet) -> Vec<JoinCondition> {
         let mut conditions = Vec::new();
         for edge in &self.edges {
@@ -133,13 +136,46 @@ impl JoinGraph {
             let to_in_right = right.contains(edge.to);
 
             // Edge crosses between left and right
-            if (from_in_left && to_in_right) || (from_in_right && to_in_left) {
-                conditions.extend(edge.conditions.clone());
+            if from_in_left && to_in_right {
+                conditions.extend(edge.conditions.iter().cloned());
+            } else if from_in_right && to_in_left {
+                conditions.extend(edge.conditions.iter().map(|cond| JoinCondition {
+                    left: cond.right.clone(),
+                    right: cond.left.clone(),
+                }));
             }
         }
         conditions
     }
 
+    /// Returns the connected components of the graph, as node sets.
+    pub fn connected_components(&self) -> Vec<BitSet> {
+        let mut components = Vec::new();
+        let mut seen = BitSet::empty();
+
+        for start in 0..self.nodes.len() {
+            if seen.contains(start) {
+                continue;
+            }
+
+            let mut component = BitSet::singleton(start);
+            let mut queue = vec![start];
+            while let Some(node) = queue.pop() {
+                for neighbor in self.neighbors(node) {
+                    if !component.contains(neighbor) {
+                        component.insert(neighbor);
+                        queue.push(neighbor);
+                    }
+                }
+            }
+
+            seen = seen.union(component);
+            components.push(component);
+        }
+
+        components
+    }
+
     /// Checks if two node sets are connected by at least one edge.
     pub fn are_connected(&self, left: &BitSet, right: &BitSet) -> bool {
         for edge in &self.edges {
@@ -354,7 +390,34 @@ impl<'a> DPccp<'a> {
         self.enumerate_ccp(full_set);
 
         // Return the best plan for the full set
-        self.memo.get(&full_set).cloned()
+        if let Some(plan) = self.memo.get(&full_set) {
+            return Some(plan.clone());
+        }
+
+        // The graph is disconnected: cross products are unavoidable
+        self.optimize_disconnected()
+    }
+
+    /// Plans a disconnected join graph.
+    ///
+    /// Each connected component is optimized on its own, and the components
+    /// are then combined with cross joins, smallest first. This keeps cross
+    /// products at the top of the tree where they can't inflate the inputs
+    /// of the conditioned joins.
+    fn optimize_disconnected(&mut self) -> Option<JoinPlan> {
+        let mut component_plans = Vec::new();
+        for component in self.graph.connected_components() {
+            if !self.memo.contains_key(&component) {
+                self.enumerate_ccp(component);
+            }
+            component_plans.push(self.memo.get(&component)?.clone());
+        }
+
+        component_plans.sort_by(|a, b| a.cardinality.total_cmp(&b.cardinality));
+
+        component_plans
+            .into_iter()
+            .reduce(|left, right| self.build_join_plan(left, right, Vec::new(), JoinType::Cross))
     }
 
     /// Enumerates connected complement pairs using DPccp algorithm.
@@ -391,7 +454,8 @@ impl<'a> DPccp<'a> {
             // Try to build a plan for s by joining s1 and s2
             if let (Some(plan1), Some(plan2)) = (self.memo.get(&s1), self.memo.get(&s2)) {
                 let conditions = self.graph.get_conditions(&s1, &s2);
-                let new_plan = self.build_join_plan(plan1.clone(), plan2.clone(), conditions);
+                let new_plan =
+                    self.build_join_plan(plan1.clone(), plan2.clone(), conditions, JoinType::Inner);
 
                 // Update memo if this is a better plan
                 let should_update = self
@@ -440,6 +504,7 @@ impl<'a> DPccp<'a> {
         left: JoinPlan,
         right: JoinPlan,
         conditions: Vec<JoinCondition>,
+        join_type: JoinType,
     ) -> JoinPlan {
         let nodes = left.nodes.union(right.nodes);
 
@@ -447,7 +512,7 @@ impl<'a> DPccp<'a> {
         let join_op = LogicalOperator::Join(JoinOp {
             left: Box::new(left.operator),
             right: Box::new(right.operator),
-            join_type: JoinType::Inner,
+            join_type,
             conditions,
         });
 
@@ -489,6 +554,27 @@ impl JoinGraphBuilder {
         id
     }
 
+    /// Registers an additional variable produced by an existing relation.
+    ///
+    /// Relations such as expands bind more than one variable; join conditions
+    /// on any of them should attach to the same node.
+    pub fn add_variable(&mut self, variable: &str, node: usize) {
+        self.variable_to_node.insert(variable.to_string(), node);
+    }
+
+    /// Returns the node a variable belongs to, if it was registered.
+    pub fn node_for_variable(&self, variable: &str) -> Option<usize> {
+        self.variable_to_node.get(variable).copied()
+    }
+
+    /// Connects two relations without an explicit join condition.
+    ///
+    /// Used for relations that share a variable, so the optimizer keeps them
+    /// adjacent instead of treating them as a cross product.
+    pub fn connect(&mut self, left: usize, right: usize) {
+        self.graph.add_edge(left, right, Vec::new());
+    }
+
     /// Adds a join condition between two variables.
     pub fn add_join_condition(
         &mut self,
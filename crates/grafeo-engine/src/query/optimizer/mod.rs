@@ -19,8 +19,12 @@ pub use cardinality::{CardinalityEstimator, ColumnStats, TableStats};
 pub use cost::{Cost, CostModel};
 pub use join_order::{BitSet, DPccp, JoinGraph, JoinGraphBuilder, JoinPlan};
 
-use crate::query::plan::{FilterOp, LogicalExpression, LogicalOperator, LogicalPlan};
+use crate::query::plan::{
+    BinaryOp, FilterOp, JoinType, LogicalExpression, LogicalOperator, LogicalPlan, UnaryOp,
+};
+use grafeo_common::types::Value;
 use grafeo_common::utils::error::Result;
+use std::cmp::Ordering;
 use std::collections::HashSet;
 
 /// Information about a join condition for join reordering.
@@ -45,7 +49,10 @@ enum RequiredColumn {
 ///
 /// Create with [`new()`](Self::new), then call [`optimize()`](Self::optimize).
 /// Use the builder methods to enable/disable specific optimizations.
+#[allow(clippy::struct_excessive_bools)]
 pub struct Optimizer {
+    /// Whether to enable predicate simplification (constant folding).
+    enable_predicate_simplification: bool,
     /// Whether to enable filter pushdown.
     enable_filter_pushdown: bool,
     /// Whether to enable join reordering.
@@ -63,6 +70,7 @@ impl Optimizer {
     #[must_use]
     pub fn new() -> Self {
         Self {
+            enable_predicate_simplification: true,
             enable_filter_pushdown: true,
             enable_join_reorder: true,
             enable_projection_pushdown: true,
@@ -71,6 +79,12 @@ impl Optimizer {
         }
     }
 
+    /// Enables or disables predicate simplification (constant folding).
+    pub fn with_predicate_simplification(mut self, enabled: bool) -> Self {
+        self.enable_predicate_simplification = enabled;
+        self
+    }
+
     /// Enables or disables filter pushdown.
     pub fn with_filter_pushdown(mut self, enabled: bool) -> Self {
         self.enable_filter_pushdown = enabled;
@@ -131,6 +145,10 @@ impl Optimizer {
         let mut root = plan.root;
 
         // Apply optimization rules
+        if self.enable_predicate_simplification {
+            root = self.simplify_expressions(root);
+        }
+
         if self.enable_filter_pushdown {
             root = self.push_filters_down(root);
         }
@@ -146,6 +164,396 @@ impl Optimizer {
         Ok(LogicalPlan::new(root))
     }
 
+    /// Folds constant expressions throughout the operator tree.
+    ///
+    /// Filters whose predicate folds to `true` are removed. Filters that fold
+    /// to `false` (or `NULL`) can never produce a row, so the subtree is
+    /// replaced with [`LogicalOperator::Empty`] and parents that produce no
+    /// rows from no input collapse with it, up to the enclosing `Return`.
+    fn simplify_expressions(&self, op: LogicalOperator) -> LogicalOperator {
+        self.simplify_operator(op, false)
+    }
+
+    /// Simplifies one operator and its inputs.
+    ///
+    /// `can_prune` is true when the parent treats an `Empty` input as "no
+    /// rows". Elsewhere `Empty` means a single row (or isn't plannable), so a
+    /// false filter is kept as-is.
+    fn simplify_operator(&self, op: LogicalOperator, can_prune: bool) -> LogicalOperator {
+        /// Simplifies `input`, reporting whether it was pruned to `Empty`.
+        fn input_of(
+            optimizer: &Optimizer,
+            input: LogicalOperator,
+            can_prune: bool,
+        ) -> (LogicalOperator, bool) {
+            let was_empty = matches!(input, LogicalOperator::Empty);
+            let input = optimizer.simplify_operator(input, can_prune);
+            let pruned = !was_empty && matches!(input, LogicalOperator::Empty);
+            (input, pruned)
+        }
+
+        match op {
+            LogicalOperator::Filter(filter) => {
+                let predicate = Self::simplify_expression(filter.predicate);
+                let (input, pruned) = input_of(self, *filter.input, can_prune);
+                match predicate {
+                    _ if pruned => LogicalOperator::Empty,
+                    LogicalExpression::Literal(Value::Bool(true)) => input,
+                    LogicalExpression::Literal(Value::Bool(false) | Value::Null) if can_prune => {
+                        LogicalOperator::Empty
+                    }
+                    predicate => LogicalOperator::Filter(FilterOp {
+                        predicate,
+                        input: Box::new(input),
+                    }),
+                }
+            }
+            LogicalOperator::Return(mut ret) => {
+                for item in &mut ret.items {
+                    Self::simplify_in_place(&mut item.expression);
+                }
+                ret.input = Box::new(self.simplify_operator(*ret.input, true));
+                LogicalOperator::Return(ret)
+            }
+            LogicalOperator::Project(mut proj) => {
+                for projection in &mut proj.projections {
+                    Self::simplify_in_place(&mut projection.expression);
+                }
+                let (input, pruned) = input_of(self, *proj.input, can_prune);
+                if pruned {
+                    return LogicalOperator::Empty;
+                }
+                proj.input = Box::new(input);
+                LogicalOperator::Project(proj)
+            }
+            LogicalOperator::Sort(mut sort) => {
+                for key in &mut sort.keys {
+                    Self::simplify_in_place(&mut key.expression);
+                }
+                let (input, pruned) = input_of(self, *sort.input, can_prune);
+                if pruned {
+                    return LogicalOperator::Empty;
+                }
+                sort.input = Box::new(input);
+                LogicalOperator::Sort(sort)
+            }
+            LogicalOperator::Aggregate(mut agg) => {
+                for expr in &mut agg.group_by {
+                    Self::simplify_in_place(expr);
+                }
+                for agg_expr in &mut agg.aggregates {
+                    if let Some(expr) = &mut agg_expr.expression {
+                        Self::simplify_in_place(expr);
+                    }
+                }
+                if let Some(having) = &mut agg.having {
+                    Self::simplify_in_place(having);
+                }
+                // A global aggregate still produces a row for empty input
+                let grouped = !agg.group_by.is_empty();
+                let (input, pruned) = input_of(self, *agg.input, can_prune && grouped);
+                if pruned {
+                    return LogicalOperator::Empty;
+                }
+                agg.input = Box::new(input);
+                LogicalOperator::Aggregate(agg)
+            }
+            LogicalOperator::Limit(mut limit) => {
+                let (input, pruned) = input_of(self, *limit.input, can_prune);
+                if pruned {
+                    return LogicalOperator::Empty;
+                }
+                limit.input = Box::new(input);
+                LogicalOperator::Limit(limit)
+            }
+            LogicalOperator::Skip(mut skip) => {
+                let (input, pruned) = input_of(self, *skip.input, can_prune);
+                if pruned {
+                    return LogicalOperator::Empty;
+                }
+                skip.input = Box::new(input);
+                LogicalOperator::Skip(skip)
+            }
+            LogicalOperator::Distinct(mut distinct) => {
+                let (input, pruned) = input_of(self, *distinct.input, can_prune);
+                if pruned {
+                    return LogicalOperator::Empty;
+                }
+                distinct.input = Box::new(input);
+                LogicalOperator::Distinct(distinct)
+            }
+            LogicalOperator::Expand(mut expand) => {
+                let (input, pruned) = input_of(self, *expand.input, can_prune);
+                if pruned {
+                    return LogicalOperator::Empty;
+                }
+                expand.input = Box::new(input);
+                LogicalOperator::Expand(expand)
+            }
+            LogicalOperator::Join(mut join) => {
+                for cond in &mut join.conditions {
+                    Self::simplify_in_place(&mut cond.left);
+                    Self::simplify_in_place(&mut cond.right);
+                }
+                // Outer joins keep rows from a side even if the other is empty
+                let left_prunes = can_prune
+                    && matches!(
+                        join.join_type,
+                        JoinType::Inner
+                            | JoinType::Cross
+                            | JoinType::Semi
+                            | JoinType::Anti
+                            | JoinType::Left
+                    );
+                let right_prunes = can_prune
+                    && matches!(
+                        join.join_type,
+                        JoinType::Inner | JoinType::Cross | JoinType::Semi | JoinType::Right
+                    );
+                let (left, left_pruned) = input_of(self, *join.left, left_prunes);
+                let (right, right_pruned) = input_of(self, *join.right, right_prunes);
+                if left_pruned || right_pruned {
+                    return LogicalOperator::Empty;
+                }
+                join.left = Box::new(left);
+                join.right = Box::new(right);
+                LogicalOperator::Join(join)
+            }
+            LogicalOperator::LeftJoin(mut join) => {
+                if let Some(condition) = &mut join.condition {
+                    Self::simplify_in_place(condition);
+                }
+                let (left, left_pruned) = input_of(self, *join.left, can_prune);
+                if left_pruned {
+                    return LogicalOperator::Empty;
+                }
+                join.left = Box::new(left);
+                join.right = Box::new(self.simplify_operator(*join.right, false));
+                LogicalOperator::LeftJoin(join)
+            }
+            LogicalOperator::AntiJoin(mut join) => {
+                let (left, left_pruned) = input_of(self, *join.left, can_prune);
+                if left_pruned {
+                    return LogicalOperator::Empty;
+                }
+                join.left = Box::new(left);
+                join.right = Box::new(self.simplify_operator(*join.right, false));
+                LogicalOperator::AntiJoin(join)
+            }
+            LogicalOperator::Bind(mut bind) => {
+                Self::simplify_in_place(&mut bind.expression);
+                let (input, pruned) = input_of(self, *bind.input, can_prune);
+                if pruned {
+                    return LogicalOperator::Empty;
+                }
+                bind.input = Box::new(input);
+                LogicalOperator::Bind(bind)
+            }
+            LogicalOperator::Unwind(mut unwind) => {
+                Self::simplify_in_place(&mut unwind.expression);
+                let (input, pruned) = input_of(self, *unwind.input, can_prune);
+                if pruned {
+                    return LogicalOperator::Empty;
+                }
+                unwind.input = Box::new(input);
+                LogicalOperator::Unwind(unwind)
+            }
+            other => other,
+        }
+    }
+
+    /// Replaces an expression with its simplified form.
+    fn simplify_in_place(expr: &mut LogicalExpression) {
+        let owned = std::mem::replace(expr, LogicalExpression::Literal(Value::Null));
+        *expr = Self::simplify_expression(owned);
+    }
+
+    /// Folds constant subexpressions of an expression, bottom-up.
+    ///
+    /// Folds that could fail at runtime (integer overflow, division by zero)
+    /// or whose result depends on NaN handling are left unevaluated.
+    fn simplify_expression(expr: LogicalExpression) -> LogicalExpression {
+        match expr {
+            LogicalExpression::Binary { left, op, right } => {
+                let left = Self::simplify_expression(*left);
+                let right = Self::simplify_expression(*right);
+                Self::fold_binary(left, op, right)
+            }
+            LogicalExpression::Unary { op, operand } => {
+                let operand = Self::simplify_expression(*operand);
+                Self::fold_unary(op, operand)
+            }
+            LogicalExpression::FunctionCall {
+                name,
+                args,
+                distinct,
+            } => LogicalExpression::FunctionCall {
+                name,
+                args: args.into_iter().map(Self::simplify_expression).collect(),
+                distinct,
+            },
+            LogicalExpression::List(items) => {
+                LogicalExpression::List(items.into_iter().map(Self::simplify_expression).collect())
+            }
+            LogicalExpression::Map(pairs) => LogicalExpression::Map(
+                pairs
+                    .into_iter()
+                    .map(|(key, value)| (key, Self::simplify_expression(value)))
+                    .collect(),
+            ),
+            LogicalExpression::Case {
+                operand,
+                when_clauses,
+                else_clause,
+            } => LogicalExpression::Case {
+                operand: operand.map(|o| Box::new(Self::simplify_expression(*o))),
+                when_clauses: when_clauses
+                    .into_iter()
+                    .map(|(cond, result)| {
+                        (
+                            Self::simplify_expression(cond),
+                            Self::simplify_expression(result),
+                        )
+                    })
+                    .collect(),
+                else_clause: else_clause.map(|e| Box::new(Self::simplify_expression(*e))),
+            },
+            other => other,
+        }
+    }
+
+    /// Folds a binary operation whose operands are already simplified.
+    fn fold_binary(
+        left: LogicalExpression,
+        op: BinaryOp,
+        right: LogicalExpression,
+    ) -> LogicalExpression {
+        use LogicalExpression::Literal;
+
+        let folded = match (&left, op, &right) {
+            // Boolean identities, valid for any (possibly non-constant) other side
+            (Literal(Value::Bool(true)), BinaryOp::And, _) => Some(right.clone()),
+            (_, BinaryOp::And, Literal(Value::Bool(true))) => Some(left.clone()),
+            (Literal(Value::Bool(false)), BinaryOp::And, _)
+            | (_, BinaryOp::And, Literal(Value::Bool(false))) => Some(Literal(Value::Bool(false))),
+            (Literal(Value::Bool(true)), BinaryOp::Or, _)
+            | (_, BinaryOp::Or, Literal(Value::Bool(true))) => Some(Literal(Value::Bool(true))),
+            (Literal(Value::Bool(false)), BinaryOp::Or, _) => Some(right.clone()),
+            (_, BinaryOp::Or, Literal(Value::Bool(false))) => Some(left.clone()),
+            (Literal(l), _, Literal(r)) => Self::fold_constant_binary(l, op, r).map(Literal),
+            _ => None,
+        };
+
+        folded.unwrap_or_else(|| LogicalExpression::Binary {
+            left: Box::new(left),
+            op,
+            right: Box::new(right),
+        })
+    }
+
+    /// Evaluates a binary operation on two literals, if it is safe to do so.
+    fn fold_constant_binary(left: &Value, op: BinaryOp, right: &Value) -> Option<Value> {
+        match op {
+            BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => {
+                match (left, right) {
+                    (Value::Int64(l), Value::Int64(r)) => {
+                        let result = match op {
+                            BinaryOp::Add => l.checked_add(*r),
+                            BinaryOp::Sub => l.checked_sub(*r),
+                            BinaryOp::Mul => l.checked_mul(*r),
+                            BinaryOp::Div => l.checked_div(*r),
+                            _ => l.checked_rem(*r),
+                        };
+                        result.map(Value::Int64)
+                    }
+                    (Value::Float64(l), Value::Float64(r)) => {
+                        let result = match op {
+                            BinaryOp::Add => l + r,
+                            BinaryOp::Sub => l - r,
+                            BinaryOp::Mul => l * r,
+                            BinaryOp::Div => l / r,
+                            _ => l % r,
+                        };
+                        result.is_finite().then_some(Value::Float64(result))
+                    }
+                    _ => None,
+                }
+            }
+            BinaryOp::Eq
+            | BinaryOp::Ne
+            | BinaryOp::Lt
+            | BinaryOp::Le
+            | BinaryOp::Gt
+            | BinaryOp::Ge => {
+                let ordering = Self::compare_literals(left, right)?;
+                let result = match op {
+                    BinaryOp::Eq => ordering == Ordering::Equal,
+                    BinaryOp::Ne => ordering != Ordering::Equal,
+                    BinaryOp::Lt => ordering == Ordering::Less,
+                    BinaryOp::Le => ordering != Ordering::Greater,
+                    BinaryOp::Gt => ordering == Ordering::Greater,
+                    _ => ordering != Ordering::Less,
+                };
+                Some(Value::Bool(result))
+            }
+            BinaryOp::And | BinaryOp::Or | BinaryOp::Xor => match (left, right) {
+                (Value::Bool(l), Value::Bool(r)) => Some(Value::Bool(match op {
+                    BinaryOp::And => *l && *r,
+                    BinaryOp::Or => *l || *r,
+                    _ => l != r,
+                })),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Compares two literals of the same kind.
+    ///
+    /// Returns `None` for mixed types, nulls, and NaN, leaving those
+    /// comparisons to the executor.
+    fn compare_literals(left: &Value, right: &Value) -> Option<Ordering> {
+        match (left, right) {
+            (Value::Int64(l), Value::Int64(r)) => Some(l.cmp(r)),
+            (Value::Float64(l), Value::Float64(r)) => l.partial_cmp(r),
+            (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+            (Value::Bool(l), Value::Bool(r)) => Some(l.cmp(r)),
+            _ => None,
+        }
+    }
+
+    /// Folds a unary operation whose operand is already simplified.
+    fn fold_unary(op: UnaryOp, operand: LogicalExpression) -> LogicalExpression {
+        match (op, operand) {
+            (UnaryOp::Not, LogicalExpression::Literal(Value::Bool(b))) => {
+                LogicalExpression::Literal(Value::Bool(!b))
+            }
+            (
+                UnaryOp::Not,
+                LogicalExpression::Unary {
+                    op: UnaryOp::Not,
+                    operand,
+                },
+            ) => *operand,
+            (UnaryOp::Neg, LogicalExpression::Literal(Value::Int64(v))) if v != i64::MIN => {
+                LogicalExpression::Literal(Value::Int64(-v))
+            }
+            (UnaryOp::Neg, LogicalExpression::Literal(Value::Float64(v))) => {
+                LogicalExpression::Literal(Value::Float64(-v))
+            }
+            (UnaryOp::IsNull, LogicalExpression::Literal(v)) => {
+                LogicalExpression::Literal(Value::Bool(v.is_null()))
+            }
+            (UnaryOp::IsNotNull, LogicalExpression::Literal(v)) => {
+                LogicalExpression::Literal(Value::Bool(!v.is_null()))
+            }
+            (op, operand) => LogicalExpression::Unary {
+                op,
+                operand: Box::new(operand),
+            },
+        }
+    }
+
     /// Pushes projections down the operator tree to eliminate unused columns early.
     ///
     /// This optimization:
@@ -410,6 +818,9 @@ impl Optimizer {
     /// 1. Extracting all base relations (scans) and join conditions
     /// 2. Building a join graph
     /// 3. Using dynamic programming to find the cheapest join order
+    ///
+    /// The reordered tree is only used when it is estimated to be cheaper
+    /// than the original one.
     fn reorder_joins(&self, op: LogicalOperator) -> LogicalOperator {
         // First, recursively optimize children
         let op = self.reorder_joins_recursive(op);
@@ -418,7 +829,11 @@ impl Optimizer {
         if let Some((relations, conditions)) = self.extract_join_tree(&op) {
             if relations.len() >= 2 {
                 if let Some(optimized) = self.optimize_join_order(&relations, &conditions) {
-                    return optimized;
+                    let original_cost = self.estimate_operator_cost(&op);
+                    let optimized_cost = self.estimate_operator_cost(&optimized);
+                    if optimized_cost.total() < original_cost.total() {
+                        return optimized;
+                    }
                 }
             }
         }
@@ -426,6 +841,22 @@ impl Optimizer {
         op
     }
 
+    /// Estimates the total cost of an operator tree, including its inputs.
+    fn estimate_operator_cost(&self, op: &LogicalOperator) -> Cost {
+        let own = self
+            .cost_model
+            .estimate(op, self.card_estimator.estimate(op));
+        match op {
+            LogicalOperator::Join(join) => {
+                own + self.estimate_operator_cost(&join.left)
+                    + self.estimate_operator_cost(&join.right)
+            }
+            LogicalOperator::Filter(filter) => own + self.estimate_operator_cost(&filter.input),
+            LogicalOperator::Expand(expand) => own + self.estimate_operator_cost(&expand.input),
+            _ => own,
+        }
+    }
+
     /// Recursively applies join reordering to child operators.
     fn reorder_joins_recursive(&self, op: LogicalOperator) -> LogicalOperator {
         match op {
@@ -493,6 +924,9 @@ impl Optimizer {
 
     /// Recursively collects base relations and join conditions.
     ///
+    /// Only inner and cross joins are reorderable. Filters and expands over a
+    /// base relation are kept together with it as a single relation.
+    ///
     /// Returns true if this subtree is part of a join tree.
     fn collect_join_tree(
         &self,
@@ -501,23 +935,27 @@ impl Optimizer {
         conditions: &mut Vec<JoinInfo>,
     ) -> bool {
         match op {
-            LogicalOperator::Join(join) => {
+            LogicalOperator::Join(join)
+                if matches!(join.join_type, JoinType::Inner | JoinType::Cross) =>
+            {
                 // Collect from both sides
                 let left_ok = self.collect_join_tree(&join.left, relations, conditions);
                 let right_ok = self.collect_join_tree(&join.right, relations, conditions);
 
-                // Add conditions from this join
+                // Add conditions from this join; a condition we can't attribute
+                // to a relation would be lost, so give up on the whole tree
                 for cond in &join.conditions {
-                    if let (Some(left_var), Some(right_var)) = (
+                    match (
                         self.extract_variable_from_expr(&cond.left),
                         self.extract_variable_from_expr(&cond.right),
                     ) {
-                        conditions.push(JoinInfo {
+                        (Some(left_var), Some(right_var)) => conditions.push(JoinInfo {
                             left_var,
                             right_var,
                             left_expr: cond.left.clone(),
                             right_expr: cond.right.clone(),
-                        });
+                        }),
+                        _ => return false,
                     }
                 }
 
@@ -532,8 +970,14 @@ impl Optimizer {
                 true
             }
             LogicalOperator::Filter(filter) => {
-                // A filter on a base relation is still part of the join tree
-                self.collect_join_tree(&filter.input, relations, conditions)
+                // A filter on a base relation stays attached to that relation
+                match Self::base_relation_variable(&filter.input) {
+                    Some(var) => {
+                        relations.push((var, op.clone()));
+                        true
+                    }
+                    None => false,
+                }
             }
             LogicalOperator::Expand(expand) => {
                 // Expand is a special case - it's like a join with the adjacency
@@ -545,6 +989,20 @@ impl Optimizer {
         }
     }
 
+    /// Returns the primary variable of a base relation (scan, or filters and
+    /// expands over a scan), or `None` if the subtree contains anything else.
+    fn base_relation_variable(op: &LogicalOperator) -> Option<String> {
+        match op {
+            LogicalOperator::NodeScan(scan) if scan.input.is_none() => Some(scan.variable.clone()),
+            LogicalOperator::EdgeScan(scan) if scan.input.is_none() => Some(scan.variable.clone()),
+            LogicalOperator::Expand(expand) => {
+                Self::base_relation_variable(&expand.input).map(|_| expand.to_variable.clone())
+            }
+            LogicalOperator::Filter(filter) => Self::base_relation_variable(&filter.input),
+            _ => None,
+        }
+    }
+
     /// Extracts the primary variable from an expression.
     fn extract_variable_from_expr(&self, expr: &LogicalExpression) -> Option<String> {
         match expr {
@@ -555,6 +1013,9 @@ impl Optimizer {
     }
 
     /// Optimizes the join order using DPccp.
+    ///
+    /// Returns `None` if a join condition can't be attributed to a relation,
+    /// since dropping it would change the query's meaning.
     fn optimize_join_order(
         &self,
         relations: &[(String, LogicalOperator)],
@@ -564,12 +1025,40 @@ impl Optimizer {
 
         // Build the join graph
         let mut builder = JoinGraphBuilder::new();
+        let mut relation_vars = Vec::with_capacity(relations.len());
 
         for (var, relation) in relations {
-            builder.add_relation(var, relation.clone());
+            let id = builder.add_relation(var, relation.clone());
+            let vars = self.collect_output_variables(relation);
+            for v in &vars {
+                builder.add_variable(v, id);
+            }
+            relation_vars.push(vars);
+        }
+
+        // Relations sharing a variable are implicitly joined on it
+        let mut connected = false;
+        for i in 0..relation_vars.len() {
+            for j in (i + 1)..relation_vars.len() {
+                if !relation_vars[i].is_disjoint(&relation_vars[j]) {
+                    builder.connect(i, j);
+                    connected = true;
+                }
+            }
+        }
+
+        // A pure cross product has no order worth choosing
+        if !connected && conditions.is_empty() {
+            return None;
         }
 
         for cond in conditions {
+            let left = builder.node_for_variable(&cond.left_var)?;
+            let right = builder.node_for_variable(&cond.right_var)?;
+            if left == right {
+                // Both sides come from one relation; can't be expressed as a join edge
+                return None;
+            }
             builder.add_join_condition(
                 &cond.left_var,
                 &cond.right_var,
@@ -593,10 +1082,12 @@ impl Optimizer {
     /// as possible to reduce the amount of data processed by upper operators.
     fn push_filters_down(&self, op: LogicalOperator) -> LogicalOperator {
         match op {
-            // For Filter operators, try to push the predicate into the child
+            // For Filter operators, merge any directly stacked filters into a
+            // single conjunction, then try to push it into the child
             LogicalOperator::Filter(filter) => {
-                let optimized_input = self.push_filters_down(*filter.input);
-                self.try_push_filter_into(filter.predicate, optimized_input)
+                let (predicate, input) = Self::coalesce_filters(filter);
+                let optimized_input = self.push_filters_down(input);
+                self.try_push_filter_into(predicate, optimized_input)
             }
             // Recursively optimize children for other operators
             LogicalOperator::Return(mut ret) => {
@@ -641,6 +1132,63 @@ impl Optimizer {
         }
     }
 
+    /// Merges a chain of directly stacked `Filter` operators into one predicate.
+    ///
+    /// `Filter(p1) -> Filter(p2) -> input` becomes `(p1 AND p2, input)`, so the
+    /// combined predicate can be split and pushed as a whole.
+    fn coalesce_filters(filter: FilterOp) -> (LogicalExpression, LogicalOperator) {
+        let mut conjuncts = vec![filter.predicate];
+        let mut input = *filter.input;
+
+        while let LogicalOperator::Filter(inner) = input {
+            conjuncts.push(inner.predicate);
+            input = *inner.input;
+        }
+
+        let predicate =
+            Self::combine_conjuncts(conjuncts).expect("at least one predicate was collected");
+        (predicate, input)
+    }
+
+    /// Splits a predicate into its top-level `AND` conjuncts.
+    fn split_conjuncts(expr: LogicalExpression, out: &mut Vec<LogicalExpression>) {
+        match expr {
+            LogicalExpression::Binary {
+                left,
+                op: BinaryOp::And,
+                right,
+            } => {
+                Self::split_conjuncts(*left, out);
+                Self::split_conjuncts(*right, out);
+            }
+            other => out.push(other),
+        }
+    }
+
+    /// Combines conjuncts into a left-deep `AND` chain.
+    ///
+    /// Returns `None` if there are no conjuncts.
+    fn combine_conjuncts(conjuncts: Vec<LogicalExpression>) -> Option<LogicalExpression> {
+        conjuncts
+            .into_iter()
+            .reduce(|acc, next| LogicalExpression::Binary {
+                left: Box::new(acc),
+                op: BinaryOp::And,
+                right: Box::new(next),
+            })
+    }
+
+    /// Wraps `op` in a filter for `predicate`, if there is one.
+    fn wrap_filter(predicate: Option<LogicalExpression>, op: LogicalOperator) -> LogicalOperator {
+        match predicate {
+            Some(predicate) => LogicalOperator::Filter(FilterOp {
+                predicate,
+                input: Box::new(op),
+            }),
+            None => op,
+        }
+    }
+
     /// Tries to push a filter predicate into the given operator.
     ///
     /// Returns either the predicate pushed into the operator, or a new
@@ -675,50 +1223,68 @@ impl Optimizer {
                 LogicalOperator::Return(ret)
             }
 
-            // Can push through Expand if predicate only uses source variable
+            // Can push through Expand the conjuncts that only use the source variable
             LogicalOperator::Expand(mut expand) => {
-                let predicate_vars = self.extract_variables(&predicate);
-
-                // Check if predicate only uses the source variable
-                let uses_only_source = predicate_vars.iter().all(|v| v == &expand.from_variable);
-
-                if uses_only_source {
-                    // Push the filter before the expand
-                    expand.input = Box::new(self.try_push_filter_into(predicate, *expand.input));
-                    LogicalOperator::Expand(expand)
-                } else {
-                    // Keep filter after expand
-                    LogicalOperator::Filter(FilterOp {
-                        predicate,
-                        input: Box::new(LogicalOperator::Expand(expand)),
-                    })
+                let mut conjuncts = Vec::new();
+                Self::split_conjuncts(predicate, &mut conjuncts);
+
+                let (below, above): (Vec<_>, Vec<_>) = conjuncts.into_iter().partition(|c| {
+                    self.extract_variables(c)
+                        .iter()
+                        .all(|v| v == &expand.from_variable)
+                });
+
+                // Push source-only conjuncts before the expand
+                if let Some(below) = Self::combine_conjuncts(below) {
+                    expand.input = Box::new(self.try_push_filter_into(below, *expand.input));
                 }
+
+                // Keep the rest after the expand
+                Self::wrap_filter(
+                    Self::combine_conjuncts(above),
+                    LogicalOperator::Expand(expand),
+                )
             }
 
-            // Can push through Join to left/right side based on variables used
+            // Can push through Join to left/right side based on variables used.
+            // Each top-level conjunct is routed independently.
             LogicalOperator::Join(mut join) => {
-                let predicate_vars = self.extract_variables(&predicate);
                 let left_vars = self.collect_output_variables(&join.left);
                 let right_vars = self.collect_output_variables(&join.right);
 
-                let uses_left = predicate_vars.iter().any(|v| left_vars.contains(v));
-                let uses_right = predicate_vars.iter().any(|v| right_vars.contains(v));
-
-                if uses_left && !uses_right {
-                    // Push to left side
-                    join.left = Box::new(self.try_push_filter_into(predicate, *join.left));
-                    LogicalOperator::Join(join)
-                } else if uses_right && !uses_left {
-                    // Push to right side
-                    join.right = Box::new(self.try_push_filter_into(predicate, *join.right));
-                    LogicalOperator::Join(join)
-                } else {
-                    // Uses both sides - keep above join
-                    LogicalOperator::Filter(FilterOp {
-                        predicate,
-                        input: Box::new(LogicalOperator::Join(join)),
-                    })
+                let mut conjuncts = Vec::new();
+                Self::split_conjuncts(predicate, &mut conjuncts);
+
+                let mut left_preds = Vec::new();
+                let mut right_preds = Vec::new();
+                let mut remaining = Vec::new();
+
+                for conjunct in conjuncts {
+                    let vars = self.extract_variables(&conjunct);
+                    let uses_left = vars.iter().any(|v| left_vars.contains(v));
+                    let uses_right = vars.iter().any(|v| right_vars.contains(v));
+
+                    if uses_left && !uses_right {
+                        left_preds.push(conjunct);
+                    } else if uses_right && !uses_left {
+                        right_preds.push(conjunct);
+                    } else {
+                        // Uses both sides (or neither) - keep above join
+                        remaining.push(conjunct);
+                    }
+                }
+
+                if let Some(left_pred) = Self::combine_conjuncts(left_preds) {
+                    join.left = Box::new(self.try_push_filter_into(left_pred, *join.left));
                 }
+                if let Some(right_pred) = Self::combine_conjuncts(right_preds) {
+                    join.right = Box::new(self.try_push_filter_into(right_pred, *join.right));
+                }
+
+                Self::wrap_filter(
+                    Self::combine_conjuncts(remaining),
+                    LogicalOperator::Join(join),
+                )
             }
 
             // Cannot push through Aggregate (predicate refers to aggregated values)
@@ -893,9 +1459,43 @@ impl Optimizer {
                 }
                 Self::collect_variables(map_expr, vars);
             }
-            LogicalExpression::ExistsSubquery(_) | LogicalExpression::CountSubquery(_) => {
+            LogicalExpression::ExistsSubquery(subquery) => {
+                Self::collect_subquery_variables(subquery, vars);
+            }
+            LogicalExpression::CountSubquery(_) => {
                 // Subqueries have their own variable scope
             }
+            LogicalExpression::InSubquery { expr, subquery } => {
+                Self::collect_variables(expr, vars);
+                Self::collect_subquery_variables(subquery, vars);
+            }
+        }
+    }
+
+    /// Collects the variables a subquery binds, and those its returned value
+    /// and WHERE read.
+    ///
+    /// The subquery may be correlated on any of them, so the filter holding
+    /// it must stay above all of them.
+    fn collect_subquery_variables(subquery: &LogicalOperator, vars: &mut HashSet<String>) {
+        Self::collect_output_variables_recursive(subquery, vars);
+        let input = match subquery {
+            LogicalOperator::Return(ret) => {
+                for item in &ret.items {
+                    Self::collect_variables(&item.expression, vars);
+                }
+                ret.input.as_ref()
+            }
+            LogicalOperator::Project(project) => {
+                for projection in &project.projections {
+                    Self::collect_variables(&projection.expression, vars);
+                }
+                project.input.as_ref()
+            }
+            other => other,
+        };
+        if let LogicalOperator::Filter(filter) = input {
+            Self::collect_variables(&filter.predicate, vars);
         }
     }
 
@@ -919,8 +1519,8 @@ mod tests {
     use super::*;
     use crate::query::plan::{
         AggregateExpr, AggregateFunction, AggregateOp, BinaryOp, DistinctOp, ExpandDirection,
-        ExpandOp, JoinOp, JoinType, LimitOp, NodeScanOp, ProjectOp, Projection, ReturnItem,
-        ReturnOp, SkipOp, SortKey, SortOp, SortOrder, UnaryOp,
+        ExpandOp, JoinCondition, JoinOp, JoinType, LimitOp, NodeScanOp, ProjectOp, Projection,
+        ReturnItem, ReturnOp, SkipOp, SortKey, SortOp, SortOrder, UnaryOp,
     };
     use grafeo_common::types::Value;
 
@@ -1119,7 +1719,9 @@ mod tests {
 
     #[test]
     fn test_optimizer_with_filter_pushdown_disabled() {
-        let optimizer = Optimizer::new().with_filter_pushdown(false);
+        let optimizer = Optimizer::new()
+            .with_filter_pushdown(false)
+            .with_predicate_simplification(false);
 
         let plan = LogicalPlan::new(LogicalOperator::Return(ReturnOp {
             items: vec![ReturnItem {
@@ -1279,7 +1881,8 @@ mod tests {
 
     #[test]
     fn test_filter_pushdown_through_limit() {
-        let optimizer = Optimizer::new();
+        // Literal predicate stands in for any filter; keep it from being folded away
+        let optimizer = Optimizer::new().with_predicate_simplification(false);
 
         let plan = LogicalPlan::new(LogicalOperator::Filter(FilterOp {
             predicate: LogicalExpression::Literal(Value::Bool(true)),
@@ -1306,7 +1909,8 @@ mod tests {
 
     #[test]
     fn test_filter_pushdown_through_sort() {
-        let optimizer = Optimizer::new();
+        // Literal predicate stands in for any filter; keep it from being folded away
+        let optimizer = Optimizer::new().with_predicate_simplification(false);
 
         let plan = LogicalPlan::new(LogicalOperator::Filter(FilterOp {
             predicate: LogicalExpression::Literal(Value::Bool(true)),
@@ -1336,7 +1940,8 @@ mod tests {
 
     #[test]
     fn test_filter_pushdown_through_distinct() {
-        let optimizer = Optimizer::new();
+        // Literal predicate stands in for any filter; keep it from being folded away
+        let optimizer = Optimizer::new().with_predicate_simplification(false);
 
         let plan = LogicalPlan::new(LogicalOperator::Filter(FilterOp {
             predicate: LogicalExpression::Literal(Value::Bool(true)),
@@ -1526,6 +2131,764 @@ mod tests {
         panic!("Expected Filter -> Join structure");
     }
 
+    // Conjunct splitting and filter coalescing tests
+
+    fn property_cmp(variable: &str, property: &str, op: BinaryOp, value: i64) -> LogicalExpression {
+        LogicalExpression::Binary {
+            left: Box::new(LogicalExpression::Property {
+                variable: variable.to_string(),
+                property: property.to_string(),
+            }),
+            op,
+            right: Box::new(LogicalExpression::Literal(Value::Int64(value))),
+        }
+    }
+
+    fn and(left: LogicalExpression, right: LogicalExpression) -> LogicalExpression {
+        LogicalExpression::Binary {
+            left: Box::new(left),
+            op: BinaryOp::And,
+            right: Box::new(right),
+        }
+    }
+
+    fn node_scan(variable: &str) -> LogicalOperator {
+        LogicalOperator::NodeScan(NodeScanOp {
+            variable: variable.to_string(),
+            label: None,
+            input: None,
+        })
+    }
+
+    fn count_conjuncts(expr: &LogicalExpression) -> usize {
+        let mut conjuncts = Vec::new();
+        Optimizer::split_conjuncts(expr.clone(), &mut conjuncts);
+        conjuncts.len()
+    }
+
+    #[test]
+    fn test_stacked_filters_are_coalesced() {
+        let optimizer = Optimizer::new();
+
+        // Filter(n.x > 1) -> Filter(n.y < 10) -> NodeScan
+        let plan = LogicalPlan::new(LogicalOperator::Filter(FilterOp {
+            predicate: property_cmp("n", "x", BinaryOp::Gt, 1),
+            input: Box::new(LogicalOperator::Filter(FilterOp {
+                predicate: property_cmp("n", "y", BinaryOp::Lt, 10),
+                input: Box::new(node_scan("n")),
+            })),
+        }));
+
+        let optimized = optimizer.optimize(plan).unwrap();
+
+        // A single Filter with an AND of both predicates
+        if let LogicalOperator::Filter(filter) = &optimized.root {
+            assert_eq!(count_conjuncts(&filter.predicate), 2);
+            assert!(matches!(
+                filter.input.as_ref(),
+                LogicalOperator::NodeScan(_)
+            ));
+            return;
+        }
+        panic!("Expected a single Filter -> NodeScan");
+    }
+
+    #[test]
+    fn test_stacked_filters_split_around_expand() {
+        let optimizer = Optimizer::new();
+
+        // Filter(b.y < 10) -> Filter(a.x > 1) -> Expand(a -> b) -> NodeScan(a)
+        let plan = LogicalPlan::new(LogicalOperator::Filter(FilterOp {
+            predicate: property_cmp("b", "y", BinaryOp::Lt, 10),
+            input: Box::new(LogicalOperator::Filter(FilterOp {
+                predicate: property_cmp("a", "x", BinaryOp::Gt, 1),
+                input: Box::new(LogicalOperator::Expand(ExpandOp {
+                    from_variable: "a".to_string(),
+                    to_variable: "b".to_string(),
+                    edge_variable: None,
+                    direction: ExpandDirection::Outgoing,
+                    edge_type: None,
+                    min_hops: 1,
+                    max_hops: Some(1),
+                    input: Box::new(node_scan("a")),
+                    path_alias: None,
+                })),
+            })),
+        }));
+
+        let optimized = optimizer.optimize(plan).unwrap();
+
+        // Expected: Filter(b) -> Expand -> Filter(a) -> NodeScan
+        if let LogicalOperator::Filter(top) = &optimized.root {
+            assert!(optimizer.extract_variables(&top.predicate).contains("b"));
+            if let LogicalOperator::Expand(expand) = top.input.as_ref() {
+                if let LogicalOperator::Filter(below) = expand.input.as_ref() {
+                    let vars = optimizer.extract_variables(&below.predicate);
+                    assert_eq!(vars.len(), 1);
+                    assert!(vars.contains("a"));
+                    return;
+                }
+            }
+        }
+        panic!("Expected Filter -> Expand -> Filter -> NodeScan structure");
+    }
+
+    #[test]
+    fn test_conjunction_split_across_join_sides() {
+        let optimizer = Optimizer::new();
+
+        // a.age > 30 AND b.size < 100 AND a.id = b.owner
+        let spanning = LogicalExpression::Binary {
+            left: Box::new(LogicalExpression::Property {
+                variable: "a".to_string(),
+                property: "id".to_string(),
+            }),
+            op: BinaryOp::Eq,
+            right: Box::new(LogicalExpression::Property {
+                variable: "b".to_string(),
+                property: "owner".to_string(),
+            }),
+        };
+        let predicate = and(
+            and(
+                property_cmp("a", "age", BinaryOp::Gt, 30),
+                property_cmp("b", "size", BinaryOp::Lt, 100),
+            ),
+            spanning,
+        );
+
+        let plan = LogicalPlan::new(LogicalOperator::Filter(FilterOp {
+            predicate,
+            input: Box::new(LogicalOperator::Join(JoinOp {
+                left: Box::new(node_scan("a")),
+                right: Box::new(node_scan("b")),
+                join_type: JoinType::Inner,
+                conditions: vec![],
+            })),
+        }));
+
+        let optimized = optimizer.optimize(plan).unwrap();
+
+        // Expected: Filter(a.id = b.owner) -> Join(Filter(a) , Filter(b))
+        if let LogicalOperator::Filter(top) = &optimized.root {
+            assert_eq!(count_conjuncts(&top.predicate), 1);
+            let top_vars = optimizer.extract_variables(&top.predicate);
+            assert!(top_vars.contains("a") && top_vars.contains("b"));
+
+            if let LogicalOperator::Join(join) = top.input.as_ref() {
+                if let (LogicalOperator::Filter(left), LogicalOperator::Filter(right)) =
+                    (join.left.as_ref(), join.right.as_ref())
+                {
+                    let left_vars = optimizer.extract_variables(&left.predicate);
+                    let right_vars = optimizer.extract_variables(&right.predicate);
+                    assert_eq!(left_vars.len(), 1);
+                    assert!(left_vars.contains("a"));
+                    assert_eq!(right_vars.len(), 1);
+                    assert!(right_vars.contains("b"));
+                    return;
+                }
+            }
+        }
+        panic!("Expected Filter -> Join with filters on both sides");
+    }
+
+    #[test]
+    fn test_stacked_filters_fully_pushed_into_join() {
+        let optimizer = Optimizer::new();
+
+        // Filter(a.x > 1) -> Filter(b.y < 10) -> Join(a, b): nothing stays on top
+        let plan = LogicalPlan::new(LogicalOperator::Filter(FilterOp {
+            predicate: property_cmp("a", "x", BinaryOp::Gt, 1),
+            input: Box::new(LogicalOperator::Filter(FilterOp {
+                predicate: property_cmp("b", "y", BinaryOp::Lt, 10),
+                input: Box::new(LogicalOperator::Join(JoinOp {
+                    left: Box::new(node_scan("a")),
+                    right: Box::new(node_scan("b")),
+                    join_type: JoinType::Inner,
+                    conditions: vec![],
+                })),
+            })),
+        }));
+
+        let optimized = optimizer.optimize(plan).unwrap();
+
+        if let LogicalOperator::Join(join) = &optimized.root {
+            assert!(matches!(join.left.as_ref(), LogicalOperator::Filter(_)));
+            assert!(matches!(join.right.as_ref(), LogicalOperator::Filter(_)));
+            return;
+        }
+        panic!("Expected Join with filters on both sides");
+    }
+
+    #[test]
+    fn test_split_and_combine_conjuncts() {
+        let expr = and(
+            property_cmp("n", "a", BinaryOp::Eq, 1),
+            and(
+                property_cmp("n", "b", BinaryOp::Eq, 2),
+                property_cmp("n", "c", BinaryOp::Eq, 3),
+            ),
+        );
+
+        let mut conjuncts = Vec::new();
+        Optimizer::split_conjuncts(expr, &mut conjuncts);
+        assert_eq!(conjuncts.len(), 3);
+
+        let combined = Optimizer::combine_conjuncts(conjuncts).unwrap();
+        assert_eq!(count_conjuncts(&combined), 3);
+        assert!(Optimizer::combine_conjuncts(Vec::new()).is_none());
+    }
+
+    // Join reordering tests
+
+    fn labeled_scan(variable: &str, label: &str) -> LogicalOperator {
+        LogicalOperator::NodeScan(NodeScanOp {
+            variable: variable.to_string(),
+            label: Some(label.to_string()),
+            input: None,
+        })
+    }
+
+    fn id_condition(left: &str, right: &str) -> JoinCondition {
+        JoinCondition {
+            left: LogicalExpression::Variable(left.to_string()),
+            right: LogicalExpression::Variable(right.to_string()),
+        }
+    }
+
+    fn contains_cross_join(op: &LogicalOperator) -> bool {
+        match op {
+            LogicalOperator::Join(join) => {
+                join.join_type == JoinType::Cross
+                    || contains_cross_join(&join.left)
+                    || contains_cross_join(&join.right)
+            }
+            _ => false,
+        }
+    }
+
+    fn four_relation_estimator() -> CardinalityEstimator {
+        let mut estimator = CardinalityEstimator::new();
+        estimator.add_table_stats("A", TableStats::new(10_000));
+        estimator.add_table_stats("B", TableStats::new(10));
+        estimator.add_table_stats("C", TableStats::new(10_000));
+        estimator.add_table_stats("D", TableStats::new(10));
+        estimator
+    }
+
+    #[test]
+    fn test_join_reorder_four_relations_beats_left_deep() {
+        // Chain a - b - c - d, written left-deep with a cross product first:
+        // ((a x c) join b) join d
+        let left_deep = LogicalOperator::Join(JoinOp {
+            left: Box::new(LogicalOperator::Join(JoinOp {
+                left: Box::new(LogicalOperator::Join(JoinOp {
+                    left: Box::new(labeled_scan("a", "A")),
+                    right: Box::new(labeled_scan("c", "C")),
+                    join_type: JoinType::Cross,
+                    conditions: vec![],
+                })),
+                right: Box::new(labeled_scan("b", "B")),
+                join_type: JoinType::Inner,
+                conditions: vec![id_condition("a", "b"), id_condition("c", "b")],
+            })),
+            right: Box::new(labeled_scan("d", "D")),
+            join_type: JoinType::Inner,
+            conditions: vec![id_condition("c", "d")],
+        });
+
+        let optimizer = Optimizer::new()
+            .with_filter_pushdown(false)
+            .with_projection_pushdown(false)
+            .with_cardinality_estimator(four_relation_estimator());
+
+        let original_cost = optimizer.estimate_operator_cost(&left_deep);
+        let original_vars = optimizer.collect_output_variables(&left_deep);
+
+        let optimized = optimizer
+            .optimize(LogicalPlan::new(left_deep))
+            .unwrap()
+            .root;
+        let optimized_cost = optimizer.estimate_operator_cost(&optimized);
+
+        assert!(optimized_cost.total() < original_cost.total());
+        assert!(!contains_cross_join(&optimized));
+        assert_eq!(
+            optimizer.collect_output_variables(&optimized),
+            original_vars
+        );
+
+        // All three conditions survive, oriented towards their join sides
+        let mut relations = Vec::new();
+        let mut conditions = Vec::new();
+        assert!(optimizer.collect_join_tree(&optimized, &mut relations, &mut conditions));
+        assert_eq!(relations.len(), 4);
+        assert_eq!(conditions.len(), 3);
+        assert_join_conditions_oriented(&optimizer, &optimized);
+    }
+
+    fn assert_join_conditions_oriented(optimizer: &Optimizer, op: &LogicalOperator) {
+        if let LogicalOperator::Join(join) = op {
+            let left_vars = optimizer.collect_output_variables(&join.left);
+            let right_vars = optimizer.collect_output_variables(&join.right);
+            for cond in &join.conditions {
+                assert!(
+                    optimizer
+                        .extract_variables(&cond.left)
+                        .is_subset(&left_vars)
+                );
+                assert!(
+                    optimizer
+                        .extract_variables(&cond.right)
+                        .is_subset(&right_vars)
+                );
+            }
+            assert_join_conditions_oriented(optimizer, &join.left);
+            assert_join_conditions_oriented(optimizer, &join.right);
+        }
+    }
+
+    #[test]
+    fn test_join_reorder_disabled_keeps_left_deep() {
+        let left_deep = LogicalOperator::Join(JoinOp {
+            left: Box::new(LogicalOperator::Join(JoinOp {
+                left: Box::new(labeled_scan("a", "A")),
+                right: Box::new(labeled_scan("c", "C")),
+                join_type: JoinType::Cross,
+                conditions: vec![],
+            })),
+            right: Box::new(labeled_scan("b", "B")),
+            join_type: JoinType::Inner,
+            conditions: vec![id_condition("a", "b"), id_condition("c", "b")],
+        });
+
+        let optimizer = Optimizer::new()
+            .with_join_reorder(false)
+            .with_cardinality_estimator(four_relation_estimator());
+        let optimized = optimizer
+            .optimize(LogicalPlan::new(left_deep))
+            .unwrap()
+            .root;
+
+        assert!(contains_cross_join(&optimized));
+    }
+
+    #[test]
+    fn test_join_reorder_defers_cross_products() {
+        // (a x c) join b on a = b, with d disconnected from everything
+        let plan = LogicalOperator::Join(JoinOp {
+            left: Box::new(LogicalOperator::Join(JoinOp {
+                left: Box::new(LogicalOperator::Join(JoinOp {
+                    left: Box::new(labeled_scan("a", "A")),
+                    right: Box::new(labeled_scan("c", "C")),
+                    join_type: JoinType::Cross,
+                    conditions: vec![],
+                })),
+                right: Box::new(labeled_scan("b", "B")),
+                join_type: JoinType::Inner,
+                conditions: vec![id_condition("a", "b")],
+            })),
+            right: Box::new(labeled_scan("d", "D")),
+            join_type: JoinType::Cross,
+            conditions: vec![],
+        });
+
+        let optimizer = Optimizer::new().with_cardinality_estimator(four_relation_estimator());
+        let optimized = optimizer.optimize(LogicalPlan::new(plan)).unwrap().root;
+
+        // The conditioned join a - b must sit below every cross join
+        fn conditioned_join_depth(op: &LogicalOperator, depth: usize) -> Option<usize> {
+            match op {
+                LogicalOperator::Join(join) if !join.conditions.is_empty() => Some(depth),
+                LogicalOperator::Join(join) => conditioned_join_depth(&join.left, depth + 1)
+                    .or_else(|| conditioned_join_depth(&join.right, depth + 1)),
+                _ => None,
+            }
+        }
+        let depth = conditioned_join_depth(&optimized, 0).expect("conditioned join kept");
+        assert!(depth > 0);
+        if let LogicalOperator::Join(join) = &optimized {
+            assert_eq!(join.join_type, JoinType::Cross);
+        }
+    }
+
+    #[test]
+    fn test_join_reorder_keeps_filtered_relations() {
+        // A filter on a base relation must survive reordering
+        let plan = LogicalOperator::Join(JoinOp {
+            left: Box::new(LogicalOperator::Join(JoinOp {
+                left: Box::new(labeled_scan("a", "A")),
+                right: Box::new(labeled_scan("c", "C")),
+                join_type: JoinType::Cross,
+                conditions: vec![],
+            })),
+            right: Box::new(LogicalOperator::Filter(FilterOp {
+                predicate: property_cmp("b", "x", BinaryOp::Gt, 1),
+                input: Box::new(labeled_scan("b", "B")),
+            })),
+            join_type: JoinType::Inner,
+            conditions: vec![id_condition("a", "b"), id_condition("c", "b")],
+        });
+
+        let optimizer = Optimizer::new().with_cardinality_estimator(four_relation_estimator());
+        let optimized = optimizer.optimize(LogicalPlan::new(plan)).unwrap().root;
+
+        fn count_filters(op: &LogicalOperator) -> usize {
+            match op {
+                LogicalOperator::Filter(filter) => 1 + count_filters(&filter.input),
+                LogicalOperator::Join(join) => {
+                    count_filters(&join.left) + count_filters(&join.right)
+                }
+                _ => 0,
+            }
+        }
+        assert!(!contains_cross_join(&optimized));
+        assert_eq!(count_filters(&optimized), 1);
+    }
+
+    #[test]
+    fn test_join_reorder_skips_outer_joins() {
+        let plan = LogicalOperator::Join(JoinOp {
+            left: Box::new(labeled_scan("a", "A")),
+            right: Box::new(labeled_scan("b", "B")),
+            join_type: JoinType::Left,
+            conditions: vec![id_condition("a", "b")],
+        });
+
+        let optimizer = Optimizer::new();
+        let mut relations = Vec::new();
+        let mut conditions = Vec::new();
+        assert!(!optimizer.collect_join_tree(&plan, &mut relations, &mut conditions));
+    }
+
+    // Predicate simplification tests
+
+    fn lit(value: Value) -> LogicalExpression {
+        LogicalExpression::Literal(value)
+    }
+
+    fn binary(
+        left: LogicalExpression,
+        op: BinaryOp,
+        right: LogicalExpression,
+    ) -> LogicalExpression {
+        LogicalExpression::Binary {
+            left: Box::new(left),
+            op,
+            right: Box::new(right),
+        }
+    }
+
+    fn not(operand: LogicalExpression) -> LogicalExpression {
+        LogicalExpression::Unary {
+            op: UnaryOp::Not,
+            operand: Box::new(operand),
+        }
+    }
+
+    fn var(name: &str) -> LogicalExpression {
+        LogicalExpression::Variable(name.to_string())
+    }
+
+    fn is_literal(expr: &LogicalExpression, expected: &Value) -> bool {
+        matches!(expr, LogicalExpression::Literal(v) if v == expected)
+    }
+
+    fn is_var(expr: &LogicalExpression, name: &str) -> bool {
+        matches!(expr, LogicalExpression::Variable(v) if v == name)
+    }
+
+    #[test]
+    fn test_fold_integer_arithmetic() {
+        let expr = binary(lit(Value::Int64(2)), BinaryOp::Add, lit(Value::Int64(3)));
+        assert!(is_literal(
+            &Optimizer::simplify_expression(expr),
+            &Value::Int64(5)
+        ));
+
+        // Nested: (2 * 3) - 1
+        let expr = binary(
+            binary(lit(Value::Int64(2)), BinaryOp::Mul, lit(Value::Int64(3))),
+            BinaryOp::Sub,
+            lit(Value::Int64(1)),
+        );
+        assert!(is_literal(
+            &Optimizer::simplify_expression(expr),
+            &Value::Int64(5)
+        ));
+    }
+
+    #[test]
+    fn test_fold_float_arithmetic() {
+        let expr = binary(
+            lit(Value::Float64(1.5)),
+            BinaryOp::Mul,
+            lit(Value::Float64(2.0)),
+        );
+        assert!(is_literal(
+            &Optimizer::simplify_expression(expr),
+            &Value::Float64(3.0)
+        ));
+    }
+
+    #[test]
+    fn test_fold_overflow_left_unevaluated() {
+        let expr = binary(
+            lit(Value::Int64(i64::MAX)),
+            BinaryOp::Add,
+            lit(Value::Int64(1)),
+        );
+        assert!(matches!(
+            Optimizer::simplify_expression(expr),
+            LogicalExpression::Binary {
+                op: BinaryOp::Add,
+                ..
+            }
+        ));
+
+        let expr = LogicalExpression::Unary {
+            op: UnaryOp::Neg,
+            operand: Box::new(lit(Value::Int64(i64::MIN))),
+        };
+        assert!(matches!(
+            Optimizer::simplify_expression(expr),
+            LogicalExpression::Unary { .. }
+        ));
+    }
+
+    #[test]
+    fn test_fold_division_by_zero_left_unevaluated() {
+        let expr = binary(lit(Value::Int64(1)), BinaryOp::Div, lit(Value::Int64(0)));
+        assert!(matches!(
+            Optimizer::simplify_expression(expr),
+            LogicalExpression::Binary { .. }
+        ));
+
+        let expr = binary(
+            lit(Value::Float64(0.0)),
+            BinaryOp::Div,
+            lit(Value::Float64(0.0)),
+        );
+        assert!(matches!(
+            Optimizer::simplify_expression(expr),
+            LogicalExpression::Binary { .. }
+        ));
+    }
+
+    #[test]
+    fn test_fold_comparisons() {
+        let expr = binary(lit(Value::Int64(1)), BinaryOp::Lt, lit(Value::Int64(2)));
+        assert!(is_literal(
+            &Optimizer::simplify_expression(expr),
+            &Value::Bool(true)
+        ));
+
+        let expr = binary(
+            lit(Value::String("a".into())),
+            BinaryOp::Eq,
+            lit(Value::String("b".into())),
+        );
+        assert!(is_literal(
+            &Optimizer::simplify_expression(expr),
+            &Value::Bool(false)
+        ));
+    }
+
+    #[test]
+    fn test_fold_nan_comparison_left_unevaluated() {
+        for op in [BinaryOp::Eq, BinaryOp::Ne, BinaryOp::Lt, BinaryOp::Ge] {
+            let expr = binary(lit(Value::Float64(f64::NAN)), op, lit(Value::Float64(1.0)));
+            assert!(matches!(
+                Optimizer::simplify_expression(expr),
+                LogicalExpression::Binary { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_fold_and_identities() {
+        let expr = binary(lit(Value::Bool(true)), BinaryOp::And, var("x"));
+        assert!(is_var(&Optimizer::simplify_expression(expr), "x"));
+
+        let expr = binary(var("x"), BinaryOp::And, lit(Value::Bool(true)));
+        assert!(is_var(&Optimizer::simplify_expression(expr), "x"));
+
+        let expr = binary(var("x"), BinaryOp::And, lit(Value::Bool(false)));
+        assert!(is_literal(
+            &Optimizer::simplify_expression(expr),
+            &Value::Bool(false)
+        ));
+    }
+
+    #[test]
+    fn test_fold_or_identities() {
+        let expr = binary(var("x"), BinaryOp::Or, lit(Value::Bool(true)));
+        assert!(is_literal(
+            &Optimizer::simplify_expression(expr),
+            &Value::Bool(true)
+        ));
+
+        let expr = binary(lit(Value::Bool(false)), BinaryOp::Or, var("x"));
+        assert!(is_var(&Optimizer::simplify_expression(expr), "x"));
+    }
+
+    #[test]
+    fn test_fold_double_negation() {
+        let expr = not(not(var("x")));
+        assert!(is_var(&Optimizer::simplify_expression(expr), "x"));
+
+        let expr = not(lit(Value::Bool(true)));
+        assert!(is_literal(
+            &Optimizer::simplify_expression(expr),
+            &Value::Bool(false)
+        ));
+    }
+
+    #[test]
+    fn test_fold_leaves_non_constant_expressions() {
+        let expr = property_cmp("n", "age", BinaryOp::Gt, 30);
+        assert!(matches!(
+            Optimizer::simplify_expression(expr),
+            LogicalExpression::Binary {
+                op: BinaryOp::Gt,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_simplify_removes_true_filter() {
+        let optimizer = Optimizer::new();
+
+        // WHERE 1 = 1 disappears entirely
+        let plan = LogicalPlan::new(LogicalOperator::Return(ReturnOp {
+            items: vec![ReturnItem {
+                expression: var("n"),
+                alias: None,
+            }],
+            distinct: false,
+            input: Box::new(LogicalOperator::Filter(FilterOp {
+                predicate: binary(lit(Value::Int64(1)), BinaryOp::Eq, lit(Value::Int64(1))),
+                input: Box::new(node_scan("n")),
+            })),
+        }));
+
+        let optimized = optimizer.optimize(plan).unwrap();
+        if let LogicalOperator::Return(ret) = &optimized.root {
+            assert!(matches!(ret.input.as_ref(), LogicalOperator::NodeScan(_)));
+            return;
+        }
+        panic!("Expected Return -> NodeScan");
+    }
+
+    #[test]
+    fn test_simplify_false_filter_prunes_subtree() {
+        let optimizer = Optimizer::new();
+
+        // Return -> Sort -> Filter(n.x > 1 AND 1 > 2) -> Expand -> NodeScan
+        let plan = LogicalPlan::new(LogicalOperator::Return(ReturnOp {
+            items: vec![ReturnItem {
+                expression: var("m"),
+                alias: None,
+            }],
+            distinct: false,
+            input: Box::new(LogicalOperator::Sort(SortOp {
+                keys: vec![SortKey {
+                    expression: var("m"),
+                    order: SortOrder::Ascending,
+                }],
+                input: Box::new(LogicalOperator::Filter(FilterOp {
+                    predicate: and(
+                        property_cmp("n", "x", BinaryOp::Gt, 1),
+                        binary(lit(Value::Int64(1)), BinaryOp::Gt, lit(Value::Int64(2))),
+                    ),
+                    input: Box::new(LogicalOperator::Expand(ExpandOp {
+                        from_variable: "n".to_string(),
+                        to_variable: "m".to_string(),
+                        edge_variable: None,
+                        direction: ExpandDirection::Outgoing,
+                        edge_type: None,
+                        min_hops: 1,
+                        max_hops: Some(1),
+                        input: Box::new(node_scan("n")),
+                        path_alias: None,
+                    })),
+                })),
+            })),
+        }));
+
+        let optimized = optimizer.optimize(plan).unwrap();
+        if let LogicalOperator::Return(ret) = &optimized.root {
+            assert!(matches!(ret.input.as_ref(), LogicalOperator::Empty));
+            return;
+        }
+        panic!("Expected Return -> Empty");
+    }
+
+    #[test]
+    fn test_simplify_false_filter_kept_under_global_aggregate() {
+        let optimizer = Optimizer::new();
+
+        // COUNT(*) over no rows still returns a row, so nothing is pruned
+        let plan = LogicalPlan::new(LogicalOperator::Return(ReturnOp {
+            items: vec![ReturnItem {
+                expression: var("cnt"),
+                alias: None,
+            }],
+            distinct: false,
+            input: Box::new(LogicalOperator::Aggregate(AggregateOp {
+                group_by: vec![],
+                aggregates: vec![AggregateExpr {
+                    function: AggregateFunction::Count,
+                    expression: None,
+                    distinct: false,
+                    alias: Some("cnt".to_string()),
+                    percentile: None,
+                }],
+                input: Box::new(LogicalOperator::Filter(FilterOp {
+                    predicate: lit(Value::Bool(false)),
+                    input: Box::new(node_scan("n")),
+                })),
+                having: None,
+            })),
+        }));
+
+        let optimized = optimizer.optimize(plan).unwrap();
+        if let LogicalOperator::Return(ret) = &optimized.root {
+            if let LogicalOperator::Aggregate(agg) = ret.input.as_ref() {
+                assert!(matches!(agg.input.as_ref(), LogicalOperator::Filter(_)));
+                return;
+            }
+        }
+        panic!("Expected Return -> Aggregate -> Filter");
+    }
+
+    #[test]
+    fn test_simplify_disabled() {
+        let optimizer = Optimizer::new()
+            .with_predicate_simplification(false)
+            .with_filter_pushdown(false);
+
+        let plan = LogicalPlan::new(LogicalOperator::Return(ReturnOp {
+            items: vec![ReturnItem {
+                expression: var("n"),
+                alias: None,
+            }],
+            distinct: false,
+            input: Box::new(LogicalOperator::Filter(FilterOp {
+                predicate: lit(Value::Bool(false)),
+                input: Box::new(node_scan("n")),
+            })),
+        }));
+
+        let optimized = optimizer.optimize(plan).unwrap();
+        if let LogicalOperator::Return(ret) = &optimized.root {
+            assert!(matches!(ret.input.as_ref(), LogicalOperator::Filter(_)));
+            return;
+        }
+        panic!("Expected Return -> Filter");
+    }
+
     // Variable extraction tests
 
     #[test]
@@ -5,9 +5,11 @@
 //! common representation.
 
 use grafeo_common::types::Value;
+use grafeo_common::utils::error::{Error, QueryError, QueryErrorKind, Result};
+use std::collections::HashSet;
 
 /// A logical query plan.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LogicalPlan {
     /// The root operator of the plan.
     pub root: LogicalOperator,
@@ -18,10 +20,19 @@ impl LogicalPlan {
     pub fn new(root: LogicalOperator) -> Self {
         Self { root }
     }
+
+    /// Returns true if the plan answers a SPARQL ASK query.
+    ///
+    /// Its result is shaped into a single boolean with
+    /// [`QueryResult::boolean`](crate::database::QueryResult::boolean).
+    #[must_use]
+    pub fn is_ask(&self) -> bool {
+        matches!(self.root, LogicalOperator::Ask(_))
+    }
 }
 
 /// A logical operator in the query plan.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum LogicalOperator {
     /// Scan all nodes, optionally filtered by label.
     NodeScan(NodeScanOp),
@@ -87,6 +98,12 @@ pub enum LogicalOperator {
     /// Scan RDF triples matching a pattern.
     TripleScan(TripleScanOp),
 
+    /// Match resources linked by a chain of one predicate (`p+`, `p*`, `p?`).
+    TriplePath(TriplePathOp),
+
+    /// Inline rows of variable bindings (SPARQL `VALUES`).
+    Values(ValuesOp),
+
     /// Union of multiple result sets.
     Union(UnionOp),
 
@@ -108,6 +125,21 @@ pub enum LogicalOperator {
     /// Find shortest path between nodes.
     ShortestPath(ShortestPathOp),
 
+    /// Register a (possibly composite) property index in the catalog.
+    CreateIndex(CreateIndexOp),
+
+    /// Add a schema constraint on a label's property.
+    CreateConstraint(CreateConstraintOp),
+
+    /// Call a registered procedure and stream its result rows.
+    CallProcedure(CallProcedureOp),
+
+    /// Instantiate SPARQL CONSTRUCT templates for each input row.
+    Construct(ConstructOp),
+
+    /// Answer a SPARQL ASK: whether the input produces any row.
+    Ask(AskOp),
+
     // ==================== SPARQL Update Operators ====================
     /// Insert RDF triples.
     InsertTriple(InsertTripleOp),
@@ -141,8 +173,180 @@ pub enum LogicalOperator {
     AddGraph(AddGraphOp),
 }
 
+impl LogicalOperator {
+    /// Returns `true` if `variable` is bound by this operator or one of its
+    /// inputs, so a later clause can refer to it instead of creating it.
+    #[must_use]
+    pub fn binds_variable(&self, variable: &str) -> bool {
+        match self {
+            Self::NodeScan(scan) => {
+                scan.variable == variable
+                    || scan
+                        .input
+                        .as_ref()
+                        .is_some_and(|i| i.binds_variable(variable))
+            }
+            Self::EdgeScan(scan) => {
+                scan.variable == variable
+                    || scan
+                        .input
+                        .as_ref()
+                        .is_some_and(|i| i.binds_variable(variable))
+            }
+            Self::Expand(expand) => {
+                expand.to_variable == variable
+                    || expand.edge_variable.as_deref() == Some(variable)
+                    || expand.input.binds_variable(variable)
+            }
+            Self::CreateNode(create) => {
+                create.variable == variable
+                    || create
+                        .input
+                        .as_ref()
+                        .is_some_and(|i| i.binds_variable(variable))
+            }
+            Self::CreateEdge(create) => {
+                create.variable.as_deref() == Some(variable)
+                    || create.input.binds_variable(variable)
+            }
+            Self::Merge(merge) => {
+                merge.variable == variable || merge.input.binds_variable(variable)
+            }
+            Self::Unwind(unwind) => {
+                unwind.variable == variable || unwind.input.binds_variable(variable)
+            }
+            Self::Bind(bind) => bind.variable == variable || bind.input.binds_variable(variable),
+            Self::Project(project) => project.projections.iter().any(|p| match &p.alias {
+                Some(alias) => alias == variable,
+                None => matches!(&p.expression, LogicalExpression::Variable(v) if v == variable),
+            }),
+            Self::Return(ret) => ret.items.iter().any(|item| match &item.alias {
+                Some(alias) => alias == variable,
+                None => matches!(&item.expression, LogicalExpression::Variable(v) if v == variable),
+            }),
+            Self::Aggregate(agg) => {
+                agg.group_by
+                    .iter()
+                    .any(|e| matches!(e, LogicalExpression::Variable(v) if v == variable))
+                    || agg
+                        .aggregates
+                        .iter()
+                        .any(|a| a.alias.as_deref() == Some(variable))
+            }
+            Self::ShortestPath(sp) => {
+                sp.path_alias == variable || sp.input.binds_variable(variable)
+            }
+            Self::TripleScan(scan) => {
+                [&scan.subject, &scan.predicate, &scan.object]
+                    .into_iter()
+                    .chain(scan.graph.as_ref())
+                    .any(|c| matches!(c, TripleComponent::Variable(v) if v == variable))
+                    || scan
+                        .input
+                        .as_ref()
+                        .is_some_and(|i| i.binds_variable(variable))
+            }
+            Self::TriplePath(path) => [&path.subject, &path.object]
+                .into_iter()
+                .any(|c| matches!(c, TripleComponent::Variable(v) if v == variable)),
+            Self::Values(values) => values.variables.iter().any(|v| v == variable),
+            Self::CallProcedure(call) => call.yields.iter().any(|y| y == variable),
+            Self::Filter(filter) => filter.input.binds_variable(variable),
+            Self::Limit(limit) => limit.input.binds_variable(variable),
+            Self::Skip(skip) => skip.input.binds_variable(variable),
+            Self::Sort(sort) => sort.input.binds_variable(variable),
+            Self::Distinct(distinct) => distinct.input.binds_variable(variable),
+            Self::SetProperty(set) => set.input.binds_variable(variable),
+            Self::AddLabel(add) => add.input.binds_variable(variable),
+            Self::RemoveLabel(remove) => remove.input.binds_variable(variable),
+            Self::DeleteNode(delete) => delete.input.binds_variable(variable),
+            Self::DeleteEdge(delete) => delete.input.binds_variable(variable),
+            Self::Construct(construct) => construct.input.binds_variable(variable),
+            Self::Ask(ask) => ask.input.binds_variable(variable),
+            Self::InsertTriple(insert) => insert
+                .input
+                .as_ref()
+                .is_some_and(|i| i.binds_variable(variable)),
+            Self::DeleteTriple(delete) => delete
+                .input
+                .as_ref()
+                .is_some_and(|i| i.binds_variable(variable)),
+            Self::Modify(modify) => modify.where_clause.binds_variable(variable),
+            Self::Join(join) => {
+                join.left.binds_variable(variable) || join.right.binds_variable(variable)
+            }
+            Self::LeftJoin(join) => {
+                join.left.binds_variable(variable) || join.right.binds_variable(variable)
+            }
+            Self::AntiJoin(join) => join.left.binds_variable(variable),
+            Self::Union(union) => union.inputs.iter().any(|i| i.binds_variable(variable)),
+            Self::Empty
+            | Self::CreateIndex(_)
+            | Self::CreateConstraint(_)
+            | Self::ClearGraph(_)
+            | Self::CreateGraph(_)
+            | Self::DropGraph(_)
+            | Self::LoadGraph(_)
+            | Self::CopyGraph(_)
+            | Self::MoveGraph(_)
+            | Self::AddGraph(_) => false,
+        }
+    }
+
+    /// Returns `true` if neither this operator nor any of its inputs
+    /// modifies the graph or the schema.
+    #[must_use]
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            Self::NodeScan(scan) => scan.input.as_deref().is_none_or(Self::is_read_only),
+            Self::EdgeScan(scan) => scan.input.as_deref().is_none_or(Self::is_read_only),
+            Self::TripleScan(scan) => scan.input.as_deref().is_none_or(Self::is_read_only),
+            Self::TriplePath(_) => true,
+            Self::Expand(expand) => expand.input.is_read_only(),
+            Self::Filter(filter) => filter.input.is_read_only(),
+            Self::Project(project) => project.input.is_read_only(),
+            Self::Aggregate(agg) => agg.input.is_read_only(),
+            Self::Limit(limit) => limit.input.is_read_only(),
+            Self::Skip(skip) => skip.input.is_read_only(),
+            Self::Sort(sort) => sort.input.is_read_only(),
+            Self::Distinct(distinct) => distinct.input.is_read_only(),
+            Self::Return(ret) => ret.input.is_read_only(),
+            Self::Bind(bind) => bind.input.is_read_only(),
+            Self::Unwind(unwind) => unwind.input.is_read_only(),
+            Self::ShortestPath(sp) => sp.input.is_read_only(),
+            Self::Construct(construct) => construct.input.is_read_only(),
+            Self::Ask(ask) => ask.input.is_read_only(),
+            Self::Join(join) => join.left.is_read_only() && join.right.is_read_only(),
+            Self::LeftJoin(join) => join.left.is_read_only() && join.right.is_read_only(),
+            Self::AntiJoin(join) => join.left.is_read_only() && join.right.is_read_only(),
+            Self::Union(union) => union.inputs.iter().all(Self::is_read_only),
+            Self::Empty | Self::Values(_) | Self::CallProcedure(_) => true,
+            Self::CreateNode(_)
+            | Self::CreateEdge(_)
+            | Self::DeleteNode(_)
+            | Self::DeleteEdge(_)
+            | Self::SetProperty(_)
+            | Self::AddLabel(_)
+            | Self::RemoveLabel(_)
+            | Self::Merge(_)
+            | Self::CreateIndex(_)
+            | Self::CreateConstraint(_)
+            | Self::InsertTriple(_)
+            | Self::DeleteTriple(_)
+            | Self::Modify(_)
+            | Self::ClearGraph(_)
+            | Self::CreateGraph(_)
+            | Self::DropGraph(_)
+            | Self::LoadGraph(_)
+            | Self::CopyGraph(_)
+            | Self::MoveGraph(_)
+            | Self::AddGraph(_) => false,
+        }
+    }
+}
+
 /// Scan nodes from the graph.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct NodeScanOp {
     /// Variable name to bind the node to.
     pub variable: String,
@@ -153,7 +357,7 @@ pub struct NodeScanOp {
 }
 
 /// Scan edges from the graph.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EdgeScanOp {
     /// Variable name to bind the edge to.
     pub variable: String,
@@ -164,7 +368,7 @@ pub struct EdgeScanOp {
 }
 
 /// Expand from nodes to their neighbors.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ExpandOp {
     /// Source node variable.
     pub from_variable: String,
@@ -176,7 +380,8 @@ pub struct ExpandOp {
     pub direction: ExpandDirection,
     /// Optional edge type filter.
     pub edge_type: Option<String>,
-    /// Minimum hops (for variable-length patterns).
+    /// Minimum hops (for variable-length patterns). Zero admits the
+    /// zero-length path, which binds the target to the source node.
     pub min_hops: u32,
     /// Maximum hops (for variable-length patterns).
     pub max_hops: Option<u32>,
@@ -199,7 +404,7 @@ pub enum ExpandDirection {
 }
 
 /// Join two inputs.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct JoinOp {
     /// Left input.
     pub left: Box<LogicalOperator>,
@@ -231,7 +436,7 @@ pub enum JoinType {
 }
 
 /// A join condition.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct JoinCondition {
     /// Left expression.
     pub left: LogicalExpression,
@@ -240,7 +445,7 @@ pub struct JoinCondition {
 }
 
 /// Aggregate with grouping.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AggregateOp {
     /// Group by expressions.
     pub group_by: Vec<LogicalExpression>,
@@ -253,7 +458,7 @@ pub struct AggregateOp {
 }
 
 /// An aggregate expression.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AggregateExpr {
     /// Aggregate function.
     pub function: AggregateFunction,
@@ -267,6 +472,48 @@ pub struct AggregateExpr {
     pub percentile: Option<f64>,
 }
 
+impl AggregateExpr {
+    /// Returns the name of the column this aggregate produces.
+    #[must_use]
+    pub fn output_column(&self) -> String {
+        self.alias
+            .clone()
+            .unwrap_or_else(|| format!("{:?}(...)", self.function).to_lowercase())
+    }
+
+    /// Reads the percentile argument of `percentile_disc`/`percentile_cont`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error unless the argument is a numeric literal between 0
+    /// and 1.
+    pub fn percentile_argument(arg: &LogicalExpression) -> Result<f64> {
+        let literal = |expr: &LogicalExpression| match expr {
+            LogicalExpression::Literal(Value::Float64(p)) => Some(*p),
+            LogicalExpression::Literal(Value::Int64(p)) => Some(*p as f64),
+            _ => None,
+        };
+        let p = match arg {
+            LogicalExpression::Unary {
+                op: UnaryOp::Neg,
+                operand,
+            } => literal(operand).map(|p| -p),
+            _ => literal(arg),
+        };
+        match p {
+            Some(p) if (0.0..=1.0).contains(&p) => Ok(p),
+            Some(p) => Err(Error::Query(QueryError::new(
+                QueryErrorKind::Semantic,
+                format!("percentile must be between 0 and 1, got {p}"),
+            ))),
+            None => Err(Error::Query(QueryError::new(
+                QueryErrorKind::Semantic,
+                "percentile must be a numeric literal",
+            ))),
+        }
+    }
+}
+
 /// Aggregate function.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AggregateFunction {
@@ -295,7 +542,7 @@ pub enum AggregateFunction {
 }
 
 /// Filter rows based on a predicate.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct FilterOp {
     /// The filter predicate.
     pub predicate: LogicalExpression,
@@ -304,7 +551,7 @@ pub struct FilterOp {
 }
 
 /// Project specific columns.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ProjectOp {
     /// Columns to project.
     pub projections: Vec<Projection>,
@@ -313,7 +560,7 @@ pub struct ProjectOp {
 }
 
 /// A single projection (column selection or computation).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Projection {
     /// Expression to compute.
     pub expression: LogicalExpression,
@@ -322,7 +569,7 @@ pub struct Projection {
 }
 
 /// Limit the number of results.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LimitOp {
     /// Maximum number of rows to return.
     pub count: usize,
@@ -331,7 +578,7 @@ pub struct LimitOp {
 }
 
 /// Skip a number of results.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SkipOp {
     /// Number of rows to skip.
     pub count: usize,
@@ -340,7 +587,7 @@ pub struct SkipOp {
 }
 
 /// Sort results.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SortOp {
     /// Sort keys.
     pub keys: Vec<SortKey>,
@@ -349,7 +596,7 @@ pub struct SortOp {
 }
 
 /// A sort key.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SortKey {
     /// Expression to sort by.
     pub expression: LogicalExpression,
@@ -367,7 +614,7 @@ pub enum SortOrder {
 }
 
 /// Remove duplicate results.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DistinctOp {
     /// Input operator.
     pub input: Box<LogicalOperator>,
@@ -377,7 +624,7 @@ pub struct DistinctOp {
 }
 
 /// Create a new node.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CreateNodeOp {
     /// Variable name to bind the created node to.
     pub variable: String,
@@ -390,7 +637,7 @@ pub struct CreateNodeOp {
 }
 
 /// Create a new edge.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CreateEdgeOp {
     /// Variable name to bind the created edge to.
     pub variable: Option<String>,
@@ -407,7 +654,7 @@ pub struct CreateEdgeOp {
 }
 
 /// Delete a node.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DeleteNodeOp {
     /// Variable of the node to delete.
     pub variable: String,
@@ -418,7 +665,7 @@ pub struct DeleteNodeOp {
 }
 
 /// Delete an edge.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DeleteEdgeOp {
     /// Variable of the edge to delete.
     pub variable: String,
@@ -427,7 +674,7 @@ pub struct DeleteEdgeOp {
 }
 
 /// Set properties on a node or edge.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SetPropertyOp {
     /// Variable of the entity to update.
     pub variable: String,
@@ -440,7 +687,7 @@ pub struct SetPropertyOp {
 }
 
 /// Add labels to a node.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AddLabelOp {
     /// Variable of the node to update.
     pub variable: String,
@@ -451,7 +698,7 @@ pub struct AddLabelOp {
 }
 
 /// Remove labels from a node.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RemoveLabelOp {
     /// Variable of the node to update.
     pub variable: String,
@@ -464,7 +711,7 @@ pub struct RemoveLabelOp {
 // ==================== RDF/SPARQL Operators ====================
 
 /// Scan RDF triples matching a pattern.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TripleScanOp {
     /// Subject pattern (variable name or IRI).
     pub subject: TripleComponent,
@@ -478,8 +725,29 @@ pub struct TripleScanOp {
     pub input: Option<Box<LogicalOperator>>,
 }
 
+/// Matches pairs of resources linked by a chain of one predicate.
+///
+/// This is the RDF form of a quantified property path: `p+` has hop bounds
+/// `1..`, `p*` has `0..`, and `p?` has `0..1`. Each pair is matched once,
+/// however many paths link it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriplePathOp {
+    /// Start of the path (variable name, IRI, or literal).
+    pub subject: TripleComponent,
+    /// The predicate IRI each step follows.
+    pub predicate: String,
+    /// Whether each step follows the predicate from object to subject.
+    pub inverse: bool,
+    /// End of the path (variable name, IRI, or literal).
+    pub object: TripleComponent,
+    /// Minimum number of steps; 0 also matches each resource to itself.
+    pub min_hops: u32,
+    /// Maximum number of steps (`None` for unbounded).
+    pub max_hops: Option<u32>,
+}
+
 /// A component of a triple pattern.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TripleComponent {
     /// A variable to bind.
     Variable(String),
@@ -489,15 +757,27 @@ pub enum TripleComponent {
     Literal(Value),
 }
 
+/// Inline binding rows, from a SPARQL `VALUES` block.
+///
+/// Joined with the surrounding pattern on shared variables. A `Null` cell
+/// is `UNDEF`: the variable is left unbound and matches any value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValuesOp {
+    /// Variables bound by each row, in column order.
+    pub variables: Vec<String>,
+    /// One value per variable for each row.
+    pub rows: Vec<Vec<Value>>,
+}
+
 /// Union of multiple result sets.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct UnionOp {
     /// Inputs to union together.
     pub inputs: Vec<LogicalOperator>,
 }
 
 /// Left outer join for OPTIONAL patterns.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LeftJoinOp {
     /// Left (required) input.
     pub left: Box<LogicalOperator>,
@@ -508,7 +788,7 @@ pub struct LeftJoinOp {
 }
 
 /// Anti-join for MINUS patterns.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AntiJoinOp {
     /// Left input (results to keep if no match on right).
     pub left: Box<LogicalOperator>,
@@ -517,7 +797,7 @@ pub struct AntiJoinOp {
 }
 
 /// Bind a variable to an expression.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BindOp {
     /// Expression to compute.
     pub expression: LogicalExpression,
@@ -531,7 +811,7 @@ pub struct BindOp {
 ///
 /// For each input row, evaluates the expression (which should return a list)
 /// and emits one row for each element in the list.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct UnwindOp {
     /// The list expression to unwind.
     pub expression: LogicalExpression,
@@ -546,7 +826,7 @@ pub struct UnwindOp {
 /// MERGE tries to match a pattern in the graph. If found, returns the existing
 /// elements (optionally applying ON MATCH SET). If not found, creates the pattern
 /// (optionally applying ON CREATE SET).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct MergeOp {
     /// The node to merge.
     pub variable: String,
@@ -566,7 +846,7 @@ pub struct MergeOp {
 ///
 /// This operator uses Dijkstra's algorithm to find the shortest path(s)
 /// between a source node and a target node, optionally filtered by edge type.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ShortestPathOp {
     /// Input operator providing source/target nodes.
     pub input: Box<LogicalOperator>,
@@ -584,10 +864,56 @@ pub struct ShortestPathOp {
     pub all_paths: bool,
 }
 
+/// Register a property index on a label.
+///
+/// With more than one property this is a composite index, ordered by the
+/// properties as listed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateIndexOp {
+    /// Label the index applies to.
+    pub label: String,
+    /// Indexed property names, in key order.
+    pub properties: Vec<String>,
+}
+
+/// Add a schema constraint, checked against existing data first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateConstraintOp {
+    /// Label the constraint applies to.
+    pub label: String,
+    /// Constrained property name.
+    pub property: String,
+    /// What the constraint requires.
+    pub kind: ConstraintKind,
+}
+
+/// What a constraint requires of a property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintKind {
+    /// No two nodes with the label share a value.
+    Unique,
+    /// Every node with the label has a non-null value.
+    NotNull,
+}
+
+/// Call a procedure, such as a registered graph algorithm.
+///
+/// A leaf: the procedure's rows are the start of the pipeline, and the
+/// yielded columns are bound as variables for the rest of the query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallProcedureOp {
+    /// Procedure name.
+    pub name: String,
+    /// Positional arguments, matched against the procedure's parameters.
+    pub arguments: Vec<LogicalExpression>,
+    /// Yielded result columns, in output order.
+    pub yields: Vec<String>,
+}
+
 // ==================== SPARQL Update Operators ====================
 
 /// Insert RDF triples.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct InsertTripleOp {
     /// Subject of the triple.
     pub subject: TripleComponent,
@@ -602,7 +928,7 @@ pub struct InsertTripleOp {
 }
 
 /// Delete RDF triples.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DeleteTripleOp {
     /// Subject pattern.
     pub subject: TripleComponent,
@@ -624,7 +950,7 @@ pub struct DeleteTripleOp {
 /// 3. Applies INSERT templates using the SAME bindings
 ///
 /// This ensures DELETE and INSERT see consistent data.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ModifyOp {
     /// DELETE triple templates (patterns with variables).
     pub delete_templates: Vec<TripleTemplate>,
@@ -637,7 +963,7 @@ pub struct ModifyOp {
 }
 
 /// A triple template for DELETE/INSERT operations.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TripleTemplate {
     /// Subject (may be a variable).
     pub subject: TripleComponent,
@@ -649,8 +975,30 @@ pub struct TripleTemplate {
     pub graph: Option<String>,
 }
 
+/// SPARQL CONSTRUCT: instantiates triple templates for each input row.
+///
+/// Blank nodes in the templates are `_:`-prefixed variables, minted fresh
+/// for every row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstructOp {
+    /// Triple templates (patterns with variables).
+    pub templates: Vec<TripleTemplate>,
+    /// WHERE clause that provides variable bindings.
+    pub input: Box<LogicalOperator>,
+}
+
+/// SPARQL ASK: tests whether the WHERE clause has any solution.
+///
+/// An `Empty` input is the empty group pattern, which has exactly one
+/// solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AskOp {
+    /// WHERE clause to test.
+    pub input: Box<LogicalOperator>,
+}
+
 /// Clear all triples from a graph.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ClearGraphOp {
     /// Target graph (None = default graph, Some("") = all named, Some(iri) = specific graph).
     pub graph: Option<String>,
@@ -659,7 +1007,7 @@ pub struct ClearGraphOp {
 }
 
 /// Create a new named graph.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CreateGraphOp {
     /// IRI of the graph to create.
     pub graph: String,
@@ -668,7 +1016,7 @@ pub struct CreateGraphOp {
 }
 
 /// Drop (remove) a named graph.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DropGraphOp {
     /// Target graph (None = default graph).
     pub graph: Option<String>,
@@ -677,7 +1025,7 @@ pub struct DropGraphOp {
 }
 
 /// Load data from a URL into a graph.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LoadGraphOp {
     /// Source URL to load data from.
     pub source: String,
@@ -688,7 +1036,7 @@ pub struct LoadGraphOp {
 }
 
 /// Copy triples from one graph to another.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CopyGraphOp {
     /// Source graph.
     pub source: Option<String>,
@@ -699,7 +1047,7 @@ pub struct CopyGraphOp {
 }
 
 /// Move triples from one graph to another.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct MoveGraphOp {
     /// Source graph.
     pub source: Option<String>,
@@ -710,7 +1058,7 @@ pub struct MoveGraphOp {
 }
 
 /// Add (merge) triples from one graph to another.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AddGraphOp {
     /// Source graph.
     pub source: Option<String>,
@@ -721,7 +1069,7 @@ pub struct AddGraphOp {
 }
 
 /// Return results (terminal operator).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ReturnOp {
     /// Items to return.
     pub items: Vec<ReturnItem>,
@@ -732,7 +1080,7 @@ pub struct ReturnOp {
 }
 
 /// A single return item.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ReturnItem {
     /// Expression to return.
     pub expression: LogicalExpression,
@@ -741,7 +1089,7 @@ pub struct ReturnItem {
 }
 
 /// A logical expression.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum LogicalExpression {
     /// A literal value.
     Literal(Value),
@@ -848,6 +1196,146 @@ pub enum LogicalExpression {
 
     /// COUNT subquery.
     CountSubquery(Box<LogicalOperator>),
+
+    /// IN subquery: `expr IN (MATCH ... RETURN x)`.
+    ///
+    /// The subquery must return a single column. Variables it shares with the
+    /// enclosing query are correlated with the outer row, and its returned
+    /// value and WHERE may also read outer variables it doesn't bind.
+    InSubquery {
+        /// The value to look for.
+        expr: Box<LogicalExpression>,
+        /// The subquery producing the candidate values.
+        subquery: Box<LogicalOperator>,
+    },
+}
+
+impl LogicalExpression {
+    /// Collects the variables this expression reads.
+    ///
+    /// Variables scoped to a list comprehension or a subquery are left out.
+    pub fn collect_variables(&self, vars: &mut HashSet<String>) {
+        match self {
+            Self::Variable(name) | Self::Labels(name) | Self::Type(name) | Self::Id(name) => {
+                vars.insert(name.clone());
+            }
+            Self::Property { variable, .. } => {
+                vars.insert(variable.clone());
+            }
+            Self::Binary { left, right, .. } => {
+                left.collect_variables(vars);
+                right.collect_variables(vars);
+            }
+            Self::Unary { operand, .. } => operand.collect_variables(vars),
+            Self::FunctionCall { args, .. } | Self::List(args) => {
+                for arg in args {
+                    arg.collect_variables(vars);
+                }
+            }
+            Self::Map(pairs) => {
+                for (_, value) in pairs {
+                    value.collect_variables(vars);
+                }
+            }
+            Self::IndexAccess { base, index } => {
+                base.collect_variables(vars);
+                index.collect_variables(vars);
+            }
+            Self::SliceAccess { base, start, end } => {
+                base.collect_variables(vars);
+                for bound in [start, end].into_iter().flatten() {
+                    bound.collect_variables(vars);
+                }
+            }
+            Self::Case {
+                operand,
+                when_clauses,
+                else_clause,
+            } => {
+                for expr in operand.iter().chain(else_clause) {
+                    expr.collect_variables(vars);
+                }
+                for (cond, result) in when_clauses {
+                    cond.collect_variables(vars);
+                    result.collect_variables(vars);
+                }
+            }
+            Self::ListComprehension {
+                variable,
+                list_expr,
+                filter_expr,
+                map_expr,
+            } => {
+                list_expr.collect_variables(vars);
+                let mut scoped = HashSet::new();
+                for expr in filter_expr.iter().chain([map_expr]) {
+                    expr.collect_variables(&mut scoped);
+                }
+                scoped.remove(variable);
+                vars.extend(scoped);
+            }
+            Self::InSubquery { expr, .. } => expr.collect_variables(vars),
+            Self::Literal(_)
+            | Self::Parameter(_)
+            | Self::ExistsSubquery(_)
+            | Self::CountSubquery(_) => {}
+        }
+    }
+
+    /// Splits a subquery condition that reads variables of the enclosing query
+    /// into an `(outer, inner)` pair of values that must be equal.
+    ///
+    /// `binds` says whether the subquery binds a variable. A condition on outer
+    /// variables alone pairs with `true`, and an equality pairs its outer side
+    /// with its inner side. Returns `None` for any other condition, which a
+    /// join on equal values can't evaluate.
+    #[must_use]
+    pub fn correlation(
+        &self,
+        binds: impl Fn(&str) -> bool,
+    ) -> Option<(LogicalExpression, LogicalExpression)> {
+        let bound = |expr: &LogicalExpression| {
+            let mut vars = HashSet::new();
+            expr.collect_variables(&mut vars);
+            let inner = vars.iter().filter(|v| binds(v)).count();
+            (inner == 0, inner == vars.len())
+        };
+
+        if bound(self).0 {
+            return Some((self.clone(), Self::Literal(Value::Bool(true))));
+        }
+        let Self::Binary {
+            left,
+            op: BinaryOp::Eq,
+            right,
+        } = self
+        else {
+            return None;
+        };
+        match (bound(left), bound(right)) {
+            ((true, _), (_, true)) => Some(((**left).clone(), (**right).clone())),
+            ((_, true), (true, _)) => Some(((**right).clone(), (**left).clone())),
+            _ => None,
+        }
+    }
+}
+
+/// Splits a predicate into its top-level AND conjuncts.
+pub fn split_conjuncts<'a>(
+    expr: &'a LogicalExpression,
+    conjuncts: &mut Vec<&'a LogicalExpression>,
+) {
+    if let LogicalExpression::Binary {
+        left,
+        op: BinaryOp::And,
+        right,
+    } = expr
+    {
+        split_conjuncts(left, conjuncts);
+        split_conjuncts(right, conjuncts);
+    } else {
+        conjuncts.push(expr);
+    }
 }
 
 /// Binary operator.
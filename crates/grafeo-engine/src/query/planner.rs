@@ -6,31 +6,48 @@
 
 use crate::query::plan::{
     AddLabelOp, AggregateFunction as LogicalAggregateFunction, AggregateOp, AntiJoinOp, BinaryOp,
-    CreateEdgeOp, CreateNodeOp, DeleteEdgeOp, DeleteNodeOp, DistinctOp, ExpandDirection, ExpandOp,
-    FilterOp, JoinOp, JoinType, LeftJoinOp, LimitOp, LogicalExpression, LogicalOperator,
-    LogicalPlan, MergeOp, NodeScanOp, RemoveLabelOp, ReturnOp, SetPropertyOp, ShortestPathOp,
-    SkipOp, SortOp, SortOrder, UnaryOp, UnionOp, UnwindOp,
+    CallProcedureOp, ConstraintKind, CreateConstraintOp, CreateEdgeOp, CreateIndexOp, CreateNodeOp,
+    DeleteEdgeOp, DeleteNodeOp, DistinctOp, ExpandDirection, ExpandOp, FilterOp, JoinOp, JoinType,
+    LeftJoinOp, LimitOp, LogicalExpression, LogicalOperator, LogicalPlan, MergeOp, NodeScanOp,
+    RemoveLabelOp, ReturnOp, SetPropertyOp, ShortestPathOp, SkipOp, SortOp, SortOrder, UnaryOp,
+    UnionOp, UnwindOp, split_conjuncts,
 };
+use grafeo_adapters::plugins::algorithms::GraphAlgorithm;
+use grafeo_adapters::plugins::{AlgorithmResult, Parameters, PluginRegistry};
+use grafeo_adapters::storage::AccessPattern;
+use grafeo_common::memory::buffer::BufferManager;
 use grafeo_common::types::LogicalType;
-use grafeo_common::types::{EpochId, TxId};
-use grafeo_common::utils::error::{Error, Result};
+use grafeo_common::types::{EpochId, IndexId, NodeId, TxId, Value};
+use grafeo_common::utils::error::{Error, QueryError, QueryErrorKind, Result};
 use grafeo_core::execution::AdaptiveContext;
+use grafeo_core::execution::DataChunk;
+use grafeo_core::execution::ExecutionMemoryContext;
+use grafeo_core::execution::SpillManager;
+use grafeo_core::execution::chunk::{DEFAULT_CHUNK_SIZE, DataChunkBuilder};
+use grafeo_core::execution::operators::OperatorError;
 use grafeo_core::execution::operators::{
     AddLabelOperator, AggregateExpr as PhysicalAggregateExpr,
-    AggregateFunction as PhysicalAggregateFunction, BinaryFilterOp, CreateEdgeOperator,
-    CreateNodeOperator, DeleteEdgeOperator, DeleteNodeOperator, DistinctOperator, ExpandOperator,
+    AggregateFunction as PhysicalAggregateFunction, BinaryFilterOp, CancellableOperator,
+    CancellationToken, ConstraintChecker, CreateEdgeOperator, CreateNodeOperator,
+    DeleteEdgeOperator, DeleteNodeOperator, DistinctOperator, EqualityCondition, ExpandOperator,
     ExpressionPredicate, FilterExpression, FilterOperator, HashAggregateOperator, HashJoinOperator,
-    JoinType as PhysicalJoinType, LimitOperator, MergeOperator, NestedLoopJoinOperator, NullOrder,
-    Operator, ProjectExpr, ProjectOperator, PropertySource, RemoveLabelOperator, ScanOperator,
-    SetPropertyOperator, ShortestPathOperator, SimpleAggregateOperator, SkipOperator,
-    SortDirection, SortKey as PhysicalSortKey, SortOperator, UnaryFilterOp, UnionOperator,
-    UnwindOperator, VariableLengthExpandOperator,
+    IndexLookup, IndexNestedLoopJoinOperator, JoinCondition, JoinType as PhysicalJoinType,
+    LimitOperator, MergeJoinOperator, MergeOperator, NestedLoopJoinOperator, NullOrder, Operator,
+    OperatorProfile, ProfiledOperator, ProjectExpr, ProjectOperator, PropertySource, RandomSource,
+    RemoveLabelOperator, ScanOperator, SetPropertyOperator, ShortestPathOperator,
+    SimpleAggregateOperator, SkipOperator, SortDirection, SortKey as PhysicalSortKey, SortOperator,
+    SpillableHashJoinOperator, TopNOperator, UnaryFilterOp, UnionOperator, UnwindOperator,
+    VariableLengthExpandOperator,
 };
 use grafeo_core::graph::{Direction, lpg::LpgStore};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::transaction::TransactionManager;
+use crate::catalog::{Catalog, CatalogError, IndexPrefixMatch, IndexType};
+use crate::query::optimizer::{CardinalityEstimator, CostModel};
+
+use crate::transaction::{EntityId, TransactionManager};
 
 /// Converts a logical plan to a physical operator tree.
 pub struct Planner {
@@ -44,8 +61,31 @@ pub struct Planner {
     viewing_epoch: EpochId,
     /// Counter for generating unique anonymous edge column names.
     anon_edge_counter: std::cell::Cell<u32>,
+    /// Schema catalog for index DDL and index selection.
+    catalog: Option<Arc<Catalog>>,
+    /// Per-operator profiles for `EXPLAIN ANALYZE`, keyed by the address of
+    /// the logical operator each physical operator was planned from.
+    profiles: Option<std::cell::RefCell<HashMap<usize, Arc<OperatorProfile>>>>,
+    /// Source for `rand()` in the planned expressions.
+    random: Arc<RandomSource>,
+    /// Estimator for join inputs; `None` uses the store statistics.
+    estimator: Option<CardinalityEstimator>,
+    /// Joins with an input estimated below this many rows use a nested loop.
+    nested_loop_threshold: f64,
+    /// Registry of the procedures `CALL` can run.
+    plugins: Option<Arc<PluginRegistry>>,
+    /// Token every planned operator checks before pulling its next chunk.
+    cancellation: Option<CancellationToken>,
+    /// Budget sorts reserve the rows they hold from.
+    buffer_manager: Option<Arc<BufferManager>>,
+    /// Spill files of this plan's hash joins, set up by the first one.
+    spill_manager: std::cell::OnceCell<Option<Arc<SpillManager>>>,
 }
 
+/// Default row estimate below which a join input counts as tiny enough for a
+/// nested-loop join.
+pub const DEFAULT_NESTED_LOOP_THRESHOLD: f64 = 32.0;
+
 impl Planner {
     /// Creates a new planner with the given store.
     ///
@@ -60,6 +100,15 @@ impl Planner {
             tx_id: None,
             viewing_epoch: epoch,
             anon_edge_counter: std::cell::Cell::new(0),
+            catalog: None,
+            profiles: None,
+            random: Arc::new(RandomSource::from_entropy()),
+            estimator: None,
+            nested_loop_threshold: DEFAULT_NESTED_LOOP_THRESHOLD,
+            plugins: None,
+            cancellation: None,
+            buffer_manager: None,
+            spill_manager: std::cell::OnceCell::new(),
         }
     }
 
@@ -84,9 +133,99 @@ impl Planner {
             tx_id,
             viewing_epoch,
             anon_edge_counter: std::cell::Cell::new(0),
+            catalog: None,
+            profiles: None,
+            random: Arc::new(RandomSource::from_entropy()),
+            estimator: None,
+            nested_loop_threshold: DEFAULT_NESTED_LOOP_THRESHOLD,
+            plugins: None,
+            cancellation: None,
+            buffer_manager: None,
+            spill_manager: std::cell::OnceCell::new(),
         }
     }
 
+    /// Sets the catalog used for `CREATE INDEX` and index selection.
+    #[must_use]
+    pub fn with_catalog(mut self, catalog: Arc<Catalog>) -> Self {
+        self.catalog = Some(catalog);
+        self
+    }
+
+    /// Sets the registry whose graph algorithms `CALL` runs.
+    #[must_use]
+    pub fn with_plugins(mut self, plugins: Arc<PluginRegistry>) -> Self {
+        self.plugins = Some(plugins);
+        self
+    }
+
+    /// Sets the source `rand()` draws from, for reproducible results.
+    #[must_use]
+    pub fn with_random_source(mut self, random: Arc<RandomSource>) -> Self {
+        self.random = random;
+        self
+    }
+
+    /// Sets the estimator used to size join inputs, instead of one built
+    /// from the store statistics.
+    #[must_use]
+    pub fn with_cardinality_estimator(mut self, estimator: CardinalityEstimator) -> Self {
+        self.estimator = Some(estimator);
+        self
+    }
+
+    /// Sets the row estimate below which a join input is small enough for a
+    /// nested-loop join.
+    #[must_use]
+    pub fn with_nested_loop_threshold(mut self, threshold: f64) -> Self {
+        self.nested_loop_threshold = threshold;
+        self
+    }
+
+    /// Stops the planned query once `timeout` has passed, counted from when
+    /// the plan first pulls a chunk.
+    ///
+    /// Every operator is wrapped in a [`CancellableOperator`], so blocking
+    /// operators stop while draining their inputs too, and running the plan
+    /// fails with [`Error::Timeout`](grafeo_common::utils::error::Error::Timeout).
+    /// `None` leaves the query unbounded.
+    #[must_use]
+    pub fn with_query_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.cancellation = timeout.map(CancellationToken::with_timeout);
+        self
+    }
+
+    /// Has sorts and hash joins reserve the rows they hold from `manager`.
+    ///
+    /// Sorts cannot spill, so one that is refused room fails the query with
+    /// [`Error::OutOfMemory`](grafeo_common::utils::error::Error::OutOfMemory)
+    /// rather than growing past the budget. Hash joins move build partitions
+    /// to the manager's spill path instead, or to the system temp directory
+    /// when it has none. `None` leaves both unbounded.
+    #[must_use]
+    pub fn with_buffer_manager(mut self, manager: Option<Arc<BufferManager>>) -> Self {
+        self.buffer_manager = manager;
+        self
+    }
+
+    /// Wraps every planned operator in a [`ProfiledOperator`].
+    ///
+    /// After the plan has run, [`profile`](Self::profile) returns the rows
+    /// and time recorded for each logical operator.
+    #[must_use]
+    pub fn with_profiling(mut self) -> Self {
+        self.profiles = Some(std::cell::RefCell::new(HashMap::new()));
+        self
+    }
+
+    /// Returns the profile recorded for the physical operator planned from
+    /// `op`, if profiling is on and `op` was planned by this planner.
+    #[must_use]
+    pub fn profile(&self, op: &LogicalOperator) -> Option<Arc<OperatorProfile>> {
+        let profiles = self.profiles.as_ref()?.borrow();
+        profiles.get(&(std::ptr::from_ref(op) as usize)).cloned()
+    }
+
     /// Returns the viewing epoch for this planner.
     #[must_use]
     pub fn viewing_epoch(&self) -> EpochId {
@@ -105,6 +244,12 @@ impl Planner {
         self.tx_manager.as_ref()
     }
 
+    /// Returns a reference to the catalog used for index selection, if any.
+    #[must_use]
+    pub fn catalog(&self) -> Option<&Arc<Catalog>> {
+        self.catalog.as_ref()
+    }
+
     /// Plans a logical plan into a physical operator.
     ///
     /// # Errors
@@ -301,8 +446,35 @@ impl Planner {
         }
     }
 
-    /// Plans a single logical operator.
+    /// Plans a single logical operator, wrapping it for profiling and
+    /// cancellation if enabled.
     fn plan_operator(&self, op: &LogicalOperator) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        let (operator, columns) = self.plan_unprofiled(op)?;
+        Ok((self.instrument(op, operator), columns))
+    }
+
+    /// Wraps the operator planned for `op` so it is profiled under `op` and
+    /// stops when the query is cancelled.
+    fn instrument(
+        &self,
+        op: &LogicalOperator,
+        mut operator: Box<dyn Operator>,
+    ) -> Box<dyn Operator> {
+        if let Some(profiles) = &self.profiles {
+            let profile = Arc::new(OperatorProfile::new());
+            profiles
+                .borrow_mut()
+                .insert(std::ptr::from_ref(op) as usize, Arc::clone(&profile));
+            operator = Box::new(ProfiledOperator::new(operator, profile));
+        }
+        if let Some(token) = &self.cancellation {
+            operator = Box::new(CancellableOperator::new(operator, token.clone()));
+        }
+        operator
+    }
+
+    /// Plans a single logical operator (and its inputs) without profiling it.
+    fn plan_unprofiled(&self, op: &LogicalOperator) -> Result<(Box<dyn Operator>, Vec<String>)> {
         match op {
             LogicalOperator::NodeScan(scan) => self.plan_node_scan(scan),
             LogicalOperator::Expand(expand) => self.plan_expand(expand),
@@ -328,6 +500,9 @@ impl Planner {
             LogicalOperator::RemoveLabel(remove_label) => self.plan_remove_label(remove_label),
             LogicalOperator::SetProperty(set_prop) => self.plan_set_property(set_prop),
             LogicalOperator::ShortestPath(sp) => self.plan_shortest_path(sp),
+            LogicalOperator::CreateIndex(create) => self.plan_create_index(create),
+            LogicalOperator::CreateConstraint(create) => self.plan_create_constraint(create),
+            LogicalOperator::CallProcedure(call) => self.plan_call_procedure(call),
             LogicalOperator::Empty => Err(Error::Internal("Empty plan".to_string())),
             _ => Err(Error::Internal(format!(
                 "Unsupported operator: {:?}",
@@ -338,11 +513,23 @@ impl Planner {
 
     /// Plans a node scan operator.
     fn plan_node_scan(&self, scan: &NodeScanOp) -> Result<(Box<dyn Operator>, Vec<String>)> {
-        let scan_op = if let Some(label) = &scan.label {
+        self.plan_indexed_node_scan(scan, None)
+    }
+
+    /// Plans a node scan that finds its nodes through `index`, if given.
+    fn plan_indexed_node_scan(
+        &self,
+        scan: &NodeScanOp,
+        index: Option<IndexLookup>,
+    ) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        let mut scan_op = if let Some(label) = &scan.label {
             ScanOperator::with_label(Arc::clone(&self.store), label)
         } else {
             ScanOperator::new(Arc::clone(&self.store))
         };
+        if let Some(lookup) = index {
+            scan_op = scan_op.with_index(lookup);
+        }
 
         // Apply MVCC context if available
         let scan_operator: Box<dyn Operator> =
@@ -460,6 +647,24 @@ impl Planner {
 
     /// Plans a RETURN clause.
     fn plan_return(&self, ret: &ReturnOp) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        // An Empty input was pruned by the optimizer: no rows, but keep the columns
+        if matches!(ret.input.as_ref(), LogicalOperator::Empty) {
+            let columns: Vec<String> = ret
+                .items
+                .iter()
+                .map(|item| {
+                    item.alias
+                        .clone()
+                        .unwrap_or_else(|| expression_to_string(&item.expression))
+                })
+                .collect();
+            let output_schema = self.derive_schema_from_columns(&columns);
+            let single_row: Box<dyn Operator> =
+                Box::new(grafeo_core::execution::operators::single_row::SingleRowOperator::new());
+            let operator = Box::new(LimitOperator::new(single_row, 0, output_schema));
+            return Ok((operator, columns));
+        }
+
         // Plan the input operator
         let (input_op, input_columns) = self.plan_operator(&ret.input)?;
 
@@ -500,8 +705,8 @@ impl Planner {
                             Error::Internal(format!("Variable '{}' not found in input", name))
                         })?;
                         projections.push(ProjectExpr::Column(col_idx));
-                        // Use Node type for variables (they could be nodes, edges, or values)
-                        output_types.push(LogicalType::Node);
+                        // Variables can be nodes, edges or plain values (e.g. from UNWIND)
+                        output_types.push(LogicalType::Any);
                     }
                     LogicalExpression::Property { variable, property } => {
                         let col_idx = *variable_columns.get(variable).ok_or_else(|| {
@@ -590,6 +795,14 @@ impl Planner {
                         // CASE can return any type - use Any
                         output_types.push(LogicalType::Any);
                     }
+                    LogicalExpression::Id(_) => {
+                        let filter_expr = self.convert_expression(&item.expression)?;
+                        projections.push(ProjectExpr::Expression {
+                            expr: filter_expr,
+                            variable_columns: variable_columns.clone(),
+                        });
+                        output_types.push(LogicalType::Int64);
+                    }
                     _ => {
                         return Err(Error::Internal(format!(
                             "Unsupported RETURN expression: {:?}",
@@ -599,12 +812,15 @@ impl Planner {
                 }
             }
 
-            let operator = Box::new(ProjectOperator::with_store(
-                input_op,
-                projections,
-                output_types,
-                Arc::clone(&self.store),
-            ));
+            let operator = Box::new(
+                ProjectOperator::with_store(
+                    input_op,
+                    projections,
+                    output_types,
+                    Arc::clone(&self.store),
+                )
+                .with_tx_context(self.viewing_epoch, self.tx_id),
+            );
 
             Ok((operator, columns))
         } else {
@@ -619,7 +835,8 @@ impl Planner {
                         Error::Internal(format!("Variable '{}' not found in input", name))
                     })?;
                     projections.push(ProjectExpr::Column(col_idx));
-                    output_types.push(LogicalType::Node);
+                    // Variables can be nodes, edges or plain values (e.g. aggregate columns)
+                    output_types.push(LogicalType::Any);
                 }
             }
 
@@ -676,54 +893,107 @@ impl Planner {
                 .unwrap_or_else(|| expression_to_string(&projection.expression));
             output_columns.push(col_name);
 
-            match &projection.expression {
-                LogicalExpression::Variable(name) => {
-                    let col_idx = *variable_columns.get(name).ok_or_else(|| {
-                        Error::Internal(format!("Variable '{}' not found in input", name))
-                    })?;
-                    projections.push(ProjectExpr::Column(col_idx));
-                    output_types.push(LogicalType::Node);
-                }
-                LogicalExpression::Property { variable, property } => {
-                    let col_idx = *variable_columns.get(variable).ok_or_else(|| {
-                        Error::Internal(format!("Variable '{}' not found in input", variable))
-                    })?;
-                    projections.push(ProjectExpr::PropertyAccess {
+            let (projection, output_type) =
+                self.projection_expr(&projection.expression, &variable_columns)?;
+            projections.push(projection);
+            output_types.push(output_type);
+        }
+
+        let operator = Box::new(
+            ProjectOperator::with_store(
+                input_op,
+                projections,
+                output_types,
+                Arc::clone(&self.store),
+            )
+            .with_tx_context(self.viewing_epoch, self.tx_id),
+        );
+
+        Ok((operator, output_columns))
+    }
+
+    /// Converts a projected expression into a physical projection and its output type.
+    fn projection_expr(
+        &self,
+        expr: &LogicalExpression,
+        variable_columns: &HashMap<String, usize>,
+    ) -> Result<(ProjectExpr, LogicalType)> {
+        match expr {
+            LogicalExpression::Variable(name) => {
+                let col_idx = *variable_columns.get(name).ok_or_else(|| {
+                    Error::Internal(format!("Variable '{}' not found in input", name))
+                })?;
+                Ok((ProjectExpr::Column(col_idx), LogicalType::Node))
+            }
+            LogicalExpression::Property { variable, property } => {
+                let col_idx = *variable_columns.get(variable).ok_or_else(|| {
+                    Error::Internal(format!("Variable '{}' not found in input", variable))
+                })?;
+                Ok((
+                    ProjectExpr::PropertyAccess {
                         column: col_idx,
                         property: property.clone(),
-                    });
-                    output_types.push(LogicalType::Any);
-                }
-                LogicalExpression::Literal(value) => {
-                    projections.push(ProjectExpr::Constant(value.clone()));
-                    output_types.push(value_to_logical_type(value));
-                }
-                _ => {
-                    // For complex expressions, use full expression evaluation
-                    let filter_expr = self.convert_expression(&projection.expression)?;
-                    projections.push(ProjectExpr::Expression {
+                    },
+                    LogicalType::Any,
+                ))
+            }
+            LogicalExpression::Literal(value) => Ok((
+                ProjectExpr::Constant(value.clone()),
+                value_to_logical_type(value),
+            )),
+            _ => {
+                // For complex expressions, use full expression evaluation
+                let filter_expr = self.convert_expression(expr)?;
+                Ok((
+                    ProjectExpr::Expression {
                         expr: filter_expr,
                         variable_columns: variable_columns.clone(),
-                    });
-                    output_types.push(LogicalType::Any);
-                }
+                    },
+                    LogicalType::Any,
+                ))
             }
         }
-
-        let operator = Box::new(ProjectOperator::with_store(
-            input_op,
-            projections,
-            output_types,
-            Arc::clone(&self.store),
-        ));
-
-        Ok((operator, output_columns))
     }
 
     /// Plans a filter operator.
+    ///
+    /// `IN` and `EXISTS` subquery conjuncts are lowered to semi-joins against
+    /// the input, and `NOT EXISTS` ones to anti-joins; the remaining conjuncts
+    /// are evaluated by a regular filter on top.
     fn plan_filter(&self, filter: &FilterOp) -> Result<(Box<dyn Operator>, Vec<String>)> {
-        // Plan the input operator first
-        let (input_op, columns) = self.plan_operator(&filter.input)?;
+        // Plan the input operator first, through an index if one serves the
+        // filter; the filter still runs over what the index finds
+        let (mut input_op, mut columns) = match (self.index_lookup(filter), filter.input.as_ref()) {
+            (Some(access), LogicalOperator::NodeScan(scan)) => {
+                let (scan_op, columns) = self.plan_indexed_node_scan(scan, Some(access.lookup))?;
+                (self.instrument(&filter.input, scan_op), columns)
+            }
+            _ => self.plan_operator(&filter.input)?,
+        };
+
+        let mut conjuncts = Vec::new();
+        split_conjuncts(&filter.predicate, &mut conjuncts);
+        let mut predicate: Option<LogicalExpression> = None;
+        for conjunct in conjuncts {
+            if let LogicalExpression::InSubquery { expr, subquery } = conjunct {
+                (input_op, columns) = self.plan_in_subquery(input_op, columns, expr, subquery)?;
+            } else if let Some((subquery, join_type)) = exists_conjunct(conjunct) {
+                (input_op, columns) =
+                    self.plan_exists_subquery(input_op, columns, subquery, join_type)?;
+            } else {
+                predicate = Some(match predicate {
+                    Some(left) => LogicalExpression::Binary {
+                        left: Box::new(left),
+                        op: BinaryOp::And,
+                        right: Box::new(conjunct.clone()),
+                    },
+                    None => conjunct.clone(),
+                });
+            }
+        }
+        let Some(predicate) = predicate else {
+            return Ok((input_op, columns));
+        };
 
         // Build variable to column index mapping
         let variable_columns: HashMap<String, usize> = columns
@@ -733,11 +1003,12 @@ impl Planner {
             .collect();
 
         // Convert logical expression to filter expression
-        let filter_expr = self.convert_expression(&filter.predicate)?;
+        let filter_expr = self.convert_expression(&predicate)?;
 
         // Create the predicate
         let predicate =
-            ExpressionPredicate::new(filter_expr, variable_columns, Arc::clone(&self.store));
+            ExpressionPredicate::new(filter_expr, variable_columns, Arc::clone(&self.store))
+                .with_tx_context(self.viewing_epoch, self.tx_id);
 
         // Create the filter operator
         let operator = Box::new(FilterOperator::new(input_op, Box::new(predicate)));
@@ -745,8 +1016,532 @@ impl Planner {
         Ok((operator, columns))
     }
 
+    /// Says whether `plan` reads storage mostly by scanning or by lookups.
+    ///
+    /// Every node, edge or triple scan counts as a scan, and every filter
+    /// the physical plan answers with an index lookup counts as a lookup
+    /// instead of the scan below it. More scans than lookups is
+    /// [`AccessPattern::Sequential`], more lookups is
+    /// [`AccessPattern::Random`], and a tie (including a plan that reads
+    /// nothing) is [`AccessPattern::Normal`].
+    #[must_use]
+    pub fn access_pattern(&self, plan: &LogicalPlan) -> AccessPattern {
+        let (mut scans, mut lookups) = (0, 0);
+        self.count_reads(&plan.root, &mut scans, &mut lookups);
+        match scans.cmp(&lookups) {
+            std::cmp::Ordering::Greater => AccessPattern::Sequential,
+            std::cmp::Ordering::Less => AccessPattern::Random,
+            std::cmp::Ordering::Equal => AccessPattern::Normal,
+        }
+    }
+
+    fn count_reads(&self, op: &LogicalOperator, scans: &mut usize, lookups: &mut usize) {
+        let inputs: Vec<&LogicalOperator> = match op {
+            LogicalOperator::Filter(filter) if self.index_lookup(filter).is_some() => {
+                // The index replaces the scan under the filter
+                *lookups += 1;
+                match filter.input.as_ref() {
+                    LogicalOperator::NodeScan(scan) => scan.input.as_deref().into_iter().collect(),
+                    input => vec![input],
+                }
+            }
+            LogicalOperator::NodeScan(scan) => {
+                *scans += 1;
+                scan.input.as_deref().into_iter().collect()
+            }
+            LogicalOperator::EdgeScan(scan) => {
+                *scans += 1;
+                scan.input.as_deref().into_iter().collect()
+            }
+            LogicalOperator::TripleScan(scan) => {
+                *scans += 1;
+                scan.input.as_deref().into_iter().collect()
+            }
+            LogicalOperator::TriplePath(_) => {
+                *scans += 1;
+                Vec::new()
+            }
+            LogicalOperator::Filter(filter) => vec![&filter.input],
+            LogicalOperator::Expand(expand) => vec![&expand.input],
+            LogicalOperator::Project(project) => vec![&project.input],
+            LogicalOperator::Aggregate(agg) => vec![&agg.input],
+            LogicalOperator::Limit(limit) => vec![&limit.input],
+            LogicalOperator::Skip(skip) => vec![&skip.input],
+            LogicalOperator::Sort(sort) => vec![&sort.input],
+            LogicalOperator::Distinct(distinct) => vec![&distinct.input],
+            LogicalOperator::Return(ret) => vec![&ret.input],
+            LogicalOperator::Bind(bind) => vec![&bind.input],
+            LogicalOperator::Unwind(unwind) => vec![&unwind.input],
+            LogicalOperator::ShortestPath(sp) => vec![&sp.input],
+            LogicalOperator::CreateNode(create) => create.input.as_deref().into_iter().collect(),
+            LogicalOperator::CreateEdge(create) => vec![&create.input],
+            LogicalOperator::DeleteNode(delete) => vec![&delete.input],
+            LogicalOperator::DeleteEdge(delete) => vec![&delete.input],
+            LogicalOperator::SetProperty(set) => vec![&set.input],
+            LogicalOperator::AddLabel(add) => vec![&add.input],
+            LogicalOperator::RemoveLabel(remove) => vec![&remove.input],
+            LogicalOperator::Merge(merge) => vec![&merge.input],
+            LogicalOperator::Join(join) => match self.index_probe(join) {
+                // The index replaces the scan of the inner side
+                Some(probe) => {
+                    *lookups += 1;
+                    vec![probe.outer]
+                }
+                None => vec![&join.left, &join.right],
+            },
+            LogicalOperator::LeftJoin(join) => vec![&join.left, &join.right],
+            LogicalOperator::AntiJoin(join) => vec![&join.left, &join.right],
+            LogicalOperator::Union(union) => union.inputs.iter().collect(),
+            _ => Vec::new(),
+        };
+        for input in inputs {
+            self.count_reads(input, scans, lookups);
+        }
+    }
+
+    /// Picks the catalog index that can serve a filter's equality predicates.
+    ///
+    /// Only filters directly over a labeled node scan qualify. Conjuncts of
+    /// the form `n.prop = <literal or parameter>` bind index keys; a
+    /// composite index is chosen only when its leading key is bound, so a
+    /// predicate on just the second column of `(a, b)` yields `None`.
+    #[must_use]
+    pub fn select_index(&self, filter: &FilterOp) -> Option<IndexPrefixMatch> {
+        let catalog = self.catalog.as_ref()?;
+        let LogicalOperator::NodeScan(scan) = filter.input.as_ref() else {
+            return None;
+        };
+        let label = catalog.get_label_id(scan.label.as_deref()?)?;
+
+        let mut conjuncts = Vec::new();
+        split_conjuncts(&filter.predicate, &mut conjuncts);
+        let equality_keys: Vec<_> = conjuncts
+            .into_iter()
+            .filter_map(|conjunct| equality_property(conjunct, &scan.variable))
+            .filter_map(|property| catalog.get_property_key_id(property))
+            .collect();
+
+        catalog.find_prefix_index(label, &equality_keys)
+    }
+
+    /// Picks a trie index that can serve a filter's `STARTS WITH` predicate.
+    ///
+    /// Only filters directly over a labeled node scan qualify, with a
+    /// conjunct `n.prop STARTS WITH <literal or parameter>`. A literal empty
+    /// prefix matches every string, so it is left to the scan. The filter
+    /// still runs over the index's candidates, which keeps case-insensitive
+    /// indexes correct for case-sensitive predicates.
+    #[must_use]
+    pub fn select_text_index(&self, filter: &FilterOp) -> Option<IndexId> {
+        let catalog = self.catalog.as_ref()?;
+        let LogicalOperator::NodeScan(scan) = filter.input.as_ref() else {
+            return None;
+        };
+        let label = catalog.get_label_id(scan.label.as_deref()?)?;
+
+        let mut conjuncts = Vec::new();
+        split_conjuncts(&filter.predicate, &mut conjuncts);
+        conjuncts.into_iter().find_map(|conjunct| {
+            let property = prefix_property(conjunct, &scan.variable)?;
+            let key = catalog.get_property_key_id(property)?;
+            catalog.find_text_index(label, key)
+        })
+    }
+
+    /// Picks the index lookup the physical plan runs for a filter over a
+    /// labeled node scan, if any.
+    ///
+    /// Equality lookups are preferred over prefix lookups. Either is only
+    /// used when the values it looks up are literals and the store holds the
+    /// index the catalog names.
+    #[must_use]
+    pub(crate) fn index_lookup(&self, filter: &FilterOp) -> Option<IndexAccess> {
+        self.equality_lookup(filter)
+            .or_else(|| self.prefix_lookup(filter))
+    }
+
+    /// Looks up the catalog index [`select_index`](Self::select_index)
+    /// picks: a hash index is probed for its one key, and a BTree index for
+    /// its leading keys.
+    fn equality_lookup(&self, filter: &FilterOp) -> Option<IndexAccess> {
+        let catalog = self.catalog.as_ref()?;
+        let LogicalOperator::NodeScan(scan) = filter.input.as_ref() else {
+            return None;
+        };
+        let label = scan.label.as_deref()?;
+        let found = self.select_index(filter)?;
+        let definition = catalog.get_index(found.index_id)?;
+        let properties: Vec<Arc<str>> = definition
+            .property_keys
+            .iter()
+            .map(|&key| catalog.get_property_key_name(key))
+            .collect::<Option<_>>()?;
+
+        let mut conjuncts = Vec::new();
+        split_conjuncts(&filter.predicate, &mut conjuncts);
+        let values: Vec<Value> = properties[..found.prefix_len]
+            .iter()
+            .map(|property| {
+                conjuncts.iter().find_map(|conjunct| {
+                    equality_literal(conjunct, &scan.variable)
+                        .filter(|(bound, _)| *bound == &**property)
+                        .map(|(_, value)| value.clone())
+                })
+            })
+            .collect::<Option<_>>()?;
+
+        let property_names: Vec<&str> = properties.iter().map(|p| &**p).collect();
+        let lookup = match definition.index_type {
+            IndexType::Hash if properties.len() == 1 => self
+                .store
+                .has_property_index(label, property_names[0])
+                .then(|| IndexLookup::Equal {
+                    property: property_names[0].to_string(),
+                    value: values[0].clone(),
+                }),
+            IndexType::BTree => self
+                .store
+                .has_composite_index(label, &property_names)
+                .then(|| IndexLookup::Composite {
+                    properties: property_names.iter().map(|p| (*p).to_string()).collect(),
+                    values,
+                }),
+            _ => None,
+        }?;
+        Some(IndexAccess {
+            index_id: found.index_id,
+            prefix: Some((found.prefix_len, found.full_key)),
+            lookup,
+        })
+    }
+
+    /// Looks up a `STARTS WITH` prefix in a text index, as
+    /// [`select_text_index`](Self::select_text_index) picks it.
+    fn prefix_lookup(&self, filter: &FilterOp) -> Option<IndexAccess> {
+        let catalog = self.catalog.as_ref()?;
+        let LogicalOperator::NodeScan(scan) = filter.input.as_ref() else {
+            return None;
+        };
+        let label = scan.label.as_deref()?;
+        let label_id = catalog.get_label_id(label)?;
+
+        let mut conjuncts = Vec::new();
+        split_conjuncts(&filter.predicate, &mut conjuncts);
+        conjuncts.into_iter().find_map(|conjunct| {
+            let (property, prefix) = prefix_literal(conjunct, &scan.variable)?;
+            let key = catalog.get_property_key_id(property)?;
+            let index_id = catalog.find_text_index(label_id, key)?;
+            self.store
+                .has_text_index(label, property)
+                .then(|| IndexAccess {
+                    index_id,
+                    prefix: None,
+                    lookup: IndexLookup::Prefix {
+                        property: property.to_string(),
+                        prefix: prefix.to_string(),
+                    },
+                })
+        })
+    }
+
+    /// Plans a CREATE INDEX statement against the catalog.
+    fn plan_create_index(
+        &self,
+        create: &CreateIndexOp,
+    ) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        let Some(catalog) = &self.catalog else {
+            return Err(Error::Internal(
+                "CREATE INDEX requires a catalog".to_string(),
+            ));
+        };
+        let operator = Box::new(CreateIndexOperator {
+            catalog: Arc::clone(catalog),
+            store: Arc::clone(&self.store),
+            label: create.label.clone(),
+            properties: create.properties.clone(),
+            executed: false,
+        });
+        Ok((operator, Vec::new()))
+    }
+
+    /// Plans a CREATE CONSTRAINT statement against the catalog.
+    fn plan_create_constraint(
+        &self,
+        create: &CreateConstraintOp,
+    ) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        let Some(catalog) = &self.catalog else {
+            return Err(Error::Internal(
+                "CREATE CONSTRAINT requires a catalog".to_string(),
+            ));
+        };
+        let operator = Box::new(CreateConstraintOperator {
+            catalog: Arc::clone(catalog),
+            store: Arc::clone(&self.store),
+            label: create.label.clone(),
+            property: create.property.clone(),
+            kind: create.kind,
+            executed: false,
+        });
+        Ok((operator, Vec::new()))
+    }
+
+    /// Returns the catalog as the checker for node property writes.
+    fn constraints(&self) -> Option<Arc<dyn ConstraintChecker>> {
+        self.catalog
+            .as_ref()
+            .map(|catalog| Arc::clone(catalog) as Arc<dyn ConstraintChecker>)
+    }
+
+    /// Plans a `CALL` of a registered graph algorithm.
+    ///
+    /// Arguments must be constants, and are checked against the algorithm's
+    /// parameters here so a bad argument fails before anything runs. The
+    /// algorithm itself runs on the first pull.
+    fn plan_call_procedure(
+        &self,
+        call: &CallProcedureOp,
+    ) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        let Some(plugins) = &self.plugins else {
+            return Err(Error::Internal(
+                "CALL requires a plugin registry".to_string(),
+            ));
+        };
+        let Some(algorithm) = plugins.get_algorithm(&call.name) else {
+            return Err(Error::Query(QueryError::new(
+                QueryErrorKind::Semantic,
+                format!("Unknown procedure '{}'", call.name),
+            )));
+        };
+
+        let arguments = call
+            .arguments
+            .iter()
+            .map(|argument| match argument {
+                LogicalExpression::Literal(value) => Ok(value.clone()),
+                _ => Err(Error::Query(QueryError::new(
+                    QueryErrorKind::Semantic,
+                    format!("Arguments to '{}' must be constants", call.name),
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let params =
+            Parameters::from_arguments(algorithm.parameters(), &arguments).map_err(|error| {
+                Error::Query(QueryError::new(
+                    QueryErrorKind::Semantic,
+                    format!("Invalid arguments to '{}': {error}", call.name),
+                ))
+            })?;
+
+        let operator = Box::new(CallProcedureOperator {
+            store: Arc::clone(&self.store),
+            algorithm,
+            params,
+            yields: call.yields.clone(),
+            result: None,
+            position: 0,
+        });
+        Ok((operator, call.yields.clone()))
+    }
+
+    /// Plans `expr IN (subquery)` as a semi-join of the input against the subquery.
+    ///
+    /// The compared value joins with the value the subquery returns. A
+    /// returned value made of outer variables alone can be compared on the
+    /// input row, which then only needs the subquery to produce some row.
+    fn plan_in_subquery(
+        &self,
+        input_op: Box<dyn Operator>,
+        columns: Vec<String>,
+        expr: &LogicalExpression,
+        subquery: &LogicalOperator,
+    ) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        let (value, inner_input) = match subquery {
+            LogicalOperator::Return(ret) if ret.items.len() == 1 => {
+                (&ret.items[0].expression, ret.input.as_ref())
+            }
+            LogicalOperator::Project(project) if project.projections.len() == 1 => {
+                (&project.projections[0].expression, project.input.as_ref())
+            }
+            _ => {
+                return Err(Error::Internal(
+                    "IN subquery must return exactly one column".to_string(),
+                ));
+            }
+        };
+
+        let mut vars = HashSet::new();
+        value.collect_variables(&mut vars);
+        let pair = if vars.iter().all(|v| inner_input.binds_variable(v)) {
+            (expr.clone(), value.clone())
+        } else if !vars.iter().any(|v| inner_input.binds_variable(v)) {
+            let compared = LogicalExpression::Binary {
+                left: Box::new(expr.clone()),
+                op: BinaryOp::Eq,
+                right: Box::new(value.clone()),
+            };
+            (compared, LogicalExpression::Literal(Value::Bool(true)))
+        } else {
+            return Err(Error::Internal(
+                "IN subquery value mixes its own and outer variables".to_string(),
+            ));
+        };
+
+        self.plan_subquery_join(
+            input_op,
+            columns,
+            inner_input,
+            vec![pair],
+            PhysicalJoinType::Semi,
+        )
+    }
+
+    /// Joins the input against a subquery, keeping the input rows that have a
+    /// matching subquery row (semi-join) or that don't (anti-join).
+    ///
+    /// Variables bound by both sides are join keys, and so are the
+    /// `(outer, inner)` value pairs in `pairs`. Conditions of the subquery's
+    /// WHERE that read outer variables the subquery doesn't bind are lifted
+    /// out of it into further pairs.
+    fn plan_subquery_join(
+        &self,
+        input_op: Box<dyn Operator>,
+        mut columns: Vec<String>,
+        subquery: &LogicalOperator,
+        mut pairs: Vec<(LogicalExpression, LogicalExpression)>,
+        join_type: PhysicalJoinType,
+    ) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        // The subquery keeps the conditions it can evaluate on its own
+        let residual = match subquery {
+            LogicalOperator::Filter(filter) => {
+                let binds = |v: &str| filter.input.binds_variable(v);
+                let mut conjuncts = Vec::new();
+                split_conjuncts(&filter.predicate, &mut conjuncts);
+                let mut inner = Vec::new();
+                for conjunct in conjuncts {
+                    let mut vars = HashSet::new();
+                    conjunct.collect_variables(&mut vars);
+                    if vars.iter().all(|v| binds(v)) {
+                        inner.push(conjunct.clone());
+                    } else {
+                        pairs.push(conjunct.correlation(binds).ok_or_else(|| {
+                            Error::Internal(format!(
+                                "Correlated subquery condition can't be joined on: {conjunct:?}"
+                            ))
+                        })?);
+                    }
+                }
+                let predicate = inner
+                    .into_iter()
+                    .reduce(|left, right| LogicalExpression::Binary {
+                        left: Box::new(left),
+                        op: BinaryOp::And,
+                        right: Box::new(right),
+                    });
+                Some(match predicate {
+                    Some(predicate) => LogicalOperator::Filter(FilterOp {
+                        predicate,
+                        input: filter.input.clone(),
+                    }),
+                    None => (*filter.input).clone(),
+                })
+            }
+            _ => None,
+        };
+        let (inner_op, mut inner_columns) =
+            self.plan_operator(residual.as_ref().unwrap_or(subquery))?;
+
+        let mut probe_keys = Vec::new();
+        let mut build_keys = Vec::new();
+        for (inner_idx, name) in inner_columns.iter().enumerate() {
+            if let Some(outer_idx) = columns.iter().position(|c| c == name) {
+                probe_keys.push(outer_idx);
+                build_keys.push(inner_idx);
+            }
+        }
+
+        let (outer_values, inner_values): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+        let (probe_op, keys) = self.extend_columns(input_op, &mut columns, &outer_values)?;
+        probe_keys.extend(keys);
+        let (build_op, keys) = self.extend_columns(inner_op, &mut inner_columns, &inner_values)?;
+        build_keys.extend(keys);
+
+        let output_schema = self.derive_schema_from_columns(&columns);
+        let operator: Box<dyn Operator> = self.hash_join(
+            probe_op,
+            build_op,
+            probe_keys,
+            build_keys,
+            join_type,
+            output_schema,
+        );
+
+        Ok((operator, columns))
+    }
+
+    /// Returns the columns holding `values` in the operator's output, adding
+    /// a projected column for each value that isn't a column already.
+    fn extend_columns(
+        &self,
+        op: Box<dyn Operator>,
+        columns: &mut Vec<String>,
+        values: &[LogicalExpression],
+    ) -> Result<(Box<dyn Operator>, Vec<usize>)> {
+        let variables: HashMap<String, usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.clone(), i))
+            .collect();
+        let width = columns.len();
+        let mut projections: Vec<ProjectExpr> = (0..width).map(ProjectExpr::Column).collect();
+        let mut keys = Vec::with_capacity(values.len());
+        for value in values {
+            match value {
+                LogicalExpression::Variable(name) if variables.contains_key(name) => {
+                    keys.push(variables[name]);
+                }
+                _ => {
+                    let (projection, _) = self.projection_expr(value, &variables)?;
+                    keys.push(projections.len());
+                    projections.push(projection);
+                    columns.push(format!("__subquery_key_{}", columns.len()));
+                }
+            }
+        }
+        if projections.len() == width {
+            return Ok((op, keys));
+        }
+
+        let types = vec![LogicalType::Any; projections.len()];
+        let op: Box<dyn Operator> = Box::new(
+            ProjectOperator::with_store(op, projections, types, Arc::clone(&self.store))
+                .with_tx_context(self.viewing_epoch, self.tx_id),
+        );
+        Ok((op, keys))
+    }
+
+    /// Plans `EXISTS { subquery }` as a semi-join of the input against the
+    /// subquery, or as an anti-join for `NOT EXISTS`.
+    ///
+    /// Variables bound by both the input and the subquery are the join keys,
+    /// along with the outer values its WHERE compares. Without any the
+    /// subquery is uncorrelated, and keeps either every input row or none.
+    fn plan_exists_subquery(
+        &self,
+        input_op: Box<dyn Operator>,
+        columns: Vec<String>,
+        subquery: &LogicalOperator,
+        join_type: PhysicalJoinType,
+    ) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        self.plan_subquery_join(input_op, columns, subquery, Vec::new(), join_type)
+    }
+
     /// Plans a LIMIT operator.
+    ///
+    /// A limit directly over a sort fuses with it into a top-N operator.
     fn plan_limit(&self, limit: &LimitOp) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        if let LogicalOperator::Sort(sort) = limit.input.as_ref() {
+            return self.plan_sort_with_limit(sort, Some(limit.count));
+        }
         let (input_op, columns) = self.plan_operator(&limit.input)?;
         let output_schema = self.derive_schema_from_columns(&columns);
         let operator = Box::new(LimitOperator::new(input_op, limit.count, output_schema));
@@ -763,6 +1558,15 @@ impl Planner {
 
     /// Plans a SORT (ORDER BY) operator.
     fn plan_sort(&self, sort: &SortOp) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        self.plan_sort_with_limit(sort, None)
+    }
+
+    /// Plans a sort, keeping only the first `limit` rows if given.
+    fn plan_sort_with_limit(
+        &self,
+        sort: &SortOp,
+        limit: Option<usize>,
+    ) -> Result<(Box<dyn Operator>, Vec<String>)> {
         let (mut input_op, input_columns) = self.plan_operator(&sort.input)?;
 
         // Build variable to column index mapping
@@ -822,12 +1626,15 @@ impl Planner {
                 output_columns.push(col_name.clone());
             }
 
-            input_op = Box::new(ProjectOperator::with_store(
-                input_op,
-                projections,
-                output_types,
-                Arc::clone(&self.store),
-            ));
+            input_op = Box::new(
+                ProjectOperator::with_store(
+                    input_op,
+                    projections,
+                    output_types,
+                    Arc::clone(&self.store),
+                )
+                .with_tx_context(self.viewing_epoch, self.tx_id),
+            );
         }
 
         // Convert logical sort keys to physical sort keys
@@ -849,7 +1656,23 @@ impl Planner {
             .collect::<Result<Vec<_>>>()?;
 
         let output_schema = self.derive_schema_from_columns(&output_columns);
-        let operator = Box::new(SortOperator::new(input_op, physical_keys, output_schema));
+        let operator: Box<dyn Operator> = match limit {
+            Some(limit) => Box::new(TopNOperator::new(
+                input_op,
+                physical_keys,
+                limit,
+                output_schema,
+            )),
+            None => {
+                let sort = SortOperator::new(input_op, physical_keys, output_schema);
+                match &self.buffer_manager {
+                    Some(manager) => Box::new(
+                        sort.with_memory_context(ExecutionMemoryContext::new(Arc::clone(manager))),
+                    ),
+                    None => Box::new(sort),
+                }
+            }
+        };
         Ok((operator, output_columns))
     }
 
@@ -961,12 +1784,15 @@ impl Planner {
                 output_types.push(LogicalType::Any); // Properties can be any type (string, int, etc.)
             }
 
-            input_op = Box::new(ProjectOperator::with_store(
-                input_op,
-                projections,
-                output_types,
-                Arc::clone(&self.store),
-            ));
+            input_op = Box::new(
+                ProjectOperator::with_store(
+                    input_op,
+                    projections,
+                    output_types,
+                    Arc::clone(&self.store),
+                )
+                .with_tx_context(self.viewing_epoch, self.tx_id),
+            );
         }
 
         // Convert group-by expressions to column indices
@@ -1031,12 +1857,7 @@ impl Planner {
                 | LogicalAggregateFunction::PercentileCont => LogicalType::Float64,
             };
             output_schema.push(result_type);
-            output_columns.push(
-                agg_expr
-                    .alias
-                    .clone()
-                    .unwrap_or_else(|| format!("{:?}(...)", agg_expr.function).to_lowercase()),
-            );
+            output_columns.push(agg_expr.output_column());
         }
 
         // Choose operator based on whether there are group-by columns
@@ -1066,7 +1887,8 @@ impl Planner {
 
             let filter_expr = self.convert_expression(having_expr)?;
             let predicate =
-                ExpressionPredicate::new(filter_expr, having_var_columns, Arc::clone(&self.store));
+                ExpressionPredicate::new(filter_expr, having_var_columns, Arc::clone(&self.store))
+                    .with_tx_context(self.viewing_epoch, self.tx_id);
             operator = Box::new(FilterOperator::new(operator, Box::new(predicate)));
         }
 
@@ -1153,6 +1975,11 @@ impl Planner {
                     operand: Box::new(operand_expr),
                 })
             }
+            LogicalExpression::FunctionCall { name, args, .. }
+                if args.is_empty() && name.eq_ignore_ascii_case("rand") =>
+            {
+                Ok(FilterExpression::Random(Arc::clone(&self.random)))
+            }
             LogicalExpression::FunctionCall { name, args, .. } => {
                 let filter_args: Vec<FilterExpression> = args
                     .iter()
@@ -1277,6 +2104,9 @@ impl Planner {
             LogicalExpression::CountSubquery(_) => Err(Error::Internal(
                 "COUNT subqueries not yet supported".to_string(),
             )),
+            LogicalExpression::InSubquery { .. } => Err(Error::Internal(
+                "IN subqueries are only supported as top-level WHERE conditions".to_string(),
+            )),
         }
     }
 
@@ -1327,15 +2157,17 @@ impl Planner {
         }
     }
 
-    /// Plans a JOIN operator.
+    /// Plans a JOIN operator, probing a hash index when
+    /// [`index_probe`](Self::index_probe) finds one and with the algorithm
+    /// from [`join_strategy`](Self::join_strategy) otherwise.
     fn plan_join(&self, join: &JoinOp) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        if let Some(probe) = self.index_probe(join) {
+            return self.plan_index_join(&probe);
+        }
+
         let (left_op, left_columns) = self.plan_operator(&join.left)?;
         let (right_op, right_columns) = self.plan_operator(&join.right)?;
 
-        // Build combined output columns
-        let mut columns = left_columns.clone();
-        columns.extend(right_columns.clone());
-
         // Convert join type
         let physical_join_type = match join.join_type {
             JoinType::Inner => PhysicalJoinType::Inner,
@@ -1348,37 +2180,281 @@ impl Planner {
         };
 
         // Build key columns from join conditions
-        let (probe_keys, build_keys): (Vec<usize>, Vec<usize>) = if join.conditions.is_empty() {
-            // Cross join - no keys
-            (vec![], vec![])
+        let keys: Vec<(usize, usize)> = join
+            .conditions
+            .iter()
+            .filter_map(|cond| {
+                // Try to extract column indices from expressions
+                let left_idx = self.expression_to_column(&cond.left, &left_columns).ok()?;
+                let right_idx = self
+                    .expression_to_column(&cond.right, &right_columns)
+                    .ok()?;
+                Some((left_idx, right_idx))
+            })
+            .collect();
+
+        let strategy = self.join_strategy(join, keys.len());
+
+        let (first_op, second_op, columns, keys) = if strategy.swap_inputs() {
+            let mut columns = right_columns;
+            columns.extend(left_columns);
+            let keys: Vec<_> = keys.into_iter().map(|(l, r)| (r, l)).collect();
+            (right_op, left_op, columns, keys)
         } else {
-            join.conditions
-                .iter()
-                .filter_map(|cond| {
-                    // Try to extract column indices from expressions
-                    let left_idx = self.expression_to_column(&cond.left, &left_columns).ok()?;
-                    let right_idx = self
-                        .expression_to_column(&cond.right, &right_columns)
-                        .ok()?;
-                    Some((left_idx, right_idx))
-                })
-                .unzip()
+            let mut columns = left_columns;
+            columns.extend(right_columns);
+            (left_op, right_op, columns, keys)
+        };
+        let output_schema = self.derive_schema_from_columns(&columns);
+
+        let operator: Box<dyn Operator> = match strategy {
+            JoinStrategy::NestedLoop { .. } => {
+                let condition: Option<Box<dyn JoinCondition>> = if keys.is_empty() {
+                    None
+                } else {
+                    Some(Box::new(EqualityCondition::on_columns(keys)))
+                };
+                Box::new(NestedLoopJoinOperator::new(
+                    first_op,
+                    second_op,
+                    condition,
+                    physical_join_type,
+                    output_schema,
+                ))
+            }
+            JoinStrategy::Merge => {
+                let (left_keys, right_keys) = keys.into_iter().unzip();
+                Box::new(MergeJoinOperator::new(
+                    first_op,
+                    second_op,
+                    left_keys,
+                    right_keys,
+                    output_schema,
+                ))
+            }
+            JoinStrategy::Hash { .. } => {
+                let (probe_keys, build_keys) = keys.into_iter().unzip();
+                self.hash_join(
+                    first_op,
+                    second_op,
+                    probe_keys,
+                    build_keys,
+                    physical_join_type,
+                    output_schema,
+                )
+            }
+        };
+
+        Ok((operator, columns))
+    }
+
+    /// Builds a hash join, one that can spill its build side when a buffer
+    /// manager bounds the memory it may hold.
+    fn hash_join(
+        &self,
+        probe: Box<dyn Operator>,
+        build: Box<dyn Operator>,
+        probe_keys: Vec<usize>,
+        build_keys: Vec<usize>,
+        join_type: PhysicalJoinType,
+        output_schema: Vec<LogicalType>,
+    ) -> Box<dyn Operator> {
+        match (&self.buffer_manager, self.spill_manager()) {
+            (Some(buffer_manager), Some(spill_manager)) => {
+                Box::new(SpillableHashJoinOperator::new(
+                    probe,
+                    build,
+                    probe_keys,
+                    build_keys,
+                    join_type,
+                    output_schema,
+                    spill_manager,
+                    Arc::clone(buffer_manager),
+                ))
+            }
+            _ => Box::new(HashJoinOperator::new(
+                probe,
+                build,
+                probe_keys,
+                build_keys,
+                join_type,
+                output_schema,
+            )),
+        }
+    }
+
+    /// Returns the spill manager for this plan's joins, creating it on first
+    /// use in the buffer manager's spill path.
+    ///
+    /// `None` without a buffer manager, or if the spill directory can't be
+    /// created; joins then keep everything in memory.
+    fn spill_manager(&self) -> Option<Arc<SpillManager>> {
+        self.spill_manager
+            .get_or_init(|| {
+                let buffer_manager = self.buffer_manager.as_ref()?;
+                let dir = buffer_manager
+                    .config()
+                    .spill_path
+                    .clone()
+                    .unwrap_or_else(|| std::env::temp_dir().join("grafeo_spill"));
+                match SpillManager::new(&dir) {
+                    Ok(manager) => Some(Arc::new(manager)),
+                    Err(e) => {
+                        tracing::warn!("Hash joins can't spill to {}: {}", dir.display(), e);
+                        None
+                    }
+                }
+            })
+            .clone()
+    }
+
+    /// Finds a hash index that can serve a join as an index-nested-loop join.
+    ///
+    /// Only inner joins with a single condition qualify, where one input is a
+    /// plain scan of a label with a catalog hash index on the property the
+    /// condition compares, built in the store. The other input is the outer
+    /// side, and the other half of the condition is its probe expression.
+    pub(crate) fn index_probe<'a>(&'a self, join: &'a JoinOp) -> Option<IndexProbe<'a>> {
+        if join.join_type != JoinType::Inner || join.conditions.len() != 1 {
+            return None;
+        }
+        let condition = &join.conditions[0];
+        let sides = [
+            (&join.left, &join.right, &condition.left, &condition.right),
+            (&join.right, &join.left, &condition.right, &condition.left),
+        ];
+        sides.into_iter().find_map(|(outer, inner, key, indexed)| {
+            let LogicalOperator::NodeScan(NodeScanOp {
+                variable,
+                label: Some(label),
+                input: None,
+            }) = inner.as_ref()
+            else {
+                return None;
+            };
+            let LogicalExpression::Property {
+                variable: indexed_variable,
+                property,
+            } = indexed
+            else {
+                return None;
+            };
+            if indexed_variable != variable {
+                return None;
+            }
+            let catalog = self.catalog.as_ref()?;
+            let index_id = catalog
+                .indexes_for_label_property(
+                    catalog.get_label_id(label)?,
+                    catalog.get_property_key_id(property)?,
+                )
+                .into_iter()
+                .find(|&id| {
+                    catalog.get_index(id).is_some_and(|definition| {
+                        definition.index_type == IndexType::Hash && !definition.is_composite()
+                    })
+                })?;
+            if !self.store.has_property_index(label, property) {
+                return None;
+            }
+            Some(IndexProbe {
+                outer,
+                variable,
+                label,
+                property,
+                key,
+                index_id,
+            })
+        })
+    }
+
+    /// Plans an index-nested-loop join found by
+    /// [`index_probe`](Self::index_probe).
+    ///
+    /// The probe key is evaluated into an extra column of the outer input
+    /// unless it already is one; the inner scan is never planned.
+    fn plan_index_join(&self, probe: &IndexProbe<'_>) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        let (outer_op, mut columns) = self.plan_operator(probe.outer)?;
+        let outer_variables: HashMap<String, usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.clone(), i))
+            .collect();
+
+        let (outer_op, probe_column): (Box<dyn Operator>, usize) = match probe.key {
+            LogicalExpression::Variable(name) if outer_variables.contains_key(name) => {
+                (outer_op, outer_variables[name])
+            }
+            _ => {
+                let (key_projection, _) = self.projection_expr(probe.key, &outer_variables)?;
+                let mut projections: Vec<ProjectExpr> =
+                    (0..columns.len()).map(ProjectExpr::Column).collect();
+                projections.push(key_projection);
+                let types = vec![LogicalType::Any; projections.len()];
+                columns.push(format!("__index_key_{}", columns.len()));
+                let op: Box<dyn Operator> = Box::new(
+                    ProjectOperator::with_store(
+                        outer_op,
+                        projections,
+                        types,
+                        Arc::clone(&self.store),
+                    )
+                    .with_tx_context(self.viewing_epoch, self.tx_id),
+                );
+                (op, columns.len() - 1)
+            }
         };
 
+        columns.push(probe.variable.to_string());
         let output_schema = self.derive_schema_from_columns(&columns);
-
-        let operator: Box<dyn Operator> = Box::new(HashJoinOperator::new(
-            left_op,
-            right_op,
-            probe_keys,
-            build_keys,
-            physical_join_type,
-            output_schema,
-        ));
+        let operator: Box<dyn Operator> = Box::new(
+            IndexNestedLoopJoinOperator::new(
+                outer_op,
+                Arc::clone(&self.store),
+                probe.label,
+                probe.property,
+                probe_column,
+                output_schema,
+            )
+            .with_tx_context(self.viewing_epoch, self.tx_id),
+        );
 
         Ok((operator, columns))
     }
 
+    /// Picks the algorithm for a join with `key_count` usable equality keys.
+    ///
+    /// An inner equi-join whose inputs are both sorted ascending on the join
+    /// keys uses a merge join. Otherwise it uses a nested-loop join when there
+    /// is no usable equality condition or one input is estimated below the
+    /// nested-loop threshold, and a hash join otherwise. An inner join may
+    /// swap its inputs: a hash join builds on whichever side the cost model
+    /// prices cheaper (the smaller one), and a nested-loop join materializes
+    /// the tiny side.
+    pub(crate) fn join_strategy(&self, join: &JoinOp, key_count: usize) -> JoinStrategy {
+        let store_estimator;
+        let estimator = match &self.estimator {
+            Some(estimator) => estimator,
+            None => {
+                store_estimator = CardinalityEstimator::from_statistics(&self.store.statistics());
+                &store_estimator
+            }
+        };
+        // Every condition must be a key for a merge on them to be exact
+        let inputs_sorted = key_count == join.conditions.len()
+            && sorted_on(&join.left, join.conditions.iter().map(|c| &c.left))
+            && sorted_on(&join.right, join.conditions.iter().map(|c| &c.right));
+        choose_join_strategy(
+            join.join_type,
+            key_count > 0,
+            inputs_sorted,
+            estimator.estimate(&join.left),
+            estimator.estimate(&join.right),
+            self.nested_loop_threshold,
+            &CostModel::new(),
+        )
+    }
+
     /// Extracts a column index from an expression.
     fn expression_to_column(&self, expr: &LogicalExpression, columns: &[String]) -> Result<usize> {
         match expr {
@@ -1463,7 +2539,8 @@ impl Planner {
                 output_schema,
                 output_column,
             )
-            .with_tx_context(self.viewing_epoch, self.tx_id),
+            .with_tx_context(self.viewing_epoch, self.tx_id)
+            .with_constraints(self.constraints()),
         );
 
         Ok((operator, columns))
@@ -1602,8 +2679,8 @@ impl Planner {
         let (right_op, right_columns) = self.plan_operator(&left_join.right)?;
 
         // Build combined output columns (left + right)
-        let mut columns = left_columns.clone();
-        columns.extend(right_columns.clone());
+        let mut join_columns = left_columns.clone();
+        join_columns.extend(right_columns.clone());
 
         // Find common variables between left and right for join keys
         let mut probe_keys = Vec::new();
@@ -1616,16 +2693,36 @@ impl Planner {
             }
         }
 
-        let output_schema = self.derive_schema_from_columns(&columns);
+        let output_schema = self.derive_schema_from_columns(&join_columns);
 
-        let operator: Box<dyn Operator> = Box::new(HashJoinOperator::new(
+        let operator: Box<dyn Operator> = self.hash_join(
             left_op,
             right_op,
             probe_keys,
-            build_keys,
+            build_keys.clone(),
             PhysicalJoinType::Left,
             output_schema,
-        ));
+        );
+
+        if build_keys.is_empty() {
+            return Ok((operator, join_columns));
+        }
+
+        // Drop the right-side copies of the join keys. They are null for
+        // unmatched rows and would otherwise shadow the left-side values.
+        let left_count = left_columns.len();
+        let mut projections = Vec::with_capacity(join_columns.len() - build_keys.len());
+        let mut columns = Vec::with_capacity(projections.capacity());
+        for (idx, name) in join_columns.iter().enumerate() {
+            if idx >= left_count && build_keys.contains(&(idx - left_count)) {
+                continue;
+            }
+            projections.push(ProjectExpr::Column(idx));
+            columns.push(name.clone());
+        }
+
+        let output_types = self.derive_schema_from_columns(&columns);
+        let operator = Box::new(ProjectOperator::new(operator, projections, output_types));
 
         Ok((operator, columns))
     }
@@ -1651,83 +2748,70 @@ impl Planner {
 
         let output_schema = self.derive_schema_from_columns(&columns);
 
-        let operator: Box<dyn Operator> = Box::new(HashJoinOperator::new(
+        let operator: Box<dyn Operator> = self.hash_join(
             left_op,
             right_op,
             probe_keys,
             build_keys,
             PhysicalJoinType::Anti,
             output_schema,
-        ));
+        );
 
         Ok((operator, columns))
     }
 
     /// Plans an unwind operator.
     fn plan_unwind(&self, unwind: &UnwindOp) -> Result<(Box<dyn Operator>, Vec<String>)> {
-        // Plan the input operator first
-        // Handle Empty specially - use a single-row operator
+        // Plan the input operator first. UNWIND without a prior MATCH reads
+        // from a single empty row.
         let (input_op, input_columns): (Box<dyn Operator>, Vec<String>) =
             if matches!(&*unwind.input, LogicalOperator::Empty) {
-                // For UNWIND without prior MATCH, create a single-row input
-                // We need an operator that produces one row with the list to unwind
-                // For now, use EmptyScan which produces no rows - we'll handle the literal
-                // list in the unwind operator itself
-                let literal_list = self.convert_expression(&unwind.expression)?;
-
-                // Create a project operator that produces a single row with the list
                 let single_row_op: Box<dyn Operator> = Box::new(
                     grafeo_core::execution::operators::single_row::SingleRowOperator::new(),
                 );
-                let project_op: Box<dyn Operator> = Box::new(ProjectOperator::with_store(
-                    single_row_op,
-                    vec![ProjectExpr::Expression {
-                        expr: literal_list,
-                        variable_columns: HashMap::new(),
-                    }],
-                    vec![LogicalType::Any],
-                    Arc::clone(&self.store),
-                ));
-
-                (project_op, vec!["__list__".to_string()])
+                (single_row_op, Vec::new())
             } else {
                 self.plan_operator(&unwind.input)?
             };
 
-        // The UNWIND expression should be a list - we need to find/evaluate it
-        // For now, we handle the case where the expression references an existing column
-        // or is a literal list
+        // Evaluate the list expression into an extra trailing column so that
+        // literals, variables and property accesses are all handled alike.
+        let variable_columns: HashMap<String, usize> = input_columns
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.clone(), i))
+            .collect();
+        let input_schema = self.derive_schema_from_columns(&input_columns);
 
-        // Find if the expression references an existing column (like a list property)
-        let list_col_idx = match &unwind.expression {
-            LogicalExpression::Variable(var) => input_columns.iter().position(|c| c == var),
-            LogicalExpression::Property { variable, .. } => {
-                // Property access needs to be evaluated - for now we'll need the filter predicate
-                // to evaluate this. For simple cases, we treat it as a list column.
-                input_columns.iter().position(|c| c == variable)
-            }
-            LogicalExpression::List(_) | LogicalExpression::Literal(_) => {
-                // Literal list expression - we'll add it as a virtual column
-                None
-            }
-            _ => None,
-        };
+        let mut projections: Vec<ProjectExpr> =
+            (0..input_columns.len()).map(ProjectExpr::Column).collect();
+        projections.push(ProjectExpr::Expression {
+            expr: self.convert_expression(&unwind.expression)?,
+            variable_columns,
+        });
+        let mut project_types = input_schema.clone();
+        project_types.push(LogicalType::Any);
+
+        let project_op: Box<dyn Operator> = Box::new(
+            ProjectOperator::with_store(
+                input_op,
+                projections,
+                project_types,
+                Arc::clone(&self.store),
+            )
+            .with_tx_context(self.viewing_epoch, self.tx_id),
+        );
 
-        // Build output columns: all input columns plus the new variable
+        // The list column is replaced by the unwound element
         let mut columns = input_columns.clone();
         columns.push(unwind.variable.clone());
 
-        // Build output schema
-        let mut output_schema = self.derive_schema_from_columns(&input_columns);
+        let mut output_schema = input_schema;
         output_schema.push(LogicalType::Any); // The unwound element type is dynamic
 
-        // Use the list column index if found, otherwise default to 0
-        // (in which case the first column should contain the list)
-        let col_idx = list_col_idx.unwrap_or(0);
-
         let operator: Box<dyn Operator> = Box::new(UnwindOperator::new(
-            input_op,
-            col_idx,
+            project_op,
+            input_columns.len(),
             unwind.variable.clone(),
             output_schema,
         ));
@@ -1745,56 +2829,63 @@ impl Planner {
             cols
         };
 
-        // Convert match properties from LogicalExpression to Value
+        // Convert match properties from LogicalExpression to Value. Skipping a
+        // key would widen the match, so anything but a literal is an error;
+        // the binder rejects it first.
         let match_properties: Vec<(String, grafeo_common::types::Value)> = merge
             .match_properties
             .iter()
-            .filter_map(|(name, expr)| {
+            .map(|(name, expr)| {
                 if let LogicalExpression::Literal(v) = expr {
-                    Some((name.clone(), v.clone()))
+                    Ok((name.clone(), v.clone()))
                 } else {
-                    None // Skip non-literal expressions for now
+                    Err(Error::Query(QueryError::new(
+                        QueryErrorKind::Semantic,
+                        format!("MERGE key property '{name}' must be a literal or parameter"),
+                    )))
                 }
             })
-            .collect();
+            .collect::<Result<_>>()?;
 
-        // Convert ON CREATE properties
-        let on_create_properties: Vec<(String, grafeo_common::types::Value)> = merge
+        // ON CREATE and ON MATCH values are evaluated when the merge runs
+        let on_create_properties: Vec<(String, FilterExpression)> = merge
             .on_create
             .iter()
-            .filter_map(|(name, expr)| {
-                if let LogicalExpression::Literal(v) = expr {
-                    Some((name.clone(), v.clone()))
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        // Convert ON MATCH properties
-        let on_match_properties: Vec<(String, grafeo_common::types::Value)> = merge
+            .map(|(name, expr)| Ok((name.clone(), self.convert_expression(expr)?)))
+            .collect::<Result<_>>()?;
+        let on_match_properties: Vec<(String, FilterExpression)> = merge
             .on_match
             .iter()
-            .filter_map(|(name, expr)| {
-                if let LogicalExpression::Literal(v) = expr {
-                    Some((name.clone(), v.clone()))
-                } else {
-                    None
-                }
-            })
-            .collect();
+            .map(|(name, expr)| Ok((name.clone(), self.convert_expression(expr)?)))
+            .collect::<Result<_>>()?;
 
         // Add the merged node variable to output columns
         columns.push(merge.variable.clone());
 
-        let operator: Box<dyn Operator> = Box::new(MergeOperator::new(
+        let mut operator = MergeOperator::new(
             Arc::clone(&self.store),
             merge.variable.clone(),
             merge.labels.clone(),
-            match_properties,
+            match_properties.clone(),
             on_create_properties,
             on_match_properties,
-        ));
+        )
+        .with_tx_context(self.viewing_epoch, self.tx_id)
+        .with_constraints(self.constraints());
+
+        // Claim the merge key as a write when the merge runs, so that
+        // concurrent merges of the same key conflict at commit instead of
+        // both inserting a node.
+        if let (Some(tx_manager), Some(tx_id)) = (&self.tx_manager, self.tx_id) {
+            let tx_manager = Arc::clone(tx_manager);
+            let key = merge_key(&merge.labels, &match_properties);
+            operator = operator.with_key_claim(Box::new(move || {
+                tx_manager
+                    .record_write(tx_id, EntityId::MergeKey(key))
+                    .map_err(|err| OperatorError::Execution(err.to_string()))
+            }));
+        }
+        let operator: Box<dyn Operator> = Box::new(operator);
 
         Ok((operator, columns))
     }
@@ -1867,17 +2958,21 @@ impl Planner {
                 ))
             })?;
 
-        // Output schema for update count
-        let output_schema = vec![LogicalType::Int64];
-        let output_columns = vec!["labels_added".to_string()];
+        // Output schema preserves input schema (passes through)
+        let output_schema: Vec<LogicalType> = columns.iter().map(|_| LogicalType::Node).collect();
+        let output_columns = columns.clone();
 
-        let operator = Box::new(AddLabelOperator::new(
-            Arc::clone(&self.store),
-            input_op,
-            node_column,
-            add_label.labels.clone(),
-            output_schema,
-        ));
+        let operator = Box::new(
+            AddLabelOperator::new(
+                Arc::clone(&self.store),
+                input_op,
+                node_column,
+                add_label.labels.clone(),
+                output_schema,
+            )
+            .with_tx_id(self.tx_id)
+            .with_constraints(self.constraints()),
+        );
 
         Ok((operator, output_columns))
     }
@@ -1900,17 +2995,20 @@ impl Planner {
                 ))
             })?;
 
-        // Output schema for update count
-        let output_schema = vec![LogicalType::Int64];
-        let output_columns = vec!["labels_removed".to_string()];
+        // Output schema preserves input schema (passes through)
+        let output_schema: Vec<LogicalType> = columns.iter().map(|_| LogicalType::Node).collect();
+        let output_columns = columns.clone();
 
-        let operator = Box::new(RemoveLabelOperator::new(
-            Arc::clone(&self.store),
-            input_op,
-            node_column,
-            remove_label.labels.clone(),
-            output_schema,
-        ));
+        let operator = Box::new(
+            RemoveLabelOperator::new(
+                Arc::clone(&self.store),
+                input_op,
+                node_column,
+                remove_label.labels.clone(),
+                output_schema,
+            )
+            .with_tx_id(self.tx_id),
+        );
 
         Ok((operator, output_columns))
     }
@@ -1933,28 +3031,47 @@ impl Planner {
                 ))
             })?;
 
-        // Convert properties to PropertySource
-        let properties: Vec<(String, PropertySource)> = set_prop
-            .properties
-            .iter()
-            .map(|(name, expr)| {
-                let source = self.expression_to_property_source(expr, &columns)?;
-                Ok((name.clone(), source))
-            })
-            .collect::<Result<Vec<_>>>()?;
+        // Convert properties to PropertySource. A `*` entry is a whole map
+        // (`SET n = {...}` / `SET n += {...}`): a map literal is split into
+        // its entries, anything else is expanded when it is evaluated.
+        let mut properties: Vec<(String, PropertySource)> = Vec::new();
+        let mut property_maps: Vec<PropertySource> = Vec::new();
+        for (name, expr) in &set_prop.properties {
+            match (name.as_str(), expr) {
+                ("*", LogicalExpression::Map(entries)) => {
+                    for (key, value) in entries {
+                        let source = self.expression_to_property_source(value, &columns)?;
+                        properties.push((key.clone(), source));
+                    }
+                }
+                ("*", expr) => {
+                    property_maps.push(self.expression_to_property_source(expr, &columns)?);
+                }
+                (name, expr) => {
+                    let source = self.expression_to_property_source(expr, &columns)?;
+                    properties.push((name.to_string(), source));
+                }
+            }
+        }
 
         // Output schema preserves input schema (passes through)
         let output_schema: Vec<LogicalType> = columns.iter().map(|_| LogicalType::Node).collect();
         let output_columns = columns.clone();
 
         // Determine if this is a node or edge (for now assume node, edge detection can be added later)
-        let operator = Box::new(SetPropertyOperator::new_for_node(
-            Arc::clone(&self.store),
-            input_op,
-            entity_column,
-            properties,
-            output_schema,
-        ));
+        let operator = Box::new(
+            SetPropertyOperator::new_for_node(
+                Arc::clone(&self.store),
+                input_op,
+                entity_column,
+                properties,
+                output_schema,
+            )
+            .with_property_maps(property_maps)
+            .with_replace(set_prop.replace)
+            .with_tx_id(self.tx_id)
+            .with_constraints(self.constraints()),
+        );
 
         Ok((operator, output_columns))
     }
@@ -2045,6 +3162,114 @@ pub fn convert_aggregate_function(func: LogicalAggregateFunction) -> PhysicalAgg
     }
 }
 
+/// Returns the subquery of an `EXISTS` or `NOT EXISTS` conjunct, with the join
+/// that lowers it.
+fn exists_conjunct(conjunct: &LogicalExpression) -> Option<(&LogicalOperator, PhysicalJoinType)> {
+    match conjunct {
+        LogicalExpression::ExistsSubquery(subquery) => Some((subquery, PhysicalJoinType::Semi)),
+        LogicalExpression::Unary {
+            op: UnaryOp::Not,
+            operand,
+        } => match operand.as_ref() {
+            LogicalExpression::ExistsSubquery(subquery) => Some((subquery, PhysicalJoinType::Anti)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the property bound by an equality conjunct on `variable`.
+///
+/// Matches `variable.prop = value` in either operand order, where the value
+/// is a literal or a parameter.
+fn equality_property<'a>(expr: &'a LogicalExpression, variable: &str) -> Option<&'a str> {
+    let LogicalExpression::Binary {
+        left,
+        op: BinaryOp::Eq,
+        right,
+    } = expr
+    else {
+        return None;
+    };
+    let (property, value) = match (left.as_ref(), right.as_ref()) {
+        (LogicalExpression::Property { .. }, value) => (left.as_ref(), value),
+        (value, LogicalExpression::Property { .. }) => (right.as_ref(), value),
+        _ => return None,
+    };
+    let LogicalExpression::Property {
+        variable: var,
+        property,
+    } = property
+    else {
+        return None;
+    };
+    let bound = matches!(
+        value,
+        LogicalExpression::Literal(_) | LogicalExpression::Parameter(_)
+    );
+    (var == variable && bound).then_some(property.as_str())
+}
+
+/// Returns the property and value of an equality conjunct on `variable`
+/// whose value is a literal.
+fn equality_literal<'a>(
+    expr: &'a LogicalExpression,
+    variable: &str,
+) -> Option<(&'a str, &'a Value)> {
+    let property = equality_property(expr, variable)?;
+    let LogicalExpression::Binary { left, right, .. } = expr else {
+        return None;
+    };
+    match (left.as_ref(), right.as_ref()) {
+        (LogicalExpression::Literal(value), _) | (_, LogicalExpression::Literal(value)) => {
+            Some((property, value))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the property tested by a `variable.prop STARTS WITH prefix` conjunct.
+///
+/// The prefix must be a parameter or a non-empty string literal.
+fn prefix_property<'a>(expr: &'a LogicalExpression, variable: &str) -> Option<&'a str> {
+    let LogicalExpression::Binary {
+        left,
+        op: BinaryOp::StartsWith,
+        right,
+    } = expr
+    else {
+        return None;
+    };
+    let LogicalExpression::Property {
+        variable: var,
+        property,
+    } = left.as_ref()
+    else {
+        return None;
+    };
+    let indexable = match right.as_ref() {
+        LogicalExpression::Literal(grafeo_common::types::Value::String(prefix)) => {
+            !prefix.is_empty()
+        }
+        LogicalExpression::Parameter(_) => true,
+        _ => false,
+    };
+    (var == variable && indexable).then_some(property.as_str())
+}
+
+/// Returns the property and prefix of a `variable.prop STARTS WITH prefix`
+/// conjunct whose prefix is a non-empty string literal.
+fn prefix_literal<'a>(expr: &'a LogicalExpression, variable: &str) -> Option<(&'a str, &'a str)> {
+    let property = prefix_property(expr, variable)?;
+    match expr {
+        LogicalExpression::Binary { right, .. } => match right.as_ref() {
+            LogicalExpression::Literal(Value::String(prefix)) => Some((property, prefix)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 /// Converts a logical expression to a filter expression.
 ///
 /// This is a standalone function that can be used by both LPG and RDF planners.
@@ -2180,9 +3405,11 @@ pub fn convert_filter_expression(expr: &LogicalExpression) -> Result<FilterExpre
                 map_expr: Box::new(map),
             })
         }
-        LogicalExpression::ExistsSubquery(_) | LogicalExpression::CountSubquery(_) => Err(
-            Error::Internal("Subqueries not yet supported in filters".to_string()),
-        ),
+        LogicalExpression::ExistsSubquery(_)
+        | LogicalExpression::CountSubquery(_)
+        | LogicalExpression::InSubquery { .. } => Err(Error::Internal(
+            "Subqueries not yet supported in filters".to_string(),
+        )),
     }
 }
 
@@ -2197,13 +3424,30 @@ fn value_to_logical_type(value: &grafeo_common::types::Value) -> LogicalType {
         Value::String(_) => LogicalType::String,
         Value::Bytes(_) => LogicalType::String, // No Bytes logical type, use String
         Value::Timestamp(_) => LogicalType::Timestamp,
+        Value::Date(_) => LogicalType::Date,
+        Value::Duration(_) => LogicalType::Duration,
         Value::List(_) => LogicalType::String, // Lists not yet supported as logical type
         Value::Map(_) => LogicalType::String,  // Maps not yet supported as logical type
     }
 }
 
+/// Hashes a MERGE pattern's labels and key properties into a conflict key.
+///
+/// Labels and properties are sorted so the key does not depend on the order
+/// they were written in.
+fn merge_key(labels: &[String], properties: &[(String, grafeo_common::types::Value)]) -> u64 {
+    let mut labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+    labels.sort_unstable();
+    let mut properties: Vec<String> = properties
+        .iter()
+        .map(|(name, value)| format!("{name}={value:?}"))
+        .collect();
+    properties.sort_unstable();
+    grafeo_common::utils::hash::hash_one(&(labels, properties))
+}
+
 /// Converts an expression to a string for column naming.
-fn expression_to_string(expr: &LogicalExpression) -> String {
+pub(crate) fn expression_to_string(expr: &LogicalExpression) -> String {
     match expr {
         LogicalExpression::Variable(name) => name.clone(),
         LogicalExpression::Property { variable, property } => {
@@ -2215,6 +3459,184 @@ fn expression_to_string(expr: &LogicalExpression) -> String {
     }
 }
 
+/// Operator that creates an index in the catalog and the store.
+///
+/// Indexes are created as BTree indexes so composite keys can serve prefix
+/// lookups. Creating an index that already exists is a no-op.
+struct CreateIndexOperator {
+    catalog: Arc<Catalog>,
+    store: Arc<LpgStore>,
+    label: String,
+    properties: Vec<String>,
+    executed: bool,
+}
+
+impl Operator for CreateIndexOperator {
+    fn next(&mut self) -> std::result::Result<Option<DataChunk>, OperatorError> {
+        if self.executed {
+            return Ok(None);
+        }
+        self.executed = true;
+
+        let properties: Vec<&str> = self.properties.iter().map(String::as_str).collect();
+        self.catalog
+            .create_composite_index(&self.store, &self.label, &properties);
+
+        Ok(None)
+    }
+
+    fn reset(&mut self) {
+        self.executed = false;
+    }
+
+    fn name(&self) -> &'static str {
+        "CreateIndex"
+    }
+}
+
+/// Operator that adds a constraint to the catalog.
+///
+/// A constraint that already exists is left as is, like an index.
+struct CreateConstraintOperator {
+    catalog: Arc<Catalog>,
+    store: Arc<LpgStore>,
+    label: String,
+    property: String,
+    kind: ConstraintKind,
+    executed: bool,
+}
+
+impl Operator for CreateConstraintOperator {
+    fn next(&mut self) -> std::result::Result<Option<DataChunk>, OperatorError> {
+        if self.executed {
+            return Ok(None);
+        }
+        self.executed = true;
+
+        let created = match self.kind {
+            ConstraintKind::Unique => {
+                self.catalog
+                    .create_unique_constraint(&self.store, &self.label, &self.property)
+            }
+            ConstraintKind::NotNull => {
+                self.catalog
+                    .create_required_constraint(&self.store, &self.label, &self.property)
+            }
+        };
+        match created {
+            Ok(()) | Err(CatalogError::ConstraintAlreadyExists) => Ok(None),
+            Err(
+                err @ (CatalogError::DuplicateValue { .. } | CatalogError::MissingValue { .. }),
+            ) => Err(OperatorError::ConstraintViolation(err.to_string())),
+            Err(err) => Err(OperatorError::Execution(err.to_string())),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.executed = false;
+    }
+
+    fn name(&self) -> &'static str {
+        "CreateConstraint"
+    }
+}
+
+/// Operator that runs a graph algorithm and streams its result rows.
+///
+/// Yielded columns are looked up by name in the algorithm's result, except
+/// `node`, which is the `node_id` column as a node so later clauses can read
+/// its properties.
+struct CallProcedureOperator {
+    store: Arc<LpgStore>,
+    algorithm: Arc<dyn GraphAlgorithm>,
+    params: Parameters,
+    yields: Vec<String>,
+    /// The result and, per yield, its result column; set on the first pull.
+    result: Option<(AlgorithmResult, Vec<usize>)>,
+    position: usize,
+}
+
+impl CallProcedureOperator {
+    fn run(&self) -> std::result::Result<(AlgorithmResult, Vec<usize>), OperatorError> {
+        let result = self
+            .algorithm
+            .execute(&self.store, &self.params)
+            .map_err(|error| OperatorError::Execution(error.to_string()))?;
+        let columns = self
+            .yields
+            .iter()
+            .map(|name| {
+                let column = if name == "node" { "node_id" } else { name };
+                result
+                    .columns
+                    .iter()
+                    .position(|c| c == column)
+                    .ok_or_else(|| {
+                        OperatorError::ColumnNotFound(format!(
+                            "'{name}' is not yielded by '{}'",
+                            self.algorithm.name()
+                        ))
+                    })
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok((result, columns))
+    }
+}
+
+impl Operator for CallProcedureOperator {
+    fn next(&mut self) -> std::result::Result<Option<DataChunk>, OperatorError> {
+        if self.result.is_none() {
+            self.result = Some(self.run()?);
+        }
+        let Some((result, columns)) = &self.result else {
+            return Ok(None);
+        };
+        if self.position >= result.rows.len() {
+            return Ok(None);
+        }
+
+        let schema: Vec<LogicalType> = self
+            .yields
+            .iter()
+            .map(|name| {
+                if name == "node" {
+                    LogicalType::Node
+                } else {
+                    LogicalType::Any
+                }
+            })
+            .collect();
+        let end = (self.position + DEFAULT_CHUNK_SIZE).min(result.rows.len());
+        let mut builder = DataChunkBuilder::with_capacity(&schema, end - self.position);
+        for row in &result.rows[self.position..end] {
+            for (index, (&column, data_type)) in columns.iter().zip(&schema).enumerate() {
+                let value = row.get(column).cloned().unwrap_or(Value::Null);
+                let Some(dst) = builder.column_mut(index) else {
+                    continue;
+                };
+                match (data_type, value) {
+                    (LogicalType::Node, Value::Int64(id)) => {
+                        dst.push_node_id(NodeId::new(id as u64));
+                    }
+                    (_, value) => dst.push_value(value),
+                }
+            }
+            builder.advance_row();
+        }
+        self.position = end;
+        Ok(Some(builder.finish()))
+    }
+
+    fn reset(&mut self) {
+        self.result = None;
+        self.position = 0;
+    }
+
+    fn name(&self) -> &'static str {
+        "CallProcedure"
+    }
+}
+
 /// A physical plan ready for execution.
 pub struct PhysicalPlan {
     /// The root physical operator.
@@ -2236,20 +3658,137 @@ impl PhysicalPlan {
         &self.columns
     }
 
-    /// Consumes the plan and returns the operator.
-    pub fn into_operator(self) -> Box<dyn Operator> {
-        self.operator
+    /// Consumes the plan and returns the operator.
+    pub fn into_operator(self) -> Box<dyn Operator> {
+        self.operator
+    }
+
+    /// Returns the adaptive context, if adaptive execution is enabled.
+    #[must_use]
+    pub fn adaptive_context(&self) -> Option<&AdaptiveContext> {
+        self.adaptive_context.as_ref()
+    }
+
+    /// Takes ownership of the adaptive context.
+    pub fn take_adaptive_context(&mut self) -> Option<AdaptiveContext> {
+        self.adaptive_context.take()
+    }
+}
+
+/// An index lookup the planner runs in place of a label scan under a filter.
+pub(crate) struct IndexAccess {
+    /// The catalog index looked up.
+    pub(crate) index_id: IndexId,
+    /// For an equality lookup, how many leading keys are bound and whether
+    /// that is all of them.
+    pub(crate) prefix: Option<(usize, bool)>,
+    /// The lookup the scan runs.
+    pub(crate) lookup: IndexLookup,
+}
+
+/// A join the planner answers by probing a hash index on its inner side.
+pub(crate) struct IndexProbe<'a> {
+    /// The input read row by row.
+    pub(crate) outer: &'a LogicalOperator,
+    /// Variable of the indexed scan, bound to the matched node.
+    pub(crate) variable: &'a str,
+    /// Label of the indexed scan.
+    pub(crate) label: &'a str,
+    /// Indexed property.
+    pub(crate) property: &'a str,
+    /// Expression over the outer input that is looked up.
+    pub(crate) key: &'a LogicalExpression,
+    /// The catalog index probed.
+    pub(crate) index_id: IndexId,
+}
+
+/// Physical join algorithm picked for a logical join.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JoinStrategy {
+    /// Nested loop; the second input is materialized.
+    NestedLoop { swap_inputs: bool },
+    /// Hash join; the second input is the build side.
+    Hash { swap_inputs: bool },
+    /// Merge join over inputs already sorted on the keys.
+    Merge,
+}
+
+impl JoinStrategy {
+    /// Whether the right input becomes the first input of the operator.
+    pub(crate) fn swap_inputs(self) -> bool {
+        match self {
+            Self::NestedLoop { swap_inputs } | Self::Hash { swap_inputs } => swap_inputs,
+            Self::Merge => false,
+        }
+    }
+
+    /// Name of the physical operator.
+    pub(crate) fn operator_name(self) -> &'static str {
+        match self {
+            Self::NestedLoop { .. } => "NestedLoopJoin",
+            Self::Hash { .. } => "HashJoin",
+            Self::Merge => "MergeJoin",
+        }
+    }
+}
+
+/// Whether `op` is known to produce rows sorted ascending on `keys`, in order.
+fn sorted_on<'a>(op: &LogicalOperator, keys: impl Iterator<Item = &'a LogicalExpression>) -> bool {
+    let LogicalOperator::Sort(sort) = op else {
+        return false;
+    };
+    let mut sort_keys = sort.keys.iter();
+    keys.into_iter().all(|key| {
+        sort_keys.next().is_some_and(|sort_key| {
+            sort_key.order == SortOrder::Ascending
+                && matches!(
+                    (&sort_key.expression, key),
+                    (LogicalExpression::Variable(a), LogicalExpression::Variable(b)) if a == b
+                )
+        })
+    })
+}
+
+/// Picks the join algorithm and input order from the estimated input sizes.
+fn choose_join_strategy(
+    join_type: JoinType,
+    has_equality: bool,
+    inputs_sorted: bool,
+    left_rows: f64,
+    right_rows: f64,
+    nested_loop_threshold: f64,
+    cost_model: &CostModel,
+) -> JoinStrategy {
+    if inputs_sorted && has_equality && join_type == JoinType::Inner {
+        return JoinStrategy::Merge;
     }
 
-    /// Returns the adaptive context, if adaptive execution is enabled.
-    #[must_use]
-    pub fn adaptive_context(&self) -> Option<&AdaptiveContext> {
-        self.adaptive_context.as_ref()
+    // Only inner joins are symmetric, so only they may swap inputs
+    let can_swap = join_type == JoinType::Inner;
+    // The nested-loop operator implements inner, left, and cross joins
+    let nested_loop_ok = matches!(
+        join_type,
+        JoinType::Inner | JoinType::Left | JoinType::Cross
+    );
+
+    if nested_loop_ok {
+        if !has_equality {
+            return JoinStrategy::NestedLoop {
+                swap_inputs: can_swap && left_rows < right_rows,
+            };
+        }
+        if right_rows < nested_loop_threshold {
+            return JoinStrategy::NestedLoop { swap_inputs: false };
+        }
+        if can_swap && left_rows < nested_loop_threshold {
+            return JoinStrategy::NestedLoop { swap_inputs: true };
+        }
     }
 
-    /// Takes ownership of the adaptive context.
-    pub fn take_adaptive_context(&mut self) -> Option<AdaptiveContext> {
-        self.adaptive_context.take()
+    let build_right = cost_model.hash_join_cost(right_rows, left_rows);
+    let build_left = cost_model.hash_join_cost(left_rows, right_rows);
+    JoinStrategy::Hash {
+        swap_inputs: can_swap && build_left.total() < build_right.total(),
     }
 }
 
@@ -2259,8 +3798,8 @@ mod tests {
     use crate::query::plan::{
         AggregateExpr as LogicalAggregateExpr, CreateEdgeOp, CreateNodeOp, DeleteNodeOp,
         DistinctOp as LogicalDistinctOp, ExpandOp, FilterOp, JoinCondition, JoinOp,
-        LimitOp as LogicalLimitOp, NodeScanOp, ReturnItem, ReturnOp, SkipOp as LogicalSkipOp,
-        SortKey, SortOp,
+        LimitOp as LogicalLimitOp, NodeScanOp, ProjectOp, Projection, ReturnItem, ReturnOp,
+        SkipOp as LogicalSkipOp, SortKey, SortOp,
     };
     use grafeo_common::types::Value;
 
@@ -2743,6 +4282,35 @@ mod tests {
         assert_eq!(physical.columns(), &["n"]);
     }
 
+    #[test]
+    fn test_plan_limit_over_sort_uses_top_n() {
+        let store = create_test_store();
+        let planner = Planner::new(store);
+
+        // MATCH (n) RETURN n ORDER BY n LIMIT 1
+        let sort = LogicalOperator::Sort(SortOp {
+            keys: vec![SortKey {
+                expression: LogicalExpression::Variable("n".to_string()),
+                order: SortOrder::Ascending,
+            }],
+            input: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                variable: "n".to_string(),
+                label: None,
+                input: None,
+            })),
+        });
+        let logical = LogicalPlan::new(LogicalOperator::Limit(LogicalLimitOp {
+            count: 1,
+            input: Box::new(sort),
+        }));
+
+        let mut physical = planner.plan(&logical).unwrap();
+        assert_eq!(physical.operator.name(), "TopN");
+        assert_eq!(physical.columns(), &["n"]);
+        let chunk = physical.operator.next().unwrap().unwrap();
+        assert_eq!(chunk.row_count(), 1);
+    }
+
     #[test]
     fn test_plan_sort_descending() {
         let store = create_test_store();
@@ -3060,6 +4628,380 @@ mod tests {
         assert_eq!(physical.columns().len(), 2);
     }
 
+    /// Estimator that sizes `Person` and `Company` scans.
+    fn sized_estimator(people: u64, companies: u64) -> CardinalityEstimator {
+        use crate::query::optimizer::TableStats;
+
+        let mut estimator = CardinalityEstimator::new();
+        estimator.add_table_stats("Person", TableStats::new(people));
+        estimator.add_table_stats("Company", TableStats::new(companies));
+        estimator
+    }
+
+    /// `(a:left_label) JOIN (b:right_label)`, on `a = b` when `equi` is set.
+    fn labeled_join(left_label: &str, right_label: &str, equi: bool) -> LogicalPlan {
+        let scan = |variable: &str, label: &str| {
+            Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                variable: variable.to_string(),
+                label: Some(label.to_string()),
+                input: None,
+            }))
+        };
+        let (join_type, conditions) = if equi {
+            (
+                JoinType::Inner,
+                vec![JoinCondition {
+                    left: LogicalExpression::Variable("a".to_string()),
+                    right: LogicalExpression::Variable("b".to_string()),
+                }],
+            )
+        } else {
+            (JoinType::Cross, vec![])
+        };
+        LogicalPlan::new(LogicalOperator::Join(JoinOp {
+            left: scan("a", left_label),
+            right: scan("b", right_label),
+            join_type,
+            conditions,
+        }))
+    }
+
+    #[test]
+    fn test_plan_hash_join_spills_under_budget() {
+        use grafeo_common::memory::buffer::BufferManagerConfig;
+
+        let store = Arc::new(LpgStore::new());
+        for _ in 0..2_000 {
+            store.create_node(&["Person"]);
+        }
+        let spill_dir = tempfile::tempdir().unwrap();
+        let buffer_manager = BufferManager::new(BufferManagerConfig {
+            spill_path: Some(spill_dir.path().to_path_buf()),
+            ..BufferManagerConfig::with_budget(4096)
+        });
+        let planner = Planner::new(store).with_buffer_manager(Some(buffer_manager));
+
+        let mut physical = planner
+            .plan(&labeled_join("Person", "Person", true))
+            .unwrap();
+        assert_eq!(physical.operator.name(), "SpillableHashJoin");
+
+        let mut rows = 0;
+        while let Some(chunk) = physical.operator.next().unwrap() {
+            rows += chunk.row_count();
+        }
+        assert_eq!(rows, 2_000);
+        assert!(planner.spill_manager().unwrap().files_created() > 0);
+    }
+
+    #[test]
+    fn test_plan_hash_join_in_memory_without_buffer_manager() {
+        let planner = Planner::new(create_test_store());
+        let physical = planner
+            .plan(&labeled_join("Person", "Person", true))
+            .unwrap();
+        assert_eq!(physical.operator.name(), "HashJoin");
+        assert!(planner.spill_manager().is_none());
+    }
+
+    #[test]
+    fn test_plan_equi_join_builds_on_smaller_side() {
+        let planner = Planner::new(create_test_store())
+            .with_cardinality_estimator(sized_estimator(50_000, 2_000));
+
+        // Companies are the smaller input on the right: build on them as is
+        let physical = planner
+            .plan(&labeled_join("Person", "Company", true))
+            .unwrap();
+        assert_eq!(physical.operator.name(), "HashJoin");
+        assert_eq!(physical.columns(), &["a", "b"]);
+
+        // Companies on the left: the inputs swap so the build side stays small
+        let physical = planner
+            .plan(&labeled_join("Company", "Person", true))
+            .unwrap();
+        assert_eq!(physical.operator.name(), "HashJoin");
+        assert_eq!(physical.columns(), &["b", "a"]);
+    }
+
+    #[test]
+    fn test_plan_join_without_equality_uses_nested_loop() {
+        let planner = Planner::new(create_test_store())
+            .with_cardinality_estimator(sized_estimator(50_000, 2_000));
+
+        let physical = planner
+            .plan(&labeled_join("Person", "Company", false))
+            .unwrap();
+        assert_eq!(physical.operator.name(), "NestedLoopJoin");
+    }
+
+    #[test]
+    fn test_plan_join_with_tiny_side_uses_nested_loop() {
+        let planner = Planner::new(create_test_store())
+            .with_cardinality_estimator(sized_estimator(50_000, 5))
+            .with_nested_loop_threshold(10.0);
+
+        // The tiny input is materialized, so it moves to the right
+        let physical = planner
+            .plan(&labeled_join("Company", "Person", true))
+            .unwrap();
+        assert_eq!(physical.operator.name(), "NestedLoopJoin");
+        assert_eq!(physical.columns(), &["b", "a"]);
+
+        // With a lower threshold it is a hash join again
+        let planner = planner.with_nested_loop_threshold(1.0);
+        let physical = planner
+            .plan(&labeled_join("Company", "Person", true))
+            .unwrap();
+        assert_eq!(physical.operator.name(), "HashJoin");
+    }
+
+    #[test]
+    fn test_plan_join_of_sorted_inputs_uses_merge_join() {
+        let sorted_scan = |variable: &str, sorted: bool| {
+            let scan = LogicalOperator::NodeScan(NodeScanOp {
+                variable: variable.to_string(),
+                label: Some("Person".to_string()),
+                input: None,
+            });
+            if !sorted {
+                return Box::new(scan);
+            }
+            Box::new(LogicalOperator::Sort(SortOp {
+                keys: vec![SortKey {
+                    expression: LogicalExpression::Variable(variable.to_string()),
+                    order: SortOrder::Ascending,
+                }],
+                input: Box::new(scan),
+            }))
+        };
+        let join = |left_sorted: bool, right_sorted: bool| {
+            LogicalPlan::new(LogicalOperator::Join(JoinOp {
+                left: sorted_scan("a", left_sorted),
+                right: sorted_scan("b", right_sorted),
+                join_type: JoinType::Inner,
+                conditions: vec![JoinCondition {
+                    left: LogicalExpression::Variable("a".to_string()),
+                    right: LogicalExpression::Variable("b".to_string()),
+                }],
+            }))
+        };
+        let planner = Planner::new(create_test_store());
+
+        let mut physical = planner.plan(&join(true, true)).unwrap();
+        assert_eq!(physical.operator.name(), "MergeJoin");
+        assert_eq!(physical.columns(), &["a", "b"]);
+        // Each of the two people matches itself
+        let chunk = physical.operator.next().unwrap().unwrap();
+        assert_eq!(chunk.row_count(), 2);
+
+        // One unsorted side rules it out
+        let physical = planner.plan(&join(true, false)).unwrap();
+        assert_ne!(physical.operator.name(), "MergeJoin");
+    }
+
+    #[test]
+    fn test_plan_merge_join_on_mixed_numeric_keys_matches_hash_join() {
+        let store = Arc::new(LpgStore::new());
+        for score in [
+            Value::Int64(1),
+            Value::Float64(2.0),
+            Value::Int64(2),
+            Value::Float64(3.5),
+        ] {
+            store.create_node_with_props(&["Person"], [("score", score)]);
+        }
+        // `n.score AS alias` over the people, sorted on the alias if asked
+        let scores = |node: &str, alias: &str, sorted: bool| {
+            let project = LogicalOperator::Project(ProjectOp {
+                projections: vec![Projection {
+                    expression: LogicalExpression::Property {
+                        variable: node.to_string(),
+                        property: "score".to_string(),
+                    },
+                    alias: Some(alias.to_string()),
+                }],
+                input: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                    variable: node.to_string(),
+                    label: Some("Person".to_string()),
+                    input: None,
+                })),
+            });
+            if !sorted {
+                return Box::new(project);
+            }
+            Box::new(LogicalOperator::Sort(SortOp {
+                keys: vec![SortKey {
+                    expression: LogicalExpression::Variable(alias.to_string()),
+                    order: SortOrder::Ascending,
+                }],
+                input: Box::new(project),
+            }))
+        };
+        let run = |right_sorted: bool| {
+            let plan = LogicalPlan::new(LogicalOperator::Join(JoinOp {
+                left: scores("a", "x", true),
+                right: scores("b", "y", right_sorted),
+                join_type: JoinType::Inner,
+                conditions: vec![JoinCondition {
+                    left: LogicalExpression::Variable("x".to_string()),
+                    right: LogicalExpression::Variable("y".to_string()),
+                }],
+            }));
+            let mut physical = Planner::new(Arc::clone(&store))
+                .with_nested_loop_threshold(0.0)
+                .plan(&plan)
+                .unwrap();
+            let name = physical.operator.name();
+            let mut pairs = Vec::new();
+            while let Some(chunk) = physical.operator.next().unwrap() {
+                for row in chunk.selected_indices() {
+                    let value = |col: usize| chunk.column(col).unwrap().get_value(row).unwrap();
+                    pairs.push(format!("{:?}={:?}", value(0), value(1)));
+                }
+            }
+            pairs.sort();
+            (name, pairs)
+        };
+
+        let (merge, merged) = run(true);
+        let (hash, hashed) = run(false);
+        assert_eq!(merge, "MergeJoin");
+        assert_eq!(hash, "HashJoin");
+        // 2 and 2.0 only match themselves, whichever join runs
+        assert_eq!(merged.len(), 4, "{merged:?}");
+        assert_eq!(merged, hashed);
+    }
+
+    /// `MATCH (a:Person) WHERE [NOT] EXISTS { MATCH (a)-[:KNOWS]->(b) }` over
+    /// three people, one of whom knows another.
+    fn plan_exists_filter(negated: bool) -> PhysicalPlan {
+        let store = Arc::new(LpgStore::new());
+        let alix = store.create_node(&["Person"]);
+        let gus = store.create_node(&["Person"]);
+        store.create_node(&["Person"]);
+        store.create_edge(alix, gus, "KNOWS");
+
+        let exists =
+            LogicalExpression::ExistsSubquery(Box::new(LogicalOperator::Expand(ExpandOp {
+                from_variable: "a".to_string(),
+                to_variable: "b".to_string(),
+                edge_variable: None,
+                direction: ExpandDirection::Outgoing,
+                edge_type: Some("KNOWS".to_string()),
+                min_hops: 1,
+                max_hops: Some(1),
+                input: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                    variable: "a".to_string(),
+                    label: None,
+                    input: None,
+                })),
+                path_alias: None,
+            })));
+        let predicate = if negated {
+            LogicalExpression::Unary {
+                op: UnaryOp::Not,
+                operand: Box::new(exists),
+            }
+        } else {
+            exists
+        };
+        let logical = LogicalPlan::new(LogicalOperator::Filter(FilterOp {
+            predicate,
+            input: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                variable: "a".to_string(),
+                label: Some("Person".to_string()),
+                input: None,
+            })),
+        }));
+
+        Planner::new(store).plan(&logical).unwrap()
+    }
+
+    fn count_rows(physical: &mut PhysicalPlan) -> usize {
+        let mut rows = 0;
+        while let Some(chunk) = physical.operator.next().unwrap() {
+            rows += chunk.row_count();
+        }
+        rows
+    }
+
+    #[test]
+    fn test_plan_exists_lowers_to_semi_join() {
+        let mut physical = plan_exists_filter(false);
+
+        // The join replaces the filter and keeps only the outer columns
+        assert_eq!(physical.operator.name(), "HashJoin");
+        assert_eq!(physical.columns(), &["a"]);
+        assert_eq!(count_rows(&mut physical), 1);
+    }
+
+    #[test]
+    fn test_plan_not_exists_lowers_to_anti_join() {
+        let mut physical = plan_exists_filter(true);
+
+        assert_eq!(physical.operator.name(), "HashJoin");
+        assert_eq!(physical.columns(), &["a"]);
+        assert_eq!(count_rows(&mut physical), 2);
+    }
+
+    #[test]
+    fn test_plan_indexed_join_probes_instead_of_scanning() {
+        let store = Arc::new(LpgStore::new());
+        let catalog = Arc::new(Catalog::new());
+        // Two people share a name, so one pet matches both
+        for name in ["Alix", "Gus", "Vincent", "Gus"] {
+            let person = store.create_node(&["Person"]);
+            store.set_node_property(person, "name", Value::from(name));
+        }
+        catalog.create_hash_index(&store, "Person", "name");
+        // Written after the index exists, so only found if writes keep it current
+        let mia = store.create_node(&["Person"]);
+        store.set_node_property(mia, "name", Value::from("Mia"));
+        for owner in ["Alix", "Gus", "Gus", "Mia"] {
+            let pet = store.create_node(&["Pet"]);
+            store.set_node_property(pet, "owner", Value::from(owner));
+        }
+
+        // MATCH (pet:Pet), (p:Person) WHERE pet.owner = p.name
+        let logical = LogicalPlan::new(LogicalOperator::Join(JoinOp {
+            left: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                variable: "pet".to_string(),
+                label: Some("Pet".to_string()),
+                input: None,
+            })),
+            right: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                variable: "p".to_string(),
+                label: Some("Person".to_string()),
+                input: None,
+            })),
+            join_type: JoinType::Inner,
+            conditions: vec![JoinCondition {
+                left: LogicalExpression::Property {
+                    variable: "pet".to_string(),
+                    property: "owner".to_string(),
+                },
+                right: LogicalExpression::Property {
+                    variable: "p".to_string(),
+                    property: "name".to_string(),
+                },
+            }],
+        }));
+        let planner = Planner::new(store).with_catalog(catalog).with_profiling();
+
+        let mut physical = planner.plan(&logical).unwrap();
+        assert_eq!(physical.operator.name(), "IndexNestedLoopJoin");
+        assert_eq!(physical.columns().last().map(String::as_str), Some("p"));
+        assert_eq!(count_rows(&mut physical), 6);
+
+        // The outer side was read once and the indexed side not at all
+        let LogicalOperator::Join(join) = &logical.root else {
+            unreachable!()
+        };
+        assert_eq!(planner.profile(&join.left).unwrap().rows(), 4);
+        assert!(planner.profile(&join.right).is_none());
+    }
+
     // ==================== Mutation Tests ====================
 
     #[test]
@@ -3246,4 +5188,280 @@ mod tests {
         // Test into_operator
         let _ = physical.into_operator();
     }
+
+    // ==================== Index Selection Tests ====================
+
+    fn eq_predicate(property: &str, value: Value) -> LogicalExpression {
+        LogicalExpression::Binary {
+            left: Box::new(LogicalExpression::Property {
+                variable: "n".to_string(),
+                property: property.to_string(),
+            }),
+            op: BinaryOp::Eq,
+            right: Box::new(LogicalExpression::Literal(value)),
+        }
+    }
+
+    fn person_filter(predicate: LogicalExpression) -> FilterOp {
+        FilterOp {
+            predicate,
+            input: Box::new(LogicalOperator::NodeScan(NodeScanOp {
+                variable: "n".to_string(),
+                label: Some("Person".to_string()),
+                input: None,
+            })),
+        }
+    }
+
+    fn planner_with_name_index() -> (Planner, IndexId) {
+        let store = create_test_store();
+        for (last, first) in [("Smith", "Ann"), ("Smith", "Bob"), ("Jones", "Ann")] {
+            store.create_node_with_props(
+                &["Person"],
+                [
+                    ("lastName", Value::from(last)),
+                    ("firstName", Value::from(first)),
+                ],
+            );
+        }
+        let catalog = Arc::new(Catalog::new());
+        let idx = catalog.create_composite_index(&store, "Person", &["lastName", "firstName"]);
+        (Planner::new(store).with_catalog(catalog), idx)
+    }
+
+    #[test]
+    fn test_plan_filter_scans_composite_index_prefix() {
+        let (planner, _) = planner_with_name_index();
+        let plan = |filter: FilterOp| {
+            let lookup = planner.index_lookup(&filter).map(|access| access.lookup);
+            let mut physical = planner
+                .plan(&LogicalPlan::new(LogicalOperator::Filter(filter)))
+                .unwrap();
+            (lookup, count_rows(&mut physical))
+        };
+
+        // The leading key alone is a prefix lookup
+        let (lookup, rows) = plan(person_filter(eq_predicate(
+            "lastName",
+            Value::from("Smith"),
+        )));
+        assert_eq!(
+            lookup,
+            Some(IndexLookup::Composite {
+                properties: vec!["lastName".to_string(), "firstName".to_string()],
+                values: vec![Value::from("Smith")],
+            })
+        );
+        assert_eq!(rows, 2);
+
+        // Both keys are a point lookup
+        let (lookup, rows) = plan(person_filter(LogicalExpression::Binary {
+            left: Box::new(eq_predicate("firstName", Value::from("Ann"))),
+            op: BinaryOp::And,
+            right: Box::new(eq_predicate("lastName", Value::from("Smith"))),
+        }));
+        assert!(matches!(
+            lookup,
+            Some(IndexLookup::Composite { values, .. }) if values.len() == 2
+        ));
+        assert_eq!(rows, 1);
+
+        // The second key alone scans the label
+        let (lookup, rows) = plan(person_filter(eq_predicate("firstName", Value::from("Ann"))));
+        assert!(lookup.is_none());
+        assert_eq!(rows, 2);
+
+        // As does an index the store doesn't hold
+        let catalog = Arc::new(Catalog::new());
+        let person = catalog.get_or_create_label("Person");
+        let last = catalog.get_or_create_property_key("lastName");
+        catalog.create_index(person, last, IndexType::BTree);
+        let planner = Planner::new(create_test_store()).with_catalog(catalog);
+        let filter = person_filter(eq_predicate("lastName", Value::from("Smith")));
+        assert!(planner.select_index(&filter).is_some());
+        assert!(planner.index_lookup(&filter).is_none());
+    }
+
+    #[test]
+    fn test_access_pattern_follows_physical_lookup() {
+        let plan = |filter: FilterOp| LogicalPlan::new(LogicalOperator::Filter(filter));
+
+        let (planner, _) = planner_with_name_index();
+        let filter = person_filter(eq_predicate("lastName", Value::from("Smith")));
+        assert_eq!(planner.access_pattern(&plan(filter)), AccessPattern::Random);
+
+        // A catalog index the store doesn't hold still runs as a full scan
+        let catalog = Arc::new(Catalog::new());
+        let person = catalog.get_or_create_label("Person");
+        let last = catalog.get_or_create_property_key("lastName");
+        catalog.create_index(person, last, IndexType::BTree);
+        let planner = Planner::new(create_test_store()).with_catalog(catalog);
+        let filter = person_filter(eq_predicate("lastName", Value::from("Smith")));
+        assert!(planner.select_index(&filter).is_some());
+        assert_eq!(
+            planner.access_pattern(&plan(filter)),
+            AccessPattern::Sequential
+        );
+    }
+
+    #[test]
+    fn test_select_index_leading_column() {
+        let (planner, idx) = planner_with_name_index();
+
+        // MATCH (n:Person) WHERE n.lastName = 'Smith'
+        let filter = person_filter(eq_predicate("lastName", Value::from("Smith")));
+        let selected = planner.select_index(&filter).unwrap();
+        assert_eq!(selected.index_id, idx);
+        assert_eq!(selected.prefix_len, 1);
+        assert!(!selected.full_key);
+    }
+
+    #[test]
+    fn test_select_index_full_key() {
+        let (planner, idx) = planner_with_name_index();
+
+        // MATCH (n:Person) WHERE n.firstName = 'Ann' AND n.lastName = 'Smith'
+        let filter = person_filter(LogicalExpression::Binary {
+            left: Box::new(eq_predicate("firstName", Value::from("Ann"))),
+            op: BinaryOp::And,
+            right: Box::new(eq_predicate("lastName", Value::from("Smith"))),
+        });
+        let selected = planner.select_index(&filter).unwrap();
+        assert_eq!(selected.index_id, idx);
+        assert_eq!(selected.prefix_len, 2);
+        assert!(selected.full_key);
+    }
+
+    #[test]
+    fn test_select_index_skips_second_column_only() {
+        let (planner, _) = planner_with_name_index();
+
+        // MATCH (n:Person) WHERE n.firstName = 'Ann'
+        let filter = person_filter(eq_predicate("firstName", Value::from("Ann")));
+        assert!(planner.select_index(&filter).is_none());
+    }
+
+    #[test]
+    fn test_select_index_ignores_non_equality() {
+        let (planner, _) = planner_with_name_index();
+
+        // MATCH (n:Person) WHERE n.lastName > 'S'
+        let filter = person_filter(LogicalExpression::Binary {
+            left: Box::new(LogicalExpression::Property {
+                variable: "n".to_string(),
+                property: "lastName".to_string(),
+            }),
+            op: BinaryOp::Gt,
+            right: Box::new(LogicalExpression::Literal(Value::from("S"))),
+        });
+        assert!(planner.select_index(&filter).is_none());
+
+        // Without a catalog nothing is selected
+        let planner = Planner::new(create_test_store());
+        let filter = person_filter(eq_predicate("lastName", Value::from("Smith")));
+        assert!(planner.select_index(&filter).is_none());
+    }
+
+    fn planner_with_name_trie(case_sensitive: bool) -> (Planner, IndexId) {
+        let store = create_test_store();
+        for name in ["Alix", "alma", "Gus"] {
+            store.create_node_with_props(&["Person"], [("name", Value::from(name))]);
+        }
+        let catalog = Arc::new(Catalog::new());
+        let idx = catalog.create_text_index(&store, "Person", "name", case_sensitive);
+        (Planner::new(store).with_catalog(catalog), idx)
+    }
+
+    fn starts_with(property: &str, prefix: LogicalExpression) -> LogicalExpression {
+        LogicalExpression::Binary {
+            left: Box::new(LogicalExpression::Property {
+                variable: "n".to_string(),
+                property: property.to_string(),
+            }),
+            op: BinaryOp::StartsWith,
+            right: Box::new(prefix),
+        }
+    }
+
+    #[test]
+    fn test_select_text_index_for_starts_with() {
+        let (planner, idx) = planner_with_name_trie(true);
+
+        // MATCH (n:Person) WHERE n.name STARTS WITH 'Al'
+        let filter = person_filter(starts_with(
+            "name",
+            LogicalExpression::Literal(Value::from("Al")),
+        ));
+        assert_eq!(planner.select_text_index(&filter), Some(idx));
+
+        // Parameterized prefixes qualify too, alongside other conjuncts
+        let filter = person_filter(LogicalExpression::Binary {
+            left: Box::new(eq_predicate("age", Value::Int64(30))),
+            op: BinaryOp::And,
+            right: Box::new(starts_with(
+                "name",
+                LogicalExpression::Parameter("prefix".to_string()),
+            )),
+        });
+        assert_eq!(planner.select_text_index(&filter), Some(idx));
+    }
+
+    #[test]
+    fn test_select_text_index_skips_empty_prefix_and_other_properties() {
+        let (planner, _) = planner_with_name_trie(true);
+
+        let filter = person_filter(starts_with(
+            "name",
+            LogicalExpression::Literal(Value::from("")),
+        ));
+        assert!(planner.select_text_index(&filter).is_none());
+
+        let filter = person_filter(starts_with(
+            "city",
+            LogicalExpression::Literal(Value::from("Am")),
+        ));
+        assert!(planner.select_text_index(&filter).is_none());
+
+        // Equality doesn't use a trie index
+        let filter = person_filter(eq_predicate("name", Value::from("Alice")));
+        assert!(planner.select_text_index(&filter).is_none());
+        assert!(planner.select_index(&filter).is_none());
+    }
+
+    #[test]
+    fn test_plan_filter_scans_text_index_prefix() {
+        let run = |planner: &Planner, prefix: &str| {
+            let filter = person_filter(starts_with(
+                "name",
+                LogicalExpression::Literal(Value::from(prefix)),
+            ));
+            let lookup = planner.index_lookup(&filter).map(|access| access.lookup);
+            let mut physical = planner
+                .plan(&LogicalPlan::new(LogicalOperator::Filter(filter)))
+                .unwrap();
+            (lookup, count_rows(&mut physical))
+        };
+
+        let (planner, _) = planner_with_name_trie(true);
+        let (lookup, rows) = run(&planner, "Al");
+        assert_eq!(
+            lookup,
+            Some(IndexLookup::Prefix {
+                property: "name".to_string(),
+                prefix: "Al".to_string(),
+            })
+        );
+        assert_eq!(rows, 1);
+
+        // The empty prefix scans the label, keeping every string
+        let (lookup, rows) = run(&planner, "");
+        assert!(lookup.is_none());
+        assert_eq!(rows, 3);
+
+        // A case-insensitive index finds more, and the filter keeps the case
+        let (planner, _) = planner_with_name_trie(false);
+        let (lookup, rows) = run(&planner, "al");
+        assert!(lookup.is_some());
+        assert_eq!(rows, 1);
+    }
 }
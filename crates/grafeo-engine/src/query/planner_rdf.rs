@@ -6,25 +6,27 @@
 //! This planner follows the same push-based, vectorized execution model as
 //! the LPG planner for consistent performance characteristics.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
+use grafeo_common::memory::buffer::BufferManager;
 use grafeo_common::types::{LogicalType, TxId, Value};
 use grafeo_common::utils::error::{Error, Result};
-use grafeo_core::execution::DataChunk;
 use grafeo_core::execution::operators::JoinType;
 use grafeo_core::execution::operators::{
-    BinaryFilterOp, FilterExpression, FilterOperator, HashAggregateOperator, JoinCondition,
-    LimitOperator, NestedLoopJoinOperator, Operator, OperatorError, Predicate, ProjectOperator,
-    SimpleAggregateOperator, SkipOperator, SortOperator, UnaryFilterOp,
+    BinaryFilterOp, CancellableOperator, CancellationToken, FilterExpression, FilterOperator,
+    HashAggregateOperator, JoinCondition, LimitOperator, NestedLoopJoinOperator, Operator,
+    OperatorError, Predicate, SimpleAggregateOperator, SkipOperator, SortOperator, UnaryFilterOp,
 };
+use grafeo_core::execution::{DataChunk, ExecutionMemoryContext};
 use grafeo_core::graph::rdf::{Literal, RdfStore, Term, Triple, TriplePattern};
 
 use crate::query::plan::{
-    AggregateFunction as LogicalAggregateFunction, AggregateOp, AntiJoinOp, ClearGraphOp,
-    CreateGraphOp, DeleteTripleOp, DropGraphOp, FilterOp, InsertTripleOp, LeftJoinOp, LimitOp,
-    LogicalExpression, LogicalOperator, LogicalPlan, ModifyOp, SkipOp, SortOp, TripleComponent,
-    TripleScanOp, TripleTemplate,
+    AggregateFunction as LogicalAggregateFunction, AggregateOp, AntiJoinOp, AskOp, ClearGraphOp,
+    ConstructOp, CreateGraphOp, DeleteTripleOp, DropGraphOp, FilterOp, InsertTripleOp, LeftJoinOp,
+    LimitOp, LogicalExpression, LogicalOperator, LogicalPlan, ModifyOp, SkipOp, SortOp,
+    TripleComponent, TriplePathOp, TripleScanOp, TripleTemplate, ValuesOp,
 };
 use crate::query::planner::{PhysicalPlan, convert_aggregate_function, convert_filter_expression};
 
@@ -42,6 +44,10 @@ pub struct RdfPlanner {
     chunk_size: usize,
     /// Optional transaction ID for transactional operations.
     tx_id: Option<TxId>,
+    /// Token every planned operator checks before pulling its next chunk.
+    cancellation: Option<CancellationToken>,
+    /// Budget sorts reserve the rows they hold from.
+    buffer_manager: Option<Arc<BufferManager>>,
 }
 
 impl RdfPlanner {
@@ -52,6 +58,8 @@ impl RdfPlanner {
             store,
             chunk_size: DEFAULT_CHUNK_SIZE,
             tx_id: None,
+            cancellation: None,
+            buffer_manager: None,
         }
     }
 
@@ -69,6 +77,23 @@ impl RdfPlanner {
         self
     }
 
+    /// Stops the planned query once `timeout` has passed, counted from when
+    /// the plan first pulls a chunk.
+    /// `None` leaves the query unbounded.
+    #[must_use]
+    pub fn with_query_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.cancellation = timeout.map(CancellationToken::with_timeout);
+        self
+    }
+
+    /// Has sorts reserve the rows they hold from `manager`, failing the
+    /// query when refused. `None` leaves them unbounded.
+    #[must_use]
+    pub fn with_buffer_manager(mut self, manager: Option<Arc<BufferManager>>) -> Self {
+        self.buffer_manager = manager;
+        self
+    }
+
     /// Plans a logical plan into a physical operator tree.
     ///
     /// # Errors
@@ -83,10 +108,25 @@ impl RdfPlanner {
         })
     }
 
-    /// Plans a single logical operator.
+    /// Plans a single logical operator, wrapping it for cancellation if a
+    /// timeout is set.
     fn plan_operator(&self, op: &LogicalOperator) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        let (operator, columns) = self.plan_uncancellable(op)?;
+        match &self.cancellation {
+            Some(token) => Ok((
+                Box::new(CancellableOperator::new(operator, token.clone())),
+                columns,
+            )),
+            None => Ok((operator, columns)),
+        }
+    }
+
+    /// Plans a single logical operator (and its inputs) without wrapping it.
+    fn plan_uncancellable(&self, op: &LogicalOperator) -> Result<(Box<dyn Operator>, Vec<String>)> {
         match op {
             LogicalOperator::TripleScan(scan) => self.plan_triple_scan(scan),
+            LogicalOperator::TriplePath(path) => self.plan_triple_path(path),
+            LogicalOperator::Values(values) => self.plan_values(values),
             LogicalOperator::Filter(filter) => self.plan_filter(filter),
             LogicalOperator::Project(project) => self.plan_project(project),
             LogicalOperator::Limit(limit) => self.plan_limit(limit),
@@ -102,6 +142,8 @@ impl RdfPlanner {
             LogicalOperator::InsertTriple(insert) => self.plan_insert_triple(insert),
             LogicalOperator::DeleteTriple(delete) => self.plan_delete_triple(delete),
             LogicalOperator::Modify(modify) => self.plan_modify(modify),
+            LogicalOperator::Construct(construct) => self.plan_construct(construct),
+            LogicalOperator::Ask(ask) => self.plan_ask(ask),
             LogicalOperator::ClearGraph(clear) => self.plan_clear_graph(clear),
             LogicalOperator::CreateGraph(create) => self.plan_create_graph(create),
             LogicalOperator::DropGraph(drop_op) => self.plan_drop_graph(drop_op),
@@ -153,6 +195,58 @@ impl RdfPlanner {
         Ok((operator, columns))
     }
 
+    /// Plans a quantified property path.
+    ///
+    /// The path is walked over the store's triples by [`RdfPathOperator`];
+    /// its columns are the endpoint variables, subject first.
+    fn plan_triple_path(&self, path: &TriplePathOp) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        if path.min_hops > 1 {
+            return Err(Error::Internal(format!(
+                "Property paths of at least {} steps are not supported",
+                path.min_hops
+            )));
+        }
+
+        let mut columns = Vec::new();
+        if let TripleComponent::Variable(name) = &path.subject {
+            columns.push(name.clone());
+        }
+        let same_variable = matches!(
+            (&path.subject, &path.object),
+            (TripleComponent::Variable(s), TripleComponent::Variable(o)) if s == o
+        );
+        if let TripleComponent::Variable(name) = &path.object {
+            if !same_variable {
+                columns.push(name.clone());
+            }
+        }
+
+        let operator = Box::new(RdfPathOperator {
+            store: Arc::clone(&self.store),
+            predicate: Term::iri(path.predicate.clone()),
+            inverse: path.inverse,
+            subject: component_to_term(&path.subject),
+            object: component_to_term(&path.object),
+            same_variable,
+            min_hops: path.min_hops,
+            max_hops: path.max_hops,
+            chunk_size: self.chunk_size,
+            pairs: None,
+            position: 0,
+        });
+        Ok((operator, columns))
+    }
+
+    /// Plans an inline VALUES block.
+    fn plan_values(&self, values: &ValuesOp) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        let operator = Box::new(RdfValuesOperator::new(
+            values.variables.len(),
+            values.rows.clone(),
+            self.chunk_size,
+        ));
+        Ok((operator, values.variables.clone()))
+    }
+
     /// Builds a TriplePattern from a TripleScanOp.
     fn build_triple_pattern(&self, scan: &TripleScanOp) -> TriplePattern {
         TriplePattern {
@@ -167,8 +261,6 @@ impl RdfPlanner {
         &self,
         ret: &crate::query::plan::ReturnOp,
     ) -> Result<(Box<dyn Operator>, Vec<String>)> {
-        let (input_op, _input_columns) = self.plan_operator(&ret.input)?;
-
         // Extract output column names
         let columns: Vec<String> = ret
             .items
@@ -180,6 +272,20 @@ impl RdfPlanner {
             })
             .collect();
 
+        // An Empty input was pruned by the optimizer: no rows, but keep the columns
+        if matches!(ret.input.as_ref(), LogicalOperator::Empty) {
+            let single_row: Box<dyn Operator> =
+                Box::new(grafeo_core::execution::operators::single_row::SingleRowOperator::new());
+            let operator = Box::new(LimitOperator::new(
+                single_row,
+                0,
+                derive_rdf_schema(&columns),
+            ));
+            return Ok((operator, columns));
+        }
+
+        let (input_op, _input_columns) = self.plan_operator(&ret.input)?;
+
         Ok((input_op, columns))
     }
 
@@ -250,7 +356,13 @@ impl RdfPlanner {
             .collect::<Result<Vec<_>>>()?;
 
         let output_schema = derive_rdf_schema(&columns);
-        let operator = Box::new(SortOperator::new(input_op, physical_keys, output_schema));
+        let sort = SortOperator::new(input_op, physical_keys, output_schema);
+        let operator: Box<dyn Operator> = match &self.buffer_manager {
+            Some(manager) => {
+                Box::new(sort.with_memory_context(ExecutionMemoryContext::new(Arc::clone(manager))))
+            }
+            None => Box::new(sort),
+        };
         Ok((operator, columns))
     }
 
@@ -400,8 +512,6 @@ impl RdfPlanner {
         let (left_op, left_columns) = self.plan_operator(&join.left)?;
         let (right_op, right_columns) = self.plan_operator(&join.right)?;
 
-        let left_col_count = left_columns.len();
-
         // Find shared variables for equi-join
         let mut shared_vars: Vec<(usize, usize)> = Vec::new(); // (left_idx, right_idx)
         for (left_idx, left_col) in left_columns.iter().enumerate() {
@@ -418,14 +528,7 @@ impl RdfPlanner {
         let full_schema = derive_rdf_schema(&full_columns);
 
         // Determine which columns to project (all left + non-duplicate right)
-        let mut projection_indices: Vec<usize> = (0..left_col_count).collect();
-        let mut output_columns = left_columns.clone();
-        for (right_idx, right_col) in right_columns.iter().enumerate() {
-            if !left_columns.contains(right_col) {
-                projection_indices.push(left_col_count + right_idx);
-                output_columns.push(right_col.clone());
-            }
-        }
+        let (sources, output_columns) = merged_join_columns(&left_columns, &right_columns);
 
         let join_type = if shared_vars.is_empty() {
             JoinType::Cross
@@ -447,15 +550,15 @@ impl RdfPlanner {
             full_schema,
         ));
 
-        // If we have duplicate columns to remove, wrap with projection
-        if projection_indices.len() < full_columns.len() {
+        // If we have duplicate columns to remove, merge them
+        if output_columns.len() < full_columns.len() {
             let output_schema = derive_rdf_schema(&output_columns);
-            let project_op = Box::new(ProjectOperator::select_columns(
+            let merge_op = Box::new(RdfMergeColumnsOperator::new(
                 join_op,
-                projection_indices,
+                sources,
                 output_schema,
             ));
-            Ok((project_op, output_columns))
+            Ok((merge_op, output_columns))
         } else {
             Ok((join_op, output_columns))
         }
@@ -466,8 +569,6 @@ impl RdfPlanner {
         let (left_op, left_columns) = self.plan_operator(&join.left)?;
         let (right_op, right_columns) = self.plan_operator(&join.right)?;
 
-        let left_col_count = left_columns.len();
-
         // Find shared variables for equi-join
         let mut shared_vars: Vec<(usize, usize)> = Vec::new();
         for (left_idx, left_col) in left_columns.iter().enumerate() {
@@ -484,14 +585,7 @@ impl RdfPlanner {
         let full_schema = derive_rdf_schema(&full_columns);
 
         // Determine which columns to project (all left + non-duplicate right)
-        let mut projection_indices: Vec<usize> = (0..left_col_count).collect();
-        let mut output_columns = left_columns.clone();
-        for (right_idx, right_col) in right_columns.iter().enumerate() {
-            if !left_columns.contains(right_col) {
-                projection_indices.push(left_col_count + right_idx);
-                output_columns.push(right_col.clone());
-            }
-        }
+        let (sources, output_columns) = merged_join_columns(&left_columns, &right_columns);
 
         let join_condition: Option<Box<dyn JoinCondition>> = if shared_vars.is_empty() {
             None
@@ -507,15 +601,15 @@ impl RdfPlanner {
             full_schema,
         ));
 
-        // If we have duplicate columns to remove, wrap with projection
-        if projection_indices.len() < full_columns.len() {
+        // If we have duplicate columns to remove, merge them
+        if output_columns.len() < full_columns.len() {
             let output_schema = derive_rdf_schema(&output_columns);
-            let project_op = Box::new(ProjectOperator::select_columns(
+            let merge_op = Box::new(RdfMergeColumnsOperator::new(
                 join_op,
-                projection_indices,
+                sources,
                 output_schema,
             ));
-            Ok((project_op, output_columns))
+            Ok((merge_op, output_columns))
         } else {
             Ok((join_op, output_columns))
         }
@@ -543,6 +637,12 @@ impl RdfPlanner {
             }
         }
 
+        // MINUS only removes solutions that share a variable with the right
+        // side, so with no shared variables every left row survives
+        if left_keys.is_empty() {
+            return Ok((left_op, left_columns));
+        }
+
         // Output is just left columns (anti-join filters out matching rows)
         let columns = left_columns.clone();
         let output_schema = derive_rdf_schema(&columns);
@@ -784,6 +884,46 @@ impl RdfPlanner {
 
         Ok((operator, Vec::new()))
     }
+
+    /// Plans a CONSTRUCT operator, fed by the WHERE clause bindings.
+    fn plan_construct(&self, construct: &ConstructOp) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        let (input_op, input_columns) = self.plan_operator(&construct.input)?;
+
+        // Build column index map for variable substitution
+        let column_map: HashMap<String, usize> = input_columns
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.clone(), i))
+            .collect();
+
+        let operator = Box::new(RdfConstructOperator::new(
+            input_op,
+            construct.templates.clone(),
+            column_map,
+            self.chunk_size,
+        ));
+        let columns = vec![
+            "subject".to_string(),
+            "predicate".to_string(),
+            "object".to_string(),
+        ];
+
+        Ok((operator, columns))
+    }
+
+    /// Plans an ASK operator.
+    ///
+    /// Produces the WHERE clause rows; callers reduce them to a boolean with
+    /// [`QueryResult::boolean`](crate::database::QueryResult::boolean).
+    fn plan_ask(&self, ask: &AskOp) -> Result<(Box<dyn Operator>, Vec<String>)> {
+        // The empty group pattern has one solution, binding nothing
+        if matches!(ask.input.as_ref(), LogicalOperator::Empty) {
+            let single_row: Box<dyn Operator> =
+                Box::new(grafeo_core::execution::operators::single_row::SingleRowOperator::new());
+            return Ok((single_row, Vec::new()));
+        }
+        self.plan_operator(&ask.input)
+    }
 }
 
 // ============================================================================
@@ -1422,6 +1562,295 @@ impl Operator for RdfModifyOperator {
     }
 }
 
+// ============================================================================
+// RDF Construct Operator (SPARQL CONSTRUCT)
+// ============================================================================
+
+/// Operator that instantiates CONSTRUCT templates with the WHERE bindings.
+///
+/// Streams each constructed triple once, as `subject`, `predicate` and
+/// `object` columns. For each binding row, a template with an unbound
+/// variable, or that would put a literal where an IRI belongs, produces
+/// nothing rather than a partial triple.
+struct RdfConstructOperator {
+    input: Box<dyn Operator>,
+    templates: Vec<TripleTemplate>,
+    column_map: HashMap<String, usize>,
+    chunk_size: usize,
+    /// Prefix for template blank nodes, unique to this query so they can't
+    /// collide with blank nodes already in the data.
+    blank_prefix: String,
+    /// Constructed triples, in construction order (lazily populated).
+    triples: Option<Vec<Triple>>,
+    /// Current position in the triples.
+    position: usize,
+}
+
+impl RdfConstructOperator {
+    fn new(
+        input: Box<dyn Operator>,
+        templates: Vec<TripleTemplate>,
+        column_map: HashMap<String, usize>,
+        chunk_size: usize,
+    ) -> Self {
+        Self {
+            input,
+            templates,
+            column_map,
+            chunk_size,
+            blank_prefix: fresh_blank_prefix(),
+            triples: None,
+            position: 0,
+        }
+    }
+
+    /// Instantiates every template for every binding row.
+    fn construct(&mut self) -> std::result::Result<Vec<Triple>, OperatorError> {
+        let mut seen = HashSet::new();
+        let mut triples = Vec::new();
+        let mut solution = 0;
+        while let Some(chunk) = self.input.next()? {
+            for row in 0..chunk.row_count() {
+                solution += 1;
+                for template in &self.templates {
+                    let subject = self.instantiate(&template.subject, &chunk, row, solution);
+                    let predicate = self.instantiate(&template.predicate, &chunk, row, solution);
+                    let object = self.instantiate(&template.object, &chunk, row, solution);
+
+                    let (Some(s), Some(p), Some(o)) = (subject, predicate, object) else {
+                        continue;
+                    };
+                    if s.is_literal() || !p.is_iri() {
+                        continue;
+                    }
+                    let triple = Triple::new(s, p, o);
+                    if seen.insert(triple.clone()) {
+                        triples.push(triple);
+                    }
+                }
+            }
+        }
+        Ok(triples)
+    }
+
+    fn instantiate(
+        &self,
+        component: &TripleComponent,
+        chunk: &DataChunk,
+        row: usize,
+        solution: usize,
+    ) -> Option<Term> {
+        let TripleComponent::Variable(name) = component else {
+            return component_to_term(component);
+        };
+        if let Some(label) = name.strip_prefix("_:") {
+            // Template blank nodes are fresh for every solution
+            return Some(Term::blank(format!(
+                "{}{solution}_{label}",
+                self.blank_prefix
+            )));
+        }
+        let var_name = name.strip_prefix('?').unwrap_or(name);
+        let col_idx = *self.column_map.get(var_name)?;
+        let value = chunk.column(col_idx)?.get_value(row)?;
+        match value {
+            // Bound blank nodes come back in their `_:id` form
+            Value::String(s) if s.starts_with("_:") => Some(Term::blank(&s[2..])),
+            value => RdfModifyOperator::value_to_term(&value),
+        }
+    }
+}
+
+/// Returns a blank node prefix that is unique to one query.
+///
+/// The process-wide counter keeps queries apart; hashing it with a randomly
+/// seeded hasher keeps the names apart from those minted by other processes.
+fn fresh_blank_prefix() -> String {
+    use std::hash::BuildHasher;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static QUERIES: AtomicU64 = AtomicU64::new(0);
+    let query = QUERIES.fetch_add(1, Ordering::Relaxed);
+    let tag = std::collections::hash_map::RandomState::new().hash_one(query);
+    format!("c{tag:016x}q{query}s")
+}
+
+impl Operator for RdfConstructOperator {
+    fn next(&mut self) -> std::result::Result<Option<DataChunk>, OperatorError> {
+        if self.triples.is_none() {
+            self.triples = Some(self.construct()?);
+        }
+        let triples = self.triples.as_ref().unwrap();
+
+        if self.position >= triples.len() {
+            return Ok(None);
+        }
+
+        let end = (self.position + self.chunk_size).min(triples.len());
+        let schema = [
+            LogicalType::String,
+            LogicalType::String,
+            LogicalType::String,
+        ];
+        let mut chunk = DataChunk::with_capacity(&schema, end - self.position);
+
+        for triple in &triples[self.position..end] {
+            if let Some(col) = chunk.column_mut(0) {
+                col.push_string(term_to_string(triple.subject()));
+            }
+            if let Some(col) = chunk.column_mut(1) {
+                col.push_string(term_to_string(triple.predicate()));
+            }
+            if let Some(col) = chunk.column_mut(2) {
+                push_term_value(col, triple.object());
+            }
+        }
+
+        chunk.set_count(end - self.position);
+        self.position = end;
+
+        Ok(Some(chunk))
+    }
+
+    fn reset(&mut self) {
+        self.triples = None;
+        self.position = 0;
+        self.input.reset();
+    }
+
+    fn name(&self) -> &'static str {
+        "RdfConstruct"
+    }
+}
+
+// ============================================================================
+// RDF Values Operator (SPARQL VALUES)
+// ============================================================================
+
+/// Operator that emits the rows of an inline VALUES block.
+///
+/// Values are pushed in their lexical form, as a triple scan would produce
+/// them; `Null` (UNDEF) cells are pushed as nulls.
+struct RdfValuesOperator {
+    width: usize,
+    rows: Vec<Vec<Value>>,
+    chunk_size: usize,
+    /// Current position in the rows.
+    position: usize,
+}
+
+impl RdfValuesOperator {
+    fn new(width: usize, rows: Vec<Vec<Value>>, chunk_size: usize) -> Self {
+        Self {
+            width,
+            rows,
+            chunk_size,
+            position: 0,
+        }
+    }
+}
+
+impl Operator for RdfValuesOperator {
+    fn next(&mut self) -> std::result::Result<Option<DataChunk>, OperatorError> {
+        if self.position >= self.rows.len() {
+            return Ok(None);
+        }
+
+        let end = (self.position + self.chunk_size).min(self.rows.len());
+        let schema = vec![LogicalType::String; self.width];
+        let mut chunk = DataChunk::with_capacity(&schema, end - self.position);
+
+        for row in &self.rows[self.position..end] {
+            for (i, value) in row.iter().enumerate() {
+                if let Some(col) = chunk.column_mut(i) {
+                    match value {
+                        Value::Null => col.push_value(Value::Null),
+                        Value::String(s) => col.push_string(Arc::clone(s)),
+                        value => push_term_value(col, &literal_term(value)),
+                    }
+                }
+            }
+        }
+
+        chunk.set_count(end - self.position);
+        self.position = end;
+
+        Ok(Some(chunk))
+    }
+
+    fn reset(&mut self) {
+        self.position = 0;
+    }
+
+    fn name(&self) -> &'static str {
+        "RdfValues"
+    }
+}
+
+// ============================================================================
+// RDF Merge Columns Operator
+// ============================================================================
+
+/// Operator that projects join output, merging each shared variable's columns.
+///
+/// Every output column lists its candidate input columns and takes the
+/// first bound value, so a variable left unbound on one side of the join
+/// (e.g. by VALUES `UNDEF`) is bound from the other.
+struct RdfMergeColumnsOperator {
+    input: Box<dyn Operator>,
+    sources: Vec<Vec<usize>>,
+    output_schema: Vec<LogicalType>,
+}
+
+impl RdfMergeColumnsOperator {
+    fn new(
+        input: Box<dyn Operator>,
+        sources: Vec<Vec<usize>>,
+        output_schema: Vec<LogicalType>,
+    ) -> Self {
+        Self {
+            input,
+            sources,
+            output_schema,
+        }
+    }
+}
+
+impl Operator for RdfMergeColumnsOperator {
+    fn next(&mut self) -> std::result::Result<Option<DataChunk>, OperatorError> {
+        let Some(chunk) = self.input.next()? else {
+            return Ok(None);
+        };
+
+        let mut output = DataChunk::with_capacity(&self.output_schema, chunk.row_count());
+        let mut count = 0;
+        for row in chunk.selected_indices() {
+            for (i, candidates) in self.sources.iter().enumerate() {
+                let value = candidates
+                    .iter()
+                    .filter_map(|&idx| chunk.column(idx)?.get_value(row))
+                    .find(|value| !value.is_null())
+                    .unwrap_or(Value::Null);
+                if let Some(col) = output.column_mut(i) {
+                    col.push_value(value);
+                }
+            }
+            count += 1;
+        }
+        output.set_count(count);
+
+        Ok(Some(output))
+    }
+
+    fn reset(&mut self) {
+        self.input.reset();
+    }
+
+    fn name(&self) -> &'static str {
+        "RdfMergeColumns"
+    }
+}
+
 // ============================================================================
 // RDF Union Operator
 // ============================================================================
@@ -1588,6 +2017,199 @@ impl Operator for RdfTripleScanOperator {
     }
 }
 
+/// Operator that matches the endpoints of a quantified property path.
+///
+/// Each step follows `predicate` from subject to object, or backwards when
+/// `inverse` is set. A breadth-first walk from every start yields each
+/// reachable resource once, so a pair linked by several paths is matched
+/// once. A bound object with an unbound subject is walked backwards from the
+/// object instead.
+struct RdfPathOperator {
+    /// The RDF store to walk.
+    store: Arc<RdfStore>,
+    /// The predicate each step follows.
+    predicate: Term,
+    /// Whether steps go from object to subject.
+    inverse: bool,
+    /// The bound subject, if any.
+    subject: Option<Term>,
+    /// The bound object, if any.
+    object: Option<Term>,
+    /// Whether subject and object are the same variable (`?x p+ ?x`).
+    same_variable: bool,
+    /// Minimum number of steps, 0 or 1.
+    min_hops: u32,
+    /// Maximum number of steps (`None` for unbounded).
+    max_hops: Option<u32>,
+    /// Chunk size for batching.
+    chunk_size: usize,
+    /// Matched (subject, object) pairs (lazily computed).
+    pairs: Option<Vec<(Term, Term)>>,
+    /// Current position in the pairs.
+    position: usize,
+}
+
+impl RdfPathOperator {
+    /// Returns the resources one step from `term`, walking forwards
+    /// (subject to object, before inversion) or backwards.
+    fn step(&self, term: &Term, forwards: bool) -> Vec<Term> {
+        let from_subject = forwards != self.inverse;
+        let pattern = if from_subject {
+            TriplePattern {
+                subject: Some(term.clone()),
+                predicate: Some(self.predicate.clone()),
+                object: None,
+            }
+        } else {
+            TriplePattern {
+                subject: None,
+                predicate: Some(self.predicate.clone()),
+                object: Some(term.clone()),
+            }
+        };
+        self.store
+            .find(&pattern)
+            .into_iter()
+            .map(|triple| {
+                if from_subject {
+                    triple.object().clone()
+                } else {
+                    triple.subject().clone()
+                }
+            })
+            .collect()
+    }
+
+    /// Returns every resource reachable from `start` within the hop bounds.
+    fn reachable(&self, start: &Term, forwards: bool) -> Vec<Term> {
+        let mut seen = HashSet::new();
+        let mut reached = Vec::new();
+        if self.min_hops == 0 {
+            seen.insert(start.clone());
+            reached.push(start.clone());
+        }
+
+        let mut frontier = vec![start.clone()];
+        let mut depth = 0;
+        while !frontier.is_empty() && self.max_hops.is_none_or(|max| depth < max) {
+            depth += 1;
+            let mut next = Vec::new();
+            for term in &frontier {
+                for neighbor in self.step(term, forwards) {
+                    if seen.insert(neighbor.clone()) {
+                        reached.push(neighbor.clone());
+                        next.push(neighbor);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        reached
+    }
+
+    /// Returns the resources a walk with an unbound subject and object
+    /// starts from.
+    ///
+    /// A zero-length path matches every resource in the store to itself;
+    /// otherwise only resources with a first step can start a match.
+    fn starts(&self) -> Vec<Term> {
+        let triples = if self.min_hops == 0 {
+            self.store.triples()
+        } else {
+            self.store.triples_with_predicate(&self.predicate)
+        };
+        let mut seen = HashSet::new();
+        let mut starts = Vec::new();
+        for triple in triples {
+            let ends: &[&Term] = match (self.min_hops, self.inverse) {
+                (0, _) => &[triple.subject(), triple.object()],
+                (_, false) => &[triple.subject()],
+                (_, true) => &[triple.object()],
+            };
+            for &term in ends {
+                if seen.insert(term.clone()) {
+                    starts.push(term.clone());
+                }
+            }
+        }
+        starts
+    }
+
+    /// Computes every matched (subject, object) pair.
+    fn compute(&self) -> Vec<(Term, Term)> {
+        match (&self.subject, &self.object) {
+            (Some(subject), object) => self
+                .reachable(subject, true)
+                .into_iter()
+                .filter(|end| object.as_ref().is_none_or(|o| o == end))
+                .map(|end| (subject.clone(), end))
+                .collect(),
+            (None, Some(object)) => self
+                .reachable(object, false)
+                .into_iter()
+                .map(|start| (start, object.clone()))
+                .collect(),
+            (None, None) => self
+                .starts()
+                .into_iter()
+                .flat_map(|start| {
+                    self.reachable(&start, true)
+                        .into_iter()
+                        .filter(|end| !self.same_variable || *end == start)
+                        .map(|end| (start.clone(), end))
+                        .collect::<Vec<_>>()
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Operator for RdfPathOperator {
+    fn next(&mut self) -> std::result::Result<Option<DataChunk>, OperatorError> {
+        if self.pairs.is_none() {
+            self.pairs = Some(self.compute());
+        }
+        let pairs = self.pairs.as_ref().unwrap();
+        if self.position >= pairs.len() {
+            return Ok(None);
+        }
+
+        let end = (self.position + self.chunk_size).min(pairs.len());
+        let output_subject = self.subject.is_none();
+        let output_object = self.object.is_none() && !self.same_variable;
+        let col_count = usize::from(output_subject) + usize::from(output_object);
+        let schema: Vec<LogicalType> = (0..col_count).map(|_| LogicalType::String).collect();
+        let mut chunk = DataChunk::with_capacity(&schema, end - self.position);
+
+        for (subject, object) in &pairs[self.position..end] {
+            let mut col_idx = 0;
+            if output_subject {
+                if let Some(col) = chunk.column_mut(col_idx) {
+                    push_term_value(col, subject);
+                }
+                col_idx += 1;
+            }
+            if output_object {
+                if let Some(col) = chunk.column_mut(col_idx) {
+                    push_term_value(col, object);
+                }
+            }
+        }
+
+        chunk.set_count(end - self.position);
+        self.position = end;
+        Ok(Some(chunk))
+    }
+
+    fn reset(&mut self) {
+        self.position = 0;
+    }
+
+    fn name(&self) -> &'static str {
+        "RdfPath"
+    }
+}
+
 // ============================================================================
 // RDF Expression Predicate
 // ============================================================================
@@ -1641,6 +2263,7 @@ impl RdfExpressionPredicate {
                 let col_idx = *self.variable_columns.get(var)?;
                 chunk.column(col_idx)?.get_value(row)
             }
+            FilterExpression::Random(source) => Some(Value::Float64(source.next_f64())),
             // These expression types are not commonly used in RDF FILTER clauses
             FilterExpression::FunctionCall { .. }
             | FilterExpression::List(_)
@@ -1833,7 +2456,8 @@ impl Predicate for RdfExpressionPredicate {
 /// Join condition for joining on shared variables in SPARQL.
 ///
 /// This condition checks that shared variables have equal values between
-/// left and right sides of a join.
+/// left and right sides of a join. An unbound (null) value on either side is
+/// compatible with anything, as for VALUES `UNDEF`.
 struct RdfJoinCondition {
     /// Pairs of (left_col_idx, right_col_idx) for shared variables
     shared_vars: Vec<(usize, usize)>,
@@ -1864,11 +2488,11 @@ impl JoinCondition for RdfJoinCondition {
 
             match (left_val, right_val) {
                 (Some(l), Some(r)) => {
-                    if l != r {
+                    if !l.is_null() && !r.is_null() && l != r {
                         return false;
                     }
                 }
-                // If either is null/missing, they don't match
+                // A missing column never matches
                 _ => return false,
             }
         }
@@ -1880,6 +2504,29 @@ impl JoinCondition for RdfJoinCondition {
 // Helper Functions
 // ============================================================================
 
+/// Lists, for each column of a join's output, the input columns it merges.
+///
+/// The output is every left column followed by the right columns not shared
+/// with the left; a shared right column is merged into its left column.
+fn merged_join_columns(
+    left_columns: &[String],
+    right_columns: &[String],
+) -> (Vec<Vec<usize>>, Vec<String>) {
+    let left_col_count = left_columns.len();
+    let mut sources: Vec<Vec<usize>> = (0..left_col_count).map(|idx| vec![idx]).collect();
+    let mut output_columns = left_columns.to_vec();
+    for (right_idx, right_col) in right_columns.iter().enumerate() {
+        match left_columns.iter().position(|col| col == right_col) {
+            Some(left_idx) => sources[left_idx].push(left_col_count + right_idx),
+            None => {
+                sources.push(vec![left_col_count + right_idx]);
+                output_columns.push(right_col.clone());
+            }
+        }
+    }
+    (sources, output_columns)
+}
+
 /// Converts an RDF Term to a string for IRI/blank node representation.
 fn term_to_string(term: &Term) -> String {
     match term {
@@ -1912,13 +2559,18 @@ fn component_to_term(component: &TripleComponent) -> Option<Term> {
     match component {
         TripleComponent::Variable(_) => None,
         TripleComponent::Iri(iri) => Some(Term::iri(iri.clone())),
-        TripleComponent::Literal(value) => match value {
-            Value::String(s) => Some(Term::literal(Arc::clone(s))),
-            Value::Int64(i) => Some(Term::typed_literal(i.to_string(), Literal::XSD_INTEGER)),
-            Value::Float64(f) => Some(Term::typed_literal(f.to_string(), Literal::XSD_DOUBLE)),
-            Value::Bool(b) => Some(Term::typed_literal(b.to_string(), Literal::XSD_BOOLEAN)),
-            _ => Some(Term::literal(value.to_string())),
-        },
+        TripleComponent::Literal(value) => Some(literal_term(value)),
+    }
+}
+
+/// Converts a literal value to the RDF term that stands for it.
+pub(crate) fn literal_term(value: &Value) -> Term {
+    match value {
+        Value::String(s) => Term::literal(Arc::clone(s)),
+        Value::Int64(i) => Term::typed_literal(i.to_string(), Literal::XSD_INTEGER),
+        Value::Float64(f) => Term::typed_literal(f.to_string(), Literal::XSD_DOUBLE),
+        Value::Bool(b) => Term::typed_literal(b.to_string(), Literal::XSD_BOOLEAN),
+        _ => Term::literal(value.to_string()),
     }
 }
 
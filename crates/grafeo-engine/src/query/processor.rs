@@ -8,9 +8,12 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
+use grafeo_common::memory::buffer::BufferManager;
 use grafeo_common::types::{EpochId, TxId, Value};
-use grafeo_common::utils::error::{Error, Result};
+use grafeo_common::utils::error::{Error, QueryError, QueryErrorKind, Result};
+use grafeo_core::execution::operators::RandomSource;
 use grafeo_core::graph::lpg::LpgStore;
 
 use crate::catalog::Catalog;
@@ -19,8 +22,9 @@ use crate::query::binder::Binder;
 use crate::query::executor::Executor;
 use crate::query::optimizer::Optimizer;
 use crate::query::plan::{LogicalExpression, LogicalOperator, LogicalPlan};
-use crate::query::planner::Planner;
+use crate::query::planner::{DEFAULT_NESTED_LOOP_THRESHOLD, Planner};
 use crate::transaction::TransactionManager;
+use grafeo_adapters::plugins::PluginRegistry;
 
 /// Supported query languages.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -89,10 +93,20 @@ pub struct QueryProcessor {
     tx_manager: Arc<TransactionManager>,
     /// Catalog for schema and index metadata.
     catalog: Arc<Catalog>,
+    /// Procedures `CALL` can run.
+    plugins: Arc<PluginRegistry>,
     /// Query optimizer.
     optimizer: Optimizer,
     /// Current transaction context (if any).
     tx_context: Option<(EpochId, TxId)>,
+    /// Source for `rand()`; `None` lets each planner seed its own.
+    random: Option<Arc<RandomSource>>,
+    /// Row estimate below which joins use a nested loop.
+    nested_loop_threshold: f64,
+    /// Longest a query may run; `None` for no limit.
+    query_timeout: Option<Duration>,
+    /// Budget sorts reserve memory from; `None` for no limit.
+    buffer_manager: Option<Arc<BufferManager>>,
     /// RDF store for triple pattern queries (optional).
     #[cfg(feature = "rdf")]
     rdf_store: Option<Arc<grafeo_core::graph::rdf::RdfStore>>,
@@ -106,8 +120,13 @@ impl QueryProcessor {
             lpg_store: store,
             tx_manager: Arc::new(TransactionManager::new()),
             catalog: Arc::new(Catalog::new()),
+            plugins: Arc::new(PluginRegistry::with_builtin_algorithms()),
             optimizer: Optimizer::new(),
             tx_context: None,
+            random: None,
+            nested_loop_threshold: DEFAULT_NESTED_LOOP_THRESHOLD,
+            query_timeout: None,
+            buffer_manager: None,
             #[cfg(feature = "rdf")]
             rdf_store: None,
         }
@@ -120,8 +139,13 @@ impl QueryProcessor {
             lpg_store: store,
             tx_manager,
             catalog: Arc::new(Catalog::new()),
+            plugins: Arc::new(PluginRegistry::with_builtin_algorithms()),
             optimizer: Optimizer::new(),
             tx_context: None,
+            random: None,
+            nested_loop_threshold: DEFAULT_NESTED_LOOP_THRESHOLD,
+            query_timeout: None,
+            buffer_manager: None,
             #[cfg(feature = "rdf")]
             rdf_store: None,
         }
@@ -138,8 +162,13 @@ impl QueryProcessor {
             lpg_store,
             tx_manager: Arc::new(TransactionManager::new()),
             catalog: Arc::new(Catalog::new()),
+            plugins: Arc::new(PluginRegistry::with_builtin_algorithms()),
             optimizer: Optimizer::new(),
             tx_context: None,
+            random: None,
+            nested_loop_threshold: DEFAULT_NESTED_LOOP_THRESHOLD,
+            query_timeout: None,
+            buffer_manager: None,
             rdf_store: Some(rdf_store),
         }
     }
@@ -160,6 +189,42 @@ impl QueryProcessor {
         self
     }
 
+    /// Sets the registry whose graph algorithms `CALL` runs.
+    #[must_use]
+    pub fn with_plugins(mut self, plugins: Arc<PluginRegistry>) -> Self {
+        self.plugins = plugins;
+        self
+    }
+
+    /// Sets the source `rand()` draws from.
+    #[must_use]
+    pub fn with_random_source(mut self, random: Arc<RandomSource>) -> Self {
+        self.random = Some(random);
+        self
+    }
+
+    /// Sets the row estimate below which joins use a nested loop.
+    #[must_use]
+    pub fn with_nested_loop_threshold(mut self, threshold: f64) -> Self {
+        self.nested_loop_threshold = threshold;
+        self
+    }
+
+    /// Cancels queries that run longer than `timeout`; `None` for no limit.
+    #[must_use]
+    pub fn with_query_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.query_timeout = timeout;
+        self
+    }
+
+    /// Fails queries whose sorts cannot get room from `manager`; `None` for
+    /// no limit.
+    #[must_use]
+    pub fn with_buffer_manager(mut self, manager: Option<Arc<BufferManager>>) -> Self {
+        self.buffer_manager = manager;
+        self
+    }
+
     /// Sets a custom optimizer.
     #[must_use]
     pub fn with_optimizer(mut self, optimizer: Optimizer) -> Self {
@@ -193,7 +258,8 @@ impl QueryProcessor {
         params: Option<&QueryParams>,
     ) -> Result<QueryResult> {
         if language.is_lpg() {
-            self.process_lpg(query, language, params)
+            let logical_plan = self.translate_lpg(query, language)?;
+            self.execute_lpg_plan(logical_plan, params)
         } else {
             #[cfg(feature = "rdf")]
             {
@@ -208,29 +274,33 @@ impl QueryProcessor {
         }
     }
 
-    /// Processes an LPG query (GQL, Cypher, Gremlin, GraphQL).
-    fn process_lpg(
+    /// Runs a translated LPG plan (GQL, Cypher, Gremlin, GraphQL).
+    ///
+    /// The plan may still contain `$name` placeholders; they are bound from
+    /// `params` before anything runs, so one translated plan can be reused
+    /// with different parameter values.
+    ///
+    /// # Errors
+    ///
+    /// Returns a semantic [`QueryError`](grafeo_common::utils::error::QueryError)
+    /// if a placeholder has no value, or an error from any later stage.
+    pub(crate) fn execute_lpg_plan(
         &self,
-        query: &str,
-        language: QueryLanguage,
+        mut logical_plan: LogicalPlan,
         params: Option<&QueryParams>,
     ) -> Result<QueryResult> {
-        // 1. Parse and translate to logical plan
-        let mut logical_plan = self.translate_lpg(query, language)?;
-
-        // 2. Substitute parameters if provided
-        if let Some(params) = params {
-            substitute_params(&mut logical_plan, params)?;
-        }
+        // 1. Bind parameters; placeholders without a value are an error
+        let no_params = QueryParams::new();
+        substitute_params(&mut logical_plan, params.unwrap_or(&no_params))?;
 
-        // 3. Semantic validation
+        // 2. Semantic validation
         let mut binder = Binder::new();
         let _binding_context = binder.bind(&logical_plan)?;
 
-        // 4. Optimize the plan
+        // 3. Optimize the plan
         let optimized_plan = self.optimizer.optimize(logical_plan)?;
 
-        // 5. Convert to physical plan with transaction context
+        // 4. Convert to physical plan with transaction context
         let planner = if let Some((epoch, tx_id)) = self.tx_context {
             Planner::with_context(
                 Arc::clone(&self.lpg_store),
@@ -245,16 +315,29 @@ impl QueryProcessor {
                 None,
                 self.tx_manager.current_epoch(),
             )
+        }
+        .with_catalog(Arc::clone(&self.catalog))
+        .with_plugins(Arc::clone(&self.plugins))
+        .with_nested_loop_threshold(self.nested_loop_threshold)
+        .with_query_timeout(self.query_timeout)
+        .with_buffer_manager(self.buffer_manager.clone());
+        let planner = match &self.random {
+            Some(random) => planner.with_random_source(Arc::clone(random)),
+            None => planner,
         };
         let mut physical_plan = planner.plan(&optimized_plan)?;
 
-        // 6. Execute and collect results
+        // 5. Execute and collect results
         let executor = Executor::with_columns(physical_plan.columns.clone());
         executor.execute(physical_plan.operator.as_mut())
     }
 
     /// Translates an LPG query to a logical plan.
-    fn translate_lpg(&self, query: &str, language: QueryLanguage) -> Result<LogicalPlan> {
+    pub(crate) fn translate_lpg(
+        &self,
+        query: &str,
+        language: QueryLanguage,
+    ) -> Result<LogicalPlan> {
         match language {
             #[cfg(feature = "gql")]
             QueryLanguage::Gql => {
@@ -309,12 +392,13 @@ impl QueryProcessor {
         let optimized_plan = self.optimizer.optimize(logical_plan)?;
 
         // 4. Convert to physical plan (using RDF planner)
-        let planner = RdfPlanner::new(Arc::clone(rdf_store));
+        let planner = RdfPlanner::new(Arc::clone(rdf_store))
+            .with_query_timeout(self.query_timeout)
+            .with_buffer_manager(self.buffer_manager.clone());
         let mut physical_plan = planner.plan(&optimized_plan)?;
 
         // 5. Execute and collect results
-        let executor = Executor::with_columns(physical_plan.columns.clone());
-        executor.execute(physical_plan.operator.as_mut())
+        Executor::execute_plan(&optimized_plan, &mut physical_plan)
     }
 
     /// Translates an RDF query to a logical plan.
@@ -564,7 +648,22 @@ fn substitute_in_operator(op: &mut LogicalOperator, params: &QueryParams) -> Res
         LogicalOperator::Modify(modify) => {
             substitute_in_operator(&mut modify.where_clause, params)?;
         }
-        LogicalOperator::ClearGraph(_)
+        LogicalOperator::Construct(construct) => {
+            substitute_in_operator(&mut construct.input, params)?;
+        }
+        LogicalOperator::Ask(ask) => {
+            substitute_in_operator(&mut ask.input, params)?;
+        }
+        LogicalOperator::CallProcedure(call) => {
+            for argument in &mut call.arguments {
+                substitute_in_expression(argument, params)?;
+            }
+        }
+        LogicalOperator::CreateIndex(_)
+        | LogicalOperator::CreateConstraint(_)
+        | LogicalOperator::Values(_)
+        | LogicalOperator::TriplePath(_)
+        | LogicalOperator::ClearGraph(_)
         | LogicalOperator::CreateGraph(_)
         | LogicalOperator::DropGraph(_)
         | LogicalOperator::LoadGraph(_)
@@ -585,7 +684,13 @@ fn substitute_in_expression(expr: &mut LogicalExpression, params: &QueryParams)
             if let Some(value) = params.get(name) {
                 *expr = LogicalExpression::Literal(value.clone());
             } else {
-                return Err(Error::Internal(format!("Missing parameter: ${}", name)));
+                return Err(Error::Query(
+                    QueryError::new(
+                        QueryErrorKind::Semantic,
+                        format!("Missing parameter: ${name}"),
+                    )
+                    .with_hint(format!("Pass a value for \"{name}\" in the parameters")),
+                ));
             }
         }
         LogicalExpression::Binary { left, right, .. } => {
@@ -660,6 +765,10 @@ fn substitute_in_expression(expr: &mut LogicalExpression, params: &QueryParams)
         LogicalExpression::ExistsSubquery(_) | LogicalExpression::CountSubquery(_) => {
             // Subqueries would need recursive parameter substitution
         }
+        LogicalExpression::InSubquery { expr, subquery } => {
+            substitute_in_expression(expr, params)?;
+            substitute_in_operator(subquery, params)?;
+        }
     }
     Ok(())
 }
@@ -765,5 +874,26 @@ mod tests {
             "Expected 'Missing parameter' error, got: {}",
             err
         );
+        assert!(
+            matches!(&err, Error::Query(e) if e.kind == QueryErrorKind::Semantic),
+            "Expected a semantic query error, got: {:?}",
+            err
+        );
+    }
+
+    #[cfg(feature = "gql")]
+    #[test]
+    fn test_missing_param_without_params_map() {
+        let store = Arc::new(LpgStore::new());
+        let processor = QueryProcessor::for_lpg(store);
+
+        let err = processor
+            .process(
+                "MATCH (n:Person) WHERE n.age > $min_age RETURN n",
+                QueryLanguage::Gql,
+                None,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("Missing parameter: $min_age"));
     }
 }
@@ -3,11 +3,12 @@
 //! Translates SPARQL 1.1 AST to the common logical plan representation.
 
 use crate::query::plan::{
-    AddGraphOp, AggregateExpr, AggregateFunction, AggregateOp, AntiJoinOp, BinaryOp, BindOp,
-    ClearGraphOp, CopyGraphOp, CreateGraphOp, DeleteTripleOp, DistinctOp, DropGraphOp, FilterOp,
-    InsertTripleOp, JoinOp, JoinType, LeftJoinOp, LimitOp, LoadGraphOp, LogicalExpression,
-    LogicalOperator, LogicalPlan, ModifyOp, MoveGraphOp, ProjectOp, Projection, SkipOp, SortKey,
-    SortOp, SortOrder, TripleComponent, TripleScanOp, TripleTemplate, UnaryOp, UnionOp,
+    AddGraphOp, AggregateExpr, AggregateFunction, AggregateOp, AntiJoinOp, AskOp, BinaryOp, BindOp,
+    ClearGraphOp, ConstructOp, CopyGraphOp, CreateGraphOp, DeleteTripleOp, DistinctOp, DropGraphOp,
+    FilterOp, InsertTripleOp, JoinOp, JoinType, LeftJoinOp, LimitOp, LoadGraphOp,
+    LogicalExpression, LogicalOperator, LogicalPlan, ModifyOp, MoveGraphOp, ProjectOp, Projection,
+    SkipOp, SortKey, SortOp, SortOrder, TripleComponent, TriplePathOp, TripleScanOp,
+    TripleTemplate, UnaryOp, UnionOp, ValuesOp,
 };
 use grafeo_adapters::query::sparql::{self, ast};
 use grafeo_common::types::Value;
@@ -25,6 +26,46 @@ pub fn translate(query: &str) -> Result<LogicalPlan> {
     translator.translate_query(&sparql_query)
 }
 
+/// Converts a literal's lexical form to a value according to its datatype IRI.
+///
+/// Integer, decimal, double, boolean and base64 datatypes give typed values;
+/// any other datatype, or a lexical form that doesn't parse, gives a string.
+pub(crate) fn typed_literal_to_value(lexical: &str, datatype: Option<&str>) -> Value {
+    match datatype {
+        Some(
+            "http://www.w3.org/2001/XMLSchema#integer"
+            | "http://www.w3.org/2001/XMLSchema#int"
+            | "http://www.w3.org/2001/XMLSchema#long",
+        ) => {
+            if let Ok(n) = lexical.parse::<i64>() {
+                return Value::Int64(n);
+            }
+        }
+        Some(
+            "http://www.w3.org/2001/XMLSchema#decimal"
+            | "http://www.w3.org/2001/XMLSchema#double"
+            | "http://www.w3.org/2001/XMLSchema#float",
+        ) => {
+            if let Ok(n) = lexical.parse::<f64>() {
+                return Value::Float64(n);
+            }
+        }
+        Some("http://www.w3.org/2001/XMLSchema#boolean") => {
+            return Value::Bool(lexical == "true" || lexical == "1");
+        }
+        Some("http://www.w3.org/2001/XMLSchema#base64Binary") => {
+            use base64::Engine;
+            if let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(lexical) {
+                return Value::Bytes(bytes.into());
+            }
+        }
+        _ => {}
+    }
+
+    // Default to string
+    Value::String(lexical.into())
+}
+
 /// Translator from SPARQL AST to LogicalPlan.
 struct SparqlTranslator {
     /// Prefix mappings for IRI resolution.
@@ -165,15 +206,19 @@ impl SparqlTranslator {
 
     fn translate_ask(&mut self, ask: &ast::AskQuery) -> Result<LogicalPlan> {
         // ASK returns true if the pattern has any matches
-        let plan = self.translate_graph_pattern(&ask.where_clause)?;
+        let mut plan = self.translate_graph_pattern(&ask.where_clause)?;
 
-        // Limit to 1 result for efficiency
-        let plan = LogicalOperator::Limit(LimitOp {
-            count: 1,
-            input: Box::new(plan),
-        });
+        // Limit to 1 result for efficiency; the empty pattern always matches
+        if !matches!(plan, LogicalOperator::Empty) {
+            plan = LogicalOperator::Limit(LimitOp {
+                count: 1,
+                input: Box::new(plan),
+            });
+        }
 
-        Ok(LogicalPlan::new(plan))
+        Ok(LogicalPlan::new(LogicalOperator::Ask(AskOp {
+            input: Box::new(plan),
+        })))
     }
 
     fn translate_construct(&mut self, construct: &ast::ConstructQuery) -> Result<LogicalPlan> {
@@ -190,8 +235,21 @@ impl SparqlTranslator {
             });
         }
 
-        // The template will be processed at execution time
-        Ok(LogicalPlan::new(plan))
+        // Template blank nodes become `_:` variables, minted per solution
+        let mut templates = Vec::new();
+        for triple in &construct.template {
+            templates.push(TripleTemplate {
+                subject: self.translate_triple_term(&triple.subject)?,
+                predicate: self.translate_property_path(&triple.predicate)?,
+                object: self.translate_triple_term(&triple.object)?,
+                graph: None,
+            });
+        }
+
+        Ok(LogicalPlan::new(LogicalOperator::Construct(ConstructOp {
+            templates,
+            input: Box::new(plan),
+        })))
     }
 
     fn translate_describe(&mut self, describe: &ast::DescribeQuery) -> Result<LogicalPlan> {
@@ -530,69 +588,20 @@ impl SparqlTranslator {
             ast::GraphPattern::Basic(triples) => self.translate_basic_pattern(triples),
 
             ast::GraphPattern::Group(patterns) => {
-                // Categorize patterns by type for proper composition
-                let mut basic_patterns: Vec<&ast::GraphPattern> = Vec::new();
+                // Elements are applied left to right so that OPTIONAL and MINUS
+                // see everything bound before them. FILTERs scope over the
+                // whole group and are applied last.
+                let mut plan = LogicalOperator::Empty;
                 let mut filter_exprs: Vec<&ast::Expression> = Vec::new();
-                let mut optional_patterns: Vec<&ast::GraphPattern> = Vec::new();
-                let mut minus_patterns: Vec<&ast::GraphPattern> = Vec::new();
-                let mut bind_patterns: Vec<(&ast::Expression, &String)> = Vec::new();
 
                 for p in patterns {
-                    match p {
-                        ast::GraphPattern::Filter(expr) => filter_exprs.push(expr),
-                        ast::GraphPattern::Optional(inner) => optional_patterns.push(inner),
-                        ast::GraphPattern::Minus(inner) => minus_patterns.push(inner),
-                        ast::GraphPattern::Bind {
-                            expression,
-                            variable,
-                        } => bind_patterns.push((expression, variable)),
-                        _ => basic_patterns.push(p),
-                    }
-                }
-
-                // 1. Translate and join basic/required patterns
-                let mut plan = LogicalOperator::Empty;
-                for p in basic_patterns {
-                    let p_plan = self.translate_graph_pattern(p)?;
-                    plan = self.join_patterns(plan, p_plan);
-                }
-
-                // 2. Apply BIND expressions (adds computed columns)
-                for (expression, variable) in bind_patterns {
-                    let expr = self.translate_expression(expression)?;
-                    plan = LogicalOperator::Bind(BindOp {
-                        expression: expr,
-                        variable: variable.clone(),
-                        input: Box::new(plan),
-                    });
-                }
-
-                // 3. Apply OPTIONAL patterns (left outer joins)
-                for inner in optional_patterns {
-                    let inner_plan = self.translate_graph_pattern(inner)?;
-                    if matches!(plan, LogicalOperator::Empty) {
-                        plan = inner_plan;
+                    if let ast::GraphPattern::Filter(expr) = p {
+                        filter_exprs.push(expr);
                     } else {
-                        plan = LogicalOperator::LeftJoin(LeftJoinOp {
-                            left: Box::new(plan),
-                            right: Box::new(inner_plan),
-                            condition: None,
-                        });
+                        plan = self.apply_group_element(plan, p)?;
                     }
                 }
 
-                // 4. Apply MINUS patterns (anti joins)
-                for inner in minus_patterns {
-                    let inner_plan = self.translate_graph_pattern(inner)?;
-                    if !matches!(plan, LogicalOperator::Empty) {
-                        plan = LogicalOperator::AntiJoin(AntiJoinOp {
-                            left: Box::new(plan),
-                            right: Box::new(inner_plan),
-                        });
-                    }
-                }
-
-                // 5. Apply FILTER expressions last (they scope over entire group)
                 if !filter_exprs.is_empty() {
                     let predicates: Vec<LogicalExpression> = filter_exprs
                         .into_iter()
@@ -618,9 +627,9 @@ impl SparqlTranslator {
                 Ok(plan)
             }
 
-            ast::GraphPattern::Optional(inner) => {
-                // Standalone OPTIONAL - handled in Group translation, but support direct call
-                self.translate_graph_pattern(inner)
+            ast::GraphPattern::Optional(_) | ast::GraphPattern::Minus(_) => {
+                // Standalone OPTIONAL/MINUS - the left side is the empty group
+                self.apply_group_element(LogicalOperator::Empty, pattern)
             }
 
             ast::GraphPattern::Union(alternatives) => {
@@ -632,11 +641,6 @@ impl SparqlTranslator {
                 Ok(LogicalOperator::Union(UnionOp { inputs }))
             }
 
-            ast::GraphPattern::Minus(inner) => {
-                // Standalone MINUS - handled in Group translation, but support direct call
-                self.translate_graph_pattern(inner)
-            }
-
             ast::GraphPattern::Filter(expr) => {
                 // Standalone FILTER - handled in Group translation, but support direct call
                 // This can happen when Filter is the top-level pattern
@@ -679,10 +683,27 @@ impl SparqlTranslator {
                 self.translate_graph_pattern(pattern)
             }
 
-            ast::GraphPattern::InlineData(_data) => {
-                // VALUES clause - inline data
-                // For now, return empty; full implementation needs a Values operator
-                Ok(LogicalOperator::Empty)
+            ast::GraphPattern::InlineData(data) => {
+                // UNDEF cells become Null, leaving the variable unbound
+                let rows = data
+                    .values
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|cell| match cell {
+                                Some(ast::DataValue::Iri(iri)) => {
+                                    Value::String(self.resolve_iri(iri).into())
+                                }
+                                Some(ast::DataValue::Literal(lit)) => self.literal_to_value(lit),
+                                None => Value::Null,
+                            })
+                            .collect()
+                    })
+                    .collect();
+                Ok(LogicalOperator::Values(ValuesOp {
+                    variables: data.variables.clone(),
+                    rows,
+                }))
             }
         }
     }
@@ -707,18 +728,119 @@ impl SparqlTranslator {
 
     fn translate_triple_pattern(&mut self, triple: &ast::TriplePattern) -> Result<LogicalOperator> {
         let subject = self.translate_triple_term(&triple.subject)?;
-        let predicate = self.translate_property_path(&triple.predicate)?;
         let object = self.translate_triple_term(&triple.object)?;
+        self.translate_path_pattern(subject, &triple.predicate, object)
+    }
+
+    /// Translates a triple pattern whose predicate may be a property path.
+    ///
+    /// - Inverse paths (`^p`) swap subject and object.
+    /// - Sequence paths (`p1/p2`) become joined triple scans linked by fresh
+    ///   intermediate variables.
+    /// - `p+`, `p*` and `p?` become a [`TriplePathOp`] with hop bounds
+    ///   `1..`, `0..` and `0..1`. A `min_hops` of 0 also yields the
+    ///   zero-length path, binding the object to the subject itself.
+    fn translate_path_pattern(
+        &mut self,
+        subject: TripleComponent,
+        path: &ast::PropertyPath,
+        object: TripleComponent,
+    ) -> Result<LogicalOperator> {
+        match path {
+            ast::PropertyPath::Inverse(inner) => {
+                self.translate_path_pattern(object, inner, subject)
+            }
+
+            ast::PropertyPath::Sequence(steps) => {
+                let mut plan = LogicalOperator::Empty;
+                let mut from = subject;
+                for (i, step) in steps.iter().enumerate() {
+                    let to = if i + 1 == steps.len() {
+                        object.clone()
+                    } else {
+                        TripleComponent::Variable(format!("_:path{}", self.next_anon()))
+                    };
+                    let step_plan = self.translate_path_pattern(from, step, to.clone())?;
+                    plan = self.join_patterns(plan, step_plan);
+                    from = to;
+                }
+                Ok(plan)
+            }
+
+            ast::PropertyPath::OneOrMore(inner) => {
+                self.translate_repeated_path(subject, inner, object, 1, None)
+            }
+            ast::PropertyPath::ZeroOrMore(inner) => {
+                self.translate_repeated_path(subject, inner, object, 0, None)
+            }
+            ast::PropertyPath::ZeroOrOne(inner) => {
+                self.translate_repeated_path(subject, inner, object, 0, Some(1))
+            }
+
+            _ => {
+                let predicate = self.translate_property_path(path)?;
+                Ok(LogicalOperator::TripleScan(TripleScanOp {
+                    subject,
+                    predicate,
+                    object,
+                    graph: None,
+                    input: None,
+                }))
+            }
+        }
+    }
+
+    /// Translates a quantified path (`p+`, `p*`, `p?`) into a
+    /// [`TriplePathOp`].
+    ///
+    /// The repeated element must be a single IRI, optionally inverted.
+    fn translate_repeated_path(
+        &mut self,
+        subject: TripleComponent,
+        inner: &ast::PropertyPath,
+        object: TripleComponent,
+        min_hops: u32,
+        max_hops: Option<u32>,
+    ) -> Result<LogicalOperator> {
+        let (predicate, inverse) = match inner {
+            ast::PropertyPath::Predicate(_) | ast::PropertyPath::RdfType => {
+                (self.path_iri(inner)?, false)
+            }
+            ast::PropertyPath::Inverse(p)
+                if matches!(
+                    p.as_ref(),
+                    ast::PropertyPath::Predicate(_) | ast::PropertyPath::RdfType
+                ) =>
+            {
+                (self.path_iri(p)?, true)
+            }
+            _ => {
+                return Err(Error::Internal(
+                    "Quantified property paths are only supported over a single IRI".to_string(),
+                ));
+            }
+        };
 
-        Ok(LogicalOperator::TripleScan(TripleScanOp {
+        Ok(LogicalOperator::TriplePath(TriplePathOp {
             subject,
             predicate,
+            inverse,
             object,
-            graph: None,
-            input: None,
+            min_hops,
+            max_hops,
         }))
     }
 
+    /// Returns the resolved IRI of a plain predicate path.
+    fn path_iri(&mut self, path: &ast::PropertyPath) -> Result<String> {
+        match self.translate_property_path(path)? {
+            TripleComponent::Iri(iri) => Ok(iri),
+            _ => Err(Error::Internal(
+                "Expected an IRI in property path".to_string(),
+            )),
+        }
+    }
+
     fn translate_triple_term(&mut self, term: &ast::TripleTerm) -> Result<TripleComponent> {
         match term {
             ast::TripleTerm::Variable(name) => Ok(TripleComponent::Variable(name.clone())),
@@ -749,7 +871,8 @@ impl SparqlTranslator {
             ast::PropertyPath::RdfType => Ok(TripleComponent::Iri(
                 "http://www.w3.org/1999/02/22-rdf-syntax-ns#type".to_string(),
             )),
-            // Complex property paths are not fully supported yet
+            // Quantified, sequence and inverse paths are expanded by
+            // translate_path_pattern; the rest are not supported yet
             _ => Err(Error::Internal(
                 "Complex property paths not yet supported".to_string(),
             )),
@@ -1109,6 +1232,59 @@ impl SparqlTranslator {
         }
     }
 
+    /// Applies one non-FILTER element of a group to the plan accumulated so far.
+    ///
+    /// OPTIONAL becomes a left outer join and MINUS an anti join against the
+    /// accumulated plan, both keyed on the variables the two sides share.
+    fn apply_group_element(
+        &mut self,
+        plan: LogicalOperator,
+        pattern: &ast::GraphPattern,
+    ) -> Result<LogicalOperator> {
+        match pattern {
+            ast::GraphPattern::Optional(inner) => {
+                let inner_plan = self.translate_graph_pattern(inner)?;
+                if matches!(plan, LogicalOperator::Empty) {
+                    // Nothing bound yet, so there is no left side to preserve
+                    Ok(inner_plan)
+                } else {
+                    Ok(LogicalOperator::LeftJoin(LeftJoinOp {
+                        left: Box::new(plan),
+                        right: Box::new(inner_plan),
+                        condition: None,
+                    }))
+                }
+            }
+            ast::GraphPattern::Minus(inner) => {
+                let inner_plan = self.translate_graph_pattern(inner)?;
+                if matches!(plan, LogicalOperator::Empty) {
+                    // Nothing to subtract from
+                    Ok(LogicalOperator::Empty)
+                } else {
+                    Ok(LogicalOperator::AntiJoin(AntiJoinOp {
+                        left: Box::new(plan),
+                        right: Box::new(inner_plan),
+                    }))
+                }
+            }
+            ast::GraphPattern::Bind {
+                expression,
+                variable,
+            } => {
+                let expr = self.translate_expression(expression)?;
+                Ok(LogicalOperator::Bind(BindOp {
+                    expression: expr,
+                    variable: variable.clone(),
+                    input: Box::new(plan),
+                }))
+            }
+            _ => {
+                let p_plan = self.translate_graph_pattern(pattern)?;
+                Ok(self.join_patterns(plan, p_plan))
+            }
+        }
+    }
+
     fn join_patterns(&self, left: LogicalOperator, right: LogicalOperator) -> LogicalOperator {
         if matches!(left, LogicalOperator::Empty) {
             return right;
@@ -1144,33 +1320,8 @@ impl SparqlTranslator {
     }
 
     fn literal_to_value(&self, lit: &ast::Literal) -> Value {
-        // Check for typed literals
-        if let Some(datatype) = &lit.datatype {
-            let dt = self.resolve_iri(datatype);
-            match dt.as_str() {
-                "http://www.w3.org/2001/XMLSchema#integer"
-                | "http://www.w3.org/2001/XMLSchema#int"
-                | "http://www.w3.org/2001/XMLSchema#long" => {
-                    if let Ok(n) = lit.value.parse::<i64>() {
-                        return Value::Int64(n);
-                    }
-                }
-                "http://www.w3.org/2001/XMLSchema#decimal"
-                | "http://www.w3.org/2001/XMLSchema#double"
-                | "http://www.w3.org/2001/XMLSchema#float" => {
-                    if let Ok(n) = lit.value.parse::<f64>() {
-                        return Value::Float64(n);
-                    }
-                }
-                "http://www.w3.org/2001/XMLSchema#boolean" => {
-                    return Value::Bool(lit.value == "true" || lit.value == "1");
-                }
-                _ => {}
-            }
-        }
-
-        // Default to string
-        Value::String(lit.value.clone().into())
+        let datatype = lit.datatype.as_ref().map(|dt| self.resolve_iri(dt));
+        typed_literal_to_value(&lit.value, datatype.as_deref())
     }
 
     fn next_anon(&mut self) -> u32 {
@@ -1274,9 +1425,11 @@ mod tests {
         assert!(result.is_ok());
 
         let plan = result.unwrap();
-        // ASK should have a Limit(1)
+        assert!(plan.is_ask());
+        // ASK should have a Limit(1) below the Ask
         fn find_limit(op: &LogicalOperator) -> Option<&LimitOp> {
             match op {
+                LogicalOperator::Ask(ask) => find_limit(&ask.input),
                 LogicalOperator::Limit(l) => Some(l),
                 _ => None,
             }
@@ -1371,6 +1524,62 @@ mod tests {
         let query = "SELECT ?x ?name WHERE { ?x ?y ?z OPTIONAL { ?x ?p ?name } }";
         let result = translate(query);
         assert!(result.is_ok());
+
+        let plan = result.unwrap();
+        fn find_left_join(op: &LogicalOperator) -> Option<&LeftJoinOp> {
+            match op {
+                LogicalOperator::LeftJoin(lj) => Some(lj),
+                LogicalOperator::Project(p) => find_left_join(&p.input),
+                _ => None,
+            }
+        }
+        let lj = find_left_join(&plan.root).expect("Expected LeftJoin");
+        assert!(matches!(lj.left.as_ref(), LogicalOperator::TripleScan(_)));
+        assert!(matches!(lj.right.as_ref(), LogicalOperator::TripleScan(_)));
+    }
+
+    #[test]
+    fn test_translate_optional_keeps_pattern_order() {
+        // The second required pattern comes after the OPTIONAL, so it must be
+        // joined onto the left join rather than into its left side
+        let query = "SELECT ?x WHERE { ?x ?p ?y OPTIONAL { ?x ?q ?z } ?x ?r ?w }";
+        let plan = translate(query).unwrap();
+
+        let LogicalOperator::Project(project) = &plan.root else {
+            panic!("Expected Project");
+        };
+        let LogicalOperator::Join(join) = project.input.as_ref() else {
+            panic!("Expected Join");
+        };
+        assert!(matches!(join.left.as_ref(), LogicalOperator::LeftJoin(_)));
+    }
+
+    #[test]
+    fn test_translate_minus() {
+        let query = "SELECT ?x WHERE { ?x ?p ?y MINUS { ?x ?q ?z } }";
+        let plan = translate(query).unwrap();
+
+        fn find_anti_join(op: &LogicalOperator) -> Option<&AntiJoinOp> {
+            match op {
+                LogicalOperator::AntiJoin(aj) => Some(aj),
+                LogicalOperator::Project(p) => find_anti_join(&p.input),
+                _ => None,
+            }
+        }
+        let aj = find_anti_join(&plan.root).expect("Expected AntiJoin");
+        assert!(matches!(aj.left.as_ref(), LogicalOperator::TripleScan(_)));
+        assert!(matches!(aj.right.as_ref(), LogicalOperator::TripleScan(_)));
+    }
+
+    #[test]
+    fn test_translate_minus_on_empty_group() {
+        let query = "SELECT ?x WHERE { MINUS { ?x ?q ?z } }";
+        let plan = translate(query).unwrap();
+
+        let LogicalOperator::Project(project) = &plan.root else {
+            panic!("Expected Project");
+        };
+        assert!(matches!(project.input.as_ref(), LogicalOperator::Empty));
     }
 
     #[test]
@@ -1380,6 +1589,162 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_translate_values_joins_pattern() {
+        let query = r#"
+            PREFIX ex: <http://example.org/>
+            SELECT ?x ?name WHERE {
+                ?x ex:name ?name
+                VALUES (?x ?name) { (ex:alice UNDEF) (ex:bob "Bob") }
+            }
+        "#;
+        let plan = translate(query).unwrap();
+
+        let LogicalOperator::Project(project) = &plan.root else {
+            panic!("Expected Project");
+        };
+        let LogicalOperator::Join(join) = project.input.as_ref() else {
+            panic!("Expected Join");
+        };
+        assert!(matches!(join.left.as_ref(), LogicalOperator::TripleScan(_)));
+        let LogicalOperator::Values(values) = join.right.as_ref() else {
+            panic!("Expected Values");
+        };
+        assert_eq!(values.variables, vec!["x", "name"]);
+        assert_eq!(
+            values.rows,
+            vec![
+                vec![Value::from("http://example.org/alice"), Value::Null],
+                vec![Value::from("http://example.org/bob"), Value::from("Bob")],
+            ]
+        );
+    }
+
+    // === Property Path Tests ===
+
+    fn find_triple_path(op: &LogicalOperator) -> Option<&TriplePathOp> {
+        match op {
+            LogicalOperator::TriplePath(p) => Some(p),
+            LogicalOperator::Project(p) => find_triple_path(&p.input),
+            LogicalOperator::Filter(f) => find_triple_path(&f.input),
+            _ => None,
+        }
+    }
+
+    fn scan_endpoints(op: &LogicalOperator) -> (&TripleComponent, &TripleComponent) {
+        match op {
+            LogicalOperator::TripleScan(scan) => (&scan.subject, &scan.object),
+            other => panic!("Expected TripleScan, got {other:?}"),
+        }
+    }
+
+    fn var_name(component: &TripleComponent) -> &str {
+        match component {
+            TripleComponent::Variable(name) => name,
+            other => panic!("Expected variable, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_translate_one_or_more_path() {
+        let plan = translate("SELECT ?x ?y WHERE { ?x <http://ex.org/knows>+ ?y }").unwrap();
+        let path = find_triple_path(&plan.root).expect("Expected TriplePath");
+        assert_eq!(var_name(&path.subject), "x");
+        assert_eq!(var_name(&path.object), "y");
+        assert_eq!(path.predicate, "http://ex.org/knows");
+        assert!(!path.inverse);
+        assert_eq!((path.min_hops, path.max_hops), (1, None));
+    }
+
+    #[test]
+    fn test_translate_zero_or_more_path() {
+        let plan = translate("SELECT ?x ?y WHERE { ?x <http://ex.org/knows>* ?y }").unwrap();
+        let path = find_triple_path(&plan.root).expect("Expected TriplePath");
+        assert_eq!((path.min_hops, path.max_hops), (0, None));
+    }
+
+    #[test]
+    fn test_translate_zero_or_one_path() {
+        let plan = translate("SELECT ?x ?y WHERE { ?x <http://ex.org/knows>? ?y }").unwrap();
+        let path = find_triple_path(&plan.root).expect("Expected TriplePath");
+        assert_eq!((path.min_hops, path.max_hops), (0, Some(1)));
+    }
+
+    #[test]
+    fn test_translate_quantified_inverse_path() {
+        // ^p+ inverts the whole repetition, swapping the endpoints
+        let plan = translate("SELECT ?x ?y WHERE { ?x ^<http://ex.org/knows>+ ?y }").unwrap();
+        let path = find_triple_path(&plan.root).expect("Expected TriplePath");
+        assert_eq!(var_name(&path.subject), "y");
+        assert_eq!(var_name(&path.object), "x");
+        assert!(!path.inverse);
+
+        // (^p)+ repeats the inverse step, walking triples backwards
+        let plan = translate("SELECT ?x ?y WHERE { ?x (^<http://ex.org/knows>)+ ?y }").unwrap();
+        let path = find_triple_path(&plan.root).expect("Expected TriplePath");
+        assert_eq!(var_name(&path.subject), "x");
+        assert_eq!(var_name(&path.object), "y");
+        assert!(path.inverse);
+        assert_eq!((path.min_hops, path.max_hops), (1, None));
+    }
+
+    #[test]
+    fn test_translate_quantified_path_with_constant_subject() {
+        let plan = translate("SELECT ?y WHERE { <http://ex.org/alice> <http://ex.org/knows>* ?y }")
+            .unwrap();
+
+        let LogicalOperator::Project(project) = &plan.root else {
+            panic!("Expected Project");
+        };
+        let LogicalOperator::TriplePath(path) = project.input.as_ref() else {
+            panic!("Expected TriplePath");
+        };
+        assert!(matches!(&path.subject, TripleComponent::Iri(iri) if iri == "http://ex.org/alice"));
+        assert_eq!(var_name(&path.object), "y");
+    }
+
+    #[test]
+    fn test_translate_sequence_path() {
+        let plan =
+            translate("SELECT ?x ?z WHERE { ?x <http://ex.org/knows>/<http://ex.org/name> ?z }")
+                .unwrap();
+
+        let LogicalOperator::Project(project) = &plan.root else {
+            panic!("Expected Project");
+        };
+        let LogicalOperator::Join(join) = project.input.as_ref() else {
+            panic!("Expected Join");
+        };
+        let (first_subject, first_object) = scan_endpoints(&join.left);
+        let (second_subject, second_object) = scan_endpoints(&join.right);
+        assert_eq!(var_name(first_subject), "x");
+        assert_eq!(var_name(second_object), "z");
+
+        // The steps are linked by a fresh intermediate variable
+        let link = var_name(first_object);
+        assert!(link.starts_with("_:path"));
+        assert_eq!(var_name(second_subject), link);
+    }
+
+    #[test]
+    fn test_translate_inverse_path() {
+        let plan = translate("SELECT ?x ?y WHERE { ?x ^<http://ex.org/knows> ?y }").unwrap();
+
+        let LogicalOperator::Project(project) = &plan.root else {
+            panic!("Expected Project");
+        };
+        let (subject, object) = scan_endpoints(&project.input);
+        assert_eq!(var_name(subject), "y");
+        assert_eq!(var_name(object), "x");
+    }
+
+    #[test]
+    fn test_translate_quantified_sequence_path_unsupported() {
+        let result =
+            translate("SELECT ?x ?y WHERE { ?x (<http://ex.org/a>/<http://ex.org/b>)+ ?y }");
+        assert!(result.is_err());
+    }
+
     // === Aggregate Tests ===
 
     #[test]
@@ -1457,6 +1822,31 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_literal_base64_binary() {
+        let translator = SparqlTranslator::new();
+        let xsd = |value: &str| {
+            ast::Literal::typed(
+                value,
+                ast::Iri::new("http://www.w3.org/2001/XMLSchema#base64Binary"),
+            )
+        };
+
+        assert_eq!(
+            translator.literal_to_value(&xsd("AAEC/w==")),
+            Value::Bytes(vec![0, 1, 2, 255].into())
+        );
+        assert_eq!(
+            translator.literal_to_value(&xsd("")),
+            Value::Bytes(Vec::new().into())
+        );
+        // Invalid base64 stays a plain string
+        assert_eq!(
+            translator.literal_to_value(&xsd("not base64!")),
+            Value::String("not base64!".into())
+        );
+    }
+
     // === Helper Function Tests ===
 
     #[test]
@@ -4,17 +4,33 @@
 //! its own transaction state, so concurrent sessions don't interfere with
 //! each other. Sessions are cheap to create - spin up as many as you need.
 
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
-use grafeo_common::types::{EpochId, NodeId, TxId, Value};
+use grafeo_adapters::storage::AccessAdvisor;
+use grafeo_common::memory::buffer::BufferManager;
+use grafeo_common::types::{EdgeId, EpochId, NodeId, PropertyKey, TxId, Value};
 use grafeo_common::utils::error::Result;
-use grafeo_core::graph::lpg::LpgStore;
+use grafeo_core::execution::operators::{ConstraintChecker, RandomSource};
+use grafeo_core::graph::lpg::{LpgStore, Savepoint};
 #[cfg(feature = "rdf")]
 use grafeo_core::graph::rdf::RdfStore;
+use grafeo_core::statistics::ColumnStatistics;
+use parking_lot::Mutex;
 
+use crate::admin::{
+    AnalyzeStats, EdgeTypeInfo, LabelInfo, LpgSchemaInfo, PropertyInfo, SchemaInfo,
+};
+use crate::catalog::Catalog;
 use crate::config::AdaptiveConfig;
 use crate::database::QueryResult;
+use crate::import::{self, CsvLoadStats, LoadTarget};
+use crate::query::planner::DEFAULT_NESTED_LOOP_THRESHOLD;
+use crate::query::{CacheStats, QueryCache};
 use crate::transaction::TransactionManager;
+use grafeo_adapters::plugins::PluginRegistry;
 
 /// Your handle to the database - execute queries and manage transactions.
 ///
@@ -37,6 +53,26 @@ pub struct Session {
     /// Adaptive execution configuration.
     #[allow(dead_code)]
     adaptive_config: AdaptiveConfig,
+    /// Cache of translated query plans, shared with the database.
+    plan_cache: Arc<QueryCache>,
+    /// Schema catalog (indexes), shared with the database.
+    catalog: Arc<Catalog>,
+    /// Procedures `CALL` can run, shared with the database.
+    plugins: Arc<PluginRegistry>,
+    /// Nodes created by [`load_csv_nodes`](Self::load_csv_nodes), by CSV id.
+    csv_node_ids: Mutex<HashMap<String, NodeId>>,
+    /// Storage to hint with each plan's access pattern before it runs.
+    access_advisor: Option<Arc<dyn AccessAdvisor>>,
+    /// Source for `rand()`, shared by every query in the session.
+    random: Arc<RandomSource>,
+    /// Row estimate below which joins use a nested loop.
+    nested_loop_threshold: f64,
+    /// Longest a query may run; `None` for no limit.
+    query_timeout: Option<Duration>,
+    /// Budget this session's sorts reserve memory from; `None` for no limit.
+    buffer_manager: Option<Arc<BufferManager>>,
+    /// Savepoints of the current transaction, oldest first.
+    savepoints: Vec<(String, Savepoint)>,
 }
 
 impl Session {
@@ -51,6 +87,16 @@ impl Session {
             current_tx: None,
             auto_commit: true,
             adaptive_config: AdaptiveConfig::default(),
+            plan_cache: Arc::new(QueryCache::default()),
+            catalog: Arc::new(Catalog::new()),
+            plugins: Arc::new(PluginRegistry::with_builtin_algorithms()),
+            csv_node_ids: Mutex::new(HashMap::new()),
+            access_advisor: None,
+            random: Arc::new(RandomSource::from_entropy()),
+            nested_loop_threshold: DEFAULT_NESTED_LOOP_THRESHOLD,
+            query_timeout: None,
+            buffer_manager: None,
+            savepoints: Vec::new(),
         }
     }
 
@@ -69,6 +115,16 @@ impl Session {
             current_tx: None,
             auto_commit: true,
             adaptive_config,
+            plan_cache: Arc::new(QueryCache::default()),
+            catalog: Arc::new(Catalog::new()),
+            plugins: Arc::new(PluginRegistry::with_builtin_algorithms()),
+            csv_node_ids: Mutex::new(HashMap::new()),
+            access_advisor: None,
+            random: Arc::new(RandomSource::from_entropy()),
+            nested_loop_threshold: DEFAULT_NESTED_LOOP_THRESHOLD,
+            query_timeout: None,
+            buffer_manager: None,
+            savepoints: Vec::new(),
         }
     }
 
@@ -87,9 +143,220 @@ impl Session {
             current_tx: None,
             auto_commit: true,
             adaptive_config,
+            plan_cache: Arc::new(QueryCache::default()),
+            catalog: Arc::new(Catalog::new()),
+            plugins: Arc::new(PluginRegistry::with_builtin_algorithms()),
+            csv_node_ids: Mutex::new(HashMap::new()),
+            access_advisor: None,
+            random: Arc::new(RandomSource::from_entropy()),
+            nested_loop_threshold: DEFAULT_NESTED_LOOP_THRESHOLD,
+            query_timeout: None,
+            buffer_manager: None,
+            savepoints: Vec::new(),
         }
     }
 
+    /// Shares a plan cache with this session.
+    #[must_use]
+    pub(crate) fn with_plan_cache(mut self, plan_cache: Arc<QueryCache>) -> Self {
+        self.plan_cache = plan_cache;
+        self
+    }
+
+    /// Shares a schema catalog with this session.
+    #[must_use]
+    pub(crate) fn with_catalog(mut self, catalog: Arc<Catalog>) -> Self {
+        self.catalog = catalog;
+        self
+    }
+
+    /// Shares a plugin registry with this session.
+    #[must_use]
+    pub(crate) fn with_plugins(mut self, plugins: Arc<PluginRegistry>) -> Self {
+        self.plugins = plugins;
+        self
+    }
+
+    /// Sets the source `rand()` draws from in this session's queries.
+    #[must_use]
+    pub(crate) fn with_random_source(mut self, random: Arc<RandomSource>) -> Self {
+        self.random = random;
+        self
+    }
+
+    /// Sets the row estimate below which this session's joins use a nested loop.
+    #[must_use]
+    pub(crate) fn with_nested_loop_threshold(mut self, threshold: f64) -> Self {
+        self.nested_loop_threshold = threshold;
+        self
+    }
+
+    /// Sets how long this session's queries may run before they are cancelled.
+    #[must_use]
+    pub(crate) fn with_query_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.query_timeout = timeout;
+        self
+    }
+
+    /// Sets the budget this session's sorts reserve memory from.
+    #[must_use]
+    pub(crate) fn with_buffer_manager(mut self, manager: Option<Arc<BufferManager>>) -> Self {
+        self.buffer_manager = manager;
+        self
+    }
+
+    /// Hints `advisor` with each plan's access pattern before it runs.
+    #[must_use]
+    pub(crate) fn with_access_advisor(mut self, advisor: Option<Arc<dyn AccessAdvisor>>) -> Self {
+        self.access_advisor = advisor;
+        self
+    }
+
+    /// Tells the access advisor, if any, how `plan` is about to read storage.
+    ///
+    /// Hints are best effort: one the OS rejects doesn't fail the query.
+    #[cfg(any(
+        feature = "gql",
+        feature = "cypher",
+        feature = "gremlin",
+        feature = "graphql"
+    ))]
+    fn advise_access(&self, planner: &crate::query::Planner, plan: &crate::query::LogicalPlan) {
+        if let Some(advisor) = &self.access_advisor {
+            let pattern = planner.access_pattern(plan);
+            if let Err(e) = advisor.advise_access(pattern) {
+                tracing::debug!("Access hint {:?} rejected: {}", pattern, e);
+            }
+        }
+    }
+
+    /// Clears the cache of translated query plans.
+    ///
+    /// The cache is shared by all sessions of the same database.
+    pub fn clear_plan_cache(&self) {
+        self.plan_cache.clear();
+    }
+
+    /// Returns hit, miss, and eviction counters of the plan cache.
+    #[must_use]
+    pub fn plan_cache_stats(&self) -> CacheStats {
+        self.plan_cache.stats()
+    }
+
+    /// Rescans the store and rebuilds the optimizer statistics.
+    ///
+    /// Label and edge type counts, property distinct counts, and histograms
+    /// are all recomputed from scratch. Analyzing an empty database yields
+    /// zeroed statistics.
+    pub fn analyze(&self) -> AnalyzeStats {
+        let start = std::time::Instant::now();
+        let before = self.store.statistics();
+        self.store.compute_statistics();
+        let after = self.store.statistics();
+
+        AnalyzeStats {
+            nodes_before: before.total_nodes,
+            nodes_after: after.total_nodes,
+            edges_before: before.total_edges,
+            edges_after: after.total_edges,
+            label_count: after.labels.len(),
+            edge_type_count: after.edge_types.len(),
+            property_count: after.properties.len(),
+            duration_ms: start.elapsed().as_millis() as u64,
+        }
+    }
+
+    /// Describes the graph: labels and edge types with their counts, the
+    /// property keys each one uses with inferred types, and the label pairs
+    /// edges of each type connect.
+    ///
+    /// Counts and property types come from the optimizer statistics, which
+    /// are recomputed first if any write made them stale, so the counts are
+    /// exact; endpoint pairs come from a scan of each edge type's edges, and
+    /// unique and NOT NULL flags from the catalog. A label or edge type
+    /// whose nodes or edges were all deleted is left out, although its name
+    /// stays registered in the store.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grafeo_engine::{GrafeoDB, SchemaInfo};
+    ///
+    /// let db = GrafeoDB::new_in_memory();
+    /// let session = db.session();
+    /// let alice = session.create_node(&["Person"]);
+    /// let acme = session.create_node(&["Company"]);
+    /// session.create_edge(alice, acme, "WORKS_AT");
+    ///
+    /// let SchemaInfo::Lpg(schema) = session.schema() else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(schema.labels.len(), 2);
+    /// assert_eq!(
+    ///     schema.edge_types[0].endpoints,
+    ///     [("Person".to_string(), "Company".to_string())]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn schema(&self) -> SchemaInfo {
+        let mut stats = self.store.statistics();
+        if stats.is_stale()
+            || stats.total_nodes != self.store.node_count() as u64
+            || stats.total_edges != self.store.edge_count() as u64
+        {
+            self.store.compute_statistics();
+            stats = self.store.statistics();
+        }
+
+        let mut labels: Vec<LabelInfo> = stats
+            .labels
+            .iter()
+            .filter(|(_, label)| label.node_count > 0)
+            .map(|(name, label)| {
+                let label_id = self.catalog.get_label_id(name);
+                LabelInfo {
+                    name: name.clone(),
+                    count: label.node_count as usize,
+                    properties: property_infos(&label.properties, |key| {
+                        let key_id = self.catalog.get_property_key_id(key);
+                        match (label_id, key_id) {
+                            (Some(label_id), Some(key_id)) => (
+                                self.catalog.is_property_unique(label_id, key_id),
+                                self.catalog.is_property_required(label_id, key_id),
+                            ),
+                            _ => (false, false),
+                        }
+                    }),
+                }
+            })
+            .collect();
+        labels.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut edge_types: Vec<EdgeTypeInfo> = stats
+            .edge_types
+            .iter()
+            .filter(|(_, edge_type)| edge_type.edge_count > 0)
+            .map(|(name, edge_type)| EdgeTypeInfo {
+                name: name.clone(),
+                count: edge_type.edge_count as usize,
+                endpoints: self
+                    .store
+                    .edge_type_endpoints(name)
+                    .into_iter()
+                    .map(|(source, target)| (source.to_string(), target.to_string()))
+                    .collect(),
+                properties: property_infos(&edge_type.properties, |_| (false, false)),
+            })
+            .collect();
+        edge_types.sort_by(|a, b| a.name.cmp(&b.name));
+
+        SchemaInfo::Lpg(LpgSchemaInfo {
+            labels,
+            edge_types,
+            property_keys: self.store.all_property_keys(),
+        })
+    }
+
     /// Executes a GQL query.
     ///
     /// # Errors
@@ -115,12 +382,119 @@ impl Session {
     /// ```
     #[cfg(feature = "gql")]
     pub fn execute(&self, query: &str) -> Result<QueryResult> {
-        use crate::query::{
-            Executor, Planner, binder::Binder, gql_translator, optimizer::Optimizer,
-        };
+        use crate::query::Executor;
 
-        // Parse and translate the query to a logical plan
-        let logical_plan = gql_translator::translate(query)?;
+        let mut physical_plan = self.plan_gql(query)?;
+
+        // Execute the plan
+        let executor = Executor::with_columns(physical_plan.columns.clone());
+        executor.execute(physical_plan.operator.as_mut())
+    }
+
+    /// Executes a GQL query, cancelling it if it runs longer than `timeout`.
+    ///
+    /// The limit replaces the session's
+    /// [`query_timeout`](Self::query_timeout) for this query only; use
+    /// [`set_query_timeout`](Self::set_query_timeout) to limit queries in
+    /// the other languages.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Timeout`](grafeo_common::utils::error::Error::Timeout)
+    /// if the query runs out of time, or an error if it fails to parse or
+    /// execute.
+    #[cfg(feature = "gql")]
+    pub fn execute_with_timeout(&self, query: &str, timeout: Duration) -> Result<QueryResult> {
+        use crate::query::Executor;
+
+        let (viewing_epoch, tx_id) = self.get_transaction_context();
+        let optimized_plan = self.optimize_gql(query)?;
+        let planner = self
+            .planner_at(viewing_epoch, tx_id)
+            .with_query_timeout(Some(timeout));
+        self.advise_access(&planner, &optimized_plan);
+        let mut physical_plan = planner.plan(&optimized_plan)?;
+
+        let executor = Executor::with_columns(physical_plan.columns.clone());
+        executor.execute(physical_plan.operator.as_mut())
+    }
+
+    /// Executes a GQL query, yielding rows lazily.
+    ///
+    /// Unlike [`execute`](Self::execute), rows are produced one operator chunk
+    /// at a time, so results never need to fit in memory and stopping early
+    /// (e.g. with `take`) stops the underlying scan. Errors raised while the
+    /// query runs are yielded as an `Err` item, after which the stream ends.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails to parse, validate, or plan.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use grafeo_engine::GrafeoDB;
+    ///
+    /// let db = GrafeoDB::new_in_memory();
+    /// let session = db.session();
+    ///
+    /// for row in session.execute_stream("MATCH (n:Person) RETURN n.name")?.take(10) {
+    ///     println!("{:?}", row?);
+    /// }
+    /// ```
+    #[cfg(feature = "gql")]
+    pub fn execute_stream(&self, query: &str) -> Result<crate::query::ResultStream> {
+        let physical_plan = self.plan_gql(query)?;
+        Ok(crate::query::ResultStream::new(
+            physical_plan.operator,
+            physical_plan.columns,
+        ))
+    }
+
+    /// Parses, validates, optimizes, and plans a GQL query.
+    #[cfg(feature = "gql")]
+    fn plan_gql(&self, query: &str) -> Result<crate::query::PhysicalPlan> {
+        let (viewing_epoch, tx_id) = self.get_transaction_context();
+        self.plan_gql_at(query, viewing_epoch, tx_id, false)
+    }
+
+    /// Plans a GQL query that reads at `viewing_epoch` on behalf of `tx_id`.
+    ///
+    /// With `read_only` set, queries that modify the graph are rejected.
+    #[cfg(feature = "gql")]
+    fn plan_gql_at(
+        &self,
+        query: &str,
+        viewing_epoch: EpochId,
+        tx_id: Option<TxId>,
+        read_only: bool,
+    ) -> Result<crate::query::PhysicalPlan> {
+        let optimized_plan = self.optimize_gql(query)?;
+
+        if read_only && !optimized_plan.root.is_read_only() {
+            return Err(grafeo_common::utils::error::Error::Transaction(
+                grafeo_common::utils::error::TransactionError::InvalidState(
+                    "Cannot modify the graph from a read-only snapshot".to_string(),
+                ),
+            ));
+        }
+
+        let planner = self.planner_at(viewing_epoch, tx_id);
+        self.advise_access(&planner, &optimized_plan);
+        planner.plan(&optimized_plan)
+    }
+
+    /// Parses, validates, and optimizes a GQL query into a logical plan.
+    #[cfg(feature = "gql")]
+    fn optimize_gql(&self, query: &str) -> Result<crate::query::LogicalPlan> {
+        use crate::query::processor::QueryLanguage;
+        use crate::query::{CacheKey, binder::Binder, gql_translator, optimizer::Optimizer};
+
+        // Parse and translate the query to a logical plan, reusing cached plans
+        let key = CacheKey::new(query, QueryLanguage::Gql);
+        let logical_plan = self
+            .plan_cache
+            .get_or_insert_parsed(key, || gql_translator::translate(query))?;
 
         // Semantic validation
         let mut binder = Binder::new();
@@ -128,36 +502,117 @@ impl Session {
 
         // Optimize the plan
         let optimizer = Optimizer::new();
-        let optimized_plan = optimizer.optimize(logical_plan)?;
-
-        // Get transaction context for MVCC visibility
-        let (viewing_epoch, tx_id) = self.get_transaction_context();
+        optimizer.optimize(logical_plan)
+    }
 
-        // Convert to physical plan with transaction context
-        let planner = Planner::with_context(
+    /// Returns a planner with this session's transaction context.
+    #[cfg(feature = "gql")]
+    fn planner_at(&self, viewing_epoch: EpochId, tx_id: Option<TxId>) -> crate::query::Planner {
+        crate::query::Planner::with_context(
             Arc::clone(&self.store),
             Arc::clone(&self.tx_manager),
             tx_id,
             viewing_epoch,
-        );
-        let mut physical_plan = planner.plan(&optimized_plan)?;
+        )
+        .with_catalog(Arc::clone(&self.catalog))
+        .with_plugins(Arc::clone(&self.plugins))
+        .with_random_source(Arc::clone(&self.random))
+        .with_nested_loop_threshold(self.nested_loop_threshold)
+        .with_query_timeout(self.query_timeout)
+        .with_buffer_manager(self.buffer_manager.clone())
+    }
 
-        // Execute the plan
-        let executor = Executor::with_columns(physical_plan.columns.clone());
-        executor.execute(physical_plan.operator.as_mut())
+    /// Describes how a GQL query would run, without running it.
+    ///
+    /// Returns the physical operator tree, one operator per line and indented
+    /// under its parent, with each operator's estimated row count and the
+    /// estimated cost of its subtree. Scans also say whether they walk every
+    /// node, use the label index, or use a property index. Estimates come
+    /// from the store statistics, so run [`analyze`](Self::analyze) first if
+    /// the data has changed a lot since they were last computed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails to parse, validate, or plan.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use grafeo_engine::GrafeoDB;
+    ///
+    /// let db = GrafeoDB::new_in_memory();
+    /// let session = db.session();
+    ///
+    /// println!("{}", session.explain("MATCH (n:Person) RETURN count(n)")?);
+    /// ```
+    #[cfg(feature = "gql")]
+    pub fn explain(&self, query: &str) -> Result<String> {
+        use crate::query::optimizer::{CardinalityEstimator, CostModel};
+
+        let optimized_plan = self.optimize_gql(query)?;
+        let (viewing_epoch, tx_id) = self.get_transaction_context();
+        let planner = self.planner_at(viewing_epoch, tx_id);
+        let estimator = CardinalityEstimator::from_statistics(&self.store.statistics());
+        crate::query::explain(&planner, &optimized_plan, &estimator, &CostModel::new())
+    }
+
+    /// Runs a GQL query and describes how it actually ran.
+    ///
+    /// Like [`explain`](Self::explain), but each operator also shows the rows
+    /// it actually emitted (`actual_rows`) and the wall-clock time spent
+    /// producing them, including time spent in its inputs. Comparing `rows`
+    /// with `actual_rows` shows where the estimates are off. An operator
+    /// below a `LIMIT` reports only the rows the limit pulled from it.
+    ///
+    /// The query really runs, so any writes in it are applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails to parse, plan, or execute.
+    #[cfg(feature = "gql")]
+    pub fn explain_analyze(&self, query: &str) -> Result<String> {
+        use crate::query::optimizer::{CardinalityEstimator, CostModel};
+
+        let optimized_plan = self.optimize_gql(query)?;
+        let (viewing_epoch, tx_id) = self.get_transaction_context();
+        let planner = self.planner_at(viewing_epoch, tx_id).with_profiling();
+        let estimator = CardinalityEstimator::from_statistics(&self.store.statistics());
+        crate::query::explain_analyze(&planner, &optimized_plan, &estimator, &CostModel::new())
     }
 
     /// Executes a GQL query with parameters.
     ///
+    /// `$name` placeholders in the query are bound to the values in `params`,
+    /// so values never need to be formatted into the query text.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the query fails to parse or execute.
+    /// Returns a semantic query error if a placeholder has no value in
+    /// `params`, or an error if the query fails to parse or execute.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use grafeo_engine::GrafeoDB;
+    /// use grafeo_engine::query::QueryParams;
+    ///
+    /// let db = GrafeoDB::new_in_memory();
+    /// let session = db.session();
+    ///
+    /// let mut params = QueryParams::new();
+    /// params.insert("name".to_string(), Value::from("Alice"));
+    /// let result = session.execute_with_params(
+    ///     "MATCH (n:Person) WHERE n.name = $name RETURN n",
+    ///     params,
+    /// )?;
+    /// ```
     #[cfg(feature = "gql")]
     pub fn execute_with_params(
         &self,
         query: &str,
-        params: std::collections::HashMap<String, Value>,
+        params: crate::query::QueryParams,
     ) -> Result<QueryResult> {
+        use crate::query::CacheKey;
         use crate::query::processor::{QueryLanguage, QueryProcessor};
 
         // Get transaction context for MVCC visibility
@@ -165,7 +620,13 @@ impl Session {
 
         // Create processor with transaction context
         let processor =
-            QueryProcessor::for_lpg_with_tx(Arc::clone(&self.store), Arc::clone(&self.tx_manager));
+            QueryProcessor::for_lpg_with_tx(Arc::clone(&self.store), Arc::clone(&self.tx_manager))
+                .with_catalog(Arc::clone(&self.catalog))
+                .with_plugins(Arc::clone(&self.plugins))
+                .with_random_source(Arc::clone(&self.random))
+                .with_nested_loop_threshold(self.nested_loop_threshold)
+                .with_query_timeout(self.query_timeout)
+                .with_buffer_manager(self.buffer_manager.clone());
 
         // Apply transaction context if in a transaction
         let processor = if let Some(tx_id) = tx_id {
@@ -174,7 +635,12 @@ impl Session {
             processor
         };
 
-        processor.process(query, QueryLanguage::Gql, Some(&params))
+        // Placeholders stay in the cached plan and are bound per call
+        let key = CacheKey::new(query, QueryLanguage::Gql);
+        let logical_plan = self
+            .plan_cache
+            .get_or_insert_parsed(key, || processor.translate_lpg(query, QueryLanguage::Gql))?;
+        processor.execute_lpg_plan(logical_plan, Some(&params))
     }
 
     /// Executes a GQL query with parameters.
@@ -236,7 +702,14 @@ impl Session {
             Arc::clone(&self.tx_manager),
             tx_id,
             viewing_epoch,
-        );
+        )
+        .with_catalog(Arc::clone(&self.catalog))
+        .with_plugins(Arc::clone(&self.plugins))
+        .with_random_source(Arc::clone(&self.random))
+        .with_nested_loop_threshold(self.nested_loop_threshold)
+        .with_query_timeout(self.query_timeout)
+        .with_buffer_manager(self.buffer_manager.clone());
+        self.advise_access(&planner, &optimized_plan);
         let mut physical_plan = planner.plan(&optimized_plan)?;
 
         // Execute the plan
@@ -290,7 +763,14 @@ impl Session {
             Arc::clone(&self.tx_manager),
             tx_id,
             viewing_epoch,
-        );
+        )
+        .with_catalog(Arc::clone(&self.catalog))
+        .with_plugins(Arc::clone(&self.plugins))
+        .with_random_source(Arc::clone(&self.random))
+        .with_nested_loop_threshold(self.nested_loop_threshold)
+        .with_query_timeout(self.query_timeout)
+        .with_buffer_manager(self.buffer_manager.clone());
+        self.advise_access(&planner, &optimized_plan);
         let mut physical_plan = planner.plan(&optimized_plan)?;
 
         // Execute the plan
@@ -316,7 +796,13 @@ impl Session {
 
         // Create processor with transaction context
         let processor =
-            QueryProcessor::for_lpg_with_tx(Arc::clone(&self.store), Arc::clone(&self.tx_manager));
+            QueryProcessor::for_lpg_with_tx(Arc::clone(&self.store), Arc::clone(&self.tx_manager))
+                .with_catalog(Arc::clone(&self.catalog))
+                .with_plugins(Arc::clone(&self.plugins))
+                .with_random_source(Arc::clone(&self.random))
+                .with_nested_loop_threshold(self.nested_loop_threshold)
+                .with_query_timeout(self.query_timeout)
+                .with_buffer_manager(self.buffer_manager.clone());
 
         // Apply transaction context if in a transaction
         let processor = if let Some(tx_id) = tx_id {
@@ -374,7 +860,14 @@ impl Session {
             Arc::clone(&self.tx_manager),
             tx_id,
             viewing_epoch,
-        );
+        )
+        .with_catalog(Arc::clone(&self.catalog))
+        .with_plugins(Arc::clone(&self.plugins))
+        .with_random_source(Arc::clone(&self.random))
+        .with_nested_loop_threshold(self.nested_loop_threshold)
+        .with_query_timeout(self.query_timeout)
+        .with_buffer_manager(self.buffer_manager.clone());
+        self.advise_access(&planner, &optimized_plan);
         let mut physical_plan = planner.plan(&optimized_plan)?;
 
         // Execute the plan
@@ -400,7 +893,13 @@ impl Session {
 
         // Create processor with transaction context
         let processor =
-            QueryProcessor::for_lpg_with_tx(Arc::clone(&self.store), Arc::clone(&self.tx_manager));
+            QueryProcessor::for_lpg_with_tx(Arc::clone(&self.store), Arc::clone(&self.tx_manager))
+                .with_catalog(Arc::clone(&self.catalog))
+                .with_plugins(Arc::clone(&self.plugins))
+                .with_random_source(Arc::clone(&self.random))
+                .with_nested_loop_threshold(self.nested_loop_threshold)
+                .with_query_timeout(self.query_timeout)
+                .with_buffer_manager(self.buffer_manager.clone());
 
         // Apply transaction context if in a transaction
         let processor = if let Some(tx_id) = tx_id {
@@ -431,12 +930,14 @@ impl Session {
         let optimized_plan = optimizer.optimize(logical_plan)?;
 
         // Convert to physical plan using RDF planner
-        let planner = RdfPlanner::new(Arc::clone(&self.rdf_store)).with_tx_id(self.current_tx);
+        let planner = RdfPlanner::new(Arc::clone(&self.rdf_store))
+            .with_tx_id(self.current_tx)
+            .with_query_timeout(self.query_timeout)
+            .with_buffer_manager(self.buffer_manager.clone());
         let mut physical_plan = planner.plan(&optimized_plan)?;
 
         // Execute the plan
-        let executor = Executor::with_columns(physical_plan.columns.clone());
-        executor.execute(physical_plan.operator.as_mut())
+        Executor::execute_plan(&optimized_plan, &mut physical_plan)
     }
 
     /// Executes a SPARQL query with parameters.
@@ -455,6 +956,35 @@ impl Session {
         self.execute_sparql(query)
     }
 
+    /// Begins a write transaction and returns a handle to it.
+    ///
+    /// Statements run through the [`Transaction`] either all apply, on
+    /// [`commit`](Transaction::commit), or none do. Dropping the handle
+    /// without committing rolls the transaction back, including when the
+    /// thread unwinds from a panic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a transaction is already active.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use grafeo_engine::GrafeoDB;
+    ///
+    /// let db = GrafeoDB::new_in_memory();
+    /// let mut session = db.session();
+    ///
+    /// let tx = session.begin()?;
+    /// tx.execute("INSERT (:Person {name: 'Alice'})")?;
+    /// tx.execute("MATCH (p:Person) SET p.age = 30")?;
+    /// tx.commit()?;
+    /// ```
+    pub fn begin(&mut self) -> Result<Transaction<'_>> {
+        self.begin_tx()?;
+        Ok(Transaction { session: self })
+    }
+
     /// Begins a new transaction.
     ///
     /// # Errors
@@ -485,9 +1015,85 @@ impl Session {
 
         let tx_id = self.tx_manager.begin();
         self.current_tx = Some(tx_id);
+        self.savepoints.clear();
+        self.sync_oldest_reader();
+        Ok(())
+    }
+
+    /// Marks a point in the current transaction to roll back to later.
+    ///
+    /// Savepoints nest: rolling back to one discards those set after it.
+    /// A savepoint reusing an earlier name hides the earlier one until it is
+    /// released. Changes to the RDF store are not covered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no transaction is active.
+    pub fn savepoint(&mut self, name: &str) -> Result<()> {
+        let tx_id = self.active_tx()?;
+        self.savepoints
+            .push((name.to_string(), self.store.savepoint(tx_id)));
+        Ok(())
+    }
+
+    /// Undoes the changes made since the savepoint `name`, keeping earlier
+    /// ones and the transaction itself.
+    ///
+    /// The savepoint stays set, so the transaction can roll back to it
+    /// again; savepoints set after it are released.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no transaction is active or it has no savepoint
+    /// called `name`.
+    pub fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> {
+        let tx_id = self.active_tx()?;
+        let index = self.savepoint_index(name)?;
+        self.savepoints.truncate(index + 1);
+        self.store
+            .rollback_to_savepoint(tx_id, self.savepoints[index].1);
+        Ok(())
+    }
+
+    /// Forgets the savepoint `name` and those set after it, keeping their
+    /// changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no transaction is active or it has no savepoint
+    /// called `name`.
+    pub fn release_savepoint(&mut self, name: &str) -> Result<()> {
+        self.active_tx()?;
+        let index = self.savepoint_index(name)?;
+        self.savepoints.truncate(index);
         Ok(())
     }
 
+    /// Returns the current transaction, failing if there is none.
+    fn active_tx(&self) -> Result<TxId> {
+        self.current_tx.ok_or_else(|| {
+            grafeo_common::utils::error::Error::Transaction(
+                grafeo_common::utils::error::TransactionError::InvalidState(
+                    "No active transaction".to_string(),
+                ),
+            )
+        })
+    }
+
+    /// Finds the most recent savepoint called `name`.
+    fn savepoint_index(&self, name: &str) -> Result<usize> {
+        self.savepoints
+            .iter()
+            .rposition(|(savepoint, _)| savepoint == name)
+            .ok_or_else(|| {
+                grafeo_common::utils::error::Error::Transaction(
+                    grafeo_common::utils::error::TransactionError::InvalidState(format!(
+                        "No savepoint named '{name}'"
+                    )),
+                )
+            })
+    }
+
     /// Commits the current transaction.
     ///
     /// Makes all changes since [`begin_tx`](Self::begin_tx) permanent.
@@ -504,11 +1110,81 @@ impl Session {
             )
         })?;
 
+        self.savepoints.clear();
+
         // Commit RDF store pending operations
         #[cfg(feature = "rdf")]
         self.rdf_store.commit_tx(tx_id);
 
-        self.tx_manager.commit(tx_id).map(|_| ())
+        // Everything the store saw this transaction modify takes part in
+        // conflict detection; an inactive transaction fails the commit below
+        for id in self.store.nodes_written_by(tx_id) {
+            let _ = self.tx_manager.record_write(tx_id, id);
+        }
+        for id in self.store.edges_written_by(tx_id) {
+            let _ = self.tx_manager.record_write(tx_id, id);
+        }
+
+        let result = match self.tx_manager.commit(tx_id) {
+            Ok(commit_epoch) => {
+                // Keep the writes out of snapshots pinned before the commit
+                self.store.commit_versions(tx_id, commit_epoch);
+                Ok(())
+            }
+            Err(err) => {
+                // A conflicting transaction must not leave its writes behind
+                self.store.discard_uncommitted_versions(tx_id);
+                let _ = self.tx_manager.abort(tx_id);
+                Err(err)
+            }
+        };
+        self.sync_oldest_reader();
+        result
+    }
+
+    /// Tells the store the epoch its oldest open transaction or snapshot
+    /// reads at, so it keeps the property and label history those need.
+    fn sync_oldest_reader(&self) {
+        self.tx_manager
+            .with_oldest_active_epoch(|oldest| self.store.set_oldest_reader(oldest));
+    }
+
+    /// Begins a read-only snapshot of the database.
+    ///
+    /// Every query run through the returned [`ReadSnapshot`] sees the graph
+    /// as it was when this method was called, even if other sessions commit
+    /// in the meantime. The snapshot is released when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use grafeo_engine::GrafeoDB;
+    ///
+    /// let db = GrafeoDB::new_in_memory();
+    /// let session = db.session();
+    ///
+    /// let snapshot = session.begin_read_only();
+    /// db.session().execute("INSERT (:Person {name: 'Alice'})")?;
+    ///
+    /// // The insert happened after the snapshot was taken
+    /// let result = snapshot.execute("MATCH (n:Person) RETURN n")?;
+    /// assert_eq!(result.row_count(), 0);
+    /// ```
+    #[must_use]
+    pub fn begin_read_only(&self) -> ReadSnapshot<'_> {
+        let tx_id = self.tx_manager.begin_read_only();
+        let epoch = self
+            .tx_manager
+            .start_epoch(tx_id)
+            .unwrap_or_else(|| self.tx_manager.current_epoch());
+        // Later writes are stamped past the snapshot, so the store follows
+        self.store.advance_epoch_to(self.tx_manager.current_epoch());
+        self.sync_oldest_reader();
+        ReadSnapshot {
+            session: self,
+            tx_id,
+            epoch,
+        }
     }
 
     /// Aborts the current transaction.
@@ -540,6 +1216,8 @@ impl Session {
             )
         })?;
 
+        self.savepoints.clear();
+
         // Discard uncommitted versions in the LPG store
         self.store.discard_uncommitted_versions(tx_id);
 
@@ -548,7 +1226,9 @@ impl Session {
         self.rdf_store.rollback_tx(tx_id);
 
         // Mark transaction as aborted in the manager
-        self.tx_manager.abort(tx_id)
+        let result = self.tx_manager.abort(tx_id);
+        self.sync_oldest_reader();
+        result
     }
 
     /// Returns whether a transaction is active.
@@ -568,12 +1248,29 @@ impl Session {
         self.auto_commit
     }
 
-    /// Returns the current transaction context for MVCC visibility.
+    /// Sets how long this session's queries may run, in any query language,
+    /// before they fail with
+    /// [`Error::Timeout`](grafeo_common::utils::error::Error::Timeout).
     ///
-    /// Returns `(viewing_epoch, tx_id)` where:
-    /// - `viewing_epoch` is the epoch at which to check version visibility
-    /// - `tx_id` is the current transaction ID (if in a transaction)
-    #[must_use]
+    /// This replaces the database's
+    /// [`query_timeout`](crate::Config::query_timeout) for the session;
+    /// `None` lets its queries run to completion.
+    pub fn set_query_timeout(&mut self, timeout: Option<Duration>) {
+        self.query_timeout = timeout;
+    }
+
+    /// Returns how long this session's queries may run, if limited.
+    #[must_use]
+    pub fn query_timeout(&self) -> Option<Duration> {
+        self.query_timeout
+    }
+
+    /// Returns the current transaction context for MVCC visibility.
+    ///
+    /// Returns `(viewing_epoch, tx_id)` where:
+    /// - `viewing_epoch` is the epoch at which to check version visibility
+    /// - `tx_id` is the current transaction ID (if in a transaction)
+    #[must_use]
     fn get_transaction_context(&self) -> (EpochId, Option<TxId>) {
         if let Some(tx_id) = self.current_tx {
             // In a transaction - use the transaction's start epoch
@@ -615,6 +1312,168 @@ impl Session {
         )
     }
 
+    /// Bulk-loads one `label` node per row of a CSV file.
+    ///
+    /// The header row names the properties, and column types are inferred
+    /// from the data (see [`import`](crate::import)). Values in `id_column`
+    /// identify the nodes to [`load_csv_edges`](Self::load_csv_edges) on this
+    /// session, so they must be unique across all node files it loads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or parsed, has no
+    /// `id_column`, or repeats an id. Nothing is inserted in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use grafeo_engine::GrafeoDB;
+    ///
+    /// let db = GrafeoDB::new_in_memory();
+    /// let session = db.session();
+    ///
+    /// session.load_csv_nodes("people.csv", "Person", "id")?;
+    /// session.load_csv_edges("knows.csv", "KNOWS", "from", "to")?;
+    /// ```
+    pub fn load_csv_nodes(
+        &self,
+        path: impl AsRef<Path>,
+        label: &str,
+        id_column: &str,
+    ) -> Result<CsvLoadStats> {
+        let mut ids = self.csv_node_ids.lock();
+        import::load_nodes(
+            &self.load_target(),
+            &mut ids,
+            path.as_ref(),
+            label,
+            id_column,
+        )
+    }
+
+    /// Bulk-loads one `edge_type` edge per row of a CSV file.
+    ///
+    /// `source_column` and `target_column` hold ids of nodes loaded with
+    /// [`load_csv_nodes`](Self::load_csv_nodes); every other column becomes
+    /// an edge property.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or parsed, lacks either
+    /// endpoint column, or refers to an unknown id. Nothing is inserted in
+    /// that case.
+    pub fn load_csv_edges(
+        &self,
+        path: impl AsRef<Path>,
+        edge_type: &str,
+        source_column: &str,
+        target_column: &str,
+    ) -> Result<CsvLoadStats> {
+        let ids = self.csv_node_ids.lock();
+        import::load_edges(
+            &self.load_target(),
+            &ids,
+            path.as_ref(),
+            edge_type,
+            source_column,
+            target_column,
+        )
+    }
+
+    /// Inserts one `label` node per property map, returning the IDs in order.
+    ///
+    /// Rows go straight to the mutation operators a [`DataChunk`] at a time,
+    /// skipping query parsing and planning, so this is much faster than one
+    /// `INSERT` per row. Inside a transaction, the nodes become visible on
+    /// commit.
+    ///
+    /// [`DataChunk`]: grafeo_core::execution::DataChunk
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a write fails, for example on a constraint
+    /// violation. Outside a transaction, earlier batches stay inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use grafeo_common::types::Value;
+    /// use grafeo_engine::GrafeoDB;
+    ///
+    /// let db = GrafeoDB::new_in_memory();
+    /// let session = db.session();
+    ///
+    /// let ids = session
+    ///     .insert_nodes(
+    ///         "Person",
+    ///         (0..3).map(|i| HashMap::from([("age".into(), Value::Int64(20 + i))])),
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(ids.len(), 3);
+    /// ```
+    pub fn insert_nodes(
+        &self,
+        label: &str,
+        rows: impl IntoIterator<Item = HashMap<PropertyKey, Value>>,
+    ) -> Result<Vec<NodeId>> {
+        import::insert_nodes(&self.load_target(), label, rows)
+    }
+
+    /// Inserts one `edge_type` edge per `(source, target, properties)` row,
+    /// returning the IDs in order.
+    ///
+    /// Like [`insert_nodes`](Self::insert_nodes), this bypasses the query
+    /// pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NodeNotFound`](grafeo_common::utils::error::Error::NodeNotFound)
+    /// if an endpoint doesn't exist, or an error if a write fails. Outside a
+    /// transaction, earlier batches stay inserted.
+    pub fn insert_edges(
+        &self,
+        edge_type: &str,
+        rows: impl IntoIterator<Item = (NodeId, NodeId, HashMap<PropertyKey, Value>)>,
+    ) -> Result<Vec<EdgeId>> {
+        import::insert_edges(&self.load_target(), edge_type, rows)
+    }
+
+    /// Loads the triples of a Turtle file into the RDF store.
+    ///
+    /// Prefixes, typed and language-tagged literals, blank nodes and
+    /// collections are supported (see [`turtle`](crate::import::turtle)).
+    /// Inside a transaction, the triples become visible on commit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't valid Turtle.
+    /// Nothing is inserted in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use grafeo_engine::GrafeoDB;
+    ///
+    /// let db = GrafeoDB::new_in_memory();
+    /// db.session().load_turtle("people.ttl")?;
+    /// let result = db.execute_sparql("SELECT ?s ?p ?o WHERE { ?s ?p ?o }")?;
+    /// ```
+    #[cfg(all(feature = "sparql", feature = "rdf"))]
+    pub fn load_turtle(&self, path: impl AsRef<Path>) -> Result<import::turtle::TurtleLoadStats> {
+        import::turtle::load(&self.rdf_store, self.current_tx, path.as_ref())
+    }
+
+    fn load_target(&self) -> LoadTarget<'_> {
+        let (epoch, tx_id) = self.get_transaction_context();
+        LoadTarget {
+            store: &self.store,
+            epoch,
+            tx_id,
+            constraints: Some(Arc::clone(&self.catalog) as Arc<dyn ConstraintChecker>),
+        }
+    }
+
     /// Creates an edge between two nodes.
     ///
     /// This is a low-level API for testing and direct manipulation.
@@ -631,6 +1490,216 @@ impl Session {
     }
 }
 
+/// An open write transaction on a [`Session`].
+///
+/// Returned by [`Session::begin`]. Queries run through it see the graph as
+/// other transactions had committed it when it began, plus its own changes.
+/// Those changes, including property and label updates, stay invisible to
+/// other sessions' queries until [`commit`](Self::commit); dropping the
+/// transaction without committing discards them.
+pub struct Transaction<'a> {
+    /// The session the transaction runs on.
+    session: &'a mut Session,
+}
+
+impl Transaction<'_> {
+    /// Returns the ID of the transaction.
+    #[must_use]
+    pub fn tx_id(&self) -> Option<TxId> {
+        self.session.current_tx
+    }
+
+    /// Executes a GQL query inside the transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails to parse or execute.
+    #[cfg(feature = "gql")]
+    pub fn execute(&self, query: &str) -> Result<QueryResult> {
+        self.session.execute(query)
+    }
+
+    /// Executes a Cypher query inside the transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails to parse or execute.
+    #[cfg(feature = "cypher")]
+    pub fn execute_cypher(&self, query: &str) -> Result<QueryResult> {
+        self.session.execute_cypher(query)
+    }
+
+    /// Executes a Gremlin query inside the transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails to parse or execute.
+    #[cfg(feature = "gremlin")]
+    pub fn execute_gremlin(&self, query: &str) -> Result<QueryResult> {
+        self.session.execute_gremlin(query)
+    }
+
+    /// Executes a GraphQL query inside the transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails to parse or execute.
+    #[cfg(feature = "graphql")]
+    pub fn execute_graphql(&self, query: &str) -> Result<QueryResult> {
+        self.session.execute_graphql(query)
+    }
+
+    /// Executes a SPARQL query inside the transaction.
+    ///
+    /// Updates to the RDF store are applied when the transaction commits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails to parse or execute.
+    #[cfg(all(feature = "sparql", feature = "rdf"))]
+    pub fn execute_sparql(&self, query: &str) -> Result<QueryResult> {
+        self.session.execute_sparql(query)
+    }
+
+    /// Commits the transaction, making its changes visible to others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction conflicts with one that committed
+    /// first; its changes are discarded in that case.
+    pub fn commit(self) -> Result<()> {
+        self.session.commit()
+    }
+
+    /// Rolls the transaction back, discarding all of its changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction is no longer active.
+    pub fn rollback(self) -> Result<()> {
+        self.session.rollback()
+    }
+
+    /// Marks a point to roll back to with [`rollback_to`](Self::rollback_to).
+    ///
+    /// See [`Session::savepoint`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction is no longer active.
+    pub fn savepoint(&mut self, name: &str) -> Result<()> {
+        self.session.savepoint(name)
+    }
+
+    /// Undoes the changes made since the savepoint `name`; the transaction
+    /// stays open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no savepoint called `name`, including
+    /// one that was released or rolled past.
+    pub fn rollback_to(&mut self, name: &str) -> Result<()> {
+        self.session.rollback_to_savepoint(name)
+    }
+
+    /// Forgets the savepoint `name` and those set after it, keeping their
+    /// changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no savepoint called `name`.
+    pub fn release_savepoint(&mut self, name: &str) -> Result<()> {
+        self.session.release_savepoint(name)
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if self.session.in_transaction() {
+            let _ = self.session.rollback();
+        }
+    }
+}
+
+/// A read-only view of the database pinned to a single epoch.
+///
+/// Returned by [`Session::begin_read_only`]. Nodes and edges, with their
+/// labels and properties, are read as of the pinned epoch, leaving out
+/// anything written by transactions still open then, so a sequence of
+/// queries sees one consistent state of the graph.
+pub struct ReadSnapshot<'a> {
+    /// The session the snapshot was taken from.
+    session: &'a Session,
+    /// Read-only transaction holding the snapshot.
+    tx_id: TxId,
+    /// Epoch the snapshot is pinned to.
+    epoch: EpochId,
+}
+
+impl ReadSnapshot<'_> {
+    /// Returns the epoch this snapshot reads at.
+    #[must_use]
+    pub fn epoch(&self) -> EpochId {
+        self.epoch
+    }
+
+    /// Returns the ID of the read-only transaction holding the snapshot.
+    #[must_use]
+    pub fn tx_id(&self) -> TxId {
+        self.tx_id
+    }
+
+    /// Executes a GQL query against the snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails to parse or execute, or if it
+    /// tries to modify the graph.
+    #[cfg(feature = "gql")]
+    pub fn execute(&self, query: &str) -> Result<QueryResult> {
+        use crate::query::Executor;
+
+        let mut physical_plan =
+            self.session
+                .plan_gql_at(query, self.epoch, Some(self.tx_id), true)?;
+
+        let executor = Executor::with_columns(physical_plan.columns.clone());
+        executor.execute(physical_plan.operator.as_mut())
+    }
+}
+
+impl Drop for ReadSnapshot<'_> {
+    fn drop(&mut self) {
+        // Nothing was written, so releasing the snapshot is an abort
+        let _ = self.session.tx_manager.abort(self.tx_id);
+        self.session.sync_oldest_reader();
+    }
+}
+
+/// Turns per-key column statistics into property descriptions sorted by
+/// name, flagged by `constraints` with whether each key is unique and
+/// required.
+fn property_infos(
+    columns: &HashMap<String, ColumnStatistics>,
+    constraints: impl Fn(&str) -> (bool, bool),
+) -> Vec<PropertyInfo> {
+    let mut properties: Vec<PropertyInfo> = columns
+        .iter()
+        .map(|(name, column)| {
+            let (unique, required) = constraints(name);
+            PropertyInfo {
+                name: name.clone(),
+                data_type: column.data_type.clone(),
+                count: (column.total_count - column.null_count) as usize,
+                unique,
+                required,
+            }
+        })
+        .collect();
+    properties.sort_by(|a, b| a.name.cmp(&b.name));
+    properties
+}
+
 #[cfg(test)]
 mod tests {
     use crate::database::GrafeoDB;
@@ -835,6 +1904,41 @@ mod tests {
             assert_eq!(result.columns[0], "n");
         }
 
+        #[test]
+        fn test_gql_results_keep_node_and_edge_column_types() {
+            use grafeo_common::types::{LogicalType, Value};
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+            let alice =
+                session.create_node_with_props(&["Person"], [("name", Value::from("Alice"))]);
+            let bob = session.create_node_with_props(&["Person"], [("name", Value::from("Bob"))]);
+            session.create_edge(alice, bob, "KNOWS");
+
+            // Through a projection, a full sort, and a top-N
+            for query in [
+                "MATCH (a:Person)-[k:KNOWS]->(b:Person) RETURN a, k, b.name",
+                "MATCH (a:Person)-[k:KNOWS]->(b:Person) RETURN a, k, b.name ORDER BY b.name",
+                "MATCH (a:Person)-[k:KNOWS]->(b:Person) RETURN a, k, b.name ORDER BY b.name LIMIT 1",
+            ] {
+                let result = session.execute(query).unwrap();
+                assert_eq!(result.row_count(), 1, "{query}");
+                assert_eq!(
+                    result.column_types[..2],
+                    [LogicalType::Node, LogicalType::Edge],
+                    "{query}"
+                );
+                assert_eq!(result.rows[0][2], Value::from("Bob"), "{query}");
+            }
+
+            // Plain integers stay untyped
+            let result = session
+                .execute("MATCH (a:Person) RETURN a, 7 AS seven ORDER BY a.name")
+                .unwrap();
+            assert_eq!(result.column_types[0], LogicalType::Node);
+            assert_ne!(result.column_types[1], LogicalType::Node);
+        }
+
         #[test]
         fn test_gql_empty_result() {
             let db = GrafeoDB::new_in_memory();
@@ -926,55 +2030,938 @@ mod tests {
         }
 
         #[test]
-        fn test_gql_where_clause_property_filter() {
+        fn test_gql_execute_stream_stops_early() {
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+
+            // A hub with many outgoing edges gives a large expand
+            let hub = session.create_node(&["Person"]);
+            for _ in 0..10_000 {
+                let other = session.create_node(&["Person"]);
+                session.create_edge(hub, other, "KNOWS");
+            }
+
+            let stream = session
+                .execute_stream("MATCH (a:Person)-[:KNOWS]->(b:Person) RETURN a, b")
+                .unwrap();
+            assert_eq!(stream.columns(), ["a".to_string(), "b".to_string()]);
+
+            let rows: Vec<_> = stream.take(5).collect::<Result<_, _>>().unwrap();
+            assert_eq!(rows.len(), 5);
+            assert!(rows.iter().all(|row| row.len() == 2));
+
+            // Draining the stream matches the materialized result
+            let streamed = session
+                .execute_stream("MATCH (a:Person)-[:KNOWS]->(b:Person) RETURN a, b")
+                .unwrap()
+                .count();
+            let materialized = session
+                .execute("MATCH (a:Person)-[:KNOWS]->(b:Person) RETURN a, b")
+                .unwrap()
+                .row_count();
+            assert_eq!(streamed, materialized);
+            assert_eq!(streamed, 10_000);
+        }
+
+        #[test]
+        fn test_gql_execute_stream_parse_error() {
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+
+            assert!(session.execute_stream("MATCH (n RETURN n").is_err());
+        }
+
+        #[test]
+        fn test_gql_execute_with_params() {
+            use crate::query::QueryParams;
             use grafeo_common::types::Value;
 
             let db = GrafeoDB::new_in_memory();
             let session = db.session();
 
-            // Create people with ages
-            session.create_node_with_props(&["Person"], [("age", Value::Int64(25))]);
-            session.create_node_with_props(&["Person"], [("age", Value::Int64(35))]);
-            session.create_node_with_props(&["Person"], [("age", Value::Int64(45))]);
+            for (name, age) in [("Alice", 30), ("Bob", 25), ("Carol", 41)] {
+                session.create_node_with_props(
+                    &["Person"],
+                    [
+                        ("name", Value::String(name.into())),
+                        ("age", Value::Int64(age)),
+                    ],
+                );
+            }
+
+            // String parameter (quotes in the value don't affect the query)
+            let mut params = QueryParams::new();
+            params.insert("name".to_string(), Value::String("Bob".into()));
+            let result = session
+                .execute_with_params("MATCH (n:Person) WHERE n.name = $name RETURN n.age", params)
+                .unwrap();
+            assert_eq!(result.rows, vec![vec![Value::Int64(25)]]);
 
-            // Query with WHERE clause: age > 30
+            let mut params = QueryParams::new();
+            params.insert("name".to_string(), Value::String("Bob' OR 1=1".into()));
             let result = session
-                .execute("MATCH (n:Person) WHERE n.age > 30 RETURN n")
+                .execute_with_params("MATCH (n:Person) WHERE n.name = $name RETURN n", params)
+                .unwrap();
+            assert_eq!(result.row_count(), 0);
+
+            // Int parameter
+            let mut params = QueryParams::new();
+            params.insert("min_age".to_string(), Value::Int64(28));
+            let result = session
+                .execute_with_params("MATCH (n:Person) WHERE n.age > $min_age RETURN n", params)
                 .unwrap();
+            assert_eq!(result.row_count(), 2);
 
-            // Should return 2 people (ages 35 and 45)
+            // List parameter
+            let mut params = QueryParams::new();
+            params.insert(
+                "names".to_string(),
+                Value::List(
+                    vec![Value::String("Alice".into()), Value::String("Carol".into())].into(),
+                ),
+            );
+            let result = session
+                .execute_with_params("MATCH (n:Person) WHERE n.name IN $names RETURN n", params)
+                .unwrap();
             assert_eq!(result.row_count(), 2);
         }
 
         #[test]
-        fn test_gql_where_clause_equality() {
-            use grafeo_common::types::Value;
+        fn test_gql_execute_with_missing_param() {
+            use crate::query::QueryParams;
+            use grafeo_common::utils::error::{Error, QueryErrorKind};
 
             let db = GrafeoDB::new_in_memory();
             let session = db.session();
+            session.create_node(&["Person"]);
 
-            // Create people with names
-            session.create_node_with_props(&["Person"], [("name", Value::String("Alice".into()))]);
-            session.create_node_with_props(&["Person"], [("name", Value::String("Bob".into()))]);
-            session.create_node_with_props(&["Person"], [("name", Value::String("Alice".into()))]);
+            let err = session
+                .execute_with_params(
+                    "MATCH (n:Person) WHERE n.age > $min_age RETURN n",
+                    QueryParams::new(),
+                )
+                .unwrap_err();
+            match err {
+                Error::Query(e) => {
+                    assert_eq!(e.kind, QueryErrorKind::Semantic);
+                    assert!(e.message.contains("$min_age"));
+                }
+                other => panic!("Expected a query error, got: {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_gql_create_composite_index() {
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
 
-            // Query with WHERE clause: name = "Alice"
             let result = session
-                .execute("MATCH (n:Person) WHERE n.name = \"Alice\" RETURN n")
+                .execute("CREATE INDEX ON :Person(lastName, firstName)")
+                .unwrap();
+            assert_eq!(result.row_count(), 0);
+            // Re-running the statement doesn't duplicate the index
+            db.session()
+                .execute("CREATE INDEX ON :Person(lastName, firstName)")
                 .unwrap();
 
-            // Should return 2 people named Alice
-            assert_eq!(result.row_count(), 2);
+            let catalog = db.catalog();
+            assert_eq!(catalog.index_count(), 1);
+            let person = catalog.get_label_id("Person").unwrap();
+            let last = catalog.get_property_key_id("lastName").unwrap();
+            let first = catalog.get_property_key_id("firstName").unwrap();
+            let idx = catalog.find_index(person, &[last, first]).unwrap();
+            assert_eq!(
+                catalog.get_index(idx).unwrap().property_keys,
+                vec![last, first]
+            );
+            assert!(
+                db.store()
+                    .has_composite_index("Person", &["lastName", "firstName"])
+            );
         }
 
         #[test]
-        fn test_gql_return_property_access() {
+        fn test_gql_text_index_answers_starts_with() {
             use grafeo_common::types::Value;
 
             let db = GrafeoDB::new_in_memory();
             let session = db.session();
+            session.execute("INSERT (:Person {name: 'Alix'})").unwrap();
+            db.catalog()
+                .create_text_index(db.store(), "Person", "name", true);
+            session.execute("INSERT (:Person {name: 'Alma'})").unwrap();
+            session.execute("INSERT (:Person {name: 'Gus'})").unwrap();
 
-            // Create people with names and ages
+            let result = session
+                .execute(
+                    "MATCH (p:Person) WHERE p.name STARTS WITH 'Al' RETURN p.name ORDER BY p.name",
+                )
+                .unwrap();
+            assert_eq!(
+                result.rows,
+                vec![vec![Value::from("Alix")], vec![Value::from("Alma")]]
+            );
+            let result = session
+                .execute("MATCH (p:Person) WHERE p.name STARTS WITH '' RETURN p.name")
+                .unwrap();
+            assert_eq!(result.row_count(), 3);
+        }
+
+        #[test]
+        fn test_gql_composite_index_answers_prefix_filters() {
+            use grafeo_common::types::Value;
+
+            let db = GrafeoDB::new_in_memory();
+            let mut session = db.session();
+            session
+                .execute("INSERT (:Person {lastName: 'Lovelace', firstName: 'Ada'})")
+                .unwrap();
+            session
+                .execute("INSERT (:Person {lastName: 'Turing', firstName: 'Alan'})")
+                .unwrap();
+            session
+                .execute("CREATE INDEX ON :Person(lastName, firstName)")
+                .unwrap();
+            session
+                .execute("INSERT (:Person {lastName: 'Lovelace', firstName: 'Byron'})")
+                .unwrap();
+            let first_names =
+                |session: &crate::Session, query: &str| session.execute(query).unwrap().rows;
+
+            // Nodes indexed on creation and written later are both found
+            assert_eq!(
+                first_names(
+                    &session,
+                    "MATCH (p:Person) WHERE p.lastName = 'Lovelace' RETURN p.firstName ORDER BY p.firstName"
+                ),
+                vec![vec![Value::from("Ada")], vec![Value::from("Byron")]]
+            );
+            assert_eq!(
+                first_names(
+                    &session,
+                    "MATCH (p:Person) WHERE p.lastName = 'Lovelace' AND p.firstName = 'Byron' RETURN p.firstName"
+                ),
+                vec![vec![Value::from("Byron")]]
+            );
+
+            // A value changed in a rolled-back transaction is only seen inside it
+            session.begin_tx().unwrap();
+            session
+                .execute("MATCH (p:Person) WHERE p.firstName = 'Alan' SET p.lastName = 'Lovelace'")
+                .unwrap();
+            assert_eq!(
+                first_names(
+                    &session,
+                    "MATCH (p:Person) WHERE p.lastName = 'Lovelace' RETURN p.firstName ORDER BY p.firstName"
+                )
+                .len(),
+                3
+            );
+            session.rollback().unwrap();
+            assert_eq!(
+                first_names(
+                    &session,
+                    "MATCH (p:Person) WHERE p.lastName = 'Lovelace' RETURN p.firstName ORDER BY p.firstName"
+                )
+                .len(),
+                2
+            );
+        }
+
+        #[test]
+        fn test_gql_unique_constraint_rejects_duplicates() {
+            use grafeo_common::types::Value;
+            use grafeo_common::utils::error::Error;
+
+            let db = GrafeoDB::new_in_memory();
+            let mut session = db.session();
+            session
+                .execute("INSERT (:User {email: 'alix@example.com'})")
+                .unwrap();
+            session
+                .execute("CREATE CONSTRAINT ON :User(email) IS UNIQUE")
+                .unwrap();
+            let catalog = db.catalog();
+            let user = catalog.get_label_id("User").unwrap();
+            let email = catalog.get_property_key_id("email").unwrap();
+            assert!(catalog.is_property_unique(user, email));
+
+            let result = session.execute("INSERT (:User {email: 'alix@example.com'})");
+            assert!(
+                matches!(&result, Err(Error::ConstraintViolation(msg)) if msg.contains("alix@example.com")),
+                "got {result:?}"
+            );
+            assert_eq!(db.node_count(), 1);
+
+            // Other labels and other values are unaffected
+            session
+                .execute("INSERT (:Admin {email: 'alix@example.com'})")
+                .unwrap();
+            session
+                .execute("INSERT (:User {email: 'gus@example.com'})")
+                .unwrap();
+            let result = session.execute(
+                "MATCH (u:User) WHERE u.email = 'gus@example.com' SET u.email = 'alix@example.com'",
+            );
+            assert!(matches!(result, Err(Error::ConstraintViolation(_))));
+
+            // Batch inserts are checked too
+            let row = || {
+                std::collections::HashMap::from([("email".into(), Value::from("alix@example.com"))])
+            };
+            assert!(matches!(
+                session.insert_nodes("User", [row()]),
+                Err(Error::ConstraintViolation(_))
+            ));
+
+            // A value taken by a rolled-back insert is free again
+            session.begin_tx().unwrap();
+            session
+                .execute("INSERT (:User {email: 'vincent@example.com'})")
+                .unwrap();
+            session.rollback().unwrap();
+            session
+                .execute("INSERT (:User {email: 'vincent@example.com'})")
+                .unwrap();
+
+            // So is a value whose node was deleted
+            session
+                .execute("MATCH (u:User) WHERE u.email = 'alix@example.com' DELETE u")
+                .unwrap();
+            session.insert_nodes("User", [row()]).unwrap();
+
+            let result = session
+                .execute("MATCH (u:User) RETURN u.email ORDER BY u.email")
+                .unwrap();
+            assert_eq!(
+                result.rows,
+                vec![
+                    vec![Value::from("alix@example.com")],
+                    vec![Value::from("gus@example.com")],
+                    vec![Value::from("vincent@example.com")],
+                ]
+            );
+        }
+
+        #[test]
+        fn test_gql_unique_constraint_on_duplicate_data_fails() {
+            use grafeo_common::utils::error::Error;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+            session
+                .execute("INSERT (:User {email: 'alix@example.com'})")
+                .unwrap();
+            session
+                .execute("INSERT (:User {email: 'alix@example.com'})")
+                .unwrap();
+
+            let result = session.execute("CREATE CONSTRAINT ON :User(email) IS UNIQUE");
+            assert!(
+                matches!(&result, Err(Error::ConstraintViolation(msg)) if msg.contains("\"alix@example.com\"")),
+                "got {result:?}"
+            );
+
+            // The constraint wasn't added, so duplicates are still accepted
+            session
+                .execute("INSERT (:User {email: 'alix@example.com'})")
+                .unwrap();
+            assert_eq!(db.node_count(), 3);
+        }
+
+        #[test]
+        fn test_gql_unique_constraint_checks_labels_and_merge() {
+            use grafeo_common::types::Value;
+            use grafeo_common::utils::error::Error;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+            session
+                .execute("CREATE CONSTRAINT ON :User(email) IS UNIQUE")
+                .unwrap();
+            session
+                .execute("INSERT (:User {email: 'alix@example.com'})")
+                .unwrap();
+            session
+                .execute("INSERT (:Guest {email: 'alix@example.com'})")
+                .unwrap();
+            session
+                .execute("INSERT (:Guest {email: 'gus@example.com'})")
+                .unwrap();
+            let users = |session: &crate::Session| {
+                session
+                    .execute("MATCH (u:User) RETURN u.email ORDER BY u.email")
+                    .unwrap()
+                    .rows
+            };
+
+            // A node can't take the label while its value is held
+            let result = session.execute("MATCH (g:Guest) SET g:User");
+            assert!(
+                matches!(&result, Err(Error::ConstraintViolation(msg)) if msg.contains("alix@example.com")),
+                "got {result:?}"
+            );
+            session
+                .execute("MATCH (g:Guest) WHERE g.email = 'gus@example.com' SET g:User")
+                .unwrap();
+            assert_eq!(
+                users(&session),
+                vec![
+                    vec![Value::from("alix@example.com")],
+                    vec![Value::from("gus@example.com")],
+                ]
+            );
+
+            // MERGE matches the holder rather than creating a duplicate
+            session
+                .execute("MERGE (u:User {email: 'alix@example.com'}) RETURN u")
+                .unwrap();
+            assert_eq!(db.node_count(), 3);
+
+            // But its ON CREATE and ON MATCH values are checked
+            let result = session.execute(
+                "MERGE (u:User {email: 'mia@example.com'}) ON CREATE SET u.email = 'alix@example.com' RETURN u",
+            );
+            assert!(matches!(result, Err(Error::ConstraintViolation(_))));
+            assert_eq!(db.node_count(), 3);
+            let result = session.execute(
+                "MERGE (u:User {email: 'gus@example.com'}) ON MATCH SET u.email = 'alix@example.com' RETURN u",
+            );
+            assert!(matches!(result, Err(Error::ConstraintViolation(_))));
+
+            // A failed check holds no value
+            session
+                .execute("MERGE (u:User {email: 'mia@example.com'}) RETURN u")
+                .unwrap();
+            assert_eq!(
+                users(&session),
+                vec![
+                    vec![Value::from("alix@example.com")],
+                    vec![Value::from("gus@example.com")],
+                    vec![Value::from("mia@example.com")],
+                ]
+            );
+        }
+
+        #[test]
+        fn test_gql_not_null_constraint() {
+            use grafeo_common::types::Value;
+            use grafeo_common::utils::error::Error;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+            session
+                .execute("CREATE CONSTRAINT ON :Person(name) IS NOT NULL")
+                .unwrap();
+
+            // A valid create
+            session
+                .execute("INSERT (:Person {name: 'Alix', age: 30})")
+                .unwrap();
+
+            // Creating without the property, or with it null, fails
+            for query in [
+                "INSERT (:Person {age: 25})",
+                "INSERT (:Person {name: null, age: 25})",
+            ] {
+                let result = session.execute(query);
+                assert!(
+                    matches!(&result, Err(Error::ConstraintViolation(msg)) if msg.contains(":Person(name)")),
+                    "{query}: got {result:?}"
+                );
+            }
+            assert!(matches!(
+                session.insert_nodes("Person", [std::collections::HashMap::new()]),
+                Err(Error::ConstraintViolation(_))
+            ));
+            assert_eq!(db.node_count(), 1);
+
+            // Setting it to null fails and leaves the value
+            let result = session.execute("MATCH (p:Person) SET p.name = null");
+            assert!(matches!(result, Err(Error::ConstraintViolation(_))));
+            let result = session.execute("MATCH (p:Person) RETURN p.name").unwrap();
+            assert_eq!(result.rows, vec![vec![Value::from("Alix")]]);
+
+            // Other labels don't need the property
+            session.execute("INSERT (:Animal {age: 4})").unwrap();
+        }
+
+        #[test]
+        fn test_gql_not_null_constraint_checks_labels_and_merge() {
+            use grafeo_common::types::Value;
+            use grafeo_common::utils::error::Error;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+            session
+                .execute("CREATE CONSTRAINT ON :Person(name) IS NOT NULL")
+                .unwrap();
+            session.execute("INSERT (:Animal {age: 4})").unwrap();
+            session
+                .execute("INSERT (:Animal {name: 'Rex', age: 6})")
+                .unwrap();
+
+            // A node can't take the label without the property
+            let result = session.execute("MATCH (a:Animal) SET a:Person");
+            assert!(
+                matches!(&result, Err(Error::ConstraintViolation(msg)) if msg.contains(":Person(name)")),
+                "got {result:?}"
+            );
+            session
+                .execute("MATCH (a:Animal) WHERE a.name = 'Rex' SET a:Person")
+                .unwrap();
+
+            // MERGE can't create one without it either
+            let result = session.execute("MERGE (p:Person {age: 30}) RETURN p");
+            assert!(matches!(result, Err(Error::ConstraintViolation(_))));
+            let result =
+                session.execute("MERGE (p:Person {age: 30}) ON CREATE SET p.name = null RETURN p");
+            assert!(matches!(result, Err(Error::ConstraintViolation(_))));
+            assert_eq!(db.node_count(), 2);
+
+            session
+                .execute("MERGE (p:Person {age: 30}) ON CREATE SET p.name = 'Alix' RETURN p")
+                .unwrap();
+            let result = session
+                .execute("MATCH (p:Person) RETURN p.name ORDER BY p.name")
+                .unwrap();
+            assert_eq!(
+                result.rows,
+                vec![vec![Value::from("Alix")], vec![Value::from("Rex")]]
+            );
+        }
+
+        #[test]
+        fn test_gql_not_null_constraint_on_incomplete_data_fails() {
+            use grafeo_common::utils::error::Error;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+            session.execute("INSERT (:Person {name: 'Alix'})").unwrap();
+            let id = session.create_node(&["Person"]);
+
+            let result = session.execute("CREATE CONSTRAINT ON :Person(name) IS NOT NULL");
+            assert!(
+                matches!(&result, Err(Error::ConstraintViolation(msg)) if msg.contains(&format!("node {id}"))),
+                "got {result:?}"
+            );
+            session.execute("INSERT (:Person {age: 25})").unwrap();
+        }
+
+        #[test]
+        fn test_gql_transaction_commit_applies_all() {
+            use grafeo_common::types::Value;
+
+            let db = GrafeoDB::new_in_memory();
+            let mut session = db.session();
+            session.execute("INSERT (:Person {name: 'Alix'})").unwrap();
+
+            let tx = session.begin().unwrap();
+            tx.execute("INSERT (:Person {name: 'Gus'})").unwrap();
+            tx.execute("MATCH (p:Person) SET p.age = 30").unwrap();
+            tx.commit().unwrap();
+
+            let result = db
+                .session()
+                .execute("MATCH (p:Person) RETURN p.age")
+                .unwrap();
+            assert_eq!(result.row_count(), 2);
+            assert!(result.rows.iter().all(|row| row[0] == Value::Int64(30)));
+        }
+
+        #[test]
+        fn test_gql_transaction_rollback_leaves_store_unchanged() {
+            use grafeo_common::types::Value;
+
+            let db = GrafeoDB::new_in_memory();
+            let mut session = db.session();
+            session
+                .execute("INSERT (:Person {name: 'Alix', age: 30})")
+                .unwrap();
+            session
+                .execute("INSERT (:Person {name: 'Gus', age: 25})")
+                .unwrap();
+
+            let tx = session.begin().unwrap();
+            tx.execute("INSERT (:Person {name: 'Vincent'})").unwrap();
+            tx.execute("MATCH (p:Person) SET p.age = 99").unwrap();
+            tx.execute("MATCH (p:Person) SET p:Archived").unwrap();
+            tx.execute("MATCH (p:Person {name: 'Gus'}) DELETE p")
+                .unwrap();
+            tx.rollback().unwrap();
+
+            let result = session
+                .execute("MATCH (p:Person) RETURN p.name, p.age")
+                .unwrap();
+            let mut rows = result.rows.clone();
+            rows.sort_by_key(|row| format!("{:?}", row[0]));
+            assert_eq!(
+                rows,
+                vec![
+                    vec![Value::String("Alix".into()), Value::Int64(30)],
+                    vec![Value::String("Gus".into()), Value::Int64(25)],
+                ]
+            );
+            let archived = session.execute("MATCH (p:Archived) RETURN p").unwrap();
+            assert_eq!(archived.row_count(), 0);
+        }
+
+        #[test]
+        fn test_gql_transaction_writes_hidden_until_commit() {
+            use grafeo_common::types::Value;
+
+            let db = GrafeoDB::new_in_memory();
+            let mut session = db.session();
+            session
+                .execute("INSERT (:Person {name: 'Alix', age: 30})")
+                .unwrap();
+            let other = db.session();
+            let read = |query: &str| other.execute(query).unwrap().rows;
+
+            let tx = session.begin().unwrap();
+            tx.execute("INSERT (:Person {name: 'Gus', age: 25})")
+                .unwrap();
+            tx.execute("MATCH (p:Person {name: 'Alix'}) SET p.age = 31, p:Admin")
+                .unwrap();
+            assert_eq!(
+                read("MATCH (p:Person) RETURN p.name, p.age"),
+                vec![vec![Value::String("Alix".into()), Value::Int64(30)]]
+            );
+            assert!(read("MATCH (p:Admin) RETURN p").is_empty());
+            assert_eq!(
+                tx.execute("MATCH (p:Admin) RETURN p.age").unwrap().rows,
+                vec![vec![Value::Int64(31)]]
+            );
+            tx.commit().unwrap();
+
+            assert_eq!(
+                read("MATCH (p:Admin) RETURN p.age"),
+                vec![vec![Value::Int64(31)]]
+            );
+            assert_eq!(read("MATCH (p:Person) RETURN p").len(), 2);
+        }
+
+        #[test]
+        fn test_gql_transaction_drop_on_panic_rolls_back() {
+            use grafeo_common::types::Value;
+
+            let db = GrafeoDB::new_in_memory();
+            let mut session = db.session();
+            session.execute("INSERT (:Person {name: 'Alix'})").unwrap();
+
+            let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                let tx = session.begin().unwrap();
+                tx.execute("INSERT (:Person {name: 'Gus'})").unwrap();
+                tx.execute("MATCH (p:Person) SET p.age = 1").unwrap();
+                panic!("failure mid-transaction");
+            }));
+            assert!(outcome.is_err());
+
+            assert!(!session.in_transaction());
+            let result = session
+                .execute("MATCH (p:Person) RETURN p.name, p.age")
+                .unwrap();
+            assert_eq!(
+                result.rows,
+                vec![vec![Value::String("Alix".into()), Value::Null]]
+            );
+            assert_eq!(db.store().node_count(), 1);
+        }
+
+        #[test]
+        fn test_gql_plan_cache_shared_and_cleared() {
+            use crate::query::QueryParams;
+            use grafeo_common::types::Value;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+            session.create_node_with_props(&["Person"], [("age", Value::Int64(35))]);
+
+            // Spacing and keyword case variants hit the same entry
+            session.execute("MATCH (n:Person) RETURN n").unwrap();
+            session.execute("match (n:Person)\n  return n").unwrap();
+
+            // Different parameter values share the parameterized plan
+            for min_age in [30, 40] {
+                let mut params = QueryParams::new();
+                params.insert("min_age".to_string(), Value::Int64(min_age));
+                session
+                    .execute_with_params("MATCH (n:Person) WHERE n.age > $min_age RETURN n", params)
+                    .unwrap();
+            }
+
+            // Other sessions of the database see the same cache
+            let other = db.session();
+            let stats = other.plan_cache_stats();
+            assert_eq!(stats.parsed_size, 2);
+            assert_eq!(stats.parsed_misses, 2);
+            assert_eq!(stats.parsed_hits, 2);
+
+            other.clear_plan_cache();
+            assert_eq!(session.plan_cache_stats().parsed_size, 0);
+        }
+
+        #[test]
+        fn test_gql_explain_join_and_aggregate() {
+            use grafeo_common::types::Value;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+            let mut people = Vec::new();
+            for age in 0..10 {
+                people.push(
+                    session.create_node_with_props(&["Person"], [("age", Value::Int64(age))]),
+                );
+            }
+            let company = session.create_node(&["Company"]);
+            for person in &people {
+                session.create_edge(*person, company, "WORKS_AT");
+            }
+            session.analyze();
+
+            let plan = session
+                .explain(
+                    "MATCH (p:Person)-[:WORKS_AT]->(c:Company) \
+                     OPTIONAL MATCH (p)-[:KNOWS]->(f:Person) \
+                     RETURN c, count(f)",
+                )
+                .unwrap();
+            let lines: Vec<&str> = plan.lines().collect();
+            assert!(lines[0].starts_with("HashAggregate groups=1 aggregates=[count]"));
+            assert!(lines[1].starts_with("  HashJoin type=left"));
+            assert!(lines[2].starts_with("    Expand p-[:WORKS_AT]->c"));
+            assert!(lines[3].starts_with("      Scan p:Person index=label (rows=10,"));
+            assert!(lines[4].starts_with("    Expand p-[:KNOWS]->f"));
+            assert_eq!(lines.len(), 6);
+
+            // An index only the catalog knows about can't be scanned, so the
+            // plan keeps the label scan that actually runs
+            let label = session.catalog.get_or_create_label("Person");
+            let rank = session.catalog.get_or_create_property_key("rank");
+            session
+                .catalog
+                .create_index(label, rank, crate::catalog::IndexType::Hash);
+            let plan = session
+                .explain("MATCH (p:Person) WHERE p.rank = 3 RETURN p")
+                .unwrap();
+            assert!(plan.contains("Scan p:Person index=label"), "{plan}");
+
+            // An equality filter on an indexed property moves the scan onto the index
+            session.execute("CREATE INDEX ON :Person(age)").unwrap();
+            let plan = session
+                .explain("MATCH (p:Person) WHERE p.age = 3 RETURN p")
+                .unwrap();
+            assert!(plan.contains("Scan p:Person index=btree#1"), "{plan}");
+
+            assert!(session.explain("MATCH (p:Person RETURN p").is_err());
+        }
+
+        #[test]
+        fn test_gql_order_by_limit_runs_top_n() {
+            use grafeo_common::types::Value;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+            // Ages out of insertion order, so the first rows scanned aren't the top
+            for age in [5, 42, 17, 8, 99, 23, 61, 3, 77, 30] {
+                session.create_node_with_props(&["Person"], [("age", Value::Int64(age))]);
+            }
+
+            let query = "MATCH (p:Person) RETURN p.age ORDER BY p.age DESC LIMIT 3";
+            let result = session.execute(query).unwrap();
+            assert_eq!(
+                result.rows,
+                vec![
+                    vec![Value::Int64(99)],
+                    vec![Value::Int64(77)],
+                    vec![Value::Int64(61)],
+                ]
+            );
+
+            let plan = session.explain_analyze(query).unwrap();
+            let lines: Vec<&str> = plan.lines().collect();
+            assert!(lines[0].starts_with("Project columns=1"), "{plan}");
+            assert!(
+                lines[1].starts_with("  TopN keys=1 count=3")
+                    && lines[1].contains("actual_rows=3,"),
+                "{plan}"
+            );
+            assert!(lines[2].contains("actual_rows=10,"), "{plan}");
+
+            // SKIP counts sorted rows too
+            let result = session
+                .execute("MATCH (p:Person) RETURN p.age ORDER BY p.age SKIP 2 LIMIT 2")
+                .unwrap();
+            assert_eq!(
+                result.rows,
+                vec![vec![Value::Int64(8)], vec![Value::Int64(17)]]
+            );
+        }
+
+        #[test]
+        fn test_gql_explain_analyze_filter_scan() {
+            use grafeo_common::types::Value;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+            for age in 0..10 {
+                session.create_node_with_props(&["Person"], [("age", Value::Int64(age))]);
+            }
+            session.create_node(&["Company"]);
+
+            let plan = session
+                .explain_analyze("MATCH (p:Person) WHERE p.age < 4 RETURN p")
+                .unwrap();
+            // (operator, actual_rows, time in ms) for each line
+            let actuals: Vec<(&str, u64, f64)> = plan
+                .lines()
+                .map(|line| {
+                    let operator = line.split_whitespace().next().unwrap();
+                    let rows = line.split("actual_rows=").nth(1).unwrap();
+                    let rows = rows[..rows.find(',').unwrap()].parse().unwrap();
+                    let time = line.split("time=").nth(1).unwrap();
+                    let time = time[..time.find("ms").unwrap()].parse().unwrap();
+                    (operator, rows, time)
+                })
+                .collect();
+            assert_eq!(actuals.len(), 3, "{plan}");
+            assert_eq!((actuals[0].0, actuals[0].1), ("Project", 4));
+            assert_eq!((actuals[1].0, actuals[1].1), ("Filter", 4));
+            assert_eq!((actuals[2].0, actuals[2].1), ("Scan", 10));
+            assert!(actuals.iter().all(|&(_, _, time)| time >= 0.0));
+
+            // A scan answered by an index is profiled like any other scan
+            session.execute("CREATE INDEX ON :Person(age)").unwrap();
+            let plan = session
+                .explain_analyze("MATCH (p:Person) WHERE p.age = 3 RETURN p")
+                .unwrap();
+            assert!(
+                plan.lines()
+                    .any(|line| line.trim_start().starts_with("Scan")
+                        && line.contains("index=btree#")
+                        && line.contains("actual_rows=1,")),
+                "{plan}"
+            );
+
+            // Rows below a LIMIT are only those the limit pulled
+            let plan = session
+                .explain_analyze("MATCH (p:Person) RETURN p LIMIT 2")
+                .unwrap();
+            assert!(plan.contains("Limit count=2 (rows="), "{plan}");
+            assert!(
+                plan.lines()
+                    .any(|line| line.trim_start().starts_with("Limit")
+                        && line.contains("actual_rows=2,"))
+            );
+        }
+
+        #[test]
+        fn test_gql_where_clause_property_filter() {
+            use grafeo_common::types::Value;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+
+            // Create people with ages
+            session.create_node_with_props(&["Person"], [("age", Value::Int64(25))]);
+            session.create_node_with_props(&["Person"], [("age", Value::Int64(35))]);
+            session.create_node_with_props(&["Person"], [("age", Value::Int64(45))]);
+
+            // Query with WHERE clause: age > 30
+            let result = session
+                .execute("MATCH (n:Person) WHERE n.age > 30 RETURN n")
+                .unwrap();
+
+            // Should return 2 people (ages 35 and 45)
+            assert_eq!(result.row_count(), 2);
+        }
+
+        #[test]
+        fn test_gql_where_clause_equality() {
+            use grafeo_common::types::Value;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+
+            // Create people with names
+            session.create_node_with_props(&["Person"], [("name", Value::String("Alice".into()))]);
+            session.create_node_with_props(&["Person"], [("name", Value::String("Bob".into()))]);
+            session.create_node_with_props(&["Person"], [("name", Value::String("Alice".into()))]);
+
+            // Query with WHERE clause: name = "Alice"
+            let result = session
+                .execute("MATCH (n:Person) WHERE n.name = \"Alice\" RETURN n")
+                .unwrap();
+
+            // Should return 2 people named Alice
+            assert_eq!(result.row_count(), 2);
+        }
+
+        #[test]
+        fn test_gql_where_clause_date_range() {
+            use grafeo_common::types::{Date, Timestamp, Value};
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+
+            for (y, m, d) in [(2019, 12, 31), (2020, 6, 15), (2021, 1, 1)] {
+                let created = Date::from_ymd(y, m, d).unwrap();
+                session.create_node_with_props(&["Post"], [("created", Value::Date(created))]);
+            }
+            let updated = Timestamp::parse("2020-06-15T12:00:00Z").unwrap();
+            session.create_node_with_props(&["Post"], [("created", Value::Timestamp(updated))]);
+
+            let result = session
+                .execute(
+                    "MATCH (p:Post) WHERE p.created > date('2020-01-01') \
+                     AND p.created < date('2021-01-01') RETURN p",
+                )
+                .unwrap();
+            // The 2020 date and the timestamp, which compares as a point in time
+            assert_eq!(result.row_count(), 2);
+
+            let result = session
+                .execute(
+                    "MATCH (p:Post) WHERE p.created >= datetime('2020-06-15T00:00:00Z') \
+                     - duration('P1D') AND p.created <= date('2020-06-15') RETURN p",
+                )
+                .unwrap();
+            assert_eq!(result.row_count(), 1);
+        }
+
+        #[test]
+        fn test_gql_rand_is_reproducible_with_seed() {
+            let run = |db: &GrafeoDB| {
+                let session = db.session();
+                session
+                    .execute("MATCH (n:Person) RETURN rand()")
+                    .unwrap()
+                    .rows
+                    .into_iter()
+                    .map(|row| row[0].as_float64().unwrap())
+                    .collect::<Vec<_>>()
+            };
+
+            let db = GrafeoDB::with_config(crate::Config::in_memory().with_random_seed(7)).unwrap();
+            db.create_node(&["Person"]);
+            db.create_node(&["Person"]);
+
+            let first = run(&db);
+            assert_eq!(first.len(), 2);
+            assert_ne!(first[0], first[1]);
+            assert!(first.iter().all(|x| (0.0..1.0).contains(x)));
+            // Each session starts from the seed
+            assert_eq!(run(&db), first);
+        }
+
+        #[test]
+        fn test_gql_return_property_access() {
+            use grafeo_common::types::Value;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+
+            // Create people with names and ages
             session.create_node_with_props(
                 &["Person"],
                 [
@@ -1030,12 +3017,335 @@ mod tests {
             // Second column should be the name
             assert_eq!(result.rows[0][1], Value::String("Alice".into()));
         }
+
+        /// Links every node to the next `fanout` nodes, so a three-hop
+        /// expand yields `nodes * fanout^3` rows.
+        fn dense_graph(db: &GrafeoDB, nodes: usize, fanout: usize) {
+            let ids: Vec<_> = (0..nodes).map(|_| db.create_node(&["Person"])).collect();
+            for (i, &src) in ids.iter().enumerate() {
+                for offset in 1..=fanout {
+                    db.create_edge(src, ids[(i + offset) % nodes], "KNOWS");
+                }
+            }
+        }
+
+        #[test]
+        fn test_gql_execute_with_timeout_cancels_large_expand() {
+            let db = GrafeoDB::new_in_memory();
+            dense_graph(&db, 200, 50);
+            let session = db.session();
+
+            let query =
+                "MATCH (a:Person)-[:KNOWS]->(b)-[:KNOWS]->(c)-[:KNOWS]->(d) RETURN count(d)";
+            let result = session.execute_with_timeout(query, std::time::Duration::from_millis(1));
+
+            assert!(
+                matches!(result, Err(grafeo_common::utils::error::Error::Timeout)),
+                "expected a timeout, got {result:?}"
+            );
+
+            // The session is still usable, and small queries fit in the limit
+            let result = session
+                .execute_with_timeout(
+                    "MATCH (n:Person) RETURN count(n)",
+                    std::time::Duration::from_secs(30),
+                )
+                .unwrap();
+            assert_eq!(result.rows[0][0], grafeo_common::types::Value::Int64(200));
+        }
+
+        #[test]
+        fn test_gql_timeout_removes_spill_files() {
+            let spill = tempfile::tempdir().unwrap();
+            let db = GrafeoDB::with_config(
+                crate::Config::in_memory()
+                    .with_memory_limit(4096)
+                    .with_spill_path(spill.path())
+                    .with_query_timeout(std::time::Duration::from_millis(500)),
+            )
+            .unwrap();
+            dense_graph(&db, 300, 8);
+            let session = db.session();
+            let spill_files = || std::fs::read_dir(spill.path()).map_or(0, Iterator::count);
+
+            // The optional side is far larger than the budget, so the join
+            // spills it, and the first chunk of its output comes out before
+            // every spilled partition has been joined
+            let mut rows = session
+                .execute_stream(
+                    "MATCH (p:Person)-[:KNOWS]->(r:Person) \
+                     OPTIONAL MATCH (p)-[:KNOWS]->(q:Person) RETURN p, r, q",
+                )
+                .unwrap();
+            assert!(rows.next().unwrap().is_ok());
+            assert!(spill_files() > 0, "the join didn't spill");
+
+            // Past the deadline the next pull fails, and dropping the
+            // cancelled plan removes what it spilled
+            std::thread::sleep(std::time::Duration::from_millis(600));
+            let failed = rows.find_map(Result::err);
+            assert!(
+                matches!(failed, Some(grafeo_common::utils::error::Error::Timeout)),
+                "expected a timeout, got {failed:?}"
+            );
+            drop(rows);
+            assert_eq!(spill_files(), 0, "cancelled query left spill files behind");
+            assert_eq!(db.buffer_manager().allocated(), 0);
+        }
+
+        #[test]
+        fn test_gql_config_query_timeout() {
+            let db = GrafeoDB::with_config(
+                crate::Config::in_memory().with_query_timeout(std::time::Duration::from_millis(1)),
+            )
+            .unwrap();
+            dense_graph(&db, 200, 50);
+
+            let result = db.session().execute(
+                "MATCH (a:Person)-[:KNOWS]->(b)-[:KNOWS]->(c)-[:KNOWS]->(d) RETURN count(d)",
+            );
+
+            assert!(
+                matches!(result, Err(grafeo_common::utils::error::Error::Timeout)),
+                "expected a timeout, got {result:?}"
+            );
+        }
+
+        #[test]
+        fn test_gql_sort_over_memory_limit_fails() {
+            let db =
+                GrafeoDB::with_config(crate::Config::in_memory().with_memory_limit(4096)).unwrap();
+            for i in 0..10_000 {
+                db.create_node_with_props(
+                    &["Person"],
+                    [("age", grafeo_common::types::Value::Int64(i))],
+                );
+            }
+            let session = db.session();
+
+            let result = session.execute("MATCH (n:Person) RETURN n.age ORDER BY n.age DESC");
+
+            assert!(
+                matches!(result, Err(grafeo_common::utils::error::Error::OutOfMemory)),
+                "expected out of memory, got {result:?}"
+            );
+            assert_eq!(db.buffer_manager().allocated(), 0);
+
+            // Queries that hold nothing still run under the same limit
+            let result = session.execute("MATCH (n:Person) RETURN count(n)").unwrap();
+            assert_eq!(
+                result.rows[0][0],
+                grafeo_common::types::Value::Int64(10_000)
+            );
+        }
+
+        #[test]
+        fn test_gql_optional_match_spills_over_memory_limit() {
+            use grafeo_common::types::Value;
+
+            let spill = tempfile::tempdir().unwrap();
+            let db = GrafeoDB::with_config(
+                crate::Config::in_memory()
+                    .with_memory_limit(4096)
+                    .with_spill_path(spill.path()),
+            )
+            .unwrap();
+            dense_graph(&db, 300, 4);
+            let lonely = db.create_node(&["Person"]);
+            let session = db.session();
+
+            // The optional side is far larger than the budget, so the join
+            // has to move it to disk to finish
+            let result = session
+                .execute(
+                    "MATCH (p:Person) OPTIONAL MATCH (p)-[:KNOWS]->(q:Person) \
+                     RETURN count(p), count(q)",
+                )
+                .unwrap();
+            assert_eq!(
+                result.rows,
+                vec![vec![Value::Int64(1_201), Value::Int64(1_200)]]
+            );
+            assert_eq!(db.buffer_manager().allocated(), 0);
+            assert!(db.get_node(lonely).is_some());
+        }
+
+        #[test]
+        fn test_gql_hints_access_pattern_before_running() {
+            use grafeo_adapters::storage::{AccessAdvisor, AccessPattern};
+            use grafeo_common::types::Value;
+            use std::sync::Arc;
+
+            #[derive(Default)]
+            struct RecordingAdvisor(parking_lot::Mutex<Vec<AccessPattern>>);
+
+            impl AccessAdvisor for RecordingAdvisor {
+                fn advise_access(
+                    &self,
+                    pattern: AccessPattern,
+                ) -> grafeo_common::utils::error::Result<()> {
+                    self.0.lock().push(pattern);
+                    Ok(())
+                }
+            }
+
+            let db = GrafeoDB::new_in_memory();
+            let advisor = Arc::new(RecordingAdvisor::default());
+            db.set_access_advisor(advisor.clone());
+            let session = db.session();
+            for age in 0..10 {
+                session.create_node_with_props(&["Person"], [("age", Value::Int64(age))]);
+            }
+
+            session.execute("MATCH (p:Person) RETURN p").unwrap();
+            session.execute("CREATE INDEX ON :Person(age)").unwrap();
+            session
+                .execute("MATCH (p:Person) WHERE p.age = 3 RETURN p")
+                .unwrap();
+            assert_eq!(
+                *advisor.0.lock(),
+                vec![
+                    AccessPattern::Sequential,
+                    AccessPattern::Normal,
+                    AccessPattern::Random
+                ]
+            );
+
+            // Turning hints off in the config leaves the advisor alone
+            let db =
+                GrafeoDB::with_config(crate::Config::in_memory().without_access_hints()).unwrap();
+            let advisor = Arc::new(RecordingAdvisor::default());
+            db.set_access_advisor(advisor.clone());
+            db.session().execute("MATCH (p:Person) RETURN p").unwrap();
+            assert!(advisor.0.lock().is_empty());
+        }
+
+        #[test]
+        fn test_gql_schema_reflects_graph() {
+            use crate::admin::SchemaInfo;
+            use grafeo_common::types::LogicalType;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+            session
+                .execute("CREATE CONSTRAINT ON :Person(name) IS UNIQUE")
+                .unwrap();
+            session
+                .execute(
+                    "INSERT (:Person {name: 'Alix', age: 30})-[:WORKS_AT {since: 2020}]->(:Company {name: 'Acme'})",
+                )
+                .unwrap();
+            session
+                .execute("INSERT (:Person {name: 'Gus', age: 41.5})-[:KNOWS]->(:Person:Admin {name: 'Vincent'})")
+                .unwrap();
+            session.execute("INSERT (:Temp {x: 1})").unwrap();
+            session.execute("MATCH (t:Temp) DELETE t").unwrap();
+
+            let SchemaInfo::Lpg(schema) = session.schema() else {
+                panic!("expected an LPG schema");
+            };
+
+            // Labels are sorted, and the one whose nodes were all deleted is gone
+            let labels: Vec<(&str, usize)> = schema
+                .labels
+                .iter()
+                .map(|label| (label.name.as_str(), label.count))
+                .collect();
+            assert_eq!(labels, [("Admin", 1), ("Company", 1), ("Person", 3)]);
+
+            let person = &schema.labels[2];
+            let properties: Vec<(&str, &LogicalType, usize, bool)> = person
+                .properties
+                .iter()
+                .map(|p| (p.name.as_str(), &p.data_type, p.count, p.unique))
+                .collect();
+            assert_eq!(
+                properties,
+                [
+                    ("age", &LogicalType::Float64, 2, false),
+                    ("name", &LogicalType::String, 3, true),
+                ]
+            );
+
+            let edge_types: Vec<(&str, usize, Vec<(String, String)>)> = schema
+                .edge_types
+                .iter()
+                .map(|e| (e.name.as_str(), e.count, e.endpoints.clone()))
+                .collect();
+            let pair = |s: &str, t: &str| (s.to_string(), t.to_string());
+            assert_eq!(
+                edge_types,
+                [
+                    (
+                        "KNOWS",
+                        1,
+                        vec![pair("Person", "Admin"), pair("Person", "Person")]
+                    ),
+                    ("WORKS_AT", 1, vec![pair("Person", "Company")]),
+                ]
+            );
+            assert_eq!(schema.edge_types[1].properties[0].name, "since");
+            assert_eq!(
+                schema.edge_types[1].properties[0].data_type,
+                LogicalType::Int64
+            );
+        }
     }
 
     #[cfg(feature = "cypher")]
     mod cypher_tests {
         use super::*;
 
+        #[test]
+        fn test_cypher_session_query_timeout() {
+            let db = GrafeoDB::new_in_memory();
+            let ids: Vec<_> = (0..200).map(|_| db.create_node(&["Person"])).collect();
+            for (i, &src) in ids.iter().enumerate() {
+                for offset in 1..=50 {
+                    db.create_edge(src, ids[(i + offset) % ids.len()], "KNOWS");
+                }
+            }
+            let mut session = db.session();
+            session.set_query_timeout(Some(std::time::Duration::from_millis(1)));
+            assert_eq!(
+                session.query_timeout(),
+                Some(std::time::Duration::from_millis(1))
+            );
+
+            let result = session.execute_cypher(
+                "MATCH (a:Person)-[:KNOWS]->(b)-[:KNOWS]->(c)-[:KNOWS]->(d) RETURN count(d)",
+            );
+            assert!(
+                matches!(result, Err(grafeo_common::utils::error::Error::Timeout)),
+                "expected a timeout, got {result:?}"
+            );
+
+            session.set_query_timeout(None);
+            let result = session
+                .execute_cypher("MATCH (n:Person) RETURN count(n)")
+                .unwrap();
+            assert_eq!(result.rows[0][0], grafeo_common::types::Value::Int64(200));
+        }
+
+        #[test]
+        fn test_cypher_in_transaction() {
+            let db = GrafeoDB::new_in_memory();
+            let mut session = db.session();
+
+            let tx = session.begin().unwrap();
+            tx.execute_cypher("CREATE (:Person {name: 'Alix'})")
+                .unwrap();
+            let own = tx.execute_cypher("MATCH (n:Person) RETURN n").unwrap();
+            assert_eq!(own.row_count(), 1);
+            let other = db.session();
+            let outside = other.execute_cypher("MATCH (n:Person) RETURN n").unwrap();
+            assert_eq!(outside.row_count(), 0);
+            tx.rollback().unwrap();
+
+            let after = session.execute_cypher("MATCH (n:Person) RETURN n").unwrap();
+            assert_eq!(after.row_count(), 0);
+        }
+
         #[test]
         fn test_cypher_query_execution() {
             let db = GrafeoDB::new_in_memory();
@@ -1066,6 +3376,63 @@ mod tests {
             assert_eq!(result.row_count(), 0);
         }
 
+        #[test]
+        fn test_cypher_date_function() {
+            use grafeo_common::types::{Date, Value};
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+            for (y, m, d) in [(2019, 5, 1), (2020, 5, 1)] {
+                let created = Date::from_ymd(y, m, d).unwrap();
+                session.create_node_with_props(&["Post"], [("created", Value::Date(created))]);
+            }
+
+            let result = session
+                .execute_cypher("MATCH (p:Post) WHERE p.created > date('2020-01-01') RETURN p")
+                .unwrap();
+            assert_eq!(result.row_count(), 1);
+        }
+
+        #[test]
+        fn test_cypher_stdev_and_percentiles() {
+            use grafeo_common::types::Value;
+
+            let db = GrafeoDB::new_in_memory();
+            let session = db.session();
+            for x in [2, 4, 4, 4, 5, 5, 7, 9] {
+                session.create_node_with_props(&["Sample"], [("x", Value::Int64(x))]);
+            }
+
+            let result = session
+                .execute_cypher(
+                    "MATCH (s:Sample) RETURN stdev(s.x) AS sd, stdevp(s.x) AS sdp, \
+                     percentileDisc(s.x, 0.5) AS pd, percentileCont(s.x, 0.9) AS pc",
+                )
+                .unwrap();
+            let row: Vec<f64> = result.rows[0]
+                .iter()
+                .map(|v| v.as_float64().unwrap())
+                .collect();
+            // Mean 5, squared deviations sum to 32
+            assert!((row[0] - (32.0f64 / 7.0).sqrt()).abs() < 1e-12);
+            assert!((row[1] - 2.0).abs() < 1e-12);
+            assert!((row[2] - 4.0).abs() < 1e-12);
+            // Rank 6.3 interpolates 7 + 0.3 * (9 - 7)
+            assert!((row[3] - 7.6).abs() < 1e-12);
+
+            let gql = session
+                .execute("MATCH (s:Sample) RETURN percentile_cont(s.x, 0.9)")
+                .unwrap();
+            assert!((gql.rows[0][0].as_float64().unwrap() - 7.6).abs() < 1e-12);
+
+            for p in ["1.5", "-0.1", "s.x"] {
+                let query = format!("MATCH (s:Sample) RETURN percentileCont(s.x, {p})");
+                assert!(session.execute_cypher(&query).is_err(), "{p}");
+                let query = format!("MATCH (s:Sample) RETURN percentile_cont(s.x, {p})");
+                assert!(session.execute(&query).is_err(), "{p}");
+            }
+        }
+
         #[test]
         fn test_cypher_parse_error() {
             let db = GrafeoDB::new_in_memory();
@@ -26,6 +26,11 @@ pub enum EntityId {
     Node(NodeId),
     /// An edge.
     Edge(EdgeId),
+    /// A MERGE key: hash of the merged labels and key properties.
+    ///
+    /// Two transactions merging the same key conflict even when each of
+    /// them creates a fresh node.
+    MergeKey(u64),
 }
 
 impl From<NodeId> for EntityId {
@@ -48,6 +53,11 @@ pub struct TxInfo {
     pub start_epoch: EpochId,
     /// Set of entities written by this transaction.
     pub write_set: HashSet<EntityId>,
+    /// Epoch of the version each written entity was read at.
+    ///
+    /// Commit fails if another transaction committed a write to the entity
+    /// after this epoch.
+    pub read_epochs: FxHashMap<EntityId, EpochId>,
     /// Set of entities read by this transaction (for serializable isolation).
     pub read_set: HashSet<EntityId>,
 }
@@ -59,6 +69,7 @@ impl TxInfo {
             state: TxState::Active,
             start_epoch,
             write_set: HashSet::new(),
+            read_epochs: FxHashMap::default(),
             read_set: HashSet::new(),
         }
     }
@@ -101,12 +112,44 @@ impl TransactionManager {
         tx_id
     }
 
+    /// Begins a read-only transaction pinned to the current epoch.
+    ///
+    /// The epoch is advanced past the snapshot, so anything written after
+    /// this call is stamped with a later epoch and stays invisible to it.
+    pub fn begin_read_only(&self) -> TxId {
+        let tx_id = TxId::new(self.next_tx_id.fetch_add(1, Ordering::Relaxed));
+        let epoch = EpochId::new(self.current_epoch.fetch_add(1, Ordering::SeqCst));
+
+        let info = TxInfo::new(epoch);
+        self.transactions.write().insert(tx_id, info);
+        tx_id
+    }
+
     /// Records a write operation for the transaction.
     ///
+    /// The entity counts as read at the transaction's start epoch.
+    ///
     /// # Errors
     ///
     /// Returns an error if the transaction is not active.
     pub fn record_write(&self, tx_id: TxId, entity: impl Into<EntityId>) -> Result<()> {
+        self.record_write_at(tx_id, entity, None)
+    }
+
+    /// Records a write to an entity whose version was read at `read_epoch`.
+    ///
+    /// Only the first read of an entity counts; with `None`, the
+    /// transaction's start epoch is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction is not active.
+    pub fn record_write_at(
+        &self,
+        tx_id: TxId,
+        entity: impl Into<EntityId>,
+        read_epoch: Option<EpochId>,
+    ) -> Result<()> {
         let mut txns = self.transactions.write();
         let info = txns.get_mut(&tx_id).ok_or_else(|| {
             Error::Transaction(TransactionError::InvalidState(
@@ -120,7 +163,10 @@ impl TransactionManager {
             )));
         }
 
-        info.write_set.insert(entity.into());
+        let entity = entity.into();
+        let read_epoch = read_epoch.unwrap_or(info.start_epoch);
+        info.write_set.insert(entity);
+        info.read_epochs.entry(entity).or_insert(read_epoch);
         Ok(())
     }
 
@@ -173,45 +219,31 @@ impl TransactionManager {
             }
         }
 
-        // Get our write set for conflict checking
-        let our_write_set: HashSet<EntityId> = txns
-            .get(&tx_id)
-            .map(|info| info.write_set.clone())
-            .unwrap_or_default();
-
-        let our_start_epoch = txns
-            .get(&tx_id)
-            .map(|info| info.start_epoch)
-            .unwrap_or(EpochId::new(0));
-
-        // Check for write-write conflicts with other committed transactions
-        for (other_tx, other_info) in txns.iter() {
-            if *other_tx == tx_id {
-                continue;
-            }
-            if other_info.state == TxState::Committed {
-                // Check if any of our writes conflict with their writes
-                for entity in &our_write_set {
-                    if other_info.write_set.contains(entity) {
-                        return Err(Error::Transaction(TransactionError::WriteConflict(
-                            format!("Write-write conflict on entity {:?}", entity),
-                        )));
+        // A write conflicts if another transaction committed a write to the
+        // same entity after we read it (first committer wins)
+        if let Some(info) = txns.get(&tx_id) {
+            for entity in &info.write_set {
+                let read_epoch = info
+                    .read_epochs
+                    .get(entity)
+                    .copied()
+                    .unwrap_or(info.start_epoch);
+                for (other_tx, commit_epoch) in committed.iter() {
+                    if *other_tx == tx_id || commit_epoch.as_u64() <= read_epoch.as_u64() {
+                        continue;
                     }
-                }
-            }
-        }
-
-        // Also check against recently committed transactions
-        for (other_tx, commit_epoch) in committed.iter() {
-            if *other_tx != tx_id && commit_epoch.as_u64() > our_start_epoch.as_u64() {
-                // Check if that transaction wrote to any of our entities
-                if let Some(other_info) = txns.get(other_tx) {
-                    for entity in &our_write_set {
-                        if other_info.write_set.contains(entity) {
-                            return Err(Error::Transaction(TransactionError::WriteConflict(
-                                format!("Write-write conflict on entity {:?}", entity),
-                            )));
-                        }
+                    let overlaps = txns
+                        .get(other_tx)
+                        .is_some_and(|other| other.write_set.contains(entity));
+                    if overlaps {
+                        return Err(Error::Transaction(TransactionError::SerializationFailure(
+                            format!(
+                                "Write-write conflict on {entity:?}: read at epoch {}, \
+                                 changed by a transaction committed at epoch {}",
+                                read_epoch.as_u64(),
+                                commit_epoch.as_u64()
+                            ),
+                        )));
                     }
                 }
             }
@@ -316,6 +348,21 @@ impl TransactionManager {
             .unwrap_or_else(|| self.current_epoch())
     }
 
+    /// Runs `f` with the start epoch of the oldest active transaction, or
+    /// `None` if none is active.
+    ///
+    /// No transaction can begin or end while `f` runs, so the epoch it is
+    /// given stays current until it returns.
+    pub fn with_oldest_active_epoch<R>(&self, f: impl FnOnce(Option<EpochId>) -> R) -> R {
+        let txns = self.transactions.read();
+        let oldest = txns
+            .values()
+            .filter(|info| info.state == TxState::Active)
+            .map(|info| info.start_epoch)
+            .min();
+        f(oldest)
+    }
+
     /// Returns the number of active transactions.
     #[must_use]
     pub fn active_count(&self) -> usize {
@@ -326,6 +373,17 @@ impl TransactionManager {
             .count()
     }
 
+    /// Returns the IDs of all active transactions.
+    #[must_use]
+    pub fn active_transactions(&self) -> Vec<TxId> {
+        self.transactions
+            .read()
+            .iter()
+            .filter(|(_, info)| info.state == TxState::Active)
+            .map(|(tx_id, _)| *tx_id)
+            .collect()
+    }
+
     /// Cleans up completed transactions that are no longer needed for conflict detection.
     ///
     /// A committed transaction's write set must be preserved until all transactions
@@ -434,6 +492,18 @@ mod tests {
         assert_eq!(mgr.state(tx), Some(TxState::Aborted));
     }
 
+    #[test]
+    fn test_begin_read_only_pins_epoch() {
+        let mgr = TransactionManager::new();
+
+        let reader = mgr.begin_read_only();
+        let pinned = mgr.start_epoch(reader).unwrap();
+
+        // Writers starting afterwards see a later epoch than the snapshot
+        let writer = mgr.begin();
+        assert!(mgr.start_epoch(writer).unwrap().as_u64() > pinned.as_u64());
+    }
+
     #[test]
     fn test_epoch_advancement() {
         let mgr = TransactionManager::new();
@@ -619,6 +689,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_conflict_uses_read_epoch() {
+        let mgr = TransactionManager::new();
+        let entity = NodeId::new(7);
+
+        let tx1 = mgr.begin();
+        let tx2 = mgr.begin();
+        mgr.record_write(tx1, entity).unwrap();
+        let commit_epoch = mgr.commit(tx1).unwrap();
+
+        // tx2 read the entity after tx1's commit, so there is nothing to lose
+        mgr.record_write_at(tx2, entity, Some(commit_epoch))
+            .unwrap();
+        assert!(mgr.commit(tx2).is_ok());
+
+        // A transaction that read the older version is rejected
+        let tx3 = mgr.begin();
+        let tx4 = mgr.begin();
+        mgr.record_write(tx3, entity).unwrap();
+        mgr.record_write(tx4, entity).unwrap();
+        mgr.commit(tx3).unwrap();
+        let err = mgr.commit(tx4).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Transaction(TransactionError::SerializationFailure(_))
+        ));
+        assert!(err.is_retryable());
+    }
+
     #[test]
     fn test_commit_epoch_monotonicity() {
         let mgr = TransactionManager::new();
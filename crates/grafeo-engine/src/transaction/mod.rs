@@ -45,6 +45,24 @@
 //! 3. **Constraint checking**: Validate invariants at commit time and retry if
 //!    violated.
 //!
+//! ## Write Claims and Deadlocks
+//!
+//! A write claims the node or edge it touches until the transaction commits or
+//! rolls back. Claims never wait: writing an entity another open transaction
+//! has claimed fails at once with a retryable
+//! [`SerializationFailure`](grafeo_common::utils::error::TransactionError::SerializationFailure).
+//! Since no transaction ever waits on another, there is no wait-for cycle to
+//! detect and deadlocks cannot happen. Two transactions claiming the same
+//! entities in opposite order get a conflict instead:
+//!
+//! ```text
+//! T1: Write A  (claims A)
+//! T2: Write B  (claims B)
+//! T1: Write B  → SerializationFailure, T1 rolls back and releases A
+//! T2: Write A  (claims A)
+//! T2: Commit ✓
+//! ```
+//!
 //! ## Epoch-Based Versioning
 //!
 //! Grafeo uses epoch-based MVCC where:
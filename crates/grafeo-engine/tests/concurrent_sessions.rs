@@ -10,6 +10,8 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Barrier};
 use std::thread;
 
+use grafeo_common::types::Value;
+use grafeo_common::utils::error::{Error, TransactionError};
 use grafeo_engine::GrafeoDB;
 
 // ============================================================================
@@ -200,6 +202,253 @@ fn test_session_isolation_between_threads() {
     let _ = before; // Acknowledge we're not asserting on this yet
 }
 
+#[test]
+fn test_read_only_snapshot_ignores_later_commits() {
+    // A long-running reader must not observe rows committed after its snapshot
+    let db = Arc::new(GrafeoDB::new_in_memory());
+    db.session()
+        .execute("INSERT (:Account {name: 'initial', balance: 1})")
+        .unwrap();
+
+    let writer_wrote = Arc::new(Barrier::new(2));
+    let snapshot_taken = Arc::new(Barrier::new(2));
+    let writer_done = Arc::new(Barrier::new(2));
+
+    let writer_handle = {
+        let db = Arc::clone(&db);
+        let writer_wrote = Arc::clone(&writer_wrote);
+        let snapshot_taken = Arc::clone(&snapshot_taken);
+        let writer_done = Arc::clone(&writer_done);
+        thread::spawn(move || {
+            // Written before the snapshot, committed after it
+            let mut session = db.session();
+            session.begin_tx().unwrap();
+            session
+                .execute("INSERT (:Account {name: 'concurrent', balance: 1})")
+                .unwrap();
+            session
+                .execute("MATCH (a:Account {name: 'initial'}) SET a.balance = 2")
+                .unwrap();
+            session
+                .execute("MATCH (a:Account {name: 'initial'}) SET a:Closed")
+                .unwrap();
+            writer_wrote.wait();
+
+            snapshot_taken.wait();
+            session.commit().unwrap();
+
+            // Started and committed entirely after the snapshot
+            session.begin_tx().unwrap();
+            session
+                .execute("INSERT (:Account {name: 'later', balance: 1})")
+                .unwrap();
+            session
+                .execute("MATCH (a:Account {name: 'initial'}) SET a.balance = 3")
+                .unwrap();
+            session.commit().unwrap();
+            db.session()
+                .execute("INSERT (:Account {name: 'autocommit', balance: 1})")
+                .unwrap();
+            db.session()
+                .execute("MATCH (a:Account {name: 'initial'}) SET a.balance = 4")
+                .unwrap();
+            db.session()
+                .execute("MATCH (a:Account {name: 'initial'}) REMOVE a:Account")
+                .unwrap();
+
+            writer_done.wait();
+        })
+    };
+
+    let session = db.session();
+    writer_wrote.wait();
+    let snapshot = session.begin_read_only();
+    let count = |query: &str| snapshot.execute(query).unwrap().row_count();
+    let balance = || {
+        snapshot
+            .execute("MATCH (a:Account) WHERE a.name = 'initial' RETURN a.balance")
+            .unwrap()
+            .rows
+    };
+
+    // Nothing the open transaction wrote shows through
+    assert_eq!(count("MATCH (n:Account) RETURN n.name"), 1);
+    assert_eq!(count("MATCH (n:Closed) RETURN n"), 0);
+    assert_eq!(balance(), vec![vec![Value::Int64(1)]]);
+
+    snapshot_taken.wait();
+    writer_done.wait();
+    writer_handle.join().expect("Writer thread panicked");
+
+    assert_eq!(count("MATCH (n:Account) RETURN n.name"), 1);
+    assert_eq!(count("MATCH (n) RETURN n"), 1);
+    assert_eq!(count("MATCH (n:Closed) RETURN n"), 0);
+    assert_eq!(balance(), vec![vec![Value::Int64(1)]]);
+
+    // A fresh query outside the snapshot sees every committed change
+    let latest = session.execute("MATCH (n:Account) RETURN n.name").unwrap();
+    assert_eq!(latest.row_count(), 3);
+    let closed = session
+        .execute("MATCH (n:Closed) RETURN n.balance")
+        .unwrap();
+    assert_eq!(closed.rows, vec![vec![Value::Int64(4)]]);
+}
+
+#[test]
+fn test_read_only_snapshot_rejects_writes() {
+    let db = GrafeoDB::new_in_memory();
+    let session = db.session();
+    let snapshot = session.begin_read_only();
+
+    assert!(snapshot.execute("INSERT (:Account)").is_err());
+    drop(snapshot);
+
+    let result = session.execute("MATCH (n:Account) RETURN n").unwrap();
+    assert_eq!(result.row_count(), 0);
+}
+
+#[test]
+fn test_concurrent_increments_conflict_and_retry() {
+    // Two transactions read the same counter and both try to increment it:
+    // one of them must fail with a retryable serialization error
+    let db = Arc::new(GrafeoDB::new_in_memory());
+    db.session()
+        .execute("INSERT (:Counter {value: 0})")
+        .unwrap();
+
+    let both_read = Arc::new(Barrier::new(2));
+    let first_attempt_conflicts = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let db = Arc::clone(&db);
+            let both_read = Arc::clone(&both_read);
+            let first_attempt_conflicts = Arc::clone(&first_attempt_conflicts);
+            thread::spawn(move || {
+                let mut session = db.session();
+                for attempt in 0.. {
+                    let tx = session.begin().unwrap();
+                    let read = tx.execute("MATCH (c:Counter) RETURN c.value").unwrap();
+                    let Value::Int64(value) = read.rows[0][0] else {
+                        panic!("counter should be an integer");
+                    };
+                    if attempt == 0 {
+                        both_read.wait();
+                    }
+
+                    let result = tx
+                        .execute(&format!("MATCH (c:Counter) SET c.value = {}", value + 1))
+                        .and_then(|_| tx.commit());
+                    match result {
+                        Ok(()) => return,
+                        Err(err) => {
+                            assert!(
+                                matches!(
+                                    err,
+                                    Error::Transaction(TransactionError::SerializationFailure(_))
+                                ),
+                                "unexpected error: {err}"
+                            );
+                            assert!(err.is_retryable());
+                            if attempt == 0 {
+                                first_attempt_conflicts.fetch_add(1, Ordering::SeqCst);
+                            }
+                            thread::yield_now();
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().expect("Increment thread panicked");
+    }
+
+    assert_eq!(first_attempt_conflicts.load(Ordering::SeqCst), 1);
+
+    let result = db
+        .session()
+        .execute("MATCH (c:Counter) RETURN c.value")
+        .unwrap();
+    assert_eq!(result.rows[0][0], Value::Int64(2));
+}
+
+#[test]
+fn test_opposite_order_writes_conflict_instead_of_deadlocking() {
+    // Each transaction claims one account, then writes the other's. Claims
+    // never wait, so the first crossing write fails at once instead of
+    // blocking; after it rolls back the other transaction can finish
+    let db = Arc::new(GrafeoDB::new_in_memory());
+    db.session()
+        .execute("INSERT (:Account {name: 'a', balance: 0}), (:Account {name: 'b', balance: 0})")
+        .unwrap();
+
+    let both_claimed = Arc::new(Barrier::new(2));
+    let first_finished = Arc::new(Barrier::new(2));
+    let aborted = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = [("a", "b"), ("b", "a")]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (own, other))| {
+            let db = Arc::clone(&db);
+            let both_claimed = Arc::clone(&both_claimed);
+            let first_finished = Arc::clone(&first_finished);
+            let aborted = Arc::clone(&aborted);
+            thread::spawn(move || {
+                let mut session = db.session();
+                let tx = session.begin().unwrap();
+                let set = |name: &str| {
+                    format!("MATCH (n:Account) WHERE n.name = '{name}' SET n.balance = {i}")
+                };
+                tx.execute(&set(own)).unwrap();
+                both_claimed.wait();
+
+                // The second thread crosses over only once the first is done
+                if i == 1 {
+                    first_finished.wait();
+                }
+                let result = tx.execute(&set(other));
+                match result {
+                    Ok(_) => tx.commit().unwrap(),
+                    Err(err) => {
+                        assert!(
+                            matches!(
+                                err,
+                                Error::Transaction(TransactionError::SerializationFailure(_))
+                            ),
+                            "unexpected error: {err}"
+                        );
+                        assert!(err.is_retryable());
+                        aborted.fetch_add(1, Ordering::SeqCst);
+                        tx.rollback().unwrap();
+                    }
+                }
+                if i == 0 {
+                    first_finished.wait();
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().expect("Writer thread panicked");
+    }
+
+    assert_eq!(aborted.load(Ordering::SeqCst), 1);
+
+    // Only the surviving transaction's writes are visible
+    let result = db
+        .session()
+        .execute("MATCH (n:Account) RETURN n.balance")
+        .unwrap();
+    assert_eq!(
+        result.rows,
+        vec![vec![Value::Int64(1)], vec![Value::Int64(1)]]
+    );
+}
+
 // ============================================================================
 // Stress Tests
 // ============================================================================
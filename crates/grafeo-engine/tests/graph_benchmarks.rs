@@ -9,8 +9,10 @@
 //! To run all benchmarks including heavy ones:
 //!   cargo test -p grafeo-engine --release -- graph_benchmarks --nocapture --include-ignored
 
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+use grafeo_common::types::{PropertyKey, Value};
 use grafeo_engine::GrafeoDB;
 
 // ============================================================================
@@ -227,6 +229,81 @@ fn bench_bulk_edge_insertion() {
     print_result("Edges with 2 properties", edge_count, duration);
 }
 
+// ============================================================================
+// Benchmark: Batch Insertion
+// ============================================================================
+
+#[test]
+fn bench_batch_insertion() {
+    print_header("BATCH INSERTION BENCHMARKS");
+
+    let node_count = LARGE_SCALE;
+    let edge_count = node_count * EDGE_MULTIPLIER;
+    let person = |i: usize| {
+        HashMap::from([
+            (PropertyKey::from("id"), Value::Int64(i as i64)),
+            (PropertyKey::from("name"), Value::from(format!("User{i}"))),
+        ])
+    };
+
+    // Baseline: one INSERT per row
+    let db = GrafeoDB::new_in_memory();
+    let session = db.session();
+    let start = Instant::now();
+    for i in 0..node_count {
+        let query = format!("INSERT (:Person {{id: {}, name: 'User{}'}})", i, i);
+        session.execute(&query).unwrap();
+    }
+    let duration = start.elapsed();
+    print_result("INSERT per row", node_count, duration);
+
+    let db = GrafeoDB::new_in_memory();
+    let session = db.session();
+    let start = Instant::now();
+    let ids = session
+        .insert_nodes("Person", (0..node_count).map(person))
+        .unwrap();
+    let duration = start.elapsed();
+    print_result("insert_nodes", node_count, duration);
+
+    let start = Instant::now();
+    let edges = session
+        .insert_edges(
+            "KNOWS",
+            (0..edge_count).map(|i| {
+                let src = ids[i % node_count];
+                let dst = ids[(i * 7 + 13) % node_count];
+                let since = HashMap::from([(PropertyKey::from("since"), Value::Int64(2000))]);
+                (src, dst, since)
+            }),
+        )
+        .unwrap();
+    let duration = start.elapsed();
+    print_result("insert_edges", edge_count, duration);
+
+    assert_eq!(ids.len(), node_count);
+    assert_eq!(edges.len(), edge_count);
+    assert_eq!(db.node_count(), node_count);
+    assert_eq!(db.edge_count(), edge_count);
+
+    // The label index and adjacency lists see the batch-inserted graph
+    let result = session.execute("MATCH (n:Person) RETURN count(n)").unwrap();
+    assert_eq!(result.rows[0][0], Value::Int64(node_count as i64));
+    let result = session
+        .execute("MATCH (n:Person)-[k:KNOWS]->(m:Person) WHERE n.id = 0 RETURN k.since, m.name")
+        .unwrap();
+    assert_eq!(result.rows.len(), EDGE_MULTIPLIER);
+    assert!(
+        result
+            .rows
+            .contains(&vec![Value::Int64(2000), Value::from("User13")])
+    );
+    let result = session
+        .execute("MATCH (n:Person) WHERE n.name = 'User42' RETURN n.id")
+        .unwrap();
+    assert_eq!(result.rows, vec![vec![Value::Int64(42)]]);
+}
+
 // ============================================================================
 // Benchmark: Graph Traversals
 // ============================================================================
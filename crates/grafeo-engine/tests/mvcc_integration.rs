@@ -104,7 +104,8 @@ fn test_committed_writes_visible_to_new_transactions() {
     let tx1 = tx_manager.begin();
     let epoch1 = tx_manager.current_epoch();
     let node_id = store.create_node_versioned(&["Committed"], epoch1, tx1);
-    tx_manager.commit(tx1).unwrap();
+    let commit_epoch = tx_manager.commit(tx1).unwrap();
+    store.commit_versions(tx1, commit_epoch);
 
     // T2 starts after commit
     let tx2 = tx_manager.begin();
@@ -336,6 +337,90 @@ fn test_session_rollback_state() {
     session.commit().unwrap();
 }
 
+#[test]
+fn test_rollback_to_savepoint_keeps_earlier_changes() {
+    let db = GrafeoDB::new_in_memory();
+    let mut session = db.session();
+    let mut tx = session.begin().unwrap();
+
+    tx.execute("INSERT (:Item {n: 1})").unwrap();
+    tx.savepoint("before_second").unwrap();
+    tx.execute("INSERT (:Item {n: 2})").unwrap();
+    tx.execute("MATCH (i:Item) WHERE i.n = 1 SET i.flag = true")
+        .unwrap();
+
+    tx.rollback_to("before_second").unwrap();
+
+    // The first insert survives, the later insert and update are gone
+    let result = tx.execute("MATCH (i:Item) RETURN i.n, i.flag").unwrap();
+    assert_eq!(result.rows, vec![vec![Value::Int64(1), Value::Null]]);
+
+    // The transaction is still open and commits what it kept
+    tx.execute("INSERT (:Item {n: 3})").unwrap();
+    tx.commit().unwrap();
+
+    let result = db
+        .session()
+        .execute("MATCH (i:Item) RETURN i.n ORDER BY i.n")
+        .unwrap();
+    assert_eq!(
+        result.rows,
+        vec![vec![Value::Int64(1)], vec![Value::Int64(3)]]
+    );
+}
+
+#[test]
+fn test_nested_savepoints() {
+    let db = GrafeoDB::new_in_memory();
+    let mut session = db.session();
+    let mut tx = session.begin().unwrap();
+
+    tx.savepoint("outer").unwrap();
+    tx.execute("INSERT (:Item {n: 1})").unwrap();
+    tx.savepoint("inner").unwrap();
+    tx.execute("INSERT (:Item {n: 2})").unwrap();
+
+    tx.rollback_to("inner").unwrap();
+    assert_eq!(
+        tx.execute("MATCH (i:Item) RETURN i").unwrap().row_count(),
+        1
+    );
+
+    // Rolling back to the outer savepoint releases the inner one
+    tx.rollback_to("outer").unwrap();
+    assert_eq!(
+        tx.execute("MATCH (i:Item) RETURN i").unwrap().row_count(),
+        0
+    );
+    assert!(tx.rollback_to("inner").is_err());
+
+    // The outer savepoint can be rolled back to again until released
+    tx.execute("INSERT (:Item {n: 3})").unwrap();
+    tx.rollback_to("outer").unwrap();
+    tx.release_savepoint("outer").unwrap();
+    assert!(tx.rollback_to("outer").is_err());
+
+    tx.commit().unwrap();
+    let result = db.session().execute("MATCH (i:Item) RETURN i").unwrap();
+    assert_eq!(result.row_count(), 0);
+}
+
+#[test]
+fn test_rollback_to_unknown_savepoint_fails() {
+    let db = GrafeoDB::new_in_memory();
+    let mut session = db.session();
+    let mut tx = session.begin().unwrap();
+
+    tx.execute("INSERT (:Item {n: 1})").unwrap();
+    assert!(tx.rollback_to("missing").is_err());
+    assert!(tx.release_savepoint("missing").is_err());
+
+    // A failed rollback leaves the transaction and its changes alone
+    tx.commit().unwrap();
+    let result = db.session().execute("MATCH (i:Item) RETURN i").unwrap();
+    assert_eq!(result.row_count(), 1);
+}
+
 // ============================================================================
 // Edge Cases
 // ============================================================================
@@ -490,3 +575,28 @@ fn test_session_auto_commit_mode() {
         "Auto-committed data should be visible"
     );
 }
+
+#[test]
+fn test_committed_store_leaves_out_open_transactions() {
+    let db = GrafeoDB::new_in_memory();
+    let session = db.session();
+    session.execute("INSERT (:Person {name: 'Alix'})").unwrap();
+
+    // Nothing open: the database's own store is shared
+    assert!(Arc::ptr_eq(&db.committed_store(), db.store()));
+
+    let mut writer = db.session();
+    writer.begin_tx().unwrap();
+    writer
+        .execute("INSERT (:Person {name: 'Pending'})")
+        .unwrap();
+
+    // An open transaction: a snapshot without its writes, which later
+    // writes don't reach
+    let committed = db.committed_store();
+    session.execute("INSERT (:Person {name: 'Gus'})").unwrap();
+    writer.commit().unwrap();
+
+    assert_eq!(committed.nodes_by_label("Person").len(), 1);
+    assert_eq!(db.store().nodes_by_label("Person").len(), 3);
+}
@@ -17,6 +17,7 @@
 //! ```
 
 use grafeo_common::types::Value;
+use grafeo_common::utils::error::{Error, QueryErrorKind};
 use grafeo_engine::GrafeoDB;
 
 // ============================================================================
@@ -306,6 +307,23 @@ mod gql_basic_patterns {
         );
     }
 
+    #[test]
+    fn test_contradictory_filter_returns_no_rows() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session
+            .execute("MATCH (n:Person) WHERE 1 = 2 RETURN n.name, n.age")
+            .unwrap();
+        assert_eq!(result.row_count(), 0);
+        assert_eq!(result.column_count(), 2, "Columns survive pruning");
+
+        let result = session
+            .execute("MATCH (n:Person) WHERE n.age > 28 AND 1 = 1 RETURN n.name")
+            .unwrap();
+        assert_eq!(result.row_count(), 2);
+    }
+
     #[test]
     #[ignore = "Parser issue with 'Node' label name - reserved keyword handling"]
     fn test_chain_traversal() {
@@ -417,6 +435,40 @@ mod gql_aggregations {
         }
     }
 
+    #[test]
+    fn test_having_on_aggregate_alias() {
+        let db = create_numeric_data();
+        let session = db.session();
+
+        let result = session
+            .execute("MATCH (p:Product) RETURN p.category, COUNT(p) AS cnt HAVING cnt > 2")
+            .unwrap();
+
+        assert_eq!(result.columns, vec!["p.category", "cnt"]);
+        assert_eq!(
+            result.row_count(),
+            1,
+            "Only Electronics has more than 2 products"
+        );
+        assert_eq!(result.rows[0][0], Value::String("Electronics".into()));
+        assert_eq!(result.rows[0][1], Value::Int64(3));
+    }
+
+    #[test]
+    fn test_having_with_aggregate_not_in_return() {
+        let db = create_numeric_data();
+        let session = db.session();
+
+        let result = session
+            .execute("MATCH (p:Product) RETURN p.category HAVING SUM(p.price) > 500")
+            .unwrap();
+
+        // The hidden SUM column must not leak into the result
+        assert_eq!(result.columns, vec!["p.category"]);
+        assert_eq!(result.row_count(), 1);
+        assert_eq!(result.rows[0][0], Value::String("Electronics".into()));
+    }
+
     #[test]
     fn test_count_empty_result() {
         let db = GrafeoDB::new_in_memory();
@@ -473,6 +525,49 @@ mod gql_joins {
         );
     }
 
+    #[test]
+    fn test_optional_match_keeps_unmatched_rows() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session
+            .execute("MATCH (a) OPTIONAL MATCH (a)-[:KNOWS]->(b) RETURN a, b")
+            .unwrap();
+
+        // Alice knows 2, Bob knows 1, Carol and both companies know nobody
+        assert_eq!(result.row_count(), 6);
+        assert!(result.rows.iter().all(|row| row[0] != Value::Null));
+        let unmatched = result.rows.iter().filter(|row| row[1] == Value::Null);
+        assert_eq!(unmatched.count(), 3);
+    }
+
+    #[test]
+    fn test_optional_match_properties() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session
+            .execute("MATCH (a:Person) OPTIONAL MATCH (a)-[:KNOWS]->(b) RETURN a.name, b.name")
+            .unwrap();
+
+        let mut rows: Vec<(Value, Value)> = result
+            .rows
+            .iter()
+            .map(|row| (row[0].clone(), row[1].clone()))
+            .collect();
+        rows.sort_by_key(|(a, b)| (format!("{a:?}"), format!("{b:?}")));
+
+        assert_eq!(
+            rows,
+            vec![
+                (Value::String("Alice".into()), Value::String("Bob".into())),
+                (Value::String("Alice".into()), Value::String("Carol".into())),
+                (Value::String("Bob".into()), Value::String("Carol".into())),
+                (Value::String("Carol".into()), Value::Null),
+            ]
+        );
+    }
+
     #[test]
     fn test_tree_parent_child() {
         let db = create_tree();
@@ -513,259 +608,1195 @@ mod gql_joins {
 // ============================================================================
 
 #[cfg(feature = "gql")]
-mod gql_mutations {
+mod gql_subqueries {
     use super::*;
 
+    fn sorted_names(result: &grafeo_engine::database::QueryResult) -> Vec<String> {
+        let mut names: Vec<String> = result
+            .rows
+            .iter()
+            .map(|row| match &row[0] {
+                Value::String(s) => s.to_string(),
+                other => panic!("Expected string name, got {other:?}"),
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
     #[test]
-    fn test_insert_node() {
-        let db = GrafeoDB::new_in_memory();
+    fn test_uncorrelated_in_subquery() {
+        let db = create_social_network();
         let session = db.session();
 
-        session
-            .execute("INSERT (:Person {name: 'Alice', age: 30})")
+        let result = session
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE p.name IN (MATCH (a:Person)-[:KNOWS]->(b:Person) RETURN b.name) \
+                 RETURN p.name",
+            )
             .unwrap();
 
+        // Only Bob and Carol are known by someone
+        assert_eq!(sorted_names(&result), vec!["Bob", "Carol"]);
+    }
+
+    #[test]
+    fn test_correlated_in_subquery() {
+        let db = create_social_network();
+        let session = db.session();
+
+        // `p` inside the subquery is the outer row's person
         let result = session
-            .execute("MATCH (n:Person) RETURN n.name, n.age")
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE 'TechCorp' IN (MATCH (p)-[:WORKS_AT]->(c:Company) RETURN c.name) \
+                 RETURN p.name",
+            )
             .unwrap();
-        assert_eq!(result.row_count(), 1, "Should have 1 Person node");
-        // Note: Property values are stored correctly but order may vary
-        // Check name exists
-        assert!(
-            result.rows[0]
-                .iter()
-                .any(|v| *v == Value::String("Alice".into())),
-            "Should find Alice in result"
-        );
+
+        assert_eq!(sorted_names(&result), vec!["Alice", "Bob"]);
     }
 
     #[test]
-    fn test_insert_multiple_nodes() {
-        let db = GrafeoDB::new_in_memory();
+    fn test_in_subquery_with_other_conditions() {
+        let db = create_social_network();
         let session = db.session();
 
-        session.execute("INSERT (:Person {name: 'Alice'})").unwrap();
-        session.execute("INSERT (:Person {name: 'Bob'})").unwrap();
-        session.execute("INSERT (:Person {name: 'Carol'})").unwrap();
+        let result = session
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE p.age > 26 AND 'TechCorp' IN (MATCH (p)-[:WORKS_AT]->(c:Company) RETURN c.name) \
+                 RETURN p.name",
+            )
+            .unwrap();
 
-        let result = session.execute("MATCH (n:Person) RETURN n").unwrap();
-        assert_eq!(result.row_count(), 3, "Should have 3 Person nodes");
+        assert_eq!(sorted_names(&result), vec!["Alice"]);
     }
 
-    #[test]
-    fn test_transaction_commit() {
+    /// Alix, nicknamed Alix, and Gus, nicknamed G.
+    fn create_nicknames() -> GrafeoDB {
         let db = GrafeoDB::new_in_memory();
-        let mut session = db.session();
+        let session = db.session();
+        for (name, nick) in [("Alix", "Alix"), ("Gus", "G")] {
+            session.create_node_with_props(
+                &["Person"],
+                [
+                    ("name", Value::String(name.into())),
+                    ("nick", Value::String(nick.into())),
+                ],
+            );
+        }
+        db
+    }
 
-        session.begin_tx().unwrap();
-        session.execute("INSERT (:Person {name: 'Alice'})").unwrap();
-        session.commit().unwrap();
+    #[test]
+    fn test_in_subquery_returns_outer_variable() {
+        let db = create_nicknames();
+        let session = db.session();
 
-        let result = session.execute("MATCH (n:Person) RETURN n").unwrap();
-        assert_eq!(result.row_count(), 1, "Node should exist after commit");
+        // `p` is only bound outside, so each row compares its own name and nick
+        let result = session
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE p.name IN (MATCH (c:Person) RETURN p.nick) \
+                 RETURN p.name",
+            )
+            .unwrap();
+
+        assert_eq!(sorted_names(&result), vec!["Alix"]);
     }
-}
 
-// ============================================================================
-// Cypher Tests
-// ============================================================================
+    #[test]
+    fn test_in_subquery_where_reads_outer_variable() {
+        let db = create_nicknames();
+        let session = db.session();
 
-#[cfg(feature = "cypher")]
-mod cypher_tests {
-    use super::*;
+        let result = session
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE 'G' IN (MATCH (c:Person) WHERE c.name = p.name RETURN c.nick) \
+                 RETURN p.name",
+            )
+            .unwrap();
+
+        assert_eq!(sorted_names(&result), vec!["Gus"]);
+    }
 
     #[test]
-    fn test_match_all_nodes() {
-        let db = create_social_network();
+    fn test_in_subquery_rejects_unjoinable_correlation() {
+        let db = create_nicknames();
         let session = db.session();
 
-        let result = session.execute_cypher("MATCH (n) RETURN n").unwrap();
-        assert_eq!(result.row_count(), 5, "Should find 5 nodes");
+        // A hash join can't evaluate `<>` between the outer row and the subquery
+        let error = session
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE 'G' IN (MATCH (c:Person) WHERE c.name <> p.name RETURN c.nick) \
+                 RETURN p.name",
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&error, Error::Query(e) if e.kind == QueryErrorKind::Semantic),
+            "expected a semantic error, got {error:?}"
+        );
     }
 
     #[test]
-    fn test_match_nodes_by_label() {
+    fn test_in_subquery_must_return_one_column() {
         let db = create_social_network();
         let session = db.session();
 
-        let result = session.execute_cypher("MATCH (n:Person) RETURN n").unwrap();
-        assert_eq!(result.row_count(), 3, "Should find 3 Person nodes");
+        let result = session.execute(
+            "MATCH (p:Person) \
+             WHERE p.name IN (MATCH (c:Company) RETURN c.name, c.founded) \
+             RETURN p.name",
+        );
+
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_match_with_property_filter() {
+    fn test_correlated_exists_subquery() {
         let db = create_social_network();
         let session = db.session();
 
         let result = session
-            .execute_cypher("MATCH (n:Person) WHERE n.age > 28 RETURN n.name")
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE EXISTS { MATCH (p)-[:KNOWS]->(q:Person) } \
+                 RETURN p.name",
+            )
             .unwrap();
-        assert_eq!(result.row_count(), 2, "Should find 2 people older than 28");
+
+        // Carol knows nobody
+        assert_eq!(sorted_names(&result), vec!["Alice", "Bob"]);
     }
 
     #[test]
-    fn test_match_relationship_pattern() {
+    fn test_correlated_not_exists_subquery() {
         let db = create_social_network();
         let session = db.session();
 
         let result = session
-            .execute_cypher("MATCH (a:Person)-[:KNOWS]->(b:Person) RETURN a.name, b.name")
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE NOT EXISTS { MATCH (p)-[:KNOWS]->(q:Person) } \
+                 RETURN p.name",
+            )
             .unwrap();
-        assert_eq!(result.row_count(), 3, "Should find 3 KNOWS relationships");
+
+        assert_eq!(sorted_names(&result), vec!["Carol"]);
     }
 
     #[test]
-    fn test_count() {
+    fn test_exists_subquery_with_other_conditions() {
         let db = create_social_network();
         let session = db.session();
 
         let result = session
-            .execute_cypher("MATCH (n:Person) RETURN count(n)")
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE p.age < 30 AND EXISTS { MATCH (p)-[:KNOWS]->(q:Person) } \
+                 RETURN p.name",
+            )
             .unwrap();
 
-        assert_eq!(result.row_count(), 1);
-        if let Value::Int64(count) = &result.rows[0][0] {
-            assert_eq!(*count, 3);
-        }
+        assert_eq!(sorted_names(&result), vec!["Bob"]);
     }
 
     #[test]
-    fn test_create_node() {
-        let db = GrafeoDB::new_in_memory();
+    fn test_exists_where_reads_outer_variable() {
+        let db = create_nicknames();
         let session = db.session();
 
-        session
-            .execute_cypher("CREATE (:Person {name: 'Alice', age: 30})")
+        // `p` is only bound outside, so the subquery looks for a name equal to
+        // this row's nick
+        let result = session
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE EXISTS { MATCH (c:Person) WHERE c.name = p.nick } \
+                 RETURN p.name",
+            )
             .unwrap();
+        assert_eq!(sorted_names(&result), vec!["Alix"]);
 
         let result = session
-            .execute_cypher("MATCH (n:Person) RETURN n.name, n.age")
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE NOT EXISTS { MATCH (c:Person) WHERE c.name = p.nick } \
+                 RETURN p.name",
+            )
             .unwrap();
-        assert_eq!(result.row_count(), 1);
+        assert_eq!(sorted_names(&result), vec!["Gus"]);
     }
 
     #[test]
-    fn test_two_hop_path() {
-        let db = create_social_network();
+    fn test_not_exists_with_outer_only_condition() {
+        let db = create_nicknames();
         let session = db.session();
 
+        // The condition on `p` alone still rejects only the rows it holds for
         let result = session
-            .execute_cypher("MATCH (a:Person)-[:KNOWS]->(b:Person)-[:KNOWS]->(c:Person) RETURN a.name, b.name, c.name")
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE NOT EXISTS { MATCH (c:Person) WHERE p.nick = 'G' } \
+                 RETURN p.name",
+            )
             .unwrap();
-
-        assert!(
-            result.row_count() >= 1,
-            "Should find at least one 2-hop path"
-        );
+        assert_eq!(sorted_names(&result), vec!["Alix"]);
     }
-}
-
-// ============================================================================
-// Gremlin Tests
-// ============================================================================
-
-#[cfg(feature = "gremlin")]
-mod gremlin_tests {
-    use super::*;
-
-    // Note: Many Gremlin tests are currently expected to fail due to variable
-    // binding issues in the Gremlin executor. These tests document expected
-    // behavior for when the executor is fully implemented.
 
     #[test]
-    fn test_gremlin_parser() {
-        // Verify Gremlin parsing works (execution may fail)
-        let db = create_social_network();
+    fn test_exists_rejects_unjoinable_correlation() {
+        let db = create_nicknames();
         let session = db.session();
 
-        // Test that we can at least parse basic Gremlin syntax
-        let result = session.execute_gremlin("g.V()");
-        // The translator should work even if execution fails
+        let error = session
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE EXISTS { MATCH (c:Person) WHERE c.name > p.nick } \
+                 RETURN p.name",
+            )
+            .unwrap_err();
         assert!(
-            result.is_ok() || result.is_err(),
-            "Gremlin should be parsed"
+            matches!(&error, Error::Query(e) if e.kind == QueryErrorKind::Semantic),
+            "expected a semantic error, got {error:?}"
         );
     }
 
     #[test]
-    #[ignore = "Gremlin executor variable binding not fully implemented"]
-    fn test_v_all_nodes() {
+    fn test_uncorrelated_exists_subquery() {
         let db = create_social_network();
         let session = db.session();
 
-        let result = session.execute_gremlin("g.V()").unwrap();
-        assert_eq!(result.row_count(), 5, "Should find 5 vertices");
+        // Without shared variables the subquery keeps every row or none
+        let none = session
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE EXISTS { MATCH (c:Company) WHERE c.founded > 2030 } \
+                 RETURN p.name",
+            )
+            .unwrap();
+        assert!(sorted_names(&none).is_empty());
+
+        let all = session
+            .execute(
+                "MATCH (p:Person) \
+                 WHERE NOT EXISTS { MATCH (c:Company) WHERE c.founded > 2030 } \
+                 RETURN p.name",
+            )
+            .unwrap();
+        assert_eq!(sorted_names(&all), vec!["Alice", "Bob", "Carol"]);
     }
+}
 
-    #[test]
-    #[ignore = "Gremlin executor variable binding not fully implemented"]
-    fn test_v_has_label() {
-        let db = create_social_network();
+#[cfg(feature = "gql")]
+mod gql_procedures {
+    use super::*;
+
+    fn float(value: &Value) -> f64 {
+        match value {
+            Value::Float64(f) => *f,
+            other => panic!("Expected float, got {other:?}"),
+        }
+    }
+
+    /// Ten targets pointed at by twenty sources, target `t{i}` by every
+    /// source whose links reach past `i`, so in-links and PageRank both fall
+    /// strictly from `t0` to `t9`. The sources have no in-links and tie below.
+    fn create_ranked_targets() -> GrafeoDB {
+        let db = GrafeoDB::new_in_memory();
         let session = db.session();
 
-        let result = session.execute_gremlin("g.V().hasLabel('Person')").unwrap();
-        assert_eq!(result.row_count(), 3, "Should find 3 Person vertices");
+        let targets: Vec<_> = (0..10)
+            .map(|i| {
+                session.create_node_with_props(
+                    &["Page"],
+                    [("id", Value::String(format!("t{i}").into()))],
+                )
+            })
+            .collect();
+        for k in 0..20 {
+            let source = session
+                .create_node_with_props(&["Page"], [("id", Value::String(format!("s{k}").into()))]);
+            for &target in &targets[..=k % 10] {
+                session.create_edge(source, target, "LINKS");
+            }
+        }
+
+        db
     }
 
     #[test]
-    #[ignore = "Gremlin predicate parsing not fully implemented"]
-    fn test_v_has_property() {
-        let db = create_social_network();
+    fn test_call_pagerank_top_nodes() {
+        let db = create_ranked_targets();
         let session = db.session();
 
         let result = session
-            .execute_gremlin("g.V().hasLabel('Person').has('age', gt(28))")
+            .execute(
+                "CALL pagerank() YIELD node, score \
+                 RETURN node.id, score ORDER BY score DESC LIMIT 10",
+            )
             .unwrap();
-        assert_eq!(result.row_count(), 2, "Should find 2 people with age > 28");
+        let ids: Vec<&Value> = result.rows.iter().map(|row| &row[0]).collect();
+        let expected: Vec<Value> = (0..10)
+            .map(|i| Value::String(format!("t{i}").into()))
+            .collect();
+        assert_eq!(ids, expected.iter().collect::<Vec<_>>());
+
+        let scores: Vec<f64> = result.rows.iter().map(|row| float(&row[1])).collect();
+        assert!(scores.windows(2).all(|w| w[0] > w[1]), "{scores:?}");
+
+        // Every source scores below the last target
+        let result = session
+            .execute(
+                "CALL pagerank() YIELD node, score \
+                 RETURN score ORDER BY score DESC SKIP 10",
+            )
+            .unwrap();
+        assert_eq!(result.row_count(), 20);
+        assert!(result.rows.iter().all(|row| float(&row[0]) < scores[9]));
     }
 
     #[test]
-    #[ignore = "Gremlin executor variable binding not fully implemented"]
-    fn test_out_step() {
-        let db = create_social_network();
+    fn test_call_pagerank_with_damping() {
+        let db = create_star();
         let session = db.session();
 
         let result = session
-            .execute_gremlin("g.V().hasLabel('Person').out('KNOWS')")
+            .execute("CALL pagerank(0.5) YIELD score RETURN score")
             .unwrap();
-        assert_eq!(result.row_count(), 3, "Should find 3 outgoing KNOWS edges");
+        assert_eq!(result.row_count(), 6);
+
+        // Damping is a float parameter
+        assert!(
+            session
+                .execute("CALL pagerank('high') YIELD score RETURN score")
+                .is_err()
+        );
+        assert!(
+            session
+                .execute("CALL pagerank(0.5, 10, 0.001, 1) YIELD score RETURN score")
+                .is_err()
+        );
     }
 
     #[test]
-    #[ignore = "Gremlin executor variable binding not fully implemented"]
-    fn test_values_step() {
-        let db = create_social_network();
+    fn test_call_connected_components() {
+        let db = create_chain();
         let session = db.session();
 
         let result = session
-            .execute_gremlin("g.V().hasLabel('Person').values('name')")
+            .execute("CALL connected_components() YIELD node, component_id RETURN component_id")
             .unwrap();
-        assert_eq!(result.row_count(), 3, "Should return 3 names");
+        assert_eq!(result.row_count(), 4);
+        assert!(result.rows.iter().all(|row| row[0] == result.rows[0][0]));
     }
 
     #[test]
-    #[ignore = "Gremlin executor variable binding not fully implemented"]
-    fn test_limit_step() {
-        let db = create_social_network();
+    fn test_call_unknown_procedure_or_column() {
+        let db = create_chain();
         let session = db.session();
 
-        let result = session.execute_gremlin("g.V().limit(2)").unwrap();
-        assert_eq!(result.row_count(), 2, "Should limit to 2 results");
+        assert!(
+            session
+                .execute("CALL no_such_algorithm() YIELD node RETURN node")
+                .is_err()
+        );
+        assert!(
+            session
+                .execute("CALL pagerank() YIELD rank RETURN rank")
+                .is_err()
+        );
     }
+}
+
+#[cfg(feature = "gql")]
+mod gql_mutations {
+    use super::*;
 
     #[test]
-    #[ignore = "Gremlin executor variable binding not fully implemented"]
-    fn test_count() {
-        let db = create_social_network();
+    fn test_insert_node() {
+        let db = GrafeoDB::new_in_memory();
         let session = db.session();
 
-        let result = session
-            .execute_gremlin("g.V().hasLabel('Person').count()")
+        session
+            .execute("INSERT (:Person {name: 'Alice', age: 30})")
             .unwrap();
 
-        assert_eq!(result.row_count(), 1);
-        if let Value::Int64(count) = &result.rows[0][0] {
-            assert_eq!(*count, 3);
+        let result = session
+            .execute("MATCH (n:Person) RETURN n.name, n.age")
+            .unwrap();
+        assert_eq!(result.row_count(), 1, "Should have 1 Person node");
+        // Note: Property values are stored correctly but order may vary
+        // Check name exists
+        assert!(
+            result.rows[0]
+                .iter()
+                .any(|v| *v == Value::String("Alice".into())),
+            "Should find Alice in result"
+        );
+    }
+
+    #[test]
+    fn test_insert_multiple_nodes() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+
+        session.execute("INSERT (:Person {name: 'Alice'})").unwrap();
+        session.execute("INSERT (:Person {name: 'Bob'})").unwrap();
+        session.execute("INSERT (:Person {name: 'Carol'})").unwrap();
+
+        let result = session.execute("MATCH (n:Person) RETURN n").unwrap();
+        assert_eq!(result.row_count(), 3, "Should have 3 Person nodes");
+    }
+
+    #[test]
+    fn test_insert_path_creates_nodes_and_edge() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+
+        session
+            .execute(
+                "INSERT (a:Person {name: 'Alice'})-[:KNOWS {since: 2020}]->(b:Person {name: 'Bob'})",
+            )
+            .unwrap();
+
+        assert_eq!(db.node_count(), 2);
+        assert_eq!(db.edge_count(), 1);
+        let result = session
+            .execute("MATCH (a:Person)-[e:KNOWS]->(b:Person) RETURN a.name, e.since, b.name")
+            .unwrap();
+        assert_eq!(result.row_count(), 1);
+        assert_eq!(
+            result.rows[0],
+            vec![
+                Value::String("Alice".into()),
+                Value::Int64(2020),
+                Value::String("Bob".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_match_insert_edge_reuses_bound_nodes() {
+        let db = create_social_network();
+        let session = db.session();
+
+        session
+            .execute(
+                "MATCH (a:Person {name: 'Alice'}), (c:Company {name: 'Startup'}) \
+                 INSERT (a)-[:WORKS_AT]->(c)",
+            )
+            .unwrap();
+
+        assert_eq!(db.node_count(), 5, "no endpoint is recreated");
+        assert_eq!(db.edge_count(), 7);
+        let result = session
+            .execute("MATCH (p:Person)-[:WORKS_AT]->(c:Company {name: 'Startup'}) RETURN p.name")
+            .unwrap();
+        assert_eq!(result.row_count(), 2);
+    }
+
+    #[test]
+    fn test_detach_delete_removes_node_and_edges() {
+        let db = create_social_network();
+        let session = db.session();
+
+        session
+            .execute("MATCH (c:Company {name: 'Startup'}) DETACH DELETE c")
+            .unwrap();
+
+        assert_eq!(db.node_count(), 4);
+        assert_eq!(db.edge_count(), 5, "Carol's WORKS_AT edge goes too");
+    }
+
+    #[test]
+    fn test_delete_connected_node_fails() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session.execute("MATCH (c:Company {name: 'Startup'}) DELETE c");
+
+        assert!(result.is_err(), "DELETE must not leave dangling edges");
+        assert_eq!(db.node_count(), 5);
+        assert_eq!(db.edge_count(), 6);
+    }
+
+    #[test]
+    fn test_delete_isolated_node() {
+        let db = create_social_network();
+        let session = db.session();
+        session.execute("INSERT (:Tag {name: 'rust'})").unwrap();
+
+        session.execute("MATCH (t:Tag) DELETE t").unwrap();
+
+        assert_eq!(db.node_count(), 5);
+        let result = session.execute("MATCH (t:Tag) RETURN t").unwrap();
+        assert_eq!(result.row_count(), 0);
+    }
+
+    #[test]
+    fn test_transaction_commit() {
+        let db = GrafeoDB::new_in_memory();
+        let mut session = db.session();
+
+        session.begin_tx().unwrap();
+        session.execute("INSERT (:Person {name: 'Alice'})").unwrap();
+        session.commit().unwrap();
+
+        let result = session.execute("MATCH (n:Person) RETURN n").unwrap();
+        assert_eq!(result.row_count(), 1, "Node should exist after commit");
+    }
+
+    #[test]
+    fn test_merge_creates_missing_node() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+
+        session
+            .execute("MERGE (n:Person {email: 'alice@example.com'}) RETURN n")
+            .unwrap();
+
+        let result = session.execute("MATCH (n:Person) RETURN n.email").unwrap();
+        assert_eq!(result.row_count(), 1);
+        assert_eq!(result.rows[0][0], Value::String("alice@example.com".into()));
+    }
+
+    #[test]
+    fn test_merge_matches_existing_node() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+
+        session
+            .execute("INSERT (:Person {email: 'alice@example.com'})")
+            .unwrap();
+        session
+            .execute("MERGE (n:Person {email: 'alice@example.com'}) RETURN n")
+            .unwrap();
+
+        let result = session.execute("MATCH (n:Person) RETURN n").unwrap();
+        assert_eq!(result.row_count(), 1, "MERGE must not duplicate the node");
+    }
+
+    #[test]
+    fn test_merge_on_create_and_on_match() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+        let query = "MERGE (n:Person {email: 'alice@example.com'}) \
+                     ON CREATE SET n.created = true \
+                     ON MATCH SET n.matched = true \
+                     RETURN n";
+
+        // First run takes the create path
+        session.execute(query).unwrap();
+        let result = session
+            .execute("MATCH (n:Person) RETURN n.created, n.matched")
+            .unwrap();
+        assert_eq!(result.rows[0], vec![Value::Bool(true), Value::Null]);
+
+        // Second run takes the match path
+        session.execute(query).unwrap();
+        let result = session
+            .execute("MATCH (n:Person) RETURN n.created, n.matched")
+            .unwrap();
+        assert_eq!(result.row_count(), 1);
+        assert_eq!(result.rows[0], vec![Value::Bool(true), Value::Bool(true)]);
+    }
+
+    #[test]
+    fn test_merge_with_parameter_key() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+        let params = std::collections::HashMap::from([(
+            "e".to_string(),
+            Value::String("bob@example.com".into()),
+        )]);
+
+        session
+            .execute("INSERT (:Person {email: 'alice@example.com'})")
+            .unwrap();
+        session
+            .execute_with_params("MERGE (n:Person {email: $e}) RETURN n", params)
+            .unwrap();
+
+        let result = session.execute("MATCH (n:Person) RETURN n").unwrap();
+        assert_eq!(result.row_count(), 2, "Parameter key must not match Alice");
+    }
+
+    #[test]
+    fn test_merge_without_key_is_rejected() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+
+        assert!(session.execute("MERGE (n:Person) RETURN n").is_err());
+    }
+
+    #[test]
+    fn test_concurrent_merges_do_not_double_insert() {
+        let db = GrafeoDB::new_in_memory();
+        let mut first = db.session();
+        let mut second = db.session();
+        let query = "MERGE (n:Person {email: 'alice@example.com'}) RETURN n";
+
+        first.begin_tx().unwrap();
+        second.begin_tx().unwrap();
+        first.execute(query).unwrap();
+        second.execute(query).unwrap();
+
+        first.commit().unwrap();
+        assert!(
+            second.commit().is_err(),
+            "Second merge on the same key must conflict"
+        );
+
+        let result = db.session().execute("MATCH (n:Person) RETURN n").unwrap();
+        assert_eq!(result.row_count(), 1);
+    }
+
+    #[test]
+    fn test_merge_set_expressions() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+        let query = "MERGE (c:Counter {k: 'b'}) \
+                     ON CREATE SET c.tag = toUpper(c.k), c.n = 1 \
+                     ON MATCH SET c.n = c.n + 1 \
+                     RETURN c.tag, c.n";
+
+        let result = session.execute(query).unwrap();
+        assert_eq!(
+            result.rows[0],
+            vec![Value::String("B".into()), Value::Int64(1)]
+        );
+
+        let result = session.execute(query).unwrap();
+        assert_eq!(
+            result.rows[0],
+            vec![Value::String("B".into()), Value::Int64(2)]
+        );
+    }
+
+    #[test]
+    fn test_merge_rejects_non_literal_key() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+
+        let error = session
+            .execute("MERGE (c:Counter {k: toUpper('b')}) RETURN c")
+            .unwrap_err();
+        assert!(
+            matches!(&error, Error::Query(e) if e.kind == QueryErrorKind::Semantic),
+            "expected a semantic error, got {error:?}"
+        );
+    }
+
+    #[test]
+    fn test_explain_merge_claims_no_key() {
+        let db = GrafeoDB::new_in_memory();
+        let mut explaining = db.session();
+        let mut merging = db.session();
+        let query = "MERGE (n:Person {email: 'alice@example.com'}) RETURN n";
+
+        explaining.begin_tx().unwrap();
+        explaining.explain(query).unwrap();
+
+        merging.begin_tx().unwrap();
+        merging.execute(query).unwrap();
+        merging.commit().unwrap();
+
+        // The explained merge never ran, so it has nothing to conflict with
+        explaining.commit().unwrap();
+    }
+}
+
+// ============================================================================
+// Cypher Tests
+// ============================================================================
+
+#[cfg(feature = "cypher")]
+mod cypher_tests {
+    use super::*;
+
+    #[test]
+    fn test_match_all_nodes() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session.execute_cypher("MATCH (n) RETURN n").unwrap();
+        assert_eq!(result.row_count(), 5, "Should find 5 nodes");
+    }
+
+    #[test]
+    fn test_match_nodes_by_label() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session.execute_cypher("MATCH (n:Person) RETURN n").unwrap();
+        assert_eq!(result.row_count(), 3, "Should find 3 Person nodes");
+    }
+
+    #[test]
+    fn test_match_with_property_filter() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session
+            .execute_cypher("MATCH (n:Person) WHERE n.age > 28 RETURN n.name")
+            .unwrap();
+        assert_eq!(result.row_count(), 2, "Should find 2 people older than 28");
+    }
+
+    #[test]
+    fn test_match_relationship_pattern() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session
+            .execute_cypher("MATCH (a:Person)-[:KNOWS]->(b:Person) RETURN a.name, b.name")
+            .unwrap();
+        assert_eq!(result.row_count(), 3, "Should find 3 KNOWS relationships");
+    }
+
+    #[test]
+    fn test_count() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session
+            .execute_cypher("MATCH (n:Person) RETURN count(n)")
+            .unwrap();
+
+        assert_eq!(result.row_count(), 1);
+        if let Value::Int64(count) = &result.rows[0][0] {
+            assert_eq!(*count, 3);
+        }
+    }
+
+    #[test]
+    fn test_create_node() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+
+        session
+            .execute_cypher("CREATE (:Person {name: 'Alice', age: 30})")
+            .unwrap();
+
+        let result = session
+            .execute_cypher("MATCH (n:Person) RETURN n.name, n.age")
+            .unwrap();
+        assert_eq!(result.row_count(), 1);
+    }
+
+    #[test]
+    fn test_unwind_literal_list() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+
+        let result = session
+            .execute_cypher("UNWIND [1, 2, 3] AS x RETURN x")
+            .unwrap();
+        assert_eq!(
+            result.rows,
+            vec![
+                vec![Value::Int64(1)],
+                vec![Value::Int64(2)],
+                vec![Value::Int64(3)],
+            ]
+        );
+
+        let result = session.execute_cypher("UNWIND [] AS x RETURN x").unwrap();
+        assert_eq!(result.row_count(), 0, "Empty list yields no rows");
+    }
+
+    #[test]
+    fn test_unwind_property_after_match() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+        session.create_node_with_props(
+            &["Post"],
+            [
+                ("title", Value::String("a".into())),
+                (
+                    "tags",
+                    Value::List(vec![Value::String("x".into()), Value::String("y".into())].into()),
+                ),
+            ],
+        );
+        session.create_node_with_props(
+            &["Post"],
+            [
+                ("title", Value::String("b".into())),
+                ("tags", Value::List(vec![].into())),
+            ],
+        );
+
+        let result = session
+            .execute_cypher("MATCH (p:Post) UNWIND p.tags AS tag RETURN p.title, tag")
+            .unwrap();
+        assert_eq!(
+            result.rows,
+            vec![
+                vec![Value::String("a".into()), Value::String("x".into())],
+                vec![Value::String("a".into()), Value::String("y".into())],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unwind_non_list_fails() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+
+        assert!(session.execute_cypher("UNWIND 5 AS x RETURN x").is_err());
+    }
+
+    #[test]
+    fn test_undirected_edge_type_matches_both_ways() {
+        let db = GrafeoDB::new_in_memory();
+        db.set_edge_type_undirected("FRIEND");
+        let session = db.session();
+        let alice =
+            session.create_node_with_props(&["Person"], [("name", Value::String("Alice".into()))]);
+        let bob =
+            session.create_node_with_props(&["Person"], [("name", Value::String("Bob".into()))]);
+        session.create_edge(alice, bob, "FRIEND");
+
+        for pattern in ["-[:FRIEND]->", "<-[:FRIEND]-", "-[:FRIEND]-"] {
+            let query = format!("MATCH (:Person {{name: 'Bob'}}){pattern}(p:Person) RETURN p.name");
+            let result = session.execute_cypher(&query).unwrap();
+            assert_eq!(
+                result.rows,
+                vec![vec![Value::String("Alice".into())]],
+                "{pattern}"
+            );
+        }
+        assert_eq!(db.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_create_edge_between_new_nodes() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+
+        session
+            .execute_cypher("CREATE (:City {name: 'Oslo'})<-[:LIVES_IN]-(:Person {name: 'Alice'})")
+            .unwrap();
+
+        assert_eq!(db.node_count(), 2);
+        assert_eq!(db.edge_count(), 1);
+        let result = session
+            .execute_cypher("MATCH (p:Person)-[:LIVES_IN]->(c:City) RETURN p.name, c.name")
+            .unwrap();
+        assert_eq!(
+            result.rows,
+            vec![vec![
+                Value::String("Alice".into()),
+                Value::String("Oslo".into())
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_match_create_edge_reuses_bound_nodes() {
+        let db = create_social_network();
+        let session = db.session();
+
+        session
+            .execute_cypher(
+                "MATCH (a:Person {name: 'Carol'}), (b:Person {name: 'Bob'}) \
+                 CREATE (a)-[:KNOWS {since: 2021}]->(b)",
+            )
+            .unwrap();
+
+        assert_eq!(db.node_count(), 5, "no endpoint is recreated");
+        assert_eq!(db.edge_count(), 7);
+        let result = session
+            .execute_cypher("MATCH (:Person {name: 'Carol'})-[e:KNOWS]->(b) RETURN b.name, e.since")
+            .unwrap();
+        assert_eq!(
+            result.rows,
+            vec![vec![Value::String("Bob".into()), Value::Int64(2021)]]
+        );
+    }
+
+    #[test]
+    fn test_with_order_by_limit_create_reuses_bound_node() {
+        let db = create_social_network();
+        let session = db.session();
+
+        session
+            .execute_cypher(
+                "MATCH (a:Person) WITH a ORDER BY a.age LIMIT 1 \
+                 CREATE (a)-[:OWNS]->(:Car {model: 'Golf'})",
+            )
+            .unwrap();
+
+        assert_eq!(db.node_count(), 6, "only the car is created");
+        assert_eq!(db.edge_count(), 7);
+        let result = session
+            .execute_cypher("MATCH (p:Person)-[:OWNS]->(c:Car) RETURN p.name, c.model")
+            .unwrap();
+        assert_eq!(
+            result.rows,
+            vec![vec![
+                Value::String("Bob".into()),
+                Value::String("Golf".into())
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_with_distinct_create_reuses_bound_node() {
+        let db = create_social_network();
+        let session = db.session();
+
+        // Alice and Bob both work at TechCorp: two rows, one distinct company.
+        session
+            .execute_cypher(
+                "MATCH (:Person)-[:WORKS_AT]->(c:Company) WITH DISTINCT c \
+                 CREATE (c)-[:LOCATED_IN]->(:City)",
+            )
+            .unwrap();
+
+        assert_eq!(db.node_count(), 7, "one city per distinct company");
+        assert_eq!(db.edge_count(), 8);
+        let mut rows = session
+            .execute_cypher("MATCH (c:Company)-[:LOCATED_IN]->(:City) RETURN c.name")
+            .unwrap()
+            .rows;
+        rows.sort_by_key(|row| format!("{row:?}"));
+        assert_eq!(
+            rows,
+            vec![
+                vec![Value::String("Startup".into())],
+                vec![Value::String("TechCorp".into())]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_merge_keeps_and_replace_clears_properties() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+        let props = |name: &str| {
+            [
+                ("name", Value::String(name.into())),
+                ("age", Value::Int64(30)),
+                ("city", Value::String("Oslo".into())),
+            ]
+        };
+        let merged = session.create_node_with_props(&["Customer"], props("Alice"));
+        let replaced = session.create_node_with_props(&["Supplier"], props("Bob"));
+
+        // Merge keeps existing properties; null removes one
+        session
+            .execute_cypher("MATCH (n:Customer) SET n += {age: 31, city: null, email: 'a@x.org'}")
+            .unwrap();
+        let node = db.get_node(merged).unwrap();
+        assert_eq!(
+            node.get_property("name"),
+            Some(&Value::String("Alice".into()))
+        );
+        assert_eq!(node.get_property("age"), Some(&Value::Int64(31)));
+        assert_eq!(node.get_property("city"), None);
+        assert_eq!(
+            node.get_property("email"),
+            Some(&Value::String("a@x.org".into()))
+        );
+
+        // Replace clears everything not in the map
+        session
+            .execute_cypher("MATCH (n:Supplier) SET n = {age: 40}")
+            .unwrap();
+        let node = db.get_node(replaced).unwrap();
+        assert_eq!(node.properties.len(), 1);
+        assert_eq!(node.get_property("age"), Some(&Value::Int64(40)));
+    }
+
+    #[test]
+    fn test_remove_labels_and_properties() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+        let alice = session.create_node_with_props(
+            &["Person", "Employee"],
+            [
+                ("name", Value::String("Alice".into())),
+                ("age", Value::Int64(30)),
+            ],
+        );
+        // Bob lacks the Employee label, which must not be an error
+        let bob = session.create_node_with_props(
+            &["Person"],
+            [
+                ("name", Value::String("Bob".into())),
+                ("age", Value::Int64(25)),
+            ],
+        );
+
+        session
+            .execute_cypher("MATCH (n:Person) REMOVE n:Employee, n.age")
+            .unwrap();
+
+        for id in [alice, bob] {
+            let node = db.get_node(id).unwrap();
+            assert_eq!(node.labels.len(), 1);
+            assert!(node.has_label("Person"));
+            assert_eq!(node.get_property("age"), None);
+            assert!(node.get_property("name").is_some());
+        }
+        let result = session
+            .execute_cypher("MATCH (n:Employee) RETURN n")
+            .unwrap();
+        assert_eq!(result.row_count(), 0);
+    }
+
+    #[test]
+    fn test_set_and_remove_label_pass_rows_through() {
+        let db = GrafeoDB::new_in_memory();
+        let session = db.session();
+        session.create_node_with_props(&["Person"], [("name", Value::String("Alice".into()))]);
+
+        let added = session
+            .execute_cypher("MATCH (n:Person) SET n:Admin RETURN n.name")
+            .unwrap();
+        assert_eq!(added.rows, vec![vec![Value::String("Alice".into())]]);
+
+        let removed = session
+            .execute_cypher("MATCH (n:Person) REMOVE n:Admin RETURN n.name")
+            .unwrap();
+        assert_eq!(removed.rows, added.rows);
+        assert_eq!(
+            session
+                .execute_cypher("MATCH (n:Admin) RETURN n")
+                .unwrap()
+                .row_count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_two_hop_path() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session
+            .execute_cypher("MATCH (a:Person)-[:KNOWS]->(b:Person)-[:KNOWS]->(c:Person) RETURN a.name, b.name, c.name")
+            .unwrap();
+
+        assert!(
+            result.row_count() >= 1,
+            "Should find at least one 2-hop path"
+        );
+    }
+}
+
+// ============================================================================
+// Gremlin Tests
+// ============================================================================
+
+#[cfg(feature = "gremlin")]
+mod gremlin_tests {
+    use super::*;
+
+    // Note: Many Gremlin tests are currently expected to fail due to variable
+    // binding issues in the Gremlin executor. These tests document expected
+    // behavior for when the executor is fully implemented.
+
+    #[test]
+    fn test_gremlin_parser() {
+        // Verify Gremlin parsing works (execution may fail)
+        let db = create_social_network();
+        let session = db.session();
+
+        // Test that we can at least parse basic Gremlin syntax
+        let result = session.execute_gremlin("g.V()");
+        // The translator should work even if execution fails
+        assert!(
+            result.is_ok() || result.is_err(),
+            "Gremlin should be parsed"
+        );
+    }
+
+    #[test]
+    #[ignore = "Gremlin executor variable binding not fully implemented"]
+    fn test_v_all_nodes() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session.execute_gremlin("g.V()").unwrap();
+        assert_eq!(result.row_count(), 5, "Should find 5 vertices");
+    }
+
+    #[test]
+    #[ignore = "Gremlin executor variable binding not fully implemented"]
+    fn test_v_has_label() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session.execute_gremlin("g.V().hasLabel('Person')").unwrap();
+        assert_eq!(result.row_count(), 3, "Should find 3 Person vertices");
+    }
+
+    #[test]
+    #[ignore = "Gremlin predicate parsing not fully implemented"]
+    fn test_v_has_property() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session
+            .execute_gremlin("g.V().hasLabel('Person').has('age', gt(28))")
+            .unwrap();
+        assert_eq!(result.row_count(), 2, "Should find 2 people with age > 28");
+    }
+
+    #[test]
+    #[ignore = "Gremlin executor variable binding not fully implemented"]
+    fn test_out_step() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session
+            .execute_gremlin("g.V().hasLabel('Person').out('KNOWS')")
+            .unwrap();
+        assert_eq!(result.row_count(), 3, "Should find 3 outgoing KNOWS edges");
+    }
+
+    #[test]
+    #[ignore = "Gremlin executor variable binding not fully implemented"]
+    fn test_values_step() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session
+            .execute_gremlin("g.V().hasLabel('Person').values('name')")
+            .unwrap();
+        assert_eq!(result.row_count(), 3, "Should return 3 names");
+    }
+
+    #[test]
+    #[ignore = "Gremlin executor variable binding not fully implemented"]
+    fn test_limit_step() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session.execute_gremlin("g.V().limit(2)").unwrap();
+        assert_eq!(result.row_count(), 2, "Should limit to 2 results");
+    }
+
+    #[test]
+    #[ignore = "Gremlin executor variable binding not fully implemented"]
+    fn test_count() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let result = session
+            .execute_gremlin("g.V().hasLabel('Person').count()")
+            .unwrap();
+
+        assert_eq!(result.row_count(), 1);
+        if let Value::Int64(count) = &result.rows[0][0] {
+            assert_eq!(*count, 3);
         }
     }
 
@@ -870,6 +1901,492 @@ mod graphql_tests {
 
         assert!(result.row_count() >= 1, "Should return nested results");
     }
+
+    #[test]
+    fn test_query_by_id_with_absent_field() {
+        let db = create_social_network();
+        let session = db.session();
+
+        let ids = session.execute_graphql("query { person { id } }").unwrap();
+        let id = match &ids.rows[0][0] {
+            Value::Int64(id) => *id,
+            other => panic!("Expected integer id, got {other:?}"),
+        };
+
+        let result = session
+            .execute_graphql(&format!("query {{ person(id: {id}) {{ name nickname }} }}"))
+            .unwrap();
+        assert_eq!(result.row_count(), 1);
+        assert_eq!(
+            result.rows[0][1],
+            Value::Null,
+            "Absent fields project to null"
+        );
+    }
+}
+
+// ============================================================================
+// SPARQL Tests
+// ============================================================================
+
+#[cfg(all(feature = "sparql", feature = "rdf"))]
+mod sparql_tests {
+    use super::*;
+    use grafeo_core::graph::rdf::{Term, Triple};
+
+    const FOAF: &str = "http://xmlns.com/foaf/0.1/";
+
+    /// Creates a database whose RDF store holds three people:
+    /// - alice: name "Alice", mbox "alice@example.org", knows bob
+    /// - bob: name "Bob", mbox "bob@example.org"
+    /// - carol: name "Carol"
+    fn create_foaf_store() -> GrafeoDB {
+        let db = GrafeoDB::new_in_memory();
+        let store = db.rdf_store();
+        let person = |name: &str| Term::iri(format!("http://example.org/{name}"));
+        let foaf = |local: &str| Term::iri(format!("{FOAF}{local}"));
+
+        store.insert(Triple::new(
+            person("alice"),
+            foaf("name"),
+            Term::literal("Alice"),
+        ));
+        store.insert(Triple::new(
+            person("bob"),
+            foaf("name"),
+            Term::literal("Bob"),
+        ));
+        store.insert(Triple::new(
+            person("carol"),
+            foaf("name"),
+            Term::literal("Carol"),
+        ));
+        store.insert(Triple::new(
+            person("alice"),
+            foaf("mbox"),
+            Term::literal("alice@example.org"),
+        ));
+        store.insert(Triple::new(
+            person("bob"),
+            foaf("mbox"),
+            Term::literal("bob@example.org"),
+        ));
+        store.insert(Triple::new(person("alice"), foaf("knows"), person("bob")));
+        db
+    }
+
+    fn sorted_names(result: &grafeo_engine::database::QueryResult) -> Vec<String> {
+        let mut names: Vec<String> = result
+            .rows
+            .iter()
+            .map(|row| match &row[0] {
+                Value::String(s) => s.to_string(),
+                other => panic!("Expected string name, got {other:?}"),
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_optional_keeps_unmatched_rows() {
+        let db = create_foaf_store();
+
+        let result = db
+            .execute_sparql(
+                r#"
+                PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+                SELECT ?name ?mbox WHERE {
+                    ?x foaf:name ?name
+                    OPTIONAL { ?x foaf:mbox ?mbox }
+                }
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(result.row_count(), 3, "Carol has no mbox but must be kept");
+        let carol = result
+            .rows
+            .iter()
+            .find(|row| row[0] == Value::String("Carol".into()))
+            .expect("Carol should be present");
+        assert_eq!(carol[1], Value::Null);
+        let alice = result
+            .rows
+            .iter()
+            .find(|row| row[0] == Value::String("Alice".into()))
+            .expect("Alice should be present");
+        assert_eq!(alice[1], Value::String("alice@example.org".into()));
+    }
+
+    #[test]
+    fn test_minus_removes_matching_rows() {
+        let db = create_foaf_store();
+
+        let result = db
+            .execute_sparql(
+                r#"
+                PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+                SELECT ?name WHERE {
+                    ?x foaf:name ?name
+                    MINUS { ?x foaf:mbox ?mbox }
+                }
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(sorted_names(&result), vec!["Carol"]);
+    }
+
+    #[test]
+    fn test_minus_without_shared_variables_keeps_all_rows() {
+        let db = create_foaf_store();
+
+        let result = db
+            .execute_sparql(
+                r#"
+                PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+                SELECT ?name WHERE {
+                    ?x foaf:name ?name
+                    MINUS { ?a foaf:knows ?b }
+                }
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(sorted_names(&result), vec!["Alice", "Bob", "Carol"]);
+    }
+
+    #[test]
+    fn test_values_joins_pattern_with_undef_wildcard() {
+        let db = create_foaf_store();
+
+        let result = db
+            .execute_sparql(
+                r#"
+                PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+                SELECT ?name ?mbox WHERE {
+                    VALUES (?name ?mbox) {
+                        ("Alice" UNDEF)
+                        (UNDEF "bob@example.org")
+                        ("Carol" "carol@example.org")
+                    }
+                    ?x foaf:name ?name .
+                    ?x foaf:mbox ?mbox
+                }
+                "#,
+            )
+            .unwrap();
+
+        // UNDEF matches any value and binds from the triple pattern; Carol has
+        // no mbox, so her row is dropped
+        let mut rows = result.rows.clone();
+        rows.sort_by_key(|row| format!("{row:?}"));
+        assert_eq!(
+            rows,
+            vec![
+                vec![Value::from("Alice"), Value::from("alice@example.org")],
+                vec![Value::from("Bob"), Value::from("bob@example.org")],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_construct_rewrites_triples() {
+        let db = create_foaf_store();
+
+        let result = db
+            .execute_sparql(
+                r#"
+                PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+                PREFIX ex: <http://example.org/>
+                CONSTRUCT {
+                    ?x ex:label ?name .
+                    ?x ex:contact ?mbox .
+                } WHERE {
+                    ?x foaf:name ?name
+                    OPTIONAL { ?x foaf:mbox ?mbox }
+                }
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(result.columns, vec!["subject", "predicate", "object"]);
+        let mut triples: Vec<Vec<Value>> = result.rows.clone();
+        triples.sort_by_key(|row| format!("{row:?}"));
+        let triple = |person: &str, predicate: &str, object: &str| {
+            vec![
+                Value::from(format!("http://example.org/{person}")),
+                Value::from(format!("http://example.org/{predicate}")),
+                Value::from(object),
+            ]
+        };
+        // Carol has no mbox, so her contact triple is skipped, not left partial
+        let mut expected = vec![
+            triple("alice", "label", "Alice"),
+            triple("alice", "contact", "alice@example.org"),
+            triple("bob", "label", "Bob"),
+            triple("bob", "contact", "bob@example.org"),
+            triple("carol", "label", "Carol"),
+        ];
+        expected.sort_by_key(|row| format!("{row:?}"));
+        assert_eq!(triples, expected);
+
+        // Constructing doesn't modify the store
+        assert_eq!(db.rdf_store().len(), 6);
+    }
+
+    #[test]
+    fn test_construct_mints_blank_nodes_per_solution() {
+        let db = create_foaf_store();
+
+        let result = db
+            .execute_sparql(
+                r#"
+                PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+                PREFIX ex: <http://example.org/>
+                CONSTRUCT { _:card ex:of ?x . _:card ex:name ?name . }
+                WHERE { ?x foaf:name ?name }
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(result.row_count(), 6);
+        let mut cards: Vec<String> = result
+            .rows
+            .iter()
+            .map(|row| match &row[0] {
+                Value::String(s) => s.to_string(),
+                other => panic!("Expected blank node, got {other:?}"),
+            })
+            .collect();
+        cards.sort();
+        cards.dedup();
+        assert_eq!(cards.len(), 3, "one fresh blank node per solution");
+        assert!(cards.iter().all(|card| card.starts_with("_:")));
+    }
+
+    #[test]
+    fn test_construct_blank_nodes_do_not_collide_with_data() {
+        let db = GrafeoDB::new_in_memory();
+        // Named like a minted blank node would be under a per-solution scheme
+        db.rdf_store().insert(Triple::new(
+            Term::blank("card_1"),
+            Term::iri("http://example.org/name"),
+            Term::literal("Existing"),
+        ));
+        let query = r#"
+            PREFIX ex: <http://example.org/>
+            CONSTRUCT { _:card ex:copyOf ?s . }
+            WHERE { ?s ex:name ?name }
+        "#;
+
+        let first = db.execute_sparql(query).unwrap();
+        let second = db.execute_sparql(query).unwrap();
+
+        assert_eq!(first.rows.len(), 1);
+        let minted = &first.rows[0][0];
+        assert_ne!(minted, &first.rows[0][2], "minted node reuses a data id");
+        assert_eq!(first.rows[0][2], Value::String("_:card_1".into()));
+        assert_ne!(minted, &second.rows[0][0], "queries share blank nodes");
+    }
+
+    #[test]
+    fn test_ask_returns_boolean() {
+        let db = create_foaf_store();
+        let session = db.session();
+
+        let found = session
+            .execute_sparql(
+                r#"
+                PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+                ASK { ?x foaf:knows ?y }
+                "#,
+            )
+            .unwrap();
+        assert_eq!(found.as_bool(), Some(true));
+        assert_eq!(found.rows, vec![vec![Value::Bool(true)]]);
+
+        let missing = session
+            .execute_sparql(
+                r#"
+                PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+                ASK { ?x foaf:knows <http://example.org/carol> }
+                "#,
+            )
+            .unwrap();
+        assert_eq!(missing.as_bool(), Some(false));
+        assert_eq!(missing.rows, vec![vec![Value::Bool(false)]]);
+    }
+
+    #[test]
+    fn test_ask_filter_rejecting_everything_is_false() {
+        let db = create_foaf_store();
+
+        for filter in [r#"FILTER(?name = "Dave")"#, "FILTER(false)"] {
+            let query = format!(
+                "PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+                 ASK {{ ?x foaf:name ?name {filter} }}"
+            );
+            let result = db.execute_sparql(&query).unwrap();
+            assert_eq!(result.as_bool(), Some(false), "{filter}");
+        }
+    }
+
+    #[test]
+    fn test_ask_empty_pattern_is_true() {
+        let db = GrafeoDB::new_in_memory();
+
+        let result = db.execute_sparql("ASK {}").unwrap();
+        assert_eq!(result.as_bool(), Some(true));
+    }
+
+    /// Creates a database whose RDF store holds a `knows` chain
+    /// a -> b -> c -> d, with `d` also named "Dee".
+    fn create_knows_chain() -> GrafeoDB {
+        let db = GrafeoDB::new_in_memory();
+        let store = db.rdf_store();
+        let person = |name: &str| Term::iri(format!("http://example.org/{name}"));
+        let knows = Term::iri(format!("{FOAF}knows"));
+        for (from, to) in [("a", "b"), ("b", "c"), ("c", "d")] {
+            store.insert(Triple::new(person(from), knows.clone(), person(to)));
+        }
+        store.insert(Triple::new(
+            person("d"),
+            Term::iri(format!("{FOAF}name")),
+            Term::literal("Dee"),
+        ));
+        db
+    }
+
+    /// Runs a query and returns its rows as sorted strings, with the
+    /// example.org prefix dropped.
+    fn sorted_rows(db: &GrafeoDB, query: &str) -> Vec<String> {
+        let result = db
+            .execute_sparql(&format!(
+                "PREFIX foaf: <{FOAF}> PREFIX ex: <http://example.org/> {query}"
+            ))
+            .unwrap();
+        let mut rows: Vec<String> = result
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|value| match value {
+                        Value::String(s) => s.trim_start_matches("http://example.org/").to_string(),
+                        other => panic!("Expected string, got {other:?}"),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        rows.sort();
+        rows
+    }
+
+    #[test]
+    fn test_one_or_more_path() {
+        let db = create_knows_chain();
+
+        assert_eq!(
+            sorted_rows(&db, "SELECT ?y WHERE { ex:a foaf:knows+ ?y }"),
+            vec!["b", "c", "d"]
+        );
+        assert_eq!(
+            sorted_rows(&db, "SELECT ?x WHERE { ?x foaf:knows+ ex:c }"),
+            vec!["a", "b"]
+        );
+        assert_eq!(
+            sorted_rows(&db, "SELECT ?x ?y WHERE { ?x foaf:knows+ ?y }"),
+            vec!["a b", "a c", "a d", "b c", "b d", "c d"]
+        );
+        assert_eq!(
+            sorted_rows(&db, "SELECT ?x WHERE { ex:c ^foaf:knows+ ?x }"),
+            vec!["a", "b"]
+        );
+        // The path joins with the rest of the pattern
+        assert_eq!(
+            sorted_rows(
+                &db,
+                "SELECT ?x WHERE { ?x foaf:knows+ ?y . ?y foaf:name \"Dee\" }"
+            ),
+            vec!["a", "b", "c"]
+        );
+        let result = db
+            .execute_sparql(
+                "PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+                 ASK { <http://example.org/d> foaf:knows+ <http://example.org/a> }",
+            )
+            .unwrap();
+        assert_eq!(result.as_bool(), Some(false));
+    }
+
+    #[test]
+    fn test_zero_or_more_path() {
+        let db = create_knows_chain();
+
+        assert_eq!(
+            sorted_rows(&db, "SELECT ?y WHERE { ex:b foaf:knows* ?y }"),
+            vec!["b", "c", "d"]
+        );
+        assert_eq!(
+            sorted_rows(&db, "SELECT ?x WHERE { ?x foaf:knows* ex:a }"),
+            vec!["a"]
+        );
+        // Every resource in the store reaches itself, the literal included
+        assert_eq!(
+            sorted_rows(&db, "SELECT ?x ?y WHERE { ?x foaf:knows* ?y }"),
+            vec![
+                "Dee Dee", "a a", "a b", "a c", "a d", "b b", "b c", "b d", "c c", "c d", "d d"
+            ]
+        );
+        assert_eq!(
+            sorted_rows(&db, "SELECT ?y WHERE { ex:a foaf:knows? ?y }"),
+            vec!["a", "b"]
+        );
+    }
+
+    #[test]
+    fn test_repeated_path_on_a_cycle() {
+        let db = create_knows_chain();
+        db.rdf_store().insert(Triple::new(
+            Term::iri("http://example.org/d"),
+            Term::iri(format!("{FOAF}knows")),
+            Term::iri("http://example.org/b"),
+        ));
+
+        // Each pair is matched once, however many paths link it
+        assert_eq!(
+            sorted_rows(&db, "SELECT ?y WHERE { ex:c foaf:knows+ ?y }"),
+            vec!["b", "c", "d"]
+        );
+        assert_eq!(
+            sorted_rows(&db, "SELECT ?x WHERE { ?x foaf:knows+ ?x }"),
+            vec!["b", "c", "d"]
+        );
+    }
+
+    #[test]
+    fn test_session_query_timeout() {
+        let db = GrafeoDB::new_in_memory();
+        for i in 0..100 {
+            db.rdf_store().insert(Triple::new(
+                Term::iri(format!("http://example.org/p{i}")),
+                Term::iri(format!("{FOAF}name")),
+                Term::literal(format!("Person {i}")),
+            ));
+        }
+        let mut session = db.session();
+        session.set_query_timeout(Some(std::time::Duration::from_millis(1)));
+
+        // A million-row cross product can't finish in a millisecond
+        let result =
+            session.execute_sparql("SELECT ?a ?b ?c WHERE { ?a ?p ?x . ?b ?q ?y . ?c ?r ?z }");
+        assert!(
+            matches!(result, Err(grafeo_common::utils::error::Error::Timeout)),
+            "expected a timeout, got {result:?}"
+        );
+    }
 }
 
 // ============================================================================
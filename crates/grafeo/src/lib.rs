@@ -37,8 +37,12 @@
 
 // Re-export the main database API
 pub use grafeo_engine::{
-    Catalog, CatalogError, Config, GrafeoDB, IndexDefinition, IndexType, Session,
+    Catalog, CatalogError, Config, GrafeoDB, IndexDefinition, IndexType, ReadSnapshot, Session,
+    Transaction,
 };
 
+// Parameters for `Session::execute_with_params`
+pub use grafeo_engine::query::QueryParams;
+
 // Re-export core types - you'll need these for working with IDs and values
 pub use grafeo_common::types::{EdgeId, NodeId, Value};
@@ -12,6 +12,8 @@ This module defines test logic for graph algorithms:
 from abc import ABC, abstractmethod
 import pytest
 
+from grafeo import CycleError
+
 
 class BaseAlgorithmsTest(ABC):
     """Abstract base class for algorithm tests."""
@@ -106,11 +108,27 @@ class BaseAlgorithmsTest(ABC):
         graph_info = self.setup_algorithm_graph(db)
         node_ids = graph_info["node_ids"]
 
-        topo = db.algorithms.topological_sort()
-        # May return None if graph has cycle
-        if topo is not None:
+        # A random graph may have a cycle, which raises CycleError
+        try:
+            topo = db.algorithms.topological_sort()
+        except CycleError as err:
+            assert len(err.args[1]) >= 1, "CycleError should carry the cycle"
+        else:
             assert len(topo) == len(node_ids)
 
+    def test_topological_sort_reports_cycle(self, db):
+        """Test that a cycle is raised with its node IDs."""
+        a = db.create_node(["Task"]).id
+        b = db.create_node(["Task"]).id
+        c = db.create_node(["Task"]).id
+        db.create_edge(a, b, "BEFORE")
+        db.create_edge(b, c, "BEFORE")
+        db.create_edge(c, b, "BEFORE")
+
+        with pytest.raises(CycleError) as excinfo:
+            db.algorithms.topological_sort()
+        assert sorted(excinfo.value.args[1]) == sorted([b, c])
+
     # ===== Shortest Path Tests =====
 
     def test_dijkstra(self, db):
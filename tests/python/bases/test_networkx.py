@@ -9,6 +9,7 @@ Tests cover:
 - Centrality: Degree, PageRank, Betweenness, Closeness
 - Components: Connected, Strongly Connected
 - MST: Kruskal/Prim
+- Attributes: round-trips through to_networkx() and from_networkx()
 """
 
 from abc import ABC, abstractmethod
@@ -255,6 +256,106 @@ class BaseNetworkXComparisonTest(ABC):
             )
 
 
+    # ===== Attribute Round-Trip Tests =====
+
+    def _setup_attributed_graph(self, db) -> dict:
+        """Create a small labeled graph with node and edge properties."""
+        alice = db.create_node(["Person"], {"name": "Alice", "age": 30, "tags": ["admin", "dev"]})
+        bob = db.create_node(["Person", "Employee"], {"name": "Bob", "score": 4.5})
+        acme = db.create_node(["Company"], {"name": "Acme", "public": True})
+        db.create_edge(alice.id, bob.id, "KNOWS", {"since": 2020})
+        db.create_edge(bob.id, acme.id, "WORKS_AT", {"role": "engineer"})
+        return {"alice": alice.id, "bob": bob.id, "acme": acme.id}
+
+    @pytest.mark.skipif(not NETWORKX_AVAILABLE, reason="NetworkX not installed")
+    def test_to_networkx_attributes(self, db):
+        """Properties should become NetworkX node and edge attributes."""
+        ids = self._setup_attributed_graph(db)
+        G = db.as_networkx().to_networkx()
+
+        assert G.nodes[ids["alice"]]["name"] == "Alice"
+        assert G.nodes[ids["alice"]]["age"] == 30
+        assert G.nodes[ids["alice"]]["tags"] == ["admin", "dev"]
+        assert sorted(G.nodes[ids["bob"]]["labels"]) == ["Employee", "Person"]
+        assert G.nodes[ids["acme"]]["public"] is True
+        assert G.edges[ids["alice"], ids["bob"]]["type"] == "KNOWS"
+        assert G.edges[ids["alice"], ids["bob"]]["since"] == 2020
+        assert G.edges[ids["bob"], ids["acme"]]["role"] == "engineer"
+
+    @pytest.mark.skipif(not NETWORKX_AVAILABLE, reason="NetworkX not installed")
+    def test_networkx_attribute_round_trip(self, db):
+        """Exporting to NetworkX and importing back should keep labels and attributes."""
+        from grafeo.grafeo import NetworkXAdapter
+
+        self._setup_attributed_graph(db)
+        G = db.as_networkx().to_networkx()
+        H = NetworkXAdapter.from_networkx(G).to_networkx()
+
+        def node_key(attrs):
+            return attrs["name"]
+
+        before = sorted((dict(a) for _, a in G.nodes(data=True)), key=node_key)
+        after = sorted((dict(a) for _, a in H.nodes(data=True)), key=node_key)
+        for attrs in before + after:
+            attrs["labels"] = sorted(attrs["labels"])
+        assert after == before
+
+        def edge_set(graph):
+            names = {n: a["name"] for n, a in graph.nodes(data=True)}
+            return sorted(
+                (names[u], names[v], sorted(a.items()))
+                for u, v, a in graph.edges(data=True)
+            )
+
+        assert edge_set(H) == edge_set(G)
+
+    @pytest.mark.skipif(not NETWORKX_AVAILABLE, reason="NetworkX not installed")
+    def test_networkx_multigraph_round_trip(self, db):
+        """Parallel edges should survive a round trip through a MultiDiGraph."""
+        from grafeo.grafeo import NetworkXAdapter
+
+        a = db.create_node(["Node"], {"name": "a"})
+        b = db.create_node(["Node"], {"name": "b"})
+        db.create_edge(a.id, b.id, "ROAD", {"km": 10})
+        db.create_edge(a.id, b.id, "ROAD", {"km": 12})
+
+        M = db.as_networkx().to_networkx(multigraph=True)
+        assert M.number_of_edges(a.id, b.id) == 2
+
+        adapter = NetworkXAdapter.from_networkx(M)
+        assert adapter.number_of_edges == 2
+        kms = sorted(d["km"] for _, _, d in adapter.to_networkx(multigraph=True).edges(data=True))
+        assert kms == [10, 12]
+
+    @pytest.mark.skipif(not NETWORKX_AVAILABLE, reason="NetworkX not installed")
+    def test_from_networkx_label_attribute(self, db):
+        """A label attribute mapping should turn an attribute into labels."""
+        from grafeo.grafeo import NetworkXAdapter
+
+        G = nx.DiGraph()
+        G.add_node("berlin", kind="City", population=3_700_000)
+        G.add_node("germany", kind=["Country", "Place"])
+        G.add_edge("berlin", "germany", type="IN")
+
+        H = NetworkXAdapter.from_networkx(G, label_attribute="kind").to_networkx()
+        nodes = {a.get("population"): a for _, a in H.nodes(data=True)}
+        assert nodes[3_700_000]["labels"] == ["City"]
+        assert "kind" not in nodes[3_700_000]
+        assert sorted(nodes[None]["labels"]) == ["Country", "Place"]
+        assert [d["type"] for _, _, d in H.edges(data=True)] == ["IN"]
+
+    @pytest.mark.skipif(not NETWORKX_AVAILABLE, reason="NetworkX not installed")
+    def test_from_networkx_unsupported_attribute(self, db):
+        """Attributes that can't be stored as properties should raise."""
+        from grafeo.grafeo import NetworkXAdapter
+
+        G = nx.Graph()
+        G.add_node(1, payload=object())
+
+        with pytest.raises(ValueError, match="payload"):
+            NetworkXAdapter.from_networkx(G)
+
+
 class BaseNetworkXBenchmarkTest(ABC):
     """Abstract base class for NetworkX vs Grafeo performance comparison.
 
@@ -162,6 +162,47 @@ class BaseSolvORComparisonTest(ABC):
                 f"MST weight mismatch: Grafeo={grafeo_weight}, solvOR={solvor_weight}"
             )
 
+    # ===== Flow Model Export Tests =====
+
+    def test_flow_model_arrays(self, db):
+        """flow_model() should produce the arrays of a hand-built assignment instance."""
+        w0 = db.create_node(["Worker"], {"name": "Ann"}).id
+        w1 = db.create_node(["Worker"], {"name": "Bob"}).id
+        t0 = db.create_node(["Task"], {"name": "Paint"}).id
+        t1 = db.create_node(["Task"], {"name": "Sand"}).id
+
+        db.create_edge(w0, t0, "CAN_DO", {"cost": 90, "capacity": 2})
+        db.create_edge(w0, t1, "CAN_DO", {"cost": 75.5})
+        db.create_edge(w1, t1, "CAN_DO")
+        # Edges of another type or running the wrong way are not arcs
+        db.create_edge(w1, t0, "KNOWS", {"cost": 1})
+        db.create_edge(t0, w1, "CAN_DO", {"cost": 1})
+
+        model = db.as_solvor().flow_model(
+            "Worker", "Task", "CAN_DO", capacity="capacity", cost="cost", default_cost=100.0
+        )
+
+        assert model["nodes"] == [w0, w1, t0, t1]
+        assert model["num_sources"] == 2
+        assert model["tails"] == [0, 0, 1]
+        assert model["heads"] == [2, 3, 3]
+        assert model["capacities"] == [2.0, 1.0, 1.0]
+        # The Bob -> Sand edge has no cost, so it gets the default
+        assert model["unit_costs"] == [90.0, 75.5, 100.0]
+        assert len(model["edges"]) == 3
+
+    def test_flow_model_no_matching_edges(self, db):
+        """flow_model() on labels without connecting edges returns empty arcs."""
+        db.create_node(["Worker"])
+        db.create_node(["Task"])
+
+        model = db.as_solvor().flow_model("Worker", "Task", "CAN_DO")
+
+        assert model["num_sources"] == 1
+        assert len(model["nodes"]) == 2
+        assert model["tails"] == []
+        assert model["unit_costs"] == []
+
 
 class BaseSolvORBenchmarkTest(ABC):
     """Abstract base class for solvOR plugin performance comparison.
@@ -142,3 +142,68 @@ class TestGQLAlgorithmVerification:
             assert b.id in path
             assert d.id in path
             assert c.id not in path, "Should not go through c"
+
+
+class TestGQLAlgorithmLiveGraph:
+    """db.algorithms runs on the committed graph shared with the database."""
+
+    def test_pagerank_and_components(self, db):
+        """pagerank and connected_components cover every node of a small graph."""
+        a = db.create_node(["Node"], {"name": "a"})
+        b = db.create_node(["Node"], {"name": "b"})
+        c = db.create_node(["Node"], {"name": "c"})
+        x = db.create_node(["Node"], {"name": "x"})
+        y = db.create_node(["Node"], {"name": "y"})
+        db.create_edge(a.id, b.id, "LINK", {})
+        db.create_edge(b.id, c.id, "LINK", {})
+        db.create_edge(x.id, y.id, "LINK", {})
+
+        algs = db.algorithms
+        pr = algs.pagerank()
+        assert set(pr) == {a.id, b.id, c.id, x.id, y.id}
+        assert abs(sum(pr.values()) - 1.0) < 0.01
+        assert pr[c.id] > pr[a.id]
+
+        components = algs.connected_components()
+        assert components[a.id] == components[b.id] == components[c.id]
+        assert components[x.id] == components[y.id]
+        assert components[a.id] != components[x.id]
+
+    def test_later_writes_are_seen(self, db):
+        """Each call sees the graph as it is when the call starts."""
+        a = db.create_node(["Node"], {"name": "a"})
+        b = db.create_node(["Node"], {"name": "b"})
+
+        algs = db.algorithms
+        assert algs.connected_component_count() == 2
+
+        db.create_edge(a.id, b.id, "LINK", {})
+        assert algs.connected_component_count() == 1
+
+    def test_snapshot_excludes_uncommitted_writes(self, db):
+        """An open transaction's writes should not reach the algorithms."""
+        a = db.create_node(["Node"], {"name": "a"})
+        b = db.create_node(["Node"], {"name": "b"})
+
+        with db.begin_transaction() as tx:
+            tx.execute("INSERT (:Node {name: 'pending'})")
+            components = db.algorithms.connected_components()
+            ranks = db.algorithms.pagerank()
+            tx.rollback()
+
+        assert set(components) == {a.id, b.id}
+        assert components[a.id] != components[b.id]
+        assert set(ranks) == {a.id, b.id}
+
+    def test_dijkstra(self, db):
+        """dijkstra(source, target, weight) should return distance and path."""
+        a = db.create_node(["Node"], {"name": "a"})
+        b = db.create_node(["Node"], {"name": "b"})
+        c = db.create_node(["Node"], {"name": "c"})
+        db.create_edge(a.id, b.id, "ROAD", {"km": 2.0})
+        db.create_edge(b.id, c.id, "ROAD", {"km": 3.0})
+        db.create_edge(a.id, c.id, "ROAD", {"km": 9.0})
+
+        distance, path = db.algorithms.dijkstra(a.id, c.id, weight="km")
+        assert distance == 5.0
+        assert path == [a.id, b.id, c.id]
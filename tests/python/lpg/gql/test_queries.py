@@ -3,6 +3,9 @@
 Tests pattern matching, paths, and aggregations using GQL (ISO standard) query language.
 """
 
+import asyncio
+import re
+
 import pytest
 from tests.python.bases.test_queries import BaseQueriesTest
 
@@ -418,3 +421,138 @@ class TestGQLSpecificAggregations:
         )
         rows = list(result)
         assert len(rows) == 2
+
+
+class TestGQLPandasExport:
+    """QueryResult.to_pandas() tests."""
+
+    def test_to_pandas_dtypes(self, db):
+        """Columns should get dtypes matching their values."""
+        pd = pytest.importorskip("pandas")
+        db.create_node(["Person"], {"name": "Alice", "age": 30, "score": 1.5, "active": True})
+        db.create_node(["Person"], {"name": "Bob", "age": 25, "score": 2.5, "active": False})
+
+        df = db.execute(
+            "MATCH (p:Person) RETURN p.name, p.age, p.score, p.active ORDER BY p.age"
+        ).to_pandas()
+
+        assert isinstance(df, pd.DataFrame)
+        assert list(df.columns) == ["p.name", "p.age", "p.score", "p.active"]
+        assert str(df["p.age"].dtype) == "int64"
+        assert str(df["p.score"].dtype) == "float64"
+        assert str(df["p.active"].dtype) == "bool"
+        assert df["p.name"].dtype == object
+        assert df["p.name"].tolist() == ["Bob", "Alice"]
+
+    def test_to_pandas_nulls_and_mixed_types(self, db):
+        """Nulls should give nullable dtypes, and mixed columns object."""
+        pytest.importorskip("pandas")
+        db.create_node(["Item"], {"rank": 1, "size": 1.5, "flag": True, "code": 7, "tags": ["a"]})
+        db.create_node(["Item"], {"rank": 2, "code": "x7", "tags": ["b", "c"]})
+
+        df = db.execute(
+            "MATCH (i:Item) RETURN i.rank, i.size, i.flag, i.code, i.tags ORDER BY i.rank"
+        ).to_pandas()
+
+        assert str(df["i.size"].dtype) == "Float64"
+        assert str(df["i.flag"].dtype) == "boolean"
+        assert df["i.size"].isna().tolist() == [False, True]
+        assert df["i.code"].dtype == object
+        assert df["i.code"].tolist() == [7, "x7"]
+        assert df["i.tags"].tolist() == [["a"], ["b", "c"]]
+
+    def test_to_pandas_nodes(self, db):
+        """Node columns should become dicts."""
+        pytest.importorskip("pandas")
+        alice = db.create_node(["Person"], {"name": "Alice"})
+
+        df = db.execute("MATCH (p:Person) RETURN p").to_pandas()
+
+        node = df["p"][0]
+        assert node["id"] == alice.id
+        assert node["labels"] == ["Person"]
+        assert node["properties"] == {"name": "Alice"}
+
+    def test_to_pandas_empty_result(self, db):
+        """An empty result should keep its column names."""
+        pytest.importorskip("pandas")
+
+        df = db.execute("MATCH (p:Person) RETURN p.name, p.age").to_pandas()
+
+        assert list(df.columns) == ["p.name", "p.age"]
+        assert len(df) == 0
+
+
+class TestGQLAsyncExecution:
+    """execute_async() and async iteration tests."""
+
+    def test_concurrent_queries(self, db):
+        """Two async queries gathered together should both complete."""
+        for i in range(200):
+            db.create_node(["Person"], {"n": i})
+            db.create_node(["City"], {"n": i})
+
+        async def run():
+            return await asyncio.gather(
+                db.execute_async("MATCH (p:Person) RETURN count(p)"),
+                db.execute_async("MATCH (c:City) WHERE c.n < $limit RETURN c.n", {"limit": 10}),
+            )
+
+        people, cities = asyncio.run(run())
+
+        assert list(people) == [[200]]
+        assert sorted(row[0] for row in cities) == list(range(10))
+
+    def test_async_iteration(self, db):
+        """async for should yield every row of the result."""
+        db.create_node(["Person"], {"name": "Alice"})
+        db.create_node(["Person"], {"name": "Bob"})
+
+        async def run():
+            result = await db.execute_async("MATCH (p:Person) RETURN p.name ORDER BY p.name")
+            return [row async for row in result]
+
+        assert asyncio.run(run()) == [["Alice"], ["Bob"]]
+
+    def test_async_query_error(self, db):
+        """A failing async query should raise when awaited."""
+
+        async def run():
+            await db.execute_async("MATCH (n RETURN n")
+
+        with pytest.raises(Exception):
+            asyncio.run(run())
+
+
+class TestGQLExplain:
+    """db.explain() plan rendering tests."""
+
+    def test_explain_join_and_aggregate(self, db):
+        """The whole operator tree should be shown, with estimated rows."""
+        company = db.create_node(["Company"], {"name": "Acme"})
+        for i in range(10):
+            person = db.create_node(["Person"], {"age": i})
+            db.create_edge(person.id, company.id, "WORKS_AT")
+
+        plan = db.explain(
+            "MATCH (p:Person)-[:WORKS_AT]->(c:Company) "
+            "OPTIONAL MATCH (p)-[:KNOWS]->(f:Person) "
+            "RETURN c.name, count(f)"
+        )
+        lines = plan.splitlines()
+
+        assert lines[0].startswith("HashAggregate")
+        assert lines[1].startswith("  HashJoin type=left")
+        assert re.search(r"Scan p:Person index=label \(rows=\d+, cost=[\d.]+\)", plan)
+
+    def test_explain_does_not_execute(self, db):
+        """Explaining a write should leave the graph unchanged."""
+        plan = db.explain("INSERT (:Person {name: 'Alice'})")
+
+        assert "  CreateNode" in plan
+        assert db.node_count == 0
+
+    def test_explain_invalid_query(self, db):
+        """A query that fails to parse should raise."""
+        with pytest.raises(Exception):
+            db.explain("MATCH (n RETURN n")